@@ -0,0 +1,65 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use normalize_facts::FileIndex;
+use std::path::Path;
+use tokio::runtime::Runtime;
+
+/// `refresh_call_graph` over a copy of `normalize-facts`, once on a single
+/// rayon thread and once on the default pool, to track the parallel speedup
+/// of symbol/call/import extraction.
+fn bench_refresh_call_graph(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let workspace_root = manifest_dir.parent().unwrap();
+    let src_crate = workspace_root.join("crates/normalize-facts/src");
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    copy_dir_recursive(&src_crate, tmp.path()).expect("failed to copy crate");
+
+    let db_path = tmp.path().join("bench.sqlite");
+    let mut idx = rt.block_on(async {
+        let mut idx = FileIndex::open(&db_path, tmp.path())
+            .await
+            .expect("open index");
+        // Bypass the content-addressed cache so every iteration parses.
+        idx.disable_ca_cache();
+        idx.refresh().await.expect("refresh");
+        idx
+    });
+
+    let mut group = c.benchmark_group("facts_extraction/refresh_call_graph");
+    group.sample_size(10);
+    for threads in [1, rayon::current_num_threads()] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_function(format!("normalize-facts/{threads}-threads"), |b| {
+            b.iter(|| {
+                pool.install(|| {
+                    rt.block_on(async {
+                        black_box(idx.refresh_call_graph().await.expect("refresh_call_graph"))
+                    })
+                })
+            });
+        });
+    }
+    group.finish();
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let ty = entry.file_type()?;
+        let dst_path = dst.join(entry.file_name());
+        if ty.is_dir() {
+            std::fs::create_dir_all(&dst_path)?;
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else {
+            std::fs::copy(entry.path(), dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+criterion_group!(benches, bench_refresh_call_graph);
+criterion_main!(benches);
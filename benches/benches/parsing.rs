@@ -0,0 +1,64 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use normalize_facts::{parse_with_grammar, try_get_grammar};
+use normalize_languages::support_for_path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Tree-sitter parse throughput per language over the bundled polyglot
+/// fixture repo. Languages whose grammar is not installed are skipped.
+fn bench_parse_per_language(c: &mut Criterion) {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/polyglot");
+
+    let mut by_grammar: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+    for path in fixture_files(&fixture) {
+        let Some(support) = support_for_path(&path) else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&path).expect("read fixture");
+        by_grammar
+            .entry(support.grammar_name())
+            .or_default()
+            .push(content);
+    }
+
+    let mut group = c.benchmark_group("parsing");
+    for (grammar, sources) in &by_grammar {
+        if try_get_grammar(grammar).is_none() {
+            eprintln!(
+                "Skipping parsing/{grammar}: grammar not installed\n\
+                 Run `normalize grammars install` or set NORMALIZE_GRAMMAR_PATH."
+            );
+            continue;
+        }
+        let bytes: usize = sources.iter().map(String::len).sum();
+        group.throughput(Throughput::Bytes(bytes as u64));
+        group.bench_function(*grammar, |b| {
+            b.iter(|| {
+                for source in sources {
+                    black_box(parse_with_grammar(grammar, source).expect("parse"));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir).expect("read fixture dir").flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+criterion_group!(benches, bench_parse_per_language);
+criterion_main!(benches);
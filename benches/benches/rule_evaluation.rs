@@ -0,0 +1,74 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use normalize_facts::{FileIndex, get_normalize_dir};
+use normalize_facts_rules_interpret::{RulesConfig, load_all_rules, run_rule};
+use normalize_rules::build_relations_from_index;
+use std::path::Path;
+use tokio::runtime::Runtime;
+
+/// Relation extraction (index → `Relations`) and evaluation of the default
+/// builtin Datalog rules over an indexed copy of the bundled polyglot fixture
+/// repo.
+fn bench_rule_evaluation(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/polyglot");
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    copy_dir_recursive(&fixture, tmp.path()).expect("failed to copy fixture");
+    let root = tmp.path();
+
+    rt.block_on(async {
+        let db_path = get_normalize_dir(root).join("index.sqlite");
+        let mut idx = FileIndex::open(&db_path, root).await.expect("open index");
+        idx.refresh().await.expect("refresh");
+        idx.refresh_call_graph().await.expect("refresh_call_graph");
+    });
+
+    let mut group = c.benchmark_group("relations");
+    group.bench_function("build_relations_from_index(polyglot)", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                black_box(
+                    build_relations_from_index(root, true)
+                        .await
+                        .expect("build relations"),
+                )
+            })
+        });
+    });
+    group.finish();
+
+    let relations = rt
+        .block_on(build_relations_from_index(root, true))
+        .expect("build relations");
+    let rules: Vec<_> = load_all_rules(root, &RulesConfig::default())
+        .into_iter()
+        .filter(|rule| rule.builtin && rule.enabled)
+        .collect();
+
+    let mut group = c.benchmark_group("rule_evaluation");
+    group.sample_size(10);
+    for rule in &rules {
+        group.bench_function(&rule.id, |b| {
+            b.iter(|| black_box(run_rule(rule, &relations).expect("run rule")));
+        });
+    }
+    group.finish();
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let ty = entry.file_type()?;
+        let dst_path = dst.join(entry.file_name());
+        if ty.is_dir() {
+            std::fs::create_dir_all(&dst_path)?;
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else {
+            std::fs::copy(entry.path(), dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+criterion_group!(benches, bench_rule_evaluation);
+criterion_main!(benches);
@@ -26,7 +26,7 @@ fn bench_build_relations(c: &mut Criterion) {
         b.iter(|| {
             rt.block_on(async {
                 black_box(
-                    build_relations_from_index(workspace_root)
+                    build_relations_from_index(workspace_root, true)
                         .await
                         .expect("build relations"),
                 )
@@ -0,0 +1,65 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use normalize_facts::{Extractor, try_get_grammar};
+use normalize_languages::support_for_path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Skeleton (symbol tree) extraction per language over the bundled polyglot
+/// fixture repo — the work behind `normalize view` and the symbol index.
+fn bench_extract_per_language(c: &mut Criterion) {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/polyglot");
+
+    let mut by_language: BTreeMap<&'static str, Vec<(PathBuf, String)>> = BTreeMap::new();
+    for path in fixture_files(&fixture) {
+        let Some(support) = support_for_path(&path) else {
+            continue;
+        };
+        if try_get_grammar(support.grammar_name()).is_none() {
+            eprintln!(
+                "Skipping skeleton/{}: grammar not installed",
+                support.name()
+            );
+            continue;
+        }
+        let content = std::fs::read_to_string(&path).expect("read fixture");
+        by_language
+            .entry(support.name())
+            .or_default()
+            .push((path, content));
+    }
+
+    let extractor = Extractor::new();
+    let mut group = c.benchmark_group("skeleton");
+    for (language, files) in &by_language {
+        let bytes: usize = files.iter().map(|(_, content)| content.len()).sum();
+        group.throughput(Throughput::Bytes(bytes as u64));
+        group.bench_function(*language, |b| {
+            b.iter(|| {
+                for (path, content) in files {
+                    black_box(extractor.extract(path, content));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir).expect("read fixture dir").flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+criterion_group!(benches, bench_extract_per_language);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use normalize_typegen::{backends, parse_openapi};
+use std::path::Path;
+
+/// OpenAPI → IR parsing and code emission for every registered typegen
+/// backend, over the bundled polyglot fixture's `openapi.json`.
+fn bench_typegen(c: &mut Criterion) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/polyglot/openapi.json");
+    let content = std::fs::read_to_string(&path).expect("read openapi.json");
+    let value: serde_json::Value = serde_json::from_str(&content).expect("parse openapi.json");
+    let schema = parse_openapi(&value).expect("openapi → IR");
+
+    let mut group = c.benchmark_group("typegen");
+    group.bench_function("parse_openapi", |b| {
+        b.iter(|| black_box(parse_openapi(&value).expect("openapi → IR")));
+    });
+    for backend in backends() {
+        group.bench_function(format!("emit/{}", backend.name()), |b| {
+            b.iter(|| black_box(backend.generate(&schema)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_typegen);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+package main
+
+import (
+	"fmt"
+	"os"
+
+	"example.com/inventory/store"
+)
+
+func lowStock(s *store.Store, threshold uint64) []*store.Item {
+	var low []*store.Item
+	for _, item := range s.Items() {
+		if item.Available() <= threshold {
+			low = append(low, item)
+		}
+	}
+	return low
+}
+
+func main() {
+	s := store.New()
+	for _, item := range []store.Item{
+		{Sku: "a", Name: "Apple", Quantity: 3},
+		{Sku: "b", Name: "Banana", Quantity: 12},
+		{Sku: "c", Name: "Cherry", Quantity: 1},
+	} {
+		if err := s.Insert(item); err != nil {
+			fmt.Fprintln(os.Stderr, err)
+			os.Exit(1)
+		}
+	}
+	for _, item := range lowStock(s, 2) {
+		fmt.Printf("low: %s (%d left)\n", item.Sku, item.Available())
+	}
+}
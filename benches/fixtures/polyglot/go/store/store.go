@@ -0,0 +1,74 @@
+package store
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+)
+
+// ErrUnknownSku is returned when an operation names a missing item.
+var ErrUnknownSku = errors.New("unknown sku")
+
+// Item is a stocked item.
+type Item struct {
+	Sku      string
+	Name     string
+	Quantity uint64
+	Reserved uint64
+}
+
+// Available returns the units not held by a reservation.
+func (i *Item) Available() uint64 {
+	if i.Reserved > i.Quantity {
+		return 0
+	}
+	return i.Quantity - i.Reserved
+}
+
+// Store is an in-memory item store keyed by SKU.
+type Store struct {
+	items map[string]*Item
+}
+
+// New returns an empty store.
+func New() *Store {
+	return &Store{items: make(map[string]*Item)}
+}
+
+// Insert adds an item, failing if its SKU is already present.
+func (s *Store) Insert(item Item) error {
+	if _, ok := s.items[item.Sku]; ok {
+		return fmt.Errorf("duplicate sku: %s", item.Sku)
+	}
+	s.items[item.Sku] = &item
+	return nil
+}
+
+// Get looks up an item by SKU.
+func (s *Store) Get(sku string) (*Item, error) {
+	item, ok := s.items[sku]
+	if !ok {
+		return nil, fmt.Errorf("%w: %s", ErrUnknownSku, sku)
+	}
+	return item, nil
+}
+
+// Restock adds units to an item and returns the new quantity.
+func (s *Store) Restock(sku string, units uint64) (uint64, error) {
+	item, err := s.Get(sku)
+	if err != nil {
+		return 0, err
+	}
+	item.Quantity += units
+	return item.Quantity, nil
+}
+
+// Items returns every item sorted by SKU.
+func (s *Store) Items() []*Item {
+	out := make([]*Item, 0, len(s.items))
+	for _, item := range s.items {
+		out = append(out, item)
+	}
+	sort.Slice(out, func(a, b int) bool { return out[a].Sku < out[b].Sku })
+	return out
+}
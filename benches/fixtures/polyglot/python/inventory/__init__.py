@@ -0,0 +1,8 @@
+"""Inventory service: items, stock levels and reservations."""
+
+from inventory.models import Item, Reservation
+from inventory.store import Store
+from inventory.reserve import Reserver
+from inventory.report import summarize, low_stock
+
+__all__ = ["Item", "Reservation", "Store", "Reserver", "summarize", "low_stock"]
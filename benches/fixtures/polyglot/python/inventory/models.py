@@ -0,0 +1,28 @@
+from dataclasses import dataclass, field
+
+
+class InventoryError(Exception):
+    """Raised by store and reservation operations."""
+
+
+@dataclass
+class Item:
+    sku: str
+    name: str
+    quantity: int = 0
+    reserved: int = 0
+    tags: list[str] = field(default_factory=list)
+
+    def available(self) -> int:
+        """Units not held by a reservation."""
+        return max(self.quantity - self.reserved, 0)
+
+    def is_low(self, threshold: int) -> bool:
+        return self.available() <= threshold
+
+
+@dataclass
+class Reservation:
+    id: int
+    sku: str
+    units: int
@@ -0,0 +1,25 @@
+from inventory.store import Store
+
+
+def summarize(store: Store) -> dict[str, int]:
+    summary = {"items": 0, "units": 0, "reserved": 0}
+    for item in store.items():
+        summary["items"] += 1
+        summary["units"] += item.quantity
+        summary["reserved"] += item.reserved
+    return summary
+
+
+def low_stock(store: Store, threshold: int):
+    low = [item for item in store.items() if item.is_low(threshold)]
+    low.sort(key=lambda item: (item.available(), item.sku))
+    return low
+
+
+def render(store: Store, threshold: int = 2) -> str:
+    lines = []
+    summary = summarize(store)
+    lines.append(f"{summary['items']} items, {summary['units']} units")
+    for item in low_stock(store, threshold):
+        lines.append(f"  low: {item.sku} ({item.available()} left)")
+    return "\n".join(lines)
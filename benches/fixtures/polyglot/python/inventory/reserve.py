@@ -0,0 +1,42 @@
+from inventory.models import InventoryError, Reservation
+from inventory.store import Store
+
+
+class Reserver:
+    """Hands out reservations against a Store."""
+
+    def __init__(self, store: Store):
+        self.store = store
+        self._next_id = 0
+        self._open: dict[int, Reservation] = {}
+
+    def reserve(self, sku: str, units: int) -> Reservation:
+        item = self.store.get(sku)
+        available = item.available()
+        if units > available:
+            raise InventoryError(f"{sku}: wanted {units}, only {available} available")
+        item.reserved += units
+        self._next_id += 1
+        reservation = Reservation(self._next_id, sku, units)
+        self._open[reservation.id] = reservation
+        return reservation
+
+    def _take(self, reservation_id: int) -> Reservation:
+        try:
+            return self._open.pop(reservation_id)
+        except KeyError:
+            raise InventoryError(f"unknown reservation #{reservation_id}") from None
+
+    def release(self, reservation_id: int) -> None:
+        reservation = self._take(reservation_id)
+        item = self.store.get(reservation.sku)
+        item.reserved = max(item.reserved - reservation.units, 0)
+
+    def commit(self, reservation_id: int) -> None:
+        reservation = self._take(reservation_id)
+        item = self.store.get(reservation.sku)
+        item.reserved = max(item.reserved - reservation.units, 0)
+        item.quantity = max(item.quantity - reservation.units, 0)
+
+    def open_reservations(self) -> list[Reservation]:
+        return sorted(self._open.values(), key=lambda r: r.id)
@@ -0,0 +1,35 @@
+from inventory.models import InventoryError, Item
+
+
+class Store:
+    """In-memory item store keyed by SKU."""
+
+    def __init__(self):
+        self._items: dict[str, Item] = {}
+
+    def insert(self, item: Item) -> None:
+        if item.sku in self._items:
+            raise InventoryError(f"duplicate sku: {item.sku}")
+        self._items[item.sku] = item
+
+    def get(self, sku: str) -> Item:
+        try:
+            return self._items[sku]
+        except KeyError:
+            raise InventoryError(f"unknown sku: {sku}") from None
+
+    def restock(self, sku: str, units: int) -> int:
+        item = self.get(sku)
+        item.quantity += units
+        return item.quantity
+
+    def remove(self, sku: str) -> Item:
+        item = self.get(sku)
+        del self._items[sku]
+        return item
+
+    def items(self):
+        return iter(sorted(self._items.values(), key=lambda i: i.sku))
+
+    def __len__(self) -> int:
+        return len(self._items)
@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Errors raised by store and reservation operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    DuplicateSku(String),
+    UnknownSku(String),
+    InsufficientStock { sku: String, wanted: u64, available: u64 },
+    UnknownReservation(u64),
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryError::DuplicateSku(sku) => write!(f, "duplicate sku: {sku}"),
+            InventoryError::UnknownSku(sku) => write!(f, "unknown sku: {sku}"),
+            InventoryError::InsufficientStock {
+                sku,
+                wanted,
+                available,
+            } => write!(f, "{sku}: wanted {wanted}, only {available} available"),
+            InventoryError::UnknownReservation(id) => write!(f, "unknown reservation #{id}"),
+        }
+    }
+}
+
+impl std::error::Error for InventoryError {}
@@ -0,0 +1,53 @@
+//! Inventory service: items, stock levels and reservations.
+
+pub mod error;
+pub mod reserve;
+pub mod store;
+
+pub use error::InventoryError;
+pub use reserve::{Reservation, Reserver};
+pub use store::{Item, Store};
+
+/// Summary of a store's contents.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Summary {
+    pub items: usize,
+    pub units: u64,
+    pub reserved: u64,
+}
+
+/// Compute a summary over every item in `store`.
+pub fn summarize(store: &Store) -> Summary {
+    let mut summary = Summary::default();
+    for item in store.items() {
+        summary.items += 1;
+        summary.units += item.quantity;
+        summary.reserved += item.reserved;
+    }
+    summary
+}
+
+/// Items whose free stock is at or below `threshold`.
+pub fn low_stock(store: &Store, threshold: u64) -> Vec<&Item> {
+    let mut low: Vec<&Item> = store
+        .items()
+        .filter(|item| item.available() <= threshold)
+        .collect();
+    low.sort_by(|a, b| a.available().cmp(&b.available()).then(a.sku.cmp(&b.sku)));
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_counts_units() {
+        let mut store = Store::new();
+        store.insert(Item::new("a", "Apple", 3)).unwrap();
+        store.insert(Item::new("b", "Banana", 5)).unwrap();
+        let summary = summarize(&store);
+        assert_eq!(summary.items, 2);
+        assert_eq!(summary.units, 8);
+    }
+}
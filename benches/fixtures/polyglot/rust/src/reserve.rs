@@ -0,0 +1,79 @@
+use crate::error::InventoryError;
+use crate::store::Store;
+use std::collections::HashMap;
+
+/// Units of one SKU held for an order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reservation {
+    pub id: u64,
+    pub sku: String,
+    pub units: u64,
+}
+
+/// Hands out reservations against a [`Store`].
+#[derive(Debug, Default)]
+pub struct Reserver {
+    next_id: u64,
+    open: HashMap<u64, Reservation>,
+}
+
+impl Reserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reserve(
+        &mut self,
+        store: &mut Store,
+        sku: &str,
+        units: u64,
+    ) -> Result<Reservation, InventoryError> {
+        let item = store.get_mut(sku)?;
+        let available = item.available();
+        if units > available {
+            return Err(InventoryError::InsufficientStock {
+                sku: sku.to_string(),
+                wanted: units,
+                available,
+            });
+        }
+        item.reserved += units;
+        self.next_id += 1;
+        let reservation = Reservation {
+            id: self.next_id,
+            sku: sku.to_string(),
+            units,
+        };
+        self.open.insert(reservation.id, reservation.clone());
+        Ok(reservation)
+    }
+
+    /// Release a reservation, returning its units to free stock.
+    pub fn release(&mut self, store: &mut Store, id: u64) -> Result<(), InventoryError> {
+        let reservation = self
+            .open
+            .remove(&id)
+            .ok_or(InventoryError::UnknownReservation(id))?;
+        let item = store.get_mut(&reservation.sku)?;
+        item.reserved = item.reserved.saturating_sub(reservation.units);
+        Ok(())
+    }
+
+    /// Fulfil a reservation: the units leave the store for good.
+    pub fn commit(&mut self, store: &mut Store, id: u64) -> Result<(), InventoryError> {
+        let reservation = self
+            .open
+            .remove(&id)
+            .ok_or(InventoryError::UnknownReservation(id))?;
+        let item = store.get_mut(&reservation.sku)?;
+        item.reserved = item.reserved.saturating_sub(reservation.units);
+        item.quantity = item.quantity.saturating_sub(reservation.units);
+        Ok(())
+    }
+
+    pub fn open_reservations(&self) -> Vec<&Reservation> {
+        let mut open: Vec<&Reservation> = self.open.values().collect();
+        open.sort_by_key(|r| r.id);
+        open
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::InventoryError;
+use std::collections::BTreeMap;
+
+/// A stocked item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub sku: String,
+    pub name: String,
+    pub quantity: u64,
+    pub reserved: u64,
+}
+
+impl Item {
+    pub fn new(sku: &str, name: &str, quantity: u64) -> Self {
+        Self {
+            sku: sku.to_string(),
+            name: name.to_string(),
+            quantity,
+            reserved: 0,
+        }
+    }
+
+    /// Units not held by a reservation.
+    pub fn available(&self) -> u64 {
+        self.quantity.saturating_sub(self.reserved)
+    }
+}
+
+/// In-memory item store keyed by SKU.
+#[derive(Debug, Default)]
+pub struct Store {
+    items: BTreeMap<String, Item>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, item: Item) -> Result<(), InventoryError> {
+        if self.items.contains_key(&item.sku) {
+            return Err(InventoryError::DuplicateSku(item.sku));
+        }
+        self.items.insert(item.sku.clone(), item);
+        Ok(())
+    }
+
+    pub fn get(&self, sku: &str) -> Result<&Item, InventoryError> {
+        self.items
+            .get(sku)
+            .ok_or_else(|| InventoryError::UnknownSku(sku.to_string()))
+    }
+
+    pub fn get_mut(&mut self, sku: &str) -> Result<&mut Item, InventoryError> {
+        self.items
+            .get_mut(sku)
+            .ok_or_else(|| InventoryError::UnknownSku(sku.to_string()))
+    }
+
+    pub fn restock(&mut self, sku: &str, units: u64) -> Result<u64, InventoryError> {
+        let item = self.get_mut(sku)?;
+        item.quantity += units;
+        Ok(item.quantity)
+    }
+
+    pub fn remove(&mut self, sku: &str) -> Result<Item, InventoryError> {
+        self.items
+            .remove(sku)
+            .ok_or_else(|| InventoryError::UnknownSku(sku.to_string()))
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
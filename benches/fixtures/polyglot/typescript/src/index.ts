@@ -0,0 +1,31 @@
+import { Reserver } from "./reserve";
+import { Store } from "./store";
+import { available, Item } from "./types";
+
+export { InventoryError } from "./types";
+export type { Item, Reservation } from "./types";
+export { Reserver, Store };
+
+export interface Summary {
+  items: number;
+  units: number;
+  reserved: number;
+}
+
+export function summarize(store: Store): Summary {
+  return store.all().reduce(
+    (acc, item) => ({
+      items: acc.items + 1,
+      units: acc.units + item.quantity,
+      reserved: acc.reserved + item.reserved,
+    }),
+    { items: 0, units: 0, reserved: 0 },
+  );
+}
+
+export function lowStock(store: Store, threshold: number): Item[] {
+  return store
+    .all()
+    .filter((item) => available(item) <= threshold)
+    .sort((a, b) => available(a) - available(b) || a.sku.localeCompare(b.sku));
+}
@@ -0,0 +1,50 @@
+import { Store } from "./store";
+import { available, InventoryError, Reservation } from "./types";
+
+export class Reserver {
+  private nextId = 0;
+  private open = new Map<number, Reservation>();
+
+  constructor(private readonly store: Store) {}
+
+  reserve(sku: string, units: number): Reservation {
+    const item = this.store.get(sku);
+    const free = available(item);
+    if (units > free) {
+      throw new InventoryError(
+        "insufficient-stock",
+        `${sku}: wanted ${units}, only ${free} available`,
+      );
+    }
+    item.reserved += units;
+    const reservation = { id: ++this.nextId, sku, units };
+    this.open.set(reservation.id, reservation);
+    return reservation;
+  }
+
+  private take(id: number): Reservation {
+    const reservation = this.open.get(id);
+    if (!reservation) {
+      throw new InventoryError("unknown-reservation", `unknown reservation #${id}`);
+    }
+    this.open.delete(id);
+    return reservation;
+  }
+
+  release(id: number): void {
+    const reservation = this.take(id);
+    const item = this.store.get(reservation.sku);
+    item.reserved = Math.max(item.reserved - reservation.units, 0);
+  }
+
+  commit(id: number): void {
+    const reservation = this.take(id);
+    const item = this.store.get(reservation.sku);
+    item.reserved = Math.max(item.reserved - reservation.units, 0);
+    item.quantity = Math.max(item.quantity - reservation.units, 0);
+  }
+
+  openReservations(): Reservation[] {
+    return [...this.open.values()].sort((a, b) => a.id - b.id);
+  }
+}
@@ -0,0 +1,40 @@
+import { InventoryError, Item } from "./types";
+
+export class Store {
+  private items = new Map<string, Item>();
+
+  insert(item: Item): void {
+    if (this.items.has(item.sku)) {
+      throw new InventoryError("duplicate-sku", `duplicate sku: ${item.sku}`);
+    }
+    this.items.set(item.sku, { ...item });
+  }
+
+  get(sku: string): Item {
+    const item = this.items.get(sku);
+    if (!item) {
+      throw new InventoryError("unknown-sku", `unknown sku: ${sku}`);
+    }
+    return item;
+  }
+
+  restock(sku: string, units: number): number {
+    const item = this.get(sku);
+    item.quantity += units;
+    return item.quantity;
+  }
+
+  remove(sku: string): Item {
+    const item = this.get(sku);
+    this.items.delete(sku);
+    return item;
+  }
+
+  all(): Item[] {
+    return [...this.items.values()].sort((a, b) => a.sku.localeCompare(b.sku));
+  }
+
+  get size(): number {
+    return this.items.size;
+  }
+}
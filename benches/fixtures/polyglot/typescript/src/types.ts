@@ -0,0 +1,32 @@
+export interface Item {
+  sku: string;
+  name: string;
+  quantity: number;
+  reserved: number;
+  tags?: string[];
+}
+
+export interface Reservation {
+  id: number;
+  sku: string;
+  units: number;
+}
+
+export type InventoryErrorKind =
+  | "duplicate-sku"
+  | "unknown-sku"
+  | "insufficient-stock"
+  | "unknown-reservation";
+
+export class InventoryError extends Error {
+  constructor(
+    public readonly kind: InventoryErrorKind,
+    message: string,
+  ) {
+    super(message);
+  }
+}
+
+export function available(item: Item): number {
+  return Math.max(item.quantity - item.reserved, 0);
+}
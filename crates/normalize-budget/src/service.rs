@@ -793,6 +793,7 @@ pub fn build_budget_report(
                 sources_run: vec!["budget".into()],
                 tool_errors: vec![],
                 daemon_cached: false,
+                metrics: vec![],
             };
         }
     };
@@ -851,6 +852,7 @@ pub fn build_budget_report(
         sources_run: vec!["budget".into()],
         tool_errors: vec![],
         daemon_cached: false,
+        metrics: vec![],
     }
 }
 
@@ -0,0 +1,264 @@
+//! Shared cache layout and locking for concurrent normalize invocations.
+//!
+//! Every machine-wide cache lives under one root, `~/.cache/normalize`
+//! (`$XDG_CACHE_HOME/normalize` when set):
+//!
+//! ```text
+//! ~/.cache/normalize/
+//!   packages/        registry responses (normalize-ecosystems)
+//!   sources/         extracted package sources (normalize-ecosystems)
+//!   indices/         distro/registry index downloads (normalize-package-index)
+//!   packages.db      global package index (normalize-languages)
+//!   ca-cache.sqlite  content-addressed extraction + symbol cache (normalize-facts)
+//! ```
+//!
+//! Two CLI invocations (or a CLI and the daemon) routinely touch the same
+//! entries at once. Writers that replace a whole entry use [`write_atomic`] so
+//! readers never see a torn file. Work that spans several files, or that must
+//! happen exactly once (initialising a database, recovering a corrupt one,
+//! installing grammars), holds a [`CacheLock`]: an advisory lock on a
+//! `<target>.lock` sibling file, released on drop. Readers that find an entry
+//! they cannot parse call [`discard_corrupt`] so the next writer rebuilds it
+//! instead of every reader failing on it forever.
+
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Root of the shared cache: `$XDG_CACHE_HOME/normalize`, else
+/// `~/.cache/normalize`. `None` when no home directory can be determined.
+pub fn cache_root() -> Option<PathBuf> {
+    let base = if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
+        PathBuf::from(cache)
+    } else if let Ok(home) = std::env::var("HOME") {
+        PathBuf::from(home).join(".cache")
+    } else if let Ok(home) = std::env::var("USERPROFILE") {
+        PathBuf::from(home).join(".cache")
+    } else {
+        return None;
+    };
+    Some(base.join("normalize"))
+}
+
+/// A subdirectory (or file) of [`cache_root`], e.g. `cache_path("packages")`.
+pub fn cache_path(name: &str) -> Option<PathBuf> {
+    Some(cache_root()?.join(name))
+}
+
+/// The lock file guarding `target`: `<target>.lock` next to it.
+pub fn lock_path(target: &Path) -> PathBuf {
+    let mut name = target
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_else(|| OsString::from("cache"));
+    name.push(".lock");
+    target.with_file_name(name)
+}
+
+/// Advisory lock on a cache entry, held until dropped.
+///
+/// The lock is taken on [`lock_path`]`(target)`, never on the target itself,
+/// so it works for directories and for files that get replaced by rename.
+/// Locks are advisory: they only exclude other holders of a `CacheLock` on
+/// the same target. Within one process each `CacheLock` is independent, so
+/// taking an exclusive lock on a target the same process already holds
+/// blocks — use [`CacheLock::try_exclusive`] where that can happen.
+#[derive(Debug)]
+pub struct CacheLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl CacheLock {
+    /// Block until no other process holds any lock on `target`.
+    pub fn exclusive(target: &Path) -> io::Result<Self> {
+        let lock = Self::open(target)?;
+        lock.file.lock()?;
+        Ok(lock)
+    }
+
+    /// Block until no other process holds an exclusive lock on `target`.
+    pub fn shared(target: &Path) -> io::Result<Self> {
+        let lock = Self::open(target)?;
+        lock.file.lock_shared()?;
+        Ok(lock)
+    }
+
+    /// Take an exclusive lock if it is free right now; `Ok(None)` if another
+    /// holder has it.
+    pub fn try_exclusive(target: &Path) -> io::Result<Option<Self>> {
+        let lock = Self::open(target)?;
+        match lock.file.try_lock() {
+            Ok(()) => Ok(Some(lock)),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+
+    /// Path of the lock file backing this guard.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open(target: &Path) -> io::Result<Self> {
+        let path = lock_path(target);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        Ok(Self { file, path })
+    }
+}
+
+/// Replace `path` with `contents` so concurrent readers see either the old
+/// or the new file, never a partial one.
+///
+/// Writes a uniquely named temp file next to `path` and renames it over the
+/// target. Creates parent directories as needed.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_else(|| OsString::from("cache"));
+    tmp_name.push(format!(
+        ".{}.{:?}.tmp",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let tmp = path.with_file_name(tmp_name);
+    let result = fs::write(&tmp, contents).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Read `path` and parse it with `parse`; on parse failure delete the file so
+/// the next writer regenerates it.
+///
+/// A missing or unreadable file is a plain miss and is left alone.
+pub fn read_or_discard<T>(path: &Path, parse: impl FnOnce(&[u8]) -> Option<T>) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    let parsed = parse(&bytes);
+    if parsed.is_none() {
+        discard_corrupt(path);
+    }
+    parsed
+}
+
+/// Remove a corrupt cache entry, plus SQLite sidecar files (`-journal`,
+/// `-wal`, `-shm`) when `path` is a database. Best-effort.
+pub fn discard_corrupt(path: &Path) {
+    let _ = fs::remove_file(path);
+    for suffix in ["-journal", "-wal", "-shm"] {
+        let mut sidecar = path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = fs::remove_file(PathBuf::from(sidecar));
+    }
+}
+
+/// Whether a SQLite error message means the database file is damaged (as
+/// opposed to busy, missing, or a query bug), so discarding it is the fix.
+pub fn is_sqlite_corruption(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("corrupt")
+        || message.contains("malformed")
+        || message.contains("disk i/o error")
+        || message.contains("not a database")
+        || message.contains("database disk image")
+        || message.contains("integrity check failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_path_is_a_sibling() {
+        assert_eq!(
+            lock_path(Path::new("/c/normalize/packages.db")),
+            PathBuf::from("/c/normalize/packages.db.lock")
+        );
+        assert_eq!(
+            lock_path(Path::new("/c/grammars")),
+            PathBuf::from("/c/grammars.lock")
+        );
+    }
+
+    #[test]
+    fn exclusive_lock_excludes_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("nested/entry.json");
+        let held = CacheLock::exclusive(&target).unwrap();
+        assert!(held.path().exists());
+        assert!(CacheLock::try_exclusive(&target).unwrap().is_none());
+        drop(held);
+        assert!(CacheLock::try_exclusive(&target).unwrap().is_some());
+
+        let a = CacheLock::shared(&target).unwrap();
+        let b = CacheLock::shared(&target).unwrap();
+        assert!(CacheLock::try_exclusive(&target).unwrap().is_none());
+        drop((a, b));
+        assert!(CacheLock::try_exclusive(&target).unwrap().is_some());
+    }
+
+    #[test]
+    fn concurrent_writers_never_tear_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry.txt");
+        std::thread::scope(|s| {
+            for i in 0..8u8 {
+                let path = &path;
+                s.spawn(move || {
+                    for _ in 0..50 {
+                        write_atomic(path, vec![b'a' + i; 4096]).unwrap();
+                        let bytes = fs::read(path).unwrap();
+                        assert_eq!(bytes.len(), 4096);
+                        assert!(bytes.iter().all(|&b| b == bytes[0]));
+                    }
+                });
+            }
+        });
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name() != "entry.txt")
+            .collect();
+        assert!(leftovers.is_empty(), "{leftovers:?}");
+    }
+
+    #[test]
+    fn corrupt_entries_are_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.sqlite");
+        fs::write(&path, b"garbage").unwrap();
+        fs::write(dir.path().join("index.sqlite-wal"), b"").unwrap();
+
+        let ok = read_or_discard(&path, |b| (b == b"garbage").then_some(1));
+        assert_eq!(ok, Some(1));
+        assert!(path.exists());
+
+        let bad: Option<u32> = read_or_discard(&path, |_| None);
+        assert_eq!(bad, None);
+        assert!(!path.exists());
+
+        fs::write(&path, b"x").unwrap();
+        discard_corrupt(&path);
+        assert!(!path.exists());
+        assert!(!dir.path().join("index.sqlite-wal").exists());
+
+        assert!(is_sqlite_corruption(
+            "SQLite failure: `file is not a database`"
+        ));
+        assert!(!is_sqlite_corruption("database is locked"));
+    }
+}
@@ -4,7 +4,7 @@ use super::{
     DiscoverError, ParseError, SessionLocation, SessionRef, SessionSource, list_jsonl_sessions,
     list_subagent_sessions, peek_lines,
 };
-use crate::{ContentBlock, Message, Role, Session, TokenUsage, Turn};
+use crate::{ApiError, ApiErrorKind, ContentBlock, Message, Role, Session, TokenUsage, Turn};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
@@ -124,22 +124,86 @@ impl SessionSource for ClaudeCodeFormat {
 
 impl ClaudeCodeFormat {
     fn parse_path(&self, path: &Path) -> Result<Session, ParseError> {
+        let mut reader = SessionReader::open(path)?;
+        let mut turns = Vec::new();
+        for turn in &mut reader {
+            turns.push(turn?);
+        }
+        let mut session = reader.into_session();
+        session.turns = turns;
+        Ok(session)
+    }
+}
+
+/// Streaming reader for a Claude Code JSONL log.
+///
+/// Yields [`Turn`]s one at a time as the file is read, so only the turn being
+/// assembled is held in memory — use it instead of
+/// [`SessionSource::load`] for transcripts too large to load whole.
+/// Session metadata (model, session id, timestamp) fills in as the entries
+/// carrying it are read; [`SessionReader::session`] returns what has been
+/// seen so far, with no turns.
+///
+/// `load` collects this reader, so both produce the same turns.
+pub struct SessionReader {
+    path: PathBuf,
+    lines: std::io::Lines<BufReader<File>>,
+    session: Session,
+    current_turn: Turn,
+    request_tokens: HashMap<String, TokenUsage>,
+    // All requestIds seen in the current turn (one per API call; multi-round turns
+    // have multiple calls: tool-call round 1, tool-call round 2, ..., final answer).
+    turn_request_ids: Vec<String>,
+    done: bool,
+}
+
+impl SessionReader {
+    /// Open `path` for streaming. Subagent metadata is read from the path up front.
+    pub fn open(path: &Path) -> Result<Self, ParseError> {
         let file = File::open(path).map_err(|e| ParseError::Io {
             path: path.to_path_buf(),
             source: e,
         })?;
-        let reader = BufReader::new(file);
+        let mut session = Session::new(path.to_path_buf(), ClaudeCodeFormat.name());
+        session.metadata.provider = Some("anthropic".to_string());
+        set_subagent_metadata(&mut session, path);
+        Ok(Self {
+            path: path.to_path_buf(),
+            lines: BufReader::new(file).lines(),
+            session,
+            current_turn: Turn::default(),
+            request_tokens: HashMap::new(),
+            turn_request_ids: Vec::new(),
+            done: false,
+        })
+    }
+
+    /// Session header read so far (metadata and subagent fields; `turns` is empty).
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
 
-        let mut session = Session::new(path.to_path_buf(), self.name());
-        let mut current_turn = Turn::default();
-        let mut request_tokens: HashMap<String, TokenUsage> = HashMap::new();
-        // All requestIds seen in the current turn (one per API call; multi-round turns
-        // have multiple calls: tool-call round 1, tool-call round 2, ..., final answer).
-        let mut turn_request_ids: Vec<String> = Vec::new();
+    /// Consume the reader, returning the session header.
+    pub fn into_session(self) -> Session {
+        self.session
+    }
 
-        for line in reader.lines() {
+    /// Finish the turn being assembled, if it has any messages.
+    fn take_turn(&mut self) -> Option<Turn> {
+        if self.current_turn.messages.is_empty() {
+            return None;
+        }
+        let mut turn = std::mem::take(&mut self.current_turn);
+        turn.token_usage = sum_turn_tokens(&self.turn_request_ids, &mut self.request_tokens);
+        self.turn_request_ids.clear();
+        Some(turn)
+    }
+
+    /// Read entries until a turn is complete, or the file ends.
+    fn next_turn(&mut self) -> Result<Option<Turn>, ParseError> {
+        while let Some(line) = self.lines.next() {
             let line = line.map_err(|e| ParseError::Io {
-                path: path.to_path_buf(),
+                path: self.path.clone(),
                 source: e,
             })?;
             if line.trim().is_empty() {
@@ -187,19 +251,28 @@ impl ClaudeCodeFormat {
                         // Tool results belong to the current turn, not a new one
                         let mut tool_msg = message;
                         tool_msg.role = Role::Tool;
-                        current_turn.messages.push(tool_msg);
+                        self.current_turn.messages.push(tool_msg);
                     } else {
                         // Flush previous turn if we have messages
-                        if !current_turn.messages.is_empty() {
-                            current_turn.token_usage =
-                                sum_turn_tokens(&turn_request_ids, &mut request_tokens);
-                            turn_request_ids.clear();
-                            session.turns.push(std::mem::take(&mut current_turn));
+                        let finished = self.take_turn();
+                        self.current_turn.messages.push(message);
+                        if finished.is_some() {
+                            return Ok(finished);
                         }
-                        current_turn.messages.push(message);
                     }
                 }
                 "assistant" => {
+                    // Final "API Error: ..." placeholder written once retries run out
+                    // (model "<synthetic>"); an API error, not model output.
+                    if entry
+                        .get("isApiErrorMessage")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false)
+                    {
+                        self.current_turn.api_errors.push(api_error_message(&entry));
+                        continue;
+                    }
+
                     let request_id = entry
                         .get("requestId")
                         .and_then(|v| v.as_str())
@@ -232,7 +305,7 @@ impl ClaudeCodeFormat {
                             model: turn_model.clone(),
                         };
                         if let Some(ref req_id) = request_id {
-                            let existing = request_tokens.entry(req_id.clone()).or_default();
+                            let existing = self.request_tokens.entry(req_id.clone()).or_default();
                             existing.input = existing.input.max(tokens.input);
                             existing.output = existing.output.max(tokens.output);
                             if let Some(cr) = tokens.cache_read {
@@ -250,8 +323,8 @@ impl ClaudeCodeFormat {
                     }
 
                     // Extract model from first assistant message
-                    if session.metadata.model.is_none() {
-                        session.metadata.model = entry
+                    if self.session.metadata.model.is_none() {
+                        self.session.metadata.model = entry
                             .get("message")
                             .and_then(|m| m.get("model"))
                             .and_then(|v| v.as_str())
@@ -259,24 +332,28 @@ impl ClaudeCodeFormat {
                     }
 
                     let message = parse_message(&entry, Role::Assistant);
-                    current_turn.messages.push(message);
+                    self.current_turn.messages.push(message);
                     if let Some(req_id) = request_id
-                        && !turn_request_ids.contains(&req_id)
+                        && !self.turn_request_ids.contains(&req_id)
                     {
-                        turn_request_ids.push(req_id);
+                        self.turn_request_ids.push(req_id);
                     }
                 }
+                // `subtype: "api_error"` records one failed attempt before a retry.
+                "system" if entry.get("subtype").and_then(|v| v.as_str()) == Some("api_error") => {
+                    self.current_turn.api_errors.push(api_error_retry(&entry));
+                }
                 "summary" => {
                     // Extract session metadata from summary
-                    if session.metadata.session_id.is_none() {
-                        session.metadata.session_id = entry
+                    if self.session.metadata.session_id.is_none() {
+                        self.session.metadata.session_id = entry
                             .get("sessionId")
                             .and_then(|v| v.as_str())
                             .map(String::from);
                     }
                     // Extract timestamp
-                    if session.metadata.timestamp.is_none() {
-                        session.metadata.timestamp = entry
+                    if self.session.metadata.timestamp.is_none() {
+                        self.session.metadata.timestamp = entry
                             .get("timestamp")
                             .and_then(|v| v.as_str())
                             .map(String::from);
@@ -287,44 +364,60 @@ impl ClaudeCodeFormat {
         }
 
         // Flush final turn
-        if !current_turn.messages.is_empty() {
-            current_turn.token_usage = sum_turn_tokens(&turn_request_ids, &mut request_tokens);
-            session.turns.push(current_turn);
-        }
+        Ok(self.take_turn())
+    }
+}
 
-        // Set provider
-        session.metadata.provider = Some("anthropic".to_string());
+impl Iterator for SessionReader {
+    type Item = Result<Turn, ParseError>;
 
-        // Detect subagent metadata from the file path.
-        // Subagent files live at <session-uuid>/subagents/agent-<id>.jsonl
-        if let Some(stem) = path.file_stem().and_then(|s| s.to_str())
-            && stem.starts_with("agent-")
-        {
-            session.agent_id = Some(stem.to_string());
-            // Parent ID from the grandparent directory name (the session UUID)
-            if let Some(parent_dir) = path.parent().and_then(|p| p.parent())
-                && let Some(parent_name) = parent_dir.file_name().and_then(|n| n.to_str())
-            {
-                session.parent_id = Some(parent_name.to_string());
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_turn() {
+            Ok(Some(turn)) => Some(Ok(turn)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
             }
-            // Read companion .meta.json for agent type, default to "subagent"
-            let meta_path = path.with_extension("meta.json");
-            session.subagent_type = Some(
-                std::fs::read_to_string(&meta_path)
-                    .ok()
-                    .and_then(|s| serde_json::from_str::<Value>(&s).ok())
-                    .and_then(|v| {
-                        v.get("agentType")
-                            .and_then(|t| t.as_str())
-                            .map(String::from)
-                    })
-                    .unwrap_or_else(|| "subagent".into()),
-            );
-        } else {
-            session.subagent_type = Some("interactive".into());
         }
+    }
+}
 
-        Ok(session)
+/// Detect subagent metadata from the file path.
+///
+/// Subagent files live at `<session-uuid>/subagents/agent-<id>.jsonl`.
+fn set_subagent_metadata(session: &mut Session, path: &Path) {
+    if let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+        && stem.starts_with("agent-")
+    {
+        session.agent_id = Some(stem.to_string());
+        // Parent ID from the grandparent directory name (the session UUID)
+        if let Some(parent_dir) = path.parent().and_then(|p| p.parent())
+            && let Some(parent_name) = parent_dir.file_name().and_then(|n| n.to_str())
+        {
+            session.parent_id = Some(parent_name.to_string());
+        }
+        // Read companion .meta.json for agent type, default to "subagent"
+        let meta_path = path.with_extension("meta.json");
+        session.subagent_type = Some(
+            std::fs::read_to_string(&meta_path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+                .and_then(|v| {
+                    v.get("agentType")
+                        .and_then(|t| t.as_str())
+                        .map(String::from)
+                })
+                .unwrap_or_else(|| "subagent".into()),
+        );
+    } else {
+        session.subagent_type = Some("interactive".into());
     }
 }
 
@@ -357,6 +450,75 @@ fn sum_turn_tokens(
     any.then_some(total)
 }
 
+/// Build an [`ApiError`] from a `system`/`api_error` retry record:
+/// `{error: {status, error: {error: {type, message}}}, retryInMs, timestamp}`.
+fn api_error_retry(entry: &Value) -> ApiError {
+    let error = entry.get("error");
+    let status = error
+        .and_then(|e| e.get("status"))
+        .and_then(|v| v.as_u64())
+        .and_then(|s| u16::try_from(s).ok());
+    let detail = error
+        .and_then(|e| e.get("error"))
+        .map(|e| e.get("error").unwrap_or(e));
+    let message = detail
+        .and_then(|d| d.get("message"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .or_else(|| {
+            error
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_default();
+    let error_type = detail
+        .and_then(|d| d.get("type"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    ApiError {
+        kind: ApiErrorKind::classify(status, &format!("{error_type} {message}")),
+        status,
+        message,
+        retry_delay_ms: entry
+            .get("retryInMs")
+            .and_then(|v| v.as_f64())
+            .map(|ms| ms.round() as u64),
+        timestamp: entry
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    }
+}
+
+/// Build an [`ApiError`] from an `isApiErrorMessage` assistant placeholder, whose
+/// text reads `API Error: 529 {"type":"error",...}` or `API Error: Request timed out.`
+fn api_error_message(entry: &Value) -> ApiError {
+    let text: String = parse_message(entry, Role::Assistant)
+        .content
+        .iter()
+        .filter_map(|b| match b {
+            ContentBlock::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let detail = text.strip_prefix("API Error: ").unwrap_or(&text);
+    let status = detail
+        .get(..3)
+        .filter(|code| code.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|code| code.parse().ok());
+    ApiError {
+        kind: ApiErrorKind::classify(status, detail),
+        status,
+        message: text.clone(),
+        retry_delay_ms: None,
+        timestamp: entry
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    }
+}
+
 fn parse_message(entry: &Value, role: Role) -> Message {
     let mut content_blocks = Vec::new();
 
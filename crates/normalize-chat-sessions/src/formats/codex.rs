@@ -13,7 +13,7 @@
 //! Reference: `codex-rs/protocol/src/protocol.rs` + `codex-rs/rollout/src/recorder.rs`.
 
 use super::{DiscoverError, ParseError, SessionLocation, SessionRef, SessionSource, peek_lines};
-use crate::{ContentBlock, Message, Role, Session, Turn};
+use crate::{ApiError, ApiErrorKind, ContentBlock, Message, Role, Session, Turn};
 use serde_json::Value;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -356,7 +356,32 @@ fn parse_rollout(path: &Path) -> Result<Session, ParseError> {
                 }
             }
 
-            // session_meta, compacted, turn_context, world_state, event_msg — skip
+            // `error` / `stream_error` events record failed model requests; the
+            // latter is followed by a retry ("...; retrying 2/5 in 400ms…").
+            "event_msg" => {
+                let Some(payload) = entry.get("payload") else {
+                    continue;
+                };
+                let event_type = payload.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                if !matches!(event_type, "error" | "stream_error") {
+                    continue;
+                }
+                let message = payload
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let status = http_status(&message);
+                current_turn.api_errors.push(ApiError {
+                    kind: ApiErrorKind::classify(status, &message),
+                    status,
+                    retry_delay_ms: retry_delay_ms(&message),
+                    message,
+                    timestamp,
+                });
+            }
+
+            // compacted, turn_context, world_state — skip
             _ => {}
         }
     }
@@ -398,3 +423,34 @@ fn extract_content_items(payload: &Value) -> Vec<ContentBlock> {
 const _: () = {
     let _ = SystemTime::UNIX_EPOCH;
 };
+
+/// HTTP status following the word `status` in an error message
+/// (`unexpected status 429 Too Many Requests`, `status: 503`).
+fn http_status(message: &str) -> Option<u16> {
+    message.match_indices("status").find_map(|(i, word)| {
+        let rest = message[i + word.len()..].trim_start_matches([' ', ':', '=']);
+        let code = rest.get(..3)?;
+        code.parse::<u16>()
+            .ok()
+            .filter(|s| (100..600).contains(s) && code.bytes().all(|b| b.is_ascii_digit()))
+    })
+}
+
+/// Retry delay from a `... retrying 2/5 in 400ms…` / `in 1.5s` suffix, in milliseconds.
+fn retry_delay_ms(message: &str) -> Option<u64> {
+    let (_, after) = message.rsplit_once("retrying")?;
+    let (_, delay) = after.split_once(" in ")?;
+    let number: String = delay
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let value: f64 = number.parse().ok()?;
+    let unit = &delay[number.len()..];
+    if unit.starts_with("ms") {
+        Some(value.round() as u64)
+    } else if unit.starts_with('s') {
+        Some((value * 1000.0).round() as u64)
+    } else {
+        None
+    }
+}
@@ -17,7 +17,7 @@
 //!            `gemini-cli/packages/core/src/config/storage.ts`
 
 use super::{DiscoverError, ParseError, SessionLocation, SessionRef, SessionSource, peek_lines};
-use crate::{ContentBlock, Message, Role, Session, TokenUsage, Turn};
+use crate::{ApiError, ApiErrorKind, ContentBlock, Message, Role, Session, TokenUsage, Turn};
 use serde_json::Value;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -163,7 +163,8 @@ fn read_session_id_from_jsonl(path: &Path) -> Option<String> {
 /// MessageRecord types:
 /// - `"user"` → User message, starts a new Turn
 /// - `"gemini"` → Assistant message with optional `toolCalls`, `thoughts`, `tokens`
-/// - `"info" | "error" | "warning"` → skip (system messages)
+/// - `"error" | "warning"` → `ApiError` when the text is a quota/overload/timeout failure
+/// - `"info"` and other error/warning text → skip (system messages)
 fn parse_gemini_jsonl(path: &Path) -> Result<Session, ParseError> {
     let file = File::open(path).map_err(|e| ParseError::Io {
         path: path.to_path_buf(),
@@ -341,7 +342,26 @@ fn parse_gemini_jsonl(path: &Path) -> Result<Session, ParseError> {
                 }
             }
 
-            // "info" | "error" | "warning" — system-level, not user-facing conversation
+            // Quota, overload and timeout failures surface as error/warning records;
+            // other system messages are not user-facing conversation.
+            "error" | "warning" => {
+                let message = record
+                    .get("content")
+                    .map(extract_part_list_union_to_string)
+                    .unwrap_or_default();
+                let kind = ApiErrorKind::classify(None, &message);
+                if kind != ApiErrorKind::Other {
+                    current_turn.api_errors.push(ApiError {
+                        kind,
+                        status: None,
+                        message,
+                        retry_delay_ms: None,
+                        timestamp,
+                    });
+                }
+            }
+
+            // "info" — system-level, not user-facing conversation
             _ => {}
         }
     }
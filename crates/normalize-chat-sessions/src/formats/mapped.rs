@@ -0,0 +1,723 @@
+//! Config-driven session source for agent formats without a dedicated parser.
+//!
+//! A [`SessionMapping`] is a small TOML document describing where a format keeps
+//! its messages, roles, content blocks, tool calls and token usage. [`MappedFormat`]
+//! turns that description into a [`SessionSource`], so a new vendor's JSON or JSONL
+//! logs can be read into a [`Session`] without writing Rust.
+//!
+//! Mappings are loaded from `~/.config/normalize/session-formats/*.toml` and
+//! registered next to the built-in sources; [`MappedFormat::from_toml`] builds one
+//! directly.
+//!
+//! Field locations are dotted paths into the JSON (`usage.prompt_tokens`,
+//! `function.arguments`, `choices.0.message`); numeric segments index arrays and
+//! the empty path is the value itself. Every section has defaults matching the
+//! Anthropic/OpenAI message shape, so most mappings only name what differs:
+//!
+//! ```toml
+//! name = "acme"
+//! layout = "json"            # one JSON document per session ("jsonl": one record per line)
+//! root = "~/.acme/sessions"  # where `discover` looks
+//! detect = ["conversation_id", "turns"]  # paths that must exist for auto-detection
+//! provider = "openai"
+//! messages = "turns"         # json: path to the message array; jsonl: path to the message in each line
+//!
+//! [metadata]
+//! session_id = "conversation_id"
+//! timestamp = "created_at"
+//! model = "settings.model"
+//!
+//! [message]
+//! role = "author"
+//! content = "body"
+//!
+//! [roles]
+//! user = ["human"]
+//! assistant = ["bot"]
+//!
+//! [tool_calls]               # OpenAI-style calls next to the content
+//! path = "tool_calls"
+//! name = "function.name"
+//! input = "function.arguments"
+//!
+//! [usage]
+//! input = "usage.prompt_tokens"
+//! output = "usage.completion_tokens"
+//! ```
+
+use super::{DiscoverError, ParseError, SessionLocation, SessionRef, SessionSource, read_file};
+use crate::{ContentBlock, Message, Role, Session, TokenUsage, Turn};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// How session records are laid out on disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappedLayout {
+    /// One JSON record per line; each line holds (at most) one message.
+    #[default]
+    Jsonl,
+    /// One JSON document per file, holding an array of messages.
+    Json,
+}
+
+/// Declarative description of a session log format.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SessionMapping {
+    /// Format identifier used by `--format` and stored in `Session::format`.
+    pub name: String,
+    /// Record layout.
+    #[serde(default)]
+    pub layout: MappedLayout,
+    /// Sessions directory (`~` is expanded); `discover` walks it recursively.
+    #[serde(default)]
+    pub root: Option<String>,
+    /// File extension of session files; defaults to the layout name.
+    #[serde(default)]
+    pub extension: Option<String>,
+    /// Paths that must all exist in the document (json) or first record (jsonl)
+    /// for auto-detection. Empty means the format is only used when named.
+    #[serde(default)]
+    pub detect: Vec<String>,
+    /// Literal provider name (e.g. `"openai"`).
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// json: path to the message array (default `messages`; `""` for a top-level
+    /// array). jsonl: path to the message inside each line (default: the line).
+    #[serde(default)]
+    pub messages: Option<String>,
+    #[serde(default)]
+    pub metadata: MetadataMapping,
+    #[serde(default)]
+    pub message: MessageMapping,
+    #[serde(default)]
+    pub roles: RoleMapping,
+    #[serde(default)]
+    pub blocks: BlockMapping,
+    /// Tool calls stored beside the content rather than as content blocks.
+    #[serde(default)]
+    pub tool_calls: Option<ToolCallMapping>,
+    #[serde(default)]
+    pub usage: UsageMapping,
+}
+
+/// Session-level fields, resolved against the document (json) or the first
+/// record that has them (jsonl).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataMapping {
+    pub session_id: Option<String>,
+    pub timestamp: Option<String>,
+    pub model: Option<String>,
+    pub project: Option<String>,
+}
+
+/// Per-message fields, relative to the message object.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageMapping {
+    /// Role string, mapped through [`RoleMapping`].
+    pub role: String,
+    /// A string, or an array of strings and typed blocks.
+    pub content: String,
+    pub timestamp: String,
+    /// Model that produced the message; recorded on the turn's usage.
+    pub model: Option<String>,
+    /// Call id answered by a tool-role message whose content is plain text.
+    pub tool_call_id: String,
+}
+
+impl Default for MessageMapping {
+    fn default() -> Self {
+        Self {
+            role: "role".into(),
+            content: "content".into(),
+            timestamp: "timestamp".into(),
+            model: None,
+            tool_call_id: "tool_call_id".into(),
+        }
+    }
+}
+
+/// Role strings accepted for each [`Role`]. Messages with other roles are skipped.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoleMapping {
+    pub user: Vec<String>,
+    pub assistant: Vec<String>,
+    pub system: Vec<String>,
+    pub tool: Vec<String>,
+}
+
+impl Default for RoleMapping {
+    fn default() -> Self {
+        Self {
+            user: vec!["user".into()],
+            assistant: vec!["assistant".into()],
+            system: vec!["system".into()],
+            tool: vec!["tool".into()],
+        }
+    }
+}
+
+impl RoleMapping {
+    fn resolve(&self, role: &str) -> Option<Role> {
+        let has = |names: &[String]| names.iter().any(|n| n == role);
+        if has(&self.user) {
+            Some(Role::User)
+        } else if has(&self.assistant) {
+            Some(Role::Assistant)
+        } else if has(&self.system) {
+            Some(Role::System)
+        } else if has(&self.tool) {
+            Some(Role::Tool)
+        } else {
+            None
+        }
+    }
+}
+
+/// Typed content blocks inside a content array, told apart by a tag field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlockMapping {
+    /// Field holding the block tag.
+    #[serde(rename = "type")]
+    pub tag: String,
+    pub text: TextBlockMapping,
+    pub thinking: TextBlockMapping,
+    pub tool_use: ToolUseBlockMapping,
+    pub tool_result: ToolResultBlockMapping,
+}
+
+impl Default for BlockMapping {
+    fn default() -> Self {
+        Self {
+            tag: "type".into(),
+            text: TextBlockMapping {
+                tag: "text".into(),
+                text: "text".into(),
+            },
+            thinking: TextBlockMapping {
+                tag: "thinking".into(),
+                text: "thinking".into(),
+            },
+            tool_use: ToolUseBlockMapping::default(),
+            tool_result: ToolResultBlockMapping::default(),
+        }
+    }
+}
+
+/// A block carrying only text (`text`, `thinking`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TextBlockMapping {
+    /// Tag value identifying the block.
+    #[serde(rename = "type")]
+    pub tag: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolUseBlockMapping {
+    #[serde(rename = "type")]
+    pub tag: String,
+    pub id: String,
+    pub name: String,
+    pub input: String,
+}
+
+impl Default for ToolUseBlockMapping {
+    fn default() -> Self {
+        Self {
+            tag: "tool_use".into(),
+            id: "id".into(),
+            name: "name".into(),
+            input: "input".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolResultBlockMapping {
+    #[serde(rename = "type")]
+    pub tag: String,
+    pub id: String,
+    pub content: String,
+    pub is_error: String,
+}
+
+impl Default for ToolResultBlockMapping {
+    fn default() -> Self {
+        Self {
+            tag: "tool_result".into(),
+            id: "tool_use_id".into(),
+            content: "content".into(),
+            is_error: "is_error".into(),
+        }
+    }
+}
+
+/// Tool calls held in an array next to the message content.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolCallMapping {
+    /// Path to the call array, relative to the message.
+    pub path: String,
+    pub id: String,
+    pub name: String,
+    /// Arguments; a string holding JSON is decoded.
+    pub input: String,
+}
+
+impl Default for ToolCallMapping {
+    fn default() -> Self {
+        Self {
+            path: "tool_calls".into(),
+            id: "id".into(),
+            name: "function.name".into(),
+            input: "function.arguments".into(),
+        }
+    }
+}
+
+/// Token counts, relative to the message. Unset paths are not read.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UsageMapping {
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub cache_read: Option<String>,
+    pub cache_create: Option<String>,
+}
+
+/// A [`SessionSource`] driven by a [`SessionMapping`].
+#[derive(Debug, Clone)]
+pub struct MappedFormat {
+    name: &'static str,
+    mapping: SessionMapping,
+}
+
+impl MappedFormat {
+    /// Build a source from a mapping.
+    ///
+    /// The name is leaked to satisfy `SessionSource::name`'s `&'static str`;
+    /// mappings are loaded once per process, so this is bounded.
+    pub fn new(mapping: SessionMapping) -> Self {
+        let name: &'static str = Box::leak(mapping.name.clone().into_boxed_str());
+        Self { name, mapping }
+    }
+
+    /// Parse a mapping from TOML source.
+    pub fn from_toml(source: &str) -> Result<Self, String> {
+        toml::from_str::<SessionMapping>(source)
+            .map(Self::new)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Read and parse a mapping file.
+    pub fn from_file(path: &Path) -> Result<Self, ParseError> {
+        let source = read_file(path)?;
+        Self::from_toml(&source).map_err(|message| ParseError::Format {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// The mapping this source was built from.
+    pub fn mapping(&self) -> &SessionMapping {
+        &self.mapping
+    }
+
+    fn extension(&self) -> &str {
+        match (&self.mapping.extension, self.mapping.layout) {
+            (Some(ext), _) => ext,
+            (None, MappedLayout::Jsonl) => "jsonl",
+            (None, MappedLayout::Json) => "json",
+        }
+    }
+
+    /// Parse a session file according to the mapping.
+    pub fn parse(&self, path: &Path) -> Result<Session, ParseError> {
+        let content = read_file(path)?;
+        let format_err = |message: String| ParseError::Format {
+            path: path.to_path_buf(),
+            message,
+        };
+        let m = &self.mapping;
+        let mut session = Session::new(path.to_path_buf(), self.name);
+        session.metadata.provider = m.provider.clone();
+
+        let messages: Vec<&Value>;
+        let document: Value;
+        let records: Vec<Value>;
+        match m.layout {
+            MappedLayout::Json => {
+                document = serde_json::from_str(&content)
+                    .map_err(|e| format_err(format!("invalid JSON: {e}")))?;
+                self.fill_metadata(&mut session, &document);
+                let path = m.messages.as_deref().unwrap_or("messages");
+                messages = lookup(&document, path)
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| format_err(format!("no message array at `{path}`")))?
+                    .iter()
+                    .collect();
+            }
+            MappedLayout::Jsonl => {
+                records = content
+                    .lines()
+                    .filter(|l| !l.trim().is_empty())
+                    .filter_map(|l| serde_json::from_str(l).ok())
+                    .collect();
+                for record in &records {
+                    self.fill_metadata(&mut session, record);
+                }
+                let path = m.messages.as_deref().unwrap_or("");
+                messages = records.iter().filter_map(|r| lookup(r, path)).collect();
+            }
+        }
+
+        let mut current_turn = Turn::default();
+        for value in messages {
+            let Some(mut message) = self.message(value) else {
+                continue;
+            };
+            let tool_result_only = !message.content.is_empty()
+                && message
+                    .content
+                    .iter()
+                    .all(|b| matches!(b, ContentBlock::ToolResult { .. }));
+            if message.role == Role::User && tool_result_only {
+                message.role = Role::Tool;
+            }
+            // A user prompt starts a new turn; a leading system prompt stays with it.
+            if message.role == Role::User
+                && current_turn.messages.iter().any(|m| m.role != Role::System)
+            {
+                session.turns.push(std::mem::take(&mut current_turn));
+            }
+            if let Some(usage) = self.usage(value) {
+                let total = current_turn
+                    .token_usage
+                    .get_or_insert_with(Default::default);
+                total.input += usage.input;
+                total.output += usage.output;
+                if let Some(n) = usage.cache_read {
+                    *total.cache_read.get_or_insert(0) += n;
+                }
+                if let Some(n) = usage.cache_create {
+                    *total.cache_create.get_or_insert(0) += n;
+                }
+                if usage.model.is_some() {
+                    total.model = usage.model;
+                }
+            }
+            current_turn.messages.push(message);
+        }
+        if !current_turn.messages.is_empty() {
+            session.turns.push(current_turn);
+        }
+        if session.metadata.model.is_none() {
+            session.metadata.model = session
+                .turns
+                .iter()
+                .find_map(|t| t.token_usage.as_ref().and_then(|u| u.model.clone()));
+        }
+        Ok(session)
+    }
+
+    /// Set any metadata fields still unset from `value`.
+    fn fill_metadata(&self, session: &mut Session, value: &Value) {
+        let fields = &self.mapping.metadata;
+        let meta = &mut session.metadata;
+        for (slot, path) in [
+            (&mut meta.session_id, &fields.session_id),
+            (&mut meta.timestamp, &fields.timestamp),
+            (&mut meta.model, &fields.model),
+            (&mut meta.project, &fields.project),
+        ] {
+            if slot.is_none()
+                && let Some(path) = path
+            {
+                *slot = lookup_string(value, path);
+            }
+        }
+    }
+
+    fn message(&self, value: &Value) -> Option<Message> {
+        let m = &self.mapping;
+        let role = m.roles.resolve(lookup(value, &m.message.role)?.as_str()?)?;
+        let mut content = Vec::new();
+        match lookup(value, &m.message.content) {
+            Some(Value::String(text)) if role == Role::Tool => {
+                content.push(ContentBlock::ToolResult {
+                    tool_use_id: lookup_string(value, &m.message.tool_call_id).unwrap_or_default(),
+                    content: text.clone(),
+                    is_error: false,
+                });
+            }
+            Some(Value::String(text)) if !text.is_empty() => {
+                content.push(ContentBlock::Text { text: text.clone() });
+            }
+            Some(Value::Array(blocks)) => {
+                content.extend(blocks.iter().filter_map(|b| self.block(b)));
+            }
+            _ => {}
+        }
+        if let Some(calls) = &m.tool_calls
+            && let Some(Value::Array(items)) = lookup(value, &calls.path)
+        {
+            for call in items {
+                let Some(name) = lookup_string(call, &calls.name) else {
+                    continue;
+                };
+                content.push(ContentBlock::ToolUse {
+                    id: lookup_string(call, &calls.id).unwrap_or_default(),
+                    name,
+                    input: decode_input(lookup(call, &calls.input)),
+                });
+            }
+        }
+        Some(Message {
+            role,
+            content,
+            timestamp: lookup_string(value, &m.message.timestamp),
+        })
+    }
+
+    fn block(&self, value: &Value) -> Option<ContentBlock> {
+        let b = &self.mapping.blocks;
+        if let Value::String(text) = value {
+            return Some(ContentBlock::Text { text: text.clone() });
+        }
+        let tag = lookup(value, &b.tag)?.as_str()?;
+        if tag == b.text.tag {
+            Some(ContentBlock::Text {
+                text: lookup_string(value, &b.text.text)?,
+            })
+        } else if tag == b.thinking.tag {
+            Some(ContentBlock::Thinking {
+                text: lookup_string(value, &b.thinking.text)?,
+            })
+        } else if tag == b.tool_use.tag {
+            Some(ContentBlock::ToolUse {
+                id: lookup_string(value, &b.tool_use.id).unwrap_or_default(),
+                name: lookup_string(value, &b.tool_use.name)?,
+                input: decode_input(lookup(value, &b.tool_use.input)),
+            })
+        } else if tag == b.tool_result.tag {
+            Some(ContentBlock::ToolResult {
+                tool_use_id: lookup_string(value, &b.tool_result.id).unwrap_or_default(),
+                content: flatten_text(lookup(value, &b.tool_result.content), &b.text),
+                is_error: lookup(value, &b.tool_result.is_error)
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn usage(&self, value: &Value) -> Option<TokenUsage> {
+        let u = &self.mapping.usage;
+        let count = |path: &Option<String>| {
+            path.as_deref()
+                .and_then(|p| lookup(value, p))
+                .and_then(Value::as_u64)
+        };
+        let (input, output) = (count(&u.input), count(&u.output));
+        if input.is_none() && output.is_none() {
+            return None;
+        }
+        Some(TokenUsage {
+            input: input.unwrap_or(0),
+            output: output.unwrap_or(0),
+            cache_read: count(&u.cache_read),
+            cache_create: count(&u.cache_create),
+            model: self
+                .mapping
+                .message
+                .model
+                .as_deref()
+                .and_then(|p| lookup_string(value, p)),
+        })
+    }
+}
+
+impl SessionSource for MappedFormat {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn sessions_root(&self, _project: Option<&Path>) -> PathBuf {
+        let root = self.mapping.root.as_deref().unwrap_or(".");
+        match root.strip_prefix("~/") {
+            Some(rest) => {
+                let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".into());
+                PathBuf::from(home).join(rest)
+            }
+            None => PathBuf::from(root),
+        }
+    }
+
+    /// Returns 0.9 when the extension matches and every `detect` path exists,
+    /// so a built-in source recognising the same file still wins.
+    fn detect(&self, path: &Path) -> f64 {
+        let m = &self.mapping;
+        if m.detect.is_empty()
+            || path.extension().and_then(|e| e.to_str()) != Some(self.extension())
+        {
+            return 0.0;
+        }
+        let probe = match m.layout {
+            MappedLayout::Jsonl => super::peek_lines(path, 1)
+                .first()
+                .and_then(|l| serde_json::from_str::<Value>(l).ok()),
+            MappedLayout::Json => read_file(path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<Value>(&s).ok()),
+        };
+        match probe {
+            Some(v) if m.detect.iter().all(|p| lookup(&v, p).is_some()) => 0.9,
+            _ => 0.0,
+        }
+    }
+
+    fn discover(&self, root: &Path) -> Result<Vec<SessionRef>, DiscoverError> {
+        let mut refs = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension().and_then(|e| e.to_str()) != Some(self.extension()) {
+                    continue;
+                }
+                let Ok(mtime) = path.metadata().and_then(|m| m.modified()) else {
+                    continue;
+                };
+                refs.push(SessionRef {
+                    format: self.name,
+                    location: SessionLocation::File(path.clone()),
+                    path,
+                    mtime,
+                    parent_session_id: None,
+                    agent_id: None,
+                    subagent_type: Some("interactive".into()),
+                });
+            }
+        }
+        Ok(refs)
+    }
+
+    fn load(&self, r: &SessionRef) -> Result<Session, ParseError> {
+        let path = match &r.location {
+            SessionLocation::File(p) => p.as_path(),
+            _ => &r.path,
+        };
+        let mut session = self.parse(path)?;
+        session.parent_id = r.parent_session_id.clone();
+        session.agent_id = r.agent_id.clone();
+        session.subagent_type = r.subagent_type.clone();
+        Ok(session)
+    }
+}
+
+/// Directory holding user session mappings: `~/.config/normalize/session-formats/`.
+pub fn mapped_formats_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("normalize").join("session-formats"))
+}
+
+/// Load every `*.toml` mapping in `dir`, sorted by file name.
+///
+/// Files that fail to parse are returned as errors so callers can report them.
+pub fn load_mapped_formats(dir: &Path) -> Vec<Result<MappedFormat, ParseError>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("toml"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|p| MappedFormat::from_file(p)).collect()
+}
+
+/// User mappings from [`mapped_formats_dir`], loaded once per process.
+///
+/// Invalid files are skipped; call [`load_mapped_formats`] to see their errors.
+pub(crate) fn user_mapped_formats() -> &'static [MappedFormat] {
+    static FORMATS: OnceLock<Vec<MappedFormat>> = OnceLock::new();
+    FORMATS.get_or_init(|| {
+        mapped_formats_dir()
+            .map(|dir| {
+                load_mapped_formats(&dir)
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// Resolve a dotted path (`a.b.0.c`) inside `value`. The empty path is `value`.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.').try_fold(value, |v, segment| match v {
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        Value::Object(map) => map.get(segment),
+        _ => None,
+    })
+}
+
+/// Look up a scalar and render it as a string (numbers become their decimal form).
+fn lookup_string(value: &Value, path: &str) -> Option<String> {
+    match lookup(value, path)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Tool arguments: strings holding JSON (OpenAI `function.arguments`) are decoded.
+fn decode_input(value: Option<&Value>) -> Value {
+    match value {
+        Some(Value::String(s)) => {
+            serde_json::from_str(s).unwrap_or_else(|_| Value::String(s.clone()))
+        }
+        Some(v) => v.clone(),
+        None => Value::Null,
+    }
+}
+
+/// Tool result content: a string, or an array of strings and text blocks joined by newlines.
+fn flatten_text(value: Option<&Value>, text: &TextBlockMapping) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::String(s) => Some(s.clone()),
+                _ => lookup_string(item, &text.text),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(v) => v.to_string(),
+        None => String::new(),
+    }
+}
@@ -24,6 +24,10 @@
 //! }
 //! register(&MyAgentSource);
 //! ```
+//!
+//! Formats that are plain JSON or JSONL can instead be described in TOML and
+//! dropped into `~/.config/normalize/session-formats/` (feature `format-mapped`);
+//! see [`MappedFormat`].
 
 #[cfg(any(feature = "format-cline", feature = "format-roo"))]
 mod anthropic_history;
@@ -35,6 +39,8 @@ mod cline;
 mod codex;
 #[cfg(feature = "format-gemini")]
 mod gemini_cli;
+#[cfg(feature = "format-mapped")]
+mod mapped;
 #[cfg(feature = "format-normalize")]
 mod normalize_agent;
 #[cfg(feature = "format-opencode")]
@@ -43,13 +49,19 @@ mod opencode;
 mod roo_code;
 
 #[cfg(feature = "format-claude")]
-pub use claude_code::ClaudeCodeFormat;
+pub use claude_code::{ClaudeCodeFormat, SessionReader};
 #[cfg(feature = "format-cline")]
 pub use cline::ClineFormat;
 #[cfg(feature = "format-codex")]
 pub use codex::CodexFormat;
 #[cfg(feature = "format-gemini")]
 pub use gemini_cli::GeminiCliFormat;
+#[cfg(feature = "format-mapped")]
+pub use mapped::{
+    BlockMapping, MappedFormat, MappedLayout, MessageMapping, MetadataMapping, RoleMapping,
+    SessionMapping, TextBlockMapping, ToolCallMapping, ToolResultBlockMapping, ToolUseBlockMapping,
+    UsageMapping, load_mapped_formats, mapped_formats_dir,
+};
 #[cfg(feature = "format-normalize")]
 pub use normalize_agent::NormalizeAgentFormat;
 #[cfg(feature = "format-opencode")]
@@ -250,6 +262,10 @@ fn init_builtin() {
         sources.push(&OpenCodeFormat);
         #[cfg(feature = "format-roo")]
         sources.push(&RooCodeFormat);
+        #[cfg(feature = "format-mapped")]
+        for format in mapped::user_mapped_formats() {
+            sources.push(format);
+        }
     });
 }
 
@@ -432,6 +448,10 @@ impl FormatRegistry {
         sources.push(Box::new(OpenCodeFormat));
         #[cfg(feature = "format-roo")]
         sources.push(Box::new(RooCodeFormat));
+        #[cfg(feature = "format-mapped")]
+        for format in mapped::user_mapped_formats() {
+            sources.push(Box::new(format.clone()));
+        }
         Self { sources }
     }
 
@@ -54,6 +54,78 @@ pub struct Turn {
     pub messages: Vec<Message>,
     /// Token usage for this turn (if available).
     pub token_usage: Option<TokenUsage>,
+    /// Provider-level API errors hit while producing this turn, in log order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_errors: Vec<ApiError>,
+}
+
+/// A provider-level API failure recorded in a session log (rate limit,
+/// overload, timeout), as opposed to a failed tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    /// HTTP status code, when the log records one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Error message as logged.
+    pub message: String,
+    /// Time the client waited before retrying, in milliseconds. `None` when the
+    /// log records no retry (e.g. the final error after retries ran out).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_delay_ms: Option<u64>,
+    pub timestamp: Option<String>,
+}
+
+/// Category of an [`ApiError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    /// Too many requests or tokens for the account (HTTP 429).
+    RateLimit,
+    /// Provider capacity exhausted (Anthropic 529 `overloaded_error`, HTTP 503).
+    Overloaded,
+    /// Request or stream timed out.
+    Timeout,
+    /// Other 5xx server error.
+    Server,
+    /// Anything else (auth, invalid request, dropped connection, ...).
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Classify from an HTTP status and/or the logged error text.
+    pub fn classify(status: Option<u16>, text: &str) -> Self {
+        let text = text.to_lowercase();
+        match status {
+            Some(429) => return ApiErrorKind::RateLimit,
+            Some(529 | 503) => return ApiErrorKind::Overloaded,
+            Some(408 | 504) => return ApiErrorKind::Timeout,
+            _ => {}
+        }
+        if text.contains("rate_limit") || text.contains("rate limit") || text.contains("quota") {
+            ApiErrorKind::RateLimit
+        } else if text.contains("overloaded") {
+            ApiErrorKind::Overloaded
+        } else if text.contains("timed out") || text.contains("timeout") {
+            ApiErrorKind::Timeout
+        } else if status.is_some_and(|s| (500..600).contains(&s)) {
+            ApiErrorKind::Server
+        } else {
+            ApiErrorKind::Other
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiErrorKind::RateLimit => "rate limit",
+            ApiErrorKind::Overloaded => "overloaded",
+            ApiErrorKind::Timeout => "timeout",
+            ApiErrorKind::Server => "server error",
+            ApiErrorKind::Other => "other",
+        }
+    }
 }
 
 /// A message from a participant.
@@ -176,6 +248,11 @@ impl Session {
         })
     }
 
+    /// Iterate over all provider-level API errors.
+    pub fn api_errors(&self) -> impl Iterator<Item = &ApiError> {
+        self.turns.iter().flat_map(|t| &t.api_errors)
+    }
+
     /// Total token usage across all turns.
     pub fn total_tokens(&self) -> TokenUsage {
         let mut total = TokenUsage::default();
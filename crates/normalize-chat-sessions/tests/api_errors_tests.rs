@@ -0,0 +1,87 @@
+//! Provider-level API error extraction (rate limits, overloads, timeouts).
+//!
+//! Records are modelled on what each client writes when a model request fails:
+//! Claude Code's `system`/`api_error` retry records and `isApiErrorMessage`
+//! placeholders, and Codex's `error`/`stream_error` events.
+
+use normalize_chat_sessions::{
+    ApiErrorKind, ClaudeCodeFormat, CodexFormat, SessionLocation, SessionRef, SessionSource,
+};
+use std::path::Path;
+use std::time::SystemTime;
+
+fn load(format: &dyn SessionSource, path: &Path) -> normalize_chat_sessions::Session {
+    format
+        .load(&SessionRef {
+            format: format.name(),
+            location: SessionLocation::File(path.to_path_buf()),
+            path: path.to_path_buf(),
+            mtime: SystemTime::UNIX_EPOCH,
+            parent_session_id: None,
+            agent_id: None,
+            subagent_type: None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn claude_api_error_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    let lines = [
+        r#"{"type":"user","message":{"role":"user","content":"fix it"},"timestamp":"t0"}"#,
+        r#"{"type":"system","subtype":"api_error","level":"error","error":{"status":529,"error":{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}},"retryInMs":612.4,"retryAttempt":1,"maxRetries":10,"timestamp":"t1"}"#,
+        r#"{"type":"system","subtype":"api_error","level":"error","error":{"status":429,"error":{"type":"error","error":{"type":"rate_limit_error","message":"Number of request tokens has exceeded your per-minute rate limit"}}},"retryInMs":1200,"retryAttempt":2,"maxRetries":10,"timestamp":"t2"}"#,
+        r#"{"type":"assistant","requestId":"r1","message":{"role":"assistant","model":"claude-sonnet-4-5-20250929","content":[{"type":"text","text":"Done."}],"usage":{"input_tokens":10,"output_tokens":5}},"timestamp":"t3"}"#,
+        r#"{"type":"user","message":{"role":"user","content":"again"},"timestamp":"t4"}"#,
+        r#"{"type":"assistant","isApiErrorMessage":true,"message":{"role":"assistant","model":"<synthetic>","content":[{"type":"text","text":"API Error: Request timed out."}]},"timestamp":"t5"}"#,
+    ];
+    std::fs::write(&path, lines.join("\n")).unwrap();
+
+    let session = load(&ClaudeCodeFormat, &path);
+    assert_eq!(session.turns.len(), 2);
+
+    let first = &session.turns[0].api_errors;
+    assert_eq!(first.len(), 2);
+    assert_eq!(first[0].kind, ApiErrorKind::Overloaded);
+    assert_eq!(first[0].status, Some(529));
+    assert_eq!(first[0].message, "Overloaded");
+    assert_eq!(first[0].retry_delay_ms, Some(612));
+    assert_eq!(first[1].kind, ApiErrorKind::RateLimit);
+    assert_eq!(first[1].retry_delay_ms, Some(1200));
+
+    // The synthetic placeholder is an error, not an assistant message.
+    let second = &session.turns[1];
+    assert_eq!(second.messages.len(), 1);
+    assert_eq!(second.api_errors.len(), 1);
+    assert_eq!(second.api_errors[0].kind, ApiErrorKind::Timeout);
+    assert_eq!(second.api_errors[0].retry_delay_ms, None);
+    assert_eq!(
+        session.metadata.model.as_deref(),
+        Some("claude-sonnet-4-5-20250929")
+    );
+}
+
+#[test]
+fn codex_error_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rollout.jsonl");
+    let lines = [
+        r#"{"timestamp":"t0","type":"session_meta","payload":{"session_id":"s1","model_provider":"openai"}}"#,
+        r#"{"timestamp":"t1","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hi"}]}}"#,
+        r#"{"timestamp":"t2","type":"event_msg","payload":{"type":"stream_error","message":"stream disconnected before completion: unexpected status 429 Too Many Requests; retrying 1/5 in 188ms…"}}"#,
+        r#"{"timestamp":"t3","type":"event_msg","payload":{"type":"stream_error","message":"stream disconnected before completion: idle timeout waiting for SSE; retrying 2/5 in 1.5s…"}}"#,
+        r#"{"timestamp":"t4","type":"event_msg","payload":{"type":"token_count","info":null}}"#,
+    ];
+    std::fs::write(&path, lines.join("\n")).unwrap();
+
+    let session = load(&CodexFormat, &path);
+    let errors: Vec<_> = session.api_errors().collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].kind, ApiErrorKind::RateLimit);
+    assert_eq!(errors[0].status, Some(429));
+    assert_eq!(errors[0].retry_delay_ms, Some(188));
+    assert_eq!(errors[1].kind, ApiErrorKind::Timeout);
+    assert_eq!(errors[1].status, None);
+    assert_eq!(errors[1].retry_delay_ms, Some(1500));
+}
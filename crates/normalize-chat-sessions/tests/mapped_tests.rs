@@ -0,0 +1,152 @@
+//! Integration tests for TOML-mapped session formats.
+//!
+//! The fixtures under `fixtures/mapped/` pair a mapping (`*.toml`) with a
+//! directory of sessions in that shape: an OpenAI chat-completions transcript
+//! (JSON, tool calls beside the content) and an invented JSONL agent log with
+//! nested messages and custom block tags.
+
+use normalize_chat_sessions::{
+    ContentBlock, MappedFormat, Role, SessionSource, load_mapped_formats,
+};
+use std::path::{Path, PathBuf};
+
+fn mapped_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("mapped")
+}
+
+fn format(name: &str) -> MappedFormat {
+    MappedFormat::from_file(&mapped_dir().join(format!("{name}.toml"))).unwrap()
+}
+
+#[test]
+fn load_mapped_formats_reads_every_toml() {
+    let names: Vec<String> = load_mapped_formats(&mapped_dir())
+        .into_iter()
+        .map(|f| f.unwrap().name().to_string())
+        .collect();
+    assert_eq!(names, ["acme", "openai-chat"]);
+}
+
+#[test]
+fn unknown_mapping_keys_are_rejected() {
+    let err = MappedFormat::from_toml("name = \"x\"\n[message]\nrol = \"who\"\n").unwrap_err();
+    assert!(err.contains("rol"), "{err}");
+}
+
+#[test]
+fn detect_requires_extension_and_paths() {
+    let openai = format("openai");
+    let acme = format("acme");
+    let chat = mapped_dir().join("openai/chat-1.json");
+    let run = mapped_dir().join("acme/run.jsonl");
+    assert!(openai.detect(&chat) > 0.0);
+    assert_eq!(openai.detect(&run), 0.0);
+    assert!(acme.detect(&run) > 0.0);
+    assert_eq!(acme.detect(&chat), 0.0);
+}
+
+#[test]
+fn discover_walks_root() {
+    let acme = format("acme");
+    let refs = acme.discover(&mapped_dir()).unwrap();
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].format, "acme");
+    assert!(refs[0].path.ends_with("acme/run.jsonl"));
+}
+
+#[test]
+fn openai_json_transcript() {
+    let session = format("openai")
+        .parse(&mapped_dir().join("openai/chat-1.json"))
+        .unwrap();
+    assert_eq!(session.format, "openai-chat");
+    assert_eq!(session.metadata.session_id.as_deref(), Some("chatcmpl-42"));
+    assert_eq!(session.metadata.timestamp.as_deref(), Some("1715100000"));
+    assert_eq!(session.metadata.model.as_deref(), Some("gpt-4o"));
+    assert_eq!(session.metadata.provider.as_deref(), Some("openai"));
+
+    // The system prompt opens the first turn; each user message starts a new one.
+    assert_eq!(session.turns.len(), 2);
+    let roles: Vec<Role> = session.turns[0].messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        [
+            Role::System,
+            Role::User,
+            Role::Assistant,
+            Role::Tool,
+            Role::Assistant
+        ]
+    );
+
+    let (name, input) = session.tool_uses().next().unwrap();
+    assert_eq!(name, "list_dir");
+    assert_eq!(input["path"], "src");
+    match &session.turns[0].messages[3].content[0] {
+        ContentBlock::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => {
+            assert_eq!(tool_use_id, "call_1");
+            assert_eq!(content, "lib.rs\nmain.rs");
+            assert!(!is_error);
+        }
+        other => panic!("expected tool result, got {other:?}"),
+    }
+
+    let usage = session.turns[0].token_usage.as_ref().unwrap();
+    assert_eq!((usage.input, usage.output), (270, 30));
+    let total = session.total_tokens();
+    assert_eq!((total.input, total.output), (440, 35));
+}
+
+#[test]
+fn jsonl_with_nested_messages_and_custom_blocks() {
+    let session = format("acme")
+        .parse(&mapped_dir().join("acme/run.jsonl"))
+        .unwrap();
+    assert_eq!(session.metadata.session_id.as_deref(), Some("s-7"));
+    assert_eq!(session.metadata.project.as_deref(), Some("/work/app"));
+    // No session-level model path: taken from the first message that has one.
+    assert_eq!(session.metadata.model.as_deref(), Some("acme-large"));
+
+    // The tool-result-only human message stays in the turn as a tool message.
+    assert_eq!(session.turns.len(), 1);
+    let messages = &session.turns[0].messages;
+    let roles: Vec<Role> = messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        [Role::User, Role::Assistant, Role::Tool, Role::Assistant]
+    );
+    assert_eq!(
+        messages[0].timestamp.as_deref(),
+        Some("2026-01-02T03:04:06Z")
+    );
+    assert!(matches!(
+        &messages[1].content[0],
+        ContentBlock::Thinking { text } if text == "Use cargo."
+    ));
+    assert!(matches!(
+        &messages[1].content[1],
+        ContentBlock::ToolUse { id, name, input }
+            if id == "c1" && name == "shell" && input["cmd"] == "cargo test"
+    ));
+    assert!(matches!(
+        &messages[2].content[0],
+        ContentBlock::ToolResult { tool_use_id, content, is_error: true }
+            if tool_use_id == "c1" && content == "1 failed"
+    ));
+    assert!(matches!(
+        &messages[3].content[0],
+        ContentBlock::Text { text } if text == "One test fails."
+    ));
+
+    let usage = session.turns[0].token_usage.as_ref().unwrap();
+    assert_eq!((usage.input, usage.output), (100, 13));
+    assert_eq!(usage.cache_read, Some(30));
+    assert_eq!(usage.model.as_deref(), Some("acme-large"));
+}
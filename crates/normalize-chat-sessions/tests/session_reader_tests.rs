@@ -0,0 +1,77 @@
+//! Streaming Claude Code reader: turns arrive one at a time and match `load`.
+
+use normalize_chat_sessions::{
+    ClaudeCodeFormat, Role, SessionLocation, SessionReader, SessionRef, SessionSource,
+};
+use std::path::Path;
+use std::time::SystemTime;
+
+fn load(path: &Path) -> normalize_chat_sessions::Session {
+    ClaudeCodeFormat
+        .load(&SessionRef {
+            format: "claude",
+            location: SessionLocation::File(path.to_path_buf()),
+            path: path.to_path_buf(),
+            mtime: SystemTime::UNIX_EPOCH,
+            parent_session_id: None,
+            agent_id: None,
+            subagent_type: None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn streams_turns_like_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    let lines = [
+        r#"{"type":"summary","sessionId":"s1","timestamp":"t0"}"#,
+        r#"{"type":"user","message":{"role":"user","content":"read it"}}"#,
+        r#"{"type":"assistant","requestId":"r1","message":{"role":"assistant","model":"claude-sonnet-4-5-20250929","content":[{"type":"tool_use","id":"u1","name":"Read","input":{"file_path":"a.rs"}}],"usage":{"input_tokens":10,"output_tokens":3}}}"#,
+        r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"u1","content":"fn main() {}"}]}}"#,
+        r#"{"type":"assistant","requestId":"r2","message":{"role":"assistant","model":"claude-sonnet-4-5-20250929","content":[{"type":"text","text":"Looks fine."}],"usage":{"input_tokens":20,"output_tokens":4}}}"#,
+        "not json",
+        r#"{"type":"user","message":{"role":"user","content":"thanks"}}"#,
+        r#"{"type":"assistant","requestId":"r3","message":{"role":"assistant","content":[{"type":"text","text":"Sure."}],"usage":{"input_tokens":30,"output_tokens":1}}}"#,
+    ];
+    std::fs::write(&path, lines.join("\n")).unwrap();
+
+    let mut reader = SessionReader::open(&path).unwrap();
+    let first = reader.next().unwrap().unwrap();
+    // The header fills in as entries are read.
+    assert_eq!(reader.session().metadata.session_id.as_deref(), Some("s1"));
+    assert_eq!(
+        reader.session().metadata.model.as_deref(),
+        Some("claude-sonnet-4-5-20250929")
+    );
+    assert!(reader.session().turns.is_empty());
+    assert_eq!(first.messages.len(), 4);
+    assert_eq!(first.messages[2].role, Role::Tool);
+    let usage = first.token_usage.as_ref().unwrap();
+    assert_eq!((usage.input, usage.output), (30, 7));
+
+    let second = reader.next().unwrap().unwrap();
+    assert_eq!(second.messages.len(), 2);
+    assert!(reader.next().is_none());
+    assert!(reader.next().is_none());
+
+    let loaded = load(&path);
+    assert_eq!(loaded.turns.len(), 2);
+    assert_eq!(
+        loaded.metadata.session_id,
+        reader.session().metadata.session_id
+    );
+    assert_eq!(
+        serde_json::to_value(&loaded.turns[0]).unwrap(),
+        serde_json::to_value(&first).unwrap()
+    );
+    let header = reader.into_session();
+    assert_eq!(header.subagent_type.as_deref(), Some("interactive"));
+    assert_eq!(header.metadata.provider.as_deref(), Some("anthropic"));
+}
+
+#[test]
+fn open_missing_file_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(SessionReader::open(&dir.path().join("missing.jsonl")).is_err());
+}
@@ -72,27 +72,6 @@ fn is_generated_file(path: &Path) -> bool {
     ) || file_name.ends_with(".lock")
 }
 
-/// Detect programming languages present under `root` (bounded-depth walk).
-///
-/// Used to resolve language-scoped filter aliases (`@rust`, `@python`, …).
-#[cfg(feature = "cli")]
-pub fn detect_project_languages(root: &Path) -> Vec<String> {
-    let mut languages = HashSet::new();
-    let walker = ignore::WalkBuilder::new(root)
-        .max_depth(Some(5))
-        .hidden(false)
-        .git_ignore(true)
-        .build();
-    for entry in walker.flatten() {
-        if let Some(lang) = normalize_languages::support_for_path(entry.path()) {
-            languages.insert(lang.name().to_string());
-        }
-    }
-    let mut result: Vec<_> = languages.into_iter().collect();
-    result.sort();
-    result
-}
-
 /// Build a `Filter` from `--exclude` / `--only` patterns, printing any warnings.
 ///
 /// Returns `None` when both slices are empty (no filtering needed). Aliases are
@@ -119,9 +98,7 @@ pub fn build_filter_with_aliases(
     if exclude.is_empty() && only.is_empty() {
         return None;
     }
-    let languages = detect_project_languages(root);
-    let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
-    match normalize_filter::Filter::new(exclude, only, aliases, &lang_refs) {
+    match normalize_filter::Filter::for_project(exclude, only, aliases, root) {
         Ok(f) => {
             for warning in f.warnings() {
                 eprintln!("warning: {}", warning);
@@ -0,0 +1,126 @@
+//! Concurrency and rate limiting for batched registry queries.
+//!
+//! [`crate::Ecosystem::query_many`] runs lookups on a bounded pool of scoped
+//! threads; every network fetch (batched or not) first passes through the
+//! per-registry [`RateLimiter`], so a large batch cannot hammer one registry.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Maximum number of concurrent lookups in a batch.
+pub const MAX_CONCURRENCY: usize = 8;
+
+/// Minimum spacing between requests to the same registry (10 requests/second).
+pub const REGISTRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Spaces out requests so at most one starts per `interval`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Block until a request slot is available, then claim it.
+    pub fn acquire(&self) {
+        let wait = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = (*next).max(now);
+            *next = slot + self.interval;
+            slot - now
+        };
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+/// Shared rate limiter for a registry, keyed by ecosystem name.
+pub fn registry_limiter(registry: &str) -> Arc<RateLimiter> {
+    static LIMITERS: OnceLock<Mutex<HashMap<String, Arc<RateLimiter>>>> = OnceLock::new();
+    let mut limiters = LIMITERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    limiters
+        .entry(registry.to_string())
+        .or_insert_with(|| Arc::new(RateLimiter::new(REGISTRY_INTERVAL)))
+        .clone()
+}
+
+/// Apply `f` to every item on at most `concurrency` threads, returning results
+/// in input order.
+pub fn run_bounded<T, R, F>(items: &[T], concurrency: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = concurrency.clamp(1, items.len().max(1));
+    if workers == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break };
+                    let result = f(item);
+                    slots.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(result);
+                }
+            });
+        }
+    });
+    slots
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_bounded_preserves_order_and_bound() {
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<usize> = (0..32).collect();
+        let out = run_bounded(&items, 4, |&i| {
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(2));
+            active.fetch_sub(1, Ordering::SeqCst);
+            i * 2
+        });
+        assert_eq!(out, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn test_rate_limiter_spacing() {
+        let limiter = RateLimiter::new(Duration::from_millis(20));
+        let start = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire();
+        }
+        // First slot is immediate; the next three are spaced 20ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+}
@@ -0,0 +1,358 @@
+//! Offline registry bundles for air-gapped machines.
+//!
+//! [`export`] queries every package in a project's dependency set (and its
+//! OSV advisories), then packs the resulting cache entries into a single
+//! `.tar.gz`. [`import`] unpacks such a bundle under
+//! `~/.cache/normalize/bundles/<name>/`, where [`crate::cache`] consults it
+//! whenever the live cache has no fresh entry, so `normalize package info`
+//! answers without touching the network.
+//!
+//! The archive holds a `bundle.json` manifest plus one
+//! `<namespace>/<key>.json` file per cache entry, in the live cache's format.
+
+use crate::lockfile::{Lockfile, LockfileFormat};
+use crate::{Ecosystem, PackageError, PackageQuery, cache};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+/// Bundle layout version written to and required from `bundle.json`.
+const FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest file at the root of a bundle.
+const MANIFEST_FILE: &str = "bundle.json";
+
+/// Contents of a bundle's `bundle.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BundleManifest {
+    /// Bundle layout version.
+    pub format: u32,
+    /// Ecosystem the bundle was exported for (e.g. "cargo", "npm").
+    pub ecosystem: String,
+    /// Unix timestamp of the export.
+    pub created_at: u64,
+    /// Cache keys (`name@version`) of the bundled packages.
+    pub packages: Vec<String>,
+}
+
+/// Result of [`export`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BundleExport {
+    /// The manifest written into the bundle.
+    pub manifest: BundleManifest,
+    /// Number of cache entries packed (package info plus advisories).
+    pub entries: usize,
+    /// Packages whose info could not be fetched, as `spec: error`.
+    pub failed: Vec<String>,
+}
+
+/// Result of [`import`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BundleImport {
+    /// Name the bundle was installed under (the archive's file stem).
+    pub name: String,
+    /// Directory the bundle was unpacked into.
+    pub path: String,
+    /// The bundle's manifest.
+    pub manifest: BundleManifest,
+    /// Number of cache entries unpacked.
+    pub entries: usize,
+}
+
+/// Export the package-info cache for a project's dependency set to `out`.
+///
+/// The dependency set is every package pinned by the ecosystem's lockfile when
+/// one is present, otherwise the manifest's declared dependencies. Each package
+/// is queried (filling the cache from the network where needed) and its
+/// advisories looked up before the entries are packed.
+pub fn export(
+    ecosystem: &dyn Ecosystem,
+    project_root: &Path,
+    out: &Path,
+) -> Result<BundleExport, PackageError> {
+    let specs = dependency_specs(ecosystem, project_root)?;
+    let spec_refs: Vec<&str> = specs.iter().map(String::as_str).collect();
+    let results = ecosystem.query_many(&spec_refs, project_root);
+
+    let mut keys = Vec::new();
+    let mut entries = Vec::new();
+    let mut failed = Vec::new();
+    for (spec, result) in specs.iter().zip(results) {
+        let info = match result {
+            Ok(info) => info,
+            Err(e) => {
+                failed.push(format!("{}: {}", spec, e));
+                continue;
+            }
+        };
+        let key = cache_key(ecosystem, spec, project_root);
+        entries.extend(cache::entry_file(ecosystem.name(), &key));
+        if let Some(osv) = ecosystem.osv_ecosystem()
+            && ecosystem.audit_package(&info.name, &info.version).is_ok()
+        {
+            let osv_key = format!("{}@{}", info.name, info.version);
+            entries.extend(cache::entry_file(&format!("osv-{}", osv), &osv_key));
+        }
+        keys.push(key);
+    }
+
+    let manifest = BundleManifest {
+        format: FORMAT_VERSION,
+        ecosystem: ecosystem.name().to_string(),
+        created_at: now(),
+        packages: keys,
+    };
+    write_bundle(&manifest, &entries, out)?;
+    Ok(BundleExport {
+        manifest,
+        entries: entries.len(),
+        failed,
+    })
+}
+
+/// Import a bundle written by [`export`], replacing any bundle of the same name.
+pub fn import(archive: &Path) -> Result<BundleImport, PackageError> {
+    let name = bundle_name(archive);
+    let bundles = cache::bundles_dir().ok_or_else(|| {
+        PackageError::Io("could not resolve cache directory (no HOME?)".to_string())
+    })?;
+    std::fs::create_dir_all(&bundles)
+        .map_err(|e| PackageError::Io(format!("failed to create cache dir: {}", e)))?;
+
+    // Unpack into a staging dir first so a bad archive never replaces a good bundle.
+    let staging = tempfile::TempDir::new_in(&bundles)
+        .map_err(|e| PackageError::Io(format!("failed to create temp dir: {}", e)))?;
+    let (manifest, entries) = unpack(archive, staging.path())?;
+
+    let target = bundles.join(&name);
+    if target.exists() {
+        std::fs::remove_dir_all(&target).map_err(|e| {
+            PackageError::Io(format!("failed to replace {}: {}", target.display(), e))
+        })?;
+    }
+    std::fs::rename(staging.keep(), &target)
+        .map_err(|e| PackageError::Io(format!("failed to move bundle into place: {}", e)))?;
+    Ok(BundleImport {
+        name,
+        path: target.display().to_string(),
+        manifest,
+        entries,
+    })
+}
+
+/// Query specs for the project's dependency set: `name@version` for each
+/// locked package, else the bare names of declared dependencies.
+fn dependency_specs(
+    ecosystem: &dyn Ecosystem,
+    project_root: &Path,
+) -> Result<Vec<String>, PackageError> {
+    let lockfile = ecosystem
+        .lockfiles()
+        .iter()
+        .map(|l| project_root.join(l.filename))
+        .filter(|p| p.is_file())
+        .find(|p| LockfileFormat::from_path(p).is_some_and(|f| f.ecosystem() == ecosystem.name()));
+    if let Some(path) = lockfile {
+        let lockfile = Lockfile::from_path(&path)?;
+        return Ok(lockfile
+            .packages
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect());
+    }
+    let mut names: Vec<String> = ecosystem
+        .list_dependencies(project_root)?
+        .iter()
+        .map(|d| d.effective_name().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// The cache key [`Ecosystem::query`] uses for `spec`.
+fn cache_key(ecosystem: &dyn Ecosystem, spec: &str, project_root: &Path) -> String {
+    let mut query = PackageQuery::parse(spec);
+    if query.version.is_none() {
+        query.version = ecosystem.installed_version(&query.name, project_root);
+    }
+    query.cache_key()
+}
+
+/// Bundle name for an archive path: its file name without `.tar.gz`/`.tgz`.
+fn bundle_name(archive: &Path) -> String {
+    let file = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("bundle");
+    let stem = file
+        .strip_suffix(".tar.gz")
+        .or_else(|| file.strip_suffix(".tgz"))
+        .unwrap_or(file);
+    stem.replace(['/', '\\', ':'], "_")
+}
+
+/// Write `manifest` and the cache entry files to a gzip-compressed tarball.
+///
+/// `entries` pairs each in-archive path (`<namespace>/<file>.json`) with the
+/// cache file to copy.
+fn write_bundle(
+    manifest: &BundleManifest,
+    entries: &[(String, PathBuf)],
+    out: &Path,
+) -> Result<(), PackageError> {
+    let io_err =
+        |e: std::io::Error| PackageError::Io(format!("failed to write {}: {}", out.display(), e));
+    let file = std::fs::File::create(out).map_err(io_err)?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| PackageError::ParseError(format!("failed to encode manifest: {}", e)))?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_FILE, json.as_slice())
+        .map_err(io_err)?;
+
+    for (name, path) in entries {
+        tar.append_path_with_name(path, name).map_err(io_err)?;
+    }
+    tar.into_inner()
+        .and_then(|gz| gz.finish())
+        .map_err(io_err)?;
+    Ok(())
+}
+
+/// Unpack a bundle into `dest`, returning its manifest and entry count.
+///
+/// Only `<namespace>/<file>.json` entries are extracted; anything else
+/// (absolute paths, `..`, deeper nesting) is rejected.
+fn unpack(archive: &Path, dest: &Path) -> Result<(BundleManifest, usize), PackageError> {
+    let file = std::fs::File::open(archive)
+        .map_err(|e| PackageError::Io(format!("failed to read {}: {}", archive.display(), e)))?;
+    let invalid = |e: std::io::Error| PackageError::ParseError(format!("invalid bundle: {}", e));
+    std::fs::create_dir_all(dest)
+        .map_err(|e| PackageError::Io(format!("failed to create {}: {}", dest.display(), e)))?;
+
+    let mut manifest = None;
+    let mut entries = 0;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    for entry in tar.entries().map_err(invalid)? {
+        let mut entry = entry.map_err(invalid)?;
+        let path = entry.path().map_err(invalid)?.into_owned();
+        if path == Path::new(MANIFEST_FILE) {
+            let parsed: BundleManifest = serde_json::from_reader(&mut entry)
+                .map_err(|e| PackageError::ParseError(format!("invalid bundle manifest: {}", e)))?;
+            if parsed.format != FORMAT_VERSION {
+                return Err(PackageError::ParseError(format!(
+                    "unsupported bundle format {} (expected {})",
+                    parsed.format, FORMAT_VERSION
+                )));
+            }
+            manifest = Some(parsed);
+        } else if is_entry_path(&path) {
+            entry.unpack_in(dest).map_err(invalid)?;
+            entries += 1;
+        } else {
+            return Err(PackageError::ParseError(format!(
+                "invalid bundle: unexpected entry {}",
+                path.display()
+            )));
+        }
+    }
+    let manifest = manifest.ok_or_else(|| {
+        PackageError::ParseError(format!("invalid bundle: missing {}", MANIFEST_FILE))
+    })?;
+    Ok((manifest, entries))
+}
+
+/// Whether `path` has the `<namespace>/<file>.json` shape of a cache entry.
+fn is_entry_path(path: &Path) -> bool {
+    let components: Vec<_> = path.components().collect();
+    components.len() == 2
+        && components.iter().all(|c| matches!(c, Component::Normal(_)))
+        && path.extension().is_some_and(|e| e == "json")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> BundleManifest {
+        BundleManifest {
+            format: FORMAT_VERSION,
+            ecosystem: "cargo".to_string(),
+            created_at: 1_700_000_000,
+            packages: vec!["serde@1.0.200".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("serde@1.0.200.json");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(&entry, r#"{"info":{},"cached_at":1}"#).unwrap();
+
+        let archive = dir.path().join("deps.tar.gz");
+        let entries = [("cargo/serde@1.0.200.json".to_string(), entry)];
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        write_bundle(&manifest(), &entries, &archive).unwrap();
+
+        let dest = dir.path().join("out");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let (manifest, count) = unpack(&archive, &dest).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(manifest.ecosystem, "cargo");
+        assert_eq!(manifest.packages, ["serde@1.0.200"]);
+        assert_eq!(
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::read_to_string(dest.join("cargo/serde@1.0.200.json")).unwrap(),
+            r#"{"info":{},"cached_at":1}"#
+        );
+    }
+
+    #[test]
+    fn test_unpack_requires_manifest() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("empty.tar.gz");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let file = std::fs::File::create(&archive).unwrap();
+        let tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        tar.into_inner().unwrap().finish().unwrap();
+
+        assert!(unpack(&archive, &dir.path().join("out")).is_err());
+    }
+
+    #[test]
+    fn test_is_entry_path() {
+        assert!(is_entry_path(Path::new("cargo/serde@1.0.200.json")));
+        assert!(is_entry_path(Path::new("osv-crates.io/serde@1.0.200.json")));
+        assert!(!is_entry_path(Path::new("../cargo/serde.json")));
+        assert!(!is_entry_path(Path::new("/etc/passwd.json")));
+        assert!(!is_entry_path(Path::new("cargo/nested/serde.json")));
+        assert!(!is_entry_path(Path::new("cargo/serde.txt")));
+    }
+
+    #[test]
+    fn test_bundle_name() {
+        assert_eq!(bundle_name(Path::new("/tmp/deps.tar.gz")), "deps");
+        assert_eq!(bundle_name(Path::new("ci.tgz")), "ci");
+    }
+}
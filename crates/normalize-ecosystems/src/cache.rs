@@ -1,29 +1,32 @@
-//! Local cache for package info (offline support).
+//! Local cache for registry responses (offline support).
+//!
+//! Entries are JSON files keyed by namespace (ecosystem name, or `osv-<ecosystem>`
+//! for advisories) and key (`package@version`).
+//!
+//! Bundles imported with [`crate::bundle::import`] live under
+//! `~/.cache/normalize/bundles/<name>/` in the same layout. They are pinned
+//! snapshots for offline machines: only the offline path ([`read_any`], used
+//! after a failed fetch) consults them, and only entries younger than
+//! [`BUNDLE_MAX_AGE`], so an old bundle never shadows a live refetch.
 
-use crate::PackageInfo;
-use std::fs;
-use std::path::PathBuf;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
+/// Oldest bundled entry [`read_any`] will still return.
+pub const BUNDLE_MAX_AGE: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
 /// Cache entry with timestamp.
 #[derive(serde::Serialize, serde::Deserialize)]
-struct CacheEntry {
-    info: PackageInfo,
+struct CacheEntry<T> {
+    info: T,
     cached_at: u64, // Unix timestamp
 }
 
 /// Get base cache directory: ~/.cache/normalize
 pub(crate) fn cache_base() -> Option<PathBuf> {
-    let base = if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
-        PathBuf::from(cache)
-    } else if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home).join(".cache")
-    } else if let Ok(home) = std::env::var("USERPROFILE") {
-        PathBuf::from(home).join(".cache")
-    } else {
-        return None;
-    };
-    Some(base.join("normalize"))
+    normalize_cache::cache_root()
 }
 
 /// Get cache directory: ~/.cache/normalize/packages
@@ -31,63 +34,140 @@ fn cache_dir() -> Option<PathBuf> {
     Some(cache_base()?.join("packages"))
 }
 
-/// Get cache file path for a package.
-fn cache_path(ecosystem: &str, package: &str) -> Option<PathBuf> {
-    let dir = cache_dir()?;
+/// Get directory holding imported bundles: ~/.cache/normalize/bundles
+pub(crate) fn bundles_dir() -> Option<PathBuf> {
+    Some(cache_base()?.join("bundles"))
+}
+
+/// Relative path of an entry within a cache or bundle directory.
+fn entry_name(ecosystem: &str, package: &str) -> String {
     // Sanitize package name for filesystem
     let safe_name = package.replace(['/', ':'], "_");
-    Some(dir.join(ecosystem).join(format!("{}.json", safe_name)))
+    format!("{}/{}.json", ecosystem, safe_name)
+}
+
+/// Get cache file path for a package.
+fn cache_path(ecosystem: &str, package: &str) -> Option<PathBuf> {
+    Some(cache_dir()?.join(entry_name(ecosystem, package)))
 }
 
-/// Read from cache if exists and not expired.
-pub fn read(ecosystem: &str, package: &str, max_age: Duration) -> Option<PackageInfo> {
+/// An existing live cache entry as (relative name, file path), for bundling.
+pub(crate) fn entry_file(ecosystem: &str, package: &str) -> Option<(String, PathBuf)> {
     let path = cache_path(ecosystem, package)?;
-    let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    path.is_file()
+        .then(|| (entry_name(ecosystem, package), path))
+}
 
-    // Check expiry
-    let now = SystemTime::now()
+/// Whether an entry cached at `cached_at` is at most `max_age` old.
+fn within(cached_at: u64, max_age: Duration) -> bool {
+    SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
-        .ok()?
-        .as_secs();
+        .is_ok_and(|now| now.as_secs().saturating_sub(cached_at) <= max_age.as_secs())
+}
 
-    if now - entry.cached_at > max_age.as_secs() {
-        return None; // Expired
-    }
+/// Read from the live cache if the entry exists and is not expired.
+pub fn read<T: DeserializeOwned>(ecosystem: &str, package: &str, max_age: Duration) -> Option<T> {
+    read_entry(ecosystem, package)
+        .filter(|entry: &CacheEntry<T>| within(entry.cached_at, max_age))
+        .map(|entry| entry.info)
+}
+
+/// Read from the live cache regardless of age, else from an imported bundle
+/// no older than [`BUNDLE_MAX_AGE`] (for offline fallback).
+pub fn read_any<T: DeserializeOwned>(ecosystem: &str, package: &str) -> Option<T> {
+    read_entry(ecosystem, package)
+        .map(|entry: CacheEntry<T>| entry.info)
+        .or_else(|| read_bundled(ecosystem, package))
+}
 
-    Some(entry.info)
+/// Read an entry from the imported bundles, in bundle name order.
+fn read_bundled<T: DeserializeOwned>(ecosystem: &str, package: &str) -> Option<T> {
+    read_bundled_in(&bundles_dir()?, ecosystem, package, BUNDLE_MAX_AGE)
+}
+
+fn read_bundled_in<T: DeserializeOwned>(
+    bundles: &Path,
+    ecosystem: &str,
+    package: &str,
+    max_age: Duration,
+) -> Option<T> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(bundles)
+        .ok()?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    let name = entry_name(ecosystem, package);
+    dirs.iter().find_map(|dir| {
+        let bytes = std::fs::read(dir.join(&name)).ok()?;
+        serde_json::from_slice::<CacheEntry<T>>(&bytes)
+            .ok()
+            .filter(|entry| within(entry.cached_at, max_age))
+            .map(|entry| entry.info)
+    })
 }
 
-/// Read from cache regardless of age (for offline fallback).
-pub fn read_any(ecosystem: &str, package: &str) -> Option<PackageInfo> {
+/// Read an entry, discarding it if it no longer parses.
+fn read_entry<T: DeserializeOwned>(ecosystem: &str, package: &str) -> Option<CacheEntry<T>> {
     let path = cache_path(ecosystem, package)?;
-    let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
-    Some(entry.info)
+    normalize_cache::read_or_discard(&path, |bytes| serde_json::from_slice(bytes).ok())
 }
 
-/// Write to cache.
-pub fn write(ecosystem: &str, package: &str, info: &PackageInfo) {
+/// Write to cache atomically.
+pub fn write<T: Serialize>(ecosystem: &str, package: &str, info: &T) {
     let Some(path) = cache_path(ecosystem, package) else {
         return;
     };
 
-    // Create directory if needed
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0);
 
     let entry = CacheEntry {
-        info: info.clone(),
+        info,
         cached_at: now,
     };
 
+    // Atomic replace, so concurrent writers (batched queries, parallel CLI
+    // invocations) never leave a torn entry for readers.
     if let Ok(json) = serde_json::to_string(&entry) {
-        let _ = fs::write(&path, json);
+        let _ = normalize_cache::write_atomic(&path, json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_bundled_in() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let bundles = tempfile::tempdir().unwrap();
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let write = |bundle: &str, info: &str, cached_at: u64| {
+            let path = bundles
+                .path()
+                .join(bundle)
+                .join(entry_name("npm", "@scope/pkg@1.0.0"));
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let entry = CacheEntry { info, cached_at };
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::write(path, serde_json::to_vec(&entry).unwrap()).unwrap();
+        };
+        write("c-ci", "third", now);
+        write("b-ci", "second", now);
+        write("a-ci", "stale", 0);
+
+        // Bundles are consulted in name order, skipping entries past the age limit.
+        let read = |package: &str| -> Option<String> {
+            read_bundled_in(bundles.path(), "npm", package, BUNDLE_MAX_AGE)
+        };
+        assert_eq!(read("@scope/pkg@1.0.0").as_deref(), Some("second"));
+        assert!(read("other@1.0.0").is_none());
     }
 }
@@ -15,6 +15,14 @@ impl Ecosystem for Cargo {
         "cargo"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("crates.io")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("cargo")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["Cargo.toml"]
     }
@@ -34,6 +42,14 @@ impl Ecosystem for Cargo {
         fetch_crates_io_info(query)
     }
 
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_crates_io_info(query))
+    }
+
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
         let lockfile = project_root.join("Cargo.lock");
         let content = std::fs::read_to_string(lockfile).ok()?;
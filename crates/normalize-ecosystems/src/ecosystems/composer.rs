@@ -13,6 +13,14 @@ impl Ecosystem for Composer {
         "composer"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("Packagist")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("composer")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["composer.json"]
     }
@@ -17,6 +17,10 @@ impl Ecosystem for Conan {
         &["conanfile.txt", "conanfile.py"]
     }
 
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("conan")
+    }
+
     fn lockfiles(&self) -> &'static [LockfileManager] {
         &[LockfileManager {
             filename: "conan.lock",
@@ -14,6 +14,14 @@ impl Ecosystem for Gem {
         "gem"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("RubyGems")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("gem")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["Gemfile", "*.gemspec"]
     }
@@ -15,6 +15,14 @@ impl Ecosystem for Go {
         "go"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("Go")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("golang")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["go.mod"]
     }
@@ -27,11 +35,19 @@ impl Ecosystem for Go {
     }
 
     fn tools(&self) -> &'static [&'static str] {
-        &["curl"] // Uses Go module proxy API
+        &["go"]
     }
 
     fn fetch_info(&self, query: &PackageQuery, _tool: &str) -> Result<PackageInfo, PackageError> {
-        fetch_go_proxy_info(&query.name)
+        fetch_go_list_info(query)
+    }
+
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_go_proxy_info(&query.name, query.version.as_deref()))
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
@@ -261,9 +277,14 @@ impl Ecosystem for Go {
     }
 }
 
-fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
-    // Get latest version from proxy
-    let url = format!("https://proxy.golang.org/{}/@latest", package);
+/// Query the module proxy: `/@latest` or `/@v/<version>.info`.
+#[cfg(feature = "registry-http")]
+fn fetch_go_proxy_info(package: &str, version: Option<&str>) -> Result<PackageInfo, PackageError> {
+    let path = escape_module_path(package);
+    let url = match version {
+        Some(v) => format!("https://proxy.golang.org/{}/@v/{}.info", path, v),
+        None => format!("https://proxy.golang.org/{}/@latest", path),
+    };
     let body = crate::http::get(&url)?;
     let v: serde_json::Value = serde_json::from_str(&body)
         .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
@@ -271,8 +292,57 @@ fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
     let version = v
         .get("Version")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| PackageError::ParseError("missing Version".to_string()))?
-        .to_string();
+        .ok_or_else(|| PackageError::ParseError("missing Version".to_string()))?;
+    Ok(go_module_info(package, version))
+}
+
+/// Resolve a module with `go list -m -json <module>@<version|latest>`.
+///
+/// Works through whatever GOPROXY/GOPRIVATE configuration the local toolchain
+/// has, so private modules the public proxy can't see still resolve.
+fn fetch_go_list_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
+    let target = format!(
+        "{}@{}",
+        query.name,
+        query.version.as_deref().unwrap_or("latest")
+    );
+    let output = Command::new("go")
+        .args(["list", "-m", "-json", &target])
+        .output()
+        .map_err(|e| PackageError::ToolFailed(format!("failed to run go: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not found") || stderr.contains("no matching versions") {
+            return Err(PackageError::NotFound(query.name.clone()));
+        }
+        return Err(PackageError::ToolFailed(stderr.trim().to_string()));
+    }
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+    let version = v
+        .get("Version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| PackageError::ParseError("missing Version".to_string()))?;
+    Ok(go_module_info(&query.name, version))
+}
+
+/// Case-encode a module path for the proxy protocol (uppercase `X` → `!x`).
+#[cfg(feature = "registry-http")]
+fn escape_module_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('!');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn go_module_info(package: &str, version: &str) -> PackageInfo {
+    let version = version.to_string();
 
     // Try to get more info from pkg.go.dev (optional, may fail)
     let repository = if package.starts_with("github.com/") {
@@ -286,7 +356,7 @@ fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
         None
     };
 
-    Ok(PackageInfo {
+    PackageInfo {
         name: package.to_string(),
         version,
         description: None, // Go proxy doesn't provide description
@@ -295,7 +365,7 @@ fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
         repository,
         features: Vec::new(),
         dependencies: Vec::new(), // Would need to parse go.mod
-    })
+    }
 }
 
 #[cfg(test)]
@@ -308,4 +378,14 @@ mod tests {
         assert_eq!(eco.name(), "go");
         assert_eq!(eco.manifest_files(), &["go.mod"]);
     }
+
+    #[test]
+    #[cfg(feature = "registry-http")]
+    fn test_escape_module_path() {
+        assert_eq!(
+            escape_module_path("github.com/BurntSushi/toml"),
+            "github.com/!burnt!sushi/toml"
+        );
+        assert_eq!(escape_module_path("golang.org/x/net"), "golang.org/x/net");
+    }
 }
@@ -13,6 +13,14 @@ impl Ecosystem for Hex {
         "hex"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("Hex")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("hex")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["mix.exs"]
     }
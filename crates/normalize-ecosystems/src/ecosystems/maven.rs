@@ -1,10 +1,19 @@
 //! Maven (Java) ecosystem.
+//!
+//! Covers Maven (`pom.xml`) and Gradle (`build.gradle`, `build.gradle.kts`)
+//! projects. Package metadata comes from Maven Central: the search API
+//! resolves the version, and the artifact's published POM supplies the
+//! description, licenses, SCM URL and dependencies. Dependency trees come
+//! from `gradle.lockfile` or the build manifest; `gradle dependencies` (which
+//! runs the project's build scripts) is only used when neither lists anything.
 
 use crate::{
     AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    PackageQuery, SpdxExpr, TreeNode,
 };
+use std::collections::HashMap;
 use std::path::Path;
+use std::process::Command;
 
 pub struct Maven;
 
@@ -13,6 +22,14 @@ impl Ecosystem for Maven {
         "maven"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("Maven")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("maven")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["pom.xml", "build.gradle", "build.gradle.kts"]
     }
@@ -31,11 +48,32 @@ impl Ecosystem for Maven {
     }
 
     fn tools(&self) -> &'static [&'static str] {
-        &["curl"] // Uses Maven Central API
+        &["mvn", "gradle"] // Metadata comes from the Maven Central API either way
     }
 
     fn fetch_info(&self, query: &PackageQuery, _tool: &str) -> Result<PackageInfo, PackageError> {
-        fetch_maven_info(&query.name)
+        fetch_maven_info(&query.name, query.version.as_deref())
+    }
+
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_maven_info(&query.name, query.version.as_deref()))
+    }
+
+    fn fetch(
+        &self,
+        query: &PackageQuery,
+        project_root: &Path,
+    ) -> Result<PackageInfo, PackageError> {
+        match fetch_maven_info(&query.name, query.version.as_deref()) {
+            Ok(info) => Ok(info),
+            Err(e @ PackageError::NotFound(_)) => Err(e),
+            // Maven Central unreachable: use what the project resolved.
+            Err(e) => local_package_info(self, &query.name, project_root).ok_or(e),
+        }
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
@@ -117,98 +155,266 @@ impl Ecosystem for Maven {
             });
         }
 
-        // Fall back to listing direct deps from manifest
-        let manifest_deps = self.list_dependencies(project_root)?;
-        let deps: Vec<TreeNode> = manifest_deps
-            .into_iter()
-            .map(|dep| TreeNode {
-                name: dep.name,
-                version: dep.version_req.unwrap_or_default(),
-                dependencies: Vec::new(),
-            })
-            .collect();
+        // Then the manifest's direct dependencies
+        let manifest_deps = self.list_dependencies(project_root);
+        if let Ok(deps) = &manifest_deps
+            && !deps.is_empty()
+        {
+            let deps = deps
+                .iter()
+                .map(|dep| TreeNode {
+                    name: dep.name.clone(),
+                    version: dep.version_req.clone().unwrap_or_default(),
+                    dependencies: Vec::new(),
+                })
+                .collect();
+            return Ok(DependencyTree {
+                roots: vec![TreeNode {
+                    name: "dependencies".to_string(),
+                    version: String::new(),
+                    dependencies: deps,
+                }],
+            });
+        }
 
-        Ok(DependencyTree {
+        // Nothing declared that we can parse (version catalogs, plugins adding
+        // dependencies): ask Gradle for the resolved runtime classpath.
+        if let Ok(deps) = gradle_dependency_tree(project_root) {
+            return Ok(DependencyTree {
+                roots: vec![TreeNode {
+                    name: "runtimeClasspath".to_string(),
+                    version: String::new(),
+                    dependencies: deps,
+                }],
+            });
+        }
+
+        manifest_deps.map(|_| DependencyTree {
             roots: vec![TreeNode {
                 name: "dependencies".to_string(),
                 version: String::new(),
-                dependencies: deps,
+                dependencies: Vec::new(),
             }],
         })
     }
 
-    fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
-        Err(PackageError::ToolFailed(
-            "audit not yet supported for Maven. Use OWASP dependency-check or Snyk".to_string(),
-        ))
+    fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
+        crate::osv::audit_declared(self, project_root)
     }
 }
 
 fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError> {
-    let mut deps = Vec::new();
+    let pom = Pom::parse(content)?;
+    Ok(pom
+        .dependencies
+        .into_iter()
+        .map(|dep| {
+            // Like Gradle's testImplementation, test/provided deps are optional
+            let optional =
+                dep.optional || matches!(dep.scope.as_deref(), Some("test" | "provided"));
+            Dependency::registry(dep.coordinate(), dep.version, optional)
+        })
+        .collect())
+}
 
-    // Simple XML parsing: <dependency><groupId>...</groupId><artifactId>...</artifactId><version>...</version></dependency>
-    let mut in_dependency = false;
-    let mut group_id = String::new();
-    let mut artifact_id = String::new();
-    let mut version = String::new();
-    let mut optional = false;
+/// The parts of a `pom.xml` used for package info and dependency listing.
+#[derive(Debug, Default)]
+struct Pom {
+    description: Option<String>,
+    url: Option<String>,
+    scm_url: Option<String>,
+    /// `<licenses>` names, in declaration order.
+    licenses: Vec<(String, Option<String>)>,
+    /// Direct `<dependencies>` (not `<dependencyManagement>` or plugin deps).
+    dependencies: Vec<PomDependency>,
+}
 
-    for line in content.lines() {
-        let line = line.trim();
+#[derive(Debug)]
+struct PomDependency {
+    group_id: String,
+    artifact_id: String,
+    /// `None` when managed by a parent or an unresolved `${property}`.
+    version: Option<String>,
+    scope: Option<String>,
+    optional: bool,
+}
 
-        if line.contains("<dependency>") {
-            in_dependency = true;
-            group_id.clear();
-            artifact_id.clear();
-            version.clear();
-            optional = false;
-        } else if line.contains("</dependency>") {
-            if in_dependency && !artifact_id.is_empty() {
-                deps.push(Dependency::registry(
-                    if group_id.is_empty() {
-                        artifact_id.clone()
-                    } else {
-                        format!("{}:{}", group_id, artifact_id)
-                    },
-                    if version.is_empty() {
-                        None
-                    } else {
-                        Some(version.clone())
-                    },
-                    optional,
-                ));
-            }
-            in_dependency = false;
-        } else if in_dependency {
-            if let Some(val) = extract_xml_value(line, "groupId") {
-                group_id = val;
-            } else if let Some(val) = extract_xml_value(line, "artifactId") {
-                artifact_id = val;
-            } else if let Some(val) = extract_xml_value(line, "version") {
-                version = val;
-            } else if line.contains("<optional>true</optional>") {
-                optional = true;
+impl PomDependency {
+    fn coordinate(&self) -> String {
+        if self.group_id.is_empty() {
+            self.artifact_id.clone()
+        } else {
+            format!("{}:{}", self.group_id, self.artifact_id)
+        }
+    }
+}
+
+impl Pom {
+    fn parse(content: &str) -> Result<Self, PackageError> {
+        let doc = roxmltree::Document::parse(content)
+            .map_err(|e| PackageError::ParseError(format!("invalid pom.xml: {}", e)))?;
+        let project = doc.root_element();
+
+        // `${...}` references resolve against <properties> and the project's
+        // own coordinates, which may be inherited from <parent>.
+        let parent = xml_child(project, "parent");
+        let mut properties: HashMap<String, String> = xml_child(project, "properties")
+            .map(|props| {
+                props
+                    .children()
+                    .filter(|n| n.is_element())
+                    .map(|n| (n.tag_name().name().to_string(), xml_node_text(n)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for field in ["version", "groupId", "artifactId"] {
+            let value = xml_child_text(project, field)
+                .or_else(|| parent.and_then(|p| xml_child_text(p, field)));
+            if let Some(value) = value {
+                properties.insert(format!("project.{}", field), value.clone());
+                properties.insert(format!("pom.{}", field), value);
             }
         }
+        let resolve =
+            |value: Option<String>| value.and_then(|v| resolve_properties(&v, &properties));
+
+        let licenses = xml_child(project, "licenses")
+            .map(|licenses| {
+                xml_children(licenses, "license")
+                    .filter_map(|l| Some((xml_child_text(l, "name")?, xml_child_text(l, "url"))))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let dependencies = xml_child(project, "dependencies")
+            .map(|deps| {
+                xml_children(deps, "dependency")
+                    .filter_map(|dep| {
+                        Some(PomDependency {
+                            group_id: resolve(xml_child_text(dep, "groupId")).unwrap_or_default(),
+                            artifact_id: resolve(xml_child_text(dep, "artifactId"))?,
+                            version: resolve(xml_child_text(dep, "version")),
+                            scope: xml_child_text(dep, "scope"),
+                            optional: xml_child_text(dep, "optional").as_deref() == Some("true"),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Pom {
+            description: xml_child_text(project, "description")
+                .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" ")),
+            url: resolve(xml_child_text(project, "url")),
+            scm_url: xml_child(project, "scm").and_then(|scm| resolve(xml_child_text(scm, "url"))),
+            licenses,
+            dependencies,
+        })
     }
 
-    Ok(deps)
+    /// The declared licenses as one SPDX expression; several `<license>`
+    /// entries mean the artifact is offered under any of them.
+    fn license_expression(&self) -> Option<String> {
+        self.licenses
+            .iter()
+            .map(|(name, url)| spdx_license(name, url.as_deref()))
+            .reduce(|a, b| SpdxExpr::Or(Box::new(a), Box::new(b)))
+            .map(|expr| expr.to_string())
+    }
 }
 
-fn extract_xml_value(line: &str, tag: &str) -> Option<String> {
-    let start_tag = format!("<{}>", tag);
-    let end_tag = format!("</{}>", tag);
+fn xml_children<'a, 'i>(
+    node: roxmltree::Node<'a, 'i>,
+    name: &'a str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'i>> {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
 
-    if let Some(start) = line.find(&start_tag) {
-        let content_start = start + start_tag.len();
-        if let Some(end) = line.find(&end_tag) {
-            return Some(line[content_start..end].to_string());
-        }
+fn xml_child<'a, 'i>(node: roxmltree::Node<'a, 'i>, name: &str) -> Option<roxmltree::Node<'a, 'i>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn xml_node_text(node: roxmltree::Node) -> String {
+    node.text().unwrap_or("").trim().to_string()
+}
+
+fn xml_child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    xml_child(node, name)
+        .map(xml_node_text)
+        .filter(|t| !t.is_empty())
+}
+
+/// Substitute `${name}` references, including properties defined in terms
+/// of other properties; `None` if any stays unresolved.
+fn resolve_properties(value: &str, properties: &HashMap<String, String>) -> Option<String> {
+    let mut value = value.to_string();
+    // Bounded, so `<a>${a}</a>` can't loop forever
+    for _ in 0..8 {
+        let Some(start) = value.find("${") else {
+            return Some(value);
+        };
+        let end = value[start..].find('}')? + start;
+        let resolved = properties.get(&value[start + 2..end])?;
+        value.replace_range(start..=end, resolved);
     }
     None
 }
 
+/// Map a POM `<license>` name (or URL) to an SPDX expression. POMs use free
+/// text ("The Apache Software License, Version 2.0") or, more recently, SPDX
+/// expressions; names that are neither are kept as a single license id.
+fn spdx_license(name: &str, url: Option<&str>) -> SpdxExpr {
+    // Compound expressions pass through; a single name ("BSD") may still need mapping.
+    if let Ok(expr) = SpdxExpr::parse(name)
+        && !matches!(expr, SpdxExpr::License { .. })
+    {
+        return expr;
+    }
+    let expr = known_license(name, url).unwrap_or(name);
+    SpdxExpr::parse(expr).unwrap_or_else(|_| SpdxExpr::License {
+        id: name.to_string(),
+        or_later: false,
+        exception: None,
+    })
+}
+
+/// SPDX expression for a well-known free-text license name or URL.
+fn known_license(name: &str, url: Option<&str>) -> Option<&'static str> {
+    let lower = name.to_lowercase();
+    let url = url.unwrap_or("").to_lowercase();
+    let has = |needle: &str| lower.contains(needle);
+    let id = if (has("apache") && has("2")) || url.contains("apache.org/licenses/license-2.0") {
+        "Apache-2.0"
+    } else if lower == "mit" || has("mit license") || url.contains("opensource.org/licenses/mit") {
+        "MIT"
+    } else if has("eclipse public license") || has("epl-") || has("epl ") {
+        if has("2.0") { "EPL-2.0" } else { "EPL-1.0" }
+    } else if has("bsd") {
+        if has("2-clause") || has("simplified") {
+            "BSD-2-Clause"
+        } else {
+            "BSD-3-Clause"
+        }
+    } else if has("classpath") {
+        "GPL-2.0-only WITH Classpath-exception-2.0"
+    } else if has("lesser") || has("lgpl") {
+        if has("3") {
+            "LGPL-3.0-only"
+        } else {
+            "LGPL-2.1-only"
+        }
+    } else if has("cddl") || has("common development and distribution") {
+        if has("1.1") { "CDDL-1.1" } else { "CDDL-1.0" }
+    } else if has("mozilla public license") || has("mpl-2") || has("mpl 2") {
+        "MPL-2.0"
+    } else {
+        return None;
+    };
+    Some(id)
+}
+
 fn parse_gradle_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError> {
     let mut deps = Vec::new();
 
@@ -253,7 +459,7 @@ fn parse_gradle_dependencies(content: &str) -> Result<Vec<Dependency>, PackageEr
     Ok(deps)
 }
 
-fn fetch_maven_info(package: &str) -> Result<PackageInfo, PackageError> {
+fn fetch_maven_info(package: &str, version: Option<&str>) -> Result<PackageInfo, PackageError> {
     // Package format: groupId:artifactId or groupId:artifactId:version
     let parts: Vec<&str> = package.split(':').collect();
     let (group_id, artifact_id) = match parts.len() {
@@ -265,14 +471,53 @@ fn fetch_maven_info(package: &str) -> Result<PackageInfo, PackageError> {
         _ => (parts[0], parts[1]),
     };
 
-    // Query Maven Central API
-    let url = format!(
-        "https://search.maven.org/solrsearch/select?q=g:{}+AND+a:{}&rows=1&wt=json",
-        group_id, artifact_id
-    );
+    // Query Maven Central API (the `gav` core indexes individual versions)
+    let url = match version {
+        Some(v) => format!(
+            "https://search.maven.org/solrsearch/select?q=g:{}+AND+a:{}+AND+v:{}&core=gav&rows=1&wt=json",
+            group_id, artifact_id, v
+        ),
+        None => format!(
+            "https://search.maven.org/solrsearch/select?q=g:{}+AND+a:{}&rows=1&wt=json",
+            group_id, artifact_id
+        ),
+    };
 
     let body = crate::http::get(&url)?;
-    parse_maven_response(&body, package)
+    let mut info = parse_maven_response(&body, package)?;
+
+    // The search index has no license or dependency data; the POM does.
+    // A missing POM (relocated or non-jar artifacts) keeps the search result.
+    let pom_url = format!(
+        "https://repo1.maven.org/maven2/{}/{}/{}/{}-{}.pom",
+        group_id.replace('.', "/"),
+        artifact_id,
+        info.version,
+        artifact_id,
+        info.version
+    );
+    if let Ok(pom) = crate::http::get(&pom_url).and_then(|xml| Pom::parse(&xml)) {
+        apply_pom(&mut info, pom);
+    }
+    Ok(info)
+}
+
+/// Fill in what the search API lacks from the artifact's published POM.
+/// Test, provided and system dependencies don't reach consumers, so they
+/// are left out.
+fn apply_pom(info: &mut PackageInfo, pom: Pom) {
+    info.license = pom.license_expression();
+    info.description = pom.description.clone();
+    info.repository = pom.scm_url.clone();
+    if let Some(url) = pom.url.clone() {
+        info.homepage = Some(url);
+    }
+    info.dependencies = pom
+        .dependencies
+        .into_iter()
+        .filter(|dep| !matches!(dep.scope.as_deref(), Some("test" | "provided" | "system")))
+        .map(|dep| Dependency::registry(dep.coordinate(), dep.version.clone(), dep.optional))
+        .collect();
 }
 
 fn search_maven_central(query: &str) -> Result<PackageInfo, PackageError> {
@@ -304,8 +549,10 @@ fn parse_maven_response(json: &str, package: &str) -> Result<PackageInfo, Packag
 
     let name = format!("{}:{}", group_id, artifact_id);
 
+    // Artifact searches report `latestVersion`; `core=gav` searches report `v`.
     let version = doc
         .get("latestVersion")
+        .or_else(|| doc.get("v"))
         .and_then(|v| v.as_str())
         .ok_or_else(|| PackageError::ParseError("missing latestVersion".to_string()))?
         .to_string();
@@ -326,3 +573,317 @@ fn parse_maven_response(json: &str, package: &str) -> Result<PackageInfo, Packag
         dependencies: Vec::new(),
     })
 }
+
+/// Resolve the runtime classpath with `gradle dependencies`, preferring the
+/// project's `gradlew` wrapper.
+fn gradle_dependency_tree(project_root: &Path) -> Result<Vec<TreeNode>, PackageError> {
+    if !["build.gradle", "build.gradle.kts"]
+        .iter()
+        .any(|f| project_root.join(f).exists())
+    {
+        return Err(PackageError::ParseError("no Gradle build".to_string()));
+    }
+
+    let wrapper = project_root.join(if cfg!(windows) {
+        "gradlew.bat"
+    } else {
+        "gradlew"
+    });
+    let program = if wrapper.exists() {
+        wrapper.into_os_string()
+    } else {
+        "gradle".into()
+    };
+    let output = Command::new(program)
+        .args([
+            "dependencies",
+            "--configuration",
+            "runtimeClasspath",
+            "--quiet",
+            "--console=plain",
+        ])
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| PackageError::ToolFailed(format!("gradle: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(PackageError::ToolFailed(format!(
+            "gradle dependencies: {}",
+            stderr
+                .lines()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or("failed")
+        )));
+    }
+
+    Ok(parse_gradle_tree(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Package info for `package` as the project resolves it (see
+/// [`Maven::dependency_tree`]): its version and direct dependencies, without
+/// registry metadata.
+fn local_package_info(maven: &Maven, package: &str, project_root: &Path) -> Option<PackageInfo> {
+    fn find<'a>(nodes: &'a [TreeNode], package: &str) -> Option<&'a TreeNode> {
+        nodes.iter().find_map(|node| {
+            let artifact = node.name.rsplit(':').next().unwrap_or(&node.name);
+            if node.name == package || artifact == package {
+                Some(node)
+            } else {
+                find(&node.dependencies, package)
+            }
+        })
+    }
+
+    let tree = maven.dependency_tree(project_root).ok()?;
+    let node = tree
+        .roots
+        .iter()
+        .find_map(|root| find(&root.dependencies, package))?;
+    Some(PackageInfo {
+        name: node.name.clone(),
+        version: node.version.clone(),
+        description: None,
+        license: None,
+        homepage: None,
+        repository: None,
+        features: Vec::new(),
+        dependencies: node
+            .dependencies
+            .iter()
+            .map(|dep| Dependency::registry(dep.name.clone(), Some(dep.version.clone()), false))
+            .collect(),
+    })
+}
+
+/// Parse the ASCII tree printed by `gradle dependencies`:
+///
+/// ```text
+/// +--- com.google.guava:guava:31.1-jre
+/// |    \--- com.google.guava:failureaccess:1.0.1
+/// \--- org.slf4j:slf4j-api:1.7.36 -> 2.0.7
+/// ```
+///
+/// Each level indents by five columns. A `->` gives the version conflict
+/// resolution picked, which is the one reported.
+fn parse_gradle_tree(output: &str) -> Vec<TreeNode> {
+    fn attach(stack: &mut Vec<(usize, TreeNode)>, roots: &mut Vec<TreeNode>) {
+        if let Some((_, node)) = stack.pop() {
+            match stack.last_mut() {
+                Some((_, parent)) => parent.dependencies.push(node),
+                None => roots.push(node),
+            }
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<(usize, TreeNode)> = Vec::new();
+    for line in output.lines() {
+        let Some(pos) = line.find("+--- ").or_else(|| line.find("\\--- ")) else {
+            continue;
+        };
+        let depth = pos / 5;
+        let (name, version) = parse_gradle_coordinate(&line[pos + 5..]);
+        while stack.last().is_some_and(|(d, _)| *d >= depth) {
+            attach(&mut stack, &mut roots);
+        }
+        stack.push((
+            depth,
+            TreeNode {
+                name,
+                version,
+                dependencies: Vec::new(),
+            },
+        ));
+    }
+    while !stack.is_empty() {
+        attach(&mut stack, &mut roots);
+    }
+    roots
+}
+
+/// Split `group:artifact:version [-> resolved] [(*)]` into name and version.
+fn parse_gradle_coordinate(text: &str) -> (String, String) {
+    let mut text = text.trim();
+    // (*) repeated subtree, (c) constraint, (n) not resolved
+    for suffix in [" (*)", " (c)", " (n)", " FAILED"] {
+        text = text.trim_end_matches(suffix);
+    }
+    let (coord, resolved) = match text.split_once(" -> ") {
+        Some((coord, resolved)) => (coord, Some(resolved.trim())),
+        None => (text, None),
+    };
+    if let Some(project) = coord.strip_prefix("project ") {
+        return (project.to_string(), String::new());
+    }
+    let parts: Vec<&str> = coord.split(':').collect();
+    let (name, declared) = match parts.as_slice() {
+        [group, artifact, version, ..] => (format!("{}:{}", group, artifact), *version),
+        [group, artifact] => (format!("{}:{}", group, artifact), ""),
+        _ => (coord.to_string(), ""),
+    };
+    (name, resolved.unwrap_or(declared).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <parent>
+    <groupId>org.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>2.1.0</version>
+  </parent>
+  <artifactId>widget</artifactId>
+  <description>
+    A widget
+    library.
+  </description>
+  <properties>
+    <guava.version>31.1-jre</guava.version>
+  </properties>
+  <licenses>
+    <license>
+      <name>The Apache Software License, Version 2.0</name>
+      <url>https://www.apache.org/licenses/LICENSE-2.0.txt</url>
+    </license>
+  </licenses>
+  <scm><url>https://github.com/example/widget</url></scm>
+  <dependencyManagement>
+    <dependencies>
+      <dependency><groupId>managed</groupId><artifactId>only</artifactId><version>1</version></dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency><groupId>com.google.guava</groupId><artifactId>guava</artifactId><version>${guava.version}</version></dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>core</artifactId>
+      <version>${project.version}</version>
+      <optional>true</optional>
+    </dependency>
+    <dependency><groupId>junit</groupId><artifactId>junit</artifactId><version>4.13.2</version><scope>test</scope></dependency>
+  </dependencies>
+</project>"#;
+
+    #[test]
+    fn test_parse_pom() -> Result<(), PackageError> {
+        let pom = Pom::parse(POM)?;
+        assert_eq!(pom.description.as_deref(), Some("A widget library."));
+        assert_eq!(pom.license_expression().as_deref(), Some("Apache-2.0"));
+        assert_eq!(
+            pom.scm_url.as_deref(),
+            Some("https://github.com/example/widget")
+        );
+
+        let deps: Vec<(String, Option<String>)> = pom
+            .dependencies
+            .iter()
+            .map(|d| (d.coordinate(), d.version.clone()))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("com.google.guava:guava".into(), Some("31.1-jre".into())),
+                ("org.example:core".into(), Some("2.1.0".into())),
+                ("junit:junit".into(), Some("4.13.2".into())),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_pom_skips_test_scope() -> Result<(), PackageError> {
+        let mut info = PackageInfo {
+            name: "org.example:widget".into(),
+            version: "2.1.0".into(),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            features: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        apply_pom(&mut info, Pom::parse(POM)?);
+        assert_eq!(info.license.as_deref(), Some("Apache-2.0"));
+        let names: Vec<&str> = info.dependencies.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["com.google.guava:guava", "org.example:core"]);
+        assert!(info.dependencies[1].optional);
+        Ok(())
+    }
+
+    #[test]
+    fn test_local_pom_marks_test_deps_optional() -> Result<(), PackageError> {
+        let deps = parse_pom_dependencies(POM)?;
+        assert_eq!(deps.len(), 3);
+        assert!(!deps[0].optional);
+        assert!(deps[2].optional);
+        Ok(())
+    }
+
+    #[test]
+    fn test_spdx_license() {
+        let spdx = |name, url| spdx_license(name, url).to_string();
+        assert_eq!(spdx("MIT License", None), "MIT");
+        assert_eq!(spdx("Eclipse Public License - v 2.0", None), "EPL-2.0");
+        assert_eq!(
+            spdx(
+                "GPL2 w/ CPE",
+                Some("https://openjdk.org/legal/gplv2+ce.html")
+            ),
+            "GPL2 w/ CPE"
+        );
+        assert_eq!(
+            spdx(
+                "Apache License",
+                Some("http://www.apache.org/licenses/LICENSE-2.0")
+            ),
+            "Apache-2.0"
+        );
+        assert_eq!(
+            spdx(
+                "GNU General Public License, version 2 with the Classpath Exception",
+                None
+            ),
+            "GPL-2.0-only WITH Classpath-exception-2.0"
+        );
+        // Names that are already SPDX expressions pass through.
+        assert_eq!(spdx("mit or Apache-2.0", None), "mit OR Apache-2.0");
+    }
+
+    #[test]
+    fn test_license_expression_joins_with_or() {
+        let pom = Pom {
+            licenses: vec![
+                ("MIT License".to_string(), None),
+                ("EPL-2.0 AND CDDL-1.1".to_string(), None),
+            ],
+            ..Pom::default()
+        };
+        assert_eq!(
+            pom.license_expression().as_deref(),
+            Some("MIT OR EPL-2.0 AND CDDL-1.1")
+        );
+    }
+
+    #[test]
+    fn test_parse_gradle_tree() {
+        let output = "\
+runtimeClasspath - Runtime classpath of source set 'main'.
++--- com.google.guava:guava:31.1-jre
+|    +--- com.google.guava:failureaccess:1.0.1
+|    \\--- org.checkerframework:checker-qual:3.12.0
++--- org.slf4j:slf4j-api:1.7.36 -> 2.0.7
+\\--- project :core
+     \\--- com.google.guava:guava:31.1-jre (*)
+";
+        let roots = parse_gradle_tree(output);
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[0].name, "com.google.guava:guava");
+        assert_eq!(roots[0].dependencies.len(), 2);
+        assert_eq!(roots[1].version, "2.0.7");
+        assert_eq!(roots[2].name, ":core");
+        assert_eq!(roots[2].dependencies[0].version, "31.1-jre");
+    }
+}
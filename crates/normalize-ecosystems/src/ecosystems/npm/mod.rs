@@ -19,6 +19,14 @@ impl Ecosystem for Npm {
         "npm"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("npm")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("npm")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["package.json"]
     }
@@ -57,6 +65,14 @@ impl Ecosystem for Npm {
         fetch_npm_registry(&query.name, query.version.as_deref())
     }
 
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_npm_registry(&query.name, query.version.as_deref()))
+    }
+
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
         // Try each lockfile format
         if let Some(v) = lockfile_npm::installed_version(package, project_root) {
@@ -13,6 +13,14 @@ impl Ecosystem for Nuget {
         "nuget"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("NuGet")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("nuget")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["*.csproj", "*.fsproj", "*.vbproj", "packages.config"]
     }
@@ -25,11 +33,19 @@ impl Ecosystem for Nuget {
     }
 
     fn tools(&self) -> &'static [&'static str] {
-        &["curl"] // Uses NuGet API
+        &["dotnet"] // Metadata comes from the NuGet v3 API either way
     }
 
     fn fetch_info(&self, query: &PackageQuery, _tool: &str) -> Result<PackageInfo, PackageError> {
-        fetch_nuget_info(&query.name)
+        fetch_nuget_info(&query.name, query.version.as_deref())
+    }
+
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_nuget_info(&query.name, query.version.as_deref()))
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
@@ -130,14 +146,16 @@ impl Ecosystem for Nuget {
         })
     }
 
-    fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
-        Err(PackageError::ToolFailed(
-            "audit not yet supported for NuGet. Use: dotnet list package --vulnerable".to_string(),
-        ))
+    fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
+        crate::osv::audit_declared(self, project_root)
     }
 }
 
-fn fetch_nuget_info(package: &str) -> Result<PackageInfo, PackageError> {
+fn fetch_nuget_info(package: &str, version: Option<&str>) -> Result<PackageInfo, PackageError> {
+    if let Some(version) = version {
+        return fetch_nuget_version(package, version);
+    }
+
     // First get the latest version
     let index_url = format!(
         "https://api.nuget.org/v3-flatcontainer/{}/index.json",
@@ -155,6 +173,14 @@ fn fetch_nuget_info(package: &str) -> Result<PackageInfo, PackageError> {
         .and_then(|v| v.as_str())
         .ok_or_else(|| PackageError::ParseError("no versions found".to_string()))?;
 
+    fetch_nuget_version(package, version)
+}
+
+fn fetch_nuget_version(package: &str, version: &str) -> Result<PackageInfo, PackageError> {
+    // Flat-container paths use lowercased ids and versions
+    let version = version.to_lowercase();
+    let version = version.as_str();
+
     // Get package metadata from nuspec
     let nuspec_url = format!(
         "https://api.nuget.org/v3-flatcontainer/{}/{}/{}.nuspec",
@@ -14,6 +14,14 @@ impl Ecosystem for Python {
         "python"
     }
 
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        Some("PyPI")
+    }
+
+    fn purl_type(&self) -> Option<&'static str> {
+        Some("pypi")
+    }
+
     fn manifest_files(&self) -> &'static [&'static str] {
         &["pyproject.toml", "setup.py", "requirements.txt"]
     }
@@ -47,6 +55,14 @@ impl Ecosystem for Python {
         fetch_pypi_info(query)
     }
 
+    #[cfg(feature = "registry-http")]
+    fn fetch_registry_info(
+        &self,
+        query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        Some(fetch_pypi_info(query))
+    }
+
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
         // Normalize package name (PEP 503: lowercase, replace - and . with _)
         let normalized = package.to_lowercase().replace(['-', '.'], "_");
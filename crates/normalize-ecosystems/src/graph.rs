@@ -0,0 +1,629 @@
+//! Merged dependency graph: lockfile, local installs and registry metadata.
+//!
+//! Each source on its own gives a partial picture. The lockfile tree pins exact
+//! versions, but not every ecosystem records edges in it.
+//! [`normalize_local_deps::LocalDependencyGraph`] follows installed manifests, but
+//! only sees what is on disk. [`PackageInfo::dependencies`] fills the gaps from
+//! the registry. [`DependencyGraph`] merges all three into one graph keyed by
+//! `name@version`, so each consumer can query it instead of reassembling it.
+
+use crate::lockfile::{Lockfile, LockfileFormat};
+use crate::{DependencyTree, Ecosystem, PackageError, PackageInfo, TreeNode};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Where a node or edge of the graph was learned from.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphSource {
+    /// The ecosystem's lockfile (or its lockfile-derived tree).
+    Lockfile,
+    /// Packages installed on disk and their manifests.
+    Local,
+    /// Registry metadata ([`PackageInfo::dependencies`]).
+    Registry,
+}
+
+/// A package at one version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GraphNode {
+    /// Node identifier: `name@version`, or `name` when the version is unknown.
+    pub id: String,
+    /// Package name.
+    pub name: String,
+    /// Resolved version, if any source recorded one.
+    pub version: Option<String>,
+    /// Path to the installed package, if it was found on disk.
+    pub path: Option<PathBuf>,
+    /// Whether the project depends on this node directly.
+    pub direct: bool,
+    /// Shortest distance from the project (1 = direct); 0 if unreachable.
+    pub depth: usize,
+    /// Sources that reported this node.
+    pub sources: Vec<GraphSource>,
+}
+
+/// A dependency from one node (or the project) to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GraphEdge {
+    /// Depending node id, or the project name.
+    pub from: String,
+    /// Dependency node id.
+    pub to: String,
+    /// Version requirement as declared, if known.
+    pub version_req: Option<String>,
+    /// Source the edge was first learned from.
+    pub source: GraphSource,
+}
+
+/// A package present at more than one version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DuplicateVersions {
+    /// Package name.
+    pub name: String,
+    /// Distinct versions, sorted.
+    pub versions: Vec<String>,
+}
+
+/// Dependency graph of a project, merged from every available source.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DependencyGraph {
+    /// Project name; edges from the project use it as `from`.
+    pub root: String,
+    /// Ecosystem name (e.g. "cargo", "npm").
+    pub ecosystem: String,
+    /// Packages, in the order they were first seen.
+    pub nodes: Vec<GraphNode>,
+    /// Dependency edges, deduplicated by `(from, to)`.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Shortest paths from the project to each reachable node of a
+/// [`DependencyGraph`], as built by [`DependencyGraph::root_paths`].
+#[derive(Debug)]
+pub struct RootPaths<'a> {
+    /// Predecessor of each reachable node on its shortest path.
+    parent: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> RootPaths<'a> {
+    /// Path from the project to node `id`, as ids (project name first, `id`
+    /// last). `None` if unreachable.
+    pub fn path(&self, id: &str) -> Option<Vec<&'a str>> {
+        let (&node, _) = self.parent.get_key_value(id)?;
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&prev) = self.parent.get(current) {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl DependencyGraph {
+    /// An empty graph for the project `root`.
+    pub fn new(root: impl Into<String>, ecosystem: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            ecosystem: ecosystem.into(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Build the graph for `project_root` from the lockfile tree, the lockfile's
+    /// pinned packages and the locally-installed packages.
+    ///
+    /// With `registry`, packages that no local source gave any dependencies are
+    /// completed from registry metadata (cached; failed lookups are skipped).
+    /// Errors only if no source produced anything.
+    pub fn build(
+        ecosystem: &dyn Ecosystem,
+        project_root: &Path,
+        registry: bool,
+    ) -> Result<Self, PackageError> {
+        // Lockfile lookups walk up to a workspace root, which needs an absolute path.
+        let project_root =
+            &std::fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+        let local = local_graph(ecosystem, project_root);
+        let root = local.as_ref().map(|g| g.root.clone()).unwrap_or_else(|| {
+            project_root
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let mut graph = Self::new(root, ecosystem.name());
+
+        let tree = ecosystem.dependency_tree(project_root).ok();
+        if let Some(tree) = &tree {
+            graph.add_tree(tree);
+        }
+        let lockfile = find_lockfile(ecosystem, project_root);
+        if let Some(lockfile) = &lockfile {
+            graph.add_lockfile(lockfile);
+        }
+        if let Some(local) = &local {
+            graph.add_local(local);
+        }
+        if tree.is_none() && lockfile.is_none() && local.is_none() {
+            return Err(PackageError::ParseError(format!(
+                "no {} lockfile or manifest found in {}",
+                ecosystem.name(),
+                project_root.display()
+            )));
+        }
+
+        if registry {
+            let specs: Vec<String> = graph
+                .nodes
+                .iter()
+                .filter(|n| !graph.edges.iter().any(|e| e.from == n.id))
+                .filter_map(|n| Some(format!("{}@{}", n.name, n.version.as_ref()?)))
+                .collect();
+            let spec_refs: Vec<&str> = specs.iter().map(String::as_str).collect();
+            for info in ecosystem
+                .query_many(&spec_refs, project_root)
+                .into_iter()
+                .flatten()
+            {
+                graph.add_package_info(&info);
+            }
+        }
+
+        graph.compute_depths();
+        Ok(graph)
+    }
+
+    /// Add a lockfile-derived tree. Top-level nodes are direct dependencies,
+    /// except a top-level node named after the project, whose children are.
+    pub fn add_tree(&mut self, tree: &DependencyTree) {
+        let root = self.root.clone();
+        let mut visited = HashSet::new();
+        for node in &tree.roots {
+            if node.name == root {
+                for child in &node.dependencies {
+                    self.add_tree_node(&root, child, &mut visited);
+                }
+            } else {
+                self.add_tree_node(&root, node, &mut visited);
+            }
+        }
+    }
+
+    fn add_tree_node(&mut self, from: &str, node: &TreeNode, visited: &mut HashSet<String>) {
+        let version = Some(node.version.as_str()).filter(|v| !v.is_empty());
+        let id = self.intern(&node.name, version, GraphSource::Lockfile);
+        self.add_edge(from, &id, None, GraphSource::Lockfile);
+        // Trees repeat shared subtrees; only descend into each node once.
+        if visited.insert(id.clone()) {
+            for child in &node.dependencies {
+                self.add_tree_node(&id, child, visited);
+            }
+        }
+    }
+
+    /// Add every package pinned by a lockfile (nodes only; flat lockfiles
+    /// carry no edges). An entry for the project itself is skipped.
+    pub fn add_lockfile(&mut self, lockfile: &Lockfile) {
+        for pkg in &lockfile.packages {
+            if pkg.name == self.root {
+                continue;
+            }
+            self.intern(&pkg.name, Some(&pkg.version), GraphSource::Lockfile);
+        }
+    }
+
+    /// Add the locally-installed graph: installed versions, paths and the
+    /// edges declared by installed manifests.
+    pub fn add_local(&mut self, local: &normalize_local_deps::LocalDependencyGraph) {
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        for node in &local.nodes {
+            let id = self.intern(&node.name, node.version.as_deref(), GraphSource::Local);
+            if let Some(path) = &node.path
+                && let Some(n) = self.nodes.iter_mut().find(|n| n.id == id)
+            {
+                n.path.get_or_insert_with(|| path.clone());
+            }
+            ids.insert(&node.name, id);
+        }
+        for edge in &local.edges {
+            let from = if edge.from == local.root {
+                self.root.clone()
+            } else {
+                match ids.get(edge.from.as_str()) {
+                    Some(id) => id.clone(),
+                    None => continue,
+                }
+            };
+            let Some(to) = ids.get(edge.to.as_str()).cloned() else {
+                continue;
+            };
+            self.add_edge(&from, &to, edge.version_req.clone(), GraphSource::Local);
+        }
+    }
+
+    /// Add a package's registry-declared dependencies as edges from
+    /// `info.name@info.version`. Targets resolve to an existing node of that
+    /// name, or a new version-less node.
+    pub fn add_package_info(&mut self, info: &PackageInfo) {
+        let from = self.intern(&info.name, Some(&info.version), GraphSource::Registry);
+        for dep in &info.dependencies {
+            let name = dep.effective_name();
+            let to = self.intern(name, None, GraphSource::Registry);
+            self.add_edge(&from, &to, dep.version_req.clone(), GraphSource::Registry);
+        }
+    }
+
+    /// Look up a node by id (`name@version` or `name`).
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Every node for package `name`, at any version.
+    pub fn nodes_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a GraphNode> {
+        self.nodes.iter().filter(move |n| n.name == name)
+    }
+
+    /// Ids that depend directly on any version of `name` (the project name for
+    /// the project itself), deduplicated, in edge order.
+    pub fn dependents(&self, name: &str) -> Vec<&str> {
+        let targets: BTreeSet<&str> = self.nodes_named(name).map(|n| n.id.as_str()).collect();
+        let mut out: Vec<&str> = Vec::new();
+        for edge in &self.edges {
+            if targets.contains(edge.to.as_str()) && !out.contains(&edge.from.as_str()) {
+                out.push(&edge.from);
+            }
+        }
+        out
+    }
+
+    /// Packages present at more than one version, sorted by name.
+    pub fn duplicates(&self) -> Vec<DuplicateVersions> {
+        let mut versions: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for node in &self.nodes {
+            if let Some(version) = &node.version {
+                versions.entry(&node.name).or_default().insert(version);
+            }
+        }
+        versions
+            .into_iter()
+            .filter(|(_, v)| v.len() > 1)
+            .map(|(name, v)| DuplicateVersions {
+                name: name.to_string(),
+                versions: v.into_iter().map(String::from).collect(),
+            })
+            .collect()
+    }
+
+    /// Shortest path from the project to node `id`, as ids (project name
+    /// first, `id` last). `None` if unreachable. To look up many nodes, build
+    /// [`DependencyGraph::root_paths`] once instead.
+    pub fn path_to_root(&self, id: &str) -> Option<Vec<&str>> {
+        self.root_paths().path(id)
+    }
+
+    /// Shortest paths from the project to every reachable node, from one
+    /// breadth-first search over an adjacency map of the edges.
+    pub fn root_paths(&self) -> RootPaths<'_> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            adjacency.entry(&edge.from).or_default().push(&edge.to);
+        }
+        let mut parent: HashMap<&str, &str> = HashMap::new();
+        let mut queue = VecDeque::from([self.root.as_str()]);
+        while let Some(id) = queue.pop_front() {
+            for &to in adjacency.get(id).into_iter().flatten() {
+                if to != self.root && !parent.contains_key(to) {
+                    parent.insert(to, id);
+                    queue.push_back(to);
+                }
+            }
+        }
+        RootPaths { parent }
+    }
+
+    /// Render as JSON.
+    pub fn to_json(&self) -> serde_json::Value {
+        // normalize-syntax-allow: rust/unwrap-in-impl - plain data, serialization cannot fail
+        serde_json::to_value(self).expect("DependencyGraph serializes")
+    }
+
+    /// Render as a Graphviz `digraph`. Direct dependencies are drawn bold;
+    /// packages at more than one version are red.
+    pub fn to_dot(&self) -> String {
+        let duplicated: BTreeSet<String> = self.duplicates().into_iter().map(|d| d.name).collect();
+        let mut out = String::from("digraph dependencies {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str(&format!("  {} [shape=box];\n", dot_id(&self.root)));
+        for node in &self.nodes {
+            let label = match &node.version {
+                Some(v) => format!("{}\\n{}", node.name, v),
+                None => node.name.clone(),
+            };
+            let mut attrs = vec![format!("label={}", dot_id(&label))];
+            if node.direct {
+                attrs.push("style=bold".to_string());
+            }
+            if duplicated.contains(&node.name) {
+                attrs.push("color=red".to_string());
+            }
+            out.push_str(&format!("  {} [{}];\n", dot_id(&node.id), attrs.join(", ")));
+        }
+        for edge in &self.edges {
+            let mut line = format!("  {} -> {}", dot_id(&edge.from), dot_id(&edge.to));
+            if let Some(req) = &edge.version_req {
+                line.push_str(&format!(" [label={}]", dot_id(req)));
+            }
+            line.push_str(";\n");
+            out.push_str(&line);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Recompute `direct` and `depth` by breadth-first search from the project.
+    fn compute_depths(&mut self) {
+        let mut depth: HashMap<String, usize> = HashMap::new();
+        let mut queue = VecDeque::from([(self.root.clone(), 0)]);
+        while let Some((id, d)) = queue.pop_front() {
+            for edge in self.edges.iter().filter(|e| e.from == id) {
+                if edge.to != self.root && !depth.contains_key(&edge.to) {
+                    depth.insert(edge.to.clone(), d + 1);
+                    queue.push_back((edge.to.clone(), d + 1));
+                }
+            }
+        }
+        for node in &mut self.nodes {
+            node.depth = depth.get(&node.id).copied().unwrap_or(0);
+            node.direct = node.depth == 1;
+        }
+    }
+
+    /// Find or create the node for `name` at `version`, returning its id.
+    ///
+    /// A versioned lookup upgrades a version-less node of the same name; a
+    /// version-less lookup matches any node of that name.
+    fn intern(&mut self, name: &str, version: Option<&str>, source: GraphSource) -> String {
+        let index = match version {
+            Some(v) => self
+                .nodes
+                .iter()
+                .position(|n| n.name == name && n.version.as_deref() == Some(v))
+                .or_else(|| {
+                    let i = self
+                        .nodes
+                        .iter()
+                        .position(|n| n.name == name && n.version.is_none())?;
+                    self.set_version(i, v);
+                    Some(i)
+                }),
+            None => self.nodes.iter().position(|n| n.name == name),
+        };
+        let index = index.unwrap_or_else(|| {
+            self.nodes.push(GraphNode {
+                id: node_id(name, version),
+                name: name.to_string(),
+                version: version.map(String::from),
+                path: None,
+                direct: false,
+                depth: 0,
+                sources: Vec::new(),
+            });
+            self.nodes.len() - 1
+        });
+        let node = &mut self.nodes[index];
+        if !node.sources.contains(&source) {
+            node.sources.push(source);
+            node.sources.sort();
+        }
+        node.id.clone()
+    }
+
+    /// Give a version-less node a version, renaming it in existing edges.
+    fn set_version(&mut self, index: usize, version: &str) {
+        let node = &mut self.nodes[index];
+        let old = std::mem::replace(&mut node.id, node_id(&node.name, Some(version)));
+        node.version = Some(version.to_string());
+        let new = node.id.clone();
+        for edge in &mut self.edges {
+            if edge.from == old {
+                edge.from = new.clone();
+            }
+            if edge.to == old {
+                edge.to = new.clone();
+            }
+        }
+    }
+
+    /// Add an edge unless `(from, to)` already exists, in which case a missing
+    /// requirement is filled in.
+    fn add_edge(&mut self, from: &str, to: &str, version_req: Option<String>, source: GraphSource) {
+        if let Some(edge) = self.edges.iter_mut().find(|e| e.from == from && e.to == to) {
+            if edge.version_req.is_none() {
+                edge.version_req = version_req;
+            }
+            return;
+        }
+        self.edges.push(GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            version_req,
+            source,
+        });
+    }
+}
+
+/// The locally-installed dependency graph whose root manifest belongs to
+/// `ecosystem`, if any.
+pub fn local_graph(
+    ecosystem: &dyn Ecosystem,
+    project_root: &Path,
+) -> Option<normalize_local_deps::LocalDependencyGraph> {
+    normalize_local_deps::registry::all_local_deps()
+        .into_iter()
+        .filter_map(|deps| normalize_local_deps::LocalDependencyGraph::build(deps, project_root))
+        .find(|graph| {
+            graph
+                .manifest
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| ecosystem.manifest_files().contains(&n))
+        })
+}
+
+/// The first parseable lockfile of `ecosystem` in `project_root`.
+fn find_lockfile(ecosystem: &dyn Ecosystem, project_root: &Path) -> Option<Lockfile> {
+    ecosystem
+        .lockfiles()
+        .iter()
+        .map(|l| project_root.join(l.filename))
+        .filter(|p| p.is_file() && LockfileFormat::from_path(p).is_some())
+        .find_map(|p| Lockfile::from_path(&p).ok())
+}
+
+fn node_id(name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(v) => format!("{}@{}", name, v),
+        None => name.to_string(),
+    }
+}
+
+/// Quote a string as a DOT identifier.
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dependency;
+
+    fn tree_node(name: &str, version: &str, dependencies: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies,
+        }
+    }
+
+    /// app -> web 1.0 -> log 0.4, app -> cli 2.0 -> log 0.3
+    fn sample() -> DependencyGraph {
+        let mut graph = DependencyGraph::new("app", "cargo");
+        graph.add_tree(&DependencyTree {
+            roots: vec![tree_node(
+                "app",
+                "0.1.0",
+                vec![
+                    tree_node("web", "1.0.0", vec![tree_node("log", "0.4.0", vec![])]),
+                    tree_node("cli", "2.0.0", vec![tree_node("log", "0.3.0", vec![])]),
+                ],
+            )],
+        });
+        graph.compute_depths();
+        graph
+    }
+
+    #[test]
+    fn test_tree_nodes_and_depths() {
+        let graph = sample();
+        assert_eq!(graph.nodes.len(), 4);
+        assert!(graph.node("app@0.1.0").is_none());
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let web = graph.node("web@1.0.0").unwrap();
+        assert!(web.direct);
+        assert_eq!(web.depth, 1);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let log = graph.node("log@0.4.0").unwrap();
+        assert!(!log.direct);
+        assert_eq!(log.depth, 2);
+        assert_eq!(log.sources, [GraphSource::Lockfile]);
+    }
+
+    #[test]
+    fn test_dependents_and_duplicates() {
+        let graph = sample();
+        assert_eq!(graph.dependents("log"), ["web@1.0.0", "cli@2.0.0"]);
+        assert_eq!(graph.dependents("web"), ["app"]);
+        assert_eq!(
+            graph.duplicates(),
+            [DuplicateVersions {
+                name: "log".to_string(),
+                versions: vec!["0.3.0".to_string(), "0.4.0".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_path_to_root() {
+        let graph = sample();
+        assert_eq!(
+            graph.path_to_root("log@0.4.0"),
+            Some(vec!["app", "web@1.0.0", "log@0.4.0"])
+        );
+        assert_eq!(
+            graph.path_to_root("log@0.3.0"),
+            Some(vec!["app", "cli@2.0.0", "log@0.3.0"])
+        );
+        assert_eq!(
+            graph.path_to_root("cli@2.0.0"),
+            Some(vec!["app", "cli@2.0.0"])
+        );
+        assert_eq!(graph.path_to_root("cli"), None);
+        assert_eq!(graph.path_to_root("missing"), None);
+    }
+
+    #[test]
+    fn test_registry_info_resolves_and_upgrades() {
+        let mut graph = DependencyGraph::new("app", "npm");
+        graph.add_package_info(&PackageInfo {
+            name: "express".to_string(),
+            version: "4.18.2".to_string(),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            features: Vec::new(),
+            dependencies: vec![Dependency::registry(
+                "debug",
+                Some("2.6.9".to_string()),
+                false,
+            )],
+        });
+        assert_eq!(graph.edges[0].to, "debug");
+
+        // Learning the version later renames the node and its edges.
+        graph.add_lockfile(&Lockfile {
+            format: LockfileFormat::PackageLockJson,
+            packages: vec![crate::LockedPackage {
+                name: "debug".to_string(),
+                version: "2.6.9".to_string(),
+            }],
+        });
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges[0].from, "express@4.18.2");
+        assert_eq!(graph.edges[0].to, "debug@2.6.9");
+        assert_eq!(graph.edges[0].version_req.as_deref(), Some("2.6.9"));
+        assert_eq!(
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            graph.node("debug@2.6.9").unwrap().sources,
+            [GraphSource::Lockfile, GraphSource::Registry]
+        );
+    }
+
+    #[test]
+    fn test_to_dot_and_json() {
+        let graph = sample();
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"app\" -> \"web@1.0.0\";"));
+        assert!(dot.contains("\"log@0.4.0\" [label=\"log\\n0.4.0\", color=red];"));
+        let json = graph.to_json();
+        assert_eq!(json["root"], "app");
+        assert_eq!(json["nodes"].as_array().map(Vec::len), Some(4));
+    }
+}
@@ -45,3 +45,16 @@ pub fn get_bytes(url: &str) -> Result<Vec<u8>, PackageError> {
         .map_err(|e| PackageError::ParseError(format!("failed to read response: {}", e)))?;
     Ok(data)
 }
+
+/// Perform a POST request with a JSON body and return the response body as a string.
+pub fn post_json(url: &str, body: &serde_json::Value) -> Result<String, PackageError> {
+    let response = ureq::post(url).send_json(body).map_err(|e| match e {
+        ureq::Error::Status(404, _) => PackageError::NotFound(url.to_string()),
+        ureq::Error::Status(code, _) => PackageError::RegistryError(format!("HTTP {}", code)),
+        ureq::Error::Transport(t) => PackageError::RegistryError(t.to_string()),
+    })?;
+
+    response
+        .into_string()
+        .map_err(|e| PackageError::ParseError(format!("failed to read response: {}", e)))
+}
@@ -16,18 +16,27 @@
 //! }
 //! ```
 
+pub mod batch;
+pub mod bundle;
 mod cache;
 pub mod doc_tree;
 pub mod docs_rs;
 pub mod ecosystems;
 #[cfg(feature = "go")]
 pub mod go_docs;
+pub mod graph;
 pub mod http;
+pub mod license;
 pub mod local_docs;
+pub mod lockfile;
+pub mod osv;
 #[cfg(feature = "python")]
 pub mod python_docs;
+pub mod sbom;
 pub mod source_archive;
 pub mod symbol_docs;
+pub mod version;
+pub mod why;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -249,6 +258,8 @@ pub enum PackageError {
     NotFound(String),
     /// Network or registry error
     RegistryError(String),
+    /// Local filesystem error (cache, bundle or output file)
+    Io(String),
 }
 
 impl std::fmt::Display for PackageError {
@@ -259,6 +270,7 @@ impl std::fmt::Display for PackageError {
             PackageError::ParseError(msg) => write!(f, "parse error: {}", msg),
             PackageError::NotFound(name) => write!(f, "package not found: {}", name),
             PackageError::RegistryError(msg) => write!(f, "registry error: {}", msg),
+            PackageError::Io(msg) => write!(f, "I/O error: {}", msg),
         }
     }
 }
@@ -304,6 +316,7 @@ impl From<PackageError> for DocsError {
             PackageError::ParseError(msg) => DocsError::ParseError(msg),
             PackageError::RegistryError(msg) => DocsError::NetworkError(msg),
             PackageError::NoToolFound => DocsError::ToolFailed("no tool found".to_string()),
+            PackageError::Io(msg) => DocsError::ToolFailed(format!("I/O error: {}", msg)),
         }
     }
 }
@@ -415,6 +428,19 @@ pub trait Ecosystem: Send + Sync {
     /// If version is None, fetches latest.
     fn fetch_info(&self, query: &PackageQuery, tool: &str) -> Result<PackageInfo, PackageError>;
 
+    /// Fetch package info directly from the registry's HTTP API, without
+    /// requiring any package-manager CLI in PATH.
+    ///
+    /// `None` means the ecosystem has no direct registry client (or the
+    /// `registry-http` feature is disabled); [`Ecosystem::query`] then uses the
+    /// tool-based [`Ecosystem::fetch_info`] only.
+    fn fetch_registry_info(
+        &self,
+        _query: &PackageQuery,
+    ) -> Option<Result<PackageInfo, PackageError>> {
+        None
+    }
+
     /// Look up installed version from lockfile.
     /// Returns None if no lockfile or package not found.
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String>;
@@ -426,6 +452,15 @@ pub trait Ecosystem: Send + Sync {
     /// Returns structured tree data.
     fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError>;
 
+    /// Explain why `package` is in the dependency tree: every path from a
+    /// direct dependency down to it, from the lockfile-derived tree.
+    fn why(&self, package: &str, project_root: &Path) -> Result<WhyResult, PackageError> {
+        Ok(WhyResult::trace(
+            &self.dependency_tree(project_root)?,
+            package,
+        ))
+    }
+
     /// Package names this project publishes (from manifest, no network calls).
     /// Default: empty vec.
     fn published_names(&self, _project_root: &Path) -> Vec<String> {
@@ -436,6 +471,48 @@ pub trait Ecosystem: Send + Sync {
     /// Default implementation returns empty result (no audit tool available).
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError>;
 
+    /// Ecosystem name in the [OSV](https://osv.dev) advisory database
+    /// (e.g. `"crates.io"`, `"PyPI"`). `None` means OSV has no feed for it.
+    fn osv_ecosystem(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Version and requirement syntax used by this ecosystem's manifests.
+    fn version_scheme(&self) -> VersionScheme {
+        VersionScheme::for_ecosystem(self.name())
+    }
+
+    /// Whether `version` satisfies the requirement `req` (e.g. `"^1.2"`,
+    /// `">=2.28,<3"`), in this ecosystem's syntax.
+    fn satisfies(&self, version: &str, req: &str) -> bool {
+        self.version_scheme().satisfies(version, req)
+    }
+
+    /// The highest of `versions` satisfying `req`.
+    fn max_satisfying<'a>(&self, versions: &[&'a str], req: &str) -> Option<&'a str> {
+        self.version_scheme().max_satisfying(versions, req)
+    }
+
+    /// [Package URL](https://github.com/package-url/purl-spec) type for this
+    /// ecosystem (e.g. `"cargo"`, `"pypi"`), used to identify SBOM components.
+    fn purl_type(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Known vulnerabilities affecting one package version, from OSV.dev.
+    ///
+    /// Results are cached; a stale cache entry is used if the network is down.
+    fn audit_package(
+        &self,
+        package: &str,
+        version: &str,
+    ) -> Result<Vec<Vulnerability>, PackageError> {
+        let ecosystem = self.osv_ecosystem().ok_or_else(|| {
+            PackageError::RegistryError(format!("no advisory database for {}", self.name()))
+        })?;
+        osv::query(ecosystem, package, version)
+    }
+
     /// Local documentation extractor for this ecosystem, if any.
     ///
     /// Returns a [`LocalDocsExtractor`] that reads doc comments from on-disk
@@ -489,7 +566,30 @@ pub trait Ecosystem: Send + Sync {
         self.find_tool()
     }
 
-    /// Convenience method: detect tool and fetch info with caching.
+    /// Fetch package info, uncached.
+    ///
+    /// Tries the direct registry client first, so lookups work in environments
+    /// without the ecosystem's CLI (e.g. CI containers). Falls back to the
+    /// tool-based fetcher when the registry client is unavailable or fails.
+    fn fetch(
+        &self,
+        query: &PackageQuery,
+        project_root: &Path,
+    ) -> Result<PackageInfo, PackageError> {
+        let registry_err = match self.fetch_registry_info(query) {
+            Some(Ok(info)) => return Ok(info),
+            // The registry answered authoritatively; a CLI would say the same.
+            Some(Err(e @ PackageError::NotFound(_))) => return Err(e),
+            Some(Err(e)) => Some(e),
+            None => None,
+        };
+        match self.detect_tool(project_root) {
+            Some(tool) => self.fetch_info(query, tool),
+            None => Err(registry_err.unwrap_or(PackageError::NoToolFound)),
+        }
+    }
+
+    /// Convenience method: fetch info with caching.
     ///
     /// Accepts "package" or "package@version" format.
     /// If no version specified, checks lockfile for installed version first.
@@ -504,9 +604,6 @@ pub trait Ecosystem: Send + Sync {
             query.version = self.installed_version(&query.name, project_root);
         }
 
-        let tool = self
-            .detect_tool(project_root)
-            .ok_or(PackageError::NoToolFound)?;
         let cache_key = query.cache_key();
         let cache_ttl = Duration::from_secs(24 * 60 * 60); // 24 hours
 
@@ -515,8 +612,9 @@ pub trait Ecosystem: Send + Sync {
             return Ok(cached);
         }
 
-        // Try network
-        match self.fetch_info(&query, tool) {
+        // Try network, spacing out requests to the same registry
+        batch::registry_limiter(self.name()).acquire();
+        match self.fetch(&query, project_root) {
             Ok(info) => {
                 cache::write(self.name(), &cache_key, &info);
                 Ok(info)
@@ -530,6 +628,52 @@ pub trait Ecosystem: Send + Sync {
             }
         }
     }
+
+    /// Query several packages concurrently.
+    ///
+    /// Runs [`Ecosystem::query`] on a pool of at most [`batch::MAX_CONCURRENCY`]
+    /// threads. Cache hits return immediately; network fetches share the
+    /// registry's rate limiter. Results are in input order.
+    fn query_many(
+        &self,
+        packages: &[&str],
+        project_root: &Path,
+    ) -> Vec<Result<PackageInfo, PackageError>> {
+        batch::run_bounded(packages, batch::MAX_CONCURRENCY, |package| {
+            self.query(package, project_root)
+        })
+    }
+}
+
+/// Audit every package in a merged dependency graph against OSV.
+///
+/// Cross-references the [`DependencyGraph`] (lockfile, installed packages and
+/// their manifests) rather than running the ecosystem's audit tool. Packages
+/// without a known version are skipped. Individual lookup failures are
+/// tolerated; an error is returned only if every lookup failed.
+pub fn audit_dependency_graph(
+    ecosystem: &dyn Ecosystem,
+    graph: &DependencyGraph,
+) -> Result<AuditResult, PackageError> {
+    let mut vulnerabilities = Vec::new();
+    let mut last_err = None;
+    let mut succeeded = false;
+    for node in &graph.nodes {
+        let Some(version) = &node.version else {
+            continue;
+        };
+        match ecosystem.audit_package(&node.name, version) {
+            Ok(vulns) => {
+                succeeded = true;
+                vulnerabilities.extend(vulns);
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+    match last_err {
+        Some(e) if !succeeded => Err(e),
+        _ => Ok(AuditResult { vulnerabilities }),
+    }
 }
 
 /// Check if a command exists in PATH.
@@ -563,6 +707,17 @@ pub use ecosystems::{
     register as register_ecosystem,
 };
 
+pub use bundle::{BundleExport, BundleImport, BundleManifest};
+pub use graph::{DependencyGraph, DuplicateVersions, GraphEdge, GraphNode, GraphSource, RootPaths};
+pub use license::{LicenseAudit, LicenseFinding, LicensePolicy, LicenseVerdict, SpdxExpr};
+pub use lockfile::{
+    LockedPackage, Lockfile, LockfileDiff, LockfileFormat, VersionChange, diff_lockfiles,
+};
+pub use osv::{LockfileAudit, PackageAdvisories, audit_lockfile};
+pub use sbom::{Sbom, SbomComponent, SbomFormat};
+pub use version::{Version, VersionReq, VersionScheme};
+pub use why::{PathEntry, WhyResult};
+
 // Re-export SymbolDoc for convenience
 pub use symbol_docs::{DocFormat, SymbolDoc};
 
@@ -573,3 +728,85 @@ pub use go_docs::{GoLocalDocsExtractor, GoRemoteDocsFetcher};
 pub use local_docs::CargoLocalDocsExtractor;
 #[cfg(feature = "python")]
 pub use python_docs::{PythonLocalDocsExtractor, PythonRemoteDocsFetcher};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ecosystem whose CLI is never in PATH and whose registry returns a canned result.
+    struct Offline(fn() -> Option<Result<PackageInfo, PackageError>>);
+
+    impl Ecosystem for Offline {
+        fn name(&self) -> &'static str {
+            "offline-test"
+        }
+        fn manifest_files(&self) -> &'static [&'static str] {
+            &[]
+        }
+        fn lockfiles(&self) -> &'static [LockfileManager] {
+            &[]
+        }
+        fn tools(&self) -> &'static [&'static str] {
+            &["normalize-test-tool-that-does-not-exist"]
+        }
+        fn fetch_info(&self, _: &PackageQuery, _: &str) -> Result<PackageInfo, PackageError> {
+            Err(PackageError::ToolFailed(
+                "tool fetcher should not run".into(),
+            ))
+        }
+        fn fetch_registry_info(
+            &self,
+            _: &PackageQuery,
+        ) -> Option<Result<PackageInfo, PackageError>> {
+            (self.0)()
+        }
+        fn installed_version(&self, _: &str, _: &Path) -> Option<String> {
+            None
+        }
+        fn list_dependencies(&self, _: &Path) -> Result<Vec<Dependency>, PackageError> {
+            Ok(Vec::new())
+        }
+        fn dependency_tree(&self, _: &Path) -> Result<DependencyTree, PackageError> {
+            Ok(DependencyTree { roots: Vec::new() })
+        }
+        fn audit(&self, _: &Path) -> Result<AuditResult, PackageError> {
+            Ok(AuditResult {
+                vulnerabilities: Vec::new(),
+            })
+        }
+    }
+
+    fn info() -> PackageInfo {
+        PackageInfo {
+            name: "pkg".into(),
+            version: "1.0.0".into(),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            features: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fetch_uses_registry_without_tool() {
+        let eco = Offline(|| Some(Ok(info())));
+        let result = eco.fetch(&PackageQuery::parse("pkg"), Path::new("."));
+        assert!(matches!(result, Ok(i) if i.version == "1.0.0"));
+    }
+
+    #[test]
+    fn test_fetch_surfaces_registry_error_without_tool() {
+        let eco = Offline(|| Some(Err(PackageError::RegistryError("HTTP 503".into()))));
+        let result = eco.fetch(&PackageQuery::parse("pkg"), Path::new("."));
+        assert!(matches!(result, Err(PackageError::RegistryError(_))));
+    }
+
+    #[test]
+    fn test_fetch_without_registry_or_tool() {
+        let eco = Offline(|| None);
+        let result = eco.fetch(&PackageQuery::parse("pkg"), Path::new("."));
+        assert!(matches!(result, Err(PackageError::NoToolFound)));
+    }
+}
@@ -0,0 +1,482 @@
+//! License compatibility checks over the dependency graph.
+//!
+//! [`SpdxExpr::parse`] reads SPDX license expressions (`MIT OR Apache-2.0`,
+//! `GPL-2.0-or-later WITH Classpath-exception-2.0`), plus the legacy
+//! `MIT/Apache-2.0` form older crates still publish. [`LicensePolicy`] sorts
+//! license ids into allowed, denied and needs-review lists, and
+//! [`LicenseAudit`] evaluates every package of a [`DependencyGraph`] against
+//! it. An `OR` is satisfied by its best branch (the licensee may choose), an
+//! `AND` only by its worst.
+
+use crate::graph::DependencyGraph;
+use crate::{Ecosystem, PackageError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpdxExpr {
+    /// A single license, e.g. `Apache-2.0`, `GPL-2.0+` or `LicenseRef-Foo`.
+    License {
+        /// License identifier without the `+` suffix.
+        id: String,
+        /// `+`: this version or any later one.
+        or_later: bool,
+        /// Exception from a `WITH` clause.
+        exception: Option<String>,
+    },
+    /// Both licenses apply.
+    And(Box<SpdxExpr>, Box<SpdxExpr>),
+    /// Either license may be chosen.
+    Or(Box<SpdxExpr>, Box<SpdxExpr>),
+}
+
+impl SpdxExpr {
+    /// Parse an expression. Operators are case-insensitive; `AND` binds
+    /// tighter than `OR`, and `/` is read as `OR`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text);
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or_expr()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(tok) => Err(format!("unexpected '{}' in license '{}'", tok, text)),
+        }
+    }
+
+    /// Every license identifier in the expression, in order.
+    pub fn license_ids(&self) -> Vec<&str> {
+        match self {
+            SpdxExpr::License { id, .. } => vec![id],
+            SpdxExpr::And(a, b) | SpdxExpr::Or(a, b) => {
+                let mut ids = a.license_ids();
+                ids.extend(b.license_ids());
+                ids
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SpdxExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpdxExpr::License {
+                id,
+                or_later,
+                exception,
+            } => {
+                write!(f, "{}", id)?;
+                if *or_later {
+                    write!(f, "+")?;
+                }
+                if let Some(exception) = exception {
+                    write!(f, " WITH {}", exception)?;
+                }
+                Ok(())
+            }
+            SpdxExpr::And(a, b) => write!(f, "{} AND {}", paren_or(a), paren_or(b)),
+            SpdxExpr::Or(a, b) => write!(f, "{} OR {}", a, b),
+        }
+    }
+}
+
+/// Render an operand of `AND`, parenthesizing a nested `OR`.
+fn paren_or(expr: &SpdxExpr) -> String {
+    match expr {
+        SpdxExpr::Or(..) => format!("({})", expr),
+        _ => expr.to_string(),
+    }
+}
+
+/// Split into parentheses, `/` and whitespace-separated words.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_whitespace() || matches!(c, '(' | ')' | '/') {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_is(&self, keyword: &str) -> bool {
+        self.tokens
+            .get(self.pos)
+            .is_some_and(|t| t.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or_expr(&mut self) -> Result<SpdxExpr, String> {
+        let mut expr = self.and_expr()?;
+        while self.peek_is("OR") || self.peek_is("/") {
+            self.pos += 1;
+            expr = SpdxExpr::Or(Box::new(expr), Box::new(self.and_expr()?));
+        }
+        Ok(expr)
+    }
+
+    fn and_expr(&mut self) -> Result<SpdxExpr, String> {
+        let mut expr = self.primary()?;
+        while self.peek_is("AND") {
+            self.pos += 1;
+            expr = SpdxExpr::And(Box::new(expr), Box::new(self.primary()?));
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<SpdxExpr, String> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err("unexpected end of license expression".to_string());
+        };
+        self.pos += 1;
+        if token == "(" {
+            let expr = self.or_expr()?;
+            if !self.peek_is(")") {
+                return Err("missing ')' in license expression".to_string());
+            }
+            self.pos += 1;
+            return Ok(expr);
+        }
+        if matches!(
+            token.to_ascii_uppercase().as_str(),
+            ")" | "/" | "AND" | "OR" | "WITH"
+        ) {
+            return Err(format!("expected a license, found '{}'", token));
+        }
+        let (id, or_later) = match token.strip_suffix('+') {
+            Some(id) => (id.to_string(), true),
+            None => (token, false),
+        };
+        let exception = if self.peek_is("WITH") {
+            self.pos += 1;
+            let exception = self
+                .tokens
+                .get(self.pos)
+                .cloned()
+                .ok_or("missing exception after WITH")?;
+            self.pos += 1;
+            Some(exception)
+        } else {
+            None
+        };
+        Ok(SpdxExpr::License {
+            id,
+            or_later,
+            exception,
+        })
+    }
+}
+
+/// Which licenses a project accepts (`[licenses]` in config).
+///
+/// Entries are SPDX ids, matched case-insensitively; an entry may also name a
+/// full `id WITH exception` or `id+` form. When `allowed` is non-empty, any
+/// license not listed anywhere is a violation; otherwise it is accepted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LicensePolicy {
+    /// Licenses that are always acceptable.
+    pub allowed: Vec<String>,
+    /// Licenses that must not appear in the dependency graph.
+    pub denied: Vec<String>,
+    /// Licenses acceptable only after a manual review.
+    pub needs_review: Vec<String>,
+}
+
+/// Outcome of checking one license (or expression) against a policy, from
+/// best to worst.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum LicenseVerdict {
+    /// Listed in `allowed`, or accepted because `allowed` is empty.
+    Allowed,
+    /// Listed in `needs_review`.
+    NeedsReview,
+    /// No license is known (none declared, or the registry lookup failed), or
+    /// it could not be parsed.
+    Unknown,
+    /// Not listed while an `allowed` list is configured.
+    NotAllowed,
+    /// Listed in `denied`.
+    Denied,
+}
+
+impl LicenseVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LicenseVerdict::Allowed => "allowed",
+            LicenseVerdict::NeedsReview => "needs review",
+            LicenseVerdict::Unknown => "unknown",
+            LicenseVerdict::NotAllowed => "not allowed",
+            LicenseVerdict::Denied => "denied",
+        }
+    }
+}
+
+impl LicensePolicy {
+    /// Verdict for a license string as published by a registry.
+    pub fn check(&self, license: Option<&str>) -> LicenseVerdict {
+        match license.map(str::trim).filter(|l| !l.is_empty()) {
+            Some(text) => match SpdxExpr::parse(text) {
+                Ok(expr) => self.evaluate(&expr),
+                Err(_) => LicenseVerdict::Unknown,
+            },
+            None => LicenseVerdict::Unknown,
+        }
+    }
+
+    /// Verdict for a parsed expression: the best branch of an `OR`, the
+    /// worst operand of an `AND`.
+    pub fn evaluate(&self, expr: &SpdxExpr) -> LicenseVerdict {
+        match expr {
+            SpdxExpr::License { id, .. } => self.evaluate_license(id, &expr.to_string()),
+            SpdxExpr::And(a, b) => self.evaluate(a).max(self.evaluate(b)),
+            SpdxExpr::Or(a, b) => self.evaluate(a).min(self.evaluate(b)),
+        }
+    }
+
+    /// Verdict for one license, listed either by bare `id` or by its `full`
+    /// form (with `+` and `WITH` exception).
+    fn evaluate_license(&self, id: &str, full: &str) -> LicenseVerdict {
+        let listed = |list: &[String]| {
+            list.iter()
+                .any(|entry| entry.eq_ignore_ascii_case(id) || entry.eq_ignore_ascii_case(full))
+        };
+        if listed(&self.denied) {
+            LicenseVerdict::Denied
+        } else if listed(&self.needs_review) {
+            LicenseVerdict::NeedsReview
+        } else if self.allowed.is_empty() || listed(&self.allowed) {
+            LicenseVerdict::Allowed
+        } else {
+            LicenseVerdict::NotAllowed
+        }
+    }
+}
+
+/// A dependency whose license is not plainly allowed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LicenseFinding {
+    /// Package name.
+    pub package: String,
+    /// Package version, if known.
+    pub version: Option<String>,
+    /// License as published by the registry.
+    pub license: Option<String>,
+    /// Outcome under the policy.
+    pub verdict: LicenseVerdict,
+    /// Shortest dependency path that pulls the package in, as graph node ids
+    /// (project first). Empty if the package is not reachable in the graph.
+    pub path: Vec<String>,
+}
+
+/// License check of every package in a dependency graph.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LicenseAudit {
+    /// Ecosystem audited (e.g. "cargo", "npm").
+    pub ecosystem: String,
+    /// Number of packages checked.
+    pub checked: usize,
+    /// Packages whose verdict is anything but allowed, worst first.
+    pub findings: Vec<LicenseFinding>,
+}
+
+impl LicenseAudit {
+    /// Build the project's dependency graph, look up each package's license in
+    /// the registry (cached) and check it against `policy`.
+    pub fn run(
+        ecosystem: &dyn Ecosystem,
+        project_root: &Path,
+        policy: &LicensePolicy,
+    ) -> Result<Self, PackageError> {
+        let graph = DependencyGraph::build(ecosystem, project_root, false)?;
+        let specs: Vec<String> = graph
+            .nodes
+            .iter()
+            .map(|n| match &n.version {
+                Some(v) => format!("{}@{}", n.name, v),
+                None => n.name.clone(),
+            })
+            .collect();
+        let spec_refs: Vec<&str> = specs.iter().map(String::as_str).collect();
+        let licenses: Vec<Option<String>> = ecosystem
+            .query_many(&spec_refs, project_root)
+            .into_iter()
+            .map(|info| info.ok().and_then(|i| i.license))
+            .collect();
+        Ok(Self::check(&graph, &licenses, policy))
+    }
+
+    /// Check `licenses[i]` (the license of `graph.nodes[i]`) against `policy`.
+    pub fn check(
+        graph: &DependencyGraph,
+        licenses: &[Option<String>],
+        policy: &LicensePolicy,
+    ) -> Self {
+        let paths = graph.root_paths();
+        let mut findings: Vec<LicenseFinding> = graph
+            .nodes
+            .iter()
+            .zip(licenses)
+            .filter_map(|(node, license)| {
+                let verdict = policy.check(license.as_deref());
+                (verdict != LicenseVerdict::Allowed).then(|| LicenseFinding {
+                    package: node.name.clone(),
+                    version: node.version.clone(),
+                    license: license.clone(),
+                    verdict,
+                    path: paths
+                        .path(&node.id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(String::from)
+                        .collect(),
+                })
+            })
+            .collect();
+        findings.sort_by(|a, b| {
+            b.verdict
+                .cmp(&a.verdict)
+                .then_with(|| a.package.cmp(&b.package))
+        });
+        Self {
+            ecosystem: graph.ecosystem.clone(),
+            checked: graph.nodes.len().min(licenses.len()),
+            findings,
+        }
+    }
+
+    /// Whether any finding is denied or not allowed.
+    pub fn has_violations(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.verdict >= LicenseVerdict::NotAllowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DependencyTree, TreeNode};
+
+    fn parse(text: &str) -> SpdxExpr {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        SpdxExpr::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_spdx() {
+        assert_eq!(parse("MIT").to_string(), "MIT");
+        assert_eq!(
+            parse("MIT OR Apache-2.0 AND BSD-3-Clause").to_string(),
+            "MIT OR Apache-2.0 AND BSD-3-Clause"
+        );
+        assert!(matches!(
+            parse("MIT or Apache-2.0 and Zlib"),
+            SpdxExpr::Or(_, b) if matches!(*b, SpdxExpr::And(..))
+        ));
+        assert_eq!(
+            parse("(MIT OR Apache-2.0) AND Unicode-3.0").to_string(),
+            "(MIT OR Apache-2.0) AND Unicode-3.0"
+        );
+        assert_eq!(
+            parse("GPL-2.0+ WITH Classpath-exception-2.0"),
+            SpdxExpr::License {
+                id: "GPL-2.0".to_string(),
+                or_later: true,
+                exception: Some("Classpath-exception-2.0".to_string()),
+            }
+        );
+        assert_eq!(parse("MIT/Apache-2.0").license_ids(), ["MIT", "Apache-2.0"]);
+        assert!(SpdxExpr::parse("MIT OR").is_err());
+        assert!(SpdxExpr::parse("(MIT").is_err());
+        assert!(SpdxExpr::parse("MIT Apache-2.0").is_err());
+    }
+
+    fn policy() -> LicensePolicy {
+        LicensePolicy {
+            allowed: vec!["MIT".into(), "Apache-2.0".into()],
+            denied: vec!["AGPL-3.0-only".into()],
+            needs_review: vec!["MPL-2.0".into()],
+        }
+    }
+
+    #[test]
+    fn test_policy_verdicts() {
+        let policy = policy();
+        assert_eq!(policy.check(Some("mit")), LicenseVerdict::Allowed);
+        assert_eq!(
+            policy.check(Some("MIT OR AGPL-3.0-only")),
+            LicenseVerdict::Allowed
+        );
+        assert_eq!(
+            policy.check(Some("MIT AND MPL-2.0")),
+            LicenseVerdict::NeedsReview
+        );
+        assert_eq!(policy.check(Some("AGPL-3.0-only")), LicenseVerdict::Denied);
+        assert_eq!(policy.check(Some("ISC")), LicenseVerdict::NotAllowed);
+        assert_eq!(policy.check(None), LicenseVerdict::Unknown);
+        assert_eq!(policy.check(Some("MIT AND")), LicenseVerdict::Unknown);
+        // Without an allow list, unlisted licenses are accepted.
+        assert_eq!(
+            LicensePolicy::default().check(Some("ISC")),
+            LicenseVerdict::Allowed
+        );
+    }
+
+    #[test]
+    fn test_audit_reports_path() {
+        let mut graph = DependencyGraph::new("app", "npm");
+        graph.add_tree(&DependencyTree {
+            roots: vec![
+                TreeNode {
+                    name: "web".to_string(),
+                    version: "1.0.0".to_string(),
+                    dependencies: vec![TreeNode {
+                        name: "copyleft".to_string(),
+                        version: "0.1.0".to_string(),
+                        dependencies: Vec::new(),
+                    }],
+                },
+                TreeNode {
+                    name: "copyleft".to_string(),
+                    version: "0.2.0".to_string(),
+                    dependencies: Vec::new(),
+                },
+            ],
+        });
+        let agpl = Some("AGPL-3.0-only".to_string());
+        let licenses = [Some("MIT".to_string()), agpl.clone(), agpl];
+        let audit = LicenseAudit::check(&graph, &licenses, &policy());
+
+        assert_eq!(audit.checked, 3);
+        assert_eq!(audit.findings.len(), 2);
+        assert!(audit.has_violations());
+        let finding = &audit.findings[0];
+        assert_eq!(finding.package, "copyleft");
+        assert_eq!(finding.verdict, LicenseVerdict::Denied);
+        // Each version gets the path to that version, not to the nearest
+        // package of the same name.
+        assert_eq!(finding.path, ["app", "web@1.0.0", "copyleft@0.1.0"]);
+        assert_eq!(audit.findings[1].path, ["app", "copyleft@0.2.0"]);
+    }
+}
@@ -0,0 +1,464 @@
+//! Structured lockfile parsing and diffing.
+//!
+//! Each parser flattens a lockfile into the set of resolved `(name, version)`
+//! pairs it pins, independent of the tree structure. [`diff_lockfiles`] compares
+//! two such sets, e.g. a lockfile before and after a PR.
+
+use crate::PackageError;
+use crate::version::VersionScheme;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Supported lockfile formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockfileFormat {
+    CargoLock,
+    PackageLockJson,
+    PnpmLockYaml,
+    PoetryLock,
+    UvLock,
+    GoSum,
+}
+
+impl LockfileFormat {
+    /// Detect the format from a lockfile's file name.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.lock" => Some(Self::CargoLock),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Self::PackageLockJson),
+            "pnpm-lock.yaml" => Some(Self::PnpmLockYaml),
+            "poetry.lock" => Some(Self::PoetryLock),
+            "uv.lock" => Some(Self::UvLock),
+            "go.sum" => Some(Self::GoSum),
+            _ => None,
+        }
+    }
+
+    /// Ecosystem name this lockfile belongs to (matches [`crate::Ecosystem::name`]).
+    pub fn ecosystem(&self) -> &'static str {
+        match self {
+            Self::CargoLock => "cargo",
+            Self::PackageLockJson | Self::PnpmLockYaml => "npm",
+            Self::PoetryLock | Self::UvLock => "python",
+            Self::GoSum => "go",
+        }
+    }
+}
+
+/// A package pinned by a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+pub struct LockedPackage {
+    /// Package name.
+    pub name: String,
+    /// Resolved version.
+    pub version: String,
+}
+
+/// A parsed lockfile: every resolved package, sorted and deduplicated.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Lockfile {
+    /// Format the lockfile was parsed as.
+    pub format: LockfileFormat,
+    /// Resolved packages.
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Parse lockfile content in the given format.
+    pub fn parse(format: LockfileFormat, content: &str) -> Result<Self, PackageError> {
+        let mut packages = match format {
+            LockfileFormat::CargoLock | LockfileFormat::PoetryLock | LockfileFormat::UvLock => {
+                parse_toml_packages(content)?
+            }
+            LockfileFormat::PackageLockJson => parse_package_lock(content)?,
+            LockfileFormat::PnpmLockYaml => parse_pnpm_lock(content)?,
+            LockfileFormat::GoSum => parse_go_sum(content),
+        };
+        packages.sort();
+        packages.dedup();
+        Ok(Self { format, packages })
+    }
+
+    /// Read and parse a lockfile, detecting the format from its file name.
+    pub fn from_path(path: &Path) -> Result<Self, PackageError> {
+        let format = LockfileFormat::from_path(path).ok_or_else(|| {
+            PackageError::ParseError(format!("unrecognized lockfile: {}", path.display()))
+        })?;
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            PackageError::ParseError(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        Self::parse(format, &content)
+    }
+
+    /// Versions pinned for each package name.
+    fn versions_by_name(&self) -> BTreeMap<&str, BTreeSet<&str>> {
+        let mut map: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for pkg in &self.packages {
+            map.entry(&pkg.name).or_default().insert(&pkg.version);
+        }
+        map
+    }
+}
+
+/// A package whose pinned version changed between two lockfiles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct VersionChange {
+    /// Package name.
+    pub name: String,
+    /// Version in the old lockfile.
+    pub from: String,
+    /// Version in the new lockfile.
+    pub to: String,
+}
+
+/// Differences between two lockfiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LockfileDiff {
+    /// Packages (or additional versions of a package) only in the new lockfile.
+    pub added: Vec<LockedPackage>,
+    /// Packages (or versions) only in the old lockfile.
+    pub removed: Vec<LockedPackage>,
+    /// Packages moved to a newer version.
+    pub upgraded: Vec<VersionChange>,
+    /// Packages moved to an older version.
+    pub downgraded: Vec<VersionChange>,
+}
+
+impl LockfileDiff {
+    /// True if the lockfiles pin the same packages.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.upgraded.is_empty()
+            && self.downgraded.is_empty()
+    }
+}
+
+/// Compare two lockfiles.
+///
+/// A package with exactly one version on each side that differs is reported as
+/// an upgrade or downgrade. When several versions of a package are pinned
+/// (common in Cargo.lock and npm), versions that appear on only one side are
+/// reported as added or removed instead.
+pub fn diff_lockfiles(old: &Lockfile, new: &Lockfile) -> LockfileDiff {
+    let old_map = old.versions_by_name();
+    let new_map = new.versions_by_name();
+    let names: BTreeSet<&str> = old_map.keys().chain(new_map.keys()).copied().collect();
+    let empty = BTreeSet::new();
+    let scheme = VersionScheme::for_ecosystem(new.format.ecosystem());
+
+    let mut diff = LockfileDiff::default();
+    for name in names {
+        let before = old_map.get(name).unwrap_or(&empty);
+        let after = new_map.get(name).unwrap_or(&empty);
+        let gone: Vec<&str> = before.difference(after).copied().collect();
+        let new_versions: Vec<&str> = after.difference(before).copied().collect();
+
+        if let ([from], [to]) = (gone.as_slice(), new_versions.as_slice()) {
+            let change = VersionChange {
+                name: name.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            };
+            if scheme.compare(from, to) == Ordering::Greater {
+                diff.downgraded.push(change);
+            } else {
+                diff.upgraded.push(change);
+            }
+            continue;
+        }
+
+        let locked = |version: &&str| LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        diff.removed.extend(gone.iter().map(locked));
+        diff.added.extend(new_versions.iter().map(locked));
+    }
+    diff
+}
+
+/// Cargo.lock, poetry.lock and uv.lock all use `[[package]]` tables with
+/// `name` and `version` keys.
+fn parse_toml_packages(content: &str) -> Result<Vec<LockedPackage>, PackageError> {
+    let parsed: toml::Value = toml::from_str(content)
+        .map_err(|e| PackageError::ParseError(format!("invalid TOML: {}", e)))?;
+    Ok(parsed
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|pkg| {
+            Some(LockedPackage {
+                name: pkg.get("name")?.as_str()?.to_string(),
+                version: pkg.get("version")?.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// package-lock.json: v2/v3 `packages["node_modules/…"]`, or v1 nested
+/// `dependencies`.
+fn parse_package_lock(content: &str) -> Result<Vec<LockedPackage>, PackageError> {
+    let parsed: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+
+    let mut out = Vec::new();
+    if let Some(pkgs) = parsed.get("packages").and_then(|p| p.as_object()) {
+        for (key, pkg) in pkgs {
+            // Keys are install paths; nested copies look like
+            // "node_modules/a/node_modules/b". The root package has key "".
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                out.push(LockedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    } else if let Some(deps) = parsed.get("dependencies") {
+        collect_v1_dependencies(deps, &mut out);
+    }
+    Ok(out)
+}
+
+fn collect_v1_dependencies(deps: &serde_json::Value, out: &mut Vec<LockedPackage>) {
+    let Some(deps) = deps.as_object() else {
+        return;
+    };
+    for (name, dep) in deps {
+        if let Some(version) = dep.get("version").and_then(|v| v.as_str()) {
+            out.push(LockedPackage {
+                name: name.clone(),
+                version: version.to_string(),
+            });
+        }
+        if let Some(nested) = dep.get("dependencies") {
+            collect_v1_dependencies(nested, out);
+        }
+    }
+}
+
+/// pnpm-lock.yaml `packages` keys: `name@version` (v9), `/name@version` (v6),
+/// or `/name/version` (v5), optionally with a `(peer@x)` suffix.
+fn parse_pnpm_lock(content: &str) -> Result<Vec<LockedPackage>, PackageError> {
+    let parsed: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|e| PackageError::ParseError(format!("invalid YAML: {}", e)))?;
+    Ok(parsed
+        .get("packages")
+        .and_then(|p| p.as_mapping())
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, _)| parse_pnpm_key(key.as_str()?))
+        .collect())
+}
+
+fn parse_pnpm_key(key: &str) -> Option<LockedPackage> {
+    let key = key.split('(').next()?.trim_start_matches('/');
+    if key.is_empty() {
+        return None;
+    }
+    // Skip the leading '@' of a scope when looking for the version separator.
+    let scope = usize::from(key.starts_with('@'));
+    let (name, version) = match key[scope..].rfind('@') {
+        Some(i) => (&key[..scope + i], &key[scope + i + 1..]),
+        None => key.rsplit_once('/')?,
+    };
+    (!name.is_empty() && !version.is_empty()).then(|| LockedPackage {
+        name: name.to_string(),
+        version: version.to_string(),
+    })
+}
+
+/// go.sum lines are `module version hash` or `module version/go.mod hash`.
+/// Only modules with a content hash were actually downloaded for the build; the
+/// `/go.mod`-only entries are versions consulted during version selection.
+fn parse_go_sum(content: &str) -> Vec<LockedPackage> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let (module, version) = (parts.next()?, parts.next()?);
+            (!version.ends_with("/go.mod")).then(|| LockedPackage {
+                name: module.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(pkgs: &[LockedPackage]) -> Vec<String> {
+        pkgs.iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect()
+    }
+
+    fn parse(format: LockfileFormat, content: &str) -> Lockfile {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        Lockfile::parse(format, content).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cargo_lock() {
+        let lock = parse(
+            LockfileFormat::CargoLock,
+            r#"
+version = 3
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+
+[[package]]
+name = "syn"
+version = "2.0.48"
+
+[[package]]
+name = "serde"
+version = "1.0.196"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        );
+        assert_eq!(
+            names(&lock.packages),
+            ["serde@1.0.196", "syn@1.0.109", "syn@2.0.48"]
+        );
+    }
+
+    #[test]
+    fn test_parse_package_lock() {
+        let lock = parse(
+            LockfileFormat::PackageLockJson,
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": {"name": "app", "version": "1.0.0"},
+                    "node_modules/react": {"version": "18.2.0"},
+                    "node_modules/@types/node": {"version": "20.11.0"},
+                    "node_modules/a/node_modules/react": {"version": "17.0.2"}
+                }
+            }"#,
+        );
+        assert_eq!(
+            names(&lock.packages),
+            ["@types/node@20.11.0", "react@17.0.2", "react@18.2.0"]
+        );
+
+        let v1 = parse(
+            LockfileFormat::PackageLockJson,
+            r#"{"dependencies": {"a": {"version": "1.0.0", "dependencies": {"b": {"version": "2.0.0"}}}}}"#,
+        );
+        assert_eq!(names(&v1.packages), ["a@1.0.0", "b@2.0.0"]);
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock() {
+        let lock = parse(
+            LockfileFormat::PnpmLockYaml,
+            r#"
+lockfileVersion: '9.0'
+packages:
+  react@18.2.0:
+    resolution: {integrity: sha512-x}
+  '@types/node@20.11.0':
+    resolution: {integrity: sha512-y}
+  /lodash@4.17.21:
+    resolution: {integrity: sha512-z}
+  /chalk/4.1.2:
+    resolution: {integrity: sha512-w}
+  vitepress@1.6.4(@algolia/client-search@5.0.0):
+    resolution: {integrity: sha512-v}
+"#,
+        );
+        assert_eq!(
+            names(&lock.packages),
+            [
+                "@types/node@20.11.0",
+                "chalk@4.1.2",
+                "lodash@4.17.21",
+                "react@18.2.0",
+                "vitepress@1.6.4"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pnpm_key_malformed() {
+        assert!(parse_pnpm_key("é").is_none());
+        assert!(parse_pnpm_key("@1.0.0").is_none());
+        assert!(parse_pnpm_key("/").is_none());
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let pkg = parse_pnpm_key("ü@1.0.0").unwrap();
+        assert_eq!((pkg.name.as_str(), pkg.version.as_str()), ("ü", "1.0.0"));
+    }
+
+    #[test]
+    fn test_parse_go_sum() {
+        let lock = parse(
+            LockfileFormat::GoSum,
+            "github.com/pkg/errors v0.9.1 h1:abc=\n\
+             github.com/pkg/errors v0.9.1/go.mod h1:def=\n\
+             golang.org/x/sys v0.1.0/go.mod h1:ghi=\n",
+        );
+        assert_eq!(names(&lock.packages), ["github.com/pkg/errors@v0.9.1"]);
+    }
+
+    #[test]
+    fn test_diff_lockfiles() {
+        let old = parse(
+            LockfileFormat::UvLock,
+            r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[[package]]
+name = "urllib3"
+version = "2.2.0"
+
+[[package]]
+name = "chardet"
+version = "5.2.0"
+"#,
+        );
+        let new = parse(
+            LockfileFormat::UvLock,
+            r#"
+[[package]]
+name = "requests"
+version = "2.32.3"
+
+[[package]]
+name = "urllib3"
+version = "1.26.18"
+
+[[package]]
+name = "idna"
+version = "3.7"
+"#,
+        );
+        let diff = diff_lockfiles(&old, &new);
+        assert_eq!(names(&diff.added), ["idna@3.7"]);
+        assert_eq!(names(&diff.removed), ["chardet@5.2.0"]);
+        assert_eq!(
+            diff.upgraded,
+            [VersionChange {
+                name: "requests".into(),
+                from: "2.31.0".into(),
+                to: "2.32.3".into()
+            }]
+        );
+        assert_eq!(diff.downgraded.len(), 1);
+        assert!(diff_lockfiles(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+//! Security advisory lookups against [OSV.dev](https://osv.dev).
+//!
+//! OSV aggregates advisories from GitHub, RustSec, PyPA, Go and others under a
+//! single query API keyed by `(ecosystem, package, version)`. Responses are
+//! cached alongside package info (see `cache`), so repeated audits of the same
+//! dependency set work offline.
+
+use crate::lockfile::{LockedPackage, Lockfile};
+use crate::{AuditResult, Ecosystem, PackageError, Vulnerability, VulnerabilitySeverity, cache};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// Advisories change more often than package metadata; refresh after 6 hours.
+const CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Known vulnerabilities affecting `package` at `version`.
+///
+/// `ecosystem` is the OSV ecosystem name (e.g. `"crates.io"`, `"PyPI"`), as
+/// returned by [`crate::Ecosystem::osv_ecosystem`]. Uses a fresh cache entry if
+/// present, otherwise queries OSV; on network failure a stale entry is used.
+pub fn query(
+    ecosystem: &str,
+    package: &str,
+    version: &str,
+) -> Result<Vec<Vulnerability>, PackageError> {
+    let namespace = format!("osv-{}", ecosystem);
+    let key = format!("{}@{}", package, version);
+
+    if let Some(cached) = cache::read(&namespace, &key, CACHE_TTL) {
+        return Ok(cached);
+    }
+
+    let fetched = fetch_pages(ecosystem, package, version, |body| {
+        crate::batch::registry_limiter("osv").acquire();
+        crate::http::post_json(OSV_QUERY_URL, body)
+    });
+    match fetched {
+        Ok(vulns) => {
+            cache::write(&namespace, &key, &vulns);
+            Ok(vulns)
+        }
+        Err(e) => cache::read_any(&namespace, &key).ok_or(e),
+    }
+}
+
+/// Run an OSV query through `post`, following `next_page_token` until every
+/// page of a large result set has been read.
+fn fetch_pages(
+    ecosystem: &str,
+    package: &str,
+    version: &str,
+    mut post: impl FnMut(&serde_json::Value) -> Result<String, PackageError>,
+) -> Result<Vec<Vulnerability>, PackageError> {
+    let mut vulns = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut body = serde_json::json!({
+            "package": { "name": package, "ecosystem": ecosystem },
+            "version": version,
+        });
+        if let Some(token) = page_token {
+            body["page_token"] = serde_json::Value::String(token);
+        }
+        let page = parse_response(&post(&body)?, package, version)?;
+        vulns.extend(page.vulns);
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => return Ok(vulns),
+        }
+    }
+}
+
+/// Audit a project's declared dependencies against OSV.
+///
+/// For ecosystems without a native audit tool (Maven, NuGet). Each dependency
+/// is checked at its locked version when a lockfile records one, otherwise at
+/// its declared version if that is an exact version rather than a range.
+pub fn audit_declared(
+    ecosystem: &dyn Ecosystem,
+    project_root: &Path,
+) -> Result<AuditResult, PackageError> {
+    let mut vulnerabilities = Vec::new();
+    for dep in ecosystem.list_dependencies(project_root)? {
+        let name = dep.effective_name();
+        let version = ecosystem
+            .installed_version(name, project_root)
+            .or_else(|| dep.version_req.clone().filter(|v| is_exact_version(v)));
+        if let Some(version) = version {
+            vulnerabilities.extend(ecosystem.audit_package(name, &version)?);
+        }
+    }
+    Ok(AuditResult { vulnerabilities })
+}
+
+/// Advisories affecting one package pinned by a lockfile.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PackageAdvisories {
+    /// Package name.
+    pub package: String,
+    /// Locked version that was checked.
+    pub version: String,
+    /// Advisories affecting this version.
+    pub advisories: Vec<Vulnerability>,
+}
+
+/// Every package pinned by a lockfile, checked against OSV.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockfileAudit {
+    /// Ecosystem the lockfile belongs to (e.g. "cargo", "npm").
+    pub ecosystem: String,
+    /// Number of locked packages that were looked up successfully.
+    pub checked: usize,
+    /// Packages with at least one advisory, in lockfile order.
+    pub affected: Vec<PackageAdvisories>,
+    /// Lookups that failed, as `name@version: error`.
+    pub errors: Vec<String>,
+}
+
+impl LockfileAudit {
+    /// All advisories across affected packages.
+    pub fn vulnerabilities(&self) -> impl Iterator<Item = &Vulnerability> {
+        self.affected.iter().flat_map(|p| &p.advisories)
+    }
+}
+
+/// Audit every package pinned by `lockfile` against OSV.
+///
+/// Unlike [`audit_declared`], this covers the full transitive set at exactly
+/// the versions the lockfile resolves. Individual lookup failures are recorded
+/// in [`LockfileAudit::errors`]; an error is returned only if every lookup
+/// failed.
+pub fn audit_lockfile(lockfile: &Lockfile) -> Result<LockfileAudit, PackageError> {
+    let name = lockfile.format.ecosystem();
+    let ecosystem = crate::get_ecosystem(name)
+        .ok_or_else(|| PackageError::RegistryError(format!("unknown ecosystem: {}", name)))?;
+    let osv_ecosystem = ecosystem
+        .osv_ecosystem()
+        .ok_or_else(|| PackageError::RegistryError(format!("no advisory database for {}", name)))?;
+    audit_packages(name, &lockfile.packages, |pkg, ver| {
+        query(osv_ecosystem, pkg, ver)
+    })
+}
+
+/// Look up each package with `lookup` (concurrently) and aggregate the results.
+fn audit_packages(
+    ecosystem: &str,
+    packages: &[LockedPackage],
+    lookup: impl Fn(&str, &str) -> Result<Vec<Vulnerability>, PackageError> + Sync,
+) -> Result<LockfileAudit, PackageError> {
+    let results = crate::batch::run_bounded(packages, crate::batch::MAX_CONCURRENCY, |pkg| {
+        lookup(&pkg.name, &pkg.version)
+    });
+    let mut audit = LockfileAudit {
+        ecosystem: ecosystem.to_string(),
+        checked: 0,
+        affected: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut last_err = None;
+    for (pkg, result) in packages.iter().zip(results) {
+        match result {
+            Ok(advisories) => {
+                audit.checked += 1;
+                if !advisories.is_empty() {
+                    audit.affected.push(PackageAdvisories {
+                        package: pkg.name.clone(),
+                        version: pkg.version.clone(),
+                        advisories,
+                    });
+                }
+            }
+            Err(e) => {
+                audit
+                    .errors
+                    .push(format!("{}@{}: {}", pkg.name, pkg.version, e));
+                last_err = Some(e);
+            }
+        }
+    }
+    match last_err {
+        Some(e) if audit.checked == 0 => Err(e),
+        _ => Ok(audit),
+    }
+}
+
+/// Whether a declared version is a single concrete version (`1.2.3`,
+/// `2.0.0-beta1`), not a range (`[1.0,2.0)`, `^1`, `1.+`) or a property
+/// reference (`${foo.version}`).
+fn is_exact_version(v: &str) -> bool {
+    v.starts_with(|c: char| c.is_ascii_digit())
+        && !v.contains(['[', '(', ',', '*', '+', '$', ' ', '^', '~', '<', '>', '='])
+}
+
+/// One page of an OSV `/v1/query` response.
+struct ResponsePage {
+    vulns: Vec<Vulnerability>,
+    /// Set when more results remain; pass it back as `page_token`.
+    next_page_token: Option<String>,
+}
+
+/// Parse an OSV `/v1/query` response body into vulnerabilities.
+fn parse_response(body: &str, package: &str, version: &str) -> Result<ResponsePage, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+
+    let next_page_token = v
+        .get("next_page_token")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.is_empty())
+        .map(String::from);
+    // OSV returns `{}` when nothing matches.
+    let vulns = v
+        .get("vulns")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let vulns = vulns
+        .iter()
+        .map(|vuln| {
+            let id = vuln.get("id").and_then(|i| i.as_str()).unwrap_or_default();
+            let aliases: Vec<&str> = vuln
+                .get("aliases")
+                .and_then(|a| a.as_array())
+                .map(|a| a.iter().filter_map(|s| s.as_str()).collect())
+                .unwrap_or_default();
+            let cve = std::iter::once(id)
+                .chain(aliases.iter().copied())
+                .find(|a| a.starts_with("CVE-"))
+                .map(String::from);
+            let title = vuln
+                .get("summary")
+                .and_then(|s| s.as_str())
+                .filter(|s| !s.is_empty())
+                .unwrap_or(id)
+                .to_string();
+
+            Vulnerability {
+                package: package.to_string(),
+                version: version.to_string(),
+                severity: severity(vuln),
+                title,
+                url: Some(format!("https://osv.dev/vulnerability/{}", id)),
+                cve,
+                fixed_in: fixed_version(vuln, package),
+            }
+        })
+        .collect();
+    Ok(ResponsePage {
+        vulns,
+        next_page_token,
+    })
+}
+
+/// Severity from `database_specific.severity` (GHSA-style labels).
+///
+/// OSV's top-level `severity` field only carries CVSS vectors, which would need
+/// a full CVSS calculator to bucket; the database label is used instead.
+fn severity(vuln: &serde_json::Value) -> VulnerabilitySeverity {
+    let label = vuln
+        .get("database_specific")
+        .and_then(|d| d.get("severity"))
+        .and_then(|s| s.as_str())
+        .unwrap_or("");
+    match label.to_ascii_lowercase().as_str() {
+        "critical" => VulnerabilitySeverity::Critical,
+        "high" => VulnerabilitySeverity::High,
+        "moderate" | "medium" => VulnerabilitySeverity::Medium,
+        "low" => VulnerabilitySeverity::Low,
+        _ => VulnerabilitySeverity::Unknown,
+    }
+}
+
+/// First `fixed` event in the affected ranges for `package`.
+fn fixed_version(vuln: &serde_json::Value, package: &str) -> Option<String> {
+    vuln.get("affected")?
+        .as_array()?
+        .iter()
+        .filter(|a| {
+            a.get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .is_none_or(|n| n == package)
+        })
+        .filter_map(|a| a.get("ranges")?.as_array())
+        .flatten()
+        .filter_map(|r| r.get("events")?.as_array())
+        .flatten()
+        .find_map(|e| e.get("fixed")?.as_str().map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let body = r#"{
+            "vulns": [{
+                "id": "GHSA-xxxx-yyyy-zzzz",
+                "summary": "Prototype pollution in lodash",
+                "aliases": ["CVE-2020-8203"],
+                "database_specific": {"severity": "HIGH"},
+                "affected": [{
+                    "package": {"name": "lodash", "ecosystem": "npm"},
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.19"}]}]
+                }]
+            }, {
+                "id": "RUSTSEC-2020-0001",
+                "affected": []
+            }]
+        }"#;
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let vulns = parse_response(body, "lodash", "4.17.15").unwrap().vulns;
+        assert_eq!(vulns.len(), 2);
+
+        let v = &vulns[0];
+        assert_eq!(v.package, "lodash");
+        assert_eq!(v.version, "4.17.15");
+        assert_eq!(v.title, "Prototype pollution in lodash");
+        assert_eq!(v.cve.as_deref(), Some("CVE-2020-8203"));
+        assert_eq!(v.fixed_in.as_deref(), Some("4.17.19"));
+        assert_eq!(v.severity, VulnerabilitySeverity::High);
+        assert_eq!(
+            v.url.as_deref(),
+            Some("https://osv.dev/vulnerability/GHSA-xxxx-yyyy-zzzz")
+        );
+
+        // Missing summary falls back to the advisory id.
+        assert_eq!(vulns[1].title, "RUSTSEC-2020-0001");
+        assert_eq!(vulns[1].severity, VulnerabilitySeverity::Unknown);
+        assert!(vulns[1].fixed_in.is_none());
+    }
+
+    #[test]
+    fn test_is_exact_version() {
+        assert!(is_exact_version("1.2.3"));
+        assert!(is_exact_version("2.0.0-beta1"));
+        assert!(!is_exact_version("[1.0,2.0)"));
+        assert!(!is_exact_version("${jackson.version}"));
+        assert!(!is_exact_version("1.+"));
+        assert!(!is_exact_version("^1.0"));
+    }
+
+    #[test]
+    fn test_audit_packages() {
+        let locked = |name: &str, version: &str| LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        let packages = [
+            locked("lodash", "4.17.15"),
+            locked("left-pad", "1.3.0"),
+            locked("broken", "0.0.1"),
+        ];
+        let result = audit_packages("npm", &packages, |name, version| match name {
+            "lodash" => parse_response(
+                r#"{"vulns": [{"id": "GHSA-p6mc-m468-83gw", "summary": "Prototype pollution"}]}"#,
+                name,
+                version,
+            )
+            .map(|page| page.vulns),
+            "broken" => Err(PackageError::NotFound(name.to_string())),
+            _ => Ok(Vec::new()),
+        });
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let audit = result.unwrap();
+
+        assert_eq!(audit.ecosystem, "npm");
+        assert_eq!(audit.checked, 2);
+        assert_eq!(audit.affected.len(), 1);
+        assert_eq!(audit.affected[0].package, "lodash");
+        assert_eq!(audit.affected[0].version, "4.17.15");
+        assert_eq!(audit.vulnerabilities().count(), 1);
+        assert_eq!(audit.errors.len(), 1);
+        assert!(audit.errors[0].starts_with("broken@0.0.1: "));
+
+        // Every lookup failing is an error rather than a clean report.
+        let all_failed = audit_packages("npm", &packages, |name, _| {
+            Err(PackageError::NotFound(name.to_string()))
+        });
+        assert!(all_failed.is_err());
+    }
+
+    #[test]
+    fn test_parse_empty_response() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let page = parse_response("{}", "serde", "1.0.0").unwrap();
+        assert!(page.vulns.is_empty());
+        assert!(page.next_page_token.is_none());
+    }
+
+    #[test]
+    fn test_fetch_pages_follows_page_token() {
+        let mut requests = Vec::new();
+        let result = fetch_pages("npm", "lodash", "4.17.15", |body| {
+            requests.push(body.clone());
+            Ok(match body.get("page_token").and_then(|t| t.as_str()) {
+                None => r#"{"vulns": [{"id": "GHSA-1"}], "next_page_token": "p2"}"#,
+                Some("p2") => r#"{"vulns": [{"id": "GHSA-2"}]}"#,
+                Some(other) => panic!("unexpected page token {other}"),
+            }
+            .to_string())
+        });
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let vulns = result.unwrap();
+        let titles: Vec<_> = vulns.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(titles, ["GHSA-1", "GHSA-2"]);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1]["package"]["name"], "lodash");
+    }
+}
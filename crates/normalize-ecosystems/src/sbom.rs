@@ -0,0 +1,447 @@
+//! Software bill of materials (SBOM) export.
+//!
+//! Builds an [`Sbom`] from the merged [`DependencyGraph`] (lockfile, local
+//! installs and registry metadata), optionally enriched with registry metadata (licenses, descriptions,
+//! repository URLs), and renders it as CycloneDX 1.5 or SPDX 2.3 JSON.
+
+use crate::Ecosystem;
+use crate::graph::DependencyGraph;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+/// SBOM output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON.
+    CycloneDx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
+impl std::str::FromStr for SbomFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Ok(Self::CycloneDx),
+            "spdx" => Ok(Self::Spdx),
+            other => Err(format!(
+                "unknown SBOM format '{}' (expected cyclonedx or spdx)",
+                other
+            )),
+        }
+    }
+}
+
+/// A package listed in an SBOM.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SbomComponent {
+    /// Package name.
+    pub name: String,
+    /// Installed version, if known.
+    pub version: Option<String>,
+    /// Package URL (`pkg:cargo/serde@1.0.0`), if the ecosystem has a purl type.
+    pub purl: Option<String>,
+    /// SPDX license expression from registry metadata.
+    pub license: Option<String>,
+    /// Short description from registry metadata.
+    pub description: Option<String>,
+    /// Homepage URL from registry metadata.
+    pub homepage: Option<String>,
+    /// Source repository URL from registry metadata.
+    pub repository: Option<String>,
+    /// Whether the project declares this package directly.
+    pub direct: bool,
+}
+
+impl SbomComponent {
+    /// `name@version`, or `name` when the version is unknown; the graph node id.
+    fn key(&self) -> String {
+        match &self.version {
+            Some(v) => format!("{}@{}", self.name, v),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Identifier used to cross-reference components within the document.
+    fn bom_ref(&self) -> String {
+        self.purl.clone().unwrap_or_else(|| self.key())
+    }
+}
+
+/// A bill of materials for one project.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Sbom {
+    /// The project the SBOM describes.
+    pub root: SbomComponent,
+    /// Ecosystem of the project manifest (e.g. `"cargo"`).
+    pub ecosystem: String,
+    /// Every dependency, direct and transitive.
+    pub components: Vec<SbomComponent>,
+    /// `(package, dependencies)` pairs, by `name@version` (the project by name).
+    pub dependencies: Vec<(String, Vec<String>)>,
+}
+
+impl Sbom {
+    /// Build an SBOM from a merged dependency graph without registry metadata.
+    pub fn from_graph(graph: &DependencyGraph, purl_type: Option<&str>) -> Self {
+        let component = |name: &str, version: Option<&String>, direct: bool| SbomComponent {
+            name: name.to_string(),
+            version: version.cloned(),
+            purl: purl_type.map(|t| purl(t, name, version.map(String::as_str))),
+            license: None,
+            description: None,
+            homepage: None,
+            repository: None,
+            direct,
+        };
+
+        let mut dependencies: Vec<(String, Vec<String>)> = Vec::new();
+        for edge in &graph.edges {
+            let i = match dependencies.iter().position(|(from, _)| *from == edge.from) {
+                Some(i) => i,
+                None => {
+                    dependencies.push((edge.from.clone(), Vec::new()));
+                    dependencies.len() - 1
+                }
+            };
+            let deps = &mut dependencies[i].1;
+            if !deps.contains(&edge.to) {
+                deps.push(edge.to.clone());
+            }
+        }
+
+        Self {
+            root: component(&graph.root, None, false),
+            ecosystem: graph.ecosystem.to_string(),
+            components: graph
+                .nodes
+                .iter()
+                .map(|n| component(&n.name, n.version.as_ref(), n.direct))
+                .collect(),
+            dependencies,
+        }
+    }
+
+    /// Fill in license, description and URLs from the ecosystem's registry.
+    ///
+    /// Uses batched, cached queries; components whose lookup fails are left
+    /// without metadata.
+    pub fn enrich(&mut self, ecosystem: &dyn Ecosystem, project_root: &Path) {
+        let queries: Vec<String> = self.components.iter().map(SbomComponent::key).collect();
+        let queries: Vec<&str> = queries.iter().map(String::as_str).collect();
+        let results = ecosystem.query_many(&queries, project_root);
+        for (component, result) in self.components.iter_mut().zip(results) {
+            if let Ok(info) = result {
+                component.license = info.license;
+                component.description = info.description;
+                component.homepage = info.homepage;
+                component.repository = info.repository;
+            }
+        }
+    }
+
+    /// Render the SBOM in the given format.
+    pub fn render(&self, format: SbomFormat) -> Value {
+        match format {
+            SbomFormat::CycloneDx => self.to_cyclonedx(),
+            SbomFormat::Spdx => self.to_spdx(),
+        }
+    }
+
+    /// CycloneDX 1.5 JSON document.
+    pub fn to_cyclonedx(&self) -> Value {
+        let refs = self.refs_by_key();
+        let component = |c: &SbomComponent, kind: &str| {
+            let mut obj = json!({
+                "type": kind,
+                "bom-ref": c.bom_ref(),
+                "name": c.name,
+            });
+            if let Some(v) = &c.version {
+                obj["version"] = json!(v);
+            }
+            if let Some(p) = &c.purl {
+                obj["purl"] = json!(p);
+            }
+            if let Some(d) = &c.description {
+                obj["description"] = json!(d);
+            }
+            if let Some(l) = &c.license {
+                obj["licenses"] = json!([{ "expression": l }]);
+            }
+            let mut external = Vec::new();
+            if let Some(url) = &c.repository {
+                external.push(json!({ "type": "vcs", "url": url }));
+            }
+            if let Some(url) = &c.homepage {
+                external.push(json!({ "type": "website", "url": url }));
+            }
+            if !external.is_empty() {
+                obj["externalReferences"] = json!(external);
+            }
+            obj
+        };
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "serialNumber": format!("urn:uuid:{}", self.uuid()),
+            "version": 1,
+            "metadata": {
+                "timestamp": timestamp(),
+                "tools": {
+                    "components": [{
+                        "type": "application",
+                        "name": "normalize",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    }],
+                },
+                "component": component(&self.root, "application"),
+            },
+            "components": self
+                .components
+                .iter()
+                .map(|c| component(c, "library"))
+                .collect::<Vec<_>>(),
+            "dependencies": self
+                .dependencies
+                .iter()
+                .map(|(from, to)| json!({
+                    "ref": refs.get(from.as_str()).cloned().unwrap_or_else(|| from.clone()),
+                    "dependsOn": to
+                        .iter()
+                        .map(|t| refs.get(t.as_str()).cloned().unwrap_or_else(|| t.clone()))
+                        .collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// SPDX 2.3 JSON document.
+    pub fn to_spdx(&self) -> Value {
+        let root_id = spdx_id(&self.root);
+        let package = |c: &SbomComponent| {
+            let license = c.license.as_deref().unwrap_or("NOASSERTION");
+            let mut obj = json!({
+                "SPDXID": spdx_id(c),
+                "name": c.name,
+                "downloadLocation": c.repository.as_deref().unwrap_or("NOASSERTION"),
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": license,
+                "copyrightText": "NOASSERTION",
+            });
+            if let Some(v) = &c.version {
+                obj["versionInfo"] = json!(v);
+            }
+            if let Some(d) = &c.description {
+                obj["summary"] = json!(d);
+            }
+            if let Some(h) = &c.homepage {
+                obj["homepage"] = json!(h);
+            }
+            if let Some(p) = &c.purl {
+                obj["externalRefs"] = json!([{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": p,
+                }]);
+            }
+            obj
+        };
+
+        let ids: HashMap<String, String> = std::iter::once(&self.root)
+            .chain(&self.components)
+            .map(|c| (c.key(), spdx_id(c)))
+            .collect();
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": root_id,
+        })];
+        for (from, to) in &self.dependencies {
+            let Some(from_id) = ids.get(from.as_str()) else {
+                continue;
+            };
+            for dep in to {
+                if let Some(to_id) = ids.get(dep.as_str()) {
+                    relationships.push(json!({
+                        "spdxElementId": from_id,
+                        "relationshipType": "DEPENDS_ON",
+                        "relatedSpdxElement": to_id,
+                    }));
+                }
+            }
+        }
+
+        json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": self.root.name,
+            "documentNamespace": format!(
+                "https://spdx.org/spdxdocs/{}-{}",
+                sanitize_id(&self.root.name),
+                self.uuid()
+            ),
+            "creationInfo": {
+                "created": timestamp(),
+                "creators": [format!("Tool: normalize-{}", env!("CARGO_PKG_VERSION"))],
+            },
+            "packages": std::iter::once(&self.root)
+                .chain(&self.components)
+                .map(package)
+                .collect::<Vec<_>>(),
+            "relationships": relationships,
+        })
+    }
+
+    fn refs_by_key(&self) -> HashMap<String, String> {
+        std::iter::once(&self.root)
+            .chain(&self.components)
+            .map(|c| (c.key(), c.bom_ref()))
+            .collect()
+    }
+
+    /// Document identifier: a version-4-shaped UUID derived from the contents
+    /// and creation time, so distinct exports get distinct identifiers.
+    fn uuid(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.root.name.hash(&mut hasher);
+        timestamp().hash(&mut hasher);
+        let hi = hasher.finish();
+        for c in &self.components {
+            c.bom_ref().hash(&mut hasher);
+        }
+        let lo = hasher.finish();
+        format!(
+            "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+            hi >> 32,
+            (hi >> 16) & 0xffff,
+            hi & 0x0fff,
+            0x8000 | ((lo >> 48) & 0x3fff),
+            lo & 0xffff_ffff_ffff
+        )
+    }
+}
+
+/// Build a package URL. Scoped npm names are percent-encoded; Maven
+/// `group:artifact` coordinates become `group/artifact`.
+pub fn purl(purl_type: &str, name: &str, version: Option<&str>) -> String {
+    let name = match purl_type {
+        "npm" => name.replacen('@', "%40", 1),
+        "maven" => name.replacen(':', "/", 1),
+        "pypi" => name.to_ascii_lowercase().replace('_', "-"),
+        _ => name.to_string(),
+    };
+    match version {
+        Some(v) => format!("pkg:{}/{}@{}", purl_type, name, v),
+        None => format!("pkg:{}/{}", purl_type, name),
+    }
+}
+
+/// SPDX element id: `SPDXRef-` followed by letters, digits, `.` and `-` only.
+fn spdx_id(c: &SbomComponent) -> String {
+    let id = match &c.version {
+        Some(v) => format!("{}-{}", c.name, v),
+        None => c.name.clone(),
+    };
+    format!("SPDXRef-Package-{}", sanitize_id(&id))
+}
+
+fn sanitize_id(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new("app", "cargo");
+        graph.add_tree(&crate::DependencyTree {
+            roots: vec![crate::TreeNode {
+                name: "serde_json".to_string(),
+                version: "1.0.1".to_string(),
+                dependencies: vec![crate::TreeNode {
+                    name: "serde".to_string(),
+                    version: "1.0.200".to_string(),
+                    dependencies: vec![],
+                }],
+            }],
+        });
+        graph
+    }
+
+    #[test]
+    fn test_purl() {
+        assert_eq!(
+            purl("cargo", "serde", Some("1.0.0")),
+            "pkg:cargo/serde@1.0.0"
+        );
+        assert_eq!(
+            purl("npm", "@types/node", Some("20.0.0")),
+            "pkg:npm/%40types/node@20.0.0"
+        );
+        assert_eq!(
+            purl("maven", "com.google.guava:guava", None),
+            "pkg:maven/com.google.guava/guava"
+        );
+        assert_eq!(
+            purl("pypi", "Typing_Extensions", None),
+            "pkg:pypi/typing-extensions"
+        );
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let mut sbom = Sbom::from_graph(&graph(), Some("cargo"));
+        sbom.components[0].license = Some("MIT OR Apache-2.0".to_string());
+        let doc = sbom.to_cyclonedx();
+        assert_eq!(doc["bomFormat"], "CycloneDX");
+        assert_eq!(doc["metadata"]["component"]["name"], "app");
+        assert_eq!(doc["components"][0]["purl"], "pkg:cargo/serde_json@1.0.1");
+        assert_eq!(
+            doc["components"][0]["licenses"][0]["expression"],
+            "MIT OR Apache-2.0"
+        );
+        assert_eq!(doc["dependencies"][1]["ref"], "pkg:cargo/serde_json@1.0.1");
+        assert_eq!(
+            doc["dependencies"][1]["dependsOn"][0],
+            "pkg:cargo/serde@1.0.200"
+        );
+    }
+
+    #[test]
+    fn test_spdx() {
+        let doc = Sbom::from_graph(&graph(), Some("cargo")).to_spdx();
+        assert_eq!(doc["spdxVersion"], "SPDX-2.3");
+        assert_eq!(doc["packages"].as_array().map(Vec::len), Some(3));
+        assert_eq!(
+            doc["packages"][1]["SPDXID"],
+            "SPDXRef-Package-serde-json-1.0.1"
+        );
+        assert_eq!(doc["packages"][1]["licenseDeclared"], "NOASSERTION");
+        let rels = doc["relationships"].as_array().map(Vec::len);
+        // DESCRIBES + app→serde_json + serde_json→serde
+        assert_eq!(rels, Some(3));
+    }
+}
@@ -50,6 +50,14 @@ pub fn fetch_and_extract(
         return Ok(target);
     }
 
+    // Serialize concurrent fetches of the same version so only one downloads;
+    // the others wait and then take the cache hit below.
+    let _lock = normalize_cache::CacheLock::exclusive(&target)
+        .map_err(|e| PackageError::ParseError(format!("failed to lock cache dir: {}", e)))?;
+    if target.is_dir() {
+        return Ok(target);
+    }
+
     if let Some(parent) = target.parent() {
         std::fs::create_dir_all(parent)
             .map_err(|e| PackageError::ParseError(format!("failed to create cache dir: {}", e)))?;
@@ -0,0 +1,702 @@
+//! Version parsing, ordering and requirement matching.
+//!
+//! One implementation shared by every caller that compares versions (outdated
+//! checks, lockfile diffs, dependency resolution), covering the three
+//! requirement dialects in use across ecosystems:
+//!
+//! - [`VersionScheme::Semver`] — Cargo: `1.2` (caret), `^`, `~`, `=`, `<`/`>`,
+//!   `*`/`x` wildcards, comma-separated.
+//! - [`VersionScheme::Npm`] — npm/Composer: `||` alternatives, space-separated
+//!   comparators, hyphen ranges (`1.2 - 2.3`), bare versions are exact.
+//! - [`VersionScheme::Pep440`] — Python: `~=`, `==`/`!=` with `.*` wildcards,
+//!   `===`, epochs, `aN`/`bN`/`rcN`/`.postN`/`.devN` suffixes.
+//!
+//! All schemes also accept Ruby/Elixir `~>` (pessimistic) requirements.
+//! Pre-releases only satisfy a requirement that itself mentions a pre-release
+//! (of the same release, for semver), matching Cargo, npm and pip.
+
+use crate::PackageError;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Requirement syntax and version grammar to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionScheme {
+    Semver,
+    Npm,
+    Pep440,
+}
+
+impl VersionScheme {
+    /// Scheme for an ecosystem name ([`crate::Ecosystem::name`]).
+    pub fn for_ecosystem(name: &str) -> Self {
+        match name {
+            "npm" | "deno" | "composer" => Self::Npm,
+            "python" => Self::Pep440,
+            _ => Self::Semver,
+        }
+    }
+
+    /// Whether `version` satisfies `req`. Unparseable input never matches.
+    pub fn satisfies(self, version: &str, req: &str) -> bool {
+        match (Version::parse(self, version), VersionReq::parse(self, req)) {
+            (Ok(v), Ok(r)) => r.matches(&v),
+            _ => false,
+        }
+    }
+
+    /// The highest version in `versions` that satisfies `req`.
+    pub fn max_satisfying<'a>(self, versions: &[&'a str], req: &str) -> Option<&'a str> {
+        let req = VersionReq::parse(self, req).ok()?;
+        versions
+            .iter()
+            .filter_map(|s| Some((*s, Version::parse(self, s).ok()?)))
+            .filter(|(_, v)| req.matches(v))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(s, _)| s)
+    }
+
+    /// Order two version strings. Falls back to string comparison when either
+    /// does not parse.
+    pub fn compare(self, a: &str, b: &str) -> Ordering {
+        match (Version::parse(self, a), Version::parse(self, b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        }
+    }
+}
+
+/// One identifier of a pre-release tag (`alpha`, `1`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreIdent {
+    // Declared first: numeric identifiers sort before alphanumeric ones.
+    Num(u64),
+    Alpha(String),
+}
+
+/// A parsed version.
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Vec<PreIdent>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    /// Synthesized range bound (see [`Version::floor`]); never opts a
+    /// requirement in to pre-releases.
+    bound: bool,
+}
+
+impl Version {
+    /// Parse a version in the given scheme. A leading `v` is accepted; build
+    /// metadata / local version labels (`+…`) are ignored.
+    pub fn parse(scheme: VersionScheme, s: &str) -> Result<Self, PackageError> {
+        let err = || PackageError::ParseError(format!("invalid version: {}", s));
+        let s = s.trim();
+        let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+        let s = s.split_once('+').map_or(s, |(v, _)| v);
+
+        let (epoch, s) = match s.split_once('!') {
+            Some((e, rest)) if scheme == VersionScheme::Pep440 => {
+                (e.parse().map_err(|_| err())?, rest)
+            }
+            _ => (0, s),
+        };
+
+        let release_end = s
+            .char_indices()
+            .find(|&(i, c)| {
+                !(c.is_ascii_digit()
+                    || (c == '.' && s[i + 1..].starts_with(|d: char| d.is_ascii_digit())))
+            })
+            .map_or(s.len(), |(i, _)| i);
+        let release: Vec<u64> = s[..release_end]
+            .split('.')
+            .map(|p| p.parse().map_err(|_| err()))
+            .collect::<Result<_, _>>()?;
+        let rest = &s[release_end..];
+
+        let mut version = Version {
+            epoch,
+            release,
+            pre: Vec::new(),
+            post: None,
+            dev: None,
+            bound: false,
+        };
+        match scheme {
+            VersionScheme::Semver | VersionScheme::Npm => {
+                if let Some(pre) = rest.strip_prefix('-') {
+                    version.pre = pre.split('.').map(pre_ident).collect();
+                } else if !rest.is_empty() {
+                    return Err(err());
+                }
+            }
+            VersionScheme::Pep440 => parse_pep440_suffix(&mut version, rest).ok_or_else(err)?,
+        }
+        Ok(version)
+    }
+
+    /// Whether this is a pre-release (or PEP 440 dev release).
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty() || self.dev.is_some()
+    }
+
+    fn release_part(&self, i: usize) -> u64 {
+        self.release.get(i).copied().unwrap_or(0)
+    }
+
+    fn cmp_release(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        (0..len)
+            .map(|i| self.release_part(i).cmp(&other.release_part(i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn from_release(release: Vec<u64>) -> Self {
+        Version {
+            epoch: 0,
+            release,
+            pre: Vec::new(),
+            post: None,
+            dev: None,
+            bound: false,
+        }
+    }
+
+    /// Lowest version with this release, below every pre-release of it; used
+    /// as an exclusive upper bound so `<2.0.0` also excludes `2.0.0-alpha`.
+    fn floor(release: Vec<u64>) -> Self {
+        Version {
+            dev: Some(0),
+            bound: true,
+            ..Self::from_release(release)
+        }
+    }
+}
+
+fn pre_ident(s: &str) -> PreIdent {
+    s.parse()
+        .map(PreIdent::Num)
+        .unwrap_or_else(|_| PreIdent::Alpha(s.to_string()))
+}
+
+/// PEP 440 suffixes: `[sep](a|b|rc)N`, `[sep](post|rev|r)N` or `-N`,
+/// `[sep]devN`, with the spelling variants PEP 440 normalizes.
+fn parse_pep440_suffix(version: &mut Version, rest: &str) -> Option<()> {
+    let mut rest = rest.to_ascii_lowercase();
+    while !rest.is_empty() {
+        let sep = rest.starts_with(['.', '-', '_']);
+        let body = if sep { &rest[1..] } else { &rest[..] };
+        let word_end = body
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(body.len());
+        let (word, tail) = body.split_at(word_end);
+        let tail = tail.strip_prefix(['.', '-', '_']).unwrap_or(tail);
+        let num_end = tail
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let num: u64 = tail[..num_end].parse().unwrap_or(0);
+        let next = tail[num_end..].to_string();
+
+        match word {
+            "a" | "alpha" if version.pre.is_empty() && version.post.is_none() => {
+                version.pre = vec![PreIdent::Alpha("a".into()), PreIdent::Num(num)];
+            }
+            "b" | "beta" if version.pre.is_empty() && version.post.is_none() => {
+                version.pre = vec![PreIdent::Alpha("b".into()), PreIdent::Num(num)];
+            }
+            "rc" | "c" | "pre" | "preview" if version.pre.is_empty() && version.post.is_none() => {
+                version.pre = vec![PreIdent::Alpha("rc".into()), PreIdent::Num(num)];
+            }
+            "post" | "rev" | "r" if version.post.is_none() => version.post = Some(num),
+            "" if sep && rest.starts_with('-') && num_end > 0 && version.post.is_none() => {
+                version.post = Some(num)
+            }
+            "dev" if version.dev.is_none() => version.dev = Some(num),
+            _ => return None,
+        }
+        rest = next;
+    }
+    Some(())
+}
+
+impl Ord for Version {
+    /// PEP 440 ordering, which reduces to semver ordering when there are no
+    /// post/dev parts: `1.0.dev0 < 1.0a1 < 1.0 < 1.0.post1`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Dev-only releases sort before any pre-release of the same version.
+        let pre_key = |v: &Version| match (v.pre.is_empty(), v.dev, v.post) {
+            (true, Some(_), None) => 0,
+            (false, _, _) => 1,
+            _ => 2,
+        };
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| self.cmp_release(other))
+            .then_with(|| pre_key(self).cmp(&pre_key(other)))
+            .then_with(|| self.pre.cmp(&other.pre))
+            .then_with(|| self.post.map(|p| p + 1).cmp(&other.post.map(|p| p + 1)))
+            .then_with(|| match (self.dev, other.dev) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(&b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    /// Release starts with the comparator's release (`==1.2.*`).
+    Prefix,
+    /// Release does not start with it (`!=1.2.*`).
+    NotPrefix,
+}
+
+#[derive(Debug, Clone)]
+struct Comparator {
+    op: Op,
+    version: Version,
+}
+
+impl Comparator {
+    fn matches(&self, v: &Version) -> bool {
+        let prefix = || {
+            v.epoch == self.version.epoch
+                && self
+                    .version
+                    .release
+                    .iter()
+                    .enumerate()
+                    .all(|(i, &p)| v.release_part(i) == p)
+        };
+        match self.op {
+            Op::Eq => *v == self.version,
+            Op::Ne => *v != self.version,
+            Op::Gt => *v > self.version,
+            Op::Ge => *v >= self.version,
+            Op::Lt => *v < self.version,
+            Op::Le => *v <= self.version,
+            Op::Prefix => prefix(),
+            Op::NotPrefix => !prefix(),
+        }
+    }
+}
+
+/// A parsed version requirement: alternatives (`||`) of comparator sets.
+#[derive(Debug, Clone)]
+pub struct VersionReq {
+    scheme: VersionScheme,
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionReq {
+    /// Parse a requirement in the given scheme's syntax.
+    pub fn parse(scheme: VersionScheme, req: &str) -> Result<Self, PackageError> {
+        let alternatives = match scheme {
+            VersionScheme::Npm => req
+                .split("||")
+                .map(|alt| parse_npm_set(alt.trim()))
+                .collect::<Result<_, _>>()?,
+            VersionScheme::Semver | VersionScheme::Pep440 => {
+                let mut set = Vec::new();
+                for part in req.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                    set.extend(parse_comparator(scheme, part)?);
+                }
+                vec![set]
+            }
+        };
+        Ok(Self {
+            scheme,
+            alternatives,
+        })
+    }
+
+    /// Whether `version` satisfies the requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives
+            .iter()
+            .any(|set| set.iter().all(|c| c.matches(version)) && self.allows_pre(set, version))
+    }
+
+    fn allows_pre(&self, set: &[Comparator], version: &Version) -> bool {
+        if !version.is_prerelease() {
+            return true;
+        }
+        set.iter().any(|c| {
+            c.version.is_prerelease()
+                && !c.version.bound
+                && (self.scheme == VersionScheme::Pep440
+                    || c.version.cmp_release(version) == Ordering::Equal)
+        })
+    }
+}
+
+/// npm comparator set: whitespace-separated comparators or a hyphen range.
+fn parse_npm_set(set: &str) -> Result<Vec<Comparator>, PackageError> {
+    if let Some((lo, hi)) = set.split_once(" - ") {
+        let (lo, hi) = (partial(lo.trim())?, partial(hi.trim())?);
+        let mut out = vec![Comparator {
+            op: Op::Ge,
+            version: lo.lower(),
+        }];
+        out.extend(hi.upper_inclusive());
+        return Ok(out);
+    }
+
+    // Re-attach operators separated from their version (`>= 1.2`).
+    let mut tokens: Vec<String> = Vec::new();
+    for tok in set.split_whitespace() {
+        match tokens.last_mut() {
+            Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(tok),
+            _ => tokens.push(tok.to_string()),
+        }
+    }
+    if tokens.is_empty() || tokens == ["latest"] {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for tok in tokens {
+        out.extend(parse_comparator(VersionScheme::Npm, &tok)?);
+    }
+    Ok(out)
+}
+
+/// Parse one comparator (`^1.2`, `>=1.0`, `~=2.1`, `==1.4.*`) into primitive
+/// comparisons.
+fn parse_comparator(scheme: VersionScheme, s: &str) -> Result<Vec<Comparator>, PackageError> {
+    let s = s.trim();
+    let ops = [
+        "===", "~=", "~>", "==", "!=", ">=", "<=", "^", "~", "=", ">", "<",
+    ];
+    let op = ops
+        .iter()
+        .find(|op| s.starts_with(**op))
+        .copied()
+        .unwrap_or("");
+    let body = s[op.len()..].trim();
+    let cmp = |op, version| Comparator { op, version };
+
+    if scheme == VersionScheme::Pep440 && op != "~>" {
+        return match op {
+            "==" | "!=" if body.ends_with(".*") => {
+                let v = Version::parse(scheme, &body[..body.len() - 2])?;
+                Ok(vec![cmp(
+                    if op == "==" {
+                        Op::Prefix
+                    } else {
+                        Op::NotPrefix
+                    },
+                    v,
+                )])
+            }
+            "~=" => {
+                let v = Version::parse(scheme, body)?;
+                if v.release.len() < 2 {
+                    return Err(PackageError::ParseError(format!(
+                        "invalid requirement: {}",
+                        s
+                    )));
+                }
+                let prefix = Version {
+                    epoch: v.epoch,
+                    ..Version::from_release(v.release[..v.release.len() - 1].to_vec())
+                };
+                Ok(vec![cmp(Op::Ge, v), cmp(Op::Prefix, prefix)])
+            }
+            _ => {
+                let v = Version::parse(scheme, body)?;
+                let op = match op {
+                    "!=" => Op::Ne,
+                    ">" => Op::Gt,
+                    ">=" => Op::Ge,
+                    "<" => Op::Lt,
+                    "<=" => Op::Le,
+                    _ => Op::Eq,
+                };
+                Ok(vec![cmp(op, v)])
+            }
+        };
+    }
+
+    let p = partial(body)?;
+    Ok(match op {
+        "" if scheme == VersionScheme::Semver => p.caret(),
+        "^" => p.caret(),
+        "~" => p.tilde(),
+        "~>" => p.pessimistic(),
+        "" | "=" | "==" => p.exact(),
+        "!=" => vec![cmp(Op::Ne, p.lower())],
+        ">=" => vec![cmp(Op::Ge, p.lower())],
+        ">" => match p.bump_last() {
+            Some(v) => vec![cmp(Op::Ge, v)],
+            None => vec![cmp(Op::Gt, p.lower())],
+        },
+        "<" => vec![cmp(Op::Lt, p.lower_exclusive())],
+        "<=" => p.upper_inclusive(),
+        _ => {
+            return Err(PackageError::ParseError(format!(
+                "invalid requirement: {}",
+                s
+            )));
+        }
+    })
+}
+
+/// A possibly-partial semver version (`1`, `1.2`, `1.2.x`, `*`).
+struct Partial {
+    /// Specified numeric components (wildcards and missing parts dropped).
+    parts: Vec<u64>,
+    /// The full version, when all three components are given.
+    full: Option<Version>,
+}
+
+fn partial(s: &str) -> Result<Partial, PackageError> {
+    let s = s.trim();
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    if let Ok(v) = Version::parse(VersionScheme::Semver, s)
+        && v.release.len() >= 3
+    {
+        return Ok(Partial {
+            parts: v.release.clone(),
+            full: Some(v),
+        });
+    }
+    let mut parts = Vec::new();
+    for p in s.split('.').filter(|p| !p.is_empty()) {
+        if matches!(p, "*" | "x" | "X") {
+            break;
+        }
+        parts.push(
+            p.parse()
+                .map_err(|_| PackageError::ParseError(format!("invalid version: {}", s)))?,
+        );
+    }
+    Ok(Partial { parts, full: None })
+}
+
+impl Partial {
+    fn lower(&self) -> Version {
+        self.full
+            .clone()
+            .unwrap_or_else(|| Version::from_release(self.padded(&self.parts)))
+    }
+
+    /// Exclusive lower-bound form for `<`: a bare release excludes its
+    /// pre-releases too.
+    fn lower_exclusive(&self) -> Version {
+        match &self.full {
+            Some(v) if v.is_prerelease() => v.clone(),
+            _ => Version::floor(self.padded(&self.parts)),
+        }
+    }
+
+    fn padded(&self, parts: &[u64]) -> Vec<u64> {
+        let mut out = parts.to_vec();
+        out.resize(out.len().max(3), 0);
+        out
+    }
+
+    /// Version after the last specified component (`1.2` → `1.3.0`).
+    fn bump_last(&self) -> Option<Version> {
+        if self.full.is_some() {
+            return None;
+        }
+        let mut parts = self.parts.clone();
+        *parts.last_mut()? += 1;
+        Some(Version::floor(self.padded(&parts)))
+    }
+
+    /// `[lower, upper)` where upper bumps component `i` of the release.
+    fn range(&self, i: usize) -> Vec<Comparator> {
+        let mut out = vec![Comparator {
+            op: Op::Ge,
+            version: self.lower(),
+        }];
+        if self.parts.is_empty() {
+            return out;
+        }
+        let mut upper = self.parts[..=i.min(self.parts.len() - 1)].to_vec();
+        if let Some(last) = upper.last_mut() {
+            *last += 1;
+        }
+        out.push(Comparator {
+            op: Op::Lt,
+            version: Version::floor(self.padded(&upper)),
+        });
+        out
+    }
+
+    fn exact(&self) -> Vec<Comparator> {
+        match &self.full {
+            Some(v) => vec![Comparator {
+                op: Op::Eq,
+                version: v.clone(),
+            }],
+            None if self.parts.is_empty() => Vec::new(),
+            None => self.range(self.parts.len() - 1),
+        }
+    }
+
+    fn caret(&self) -> Vec<Comparator> {
+        // First non-zero component is the one that may not change; for
+        // `^0.0` / `^0` the last given component.
+        let first_nonzero = self.parts.iter().position(|&p| p != 0);
+        let i = match first_nonzero {
+            Some(i) => i,
+            None => self.parts.len().saturating_sub(1),
+        };
+        self.range(i)
+    }
+
+    fn tilde(&self) -> Vec<Comparator> {
+        self.range(if self.parts.len() >= 2 { 1 } else { 0 })
+    }
+
+    /// `~> 1.2.3` allows `>= 1.2.3, < 1.3.0`; `~> 1.2` allows `< 2.0`.
+    fn pessimistic(&self) -> Vec<Comparator> {
+        self.range(self.parts.len().saturating_sub(2))
+    }
+
+    fn upper_inclusive(&self) -> Vec<Comparator> {
+        match self.bump_last() {
+            Some(v) => vec![Comparator {
+                op: Op::Lt,
+                version: v,
+            }],
+            None if self.parts.is_empty() => Vec::new(),
+            None => vec![Comparator {
+                op: Op::Le,
+                version: self.lower(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use VersionScheme::*;
+
+    #[test]
+    fn test_semver_requirements() {
+        assert!(Semver.satisfies("1.4.0", "1.2"));
+        assert!(!Semver.satisfies("2.0.0", "1.2"));
+        assert!(Semver.satisfies("0.2.9", "^0.2.3"));
+        assert!(!Semver.satisfies("0.3.0", "^0.2.3"));
+        assert!(!Semver.satisfies("0.0.4", "^0.0.3"));
+        assert!(Semver.satisfies("1.2.9", "~1.2.3"));
+        assert!(!Semver.satisfies("1.3.0", "~1.2.3"));
+        assert!(Semver.satisfies("1.5.0", ">=1.2, <1.6"));
+        assert!(!Semver.satisfies("1.6.0", ">=1.2, <1.6"));
+        assert!(Semver.satisfies("3.1.4", "*"));
+        assert!(Semver.satisfies("1.2.7", "=1.2"));
+        assert!(Semver.satisfies("v1.9.0", "~> 1.2"));
+    }
+
+    #[test]
+    fn test_npm_requirements() {
+        assert!(Npm.satisfies("1.2.3", "1.2.3"));
+        assert!(!Npm.satisfies("1.2.4", "1.2.3"));
+        assert!(Npm.satisfies("1.2.9", "1.2.x"));
+        assert!(Npm.satisfies("2.5.0", "^1.0.0 || ^2.0.0"));
+        assert!(Npm.satisfies("1.5.0", ">= 1.2 < 2"));
+        assert!(Npm.satisfies("2.3.9", "1.2 - 2.3"));
+        assert!(!Npm.satisfies("2.4.0", "1.2 - 2.3"));
+        assert!(Npm.satisfies("1.3.0", ">1.2"));
+        assert!(!Npm.satisfies("1.2.5", ">1.2"));
+        assert!(Npm.satisfies("4.0.0", "latest"));
+    }
+
+    #[test]
+    fn test_prerelease_rules() {
+        assert!(!Semver.satisfies("2.0.0-alpha", "^1.0"));
+        assert!(!Npm.satisfies("2.0.0-rc.1", "<2.0.0"));
+        assert!(Npm.satisfies("1.2.3-beta.2", ">=1.2.3-beta.1"));
+        assert!(!Npm.satisfies("1.2.4-beta.2", ">=1.2.3-beta.1"));
+        assert!(!Pep440.satisfies("2.0rc1", ">=1.0"));
+        assert!(Pep440.satisfies("2.0rc1", ">=2.0b1"));
+        assert!(!Pep440.satisfies("1.5rc1", "~> 1.2"));
+    }
+
+    #[test]
+    fn test_pep440_requirements() {
+        assert!(Pep440.satisfies("2.31.0", ">=2.28,<3"));
+        assert!(Pep440.satisfies("2.2.5", "~=2.2.0"));
+        assert!(!Pep440.satisfies("2.3.0", "~=2.2.0"));
+        assert!(Pep440.satisfies("2.9", "~=2.2"));
+        assert!(Pep440.satisfies("1.4.2", "==1.4.*"));
+        assert!(!Pep440.satisfies("1.5.0", "==1.4.*"));
+        assert!(!Pep440.satisfies("1.4.2", "!=1.4.*"));
+        assert!(Pep440.satisfies("1.0.0", "==1.0"));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let order = |scheme: VersionScheme, versions: &[&str]| {
+            for pair in versions.windows(2) {
+                assert_eq!(
+                    scheme.compare(pair[0], pair[1]),
+                    Ordering::Less,
+                    "{:?}",
+                    pair
+                );
+            }
+        };
+        order(
+            Semver,
+            &[
+                "1.0.0-alpha",
+                "1.0.0-alpha.1",
+                "1.0.0-beta",
+                "1.0.0-rc.1",
+                "1.0.0",
+                "1.10.0",
+            ],
+        );
+        order(
+            Pep440,
+            &[
+                "1.0.dev0",
+                "1.0a1",
+                "1.0b2",
+                "1.0rc1",
+                "1.0",
+                "1.0.post1",
+                "1!0.1",
+            ],
+        );
+        assert_eq!(Pep440.compare("1.0", "1.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_max_satisfying() {
+        let versions = ["1.0.0", "1.4.2", "1.9.0", "2.0.0", "2.1.0-beta"];
+        assert_eq!(Semver.max_satisfying(&versions, "^1.2"), Some("1.9.0"));
+        assert_eq!(Npm.max_satisfying(&versions, ">=2"), Some("2.0.0"));
+        assert_eq!(Npm.max_satisfying(&versions, "^3"), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Reverse-dependency queries: why is a package in the dependency tree?
+//!
+//! Works on the [`DependencyTree`] every ecosystem builds from its lockfile, so
+//! the answer has the same shape for cargo, npm, pip, go, etc.
+
+use crate::{DependencyTree, TreeNode};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// One package along a dependency path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PathEntry {
+    /// Package name.
+    pub name: String,
+    /// Package version (may be empty if not known).
+    pub version: String,
+}
+
+/// Every path from a direct dependency down to a queried package.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WhyResult {
+    /// The package that was traced.
+    pub package: String,
+    /// Paths ordered root → target; the first entry is a direct dependency and
+    /// the last is the matched package.
+    pub paths: Vec<Vec<PathEntry>>,
+}
+
+impl WhyResult {
+    /// Trace `package` through `tree`.
+    ///
+    /// Matches the exact name or a trailing path segment (`x/y` matches `y`,
+    /// for Go modules and npm scopes). Cycles in the tree are not followed.
+    pub fn trace(tree: &DependencyTree, package: &str) -> Self {
+        let mut paths = Vec::new();
+        let mut current = Vec::new();
+        for root in &tree.roots {
+            collect_paths(root, package, &mut current, &mut paths);
+        }
+        Self {
+            package: package.to_string(),
+            paths,
+        }
+    }
+
+    /// Direct dependencies that pull the package in, deduplicated, in order.
+    pub fn direct_dependents(&self) -> Vec<&str> {
+        let mut out: Vec<&str> = Vec::new();
+        for path in &self.paths {
+            if let Some(first) = path.first()
+                && !out.contains(&first.name.as_str())
+            {
+                out.push(&first.name);
+            }
+        }
+        out
+    }
+
+    /// Render as an inverted tree, like `cargo tree -i`: the matched package at
+    /// the top, each level below listing what depends on the level above.
+    /// Shared path suffixes are merged.
+    pub fn to_inverted_tree(&self) -> String {
+        let mut roots: Vec<InvertedNode> = Vec::new();
+        for path in &self.paths {
+            let mut level = &mut roots;
+            for entry in path.iter().rev() {
+                let idx = match level.iter().position(|n| n.entry == *entry) {
+                    Some(i) => i,
+                    None => {
+                        level.push(InvertedNode {
+                            entry: entry.clone(),
+                            children: Vec::new(),
+                        });
+                        level.len() - 1
+                    }
+                };
+                level = &mut level[idx].children;
+            }
+        }
+        let mut out = String::new();
+        for node in &roots {
+            node.render(&mut out, 0);
+        }
+        out.trim_end().to_string()
+    }
+}
+
+struct InvertedNode {
+    entry: PathEntry,
+    children: Vec<InvertedNode>,
+}
+
+impl InvertedNode {
+    fn render(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        if self.entry.version.is_empty() {
+            out.push_str(&format!("{}{}\n", indent, self.entry.name));
+        } else {
+            out.push_str(&format!(
+                "{}{} v{}\n",
+                indent, self.entry.name, self.entry.version
+            ));
+        }
+        for child in &self.children {
+            child.render(out, depth + 1);
+        }
+    }
+}
+
+fn collect_paths(
+    node: &TreeNode,
+    target: &str,
+    current: &mut Vec<PathEntry>,
+    paths: &mut Vec<Vec<PathEntry>>,
+) {
+    if current.iter().any(|e| e.name == node.name) {
+        return;
+    }
+    current.push(PathEntry {
+        name: node.name.clone(),
+        version: node.version.clone(),
+    });
+    if node.name == target || node.name.ends_with(&format!("/{}", target)) {
+        paths.push(current.clone());
+    } else {
+        for child in &node.dependencies {
+            collect_paths(child, target, current, paths);
+        }
+    }
+    current.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, version: &str, deps: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies: deps,
+        }
+    }
+
+    fn tree() -> DependencyTree {
+        let serde = || node("serde", "1.0.200", vec![]);
+        DependencyTree {
+            roots: vec![
+                node(
+                    "serde_json",
+                    "1.0.1",
+                    vec![serde(), node("itoa", "1.0.0", vec![])],
+                ),
+                node(
+                    "toml",
+                    "0.8.0",
+                    vec![node("serde_spanned", "0.6.0", vec![serde()]), serde()],
+                ),
+                node("regex", "1.10.0", vec![]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_trace_paths() {
+        let why = WhyResult::trace(&tree(), "serde");
+        let names: Vec<Vec<&str>> = why
+            .paths
+            .iter()
+            .map(|p| p.iter().map(|e| e.name.as_str()).collect())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                vec!["serde_json", "serde"],
+                vec!["toml", "serde_spanned", "serde"],
+                vec!["toml", "serde"],
+            ]
+        );
+        assert_eq!(why.direct_dependents(), ["serde_json", "toml"]);
+    }
+
+    #[test]
+    fn test_inverted_tree() {
+        let why = WhyResult::trace(&tree(), "serde");
+        assert_eq!(
+            why.to_inverted_tree(),
+            "serde v1.0.200\n  serde_json v1.0.1\n  serde_spanned v0.6.0\n    toml v0.8.0\n  toml v0.8.0"
+        );
+    }
+
+    #[test]
+    fn test_trace_missing_and_cycle() {
+        let cyclic = DependencyTree {
+            roots: vec![node(
+                "a",
+                "1",
+                vec![node("b", "1", vec![node("a", "1", vec![])])],
+            )],
+        };
+        assert!(WhyResult::trace(&cyclic, "missing").paths.is_empty());
+        assert_eq!(WhyResult::trace(&cyclic, "b").paths.len(), 1);
+    }
+}
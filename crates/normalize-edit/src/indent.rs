@@ -0,0 +1,140 @@
+//! Indentation style detection.
+//!
+//! Edits insert code written in whatever style the caller used. [`IndentStyle`]
+//! infers a file's style (tabs, or spaces of some width) so inserted code can
+//! be re-indented to match: tabs in Go, two spaces in most JavaScript, and so on.
+
+/// How a file indents one nesting level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(usize),
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        IndentStyle::Spaces(4)
+    }
+}
+
+/// Widest space indent step considered when inferring a width
+const MAX_WIDTH: usize = 8;
+
+impl IndentStyle {
+    /// Infer the style of `content`.
+    ///
+    /// Tabs win if more lines start with a tab than with a space. Otherwise the
+    /// width is the most common increase in indentation from one non-blank
+    /// line to the next (the smaller width on a tie). Block comment
+    /// continuation lines (` * ...`) are skipped, since their one-space offset
+    /// isn't a nesting level. Falls back to four spaces when nothing is
+    /// indented.
+    pub fn detect(content: &str) -> Self {
+        let mut tab_lines = 0usize;
+        let mut space_lines = 0usize;
+        let mut steps = [0usize; MAX_WIDTH + 1];
+        let mut prev = 0usize;
+
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('*') {
+                continue;
+            }
+            if line.starts_with('\t') {
+                tab_lines += 1;
+                continue;
+            }
+            let width = line.len() - line.trim_start_matches(' ').len();
+            if width > 0 {
+                space_lines += 1;
+            }
+            if width > prev && width - prev <= MAX_WIDTH {
+                steps[width - prev] += 1;
+            }
+            prev = width;
+        }
+
+        if tab_lines > space_lines {
+            return IndentStyle::Tabs;
+        }
+        let mut best = None;
+        for (width, &count) in steps.iter().enumerate().skip(1) {
+            if count > 0 && best.is_none_or(|(_, c)| count > c) {
+                best = Some((width, count));
+            }
+        }
+        best.map_or_else(Self::default, |(width, _)| IndentStyle::Spaces(width))
+    }
+
+    /// One level of indentation
+    pub fn unit(&self) -> String {
+        match self {
+            IndentStyle::Tabs => "\t".to_string(),
+            IndentStyle::Spaces(n) => " ".repeat(*n),
+        }
+    }
+
+    /// Convert `text`'s indentation to this style, keeping nesting levels.
+    ///
+    /// The style `text` was written in is detected from `text` itself. Spaces
+    /// left over after the last whole level (alignment) are kept as spaces.
+    pub fn reindent(&self, text: &str) -> String {
+        let from = Self::detect(text);
+        if from == *self {
+            return text.to_string();
+        }
+        let unit = self.unit();
+        text.split_inclusive('\n')
+            .map(|line| {
+                let (levels, extra, rest) = match from {
+                    IndentStyle::Tabs => {
+                        let rest = line.trim_start_matches('\t');
+                        (line.len() - rest.len(), 0, rest)
+                    }
+                    IndentStyle::Spaces(width) => {
+                        let rest = line.trim_start_matches(' ');
+                        let n = line.len() - rest.len();
+                        (n / width, n % width, rest)
+                    }
+                };
+                format!("{}{}{}", unit.repeat(levels), " ".repeat(extra), rest)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_tabs() {
+        let go = "type Foo struct {\n\tA int\n}\n\nfunc (f Foo) Bar() {\n\tif true {\n\t\treturn\n\t}\n}\n";
+        assert_eq!(IndentStyle::detect(go), IndentStyle::Tabs);
+    }
+
+    #[test]
+    fn test_detect_space_width() {
+        let js =
+            "class A {\n  foo() {\n    return 1;\n  }\n\n  /**\n   * Doc.\n   */\n  bar() {}\n}\n";
+        assert_eq!(IndentStyle::detect(js), IndentStyle::Spaces(2));
+        let py = "class A:\n    def foo(self):\n        return 1\n";
+        assert_eq!(IndentStyle::detect(py), IndentStyle::Spaces(4));
+        assert_eq!(IndentStyle::detect("x = 1\n"), IndentStyle::Spaces(4));
+    }
+
+    #[test]
+    fn test_reindent() {
+        let snippet = "func (f Foo) Baz() {\n    if x {\n        return\n    }\n}";
+        assert_eq!(
+            IndentStyle::Tabs.reindent(snippet),
+            "func (f Foo) Baz() {\n\tif x {\n\t\treturn\n\t}\n}"
+        );
+        assert_eq!(
+            IndentStyle::Spaces(2).reindent("def f():\n    return (1,\n             2)\n"),
+            "def f():\n  return (1,\n       2)\n"
+        );
+        let tabs = "a {\n\tb\n}";
+        assert_eq!(IndentStyle::Tabs.reindent(tabs), tabs);
+    }
+}
@@ -3,6 +3,19 @@
 //! Provides utilities to locate symbols within source files and apply targeted
 //! text replacements — used by `normalize edit` to rewrite functions, methods,
 //! and other named constructs without touching the rest of the file.
+//!
+//! Each [`Editor`] operation has a `*_edit` form returning the [`TextEdit`] it
+//! makes, so several operations located against the same content can be
+//! combined in an [`EditPlan`] and applied together.
+
+mod indent;
+mod move_symbol;
+mod plan;
+mod trivia;
+
+pub use indent::IndentStyle;
+pub use plan::{EditPlan, TextEdit};
+pub use trivia::{IncludeTrivia, leading_trivia_start};
 
 use normalize_facts::{Extractor, Symbol};
 use normalize_languages::parsers::{grammar_loader, parse_with_grammar};
@@ -25,6 +38,20 @@ pub struct SymbolLocation {
     pub indent: String,
 }
 
+/// Split a qualified symbol name (`Foo::new`, `ClassName.method`) into its
+/// segments, outermost first. Returns `None` for unqualified names or names
+/// with an empty segment.
+fn split_qualified(name: &str) -> Option<Vec<&str>> {
+    let segments: Vec<&str> = if name.contains("::") {
+        name.split("::").collect()
+    } else if name.contains('.') {
+        name.split('.').collect()
+    } else {
+        return None;
+    };
+    (!segments.iter().any(|s| s.is_empty())).then_some(segments)
+}
+
 /// Convert a 1-based line number to byte offset in content.
 /// Clamps to content length for safety (last line may not have trailing newline).
 ///
@@ -72,7 +99,36 @@ impl Editor {
         Self {}
     }
 
+    /// Find the one symbol matching `name` (see [`Editor::find_unique_symbol`]),
+    /// optionally widening the location to its leading comments, decorators and
+    /// attributes.
+    ///
+    /// Without a grammar for the file, [`IncludeTrivia::Leading`] behaves like
+    /// [`IncludeTrivia::Definition`].
+    pub fn find_symbol_with(
+        &self,
+        path: &Path,
+        content: &str,
+        name: &str,
+        case_insensitive: bool,
+        trivia: IncludeTrivia,
+    ) -> Result<SymbolLocation, String> {
+        let mut loc = self.find_unique_symbol(path, content, name, case_insensitive)?;
+        if trivia == IncludeTrivia::Leading {
+            let (start, _) = leading_trivia_start(path, content, &loc);
+            if start < loc.start_byte {
+                loc.start_line = content[..start].matches('\n').count() + 1;
+                loc.start_byte = start;
+            }
+        }
+        Ok(loc)
+    }
+
     /// Find a symbol by name in a file (uses skeleton extractor)
+    ///
+    /// Returns the first match in document order; see [`Editor::find_symbols`]
+    /// for how `name` is matched, and [`Editor::find_unique_symbol`] to reject
+    /// ambiguous names instead.
     pub fn find_symbol(
         &self,
         path: &Path,
@@ -80,50 +136,162 @@ impl Editor {
         name: &str,
         case_insensitive: bool,
     ) -> Option<SymbolLocation> {
+        self.find_symbols(path, content, name, case_insensitive)
+            .into_iter()
+            .next()
+    }
+
+    /// Find every symbol matching `name` in a file, in document order.
+    ///
+    /// `name` may be qualified with its enclosing symbols, as in `Foo::new` or
+    /// `ClassName.method`: the last segment names the symbol and the preceding
+    /// segments must match its nearest parents. A name is only treated as
+    /// qualified when nothing matches it verbatim, so names containing `.` or
+    /// `::` (Markdown headings, say) still resolve.
+    pub fn find_symbols(
+        &self,
+        path: &Path,
+        content: &str,
+        name: &str,
+        case_insensitive: bool,
+    ) -> Vec<SymbolLocation> {
+        self.find_qualified(path, content, name, case_insensitive)
+            .into_iter()
+            .map(|(_, loc)| loc)
+            .collect()
+    }
+
+    /// Find the one symbol matching `name`, as [`Editor::find_symbols`] does.
+    ///
+    /// Errors if nothing matches, or if several symbols do; the ambiguity error
+    /// lists each candidate's qualified name so the caller can pick one.
+    pub fn find_unique_symbol(
+        &self,
+        path: &Path,
+        content: &str,
+        name: &str,
+        case_insensitive: bool,
+    ) -> Result<SymbolLocation, String> {
+        let mut matches = self.find_qualified(path, content, name, case_insensitive);
+        match matches.len() {
+            0 => Err(format!("Symbol not found: {}", name)),
+            1 => Ok(matches.remove(0).1),
+            n => {
+                let candidates: Vec<String> = matches
+                    .iter()
+                    .map(|(qualified, loc)| format!("{} (line {})", qualified, loc.start_line))
+                    .collect();
+                Err(format!(
+                    "Ambiguous symbol '{}': {} matches: {}; use a qualified name",
+                    name,
+                    n,
+                    candidates.join(", ")
+                ))
+            }
+        }
+    }
+
+    /// Matches for `name` paired with their `::`-joined qualified names.
+    fn find_qualified(
+        &self,
+        path: &Path,
+        content: &str,
+        name: &str,
+        case_insensitive: bool,
+    ) -> Vec<(String, SymbolLocation)> {
         let extractor = Extractor::new();
         let result = extractor.extract(path, content);
 
-        fn search_symbols(
-            symbols: &[Symbol],
-            name: &str,
+        fn search_symbols<'a>(
+            symbols: &'a [Symbol],
+            parents: &mut Vec<&'a str>,
+            segments: &[&str],
             content: &str,
             case_insensitive: bool,
-        ) -> Option<SymbolLocation> {
-            for sym in symbols {
-                let matches = if case_insensitive {
-                    sym.name.eq_ignore_ascii_case(name)
+            found: &mut Vec<(String, SymbolLocation)>,
+        ) {
+            let eq = |a: &str, b: &str| {
+                if case_insensitive {
+                    a.eq_ignore_ascii_case(b)
                 } else {
-                    sym.name == name
-                };
+                    a == b
+                }
+            };
+            let (name, qualifiers) = segments.split_last().expect("at least one segment");
+            for sym in symbols {
+                let matches = eq(&sym.name, name)
+                    && parents.len() >= qualifiers.len()
+                    && parents[parents.len() - qualifiers.len()..]
+                        .iter()
+                        .zip(qualifiers)
+                        .all(|(p, q)| eq(p, q));
                 if matches {
                     let start_byte = line_to_byte(content, sym.start_line);
                     let end_byte = line_to_byte(content, sym.end_line + 1);
-
-                    return Some(SymbolLocation {
-                        name: sym.name.clone(),
-                        kind: sym.kind.as_str().to_string(),
-                        start_byte,
-                        end_byte,
-                        start_line: sym.start_line,
-                        end_line: sym.end_line,
-                        indent: String::new(),
-                    });
+                    let mut qualified = parents.join("::");
+                    if !qualified.is_empty() {
+                        qualified.push_str("::");
+                    }
+                    qualified.push_str(&sym.name);
+
+                    found.push((
+                        qualified,
+                        SymbolLocation {
+                            name: sym.name.clone(),
+                            kind: sym.kind.as_str().to_string(),
+                            start_byte,
+                            end_byte,
+                            start_line: sym.start_line,
+                            end_line: sym.end_line,
+                            indent: String::new(),
+                        },
+                    ));
                 }
                 // Search children
-                if let Some(loc) = search_symbols(&sym.children, name, content, case_insensitive) {
-                    return Some(loc);
-                }
+                parents.push(&sym.name);
+                search_symbols(
+                    &sym.children,
+                    parents,
+                    segments,
+                    content,
+                    case_insensitive,
+                    found,
+                );
+                parents.pop();
             }
-            None
         }
 
-        search_symbols(&result.symbols, name, content, case_insensitive)
+        let mut found = Vec::new();
+        search_symbols(
+            &result.symbols,
+            &mut Vec::new(),
+            &[name],
+            content,
+            case_insensitive,
+            &mut found,
+        );
+        if found.is_empty()
+            && let Some(segments) = split_qualified(name)
+        {
+            search_symbols(
+                &result.symbols,
+                &mut Vec::new(),
+                &segments,
+                content,
+                case_insensitive,
+                &mut found,
+            );
+        }
+        found
     }
 
     /// Delete a symbol from the content
     pub fn delete_symbol(&self, content: &str, loc: &SymbolLocation) -> String {
-        let mut result = String::new();
+        self.delete_symbol_edit(content, loc).apply(content)
+    }
 
+    /// The edit [`Editor::delete_symbol`] makes
+    pub fn delete_symbol_edit(&self, content: &str, loc: &SymbolLocation) -> TextEdit {
         // Find the start of the line containing the symbol
         let line_start = content[..loc.start_byte]
             .rfind('\n')
@@ -152,24 +320,25 @@ impl Editor {
             }
         }
 
-        result.push_str(&content[..line_start]);
-        result.push_str(&content[end_byte..]);
-
-        result
+        TextEdit::new(line_start, end_byte, "")
     }
 
     /// Replace a symbol with new content
     pub fn replace_symbol(&self, content: &str, loc: &SymbolLocation, new_content: &str) -> String {
-        let mut result = String::new();
+        self.replace_symbol_edit(content, loc, new_content)
+            .apply(content)
+    }
 
+    /// The edit [`Editor::replace_symbol`] makes
+    pub fn replace_symbol_edit(
+        &self,
+        content: &str,
+        loc: &SymbolLocation,
+        new_content: &str,
+    ) -> TextEdit {
         // Apply indentation to new content
-        let indented = self.apply_indent(new_content, &loc.indent);
-
-        result.push_str(&content[..loc.start_byte]);
-        result.push_str(&indented);
-        result.push_str(&content[loc.end_byte..]);
-
-        result
+        let indented = self.indent_like(content, new_content, &loc.indent);
+        TextEdit::new(loc.start_byte, loc.end_byte, indented)
     }
 
     /// Count blank lines before a position
@@ -210,8 +379,17 @@ impl Editor {
 
     /// Insert content before a symbol
     pub fn insert_before(&self, content: &str, loc: &SymbolLocation, new_content: &str) -> String {
-        let mut result = String::new();
+        self.insert_before_edit(content, loc, new_content)
+            .apply(content)
+    }
 
+    /// The edit [`Editor::insert_before`] makes
+    pub fn insert_before_edit(
+        &self,
+        content: &str,
+        loc: &SymbolLocation,
+        new_content: &str,
+    ) -> TextEdit {
         // Find the start of the line containing the symbol
         let line_start = content[..loc.start_byte]
             .rfind('\n')
@@ -224,22 +402,26 @@ impl Editor {
         let spacing = "\n".repeat(blank_lines.max(1) + 1);
 
         // Apply indentation to new content
-        let indented = self.apply_indent(new_content, &loc.indent);
+        let indented = self.indent_like(content, new_content, &loc.indent);
 
-        result.push_str(&content[..line_start]);
-        result.push_str(&indented);
-        result.push_str(&spacing);
-        result.push_str(&content[line_start..]);
-
-        result
+        TextEdit::insert(line_start, indented + &spacing)
     }
 
     /// Insert content after a symbol
     pub fn insert_after(&self, content: &str, loc: &SymbolLocation, new_content: &str) -> String {
-        let mut result = String::new();
+        self.insert_after_edit(content, loc, new_content)
+            .apply(content)
+    }
 
+    /// The edit [`Editor::insert_after`] makes
+    pub fn insert_after_edit(
+        &self,
+        content: &str,
+        loc: &SymbolLocation,
+        new_content: &str,
+    ) -> TextEdit {
         // Apply indentation to new content
-        let indented = self.apply_indent(new_content, &loc.indent);
+        let indented = self.indent_like(content, new_content, &loc.indent);
 
         // Find the end of the symbol (include trailing newline)
         let end_pos = if loc.end_byte < content.len() && content.as_bytes()[loc.end_byte] == b'\n' {
@@ -259,44 +441,49 @@ impl Editor {
             next_content_pos += 1;
         }
 
-        result.push_str(&content[..end_pos]);
-        result.push_str(&spacing);
-        result.push_str(&indented);
-
+        // Replaces the blank lines between the symbol and what follows it
+        let mut text = spacing + &indented;
         if next_content_pos < content.len() {
             // +1 for the newline ending the inserted content
-            result.push_str(&"\n".repeat(blank_lines.max(1) + 1));
-            result.push_str(&content[next_content_pos..]);
+            text.push_str(&"\n".repeat(blank_lines.max(1) + 1));
         } else {
-            result.push('\n');
+            text.push('\n');
         }
 
-        result
+        TextEdit::new(end_pos, next_content_pos, text)
     }
 
     /// Insert content at the beginning of a file
     pub fn prepend_to_file(&self, content: &str, new_content: &str) -> String {
-        let mut result = String::new();
-        result.push_str(new_content);
+        self.prepend_to_file_edit(new_content).apply(content)
+    }
+
+    /// The edit [`Editor::prepend_to_file`] makes
+    pub fn prepend_to_file_edit(&self, new_content: &str) -> TextEdit {
+        let mut text = new_content.to_string();
         if !new_content.ends_with('\n') {
-            result.push('\n');
+            text.push('\n');
         }
-        result.push_str(content);
-        result
+        TextEdit::insert(0, text)
     }
 
     /// Insert content at the end of a file
     pub fn append_to_file(&self, content: &str, new_content: &str) -> String {
-        let mut result = String::new();
-        result.push_str(content);
+        self.append_to_file_edit(content, new_content)
+            .apply(content)
+    }
+
+    /// The edit [`Editor::append_to_file`] makes
+    pub fn append_to_file_edit(&self, content: &str, new_content: &str) -> TextEdit {
+        let mut text = String::new();
         if !content.ends_with('\n') {
-            result.push('\n');
+            text.push('\n');
         }
-        result.push_str(new_content);
+        text.push_str(new_content);
         if !new_content.ends_with('\n') {
-            result.push('\n');
+            text.push('\n');
         }
-        result
+        TextEdit::insert(content.len(), text)
     }
 
     /// Find the body of a container symbol (class, impl block, markdown section) for prepend/append
@@ -325,25 +512,27 @@ impl Editor {
         body: &ContainerBody,
         new_content: &str,
     ) -> String {
-        let mut result = String::new();
+        self.prepend_to_container_edit(content, body, new_content)
+            .apply(content)
+    }
 
+    /// The edit [`Editor::prepend_to_container`] makes
+    pub fn prepend_to_container_edit(
+        &self,
+        content: &str,
+        body: &ContainerBody,
+        new_content: &str,
+    ) -> TextEdit {
         // Apply indentation to new content
-        let indented = self.apply_indent(new_content, &body.inner_indent);
-
-        result.push_str(&content[..body.content_start]);
-
-        // Add the new content
-        result.push_str(&indented);
-        result.push('\n');
+        let mut text = self.indent_like(content, new_content, &body.inner_indent);
+        text.push('\n');
 
         // Add spacing if there's existing content
         if !body.is_empty {
-            result.push('\n');
+            text.push('\n');
         }
 
-        result.push_str(&content[body.content_start..]);
-
-        result
+        TextEdit::insert(body.content_start, text)
     }
 
     /// Append content inside a container (class/impl body)
@@ -353,10 +542,19 @@ impl Editor {
         body: &ContainerBody,
         new_content: &str,
     ) -> String {
-        let mut result = String::new();
+        self.append_to_container_edit(content, body, new_content)
+            .apply(content)
+    }
 
+    /// The edit [`Editor::append_to_container`] makes
+    pub fn append_to_container_edit(
+        &self,
+        content: &str,
+        body: &ContainerBody,
+        new_content: &str,
+    ) -> TextEdit {
         // Apply indentation to new content
-        let indented = self.apply_indent(new_content, &body.inner_indent);
+        let indented = self.indent_like(content, new_content, &body.inner_indent);
 
         // Trim trailing whitespace/newlines from existing content
         let mut end_pos = body.content_end;
@@ -370,22 +568,18 @@ impl Editor {
             end_pos -= 1;
         }
 
-        result.push_str(&content[..end_pos]);
-
         // Add blank line before new content (Python/Rust convention for methods)
-        if !body.is_empty {
-            result.push_str("\n\n");
+        let mut text = if !body.is_empty {
+            "\n\n".to_string()
         } else {
-            result.push('\n');
-        }
+            "\n".to_string()
+        };
 
         // Add the new content
-        result.push_str(&indented);
-        result.push('\n');
-
-        result.push_str(&content[body.content_end..]);
+        text.push_str(&indented);
+        text.push('\n');
 
-        result
+        TextEdit::new(end_pos, body.content_end, text)
     }
 
     /// Rename all word-boundary occurrences of `old_name` on a specific line (1-based).
@@ -413,6 +607,13 @@ impl Editor {
         Some(result)
     }
 
+    /// Re-indent `new_content` in the [`IndentStyle`] of `file_content`, then
+    /// prefix each line with `indent`
+    pub fn indent_like(&self, file_content: &str, new_content: &str, indent: &str) -> String {
+        let reindented = IndentStyle::detect(file_content).reindent(new_content);
+        self.apply_indent(&reindented, indent)
+    }
+
     /// Apply indentation to content
     pub fn apply_indent(&self, content: &str, indent: &str) -> String {
         content
@@ -551,7 +752,7 @@ fn find_container_body_via_tags(
                 .chars()
                 .take_while(|c| c.is_whitespace())
                 .collect();
-            let inner_indent = format!("{}    ", container_indent);
+            let inner_indent = container_indent + &IndentStyle::detect(content).unit();
             if let Some(body) = support.analyze_container_body(&body_node, content, &inner_indent) {
                 return Some(body);
             }
@@ -583,6 +784,55 @@ def bar():
         assert_eq!(loc.kind, "function");
     }
 
+    #[test]
+    fn test_find_symbol_with_leading_trivia() {
+        let editor = Editor::new();
+        let content = "import os\n\n# Helper.\n@cache\ndef foo():\n    pass\n";
+        let path = PathBuf::from("test.py");
+        let bare = editor
+            .find_symbol_with(&path, content, "foo", false, IncludeTrivia::Definition)
+            .unwrap();
+        let loc = editor
+            .find_symbol_with(&path, content, "foo", false, IncludeTrivia::Leading)
+            .unwrap();
+        assert!(content[loc.start_byte..].starts_with("# Helper.\n@cache\n"));
+        assert_eq!(loc.start_line, 3);
+        assert_eq!(loc.end_byte, bare.end_byte);
+
+        let result = editor.delete_symbol(content, &loc);
+        assert_eq!(result, "import os\n\n");
+    }
+
+    #[test]
+    fn test_find_symbols_qualified() {
+        let editor = Editor::new();
+        let content = "struct Foo;\nstruct Bar;\n\nimpl Foo {\n    fn new() -> Self {\n        Foo\n    }\n}\n\nimpl Bar {\n    fn new() -> Self {\n        Bar\n    }\n}\n";
+        let path = PathBuf::from("test.rs");
+        assert_eq!(editor.find_symbols(&path, content, "new", false).len(), 2);
+
+        let bar = editor.find_symbols(&path, content, "Bar::new", false);
+        assert_eq!(bar.len(), 1);
+        assert_eq!(bar[0].start_line, 11);
+
+        let err = editor
+            .find_unique_symbol(&path, content, "new", false)
+            .unwrap_err();
+        assert!(err.contains("Foo::new (line 5)"), "{}", err);
+        assert!(err.contains("Bar::new (line 11)"), "{}", err);
+        let loc = editor
+            .find_unique_symbol(&path, content, "Foo.new", false)
+            .unwrap();
+        assert_eq!(loc.start_line, 5);
+    }
+
+    #[test]
+    fn test_split_qualified() {
+        assert_eq!(split_qualified("Foo::new"), Some(vec!["Foo", "new"]));
+        assert_eq!(split_qualified("a.b.c"), Some(vec!["a", "b", "c"]));
+        assert_eq!(split_qualified("new"), None);
+        assert_eq!(split_qualified("Foo::"), None);
+    }
+
     #[test]
     fn test_delete_symbol() {
         let editor = Editor::new();
@@ -651,6 +901,22 @@ def bar():
         assert!(second_pos < last_pos);
     }
 
+    #[test]
+    fn test_append_to_container_matches_file_indent() {
+        let editor = Editor::new();
+        let content = "class Foo {\n  first() {\n    return 1;\n  }\n}\n";
+        let body = editor
+            .find_container_body(&PathBuf::from("test.js"), content, "Foo")
+            .unwrap();
+        assert_eq!(body.inner_indent, "  ");
+        let result = editor.append_to_container(content, &body, "last() {\n    return 99;\n}");
+        assert!(
+            result.contains("\n  last() {\n    return 99;\n  }\n"),
+            "{}",
+            result
+        );
+    }
+
     #[test]
     fn test_prepend_to_rust_impl() {
         let editor = Editor::new();
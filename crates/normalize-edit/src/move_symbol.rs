@@ -0,0 +1,396 @@
+//! Moving a symbol from one file to another.
+//!
+//! [`Editor::move_symbol`] plans the text changes in the two files involved:
+//! the definition is removed from the source and placed in the destination,
+//! inside the same-named container when the destination has one. Imports are
+//! fixed up on both sides: the destination stops importing the symbol from the
+//! source, and the source imports it from the destination if it still uses it.
+//! Imports are only rewritten for languages whose module paths follow from
+//! file paths (Python, JavaScript, TypeScript). Other files that import the
+//! symbol need the index to find; `normalize move` handles those.
+
+use crate::{EditPlan, Editor, SymbolLocation, TextEdit, line_to_byte};
+use normalize_facts::{Extractor, Symbol, SymbolParser};
+use normalize_languages::{Import, support_for_path};
+use std::path::{Component, Path, PathBuf};
+
+impl Editor {
+    /// Plan moving symbol `name` from `from_file` to `to_file`.
+    ///
+    /// Nothing is written; apply or diff the returned plan. A missing
+    /// `to_file` is created when the plan is applied.
+    pub fn move_symbol(
+        &self,
+        from_file: &Path,
+        to_file: &Path,
+        name: &str,
+    ) -> Result<EditPlan, String> {
+        let content = std::fs::read_to_string(from_file)
+            .map_err(|e| format!("Failed to read {}: {}", from_file.display(), e))?;
+        let loc = self.find_unique_symbol(from_file, &content, name, false)?;
+        self.move_symbol_at(from_file, &loc, to_file)
+    }
+
+    /// [`Editor::move_symbol`] for an already located symbol, e.g. one whose
+    /// start was extended to cover its doc comment and attributes.
+    pub fn move_symbol_at(
+        &self,
+        from_file: &Path,
+        loc: &SymbolLocation,
+        to_file: &Path,
+    ) -> Result<EditPlan, String> {
+        if from_file == to_file {
+            return Err(format!(
+                "Source and destination are the same file: {}",
+                from_file.display()
+            ));
+        }
+        let name = loc.name.as_str();
+        let mut plan = EditPlan::new();
+        let from_content = plan.original(from_file)?.to_string();
+        let to_content = plan.original(to_file)?.to_string();
+
+        // The symbol's whole lines, without the nesting indent it had in the source
+        let line_start = from_content[..loc.start_byte]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let definition = dedent(from_content[line_start..loc.end_byte].trim_end_matches('\n'));
+
+        let removal = self.delete_symbol_edit(&from_content, loc);
+        let remaining = removal.apply(&from_content);
+        plan.add(from_file, removal, &format!("remove {}", name))?;
+
+        let container = symbol_parent(from_file, &from_content, name)
+            .and_then(|parent| self.find_container_body(to_file, &to_content, &parent));
+        let insertion = match &container {
+            Some(body) => self.append_to_container_edit(&to_content, body, &definition),
+            None if to_content.trim().is_empty() => {
+                TextEdit::new(0, to_content.len(), definition + "\n")
+            }
+            // Blank line between the existing content and the moved definition
+            None => self.append_to_file_edit(&to_content, &format!("\n{}", definition)),
+        };
+        plan.add(to_file, insertion, &format!("insert {}", name))?;
+
+        for edit in drop_import_edits(to_file, &to_content, from_file, name) {
+            plan.add(to_file, edit, &format!("drop import of {}", name))?;
+        }
+        // Only top-level symbols are imported by name.
+        if container.is_none()
+            && contains_word(&remaining, name)
+            && let Some(edit) = add_import_edit(from_file, &from_content, to_file, name)
+        {
+            plan.add(from_file, edit, &format!("import {}", name))?;
+        }
+        Ok(plan)
+    }
+}
+
+/// Name of the symbol containing `name` in `content`, if it is nested
+fn symbol_parent(path: &Path, content: &str, name: &str) -> Option<String> {
+    fn search(symbols: &[Symbol], parent: Option<&str>, name: &str) -> Option<Option<String>> {
+        for sym in symbols {
+            if sym.name == name {
+                return Some(parent.map(str::to_string));
+            }
+            if let Some(found) = search(&sym.children, Some(&sym.name), name) {
+                return Some(found);
+            }
+        }
+        None
+    }
+    let result = Extractor::new().extract(path, content);
+    search(&result.symbols, None, name).flatten()
+}
+
+/// Remove the leading whitespace of `text`'s first line from every line
+fn dedent(text: &str) -> String {
+    let first = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let indent = &first[..first.len() - first.trim_start().len()];
+    if indent.is_empty() {
+        return text.to_string();
+    }
+    text.lines()
+        .map(|line| line.strip_prefix(indent).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether `text` contains `word` delimited by non-identifier characters
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(i, _)| {
+        !text[..i].chars().next_back().is_some_and(is_ident)
+            && !text[i + word.len()..].chars().next().is_some_and(is_ident)
+    })
+}
+
+/// Whether import specifier `module` refers to `file` (by its stem:
+/// `./utils`, `.utils` and `pkg.utils` all name `utils.py`/`utils.ts`)
+fn module_names_file(module: &str, file: &Path) -> bool {
+    let last = module
+        .rsplit(['.', '/', ':'])
+        .find(|s| !s.is_empty())
+        .unwrap_or("");
+    file.file_stem().is_some_and(|stem| stem == last)
+}
+
+/// Edits removing `name` from the imports in `to_file` that pull it from `from_file`.
+///
+/// A statement importing other names too is rewritten without `name`. Import
+/// lines that don't contain both the module and the name (multi-line
+/// statements) are left alone.
+fn drop_import_edits(to_file: &Path, content: &str, from_file: &Path, name: &str) -> Vec<TextEdit> {
+    let Some(support) = support_for_path(to_file) else {
+        return Vec::new();
+    };
+    let imports = SymbolParser::new().parse_imports(to_file, content);
+    let mut edits = Vec::new();
+    for import in &imports {
+        let Some(module) = &import.module else {
+            continue;
+        };
+        if import.name != name || import.alias.is_some() || !module_names_file(module, from_file) {
+            continue;
+        }
+        let start = line_to_byte(content, import.line);
+        let end = line_to_byte(content, import.line + 1);
+        let line = &content[start..end];
+        if !line.contains(module.as_str()) || !contains_word(line, name) {
+            continue;
+        }
+        let others: Vec<String> = imports
+            .iter()
+            .filter(|i| i.line == import.line && i.name != name)
+            .map(|i| i.name.clone())
+            .collect();
+        if others.is_empty() {
+            edits.push(TextEdit::new(start, end, ""));
+            continue;
+        }
+        let statement = support.format_import(
+            &Import {
+                module: module.clone(),
+                names: others,
+                alias: None,
+                is_wildcard: false,
+                is_relative: module.starts_with('.'),
+                line: import.line,
+            },
+            None,
+        );
+        if !statement.is_empty() {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let newline = if line.ends_with('\n') { "\n" } else { "" };
+            edits.push(TextEdit::new(
+                start,
+                end,
+                format!("{indent}{statement}{newline}"),
+            ));
+        }
+    }
+    edits
+}
+
+/// Edit adding `import name from to_file` to `from_file`, after its last
+/// import (or at the top). `None` when the module path can't be derived.
+fn add_import_edit(
+    from_file: &Path,
+    content: &str,
+    to_file: &Path,
+    name: &str,
+) -> Option<TextEdit> {
+    let support = support_for_path(from_file)?;
+    let module = module_path(from_file, to_file)?;
+    let statement = support.format_import(
+        &Import {
+            is_relative: module.starts_with('.'),
+            module,
+            names: vec![name.to_string()],
+            alias: None,
+            is_wildcard: false,
+            line: 0,
+        },
+        None,
+    );
+    if statement.is_empty() {
+        return None;
+    }
+
+    let imports = SymbolParser::new().parse_imports(from_file, content);
+    let first = imports.iter().map(|i| i.line).min();
+    let last = imports.iter().map(|i| i.line).max();
+    match (first, last) {
+        (Some(first), Some(last)) => {
+            let last_start = line_to_byte(content, last);
+            let last_end = line_to_byte(content, last + 1);
+            let last_line = content[last_start..last_end].trim_end();
+            // A statement continuing past its first line ends elsewhere.
+            if last_line.ends_with(['(', '{', ',', '\\']) {
+                Some(TextEdit::insert(
+                    line_to_byte(content, first),
+                    statement + "\n",
+                ))
+            } else {
+                let newline = if content[..last_end].ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                Some(TextEdit::insert(
+                    last_end,
+                    format!("{newline}{statement}\n"),
+                ))
+            }
+        }
+        _ => Some(TextEdit::insert(0, statement + "\n\n")),
+    }
+}
+
+/// Import specifier for `to_file` as written in `from_file`
+fn module_path(from_file: &Path, to_file: &Path) -> Option<String> {
+    match to_file.extension()?.to_str()? {
+        "py" => python_module(to_file),
+        "js" | "mjs" | "cjs" | "jsx" | "ts" | "mts" | "cts" | "tsx" => {
+            let rel = relative_path(&to_file.with_extension(""), from_file.parent()?);
+            let rel = rel.to_str()?.replace('\\', "/");
+            Some(if rel.starts_with('.') {
+                rel
+            } else {
+                format!("./{}", rel)
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Dotted module path of a Python file, walking up through packages
+/// (directories with `__init__.py`)
+fn python_module(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let mut parts = Vec::new();
+    if stem != "__init__" {
+        parts.push(stem.to_string());
+    }
+    let mut dir = path.parent()?;
+    while dir.join("__init__.py").exists() {
+        parts.push(dir.file_name()?.to_str()?.to_string());
+        dir = dir.parent()?;
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    parts.reverse();
+    Some(parts.join("."))
+}
+
+/// `target` relative to directory `base`
+fn relative_path(target: &Path, base: &Path) -> PathBuf {
+    let target: Vec<Component> = target.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = target.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut out = PathBuf::new();
+    for _ in common..base.len() {
+        out.push("..");
+    }
+    for c in &target[common..] {
+        out.push(c.as_os_str());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_module_names_file() {
+        let file = Path::new("src/utils.py");
+        assert!(module_names_file("pkg.utils", file));
+        assert!(module_names_file(".utils", file));
+        assert!(module_names_file("./utils", file));
+        assert!(!module_names_file("pkg.other", file));
+    }
+
+    #[test]
+    fn test_relative_module_path() {
+        assert_eq!(
+            module_path(Path::new("src/app.ts"), Path::new("src/lib/foo.ts")).as_deref(),
+            Some("./lib/foo")
+        );
+        assert_eq!(
+            module_path(Path::new("src/lib/foo.ts"), Path::new("src/app.ts")).as_deref(),
+            Some("../app")
+        );
+        assert_eq!(module_path(Path::new("a.rs"), Path::new("b.rs")), None);
+    }
+
+    #[test]
+    fn test_dedent_and_contains_word() {
+        assert_eq!(
+            dedent("    def f(self):\n        pass"),
+            "def f(self):\n    pass"
+        );
+        assert!(contains_word("x = foo(1)", "foo"));
+        assert!(!contains_word("x = foobar(1)", "foo"));
+    }
+
+    #[test]
+    fn test_move_python_function_fixes_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.py");
+        let dest = dir.path().join("dest.py");
+        fs::write(
+            &src,
+            "import os\n\n\ndef helper():\n    return 1\n\n\ndef main():\n    return helper()\n",
+        )
+        .unwrap();
+        fs::write(
+            &dest,
+            "from src import helper, main\n\n\ndef other():\n    return helper()\n",
+        )
+        .unwrap();
+
+        let plan = Editor::new().move_symbol(&src, &dest, "helper").unwrap();
+        let rendered: std::collections::HashMap<_, _> = plan.render().into_iter().collect();
+
+        let src_new = &rendered[&src];
+        assert!(!src_new.contains("def helper"), "{}", src_new);
+        assert!(
+            src_new.contains("import os\nfrom dest import helper\n"),
+            "{}",
+            src_new
+        );
+
+        let dest_new = &rendered[&dest];
+        assert!(
+            dest_new.starts_with("from src import main\n"),
+            "{}",
+            dest_new
+        );
+        assert!(
+            dest_new.contains("\ndef helper():\n    return 1\n"),
+            "{}",
+            dest_new
+        );
+    }
+
+    #[test]
+    fn test_move_method_into_same_named_class() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.py");
+        let dest = dir.path().join("b.py");
+        fs::write(&src, "class Foo:\n    def keep(self):\n        pass\n\n    def moved(self):\n        return 1\n").unwrap();
+        fs::write(&dest, "class Foo:\n    def existing(self):\n        pass\n").unwrap();
+
+        let plan = Editor::new().move_symbol(&src, &dest, "moved").unwrap();
+        let (_, dest_new) = plan.render().into_iter().find(|(p, _)| *p == dest).unwrap();
+        assert!(
+            dest_new.contains("        pass\n\n    def moved(self):\n        return 1\n"),
+            "{}",
+            dest_new
+        );
+    }
+}
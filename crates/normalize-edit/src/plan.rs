@@ -0,0 +1,641 @@
+//! Multi-edit transactions.
+//!
+//! An [`EditPlan`] collects operations across files without touching the disk.
+//! Every operation is located against the file's original content and recorded
+//! as a [`TextEdit`], so later operations don't see (or get confused by) the
+//! output of earlier ones. Edits whose byte ranges overlap are rejected when
+//! they are added. A plan can also remove whole files, which is how a file
+//! move is expressed. The finished plan is either written out with
+//! [`EditPlan::apply`] (all files or none) or rendered as a unified diff with
+//! [`EditPlan::diff`].
+
+use crate::{Editor, SymbolLocation};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Replacement of the bytes `start..end` of a file's original content.
+///
+/// An empty range (`start == end`) is an insertion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(start: usize, end: usize, text: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    /// Insert `text` at byte offset `at`
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        Self::new(at, at, text)
+    }
+
+    /// Whether the two edits can't both be applied.
+    ///
+    /// Ranges that share a byte conflict, as does an insertion strictly inside
+    /// the other edit's range. Edits that only touch at a boundary, and
+    /// insertions at the same offset, don't.
+    pub fn conflicts_with(&self, other: &TextEdit) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// Apply this edit alone to `content`
+    pub fn apply(&self, content: &str) -> String {
+        let mut result = String::with_capacity(content.len() + self.text.len());
+        result.push_str(&content[..self.start]);
+        result.push_str(&self.text);
+        result.push_str(&content[self.end..]);
+        result
+    }
+}
+
+/// An edit and the operation that produced it (for conflict messages)
+struct LabeledEdit {
+    edit: TextEdit,
+    label: String,
+}
+
+/// A file's content when the plan first read it, and the edits planned for it
+struct PlannedFile {
+    original: String,
+    /// The file didn't exist; `original` is empty and apply creates it
+    created: bool,
+    /// Apply deletes the file (it has no edits)
+    removed: bool,
+    edits: Vec<LabeledEdit>,
+}
+
+impl PlannedFile {
+    /// Original content with every planned edit applied
+    fn render(&self) -> String {
+        let mut edits: Vec<&TextEdit> = self.edits.iter().map(|e| &e.edit).collect();
+        // Insertions go before a replacement starting at the same offset;
+        // the sort is stable, so insertions at one offset keep their order.
+        edits.sort_by_key(|e| (e.start, e.end));
+
+        let mut result = String::with_capacity(self.original.len());
+        let mut cursor = 0;
+        for edit in edits {
+            result.push_str(&self.original[cursor..edit.start]);
+            result.push_str(&edit.text);
+            cursor = edit.end;
+        }
+        result.push_str(&self.original[cursor..]);
+        result
+    }
+}
+
+/// A set of edits across files, applied together.
+///
+/// ```no_run
+/// # use normalize_edit::EditPlan;
+/// # use std::path::Path;
+/// let mut plan = EditPlan::new();
+/// plan.delete(Path::new("src/a.py"), "old_helper")?;
+/// plan.insert_after(Path::new("src/a.py"), "main", "def new_helper():\n    pass")?;
+/// print!("{}", plan.diff());
+/// plan.apply()?;
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Default)]
+pub struct EditPlan {
+    editor: Editor,
+    files: BTreeMap<PathBuf, PlannedFile>,
+}
+
+impl EditPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Content of `path` as of the first time the plan read it (empty for a
+    /// file that doesn't exist yet, which applying the plan creates).
+    ///
+    /// Edits added with [`EditPlan::add`] are byte ranges into this content.
+    pub fn original(&mut self, path: &Path) -> Result<&str, String> {
+        Ok(&self.file(path)?.original)
+    }
+
+    /// Files with planned edits or removals
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(|(_, f)| !f.edits.is_empty() || f.removed)
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// Number of planned edits and file removals across all files
+    pub fn len(&self) -> usize {
+        self.files
+            .values()
+            .map(|f| f.edits.len() + usize::from(f.removed))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add a raw edit to `path`; `label` names it in conflict errors.
+    ///
+    /// Fails if the range is outside the original content, doesn't fall on
+    /// character boundaries, or conflicts with an edit already in the plan.
+    pub fn add(&mut self, path: &Path, edit: TextEdit, label: &str) -> Result<(), String> {
+        let file = self.file(path)?;
+        if edit.start > edit.end
+            || !file.original.is_char_boundary(edit.start)
+            || !file.original.is_char_boundary(edit.end)
+        {
+            return Err(format!(
+                "Invalid edit range {}..{} in {} ({})",
+                edit.start,
+                edit.end,
+                path.display(),
+                label
+            ));
+        }
+        if file.removed {
+            return Err(format!(
+                "Conflicting edits in {}: remove file and {}",
+                path.display(),
+                label
+            ));
+        }
+        if let Some(existing) = file.edits.iter().find(|e| e.edit.conflicts_with(&edit)) {
+            return Err(format!(
+                "Conflicting edits in {}: {} (bytes {}..{}) and {} (bytes {}..{})",
+                path.display(),
+                existing.label,
+                existing.edit.start,
+                existing.edit.end,
+                label,
+                edit.start,
+                edit.end
+            ));
+        }
+        file.edits.push(LabeledEdit {
+            edit,
+            label: label.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Delete symbol `name` from `path`
+    pub fn delete(&mut self, path: &Path, name: &str) -> Result<(), String> {
+        let (content, loc) = self.locate(path, name)?;
+        let edit = self.editor.delete_symbol_edit(&content, &loc);
+        self.add(path, edit, &format!("delete {}", name))
+    }
+
+    /// Replace symbol `name` in `path` with `new_content`
+    pub fn replace(&mut self, path: &Path, name: &str, new_content: &str) -> Result<(), String> {
+        let (content, loc) = self.locate(path, name)?;
+        let edit = self.editor.replace_symbol_edit(&content, &loc, new_content);
+        self.add(path, edit, &format!("replace {}", name))
+    }
+
+    /// Insert `new_content` before symbol `name` in `path`
+    pub fn insert_before(
+        &mut self,
+        path: &Path,
+        name: &str,
+        new_content: &str,
+    ) -> Result<(), String> {
+        let (content, loc) = self.locate(path, name)?;
+        let edit = self.editor.insert_before_edit(&content, &loc, new_content);
+        self.add(path, edit, &format!("insert before {}", name))
+    }
+
+    /// Insert `new_content` after symbol `name` in `path`
+    pub fn insert_after(
+        &mut self,
+        path: &Path,
+        name: &str,
+        new_content: &str,
+    ) -> Result<(), String> {
+        let (content, loc) = self.locate(path, name)?;
+        let edit = self.editor.insert_after_edit(&content, &loc, new_content);
+        self.add(path, edit, &format!("insert after {}", name))
+    }
+
+    /// Insert `new_content` at the start of container `name`'s body in `path`
+    pub fn prepend_to_container(
+        &mut self,
+        path: &Path,
+        name: &str,
+        new_content: &str,
+    ) -> Result<(), String> {
+        let content = self.original(path)?.to_string();
+        let body = self
+            .editor
+            .find_container_body(path, &content, name)
+            .ok_or_else(|| format!("Container not found: {}", name))?;
+        let edit = self
+            .editor
+            .prepend_to_container_edit(&content, &body, new_content);
+        self.add(path, edit, &format!("prepend to {}", name))
+    }
+
+    /// Insert `new_content` at the end of container `name`'s body in `path`
+    pub fn append_to_container(
+        &mut self,
+        path: &Path,
+        name: &str,
+        new_content: &str,
+    ) -> Result<(), String> {
+        let content = self.original(path)?.to_string();
+        let body = self
+            .editor
+            .find_container_body(path, &content, name)
+            .ok_or_else(|| format!("Container not found: {}", name))?;
+        let edit = self
+            .editor
+            .append_to_container_edit(&content, &body, new_content);
+        self.add(path, edit, &format!("append to {}", name))
+    }
+
+    /// Insert `new_content` at the beginning of `path`
+    pub fn prepend_to_file(&mut self, path: &Path, new_content: &str) -> Result<(), String> {
+        let edit = self.editor.prepend_to_file_edit(new_content);
+        self.add(path, edit, "prepend to file")
+    }
+
+    /// Insert `new_content` at the end of `path`
+    pub fn append_to_file(&mut self, path: &Path, new_content: &str) -> Result<(), String> {
+        let content = self.original(path)?.to_string();
+        let edit = self.editor.append_to_file_edit(&content, new_content);
+        self.add(path, edit, "append to file")
+    }
+
+    /// Delete `path` when the plan is applied.
+    ///
+    /// Fails if the file doesn't exist or already has edits in the plan.
+    pub fn remove_file(&mut self, path: &Path) -> Result<(), String> {
+        let file = self.file(path)?;
+        if file.created {
+            return Err(format!("Cannot remove {}: file not found", path.display()));
+        }
+        if let Some(existing) = file.edits.first() {
+            return Err(format!(
+                "Conflicting edits in {}: {} and remove file",
+                path.display(),
+                existing.label
+            ));
+        }
+        file.removed = true;
+        Ok(())
+    }
+
+    /// Files the plan deletes, in path order
+    pub fn removals(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(|(_, f)| f.removed)
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// New content of every file the plan changes, in path order
+    pub fn render(&self) -> Vec<(PathBuf, String)> {
+        self.files
+            .iter()
+            .filter(|(_, f)| !f.edits.is_empty())
+            .map(|(path, f)| (path.clone(), f.render()))
+            .filter(|(path, modified)| *modified != self.files[path].original)
+            .collect()
+    }
+
+    /// Unified diff of the plan against the original contents, without writing
+    pub fn diff(&self) -> String {
+        let mut out = String::new();
+        for (path, modified) in self.render() {
+            let original = &self.files[&path].original;
+            let name = path.display().to_string();
+            let diff = similar::TextDiff::from_lines(original.as_str(), modified.as_str());
+            out.push_str(
+                &diff
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&format!("a/{}", name), &format!("b/{}", name))
+                    .to_string(),
+            );
+        }
+        for path in self.removals() {
+            let original = &self.files[path].original;
+            let diff = similar::TextDiff::from_lines(original.as_str(), "");
+            out.push_str(
+                &diff
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&format!("a/{}", path.display()), "/dev/null")
+                    .to_string(),
+            );
+        }
+        out
+    }
+
+    /// Write every changed file and delete every removed one, or do none of it.
+    ///
+    /// Files that didn't exist when planned are created, along with missing
+    /// parent directories. Removals happen after every write has landed.
+    /// Fails without writing if a file changed on disk after the plan read
+    /// it. New contents are staged in uniquely named temp files next to each
+    /// file (keeping the file's permissions) and renamed into place; if a
+    /// rename or removal fails, files already replaced or removed are
+    /// restored. Returns the paths written, then the paths removed.
+    pub fn apply(&self) -> Result<Vec<PathBuf>, String> {
+        let changes = self.render();
+        let removals: Vec<&Path> = self.removals().collect();
+        let touched = changes.iter().map(|(path, _)| path.as_path());
+        for path in touched.chain(removals.iter().copied()) {
+            let file = &self.files[path];
+            let current = match std::fs::read_to_string(path) {
+                Ok(current) => Some(current),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+            };
+            let unchanged = match current {
+                Some(current) => !file.created && current == file.original,
+                None => file.created,
+            };
+            if !unchanged {
+                return Err(format!(
+                    "{} changed on disk since the edits were planned",
+                    path.display()
+                ));
+            }
+        }
+
+        let mut staged = Vec::with_capacity(changes.len());
+        for (path, content) in &changes {
+            // Staged files that aren't persisted are removed when dropped.
+            let tmp = self
+                .stage(path, content)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            staged.push(tmp);
+        }
+
+        for (i, ((path, _), tmp)) in changes.iter().zip(staged).enumerate() {
+            if let Err(e) = tmp.persist(path) {
+                self.restore(changes[..i].iter().map(|(path, _)| path.as_path()));
+                return Err(format!("Failed to write {}: {}", path.display(), e.error));
+            }
+        }
+
+        for (i, path) in removals.iter().enumerate() {
+            if let Err(e) = std::fs::remove_file(path) {
+                let written = changes.iter().map(|(path, _)| path.as_path());
+                self.restore(written.chain(removals[..i].iter().copied()));
+                return Err(format!("Failed to remove {}: {}", path.display(), e));
+            }
+        }
+
+        let written = changes.into_iter().map(|(path, _)| path);
+        Ok(written
+            .chain(removals.into_iter().map(Path::to_path_buf))
+            .collect())
+    }
+
+    /// Put `paths` back the way the plan found them (best effort, for rollback)
+    fn restore<'a>(&self, paths: impl Iterator<Item = &'a Path>) {
+        for path in paths {
+            let file = &self.files[path];
+            let _ = if file.created {
+                std::fs::remove_file(path)
+            } else {
+                std::fs::write(path, &file.original)
+            };
+        }
+    }
+
+    /// Write `content` to a temp file in `path`'s directory, ready to be
+    /// renamed over it
+    fn stage(&self, path: &Path, content: &str) -> std::io::Result<NamedTempFile> {
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let mut builder = tempfile::Builder::new();
+        if self.files[path].created {
+            std::fs::create_dir_all(dir)?;
+        } else {
+            builder.permissions(std::fs::metadata(path)?.permissions());
+        }
+        let mut tmp = builder.prefix(".normalize-edit").tempfile_in(dir)?;
+        tmp.write_all(content.as_bytes())?;
+        Ok(tmp)
+    }
+
+    /// The planned file for `path`, reading it on first use
+    fn file(&mut self, path: &Path) -> Result<&mut PlannedFile, String> {
+        if !self.files.contains_key(path) {
+            let (original, created) = match std::fs::read_to_string(path) {
+                Ok(original) => (original, false),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (String::new(), true),
+                Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+            };
+            self.files.insert(
+                path.to_path_buf(),
+                PlannedFile {
+                    original,
+                    created,
+                    removed: false,
+                    edits: Vec::new(),
+                },
+            );
+        }
+        Ok(self.files.get_mut(path).expect("inserted above"))
+    }
+
+    /// Original content of `path` and the location of symbol `name` in it
+    fn locate(&mut self, path: &Path, name: &str) -> Result<(String, SymbolLocation), String> {
+        let content = self.original(path)?.to_string();
+        let loc = self
+            .editor
+            .find_unique_symbol(path, &content, name, false)?;
+        Ok((content, loc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_conflicting_edits_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.add(&path, TextEdit::new(4, 8, "TWO\n"), "first")
+            .unwrap();
+        let err = plan
+            .add(&path, TextEdit::new(6, 10, ""), "second")
+            .unwrap_err();
+        assert!(err.contains("first") && err.contains("second"), "{}", err);
+        // An insertion inside a replaced range conflicts too.
+        assert!(plan.add(&path, TextEdit::insert(5, "x"), "third").is_err());
+        // Touching ranges don't.
+        plan.add(&path, TextEdit::insert(8, "2.5\n"), "fourth")
+            .unwrap();
+        assert_eq!(plan.len(), 2);
+    }
+
+    #[test]
+    fn test_edits_are_located_against_original_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.add(&path, TextEdit::new(0, 4, ""), "delete one")
+            .unwrap();
+        plan.add(&path, TextEdit::new(8, 13, "THREE"), "replace three")
+            .unwrap();
+        plan.add(&path, TextEdit::insert(8, "2.5\n"), "insert")
+            .unwrap();
+        let rendered = plan.render();
+        assert_eq!(
+            rendered,
+            vec![(path.clone(), "two\n2.5\nTHREE\n".to_string())]
+        );
+        // Nothing is written until apply.
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\nthree\n");
+
+        assert_eq!(plan.apply().unwrap(), vec![path.clone()]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two\n2.5\nTHREE\n");
+    }
+
+    #[test]
+    fn test_diff_is_unified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.add(&path, TextEdit::new(4, 7, "TWO"), "replace two")
+            .unwrap();
+        let diff = plan.diff();
+        let name = path.display();
+        assert!(
+            diff.starts_with(&format!("--- a/{}\n+++ b/{}\n", name, name)),
+            "{}",
+            diff
+        );
+        assert!(diff.contains("-two\n+TWO\n"), "{}", diff);
+    }
+
+    #[test]
+    fn test_apply_refuses_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        fs::write(&a, "a\n").unwrap();
+        fs::write(&b, "b\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.append_to_file(&a, "more a").unwrap();
+        plan.append_to_file(&b, "more b").unwrap();
+        fs::write(&b, "changed\n").unwrap();
+
+        assert!(plan.apply().unwrap_err().contains("changed on disk"));
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a\n");
+    }
+
+    #[test]
+    fn test_apply_creates_files_and_leaves_no_staging_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("sub/b.txt");
+        fs::write(&a, "a\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.append_to_file(&a, "more a").unwrap();
+        plan.append_to_file(&b, "b").unwrap();
+        assert_eq!(plan.apply().unwrap(), vec![a.clone(), b.clone()]);
+
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a\nmore a\n");
+        assert!(fs::read_to_string(&b).unwrap().contains('b'));
+        let mut names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["a.txt", "sub"]);
+    }
+
+    #[test]
+    fn test_remove_file_moves_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("old.txt");
+        let to = dir.path().join("new/moved.txt");
+        fs::write(&from, "content\n").unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.add(&to, TextEdit::insert(0, "content\n"), "move")
+            .unwrap();
+        plan.remove_file(&from).unwrap();
+        assert!(plan.append_to_file(&from, "more").is_err());
+        assert!(plan.remove_file(&to).is_err());
+        assert_eq!(plan.len(), 2);
+        assert!(plan.diff().contains("+++ /dev/null"));
+
+        assert_eq!(plan.apply().unwrap(), vec![to.clone(), from.clone()]);
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "content\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.sh");
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.append_to_file(&path, "echo hi").unwrap();
+        plan.apply().unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_symbol_operations_compose() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.py");
+        fs::write(
+            &path,
+            "def foo():\n    pass\n\ndef bar():\n    return 42\n\ndef baz():\n    return 1\n",
+        )
+        .unwrap();
+
+        let mut plan = EditPlan::new();
+        plan.delete(&path, "foo").unwrap();
+        plan.insert_after(&path, "bar", "def qux():\n    pass")
+            .unwrap();
+        plan.replace(&path, "baz", "def baz():\n    return 2")
+            .unwrap();
+        assert!(plan.delete(&path, "missing").is_err());
+
+        let (_, result) = plan.render().pop().unwrap();
+        assert!(!result.contains("foo"));
+        let bar = result.find("def bar").unwrap();
+        let qux = result.find("def qux").unwrap();
+        let baz = result.find("def baz").unwrap();
+        assert!(bar < qux && qux < baz, "{}", result);
+        assert!(result.contains("return 2"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Leading trivia: the comments, decorators and attributes attached to a
+//! definition.
+//!
+//! Extractors report a symbol's span as the definition node alone, so a
+//! Python function's decorators or a Rust item's `#[...]` attributes and doc
+//! comments sit outside it. [`leading_trivia_start`] finds where that attached
+//! block begins; [`IncludeTrivia`] asks [`Editor::find_symbol_with`] to widen
+//! the returned location to it.
+//!
+//! [`Editor::find_symbol_with`]: crate::Editor::find_symbol_with
+
+use crate::SymbolLocation;
+use normalize_languages::parsers::{grammar_loader, parse_with_grammar};
+use normalize_languages::{satisfies_predicates, support_for_path};
+use std::collections::HashSet;
+use std::path::Path;
+use streaming_iterator::StreamingIterator;
+
+/// How much of a symbol a located range covers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IncludeTrivia {
+    /// The definition only.
+    #[default]
+    Definition,
+    /// The definition plus the comments, decorators and attributes directly
+    /// above it (no blank line or other code in between).
+    Leading,
+}
+
+/// Tree-sitter node `kind` values that count as a leading decoration
+/// (doc comment, attribute, decorator, annotation, pragma) for any language.
+///
+/// Comment kinds are matched separately by substring: any kind containing
+/// `"comment"` is treated as a decoration. Listed here are the non-comment
+/// kinds across the grammars normalize supports.
+const DECORATION_KINDS: &[&str] = &[
+    "attribute_item",       // Rust outer attribute `#[...]`
+    "inner_attribute_item", // Rust inner attribute `#![...]`
+    "meta_item",            // Rust attribute body
+    "attribute",            // C#, generic
+    "attribute_list",       // C#
+    "decorator",            // Python, JS/TS (TypeScript decorators)
+    "decorator_list",       // grouped decorators
+    "annotation",           // Java, Kotlin
+    "marker_annotation",    // Java
+    "modifiers",            // Java/Kotlin annotations live under `modifiers`
+    "pragma",               // C/C++
+    "preproc_call",         // C/C++ preprocessor lines like `#pragma`
+];
+
+fn is_decoration_kind(kind: &str) -> bool {
+    kind.contains("comment") || DECORATION_KINDS.contains(&kind)
+}
+
+/// Tree-sitter node kinds that wrap a definition together with its decorations
+/// (decorators, attributes, export modifier, etc.) under a single parent. When
+/// the captured symbol node's parent is one of these, leading comments live as
+/// siblings of the *wrapper*, not of the symbol — so the walk must climb to
+/// the wrapper before scanning previous siblings.
+const DECORATION_WRAPPER_KINDS: &[&str] = &[
+    "decorated_definition", // Python `@decorator\ndef foo()` / `@decorator\nclass Foo`
+    "export_statement",     // TypeScript/JavaScript `export function foo()` / `export class Foo`
+    "export_default_declaration", // TypeScript/JavaScript `export default class Foo`
+    "ambient_declaration",  // TypeScript `declare ...`
+];
+
+fn is_decoration_wrapper_kind(kind: &str) -> bool {
+    DECORATION_WRAPPER_KINDS.contains(&kind)
+}
+
+/// Start of a symbol including its leading trivia.
+///
+/// Walks backward from the symbol's node through preceding named siblings,
+/// collecting decoration nodes (doc comments, attributes, decorators, etc.).
+/// Returns `(byte_offset, warning)` where:
+/// - `byte_offset` is the line-start of the earliest decoration found, or
+///   `loc.start_byte` if there are no decorations or no grammar is available.
+/// - `warning` is `Some(msg)` when the function fell back because the grammar
+///   was unavailable; `None` when the grammar was used (even if no decorations
+///   were found).
+///
+/// Classification is by `node.kind()` from the grammar — never by source text.
+pub fn leading_trivia_start(
+    file: &Path,
+    content: &str,
+    loc: &SymbolLocation,
+    // normalize-syntax-allow: rust/tuple-return
+) -> (usize, Option<String>) {
+    let fallback = loc.start_byte;
+    let Some(support) = support_for_path(file) else {
+        let ext = file
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("<unknown>");
+        return (
+            fallback,
+            Some(format!(
+                "No language support for {ext}: doc comments and attributes will not be included with the symbol"
+            )),
+        );
+    };
+    let grammar = support.grammar_name();
+    let Some(tree) = parse_with_grammar(grammar, content) else {
+        return (
+            fallback,
+            Some(format!(
+                "Grammar for {grammar} not loaded: doc comments and attributes will not be included. Install grammars with `normalize grammars install`."
+            )),
+        );
+    };
+
+    let root = tree.root_node();
+    // The symbol's def node — descendant_for_byte_range returns the smallest
+    // node containing the range. Using the full [start, end) of the symbol can
+    // overshoot the def node when end_byte is set to the start of the line
+    // after the symbol (a common convention) — that byte may not lie within
+    // the def node, forcing us up to `module`. Use a point query at the start
+    // byte to anchor on the def itself; we then walk up to find the outermost
+    // ancestor that begins at the same byte.
+    let sym_start = loc.start_byte.min(content.len());
+    let Some(mut node) = root.descendant_for_byte_range(sym_start, sym_start) else {
+        return (fallback, None);
+    };
+
+    // descendant_for_byte_range may return a small inner node (e.g. an identifier)
+    // when the symbol's start byte is line-aligned. Walk up to the outermost
+    // ancestor whose start_byte equals the matched node's start_byte — this is
+    // the def/declaration node we want preceding-sibling info for.
+    while let Some(parent) = node.parent() {
+        if parent.start_byte() == node.start_byte() && parent.id() != root.id() {
+            node = parent;
+        } else {
+            break;
+        }
+    }
+
+    // Build the set of decoration node IDs using the decorations query when
+    // available, falling back to the hardcoded kind list otherwise.
+    let loader = grammar_loader();
+    let decoration_ids: Option<HashSet<usize>> = loader.get_decorations(grammar).and_then(|q| {
+        let compiled = loader.get_compiled_query(grammar, "decorations", &q)?;
+        let mut qcursor = tree_sitter::QueryCursor::new();
+        let mut matches = qcursor.matches(&compiled, root, content.as_bytes());
+        let mut ids = HashSet::new();
+        let source_bytes = content.as_bytes();
+        while let Some(m) = matches.next() {
+            if !satisfies_predicates(&compiled, m, source_bytes) {
+                continue;
+            }
+            for capture in m.captures {
+                ids.insert(capture.node.id());
+            }
+        }
+        Some(ids)
+    });
+
+    let is_decoration = |n: tree_sitter::Node<'_>| -> bool {
+        if let Some(ref ids) = decoration_ids {
+            ids.contains(&n.id())
+        } else {
+            is_decoration_kind(n.kind())
+        }
+    };
+
+    // Walk preceding named siblings while they classify as decorations.
+    //
+    // Some grammars wrap a definition together with its decorators/attributes
+    // under a single node (e.g. Python `decorated_definition`, TS `export_statement`).
+    // When we exhaust prev siblings within that wrapper, climb to the wrapper
+    // and continue scanning siblings of the wrapper itself — leading comments
+    // live there, not under the wrapper.
+    let initial_start = node.start_byte();
+    let mut earliest_start = initial_start;
+    let mut cursor = node;
+    loop {
+        while let Some(prev) = cursor.prev_named_sibling() {
+            if !is_decoration(prev) {
+                // Encountered a non-decoration sibling; stop entirely.
+                return finalize(content, earliest_start, initial_start, fallback);
+            }
+            // Only include if the gap between `prev` and the decoration block we've
+            // already accepted is whitespace-only (no intervening code/punctuation).
+            let gap = &content.as_bytes()[prev.end_byte()..earliest_start];
+            if !gap.iter().all(|b| b.is_ascii_whitespace()) {
+                return finalize(content, earliest_start, initial_start, fallback);
+            }
+            earliest_start = prev.start_byte();
+            cursor = prev;
+        }
+        // No more prev siblings inside the current scope. If the parent is a
+        // known decoration-wrapper, step out to the wrapper and keep scanning.
+        let Some(parent) = cursor.parent() else { break };
+        if parent.id() == root.id() || !is_decoration_wrapper_kind(parent.kind()) {
+            break;
+        }
+        // The wrapper's leading content (everything up to its first child) is
+        // part of the symbol's surface — walk wrapper's prev siblings next.
+        cursor = parent;
+    }
+    finalize(content, earliest_start, initial_start, fallback)
+}
+
+fn finalize(
+    content: &str,
+    earliest_start: usize,
+    initial_start: usize,
+    fallback: usize,
+    // normalize-syntax-allow: rust/tuple-return
+) -> (usize, Option<String>) {
+    if earliest_start == initial_start {
+        return (fallback, None);
+    }
+    // Snap to the start of the line containing earliest_start so we capture
+    // any indentation on that line (consistent with `delete_symbol`'s line
+    // semantics).
+    let snapped = content[..earliest_start]
+        .rfind('\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    (snapped, None)
+}
@@ -141,6 +141,9 @@ pub struct Symbol {
     /// query. `None` for symbol kinds where complexity doesn't apply (classes, modules, etc.)
     /// or when the language has no complexity query and the base value could not be computed.
     pub complexity: Option<usize>,
+    /// Maximum nesting depth of control structures inside the function (0 for
+    /// straight-line code). Set alongside [`Symbol::complexity`].
+    pub nesting_depth: Option<usize>,
 }
 
 /// A flattened symbol for indexing (parent reference instead of nested children)
@@ -169,4 +172,6 @@ pub struct FlatSymbol {
     pub docstring: Option<String>,
     /// Cyclomatic complexity, computed for function/method symbols. See [`Symbol::complexity`].
     pub complexity: Option<usize>,
+    /// Nesting depth, computed for function/method symbols. See [`Symbol::nesting_depth`].
+    pub nesting_depth: Option<usize>,
 }
@@ -40,6 +40,9 @@ pub struct Location {
     pub line: u32,
     /// Column number (1-indexed, optional)
     pub column: Option<u32>,
+    /// Last line of the span (1-indexed, inclusive; optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
 }
 
 impl Location {
@@ -49,6 +52,7 @@ impl Location {
             file: file.into(),
             line,
             column: None,
+            end_line: None,
         }
     }
 
@@ -58,6 +62,17 @@ impl Location {
             file: file.into(),
             line,
             column: Some(column),
+            end_line: None,
+        }
+    }
+
+    /// Create a location spanning `start_line..=end_line`
+    pub fn span(file: &str, start_line: u32, end_line: u32) -> Self {
+        Self {
+            file: file.into(),
+            line: start_line,
+            column: None,
+            end_line: Some(end_line),
         }
     }
 }
@@ -116,6 +131,12 @@ impl Diagnostic {
         self
     }
 
+    /// Set the primary location to a line span
+    pub fn at_span(mut self, file: &str, start_line: u32, end_line: u32) -> Self {
+        self.location = Some(Location::span(file, start_line, end_line));
+        self
+    }
+
     /// Add a related location
     pub fn with_related(mut self, file: &str, line: u32) -> Self {
         self.related.push(Location::new(file, line));
@@ -0,0 +1,55 @@
+//! Structured rule output beyond diagnostics.
+//!
+//! Rules report problems as [`Diagnostic`]s. A rule can also declare output
+//! relations whose tuples are measurements rather than problems (e.g. a
+//! per-file size or a per-module coupling score); those come back as
+//! [`Metric`]s. [`Finding`] carries either kind.
+
+use crate::{Diagnostic, Location};
+use serde::{Deserialize, Serialize};
+
+/// A measurement emitted by a rule through a declared output relation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Metric {
+    /// Rule ID that produced this metric
+    pub rule_id: String,
+    /// Output relation the tuple was read from
+    pub relation: String,
+    /// Metric name (the `name` column, or the relation name when there is none)
+    pub name: String,
+    /// Measured value
+    pub value: f64,
+    /// Location the measurement applies to (`None` for project-wide metrics)
+    pub location: Option<Location>,
+    /// Remaining columns in declaration order, rendered as text
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<(String, String)>,
+}
+
+/// Any output of a rule: a diagnostic or a metric.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Finding {
+    /// A problem report
+    Diagnostic(Diagnostic),
+    /// A measurement
+    Metric(Metric),
+}
+
+impl Finding {
+    /// The rule that produced this finding.
+    pub fn rule_id(&self) -> &str {
+        match self {
+            Finding::Diagnostic(d) => &d.rule_id,
+            Finding::Metric(m) => &m.rule_id,
+        }
+    }
+
+    /// The finding's location, if it has one.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Finding::Diagnostic(d) => d.location.as_ref(),
+            Finding::Metric(m) => m.location.as_ref(),
+        }
+    }
+}
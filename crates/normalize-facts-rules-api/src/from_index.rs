@@ -0,0 +1,337 @@
+//! Populating [`Relations`] from the persistent facts index (feature `index`).
+//!
+//! [`Relations::from_index`] reads the symbol, call (including resolved call
+//! targets), import, type, file info and CFG tables that `normalize structure
+//! rebuild` maintains, so rule runs never re-walk or re-parse the project.
+//! A [`RelationsFilter`] narrows the result to a subset of files without
+//! cutting cross-file edges: calls and imports out of the subset are kept, and
+//! the declarations at the other end of each resolved edge (in either
+//! direction) stay visible, so rules that join across files still match.
+
+use crate::Relations;
+use normalize_facts::FileIndex;
+use std::collections::HashSet;
+
+/// Which files [`Relations::from_index`] loads facts for.
+#[derive(Clone, Debug, Default)]
+pub struct RelationsFilter {
+    /// Root-relative paths as stored in the index; `None` means every file.
+    files: Option<HashSet<String>>,
+}
+
+impl RelationsFilter {
+    /// Every file in the index.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Only facts owned by `files` (root-relative, as stored in the index),
+    /// plus the declarations of files they are linked to by resolved imports
+    /// or calls.
+    pub fn files<I, S>(files: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            files: Some(files.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Whether facts owned by `file` are in scope.
+    pub fn includes(&self, file: &str) -> bool {
+        self.files.as_ref().is_none_or(|f| f.contains(file))
+    }
+}
+
+/// Map an index error to the `Failed to get <what>: <err>` message used by callers.
+fn context<E: std::fmt::Display>(what: &'static str) -> impl FnOnce(E) -> String {
+    move |e| format!("Failed to get {what}: {e}")
+}
+
+impl Relations {
+    /// Build relations from the facts index, scoped by `filter`.
+    ///
+    /// Per-file facts (calls, resolved calls, qualifiers, imports, file info,
+    /// CFG edges and effects) are loaded for in-scope files only. Declaration facts (symbols,
+    /// ranges, visibility, parents, attributes, implements, type methods) are
+    /// loaded for in-scope files and for every file one resolved import or call
+    /// away from them, in either direction. Churn and dependency facts are not
+    /// stored per file in these tables; callers add them separately.
+    pub async fn from_index(idx: &FileIndex, filter: &RelationsFilter) -> Result<Self, String> {
+        // Files whose declarations are needed to keep cross-file edges intact.
+        let linked: Option<HashSet<String>> = match &filter.files {
+            None => None,
+            Some(scope) => {
+                let mut linked = scope.clone();
+                let mut edges = idx
+                    .all_resolved_import_edges()
+                    .await
+                    .map_err(context("resolved imports"))?;
+                edges.extend(
+                    idx.all_resolved_call_edges()
+                        .await
+                        .map_err(context("resolved calls"))?,
+                );
+                for (from, to) in edges {
+                    if scope.contains(&from) {
+                        linked.insert(to);
+                    } else if scope.contains(&to) {
+                        linked.insert(from);
+                    }
+                }
+                Some(linked)
+            }
+        };
+        let declared = |file: &str| linked.as_ref().is_none_or(|l| l.contains(file));
+        let owned = |file: &str| filter.includes(file);
+
+        let mut relations = Relations::new();
+
+        // Symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl)
+        let symbols = idx
+            .all_symbols_with_details()
+            .await
+            .map_err(context("symbols"))?;
+        for (file, name, kind, start_line, end_line, parent, visibility, is_impl) in &symbols {
+            if !declared(file) {
+                continue;
+            }
+            relations.add_symbol(file, name, kind, *start_line as u32);
+            relations.add_symbol_range(file, name, *start_line as u32, *end_line as u32);
+            relations.add_visibility(file, name, visibility);
+            if let Some(parent_name) = parent {
+                relations.add_parent(file, name, parent_name);
+            }
+            if *is_impl {
+                relations.add_is_impl(file, name);
+            }
+        }
+
+        let attrs = idx
+            .all_symbol_attributes()
+            .await
+            .map_err(context("symbol attributes"))?;
+        for (file, name, attribute) in attrs.iter().filter(|a| declared(&a.0)) {
+            relations.add_attribute(file, name, attribute);
+        }
+
+        let implements = idx
+            .all_symbol_implements()
+            .await
+            .map_err(context("symbol implements"))?;
+        for (file, name, interface) in implements.iter().filter(|i| declared(&i.0)) {
+            relations.add_implements(file, name, interface);
+        }
+
+        let type_methods = idx
+            .all_type_methods()
+            .await
+            .map_err(context("type methods"))?;
+        for (file, type_name, method_name) in type_methods.iter().filter(|t| declared(&t.0)) {
+            relations.add_type_method(file, type_name, method_name);
+        }
+
+        // Imports (file, module, name, line)
+        let imports = idx.all_imports().await.map_err(context("imports"))?;
+        for (file, module, name, _line) in imports.iter().filter(|i| owned(&i.0)) {
+            relations.add_import(file, module, name);
+        }
+
+        // Calls (caller_file, caller_symbol, callee_name, qualifier, line)
+        let calls = idx
+            .all_calls_with_qualifiers()
+            .await
+            .map_err(context("calls"))?;
+        for (file, caller, callee, qualifier, line) in calls.iter().filter(|c| owned(&c.0)) {
+            relations.add_call(file, caller, callee, *line);
+            if let Some(qual) = qualifier {
+                relations.add_qualifier(file, caller, callee, qual);
+            }
+        }
+
+        // Resolved calls (caller_file, caller_symbol, callee_file, callee_name, line)
+        let resolved_calls = idx
+            .all_resolved_calls()
+            .await
+            .map_err(context("resolved calls"))?;
+        for (file, caller, callee_file, callee, line) in
+            resolved_calls.iter().filter(|c| owned(&c.0))
+        {
+            relations.add_resolved_call(file, caller, callee_file, callee, *line);
+        }
+
+        let file_info = idx.all_file_info().await.map_err(context("file info"))?;
+        for info in file_info.iter().filter(|f| owned(&f.file)) {
+            relations.add_file_info(
+                &info.file,
+                &info.language,
+                u32::try_from(info.lines).unwrap_or(u32::MAX),
+                u32::try_from(info.blank_lines).unwrap_or(u32::MAX),
+                u32::try_from(info.comment_lines).unwrap_or(u32::MAX),
+            );
+        }
+
+        let complexity = idx
+            .all_symbol_complexity()
+            .await
+            .map_err(context("complexity"))?;
+        for (file, name, cyclomatic, depth) in complexity.iter().filter(|c| owned(&c.0)) {
+            relations.add_complexity(
+                file,
+                name,
+                u32::try_from(*cyclomatic).unwrap_or(u32::MAX),
+                u32::try_from(*depth).unwrap_or(u32::MAX),
+            );
+        }
+
+        let cfg_edges = idx.all_cfg_edges().await.map_err(context("CFG edges"))?;
+        for (file, func, func_line, from, to, kind, exception_type) in
+            cfg_edges.iter().filter(|e| owned(&e.0))
+        {
+            relations.add_cfg_edge(file, func, *func_line, *from, *to, kind, exception_type);
+        }
+
+        let cfg_effects = idx
+            .all_cfg_effects()
+            .await
+            .map_err(context("CFG effects"))?;
+        for (file, func, func_line, block, kind, line, label) in
+            cfg_effects.iter().filter(|e| owned(&e.0))
+        {
+            relations.add_cfg_effect(file, func, *func_line, *block, kind, *line, label);
+        }
+
+        Ok(relations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    async fn indexed(files: &[(&str, &str)]) -> (tempfile::TempDir, FileIndex) {
+        let dir = tempdir().unwrap();
+        for (path, content) in files {
+            fs::write(dir.path().join(path), content).unwrap();
+        }
+        let mut idx = FileIndex::open(&dir.path().join("index.sqlite"), dir.path())
+            .await
+            .unwrap();
+        idx.refresh().await.unwrap();
+        idx.refresh_call_graph().await.unwrap();
+        (dir, idx)
+    }
+
+    fn symbol_files(relations: &Relations) -> HashSet<&str> {
+        relations.symbols.iter().map(|s| s.file.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn all_filter_loads_every_file() {
+        let (_dir, idx) = indexed(&[
+            ("a.py", "def alpha():\n    beta()\n"),
+            ("b.py", "def beta(): pass\n"),
+        ])
+        .await;
+        let relations = Relations::from_index(&idx, &RelationsFilter::all())
+            .await
+            .unwrap();
+        assert_eq!(symbol_files(&relations), HashSet::from(["a.py", "b.py"]));
+        assert_eq!(relations.calls.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn file_filter_keeps_declarations_across_resolved_imports() {
+        let (_dir, idx) = indexed(&[
+            ("a.py", "from b import beta\n\ndef alpha():\n    beta()\n"),
+            ("b.py", "def beta(): pass\n"),
+            ("c.py", "def gamma(): pass\n"),
+        ])
+        .await;
+        let relations = Relations::from_index(&idx, &RelationsFilter::files(["a.py"]))
+            .await
+            .unwrap();
+
+        // b.py is imported by a.py, so its declarations stay; c.py is unrelated.
+        assert_eq!(symbol_files(&relations), HashSet::from(["a.py", "b.py"]));
+        assert!(relations.calls.iter().all(|c| c.caller_file == "a.py"));
+        assert!(relations.imports.iter().all(|i| i.from_file == "a.py"));
+
+        // Scoping to the imported file keeps the importer's declarations too.
+        let relations = Relations::from_index(&idx, &RelationsFilter::files(["b.py"]))
+            .await
+            .unwrap();
+        assert_eq!(symbol_files(&relations), HashSet::from(["a.py", "b.py"]));
+        assert!(relations.calls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resolved_calls_name_the_defining_file() {
+        let (_dir, idx) = indexed(&[
+            ("a.py", "from b import beta\n\ndef alpha():\n    beta()\n    gamma()\n\ndef gamma(): pass\n"),
+            ("b.py", "def beta(): pass\n"),
+        ])
+        .await;
+        let relations = Relations::from_index(&idx, &RelationsFilter::files(["a.py"]))
+            .await
+            .unwrap();
+        let mut resolved: Vec<(&str, &str, &str, &str)> = relations
+            .resolved_calls
+            .iter()
+            .map(|c| {
+                (
+                    c.caller_file.as_str(),
+                    c.caller_name.as_str(),
+                    c.callee_file.as_str(),
+                    c.callee_name.as_str(),
+                )
+            })
+            .collect();
+        resolved.sort();
+        assert_eq!(
+            resolved,
+            vec![
+                ("a.py", "alpha", "a.py", "gamma"),
+                ("a.py", "alpha", "b.py", "beta"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn file_info_counts_blank_and_comment_lines() {
+        let (_dir, idx) = indexed(&[
+            ("a.py", "# header\n\ndef alpha():\n    return 1  # one\n"),
+            ("b.py", "def beta(): pass\n"),
+        ])
+        .await;
+        let relations = Relations::from_index(&idx, &RelationsFilter::files(["a.py"]))
+            .await
+            .unwrap();
+        assert_eq!(relations.file_info.len(), 1);
+        let info = &relations.file_info[0];
+        assert_eq!(info.file, "a.py");
+        assert_eq!(info.language, "python");
+        assert_eq!((info.loc, info.blank_lines, info.comment_lines), (4, 1, 2));
+    }
+
+    #[tokio::test]
+    async fn complexity_covers_functions() {
+        let (_dir, idx) = indexed(&[(
+            "a.py",
+            "def alpha(x):\n    if x:\n        for i in x:\n            pass\n    return 1\n\nclass B:\n    pass\n",
+        )])
+        .await;
+        let relations = Relations::from_index(&idx, &RelationsFilter::all())
+            .await
+            .unwrap();
+        let facts: Vec<_> = relations
+            .complexity
+            .iter()
+            .map(|c| (c.file.as_str(), c.name.as_str(), c.cyclomatic, c.depth))
+            .collect();
+        assert_eq!(facts, [("a.py", "alpha", 3, 2)]);
+    }
+}
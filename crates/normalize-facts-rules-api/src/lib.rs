@@ -1,7 +1,7 @@
 //! Data types for normalize rule evaluation.
 //!
-//! This crate defines the `Relations` input facts and `Diagnostic` output type used
-//! by the fact rule engine. Rules run as interpreted `.dl` files via
+//! This crate defines the `Relations` input facts and the `Diagnostic` and
+//! `Metric` output types used by the fact rule engine. Rules run as interpreted `.dl` files via
 //! `normalize-facts-rules-interpret`; there is no dynamic library loading.
 //!
 //! # Architecture
@@ -12,14 +12,23 @@
 //!
 //! Facts are extracted from code by normalize-facts and passed to the Datalog engine.
 //! Each rule evaluates over these relations and produces Diagnostics.
+//! With the `index` feature, [`Relations::from_index`] loads them straight from
+//! the persistent facts index.
 
 mod diagnostic;
+mod finding;
+#[cfg(feature = "index")]
+mod from_index;
 mod relations;
 
 pub use diagnostic::{Diagnostic, DiagnosticLevel, Location};
+pub use finding::{Finding, Metric};
+#[cfg(feature = "index")]
+pub use from_index::RelationsFilter;
 pub use relations::{
-    AttributeFact, CallFact, ImplementsFact, ImportFact, IsImplFact, ParentFact, QualifierFact,
-    Relations, SymbolFact, SymbolRangeFact, TypeMethodFact, VisibilityFact,
+    AttributeFact, CallFact, ComplexityFact, FileInfoFact, ImplementsFact, ImportFact, IsImplFact,
+    ParentFact, QualifierFact, Relations, SymbolFact, SymbolRangeFact, TypeMethodFact,
+    VisibilityFact,
 };
 
 // Re-export ascent for rule implementors
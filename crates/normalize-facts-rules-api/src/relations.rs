@@ -14,6 +14,8 @@
 //! - `implements(file, name, interface)` - interface/trait implementation
 //! - `is_impl(file, name)` - symbol is a trait/interface implementation
 //! - `type_method(file, type_name, method_name)` - method signatures on types
+//! - `file_info(file, language, loc, blank_lines, comment_lines)` - per-file line counts
+//! - `complexity(file, name, cyclomatic, depth)` - per-function complexity metrics
 //!
 //! Cross-file resolution predicates (Phase 0):
 //!
@@ -25,6 +27,20 @@
 //! - `resolved_reference(use_file, use_line, def_file, def_name, def_kind)` - resolved symbol refs
 //! - `resolved_call(caller_file, caller_name, callee_file, callee_name, line)` - resolved calls
 //! - `module_search_path(workspace_root, language, kind, path)` - module search paths
+//!
+//! Dependency predicates:
+//!
+//! - `depends_on(package, dependency, version)` - resolved package dependencies
+//!
+//! Git history predicates:
+//!
+//! - `churn(file, commits_90d)` - commits touching a file in the last 90 days
+//!
+//! Agent session predicates (filled by `normalize sessions rules`, empty for code rules):
+//!
+//! - `tool_call(session, turn, call, tool, is_error)` - tool invocations and whether they failed
+//! - `session_cost(session, dollars)` - estimated session cost in USD
+//! - `api_error(session, turn, kind)` - provider API errors (rate limits, overloads, ...)
 
 /// A symbol fact: a named entity defined in a file.
 ///
@@ -326,6 +342,113 @@ pub struct ModuleSearchPathFact {
     pub path: String,
 }
 
+/// A dependency fact: one package depends on another.
+///
+/// Maps to Datalog: `depends_on(package, dependency, version)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct DependsOnFact {
+    /// Depending package (the project itself for direct dependencies)
+    pub package: String,
+    /// Dependency package name
+    pub dependency: String,
+    /// Installed version if resolved locally, else the declared requirement ("" if neither)
+    pub version: String,
+    /// Manifest declaring the dependency; used as the fact's source, not exposed to rules
+    pub manifest: String,
+}
+
+/// A file info fact: language and line counts of an indexed source file.
+///
+/// Maps to Datalog: `file_info(file, language, loc, blank_lines, comment_lines)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct FileInfoFact {
+    /// File path relative to project root
+    pub file: String,
+    /// Grammar name of the file's language (e.g. "python", "rust")
+    pub language: String,
+    /// Total number of lines, including blank and comment lines
+    pub loc: u32,
+    /// Lines containing only whitespace
+    pub blank_lines: u32,
+    /// Non-blank lines covered by a comment
+    pub comment_lines: u32,
+}
+
+/// A complexity fact: metrics of one function or method.
+///
+/// Maps to Datalog: `complexity(file, name, cyclomatic, depth)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct ComplexityFact {
+    /// File path relative to project root
+    pub file: String,
+    /// Function or method name
+    pub name: String,
+    /// McCabe cyclomatic complexity (decision points + 1)
+    pub cyclomatic: u32,
+    /// Maximum nesting depth of control structures inside the body
+    pub depth: u32,
+}
+
+/// A churn fact: how often a file changed recently.
+///
+/// Maps to Datalog: `churn(file, commits_90d)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct ChurnFact {
+    /// File path relative to project root
+    pub file: String,
+    /// Commits touching the file in the last 90 days
+    pub commits_90d: u32,
+}
+
+/// A tool call fact: one tool invocation in an agent session.
+///
+/// Maps to Datalog: `tool_call(session, turn, call, tool, is_error)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct ToolCallFact {
+    /// Session identifier
+    pub session: String,
+    /// 1-based turn number
+    pub turn: u32,
+    /// 0-based index of the call within the session. Keeps two identical calls
+    /// in one turn distinct, so `count()` aggregates see both.
+    pub call: u32,
+    /// Tool name (e.g. "Bash", "Read")
+    pub tool: String,
+    /// Whether the tool result was an error
+    pub is_error: bool,
+}
+
+/// A session cost fact.
+///
+/// Maps to Datalog: `session_cost(session, dollars)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct SessionCostFact {
+    /// Session identifier
+    pub session: String,
+    /// Estimated cost in USD
+    pub dollars: f64,
+}
+
+/// A provider API error fact.
+///
+/// Maps to Datalog: `api_error(session, turn, kind)`
+#[derive(Clone, Debug, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+#[rkyv(derive(Debug))]
+pub struct ApiErrorFact {
+    /// Session identifier
+    pub session: String,
+    /// 1-based turn number
+    pub turn: u32,
+    /// Error kind: "rate_limit", "overloaded", "timeout", "server" or "other"
+    pub kind: String,
+}
+
 /// A CFG block fact.
 ///
 /// Maps to Datalog: `cfg_block(file, func, func_line, block, kind)`
@@ -460,6 +583,10 @@ pub struct Relations {
     pub is_impls: Vec<IsImplFact>,
     /// Type method facts (method signatures on types)
     pub type_methods: Vec<TypeMethodFact>,
+    /// File info facts (language and line counts per source file)
+    pub file_info: Vec<FileInfoFact>,
+    /// Complexity facts (cyclomatic complexity and nesting depth per function)
+    pub complexity: Vec<ComplexityFact>,
     /// Resolved import facts (import resolved to a specific file)
     pub resolved_imports: Vec<ResolvedImportFact>,
     /// Module identity facts (file → canonical module path)
@@ -476,6 +603,16 @@ pub struct Relations {
     pub resolved_calls: Vec<ResolvedCallFact>,
     /// Module search path facts (directories to search for modules)
     pub module_search_paths: Vec<ModuleSearchPathFact>,
+    /// Dependency facts (package → dependency, from manifests and installed packages)
+    pub depends_on: Vec<DependsOnFact>,
+    /// Churn facts (recent commit count per file, from git history)
+    pub churn: Vec<ChurnFact>,
+    /// Tool call facts (agent sessions)
+    pub tool_calls: Vec<ToolCallFact>,
+    /// Session cost facts (agent sessions)
+    pub session_costs: Vec<SessionCostFact>,
+    /// API error facts (agent sessions)
+    pub api_errors: Vec<ApiErrorFact>,
     /// CFG block facts (one per basic block per function)
     pub cfg_blocks: Vec<CfgBlockFact>,
     /// CFG edge facts (one per control-flow edge per function)
@@ -714,6 +851,93 @@ impl Relations {
         });
     }
 
+    /// Add a dependency fact
+    pub fn add_depends_on(
+        &mut self,
+        package: &str,
+        dependency: &str,
+        version: &str,
+        manifest: &str,
+    ) {
+        self.depends_on.push(DependsOnFact {
+            package: package.into(),
+            dependency: dependency.into(),
+            version: version.into(),
+            manifest: manifest.into(),
+        });
+    }
+
+    /// Add a file info fact
+    pub fn add_file_info(
+        &mut self,
+        file: &str,
+        language: &str,
+        loc: u32,
+        blank_lines: u32,
+        comment_lines: u32,
+    ) {
+        self.file_info.push(FileInfoFact {
+            file: file.into(),
+            language: language.into(),
+            loc,
+            blank_lines,
+            comment_lines,
+        });
+    }
+
+    /// Add a complexity fact
+    pub fn add_complexity(&mut self, file: &str, name: &str, cyclomatic: u32, depth: u32) {
+        self.complexity.push(ComplexityFact {
+            file: file.into(),
+            name: name.into(),
+            cyclomatic,
+            depth,
+        });
+    }
+
+    /// Add a churn fact
+    pub fn add_churn(&mut self, file: &str, commits_90d: u32) {
+        self.churn.push(ChurnFact {
+            file: file.into(),
+            commits_90d,
+        });
+    }
+
+    /// Add a tool call fact
+    pub fn add_tool_call(
+        &mut self,
+        session: &str,
+        turn: u32,
+        call: u32,
+        tool: &str,
+        is_error: bool,
+    ) {
+        self.tool_calls.push(ToolCallFact {
+            session: session.into(),
+            turn,
+            call,
+            tool: tool.into(),
+            is_error,
+        });
+    }
+
+    /// Add a session cost fact
+    pub fn add_session_cost(&mut self, session: &str, dollars: f64) {
+        self.session_costs.push(SessionCostFact {
+            session: session.into(),
+            dollars,
+        });
+    }
+
+    /// Add an API error fact
+    pub fn add_api_error(&mut self, session: &str, turn: u32, kind: &str) {
+        self.api_errors.push(ApiErrorFact {
+            session: session.into(),
+            turn,
+            kind: kind.into(),
+        });
+    }
+
     /// Add a CFG block fact
     pub fn add_cfg_block(
         &mut self,
@@ -0,0 +1,153 @@
+//! Baselines: accepted pre-existing fact rule violations.
+//!
+//! A baseline records the diagnostics a project had when it adopted a rule, so
+//! later runs report only new violations. Entries are matched by rule id and a
+//! [`diagnostic_fingerprint`] that leaves out line numbers, so edits elsewhere
+//! in a file don't resurface baselined findings.
+
+use normalize_facts_rules_api::Diagnostic;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Baseline location, relative to the project root.
+pub const BASELINE_PATH: &str = ".normalize/facts-baseline.json";
+
+/// Current on-disk format version.
+const BASELINE_VERSION: u32 = 1;
+
+/// One accepted diagnostic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Rule that reported the diagnostic.
+    pub rule_id: String,
+    /// [`diagnostic_fingerprint`] of the diagnostic.
+    pub fingerprint: String,
+    /// File the diagnostic was reported against (informational).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    /// Diagnostic message (informational).
+    #[serde(default)]
+    pub message: String,
+}
+
+/// The set of diagnostics accepted by `.normalize/facts-baseline.json`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Format version.
+    pub version: u32,
+    /// Accepted diagnostics, sorted by file, rule and message. A diagnostic
+    /// reported N times needs N entries.
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Stable identity of a diagnostic for baseline matching.
+///
+/// Hashes the rule id, file and message, but not the line: a finding keeps its
+/// fingerprint when code above it moves.
+pub fn diagnostic_fingerprint(d: &Diagnostic) -> String {
+    let file = d.location.as_ref().map_or("", |l| l.file.as_str());
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(d.rule_id.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(file.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(d.message.as_bytes());
+    hasher.finalize().to_hex()[..16].to_string()
+}
+
+impl Baseline {
+    /// Baseline accepting exactly `diagnostics`.
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> Self {
+        let mut entries: Vec<BaselineEntry> = diagnostics
+            .iter()
+            .map(|d| BaselineEntry {
+                rule_id: d.rule_id.clone(),
+                fingerprint: diagnostic_fingerprint(d),
+                file: d
+                    .location
+                    .as_ref()
+                    .map(|l| l.file.clone())
+                    .unwrap_or_default(),
+                message: d.message.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.file, &a.rule_id, &a.message, &a.fingerprint).cmp(&(
+                &b.file,
+                &b.rule_id,
+                &b.message,
+                &b.fingerprint,
+            ))
+        });
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// Load the project's baseline. `Ok(None)` when there is none.
+    pub fn load(root: &Path) -> Result<Option<Self>, String> {
+        let path = root.join(BASELINE_PATH);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let baseline: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid baseline {}: {}", path.display(), e))?;
+        if baseline.version > BASELINE_VERSION {
+            return Err(format!(
+                "Baseline {} has version {}, newer than supported ({})",
+                path.display(),
+                baseline.version,
+                BASELINE_VERSION
+            ));
+        }
+        Ok(Some(baseline))
+    }
+
+    /// Write the baseline to the project's `.normalize/facts-baseline.json`.
+    pub fn save(&self, root: &Path) -> Result<(), String> {
+        let path = root.join(BASELINE_PATH);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
+        std::fs::write(&path, json + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Remove baselined diagnostics, returning how many were removed.
+    ///
+    /// Each entry absorbs at most one diagnostic, so a file that gains another
+    /// copy of a baselined finding still reports the extra one.
+    pub fn filter(&self, diagnostics: &mut Vec<Diagnostic>) -> usize {
+        // rule id → fingerprint → entries not yet matched
+        let mut remaining: HashMap<&str, HashMap<&str, usize>> = HashMap::new();
+        for entry in &self.entries {
+            *remaining
+                .entry(entry.rule_id.as_str())
+                .or_default()
+                .entry(entry.fingerprint.as_str())
+                .or_default() += 1;
+        }
+        let before = diagnostics.len();
+        diagnostics.retain(|d| {
+            let fingerprint = diagnostic_fingerprint(d);
+            match remaining
+                .get_mut(d.rule_id.as_str())
+                .and_then(|by_fp| by_fp.get_mut(fingerprint.as_str()))
+            {
+                Some(n) if *n > 0 => {
+                    *n -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+        before - diagnostics.len()
+    }
+}
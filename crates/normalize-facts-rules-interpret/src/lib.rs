@@ -41,16 +41,58 @@
 //! - `resolved_reference(use_file: String, use_line: u32, def_file: String, def_name: String, def_kind: String)` — Phase 0
 //! - `resolved_call(caller_file: String, caller_name: String, callee_file: String, callee_name: String, line: u32)` — Phase 0
 //! - `module_search_path(workspace_root: String, language: String, kind: String, path: String)` — Phase 0
+//! - `depends_on(package: String, dependency: String, version: String)` — resolved package dependencies
+//! - `churn(file: String, commits_90d: u32)` — commits touching a file in the last 90 days
+//! - `file_info(file: String, language: String, loc: u32, blank_lines: u32, comment_lines: u32)` — per-file line counts
+//! - `complexity(file: String, name: String, cyclomatic: u32, depth: u32)` — per-function complexity
 //!
-//! Output relation — all diagnostics go here:
+//! Session rules (see [`load_session_rules`]) instead see facts about agent sessions:
+//! - `tool_call(session: String, turn: u32, call: u32, tool: String, is_error: bool)`
+//! - `session_cost(session: String, dollars: f64)`
+//! - `api_error(session: String, turn: u32, kind: String)`
+//!
+//! Output relations — all diagnostics go here:
 //! - `diagnostic(severity, rule_id, file, line, message)` — severity = "warning"/"error"/"info"/"hint";
 //!   file = "" for no location; line = 0 when the source has no line info.
+//! - `warning_at(rule_id, file, line, message)` / `error_at(rule_id, file, line, message)` —
+//!   shorthand for `diagnostic` with a fixed severity.
+//! - `warning_span(rule_id, file, start_line, end_line, message)` / `error_span(...)` —
+//!   the same, located on an inclusive line range (e.g. a whole function from `symbol_range`).
+//!
+//! `allow` patterns match the diagnostic's `file`, so rules whose message is a
+//! symbol or module name stay suppressible by path. Diagnostics without a file
+//! are still matched by message, but that fallback is deprecated.
+//!
+//! Rules can also emit measurements. An `[output]` table in the frontmatter maps
+//! a relation the rule declares to column roles; [`run_rule_findings`] returns
+//! its tuples as [`Finding::Metric`]s alongside the rule's diagnostics:
+//!
+//! ```dl
+//! # ---
+//! # id = "file-size"
+//! # [output]
+//! # size = ["file", "value"]
+//! # ---
+//! relation size(String, u32);
+//! size(file, loc) <-- file_info(file, _, loc, _, _);
+//! ```
+//!
+//! Roles are `file`, `line`, `name` and `value` (required); any other column
+//! name is kept as a text field on the metric.
+//!
+//! A [`Baseline`] (`.normalize/facts-baseline.json`) lists accepted existing
+//! violations; [`Baseline::filter`] drops them so only new ones are reported.
+
+mod baseline;
+pub use baseline::{BASELINE_PATH, Baseline, BaselineEntry, diagnostic_fingerprint};
 
-use ascent_interpreter::eval::{Engine, SourceId, Value};
+use ascent_interpreter::eval::{Engine, OrderedFloat, SourceId, Value};
 use ascent_interpreter::ir::Program;
 use ascent_interpreter::syntax::AscentProgram;
 use glob::Pattern;
-use normalize_facts_rules_api::{Diagnostic, DiagnosticLevel, Relations};
+use normalize_facts_rules_api::{
+    ComplexityFact, Diagnostic, DiagnosticLevel, FileInfoFact, Finding, Location, Metric, Relations,
+};
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::hash::{DefaultHasher, Hash, Hasher};
@@ -78,12 +120,23 @@ relation symbol_use(String, String, u32);
 relation resolved_reference(String, u32, String, String, String);
 relation resolved_call(String, String, String, String, u32);
 relation module_search_path(String, String, String, String);
+relation depends_on(String, String, String);
+relation churn(String, u32);
+relation file_info(String, String, u32, u32, u32);
+relation complexity(String, String, u32, u32);
+relation tool_call(String, u32, u32, String, bool);
+relation session_cost(String, f64);
+relation api_error(String, u32, String);
 relation cfg_block(String, String, u32, u32, String);
 relation cfg_edge(String, String, u32, u32, u32, String, String);
 relation cfg_def(String, String, u32, u32, String);
 relation cfg_use(String, String, u32, u32, String);
 relation cfg_effect(String, String, u32, u32, String, u32, String);
 relation diagnostic(String, String, String, u32, String);
+relation warning_at(String, String, u32, String);
+relation error_at(String, String, u32, String);
+relation warning_span(String, String, u32, u32, String);
+relation error_span(String, String, u32, u32, String);
 "#;
 
 // =============================================================================
@@ -110,7 +163,7 @@ pub struct FactsRule {
     pub source: String,
     /// Description for display when listing rules.
     pub message: String,
-    /// Glob patterns for diagnostic messages to suppress.
+    /// Glob patterns for diagnostic files to suppress.
     pub allow: Vec<Pattern>,
     /// Severity level for diagnostics from this rule.
     pub severity: Severity,
@@ -126,6 +179,105 @@ pub struct FactsRule {
     pub doc: Option<String>,
     /// Whether this rule is recommended for most projects (catches real bugs, not style).
     pub recommended: bool,
+    /// Output relations read back as metrics, from the frontmatter `[output]` table.
+    pub outputs: Vec<OutputSpec>,
+    /// Severity by file pattern from the config `paths` table, longest pattern first.
+    pub path_severity: Vec<(Pattern, Severity)>,
+}
+
+/// Whether any of `patterns` matches the file `d` is reported against.
+///
+/// Diagnostics without a file fall back to matching their message, which is how
+/// allow patterns worked before findings carried files. Rules should report a
+/// file with `warning_at`/`error_at` instead; the fallback will be removed.
+pub fn allow_matches(patterns: &[Pattern], d: &Diagnostic) -> bool {
+    let target = match d.location.as_ref() {
+        Some(loc) => loc.file.as_str(),
+        None => d.message.as_str(),
+    };
+    patterns.iter().any(|p| p.matches(target))
+}
+
+/// Warn, once per rule, that a finding without a file was suppressed by message.
+fn warn_message_allow(rule_id: &str) {
+    static WARNED: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+    let Ok(mut warned) = WARNED.lock() else {
+        return;
+    };
+    if warned.iter().any(|id| id == rule_id) {
+        return;
+    }
+    warned.push(rule_id.to_string());
+    tracing::warn!(
+        rule_id,
+        "allow pattern matched the message of a finding without a file; \
+         message matching is deprecated, report a file with warning_at/error_at"
+    );
+}
+
+/// An output relation declared in a rule's frontmatter and the role of each column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSpec {
+    /// Relation name, as declared in the rule source.
+    pub relation: String,
+    /// One role per column: `file`, `line`, `name`, `value`, or a field name.
+    pub columns: Vec<String>,
+}
+
+impl FactsRule {
+    /// Whether an `allow` pattern matches the file `d` is reported against.
+    ///
+    /// Diagnostics without a file are matched by their message instead (see
+    /// [`allow_matches`]); for user rules that fallback is deprecated and logs a
+    /// warning.
+    pub fn allows(&self, d: &Diagnostic) -> bool {
+        let allowed = allow_matches(&self.allow, d);
+        if allowed && d.location.is_none() && !self.builtin {
+            warn_message_allow(&self.id);
+        }
+        allowed
+    }
+
+    /// Severity of the longest `paths` pattern matching `file`, if any.
+    pub fn path_severity_for(&self, file: &str) -> Option<Severity> {
+        self.path_severity
+            .iter()
+            .find(|(p, _)| p.matches(file))
+            .map(|(_, sev)| *sev)
+    }
+
+    /// Set each diagnostic's level from the rule's severity.
+    ///
+    /// The rule-wide severity promotes (`error`) or demotes (`info`/`hint` turn
+    /// warnings into hints) what the rule emitted. A `paths` severity matching
+    /// the diagnostic's file sets the level outright.
+    pub fn apply_severity(&self, diagnostics: &mut [Diagnostic]) {
+        for d in diagnostics.iter_mut() {
+            let path_severity = d
+                .location
+                .as_ref()
+                .and_then(|loc| self.path_severity_for(&loc.file));
+            match path_severity {
+                Some(Severity::Error) => d.level = DiagnosticLevel::Error,
+                Some(Severity::Warning) => d.level = DiagnosticLevel::Warning,
+                Some(Severity::Info | Severity::Hint) => d.level = DiagnosticLevel::Hint,
+                None => match self.severity {
+                    Severity::Error => d.level = DiagnosticLevel::Error,
+                    // `DiagnosticLevel` has no `Info` variant; `Hint` is the closest
+                    // available level (quieter than Warning). This is a lossy mapping:
+                    // both "info" and "hint" from the Datalog `diagnostic` relation end
+                    // up as `Hint` after severity promotion. A future `Info` variant in
+                    // `DiagnosticLevel` would allow an exact mapping.
+                    Severity::Info | Severity::Hint => {
+                        if d.level == DiagnosticLevel::Warning {
+                            d.level = DiagnosticLevel::Hint;
+                        }
+                    }
+                    Severity::Warning => {} // default, no change
+                },
+            }
+        }
+    }
 }
 
 /// A builtin rule definition (id + embedded content).
@@ -152,6 +304,10 @@ const BUILTIN_RULES: &[BuiltinFactsRule] = &[
         id: "god-file",
         content: include_str!("builtin_dl/god_file.dl"),
     },
+    BuiltinFactsRule {
+        id: "hot-god-file",
+        content: include_str!("builtin_dl/hot_god_file.dl"),
+    },
     BuiltinFactsRule {
         id: "fan-out",
         content: include_str!("builtin_dl/fan_out.dl"),
@@ -255,6 +411,52 @@ pub fn load_all_rules(project_root: &Path, config: &RulesConfig) -> Vec<FactsRul
     }
 
     // 4. Apply config overrides
+    apply_config_overrides(&mut rules_by_id, config);
+
+    rules_by_id.into_values().collect()
+}
+
+/// Builtin session rules, evaluated over agent session facts
+/// (`tool_call`, `session_cost`, `api_error`) rather than code facts.
+const BUILTIN_SESSION_RULES: &[BuiltinFactsRule] = &[BuiltinFactsRule {
+    id: "tool-error-rate",
+    content: include_str!("builtin_dl/session/tool_error_rate.dl"),
+}];
+
+/// Load session rules from all sources, merged by ID.
+/// Order: builtins → ~/.config/normalize/session-rules/ → .normalize/session-rules/
+/// Then applies config overrides (deny, enabled, allow), same as [`load_all_rules`].
+///
+/// Session rules live apart from code rules because they see different facts:
+/// a code rule run over session facts (or vice versa) would silently match nothing.
+pub fn load_session_rules(project_root: &Path, config: &RulesConfig) -> Vec<FactsRule> {
+    let mut rules_by_id: HashMap<String, FactsRule> = HashMap::new();
+
+    for builtin in BUILTIN_SESSION_RULES {
+        if let Some(rule) = parse_rule_content(builtin.content, builtin.id, true) {
+            rules_by_id.insert(rule.id.clone(), rule);
+        }
+    }
+
+    if let Some(config_dir) = dirs::config_dir() {
+        let user_rules_dir = config_dir.join("normalize").join("session-rules");
+        for rule in load_rules_from_dir(&user_rules_dir) {
+            rules_by_id.insert(rule.id.clone(), rule);
+        }
+    }
+
+    let project_rules_dir = project_root.join(".normalize").join("session-rules");
+    for rule in load_rules_from_dir(&project_rules_dir) {
+        rules_by_id.insert(rule.id.clone(), rule);
+    }
+
+    apply_config_overrides(&mut rules_by_id, config);
+
+    rules_by_id.into_values().collect()
+}
+
+/// Apply per-rule config overrides (severity, enabled, allow, tags) to loaded rules.
+fn apply_config_overrides(rules_by_id: &mut HashMap<String, FactsRule>, config: &RulesConfig) {
     for (rule_id, override_cfg) in &config.rules {
         if let Some(rule) = rules_by_id.get_mut(rule_id) {
             if let Some(ref sev_str) = override_cfg.severity
@@ -275,10 +477,21 @@ pub fn load_all_rules(project_root: &Path, config: &RulesConfig) -> Vec<FactsRul
                     rule.tags.push(tag.clone());
                 }
             }
+            for (pattern_str, sev_str) in &override_cfg.paths {
+                match (Pattern::new(pattern_str), sev_str.parse::<Severity>()) {
+                    (Ok(pattern), Ok(sev)) => rule.path_severity.push((pattern, sev)),
+                    _ => tracing::warn!(
+                        "ignoring paths entry {:?} = {:?} for rule {}",
+                        pattern_str,
+                        sev_str,
+                        rule_id
+                    ),
+                }
+            }
+            rule.path_severity
+                .sort_by_key(|(p, _)| std::cmp::Reverse(p.as_str().len()));
         }
     }
-
-    rules_by_id.into_values().collect()
 }
 
 /// Load rules from a directory (only `.dl` files).
@@ -307,7 +520,10 @@ fn load_rules_from_dir(rules_dir: &Path) -> Vec<FactsRule> {
 }
 
 /// Parse a rule file with TOML frontmatter.
-fn parse_rule_file(path: &Path) -> Option<FactsRule> {
+///
+/// The rule id defaults to the file stem. Returns `None` (after logging) when
+/// the file can't be read or its frontmatter is invalid.
+pub fn parse_rule_file(path: &Path) -> Option<FactsRule> {
     let content = match std::fs::read_to_string(path) {
         Ok(s) => s,
         Err(e) => {
@@ -334,8 +550,12 @@ fn parse_rule_file(path: &Path) -> Option<FactsRule> {
 /// # message = "What this rule checks for"
 /// # allow = ["**/tests/**"]
 /// # enabled = true
+/// # [output]
+/// # size = ["file", "value"]
 /// # ---
 /// ```
+///
+/// The optional `[output]` table must come last, after the top-level keys.
 pub fn parse_rule_content(content: &str, default_id: &str, is_builtin: bool) -> Option<FactsRule> {
     let lines: Vec<&str> = content.lines().collect();
 
@@ -450,6 +670,34 @@ pub fn parse_rule_content(content: &str, default_id: &str, is_builtin: bool) ->
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    let outputs: Vec<OutputSpec> = frontmatter
+        .get("output")
+        .and_then(|v| v.as_table())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(relation, columns)| {
+                    let columns: Vec<String> = columns
+                        .as_array()?
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .collect();
+                    if !columns.iter().any(|c| c == "value") {
+                        tracing::warn!(
+                            "output relation {relation} of rule {id} has no \"value\" column; ignored"
+                        );
+                        return None;
+                    }
+                    Some(OutputSpec {
+                        relation: relation.clone(),
+                        columns,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
     Some(FactsRule {
         id,
         source: source_str.trim().to_string(),
@@ -462,6 +710,8 @@ pub fn parse_rule_content(content: &str, default_id: &str, is_builtin: bool) ->
         tags,
         doc,
         recommended,
+        outputs,
+        path_severity: Vec::new(),
     })
 }
 
@@ -509,66 +759,65 @@ pub fn run_rule(
     relations: &Relations,
 ) -> Result<Vec<Diagnostic>, InterpretError> {
     let mut diagnostics = run_rules_source(&rule.source, relations)?;
+    apply_rule_policy(rule, &mut diagnostics);
+    Ok(diagnostics)
+}
 
-    // Filter out allowed diagnostics.
-    // For located diagnostics (file != ""), match the allow glob against the file path.
-    // For unlocated diagnostics, match against the message (e.g. hub-file puts module
-    // name in message).
-    if !rule.allow.is_empty() {
-        diagnostics.retain(|d| {
-            let match_str = match d.location.as_ref() {
-                Some(loc) => loc.file.as_str(),
-                None => d.message.as_str(),
-            };
-            !rule.allow.iter().any(|p| p.matches(match_str))
-        });
-    }
+/// Run a FactsRule and return its diagnostics followed by the metrics read
+/// from its declared output relations.
+///
+/// Diagnostics get the same `allow` filtering and severity as [`run_rule`];
+/// metrics located in an allowed file are dropped too.
+pub fn run_rule_findings(
+    rule: &FactsRule,
+    relations: &Relations,
+) -> Result<Vec<Finding>, InterpretError> {
+    let engine = evaluate_source(&rule.source, relations)?;
+    let mut diagnostics = extract_diagnostics(&engine);
+    apply_rule_policy(rule, &mut diagnostics);
+    let metrics = extract_metrics(&engine, rule).into_iter().filter(|m| {
+        m.location
+            .as_ref()
+            .is_none_or(|loc| !rule.allow.iter().any(|p| p.matches(loc.file.as_str())))
+    });
+    Ok(diagnostics
+        .into_iter()
+        .map(Finding::Diagnostic)
+        .chain(metrics.map(Finding::Metric))
+        .collect())
+}
 
-    // Apply severity: promote or demote diagnostics
-    match rule.severity {
-        Severity::Error => {
-            for d in &mut diagnostics {
-                d.level = DiagnosticLevel::Error;
-            }
-        }
-        Severity::Info | Severity::Hint => {
-            // `DiagnosticLevel` has no `Info` variant; `Hint` is the closest
-            // available level (quieter than Warning). This is a lossy mapping:
-            // both "info" and "hint" from the Datalog `diagnostic` relation end
-            // up as `Hint` after severity promotion. A future `Info` variant in
-            // `DiagnosticLevel` would allow an exact mapping.
-            for d in &mut diagnostics {
-                if d.level == DiagnosticLevel::Warning {
-                    d.level = DiagnosticLevel::Hint;
-                }
-            }
-        }
-        Severity::Warning => {} // default, no change
+/// Apply a rule's `allow` patterns and severity to its raw diagnostics.
+fn apply_rule_policy(rule: &FactsRule, diagnostics: &mut Vec<Diagnostic>) {
+    if !rule.allow.is_empty() {
+        diagnostics.retain(|d| !rule.allows(d));
     }
 
-    Ok(diagnostics)
+    rule.apply_severity(diagnostics);
 }
 
 /// Filter out diagnostics suppressed by `normalize-facts-allow: rule-id` comments in source files.
 ///
-/// When a diagnostic's message is a file path (relative to `root`), the first 10
+/// For diagnostics located in a file (relative to `root`), the first 10
 /// lines of that file are checked for `// normalize-facts-allow: rule-id` or
 /// `# normalize-facts-allow: rule-id`. This mirrors the inline suppression mechanism
-/// from syntax-rules.
+/// from syntax-rules. Diagnostics without a file are checked against the file
+/// their message names, if any; that fallback is deprecated (see [`allow_matches`]).
 pub fn filter_inline_allowed(diagnostics: &mut Vec<Diagnostic>, root: &Path) {
     diagnostics.retain(|d| {
-        // For file-located diagnostics, check the location file directly.
-        // For unlocated diagnostics, try interpreting the message as a file path.
-        let file_str = match d.location.as_ref() {
+        let file = match d.location.as_ref() {
             Some(loc) => loc.file.as_str(),
             None => d.message.as_str(),
         };
-        let path = root.join(file_str);
-        if path.is_file() {
-            !file_has_allow_comment(&path, d.rule_id.as_str())
-        } else {
-            true // not a file path, keep it
+        let path = root.join(file);
+        if !path.is_file() {
+            return true; // not a file path, keep it
         }
+        let allowed = file_has_allow_comment(&path, d.rule_id.as_str());
+        if allowed && d.location.is_none() {
+            warn_message_allow(d.rule_id.as_str());
+        }
+        !allowed
     });
 }
 
@@ -606,6 +855,14 @@ pub fn run_rules_source(
     source: &str,
     relations: &Relations,
 ) -> Result<Vec<Diagnostic>, InterpretError> {
+    let engine = evaluate_source(source, relations)?;
+
+    // Extract diagnostics from output relations
+    Ok(extract_diagnostics(&engine))
+}
+
+/// Evaluate rules from a source string to fixpoint and return the engine.
+fn evaluate_source(source: &str, relations: &Relations) -> Result<Engine, InterpretError> {
     // Combine preamble with user rules
     let full_source = format!("{}\n{}", PREAMBLE, source);
 
@@ -626,9 +883,7 @@ pub fn run_rules_source(
         .run()
         .map_err(|e| InterpretError::Eval(e.to_string()))?;
     engine.materialize();
-
-    // Extract diagnostics from output relations
-    Ok(extract_diagnostics(&engine))
+    Ok(engine)
 }
 
 /// Run rules incrementally against an already-populated engine.
@@ -1100,6 +1355,58 @@ pub fn run_rule_incremental(
             dirty_input_relations.insert("module_search_path");
         }
     }
+    for s in new_relations.depends_on.iter() {
+        if changed_set.contains(s.manifest.as_str()) {
+            let sid = cached.engine.intern_source(s.manifest.as_str());
+            cached
+                .engine
+                .insert_with_source(
+                    "depends_on",
+                    vec![
+                        Value::string(&s.package),
+                        Value::string(&s.dependency),
+                        Value::string(&s.version),
+                    ],
+                    sid,
+                )
+                .map_err(|e| InterpretError::Parse(e.to_string()))?;
+            dirty_input_relations.insert("depends_on");
+        }
+    }
+    for s in new_relations.churn.iter() {
+        if changed_set.contains(s.file.as_str()) {
+            let sid = cached.engine.intern_source(s.file.as_str());
+            cached
+                .engine
+                .insert_with_source(
+                    "churn",
+                    vec![Value::string(&s.file), Value::U32(s.commits_90d)],
+                    sid,
+                )
+                .map_err(|e| InterpretError::Parse(e.to_string()))?;
+            dirty_input_relations.insert("churn");
+        }
+    }
+    for s in new_relations.file_info.iter() {
+        if changed_set.contains(s.file.as_str()) {
+            let sid = cached.engine.intern_source(s.file.as_str());
+            cached
+                .engine
+                .insert_with_source("file_info", file_info_tuple(s), sid)
+                .map_err(|e| InterpretError::Parse(e.to_string()))?;
+            dirty_input_relations.insert("file_info");
+        }
+    }
+    for c in new_relations.complexity.iter() {
+        if changed_set.contains(c.file.as_str()) {
+            let sid = cached.engine.intern_source(c.file.as_str());
+            cached
+                .engine
+                .insert_with_source("complexity", complexity_tuple(c), sid)
+                .map_err(|e| InterpretError::Parse(e.to_string()))?;
+            dirty_input_relations.insert("complexity");
+        }
+    }
     // All dirty input relations are also retracted (we retracted + re-inserted).
     let dirty_vec: Vec<&str> = dirty_input_relations.iter().copied().collect();
     cached
@@ -1179,11 +1486,49 @@ pub fn run_rules_batch(
     rules: &[&FactsRule],
     relations: &Relations,
 ) -> Result<Vec<Diagnostic>, InterpretError> {
-    if rules.is_empty() {
-        return Ok(Vec::new());
+    let mut all_diagnostics = Vec::new();
+    for result in run_rules_batch_per_rule(rules, relations) {
+        all_diagnostics.extend(result?);
     }
+    Ok(all_diagnostics)
+}
 
-    let results: Vec<Result<Vec<Diagnostic>, InterpretError>> = rules
+/// Run independent rules concurrently against the same read-only relations.
+///
+/// Each rule is evaluated on the current rayon pool (see [`with_rule_threads`]
+/// to bound its size). Output is deterministic: diagnostics are grouped in
+/// `rules` order and sorted by location and message within each rule, however
+/// the work was scheduled.
+pub fn run_rules_parallel(
+    rules: &[FactsRule],
+    relations: &Relations,
+) -> Result<Vec<Diagnostic>, InterpretError> {
+    let rule_refs: Vec<&FactsRule> = rules.iter().collect();
+    run_rules_batch(&rule_refs, relations)
+}
+
+/// Run `f` with rule evaluation limited to `threads` worker threads.
+///
+/// `None` (or `Some(0)`) uses the global rayon pool, which has one thread per
+/// CPU core. Falls back to the global pool if a dedicated pool can't be built.
+pub fn with_rule_threads<R: Send>(threads: Option<usize>, f: impl FnOnce() -> R + Send) -> R {
+    let pool = threads
+        .filter(|&n| n > 0)
+        .and_then(|n| rayon::ThreadPoolBuilder::new().num_threads(n).build().ok());
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+/// Like [`run_rules_batch`], but returns each rule's result separately (in
+/// `rules` order) so callers can cache per-rule output and keep the results of
+/// rules that succeeded when another fails.
+pub fn run_rules_batch_per_rule(
+    rules: &[&FactsRule],
+    relations: &Relations,
+) -> Vec<Result<Vec<Diagnostic>, InterpretError>> {
+    rules
         .par_iter()
         .map(|rule| {
             let full_source = format!("{}\n{}", PREAMBLE, &rule.source);
@@ -1204,46 +1549,302 @@ pub fn run_rules_batch(
             engine.materialize();
 
             let mut diagnostics = extract_diagnostics(&engine);
+            sort_diagnostics(&mut diagnostics);
 
             // Apply per-rule allow patterns.
             if !rule.allow.is_empty() {
-                diagnostics.retain(|d| {
-                    let match_str = match d.location.as_ref() {
-                        Some(loc) => loc.file.as_str(),
-                        None => d.message.as_str(),
-                    };
-                    !rule.allow.iter().any(|p| p.matches(match_str))
-                });
+                diagnostics.retain(|d| !rule.allows(d));
             }
 
             // Apply per-rule severity.
-            match rule.severity {
-                Severity::Error => {
-                    for d in &mut diagnostics {
-                        d.level = DiagnosticLevel::Error;
-                    }
-                }
-                Severity::Info | Severity::Hint => {
-                    // `DiagnosticLevel` has no `Info` variant; `Hint` is the closest
-                    // available level. See the same comment in `run_rule` for details.
-                    for d in &mut diagnostics {
-                        if d.level == DiagnosticLevel::Warning {
-                            d.level = DiagnosticLevel::Hint;
-                        }
-                    }
-                }
-                Severity::Warning => {}
-            }
+            rule.apply_severity(&mut diagnostics);
 
             Ok(diagnostics)
         })
-        .collect();
+        .collect()
+}
 
-    let mut all_diagnostics = Vec::new();
-    for result in results {
-        all_diagnostics.extend(result?);
+/// Order one rule's diagnostics by file, line and message, so output does not
+/// depend on the engine's relation iteration order. Unlocated diagnostics sort first.
+fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    fn key(d: &Diagnostic) -> Option<(&str, u32, Option<u32>)> {
+        d.location
+            .as_ref()
+            .map(|loc| (loc.file.as_str(), loc.line, loc.end_line))
     }
-    Ok(all_diagnostics)
+    diagnostics.sort_by(|a, b| key(a).cmp(&key(b)).then_with(|| a.message.cmp(&b.message)));
+}
+
+// =============================================================================
+// Persistent caching: content fingerprints of input facts
+// =============================================================================
+
+/// [`FactSink`] that hashes tuples instead of storing them.
+///
+/// Each tuple is hashed on its own and the hashes are summed, so a relation's
+/// fingerprint does not depend on the order its facts were extracted in.
+#[derive(Default)]
+struct RelationHasher {
+    sums: HashMap<String, u128>,
+}
+
+impl FactSink for RelationHasher {
+    fn insert(&mut self, relation: &str, tuple: Vec<Value>) -> Result<(), String> {
+        // `Debug` renders interned strings by content, unlike `Hash`, which
+        // uses the process-local intern id.
+        let hash = blake3::hash(format!("{tuple:?}").as_bytes());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&hash.as_bytes()[..16]);
+        let value = u128::from_le_bytes(bytes);
+        match self.sums.get_mut(relation) {
+            Some(sum) => *sum = sum.wrapping_add(value),
+            None => {
+                self.sums.insert(relation.to_string(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Content fingerprint of every built-in input relation, keyed by relation name.
+///
+/// Fingerprints are stable across processes and independent of fact order.
+/// Empty relations are present with fingerprint `0`. Combine with
+/// [`rule_cache_key`] to decide whether a rule's persisted output is still valid.
+pub fn relation_fingerprints(relations: &Relations) -> HashMap<String, u128> {
+    let mut hasher = RelationHasher::default();
+    // Inserting into a RelationHasher never fails.
+    let _ = populate_facts(&mut hasher, relations);
+    let mut fingerprints = hasher.sums;
+    for line in PREAMBLE.lines() {
+        if let Some(name) = line
+            .strip_prefix("relation ")
+            .and_then(|rest| rest.split('(').next())
+        {
+            fingerprints.entry(name.to_string()).or_insert(0);
+        }
+    }
+    fingerprints
+}
+
+/// Cache key for a rule's diagnostics given the [`relation_fingerprints`] of
+/// its input facts.
+///
+/// Covers the rule source, allow patterns and severity, the interpreter
+/// version, and the fingerprints of only the relations the rule references —
+/// so a change to, say, `call` facts leaves the keys of rules that read only
+/// `import` untouched.
+pub fn rule_cache_key(rule: &FactsRule, fingerprints: &HashMap<String, u128>) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(b"\0");
+    hasher.update(rule.source.as_bytes());
+    hasher.update(b"\0");
+    for pattern in &rule.allow {
+        hasher.update(pattern.as_str().as_bytes());
+        hasher.update(b"\0");
+    }
+    hasher.update(format!("{:?}", rule.severity).as_bytes());
+    for (pattern, sev) in &rule.path_severity {
+        hasher.update(b"\0");
+        hasher.update(pattern.as_str().as_bytes());
+        hasher.update(format!("{:?}", sev).as_bytes());
+    }
+    for name in compile_rules_source(&rule.source).relations_used {
+        if let Some(fingerprint) = fingerprints.get(&name) {
+            hasher.update(b"\0");
+            hasher.update(name.as_bytes());
+            hasher.update(&fingerprint.to_le_bytes());
+        }
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+// =============================================================================
+// Watch mode: relation → rule dependency tracking
+// =============================================================================
+
+/// Re-evaluation state for a long-running watch over a fixed set of rules.
+///
+/// Each [`update`](Self::update) takes the current relations (rebuilt after the
+/// changed files were re-extracted), compares each input relation's
+/// [`relation_fingerprints`] with the previous update, and re-runs only the
+/// rules that read a relation that changed. The result is reported as a diff
+/// against the previous update's diagnostics.
+pub struct WatchSession {
+    rules: Vec<FactsRule>,
+    /// Input relations each rule reads, parallel to `rules`.
+    reads: Vec<Vec<String>>,
+    /// Diagnostics of each rule as of the last update, before inline suppression.
+    raw: Vec<Vec<Diagnostic>>,
+    /// Visible diagnostics as of the last update, keyed for diffing.
+    visible: Vec<(String, Diagnostic)>,
+    fingerprints: HashMap<String, u128>,
+    root: Option<PathBuf>,
+    baseline: Option<Baseline>,
+}
+
+/// What changed in one [`WatchSession::update`].
+#[derive(Debug, Default)]
+pub struct WatchDelta {
+    /// Input relations whose contents changed, sorted.
+    pub changed_relations: Vec<String>,
+    /// Rules that were re-evaluated, in rule order.
+    pub rerun: Vec<String>,
+    /// Diagnostics that appeared.
+    pub new: Vec<Diagnostic>,
+    /// Diagnostics that went away.
+    pub resolved: Vec<Diagnostic>,
+    /// Rules that failed to evaluate; their previous diagnostics are kept.
+    pub errors: Vec<(String, InterpretError)>,
+}
+
+impl WatchSession {
+    /// Track `rules`. Nothing is evaluated until the first [`update`](Self::update),
+    /// which runs every rule and reports all its diagnostics as new.
+    pub fn new(rules: Vec<FactsRule>) -> Self {
+        let inputs = relation_fingerprints(&Relations::new());
+        let reads = rules
+            .iter()
+            .map(|rule| {
+                let mut used = compile_rules_source(&rule.source).relations_used;
+                used.retain(|name| {
+                    inputs.contains_key(name) && !DIAGNOSTIC_RELATIONS.contains(&name.as_str())
+                });
+                used
+            })
+            .collect();
+        let raw = vec![Vec::new(); rules.len()];
+        Self {
+            rules,
+            reads,
+            raw,
+            visible: Vec::new(),
+            fingerprints: HashMap::new(),
+            root: None,
+            baseline: None,
+        }
+    }
+
+    /// Drop diagnostics suppressed by `normalize-facts-allow` comments in files
+    /// under `root` (see [`filter_inline_allowed`]).
+    pub fn with_inline_allow(mut self, root: &Path) -> Self {
+        self.root = Some(root.to_path_buf());
+        self
+    }
+
+    /// Drop diagnostics accepted by `baseline`.
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Input relations read by the rule with `id`.
+    pub fn relations_read_by(&self, id: &str) -> &[String] {
+        self.rules
+            .iter()
+            .position(|r| r.id == id)
+            .map_or(&[], |i| &self.reads[i])
+    }
+
+    /// Ids of the rules that read `relation`.
+    pub fn rules_reading<'a>(&'a self, relation: &'a str) -> impl Iterator<Item = &'a str> {
+        self.rules
+            .iter()
+            .zip(&self.reads)
+            .filter(move |(_, reads)| reads.iter().any(|r| r == relation))
+            .map(|(rule, _)| rule.id.as_str())
+    }
+
+    /// All diagnostics as of the last update.
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.visible.iter().map(|(_, d)| d)
+    }
+
+    /// Re-run the rules affected by changes in `relations` and diff the result.
+    pub fn update(&mut self, relations: &Relations) -> WatchDelta {
+        let fingerprints = relation_fingerprints(relations);
+        let mut changed_relations: Vec<String> = fingerprints
+            .iter()
+            .filter(|(name, fp)| self.fingerprints.get(*name) != Some(*fp))
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed_relations.sort();
+        // The first update evaluates everything, including rules that read no
+        // input relation.
+        let first = self.fingerprints.is_empty();
+        self.fingerprints = fingerprints;
+
+        let affected: Vec<usize> = (0..self.rules.len())
+            .filter(|&i| {
+                first
+                    || self.reads[i]
+                        .iter()
+                        .any(|r| changed_relations.binary_search(r).is_ok())
+            })
+            .collect();
+        let rule_refs: Vec<&FactsRule> = affected.iter().map(|&i| &self.rules[i]).collect();
+        let mut errors = Vec::new();
+        for (&i, result) in affected
+            .iter()
+            .zip(run_rules_batch_per_rule(&rule_refs, relations))
+        {
+            match result {
+                Ok(diagnostics) => self.raw[i] = diagnostics,
+                Err(e) => errors.push((self.rules[i].id.clone(), e)),
+            }
+        }
+
+        let mut current: Vec<Diagnostic> = self.raw.iter().flatten().cloned().collect();
+        if let Some(root) = &self.root {
+            filter_inline_allowed(&mut current, root);
+        }
+        if let Some(baseline) = &self.baseline {
+            baseline.filter(&mut current);
+        }
+        let current: Vec<(String, Diagnostic)> = current
+            .into_iter()
+            .map(|d| (diagnostic_key(&d), d))
+            .collect();
+        let previous: std::collections::HashSet<&str> =
+            self.visible.iter().map(|(k, _)| k.as_str()).collect();
+        let now: std::collections::HashSet<&str> =
+            current.iter().map(|(k, _)| k.as_str()).collect();
+        let new = current
+            .iter()
+            .filter(|(k, _)| !previous.contains(k.as_str()))
+            .map(|(_, d)| d.clone())
+            .collect();
+        let resolved = self
+            .visible
+            .iter()
+            .filter(|(k, _)| !now.contains(k.as_str()))
+            .map(|(_, d)| d.clone())
+            .collect();
+        self.visible = current;
+
+        WatchDelta {
+            changed_relations,
+            rerun: affected.iter().map(|&i| self.rules[i].id.clone()).collect(),
+            new,
+            resolved,
+            errors,
+        }
+    }
+}
+
+/// Preamble relations rules write to rather than read from.
+const DIAGNOSTIC_RELATIONS: &[&str] = &[
+    "diagnostic",
+    "warning_at",
+    "error_at",
+    "warning_span",
+    "error_span",
+];
+
+/// Identity of a diagnostic across watch updates: everything it reports.
+fn diagnostic_key(d: &Diagnostic) -> String {
+    serde_json::to_string(d).unwrap_or_else(|_| format!("{d:?}"))
 }
 
 /// Populate the engine with facts from Relations, tagging each fact with a per-file source ID.
@@ -1528,6 +2129,85 @@ fn populate_facts_with_sources(
             )
             .map_err(|e| InterpretError::Parse(e.to_string()))?;
     }
+    for dep in relations.depends_on.iter() {
+        let sid = engine.intern_source(dep.manifest.as_str());
+        engine
+            .insert_with_source(
+                "depends_on",
+                vec![
+                    Value::string(&dep.package),
+                    Value::string(&dep.dependency),
+                    Value::string(&dep.version),
+                ],
+                sid,
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for ch in relations.churn.iter() {
+        let sid = engine.intern_source(ch.file.as_str());
+        engine
+            .insert_with_source(
+                "churn",
+                vec![Value::string(&ch.file), Value::U32(ch.commits_90d)],
+                sid,
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for info in relations.file_info.iter() {
+        let sid = engine.intern_source(info.file.as_str());
+        engine
+            .insert_with_source("file_info", file_info_tuple(info), sid)
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for c in relations.complexity.iter() {
+        let sid = engine.intern_source(c.file.as_str());
+        engine
+            .insert_with_source("complexity", complexity_tuple(c), sid)
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for tc in relations.tool_calls.iter() {
+        let sid = engine.intern_source(tc.session.as_str());
+        engine
+            .insert_with_source(
+                "tool_call",
+                vec![
+                    Value::string(&tc.session),
+                    Value::U32(tc.turn),
+                    Value::U32(tc.call),
+                    Value::string(&tc.tool),
+                    Value::Bool(tc.is_error),
+                ],
+                sid,
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for sc in relations.session_costs.iter() {
+        let sid = engine.intern_source(sc.session.as_str());
+        engine
+            .insert_with_source(
+                "session_cost",
+                vec![
+                    Value::string(&sc.session),
+                    Value::F64(OrderedFloat(sc.dollars)),
+                ],
+                sid,
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+    for ae in relations.api_errors.iter() {
+        let sid = engine.intern_source(ae.session.as_str());
+        engine
+            .insert_with_source(
+                "api_error",
+                vec![
+                    Value::string(&ae.session),
+                    Value::U32(ae.turn),
+                    Value::string(&ae.kind),
+                ],
+                sid,
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
     for blk in relations.cfg_blocks.iter() {
         let sid = engine.intern_source(blk.file.as_str());
         engine
@@ -1615,8 +2295,43 @@ fn populate_facts_with_sources(
     Ok(())
 }
 
+/// `file_info(file, language, loc, blank_lines, comment_lines)` tuple for `info`.
+fn file_info_tuple(info: &FileInfoFact) -> Vec<Value> {
+    vec![
+        Value::string(&info.file),
+        Value::string(&info.language),
+        Value::U32(info.loc),
+        Value::U32(info.blank_lines),
+        Value::U32(info.comment_lines),
+    ]
+}
+
+/// `complexity(file, name, cyclomatic, depth)` tuple for `c`.
+fn complexity_tuple(c: &ComplexityFact) -> Vec<Value> {
+    vec![
+        Value::string(&c.file),
+        Value::string(&c.name),
+        Value::U32(c.cyclomatic),
+        Value::U32(c.depth),
+    ]
+}
+
+/// Destination for the tuples [`populate_facts`] produces: an [`Engine`], or a
+/// [`RelationHasher`] when only the facts' fingerprints are needed.
+trait FactSink {
+    fn insert(&mut self, relation: &str, tuple: Vec<Value>) -> Result<(), String>;
+}
+
+impl FactSink for Engine {
+    fn insert(&mut self, relation: &str, tuple: Vec<Value>) -> Result<(), String> {
+        Engine::insert(self, relation, tuple)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
 /// Populate the engine with facts from Relations.
-fn populate_facts(engine: &mut Engine, relations: &Relations) -> Result<(), InterpretError> {
+fn populate_facts(engine: &mut impl FactSink, relations: &Relations) -> Result<(), InterpretError> {
     for sym in relations.symbols.iter() {
         engine
             .insert(
@@ -1871,6 +2586,80 @@ fn populate_facts(engine: &mut Engine, relations: &Relations) -> Result<(), Inte
             .map_err(|e| InterpretError::Parse(e.to_string()))?;
     }
 
+    for dep in relations.depends_on.iter() {
+        engine
+            .insert(
+                "depends_on",
+                vec![
+                    Value::string(&dep.package),
+                    Value::string(&dep.dependency),
+                    Value::string(&dep.version),
+                ],
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for ch in relations.churn.iter() {
+        engine
+            .insert(
+                "churn",
+                vec![Value::string(&ch.file), Value::U32(ch.commits_90d)],
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for info in relations.file_info.iter() {
+        engine
+            .insert("file_info", file_info_tuple(info))
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for c in relations.complexity.iter() {
+        engine
+            .insert("complexity", complexity_tuple(c))
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for tc in relations.tool_calls.iter() {
+        engine
+            .insert(
+                "tool_call",
+                vec![
+                    Value::string(&tc.session),
+                    Value::U32(tc.turn),
+                    Value::U32(tc.call),
+                    Value::string(&tc.tool),
+                    Value::Bool(tc.is_error),
+                ],
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for sc in relations.session_costs.iter() {
+        engine
+            .insert(
+                "session_cost",
+                vec![
+                    Value::string(&sc.session),
+                    Value::F64(OrderedFloat(sc.dollars)),
+                ],
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
+    for ae in relations.api_errors.iter() {
+        engine
+            .insert(
+                "api_error",
+                vec![
+                    Value::string(&ae.session),
+                    Value::U32(ae.turn),
+                    Value::string(&ae.kind),
+                ],
+            )
+            .map_err(|e| InterpretError::Parse(e.to_string()))?;
+    }
+
     for blk in relations.cfg_blocks.iter() {
         engine
             .insert(
@@ -1962,6 +2751,37 @@ fn populate_facts(engine: &mut Engine, relations: &Relations) -> Result<(), Inte
 fn extract_diagnostics(engine: &Engine) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
+    for (relation, severity) in [
+        ("warning_at", DiagnosticLevel::Warning),
+        ("error_at", DiagnosticLevel::Error),
+        ("warning_span", DiagnosticLevel::Warning),
+        ("error_span", DiagnosticLevel::Error),
+    ] {
+        let Some(tuples) = engine.relation(relation) else {
+            continue;
+        };
+        for tuple in tuples.iter() {
+            let (rule_id, file, line, end_line, message) = match tuple {
+                [rule_id, file, Value::U32(line), message] => (rule_id, file, *line, None, message),
+                [rule_id, file, Value::U32(start), Value::U32(end), message] => {
+                    (rule_id, file, *start, Some(*end), message)
+                }
+                _ => continue,
+            };
+            let (Some(rule_id), Some(file), Some(message)) =
+                (rule_id.as_str(), file.as_str(), message.as_str())
+            else {
+                continue;
+            };
+            let d = Diagnostic::new(rule_id, severity, message);
+            diagnostics.push(match end_line {
+                _ if file.is_empty() => d,
+                Some(end_line) => d.at_span(file, line, end_line.max(line)),
+                None => d.at(file, line),
+            });
+        }
+    }
+
     if let Some(diags) = engine.relation("diagnostic") {
         for tuple in diags.iter() {
             if let [severity, rule_id, file, Value::U32(line), message] = tuple {
@@ -1989,6 +2809,65 @@ fn extract_diagnostics(engine: &Engine) -> Vec<Diagnostic> {
     diagnostics
 }
 
+/// Read a rule's declared output relations back as metrics.
+///
+/// Tuples whose arity doesn't match the declared columns, or whose `value`
+/// column isn't numeric, are skipped.
+fn extract_metrics(engine: &Engine, rule: &FactsRule) -> Vec<Metric> {
+    let mut metrics = Vec::new();
+    for spec in &rule.outputs {
+        let Some(tuples) = engine.relation(&spec.relation) else {
+            continue;
+        };
+        for tuple in tuples.iter() {
+            if tuple.len() != spec.columns.len() {
+                continue;
+            }
+            let mut value = None;
+            let mut name = None;
+            let mut file = None;
+            let mut line = 0u32;
+            let mut fields = Vec::new();
+            for (role, v) in spec.columns.iter().zip(tuple.iter()) {
+                match role.as_str() {
+                    "value" => value = v.as_f64(),
+                    "name" => name = Some(value_text(v)),
+                    "file" => file = v.as_str().filter(|f| !f.is_empty()),
+                    "line" => line = v.as_i64().and_then(|l| u32::try_from(l).ok()).unwrap_or(0),
+                    _ => fields.push((role.clone(), value_text(v))),
+                }
+            }
+            let Some(value) = value else {
+                continue;
+            };
+            metrics.push(Metric {
+                rule_id: rule.id.clone(),
+                relation: spec.relation.clone(),
+                name: name.unwrap_or_else(|| spec.relation.clone()),
+                value,
+                location: file.map(|f| Location::new(f, line)),
+                fields,
+            });
+        }
+    }
+    metrics
+}
+
+/// Render a Datalog value as plain text (strings unquoted, numbers as digits).
+fn value_text(v: &Value) -> String {
+    if let Some(s) = v.as_str() {
+        s.to_string()
+    } else if let Some(i) = v.as_i64() {
+        i.to_string()
+    } else if let Some(f) = v.as_f64() {
+        f.to_string()
+    } else if let Some(b) = v.as_bool() {
+        b.to_string()
+    } else {
+        v.to_string()
+    }
+}
+
 // =============================================================================
 // Compile / validate API
 // =============================================================================
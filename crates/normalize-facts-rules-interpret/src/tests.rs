@@ -96,6 +96,143 @@ diagnostic("warning", "x", "", 0u32, "y") <-- symbol(_, _, _, _);
     assert!(!rule.enabled);
 }
 
+#[test]
+fn test_run_rule_findings_reads_output_relations() {
+    let content = r#"
+# ---
+# id = "file-size"
+# allow = ["vendor/**"]
+# [output]
+# size = ["file", "lang", "value"]
+# no_value = ["file", "name"]
+# ---
+
+relation size(String, String, u32);
+size(file, lang, loc) <-- file_info(file, lang, loc, _, _);
+relation no_value(String, String);
+no_value(file, "x") <-- file_info(file, _, _, _, _);
+diagnostic("warning", "file-size", file, 0u32, "big") <--
+    file_info(file, _, loc, _, _), if loc > 100u32;
+"#;
+    let rule = parse_rule_content(content, "x", false).unwrap();
+    // `no_value` declares no "value" column, so it is not an output.
+    assert_eq!(
+        rule.outputs,
+        vec![OutputSpec {
+            relation: "size".to_string(),
+            columns: vec!["file".into(), "lang".into(), "value".into()],
+        }]
+    );
+
+    let mut relations = Relations::new();
+    relations.add_file_info("a.rs", "rust", 120, 0, 0);
+    relations.add_file_info("vendor/b.rs", "rust", 10, 0, 0);
+
+    let findings = run_rule_findings(&rule, &relations).unwrap();
+    assert_eq!(findings.len(), 2);
+    let Finding::Diagnostic(d) = &findings[0] else {
+        panic!("expected a diagnostic first, got {:?}", findings[0]);
+    };
+    assert_eq!(d.message, "big");
+    let Finding::Metric(m) = &findings[1] else {
+        panic!("expected a metric, got {:?}", findings[1]);
+    };
+    assert_eq!(m.rule_id, "file-size");
+    assert_eq!(m.name, "size");
+    assert_eq!(m.value, 120.0);
+    assert_eq!(m.location.as_ref().unwrap().file, "a.rs");
+    assert_eq!(m.fields, vec![("lang".to_string(), "rust".to_string())]);
+}
+
+#[test]
+fn test_watch_session_reruns_only_affected_rules() {
+    let cycle = parse_rule_content(
+        r#"
+relation reaches(String, String);
+reaches(a, b) <-- import(a, b, _);
+reaches(a, c) <-- import(a, b, _), reaches(b, c);
+diagnostic("warning", "cycle", a, 0u32, "cycle") <-- reaches(a, a);
+"#,
+        "cycle",
+        false,
+    )
+    .unwrap();
+    let big = parse_rule_content(
+        r#"
+diagnostic("warning", "big", file, 0u32, "big") <--
+    file_info(file, _, loc, _, _), if loc > 100u32;
+"#,
+        "big",
+        false,
+    )
+    .unwrap();
+    let mut session = WatchSession::new(vec![cycle, big]);
+    assert_eq!(session.relations_read_by("cycle"), ["import".to_string()]);
+    assert_eq!(
+        session.rules_reading("file_info").collect::<Vec<_>>(),
+        ["big"]
+    );
+
+    let mut relations = Relations::new();
+    relations.add_import("a", "b", "x");
+    relations.add_file_info("a", "rust", 500, 0, 0);
+    let delta = session.update(&relations);
+    assert_eq!(delta.rerun, ["cycle", "big"]);
+    assert_eq!(delta.new.len(), 1);
+    assert_eq!(delta.new[0].rule_id, "big");
+
+    // Only `import` changed: `big` is not re-run, and the cycle is new.
+    relations.add_import("b", "a", "y");
+    let delta = session.update(&relations);
+    assert_eq!(delta.changed_relations, ["import"]);
+    assert_eq!(delta.rerun, ["cycle"]);
+    assert_eq!(delta.new.len(), 2);
+    assert!(delta.resolved.is_empty());
+    assert_eq!(session.diagnostics().count(), 3);
+
+    // No changes: nothing re-runs.
+    let delta = session.update(&relations);
+    assert!(delta.rerun.is_empty() && delta.new.is_empty());
+
+    // Breaking the cycle resolves both diagnostics.
+    relations.imports.pop();
+    let delta = session.update(&relations);
+    assert_eq!(delta.resolved.len(), 2);
+    assert!(delta.resolved.iter().all(|d| d.rule_id == "cycle"));
+    assert!(delta.new.is_empty());
+}
+
+#[test]
+fn test_baseline_filters_only_accepted_diagnostics() {
+    let old = vec![
+        Diagnostic::warning("dead-code", "unused_fn").at("a.rs", 10),
+        Diagnostic::warning("dead-code", "other_fn").at("b.rs", 3),
+    ];
+    let baseline = Baseline::from_diagnostics(&old);
+    assert_eq!(baseline.entries.len(), 2);
+    assert_eq!(baseline.entries[0].file, "a.rs");
+
+    // The baselined finding moved lines, and a second copy plus a new one appeared.
+    let mut current = vec![
+        Diagnostic::warning("dead-code", "unused_fn").at("a.rs", 14),
+        Diagnostic::warning("dead-code", "unused_fn").at("a.rs", 30),
+        Diagnostic::warning("dead-code", "new_fn").at("a.rs", 40),
+    ];
+    assert_eq!(baseline.filter(&mut current), 1);
+    let messages: Vec<&str> = current.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["unused_fn", "new_fn"]);
+
+    // Fingerprints depend on the rule, file and message, not the line.
+    assert_eq!(
+        diagnostic_fingerprint(&old[0]),
+        diagnostic_fingerprint(&Diagnostic::error("dead-code", "unused_fn").at("a.rs", 99))
+    );
+    assert_ne!(
+        diagnostic_fingerprint(&old[0]),
+        diagnostic_fingerprint(&Diagnostic::warning("dead-code", "unused_fn").at("c.rs", 10))
+    );
+}
+
 #[test]
 fn test_builtin_rules_parse() {
     for builtin in BUILTIN_RULES {
@@ -115,7 +252,7 @@ fn test_allow_patterns() {
 # allow = ["**/tests/**", "**/*_test.py"]
 # ---
 
-diagnostic("warning", "test-allow", "", 0u32, file) <-- symbol(file, _, _, _);
+diagnostic("warning", "test-allow", file, line, name) <-- symbol(file, name, _, line);
 "#;
 
     let mut relations = Relations::new();
@@ -127,10 +264,171 @@ diagnostic("warning", "test-allow", "", 0u32, file) <-- symbol(file, _, _, _);
     assert_eq!(rule.allow.len(), 2);
 
     let result = run_rule(&rule, &relations).unwrap();
+    // The message is the symbol name; allow patterns match the file field.
     // tests/test_foo.py matches **/tests/**, foo_test.py matches **/*_test.py
     // Only src/main.py should remain
     assert_eq!(result.len(), 1);
-    assert_eq!(result[0].message.as_str(), "src/main.py");
+    assert_eq!(result[0].message.as_str(), "foo");
+    assert_eq!(
+        result[0].location.as_ref().unwrap().file.as_str(),
+        "src/main.py"
+    );
+}
+
+#[test]
+fn test_rule_cache_key_tracks_only_used_relations() {
+    let imports = parse_rule_content(
+        r#"warning_at("imports", file, 0u32, m) <-- import(file, m, _);"#,
+        "imports",
+        false,
+    )
+    .unwrap();
+    let calls = parse_rule_content(
+        r#"warning_at("calls", file, line, c) <-- call(file, _, c, line);"#,
+        "calls",
+        false,
+    )
+    .unwrap();
+
+    let mut relations = Relations::new();
+    relations.add_import("a.py", "b", "*");
+    relations.add_import("b.py", "c", "*");
+    relations.add_call("a.py", "main", "helper", 3);
+    let before = relation_fingerprints(&relations);
+    assert_eq!(before["symbol"], 0);
+
+    // Same facts in a different order: same fingerprints.
+    let mut reordered = Relations::new();
+    reordered.add_call("a.py", "main", "helper", 3);
+    reordered.add_import("b.py", "c", "*");
+    reordered.add_import("a.py", "b", "*");
+    assert_eq!(relation_fingerprints(&reordered), before);
+
+    relations.add_call("b.py", "run", "helper", 7);
+    let after = relation_fingerprints(&relations);
+    assert_eq!(before["import"], after["import"]);
+    assert_ne!(before["call"], after["call"]);
+    assert_eq!(
+        rule_cache_key(&imports, &before),
+        rule_cache_key(&imports, &after)
+    );
+    assert_ne!(
+        rule_cache_key(&calls, &before),
+        rule_cache_key(&calls, &after)
+    );
+}
+
+#[test]
+fn test_run_rules_batch_per_rule() {
+    let ok = parse_rule_content(
+        r#"warning_at("ok", file, line, name) <-- symbol(file, name, _, line);"#,
+        "ok",
+        false,
+    )
+    .unwrap();
+    let broken = parse_rule_content("this is not datalog", "broken", false).unwrap();
+
+    let mut relations = Relations::new();
+    relations.add_symbol("src/a.rs", "main", "function", 1);
+    let results = run_rules_batch_per_rule(&[&ok, &broken], &relations);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().len(), 1);
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn test_run_rules_parallel_is_deterministic() {
+    let rules: Vec<FactsRule> = ["a", "b", "c"]
+        .iter()
+        .map(|id| {
+            let source =
+                format!(r#"warning_at("{id}", file, line, name) <-- symbol(file, name, _, line);"#);
+            parse_rule_content(&source, id, false).unwrap()
+        })
+        .collect();
+
+    let mut relations = Relations::new();
+    for (file, name, line) in [
+        ("z.rs", "z", 9),
+        ("a.rs", "b", 2),
+        ("a.rs", "a", 2),
+        ("a.rs", "c", 1),
+    ] {
+        relations.add_symbol(file, name, "function", line);
+    }
+
+    let summary = |diagnostics: Vec<Diagnostic>| -> Vec<String> {
+        diagnostics
+            .iter()
+            .map(|d| {
+                let loc = d.location.as_ref().unwrap();
+                format!("{} {}:{} {}", d.rule_id, loc.file, loc.line, d.message)
+            })
+            .collect()
+    };
+    let parallel = summary(run_rules_parallel(&rules, &relations).unwrap());
+    let single =
+        summary(with_rule_threads(Some(1), || run_rules_parallel(&rules, &relations)).unwrap());
+    assert_eq!(parallel, single);
+    assert_eq!(
+        &parallel[..4],
+        ["a a.rs:1 c", "a a.rs:2 a", "a a.rs:2 b", "a z.rs:9 z"]
+    );
+    assert_eq!(parallel.len(), 12);
+    assert!(parallel[4].starts_with("b ") && parallel[8].starts_with("c "));
+}
+
+#[test]
+fn test_span_relations() {
+    let mut relations = Relations::new();
+    relations.add_symbol("src/a.rs", "Parser", "struct", 10);
+    relations.add_symbol_range("src/a.rs", "Parser", 10, 42);
+
+    let rules = r#"
+        warning_span("long-type", file, start, end, name) <-- symbol_range(file, name, start, end);
+        error_span("inverted", file, end, start, name) <-- symbol_range(file, name, start, end);
+    "#;
+    let mut result = run_rules_source(rules, &relations).unwrap();
+    result.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+    assert_eq!(result.len(), 2);
+
+    assert_eq!(result[0].rule_id, "inverted");
+    assert_eq!(result[0].level, DiagnosticLevel::Error);
+    // An end before the start collapses to a single line.
+    let loc = result[0].location.as_ref().unwrap();
+    assert_eq!((loc.line, loc.end_line), (42, Some(42)));
+
+    assert_eq!(result[1].level, DiagnosticLevel::Warning);
+    let loc = result[1].location.as_ref().unwrap();
+    assert_eq!(
+        (loc.file.as_str(), loc.line, loc.end_line),
+        ("src/a.rs", 10, Some(42))
+    );
+}
+
+#[test]
+fn test_allow_matches_file_then_message() {
+    let content = r#"
+# ---
+# id = "test-allow-message"
+# allow = ["src/**"]
+# ---
+
+warning_at("test-allow-message", file, line, name) <-- symbol(file, name, _, line);
+error_at("test-allow-message", "", 0u32, name) <-- symbol(_, name, _, _);
+"#;
+
+    let mut relations = Relations::new();
+    relations.add_symbol("lib/a.py", "src/looks_like_a_path", "function", 3);
+
+    let rule = parse_rule_content(content, "test-allow-message", false).unwrap();
+    let result = run_rule(&rule, &relations).unwrap();
+    // A located diagnostic is matched by its file, not its path-like message;
+    // one without a file still falls back to its message (deprecated).
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].level, DiagnosticLevel::Warning);
+    let loc = result[0].location.as_ref().unwrap();
+    assert_eq!((loc.file.as_str(), loc.line), ("lib/a.py", 3));
 }
 
 #[test]
@@ -255,6 +553,85 @@ fn test_god_file() {
     assert_eq!(result[0].message.as_str(), "big.py");
 }
 
+#[test]
+fn test_hot_god_file() {
+    let mut relations = Relations::new();
+    for file in ["hot.py", "cold.py", "small.py"] {
+        let count = if file == "small.py" { 5 } else { 31 };
+        for i in 0..count {
+            relations.add_symbol(file, &format!("sym_{}", i), "function", i);
+        }
+    }
+    relations.add_churn("hot.py", 12);
+    relations.add_churn("cold.py", 2);
+    relations.add_churn("small.py", 40);
+
+    // Disabled by default, force-enable for test
+    let mut rule = find_builtin("hot-god-file");
+    rule.enabled = true;
+    let result = run_rule(&rule, &relations).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].message.as_str(), "hot.py");
+}
+
+#[test]
+fn test_tool_error_rate() {
+    let mut relations = Relations::new();
+    // s1: Bash fails 2 of 5 calls (40%), Read fails 1 of 10 (10%).
+    // Two identical failing Bash calls in one turn must both count.
+    let mut call = 0;
+    for (turn, is_error) in [(1, true), (1, true), (2, false), (3, false), (4, false)] {
+        relations.add_tool_call("s1", turn, call, "Bash", is_error);
+        call += 1;
+    }
+    for turn in 0..10 {
+        relations.add_tool_call("s1", turn, call, "Read", turn == 0);
+        call += 1;
+    }
+    // s2: Bash fails 3 of 4 calls, below the minimum call count.
+    for call in 0..4 {
+        relations.add_tool_call("s2", call + 1, call, "Bash", call > 0);
+    }
+
+    let rule = load_session_rules(Path::new("/nonexistent"), &RulesConfig::default())
+        .into_iter()
+        .find(|r| r.id == "tool-error-rate")
+        .unwrap();
+    let result = run_rule(&rule, &relations).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].message.as_str(), "Bash");
+    assert_eq!(result[0].location.as_ref().unwrap().file.as_str(), "s1");
+}
+
+#[test]
+fn test_session_cost_and_api_error_facts() {
+    let mut relations = Relations::new();
+    relations.add_session_cost("cheap", 0.25);
+    relations.add_session_cost("pricey", 12.5);
+    relations.add_api_error("pricey", 3, "rate_limit");
+
+    let rules = r#"
+diagnostic("warning", "expensive", s, 0u32, s) <-- session_cost(s, d), if d > 10.0;
+diagnostic("warning", "throttled", s, t, k) <-- api_error(s, t, k), if k == "rate_limit";
+"#;
+    let mut result = run_rules_source(rules, &relations).unwrap();
+    result.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].rule_id.as_str(), "expensive");
+    assert_eq!(result[0].message.as_str(), "pricey");
+    assert_eq!(result[1].rule_id.as_str(), "throttled");
+    assert_eq!(result[1].location.as_ref().unwrap().line, 3);
+}
+
+#[test]
+fn test_session_builtin_rules_parse() {
+    for builtin in BUILTIN_SESSION_RULES {
+        let rule = parse_rule_content(builtin.content, builtin.id, true)
+            .unwrap_or_else(|| panic!("Failed to parse session builtin: {}", builtin.id));
+        assert!(rule.builtin);
+    }
+}
+
 #[test]
 fn test_fan_out() {
     let mut relations = Relations::new();
@@ -284,6 +661,11 @@ fn test_hub_file() {
     for i in 0..31 {
         relations.add_import(&format!("file_{}.py", i), "utils.py", "helper");
     }
+    relations.add_resolved_import("file_0.py", "src/utils.py", "helper", "helper", "named");
+    // 31 files import an external package that resolves to no project file
+    for i in 0..31 {
+        relations.add_import(&format!("file_{}.py", i), "requests", "get");
+    }
     // Only 2 files import rare.py
     relations.add_import("a.py", "rare.py", "x");
     relations.add_import("b.py", "rare.py", "y");
@@ -291,9 +673,23 @@ fn test_hub_file() {
     // Hub-file is disabled by default, force-enable for test
     let mut rule = find_builtin("hub-file");
     rule.enabled = true;
-    let result = run_rule(&rule, &relations).unwrap();
-    assert_eq!(result.len(), 1);
-    assert_eq!(result[0].message.as_str(), "utils.py");
+    let mut result = run_rule(&rule, &relations).unwrap();
+    result.sort_by(|a, b| a.message.as_str().cmp(b.message.as_str()));
+    assert_eq!(result.len(), 2);
+    // Resolved modules are reported at the file they resolve to.
+    assert!(result[0].location.is_none());
+    assert_eq!(result[0].message.as_str(), "requests");
+    assert_eq!(result[1].message.as_str(), "utils.py");
+    let loc = result[1].location.as_ref().unwrap();
+    assert_eq!(loc.file.as_str(), "src/utils.py");
+
+    // An allow pattern matches the resolved file, or the module name when the
+    // module has no file.
+    rule.allow = vec![
+        Pattern::new("src/**").unwrap(),
+        Pattern::new("requests").unwrap(),
+    ];
+    assert!(run_rule(&rule, &relations).unwrap().is_empty());
 }
 
 #[test]
@@ -374,6 +770,53 @@ fn test_config_override_deny() {
     assert_eq!(self_import.severity, Severity::Error);
 }
 
+#[test]
+fn test_config_override_paths_severity() {
+    let mut relations = Relations::new();
+    for file in [
+        "src/core/a.py",
+        "src/core/gen/b.py",
+        "experimental/c.py",
+        "d.py",
+    ] {
+        relations.add_import(file, file, "*");
+    }
+
+    let mut config = RulesConfig::default();
+    config.rules.insert(
+        "self-import".to_string(),
+        RuleOverride {
+            paths: [
+                ("src/core/**", "error"),
+                ("src/core/gen/**", "warning"),
+                ("experimental/**", "info"),
+            ]
+            .into_iter()
+            .map(|(p, s)| (p.to_string(), s.to_string()))
+            .collect(),
+            ..Default::default()
+        },
+    );
+    let rules = load_all_rules(Path::new("/nonexistent"), &config);
+    let rule = rules.iter().find(|r| r.id == "self-import").unwrap();
+    assert_eq!(
+        rule.path_severity_for("src/core/gen/b.py"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(rule.path_severity_for("d.py"), None);
+
+    let levels: HashMap<String, DiagnosticLevel> = run_rule(rule, &relations)
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.location.unwrap().file, d.level))
+        .collect();
+    assert_eq!(levels["src/core/a.py"], DiagnosticLevel::Error);
+    // The longer pattern wins over `src/core/**`.
+    assert_eq!(levels["src/core/gen/b.py"], DiagnosticLevel::Warning);
+    assert_eq!(levels["experimental/c.py"], DiagnosticLevel::Hint);
+    assert_eq!(levels["d.py"], DiagnosticLevel::Warning);
+}
+
 #[test]
 fn test_config_override_allow() {
     let mut relations = Relations::new();
@@ -462,16 +905,19 @@ fn test_filter_inline_allowed() {
     std::fs::write(dir.join("normal.py"), "def bar(): pass\n").unwrap();
 
     let mut diagnostics = vec![
-        Diagnostic::warning("test-rule", "suppressed.py"),
-        Diagnostic::warning("test-rule", "normal.py"),
-        Diagnostic::warning("test-rule", "nonexistent.py"), // not a file, kept
+        Diagnostic::warning("test-rule", "foo").at("suppressed.py", 2),
+        Diagnostic::warning("test-rule", "bar").at("normal.py", 1),
+        Diagnostic::warning("test-rule", "baz").at("nonexistent.py", 1), // not a file, kept
+        Diagnostic::warning("test-rule", "suppressed.py"), // no location, matched by message
+        Diagnostic::warning("test-rule", "normal.py"),     // no location, no comment
     ];
 
     filter_inline_allowed(&mut diagnostics, &dir);
 
-    assert_eq!(diagnostics.len(), 2);
-    assert_eq!(diagnostics[0].message.as_str(), "normal.py");
-    assert_eq!(diagnostics[1].message.as_str(), "nonexistent.py");
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].message.as_str(), "bar");
+    assert_eq!(diagnostics[1].message.as_str(), "baz");
+    assert_eq!(diagnostics[2].message.as_str(), "normal.py");
 
     let _ = std::fs::remove_dir_all(&dir);
 }
@@ -575,6 +1021,40 @@ fn test_symbol_range_relation() {
     assert_eq!(result[0].message.as_str(), "big_func");
 }
 
+#[test]
+fn test_file_info_relation() {
+    let mut relations = Relations::new();
+    relations.add_file_info("big.rs", "rust", 1500, 200, 100);
+    relations.add_file_info("commented.rs", "rust", 1500, 200, 400);
+    relations.add_file_info("big.py", "python", 1500, 0, 0);
+
+    let rules = r#"
+        diagnostic("warning", "long-rust-file", file, 0u32, file) <--
+            file_info(file, lang, loc, blank, comments), if lang == "rust",
+            let code = loc - blank - comments, if code > 1000u32;
+    "#;
+
+    let result = run_rules_source(rules, &relations).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].message.as_str(), "big.rs");
+}
+
+#[test]
+fn test_complexity_relation() {
+    let mut relations = Relations::new();
+    relations.add_complexity("a.rs", "tangled", 18, 4);
+    relations.add_complexity("a.rs", "simple", 2, 1);
+
+    let rules = r#"
+        diagnostic("warning", "complex-function", file, 0u32, name) <--
+            complexity(file, name, cc, _), if cc > 15u32;
+    "#;
+
+    let result = run_rules_source(rules, &relations).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].message.as_str(), "tangled");
+}
+
 #[test]
 fn test_implements_relation() {
     let mut relations = Relations::new();
@@ -0,0 +1,501 @@
+//! Fixture-driven tests for Datalog fact rules.
+//!
+//! A rule `.normalize/rules/foo.dl` is tested by a colocated fixture
+//! `foo.test.toml` (or `foo.test.json`) listing cases. Each case gives input
+//! facts, keyed by relation name with one array per tuple, and the diagnostics
+//! the rule must produce:
+//!
+//! ```toml
+//! [[case]]
+//! name = "two-file cycle"
+//!
+//! [case.facts]
+//! import = [
+//!     ["a.py", "b", "x"],
+//!     ["b.py", "a", "y"],
+//! ]
+//!
+//! [[case.expect]]
+//! file = "a.py"
+//! message = "a.py"
+//! ```
+//!
+//! Every field of an expectation is optional; omitted fields match anything
+//! and `message` is a substring match. A case passes when each expectation
+//! matches a distinct diagnostic and no diagnostic is left over. Rules run
+//! through [`normalize_facts_rules_interpret::run_rule`], so the rule's `allow`
+//! patterns and severity apply exactly as in `normalize rules run`.
+
+use normalize_facts_rules_api::{Diagnostic, DiagnosticLevel, Relations};
+use normalize_facts_rules_interpret::{FactsRule, parse_rule_file, run_rule};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A fixture file: the test cases for one rule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Test cases, in file order.
+    #[serde(default, rename = "case")]
+    pub cases: Vec<FixtureCase>,
+}
+
+/// One test case: input facts and the diagnostics they should produce.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureCase {
+    /// Case name shown in results.
+    pub name: String,
+    /// Input tuples keyed by relation name (`symbol`, `import`, ...).
+    #[serde(default)]
+    pub facts: BTreeMap<String, Vec<Vec<FactValue>>>,
+    /// Diagnostics the rule must produce; empty means "no findings".
+    #[serde(default)]
+    pub expect: Vec<ExpectedDiagnostic>,
+}
+
+/// One column of a fixture tuple.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FactValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+/// A diagnostic a case expects. `None` fields match any value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedDiagnostic {
+    /// Rule id of the diagnostic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// `"error"`, `"warning"` or `"hint"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// File the diagnostic is reported against; `""` means no location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Line in `file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// Substring of the diagnostic message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ExpectedDiagnostic {
+    /// Whether `d` satisfies every field this expectation sets.
+    pub fn matches(&self, d: &Diagnostic) -> bool {
+        let file = d.location.as_ref().map_or("", |l| l.file.as_str());
+        let line = d.location.as_ref().map_or(0, |l| l.line);
+        self.rule.as_ref().is_none_or(|r| *r == d.rule_id)
+            && self
+                .severity
+                .as_ref()
+                .is_none_or(|s| s == level_name(d.level))
+            && self.file.as_ref().is_none_or(|f| f == file)
+            && self.line.is_none_or(|l| l == line)
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|m| d.message.contains(m.as_str()))
+    }
+}
+
+impl std::fmt::Display for ExpectedDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(rule) = &self.rule {
+            parts.push(format!("rule={rule}"));
+        }
+        if let Some(severity) = &self.severity {
+            parts.push(format!("severity={severity}"));
+        }
+        if let Some(file) = &self.file {
+            parts.push(format!("file={file:?}"));
+        }
+        if let Some(line) = self.line {
+            parts.push(format!("line={line}"));
+        }
+        if let Some(message) = &self.message {
+            parts.push(format!("message~{message:?}"));
+        }
+        if parts.is_empty() {
+            write!(f, "any diagnostic")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+/// Outcome of one fixture case.
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    /// Case name from the fixture.
+    pub name: String,
+    /// Expectations no diagnostic matched.
+    pub missing: Vec<ExpectedDiagnostic>,
+    /// Diagnostics no expectation matched.
+    pub unexpected: Vec<Diagnostic>,
+    /// Set when the facts were invalid or the rule failed to evaluate.
+    pub error: Option<String>,
+}
+
+impl CaseResult {
+    /// Whether the case passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.missing.is_empty() && self.unexpected.is_empty()
+    }
+
+    /// One line per problem, for display.
+    pub fn diff(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(e) = &self.error {
+            lines.push(format!("error: {e}"));
+        }
+        for m in &self.missing {
+            lines.push(format!("missing: {m}"));
+        }
+        for d in &self.unexpected {
+            let location = d
+                .location
+                .as_ref()
+                .map(|l| format!("{}:{} ", l.file, l.line))
+                .unwrap_or_default();
+            lines.push(format!(
+                "unexpected: {location}[{}] {} {}",
+                d.rule_id,
+                level_name(d.level),
+                d.message
+            ));
+        }
+        lines
+    }
+}
+
+/// Results of running one rule's fixture.
+#[derive(Debug, Clone)]
+pub struct RuleTestResult {
+    /// Id of the rule under test.
+    pub rule_id: String,
+    /// Fixture file the cases came from.
+    pub fixture: PathBuf,
+    /// One result per case, in fixture order.
+    pub cases: Vec<CaseResult>,
+}
+
+/// The fixture colocated with a rule file: `foo.dl` → `foo.test.toml`, else
+/// `foo.test.json`. `None` when neither exists.
+pub fn fixture_for(rule_path: &Path) -> Option<PathBuf> {
+    ["test.toml", "test.json"]
+        .iter()
+        .map(|ext| rule_path.with_extension(ext))
+        .find(|p| p.is_file())
+}
+
+/// Read a fixture file; the format is chosen by extension (`.json`, else TOML).
+pub fn load_fixture(path: &Path) -> Result<Fixture, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    if path.extension().is_some_and(|e| e == "json") {
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {e}", path.display()))
+    } else {
+        toml::from_str(&content).map_err(|e| format!("Invalid {}: {e}", path.display()))
+    }
+}
+
+/// Run one case against `rule`.
+pub fn run_case(rule: &FactsRule, case: &FixtureCase) -> CaseResult {
+    let mut result = CaseResult {
+        name: case.name.clone(),
+        missing: Vec::new(),
+        unexpected: Vec::new(),
+        error: None,
+    };
+    let diagnostics = match relations_from_facts(&case.facts)
+        .and_then(|relations| run_rule(rule, &relations).map_err(|e| e.to_string()))
+    {
+        Ok(d) => d,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+
+    let mut unmatched: Vec<Option<Diagnostic>> = diagnostics.into_iter().map(Some).collect();
+    for expected in &case.expect {
+        let hit = unmatched
+            .iter_mut()
+            .find(|d| d.as_ref().is_some_and(|d| expected.matches(d)));
+        match hit {
+            Some(slot) => *slot = None,
+            None => result.missing.push(expected.clone()),
+        }
+    }
+    result.unexpected = unmatched.into_iter().flatten().collect();
+    result
+}
+
+/// Run every case of `fixture` against `rule`.
+pub fn test_rule(rule: &FactsRule, fixture: &Fixture, fixture_path: &Path) -> RuleTestResult {
+    RuleTestResult {
+        rule_id: rule.id.clone(),
+        fixture: fixture_path.to_path_buf(),
+        cases: fixture.cases.iter().map(|c| run_case(rule, c)).collect(),
+    }
+}
+
+/// Test every `.dl` rule in `dir` that has a colocated fixture, sorted by
+/// file name. Rules without a fixture are skipped.
+pub fn test_rules_dir(dir: &Path) -> Result<Vec<RuleTestResult>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+    let mut rule_paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "dl"))
+        .collect();
+    rule_paths.sort();
+
+    let mut results = Vec::new();
+    for rule_path in rule_paths {
+        let Some(fixture_path) = fixture_for(&rule_path) else {
+            continue;
+        };
+        let rule = parse_rule_file(&rule_path)
+            .ok_or_else(|| format!("Failed to parse rule {}", rule_path.display()))?;
+        let fixture = load_fixture(&fixture_path)?;
+        results.push(test_rule(&rule, &fixture, &fixture_path));
+    }
+    Ok(results)
+}
+
+/// Build [`Relations`] from fixture tuples.
+///
+/// Relation names and column order follow the Datalog preamble
+/// (`symbol(file, name, kind, line)`, ...). `depends_on` takes the three
+/// columns rules see; its manifest is left empty.
+pub fn relations_from_facts(
+    facts: &BTreeMap<String, Vec<Vec<FactValue>>>,
+) -> Result<Relations, String> {
+    let mut r = Relations::new();
+    for (relation, rows) in facts {
+        for row in rows {
+            let t = Tuple { relation, row };
+            match relation.as_str() {
+                "symbol" => {
+                    t.arity(4)?;
+                    r.add_symbol(t.s(0)?, t.s(1)?, t.s(2)?, t.u(3)?)
+                }
+                "import" => {
+                    t.arity(3)?;
+                    r.add_import(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "call" => {
+                    t.arity(4)?;
+                    r.add_call(t.s(0)?, t.s(1)?, t.s(2)?, t.u(3)?)
+                }
+                "visibility" => {
+                    t.arity(3)?;
+                    r.add_visibility(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "attribute" => {
+                    t.arity(3)?;
+                    r.add_attribute(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "parent" => {
+                    t.arity(3)?;
+                    r.add_parent(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "qualifier" => {
+                    t.arity(4)?;
+                    r.add_qualifier(t.s(0)?, t.s(1)?, t.s(2)?, t.s(3)?)
+                }
+                "symbol_range" => {
+                    t.arity(4)?;
+                    r.add_symbol_range(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?)
+                }
+                "implements" => {
+                    t.arity(3)?;
+                    r.add_implements(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "is_impl" => {
+                    t.arity(2)?;
+                    r.add_is_impl(t.s(0)?, t.s(1)?)
+                }
+                "type_method" => {
+                    t.arity(3)?;
+                    r.add_type_method(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "resolved_import" => {
+                    t.arity(5)?;
+                    r.add_resolved_import(t.s(0)?, t.s(1)?, t.s(2)?, t.s(3)?, t.s(4)?)
+                }
+                "module" => {
+                    t.arity(2)?;
+                    r.add_module(t.s(0)?, t.s(1)?)
+                }
+                "export" => {
+                    t.arity(3)?;
+                    r.add_export(t.s(0)?, t.s(1)?, t.s(2)?)
+                }
+                "reexport" => {
+                    t.arity(4)?;
+                    r.add_reexport(t.s(0)?, t.s(1)?, t.s(2)?, t.s(3)?)
+                }
+                "symbol_use" => {
+                    t.arity(3)?;
+                    r.add_symbol_use(t.s(0)?, t.s(1)?, t.u(2)?)
+                }
+                "resolved_reference" => {
+                    t.arity(5)?;
+                    r.add_resolved_reference(t.s(0)?, t.u(1)?, t.s(2)?, t.s(3)?, t.s(4)?)
+                }
+                "resolved_call" => {
+                    t.arity(5)?;
+                    r.add_resolved_call(t.s(0)?, t.s(1)?, t.s(2)?, t.s(3)?, t.u(4)?)
+                }
+                "module_search_path" => {
+                    t.arity(4)?;
+                    r.add_module_search_path(t.s(0)?, t.s(1)?, t.s(2)?, t.s(3)?)
+                }
+                "depends_on" => {
+                    t.arity(3)?;
+                    r.add_depends_on(t.s(0)?, t.s(1)?, t.s(2)?, "")
+                }
+                "file_info" => {
+                    t.arity(5)?;
+                    r.add_file_info(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?, t.u(4)?)
+                }
+                "complexity" => {
+                    t.arity(4)?;
+                    r.add_complexity(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?)
+                }
+                "churn" => {
+                    t.arity(2)?;
+                    r.add_churn(t.s(0)?, t.u(1)?)
+                }
+                "tool_call" => {
+                    t.arity(5)?;
+                    r.add_tool_call(t.s(0)?, t.u(1)?, t.u(2)?, t.s(3)?, t.b(4)?)
+                }
+                "session_cost" => {
+                    t.arity(2)?;
+                    r.add_session_cost(t.s(0)?, t.f(1)?)
+                }
+                "api_error" => {
+                    t.arity(3)?;
+                    r.add_api_error(t.s(0)?, t.u(1)?, t.s(2)?)
+                }
+                "cfg_block" => {
+                    t.arity(5)?;
+                    r.add_cfg_block(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?, t.s(4)?)
+                }
+                "cfg_edge" => {
+                    t.arity(7)?;
+                    r.add_cfg_edge(
+                        t.s(0)?,
+                        t.s(1)?,
+                        t.u(2)?,
+                        t.u(3)?,
+                        t.u(4)?,
+                        t.s(5)?,
+                        t.s(6)?,
+                    )
+                }
+                "cfg_def" => {
+                    t.arity(5)?;
+                    r.add_cfg_def(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?, t.s(4)?)
+                }
+                "cfg_use" => {
+                    t.arity(5)?;
+                    r.add_cfg_use(t.s(0)?, t.s(1)?, t.u(2)?, t.u(3)?, t.s(4)?)
+                }
+                "cfg_effect" => {
+                    t.arity(7)?;
+                    r.add_cfg_effect(
+                        t.s(0)?,
+                        t.s(1)?,
+                        t.u(2)?,
+                        t.u(3)?,
+                        t.s(4)?,
+                        t.u(5)?,
+                        t.s(6)?,
+                    )
+                }
+                other => return Err(format!("unknown input relation `{other}`")),
+            }
+        }
+    }
+    Ok(r)
+}
+
+/// A fixture tuple being converted, for typed column access with errors
+/// that name the relation and column.
+struct Tuple<'a> {
+    relation: &'a str,
+    row: &'a [FactValue],
+}
+
+impl Tuple<'_> {
+    fn arity(&self, n: usize) -> Result<(), String> {
+        if self.row.len() == n {
+            Ok(())
+        } else {
+            Err(format!(
+                "`{}` takes {n} columns, got {}",
+                self.relation,
+                self.row.len()
+            ))
+        }
+    }
+
+    fn type_error(&self, i: usize, expected: &str) -> String {
+        format!(
+            "`{}` column {} must be {expected}, got {:?}",
+            self.relation,
+            i + 1,
+            self.row[i]
+        )
+    }
+
+    fn s(&self, i: usize) -> Result<&str, String> {
+        match &self.row[i] {
+            FactValue::Str(s) => Ok(s),
+            _ => Err(self.type_error(i, "a string")),
+        }
+    }
+
+    fn u(&self, i: usize) -> Result<u32, String> {
+        match &self.row[i] {
+            FactValue::Int(n) => u32::try_from(*n).map_err(|_| self.type_error(i, "a u32")),
+            _ => Err(self.type_error(i, "a u32")),
+        }
+    }
+
+    fn b(&self, i: usize) -> Result<bool, String> {
+        match &self.row[i] {
+            FactValue::Bool(b) => Ok(*b),
+            _ => Err(self.type_error(i, "a bool")),
+        }
+    }
+
+    fn f(&self, i: usize) -> Result<f64, String> {
+        match &self.row[i] {
+            FactValue::Float(f) => Ok(*f),
+            FactValue::Int(n) => Ok(*n as f64),
+            _ => Err(self.type_error(i, "a number")),
+        }
+    }
+}
+
+fn level_name(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::Hint => "hint",
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,164 @@
+use super::*;
+use normalize_facts_rules_interpret::parse_rule_content;
+
+const CYCLE_RULE: &str = r#"# ---
+# id = "cycle"
+# message = "Import cycle"
+# ---
+
+relation reaches(String, String);
+reaches(a, b) <-- import(a, b, _);
+reaches(a, c) <-- import(a, b, _), reaches(b, c);
+diagnostic("warning", "cycle", a, 0u32, a) <-- reaches(a, a);
+"#;
+
+fn cycle_rule() -> FactsRule {
+    parse_rule_content(CYCLE_RULE, "cycle", false).unwrap()
+}
+
+fn parse(toml_src: &str) -> Fixture {
+    toml::from_str(toml_src).unwrap()
+}
+
+#[test]
+fn passing_case_matches_expected_diagnostics() {
+    let fixture = parse(
+        r#"
+[[case]]
+name = "two-file cycle"
+
+[case.facts]
+import = [["a", "b", "x"], ["b", "a", "y"]]
+
+[[case.expect]]
+file = "a"
+severity = "warning"
+
+[[case.expect]]
+rule = "cycle"
+message = "b"
+"#,
+    );
+    let result = run_case(&cycle_rule(), &fixture.cases[0]);
+    assert!(result.passed(), "{:?}", result.diff());
+}
+
+#[test]
+fn empty_expect_requires_no_diagnostics() {
+    let fixture = parse(
+        r#"
+[[case]]
+name = "no cycle"
+
+[case.facts]
+import = [["a", "b", "x"]]
+"#,
+    );
+    let result = run_case(&cycle_rule(), &fixture.cases[0]);
+    assert!(result.passed(), "{:?}", result.diff());
+}
+
+#[test]
+fn reports_missing_and_unexpected() {
+    let fixture = parse(
+        r#"
+[[case]]
+name = "wrong expectation"
+
+[case.facts]
+import = [["a", "a", "x"]]
+
+[[case.expect]]
+file = "z"
+"#,
+    );
+    let result = run_case(&cycle_rule(), &fixture.cases[0]);
+    assert!(!result.passed());
+    assert_eq!(result.missing.len(), 1);
+    assert_eq!(result.unexpected.len(), 1);
+    let diff = result.diff();
+    assert!(diff[0].starts_with("missing: file=\"z\""), "{diff:?}");
+    assert!(
+        diff[1].contains("unexpected: a:0 [cycle] warning"),
+        "{diff:?}"
+    );
+}
+
+#[test]
+fn invalid_facts_are_case_errors() {
+    let fixture = parse(
+        r#"
+[[case]]
+name = "bad arity"
+
+[case.facts]
+import = [["a", "b"]]
+
+[[case]]
+name = "bad relation"
+
+[case.facts]
+imports = [["a", "b", "c"]]
+
+[[case]]
+name = "bad type"
+
+[case.facts]
+symbol = [["a.py", "f", "function", "ten"]]
+"#,
+    );
+    let rule = cycle_rule();
+    let errors: Vec<String> = fixture
+        .cases
+        .iter()
+        .map(|c| run_case(&rule, c).error.unwrap())
+        .collect();
+    assert_eq!(errors[0], "`import` takes 3 columns, got 2");
+    assert_eq!(errors[1], "unknown input relation `imports`");
+    assert!(errors[2].starts_with("`symbol` column 4 must be a u32"));
+}
+
+#[test]
+fn typed_columns_convert() {
+    let fixture: Fixture = serde_json::from_str(
+        r#"{"case": [{"name": "mixed", "facts": {
+            "tool_call": [["s1", 1, 2, "Bash", true]],
+            "session_cost": [["s1", 2]],
+            "depends_on": [["app", "serde", "1.0"]]
+        }}]}"#,
+    )
+    .unwrap();
+    let relations = relations_from_facts(&fixture.cases[0].facts).unwrap();
+    assert!(relations.tool_calls[0].is_error);
+    assert_eq!(relations.session_costs[0].dollars, 2.0);
+    assert_eq!(relations.depends_on[0].manifest, "");
+}
+
+#[test]
+fn discovers_colocated_fixtures() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("cycle.dl"), CYCLE_RULE).unwrap();
+    std::fs::write(
+        dir.path().join("cycle.test.toml"),
+        r#"
+[[case]]
+name = "self import"
+[case.facts]
+import = [["a", "a", "x"]]
+[[case.expect]]
+file = "a"
+"#,
+    )
+    .unwrap();
+    // A rule without a fixture is skipped.
+    std::fs::write(dir.path().join("other.dl"), CYCLE_RULE).unwrap();
+
+    let results = test_rules_dir(dir.path()).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "cycle");
+    assert!(
+        results[0].cases[0].passed(),
+        "{:?}",
+        results[0].cases[0].diff()
+    );
+}
@@ -126,16 +126,35 @@ fn maybe_build_runtime() -> Result<Option<Runtime>, Error> {
 
 impl CaCache {
     /// Open (or create) the CA cache at the given path. Creates parent directories.
+    ///
+    /// The file is shared by every process on the machine, so initialisation
+    /// runs under the cache lock; a corrupt database is discarded and recreated.
     pub(crate) fn open(path: &Path, max_size_bytes: u64) -> Result<Self, Error> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| Error(format!("create_dir_all: {e}")))?;
         }
         let runtime = maybe_build_runtime()?;
-        let init = async {
-            let db = Builder::new_local(path).build().await?;
-            let conn = db.connect()?;
-            conn.execute_batch(
-                "PRAGMA journal_mode=WAL;
+        let _lock = normalize_cache::CacheLock::exclusive(path)
+            .map_err(|e| Error(format!("lock {}: {e}", path.display())))?;
+        let (db, conn) = match block_on_helper(&runtime, Self::init(path)) {
+            Err(e) if normalize_cache::is_sqlite_corruption(&e.to_string()) => {
+                tracing::warn!("normalize-facts: CA cache corrupted, rebuilding: {}", e);
+                normalize_cache::discard_corrupt(path);
+                block_on_helper(&runtime, Self::init(path))?
+            }
+            result => result?,
+        };
+        Ok(Self {
+            inner: Arc::new(Inner { conn, db, runtime }),
+            max_size_bytes,
+        })
+    }
+
+    async fn init(path: &Path) -> Result<(Database, Connection), libsql::Error> {
+        let db = Builder::new_local(path).build().await?;
+        let conn = db.connect()?;
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
                  PRAGMA synchronous=NORMAL;
                  PRAGMA busy_timeout=5000;
                  CREATE TABLE IF NOT EXISTS ca_entries (
@@ -146,23 +165,15 @@ impl CaCache {
                    last_used INTEGER NOT NULL DEFAULT (strftime('%s','now')),
                    PRIMARY KEY (hash, extr_ver, grammar)
                  ) WITHOUT ROWID;",
-            )
-            .await?;
-            Ok::<_, libsql::Error>((db, conn))
-        };
-        let (db, conn) = block_on_helper(&runtime, init)?;
-        Ok(Self {
-            inner: Arc::new(Inner { conn, db, runtime }),
-            max_size_bytes,
-        })
+        )
+        .await?;
+        Ok((db, conn))
     }
 
-    /// Default path: `~/.config/normalize/ca-cache.sqlite`.
+    /// Default path: `~/.cache/normalize/ca-cache.sqlite`.
     pub(crate) fn default_path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("normalize")
-            .join("ca-cache.sqlite")
+        normalize_cache::cache_path("ca-cache.sqlite")
+            .unwrap_or_else(|| PathBuf::from(".normalize").join("ca-cache.sqlite"))
     }
 
     /// Look up a cached payload. Returns `None` on miss or version mismatch.
@@ -346,7 +357,7 @@ pub(crate) const SYMBOL_CACHE_VERSIONS: &[&str] = &["symbols-v2-all", "symbols-v
 /// Get the global symbol cache singleton.
 ///
 /// Returns `None` if the cache could not be opened (e.g., no write permission
-/// to `~/.config/normalize/`). Callers should treat `None` as a cache miss
+/// to `~/.cache/normalize/`). Callers should treat `None` as a cache miss
 /// and proceed with live parsing.
 pub(crate) fn symbol_cache() -> Option<&'static CaCache> {
     SYMBOL_CACHE
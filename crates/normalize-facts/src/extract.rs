@@ -65,6 +65,7 @@ impl ExtractResult {
                     is_interface_impl: sym.is_interface_impl,
                     implements: sym.implements.clone(),
                     complexity: sym.complexity,
+                    nesting_depth: sym.nesting_depth,
                 })
             } else {
                 None
@@ -113,6 +114,7 @@ impl ExtractResult {
                 is_interface_impl: sym.is_interface_impl,
                 implements: sym.implements.clone(),
                 complexity: sym.complexity,
+                nesting_depth: sym.nesting_depth,
             })
         }
 
@@ -287,10 +289,11 @@ impl Extractor {
         // semantics change in a way that invalidates existing cached results.
         // Cross-file resolver results are not cached (resolver.is_none() guard below).
         // v2 (2026-07-15): Symbol gained a `complexity` field.
+        // v3 (2026-10-17): Symbol gained a `nesting_depth` field.
         let cache_ver = if self.options.include_private {
-            "symbols-v2-all"
+            "symbols-v3-all"
         } else {
-            "symbols-v2-public"
+            "symbols-v3-public"
         };
 
         // Check the persistent symbol cache before parsing (only when no cross-file
@@ -431,10 +434,10 @@ fn build_symbol_from_def<'tree>(
     } else {
         normalize_languages::ImplementsInfo::default()
     };
-    // Cyclomatic complexity only applies to functions/methods — other symbol kinds
+    // Complexity only applies to functions/methods — other symbol kinds
     // (classes, modules, types, ...) genuinely have no complexity concept.
-    let complexity = matches!(kind, SymbolKind::Function | SymbolKind::Method)
-        .then(|| compute_complexity(&def.node, support, content.as_bytes()));
+    let metrics = matches!(kind, SymbolKind::Function | SymbolKind::Method)
+        .then(|| support.complexity(&def.node, content));
     Some(Symbol {
         name: name.to_string(),
         kind,
@@ -447,7 +450,8 @@ fn build_symbol_from_def<'tree>(
         children: Vec::new(),
         is_interface_impl: implements_info.is_interface,
         implements: implements_info.implements,
-        complexity,
+        complexity: metrics.map(|m| m.cyclomatic),
+        nesting_depth: metrics.map(|m| m.depth),
     })
 }
 
@@ -625,49 +629,16 @@ fn reverse_children_recursive(children: &mut [Symbol]) {
     }
 }
 
-/// Compute cyclomatic complexity for a function node using the `.complexity.scm` query.
+/// Compute cyclomatic complexity for a function node via [`Language::complexity`].
 /// Returns 1 (base complexity) for languages without a complexity query.
 pub fn compute_complexity(
     node: &tree_sitter::Node,
     support: &dyn Language,
     source: &[u8],
 ) -> usize {
-    let grammar_name = support.grammar_name();
-    let loader = parsers::grammar_loader();
-    if let Some(scm) = loader.get_complexity(grammar_name)
-        && let Some(query) = loader.get_compiled_query(grammar_name, "complexity", &scm)
-    {
-        return count_complexity_with_query(node, source, &query);
-    }
-    1
-}
-
-/// Count complexity using a `@complexity` query.
-fn count_complexity_with_query(
-    node: &tree_sitter::Node,
-    source: &[u8],
-    query: &tree_sitter::Query,
-) -> usize {
-    let complexity_idx = query
-        .capture_names()
-        .iter()
-        .position(|n| *n == "complexity");
-    let Some(complexity_idx) = complexity_idx else {
-        return 1;
-    };
-
-    let mut qcursor = tree_sitter::QueryCursor::new();
-    qcursor.set_byte_range(node.byte_range());
-    let mut complexity = 1usize;
-    let mut matches = qcursor.matches(query, *node, source);
-    while let Some(m) = matches.next() {
-        for capture in m.captures {
-            if capture.index as usize == complexity_idx {
-                complexity += 1;
-            }
-        }
-    }
-    complexity
+    std::str::from_utf8(source)
+        .map(|content| support.complexity(node, content).cyclomatic)
+        .unwrap_or(1)
 }
 
 #[cfg(test)]
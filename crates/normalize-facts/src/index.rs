@@ -1,10 +1,10 @@
 use crate::symbols::SymbolParser;
 use ignore::WalkBuilder;
-use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
 use libsql::{Connection, Database, params};
 pub use normalize_facts_core::IndexedFile;
 use normalize_facts_core::{FlatImport, FlatSymbol, TypeRef, split_identifier_words};
 use normalize_languages::support_for_path;
+use normalize_output::progress::{self, NoProgress, Progress, ProgressKind};
 use normalize_rules_config::WalkConfig;
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
@@ -87,6 +87,7 @@ struct ParsedSymbol {
     implements: Vec<String>,
     docstring: Option<String>,
     complexity: Option<usize>,
+    nesting_depth: Option<usize>,
 }
 
 /// One call-site entry: (caller_symbol, callee_name, callee_qualifier, access, line).
@@ -105,6 +106,8 @@ struct ParsedFileData {
     type_refs: Vec<TypeRef>,
     /// CFG data (blocks, edges, defs, uses) for function-level analysis.
     cfg: CfgData,
+    /// Language and line counts for the `file_info` table.
+    info: FileInfo,
 }
 
 /// CA-cache payload: all extracted data for a single file, keyed by content hash.
@@ -119,7 +122,7 @@ struct CachedFileData {
 }
 
 // Not yet public - just delete .normalize/index.sqlite on schema changes
-const SCHEMA_VERSION: i64 = 17;
+const SCHEMA_VERSION: i64 = 20;
 
 /// Bump when extraction logic changes to invalidate cached results.
 /// Bumped to "2" (2026-04-27): purge CA cache entries that may have been poisoned
@@ -127,7 +130,10 @@ const SCHEMA_VERSION: i64 = 17;
 /// Bumped to "3" (2026-07-15): `ParsedSymbol`/`CachedFileData` gained a `complexity`
 /// field; old bincode-serialized cache entries have a different byte layout and
 /// must not be deserialized against the new struct shape.
-const EXTRACTOR_VERSION: &str = "3";
+/// Bumped to "4" (2026-10-17): incremental reindexing cached entries with empty
+/// `type_methods`; it now shares the full extraction path.
+/// Bumped to "5" (2026-10-17): `ParsedSymbol` gained `nesting_depth`.
+const EXTRACTOR_VERSION: &str = "5";
 
 /// Check if a file path has a supported source extension.
 fn is_source_file(path: &str) -> bool {
@@ -161,6 +167,86 @@ pub struct SymbolMatch {
     pub parent: Option<String>,
 }
 
+/// Score `name` against a fuzzy `query`, or `None` if the query characters
+/// don't all appear in order (case-insensitive).
+///
+/// Exact and prefix matches rank highest; otherwise matches get a bonus for
+/// consecutive characters and for landing on word boundaries (after `_`, `-`,
+/// `.`, or a lower→upper camelCase transition), and a penalty for gaps.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = name.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    if lower == query {
+        return Some(10_000);
+    }
+    if lower.starts_with(&query) {
+        return Some(5_000 - (lower.len() - query.len()) as i64);
+    }
+
+    let mut score = 0i64;
+    let mut qi = 0;
+    let mut last: Option<usize> = None;
+    for (i, &c) in lower.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if c != query[qi] {
+            continue;
+        }
+        let boundary = i == 0
+            || matches!(chars[i - 1], '_' | '-' | '.' | ':')
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase());
+        if boundary {
+            score += 30;
+        }
+        match last {
+            Some(prev) if prev + 1 == i => score += 20,
+            Some(prev) => score -= (i - prev - 1).min(10) as i64,
+            None => score -= i.min(10) as i64,
+        }
+        score += 10;
+        last = Some(i);
+        qi += 1;
+    }
+    (qi == query.len()).then_some(score)
+}
+
+/// Git churn for one file, aggregated from the `file_commits` table.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FileChurn {
+    pub file: String,
+    /// Commits touching the file across all walked history.
+    pub commits: usize,
+    /// Commits touching the file at or after the `since` timestamp of the query.
+    pub recent_commits: usize,
+    /// Unix timestamp of the newest commit touching the file.
+    pub last_changed: u64,
+    /// Distinct author emails across all commits touching the file.
+    pub authors: usize,
+}
+
+/// Language and line counts for one source file, from the `file_info` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct FileInfo {
+    pub file: String,
+    /// Grammar name of the file's language (e.g. "python", "rust").
+    pub language: String,
+    /// Total lines, including blank and comment lines.
+    pub lines: usize,
+    /// Lines containing only whitespace.
+    pub blank_lines: usize,
+    /// Non-blank lines covered by a comment node.
+    pub comment_lines: usize,
+}
+
 /// Files that changed since last index
 #[derive(Debug, Default)]
 pub struct ChangedFiles {
@@ -194,6 +280,9 @@ impl FileIndex {
     /// Open or create an index at the specified database path.
     /// On corruption, automatically deletes and recreates the index.
     ///
+    /// Opening (schema setup and any recovery) holds the index's cache lock, so
+    /// concurrent invocations never initialise or delete the file under each other.
+    ///
     /// # Arguments
     /// * `db_path` - Path to the SQLite database file
     /// * `root` - Project root directory (used for file walking during refresh)
@@ -209,32 +298,19 @@ impl FileIndex {
             );
         }
 
+        let _lock = normalize_cache::CacheLock::exclusive(db_path).map_err(|e| {
+            libsql::Error::SqliteFailure(1, format!("Failed to lock index {:?}: {}", db_path, e))
+        })?;
+
         // Try to open, with recovery on corruption
         match Self::try_open(db_path, root).await {
-            Ok(idx) => Ok(idx),
-            Err(e) => {
-                // Check for corruption-like errors
-                let err_str = e.to_string().to_lowercase();
-                let is_corruption = err_str.contains("corrupt")
-                    || err_str.contains("malformed")
-                    || err_str.contains("disk i/o error")
-                    || err_str.contains("not a database")
-                    || err_str.contains("database disk image")
-                    || err_str.contains("integrity check failed");
-
-                if is_corruption {
-                    tracing::warn!("Index corrupted, rebuilding: {}", e);
-                    // Delete corrupted database and retry
-                    let _ = std::fs::remove_file(db_path);
-                    // Also remove journal/wal files if they exist
-                    let _ = std::fs::remove_file(db_path.with_extension("sqlite-journal"));
-                    let _ = std::fs::remove_file(db_path.with_extension("sqlite-wal"));
-                    let _ = std::fs::remove_file(db_path.with_extension("sqlite-shm"));
-                    Self::try_open(db_path, root).await
-                } else {
-                    Err(e)
-                }
+            Err(e) if normalize_cache::is_sqlite_corruption(&e.to_string()) => {
+                tracing::warn!("Index corrupted, rebuilding: {}", e);
+                // Delete the corrupted database (and its journal/wal files) and retry
+                normalize_cache::discard_corrupt(db_path);
+                Self::try_open(db_path, root).await
             }
+            result => result,
         }
     }
 
@@ -282,6 +358,30 @@ impl FileIndex {
             (),
         )
         .await?;
+        // mtime of each file as of its last symbol/call/import extraction. Kept
+        // separate from `files` so a file-walk refresh (which updates
+        // `files.mtime`) doesn't hide the change from the call-graph refresh.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS facts_files (
+                path TEXT PRIMARY KEY,
+                mtime INTEGER NOT NULL
+            )",
+            (),
+        )
+        .await?;
+        // Language and line counts per source file, written alongside the
+        // file's symbols so they share its extraction lifecycle.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS file_info (
+                path TEXT PRIMARY KEY,
+                language TEXT NOT NULL,
+                lines INTEGER NOT NULL,
+                blank_lines INTEGER NOT NULL,
+                comment_lines INTEGER NOT NULL
+            )",
+            (),
+        )
+        .await?;
 
         // Call graph for fast caller/callee lookups
         conn.execute(
@@ -331,7 +431,8 @@ impl FileIndex {
                 parent TEXT,
                 visibility TEXT NOT NULL DEFAULT 'public',
                 is_impl INTEGER NOT NULL DEFAULT 0,
-                complexity INTEGER
+                complexity INTEGER,
+                nesting_depth INTEGER
             )",
             (),
         )
@@ -499,6 +600,9 @@ impl FileIndex {
         conn.execute("ALTER TABLE symbols ADD COLUMN complexity INTEGER", ())
             .await
             .ok();
+        conn.execute("ALTER TABLE symbols ADD COLUMN nesting_depth INTEGER", ())
+            .await
+            .ok();
         // resolved_file was added to imports after schema version 5 was already set;
         // run unconditionally so existing v5 DBs without the column get migrated.
         conn.execute("ALTER TABLE imports ADD COLUMN resolved_file TEXT", ())
@@ -524,6 +628,8 @@ impl FileIndex {
             conn.execute("DELETE FROM calls", ()).await?;
             conn.execute("DELETE FROM symbols", ()).await?;
             conn.execute("DELETE FROM imports", ()).await?;
+            conn.execute("DELETE FROM facts_files", ()).await?;
+            conn.execute("DELETE FROM file_info", ()).await?;
             // Add new columns that may not exist in older schema versions.
             // Use .ok() to tolerate "duplicate column" errors on already-migrated DBs.
             conn.execute("ALTER TABLE imports ADD COLUMN resolved_file TEXT", ())
@@ -559,6 +665,7 @@ impl FileIndex {
             // file_churn: clear on schema bump so the next rebuild_co_change_edges()
             // repopulates it from the same git walk.
             conn.execute("DELETE FROM file_churn", ()).await.ok();
+            conn.execute("DELETE FROM file_commits", ()).await.ok();
             // CFG tables: clear so next rebuild repopulates them.
             conn.execute("DELETE FROM cfg_blocks", ()).await.ok();
             conn.execute("DELETE FROM cfg_edges", ()).await.ok();
@@ -674,6 +781,25 @@ impl FileIndex {
         )
         .await?;
 
+        // One row per (commit, source file) touch: commit time and author email.
+        // Also populated by rebuild_co_change_edges(); kept un-aggregated so
+        // time-windowed counts and distinct-author counts stay exact across
+        // incremental walks (see `file_churn_since`).
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS file_commits (
+                file TEXT NOT NULL,
+                commit_time INTEGER NOT NULL,
+                author TEXT NOT NULL
+            )",
+            (),
+        )
+        .await?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_file_commits_file ON file_commits(file)",
+            (),
+        )
+        .await?;
+
         // CFG blocks, edges, defs, and uses for control-flow analysis.
         conn.execute(
             "CREATE TABLE IF NOT EXISTS cfg_blocks (
@@ -873,6 +999,22 @@ impl FileIndex {
         self.progress = enabled;
     }
 
+    /// Reporter for one phase of a long-running operation; silent unless
+    /// [`set_progress`](Self::set_progress) enabled progress output.
+    fn progress_reporter(&self, kind: ProgressKind, message: &str) -> Box<dyn Progress> {
+        if self.progress {
+            progress::reporter(kind, message)
+        } else {
+            Box::new(NoProgress)
+        }
+    }
+
+    /// Stop reading from and writing to the content-addressed extraction cache,
+    /// so every file is parsed. Used by benchmarks.
+    pub fn disable_ca_cache(&mut self) {
+        self.ca_cache = None;
+    }
+
     /// Set the walk configuration used by [`FileIndex::refresh`] and
     /// [`FileIndex::get_changed_files`].
     ///
@@ -1284,17 +1426,7 @@ impl FileIndex {
 
         self.begin_clean().await?;
 
-        let pb = if self.progress && std::io::IsTerminal::is_terminal(&std::io::stderr()) {
-            let pb = ProgressBar::new_spinner();
-            pb.set_style(
-                ProgressStyle::with_template("{spinner:.cyan} {msg} [{elapsed_precise}]")
-                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
-            );
-            pb.set_message("Scanning files...");
-            pb
-        } else {
-            ProgressBar::hidden()
-        };
+        let pb = self.progress_reporter(ProgressKind::Spinner, "Scanning files...");
 
         let mut count = 0;
         let body: Result<(), libsql::Error> = async {
@@ -1333,12 +1465,11 @@ impl FileIndex {
                         )
                         .await?;
                     count += 1;
-                    pb.set_message(format!("Scanning files... {count}"));
-                    pb.tick();
+                    pb.set_message(&format!("Scanning files... {count}"));
                 }
             }
 
-            pb.finish_and_clear();
+            pb.finish();
 
             // Update last indexed time
             let now = SystemTime::now()
@@ -1442,8 +1573,8 @@ impl FileIndex {
         // Insert symbols
         for sym in symbols {
             self.conn.execute(
-                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                params![path.to_string(), sym.name.clone(), sym.kind.as_str(), sym.start_line as i64, sym.end_line as i64, sym.parent.clone(), sym.visibility.as_str(), sym.is_interface_impl as i64, sym.complexity.map(|c| c as i64)],
+                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl, complexity, nesting_depth) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![path.to_string(), sym.name.clone(), sym.kind.as_str(), sym.start_line as i64, sym.end_line as i64, sym.parent.clone(), sym.visibility.as_str(), sym.is_interface_impl as i64, sym.complexity.map(|c| c as i64), sym.nesting_depth.map(|d| d as i64)],
             ).await?;
             for attr in &sym.attributes {
                 self.conn
@@ -1778,6 +1909,60 @@ impl FileIndex {
         Ok(symbols)
     }
 
+    /// Fuzzy symbol search: every character of `query` must appear in the
+    /// symbol name in order (case-insensitive). Results are ranked by
+    /// [`fuzzy_score`], then by name length, name, file and line.
+    pub async fn fuzzy_find_symbols(
+        &self,
+        query: &str,
+        kind: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SymbolMatch>, libsql::Error> {
+        let mut rows = if let Some(k) = kind {
+            self.conn
+                .query(
+                    "SELECT name, kind, file, start_line, end_line, parent FROM symbols
+                     WHERE kind = ?1",
+                    params![k],
+                )
+                .await?
+        } else {
+            self.conn
+                .query(
+                    "SELECT name, kind, file, start_line, end_line, parent FROM symbols",
+                    (),
+                )
+                .await?
+        };
+
+        let mut scored = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let name: String = row.get(0)?;
+            let Some(score) = fuzzy_score(query, &name) else {
+                continue;
+            };
+            scored.push((
+                score,
+                SymbolMatch {
+                    name,
+                    kind: row.get(1)?,
+                    file: row.get(2)?,
+                    start_line: u64::try_from(row.get::<i64>(3)?).unwrap_or(0) as usize,
+                    end_line: u64::try_from(row.get::<i64>(4)?).unwrap_or(0) as usize,
+                    parent: row.get(5)?,
+                },
+            ));
+        }
+        scored.sort_by(|(sa, a), (sb, b)| {
+            sb.cmp(sa)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+        Ok(scored.into_iter().take(limit).map(|(_, m)| m).collect())
+    }
+
     /// Get call graph stats
     pub async fn call_graph_stats(&self) -> Result<CallGraphStats, libsql::Error> {
         let symbols = {
@@ -1870,6 +2055,52 @@ impl FileIndex {
         Ok(edges)
     }
 
+    /// Load distinct cross-file call edges from the calls table.
+    /// Returns `Vec<(caller_file, callee_file)>` for calls whose
+    /// `callee_resolved_file` is set and differs from the caller's file.
+    pub async fn all_resolved_call_edges(&self) -> Result<Vec<(String, String)>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT DISTINCT caller_file, callee_resolved_file FROM calls
+                 WHERE callee_resolved_file IS NOT NULL AND callee_resolved_file != caller_file",
+                (),
+            )
+            .await?;
+        let mut edges = Vec::new();
+        while let Some(row) = rows.next().await? {
+            edges.push((row.get(0)?, row.get(1)?));
+        }
+        Ok(edges)
+    }
+
+    /// Load every call whose target file has been resolved.
+    /// Returns `Vec<(caller_file, caller_symbol, callee_resolved_file, callee_name, line)>`,
+    /// including same-file calls. Feeds the `resolved_call` fact relation.
+    pub async fn all_resolved_calls(
+        &self,
+    ) -> Result<Vec<(String, String, String, String, u32)>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT caller_file, caller_symbol, callee_resolved_file, callee_name, line FROM calls
+                 WHERE callee_resolved_file IS NOT NULL",
+                (),
+            )
+            .await?;
+        let mut calls = Vec::new();
+        while let Some(row) = rows.next().await? {
+            calls.push((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                u32::try_from(row.get::<i64>(4)?).unwrap_or(0),
+            ));
+        }
+        Ok(calls)
+    }
+
     /// Load all resolved import edges with line numbers.
     /// Returns `Vec<(importer_file, line, resolved_file)>` for rows where
     /// `resolved_file IS NOT NULL`. Used by the boundary-violations native rule
@@ -2189,6 +2420,34 @@ impl FileIndex {
         Ok(symbols)
     }
 
+    /// Load complexity metrics for every symbol that has them (functions and
+    /// methods). Returns Vec<(file, name, cyclomatic, nesting_depth)>; depth is 0
+    /// for rows indexed before it was recorded.
+    pub async fn all_symbol_complexity(
+        &self,
+    ) -> Result<Vec<(String, String, usize, usize)>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT file, name, complexity, nesting_depth FROM symbols WHERE complexity IS NOT NULL",
+                (),
+            )
+            .await?;
+        let mut metrics = Vec::new();
+        while let Some(row) = rows.next().await? {
+            metrics.push((
+                row.get(0)?,
+                row.get(1)?,
+                u64::try_from(row.get::<i64>(2)?).unwrap_or(0) as usize,
+                row.get::<i64>(3)
+                    .ok()
+                    .and_then(|d| u64::try_from(d).ok())
+                    .unwrap_or(0) as usize,
+            ));
+        }
+        Ok(metrics)
+    }
+
     /// Load all symbol attributes from the symbol_attributes table.
     /// Returns Vec<(file, name, attribute)>.
     pub async fn all_symbol_attributes(
@@ -2722,6 +2981,55 @@ impl FileIndex {
         Ok(importers)
     }
 
+    /// Find imports that resolve to `file` (root-relative path).
+    /// Returns Vec<(importer_file, module, name, line)>.
+    /// Used by `move-file` to rewrite importers.
+    pub async fn find_file_importers(
+        &self,
+        file: &str,
+    ) -> Result<Vec<(String, Option<String>, String, usize)>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT file, module, name, line FROM imports WHERE resolved_file = ?1",
+                params![file],
+            )
+            .await?;
+        let mut importers = Vec::new();
+        while let Some(row) = rows.next().await? {
+            importers.push((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                u64::try_from(row.get::<i64>(3)?).unwrap_or(0) as usize,
+            ));
+        }
+        Ok(importers)
+    }
+
+    /// List the import statements of `file` (root-relative path).
+    /// Returns Vec<(module, line)>, one row per distinct statement.
+    pub async fn imports_of_file(
+        &self,
+        file: &str,
+    ) -> Result<Vec<(Option<String>, usize)>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT DISTINCT module, line FROM imports WHERE file = ?1",
+                params![file],
+            )
+            .await?;
+        let mut imports = Vec::new();
+        while let Some(row) = rows.next().await? {
+            imports.push((
+                row.get(0)?,
+                u64::try_from(row.get::<i64>(1)?).unwrap_or(0) as usize,
+            ));
+        }
+        Ok(imports)
+    }
+
     /// Check whether a file already has an import named `name` (as `name` or `alias`).
     /// Used for rename conflict detection.
     pub async fn has_import_named(&self, file: &str, name: &str) -> Result<bool, libsql::Error> {
@@ -2854,259 +3162,25 @@ impl FileIndex {
             files
         };
 
-        // Parse all files in parallel
-        // Each thread gets its own SymbolParser (tree-sitter parsers have mutable state)
+        // Extract facts from all files in parallel. Workers share the CA cache
+        // and reuse their thread's tree-sitter parsers (see
+        // `normalize_languages::parsers::with_parser`); SQLite insertion below
+        // stays sequential.
         let root = self.root.clone();
+        let ca_cache = self.ca_cache.as_ref();
 
-        // Pre-pass: check CA cache for all files (serial, fast disk reads)
-        let mut cached_data: Vec<ParsedFileData> = Vec::new();
-        let mut uncached_files: Vec<String> = Vec::new();
-        // Files whose symbol data came from CA cache: need CFG rebuilt separately.
-        let mut ca_cached_files: Vec<String> = Vec::new();
-
-        for file_path in &files {
-            let full_path = root.join(file_path);
-            let bytes = match std::fs::read(&full_path) {
-                Ok(b) => b,
-                Err(_) => {
-                    uncached_files.push(file_path.clone());
-                    continue;
-                }
-            };
-            let grammar = match support_for_path(&full_path) {
-                Some(s) => s.grammar_name().to_string(),
-                None => {
-                    uncached_files.push(file_path.clone());
-                    continue;
-                }
-            };
-            let hash = blake3::hash(&bytes);
-            if let Some(ca) = &self.ca_cache {
-                match ca.get::<CachedFileData>(hash.as_bytes(), EXTRACTOR_VERSION, &grammar) {
-                    Ok(Some(cached)) => {
-                        ca_cached_files.push(file_path.clone());
-                        cached_data.push(ParsedFileData {
-                            file_path: file_path.clone(),
-                            symbols: cached.symbols,
-                            calls: cached.calls,
-                            imports: cached.imports,
-                            type_methods: cached.type_methods,
-                            type_refs: cached.type_refs,
-                            // CFG data is not CA-cached — always rebuilt during parse.
-                            cfg: CfgData {
-                                blocks: Vec::new(),
-                                edges: Vec::new(),
-                                defs: Vec::new(),
-                                uses: Vec::new(),
-                                effects: Vec::new(),
-                            },
-                        });
-                        continue;
-                    }
-                    Ok(None) => {}
-                    Err(e) => {
-                        tracing::warn!("normalize-facts: CA cache get error: {}", e);
-                    }
-                }
-            }
-            uncached_files.push(file_path.clone());
-        }
-
-        let ca_cache_for_rayon = self.ca_cache.clone();
-
-        let pb = if self.progress && std::io::IsTerminal::is_terminal(&std::io::stderr()) {
-            let pb = ProgressBar::new(uncached_files.len() as u64);
-            pb.set_style(
-                ProgressStyle::with_template(
-                    "{spinner:.cyan} Parsing symbols... [{bar:30.cyan/dim}] {pos}/{len} files [{elapsed_precise}]",
-                )
-                .unwrap_or_else(|_| ProgressStyle::default_bar())
-                .progress_chars("##-"),
-            );
-            pb
-        } else {
-            ProgressBar::hidden()
-        };
-        let mut parsed_data: Vec<ParsedFileData> = uncached_files
+        let pb = self.progress_reporter(ProgressKind::Bar, "Parsing symbols...");
+        pb.set_total(Some(files.len() as u64));
+        let parsed_data: Vec<ParsedFileData> = files
             .par_iter()
-            .progress_with(pb.clone())
-            .filter_map(|file_path| {
-                let full_path = root.join(file_path);
-                let bytes = std::fs::read(&full_path).ok()?;
-                let content = String::from_utf8_lossy(&bytes).into_owned();
-
-                let grammar = support_for_path(&full_path)
-                    .map(|s| s.grammar_name().to_string())
-                    .unwrap_or_default();
-                let hash = blake3::hash(&bytes);
-
-                // Each thread creates its own parser
-                let mut parser = SymbolParser::new();
-
-                // parse_file returns None when the grammar .so is unavailable.
-                // In that case, skip the file entirely — don't index it as empty.
-                // The missing grammar is already recorded in `parsers::report_missing_grammar`
-                // (called from `parse_file` -> `try_get_grammar`), so callers can summarise.
-                let symbols = parser.parse_file(&full_path, &content)?;
-
-                let mut sym_data = Vec::with_capacity(symbols.len());
-                let mut call_data = Vec::new();
-
-                for sym in &symbols {
-                    sym_data.push(ParsedSymbol {
-                        name: sym.name.clone(),
-                        kind: sym.kind.as_str().to_string(),
-                        start_line: sym.start_line,
-                        end_line: sym.end_line,
-                        parent: sym.parent.clone(),
-                        visibility: sym.visibility.as_str().to_string(),
-                        attributes: sym.attributes.clone(),
-                        is_interface_impl: sym.is_interface_impl,
-                        implements: sym.implements.clone(),
-                        docstring: sym.docstring.clone(),
-                        complexity: sym.complexity,
-                    });
-
-                    // Only index calls for functions/methods
-                    let kind = sym.kind.as_str();
-                    if kind == "function" || kind == "method" {
-                        let calls = parser.find_callees_for_symbol(&full_path, &content, sym);
-                        for (callee_name, line, qualifier, access) in calls {
-                            call_data.push((
-                                sym.name.clone(),
-                                callee_name,
-                                qualifier,
-                                access,
-                                line,
-                            ));
-                        }
-                    }
-                }
-
-                // Parse imports using trait-based extraction (works for all supported languages)
-                let imports = parser.parse_imports(&full_path, &content);
-
-                // Extract type methods for cross-file interface resolution
-                // We need to use the full symbol extraction to get hierarchy
-                let extractor = crate::extract::Extractor::new();
-                let extract_result = extractor.extract(&full_path, &content);
-                let mut type_methods = Vec::new();
-                for sym in &extract_result.symbols {
-                    if matches!(
-                        sym.kind,
-                        normalize_languages::SymbolKind::Interface
-                            | normalize_languages::SymbolKind::Class
-                            | normalize_languages::SymbolKind::Trait
-                            | normalize_languages::SymbolKind::Struct
-                    ) {
-                        for child in &sym.children {
-                            if matches!(
-                                child.kind,
-                                normalize_languages::SymbolKind::Method
-                                    | normalize_languages::SymbolKind::Function
-                            ) {
-                                type_methods.push((sym.name.clone(), child.name.clone()));
-                            }
-                        }
-                    }
-                }
-
-                // Extract type references using tree-sitter queries
-                let type_refs = parser.find_type_refs(&full_path, &content);
-
-                // Build CFGs for function/method symbols (best-effort — errors are non-fatal).
-                let cfg = build_cfg_data_for_file(&full_path, &bytes, grammar.as_str(), &symbols);
-
-                // Store result in CA cache (best-effort).
-                // Grammar availability is already guaranteed above (parse_file returned Some),
-                // so empty results here are legitimate and safe to cache.
-                if !grammar.is_empty()
-                    && let Some(ca) = &ca_cache_for_rayon
-                {
-                    let cached = CachedFileData {
-                        symbols: sym_data
-                            .iter()
-                            .map(|s| ParsedSymbol {
-                                name: s.name.clone(),
-                                kind: s.kind.clone(),
-                                start_line: s.start_line,
-                                end_line: s.end_line,
-                                parent: s.parent.clone(),
-                                visibility: s.visibility.clone(),
-                                attributes: s.attributes.clone(),
-                                is_interface_impl: s.is_interface_impl,
-                                implements: s.implements.clone(),
-                                docstring: s.docstring.clone(),
-                                complexity: s.complexity,
-                            })
-                            .collect(),
-                        calls: call_data.clone(),
-                        imports: imports.clone(),
-                        type_methods: type_methods.clone(),
-                        type_refs: type_refs.clone(),
-                    };
-                    if let Err(e) = ca.put(hash.as_bytes(), EXTRACTOR_VERSION, &grammar, &cached) {
-                        tracing::warn!("normalize-facts: CA cache put error: {}", e);
-                    }
-                }
-
-                Some(ParsedFileData {
-                    file_path: file_path.clone(),
-                    symbols: sym_data,
-                    calls: call_data,
-                    imports,
-                    type_methods,
-                    type_refs,
-                    cfg,
-                })
-            })
+            .inspect(|_| pb.inc(1))
+            .filter_map(|file_path| extract_file_data(&root, file_path, ca_cache))
             .collect();
 
-        // Merge CA-cached results
-        parsed_data.extend(cached_data);
-
-        // Build CFG data for files that came from the CA cache (their cfg vecs are empty).
-        if !ca_cached_files.is_empty() {
-            // For each cached file, rebuild CFG data using a fresh parser (re-reads the file).
-            let cfg_updates: Vec<(String, CfgData)> = ca_cached_files
-                .par_iter()
-                .filter_map(|file_path| {
-                    let full_path = root.join(file_path);
-                    let bytes = std::fs::read(&full_path).ok()?;
-                    let lang_support = support_for_path(&full_path)?;
-                    let grammar_name = lang_support.grammar_name();
-                    let symbols: Vec<FlatSymbol> = {
-                        let p = SymbolParser::new();
-                        let content = String::from_utf8_lossy(&bytes).into_owned();
-                        p.parse_file(&full_path, &content)?
-                    };
-                    let cfg = build_cfg_data_for_file(&full_path, &bytes, grammar_name, &symbols);
-                    Some((file_path.clone(), cfg))
-                })
-                .collect();
-            // Patch cfg into parsed_data.
-            for (fpath, cfg) in cfg_updates {
-                if let Some(data) = parsed_data.iter_mut().find(|d| d.file_path == fpath) {
-                    data.cfg = cfg;
-                }
-            }
-        }
-
-        pb.finish_and_clear();
+        pb.finish();
 
-        let pb_insert = if self.progress && std::io::IsTerminal::is_terminal(&std::io::stderr()) {
-            let pb = ProgressBar::new(parsed_data.len() as u64);
-            pb.set_style(
-                ProgressStyle::with_template(
-                    "{spinner:.cyan} Storing index... [{bar:30.cyan/dim}] {pos}/{len} files [{elapsed_precise}]",
-                )
-                .unwrap_or_else(|_| ProgressStyle::default_bar())
-                .progress_chars("##-"),
-            );
-            pb
-        } else {
-            ProgressBar::hidden()
-        };
+        let pb_insert = self.progress_reporter(ProgressKind::Bar, "Storing index...");
+        pb_insert.set_total(Some(parsed_data.len() as u64));
 
         self.begin_clean().await?;
 
@@ -3128,157 +3202,30 @@ impl FileIndex {
         self.conn.execute("DELETE FROM cfg_defs", ()).await?;
         self.conn.execute("DELETE FROM cfg_uses", ()).await?;
         self.conn.execute("DELETE FROM cfg_effects", ()).await?;
+        self.conn.execute("DELETE FROM file_info", ()).await?;
 
         let mut symbol_count = 0;
         let mut call_count = 0;
         let mut import_count = 0;
 
         for data in &parsed_data {
-            for sym in &data.symbols {
-                self.conn.execute(
-                    "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                    params![data.file_path.clone(), sym.name.clone(), sym.kind.clone(), sym.start_line as i64, sym.end_line as i64, sym.parent.clone(), sym.visibility.clone(), sym.is_interface_impl as i64, sym.complexity.map(|c| c as i64)],
-                ).await?;
-                for attr in &sym.attributes {
-                    self.conn.execute(
-                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
-                        params![data.file_path.clone(), sym.name.clone(), attr.clone()],
-                    ).await?;
-                }
-                if let Some(doc) = &sym.docstring {
-                    self.conn.execute(
-                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
-                        params![data.file_path.clone(), sym.name.clone(), format!("doc:{doc}")],
-                    ).await?;
-                }
-                for iface in &sym.implements {
-                    self.conn.execute(
-                        "INSERT INTO symbol_implements (file, name, interface) VALUES (?1, ?2, ?3)",
-                        params![data.file_path.clone(), sym.name.clone(), iface.clone()],
-                    ).await?;
-                }
-                for word in split_identifier_words(&sym.name) {
-                    self.conn.execute(
-                        "INSERT INTO symbol_words (file, symbol_name, word) VALUES (?1, ?2, ?3)",
-                        params![data.file_path.clone(), sym.name.clone(), word],
-                    ).await?;
-                }
-                symbol_count += 1;
-            }
+            let stats = self.insert_file_data(data).await?;
+            symbol_count += stats.symbols;
+            call_count += stats.calls;
+            import_count += stats.imports;
 
-            for (caller_symbol, callee_name, qualifier, access, line) in &data.calls {
-                self.conn.execute(
-                    "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, access, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![data.file_path.clone(), caller_symbol.clone(), callee_name.clone(), qualifier.clone(), access.clone(), *line as i64],
-                ).await?;
-                call_count += 1;
-            }
+            pb_insert.inc(1);
+        }
 
-            for imp in &data.imports {
-                self.conn.execute(
-                    "INSERT INTO imports (file, module, name, alias, line, is_reexport) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![data.file_path.clone(), imp.module.clone(), imp.name.clone(), imp.alias.clone(), imp.line as i64, imp.is_reexport as i64],
-                ).await?;
-                import_count += 1;
-            }
+        pb_insert.finish();
 
-            for (type_name, method_name) in &data.type_methods {
-                self.conn.execute(
-                    "INSERT OR IGNORE INTO type_methods (file, type_name, method_name) VALUES (?1, ?2, ?3)",
-                    params![data.file_path.clone(), type_name.clone(), method_name.clone()],
-                ).await?;
-            }
-
-            for tr in &data.type_refs {
-                self.conn.execute(
-                    "INSERT INTO type_refs (file, source_symbol, target_type, kind, line) VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![data.file_path.clone(), tr.source_symbol.clone(), tr.target_type.clone(), tr.kind.as_str(), tr.line as i64],
-                ).await?;
-            }
-
-            // Insert CFG blocks
-            for blk in &data.cfg.blocks {
-                self.conn.execute(
-                    "INSERT OR IGNORE INTO cfg_blocks (file, function_qname, function_start_line, block_id, kind, byte_start, byte_end, start_line, end_line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                    params![
-                        data.file_path.clone(),
-                        blk.function_qname.clone(),
-                        blk.function_start_line as i64,
-                        blk.block_id as i64,
-                        blk.kind.clone(),
-                        blk.byte_start as i64,
-                        blk.byte_end as i64,
-                        blk.start_line as i64,
-                        blk.end_line as i64,
-                    ],
-                ).await?;
-            }
-            // Insert CFG edges
-            for edge in &data.cfg.edges {
-                self.conn.execute(
-                    "INSERT INTO cfg_edges (file, function_qname, function_start_line, from_block, to_block, kind, exception_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                    params![
-                        data.file_path.clone(),
-                        edge.function_qname.clone(),
-                        edge.function_start_line as i64,
-                        edge.from_block as i64,
-                        edge.to_block as i64,
-                        edge.kind.clone(),
-                        edge.exception_type.clone(),
-                    ],
-                ).await?;
-            }
-            // Insert CFG defs
-            for def in &data.cfg.defs {
-                self.conn.execute(
-                    "INSERT INTO cfg_defs (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                    params![
-                        data.file_path.clone(),
-                        def.function_qname.clone(),
-                        def.function_start_line as i64,
-                        def.block_id as i64,
-                        def.name.clone(),
-                        def.byte_offset as i64,
-                        def.line as i64,
-                    ],
-                ).await?;
-            }
-            // Insert CFG uses
-            for use_ in &data.cfg.uses {
-                self.conn.execute(
-                    "INSERT INTO cfg_uses (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                    params![
-                        data.file_path.clone(),
-                        use_.function_qname.clone(),
-                        use_.function_start_line as i64,
-                        use_.block_id as i64,
-                        use_.name.clone(),
-                        use_.byte_offset as i64,
-                        use_.line as i64,
-                    ],
-                ).await?;
-            }
-            // Insert CFG effects
-            for eff in &data.cfg.effects {
-                self.conn.execute(
-                    "INSERT INTO cfg_effects (file, function_qname, function_start_line, block_id, kind, byte_offset, line, label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                    params![
-                        data.file_path.clone(),
-                        eff.function_qname.clone(),
-                        eff.function_start_line as i64,
-                        eff.block_id as i64,
-                        eff.kind.clone(),
-                        eff.byte_offset as i64,
-                        eff.line as i64,
-                        eff.label.clone(),
-                    ],
-                ).await?;
-            }
-
-            pb_insert.inc(1);
-        }
-
-        pb_insert.finish_and_clear();
+        self.conn.execute("DELETE FROM facts_files", ()).await?;
+        self.conn
+            .execute(
+                "INSERT INTO facts_files (path, mtime) SELECT path, mtime FROM files WHERE is_dir = 0",
+                (),
+            )
+            .await?;
 
         self.conn.execute("COMMIT", ()).await?;
 
@@ -3316,6 +3263,181 @@ impl FileIndex {
         })
     }
 
+    /// Insert one file's extracted facts. Expects to be called inside a
+    /// transaction, after any previous rows for the file have been deleted.
+    async fn insert_file_data(
+        &self,
+        data: &ParsedFileData,
+    ) -> Result<CallGraphStats, libsql::Error> {
+        let mut stats = CallGraphStats::default();
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO file_info (path, language, lines, blank_lines, comment_lines) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    data.file_path.clone(),
+                    data.info.language.clone(),
+                    data.info.lines as i64,
+                    data.info.blank_lines as i64,
+                    data.info.comment_lines as i64,
+                ],
+            )
+            .await?;
+        for sym in &data.symbols {
+            self.conn.execute(
+                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl, complexity, nesting_depth) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![data.file_path.clone(), sym.name.clone(), sym.kind.clone(), sym.start_line as i64, sym.end_line as i64, sym.parent.clone(), sym.visibility.clone(), sym.is_interface_impl as i64, sym.complexity.map(|c| c as i64), sym.nesting_depth.map(|d| d as i64)],
+            ).await?;
+            for attr in &sym.attributes {
+                self.conn
+                    .execute(
+                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
+                        params![data.file_path.clone(), sym.name.clone(), attr.clone()],
+                    )
+                    .await?;
+            }
+            if let Some(doc) = &sym.docstring {
+                self.conn
+                    .execute(
+                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
+                        params![
+                            data.file_path.clone(),
+                            sym.name.clone(),
+                            format!("doc:{doc}")
+                        ],
+                    )
+                    .await?;
+            }
+            for iface in &sym.implements {
+                self.conn
+                    .execute(
+                        "INSERT INTO symbol_implements (file, name, interface) VALUES (?1, ?2, ?3)",
+                        params![data.file_path.clone(), sym.name.clone(), iface.clone()],
+                    )
+                    .await?;
+            }
+            for word in split_identifier_words(&sym.name) {
+                self.conn
+                    .execute(
+                        "INSERT INTO symbol_words (file, symbol_name, word) VALUES (?1, ?2, ?3)",
+                        params![data.file_path.clone(), sym.name.clone(), word],
+                    )
+                    .await?;
+            }
+            stats.symbols += 1;
+        }
+
+        for (caller_symbol, callee_name, qualifier, access, line) in &data.calls {
+            self.conn.execute(
+                "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, access, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![data.file_path.clone(), caller_symbol.clone(), callee_name.clone(), qualifier.clone(), access.clone(), *line as i64],
+            ).await?;
+            stats.calls += 1;
+        }
+
+        for imp in &data.imports {
+            self.conn.execute(
+                "INSERT INTO imports (file, module, name, alias, line, is_reexport) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![data.file_path.clone(), imp.module.clone(), imp.name.clone(), imp.alias.clone(), imp.line as i64, imp.is_reexport as i64],
+            ).await?;
+            stats.imports += 1;
+        }
+
+        for (type_name, method_name) in &data.type_methods {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO type_methods (file, type_name, method_name) VALUES (?1, ?2, ?3)",
+                params![data.file_path.clone(), type_name.clone(), method_name.clone()],
+            ).await?;
+        }
+
+        for tr in &data.type_refs {
+            self.conn.execute(
+                "INSERT INTO type_refs (file, source_symbol, target_type, kind, line) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![data.file_path.clone(), tr.source_symbol.clone(), tr.target_type.clone(), tr.kind.as_str(), tr.line as i64],
+            ).await?;
+        }
+
+        // Insert CFG blocks
+        for blk in &data.cfg.blocks {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO cfg_blocks (file, function_qname, function_start_line, block_id, kind, byte_start, byte_end, start_line, end_line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    data.file_path.clone(),
+                    blk.function_qname.clone(),
+                    blk.function_start_line as i64,
+                    blk.block_id as i64,
+                    blk.kind.clone(),
+                    blk.byte_start as i64,
+                    blk.byte_end as i64,
+                    blk.start_line as i64,
+                    blk.end_line as i64,
+                ],
+            ).await?;
+        }
+        // Insert CFG edges
+        for edge in &data.cfg.edges {
+            self.conn.execute(
+                "INSERT INTO cfg_edges (file, function_qname, function_start_line, from_block, to_block, kind, exception_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    data.file_path.clone(),
+                    edge.function_qname.clone(),
+                    edge.function_start_line as i64,
+                    edge.from_block as i64,
+                    edge.to_block as i64,
+                    edge.kind.clone(),
+                    edge.exception_type.clone(),
+                ],
+            ).await?;
+        }
+        // Insert CFG defs
+        for def in &data.cfg.defs {
+            self.conn.execute(
+                "INSERT INTO cfg_defs (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    data.file_path.clone(),
+                    def.function_qname.clone(),
+                    def.function_start_line as i64,
+                    def.block_id as i64,
+                    def.name.clone(),
+                    def.byte_offset as i64,
+                    def.line as i64,
+                ],
+            ).await?;
+        }
+        // Insert CFG uses
+        for use_ in &data.cfg.uses {
+            self.conn.execute(
+                "INSERT INTO cfg_uses (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    data.file_path.clone(),
+                    use_.function_qname.clone(),
+                    use_.function_start_line as i64,
+                    use_.block_id as i64,
+                    use_.name.clone(),
+                    use_.byte_offset as i64,
+                    use_.line as i64,
+                ],
+            ).await?;
+        }
+        // Insert CFG effects
+        for eff in &data.cfg.effects {
+            self.conn.execute(
+                "INSERT INTO cfg_effects (file, function_qname, function_start_line, block_id, kind, byte_offset, line, label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    data.file_path.clone(),
+                    eff.function_qname.clone(),
+                    eff.function_start_line as i64,
+                    eff.block_id as i64,
+                    eff.kind.clone(),
+                    eff.byte_offset as i64,
+                    eff.line as i64,
+                    eff.label.clone(),
+                ],
+            ).await?;
+        }
+
+        Ok(stats)
+    }
+
     /// Reindex specific files: delete old data and re-extract symbols/calls/imports.
     /// Expects to be called inside a transaction.
     async fn reindex_files(
@@ -3355,6 +3477,12 @@ impl FileIndex {
                     params![path.clone()],
                 )
                 .await?;
+            self.conn
+                .execute(
+                    "DELETE FROM type_methods WHERE file = ?1",
+                    params![path.clone()],
+                )
+                .await?;
             self.conn
                 .execute(
                     "DELETE FROM type_refs WHERE file = ?1",
@@ -3391,278 +3519,29 @@ impl FileIndex {
                     params![path.clone()],
                 )
                 .await?;
+            self.conn
+                .execute(
+                    "DELETE FROM file_info WHERE path = ?1",
+                    params![path.clone()],
+                )
+                .await?;
         }
 
-        let mut parser = SymbolParser::new();
+        // Extract in parallel, insert sequentially.
+        let ca_cache = self.ca_cache.as_ref();
+        let parsed_data: Vec<ParsedFileData> = changed_files
+            .par_iter()
+            .filter_map(|file_path| extract_file_data(&self.root, file_path, ca_cache))
+            .collect();
+
         let mut symbol_count = 0;
         let mut call_count = 0;
         let mut import_count = 0;
-
-        // Parse changed files
-        for file_path in changed_files {
-            let full_path = self.root.join(file_path);
-            let bytes = match std::fs::read(&full_path) {
-                Ok(b) => b,
-                Err(_) => continue,
-            };
-
-            let grammar = support_for_path(&full_path)
-                .map(|s| s.grammar_name().to_string())
-                .unwrap_or_default();
-            let hash = blake3::hash(&bytes);
-
-            // Try CA cache first (best-effort)
-            let cached: Option<CachedFileData> = if !grammar.is_empty() {
-                self.ca_cache.as_ref().and_then(|ca| {
-                    ca.get::<CachedFileData>(hash.as_bytes(), EXTRACTOR_VERSION, &grammar)
-                        .unwrap_or_else(|e| {
-                            tracing::warn!("normalize-facts: CA cache get error: {}", e);
-                            None
-                        })
-                })
-            } else {
-                None
-            };
-
-            let (sym_data, call_data, imports, type_refs) = if let Some(c) = cached {
-                (c.symbols, c.calls, c.imports, c.type_refs)
-            } else {
-                let content = String::from_utf8_lossy(&bytes).into_owned();
-
-                // parse_file returns None when the grammar .so is unavailable.
-                // Skip the file entirely — don't index it as empty.
-                // The missing grammar is already recorded in `parsers::report_missing_grammar`
-                // (called from `parse_file` -> `try_get_grammar`), so callers can summarise.
-                let symbols = match parser.parse_file(&full_path, &content) {
-                    Some(s) => s,
-                    None => continue,
-                };
-
-                let mut sym_data = Vec::with_capacity(symbols.len());
-                let mut call_data_local: Vec<CallEntry> = Vec::new();
-
-                for sym in &symbols {
-                    sym_data.push(ParsedSymbol {
-                        name: sym.name.clone(),
-                        kind: sym.kind.as_str().to_string(),
-                        start_line: sym.start_line,
-                        end_line: sym.end_line,
-                        parent: sym.parent.clone(),
-                        visibility: sym.visibility.as_str().to_string(),
-                        attributes: sym.attributes.clone(),
-                        is_interface_impl: sym.is_interface_impl,
-                        implements: sym.implements.clone(),
-                        docstring: sym.docstring.clone(),
-                        complexity: sym.complexity,
-                    });
-                    let kind = sym.kind.as_str();
-                    if kind == "function" || kind == "method" {
-                        let calls = parser.find_callees_for_symbol(&full_path, &content, sym);
-                        for (callee_name, line, qualifier, access) in calls {
-                            call_data_local.push((
-                                sym.name.clone(),
-                                callee_name,
-                                qualifier,
-                                access,
-                                line,
-                            ));
-                        }
-                    }
-                }
-
-                let imports = parser.parse_imports(&full_path, &content);
-                let type_refs = parser.find_type_refs(&full_path, &content);
-
-                // Store in CA cache (best-effort).
-                // Grammar availability is already guaranteed above (parse_file returned Some),
-                // so empty results here are legitimate and safe to cache.
-                if !grammar.is_empty()
-                    && let Some(ca) = &self.ca_cache
-                {
-                    let cached_store = CachedFileData {
-                        symbols: sym_data
-                            .iter()
-                            .map(|s| ParsedSymbol {
-                                name: s.name.clone(),
-                                kind: s.kind.clone(),
-                                start_line: s.start_line,
-                                end_line: s.end_line,
-                                parent: s.parent.clone(),
-                                visibility: s.visibility.clone(),
-                                attributes: s.attributes.clone(),
-                                is_interface_impl: s.is_interface_impl,
-                                implements: s.implements.clone(),
-                                docstring: s.docstring.clone(),
-                                complexity: s.complexity,
-                            })
-                            .collect(),
-                        calls: call_data_local.clone(),
-                        imports: imports.clone(),
-                        type_methods: Vec::new(), // type_methods not extracted in incremental path
-                        type_refs: type_refs.clone(),
-                    };
-                    if let Err(e) =
-                        ca.put(hash.as_bytes(), EXTRACTOR_VERSION, &grammar, &cached_store)
-                    {
-                        tracing::warn!("normalize-facts: CA cache put error: {}", e);
-                    }
-                }
-
-                (sym_data, call_data_local, imports, type_refs)
-            };
-
-            // Insert symbols
-            for sym in &sym_data {
-                self.conn.execute(
-                    "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, visibility, is_impl, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                    params![file_path.clone(), sym.name.clone(), sym.kind.clone(), sym.start_line as i64, sym.end_line as i64, sym.parent.clone(), sym.visibility.clone(), sym.is_interface_impl as i64, sym.complexity.map(|c| c as i64)],
-                ).await?;
-                for attr in &sym.attributes {
-                    self.conn.execute(
-                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
-                        params![file_path.clone(), sym.name.clone(), attr.clone()],
-                    ).await?;
-                }
-                if let Some(doc) = &sym.docstring {
-                    self.conn.execute(
-                        "INSERT INTO symbol_attributes (file, name, attribute) VALUES (?1, ?2, ?3)",
-                        params![file_path.clone(), sym.name.clone(), format!("doc:{doc}")],
-                    ).await?;
-                }
-                for iface in &sym.implements {
-                    self.conn.execute(
-                        "INSERT INTO symbol_implements (file, name, interface) VALUES (?1, ?2, ?3)",
-                        params![file_path.clone(), sym.name.clone(), iface.clone()],
-                    ).await?;
-                }
-                for word in split_identifier_words(&sym.name) {
-                    self.conn.execute(
-                        "INSERT INTO symbol_words (file, symbol_name, word) VALUES (?1, ?2, ?3)",
-                        params![file_path.clone(), sym.name.clone(), word],
-                    ).await?;
-                }
-                symbol_count += 1;
-            }
-
-            // Insert calls
-            for (caller_symbol, callee_name, qualifier, access, line) in &call_data {
-                self.conn.execute(
-                    "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, access, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![file_path.clone(), caller_symbol.clone(), callee_name.clone(), qualifier.clone(), access.clone(), *line as i64],
-                ).await?;
-                call_count += 1;
-            }
-
-            // Insert imports
-            for imp in &imports {
-                self.conn.execute(
-                    "INSERT INTO imports (file, module, name, alias, line, is_reexport) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![file_path.clone(), imp.module.clone(), imp.name.clone(), imp.alias.clone(), imp.line as i64, imp.is_reexport as i64],
-                ).await?;
-                import_count += 1;
-            }
-
-            // Insert type references
-            for tr in &type_refs {
-                self.conn.execute(
-                    "INSERT INTO type_refs (file, source_symbol, target_type, kind, line) VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![file_path.clone(), tr.source_symbol.clone(), tr.target_type.clone(), tr.kind.as_str(), tr.line as i64],
-                ).await?;
-            }
-
-            // Build and insert CFG data (best-effort).
-            let full_path_for_cfg = self.root.join(file_path);
-            let grammar_for_cfg = support_for_path(&full_path_for_cfg)
-                .map(|s| s.grammar_name().to_string())
-                .unwrap_or_default();
-            if !grammar_for_cfg.is_empty() {
-                // Parse FlatSymbol list to get function symbols (needed for CFG building).
-                let flat_symbols: Vec<FlatSymbol> = {
-                    let p = SymbolParser::new();
-                    let content = String::from_utf8_lossy(&bytes).into_owned();
-                    p.parse_file(&full_path_for_cfg, &content)
-                        .unwrap_or_default()
-                };
-                let cfg_data = build_cfg_data_for_file(
-                    &full_path_for_cfg,
-                    &bytes,
-                    &grammar_for_cfg,
-                    &flat_symbols,
-                );
-                for blk in &cfg_data.blocks {
-                    self.conn.execute(
-                        "INSERT OR IGNORE INTO cfg_blocks (file, function_qname, function_start_line, block_id, kind, byte_start, byte_end, start_line, end_line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                        params![
-                            file_path.clone(),
-                            blk.function_qname.clone(),
-                            blk.function_start_line as i64,
-                            blk.block_id as i64,
-                            blk.kind.clone(),
-                            blk.byte_start as i64,
-                            blk.byte_end as i64,
-                            blk.start_line as i64,
-                            blk.end_line as i64,
-                        ],
-                    ).await?;
-                }
-                for edge in &cfg_data.edges {
-                    self.conn.execute(
-                        "INSERT INTO cfg_edges (file, function_qname, function_start_line, from_block, to_block, kind, exception_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                        params![
-                            file_path.clone(),
-                            edge.function_qname.clone(),
-                            edge.function_start_line as i64,
-                            edge.from_block as i64,
-                            edge.to_block as i64,
-                            edge.kind.clone(),
-                            edge.exception_type.clone(),
-                        ],
-                    ).await?;
-                }
-                for def in &cfg_data.defs {
-                    self.conn.execute(
-                        "INSERT INTO cfg_defs (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                        params![
-                            file_path.clone(),
-                            def.function_qname.clone(),
-                            def.function_start_line as i64,
-                            def.block_id as i64,
-                            def.name.clone(),
-                            def.byte_offset as i64,
-                            def.line as i64,
-                        ],
-                    ).await?;
-                }
-                for use_ in &cfg_data.uses {
-                    self.conn.execute(
-                        "INSERT INTO cfg_uses (file, function_qname, function_start_line, block_id, name, byte_offset, line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                        params![
-                            file_path.clone(),
-                            use_.function_qname.clone(),
-                            use_.function_start_line as i64,
-                            use_.block_id as i64,
-                            use_.name.clone(),
-                            use_.byte_offset as i64,
-                            use_.line as i64,
-                        ],
-                    ).await?;
-                }
-                for eff in &cfg_data.effects {
-                    self.conn.execute(
-                        "INSERT INTO cfg_effects (file, function_qname, function_start_line, block_id, kind, byte_offset, line, label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                        params![
-                            file_path.clone(),
-                            eff.function_qname.clone(),
-                            eff.function_start_line as i64,
-                            eff.block_id as i64,
-                            eff.kind.clone(),
-                            eff.byte_offset as i64,
-                            eff.line as i64,
-                            eff.label.clone(),
-                        ],
-                    ).await?;
-                }
-            }
+        for data in &parsed_data {
+            let stats = self.insert_file_data(data).await?;
+            symbol_count += stats.symbols;
+            call_count += stats.calls;
+            import_count += stats.imports;
         }
 
         Ok(CallGraphStats {
@@ -3672,12 +3551,91 @@ impl FileIndex {
         })
     }
 
+    /// Files whose extracted facts are out of date relative to the `files`
+    /// table: never extracted (`added`), touched since extraction (`modified`),
+    /// or gone from `files` (`deleted`).
+    ///
+    /// Compares against `facts_files` rather than walking the filesystem, so it
+    /// sees changes that [`Self::incremental_refresh`] has already recorded in
+    /// `files`.
+    async fn stale_fact_files(&self) -> Result<ChangedFiles, libsql::Error> {
+        let mut result = ChangedFiles::default();
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT f.path, x.mtime IS NULL FROM files f
+                 LEFT JOIN facts_files x ON x.path = f.path
+                 WHERE f.is_dir = 0 AND (x.mtime IS NULL OR x.mtime < f.mtime)",
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            let path: String = row.get(0)?;
+            if row.get::<i64>(1)? != 0 {
+                result.added.push(path);
+            } else {
+                result.modified.push(path);
+            }
+        }
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT path FROM facts_files WHERE path NOT IN (SELECT path FROM files)",
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            result.deleted.push(row.get(0)?);
+        }
+        Ok(result)
+    }
+
+    /// Record that facts for `changed` are current as of their `files.mtime`,
+    /// and forget `deleted`. Call inside the reindexing transaction.
+    async fn record_fact_files(
+        &self,
+        deleted: &[String],
+        changed: &[String],
+    ) -> Result<(), libsql::Error> {
+        for path in deleted {
+            self.conn
+                .execute(
+                    "DELETE FROM facts_files WHERE path = ?1",
+                    params![path.clone()],
+                )
+                .await?;
+        }
+        for path in changed {
+            self.conn
+                .execute(
+                    "INSERT OR REPLACE INTO facts_files (path, mtime)
+                     SELECT path, mtime FROM files WHERE path = ?1",
+                    params![path.clone()],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Incrementally update call graph for changed files only.
     /// Much faster than full refresh when few files changed.
+    ///
+    /// Staleness is judged against the `files` table, so run
+    /// [`Self::incremental_refresh`] (or a full [`Self::refresh`]) first.
     pub async fn incremental_call_graph_refresh(
         &mut self,
     ) -> Result<CallGraphStats, libsql::Error> {
-        let changed = self.get_changed_files().await?;
+        let changed = self.stale_fact_files().await?;
+        if changed.added.is_empty() && changed.modified.is_empty() && changed.deleted.is_empty() {
+            return Ok(CallGraphStats::default());
+        }
+        let recorded: Vec<String> = changed
+            .added
+            .iter()
+            .chain(&changed.modified)
+            .cloned()
+            .collect();
+        let forgotten = changed.deleted.clone();
 
         // Only process supported source and data files
         let changed_files: Vec<String> = changed
@@ -3693,15 +3651,16 @@ impl FileIndex {
             .filter(|f| is_source_file(f))
             .collect();
 
-        if changed_files.is_empty() && deleted_source_files.is_empty() {
-            return Ok(CallGraphStats::default());
-        }
-
         self.begin_clean().await?;
-        let stats = match self
-            .reindex_files(&deleted_source_files, &changed_files)
-            .await
-        {
+        let reindexed = async {
+            let stats = self
+                .reindex_files(&deleted_source_files, &changed_files)
+                .await?;
+            self.record_fact_files(&forgotten, &recorded).await?;
+            Ok(stats)
+        }
+        .await;
+        let stats = match reindexed {
             Ok(stats) => {
                 self.conn.execute("COMMIT", ()).await?;
                 stats
@@ -3768,11 +3727,15 @@ impl FileIndex {
         }
 
         self.begin_clean().await?;
-        let reindex_result = if exists {
-            self.reindex_files(&[], &[rel_path.to_string()]).await
-        } else {
-            self.reindex_files(&[rel_path.to_string()], &[]).await
-        };
+        let path = [rel_path.to_string()];
+        let (deleted, changed): (&[String], &[String]) =
+            if exists { (&[], &path) } else { (&path, &[]) };
+        let reindex_result = async {
+            let stats = self.reindex_files(deleted, changed).await?;
+            self.record_fact_files(deleted, changed).await?;
+            Ok(stats)
+        }
+        .await;
         let stats = match reindex_result {
             Ok(stats) => {
                 self.conn.execute("COMMIT", ()).await?;
@@ -3956,11 +3919,13 @@ impl FileIndex {
         let walk_data = walk_commits_for_co_change(&repo, since_commit);
         let commit_files = walk_data.commit_files;
         let new_churn = walk_data.churn;
+        let touches = walk_data.touches;
 
         if commit_files.is_empty() && new_churn.is_empty() && since_commit.is_none() {
             // No history (or empty repo): ensure tables are cleared and metadata stored.
             self.conn.execute("DELETE FROM co_change_edges", ()).await?;
             self.conn.execute("DELETE FROM file_churn", ()).await?;
+            self.conn.execute("DELETE FROM file_commits", ()).await?;
             self.conn
                 .execute(
                     "INSERT OR REPLACE INTO meta (key, value) VALUES ('co_change_last_commit', ?1)",
@@ -4089,6 +4054,22 @@ impl FileIndex {
             ).await?;
         }
 
+        // Per-commit touches: a full walk replaces the table, an incremental
+        // walk only saw new commits and appends them.
+        self.conn.execute("BEGIN", ()).await?;
+        if since_commit.is_none() {
+            self.conn.execute("DELETE FROM file_commits", ()).await?;
+        }
+        for (file, commit_time, author) in &touches {
+            self.conn
+                .execute(
+                    "INSERT INTO file_commits (file, commit_time, author) VALUES (?1, ?2, ?3)",
+                    params![file.clone(), *commit_time as i64, author.clone()],
+                )
+                .await?;
+        }
+        self.conn.execute("COMMIT", ()).await?;
+
         // Record the HEAD SHA so the next incremental run knows where to resume.
         self.conn
             .execute(
@@ -4142,6 +4123,56 @@ impl FileIndex {
     }
 
     /// Return the stored HEAD SHA from the last co-change rebuild, if any.
+    /// Per-file git churn from the `file_commits` table, most-churned first.
+    ///
+    /// `recent_commits` counts commits at or after the unix timestamp `since`.
+    /// Empty until `rebuild_co_change_edges()` has walked history.
+    pub async fn file_churn_since(&self, since: u64) -> Result<Vec<FileChurn>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT file, COUNT(*), SUM(commit_time >= ?1), MAX(commit_time), COUNT(DISTINCT author)
+                 FROM file_commits GROUP BY file
+                 ORDER BY SUM(commit_time >= ?1) DESC, COUNT(*) DESC, file",
+                params![since as i64],
+            )
+            .await?;
+        let mut churn = Vec::new();
+        while let Some(row) = rows.next().await? {
+            churn.push(FileChurn {
+                file: row.get(0)?,
+                commits: u64::try_from(row.get::<i64>(1)?).unwrap_or(0) as usize,
+                recent_commits: u64::try_from(row.get::<i64>(2)?).unwrap_or(0) as usize,
+                last_changed: u64::try_from(row.get::<i64>(3)?).unwrap_or(0),
+                authors: u64::try_from(row.get::<i64>(4)?).unwrap_or(0) as usize,
+            });
+        }
+        Ok(churn)
+    }
+
+    /// Language and line counts for every source file with extracted facts.
+    /// Filled by `refresh_call_graph()` and its incremental variant.
+    pub async fn all_file_info(&self) -> Result<Vec<FileInfo>, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT path, language, lines, blank_lines, comment_lines FROM file_info ORDER BY path",
+                (),
+            )
+            .await?;
+        let mut info = Vec::new();
+        while let Some(row) = rows.next().await? {
+            info.push(FileInfo {
+                file: row.get(0)?,
+                language: row.get(1)?,
+                lines: u64::try_from(row.get::<i64>(2)?).unwrap_or(0) as usize,
+                blank_lines: u64::try_from(row.get::<i64>(3)?).unwrap_or(0) as usize,
+                comment_lines: u64::try_from(row.get::<i64>(4)?).unwrap_or(0) as usize,
+            });
+        }
+        Ok(info)
+    }
+
     pub async fn co_change_last_commit(&self) -> Option<String> {
         let mut rows = self
             .conn
@@ -4355,6 +4386,207 @@ impl FileIndex {
     }
 }
 
+// =============================================================================
+// Per-file fact extraction
+// =============================================================================
+
+/// Extract symbols, calls, imports, type methods, type refs and CFG data for one file.
+///
+/// Pure with respect to the index, so callers run it across files with rayon and
+/// insert the results sequentially. Symbol-level data comes from the CA cache when
+/// the content hash hits (CFG data is never cached and is always rebuilt); otherwise
+/// the file is parsed and the result stored back in the cache (best-effort).
+///
+/// Returns `None` when the file can't be read or its grammar is unavailable — such
+/// files are skipped rather than indexed as empty. The missing grammar is already
+/// recorded by `parsers::report_missing_grammar`, so callers can summarise.
+fn extract_file_data(
+    root: &Path,
+    file_path: &str,
+    ca_cache: Option<&crate::ca_cache::CaCache>,
+) -> Option<ParsedFileData> {
+    let full_path = root.join(file_path);
+    let bytes = std::fs::read(&full_path).ok()?;
+    let content = String::from_utf8_lossy(&bytes).into_owned();
+    let grammar = support_for_path(&full_path)
+        .map(|s| s.grammar_name().to_string())
+        .unwrap_or_default();
+    let hash = blake3::hash(&bytes);
+    let mut parser = SymbolParser::new();
+
+    if !grammar.is_empty()
+        && let Some(ca) = ca_cache
+    {
+        match ca.get::<CachedFileData>(hash.as_bytes(), EXTRACTOR_VERSION, &grammar) {
+            Ok(Some(cached)) => {
+                let symbols = parser.parse_file(&full_path, &content)?;
+                let cfg = build_cfg_data_for_file(&full_path, &bytes, &grammar, &symbols);
+                return Some(ParsedFileData {
+                    file_path: file_path.to_string(),
+                    symbols: cached.symbols,
+                    calls: cached.calls,
+                    imports: cached.imports,
+                    type_methods: cached.type_methods,
+                    type_refs: cached.type_refs,
+                    cfg,
+                    info: file_info_for(file_path, &grammar, &content),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("normalize-facts: CA cache get error: {}", e);
+            }
+        }
+    }
+
+    let symbols = parser.parse_file(&full_path, &content)?;
+
+    let mut sym_data = Vec::with_capacity(symbols.len());
+    let mut call_data = Vec::new();
+    for sym in &symbols {
+        sym_data.push(ParsedSymbol {
+            name: sym.name.clone(),
+            kind: sym.kind.as_str().to_string(),
+            start_line: sym.start_line,
+            end_line: sym.end_line,
+            parent: sym.parent.clone(),
+            visibility: sym.visibility.as_str().to_string(),
+            attributes: sym.attributes.clone(),
+            is_interface_impl: sym.is_interface_impl,
+            implements: sym.implements.clone(),
+            docstring: sym.docstring.clone(),
+            complexity: sym.complexity,
+            nesting_depth: sym.nesting_depth,
+        });
+
+        // Only index calls for functions/methods
+        let kind = sym.kind.as_str();
+        if kind == "function" || kind == "method" {
+            let calls = parser.find_callees_for_symbol(&full_path, &content, sym);
+            for (callee_name, line, qualifier, access) in calls {
+                call_data.push((sym.name.clone(), callee_name, qualifier, access, line));
+            }
+        }
+    }
+
+    // Parse imports using trait-based extraction (works for all supported languages)
+    let imports = parser.parse_imports(&full_path, &content);
+
+    // Extract type methods for cross-file interface resolution
+    // We need to use the full symbol extraction to get hierarchy
+    let extract_result = crate::extract::Extractor::new().extract(&full_path, &content);
+    let mut type_methods = Vec::new();
+    for sym in &extract_result.symbols {
+        if matches!(
+            sym.kind,
+            normalize_languages::SymbolKind::Interface
+                | normalize_languages::SymbolKind::Class
+                | normalize_languages::SymbolKind::Trait
+                | normalize_languages::SymbolKind::Struct
+        ) {
+            for child in &sym.children {
+                if matches!(
+                    child.kind,
+                    normalize_languages::SymbolKind::Method
+                        | normalize_languages::SymbolKind::Function
+                ) {
+                    type_methods.push((sym.name.clone(), child.name.clone()));
+                }
+            }
+        }
+    }
+
+    // Extract type references using tree-sitter queries
+    let type_refs = parser.find_type_refs(&full_path, &content);
+
+    // Build CFGs for function/method symbols (best-effort — errors are non-fatal).
+    let cfg = build_cfg_data_for_file(&full_path, &bytes, &grammar, &symbols);
+
+    // Store result in CA cache (best-effort).
+    // Grammar availability is already guaranteed above (parse_file returned Some),
+    // so empty results here are legitimate and safe to cache.
+    if !grammar.is_empty()
+        && let Some(ca) = ca_cache
+    {
+        let cached = CachedFileData {
+            symbols: sym_data
+                .iter()
+                .map(|s| ParsedSymbol {
+                    name: s.name.clone(),
+                    kind: s.kind.clone(),
+                    start_line: s.start_line,
+                    end_line: s.end_line,
+                    parent: s.parent.clone(),
+                    visibility: s.visibility.clone(),
+                    attributes: s.attributes.clone(),
+                    is_interface_impl: s.is_interface_impl,
+                    implements: s.implements.clone(),
+                    docstring: s.docstring.clone(),
+                    complexity: s.complexity,
+                    nesting_depth: s.nesting_depth,
+                })
+                .collect(),
+            calls: call_data.clone(),
+            imports: imports.clone(),
+            type_methods: type_methods.clone(),
+            type_refs: type_refs.clone(),
+        };
+        if let Err(e) = ca.put(hash.as_bytes(), EXTRACTOR_VERSION, &grammar, &cached) {
+            tracing::warn!("normalize-facts: CA cache put error: {}", e);
+        }
+    }
+
+    Some(ParsedFileData {
+        file_path: file_path.to_string(),
+        symbols: sym_data,
+        calls: call_data,
+        imports,
+        type_methods,
+        type_refs,
+        cfg,
+        info: file_info_for(file_path, &grammar, &content),
+    })
+}
+
+/// Count total, blank and comment lines of a parsed source file.
+///
+/// Comment lines are non-blank rows covered by any node whose kind contains
+/// "comment", so a line holding both code and a trailing comment counts as a
+/// comment line and `blank_lines + comment_lines` never exceeds `lines`.
+fn file_info_for(file_path: &str, grammar: &str, content: &str) -> FileInfo {
+    let mut comment_rows = std::collections::HashSet::new();
+    if let Some(tree) = crate::parsers::parse_with_grammar(grammar, content) {
+        let mut cursor = tree.walk();
+        'walk: loop {
+            let node = cursor.node();
+            if node.kind().contains("comment") {
+                comment_rows.extend(node.start_position().row..=node.end_position().row);
+            } else if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+            }
+        }
+    }
+    let mut info = FileInfo {
+        file: file_path.to_string(),
+        language: grammar.to_string(),
+        ..FileInfo::default()
+    };
+    for (row, line) in content.lines().enumerate() {
+        info.lines += 1;
+        if line.trim().is_empty() {
+            info.blank_lines += 1;
+        } else if comment_rows.contains(&row) {
+            info.comment_lines += 1;
+        }
+    }
+    info
+}
+
 // =============================================================================
 // CFG building helpers
 // =============================================================================
@@ -4599,6 +4831,8 @@ struct CoChangeWalkData {
     /// single-file commits, which `commit_files` excludes) — piggybacked onto the
     /// same walk so churn collection costs no extra git-log traversal.
     churn: std::collections::HashMap<String, FileChurnAcc>,
+    /// Every (file, commit time, author email) touch, for the `file_commits` table.
+    touches: Vec<(String, u64, String)>,
 }
 
 /// Walk commits via gix, returning per-commit lists of *source* files changed
@@ -4619,6 +4853,7 @@ fn walk_commits_for_co_change(
             return CoChangeWalkData {
                 commit_files: Vec::new(),
                 churn: std::collections::HashMap::new(),
+                touches: Vec::new(),
             };
         }
     };
@@ -4634,6 +4869,7 @@ fn walk_commits_for_co_change(
             return CoChangeWalkData {
                 commit_files: Vec::new(),
                 churn: std::collections::HashMap::new(),
+                touches: Vec::new(),
             };
         }
     };
@@ -4644,6 +4880,7 @@ fn walk_commits_for_co_change(
     let mut commit_files = Vec::new();
     let mut churn: std::collections::HashMap<String, FileChurnAcc> =
         std::collections::HashMap::new();
+    let mut touches = Vec::new();
 
     for info in walk {
         let Ok(info) = info else { continue };
@@ -4659,6 +4896,10 @@ fn walk_commits_for_co_change(
         let Ok(commit) = info.object() else { continue };
         let Ok(tree) = commit.tree() else { continue };
         let commit_time = info.commit_time.unwrap_or(0) as u64;
+        let author = commit
+            .author()
+            .map(|a| String::from_utf8_lossy(a.email).into_owned())
+            .unwrap_or_default();
 
         let parent_tree = info
             .parent_ids()
@@ -4711,6 +4952,7 @@ fn walk_commits_for_co_change(
             if commit_time > acc.last_changed {
                 acc.last_changed = commit_time;
             }
+            touches.push((path_str.clone(), commit_time, author.clone()));
 
             files.push(path_str);
         }
@@ -4723,6 +4965,7 @@ fn walk_commits_for_co_change(
     CoChangeWalkData {
         commit_files,
         churn,
+        touches,
     }
 }
 
@@ -4797,6 +5040,179 @@ mod tests {
         assert!(matches[0].path.ends_with("cli.py"));
     }
 
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("parse", "Parse"), Some(10_000));
+        assert!(fuzzy_score("xyz", "parse_file").is_none());
+        assert!(fuzzy_score("pf", "parse_file").is_some());
+        // Prefix beats word-boundary subsequence, which beats scattered letters.
+        let prefix = fuzzy_score("par", "parse_file").unwrap();
+        let boundary = fuzzy_score("pf", "parse_file").unwrap();
+        let scattered = fuzzy_score("pf", "shipfast").unwrap();
+        assert!(prefix > boundary && boundary > scattered);
+        // camelCase humps count as boundaries.
+        assert!(
+            fuzzy_score("fsm", "FileStateMachine").unwrap()
+                > fuzzy_score("fsm", "offsetsum").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fuzzy_find_symbols() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.py"),
+            "def parse_file(): pass\n\ndef prepare_fields(): pass\n\ndef unrelated(): pass\n",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(&dir.path().join("index.sqlite"), dir.path())
+            .await
+            .unwrap();
+        index.refresh().await.unwrap();
+        index.refresh_call_graph().await.unwrap();
+
+        let names: Vec<String> = index
+            .fuzzy_find_symbols("pf", None, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, ["parse_file", "prepare_fields"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_churn_since() {
+        let dir = tempdir().unwrap();
+        let git = |args: &[&str], email: &str, date: &str| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .env("GIT_AUTHOR_NAME", email)
+                .env("GIT_AUTHOR_EMAIL", email)
+                .env("GIT_COMMITTER_NAME", email)
+                .env("GIT_COMMITTER_EMAIL", email)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "git {:?} failed", args);
+        };
+        let commit = |body: &str, email: &str, date: &str| {
+            fs::write(dir.path().join("lib.py"), body).unwrap();
+            git(&["add", "lib.py"], email, date);
+            git(&["commit", "-q", "-m", "change"], email, date);
+        };
+        git(&["init", "-q"], "a@example.com", "@1000000000 +0000");
+        commit("a = 1\n", "a@example.com", "@1000000000 +0000");
+        commit("a = 2\n", "b@example.com", "@1700000000 +0000");
+
+        let index = FileIndex::open(&dir.path().join("index.sqlite"), dir.path())
+            .await
+            .unwrap();
+        index.rebuild_co_change_edges(None).await.unwrap();
+        let churn = index.file_churn_since(1_500_000_000).await.unwrap();
+        assert_eq!(
+            churn,
+            vec![FileChurn {
+                file: "lib.py".to_string(),
+                commits: 2,
+                recent_commits: 1,
+                last_changed: 1_700_000_000,
+                authors: 2,
+            }]
+        );
+
+        // An incremental walk appends only the new commit.
+        commit("a = 3\n", "a@example.com", "@1700000100 +0000");
+        let since = index.co_change_last_commit().await;
+        index
+            .rebuild_co_change_edges(since.as_deref())
+            .await
+            .unwrap();
+        let churn = index.file_churn_since(1_500_000_000).await.unwrap();
+        assert_eq!((churn[0].commits, churn[0].recent_commits), (3, 2));
+        assert_eq!(churn[0].authors, 2);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_refresh_reindexes_symbols() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("lib.py");
+        fs::write(&file, "def first(): pass\n").unwrap();
+
+        let mut index = FileIndex::open(&dir.path().join("index.sqlite"), dir.path())
+            .await
+            .unwrap();
+        index.refresh().await.unwrap();
+        index.refresh_call_graph().await.unwrap();
+
+        fs::write(&file, "def first(): pass\n\ndef second(): pass\n").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        // The file walk records the new mtime first; the call-graph refresh must
+        // still see that the file's symbols are stale.
+        let changed = index.incremental_refresh_force().await.unwrap();
+        assert!(changed.contains(&file));
+        let stats = index.incremental_call_graph_refresh().await.unwrap();
+        assert_eq!(stats.symbols, 2);
+        assert_eq!(
+            index
+                .find_symbols("second", None, false, 5)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Nothing left to do on a second pass.
+        let again = index.incremental_call_graph_refresh().await.unwrap();
+        assert_eq!(again.symbols, 0);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_refresh_records_type_methods() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("shapes.py");
+        fs::write(
+            &file,
+            "def helper(): pass
+",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(&dir.path().join("index.sqlite"), dir.path())
+            .await
+            .unwrap();
+        index.disable_ca_cache();
+        index.refresh().await.unwrap();
+        index.refresh_call_graph().await.unwrap();
+
+        fs::write(&file, "class Shape:\n    def area(self): pass\n").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        index.incremental_refresh_force().await.unwrap();
+        index.incremental_call_graph_refresh().await.unwrap();
+        assert_eq!(
+            index.get_type_methods("shapes.py", "Shape").await.unwrap(),
+            vec!["area".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn test_find_by_stem() {
         let dir = tempdir().unwrap();
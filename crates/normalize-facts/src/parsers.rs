@@ -17,6 +17,7 @@ use std::sync::Arc;
 pub use normalize_languages::parsers::{
     MissingGrammar, available_external_grammars, parse_with_grammar, parser_for,
     peek_missing_grammars, report_missing_grammar, take_missing_grammars, try_get_grammar,
+    with_parser,
 };
 
 /// Get the global grammar loader singleton (canonical instance from `normalize-languages`).
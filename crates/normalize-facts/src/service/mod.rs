@@ -1,6 +1,6 @@
 //! Standalone CLI service for normalize-facts — the canonical `structure` verb.
 //!
-//! Exposes `structure` subcommands: rebuild, stats, files, packages, query,
+//! Exposes `structure` subcommands: rebuild, stats, files, symbols, packages, query,
 //! test-fixtures, and the CFG dataflow trio (liveness, effects, exceptions).
 //!
 //! The service owns its config access: it loads the `[walk]` and `[aliases]`
@@ -164,25 +164,6 @@ fn load_aliases(root: &Path) -> AliasConfig {
     normalize_config_paths::ConfigSlices::load(root).slice("aliases")
 }
 
-/// Detect programming languages present under `root` (bounded-depth walk).
-/// Used to resolve language-scoped filter aliases (`@tests`, …).
-fn detect_project_languages(root: &Path) -> Vec<String> {
-    let mut languages = std::collections::HashSet::new();
-    let walker = ignore::WalkBuilder::new(root)
-        .max_depth(Some(5))
-        .hidden(false)
-        .git_ignore(true)
-        .build();
-    for entry in walker.flatten() {
-        if let Some(lang) = normalize_languages::support_for_path(entry.path()) {
-            languages.insert(lang.name().to_string());
-        }
-    }
-    let mut result: Vec<_> = languages.into_iter().collect();
-    result.sort();
-    result
-}
-
 /// Build a `Filter` from `--exclude` / `--only` patterns, printing any warnings.
 /// Returns `None` when both slices are empty (no filtering needed).
 fn build_filter(root: &Path, exclude: &[String], only: &[String]) -> Option<Filter> {
@@ -190,9 +171,7 @@ fn build_filter(root: &Path, exclude: &[String], only: &[String]) -> Option<Filt
         return None;
     }
     let aliases = load_aliases(root);
-    let languages = detect_project_languages(root);
-    let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
-    match Filter::new(exclude, only, &aliases, &lang_refs) {
+    match Filter::for_project(exclude, only, &aliases, root) {
         Ok(f) => {
             for warning in f.warnings() {
                 eprintln!("warning: {}", warning);
@@ -443,6 +422,62 @@ impl OutputFormatter for FileListReport {
     }
 }
 
+/// Symbol definitions found by `normalize structure symbols`.
+#[derive(Serialize, JsonSchema)]
+pub struct SymbolSearchReport {
+    /// The query as given.
+    pub query: String,
+    /// `"exact"` or `"fuzzy"`.
+    pub mode: String,
+    pub symbols: Vec<SymbolHit>,
+}
+
+/// One symbol definition in a [`SymbolSearchReport`].
+#[derive(Serialize, JsonSchema)]
+pub struct SymbolHit {
+    pub name: String,
+    pub kind: String,
+    /// Path relative to the project root.
+    pub file: String,
+    /// 1-based start line.
+    pub line: usize,
+    /// 1-based end line (inclusive).
+    pub end_line: usize,
+    /// Enclosing symbol (class, impl, module), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+}
+
+impl OutputFormatter for SymbolSearchReport {
+    fn format_text(&self) -> String {
+        if self.symbols.is_empty() {
+            return format!("No symbols matching '{}'\n", self.query);
+        }
+        let width = self
+            .symbols
+            .iter()
+            .map(|s| s.file.len() + s.line.to_string().len() + 1)
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for s in &self.symbols {
+            let location = format!("{}:{}", s.file, s.line);
+            let name = match &s.container {
+                Some(c) => format!("{}/{}", c, s.name),
+                None => s.name.clone(),
+            };
+            out.push_str(&format!(
+                "{:<width$}  {:<10} {}\n",
+                location,
+                s.kind,
+                name,
+                width = width
+            ));
+        }
+        out
+    }
+}
+
 /// Report for `normalize structure packages`: indexed package counts per ecosystem.
 #[derive(Serialize, JsonSchema)]
 pub struct PackagesReport {
@@ -601,15 +636,7 @@ pub struct ExtractionFixtureCaseResult {
 // =============================================================================
 
 fn get_cache_dir() -> Option<PathBuf> {
-    if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
-        Some(PathBuf::from(cache).join("normalize"))
-    } else if let Ok(home) = std::env::var("HOME") {
-        Some(PathBuf::from(home).join(".cache").join("normalize"))
-    } else if let Ok(home) = std::env::var("USERPROFILE") {
-        Some(PathBuf::from(home).join(".cache").join("normalize"))
-    } else {
-        None
-    }
+    normalize_cache::cache_root()
 }
 
 fn dir_size(path: &Path) -> u64 {
@@ -772,16 +799,20 @@ async fn index_language_packages(
         };
         let discovered = deps.discover_packages(&source);
 
-        for (pkg_name, pkg_path) in discovered {
-            if let Ok(true) = pkg_index.is_indexed(eco_key, &pkg_name).await {
+        for pkg in discovered {
+            if let Ok(true) = pkg_index
+                .is_version_indexed(eco_key, &pkg.name, pkg.version.as_deref())
+                .await
+            {
                 continue;
             }
 
             let pkg_id = match pkg_index
                 .insert_package(
                     eco_key,
-                    &pkg_name,
-                    &pkg_path.to_string_lossy(),
+                    &pkg.name,
+                    &pkg.path.to_string_lossy(),
+                    pkg.version.as_deref(),
                     min_version,
                     max_version,
                 )
@@ -793,7 +824,7 @@ async fn index_language_packages(
 
             total_packages += 1;
             total_symbols +=
-                index_package_symbols(deps, pkg_index, &extractor, pkg_id, &pkg_path).await;
+                index_package_symbols(deps, pkg_index, &extractor, pkg_id, &pkg.path).await;
         }
     }
 
@@ -1090,6 +1121,60 @@ async fn stats_data(root: Option<&Path>) -> Result<FactsStats, String> {
     })
 }
 
+/// Search the index for symbol definitions. A `Container/name` query also
+/// filters on the enclosing symbol (case-insensitive substring).
+async fn search_symbols_data(
+    query: &str,
+    exact: bool,
+    kind: Option<&str>,
+    root: &Path,
+    limit: usize,
+) -> Result<SymbolSearchReport, String> {
+    let idx = ensure_ready(root).await?;
+    let (container, name) = match query.rsplit_once('/') {
+        Some((c, n)) if !n.is_empty() => (Some(c.to_lowercase()), n),
+        _ => (None, query),
+    };
+    // Over-fetch when filtering by container so the limit applies afterwards.
+    let fetch = if container.is_some() {
+        limit.saturating_mul(10)
+    } else {
+        limit
+    };
+    let matches = if exact {
+        idx.find_symbols(name, kind, false, fetch).await
+    } else {
+        idx.fuzzy_find_symbols(name, kind, fetch).await
+    }
+    .map_err(|e| format!("Failed to search symbols: {}", e))?;
+
+    let symbols = matches
+        .into_iter()
+        .filter(|m| match &container {
+            Some(c) => m
+                .parent
+                .as_ref()
+                .is_some_and(|p| p.to_lowercase().contains(c.as_str())),
+            None => true,
+        })
+        .take(limit)
+        .map(|m| SymbolHit {
+            name: m.name,
+            kind: m.kind,
+            file: m.file,
+            line: m.start_line,
+            end_line: m.end_line,
+            container: m.parent,
+        })
+        .collect();
+
+    Ok(SymbolSearchReport {
+        query: query.to_string(),
+        mode: if exact { "exact" } else { "fuzzy" }.to_string(),
+        symbols,
+    })
+}
+
 async fn list_files_data(
     prefix: Option<&str>,
     root: Option<&Path>,
@@ -1328,6 +1413,39 @@ impl FactsCliService {
         list_files_data(prefix.as_deref(), root_path.as_deref(), limit).await
     }
 
+    /// Search symbol definitions across the workspace
+    ///
+    /// Looks up functions, types, methods, etc. by name in the structural index
+    /// (building it on first use). Matching is fuzzy by default: the query's
+    /// characters must appear in order, ranked by prefix and word-boundary hits.
+    /// Use `Container/name` to restrict to members of a matching class/impl/module.
+    ///
+    /// Examples:
+    ///   normalize structure symbols parse_file           # fuzzy search
+    ///   normalize structure symbols pf                   # matches parse_file, prepare_fields, ...
+    ///   normalize structure symbols Parser --exact       # exact name (case-insensitive)
+    ///   normalize structure symbols Index/open -k method # methods named like "open" in Index*
+    #[cli(display_with = "display_output")]
+    pub async fn symbols(
+        &self,
+        #[param(positional, help = "Symbol name or Container/name to search for")] query: String,
+        #[param(help = "Match the name exactly (case-insensitive) instead of fuzzily")] exact: bool,
+        #[param(
+            short = 'k',
+            help = "Filter by symbol kind (function, class, method, ...)"
+        )]
+        kind: Option<String>,
+        #[param(short = 'l', help = "Maximum number of results (default: 50)")] limit: Option<
+            usize,
+        >,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<SymbolSearchReport, String> {
+        let root = resolve_root(root)?;
+        search_symbols_data(&query, exact, kind.as_deref(), &root, limit.unwrap_or(50)).await
+    }
+
     /// Index external packages (stdlib, site-packages) into global cache
     ///
     /// Examples:
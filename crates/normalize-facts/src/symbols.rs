@@ -74,6 +74,7 @@ impl SymbolParser {
             implements: sym.implements.clone(),
             docstring: sym.docstring.clone(),
             complexity: sym.complexity,
+            nesting_depth: sym.nesting_depth,
         });
 
         // Recurse into children with current symbol as parent
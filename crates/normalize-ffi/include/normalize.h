@@ -0,0 +1,42 @@
+/*
+ * normalize C API.
+ *
+ * Every request and response is a NUL-terminated UTF-8 JSON string.
+ * Responses are {"ok": true, "result": ...} or {"ok": false, "error": "..."}
+ * and must be released with normalize_string_free(). See the normalize-ffi
+ * crate documentation for the request fields of each call.
+ */
+
+#ifndef NORMALIZE_H
+#define NORMALIZE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Optional setup: {"grammar_paths": [...], "root": "..."}. NULL for defaults. */
+char *normalize_init(const char *options);
+
+/* {"path": "...", "format": "claude"?} */
+char *normalize_analyze_session(const char *request);
+
+/* {"root": "...", "target"?, "rule"?, "tag"?, "type"?, "only"?, "exclude"?, "files"?} */
+char *normalize_run_rules(const char *request);
+
+/* {"path": "...", "content": "..."?} */
+char *normalize_skeleton(const char *request);
+
+/* {"source": "...", "from": "typescript", "to": "lua"} */
+char *normalize_translate(const char *request);
+
+/* Release a response. NULL is ignored. */
+void normalize_string_free(char *s);
+
+/* Static version string; do not free. */
+const char *normalize_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NORMALIZE_H */
@@ -0,0 +1,461 @@
+//! C ABI for embedding normalize in-process.
+//!
+//! Tools written in Python, Node or anything else with a C FFI can load
+//! `libnormalize_ffi` once and call into it directly instead of spawning the
+//! `normalize` CLI per request (which pays process startup, config loading and
+//! grammar loading every time). The declarations live in
+//! `include/normalize.h`.
+//!
+//! Every entry point takes a NUL-terminated UTF-8 JSON request and returns a
+//! newly allocated NUL-terminated JSON response, which the caller must release
+//! with [`normalize_string_free`]. Responses are always an envelope:
+//!
+//! ```json
+//! {"ok": true, "result": { ... }}
+//! {"ok": false, "error": "No reader available for language: cobol"}
+//! ```
+//!
+//! | function | request | result |
+//! |---|---|---|
+//! | `normalize_init` | `{"grammar_paths": [...], "root": "."}` (all optional) | `{"version", "grammar_paths_applied"}` |
+//! | `normalize_analyze_session` | `{"path", "format"?}` | session analysis report |
+//! | `normalize_run_rules` | `{"root", "target"?, "rule"?, "tag"?, "type"?, "only"?, "exclude"?, "files"?}` | diagnostics report |
+//! | `normalize_skeleton` | `{"path", "content"?}` | `{"file", "language", "symbols"}` |
+//! | `normalize_translate` | `{"source", "from", "to"}` | `{"code", "source_language", "target_language"}` |
+//!
+//! Results have the same shape as the CLI's `--json` output for the matching
+//! command. Calls may be made from any thread; panics are caught at the
+//! boundary and reported as errors.
+//!
+//! From Python:
+//!
+//! ```python
+//! import ctypes, json
+//! lib = ctypes.CDLL("libnormalize_ffi.so")
+//! lib.normalize_skeleton.restype = ctypes.c_void_p
+//! ptr = lib.normalize_skeleton(json.dumps({"path": "src/lib.rs"}).encode())
+//! response = json.loads(ctypes.string_at(ptr))
+//! lib.normalize_string_free(ctypes.c_void_p(ptr))
+//! ```
+
+use normalize_chat_sessions::{parse_session, parse_session_with_format};
+use normalize_languages::{parsers::set_grammar_paths, support_for_path};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Options for [`normalize_init`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct InitRequest {
+    /// Extra grammar directories, searched after `NORMALIZE_GRAMMAR_PATH` and
+    /// `~/.config/normalize/grammars`.
+    grammar_paths: Vec<PathBuf>,
+    /// Project whose `.normalize/pricing.toml`, `corrections.toml` and
+    /// `errors.toml` apply to session analysis.
+    root: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct InitResult {
+    version: &'static str,
+    /// False when grammars were already loaded before `normalize_init`, so
+    /// `grammar_paths` could not take effect.
+    grammar_paths_applied: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnalyzeSessionRequest {
+    path: PathBuf,
+    /// Force a session format (claude, codex, gemini, normalize, ...).
+    #[serde(default)]
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunRulesRequest {
+    root: PathBuf,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    rule: Option<String>,
+    #[serde(default)]
+    tag: Option<String>,
+    /// all, syntax, fact, native or sarif.
+    #[serde(default, rename = "type")]
+    rule_type: Option<String>,
+    #[serde(default)]
+    only: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SkeletonRequest {
+    /// File path; also selects the language when `content` is given.
+    path: PathBuf,
+    /// Source text to use instead of reading `path`.
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SkeletonResult {
+    file: String,
+    language: &'static str,
+    symbols: Vec<normalize_facts::Symbol>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TranslateRequest {
+    source: String,
+    from: String,
+    to: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TranslateResult {
+    code: String,
+    source_language: String,
+    target_language: String,
+}
+
+/// Initialise the library. Optional: every other entry point works without
+/// it, but grammar paths only apply if this runs before the first call that
+/// parses code.
+///
+/// `options` may be NULL for defaults.
+///
+/// # Safety
+///
+/// `options` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_init(options: *const c_char) -> *mut c_char {
+    let options = if options.is_null() {
+        c"{}".as_ptr()
+    } else {
+        options
+    };
+    // SAFETY: forwarded from the caller's contract.
+    unsafe {
+        handle(options, |req: InitRequest| {
+            let grammar_paths_applied = set_grammar_paths(req.grammar_paths);
+            if let Some(root) = req.root {
+                let table = normalize_session_analysis::PricingTable::load(&root)?;
+                normalize_session_analysis::PricingTable::install(table);
+                let patterns = normalize_session_analysis::CorrectionPatterns::load(&root)?;
+                normalize_session_analysis::CorrectionPatterns::install(patterns);
+                let rules = normalize_session_analysis::ErrorCategorizers::load(&root)?;
+                normalize_session_analysis::ErrorCategorizers::install(rules);
+            }
+            Ok(InitResult {
+                version: env!("CARGO_PKG_VERSION"),
+                grammar_paths_applied,
+            })
+        })
+    }
+}
+
+/// Parse a session log and analyze it (`normalize sessions show --analyze`).
+///
+/// # Safety
+///
+/// `request` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_analyze_session(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller's contract.
+    unsafe {
+        handle(request, |req: AnalyzeSessionRequest| {
+            let session = match &req.format {
+                Some(format) => parse_session_with_format(&req.path, format),
+                None => parse_session(&req.path),
+            }
+            .map_err(|e| format!("Failed to parse {}: {}", req.path.display(), e))?;
+            Ok(normalize_session_analysis::analyze_session(&session))
+        })
+    }
+}
+
+/// Run syntax, fact and native rules over a project (`normalize rules run`).
+/// Error-severity findings are part of the result, not an error.
+///
+/// # Safety
+///
+/// `request` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_run_rules(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller's contract.
+    unsafe {
+        handle(request, |req: RunRulesRequest| {
+            let service = normalize_rules::RulesService::new(&std::cell::Cell::new(false));
+            runtime()?.block_on(service.run(
+                req.rule,
+                req.tag,
+                false,
+                false,
+                false,
+                req.target,
+                req.rule_type,
+                Vec::new(),
+                Some(req.root.to_string_lossy().into_owned()),
+                true,
+                None,
+                req.only,
+                req.exclude,
+                req.files,
+            ))
+        })
+    }
+}
+
+/// Extract the symbol skeleton of one file (`normalize view <file>`).
+///
+/// # Safety
+///
+/// `request` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_skeleton(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller's contract.
+    unsafe {
+        handle(request, |req: SkeletonRequest| {
+            let support = support_for_path(&req.path)
+                .ok_or_else(|| format!("Unsupported file type: {}", req.path.display()))?;
+            let content = match req.content {
+                Some(content) => content,
+                None => std::fs::read_to_string(&req.path)
+                    .map_err(|e| format!("Failed to read {}: {}", req.path.display(), e))?,
+            };
+            let extracted = normalize_facts::Extractor::new().extract(&req.path, &content);
+            Ok(SkeletonResult {
+                file: extracted.file_path,
+                language: support.name(),
+                symbols: extracted.symbols,
+            })
+        })
+    }
+}
+
+/// Translate source between languages (`normalize translate`).
+///
+/// # Safety
+///
+/// `request` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_translate(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller's contract.
+    unsafe {
+        handle(request, |req: TranslateRequest| {
+            let reader = normalize_surface_syntax::registry::reader_for_language(&req.from)
+                .ok_or_else(|| format!("No reader available for language: {}", req.from))?;
+            let writer = normalize_surface_syntax::registry::writer_for_language(&req.to)
+                .ok_or_else(|| format!("No writer available for language: {}", req.to))?;
+            let ir = reader
+                .read(&req.source)
+                .map_err(|e| format!("Failed to parse source as {}: {}", req.from, e))?;
+            Ok(TranslateResult {
+                code: writer.write(&ir),
+                source_language: req.from,
+                target_language: req.to,
+            })
+        })
+    }
+}
+
+/// Release a string returned by any `normalize_*` function. NULL is ignored.
+///
+/// # Safety
+///
+/// `s` must be NULL or a pointer returned by this library that has not been
+/// freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn normalize_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: `s` came from `CString::into_raw` in `respond`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Library version as a static NUL-terminated string (do not free).
+#[unsafe(no_mangle)]
+pub extern "C" fn normalize_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Shared runtime for the async service layer.
+fn runtime() -> Result<&'static tokio::runtime::Runtime, String> {
+    static RUNTIME: OnceLock<Result<tokio::runtime::Runtime, String>> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| format!("Failed to start runtime: {}", e))
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Decode `request`, run `f`, and encode the outcome as a response envelope.
+///
+/// # Safety
+///
+/// `request` must be NULL or point to a NUL-terminated string.
+unsafe fn handle<Req, Res>(
+    request: *const c_char,
+    f: impl FnOnce(Req) -> Result<Res, String>,
+) -> *mut c_char
+where
+    Req: DeserializeOwned,
+    Res: Serialize,
+{
+    let outcome = catch_unwind(AssertUnwindSafe(|| {
+        if request.is_null() {
+            return Err("request is NULL".to_string());
+        }
+        // SAFETY: non-null and NUL-terminated per the caller's contract.
+        let request = unsafe { CStr::from_ptr(request) }
+            .to_str()
+            .map_err(|e| format!("request is not UTF-8: {}", e))?;
+        let request: Req =
+            serde_json::from_str(request).map_err(|e| format!("Invalid request: {}", e))?;
+        let result = f(request)?;
+        serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
+    }))
+    .unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(format!("normalize panicked: {}", message))
+    });
+    respond(outcome)
+}
+
+fn respond(outcome: Result<serde_json::Value, String>) -> *mut c_char {
+    let envelope = match outcome {
+        Ok(result) => serde_json::json!({ "ok": true, "result": result }),
+        Err(error) => serde_json::json!({ "ok": false, "error": error }),
+    };
+    // serde_json escapes NUL, so the encoded text never contains one.
+    CString::new(envelope.to_string())
+        .unwrap_or_default()
+        .into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(
+        f: unsafe extern "C" fn(*const c_char) -> *mut c_char,
+        request: &str,
+    ) -> serde_json::Value {
+        let request = CString::new(request).unwrap();
+        unsafe {
+            let ptr = f(request.as_ptr());
+            let response = CStr::from_ptr(ptr).to_str().unwrap().to_string();
+            normalize_string_free(ptr);
+            serde_json::from_str(&response).unwrap()
+        }
+    }
+
+    #[test]
+    fn version_matches_crate() {
+        let version = unsafe { CStr::from_ptr(normalize_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn bad_requests_return_error_envelopes() {
+        let response = call(normalize_translate, "not json");
+        assert_eq!(response["ok"], false);
+        assert!(
+            response["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Invalid request")
+        );
+
+        let response = call(
+            normalize_translate,
+            r#"{"source": "", "from": "cobol", "to": "lua"}"#,
+        );
+        assert_eq!(response["error"], "No reader available for language: cobol");
+
+        let response = call(normalize_skeleton, r#"{"path": "x.rs", "bogus": 1}"#);
+        assert_eq!(response["ok"], false);
+
+        let response = call(
+            normalize_analyze_session,
+            r#"{"path": "/nonexistent/session.jsonl"}"#,
+        );
+        assert_eq!(response["ok"], false);
+
+        let response = unsafe { normalize_skeleton(std::ptr::null()) };
+        let text = unsafe { CStr::from_ptr(response) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { normalize_string_free(response) };
+        assert_eq!(text, r#"{"error":"request is NULL","ok":false}"#);
+    }
+
+    #[test]
+    fn init_accepts_null_and_options() {
+        let response = unsafe { normalize_init(std::ptr::null()) };
+        let text = unsafe { CStr::from_ptr(response) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { normalize_string_free(response) };
+        let response: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(response["ok"], true);
+        assert_eq!(response["result"]["version"], env!("CARGO_PKG_VERSION"));
+
+        let response = call(normalize_init, r#"{"grammar_paths": ["/tmp"]}"#);
+        assert_eq!(response["result"]["grammar_paths_applied"], false);
+    }
+
+    #[test]
+    fn run_rules_reports_findings_without_failing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        let request = serde_json::json!({ "root": dir.path(), "type": "syntax" });
+        let response = call(normalize_run_rules, &request.to_string());
+        assert_eq!(response["ok"], true, "{response}");
+        assert!(response["result"]["issues"].is_array(), "{response}");
+    }
+
+    #[test]
+    fn skeleton_and_translate_round_trip() {
+        let response = call(
+            normalize_skeleton,
+            r#"{"path": "lib.rs", "content": "pub fn add(a: i32, b: i32) -> i32 { a + b }\n"}"#,
+        );
+        assert_eq!(response["ok"], true, "{response}");
+        assert_eq!(response["result"]["language"], "Rust");
+        assert_eq!(response["result"]["symbols"][0]["name"], "add");
+
+        let response = call(
+            normalize_translate,
+            r#"{"source": "const x = 1 + 2;", "from": "typescript", "to": "lua"}"#,
+        );
+        assert_eq!(response["ok"], true, "{response}");
+        assert!(
+            response["result"]["code"]
+                .as_str()
+                .unwrap()
+                .contains("local x")
+        );
+    }
+}
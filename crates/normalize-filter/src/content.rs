@@ -0,0 +1,220 @@
+//! Content-based file classification for `@binary`, `@minified` and `@generated-content`.
+//!
+//! Unlike glob aliases these look inside the file, so the [`Filter`](crate::Filter)
+//! consults the classifier lazily — only for paths the glob patterns have not already
+//! decided — and caches the result per path.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// How many leading bytes of a file are sniffed.
+const SNIFF_BYTES: usize = 8 * 1024;
+
+/// Lines beyond this many bytes on average mark a text file as minified.
+const MINIFIED_AVG_LINE_LEN: usize = 250;
+
+/// Files smaller than this are never considered minified.
+const MINIFIED_MIN_BYTES: usize = 1024;
+
+/// Only the first few lines are searched for a generated-file marker.
+const GENERATED_HEADER_LINES: usize = 10;
+
+/// Markers that tools put in the header of generated files (matched case-insensitively).
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "do not edit",
+    "code generated",
+    "auto-generated",
+    "autogenerated",
+];
+
+/// A property of a file's contents that a filter alias can select on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentClass {
+    /// Contains a NUL byte in its first 8 KiB (the heuristic git uses).
+    Binary,
+    /// Text whose average line length suggests minified output (bundled JS/CSS).
+    Minified,
+    /// Has a generated-file marker such as `@generated` or `DO NOT EDIT` in its header.
+    Generated,
+}
+
+impl ContentClass {
+    /// All classes, in display order.
+    pub const ALL: [ContentClass; 3] = [Self::Binary, Self::Minified, Self::Generated];
+
+    /// Name used in `syntax = "content"` alias values.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Binary => "binary",
+            Self::Minified => "minified",
+            Self::Generated => "generated",
+        }
+    }
+
+    /// Parse a class name as written in an alias value.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+impl std::fmt::Display for ContentClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The content classes a file belongs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentClasses {
+    pub binary: bool,
+    pub minified: bool,
+    pub generated: bool,
+}
+
+impl ContentClasses {
+    /// Whether the file belongs to `class`.
+    pub fn contains(self, class: ContentClass) -> bool {
+        match class {
+            ContentClass::Binary => self.binary,
+            ContentClass::Minified => self.minified,
+            ContentClass::Generated => self.generated,
+        }
+    }
+}
+
+/// Classify a file from its leading bytes (at most the first 8 KiB are examined).
+pub fn classify_bytes(bytes: &[u8]) -> ContentClasses {
+    let sample = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if sample.contains(&0) {
+        return ContentClasses {
+            binary: true,
+            ..ContentClasses::default()
+        };
+    }
+
+    let text = String::from_utf8_lossy(sample);
+    let line_count = text.lines().count().max(1);
+    let minified =
+        sample.len() >= MINIFIED_MIN_BYTES && sample.len() / line_count > MINIFIED_AVG_LINE_LEN;
+    let generated = text.lines().take(GENERATED_HEADER_LINES).any(|line| {
+        let line = line.to_ascii_lowercase();
+        GENERATED_MARKERS.iter().any(|marker| line.contains(marker))
+    });
+
+    ContentClasses {
+        binary: false,
+        minified,
+        generated,
+    }
+}
+
+/// Reads and classifies files on demand, caching the result per path.
+///
+/// Relative paths are resolved against `root` (or the working directory without one).
+/// Unreadable files belong to no class.
+#[derive(Debug, Default)]
+pub struct ContentClassifier {
+    root: Option<PathBuf>,
+    cache: Mutex<HashMap<PathBuf, ContentClasses>>,
+}
+
+impl ContentClassifier {
+    /// Create a classifier that resolves relative paths against `root`.
+    pub fn new(root: Option<&Path>) -> Self {
+        Self {
+            root: root.map(Path::to_path_buf),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Classify the file at `path`, reading it at most once.
+    pub fn classify(&self, path: &Path) -> ContentClasses {
+        if let Some(classes) = self.lock_cache().get(path) {
+            return *classes;
+        }
+        let full = match self.root {
+            Some(ref root) => root.join(path),
+            None => path.to_path_buf(),
+        };
+        let classes = read_head(&full)
+            .map(|bytes| classify_bytes(&bytes))
+            .unwrap_or_default();
+        self.lock_cache().insert(path.to_path_buf(), classes);
+        classes
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, ContentClasses>> {
+        // A panic while holding the lock cannot leave the map half-updated
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Read up to [`SNIFF_BYTES`] from the start of a regular file.
+fn read_head(path: &Path) -> Option<Vec<u8>> {
+    let file = std::fs::File::open(path).ok()?;
+    if !file.metadata().ok()?.is_file() {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(SNIFF_BYTES);
+    file.take(SNIFF_BYTES as u64).read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_binary() {
+        let classes = classify_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+        assert!(classes.binary);
+        assert!(!classes.minified);
+        assert!(!classes.generated);
+    }
+
+    #[test]
+    fn test_classify_minified() {
+        let bundle = format!("!function(){{{}}}();\n", "var a=1;".repeat(400));
+        assert!(classify_bytes(bundle.as_bytes()).minified);
+
+        let source = "function add(a, b) {\n  return a + b;\n}\n".repeat(50);
+        assert!(!classify_bytes(source.as_bytes()).minified);
+
+        // Too small to judge, even as one long line
+        assert!(!classify_bytes("x".repeat(600).as_bytes()).minified);
+    }
+
+    #[test]
+    fn test_classify_generated() {
+        let go = "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n";
+        assert!(classify_bytes(go.as_bytes()).generated);
+
+        let rust = "// @generated by build.rs\npub const X: u32 = 1;\n";
+        assert!(classify_bytes(rust.as_bytes()).generated);
+
+        let late_marker = format!("{}// DO NOT EDIT\n", "fn f() {}\n".repeat(20));
+        assert!(!classify_bytes(late_marker.as_bytes()).generated);
+    }
+
+    #[test]
+    fn test_classifier_caches_per_path() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("gen.rs"), "// @generated\n").unwrap();
+
+        let classifier = ContentClassifier::new(Some(dir.path()));
+        assert!(classifier.classify(Path::new("gen.rs")).generated);
+
+        // The cached result survives the file changing on disk
+        std::fs::write(dir.path().join("gen.rs"), "fn main() {}\n").unwrap();
+        assert!(classifier.classify(Path::new("gen.rs")).generated);
+
+        assert_eq!(
+            classifier.classify(Path::new("missing.rs")),
+            ContentClasses::default()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Project language detection for language-aware aliases such as `@tests`.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// How deep the extension census walks below the project root.
+const CENSUS_MAX_DEPTH: usize = 5;
+
+/// Detect the languages used under `project_root`, sorted by name.
+///
+/// Combines manifest files at the root (`Cargo.toml`, `go.mod`, `package.json`, ...)
+/// with a census of file extensions within a few directory levels, honouring
+/// `.gitignore`. Names come from the `normalize-languages` registry, so they are the
+/// identifiers [`Filter::new`](crate::Filter::new) expects.
+pub fn detect_languages(project_root: &Path) -> Vec<&'static str> {
+    let mut languages = BTreeSet::new();
+
+    // A manifest names the project's language even when its sources sit deeper than
+    // the census reaches (or are all gitignored, as in a fresh checkout with generated code).
+    for lang in normalize_languages::supported_languages() {
+        if lang
+            .project_manifests()
+            .iter()
+            .any(|manifest| project_root.join(manifest).is_file())
+        {
+            languages.insert(lang.name());
+        }
+    }
+
+    let walker = ignore::WalkBuilder::new(project_root)
+        .max_depth(Some(CENSUS_MAX_DEPTH))
+        .hidden(false)
+        .git_ignore(true)
+        .build();
+    for entry in walker.flatten() {
+        if let Some(lang) = normalize_languages::support_for_path(entry.path()) {
+            languages.insert(lang.name());
+        }
+    }
+
+    languages.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_languages_from_manifest_and_census() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("go.mod"), "module example.com/m\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("scripts/build.py"), "print('hi')\n").unwrap();
+
+        let languages = detect_languages(dir.path());
+        let go = normalize_languages::support_for_extension("go").map(|l| l.name());
+        let python = normalize_languages::support_for_extension("py").map(|l| l.name());
+        assert!(go.is_some_and(|name| languages.contains(&name)));
+        assert!(python.is_some_and(|name| languages.contains(&name)));
+
+        let mut sorted = languages.clone();
+        sorted.sort_unstable();
+        assert_eq!(languages, sorted);
+    }
+}
@@ -12,13 +12,23 @@
 //! aliases via `[aliases]` in `.normalize/config.toml` at any directory level
 //! (inner overrides outer).
 
+pub mod content;
+#[cfg(feature = "detect")]
+mod detect;
+pub mod predicate;
 #[cfg(feature = "cli")]
 pub mod service;
 
+pub use content::{ContentClass, ContentClassifier};
+#[cfg(feature = "detect")]
+pub use detect::detect_languages;
+pub use predicate::{FilterPredicate, parse_duration, parse_size};
+
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 // ============================================================================
 // Alias types
@@ -38,6 +48,9 @@ pub enum AliasSyntax {
     Sql,
     /// Filesystem path syntax.
     Path,
+    /// Content classifier names (`binary`, `minified`, `generated`); selects files by
+    /// what they contain rather than by name.
+    Content,
 }
 
 impl std::fmt::Display for AliasSyntax {
@@ -47,6 +60,7 @@ impl std::fmt::Display for AliasSyntax {
             AliasSyntax::Glob => write!(f, "glob"),
             AliasSyntax::Sql => write!(f, "sql"),
             AliasSyntax::Path => write!(f, "path"),
+            AliasSyntax::Content => write!(f, "content"),
         }
     }
 }
@@ -212,12 +226,26 @@ fn infer_syntax(value: &AliasValue) -> AliasSyntax {
 /// [aliases]
 /// tests = ["**/*test*"]
 /// ```
+///
+/// Scoped tables (a table without a `value` key) hold aliases that apply to one
+/// command only and shadow the global table there. `[aliases.filter]` applies to
+/// `--exclude` / `--only`, `[aliases.view]` to path targets such as `normalize view @todo`:
+/// ```toml
+/// [aliases.view]
+/// todo = ["TODO.md"]
+///
+/// [aliases.filter]
+/// vendor = ["vendor/**"]
+/// ```
 #[derive(Debug, Clone, Default, Serialize)]
 #[cfg_attr(feature = "config", derive(schemars::JsonSchema))]
 pub struct AliasConfig {
     /// Map of alias names to their definitions.
     #[serde(flatten)]
     pub entries: HashMap<String, AliasEntry>,
+    /// Per-command alias tables, keyed by command (scope) name.
+    #[serde(flatten)]
+    pub scopes: HashMap<String, HashMap<String, AliasEntry>>,
 }
 
 // Custom Deserialize for backward compatibility with legacy `name = [...]` format.
@@ -238,11 +266,9 @@ impl<'de> Deserialize<'de> for AliasConfig {
             BareString(String),
         }
 
-        let raw: HashMap<String, RawEntry> = HashMap::deserialize(deserializer)?;
-        let entries = raw
-            .into_iter()
-            .map(|(name, raw)| {
-                let entry = match raw {
+        impl From<RawEntry> for AliasEntry {
+            fn from(raw: RawEntry) -> Self {
+                match raw {
                     RawEntry::Typed(e) => e,
                     RawEntry::Legacy(patterns) => AliasEntry {
                         syntax: Some(AliasSyntax::Glob),
@@ -254,15 +280,59 @@ impl<'de> Deserialize<'de> for AliasConfig {
                         value: AliasValue::Single(s),
                         description: None,
                     },
-                };
-                (name, entry)
-            })
-            .collect();
-        Ok(AliasConfig { entries })
+                }
+            }
+        }
+
+        /// A top-level value: an alias, or a scoped table of aliases (`[aliases.view]`).
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawTopLevel {
+            Entry(RawEntry),
+            Scope(HashMap<String, RawEntry>),
+        }
+
+        let raw: HashMap<String, RawTopLevel> = HashMap::deserialize(deserializer)?;
+        let mut config = AliasConfig::default();
+        for (name, raw) in raw {
+            match raw {
+                RawTopLevel::Entry(entry) => {
+                    config.entries.insert(name, entry.into());
+                }
+                RawTopLevel::Scope(table) => {
+                    let scope = table.into_iter().map(|(n, e)| (n, e.into())).collect();
+                    config.scopes.insert(name, scope);
+                }
+            }
+        }
+        Ok(config)
     }
 }
 
 impl AliasConfig {
+    /// Scope for aliases used in `--exclude` / `--only` patterns.
+    pub const FILTER_SCOPE: &'static str = "filter";
+
+    /// Scope for aliases used as path targets (`normalize view @todo`).
+    pub const TARGET_SCOPE: &'static str = "view";
+
+    /// The aliases visible to `command`: the global table with `[aliases.<command>]`
+    /// entries layered on top. Other scopes are dropped from the result.
+    pub fn scoped(&self, command: &str) -> AliasConfig {
+        let mut entries = self.entries.clone();
+        if let Some(scope) = self.scopes.get(command) {
+            entries.extend(
+                scope
+                    .iter()
+                    .map(|(name, entry)| (name.clone(), entry.clone())),
+            );
+        }
+        AliasConfig {
+            entries,
+            scopes: HashMap::new(),
+        }
+    }
+
     /// Names of all built-in aliases.
     pub fn builtin_names() -> &'static [&'static str] {
         &[
@@ -271,6 +341,9 @@ impl AliasConfig {
             "build",
             "docs",
             "generated",
+            "binary",
+            "minified",
+            "generated-content",
             "vocabulary",
             "stable-core",
             "unstable-core",
@@ -295,7 +368,7 @@ impl AliasConfig {
             }
             return match entry.resolved_syntax() {
                 AliasSyntax::Glob | AliasSyntax::Path => Some(entry.value.as_strings()),
-                AliasSyntax::Command | AliasSyntax::Sql => None,
+                AliasSyntax::Command | AliasSyntax::Sql | AliasSyntax::Content => None,
             };
         }
 
@@ -303,7 +376,7 @@ impl AliasConfig {
         let builtin = Self::builtin(name, languages)?;
         match builtin.resolved_syntax() {
             AliasSyntax::Glob | AliasSyntax::Path => Some(builtin.value.as_strings()),
-            AliasSyntax::Command | AliasSyntax::Sql => None,
+            AliasSyntax::Command | AliasSyntax::Sql | AliasSyntax::Content => None,
         }
     }
 
@@ -447,6 +520,23 @@ impl AliasConfig {
                 ),
                 description: Some("Generated code files".to_string()),
             }),
+            "binary" => Some(AliasEntry {
+                syntax: Some(AliasSyntax::Content),
+                value: AliasValue::Single("binary".to_string()),
+                description: Some("Binary files (NUL bytes in the first 8 KiB)".to_string()),
+            }),
+            "minified" => Some(AliasEntry {
+                syntax: Some(AliasSyntax::Content),
+                value: AliasValue::Single("minified".to_string()),
+                description: Some("Minified text such as bundled JS/CSS".to_string()),
+            }),
+            "generated-content" => Some(AliasEntry {
+                syntax: Some(AliasSyntax::Content),
+                value: AliasValue::Single("generated".to_string()),
+                description: Some(
+                    "Files with a generated-code header (@generated, DO NOT EDIT)".to_string(),
+                ),
+            }),
             "vocabulary" => Some(AliasEntry {
                 syntax: Some(AliasSyntax::Command),
                 value: AliasValue::Single(
@@ -491,9 +581,12 @@ pub enum FilterError {
     UnknownAlias(String),
     /// An alias exists but has the wrong syntax for use as a filter pattern.
     #[error(
-        "alias @{name} has syntax '{syntax}' and cannot be used as a filter pattern (only glob/path aliases work with --exclude/--only)"
+        "alias @{name} has syntax '{syntax}' and cannot be used as a filter pattern (only glob/path/content aliases work with --exclude/--only)"
     )]
     WrongSyntax { name: String, syntax: String },
+    /// A size or duration literal for a metadata predicate could not be parsed.
+    #[error("invalid filter value '{value}': {reason}")]
+    InvalidPredicate { value: String, reason: String },
 }
 
 impl From<FilterError> for String {
@@ -540,6 +633,8 @@ pub struct ResolvedAlias {
     pub value: AliasValue,
     pub description: Option<String>,
     pub status: AliasStatus,
+    /// Command scope (`[aliases.<scope>]`), or `None` for the global table.
+    pub scope: Option<String>,
 }
 
 /// Result of resolving a filter value.
@@ -547,21 +642,51 @@ pub struct ResolvedAlias {
 pub enum AliasResolution {
     /// Resolved to glob patterns
     Patterns(Vec<String>),
+    /// Resolved to content classes, checked by reading the file
+    Content(Vec<ContentClass>),
     /// Alias not found
     UnknownAlias(String),
     /// Alias is disabled (empty value)
     DisabledAlias(String),
     /// Alias exists but has wrong syntax for filter use
     WrongSyntax { name: String, syntax: AliasSyntax },
+    /// Content alias names a classifier that does not exist
+    UnknownContentClass { name: String, value: String },
+}
+
+/// Whether a walker needs to look inside a directory, from [`Filter::matches_dir`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirDecision {
+    /// Nothing under the directory can pass the filter; prune it.
+    Skip,
+    /// Something under the directory may pass the filter; walk into it.
+    Descend,
 }
 
 /// Filter engine that resolves aliases and matches paths.
-#[derive(Debug)]
+///
+/// Patterns follow gitignore semantics: a pattern containing `/` (other than a trailing
+/// one) is anchored to the root, a trailing `/` matches directories only, and `!pattern`
+/// re-includes paths matched by an earlier pattern. A pattern matching a directory also
+/// matches everything beneath it.
+#[derive(Debug, Clone)]
 pub struct Filter {
     /// Compiled exclude patterns
     exclude_matcher: Option<Gitignore>,
     /// Compiled include patterns (only mode)
     only_matcher: Option<Gitignore>,
+    /// Directory prefixes under which an exclude `!` pattern may re-include paths
+    exclude_reincludes: Vec<String>,
+    /// Directory prefixes under which an only pattern may match (`None` without --only)
+    only_scopes: Option<Vec<String>>,
+    /// Size/age predicates resolved against the project root
+    predicates: Option<predicate::ResolvedPredicates>,
+    /// Content classes (`@binary`, ...) that exclude a file
+    exclude_content: Vec<ContentClass>,
+    /// Content classes that include a file in only mode, alongside the only patterns
+    only_content: Vec<ContentClass>,
+    /// Classifies files for the content classes, caching per path
+    classifier: Arc<ContentClassifier>,
     /// Warnings accumulated during construction
     warnings: Vec<String>,
 }
@@ -569,7 +694,8 @@ pub struct Filter {
 impl Filter {
     /// Create a new filter from exclude/only patterns.
     ///
-    /// Patterns starting with `@` are resolved as aliases (glob/path syntax only).
+    /// Patterns starting with `@` are resolved as aliases (glob/path/content syntax),
+    /// with `[aliases.filter]` entries taking precedence over the global table.
     /// Returns warnings for disabled aliases.
     pub fn new(
         exclude: &[String],
@@ -577,79 +703,227 @@ impl Filter {
         config: &AliasConfig,
         languages: &[&str],
     ) -> Result<Self, FilterError> {
+        let config = &config.scoped(AliasConfig::FILTER_SCOPE);
         let mut warnings = Vec::new();
 
-        let exclude_matcher = if exclude.is_empty() {
+        let ResolvedPatterns {
+            globs: exclude_patterns,
+            content: exclude_content,
+        } = resolve_patterns(exclude, config, languages, &mut warnings)?;
+        let exclude_matcher = if exclude_patterns.is_empty() {
             None
         } else {
-            let patterns = resolve_patterns(exclude, config, languages, &mut warnings)?;
-            if patterns.is_empty() {
-                None
-            } else {
-                Some(build_matcher(&patterns)?)
-            }
+            Some(build_matcher(&exclude_patterns)?)
         };
+        let exclude_reincludes = exclude_patterns
+            .iter()
+            .filter_map(|p| p.strip_prefix('!'))
+            .map(scope_prefix)
+            .collect();
 
-        let only_matcher = if only.is_empty() {
+        let ResolvedPatterns {
+            globs: only_patterns,
+            content: only_content,
+        } = resolve_patterns(only, config, languages, &mut warnings)?;
+        let only_matcher = if only_patterns.is_empty() {
             None
         } else {
-            let patterns = resolve_patterns(only, config, languages, &mut warnings)?;
-            if patterns.is_empty() {
-                None
-            } else {
-                Some(build_matcher(&patterns)?)
+            Some(build_matcher(&only_patterns)?)
+        };
+        let only_scopes = if only_patterns.is_empty() && only_content.is_empty() {
+            None
+        } else {
+            let mut scopes: Vec<String> = only_patterns
+                .iter()
+                .filter(|p| !p.starts_with('!'))
+                .map(|p| scope_prefix(p))
+                .collect();
+            // A file anywhere may have the content an alias selects
+            if !only_content.is_empty() {
+                scopes.push(String::new());
             }
+            Some(scopes)
         };
 
         Ok(Self {
             exclude_matcher,
             only_matcher,
+            exclude_reincludes,
+            only_scopes,
+            predicates: None,
+            exclude_content,
+            only_content,
+            classifier: Arc::new(ContentClassifier::new(None)),
             warnings,
         })
     }
 
+    /// Create a filter for the project at `root`, detecting its languages with
+    /// [`detect_languages`] so language-aware aliases such as `@tests` expand correctly.
+    /// Content aliases read files relative to `root`.
+    #[cfg(feature = "detect")]
+    pub fn for_project(
+        exclude: &[String],
+        only: &[String],
+        config: &AliasConfig,
+        root: &Path,
+    ) -> Result<Self, FilterError> {
+        // Language detection walks the tree, so skip it when there is nothing to resolve
+        let languages = if exclude.is_empty() && only.is_empty() {
+            Vec::new()
+        } else {
+            detect_languages(root)
+        };
+        Ok(Self::new(exclude, only, config, &languages)?.with_root(root))
+    }
+
+    /// Resolve relative paths against `root` when reading files for content aliases
+    /// such as `@binary`. Without a root they are read relative to the working directory.
+    pub fn with_root(mut self, root: &Path) -> Self {
+        self.classifier = Arc::new(ContentClassifier::new(Some(root)));
+        self
+    }
+
+    /// Add size/age predicates, checked by [`matches`](Self::matches) against files under
+    /// `root`. For [`FilterPredicate::ModifiedSince`] this runs git once to collect recently
+    /// changed files, falling back to mtimes outside a repository.
+    pub fn with_predicates(mut self, root: &Path, predicates: &[FilterPredicate]) -> Self {
+        let resolved = predicate::ResolvedPredicates::resolve(root, predicates);
+        self.predicates = resolved.is_active().then_some(resolved);
+        self
+    }
+
     /// Get warnings from filter construction.
     pub fn warnings(&self) -> &[String] {
         &self.warnings
     }
 
     /// Check if a path should be included.
+    ///
+    /// `path` is relative to the project root; a leading `./` is ignored.
     pub fn matches(&self, path: &Path) -> bool {
-        if let Some(ref only) = self.only_matcher
-            && !only.matched(path, false).is_ignore()
+        let path = strip_current_dir(path);
+        if self.only_matcher.is_some() || !self.only_content.is_empty() {
+            let by_pattern = self
+                .only_matcher
+                .as_ref()
+                .is_some_and(|only| matched_with_parents(only, path, false));
+            if !by_pattern && !self.has_content_class(path, &self.only_content) {
+                return false;
+            }
+        }
+        if let Some(ref exclude) = self.exclude_matcher
+            && matched_with_parents(exclude, path, false)
         {
             return false;
         }
-        if let Some(ref exclude) = self.exclude_matcher
-            && exclude.matched(path, false).is_ignore()
+        if let Some(ref predicates) = self.predicates
+            && !predicates.matches(path)
         {
             return false;
         }
-        true
+        // Reading the file is the most expensive check, so it comes last
+        !self.has_content_class(path, &self.exclude_content)
+    }
+
+    /// Whether the file at `path` belongs to any of `classes`; reads it only if needed.
+    fn has_content_class(&self, path: &Path, classes: &[ContentClass]) -> bool {
+        if classes.is_empty() {
+            return false;
+        }
+        let found = self.classifier.classify(path);
+        classes.iter().any(|&class| found.contains(class))
+    }
+
+    /// Check whether a walker needs to descend into a directory.
+    ///
+    /// Returns [`DirDecision::Skip`] only when no path beneath `dir` can pass
+    /// [`matches`](Self::matches): the directory is excluded and no `!` pattern could
+    /// re-include something inside it, or every `--only` pattern is anchored elsewhere.
+    /// Unanchored patterns such as `*.rs` can match anywhere, so they never prune.
+    pub fn matches_dir(&self, dir: &Path) -> DirDecision {
+        let dir = strip_current_dir(dir);
+        let Some(dir_str) = dir.to_str() else {
+            return DirDecision::Descend;
+        };
+        if dir_str.is_empty() {
+            return DirDecision::Descend;
+        }
+        let dir_prefix = format!("{}/", dir_str.trim_end_matches('/'));
+
+        if let Some(ref exclude) = self.exclude_matcher
+            && matched_with_parents(exclude, dir, true)
+            && !self
+                .exclude_reincludes
+                .iter()
+                .any(|scope| scopes_overlap(scope, &dir_prefix))
+        {
+            return DirDecision::Skip;
+        }
+
+        if let Some(ref scopes) = self.only_scopes
+            && !scopes
+                .iter()
+                .any(|scope| scopes_overlap(scope, &dir_prefix))
+        {
+            return DirDecision::Skip;
+        }
+
+        DirDecision::Descend
     }
 
     /// Check if any filters are active.
     #[allow(dead_code)]
     pub fn is_active(&self) -> bool {
-        self.exclude_matcher.is_some() || self.only_matcher.is_some()
+        self.exclude_matcher.is_some()
+            || self.only_matcher.is_some()
+            || self.predicates.is_some()
+            || !self.exclude_content.is_empty()
+            || !self.only_content.is_empty()
     }
 }
 
+/// Patterns after alias expansion: globs for the matcher plus content classes.
+struct ResolvedPatterns {
+    globs: Vec<String>,
+    content: Vec<ContentClass>,
+}
+
 /// Resolve patterns, expanding aliases.
 fn resolve_patterns(
     patterns: &[String],
     config: &AliasConfig,
     languages: &[&str],
     warnings: &mut Vec<String>,
-) -> Result<Vec<String>, FilterError> {
+) -> Result<ResolvedPatterns, FilterError> {
     let mut result = Vec::new();
+    let mut content = Vec::new();
 
     for pattern in patterns {
-        if let Some(alias_name) = pattern.strip_prefix('@') {
+        // `!@alias` re-includes everything the alias matches
+        let (negated, name) = match pattern.strip_prefix('!') {
+            Some(rest) if rest.starts_with('@') => (true, rest),
+            _ => (false, pattern.as_str()),
+        };
+        if let Some(alias_name) = name.strip_prefix('@') {
             match resolve_alias(alias_name, config, languages) {
+                AliasResolution::Patterns(ps) if negated => {
+                    result.extend(ps.iter().map(|p| match p.strip_prefix('!') {
+                        Some(positive) => positive.to_string(),
+                        None => format!("!{p}"),
+                    }));
+                }
                 AliasResolution::Patterns(ps) => {
                     result.extend(ps);
                 }
+                AliasResolution::Content(_) if negated => {
+                    return Err(FilterError::InvalidPatternHint(format!(
+                        "'{pattern}' cannot be negated — content aliases select files by what they contain, not by path"
+                    )));
+                }
+                AliasResolution::Content(classes) => {
+                    content.extend(classes);
+                }
                 AliasResolution::UnknownAlias(name) => {
                     return Err(FilterError::UnknownAlias(name));
                 }
@@ -662,6 +936,14 @@ fn resolve_patterns(
                         syntax: syntax.to_string(),
                     });
                 }
+                AliasResolution::UnknownContentClass { name, value } => {
+                    return Err(FilterError::InvalidPattern {
+                        pattern: format!("@{name}"),
+                        reason: format!(
+                            "unknown content class '{value}' (expected binary, minified or generated)"
+                        ),
+                    });
+                }
             }
         } else if looks_like_language_name(pattern) {
             let matched_lang = languages
@@ -682,7 +964,10 @@ fn resolve_patterns(
         }
     }
 
-    Ok(result)
+    Ok(ResolvedPatterns {
+        globs: result,
+        content,
+    })
 }
 
 /// Returns true if `pattern` looks like a bare language name rather than a glob.
@@ -703,6 +988,25 @@ fn resolve_alias(name: &str, config: &AliasConfig, languages: &[&str]) -> AliasR
         return AliasResolution::DisabledAlias(name.to_string());
     }
 
+    // Content aliases resolve to classifier names rather than patterns
+    if config.syntax_of(name) == Some(AliasSyntax::Content)
+        && let Some(entry) = config.get_entry_resolved(name, languages)
+    {
+        let mut classes = Vec::new();
+        for value in entry.value.as_strings() {
+            match ContentClass::from_name(&value) {
+                Some(class) => classes.push(class),
+                None => {
+                    return AliasResolution::UnknownContentClass {
+                        name: name.to_string(),
+                        value,
+                    };
+                }
+            }
+        }
+        return AliasResolution::Content(classes);
+    }
+
     // Check if it exists but has wrong syntax for filter use
     if let Some(syntax) = config.syntax_of(name)
         && !matches!(syntax, AliasSyntax::Glob | AliasSyntax::Path)
@@ -738,6 +1042,56 @@ fn build_matcher(patterns: &[String]) -> Result<Gitignore, FilterError> {
     })
 }
 
+/// Strip leading `./` components so relative paths line up with anchored patterns.
+fn strip_current_dir(path: &Path) -> &Path {
+    let mut path = path;
+    while let Ok(rest) = path.strip_prefix(".") {
+        if rest == path {
+            break;
+        }
+        path = rest;
+    }
+    path
+}
+
+/// Whether `path` or, if it has no match of its own, its nearest matching parent
+/// directory is matched by `matcher`.
+///
+/// Absolute paths are matched on their own: the parent walk requires a relative path.
+fn matched_with_parents(matcher: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    if path.has_root() {
+        matcher.matched(path, is_dir).is_ignore()
+    } else {
+        matcher
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    }
+}
+
+/// The directory prefix (with trailing `/`) that a pattern is confined to, or `""` if it
+/// can match anywhere. Only anchored patterns — those with a `/` before the end — are
+/// confined: `vendor/keep/**` → `vendor/keep/`, `/src` → `src/`, `*.rs` → `""`.
+fn scope_prefix(pattern: &str) -> String {
+    let trimmed = pattern.trim_end_matches('/');
+    if !trimmed.contains('/') {
+        return String::new();
+    }
+    let pattern = trimmed.trim_start_matches('/');
+    match pattern.find(['*', '?', '[', '{', '\\']) {
+        // Literal pattern: confined to the path itself and what lies beneath it
+        None => format!("{pattern}/"),
+        Some(i) => match pattern[..i].rfind('/') {
+            Some(slash) => pattern[..=slash].to_string(),
+            None => String::new(),
+        },
+    }
+}
+
+/// Whether a pattern confined to `scope` may match something at or under `dir_prefix`.
+fn scopes_overlap(scope: &str, dir_prefix: &str) -> bool {
+    scope.is_empty() || scope.starts_with(dir_prefix) || dir_prefix.starts_with(scope)
+}
+
 /// Get all resolved aliases for display.
 pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAlias> {
     let mut aliases = Vec::new();
@@ -752,6 +1106,7 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
                     value: AliasValue::Multiple(vec![]),
                     description: user_entry.description.clone(),
                     status: AliasStatus::Disabled,
+                    scope: None,
                 });
             } else {
                 aliases.push(ResolvedAlias {
@@ -760,6 +1115,7 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
                     value: user_entry.value.clone(),
                     description: user_entry.description.clone(),
                     status: AliasStatus::Overridden,
+                    scope: None,
                 });
             }
         } else if let Some(builtin) = AliasConfig::builtin(name, languages) {
@@ -769,6 +1125,7 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
                 value: builtin.value,
                 description: builtin.description,
                 status: AliasStatus::Builtin,
+                scope: None,
             });
         }
     }
@@ -782,6 +1139,20 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
                 value: entry.value.clone(),
                 description: entry.description.clone(),
                 status: AliasStatus::Custom,
+                scope: None,
+            });
+        }
+    }
+
+    for (scope, table) in &config.scopes {
+        for (name, entry) in table {
+            aliases.push(ResolvedAlias {
+                name: name.clone(),
+                syntax: entry.resolved_syntax(),
+                value: entry.value.clone(),
+                description: entry.description.clone(),
+                status: AliasStatus::Custom,
+                scope: Some(scope.clone()),
             });
         }
     }
@@ -798,7 +1169,8 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
         match (a_builtin, b_builtin) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.cmp(&b.name),
+            // Global aliases before scoped ones, then by scope and name
+            _ => (&a.scope, &a.name).cmp(&(&b.scope, &b.name)),
         }
     });
 
@@ -810,58 +1182,82 @@ pub fn list_aliases(config: &AliasConfig, languages: &[&str]) -> Vec<ResolvedAli
 /// Does not hard-error so a config typo does not break all of normalize.
 pub fn validate_aliases(config: &AliasConfig) {
     for (name, entry) in &config.entries {
-        if entry.syntax.is_none() {
-            let inferred = entry.resolved_syntax();
+        validate_entry(name, entry);
+    }
+    for (scope, table) in &config.scopes {
+        if !KNOWN_SUBCOMMANDS.contains(&scope.as_str()) {
             tracing::warn!(
-                "alias @{}: missing 'syntax' field; inferred as '{}'. \
-                 Add `syntax = \"{}\"` to silence this warning.",
-                name,
-                inferred,
-                inferred,
+                "aliases.{}: has no 'value' so it is read as a per-command alias table, \
+                 but '{}' is not a normalize command",
+                scope,
+                scope,
             );
         }
-        match entry.resolved_syntax() {
-            AliasSyntax::Glob => {
-                for pattern in entry.value.as_strings() {
-                    let mut builder = GitignoreBuilder::new("");
-                    if let Err(e) = builder.add_line(None, &pattern) {
-                        tracing::warn!(
-                            "alias @{}: invalid glob pattern '{}': {}",
-                            name,
-                            pattern,
-                            e
-                        );
-                    }
+        for (name, entry) in table {
+            validate_entry(&format!("{name} (in [aliases.{scope}])"), entry);
+        }
+    }
+}
+
+/// Warn about problems in a single alias entry; `name` is used in messages only.
+fn validate_entry(name: &str, entry: &AliasEntry) {
+    if entry.syntax.is_none() {
+        let inferred = entry.resolved_syntax();
+        tracing::warn!(
+            "alias @{}: missing 'syntax' field; inferred as '{}'. \
+             Add `syntax = \"{}\"` to silence this warning.",
+            name,
+            inferred,
+            inferred,
+        );
+    }
+    match entry.resolved_syntax() {
+        AliasSyntax::Glob => {
+            for pattern in entry.value.as_strings() {
+                let mut builder = GitignoreBuilder::new("");
+                if let Err(e) = builder.add_line(None, &pattern) {
+                    tracing::warn!("alias @{}: invalid glob pattern '{}': {}", name, pattern, e);
                 }
             }
-            AliasSyntax::Command => {
-                let cmd = match &entry.value {
-                    AliasValue::Single(s) => s.clone(),
-                    AliasValue::Multiple(v) => v.join(" "),
-                };
-                if cmd.is_empty() {
-                    tracing::warn!("alias @{}: command value is empty", name);
-                } else if let Err(e) = shell_words::split(&cmd) {
+        }
+        AliasSyntax::Command => {
+            let cmd = match &entry.value {
+                AliasValue::Single(s) => s.clone(),
+                AliasValue::Multiple(v) => v.join(" "),
+            };
+            if cmd.is_empty() {
+                tracing::warn!("alias @{}: command value is empty", name);
+            } else if let Err(e) = shell_words::split(&cmd) {
+                tracing::warn!(
+                    "alias @{}: invalid shell syntax in command value: {}",
+                    name,
+                    e
+                );
+            }
+        }
+        AliasSyntax::Path => {
+            if entry.value.is_empty() {
+                tracing::warn!("alias @{}: path value is empty", name);
+            }
+        }
+        AliasSyntax::Content => {
+            for value in entry.value.as_strings() {
+                if ContentClass::from_name(&value).is_none() {
                     tracing::warn!(
-                        "alias @{}: invalid shell syntax in command value: {}",
+                        "alias @{}: unknown content class '{}' (expected binary, minified or generated)",
                         name,
-                        e
+                        value
                     );
                 }
             }
-            AliasSyntax::Path => {
-                if entry.value.is_empty() {
-                    tracing::warn!("alias @{}: path value is empty", name);
-                }
-            }
-            AliasSyntax::Sql => {
-                let sql = match &entry.value {
-                    AliasValue::Single(s) => s.clone(),
-                    AliasValue::Multiple(v) => v.join(" "),
-                };
-                if sql.is_empty() {
-                    tracing::warn!("alias @{}: sql value is empty", name);
-                }
+        }
+        AliasSyntax::Sql => {
+            let sql = match &entry.value {
+                AliasValue::Single(s) => s.clone(),
+                AliasValue::Multiple(v) => v.join(" "),
+            };
+            if sql.is_empty() {
+                tracing::warn!("alias @{}: sql value is empty", name);
             }
         }
     }
@@ -1182,4 +1578,255 @@ my-cmd = "view src/"
         };
         assert_eq!(explicit.resolved_syntax(), AliasSyntax::Path);
     }
+
+    fn patterns(ps: &[&str]) -> Vec<String> {
+        ps.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matches_dir_prunes_excluded_tree() {
+        let config = AliasConfig::default();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&patterns(&["vendor/"]), &[], &config, &[]).unwrap();
+
+        assert_eq!(filter.matches_dir(Path::new("vendor")), DirDecision::Skip);
+        assert_eq!(
+            filter.matches_dir(Path::new("lib/vendor")),
+            DirDecision::Skip
+        );
+        assert_eq!(filter.matches_dir(Path::new("src")), DirDecision::Descend);
+        // Files beneath an excluded directory are excluded too
+        assert!(!filter.matches(Path::new("vendor/a/b.rs")));
+        assert!(!filter.matches(Path::new("./vendor/a/b.rs")));
+        assert!(filter.matches(Path::new("src/vendor.rs")));
+    }
+
+    #[test]
+    fn test_matches_dir_keeps_reincluded_tree() {
+        let config = AliasConfig::default();
+        let exclude = patterns(&["vendor/**", "!vendor/keep/**"]);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&exclude, &[], &config, &[]).unwrap();
+
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor")),
+            DirDecision::Descend
+        );
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor/keep")),
+            DirDecision::Descend
+        );
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor/other")),
+            DirDecision::Skip
+        );
+        assert!(filter.matches(Path::new("vendor/keep/a.rs")));
+        assert!(!filter.matches(Path::new("vendor/other/a.rs")));
+    }
+
+    #[test]
+    fn test_matches_dir_unanchored_reinclude_never_prunes() {
+        let config = AliasConfig::default();
+        let exclude = patterns(&["vendor/**", "!*.proto"]);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&exclude, &[], &config, &[]).unwrap();
+
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor/deep")),
+            DirDecision::Descend
+        );
+        assert!(filter.matches(Path::new("vendor/deep/api.proto")));
+        assert!(!filter.matches(Path::new("vendor/deep/api.rs")));
+    }
+
+    #[test]
+    fn test_matches_dir_only_anchored() {
+        let config = AliasConfig::default();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&[], &patterns(&["src/**/*.rs"]), &config, &[]).unwrap();
+
+        assert_eq!(filter.matches_dir(Path::new("src")), DirDecision::Descend);
+        assert_eq!(
+            filter.matches_dir(Path::new("src/nested")),
+            DirDecision::Descend
+        );
+        assert_eq!(filter.matches_dir(Path::new("docs")), DirDecision::Skip);
+        assert!(filter.matches(Path::new("src/nested/lib.rs")));
+
+        // Unanchored only patterns may match anywhere
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&[], &patterns(&["*.rs"]), &config, &[]).unwrap();
+        assert_eq!(filter.matches_dir(Path::new("docs")), DirDecision::Descend);
+    }
+
+    #[test]
+    fn test_negated_alias() {
+        let mut config = AliasConfig::default();
+        config.entries.insert(
+            "generated".to_string(),
+            AliasEntry {
+                syntax: Some(AliasSyntax::Glob),
+                value: AliasValue::Multiple(vec!["vendor/gen/**".to_string()]),
+                description: None,
+            },
+        );
+        let exclude = patterns(&["vendor/", "!@generated"]);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&exclude, &[], &config, &[]).unwrap();
+
+        assert!(filter.matches(Path::new("vendor/gen/types.rs")));
+        assert!(!filter.matches(Path::new("vendor/lib/a.rs")));
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor")),
+            DirDecision::Descend
+        );
+        assert_eq!(
+            filter.matches_dir(Path::new("vendor/lib")),
+            DirDecision::Skip
+        );
+    }
+
+    #[test]
+    fn test_with_predicates() {
+        let config = AliasConfig::default();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.path().join("big.rs"), vec![b'/'; 2048]).unwrap();
+
+        let filter = Filter::new(&[], &patterns(&["*.rs"]), &config, &[])
+            .unwrap()
+            .with_predicates(
+                dir.path(),
+                &[
+                    FilterPredicate::LargerThan(1024),
+                    // Not a git repository, so judged by mtime: both files are fresh
+                    FilterPredicate::ModifiedSince(std::time::Duration::from_secs(3600)),
+                ],
+            );
+
+        assert!(filter.matches(Path::new("small.rs")));
+        assert!(filter.matches(Path::new("./small.rs")));
+        assert!(!filter.matches(Path::new("big.rs")));
+        // Missing files have no mtime, so they fail the age check
+        assert!(!filter.matches(Path::new("missing.rs")));
+
+        let unfiltered = Filter::new(&[], &[], &config, &[]).unwrap();
+        assert!(
+            !unfiltered
+                .clone()
+                .with_predicates(dir.path(), &[])
+                .is_active()
+        );
+        assert!(
+            unfiltered
+                .with_predicates(dir.path(), &[FilterPredicate::LargerThan(1)])
+                .is_active()
+        );
+    }
+
+    #[test]
+    fn test_content_aliases() {
+        let config = AliasConfig::default();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("api.pb.rs"), "// @generated\n").unwrap();
+        std::fs::write(dir.path().join("schema.rs"), "// DO NOT EDIT\n").unwrap();
+        std::fs::write(dir.path().join("logo.png"), b"\x89PNG\0\0").unwrap();
+
+        let exclude = patterns(&["@binary", "@generated-content"]);
+        let filter = Filter::new(&exclude, &[], &config, &[])
+            .unwrap()
+            .with_root(dir.path());
+        assert!(filter.is_active());
+        assert!(filter.matches(Path::new("main.rs")));
+        assert!(!filter.matches(Path::new("schema.rs")));
+        assert!(!filter.matches(Path::new("logo.png")));
+        // Content aliases never prune directories
+        assert_eq!(filter.matches_dir(Path::new("src")), DirDecision::Descend);
+
+        // In only mode, content aliases and globs are alternatives
+        let only = patterns(&["*.png", "@generated-content"]);
+        let filter = Filter::new(&[], &only, &config, &[])
+            .unwrap()
+            .with_root(dir.path());
+        assert!(filter.matches(Path::new("logo.png")));
+        assert!(filter.matches(Path::new("api.pb.rs")));
+        assert!(!filter.matches(Path::new("main.rs")));
+        assert_eq!(filter.matches_dir(Path::new("src")), DirDecision::Descend);
+    }
+
+    #[test]
+    fn test_content_alias_errors() {
+        let mut config = AliasConfig::default();
+        config.entries.insert(
+            "blobs".to_string(),
+            AliasEntry {
+                syntax: Some(AliasSyntax::Content),
+                value: AliasValue::Multiple(vec!["binary".to_string(), "huge".to_string()]),
+                description: None,
+            },
+        );
+
+        let result = Filter::new(&patterns(&["@blobs"]), &[], &config, &[]);
+        assert!(
+            matches!(result, Err(FilterError::InvalidPattern { ref reason, .. }) if reason.contains("huge"))
+        );
+
+        let result = Filter::new(&patterns(&["vendor/", "!@binary"]), &[], &config, &[]);
+        assert!(matches!(result, Err(FilterError::InvalidPatternHint(_))));
+
+        // Content aliases cannot be expanded as globs
+        assert_eq!(config.get("binary"), None);
+    }
+
+    #[test]
+    fn test_scoped_aliases() {
+        let toml_str = r#"
+config = ["*.toml"]
+
+[view]
+config = [".normalize/config.toml"]
+todo = ["TODO.md"]
+
+[filter]
+vendor = ["vendor/**"]
+
+[vocabulary]
+syntax = "command"
+value = "view ."
+"#;
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let config: AliasConfig = toml::from_str(toml_str).unwrap();
+
+        // A table with `value` is still a typed entry, not a scope
+        assert_eq!(config.syntax_of("vocabulary"), Some(AliasSyntax::Command));
+        assert_eq!(config.scopes.len(), 2);
+
+        let view = config.scoped("view");
+        assert_eq!(
+            view.get("config"),
+            Some(vec![".normalize/config.toml".to_string()])
+        );
+        assert_eq!(view.get("todo"), Some(vec!["TODO.md".to_string()]));
+        assert_eq!(view.get("vendor"), None);
+
+        // Filters see the global `config` and their own `vendor`, not the view scope
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let filter = Filter::new(&patterns(&["@config", "@vendor"]), &[], &config, &[]).unwrap();
+        assert!(!filter.matches(Path::new("Cargo.toml")));
+        assert!(!filter.matches(Path::new("vendor/lib.rs")));
+        assert!(filter.matches(Path::new("TODO.md")));
+        assert!(matches!(
+            Filter::new(&patterns(&["@todo"]), &[], &config, &[]),
+            Err(FilterError::UnknownAlias(_))
+        ));
+
+        let listed = list_aliases(&config, &[]);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let todo = listed.iter().find(|a| a.name == "todo").unwrap();
+        assert_eq!(todo.scope.as_deref(), Some("view"));
+        assert_eq!(todo.status, AliasStatus::Custom);
+    }
 }
@@ -0,0 +1,222 @@
+//! Structured filter predicates: file size and modification age.
+//!
+//! Unlike glob patterns these need file metadata, so they are resolved against a
+//! project root via [`Filter::with_predicates`](crate::Filter::with_predicates).
+
+use crate::FilterError;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A metadata-based filter condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterPredicate {
+    /// Exclude files larger than this many bytes (`--exclude-larger-than`).
+    LargerThan(u64),
+    /// Only include files modified within this duration (`--only-modified-since`).
+    ///
+    /// Inside a git repository a file counts as modified if a commit in the window touched
+    /// it or it has uncommitted changes; elsewhere the file's mtime is used.
+    ModifiedSince(Duration),
+}
+
+impl FilterPredicate {
+    /// Parse an `--exclude-larger-than` value such as `500KB` or `1.5MB`.
+    pub fn larger_than(value: &str) -> Result<Self, FilterError> {
+        parse_size(value).map(Self::LargerThan)
+    }
+
+    /// Parse an `--only-modified-since` value such as `3d` or `2w`.
+    pub fn modified_since(value: &str) -> Result<Self, FilterError> {
+        parse_duration(value).map(Self::ModifiedSince)
+    }
+}
+
+/// Parse a size literal into bytes.
+///
+/// Accepts a number (fractions allowed) followed by an optional unit: `B`, `KB`, `MB`,
+/// `GB` or `TB`, case-insensitive. Units are binary (`1KB` = 1024 bytes); the `KiB`
+/// spellings and bare `K`/`M`/`G`/`T` are accepted too.
+pub fn parse_size(value: &str) -> Result<u64, FilterError> {
+    let invalid = |reason: &str| FilterError::InvalidPredicate {
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+    let (number, unit) = split_number(value.trim());
+    let number: f64 = number
+        .parse()
+        .map_err(|_| invalid("expected a number followed by a unit, e.g. 1MB"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return Err(invalid("unknown unit (expected B, KB, MB, GB or TB)")),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Parse a duration literal such as `30m`, `12h`, `3d` or `2w`.
+///
+/// Units: `s`, `m` (minutes), `h`, `d`, `w`, `y` (365 days).
+pub fn parse_duration(value: &str) -> Result<Duration, FilterError> {
+    let invalid = |reason: &str| FilterError::InvalidPredicate {
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+    let (number, unit) = split_number(value.trim());
+    let number: u64 = number
+        .parse()
+        .map_err(|_| invalid("expected a whole number followed by a unit, e.g. 2w"))?;
+    let seconds: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "y" => 365 * 24 * 60 * 60,
+        "" => return Err(invalid("missing unit (expected s, m, h, d, w or y)")),
+        _ => return Err(invalid("unknown unit (expected s, m, h, d, w or y)")),
+    };
+    Ok(Duration::from_secs(number.saturating_mul(seconds)))
+}
+
+/// Split `"1.5MB"` into `("1.5", "MB")`.
+fn split_number(value: &str) -> (&str, &str) {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    value.split_at(end)
+}
+
+/// Predicates resolved against a project root, ready for per-path checks.
+#[derive(Debug, Clone)]
+pub(crate) struct ResolvedPredicates {
+    root: PathBuf,
+    max_size: Option<u64>,
+    modified: Option<ModifiedCheck>,
+}
+
+#[derive(Debug, Clone)]
+enum ModifiedCheck {
+    /// Root-relative paths touched by git since the cutoff, plus uncommitted changes.
+    Git(HashSet<PathBuf>),
+    /// Files whose mtime is at or after the cutoff.
+    Mtime(SystemTime),
+}
+
+impl ResolvedPredicates {
+    /// Resolve predicates for `root`. Several size limits keep the smallest; several
+    /// windows keep the shortest. Reads the git history once up front (through gix) for
+    /// `ModifiedSince`.
+    pub(crate) fn resolve(root: &Path, predicates: &[FilterPredicate]) -> Self {
+        let mut max_size: Option<u64> = None;
+        let mut window: Option<Duration> = None;
+        for predicate in predicates {
+            match *predicate {
+                FilterPredicate::LargerThan(bytes) => {
+                    max_size = Some(max_size.map_or(bytes, |m| m.min(bytes)));
+                }
+                FilterPredicate::ModifiedSince(d) => {
+                    window = Some(window.map_or(d, |w| w.min(d)));
+                }
+            }
+        }
+        let modified = window.map(|window| {
+            let cutoff = SystemTime::now().checked_sub(window).unwrap_or(UNIX_EPOCH);
+            match git_modified_since(root, cutoff) {
+                Some(paths) => ModifiedCheck::Git(paths),
+                None => ModifiedCheck::Mtime(cutoff),
+            }
+        });
+        Self {
+            root: root.to_path_buf(),
+            max_size,
+            modified,
+        }
+    }
+
+    /// Check a root-relative path. Paths without file metadata (directories, paths that
+    /// do not exist) pass the size check and are judged on git history or mtime alone.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        if self.max_size.is_none() && self.modified.is_none() {
+            return true;
+        }
+        let full = self.root.join(path);
+        let metadata = std::fs::metadata(&full).ok();
+        if let Some(max) = self.max_size
+            && let Some(ref meta) = metadata
+            && meta.is_file()
+            && meta.len() > max
+        {
+            return false;
+        }
+        match &self.modified {
+            None => true,
+            Some(ModifiedCheck::Git(paths)) => paths.contains(path),
+            Some(ModifiedCheck::Mtime(cutoff)) => metadata
+                .and_then(|m| m.modified().ok())
+                .is_some_and(|mtime| mtime >= *cutoff),
+        }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.max_size.is_some() || self.modified.is_some()
+    }
+}
+
+/// Paths under `root` (relative to it) that git reports as changed since `cutoff`:
+/// touched by a commit, changed against `HEAD`, or untracked. `None` outside a repository.
+fn git_modified_since(root: &Path, cutoff: SystemTime) -> Option<HashSet<PathBuf>> {
+    let since = cutoff.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let paths = normalize_git::git_paths_changed_since(root, since.as_secs() as i64)?;
+    Some(paths.into_iter().map(PathBuf::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("10B").unwrap(), 10);
+        assert_eq!(parse_size("1KB").unwrap(), 1024);
+        assert_eq!(parse_size("1mb").unwrap(), 1024 * 1024);
+        assert_eq!(parse_size("1.5M").unwrap(), 1024 * 1024 * 3 / 2);
+        assert_eq!(parse_size("2 GiB").unwrap(), 2 << 30);
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("3 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(
+            parse_duration("2w").unwrap(),
+            Duration::from_secs(14 * 86400)
+        );
+        assert!(parse_duration("2").is_err());
+        assert!(parse_duration("1.5d").is_err());
+        assert!(parse_duration("3fortnights").is_err());
+    }
+
+    #[test]
+    fn test_size_predicate() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.txt"), "x").unwrap();
+        std::fs::write(dir.path().join("big.txt"), vec![b'x'; 4096]).unwrap();
+
+        let resolved =
+            ResolvedPredicates::resolve(dir.path(), &[FilterPredicate::LargerThan(1024)]);
+        assert!(resolved.matches(Path::new("small.txt")));
+        assert!(!resolved.matches(Path::new("big.txt")));
+        // Missing files have no size to compare
+        assert!(resolved.matches(Path::new("missing.txt")));
+    }
+}
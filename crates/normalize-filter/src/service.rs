@@ -9,12 +9,11 @@
 //! language-aware built-ins like `@tests` resolve correctly — without any
 //! dependency on the main crate's `NormalizeConfig`.
 
-use crate::{AliasConfig, Filter, list_aliases};
+use crate::{AliasConfig, Filter, FilterPredicate, detect_languages, list_aliases};
 use normalize_output::OutputFormatter;
 use schemars::JsonSchema;
 use serde::Serialize;
 use server_less::cli;
-use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 // =============================================================================
@@ -54,6 +53,9 @@ pub struct AliasReportEntry {
     /// Human-readable description.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Command the alias is scoped to (`[aliases.<scope>]`); absent for global aliases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 /// List of resolved aliases.
@@ -71,9 +73,13 @@ impl OutputFormatter for AliasesReport {
         let mut out = String::new();
         for alias in &self.aliases {
             out.push_str(&format!(
-                "@{} [{}, {}]",
+                "@{} [{}, {}",
                 alias.name, alias.status, alias.syntax
             ));
+            if let Some(scope) = &alias.scope {
+                out.push_str(&format!(", scope={scope}"));
+            }
+            out.push(']');
             if !alias.value.is_empty() {
                 if alias.value.len() == 1 {
                     out.push_str(&format!(": {}", alias.value[0]));
@@ -119,27 +125,6 @@ fn load_alias_config(root: &Path) -> AliasConfig {
     normalize_config_paths::load_section_hierarchical(root, "aliases")
 }
 
-/// Detect programming languages present under `root` (bounded depth walk).
-///
-/// Mirrors the main crate's detection: maps file paths to language names via the
-/// `normalize-languages` registry. Used so `@tests` expands to the right globs.
-fn detect_project_languages(root: &Path) -> Vec<String> {
-    let mut languages = HashSet::new();
-    let walker = ignore::WalkBuilder::new(root)
-        .max_depth(Some(5))
-        .hidden(false)
-        .git_ignore(true)
-        .build();
-    for entry in walker.flatten() {
-        if let Some(lang) = normalize_languages::support_for_path(entry.path()) {
-            languages.insert(lang.name().to_string());
-        }
-    }
-    let mut result: Vec<_> = languages.into_iter().collect();
-    result.sort();
-    result
-}
-
 // =============================================================================
 // Service
 // =============================================================================
@@ -180,21 +165,32 @@ impl FilterCliService {
     /// Examples:
     ///   normalize filter matches src/main.rs --only "*.rs"
     ///   normalize filter matches foo_test.go --exclude @tests
+    ///   normalize filter matches assets/logo.png --exclude-larger-than 1MB
     #[cli(display_with = "display_output")]
     pub fn matches(
         &self,
         #[param(positional, help = "Path to check")] path: String,
         #[param(help = "Exclude files matching pattern or alias")] exclude: Vec<String>,
         #[param(help = "Include only files matching pattern or alias")] only: Vec<String>,
+        #[param(help = "Exclude files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Include only files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
             String,
         >,
     ) -> Result<MatchReport, String> {
         let root_path = resolve_root(root)?;
         let config = load_alias_config(&root_path);
-        let languages = detect_project_languages(&root_path);
-        let lang_refs: Vec<&str> = languages.iter().map(String::as_str).collect();
-        let filter = Filter::new(&exclude, &only, &config, &lang_refs)?;
+        let mut predicates = Vec::new();
+        if let Some(size) = exclude_larger_than {
+            predicates.push(FilterPredicate::larger_than(&size)?);
+        }
+        if let Some(window) = only_modified_since {
+            predicates.push(FilterPredicate::modified_since(&window)?);
+        }
+        let filter = Filter::for_project(&exclude, &only, &config, &root_path)?
+            .with_predicates(&root_path, &predicates);
         for warning in filter.warnings() {
             eprintln!("warning: {warning}");
         }
@@ -218,9 +214,8 @@ impl FilterCliService {
     ) -> Result<AliasesReport, String> {
         let root_path = resolve_root(root)?;
         let config = load_alias_config(&root_path);
-        let languages = detect_project_languages(&root_path);
-        let lang_refs: Vec<&str> = languages.iter().map(String::as_str).collect();
-        let resolved = list_aliases(&config, &lang_refs);
+        let languages = detect_languages(&root_path);
+        let resolved = list_aliases(&config, &languages);
         let aliases = resolved
             .into_iter()
             .map(|a| AliasReportEntry {
@@ -229,11 +224,12 @@ impl FilterCliService {
                 status: a.status.to_string(),
                 value: a.value.as_strings(),
                 description: a.description,
+                scope: a.scope,
             })
             .collect();
         Ok(AliasesReport {
             aliases,
-            detected_languages: languages,
+            detected_languages: languages.into_iter().map(String::from).collect(),
         })
     }
 }
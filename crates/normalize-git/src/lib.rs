@@ -10,7 +10,8 @@
 //! `normalize-budget`, `normalize-ratchet`, `normalize-semantic`, and the main
 //! `normalize` crate.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::Path;
 
 // ── Repository open ──────────────────────────────────────────────────────────
@@ -792,6 +793,90 @@ pub fn git_file_churn_stats(root: &Path) -> HashMap<String, Vec<FileChurnEntry>>
     stats
 }
 
+// ── Blame-based line-range churn ─────────────────────────────────────────────
+
+/// Churn attributed to a line range by blaming the file at HEAD.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeChurn {
+    /// Distinct commits that last touched a line in the range.
+    pub commits: usize,
+    /// Unix timestamp of the newest such commit (0 if none).
+    pub last_modified: u64,
+    /// Distinct author emails among those commits.
+    pub authors: usize,
+}
+
+/// Blame `rel_path` at HEAD once and summarize the commits behind each
+/// 1-based inclusive line range in `ranges` (results are in the same order).
+///
+/// Only commits whose lines survive at HEAD are counted, so this measures how
+/// many changes shaped the current code rather than total historical edits.
+/// Returns `None` if the repository or file cannot be blamed.
+pub fn git_blame_range_churn(
+    root: &Path,
+    rel_path: &str,
+    ranges: &[(usize, usize)],
+) -> Option<Vec<RangeChurn>> {
+    let repo = open_repo(root)?;
+    let head_id = repo.head_id().ok()?;
+    let path_bstr: &gix::bstr::BStr = rel_path.as_bytes().into();
+    let outcome = repo
+        .blame_file(
+            path_bstr,
+            head_id.detach(),
+            gix::repository::blame_file::Options::default(),
+        )
+        .ok()?;
+
+    // commit -> (timestamp, author email), resolved once per commit.
+    let mut commit_info: HashMap<gix::hash::ObjectId, (u64, String)> = HashMap::new();
+    for entry in &outcome.entries {
+        commit_info.entry(entry.commit_id).or_insert_with(|| {
+            repo.find_object(entry.commit_id)
+                .ok()
+                .map(|obj| {
+                    let commit = obj.into_commit();
+                    let time = commit.time().map(|t| t.seconds.max(0) as u64).unwrap_or(0);
+                    let email = commit
+                        .author()
+                        .map(|a| String::from_utf8_lossy(a.email).into_owned())
+                        .unwrap_or_default();
+                    (time, email)
+                })
+                .unwrap_or_default()
+        });
+    }
+
+    let result = ranges
+        .iter()
+        .map(|&(start, end)| {
+            let mut commits = std::collections::HashSet::new();
+            let mut authors = std::collections::HashSet::new();
+            let mut last_modified = 0;
+            for entry in &outcome.entries {
+                // Blame entries are 0-based half-open; ranges are 1-based inclusive.
+                let entry_start = entry.start_in_blamed_file as usize + 1;
+                let entry_end = entry_start + entry.len.get() as usize - 1;
+                if entry_end < start || entry_start > end {
+                    continue;
+                }
+                if commits.insert(entry.commit_id)
+                    && let Some((time, email)) = commit_info.get(&entry.commit_id)
+                {
+                    last_modified = last_modified.max(*time);
+                    authors.insert(email.as_str());
+                }
+            }
+            RangeChurn {
+                commits: commits.len(),
+                last_modified,
+                authors: authors.len(),
+            }
+        })
+        .collect();
+    Some(result)
+}
+
 /// Added/deleted line counts from `count_diff_lines`.
 pub struct LineDiff {
     pub added: usize,
@@ -832,6 +917,241 @@ fn count_lines(data: &[u8]) -> usize {
     data.iter().filter(|&&b| b == b'\n').count() + 1
 }
 
+// ── Line-range history (`git log -L`) ────────────────────────────────────────
+
+/// A commit that changed lines inside a traced range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeCommit {
+    /// Full commit hash.
+    pub id: String,
+    pub author: String,
+    pub email: String,
+    /// Commit time (Unix seconds).
+    pub timestamp: i64,
+    /// First line of the commit message.
+    pub summary: String,
+    /// Path of the file in this commit (differs from the traced path across renames).
+    pub path: String,
+    /// The traced range as it stood after this commit (1-based inclusive).
+    pub start: usize,
+    pub end: usize,
+    /// Lines of the range added or rewritten by this commit.
+    pub added: usize,
+    /// Lines removed from the range by this commit.
+    pub deleted: usize,
+}
+
+/// Trace the commits that changed lines `start..=end` (1-based) of `rel_path` at HEAD.
+///
+/// Equivalent to `git log --first-parent -L start,end:rel_path`: each commit is
+/// diffed against its first parent, and the range is carried backwards through
+/// the diff hunks so that edits above it shift it rather than lose it. Renames
+/// are followed. The walk stops at the commit that introduced the range, or once
+/// `limit` commits have been collected. Results are newest first.
+pub fn git_log_line_range(
+    root: &Path,
+    rel_path: &str,
+    start: usize,
+    end: usize,
+    limit: usize,
+) -> Result<Vec<RangeCommit>, String> {
+    use gix::bstr::ByteSlice;
+
+    if start == 0 || end < start {
+        return Err(format!("invalid line range {start}..={end}"));
+    }
+    let repo = open_repo(root).ok_or_else(|| "not a git repository".to_string())?;
+    let mut commit_id = repo
+        .head_id()
+        .map_err(|e| format!("failed to resolve HEAD: {e}"))?
+        .detach();
+    let mut path = rel_path.to_string();
+    let (mut start, mut end) = (start, end);
+    let mut out = Vec::new();
+
+    while out.len() < limit {
+        let commit = repo
+            .find_object(commit_id)
+            .map_err(|e| format!("failed to read commit {commit_id}: {e}"))?
+            .into_commit();
+        let tree = commit.tree().map_err(|e| e.to_string())?;
+        let Some(blob_id) = blob_at(&tree, &path) else {
+            if out.is_empty() {
+                return Err(format!("{path} is not tracked at HEAD"));
+            }
+            break;
+        };
+
+        let parent = commit
+            .parent_ids()
+            .next()
+            .and_then(|id| id.object().ok())
+            .map(|obj| obj.into_commit());
+        let parent_tree = parent.as_ref().and_then(|p| p.tree().ok());
+        let parent_path = parent_tree.as_ref().and_then(|pt| {
+            if blob_at(pt, &path).is_some() {
+                Some(path.clone())
+            } else {
+                rename_source(&repo, pt, &tree, &path)
+            }
+        });
+        let parent_blob = parent_tree
+            .as_ref()
+            .zip(parent_path.as_deref())
+            .and_then(|(pt, p)| blob_at(pt, p));
+
+        // Unchanged file: the range carries over untouched.
+        if parent_blob == Some(blob_id)
+            && let (Some(p), Some(pp)) = (&parent, &parent_path)
+        {
+            commit_id = p.id;
+            path = pp.clone();
+            continue;
+        }
+
+        let new_text = read_blob_bytes(&repo, blob_id).unwrap_or_default();
+        let old_text = parent_blob
+            .and_then(|id| read_blob_bytes(&repo, id))
+            .unwrap_or_default();
+        let hunks = line_hunks(&old_text, &new_text);
+        let (old_range, added, deleted) = if parent_blob.is_some() {
+            map_range_through_hunks(&hunks, start, end)
+        } else {
+            (None, end - start + 1, 0)
+        };
+
+        if added > 0 || deleted > 0 {
+            let author = commit.author().ok();
+            out.push(RangeCommit {
+                id: commit_id.to_hex().to_string(),
+                author: author
+                    .as_ref()
+                    .map(|a| a.name.to_str_lossy().into_owned())
+                    .unwrap_or_default(),
+                email: author
+                    .as_ref()
+                    .map(|a| a.email.to_str_lossy().into_owned())
+                    .unwrap_or_default(),
+                timestamp: commit.time().map(|t| t.seconds).unwrap_or(0),
+                summary: commit
+                    .message()
+                    .map(|m| m.summary().to_str_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: path.clone(),
+                start,
+                end,
+                added,
+                deleted,
+            });
+        }
+
+        // The whole range was introduced here (or this is the root commit).
+        let (Some((old_start, old_end)), Some(p), Some(pp)) = (old_range, &parent, parent_path)
+        else {
+            break;
+        };
+        commit_id = p.id;
+        path = pp;
+        start = old_start;
+        end = old_end;
+    }
+
+    Ok(out)
+}
+
+/// Look up the blob id of `path` in `tree`.
+fn blob_at(tree: &gix::Tree<'_>, path: &str) -> Option<gix::hash::ObjectId> {
+    let entry = tree.lookup_entry_by_path(path).ok()??;
+    entry.mode().is_blob().then(|| entry.object_id())
+}
+
+/// Find the path `path` was renamed from between `parent` and `tree`, if any.
+fn rename_source(
+    repo: &gix::Repository,
+    parent: &gix::Tree<'_>,
+    tree: &gix::Tree<'_>,
+    path: &str,
+) -> Option<String> {
+    use gix::object::tree::diff::ChangeDetached;
+    let changes = repo
+        .diff_tree_to_tree(Some(parent), Some(tree), None)
+        .ok()?;
+    changes.into_iter().find_map(|change| match change {
+        ChangeDetached::Rewrite {
+            source_location,
+            location,
+            ..
+        } if location == path.as_bytes() => {
+            Some(String::from_utf8_lossy(&source_location).into_owned())
+        }
+        _ => None,
+    })
+}
+
+/// Line-level diff hunks between two blobs as 0-based half-open
+/// `(before, after)` line ranges, in file order.
+fn line_hunks(old: &[u8], new: &[u8]) -> Vec<(Range<u32>, Range<u32>)> {
+    use gix::diff::blob::{Algorithm, diff, intern::InternedInput, sources::byte_lines};
+    let input = InternedInput::new(byte_lines(old), byte_lines(new));
+    let mut hunks = Vec::new();
+    diff(
+        Algorithm::Histogram,
+        &input,
+        |before: Range<u32>, after: Range<u32>| hunks.push((before, after)),
+    );
+    hunks
+}
+
+/// Carry the 1-based inclusive range `start..=end` of the new file back to the old one.
+///
+/// Returns the range in the old file (`None` when every line in it was added),
+/// plus how many lines of the range the hunks added and deleted.
+fn map_range_through_hunks(
+    hunks: &[(Range<u32>, Range<u32>)],
+    start: usize,
+    end: usize,
+) -> (Option<(usize, usize)>, usize, usize) {
+    // Work 0-based half-open: new lines [lo, hi).
+    let (lo, hi) = (start as u32 - 1, end as u32);
+    let mut added = 0;
+    let mut deleted = 0;
+    // old line = new line + shift, for lines outside hunks.
+    let mut shift: i64 = 0;
+    let mut old_lo: Option<i64> = None;
+    let mut old_hi: Option<i64> = None;
+    for (before, after) in hunks {
+        let touches = if after.is_empty() {
+            // Pure deletion strictly between two lines of the range.
+            after.start > lo && after.start < hi
+        } else {
+            after.start < hi && after.end > lo
+        };
+        if touches {
+            added += (after.end.min(hi) - after.start.max(lo)) as usize;
+            deleted += before.len();
+        }
+        if old_lo.is_none() && lo < after.end {
+            old_lo = Some(if lo >= after.start {
+                before.start as i64
+            } else {
+                lo as i64 + shift
+            });
+        }
+        if old_hi.is_none() && hi <= after.end {
+            old_hi = Some(if hi > after.start {
+                before.end as i64
+            } else {
+                hi as i64 + shift
+            });
+        }
+        shift += before.len() as i64 - after.len() as i64;
+    }
+    let old_lo = old_lo.unwrap_or(lo as i64 + shift);
+    let old_hi = old_hi.unwrap_or(hi as i64 + shift);
+    let old_range = (old_lo < old_hi).then(|| (old_lo as usize + 1, old_hi as usize));
+    (old_range, added, deleted)
+}
+
 // ── Author shortlog ──────────────────────────────────────────────────────────
 
 /// One author's commit count in a repository.
@@ -1044,6 +1364,99 @@ pub fn git_per_commit_files(root: &Path) -> Vec<Vec<String>> {
     result
 }
 
+// ── Recently changed paths ───────────────────────────────────────────────────
+
+/// Return paths under `root` (relative to it) changed since `since` (seconds since
+/// the Unix epoch): touched by a commit made at or after `since`, changed against
+/// HEAD (staged or not), or untracked and not ignored.
+///
+/// Equivalent to the union of `git log --since=@<since> --name-only --relative`,
+/// `git diff HEAD --name-only --relative` and `git ls-files --others --exclude-standard`.
+/// Returns `None` if `root` is not inside a repository with a working tree.
+pub fn git_paths_changed_since(root: &Path, since: i64) -> Option<HashSet<String>> {
+    let repo = open_repo(root)?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let prefix = root.canonicalize().ok()?;
+    let prefix = prefix.strip_prefix(&workdir).ok()?;
+    let mut repo_paths: HashSet<String> = HashSet::new();
+
+    // An unborn HEAD has no history; uncommitted and untracked files still count.
+    if let Ok(head_id) = repo.head_id()
+        && let Ok(walk) = head_id
+            .ancestors()
+            .sorting(gix::revision::walk::Sorting::ByCommitTimeCutoff {
+                order: gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
+                seconds: since,
+            })
+            .all()
+    {
+        for info in walk {
+            let Ok(info) = info else { continue };
+            let Ok(commit) = info.object() else { continue };
+            let Ok(tree) = commit.tree() else { continue };
+            let parent_tree = info
+                .parent_ids()
+                .next()
+                .and_then(|pid| pid.object().ok())
+                .and_then(|obj| obj.into_commit().tree().ok());
+            let Ok(changes) = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            else {
+                continue;
+            };
+            for change in changes {
+                use gix::object::tree::diff::ChangeDetached;
+                let (location, entry_mode) = match change {
+                    ChangeDetached::Addition {
+                        location,
+                        entry_mode,
+                        ..
+                    }
+                    | ChangeDetached::Deletion {
+                        location,
+                        entry_mode,
+                        ..
+                    }
+                    | ChangeDetached::Modification {
+                        location,
+                        entry_mode,
+                        ..
+                    }
+                    | ChangeDetached::Rewrite {
+                        location,
+                        entry_mode,
+                        ..
+                    } => (location, entry_mode),
+                };
+                // Like `git log --name-only`, list files, not the directories holding them.
+                if entry_mode.is_tree() {
+                    continue;
+                }
+                repo_paths.insert(String::from_utf8_lossy(&location).into_owned());
+            }
+        }
+    }
+
+    if let Ok(status) = repo.status(gix::progress::Discard)
+        && let Ok(iter) = status
+            .untracked_files(gix::status::UntrackedFiles::Files)
+            .into_iter(Vec::new())
+    {
+        for item in iter.flatten() {
+            repo_paths.insert(String::from_utf8_lossy(item.location()).into_owned());
+        }
+    }
+
+    Some(
+        repo_paths
+            .into_iter()
+            .filter_map(|path| {
+                let rel = Path::new(&path).strip_prefix(prefix).ok()?;
+                Some(rel.to_string_lossy().into_owned())
+            })
+            .collect(),
+    )
+}
+
 // ── Date formatting ──────────────────────────────────────────────────────────
 
 /// Format a Unix timestamp as `YYYY-MM-DD` without shelling out to `date`.
@@ -1112,3 +1525,35 @@ where
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_shifts_past_edits_above_it() {
+        // Two lines inserted at the top: new 5..=7 was old 3..=5, untouched.
+        let hunks = vec![(0..0, 0..2)];
+        assert_eq!(map_range_through_hunks(&hunks, 5, 7), (Some((3, 5)), 0, 0));
+    }
+
+    #[test]
+    fn range_counts_lines_changed_inside_it() {
+        // Old line 4 replaced by new lines 4..=5.
+        let hunks = vec![(3..4, 3..5)];
+        assert_eq!(map_range_through_hunks(&hunks, 2, 8), (Some((2, 7)), 2, 1));
+    }
+
+    #[test]
+    fn range_entirely_added_has_no_old_range() {
+        let hunks = vec![(2..2, 2..6)];
+        assert_eq!(map_range_through_hunks(&hunks, 3, 6), (None, 4, 0));
+    }
+
+    #[test]
+    fn deletion_at_range_edge_is_outside_it() {
+        // Old lines 6..=7 deleted right after the range's last line (new 5).
+        let hunks = vec![(5..7, 5..5)];
+        assert_eq!(map_range_through_hunks(&hunks, 2, 5), (Some((2, 5)), 0, 0));
+    }
+}
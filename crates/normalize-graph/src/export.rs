@@ -0,0 +1,358 @@
+//! Dependency-graph export — the `graph export` CLI verb.
+//!
+//! Renders the module, symbol, or type graph from the index as Graphviz DOT,
+//! Mermaid, or JSON Graph Format so it can be dropped into an architecture
+//! review or a docs page. Nodes that sit in a dependency cycle (a non-trivial
+//! strongly connected component), and the edges between them, are highlighted
+//! in every format.
+
+use crate::report::{GraphTarget, build_graph};
+use crate::tarjan_sccs;
+use normalize_index::FileIndex;
+use normalize_output::OutputFormatter;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Output format for `graph export`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Graphviz DOT (`dot -Tsvg`)
+    Dot,
+    /// Mermaid flowchart (renders in GitHub/GitLab markdown)
+    Mermaid,
+    /// JSON Graph Format (jsongraphformat.info)
+    Json,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown export format '{}', expected 'dot', 'mermaid', or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dot => write!(f, "dot"),
+            Self::Mermaid => write!(f, "mermaid"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// A node in the exported graph.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ExportNode {
+    pub id: String,
+    /// Whether the node is part of a dependency cycle.
+    pub in_cycle: bool,
+}
+
+/// A directed edge in the exported graph.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ExportEdge {
+    pub from: String,
+    pub to: String,
+    /// Whether both endpoints are in the same cycle (the edge closes a loop).
+    pub in_cycle: bool,
+}
+
+/// Report for `normalize graph export`.
+///
+/// `--json` serializes the nodes, edges, and cycles directly; text output is the
+/// rendering selected by `format`.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GraphExportReport {
+    pub target: GraphTarget,
+    pub format: ExportFormat,
+    /// Nodes, sorted by id.
+    pub nodes: Vec<ExportNode>,
+    /// Edges, sorted by (from, to).
+    pub edges: Vec<ExportEdge>,
+    /// Dependency cycles (non-trivial SCCs), each sorted, largest first.
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl OutputFormatter for GraphExportReport {
+    fn format_text(&self) -> String {
+        match self.format {
+            ExportFormat::Dot => self.to_dot(),
+            ExportFormat::Mermaid => self.to_mermaid(),
+            ExportFormat::Json => self.to_json_graph(),
+        }
+    }
+}
+
+impl GraphExportReport {
+    /// Build an export from an adjacency list, keeping only nodes accepted by `keep`.
+    ///
+    /// Edges are kept when both endpoints are. Cycles are computed on the kept
+    /// subgraph, so highlighting always matches what is drawn. With `cycles_only`,
+    /// nodes outside any cycle are dropped as well.
+    pub fn build(
+        adj: &HashMap<String, HashSet<String>>,
+        target: GraphTarget,
+        format: ExportFormat,
+        keep: impl Fn(&str) -> bool,
+        cycles_only: bool,
+    ) -> Self {
+        let mut kept: HashMap<String, HashSet<String>> = HashMap::new();
+        for (from, tos) in adj {
+            if !keep(from) {
+                continue;
+            }
+            let entry = kept.entry(from.clone()).or_default();
+            entry.extend(tos.iter().filter(|to| keep(to)).cloned());
+        }
+        for tos in adj.values() {
+            for to in tos.iter().filter(|to| keep(to)) {
+                kept.entry(to.clone()).or_default();
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = tarjan_sccs(&kept)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .map(|mut scc| {
+                scc.sort();
+                scc
+            })
+            .collect();
+        cycles.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        let component: HashMap<&str, usize> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, scc)| scc.iter().map(move |n| (n.as_str(), i)))
+            .collect();
+
+        let ids: BTreeSet<&String> = kept
+            .keys()
+            .filter(|n| !cycles_only || component.contains_key(n.as_str()))
+            .collect();
+        let nodes = ids
+            .iter()
+            .map(|id| ExportNode {
+                id: (*id).clone(),
+                in_cycle: component.contains_key(id.as_str()),
+            })
+            .collect();
+        let mut edges: Vec<ExportEdge> = kept
+            .iter()
+            .filter(|(from, _)| ids.contains(from))
+            .flat_map(|(from, tos)| {
+                tos.iter().filter(|to| ids.contains(to)).map(|to| {
+                    let in_cycle = matches!(
+                        (component.get(from.as_str()), component.get(to.as_str())),
+                        (Some(a), Some(b)) if a == b
+                    );
+                    ExportEdge {
+                        from: from.clone(),
+                        to: to.clone(),
+                        in_cycle,
+                    }
+                })
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        Self {
+            target,
+            format,
+            nodes,
+            edges,
+            cycles,
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph dependencies {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box];\n");
+        for node in &self.nodes {
+            out.push_str(&format!("  {}", dot_id(&node.id)));
+            if node.in_cycle {
+                out.push_str(" [color=red, fontcolor=red]");
+            }
+            out.push_str(";\n");
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("  {} -> {}", dot_id(&edge.from), dot_id(&edge.to)));
+            if edge.in_cycle {
+                out.push_str(" [color=red]");
+            }
+            out.push_str(";\n");
+        }
+        out.push('}');
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        // Mermaid ids must be simple identifiers; labels carry the real names.
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut out = String::from("flowchart LR\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            out.push_str(&format!(
+                "  n{}[\"{}\"]\n",
+                i,
+                node.id.replace('"', "#quot;")
+            ));
+        }
+        let mut cycle_links = Vec::new();
+        for (i, edge) in self.edges.iter().enumerate() {
+            if let (Some(from), Some(to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            {
+                out.push_str(&format!("  n{} --> n{}\n", from, to));
+            }
+            if edge.in_cycle {
+                cycle_links.push(i.to_string());
+            }
+        }
+        let cycle_nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.in_cycle)
+            .map(|(i, _)| format!("n{}", i))
+            .collect();
+        if !cycle_nodes.is_empty() {
+            out.push_str("  classDef cycle stroke:#d33,stroke-width:2px,color:#d33\n");
+            out.push_str(&format!("  class {} cycle\n", cycle_nodes.join(",")));
+        }
+        if !cycle_links.is_empty() {
+            out.push_str(&format!(
+                "  linkStyle {} stroke:#d33,stroke-width:2px\n",
+                cycle_links.join(",")
+            ));
+        }
+        out.trim_end().to_string()
+    }
+
+    fn to_json_graph(&self) -> String {
+        let nodes: BTreeMap<&str, serde_json::Value> = self
+            .nodes
+            .iter()
+            .map(|n| {
+                (
+                    n.id.as_str(),
+                    serde_json::json!({ "label": n.id, "metadata": { "in_cycle": n.in_cycle } }),
+                )
+            })
+            .collect();
+        let edges: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "source": e.from,
+                    "target": e.to,
+                    "metadata": { "in_cycle": e.in_cycle },
+                })
+            })
+            .collect();
+        let graph = serde_json::json!({
+            "graph": {
+                "directed": true,
+                "type": self.target.to_string(),
+                "nodes": nodes,
+                "edges": edges,
+                "metadata": { "cycles": self.cycles },
+            }
+        });
+        serde_json::to_string_pretty(&graph).unwrap_or_default()
+    }
+}
+
+/// Build the requested graph from the index and export the part accepted by `keep`.
+pub async fn export_graph(
+    idx: &FileIndex,
+    target: GraphTarget,
+    format: ExportFormat,
+    keep: impl Fn(&str) -> bool,
+    cycles_only: bool,
+) -> Result<GraphExportReport, libsql::Error> {
+    let adj = build_graph(idx, target).await?;
+    Ok(GraphExportReport::build(
+        &adj,
+        target,
+        format,
+        keep,
+        cycles_only,
+    ))
+}
+
+/// Quote a string as a DOT identifier.
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> HashMap<String, HashSet<String>> {
+        let mut adj: HashMap<String, HashSet<String>> = HashMap::new();
+        for (from, to) in edges {
+            adj.entry(from.to_string())
+                .or_default()
+                .insert(to.to_string());
+        }
+        adj
+    }
+
+    #[test]
+    fn test_export_highlights_cycles_and_filters() {
+        let adj = graph(&[
+            ("src/a.rs", "src/b.rs"),
+            ("src/b.rs", "src/a.rs"),
+            ("src/b.rs", "src/c.rs"),
+            ("tests/t.rs", "src/a.rs"),
+        ]);
+        let report = GraphExportReport::build(
+            &adj,
+            GraphTarget::Modules,
+            ExportFormat::Dot,
+            |n| n.starts_with("src/"),
+            false,
+        );
+        let ids: Vec<&str> = report.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["src/a.rs", "src/b.rs", "src/c.rs"]);
+        assert_eq!(report.cycles, vec![vec!["src/a.rs", "src/b.rs"]]);
+        let cyclic: Vec<bool> = report.edges.iter().map(|e| e.in_cycle).collect();
+        assert_eq!(cyclic, [true, true, false]);
+
+        let dot = report.format_text();
+        assert!(dot.contains("\"src/a.rs\" -> \"src/b.rs\" [color=red];"));
+        assert!(dot.contains("\"src/b.rs\" -> \"src/c.rs\";"));
+
+        let cycles_only = GraphExportReport::build(
+            &adj,
+            GraphTarget::Modules,
+            ExportFormat::Mermaid,
+            |_| true,
+            true,
+        );
+        assert_eq!(cycles_only.nodes.len(), 2);
+        let mermaid = cycles_only.format_text();
+        assert!(mermaid.contains("n0[\"src/a.rs\"]"));
+        assert!(mermaid.contains("class n0,n1 cycle"));
+        assert!(mermaid.contains("linkStyle 0,1 "));
+    }
+}
@@ -14,10 +14,14 @@ use std::collections::{HashMap, HashSet, VecDeque};
 // formatting, and the server-less `GraphService`. Gated behind `cli` so
 // library consumers of the pure algorithms don't pull in the index/config stack.
 #[cfg(feature = "cli")]
+pub mod export;
+#[cfg(feature = "cli")]
 pub mod report;
 #[cfg(feature = "cli")]
 pub mod service;
 
+#[cfg(feature = "cli")]
+pub use export::{ExportEdge, ExportFormat, ExportNode, GraphExportReport, export_graph};
 #[cfg(feature = "cli")]
 pub use report::{
     DependentsReport, GraphReport, GraphStats, GraphTarget, ImportPathReport, analyze_dependents,
@@ -111,18 +111,22 @@ pub async fn analyze_graph(
     limit: usize,
     target: GraphTarget,
 ) -> Result<GraphReport, libsql::Error> {
-    let adj = match target {
-        GraphTarget::Modules => {
-            let graph = build_import_graph(idx).await?;
-            graph.imports_by_file
-        }
-        GraphTarget::Symbols => build_call_graph(idx).await?,
-        GraphTarget::Types => build_type_graph(idx).await?,
-    };
-
+    let adj = build_graph(idx, target).await?;
     Ok(assemble_graph_report(&adj, target, limit))
 }
 
+/// Build the adjacency list for the requested graph target from the index.
+pub(crate) async fn build_graph(
+    idx: &FileIndex,
+    target: GraphTarget,
+) -> Result<HashMap<String, HashSet<String>>, libsql::Error> {
+    match target {
+        GraphTarget::Modules => Ok(build_import_graph(idx).await?.imports_by_file),
+        GraphTarget::Symbols => build_call_graph(idx).await,
+        GraphTarget::Types => build_type_graph(idx).await,
+    }
+}
+
 /// Find all modules/symbols that (transitively) depend on a given file.
 ///
 /// For modules: returns structured output with depth, test coverage, fan-in,
@@ -1,8 +1,8 @@
 //! CLI service for the `graph` verb.
 //!
 //! Implements `normalize graph` (module/symbol/type graph analysis),
-//! `graph dependents`, and `graph import-path` via the server-less `#[cli]`
-//! pattern.
+//! `graph dependents`, `graph import-path`, and `graph export` via the
+//! server-less `#[cli]` pattern.
 //!
 //! The service owns its config access: it loads the `[index]`, `[walk]`, and
 //! `[pretty]` sections directly from the global and project `config.toml` files
@@ -10,7 +10,9 @@
 //! main crate's monolithic `NormalizeConfig`. Index acquisition goes through
 //! `normalize_index::require_import_graph`, which takes the config **slices**.
 
+use crate::export::{ExportFormat, GraphExportReport};
 use crate::report::{DependentsReport, GraphReport, GraphTarget, ImportPathReport};
+use normalize_filter::{AliasConfig, Filter};
 use normalize_index::{IndexConfig, require_import_graph};
 use normalize_output::{OutputFormatter, PrettyConfig};
 use normalize_rules_config::WalkConfig;
@@ -18,12 +20,13 @@ use server_less::cli;
 use std::cell::Cell;
 use std::path::Path;
 
-/// The `[index]` + `[walk]` + `[pretty]` config sections this service reads.
+/// The `[index]` + `[walk]` + `[pretty]` + `[aliases]` config sections this service reads.
 #[derive(Default)]
 struct GraphConfig {
     index: IndexConfig,
     walk: WalkConfig,
     pretty: PrettyConfig,
+    aliases: AliasConfig,
 }
 
 /// Load the relevant config sections from the global then project `config.toml`.
@@ -38,9 +41,28 @@ fn load_config(root: &Path) -> GraphConfig {
         index: slices.slice("index"),
         walk: slices.slice("walk"),
         pretty: slices.slice("pretty"),
+        aliases: slices.slice("aliases"),
     }
 }
 
+/// Build a `Filter` from `--exclude` / `--only` patterns, printing any warnings.
+/// Returns `None` when both slices are empty (no filtering needed).
+fn build_filter(
+    root: &Path,
+    aliases: &AliasConfig,
+    exclude: &[String],
+    only: &[String],
+) -> Result<Option<Filter>, String> {
+    if exclude.is_empty() && only.is_empty() {
+        return Ok(None);
+    }
+    let filter = Filter::for_project(exclude, only, aliases, root).map_err(|e| e.to_string())?;
+    for warning in filter.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+    Ok(Some(filter))
+}
+
 /// CLI service implementing `normalize graph` subcommands.
 pub struct GraphService {
     pretty: Cell<bool>,
@@ -98,6 +120,10 @@ impl GraphService {
     fn display_import_path(&self, r: &ImportPathReport) -> String {
         self.display_output(r)
     }
+
+    fn display_export(&self, r: &GraphExportReport) -> String {
+        self.display_output(r)
+    }
 }
 
 impl server_less::CliGlobals for GraphService {
@@ -216,4 +242,58 @@ impl GraphService {
         .await
         .map_err(|e| format!("Import path query failed: {}", e))
     }
+
+    /// Export the dependency graph as Graphviz DOT, Mermaid, or JSON Graph Format (requires facts index)
+    ///
+    /// Nodes in a dependency cycle, and the edges that close it, are highlighted in red.
+    /// Use --only/--exclude (globs, directories, or @aliases) to cut the graph down to
+    /// the part under review; edges are kept when both ends pass the filter.
+    ///
+    /// Examples:
+    ///   normalize graph export | dot -Tsvg > deps.svg              # Graphviz (default)
+    ///   normalize graph export --format mermaid --only 'src/core/**'   # one directory, Mermaid
+    ///   normalize graph export --format json --cycles              # only files in import cycles
+    #[cli(display_with = "display_export")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn export(
+        &self,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(help = "Output format: dot (default), mermaid, or json")] format: Option<
+            ExportFormat,
+        >,
+        #[param(help = "Graph nodes: modules (default), symbols, or types")] on: Option<
+            GraphTarget,
+        >,
+        #[param(help = "Exclude files matching pattern or @alias")] exclude: Vec<String>,
+        #[param(help = "Include only files matching pattern or @alias")] only: Vec<String>,
+        #[param(help = "Only include nodes that are part of a dependency cycle")] cycles: bool,
+    ) -> Result<GraphExportReport, String> {
+        let root_path = Self::root_path(root)?;
+        let cfg = load_config(&root_path);
+        self.resolve_format(&cfg);
+        let target = on.unwrap_or(GraphTarget::Modules);
+        let filter = build_filter(&root_path, &cfg.aliases, &exclude, &only)?;
+        if filter.is_some() && target == GraphTarget::Types {
+            return Err("--only/--exclude filter by file, but type graph nodes have no file; use --on modules or --on symbols".to_string());
+        }
+        let idx = self.acquire(&root_path, &cfg).await?;
+        // Symbol nodes are "file:symbol"; filters apply to the file part.
+        let keep = |node: &str| {
+            filter.as_ref().is_none_or(|f| {
+                let file = node.split_once(':').map_or(node, |(file, _)| file);
+                f.matches(Path::new(file))
+            })
+        };
+        crate::export::export_graph(
+            &idx,
+            target,
+            format.unwrap_or(ExportFormat::Dot),
+            keep,
+            cycles,
+        )
+        .await
+        .map_err(|e| format!("Graph export failed: {}", e))
+    }
 }
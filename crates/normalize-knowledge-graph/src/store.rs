@@ -111,7 +111,30 @@ type D = jaq_core::data::JustLut<jaq_json::Val>;
 type CompiledFilter = jaq_core::compile::Filter<jaq_core::Native<D>>;
 
 #[cfg(feature = "cli")]
-fn jq_compile(expr: &str) -> Result<CompiledFilter, String> {
+thread_local! {
+    /// Compiled filters by source. Predicates and transforms run once per unit,
+    /// so without this a single `--where` recompiles the same program N times.
+    static JQ_CACHE: std::cell::RefCell<std::collections::HashMap<String, std::rc::Rc<CompiledFilter>>> =
+        std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+/// Compile `expr`, reusing an earlier compilation of the same source.
+#[cfg(feature = "cli")]
+fn jq_compile(expr: &str) -> Result<std::rc::Rc<CompiledFilter>, String> {
+    if let Some(filter) = JQ_CACHE.with(|cache| cache.borrow().get(expr).cloned()) {
+        return Ok(filter);
+    }
+    let filter = std::rc::Rc::new(jq_compile_uncached(expr)?);
+    JQ_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert(expr.to_string(), std::rc::Rc::clone(&filter))
+    });
+    Ok(filter)
+}
+
+#[cfg(feature = "cli")]
+fn jq_compile_uncached(expr: &str) -> Result<CompiledFilter, String> {
     use jaq_core::load::{Arena, File, Loader};
 
     let arena = Arena::default();
@@ -653,6 +676,15 @@ mod tests {
         assert_eq!(parsed_links[0].to, "other-unit");
     }
 
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_jq_compile_reuses_programs() {
+        let a = jq_compile(".metadata.tag").unwrap();
+        let b = jq_compile(".metadata.tag").unwrap();
+        assert!(std::rc::Rc::ptr_eq(&a, &b));
+        assert!(jq_compile(".metadata |").is_err());
+    }
+
     #[test]
     fn test_parse_no_frontmatter() {
         let contents = "Just body text\n";
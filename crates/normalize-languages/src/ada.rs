@@ -17,6 +17,9 @@ impl Language for Ada {
     fn grammar_name(&self) -> &'static str {
         "ada"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("--")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Batch {
     fn grammar_name(&self) -> &'static str {
         "batch"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("REM")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
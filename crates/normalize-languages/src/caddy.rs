@@ -16,6 +16,9 @@ impl Language for Caddy {
     fn grammar_name(&self) -> &'static str {
         "caddy"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Capnp {
     fn grammar_name(&self) -> &'static str {
         "capnp"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -18,6 +18,9 @@ impl Language for Clojure {
     fn grammar_name(&self) -> &'static str {
         "clojure"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some(";;")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
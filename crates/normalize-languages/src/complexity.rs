@@ -0,0 +1,81 @@
+//! Per-function complexity metrics from `.complexity.scm` queries.
+//!
+//! The query marks decision points with `@complexity` and scope-opening
+//! constructs with `@nesting` (see `queries/*.complexity.scm`). Cyclomatic
+//! complexity is 1 plus the number of `@complexity` captures inside the
+//! function; nesting depth is the deepest chain of `@nesting` nodes below it.
+
+use crate::parsers::grammar_loader;
+use std::collections::HashSet;
+use tree_sitter::{Node, StreamingIterator};
+
+/// Complexity of one function or method body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComplexityMetrics {
+    /// McCabe cyclomatic complexity: decision points + 1.
+    pub cyclomatic: usize,
+    /// Maximum nesting depth of control structures (and nested definitions)
+    /// inside the function; 0 for straight-line code.
+    pub depth: usize,
+}
+
+impl Default for ComplexityMetrics {
+    fn default() -> Self {
+        Self {
+            cyclomatic: 1,
+            depth: 0,
+        }
+    }
+}
+
+/// Metrics for `node` using the complexity query of `grammar`. Languages
+/// without a query get the base value (`cyclomatic = 1`, `depth = 0`).
+pub fn query_complexity(grammar: &str, node: &Node, source: &[u8]) -> ComplexityMetrics {
+    let loader = grammar_loader();
+    let Some(scm) = loader.get_complexity(grammar) else {
+        return ComplexityMetrics::default();
+    };
+    let Some(query) = loader.get_compiled_query(grammar, "complexity", &scm) else {
+        return ComplexityMetrics::default();
+    };
+    complexity_with_query(node, source, &query)
+}
+
+/// Metrics for `node` from an already compiled complexity query.
+pub fn complexity_with_query(
+    node: &Node,
+    source: &[u8],
+    query: &tree_sitter::Query,
+) -> ComplexityMetrics {
+    let index_of = |name: &str| query.capture_names().iter().position(|n| *n == name);
+    let complexity_idx = index_of("complexity");
+    let nesting_idx = index_of("nesting");
+
+    let mut metrics = ComplexityMetrics::default();
+    let mut nesting: HashSet<usize> = HashSet::new();
+    let mut qcursor = tree_sitter::QueryCursor::new();
+    qcursor.set_byte_range(node.byte_range());
+    let mut matches = qcursor.matches(query, *node, source);
+    while let Some(m) = matches.next() {
+        for capture in m.captures {
+            let idx = Some(capture.index as usize);
+            if idx == complexity_idx {
+                metrics.cyclomatic += 1;
+            } else if idx == nesting_idx && capture.node.id() != node.id() {
+                nesting.insert(capture.node.id());
+            }
+        }
+    }
+
+    // Depth of a nesting node = nesting nodes on its path up to `node`.
+    let mut cursor = node.walk();
+    let mut stack: Vec<(Node, usize)> = vec![(*node, 0)];
+    while let Some((current, depth)) = stack.pop() {
+        metrics.depth = metrics.depth.max(depth);
+        for child in current.children(&mut cursor) {
+            let child_depth = depth + usize::from(nesting.contains(&child.id()));
+            stack.push((child, child_depth));
+        }
+    }
+    metrics
+}
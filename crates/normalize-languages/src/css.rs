@@ -19,6 +19,9 @@ impl Language for Css {
     fn grammar_name(&self) -> &'static str {
         "css"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
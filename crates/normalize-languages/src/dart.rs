@@ -20,6 +20,10 @@ impl Language for Dart {
         "dart"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["pubspec.yaml"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -16,6 +16,9 @@ impl Language for Diff {
     fn grammar_name(&self) -> &'static str {
         "diff"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
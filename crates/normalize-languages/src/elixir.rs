@@ -18,6 +18,13 @@ impl Language for Elixir {
     fn grammar_name(&self) -> &'static str {
         "elixir"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
+
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["mix.exs"]
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -18,6 +18,9 @@ impl Language for Erlang {
     fn grammar_name(&self) -> &'static str {
         "erlang"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("%")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
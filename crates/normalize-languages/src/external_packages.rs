@@ -13,7 +13,7 @@
 //! - Global cache: ~/.cache/normalize/ for indexed packages
 //! - PackageIndex: SQLite-backed package/symbol index
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // =============================================================================
 // Shared Types
@@ -36,17 +36,7 @@ pub struct ResolvedPackage {
 
 /// Get the global normalize cache directory (~/.cache/normalize/).
 pub fn get_global_cache_dir() -> Option<PathBuf> {
-    let cache_base = if let Ok(xdg) = std::env::var("XDG_CACHE_HOME") {
-        PathBuf::from(xdg)
-    } else if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home).join(".cache")
-    } else if let Ok(home) = std::env::var("USERPROFILE") {
-        PathBuf::from(home).join(".cache")
-    } else {
-        return None;
-    };
-
-    let normalize_cache = cache_base.join("normalize");
+    let normalize_cache = normalize_cache::cache_root()?;
     if !normalize_cache.exists() {
         std::fs::create_dir_all(&normalize_cache).ok()?;
     }
@@ -133,6 +123,8 @@ pub struct PackageRecord {
     pub language: String,
     pub name: String,
     pub path: String,
+    /// Installed package version, when discovery could determine it.
+    pub version: Option<String>,
     pub min_major: u32,
     pub min_minor: u32,
     pub max_major: Option<u32>,
@@ -179,6 +171,21 @@ impl PackageIndex {
             libsql::Error::SqliteFailure(1, "Cannot determine cache directory".into())
         })?;
 
+        // Concurrent invocations share one database: create the schema, or
+        // replace a corrupt file, one process at a time.
+        let _lock = normalize_cache::CacheLock::exclusive(&db_path).map_err(|e| {
+            libsql::Error::SqliteFailure(1, format!("Cannot lock package index: {e}"))
+        })?;
+        match Self::open_at(&db_path).await {
+            Err(e) if normalize_cache::is_sqlite_corruption(&e.to_string()) => {
+                normalize_cache::discard_corrupt(&db_path);
+                Ok(Self::open_at(&db_path).await?)
+            }
+            result => Ok(result?),
+        }
+    }
+
+    async fn open_at(db_path: &Path) -> Result<Self, libsql::Error> {
         let db = libsql::Builder::new_local(db_path).build().await?;
         let conn = db.connect()?;
         let index = PackageIndex { conn, db };
@@ -206,12 +213,21 @@ impl PackageIndex {
                 min_minor INTEGER NOT NULL,
                 max_major INTEGER,
                 max_minor INTEGER,
-                indexed_at INTEGER NOT NULL
+                indexed_at INTEGER NOT NULL,
+                version TEXT
             )",
                 (),
             )
             .await?;
 
+        // Migrate existing databases: the package version column was added later.
+        // SQLite errors on duplicate ADD COLUMN, so a failure means it already exists.
+        let added_version = self
+            .conn
+            .execute("ALTER TABLE packages ADD COLUMN version TEXT", ())
+            .await
+            .is_ok();
+
         self.conn
             .execute(
                 "CREATE INDEX IF NOT EXISTS idx_packages_lang_name ON packages(language, name)",
@@ -247,14 +263,33 @@ impl PackageIndex {
             )
             .await?;
 
+        // Packages indexed before versions were recorded have a NULL version,
+        // which never matches an installed version; drop them so they are
+        // re-indexed rather than kept alongside a versioned copy.
+        if added_version {
+            self.conn
+                .execute(
+                    "DELETE FROM symbols WHERE package_id IN \
+                     (SELECT id FROM packages WHERE version IS NULL)",
+                    (),
+                )
+                .await?;
+            self.conn
+                .execute("DELETE FROM packages WHERE version IS NULL", ())
+                .await?;
+        }
+
         Ok(())
     }
 
+    /// Insert a package. `version` is the installed package version (not the
+    /// language version range given by `min_version`/`max_version`).
     pub async fn insert_package(
         &self,
         language: &str,
         name: &str,
         path: &str,
+        version: Option<&str>,
         min_version: Version,
         max_version: Option<Version>,
     ) -> Result<i64, libsql::Error> {
@@ -264,8 +299,8 @@ impl PackageIndex {
             .as_secs() as i64;
 
         self.conn.execute(
-            "INSERT INTO packages (language, name, path, min_major, min_minor, max_major, max_minor, indexed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO packages (language, name, path, min_major, min_minor, max_major, max_minor, indexed_at, version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 language,
                 name,
@@ -275,6 +310,7 @@ impl PackageIndex {
                 max_version.map(|v| v.major),
                 max_version.map(|v| v.minor),
                 now,
+                version,
             ],
         ).await?;
         Ok(self.conn.last_insert_rowid())
@@ -307,7 +343,7 @@ impl PackageIndex {
         let mut rows = self
             .conn
             .query(
-                "SELECT id, language, name, path, min_major, min_minor, max_major, max_minor
+                "SELECT id, language, name, path, min_major, min_minor, max_major, max_minor, version
              FROM packages WHERE language = ?1 AND name = ?2",
                 params![language, name],
             )
@@ -320,6 +356,7 @@ impl PackageIndex {
                 language: row.get(1)?,
                 name: row.get(2)?,
                 path: row.get(3)?,
+                version: row.get(8)?,
                 min_major: row.get(4)?,
                 min_minor: row.get(5)?,
                 max_major: row.get(6)?,
@@ -372,7 +409,7 @@ impl PackageIndex {
     ) -> Result<Vec<(PackageRecord, SymbolRecord)>, libsql::Error> {
         let mut rows = self.conn.query(
             "SELECT p.id, p.language, p.name, p.path, p.min_major, p.min_minor, p.max_major, p.max_minor,
-                    s.id, s.package_id, s.name, s.kind, s.signature, s.line
+                    s.id, s.package_id, s.name, s.kind, s.signature, s.line, p.version
              FROM symbols s
              JOIN packages p ON s.package_id = p.id
              WHERE p.language = ?1 AND s.name = ?2",
@@ -387,6 +424,7 @@ impl PackageIndex {
                     language: row.get(1)?,
                     name: row.get(2)?,
                     path: row.get(3)?,
+                    version: row.get(14)?,
                     min_major: row.get(4)?,
                     min_minor: row.get(5)?,
                     max_major: row.get(6)?,
@@ -430,6 +468,32 @@ impl PackageIndex {
         }
     }
 
+    /// Whether this exact installed version of a package is already indexed.
+    ///
+    /// Multiple installed versions of one package are indexed separately;
+    /// an unknown version (`None`) only matches other unknown versions.
+    pub async fn is_version_indexed(
+        &self,
+        language: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<bool, libsql::Error> {
+        let mut rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) FROM packages WHERE language = ?1 AND name = ?2 AND version IS ?3",
+                params![language, name, version],
+            )
+            .await?;
+
+        if let Some(row) = rows.next().await? {
+            let count: i64 = row.get(0)?;
+            Ok(count > 0)
+        } else {
+            Ok(false)
+        }
+    }
+
     pub async fn delete_package(&self, package_id: i64) -> Result<(), libsql::Error> {
         self.conn
             .execute(
@@ -501,6 +565,7 @@ mod tests {
                 "python",
                 "requests",
                 "/path/to/requests",
+                Some("2.31.0"),
                 Version { major: 3, minor: 8 },
                 None,
             )
@@ -519,7 +584,9 @@ mod tests {
             .await
             .unwrap();
         assert!(found.is_some());
-        assert_eq!(found.unwrap().name, "requests");
+        let found = found.unwrap();
+        assert_eq!(found.name, "requests");
+        assert_eq!(found.version.as_deref(), Some("2.31.0"));
 
         // Find the symbol
         let symbols = index.get_symbols(pkg_id).await.unwrap();
@@ -529,5 +596,74 @@ mod tests {
         // Check indexed
         assert!(index.is_indexed("python", "requests").await.unwrap());
         assert!(!index.is_indexed("python", "nonexistent").await.unwrap());
+        assert!(
+            index
+                .is_version_indexed("python", "requests", Some("2.31.0"))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !index
+                .is_version_indexed("python", "requests", Some("2.32.0"))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !index
+                .is_version_indexed("python", "requests", None)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_drops_unversioned_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("packages.db");
+        {
+            let db = libsql::Builder::new_local(&db_path).build().await.unwrap();
+            let conn = db.connect().unwrap();
+            conn.execute(
+                "CREATE TABLE packages (
+                id INTEGER PRIMARY KEY,
+                language TEXT NOT NULL,
+                name TEXT NOT NULL,
+                path TEXT NOT NULL,
+                min_major INTEGER NOT NULL,
+                min_minor INTEGER NOT NULL,
+                max_major INTEGER,
+                max_minor INTEGER,
+                indexed_at INTEGER NOT NULL
+            )",
+                (),
+            )
+            .await
+            .unwrap();
+            conn.execute(
+                "INSERT INTO packages (language, name, path, min_major, min_minor, indexed_at)
+                 VALUES ('python', 'requests', '/site/requests', 3, 8, 0)",
+                (),
+            )
+            .await
+            .unwrap();
+        }
+
+        let index = PackageIndex::open_at(&db_path).await.unwrap();
+        assert!(!index.is_indexed("python", "requests").await.unwrap());
+
+        // Reopening an already migrated database keeps unknown-version rows.
+        let min = Version { major: 3, minor: 8 };
+        index
+            .insert_package("python", "requests", "/site/requests", None, min, None)
+            .await
+            .unwrap();
+        drop(index);
+        let index = PackageIndex::open_at(&db_path).await.unwrap();
+        assert!(
+            index
+                .is_version_indexed("python", "requests", None)
+                .await
+                .unwrap()
+        );
     }
 }
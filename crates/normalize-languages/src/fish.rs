@@ -16,6 +16,9 @@ impl Language for Fish {
     fn grammar_name(&self) -> &'static str {
         "fish"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
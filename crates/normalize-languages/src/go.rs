@@ -23,6 +23,10 @@ impl Language for Go {
         "go"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["go.mod"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
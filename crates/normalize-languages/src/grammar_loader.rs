@@ -4,6 +4,18 @@
 //! Also loads highlight queries (.scm files) for syntax highlighting.
 //! Grammars are compiled from arborium sources via `cargo xtask build-grammars`.
 //!
+//! With the `static-grammars` feature, the core grammars are linked into the
+//! binary instead (see [`crate::static_grammars`]). Statically linked grammars
+//! win over shared libraries of the same name; [`GrammarLoader::source`] reports
+//! which mode each loaded grammar used.
+//!
+//! # Pinning
+//!
+//! Search paths built by `cargo xtask build-grammars` carry a `grammars.toml`
+//! pinning each library's version, ABI and content hash. Libraries are checked
+//! against their pin before `dlopen` (see [`crate::grammar_manifest`]);
+//! [`GrammarLoader::verify`] checks every installed grammar at once.
+//!
 //! # ABI Compatibility
 //!
 //! Tree-sitter grammars have an ABI version embedded at compile time. The tree-sitter
@@ -39,6 +51,7 @@
 //! // Tree now has dangling pointers -> segfault on use
 //! ```
 
+use crate::grammar_manifest::{GrammarManifest, StaleGrammar, StaleGrammars};
 use libloading::{Library, Symbol};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -61,6 +74,18 @@ pub enum GrammarLoadError {
         /// Underlying error message from libloading.
         detail: String,
     },
+    /// The library doesn't match its `grammars.toml` pin, so it was not loaded.
+    #[error("grammar {0}; rebuild with `{rebuild}`", rebuild = crate::grammar_manifest::REBUILD_COMMAND)]
+    Stale(StaleGrammar),
+}
+
+/// Where a loaded grammar came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarSource {
+    /// Linked into the binary (`static-grammars` feature).
+    Static,
+    /// Loaded from the shared library at this path.
+    Dynamic(PathBuf),
 }
 
 /// Loaded grammar with its backing library.
@@ -70,9 +95,12 @@ pub enum GrammarLoadError {
 /// the language is in use causes undefined behavior (typically segfault).
 struct LoadedGrammar {
     /// Backing shared library - must outlive any use of `language`.
-    _library: Library,
+    /// `None` for statically linked grammars.
+    _library: Option<Library>,
     /// Tree-sitter Language (contains pointers into `_library`).
     language: Language,
+    /// How the grammar was loaded.
+    source: GrammarSource,
 }
 
 /// Dynamic grammar loader with caching.
@@ -107,6 +135,8 @@ pub struct GrammarLoader {
     cfg_cache: RwLock<HashMap<String, Arc<String>>>,
     /// Cached compiled tree-sitter queries (keyed by "grammar:query_type").
     compiled_query_cache: RwLock<HashMap<String, Arc<tree_sitter::Query>>>,
+    /// Cached `grammars.toml` per search path (`None` when absent or unreadable).
+    manifests: RwLock<HashMap<PathBuf, Option<Arc<GrammarManifest>>>>,
 }
 
 impl GrammarLoader {
@@ -148,6 +178,7 @@ impl GrammarLoader {
             test_regions_cache: RwLock::new(HashMap::new()),
             cfg_cache: RwLock::new(HashMap::new()),
             compiled_query_cache: RwLock::new(HashMap::new()),
+            manifests: RwLock::new(HashMap::new()),
         }
     }
 
@@ -169,6 +200,7 @@ impl GrammarLoader {
             test_regions_cache: RwLock::new(HashMap::new()),
             cfg_cache: RwLock::new(HashMap::new()),
             compiled_query_cache: RwLock::new(HashMap::new()),
+            manifests: RwLock::new(HashMap::new()),
         }
     }
 
@@ -179,7 +211,8 @@ impl GrammarLoader {
 
     /// Get a grammar by name.
     ///
-    /// Returns `Ok(lang)` if found and loaded successfully,
+    /// Statically linked grammars are preferred; otherwise the search paths are
+    /// tried. Returns `Ok(lang)` if found and loaded successfully,
     /// `Err(GrammarLoadError::NotFound)` if no `.so`/`.dylib` exists in any
     /// search path, and other `Err` variants for load or ABI failures.
     pub fn get(&self, name: &str) -> Result<Language, GrammarLoadError> {
@@ -193,9 +226,56 @@ impl GrammarLoader {
             return Ok(loaded.language.clone());
         }
 
+        if let Some(lang_fn) = crate::static_grammars::language_fn(name) {
+            let language = Language::new(lang_fn);
+            self.insert(name, None, language.clone(), GrammarSource::Static);
+            return Ok(language);
+        }
+
         self.load_external(name)
     }
 
+    /// How the grammar `name` was loaded, or `None` if it hasn't been loaded
+    /// (successfully) by this loader yet.
+    pub fn source(&self, name: &str) -> Option<GrammarSource> {
+        self.cache
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .map(|loaded| loaded.source.clone())
+    }
+
+    /// Every grammar loaded so far with how it was loaded, sorted by name.
+    pub fn loaded_sources(&self) -> Vec<(String, GrammarSource)> {
+        let mut sources: Vec<(String, GrammarSource)> = self
+            .cache
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, loaded)| (name.clone(), loaded.source.clone()))
+            .collect();
+        sources.sort_by(|a, b| a.0.cmp(&b.0));
+        sources
+    }
+
+    fn insert(
+        &self,
+        name: &str,
+        library: Option<Library>,
+        language: Language,
+        source: GrammarSource,
+    ) {
+        let loaded = Arc::new(LoadedGrammar {
+            _library: library,
+            language,
+            source,
+        });
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), loaded);
+    }
+
     /// Get the highlight query for a grammar.
     ///
     /// Returns None if no highlight query found for the grammar.
@@ -609,6 +689,9 @@ impl GrammarLoader {
         for search_path in &self.search_paths {
             let lib_path = search_path.join(&lib_name);
             if lib_path.exists() {
+                if let Some(stale) = self.check_pin(name, &lib_path) {
+                    return Err(GrammarLoadError::Stale(stale));
+                }
                 return self.load_from_path(name, &lib_path);
             }
         }
@@ -616,6 +699,58 @@ impl GrammarLoader {
         Err(GrammarLoadError::NotFound(name.to_string()))
     }
 
+    /// Check the library at `path` against its directory's `grammars.toml`.
+    /// Returns `None` if it matches or isn't pinned.
+    fn check_pin(&self, name: &str, path: &Path) -> Option<StaleGrammar> {
+        let manifest = self.manifest(path.parent()?)?;
+        let pin = manifest.get(name)?;
+        let reason = pin.verify(path).err()?;
+        Some(StaleGrammar {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            version: pin.version.clone(),
+            reason,
+        })
+    }
+
+    fn manifest(&self, dir: &Path) -> Option<Arc<GrammarManifest>> {
+        if let Some(cached) = self
+            .manifests
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(dir)
+        {
+            return cached.clone();
+        }
+        let manifest = match GrammarManifest::load(dir) {
+            Ok(manifest) => manifest.map(Arc::new),
+            Err(e) => {
+                log::warn!("Ignoring grammar manifest: {e}");
+                None
+            }
+        };
+        self.manifests
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(dir.to_path_buf(), manifest.clone());
+        manifest
+    }
+
+    /// Check every grammar the loader would load from its search paths against
+    /// its pin, without loading any of them.
+    pub fn verify(&self) -> Result<(), StaleGrammars> {
+        let grammars: Vec<StaleGrammar> = self
+            .available_external_with_paths()
+            .into_iter()
+            .filter_map(|(name, path)| self.check_pin(&name, &path))
+            .collect();
+        if grammars.is_empty() {
+            Ok(())
+        } else {
+            Err(StaleGrammars { grammars })
+        }
+    }
+
     /// Load grammar from a specific path.
     fn load_from_path(&self, name: &str, path: &Path) -> Result<Language, GrammarLoadError> {
         // SAFETY: Loading shared libraries is inherently unsafe. We accept this risk because:
@@ -663,15 +798,12 @@ impl GrammarLoader {
         };
 
         // Cache the loaded grammar
-        let loaded = Arc::new(LoadedGrammar {
-            _library: library,
-            language: language.clone(),
-        });
-
-        self.cache
-            .write()
-            .unwrap_or_else(|e| e.into_inner())
-            .insert(name.to_string(), loaded);
+        self.insert(
+            name,
+            Some(library),
+            language.clone(),
+            GrammarSource::Dynamic(path.to_path_buf()),
+        );
 
         Ok(language)
     }
@@ -1951,6 +2083,58 @@ mod tests {
         assert!(loader.get_calls("unknown-lang-xyz").is_none());
     }
 
+    #[test]
+    fn test_stale_grammar_is_not_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join(grammar_lib_name("python"));
+        std::fs::write(&lib, b"not a real library").unwrap();
+        std::fs::write(
+            dir.path().join(crate::grammar_manifest::GRAMMAR_MANIFEST),
+            "[python]\nversion = \"2.17.0\"\nblake3 = \"0000\"\n",
+        )
+        .unwrap();
+
+        let loader = GrammarLoader::with_paths(vec![dir.path().to_path_buf()]);
+        let stale = loader.verify().unwrap_err();
+        assert_eq!(stale.grammars.len(), 1);
+        assert_eq!(stale.grammars[0].name, "python");
+        assert!(stale.to_string().contains("cargo xtask build-grammars"));
+
+        if crate::static_grammar_names().contains(&"python") {
+            return;
+        }
+        let err = loader.get("python").unwrap_err();
+        assert!(matches!(err, GrammarLoadError::Stale(_)), "{err}");
+        assert!(
+            err.to_string().contains("does not match pinned 0000"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_source_reports_only_loaded_grammars() {
+        let loader = GrammarLoader::with_paths(vec![]);
+        assert_eq!(loader.source("python"), None);
+        assert!(loader.get("unknown-lang-xyz").is_err());
+        assert_eq!(loader.source("unknown-lang-xyz"), None);
+        assert!(loader.loaded_sources().is_empty());
+    }
+
+    #[cfg(feature = "static-grammars")]
+    #[test]
+    fn test_static_grammars_preferred() {
+        let loader = GrammarLoader::with_paths(vec![]);
+        for name in crate::static_grammar_names() {
+            assert!(loader.get(name).is_ok(), "static grammar {name} failed");
+            assert_eq!(loader.source(name), Some(GrammarSource::Static));
+        }
+        let lang = loader.get("python").unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang).unwrap();
+        let tree = parser.parse("def f():\n    pass\n", None).unwrap();
+        assert!(!tree.root_node().has_error());
+    }
+
     #[test]
     fn test_load_from_env() {
         // Set up env var pointing to target/grammars
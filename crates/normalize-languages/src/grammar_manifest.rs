@@ -0,0 +1,264 @@
+//! Grammar pinning via `grammars.toml`.
+//!
+//! `cargo xtask build-grammars` writes a `grammars.toml` next to the libraries
+//! it builds, pinning each grammar's source version, tree-sitter ABI and blake3
+//! content hash:
+//!
+//! ```toml
+//! [python]
+//! version = "2.17.0"
+//! abi = 15
+//! blake3 = "9f2c…"
+//! ```
+//!
+//! [`GrammarLoader`](crate::GrammarLoader) checks a library against its pin
+//! before `dlopen`, so a stale or swapped `.so` is reported as
+//! [`GrammarLoadError::Stale`](crate::GrammarLoadError::Stale) instead of
+//! crashing on an ABI mismatch. Directories without a manifest, and grammars
+//! the manifest doesn't list (user-added grammars), load unverified.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest inside a grammar search path.
+pub const GRAMMAR_MANIFEST: &str = "grammars.toml";
+
+/// Command that rebuilds grammars and their manifest.
+pub const REBUILD_COMMAND: &str = "cargo xtask build-grammars --force";
+
+/// Expected properties of one grammar library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrammarPin {
+    /// Source version the library was built from (arborium crate version, or
+    /// `"local"` for workspace grammars).
+    pub version: String,
+    /// Tree-sitter ABI declared by the grammar's `parser.c`, if recorded.
+    pub abi: Option<u32>,
+    /// Hex blake3 hash of the library file.
+    pub blake3: String,
+}
+
+/// Parsed `grammars.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct GrammarManifest {
+    pins: HashMap<String, GrammarPin>,
+}
+
+impl GrammarManifest {
+    /// Load `dir/grammars.toml`. `Ok(None)` if the directory has no manifest.
+    pub fn load(dir: &Path) -> Result<Option<Self>, String> {
+        let path = dir.join(GRAMMAR_MANIFEST);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        Self::parse(&text)
+            .map(Some)
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Parse manifest text.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let mut pins = HashMap::new();
+        for (name, value) in table {
+            let entry = value
+                .as_table()
+                .ok_or_else(|| format!("[{name}] must be a table"))?;
+            let string = |key: &str| {
+                entry
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("[{name}] is missing `{key}`"))
+            };
+            let abi = match entry.get("abi") {
+                None => None,
+                Some(v) => Some(
+                    v.as_integer()
+                        .and_then(|n| u32::try_from(n).ok())
+                        .ok_or_else(|| format!("[{name}] `abi` must be a positive integer"))?,
+                ),
+            };
+            let pin = GrammarPin {
+                version: string("version")?,
+                abi,
+                blake3: string("blake3")?,
+            };
+            pins.insert(name, pin);
+        }
+        Ok(Self { pins })
+    }
+
+    /// The pin for grammar `name`, if listed.
+    pub fn get(&self, name: &str) -> Option<&GrammarPin> {
+        self.pins.get(name)
+    }
+
+    /// Listed grammar names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.pins.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl GrammarPin {
+    /// Check the library at `path` against this pin.
+    pub fn verify(&self, path: &Path) -> Result<(), StaleReason> {
+        if let Some(abi) = self.abi {
+            let supported = tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION as u32
+                ..=tree_sitter::LANGUAGE_VERSION as u32;
+            if !supported.contains(&abi) {
+                return Err(StaleReason::UnsupportedAbi {
+                    abi,
+                    min: *supported.start(),
+                    max: *supported.end(),
+                });
+            }
+        }
+        let bytes = std::fs::read(path).map_err(|e| StaleReason::Unreadable(e.to_string()))?;
+        let actual = blake3::hash(&bytes).to_hex().to_string();
+        if !actual.eq_ignore_ascii_case(&self.blake3) {
+            return Err(StaleReason::HashMismatch {
+                expected: self.blake3.clone(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why a grammar library failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleReason {
+    /// The library's contents differ from the pinned hash (rebuilt, replaced
+    /// or corrupted since the manifest was written).
+    HashMismatch { expected: String, actual: String },
+    /// The grammar was generated for a tree-sitter ABI this build can't load.
+    UnsupportedAbi { abi: u32, min: u32, max: u32 },
+    /// The library file could not be read.
+    Unreadable(String),
+}
+
+impl fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashMismatch { expected, actual } => write!(
+                f,
+                "content hash {} does not match pinned {}",
+                short_hash(actual),
+                short_hash(expected)
+            ),
+            Self::UnsupportedAbi { abi, min, max } => {
+                write!(f, "built for tree-sitter ABI {abi}, supported {min}-{max}")
+            }
+            Self::Unreadable(e) => write!(f, "unreadable: {e}"),
+        }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..12).unwrap_or(hash)
+}
+
+/// A grammar library that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleGrammar {
+    /// Grammar name (e.g. `"python"`).
+    pub name: String,
+    /// Library path.
+    pub path: PathBuf,
+    /// Pinned source version.
+    pub version: String,
+    /// What didn't match.
+    pub reason: StaleReason,
+}
+
+impl fmt::Display for StaleGrammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, pinned {}): {}",
+            self.name,
+            self.path.display(),
+            self.version,
+            self.reason
+        )
+    }
+}
+
+/// Every stale grammar found by
+/// [`GrammarLoader::verify`](crate::GrammarLoader::verify).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleGrammars {
+    pub grammars: Vec<StaleGrammar>,
+}
+
+impl std::error::Error for StaleGrammars {}
+
+impl fmt::Display for StaleGrammars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} stale grammar(s):", self.grammars.len())?;
+        for grammar in &self.grammars {
+            writeln!(f, "  {grammar}")?;
+        }
+        write!(f, "Rebuild with: {REBUILD_COMMAND}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pin_for(bytes: &[u8], abi: Option<u32>) -> GrammarPin {
+        GrammarPin {
+            version: "2.17.0".to_string(),
+            abi,
+            blake3: blake3::hash(bytes).to_hex().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = GrammarManifest::parse(
+            r#"
+            [python]
+            version = "2.17.0"
+            abi = 15
+            blake3 = "abc"
+
+            [c-sharp]
+            version = "local"
+            blake3 = "def"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.names(), ["c-sharp", "python"]);
+        assert_eq!(manifest.get("python").unwrap().abi, Some(15));
+        assert_eq!(manifest.get("c-sharp").unwrap().abi, None);
+
+        let err = GrammarManifest::parse("[go]\nversion = \"1\"\n").unwrap_err();
+        assert!(err.contains("missing `blake3`"), "{err}");
+    }
+
+    #[test]
+    fn test_verify_detects_hash_and_abi_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("python.so");
+        std::fs::write(&lib, b"grammar v1").unwrap();
+
+        let abi = tree_sitter::LANGUAGE_VERSION as u32;
+        assert_eq!(pin_for(b"grammar v1", Some(abi)).verify(&lib), Ok(()));
+        assert!(matches!(
+            pin_for(b"grammar v0", Some(abi)).verify(&lib),
+            Err(StaleReason::HashMismatch { .. })
+        ));
+        assert!(matches!(
+            pin_for(b"grammar v1", Some(abi + 1)).verify(&lib),
+            Err(StaleReason::UnsupportedAbi { .. })
+        ));
+    }
+}
@@ -40,6 +40,9 @@ impl Language for GraphQL {
     fn grammar_name(&self) -> &'static str {
         "graphql"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
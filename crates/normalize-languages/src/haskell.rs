@@ -18,6 +18,13 @@ impl Language for Haskell {
     fn grammar_name(&self) -> &'static str {
         "haskell"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("--")
+    }
+
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["stack.yaml"]
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Idris {
     fn grammar_name(&self) -> &'static str {
         "idris"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("--")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
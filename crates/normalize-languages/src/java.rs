@@ -19,6 +19,10 @@ impl Language for Java {
         "java"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["pom.xml", "build.gradle"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
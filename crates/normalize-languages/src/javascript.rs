@@ -23,6 +23,10 @@ impl Language for JavaScript {
         "javascript"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["package.json"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -15,6 +15,9 @@ impl Language for Jinja2 {
     fn grammar_name(&self) -> &'static str {
         "jinja2"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
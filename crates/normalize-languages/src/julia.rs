@@ -21,6 +21,9 @@ impl Language for Julia {
     fn grammar_name(&self) -> &'static str {
         "julia"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
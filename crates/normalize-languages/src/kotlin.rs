@@ -37,6 +37,10 @@ impl Language for Kotlin {
         "kotlin"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["build.gradle.kts"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -14,6 +14,8 @@
 //! - `langs-functional`: Haskell, OCaml, Elixir, etc.
 //! - `langs-config`: JSON, YAML, TOML, HCL, etc.
 //! - `lang-*`: Individual language flags
+//! - `static-grammars`: Link the core grammars into the binary; `GrammarLoader`
+//!   prefers them and falls back to shared libraries for everything else
 //!
 //! # Example
 //!
@@ -36,15 +38,18 @@
 
 pub mod ast_grep;
 mod body;
+pub mod complexity;
 mod component;
 mod docstring;
 pub mod ecmascript;
 pub mod external_packages;
 pub mod ffi;
 mod grammar_loader;
+pub mod grammar_manifest;
 pub mod parsers;
 pub mod query_predicates;
 mod registry;
+mod static_grammars;
 mod traits;
 
 // Language implementations (feature-gated)
@@ -244,13 +249,15 @@ pub mod zig;
 pub mod zsh;
 
 // Re-exports (always available)
-pub use grammar_loader::{GrammarLoadError, GrammarLoader};
+pub use complexity::ComplexityMetrics;
+pub use grammar_loader::{GrammarLoadError, GrammarLoader, GrammarSource};
 pub use normalize_facts_core::Export;
 pub use query_predicates::satisfies_predicates;
 pub use registry::{
     is_programming_language, is_test_path, register, support_for_extension, support_for_grammar,
     support_for_path, supported_languages, test_file_globs_for_path, validate_unused_kinds_audit,
 };
+pub use static_grammars::static_grammar_names;
 pub use traits::{
     CallSiteSpec, ContainerBody, EmbeddedBlock, ExtractedFnSpec, GenParam, GenReturn,
     ImplementsInfo, Import, ImportSpec, InterfaceResolver, Language, LanguageEmbedded,
@@ -19,6 +19,9 @@ impl Language for Lua {
     fn grammar_name(&self) -> &'static str {
         "lua"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("--")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Markdown {
     fn grammar_name(&self) -> &'static str {
         "markdown"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Meson {
     fn grammar_name(&self) -> &'static str {
         "meson"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -16,6 +16,9 @@ impl Language for Nginx {
     fn grammar_name(&self) -> &'static str {
         "nginx"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
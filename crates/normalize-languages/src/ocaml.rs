@@ -18,6 +18,9 @@ impl Language for OCaml {
     fn grammar_name(&self) -> &'static str {
         "ocaml"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -19,8 +19,16 @@
 //! [`try_get_grammar`] / [`parse_with_grammar`] / [`parser_for`] to get the
 //! warning automatically; call [`report_missing_grammar`] directly if you
 //! call [`GrammarLoader::get`] yourself.
+//!
+//! # Parser pool
+//!
+//! Each thread keeps one `tree_sitter::Parser` per grammar, reused by
+//! [`parse_with_grammar`] and [`with_parser`]. Fact extraction parses every
+//! file several times (symbols, calls, imports, type refs) across rayon
+//! workers, so this avoids rebuilding a parser for each of those passes.
 
 use crate::{GrammarLoadError, GrammarLoader};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 use tree_sitter::Parser;
@@ -111,6 +119,18 @@ pub fn peek_missing_grammars() -> Vec<MissingGrammar> {
         .collect()
 }
 
+/// Create the global grammar loader with `paths` searched after the default
+/// locations, for embedders that ship their own grammar directories. Only
+/// takes effect before the first grammar lookup; returns `false` if the global
+/// loader already exists.
+pub fn set_grammar_paths(paths: impl IntoIterator<Item = std::path::PathBuf>) -> bool {
+    let mut loader = GrammarLoader::new();
+    for path in paths {
+        loader.add_path(path);
+    }
+    GRAMMAR_LOADER.set(Arc::new(loader)).is_ok()
+}
+
 /// Get the global grammar loader singleton.
 pub fn grammar_loader() -> Arc<GrammarLoader> {
     GRAMMAR_LOADER
@@ -143,14 +163,42 @@ pub fn parser_for(grammar: &str) -> Option<Parser> {
     Some(parser)
 }
 
-/// Parse source code with a specific grammar.
+thread_local! {
+    /// Per-thread parser pool, keyed by grammar name.
+    static PARSER_POOL: RefCell<HashMap<String, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Run `f` with this thread's pooled parser for `grammar`.
+///
+/// The parser is taken out of the pool for the duration of `f`, so nested
+/// calls (for another grammar, or the same one) are fine — they just build a
+/// fresh parser. It is reset before being returned to the pool. `f` must not
+/// change the parser's language. Returns `None` if the grammar fails to load.
+pub fn with_parser<R>(grammar: &str, f: impl FnOnce(&mut Parser) -> R) -> Option<R> {
+    let pooled = PARSER_POOL.with(|pool| pool.borrow_mut().remove(grammar));
+    let mut parser = match pooled {
+        Some(parser) => parser,
+        None => parser_for(grammar)?,
+    };
+    let result = f(&mut parser);
+    parser.reset();
+    PARSER_POOL.with(|pool| pool.borrow_mut().insert(grammar.to_string(), parser));
+    Some(result)
+}
+
+/// Parse source code with a specific grammar, using this thread's pooled parser.
 ///
 /// The grammar name should match tree-sitter grammar names
 /// (e.g., "python", "rust", "typescript"). Emits a warning to stderr on the
 /// first call where the grammar fails to load.
 pub fn parse_with_grammar(grammar: &str, source: &str) -> Option<tree_sitter::Tree> {
-    let mut parser = parser_for(grammar)?;
-    parser.parse(source, None)
+    with_parser(grammar, |parser| parser.parse(source, None)).flatten()
+}
+
+/// Grammars loaded so far by the global loader, with whether each was
+/// statically linked or loaded from a shared library.
+pub fn grammar_sources() -> Vec<(String, crate::GrammarSource)> {
+    grammar_loader().loaded_sources()
 }
 
 /// List grammars available in external search paths.
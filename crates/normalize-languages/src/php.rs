@@ -19,6 +19,10 @@ impl Language for Php {
         "php"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["composer.json"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -25,6 +25,13 @@ impl Language for Python {
     fn grammar_name(&self) -> &'static str {
         "python"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
+
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["pyproject.toml", "setup.py", "requirements.txt", "Pipfile"]
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -22,6 +22,9 @@ impl Language for R {
     fn grammar_name(&self) -> &'static str {
         "r"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
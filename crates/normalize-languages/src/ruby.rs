@@ -21,6 +21,13 @@ impl Language for Ruby {
     fn grammar_name(&self) -> &'static str {
         "ruby"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
+
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["Gemfile"]
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
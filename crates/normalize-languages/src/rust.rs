@@ -22,6 +22,10 @@ impl Language for Rust {
         "rust"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["Cargo.toml"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -617,6 +621,7 @@ fn merge_rust_impl_blocks(symbols: &mut Vec<crate::Symbol>) {
                 implements: impls,
                 // Synthesized module-like grouping symbol, not a function — no complexity.
                 complexity: None,
+                nesting_depth: None,
             });
         }
     }
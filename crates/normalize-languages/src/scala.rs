@@ -19,6 +19,10 @@ impl Language for Scala {
         "scala"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["build.sbt"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
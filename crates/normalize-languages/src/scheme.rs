@@ -18,6 +18,9 @@ impl Language for Scheme {
     fn grammar_name(&self) -> &'static str {
         "scheme"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some(";;")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -15,6 +15,9 @@ impl Language for SshConfig {
     fn grammar_name(&self) -> &'static str {
         "ssh-config"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
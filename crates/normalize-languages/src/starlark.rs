@@ -16,6 +16,9 @@ impl Language for Starlark {
     fn grammar_name(&self) -> &'static str {
         "starlark"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -0,0 +1,45 @@
+//! Grammars linked into the binary by the `static-grammars` feature.
+//!
+//! Dynamic loading needs the grammar shared libraries installed next to the
+//! binary's config, which is awkward for single-binary distribution on
+//! locked-down hosts. With `static-grammars`, the core grammars are compiled in
+//! from their arborium crates and [`GrammarLoader`](crate::GrammarLoader) uses
+//! them before searching for `.so`/`.dylib` files. Grammars not listed here still
+//! load dynamically.
+
+use tree_sitter_language::LanguageFn;
+
+#[cfg(feature = "static-grammars")]
+const GRAMMARS: &[(&str, LanguageFn)] = &[
+    ("bash", arborium_bash::language()),
+    ("c", arborium_c::language()),
+    ("cpp", arborium_cpp::language()),
+    ("go", arborium_go::language()),
+    ("java", arborium_java::language()),
+    ("javascript", arborium_javascript::language()),
+    ("json", arborium_json::language()),
+    ("markdown", arborium_markdown::language()),
+    ("python", arborium_python::language()),
+    ("rust", arborium_rust::language()),
+    ("toml", arborium_toml::language()),
+    ("tsx", arborium_tsx::language()),
+    ("typescript", arborium_typescript::language()),
+    ("yaml", arborium_yaml::language()),
+];
+
+#[cfg(not(feature = "static-grammars"))]
+const GRAMMARS: &[(&str, LanguageFn)] = &[];
+
+/// Entry point of the statically linked grammar `name`, if any.
+pub(crate) fn language_fn(name: &str) -> Option<LanguageFn> {
+    GRAMMARS
+        .iter()
+        .find(|(grammar, _)| *grammar == name)
+        .map(|(_, f)| *f)
+}
+
+/// Names of the grammars linked into this binary, sorted. Empty unless built
+/// with the `static-grammars` feature.
+pub fn static_grammar_names() -> Vec<&'static str> {
+    GRAMMARS.iter().map(|(name, _)| *name).collect()
+}
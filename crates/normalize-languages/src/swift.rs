@@ -37,6 +37,10 @@ impl Language for Swift {
         "swift"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["Package.swift"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
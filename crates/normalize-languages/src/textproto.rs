@@ -15,6 +15,9 @@ impl Language for TextProto {
     fn grammar_name(&self) -> &'static str {
         "textproto"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
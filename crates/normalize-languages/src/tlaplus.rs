@@ -16,6 +16,9 @@ impl Language for TlaPlus {
     fn grammar_name(&self) -> &'static str {
         "tlaplus"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("\\*")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -264,6 +264,7 @@ pub fn simple_symbol(
         // (it's a standalone node-only builder); callers needing complexity should use
         // the tags-based extraction path instead, which computes it per symbol.
         complexity: None,
+        nesting_depth: None,
     }
 }
 
@@ -312,6 +313,15 @@ pub trait Language: Send + Sync {
         None
     }
 
+    /// Complexity metrics for a function/method definition node.
+    ///
+    /// The default runs the grammar's `.complexity.scm` query (`@complexity`
+    /// decision points, `@nesting` scopes). Override for languages whose
+    /// branches can't be expressed as node captures.
+    fn complexity(&self, node: &Node, content: &str) -> crate::ComplexityMetrics {
+        crate::complexity::query_complexity(self.grammar_name(), node, content.as_bytes())
+    }
+
     /// Extract attributes/annotations/decorators attached to a definition node.
     /// Called by generic extraction for every tagged symbol.
     /// Returns empty vec if this language has no attribute convention.
@@ -388,6 +398,20 @@ pub trait Language: Send + Sync {
         ""
     }
 
+    /// Prefix that starts a line comment (`//`, `#`, `--`, ...), or `None` if
+    /// the language only has block comments. Used when inserting comments such
+    /// as `normalize-syntax-allow` markers.
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("//")
+    }
+
+    /// Files at a project root whose presence implies the project is written in
+    /// this language (`Cargo.toml`, `go.mod`, ...), even when no source file of
+    /// that language is nearby.
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     // === Visibility ===
 
     /// Get visibility of a node.
@@ -26,6 +26,10 @@ impl Language for TypeScript {
         "typescript"
     }
 
+    fn project_manifests(&self) -> &'static [&'static str] {
+        &["tsconfig.json", "deno.json"]
+    }
+
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
     }
@@ -15,6 +15,9 @@ impl Language for Uiua {
     fn grammar_name(&self) -> &'static str {
         "uiua"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("#")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
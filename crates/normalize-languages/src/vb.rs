@@ -18,6 +18,9 @@ impl Language for VB {
     fn grammar_name(&self) -> &'static str {
         "vb"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some("'")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
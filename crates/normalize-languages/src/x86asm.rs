@@ -15,6 +15,9 @@ impl Language for X86Asm {
     fn grammar_name(&self) -> &'static str {
         "x86asm"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        Some(";")
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
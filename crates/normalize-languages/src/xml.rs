@@ -19,6 +19,9 @@ impl Language for Xml {
     fn grammar_name(&self) -> &'static str {
         "xml"
     }
+    fn line_comment(&self) -> Option<&'static str> {
+        None
+    }
 
     fn as_symbols(&self) -> Option<&dyn LanguageSymbols> {
         Some(self)
@@ -2,7 +2,7 @@
 
 use crate::ResolvedPackage;
 use crate::c_cpp;
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps};
 use crate::{has_extension, skip_dotfiles};
 use std::path::{Path, PathBuf};
 
@@ -94,7 +94,7 @@ impl LocalDeps for CDeps {
         entry_name.to_string()
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         self.discover_recursive_packages(&source.path, &source.path)
     }
 
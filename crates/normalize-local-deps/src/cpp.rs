@@ -3,7 +3,7 @@
 use crate::ResolvedPackage;
 use crate::c_cpp;
 use crate::skip_dotfiles;
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps};
 use std::path::{Path, PathBuf};
 
 /// C++ local dependency resolution.
@@ -115,7 +115,7 @@ impl LocalDeps for CppDeps {
         entry_name.to_string()
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         self.discover_recursive_packages(&source.path, &source.path)
     }
 
@@ -3,9 +3,10 @@
 //! This module contains common logic shared between JavaScript, TypeScript, and TSX
 //! for resolving imports and discovering packages on disk.
 
-use crate::ResolvedPackage;
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, ResolvedPackage};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 
 // Extension preferences for each language variant
 pub const JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs"];
@@ -112,17 +113,29 @@ pub fn get_node_version() -> Option<String> {
 /// - `lodash` -> `node_modules/lodash`
 /// - `@scope/pkg` -> `node_modules/@scope/pkg`
 /// - `lodash/fp` -> `node_modules/lodash/fp`
+/// - pnpm's isolated layout, where only direct dependencies are linked at the
+///   top level and everything else lives in `node_modules/.pnpm`
 fn resolve_node_import(import_path: &str, node_modules: &Path) -> Option<ResolvedPackage> {
     // Parse package name (handle scoped packages)
     let parsed = parse_node_package_name(import_path);
 
     let pkg_dir = node_modules.join(&parsed.name);
-    if !pkg_dir.is_dir() {
-        return None;
-    }
+    let pkg_dir = if pkg_dir.is_dir() {
+        pkg_dir
+    } else {
+        find_pnpm_package(&node_modules.join(".pnpm"), &parsed.name)?
+    };
+    resolve_in_package_dir(import_path, parsed.subpath, &pkg_dir)
+}
 
+/// Resolve an import against an already-located package directory.
+fn resolve_in_package_dir(
+    import_path: &str,
+    subpath: Option<&str>,
+    pkg_dir: &Path,
+) -> Option<ResolvedPackage> {
     // If there's a subpath, resolve it directly
-    if let Some(subpath) = parsed.subpath {
+    if let Some(subpath) = subpath {
         let target = pkg_dir.join(subpath);
         if let Some(resolved) = resolve_node_file_or_dir(&target) {
             return Some(ResolvedPackage {
@@ -151,7 +164,7 @@ fn resolve_node_import(import_path: &str, node_modules: &Path) -> Option<Resolve
     }
 
     // Fall back to index.js
-    if let Some(resolved) = resolve_node_file_or_dir(&pkg_dir) {
+    if let Some(resolved) = resolve_node_file_or_dir(pkg_dir) {
         return Some(ResolvedPackage {
             path: resolved,
             name: import_path.to_string(),
@@ -237,8 +250,21 @@ pub fn resolve_external_import(import_name: &str, project_root: &Path) -> Option
         return None;
     }
 
-    let node_modules = find_node_modules(project_root)?;
-    resolve_node_import(import_name, &node_modules)
+    if let Some(node_modules) = find_node_modules(project_root)
+        && let Some(pkg) = resolve_node_import(import_name, &node_modules)
+    {
+        return Some(pkg);
+    }
+
+    // Yarn Plug'n'Play projects have no node_modules at all
+    let pnp = get_pnp_cache(project_root)?;
+    let parsed = parse_node_package_name(import_name);
+    let pkg_dir = pnp
+        .packages
+        .iter()
+        .filter(|p| p.name == parsed.name)
+        .find_map(|p| pnp_package_dir(&pnp.dir, p))?;
+    resolve_in_package_dir(import_name, parsed.subpath, &pkg_dir)
 }
 
 /// Get the Node.js version.
@@ -251,6 +277,322 @@ pub fn find_package_cache(project_root: &Path) -> Option<PathBuf> {
     find_node_modules(project_root)
 }
 
+// ============================================================================
+// pnpm store resolution
+// ============================================================================
+
+/// Split a pnpm store entry name (`@scope+name@1.2.3_peer@4.5.6`) into the
+/// package name (`@scope/name`) and version (`1.2.3`).
+fn parse_pnpm_store_entry(entry: &str) -> Option<(String, &str)> {
+    // Scoped names start with '@', so the version separator is the next '@'.
+    let search_from = usize::from(entry.starts_with('@'));
+    let at = entry[search_from..].find('@')? + search_from;
+    let name = entry[..at].replace('+', "/");
+    // Peer-dependency suffixes follow the version: `1.2.3_react@18.2.0` or `1.2.3(react@18.2.0)`.
+    let version = &entry[at + 1..];
+    let version = version.split(['_', '(']).next().filter(|v| !v.is_empty())?;
+    Some((name, version))
+}
+
+/// Find a package inside a pnpm virtual store (`node_modules/.pnpm`).
+///
+/// Prefers pnpm's hidden hoisting directory (`.pnpm/node_modules/<name>`),
+/// then the highest-versioned `.pnpm/<name>@<version>/node_modules/<name>`.
+pub fn find_pnpm_package(pnpm_store: &Path, name: &str) -> Option<PathBuf> {
+    let hoisted = pnpm_store.join("node_modules").join(name);
+    if hoisted.is_dir() {
+        return Some(hoisted);
+    }
+
+    let mut best: Option<(String, PathBuf)> = None;
+    for entry in std::fs::read_dir(pnpm_store).ok()?.flatten() {
+        let entry_name = entry.file_name().to_string_lossy().into_owned();
+        let Some((pkg_name, version)) = parse_pnpm_store_entry(&entry_name) else {
+            continue;
+        };
+        if pkg_name != name {
+            continue;
+        }
+        let dir = entry.path().join("node_modules").join(name);
+        if dir.is_dir()
+            && best
+                .as_ref()
+                .is_none_or(|(v, _)| deno_version_cmp(version, v).is_gt())
+        {
+            best = Some((version.to_string(), dir));
+        }
+    }
+    best.map(|(_, dir)| dir)
+}
+
+/// Discover every package in a pnpm virtual store (`node_modules/.pnpm`).
+///
+/// Each store entry `<name>@<version>` holds the package at
+/// `<entry>/node_modules/<name>`. Multiple installed versions of the same
+/// package are all returned.
+pub fn discover_pnpm_packages(pnpm_store: &Path) -> Vec<DiscoveredPackage> {
+    let Ok(entries) = std::fs::read_dir(pnpm_store) else {
+        return Vec::new();
+    };
+    let mut packages = Vec::new();
+    for entry in entries.flatten() {
+        let entry_name = entry.file_name().to_string_lossy().into_owned();
+        let Some((name, version)) = parse_pnpm_store_entry(&entry_name) else {
+            continue;
+        };
+        let dir = entry.path().join("node_modules").join(&name);
+        if dir.is_dir() {
+            let manifest = dir.join("package.json");
+            packages.push(DiscoveredPackage {
+                name,
+                path: dir,
+                version: Some(version.to_string()),
+                manifest: manifest.is_file().then_some(manifest),
+            });
+        }
+    }
+    packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    packages
+}
+
+// ============================================================================
+// Yarn Plug'n'Play resolution
+// ============================================================================
+
+/// A package entry from a Yarn PnP manifest (`.pnp.cjs` / `.pnp.data.json`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnpPackage {
+    /// Package name (`lodash`, `@types/node`).
+    pub name: String,
+    /// Locator reference (`npm:4.17.21`, `workspace:packages/app`).
+    pub reference: String,
+    /// Location relative to the manifest directory. Zip-archived packages
+    /// look like `./.yarn/cache/<file>.zip/node_modules/<name>/`.
+    pub location: String,
+}
+
+/// A project's parsed Yarn PnP manifest.
+#[derive(Clone)]
+struct PnpManifest {
+    /// Directory holding `.pnp.cjs` / `.pnp.data.json`.
+    dir: PathBuf,
+    packages: Arc<Vec<PnpPackage>>,
+}
+
+/// Last manifest lookup, keyed by project root. `None` records that the
+/// project has no PnP manifest.
+static PNP_CACHE: Mutex<Option<(PathBuf, Option<PnpManifest>)>> = Mutex::new(None);
+
+/// Get the cached PnP manifest for a project, locating and parsing it on
+/// first use.
+fn get_pnp_cache(project_root: &Path) -> Option<PnpManifest> {
+    // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a process-level cache is unrecoverable
+    let mut cache_guard = PNP_CACHE.lock().unwrap();
+
+    if let Some((ref root, ref manifest)) = *cache_guard
+        && root == project_root
+    {
+        return manifest.clone();
+    }
+
+    let manifest = find_pnp_root(project_root).and_then(|dir| {
+        let packages = Arc::new(read_pnp_packages(&dir)?);
+        Some(PnpManifest { dir, packages })
+    });
+    *cache_guard = Some((project_root.to_path_buf(), manifest.clone()));
+    manifest
+}
+
+/// Find the directory holding a Yarn PnP manifest by walking up from `start`.
+pub fn find_pnp_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".pnp.cjs").is_file() || dir.join(".pnp.data.json").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Extract the JSON runtime state embedded in `.pnp.cjs`.
+///
+/// Yarn inlines it as a single-quoted JS string assigned to `RAW_RUNTIME_STATE`.
+fn extract_pnp_runtime_state(pnp_cjs: &str) -> Option<String> {
+    let start = pnp_cjs.find("RAW_RUNTIME_STATE")?;
+    let rest = &pnp_cjs[start..];
+    let rest = &rest[rest.find('\'')? + 1..];
+    let mut out = String::with_capacity(rest.len());
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                other => out.push(other),
+            },
+            '\'' => return Some(out),
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Parse the `packageRegistryData` table of a PnP runtime state.
+fn parse_pnp_registry(state: &serde_json::Value) -> Vec<PnpPackage> {
+    let mut packages = Vec::new();
+    let Some(registry) = state.get("packageRegistryData").and_then(|v| v.as_array()) else {
+        return packages;
+    };
+    for entry in registry {
+        // [name | null, [[reference | null, { packageLocation, ... }], ...]]
+        let Some(name) = entry.get(0).and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let Some(refs) = entry.get(1).and_then(|v| v.as_array()) else {
+            continue;
+        };
+        for r in refs {
+            let reference = r.get(0).and_then(|v| v.as_str()).unwrap_or_default();
+            let Some(location) = r
+                .get(1)
+                .and_then(|info| info.get("packageLocation"))
+                .and_then(|v| v.as_str())
+            else {
+                continue;
+            };
+            packages.push(PnpPackage {
+                name: name.to_string(),
+                reference: reference.to_string(),
+                location: location.to_string(),
+            });
+        }
+    }
+    packages
+}
+
+/// Read all packages from the PnP manifest in `pnp_dir`.
+pub fn read_pnp_packages(pnp_dir: &Path) -> Option<Vec<PnpPackage>> {
+    let state = if let Ok(json) = std::fs::read_to_string(pnp_dir.join(".pnp.data.json")) {
+        json
+    } else {
+        extract_pnp_runtime_state(&std::fs::read_to_string(pnp_dir.join(".pnp.cjs")).ok()?)?
+    };
+    let state: serde_json::Value = serde_json::from_str(&state).ok()?;
+    Some(parse_pnp_registry(&state))
+}
+
+/// Get an on-disk directory for a PnP package.
+///
+/// Unplugged, workspace, and `link:`/`portal:` packages are plain directories.
+/// Zip-archived packages are extracted once into
+/// `<cache>/normalize/yarn-pnp/<archive>/` so they can be read like any other
+/// package; archive names embed a content checksum, so extractions are reused.
+pub fn pnp_package_dir(pnp_dir: &Path, package: &PnpPackage) -> Option<PathBuf> {
+    let location = package.location.trim_start_matches("./");
+    let Some(zip_end) = location.find(".zip/") else {
+        let dir = pnp_dir.join(location);
+        return dir.is_dir().then_some(dir);
+    };
+
+    let archive = pnp_dir.join(&location[..zip_end + ".zip".len()]);
+    let inner = location[zip_end + ".zip/".len()..].trim_end_matches('/');
+    let stem = archive.file_stem()?.to_string_lossy().into_owned();
+    let extract_root = dirs::cache_dir()?
+        .join("normalize")
+        .join("yarn-pnp")
+        .join(&stem);
+    let dir = extract_root.join(inner);
+    if dir.is_dir() {
+        return Some(dir);
+    }
+    extract_zip_archive(&archive, &extract_root).ok()?;
+    dir.is_dir().then_some(dir)
+}
+
+/// Extract a zip archive into `dest`, staging into a sibling directory and
+/// renaming so concurrent readers never observe a partial extraction.
+fn extract_zip_archive(archive: &Path, dest: &Path) -> std::io::Result<()> {
+    let parent = dest
+        .parent()
+        .ok_or_else(|| std::io::Error::other("extraction target has no parent"))?;
+    std::fs::create_dir_all(parent)?;
+    let staging = tempfile::TempDir::new_in(parent)?;
+
+    let file = std::fs::File::open(archive)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(std::io::Error::other)?;
+    zip.extract(staging.path()).map_err(std::io::Error::other)?;
+
+    let staged = staging.keep();
+    match std::fs::rename(&staged, dest) {
+        Ok(()) => Ok(()),
+        // Another process finished first; its extraction is equivalent.
+        Err(_) if dest.is_dir() => {
+            let _ = std::fs::remove_dir_all(&staged);
+            Ok(())
+        }
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staged);
+            Err(e)
+        }
+    }
+}
+
+/// Discover the packages listed in a Yarn PnP manifest.
+///
+/// Workspace packages (references starting with `workspace:`) are project
+/// code and are skipped.
+pub fn discover_pnp_packages(pnp_dir: &Path) -> Vec<DiscoveredPackage> {
+    let Some(packages) = read_pnp_packages(pnp_dir) else {
+        return Vec::new();
+    };
+    packages
+        .iter()
+        .filter(|p| !p.reference.starts_with("workspace:"))
+        .filter_map(|p| {
+            let dir = pnp_package_dir(pnp_dir, p)?;
+            let manifest = dir.join("package.json");
+            let mut package = DiscoveredPackage::new(p.name.clone(), dir);
+            // `npm:4.17.21`; other protocols (`git:`, `patch:`) carry no plain version.
+            if let Some(version) = p.reference.strip_prefix("npm:") {
+                package = package.with_version(version);
+            }
+            Some(if manifest.is_file() {
+                package.with_manifest(manifest)
+            } else {
+                package
+            })
+        })
+        .collect()
+}
+
+/// Dependency sources for Node-style package managers rooted at `project_root`:
+/// a flat `node_modules`, pnpm's virtual store, and Yarn PnP manifests.
+pub fn node_dep_sources(project_root: &Path) -> Vec<LocalDepSource> {
+    let mut sources = Vec::new();
+    if let Some(node_modules) = find_node_modules(project_root) {
+        let pnpm_store = node_modules.join(".pnpm");
+        if pnpm_store.is_dir() {
+            sources.push(LocalDepSource {
+                name: "pnpm-store",
+                path: pnpm_store,
+                kind: LocalDepSourceKind::Pnpm,
+                version_specific: false,
+            });
+        } else {
+            sources.push(LocalDepSource {
+                name: "node_modules",
+                path: node_modules,
+                kind: LocalDepSourceKind::NpmScoped,
+                version_specific: false,
+            });
+        }
+    } else if let Some(pnp_dir) = find_pnp_root(project_root) {
+        sources.push(LocalDepSource {
+            name: "yarn-pnp",
+            path: pnp_dir,
+            kind: LocalDepSourceKind::YarnPnp,
+            version_specific: false,
+        });
+    }
+    sources
+}
+
 // ============================================================================
 // Deno external package resolution
 // ============================================================================
@@ -510,3 +852,81 @@ pub fn find_package_entry(dir: &Path) -> Option<PathBuf> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pnpm_store_entry() {
+        assert_eq!(
+            parse_pnpm_store_entry("lodash@4.17.21"),
+            Some(("lodash".to_string(), "4.17.21"))
+        );
+        assert_eq!(
+            parse_pnpm_store_entry("@types+node@20.11.5"),
+            Some(("@types/node".to_string(), "20.11.5"))
+        );
+        assert_eq!(
+            parse_pnpm_store_entry("react-dom@18.2.0_react@18.2.0"),
+            Some(("react-dom".to_string(), "18.2.0"))
+        );
+        assert_eq!(
+            parse_pnpm_store_entry("@scope+pkg@1.0.0(react@18.2.0)"),
+            Some(("@scope/pkg".to_string(), "1.0.0"))
+        );
+        assert_eq!(parse_pnpm_store_entry("node_modules"), None);
+        assert_eq!(parse_pnpm_store_entry("lock.yaml"), None);
+    }
+
+    #[test]
+    fn test_parse_pnp_runtime_state() {
+        let pnp_cjs = r#"#!/usr/bin/env node
+/* eslint-disable */
+"use strict";
+
+const RAW_RUNTIME_STATE =
+'{\
+  "__info": ["This file is automatically generated."],\
+  "packageRegistryData": [\
+    [null, [[null, {"packageLocation": "./", "linkType": "SOFT"}]]],\
+    ["app", [["workspace:.", {"packageLocation": "./", "linkType": "SOFT"}]]],\
+    ["lodash", [["npm:4.17.21", {"packageLocation": "./.yarn/cache/lodash-npm-4.17.21-6382451519-eb835a2e51.zip/node_modules/lodash/", "linkType": "HARD"}]]],\
+    ["esbuild", [["npm:0.19.0", {"packageLocation": "./.yarn/unplugged/esbuild-npm-0.19.0-abc/node_modules/esbuild/", "linkType": "HARD"}]]]\
+  ]\
+}';
+"#;
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let state = extract_pnp_runtime_state(pnp_cjs).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let value: serde_json::Value = serde_json::from_str(&state).unwrap();
+        let packages = parse_pnp_registry(&value);
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "lodash", "esbuild"]);
+        assert_eq!(packages[1].reference, "npm:4.17.21");
+        assert!(packages[1].location.contains(".zip/node_modules/lodash/"));
+    }
+
+    #[test]
+    fn test_pnpm_hoisted_and_versioned_lookup() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let store = tmp.path();
+        for dir in [
+            "ms@2.0.0/node_modules/ms",
+            "ms@2.1.3/node_modules/ms",
+            "@scope+util@1.0.0/node_modules/@scope/util",
+        ] {
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::create_dir_all(store.join(dir)).unwrap();
+        }
+
+        let ms = find_pnpm_package(store, "ms");
+        let util = find_pnpm_package(store, "@scope/util");
+        let all = discover_pnpm_packages(store);
+
+        assert!(ms.is_some_and(|p| p.ends_with("ms@2.1.3/node_modules/ms")));
+        assert!(util.is_some());
+        assert_eq!(all.len(), 3);
+    }
+}
@@ -1,6 +1,6 @@
 //! Go local dependency discovery.
 
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage};
 use normalize_manifest::GoModule;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -242,6 +242,15 @@ fn resolve_go_mod_cache_import(import_path: &str, mod_cache: &Path) -> Option<Re
 // Go local dependency discovery
 // ============================================================================
 
+/// Extract the module version from a module-cache relative path
+/// (`github.com/pkg/errors@v0.9.1/errors.go` -> `v0.9.1`).
+fn module_cache_version(rel_path: &str) -> Option<String> {
+    rel_path
+        .split('/')
+        .find_map(|segment| segment.split_once('@'))
+        .map(|(_, version)| version.to_string())
+}
+
 /// Go local dependency discovery.
 pub struct GoDeps;
 
@@ -369,8 +378,12 @@ impl LocalDeps for GoDeps {
             .to_string()
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
-        self.discover_recursive_packages(&source.path, &source.path)
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+        let mut packages = self.discover_recursive_packages(&source.path, &source.path);
+        for package in &mut packages {
+            package.version = module_cache_version(&package.name);
+        }
+        packages
     }
 
     fn find_package_entry(&self, path: &Path) -> Option<PathBuf> {
@@ -448,4 +461,17 @@ require (
         let result = resolve_go_import("github.com/other/lib", &module, Path::new("/fake/root"));
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_module_cache_version() {
+        assert_eq!(
+            module_cache_version("github.com/pkg/errors@v0.9.1/errors.go").as_deref(),
+            Some("v0.9.1")
+        );
+        assert_eq!(
+            module_cache_version("golang.org/x/sync@v0.3.0/errgroup/errgroup.go").as_deref(),
+            Some("v0.3.0")
+        );
+        assert!(module_cache_version("fmt/print.go").is_none());
+    }
 }
@@ -0,0 +1,403 @@
+//! Resolved dependency graph for a project.
+//!
+//! Combines the dependencies declared in the project's manifest with the
+//! packages discovered on disk by a [`LocalDeps`] implementation, then follows
+//! each installed package's own manifest to collect transitive dependencies.
+//!
+//! The graph is serializable (JSON via serde) and can be rendered as Graphviz
+//! DOT with [`LocalDependencyGraph::to_dot`].
+
+use crate::{DiscoveredPackage, LocalDeps};
+use normalize_manifest::{DepKind, ParsedManifest, parse_manifest};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// A package in the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyNode {
+    /// Package name as declared in the manifest.
+    pub name: String,
+    /// Installed version, if the package was found on disk and records one.
+    pub version: Option<String>,
+    /// Path to the installed package; `None` when it could not be located.
+    pub path: Option<PathBuf>,
+    /// Whether the project manifest declares this package directly.
+    pub direct: bool,
+    /// Shortest distance from the project root (1 = direct dependency).
+    pub depth: usize,
+}
+
+/// A declared dependency from one package to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyEdge {
+    /// Depending package (the project name for direct dependencies).
+    pub from: String,
+    /// Dependency name.
+    pub to: String,
+    /// Version requirement as written in the declaring manifest.
+    pub version_req: Option<String>,
+    pub kind: DepKind,
+    /// Manifest the edge was read from.
+    pub manifest: PathBuf,
+}
+
+/// Direct and transitive dependencies of a project, resolved against the
+/// locally-installed packages.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalDependencyGraph {
+    /// Project name (from the manifest, falling back to the directory name).
+    pub root: String,
+    /// Ecosystem of the root manifest (e.g. `"cargo"`, `"npm"`).
+    pub ecosystem: &'static str,
+    /// Path to the root manifest.
+    pub manifest: PathBuf,
+    /// One node per dependency, in breadth-first discovery order.
+    pub nodes: Vec<DependencyNode>,
+    /// Edges in the order they were declared.
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl LocalDependencyGraph {
+    /// Build the graph for `project_root` using `deps` for package discovery.
+    ///
+    /// Returns `None` if the project has no recognised manifest. Dev
+    /// dependencies are followed only from the root manifest, since installed
+    /// packages don't ship theirs.
+    pub fn build(deps: &dyn LocalDeps, project_root: &Path) -> Option<Self> {
+        let (manifest_path, manifest) = read_root_manifest(deps, project_root)?;
+        let root = manifest.name.clone().unwrap_or_else(|| {
+            std::fs::canonicalize(project_root)
+                .unwrap_or_else(|_| project_root.to_path_buf())
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+
+        let installed = InstalledPackages::discover(deps, project_root);
+        let mut graph = LocalDependencyGraph {
+            root: root.clone(),
+            ecosystem: manifest.ecosystem,
+            manifest: manifest_path.clone(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(usize, ParsedManifest, PathBuf)> = VecDeque::new();
+        queue.push_back((0, manifest, manifest_path));
+
+        while let Some((depth, manifest, manifest_path)) = queue.pop_front() {
+            let from = if depth == 0 {
+                root.clone()
+            } else {
+                manifest.name.clone().unwrap_or_default()
+            };
+            for dep in manifest.dependencies {
+                if depth > 0 && dep.kind == DepKind::Dev {
+                    continue;
+                }
+                graph.edges.push(DependencyEdge {
+                    from: from.clone(),
+                    to: dep.name.clone(),
+                    version_req: dep.version_req.clone(),
+                    kind: dep.kind,
+                    manifest: manifest_path.clone(),
+                });
+                if !seen.insert(dep.name.clone()) {
+                    continue;
+                }
+                let pkg = installed.find(&dep.name, dep.version_req.as_deref());
+                graph.nodes.push(DependencyNode {
+                    name: dep.name.clone(),
+                    version: pkg.and_then(|p| p.version.clone()),
+                    path: pkg.map(|p| p.path.clone()),
+                    direct: depth == 0,
+                    depth: depth + 1,
+                });
+                if let Some(child_path) = pkg.and_then(|p| p.manifest.clone())
+                    && let Some(mut child) = read_manifest(&child_path)
+                {
+                    child.name = Some(dep.name.clone());
+                    queue.push_back((depth + 1, child, child_path));
+                }
+            }
+        }
+        Some(graph)
+    }
+
+    /// Look up a node by package name.
+    pub fn node(&self, name: &str) -> Option<&DependencyNode> {
+        self.nodes.iter().find(|n| n.name == name)
+    }
+
+    /// Direct dependencies of `name` (the root name for the project itself).
+    pub fn dependencies_of<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a DependencyEdge> {
+        self.edges.iter().filter(move |e| e.from == name)
+    }
+
+    /// Render as a Graphviz `digraph`. Direct dependencies are drawn bold;
+    /// packages that couldn't be found locally are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph dependencies {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str(&format!("  {} [shape=box];\n", dot_id(&self.root)));
+        for node in &self.nodes {
+            let label = match &node.version {
+                Some(v) => format!("{}\\n{}", node.name, v),
+                None => node.name.clone(),
+            };
+            let mut attrs = vec![format!("label={}", dot_id(&label))];
+            let mut styles = Vec::new();
+            if node.direct {
+                styles.push("bold");
+            }
+            if node.path.is_none() {
+                styles.push("dashed");
+            }
+            if !styles.is_empty() {
+                attrs.push(format!("style={}", dot_id(&styles.join(","))));
+            }
+            out.push_str(&format!(
+                "  {} [{}];\n",
+                dot_id(&node.name),
+                attrs.join(", ")
+            ));
+        }
+        for edge in &self.edges {
+            let mut line = format!("  {} -> {}", dot_id(&edge.from), dot_id(&edge.to));
+            if let Some(req) = &edge.version_req {
+                line.push_str(&format!(" [label={}]", dot_id(req)));
+            }
+            line.push_str(";\n");
+            out.push_str(&line);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Quote a string as a DOT identifier.
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+fn read_manifest(path: &Path) -> Option<ParsedManifest> {
+    let filename = path.file_name()?.to_str()?;
+    let content = std::fs::read_to_string(path).ok()?;
+    parse_manifest(filename, &content)
+}
+
+fn read_root_manifest(deps: &dyn LocalDeps, root: &Path) -> Option<(PathBuf, ParsedManifest)> {
+    deps.project_manifest_filenames().iter().find_map(|name| {
+        let path = root.join(name);
+        read_manifest(&path).map(|m| (path, m))
+    })
+}
+
+/// Normalize a package name for lookup: case-insensitive, with `-`, `_` and
+/// `.` treated as equivalent (PEP 503 style; also covers Cargo's `-`/`_`).
+fn normalize_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace(['-', '.'], "_")
+}
+
+/// Locally-installed packages indexed by normalized name.
+struct InstalledPackages {
+    by_name: HashMap<String, Vec<DiscoveredPackage>>,
+}
+
+impl InstalledPackages {
+    fn discover(deps: &dyn LocalDeps, project_root: &Path) -> Self {
+        let mut by_name: HashMap<String, Vec<DiscoveredPackage>> = HashMap::new();
+        for source in deps.dep_sources(project_root) {
+            if source.name == "stdlib" {
+                continue;
+            }
+            for pkg in deps.discover_packages(&source) {
+                by_name
+                    .entry(normalize_name(&pkg.name))
+                    .or_default()
+                    .push(pkg);
+            }
+        }
+        Self { by_name }
+    }
+
+    /// Pick the installed package for a dependency. When several versions
+    /// are installed (shared caches), prefer one matching the literal version
+    /// in the requirement, then the first source in priority order.
+    fn find(&self, name: &str, version_req: Option<&str>) -> Option<&DiscoveredPackage> {
+        let candidates = self.by_name.get(&normalize_name(name))?;
+        let wanted = version_req
+            .map(|r| r.trim_start_matches(|c: char| !c.is_ascii_digit()))
+            .filter(|r| !r.is_empty());
+        if let Some(wanted) = wanted
+            && let Some(pkg) = candidates.iter().find(|p| {
+                p.version
+                    .as_deref()
+                    .is_some_and(|v| version_has_prefix(v, wanted))
+            })
+        {
+            return Some(pkg);
+        }
+        candidates.first()
+    }
+}
+
+/// Whether `version` is `prefix` or extends it by whole `.` segments, so that
+/// "1.2" matches "1.2.0" but not "1.20.0".
+fn version_has_prefix(version: &str, prefix: &str) -> bool {
+    version
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LocalDepSource, LocalDepSourceKind};
+
+    /// Minimal npm-style ecosystem: `node_modules` as a flat source.
+    struct NodeModules;
+
+    impl LocalDeps for NodeModules {
+        fn language_name(&self) -> &'static str {
+            "test"
+        }
+        fn indexable_extensions(&self) -> &'static [&'static str] {
+            &["js"]
+        }
+        fn project_manifest_filenames(&self) -> &'static [&'static str] {
+            &["package.json"]
+        }
+        fn dep_sources(&self, project_root: &Path) -> Vec<LocalDepSource> {
+            vec![LocalDepSource {
+                name: "node_modules",
+                path: project_root.join("node_modules"),
+                kind: LocalDepSourceKind::Flat,
+                version_specific: false,
+            }]
+        }
+        fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+            self.discover_flat_packages(&source.path)
+        }
+    }
+
+    fn write_package(dir: &Path, json: &str) {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(dir).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(dir.join("package.json"), json).unwrap();
+    }
+
+    fn fixture() -> LocalDependencyGraph {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write_package(
+            root,
+            r#"{"name": "app", "version": "0.1.0",
+                "dependencies": {"lib-a": "^1.2.0", "missing": "2"},
+                "devDependencies": {"tester": "~3.0.0"}}"#,
+        );
+        let nm = root.join("node_modules");
+        write_package(
+            &nm.join("lib-a"),
+            r#"{"name": "lib-a", "version": "1.2.3",
+                "dependencies": {"lib-b": "^0.4"},
+                "devDependencies": {"ignored": "1"}}"#,
+        );
+        write_package(
+            &nm.join("lib-b"),
+            r#"{"name": "lib-b", "version": "0.4.1"}"#,
+        );
+        write_package(
+            &nm.join("tester"),
+            r#"{"name": "tester", "version": "3.0.2"}"#,
+        );
+        let graph = LocalDependencyGraph::build(&NodeModules, root);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        graph.unwrap()
+    }
+
+    #[test]
+    fn test_build_resolves_direct_and_transitive() {
+        let graph = fixture();
+        assert_eq!(graph.root, "app");
+        assert_eq!(graph.ecosystem, "npm");
+
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let a = graph.node("lib-a").unwrap();
+        assert!(a.direct);
+        assert_eq!(a.depth, 1);
+        assert_eq!(a.version.as_deref(), Some("1.2.3"));
+
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let b = graph.node("lib-b").unwrap();
+        assert!(!b.direct);
+        assert_eq!(b.depth, 2);
+        assert_eq!(b.version.as_deref(), Some("0.4.1"));
+        assert!(
+            b.path
+                .as_ref()
+                .is_some_and(|p| p.ends_with("node_modules/lib-b"))
+        );
+
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let missing = graph.node("missing").unwrap();
+        assert!(missing.path.is_none());
+        assert!(missing.version.is_none());
+
+        // Dev deps of installed packages are not followed; the root's are.
+        assert!(graph.node("tester").is_some());
+        assert!(graph.node("ignored").is_none());
+
+        let from_a: Vec<_> = graph
+            .dependencies_of("lib-a")
+            .map(|e| e.to.as_str())
+            .collect();
+        assert_eq!(from_a, ["lib-b"]);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = fixture().to_dot();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains(r#""app" -> "lib-a" [label="^1.2.0"];"#));
+        assert!(dot.contains(r#""lib-a" -> "lib-b""#));
+        assert!(dot.contains(r#""missing" [label="missing", style="bold,dashed"];"#));
+    }
+
+    #[test]
+    fn test_find_matches_whole_version_segments() {
+        let installed = InstalledPackages {
+            by_name: HashMap::from([(
+                "lib".to_string(),
+                vec![
+                    DiscoveredPackage::new("lib", "/cache/lib-1.20.0").with_version("1.20.0"),
+                    DiscoveredPackage::new("lib", "/cache/lib-1.2.5").with_version("1.2.5"),
+                ],
+            )]),
+        };
+        let version = |req| {
+            installed
+                .find("lib", req)
+                .and_then(|p| p.version.as_deref())
+        };
+        assert_eq!(version(Some("1.2")), Some("1.2.5"));
+        assert_eq!(version(Some("^1.20")), Some("1.20.0"));
+        assert_eq!(version(Some("1.2.5")), Some("1.2.5"));
+        // No installed version matches: fall back to the first source.
+        assert_eq!(version(Some("1.3")), Some("1.20.0"));
+    }
+
+    #[test]
+    fn test_no_manifest() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let graph = LocalDependencyGraph::build(&NodeModules, tmp.path());
+        assert!(graph.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 //! Java local dependency discovery.
 
 use crate::ResolvedPackage;
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps};
 use crate::{has_extension, skip_dotfiles};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -346,7 +346,7 @@ fn find_maven_jar(version_dir: &Path, artifact: &str) -> Option<PathBuf> {
 }
 
 /// Discover packages in Maven repository structure.
-pub fn discover_maven_packages(maven_repo: &Path, current: &Path) -> Vec<(String, PathBuf)> {
+pub fn discover_maven_packages(maven_repo: &Path, current: &Path) -> Vec<DiscoveredPackage> {
     let entries = match std::fs::read_dir(current) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
@@ -367,7 +367,14 @@ pub fn discover_maven_packages(maven_repo: &Path, current: &Path) -> Vec<(String
                         let pkg_name = format!("{}:{}", group, artifact);
 
                         if let Some(jar_path) = find_maven_jar(&path, &artifact) {
-                            packages.push((pkg_name, jar_path));
+                            let version = entry.file_name().to_string_lossy().into_owned();
+                            let pom = path.join(format!("{artifact}-{version}.pom"));
+                            let mut package =
+                                DiscoveredPackage::new(pkg_name, jar_path).with_version(version);
+                            if pom.is_file() {
+                                package.manifest = Some(pom);
+                            }
+                            packages.push(package);
                         }
                     }
                 }
@@ -381,7 +388,7 @@ pub fn discover_maven_packages(maven_repo: &Path, current: &Path) -> Vec<(String
 }
 
 /// Discover packages in Gradle cache structure.
-pub fn discover_gradle_packages(gradle_cache: &Path, current: &Path) -> Vec<(String, PathBuf)> {
+pub fn discover_gradle_packages(gradle_cache: &Path, current: &Path) -> Vec<DiscoveredPackage> {
     let entries = match std::fs::read_dir(current) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
@@ -404,18 +411,20 @@ pub fn discover_gradle_packages(gradle_cache: &Path, current: &Path) -> Vec<(Str
                             && !file_name.ends_with("-sources.jar")
                             && !file_name.ends_with("-javadoc.jar")
                         {
-                            // Extract package info from path
+                            // Extract package info from path: <group>/<artifact>/<version>/<hash>
                             if let Ok(rel) = current.strip_prefix(gradle_cache) {
-                                let parts: Vec<_> = rel.components().collect();
-                                if let (Some(artifact), true) = (parts.last(), parts.len() >= 2) {
-                                    let group = parts[..parts.len() - 1]
-                                        .iter()
-                                        .map(|c| c.as_os_str().to_string_lossy())
-                                        .collect::<Vec<_>>()
-                                        .join(".");
-                                    let artifact = artifact.as_os_str().to_string_lossy();
-                                    let pkg_name = format!("{}:{}", group, artifact);
-                                    packages.push((pkg_name, file.path()));
+                                let parts: Vec<_> = rel
+                                    .components()
+                                    .map(|c| c.as_os_str().to_string_lossy())
+                                    .collect();
+                                if let [group @ .., artifact, version] = parts.as_slice()
+                                    && !group.is_empty()
+                                {
+                                    let pkg_name = format!("{}:{}", group.join("."), artifact);
+                                    packages.push(
+                                        DiscoveredPackage::new(pkg_name, file.path())
+                                            .with_version(version.as_ref()),
+                                    );
                                 }
                             }
                         }
@@ -555,7 +564,7 @@ impl LocalDeps for JavaDeps {
         !is_dir && !has_extension(name, self.indexable_extensions())
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         match source.kind {
             LocalDepSourceKind::Maven => discover_maven_packages(&source.path, &source.path),
             LocalDepSourceKind::Gradle => discover_gradle_packages(&source.path, &source.path),
@@ -2,7 +2,7 @@
 
 use crate::ResolvedPackage;
 use crate::ecmascript;
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps};
 use crate::{has_extension, skip_dotfiles};
 use std::path::{Path, PathBuf};
 
@@ -52,15 +52,7 @@ impl LocalDeps for JavaScriptDeps {
     }
 
     fn dep_sources(&self, project_root: &Path) -> Vec<LocalDepSource> {
-        let mut sources = Vec::new();
-        if let Some(cache) = self.find_package_cache(project_root) {
-            sources.push(LocalDepSource {
-                name: "node_modules",
-                path: cache,
-                kind: LocalDepSourceKind::NpmScoped,
-                version_specific: false,
-            });
-        }
+        let mut sources = ecmascript::node_dep_sources(project_root);
         // Also check for Deno cache
         if let Some(deno_cache) = ecmascript::find_deno_cache() {
             let npm_cache = deno_cache.join("npm").join("registry.npmjs.org");
@@ -88,9 +80,11 @@ impl LocalDeps for JavaScriptDeps {
         !is_dir && !has_extension(name, self.indexable_extensions())
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         match source.kind {
             LocalDepSourceKind::NpmScoped => self.discover_npm_scoped_packages(&source.path),
+            LocalDepSourceKind::Pnpm => ecmascript::discover_pnpm_packages(&source.path),
+            LocalDepSourceKind::YarnPnp => ecmascript::discover_pnp_packages(&source.path),
             LocalDepSourceKind::Deno => discover_deno_packages(&source.path),
             _ => Vec::new(),
         }
@@ -143,7 +137,7 @@ impl LocalDeps for JavaScriptDeps {
 }
 
 /// Discover packages in Deno npm cache (package/version/ structure with scoped packages).
-fn discover_deno_packages(source_path: &Path) -> Vec<(String, PathBuf)> {
+fn discover_deno_packages(source_path: &Path) -> Vec<DiscoveredPackage> {
     let entries = match std::fs::read_dir(source_path) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
@@ -166,15 +160,13 @@ fn discover_deno_packages(source_path: &Path) -> Vec<(String, PathBuf)> {
                     let scoped_path = scoped_entry.path();
                     let scoped_name =
                         format!("{}/{}", name, scoped_entry.file_name().to_string_lossy());
-                    if let Some((pkg_name, pkg_path)) =
-                        find_deno_version_dir(&scoped_path, &scoped_name)
-                    {
-                        packages.push((pkg_name, pkg_path));
+                    if let Some(package) = find_deno_version_dir(&scoped_path, &scoped_name) {
+                        packages.push(package);
                     }
                 }
             }
-        } else if let Some((pkg_name, pkg_path)) = find_deno_version_dir(&path, &name) {
-            packages.push((pkg_name, pkg_path));
+        } else if let Some(package) = find_deno_version_dir(&path, &name) {
+            packages.push(package);
         }
     }
 
@@ -182,7 +174,7 @@ fn discover_deno_packages(source_path: &Path) -> Vec<(String, PathBuf)> {
 }
 
 /// Find the latest version directory in a Deno package directory.
-fn find_deno_version_dir(pkg_path: &Path, pkg_name: &str) -> Option<(String, PathBuf)> {
+fn find_deno_version_dir(pkg_path: &Path, pkg_name: &str) -> Option<DiscoveredPackage> {
     let versions: Vec<_> = std::fs::read_dir(pkg_path)
         .ok()?
         .flatten()
@@ -195,5 +187,12 @@ fn find_deno_version_dir(pkg_path: &Path, pkg_name: &str) -> Option<(String, Pat
 
     // Use the last version (sorted lexically, usually latest)
     let version_dir = versions.last()?.path();
-    Some((pkg_name.to_string(), version_dir))
+    let version = version_dir.file_name()?.to_string_lossy().into_owned();
+    let package = DiscoveredPackage::new(pkg_name, version_dir.clone()).with_version(version);
+    let manifest = version_dir.join("package.json");
+    Some(if manifest.is_file() {
+        package.with_manifest(manifest)
+    } else {
+        package
+    })
 }
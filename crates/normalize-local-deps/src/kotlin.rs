@@ -2,7 +2,7 @@
 
 use crate::ResolvedPackage;
 use crate::java::{find_gradle_cache, find_maven_repository, get_java_version};
-use crate::{LocalDepSource, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDeps};
 use crate::{has_extension, skip_dotfiles};
 use std::path::{Path, PathBuf};
 
@@ -117,7 +117,7 @@ impl LocalDeps for KotlinDeps {
         !is_dir && !has_extension(name, self.indexable_extensions())
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         // Reuse Java's package discovery
         crate::java::JavaDeps.discover_packages(source)
     }
@@ -8,7 +8,7 @@
 //! "given this project on disk, where are the locally-installed packages
 //! so we can index their symbols?"
 //!
-//! Only ~10 language ecosystems have real implementations. The trait provides
+//! Only ~11 language ecosystems have real implementations. The trait provides
 //! blanket defaults returning empty/None for all methods.
 
 #[cfg(any(feature = "lang-javascript", feature = "lang-typescript"))]
@@ -22,6 +22,7 @@ pub mod c_cpp;
 pub mod cpp;
 #[cfg(feature = "lang-go")]
 pub mod go;
+pub mod graph;
 #[cfg(feature = "lang-java")]
 pub mod java;
 #[cfg(feature = "lang-javascript")]
@@ -37,6 +38,10 @@ pub mod rust_lang;
 pub mod scala;
 #[cfg(feature = "lang-typescript")]
 pub mod typescript;
+#[cfg(feature = "lang-zig")]
+pub mod zig;
+
+pub use graph::{DependencyEdge, DependencyNode, LocalDependencyGraph};
 
 use std::path::{Path, PathBuf};
 
@@ -51,6 +56,53 @@ pub struct ResolvedPackage {
     pub is_namespace: bool,
 }
 
+/// A package found in a local dependency source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredPackage {
+    /// Package name as imported (e.g., "requests", "@types/node", "serde")
+    pub name: String,
+    /// Path to the package directory or entry file
+    pub path: PathBuf,
+    /// Installed version, when the manifest or on-disk layout records one
+    pub version: Option<String>,
+    /// Manifest the metadata was read from (package.json, METADATA, Cargo.toml, ...)
+    pub manifest: Option<PathBuf>,
+}
+
+impl DiscoveredPackage {
+    /// A package with no version or manifest information.
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            version: None,
+            manifest: None,
+        }
+    }
+
+    /// Set the installed version.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Record the package's manifest, reading the version from it if none is set yet.
+    pub fn with_manifest(mut self, manifest: PathBuf) -> Self {
+        if self.version.is_none() {
+            self.version = read_manifest_version(&manifest);
+        }
+        self.manifest = Some(manifest);
+        self
+    }
+}
+
+/// Read the `version` field of a manifest file, dispatching on its filename.
+pub fn read_manifest_version(manifest: &Path) -> Option<String> {
+    let filename = manifest.file_name()?.to_str()?;
+    let content = std::fs::read_to_string(manifest).ok()?;
+    normalize_manifest::parse_manifest(filename, &content)?.version
+}
+
 /// A source of local packages to index.
 #[derive(Debug, Clone)]
 pub struct LocalDepSource {
@@ -83,6 +135,13 @@ pub enum LocalDepSourceKind {
     Cargo,
     /// Deno cache structure (needs special handling for npm vs URL deps).
     Deno,
+    /// pnpm virtual store (`node_modules/.pnpm/<name>@<version>/node_modules/<name>`).
+    Pnpm,
+    /// Yarn Plug'n'Play manifest directory (`.pnp.cjs` / `.pnp.data.json`).
+    YarnPnp,
+    /// Zig packages declared in a project's `build.zig.zon`; `path` is the
+    /// project root and packages resolve into the global cache by hash.
+    ZigZon,
 }
 
 // === Helper functions for should_skip_dep_entry ===
@@ -207,13 +266,31 @@ pub trait LocalDeps: Send + Sync {
     }
 
     /// Discover packages in a source directory.
-    /// Returns (package_name, path) pairs for all packages found.
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    /// Returns every package found, with version and manifest where known.
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         self.discover_flat_packages(&source.path)
     }
 
+    /// Describe a package directory, reading its version from the first of
+    /// `project_manifest_filenames()` present inside it.
+    fn describe_package(&self, name: String, path: PathBuf) -> DiscoveredPackage {
+        let manifest = if path.is_dir() {
+            self.project_manifest_filenames()
+                .iter()
+                .map(|f| path.join(f))
+                .find(|m| m.is_file())
+        } else {
+            None
+        };
+        let package = DiscoveredPackage::new(name, path);
+        match manifest {
+            Some(manifest) => package.with_manifest(manifest),
+            None => package,
+        }
+    }
+
     /// Discover packages in a flat directory (each entry is a package).
-    fn discover_flat_packages(&self, source_path: &Path) -> Vec<(String, PathBuf)> {
+    fn discover_flat_packages(&self, source_path: &Path) -> Vec<DiscoveredPackage> {
         let entries = match std::fs::read_dir(source_path) {
             Ok(e) => e,
             Err(_) => return Vec::new(),
@@ -229,7 +306,7 @@ pub trait LocalDeps: Send + Sync {
             }
 
             let module_name = self.dep_module_name(&name);
-            packages.push((module_name, path));
+            packages.push(self.describe_package(module_name, path));
         }
         packages
     }
@@ -239,7 +316,7 @@ pub trait LocalDeps: Send + Sync {
         &self,
         base_path: &Path,
         current_path: &Path,
-    ) -> Vec<(String, PathBuf)> {
+    ) -> Vec<DiscoveredPackage> {
         let entries = match std::fs::read_dir(current_path) {
             Ok(e) => e,
             Err(_) => return Vec::new(),
@@ -263,7 +340,7 @@ pub trait LocalDeps: Send + Sync {
                     .strip_prefix(base_path)
                     .map(|p| p.to_string_lossy().to_string())
                     .unwrap_or_else(|_| name);
-                packages.push((rel_path, path));
+                packages.push(DiscoveredPackage::new(rel_path, path));
             }
         }
         packages
@@ -281,7 +358,7 @@ pub trait LocalDeps: Send + Sync {
     }
 
     /// Discover packages in npm-scoped directory (handles @scope/package).
-    fn discover_npm_scoped_packages(&self, source_path: &Path) -> Vec<(String, PathBuf)> {
+    fn discover_npm_scoped_packages(&self, source_path: &Path) -> Vec<DiscoveredPackage> {
         let entries = match std::fs::read_dir(source_path) {
             Ok(e) => e,
             Err(_) => return Vec::new(),
@@ -306,12 +383,12 @@ pub trait LocalDeps: Send + Sync {
                             continue;
                         }
                         let full_name = format!("{}/{}", name, scoped_name);
-                        packages.push((full_name, scoped_path));
+                        packages.push(self.describe_package(full_name, scoped_path));
                     }
                 }
             } else {
                 let module_name = self.dep_module_name(&name);
-                packages.push((module_name, path));
+                packages.push(self.describe_package(module_name, path));
             }
         }
         packages
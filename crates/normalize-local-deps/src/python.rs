@@ -1,152 +1,480 @@
 //! Python local dependency discovery.
 
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 // ============================================================================
-// Python path cache (filesystem-based detection, no subprocess calls)
+// Python environment detection (filesystem-based, no subprocess calls)
 // ============================================================================
 
-static PYTHON_CACHE: Mutex<Option<PythonPathCache>> = Mutex::new(None);
-
-/// Cached Python paths detected from filesystem structure.
-#[derive(Clone)]
-struct PythonPathCache {
-    /// Canonical project root used as cache key
-    root: PathBuf,
-    /// Python version (e.g., "3.13")
-    version: Option<String>,
-    /// Stdlib path (e.g., /usr/.../lib/python3.13/)
-    stdlib: Option<PathBuf>,
-    /// Site-packages path
-    site_packages: Option<PathBuf>,
-}
-
-impl PythonPathCache {
-    fn new(root: &Path) -> Self {
-        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
-
-        // Try to find Python from venv or PATH
-        let python_bin = if root.join(".venv/bin/python").exists() {
-            Some(root.join(".venv/bin/python"))
-        } else if root.join("venv/bin/python").exists() {
-            Some(root.join("venv/bin/python"))
-        } else {
-            // Look in PATH
-            std::env::var("PATH").ok().and_then(|path| {
-                for dir in path.split(':') {
-                    let python = PathBuf::from(dir).join("python3");
-                    if python.exists() {
-                        return Some(python);
-                    }
-                    let python = PathBuf::from(dir).join("python");
-                    if python.exists() {
-                        return Some(python);
-                    }
-                }
-                None
+/// Last detected environment, keyed by canonical project root. `None` records
+/// that detection found no environment, so it is not retried on every lookup.
+static PYTHON_CACHE: Mutex<Option<(PathBuf, Option<PythonEnv>)>> = Mutex::new(None);
+
+/// Kind of Python environment a project resolves imports against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonEnvKind {
+    /// Virtualenv created by uv (`pyvenv.cfg` carries a `uv` key).
+    Uv,
+    /// Standard virtualenv (`python -m venv`, virtualenv, poetry, hatch).
+    Venv,
+    /// Conda environment (prefix contains `conda-meta/`).
+    Conda,
+    /// PEP 582 local packages directory (`__pypackages__/X.Y/lib`).
+    Pep582,
+    /// Interpreter found on `PATH`, no isolated environment.
+    System,
+}
+
+/// A detected Python environment.
+#[derive(Debug, Clone)]
+pub struct PythonEnv {
+    pub kind: PythonEnvKind,
+    /// Environment root (venv directory, conda prefix, `__pypackages__/X.Y`, or system prefix).
+    pub prefix: PathBuf,
+    /// Python version (e.g., "3.13").
+    pub version: Option<String>,
+    /// Stdlib path (e.g., /usr/.../lib/python3.13/).
+    pub stdlib: Option<PathBuf>,
+    /// Site-packages path.
+    pub site_packages: Option<PathBuf>,
+    /// Import roots of editable installs (`.pth` path entries and
+    /// `direct_url.json` editable projects) found in site-packages.
+    pub editable_paths: Vec<PathBuf>,
+}
+
+impl PythonEnv {
+    fn empty(kind: PythonEnvKind, prefix: PathBuf) -> Self {
+        Self {
+            kind,
+            prefix,
+            version: None,
+            stdlib: None,
+            site_packages: None,
+            editable_paths: Vec::new(),
+        }
+    }
+}
+
+/// Detect the Python environment for a project.
+///
+/// Search order:
+/// 1. Project-local environments, walking up from `project_root` to the
+///    repository root (so uv workspace members find the workspace `.venv`):
+///    `.venv`/`venv` (uv or standard venv), `.conda` (conda prefix),
+///    `__pypackages__` (PEP 582).
+/// 2. The activated environment (`VIRTUAL_ENV`, then `CONDA_PREFIX`).
+/// 3. The first `python3`/`python` on `PATH`.
+pub fn detect_python_env(project_root: &Path) -> Option<PythonEnv> {
+    get_python_cache(project_root)
+}
+
+/// Walk from `start` up to (and including) the nearest directory containing `.git`.
+fn ancestors_to_repo_root(start: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for dir in start.ancestors() {
+        dirs.push(dir.to_path_buf());
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    dirs
+}
+
+fn detect_python_env_uncached(root: &Path) -> Option<PythonEnv> {
+    for dir in ancestors_to_repo_root(root) {
+        for name in [".venv", "venv"] {
+            if let Some(env) = venv_env(&dir.join(name)) {
+                return Some(env);
+            }
+        }
+        if let Some(env) = conda_env(&dir.join(".conda")) {
+            return Some(env);
+        }
+        if let Some(env) = pep582_env(&dir.join("__pypackages__")) {
+            return Some(env);
+        }
+    }
+
+    if let Ok(venv) = std::env::var("VIRTUAL_ENV")
+        && let Some(env) = venv_env(Path::new(&venv))
+    {
+        return Some(env);
+    }
+    if let Ok(prefix) = std::env::var("CONDA_PREFIX")
+        && let Some(env) = conda_env(Path::new(&prefix))
+    {
+        return Some(env);
+    }
+
+    system_env()
+}
+
+/// Parse `key = value` lines from a `pyvenv.cfg`.
+fn read_pyvenv_cfg(path: &Path) -> Option<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(
+        content
+            .lines()
+            .filter_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                Some((k.trim().to_string(), v.trim().to_string()))
             })
-        };
+            .collect(),
+    )
+}
 
-        let Some(python_bin) = python_bin else {
-            return Self {
-                root,
-                version: None,
-                stdlib: None,
-                site_packages: None,
-            };
+/// Truncate a version string to major.minor ("3.12.1.final.0" -> "3.12").
+fn major_minor(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    if major.chars().all(|c| c.is_ascii_digit()) && minor.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("{}.{}", major, minor))
+    } else {
+        None
+    }
+}
+
+/// Numeric sort key for a "X.Y" version, so 3.10 outranks 3.9.
+fn version_key(version: &str) -> (u32, u32) {
+    let mut it = version.split('.').map(|p| p.parse().unwrap_or(0));
+    (it.next().unwrap_or(0), it.next().unwrap_or(0))
+}
+
+/// Find the highest `pythonX.Y` directory under `lib` (returns version and path).
+fn find_versioned_lib(lib: &Path) -> Option<(String, PathBuf)> {
+    let mut best: Option<(String, PathBuf)> = None;
+    for entry in std::fs::read_dir(lib).ok()?.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Some(ver) = name.strip_prefix("python") else {
+            continue;
         };
+        if !entry.path().is_dir() || major_minor(ver).is_none() {
+            continue;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|(v, _)| version_key(ver) > version_key(v))
+        {
+            best = Some((ver.to_string(), entry.path()));
+        }
+    }
+    best
+}
 
-        // Resolve symlinks to find the actual Python installation
-        let python_real = std::fs::canonicalize(&python_bin).unwrap_or(python_bin.clone());
-
-        // Python binary is typically at /prefix/bin/python3
-        // Stdlib is at /prefix/lib/pythonX.Y/
-        // Site-packages is at /prefix/lib/pythonX.Y/site-packages/ (system)
-        // Or for venv: venv/lib/pythonX.Y/site-packages/
-
-        let prefix = python_real.parent().and_then(|bin| bin.parent());
-
-        // Look for lib/pythonX.Y directories to detect version
-        let (version, stdlib, site_packages) = if let Some(prefix) = prefix {
-            let lib = prefix.join("lib");
-            if lib.exists() {
-                // Find pythonX.Y directories
-                let mut best_version: Option<(String, PathBuf)> = None;
-                if let Ok(entries) = std::fs::read_dir(&lib) {
-                    for entry in entries.flatten() {
-                        let name = entry.file_name();
-                        let name = name.to_string_lossy();
-                        if name.starts_with("python") && entry.path().is_dir() {
-                            let ver = name.trim_start_matches("python");
-                            // Check it looks like a version (X.Y)
-                            if ver.contains('.')
-                                && ver.chars().next().is_some_and(|c| c.is_ascii_digit())
-                            {
-                                // Prefer higher versions
-                                if best_version.as_ref().is_none_or(|(v, _)| ver > v.as_str()) {
-                                    best_version = Some((ver.to_string(), entry.path()));
-                                }
-                            }
-                        }
-                    }
+/// Find the stdlib directory below an installation prefix.
+fn stdlib_in_prefix(prefix: &Path, version: Option<&str>) -> Option<PathBuf> {
+    if let Some(ver) = version {
+        let dir = prefix.join("lib").join(format!("python{}", ver));
+        if dir.is_dir() {
+            return Some(dir);
+        }
+    } else if let Some((_, dir)) = find_versioned_lib(&prefix.join("lib")) {
+        return Some(dir);
+    }
+    // Windows layout: <prefix>/Lib
+    let dir = prefix.join("Lib");
+    dir.join("os.py").is_file().then_some(dir)
+}
+
+/// Detect a virtualenv (uv or standard) at `venv`.
+fn venv_env(venv: &Path) -> Option<PythonEnv> {
+    let cfg = read_pyvenv_cfg(&venv.join("pyvenv.cfg"))?;
+    let get = |key: &str| cfg.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+    let kind = if get("uv").is_some() {
+        PythonEnvKind::Uv
+    } else {
+        PythonEnvKind::Venv
+    };
+    let mut env = PythonEnv::empty(kind, venv.to_path_buf());
+
+    let site_packages = find_site_packages_in_venv(venv);
+    env.version = get("version_info")
+        .or_else(|| get("version"))
+        .and_then(major_minor)
+        .or_else(|| {
+            let site = site_packages.as_ref()?;
+            let lib_dir = site.parent()?.file_name()?.to_string_lossy().into_owned();
+            lib_dir.strip_prefix("python").and_then(major_minor)
+        });
+
+    // `home` is the bin directory of the base interpreter; its parent is the
+    // base prefix holding the stdlib. For uv-managed Pythons this points into
+    // uv's python store, which has the same layout.
+    env.stdlib = get("home")
+        .and_then(|home| {
+            let home = PathBuf::from(home);
+            let home = home.canonicalize().unwrap_or(home);
+            stdlib_in_prefix(home.parent()?, env.version.as_deref())
+        })
+        .or_else(|| {
+            let python = std::fs::canonicalize(venv.join("bin").join("python")).ok()?;
+            stdlib_in_prefix(python.parent()?.parent()?, env.version.as_deref())
+        });
+
+    env.editable_paths = site_packages
+        .as_deref()
+        .map(find_editable_paths)
+        .unwrap_or_default();
+    env.site_packages = site_packages;
+    Some(env)
+}
+
+/// Detect a conda environment at `prefix`.
+fn conda_env(prefix: &Path) -> Option<PythonEnv> {
+    if !prefix.join("conda-meta").is_dir() {
+        return None;
+    }
+    let mut env = PythonEnv::empty(PythonEnvKind::Conda, prefix.to_path_buf());
+    if let Some((ver, stdlib)) = find_versioned_lib(&prefix.join("lib")) {
+        env.version = major_minor(&ver);
+        let site = stdlib.join("site-packages");
+        env.site_packages = site.is_dir().then_some(site);
+        env.stdlib = Some(stdlib);
+    } else {
+        // Windows conda layout: <prefix>/Lib/site-packages
+        let lib = prefix.join("Lib");
+        let site = lib.join("site-packages");
+        env.site_packages = site.is_dir().then_some(site);
+        env.stdlib = lib.is_dir().then_some(lib);
+    }
+    env.editable_paths = env
+        .site_packages
+        .as_deref()
+        .map(find_editable_paths)
+        .unwrap_or_default();
+    Some(env)
+}
+
+/// Detect a PEP 582 `__pypackages__` directory (packages in `X.Y/lib`).
+fn pep582_env(pypackages: &Path) -> Option<PythonEnv> {
+    let mut best: Option<(String, PathBuf)> = None;
+    for entry in std::fs::read_dir(pypackages).ok()?.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let lib = entry.path().join("lib");
+        if major_minor(&name).is_some()
+            && lib.is_dir()
+            && best
+                .as_ref()
+                .is_none_or(|(v, _)| version_key(&name) > version_key(v))
+        {
+            best = Some((name, lib));
+        }
+    }
+    let (ver, lib) = best?;
+    let mut env = PythonEnv::empty(PythonEnvKind::Pep582, pypackages.join(&ver));
+    // The packages directory doesn't carry a stdlib; borrow the system one.
+    env.stdlib = system_env().and_then(|sys| {
+        (sys.version.as_deref() == Some(ver.as_str()))
+            .then_some(sys.stdlib)
+            .flatten()
+    });
+    env.version = Some(ver);
+    env.editable_paths = find_editable_paths(&lib);
+    env.site_packages = Some(lib);
+    Some(env)
+}
+
+/// Detect the system Python from `PATH`.
+fn system_env() -> Option<PythonEnv> {
+    let path = std::env::var("PATH").ok()?;
+    let python_bin = std::env::split_paths(&path).find_map(|dir| {
+        ["python3", "python"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|p| p.exists())
+    })?;
+
+    // Resolve symlinks to find the actual Python installation.
+    // Python binary is typically at /prefix/bin/python3, stdlib at /prefix/lib/pythonX.Y/.
+    let python_real = std::fs::canonicalize(&python_bin).unwrap_or(python_bin);
+    let prefix = python_real.parent()?.parent()?;
+    let mut env = PythonEnv::empty(PythonEnvKind::System, prefix.to_path_buf());
+    if let Some((ver, stdlib)) = find_versioned_lib(&prefix.join("lib")) {
+        env.version = major_minor(&ver);
+        let site = stdlib.join("site-packages");
+        env.site_packages = site.is_dir().then_some(site);
+        env.stdlib = Some(stdlib);
+    }
+    Some(env)
+}
+
+/// Collect import roots contributed by editable installs in `site_packages`.
+///
+/// Two mechanisms are recognized:
+/// - `*.pth` files whose lines are directory paths (legacy `setup.py develop`,
+///   uv/hatch/pdm editables). `import ...` lines are executable hooks, not paths.
+/// - `*.dist-info/direct_url.json` with `dir_info.editable = true` (PEP 610),
+///   whose `file://` URL names the project directory; its `src/` layout is
+///   preferred when present.
+fn find_editable_paths(site_packages: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut push = |p: PathBuf| {
+        if p.is_dir() && !paths.contains(&p) {
+            paths.push(p);
+        }
+    };
+
+    let Ok(entries) = std::fs::read_dir(site_packages) else {
+        return Vec::new();
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.ends_with(".pth") {
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') || line.starts_with("import ") {
+                    continue;
                 }
+                push(site_packages.join(line));
+            }
+        } else if name.ends_with(".dist-info")
+            && let Some(project_dir) = editable_project_dir(&entry.path().join("direct_url.json"))
+        {
+            let src = project_dir.join("src");
+            push(if src.is_dir() { src } else { project_dir });
+        }
+    }
+    paths
+}
+
+/// Map top-level import names to `(version, METADATA path)` for every
+/// `*.dist-info` distribution installed in `site_packages`.
+///
+/// Import names come from `top_level.txt`, falling back to the first path
+/// component of each `RECORD` entry, then to the normalized distribution name.
+fn read_dist_info(site_packages: &Path) -> HashMap<String, (String, PathBuf)> {
+    let mut installed = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(site_packages) else {
+        return installed;
+    };
+    for entry in entries.flatten() {
+        let dist_info = entry.path();
+        if !entry.file_name().to_string_lossy().ends_with(".dist-info") {
+            continue;
+        }
+        let metadata = dist_info.join("METADATA");
+        let Some((name, version)) = std::fs::read_to_string(&metadata)
+            .ok()
+            .and_then(|content| parse_core_metadata(&content))
+        else {
+            continue;
+        };
 
-                if let Some((ver, stdlib_path)) = best_version {
-                    // For venv, site-packages is in the venv
-                    let site = if root.join(".venv").exists() || root.join("venv").exists() {
-                        let venv = if root.join(".venv").exists() {
-                            root.join(".venv")
-                        } else {
-                            root.join("venv")
-                        };
-                        let venv_site = venv
-                            .join("lib")
-                            .join(format!("python{}", ver))
-                            .join("site-packages");
-                        if venv_site.exists() {
-                            Some(venv_site)
-                        } else {
-                            // Fall back to system site-packages
-                            let sys_site = stdlib_path.join("site-packages");
-                            if sys_site.exists() {
-                                Some(sys_site)
-                            } else {
-                                None
-                            }
-                        }
-                    } else {
-                        let sys_site = stdlib_path.join("site-packages");
-                        if sys_site.exists() {
-                            Some(sys_site)
-                        } else {
-                            None
-                        }
-                    };
-
-                    (Some(ver), Some(stdlib_path), site)
+        let mut modules: Vec<String> = std::fs::read_to_string(dist_info.join("top_level.txt"))
+            .map(|s| s.lines().map(str::trim).map(String::from).collect())
+            .unwrap_or_default();
+        if modules.is_empty()
+            && let Ok(record) = std::fs::read_to_string(dist_info.join("RECORD"))
+        {
+            for line in record.lines() {
+                let path = line.split(',').next().unwrap_or_default();
+                let first = path.split('/').next().unwrap_or_default();
+                let module = if path.contains('/') {
+                    first
                 } else {
-                    (None, None, None)
+                    first.strip_suffix(".py").unwrap_or_default()
+                };
+                if !module.is_empty()
+                    && !module.ends_with(".dist-info")
+                    && !module.starts_with("..")
+                    && !modules.iter().any(|m| m == module)
+                {
+                    modules.push(module.to_string());
                 }
-            } else {
-                (None, None, None)
             }
-        } else {
-            (None, None, None)
-        };
+        }
+        if modules.is_empty() {
+            modules.push(name.to_lowercase().replace(['-', '.'], "_"));
+        }
 
-        Self {
-            root,
-            version,
-            stdlib,
-            site_packages,
+        for module in modules.into_iter().filter(|m| !m.is_empty()) {
+            installed.insert(module, (version.clone(), metadata.clone()));
+        }
+    }
+    installed
+}
+
+/// Read `Name` and `Version` from core metadata (`METADATA`/`PKG-INFO`).
+///
+/// The headers are RFC 822 style and precede the long description.
+fn parse_core_metadata(content: &str) -> Option<(String, String)> {
+    let mut name = None;
+    let mut version = None;
+    for line in content.lines() {
+        if line.is_empty() {
+            break;
+        }
+        if let Some(v) = line.strip_prefix("Name:") {
+            name = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("Version:") {
+            version = Some(v.trim().to_string());
+        }
+    }
+    Some((name?, version?))
+}
+
+/// PEP 610 `direct_url.json`, reduced to the fields editable installs need.
+#[derive(serde::Deserialize)]
+struct DirectUrl {
+    url: String,
+    #[serde(default)]
+    dir_info: DirInfo,
+}
+
+#[derive(Default, serde::Deserialize)]
+struct DirInfo {
+    #[serde(default)]
+    editable: bool,
+}
+
+/// Read a PEP 610 `direct_url.json`, returning the project directory if it
+/// records an editable install from a local path.
+fn editable_project_dir(direct_url: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(direct_url).ok()?;
+    let direct_url: DirectUrl = serde_json::from_str(&content).ok()?;
+    if !direct_url.dir_info.editable {
+        return None;
+    }
+    let path = direct_url.url.strip_prefix("file://")?;
+    let path = percent_decode(path);
+    // Windows file URLs look like file:///C:/path
+    let path = match path.strip_prefix('/') {
+        Some(rest) if rest.get(1..2) == Some(":") => rest.to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Decode `%XX` escapes in a file URL path.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = s.get(i + 1..i + 3)
+            && let Ok(b) = u8::from_str_radix(hex, 16)
+        {
+            out.push(b);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
         }
     }
+    String::from_utf8_lossy(&out).into_owned()
 }
 
-/// Get cached Python paths for a project.
-fn get_python_cache(project_root: &Path) -> PythonPathCache {
+/// Get the cached Python environment for a project.
+fn get_python_cache(project_root: &Path) -> Option<PythonEnv> {
     let canonical = project_root
         .canonicalize()
         .unwrap_or_else(|_| project_root.to_path_buf());
@@ -154,15 +482,15 @@ fn get_python_cache(project_root: &Path) -> PythonPathCache {
     // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a process-level cache is unrecoverable
     let mut cache_guard = PYTHON_CACHE.lock().unwrap();
 
-    if let Some(ref cache) = *cache_guard
-        && cache.root == canonical
+    if let Some((ref root, ref env)) = *cache_guard
+        && *root == canonical
     {
-        return cache.clone();
+        return env.clone();
     }
 
-    let new_cache = PythonPathCache::new(project_root);
-    *cache_guard = Some(new_cache.clone());
-    new_cache
+    let env = detect_python_env_uncached(&canonical);
+    *cache_guard = Some((canonical, env.clone()));
+    env
 }
 
 // ============================================================================
@@ -171,12 +499,12 @@ fn get_python_cache(project_root: &Path) -> PythonPathCache {
 
 /// Get Python version from filesystem structure (no subprocess).
 pub fn get_python_version(project_root: &Path) -> Option<String> {
-    get_python_cache(project_root).version
+    get_python_cache(project_root)?.version
 }
 
 /// Find Python stdlib directory from filesystem structure (no subprocess).
 pub fn find_python_stdlib(project_root: &Path) -> Option<PathBuf> {
-    get_python_cache(project_root).stdlib
+    get_python_cache(project_root)?.stdlib
 }
 
 /// Check if a module name is a Python stdlib module.
@@ -201,28 +529,9 @@ fn resolve_python_stdlib_import(import_name: &str, stdlib_path: &Path) -> Option
 
 /// Find Python site-packages directory for a project.
 ///
-/// Search order:
-/// 1. .venv/lib/pythonX.Y/site-packages/ (uv, poetry, standard venv)
-/// 2. Walk up looking for venv directories
+/// See [`detect_python_env`] for the environment search order.
 pub fn find_python_site_packages(project_root: &Path) -> Option<PathBuf> {
-    // Use cached result from filesystem detection
-    if let Some(site) = get_python_cache(project_root).site_packages {
-        return Some(site);
-    }
-
-    // Fall back to scanning parent directories for venvs
-    let mut current = project_root.to_path_buf();
-    while let Some(parent) = current.parent() {
-        let venv_dir = parent.join(".venv");
-        if venv_dir.is_dir()
-            && let Some(site_packages) = find_site_packages_in_venv(&venv_dir)
-        {
-            return Some(site_packages);
-        }
-        current = parent.to_path_buf();
-    }
-
-    None
+    get_python_cache(project_root)?.site_packages
 }
 
 /// Find site-packages within a venv directory.
@@ -438,12 +747,19 @@ impl LocalDeps for PythonDeps {
             return Some(pkg);
         }
 
+        let env = detect_python_env(project_root)?;
+
         // Then site-packages
-        if let Some(site_packages) = find_python_site_packages(project_root) {
-            return resolve_python_import(import_name, &site_packages);
+        if let Some(site_packages) = &env.site_packages
+            && let Some(pkg) = resolve_python_import(import_name, site_packages)
+        {
+            return Some(pkg);
         }
 
-        None
+        // Then editable installs, which live outside site-packages
+        env.editable_paths
+            .iter()
+            .find_map(|root| resolve_python_import(import_name, root))
     }
 
     fn is_stdlib_import(&self, import_name: &str, project_root: &Path) -> bool {
@@ -512,10 +828,29 @@ impl LocalDeps for PythonDeps {
                 version_specific: true,
             });
         }
-        if let Some(cache) = self.find_package_cache(project_root) {
+        let Some(env) = detect_python_env(project_root) else {
+            return sources;
+        };
+        if let Some(site_packages) = env.site_packages {
             sources.push(LocalDepSource {
-                name: "site-packages",
-                path: cache,
+                name: if env.kind == PythonEnvKind::Pep582 {
+                    "__pypackages__"
+                } else {
+                    "site-packages"
+                },
+                path: site_packages,
+                kind: LocalDepSourceKind::Flat,
+                version_specific: false,
+            });
+        }
+        for path in env.editable_paths {
+            // An editable install of the project itself is already indexed as project code.
+            if path.starts_with(project_root) {
+                continue;
+            }
+            sources.push(LocalDepSource {
+                name: "editable",
+                path,
                 kind: LocalDepSourceKind::Flat,
                 version_specific: false,
             });
@@ -523,8 +858,16 @@ impl LocalDeps for PythonDeps {
         sources
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
-        self.discover_flat_packages(&source.path)
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+        let installed = read_dist_info(&source.path);
+        let mut packages = self.discover_flat_packages(&source.path);
+        for package in &mut packages {
+            if let Some((version, metadata)) = installed.get(&package.name) {
+                package.version = Some(version.clone());
+                package.manifest = Some(metadata.clone());
+            }
+        }
+        packages
     }
 
     fn file_path_to_module_name(&self, path: &Path) -> Option<String> {
@@ -567,3 +910,138 @@ impl LocalDeps for PythonDeps {
         candidates
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uv_workspace_member_finds_root_venv() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        let site = root.join(".venv/lib/python3.12/site-packages");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(&site).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            root.join(".venv/pyvenv.cfg"),
+            "home = /nonexistent/bin\nimplementation = CPython\nuv = 0.5.1\nversion_info = 3.12.7\n",
+        )
+        .unwrap();
+        let member = root.join("packages/core");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(&member).unwrap();
+
+        let env = detect_python_env_uncached(&member);
+
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let env = env.unwrap();
+        assert_eq!(env.kind, PythonEnvKind::Uv);
+        assert_eq!(env.version.as_deref(), Some("3.12"));
+        assert_eq!(env.site_packages, Some(site));
+    }
+
+    #[test]
+    fn test_editable_paths_from_pth_and_direct_url() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let site = root.join("site-packages");
+        let legacy = root.join("legacy");
+        let modern = root.join("modern");
+        for dir in [&site, &legacy, &modern.join("src")] {
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            site.join("_legacy.pth"),
+            format!("# comment\nimport _hook\n{}\n", legacy.display()),
+        )
+        .unwrap();
+        let dist_info = site.join("modern-1.0.dist-info");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(&dist_info).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            dist_info.join("direct_url.json"),
+            format!(
+                r#"{{"url": "file://{}", "dir_info": {{"editable": true}}}}"#,
+                modern.display()
+            ),
+        )
+        .unwrap();
+
+        let mut paths = find_editable_paths(&site);
+        paths.sort();
+
+        assert_eq!(paths, vec![legacy, modern.join("src")]);
+    }
+
+    #[test]
+    fn test_dist_info_versions_map_to_import_names() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let site = tmp.path();
+        let pyyaml = site.join("PyYAML-6.0.1.dist-info");
+        let six = site.join("six-1.16.0.dist-info");
+        for dir in [&pyyaml, &six, &site.join("yaml")] {
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            pyyaml.join("METADATA"),
+            "Metadata-Version: 2.1\nName: PyYAML\nVersion: 6.0.1\n\nVersion: not-a-header\n",
+        )
+        .unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(pyyaml.join("top_level.txt"), "_yaml\nyaml\n").unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(six.join("METADATA"), "Name: six\nVersion: 1.16.0\n").unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            six.join("RECORD"),
+            "six.py,sha256=abc,34549\nsix-1.16.0.dist-info/METADATA,,\n",
+        )
+        .unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(site.join("six.py"), "").unwrap();
+
+        let source = LocalDepSource {
+            name: "site-packages",
+            path: site.to_path_buf(),
+            kind: LocalDepSourceKind::Flat,
+            version_specific: false,
+        };
+        let mut packages = PythonDeps.discover_packages(&source);
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let found: Vec<(&str, Option<&str>)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("six", Some("1.16.0")), ("yaml", Some("6.0.1"))]
+        );
+        assert!(
+            packages[1]
+                .manifest
+                .as_ref()
+                .is_some_and(|m| m.ends_with("PyYAML-6.0.1.dist-info/METADATA"))
+        );
+    }
+
+    #[test]
+    fn test_major_minor() {
+        assert_eq!(major_minor("3.12.1.final.0").as_deref(), Some("3.12"));
+        assert_eq!(major_minor("3.9").as_deref(), Some("3.9"));
+        assert!(major_minor("3").is_none());
+        assert!(major_minor("abc.def").is_none());
+        assert!(version_key("3.10") > version_key("3.9"));
+    }
+}
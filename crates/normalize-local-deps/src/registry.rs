@@ -39,6 +39,8 @@ fn init_builtin() {
         register(&crate::c::CDeps);
         #[cfg(feature = "lang-cpp")]
         register(&crate::cpp::CppDeps);
+        #[cfg(feature = "lang-zig")]
+        register(&crate::zig::ZigDeps);
     });
 }
 
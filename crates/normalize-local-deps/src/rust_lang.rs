@@ -1,7 +1,8 @@
 //! Rust local dependency discovery.
 
 use crate::{
-    LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage, has_extension, skip_dotfiles,
+    DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage,
+    has_extension, skip_dotfiles,
 };
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -102,7 +103,7 @@ fn resolve_rust_crate(crate_name: &str, registry: &Path) -> Option<ResolvedPacka
 
 /// Discover packages in Cargo registry structure.
 /// Structure: ~/.cargo/registry/src/index.crates.io-*/crate-version/
-fn discover_cargo_packages(registry: &Path) -> Vec<(String, PathBuf)> {
+fn discover_cargo_packages(registry: &Path) -> Vec<DiscoveredPackage> {
     let mut packages = Vec::new();
 
     // Registry structure: registry/src/index.crates.io-*/crate-version/
@@ -130,17 +131,24 @@ fn discover_cargo_packages(registry: &Path) -> Vec<(String, PathBuf)> {
                 continue;
             }
 
-            // Extract crate name (remove version suffix: "foo-1.2.3" -> "foo")
-            let name = crate_name
-                .rsplit_once('-')
-                .map(|(n, _)| n)
-                .unwrap_or(&crate_name);
-
             // Find src/lib.rs
             let lib_rs = crate_path.join("src").join("lib.rs");
-            if lib_rs.is_file() {
-                packages.push((name.to_string(), lib_rs));
+            if !lib_rs.is_file() {
+                continue;
             }
+
+            // Strip the version suffix: "foo-1.2.3" -> "foo". Both names and
+            // pre-release versions may contain '-', so prefer the manifest version.
+            let package = DiscoveredPackage::new(crate_name.as_str(), lib_rs)
+                .with_manifest(crate_path.join("Cargo.toml"));
+            let name = package
+                .version
+                .as_deref()
+                .and_then(|v| crate_name.strip_suffix(v)?.strip_suffix('-'))
+                .or_else(|| crate_name.rsplit_once('-').map(|(n, _)| n))
+                .unwrap_or(&crate_name)
+                .to_string();
+            packages.push(DiscoveredPackage { name, ..package });
         }
     }
 
@@ -300,7 +308,7 @@ impl LocalDeps for RustDeps {
         !is_dir && !has_extension(name, self.indexable_extensions())
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
         if source.kind != LocalDepSourceKind::Cargo {
             return Vec::new();
         }
@@ -2,7 +2,7 @@
 
 use crate::ResolvedPackage;
 use crate::ecmascript;
-use crate::{LocalDepSource, LocalDepSourceKind, LocalDeps};
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps};
 use crate::{has_extension, skip_dotfiles};
 use std::path::{Path, PathBuf};
 
@@ -52,16 +52,7 @@ impl LocalDeps for TypeScriptDeps {
     }
 
     fn dep_sources(&self, project_root: &Path) -> Vec<LocalDepSource> {
-        let mut sources = Vec::new();
-        if let Some(cache) = self.find_package_cache(project_root) {
-            sources.push(LocalDepSource {
-                name: "node_modules",
-                path: cache,
-                kind: LocalDepSourceKind::NpmScoped,
-                version_specific: false,
-            });
-        }
-        sources
+        ecmascript::node_dep_sources(project_root)
     }
 
     fn should_skip_dep_entry(&self, name: &str, is_dir: bool) -> bool {
@@ -84,8 +75,12 @@ impl LocalDeps for TypeScriptDeps {
         entry_name.to_string()
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
-        self.discover_npm_scoped_packages(&source.path)
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+        match source.kind {
+            LocalDepSourceKind::Pnpm => ecmascript::discover_pnpm_packages(&source.path),
+            LocalDepSourceKind::YarnPnp => ecmascript::discover_pnp_packages(&source.path),
+            _ => self.discover_npm_scoped_packages(&source.path),
+        }
     }
 
     fn find_package_entry(&self, path: &Path) -> Option<PathBuf> {
@@ -167,16 +162,7 @@ impl LocalDeps for TsxDeps {
     }
 
     fn dep_sources(&self, project_root: &Path) -> Vec<LocalDepSource> {
-        let mut sources = Vec::new();
-        if let Some(cache) = self.find_package_cache(project_root) {
-            sources.push(LocalDepSource {
-                name: "node_modules",
-                path: cache,
-                kind: LocalDepSourceKind::NpmScoped,
-                version_specific: false,
-            });
-        }
-        sources
+        ecmascript::node_dep_sources(project_root)
     }
 
     fn should_skip_dep_entry(&self, name: &str, is_dir: bool) -> bool {
@@ -199,8 +185,12 @@ impl LocalDeps for TsxDeps {
         entry_name.to_string()
     }
 
-    fn discover_packages(&self, source: &LocalDepSource) -> Vec<(String, PathBuf)> {
-        self.discover_npm_scoped_packages(&source.path)
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+        match source.kind {
+            LocalDepSourceKind::Pnpm => ecmascript::discover_pnpm_packages(&source.path),
+            LocalDepSourceKind::YarnPnp => ecmascript::discover_pnp_packages(&source.path),
+            _ => self.discover_npm_scoped_packages(&source.path),
+        }
     }
 
     fn find_package_entry(&self, path: &Path) -> Option<PathBuf> {
@@ -0,0 +1,462 @@
+//! Zig local dependency discovery.
+//!
+//! Zig has two kinds of external imports:
+//! - `@import("std")` (and `builtin`) resolve to the Zig installation's `lib/std` tree.
+//! - `@import("name")` for a package declared in `build.zig.zon`, fetched into the
+//!   global package cache (`~/.cache/zig/p/<hash>/`) or referenced by local `.path`.
+//!
+//! Relative file imports (`@import("foo.zig")`) are project-local.
+
+use crate::{DiscoveredPackage, LocalDepSource, LocalDepSourceKind, LocalDeps, ResolvedPackage};
+use normalize_manifest::ZonDependency;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// ============================================================================
+// Zig installation discovery
+// ============================================================================
+
+/// Extract a string field from `zig env` output.
+///
+/// `zig env` printed JSON (`"std_dir": "..."`) up to 0.13 and ZON
+/// (`.std_dir = "..."`) from 0.14 on; both forms are accepted.
+fn zig_env_field(output: &str, field: &str) -> Option<String> {
+    for line in output.lines() {
+        let trimmed = line.trim().trim_start_matches('.').trim_start_matches('"');
+        let Some(rest) = trimmed.strip_prefix(field) else {
+            continue;
+        };
+        let rest = rest.trim_start_matches('"').trim_start();
+        // A longer field sharing the prefix (`std_dir_x`) has no separator here.
+        let Some(rest) = rest.strip_prefix(':').or_else(|| rest.strip_prefix('=')) else {
+            continue;
+        };
+        let Some(rest) = rest.trim().strip_prefix('"') else {
+            continue;
+        };
+        let end = rest.find('"')?;
+        // JSON and ZON both escape backslashes (Windows paths).
+        return Some(rest[..end].replace("\\\\", "\\"));
+    }
+    None
+}
+
+/// Run `zig env` and return its stdout.
+fn zig_env() -> Option<String> {
+    let output = Command::new("zig").arg("env").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Get Zig version (major.minor).
+pub fn get_zig_version() -> Option<String> {
+    let output = Command::new("zig").arg("version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "0.13.0" or "0.14.0-dev.1234+abcdef" -> "0.13" / "0.14"
+    let version_str = String::from_utf8_lossy(&output.stdout);
+    let parts: Vec<&str> = version_str.trim().split('.').collect();
+    if parts.len() >= 2 {
+        return Some(format!("{}.{}", parts[0], parts[1]));
+    }
+    None
+}
+
+/// Find the Zig standard library directory (`<zig lib dir>/std`).
+pub fn find_zig_stdlib() -> Option<PathBuf> {
+    // ZIG_LIB_DIR overrides the lib directory for the compiler itself.
+    if let Ok(lib_dir) = std::env::var("ZIG_LIB_DIR") {
+        let std_dir = PathBuf::from(lib_dir).join("std");
+        if std_dir.is_dir() {
+            return Some(std_dir);
+        }
+    }
+
+    if let Some(env) = zig_env() {
+        if let Some(std_dir) = zig_env_field(&env, "std_dir").map(PathBuf::from)
+            && std_dir.is_dir()
+        {
+            return Some(std_dir);
+        }
+        if let Some(lib_dir) = zig_env_field(&env, "lib_dir").map(PathBuf::from)
+            && lib_dir.join("std").is_dir()
+        {
+            return Some(lib_dir.join("std"));
+        }
+    }
+
+    // Common locations
+    for path in &[
+        "/usr/lib/zig/std",
+        "/usr/local/lib/zig/std",
+        "/opt/zig/lib/std",
+        "/usr/local/zig/lib/std",
+    ] {
+        let std_dir = PathBuf::from(path);
+        if std_dir.is_dir() {
+            return Some(std_dir);
+        }
+    }
+
+    None
+}
+
+/// Find the Zig global package cache (`<global cache>/p`).
+///
+/// Uses `ZIG_GLOBAL_CACHE_DIR`, then the platform defaults (`$XDG_CACHE_HOME/zig`,
+/// `~/.cache/zig`, `%LOCALAPPDATA%\zig`), then `zig env`.
+pub fn find_zig_package_cache() -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Ok(dir) = std::env::var("ZIG_GLOBAL_CACHE_DIR") {
+        candidates.push(PathBuf::from(dir));
+    }
+    if let Ok(xdg) = std::env::var("XDG_CACHE_HOME") {
+        candidates.push(PathBuf::from(xdg).join("zig"));
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        candidates.push(PathBuf::from(home).join(".cache").join("zig"));
+    }
+    if let Ok(local) = std::env::var("LOCALAPPDATA") {
+        candidates.push(PathBuf::from(local).join("zig"));
+    }
+
+    for dir in &candidates {
+        let p = dir.join("p");
+        if p.is_dir() {
+            return Some(p);
+        }
+    }
+
+    // Only spawn `zig env` when none of the conventional locations exist.
+    let env = zig_env()?;
+    let p = PathBuf::from(zig_env_field(&env, "global_cache_dir")?).join("p");
+    p.is_dir().then_some(p)
+}
+
+// ============================================================================
+// build.zig.zon resolution
+// ============================================================================
+
+/// Find the nearest `build.zig.zon` by walking up from a file or directory.
+fn find_build_zig_zon(start: &Path) -> Option<PathBuf> {
+    let mut current = if start.is_file() {
+        start.parent()?.to_path_buf()
+    } else {
+        start.to_path_buf()
+    };
+
+    loop {
+        let zon = current.join("build.zig.zon");
+        if zon.is_file() {
+            return Some(zon);
+        }
+        if !current.pop() {
+            break;
+        }
+    }
+
+    None
+}
+
+/// Read the dependency table from a `build.zig.zon`.
+fn read_zon_dependencies(zon_path: &Path) -> Vec<ZonDependency> {
+    std::fs::read_to_string(zon_path)
+        .map(|content| normalize_manifest::zig_dependencies(&content))
+        .unwrap_or_default()
+}
+
+/// Find the root source file of a Zig package directory.
+///
+/// Zig packages expose modules via `build.zig`, which we don't evaluate; use the
+/// conventional root file names instead.
+fn find_zig_package_root(pkg_dir: &Path, name: &str) -> Option<PathBuf> {
+    let candidates = [
+        pkg_dir.join("src").join("root.zig"),
+        pkg_dir.join("src").join(format!("{}.zig", name)),
+        pkg_dir.join("src").join("lib.zig"),
+        pkg_dir.join("src").join("main.zig"),
+        pkg_dir.join(format!("{}.zig", name)),
+        pkg_dir.join("root.zig"),
+        pkg_dir.join("lib.zig"),
+    ];
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Resolve a dependency declared in `build.zig.zon` to its on-disk directory.
+fn resolve_zon_dependency(
+    dep: &ZonDependency,
+    zon_dir: &Path,
+    package_cache: Option<&Path>,
+) -> Option<PathBuf> {
+    if let Some(path) = &dep.path {
+        let dir = zon_dir.join(path);
+        return dir.is_dir().then_some(dir);
+    }
+    let dir = package_cache?.join(dep.hash.as_deref()?);
+    dir.is_dir().then_some(dir)
+}
+
+/// Check if an import names the Zig standard library or compiler-provided modules.
+fn is_zig_stdlib_import(import_name: &str) -> bool {
+    matches!(import_name, "std" | "builtin")
+}
+
+// ============================================================================
+// Zig local dependency discovery
+// ============================================================================
+
+/// Zig local dependency discovery.
+pub struct ZigDeps;
+
+impl LocalDeps for ZigDeps {
+    fn ecosystem_key(&self) -> &'static str {
+        "zig"
+    }
+
+    fn language_name(&self) -> &'static str {
+        "Zig"
+    }
+
+    fn project_manifest_filenames(&self) -> &'static [&'static str] {
+        &["build.zig.zon", "build.zig"]
+    }
+
+    fn indexable_extensions(&self) -> &'static [&'static str] {
+        &["zig"]
+    }
+
+    fn resolve_local_import(
+        &self,
+        import_name: &str,
+        current_file: &Path,
+        _project_root: &Path,
+    ) -> Option<PathBuf> {
+        // Only file imports (`@import("foo.zig")`) are relative; bare names are modules.
+        if !import_name.ends_with(".zig") {
+            return None;
+        }
+        let target = current_file.parent()?.join(import_name);
+        target.is_file().then_some(target)
+    }
+
+    fn resolve_external_import(
+        &self,
+        import_name: &str,
+        project_root: &Path,
+    ) -> Option<ResolvedPackage> {
+        if import_name.ends_with(".zig") {
+            return None;
+        }
+
+        if is_zig_stdlib_import(import_name) {
+            // `builtin` is generated per-compilation; only `std` has source on disk.
+            let std_root = find_zig_stdlib()?.join("std.zig");
+            if import_name == "std" && std_root.is_file() {
+                return Some(ResolvedPackage {
+                    path: std_root,
+                    name: import_name.to_string(),
+                    is_namespace: false,
+                });
+            }
+            return None;
+        }
+
+        let zon_path = find_build_zig_zon(project_root)?;
+        let zon_dir = zon_path.parent()?;
+        let dep = read_zon_dependencies(&zon_path)
+            .into_iter()
+            .find(|d| d.name == import_name)?;
+        let cache = find_zig_package_cache();
+        let pkg_dir = resolve_zon_dependency(&dep, zon_dir, cache.as_deref())?;
+        Some(ResolvedPackage {
+            path: find_zig_package_root(&pkg_dir, &dep.name).unwrap_or(pkg_dir),
+            name: dep.name,
+            is_namespace: false,
+        })
+    }
+
+    fn is_stdlib_import(&self, import_name: &str, _project_root: &Path) -> bool {
+        is_zig_stdlib_import(import_name)
+    }
+
+    fn get_version(&self, _project_root: &Path) -> Option<String> {
+        get_zig_version()
+    }
+
+    fn find_package_cache(&self, _project_root: &Path) -> Option<PathBuf> {
+        find_zig_package_cache()
+    }
+
+    fn find_stdlib(&self, _project_root: &Path) -> Option<PathBuf> {
+        find_zig_stdlib()
+    }
+
+    fn dep_sources(&self, project_root: &Path) -> Vec<LocalDepSource> {
+        let mut sources = Vec::new();
+        if let Some(stdlib) = self.find_stdlib(project_root) {
+            sources.push(LocalDepSource {
+                name: "stdlib",
+                path: stdlib,
+                kind: LocalDepSourceKind::Recursive,
+                version_specific: true,
+            });
+        }
+        // Only the packages this project declares — the global cache is shared
+        // by every Zig project on the machine.
+        if find_build_zig_zon(project_root).is_some() {
+            sources.push(LocalDepSource {
+                name: "build.zig.zon",
+                path: project_root.to_path_buf(),
+                kind: LocalDepSourceKind::ZigZon,
+                version_specific: false,
+            });
+        }
+        sources
+    }
+
+    fn should_skip_dep_entry(&self, name: &str, is_dir: bool) -> bool {
+        if name.starts_with('.') {
+            return true;
+        }
+        if is_dir && (name == "zig-cache" || name == "zig-out" || name == "test") {
+            return true;
+        }
+        !is_dir && !name.ends_with(".zig")
+    }
+
+    fn dep_module_name(&self, entry_name: &str) -> String {
+        entry_name
+            .strip_suffix(".zig")
+            .unwrap_or(entry_name)
+            .to_string()
+    }
+
+    fn discover_packages(&self, source: &LocalDepSource) -> Vec<DiscoveredPackage> {
+        if source.kind != LocalDepSourceKind::ZigZon {
+            return self.discover_recursive_packages(&source.path, &source.path);
+        }
+
+        let Some(zon_path) = find_build_zig_zon(&source.path) else {
+            return Vec::new();
+        };
+        let Some(zon_dir) = zon_path.parent() else {
+            return Vec::new();
+        };
+        let cache = find_zig_package_cache();
+        read_zon_dependencies(&zon_path)
+            .into_iter()
+            .filter_map(|dep| {
+                let dir = resolve_zon_dependency(&dep, zon_dir, cache.as_deref())?;
+                let entry = find_zig_package_root(&dir, &dep.name)?;
+                let package = DiscoveredPackage::new(dep.name, entry);
+                let manifest = dir.join("build.zig.zon");
+                Some(if manifest.is_file() {
+                    package.with_manifest(manifest)
+                } else {
+                    package
+                })
+            })
+            .collect()
+    }
+
+    fn find_package_entry(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+        let name = path.file_name()?.to_string_lossy();
+        find_zig_package_root(path, &name)
+    }
+
+    fn file_path_to_module_name(&self, path: &Path) -> Option<String> {
+        if path.extension()?.to_str()? != "zig" {
+            return None;
+        }
+        // Zig imports files by path relative to the importer.
+        path.file_name()?.to_str().map(|s| s.to_string())
+    }
+
+    fn module_name_to_paths(&self, module: &str) -> Vec<String> {
+        if module.ends_with(".zig") {
+            vec![module.to_string()]
+        } else {
+            vec![format!("{}.zig", module), format!("src/{}.zig", module)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zig_env_field_json_and_zon() {
+        let json = r#"{
+ "zig_exe": "/opt/zig/zig",
+ "lib_dir": "/opt/zig/lib",
+ "std_dir": "/opt/zig/lib/std",
+ "global_cache_dir": "/home/u/.cache/zig"
+}"#;
+        assert_eq!(
+            zig_env_field(json, "std_dir").as_deref(),
+            Some("/opt/zig/lib/std")
+        );
+        assert_eq!(
+            zig_env_field(json, "global_cache_dir").as_deref(),
+            Some("/home/u/.cache/zig")
+        );
+
+        let zon = r#".{
+    .zig_exe = "/opt/zig/zig",
+    .lib_dir = "/opt/zig/lib",
+    .std_dir = "/opt/zig/lib/std",
+}"#;
+        assert_eq!(
+            zig_env_field(zon, "lib_dir").as_deref(),
+            Some("/opt/zig/lib")
+        );
+        assert_eq!(
+            zig_env_field(zon, "std_dir").as_deref(),
+            Some("/opt/zig/lib/std")
+        );
+        assert!(zig_env_field(zon, "version").is_none());
+
+        // A field that only shares the prefix is skipped, not a lookup failure.
+        let prefixed = r#".{
+    .lib_dir_override = "/tmp/lib",
+    .lib_dir = "/opt/zig/lib",
+}"#;
+        assert_eq!(
+            zig_env_field(prefixed, "lib_dir").as_deref(),
+            Some("/opt/zig/lib")
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_dependency() {
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::create_dir_all(dir.join("vendored/src")).unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(
+            dir.join("app/build.zig.zon"),
+            ".{\n    .name = .app,\n    .dependencies = .{\n        .vendored = .{ .path = \"../vendored\" },\n    },\n}\n",
+        )
+        .unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(dir.join("vendored/src/root.zig"), "pub fn f() void {}\n").unwrap();
+
+        let resolved = ZigDeps.resolve_external_import("vendored", &dir.join("app"));
+
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let resolved = resolved.unwrap();
+        assert_eq!(resolved.name, "vendored");
+        assert!(resolved.path.ends_with("vendored/src/root.zig"));
+    }
+}
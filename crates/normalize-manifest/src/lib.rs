@@ -13,6 +13,7 @@
 //!
 //! - `go_module(content)` — extract module info from `go.mod`
 //! - `npm_entry_point(content)` — extract entry point from `package.json`
+//! - `zig_dependencies(content)` — extract `.dependencies` from `build.zig.zon`
 
 #[cfg(feature = "eval")]
 pub mod eval;
@@ -64,6 +65,7 @@ pub mod zig;
 
 pub use go_mod::GoModule;
 pub use npm::npm_entry_point;
+pub use zig::ZonDependency;
 
 use serde::Serialize;
 
@@ -295,3 +297,10 @@ pub fn parse_manifest_eval(
 pub fn go_module(content: &str) -> Option<GoModule> {
     go_mod::parse_go_module(content)
 }
+
+/// Parse `build.zig.zon` content to extract dependency URLs, hashes, and paths.
+///
+/// Convenience wrapper for `normalize-local-deps` internal use.
+pub fn zig_dependencies(content: &str) -> Vec<ZonDependency> {
+    zig::parse_zon_dependencies(content)
+}
@@ -6,6 +6,33 @@
 
 use crate::{DeclaredDep, DepKind, ManifestError, ManifestParser, ParsedManifest};
 
+/// A dependency entry from the `.dependencies` table of a `build.zig.zon`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZonDependency {
+    /// Dependency key (the name passed to `b.dependency("name", ...)`).
+    pub name: String,
+    /// Remote tarball/git URL (`.url`), for fetched packages.
+    pub url: Option<String>,
+    /// Package hash (`.hash`); names the directory in the global package cache.
+    pub hash: Option<String>,
+    /// Local path (`.path`), relative to the directory of `build.zig.zon`.
+    pub path: Option<String>,
+}
+
+/// Contents of a `build.zig.zon` relevant to manifest parsing and dependency resolution.
+struct ZonScan {
+    name: Option<String>,
+    version: Option<String>,
+    dependencies: Vec<ZonDependency>,
+}
+
+/// Parse the `.dependencies` table of a `build.zig.zon`, keeping `.url`/`.hash`/`.path`.
+///
+/// Exposed via `crate::zig_dependencies()` for `normalize-local-deps`.
+pub(crate) fn parse_zon_dependencies(content: &str) -> Vec<ZonDependency> {
+    scan_zon(content).dependencies
+}
+
 /// Parser for `build.zig.zon` files.
 pub struct ZigZonParser;
 
@@ -15,138 +42,180 @@ impl ManifestParser for ZigZonParser {
     }
 
     fn parse(&self, content: &str) -> Result<ParsedManifest, ManifestError> {
-        let mut name: Option<String> = None;
-        let mut version: Option<String> = None;
-        let mut deps: Vec<DeclaredDep> = Vec::new();
-
-        // Track parser state.
-        // We care about three depth levels:
-        //   top-level `.{ ... }` → depth 1
-        //   `.dependencies = .{ ... }` → depth 2
-        //   individual dep `.depname = .{ ... }` → depth 3
-        #[derive(PartialEq)]
-        enum State {
-            TopLevel,
-            InDeps,
-            InDepEntry,
-        }
+        let scan = scan_zon(content);
+        Ok(ParsedManifest {
+            ecosystem: "zig",
+            name: scan.name,
+            version: scan.version,
+            dependencies: scan
+                .dependencies
+                .into_iter()
+                .map(|d| DeclaredDep {
+                    name: d.name,
+                    version_req: None,
+                    kind: DepKind::Normal,
+                })
+                .collect(),
+        })
+    }
+}
 
-        let mut state = State::TopLevel;
-        let mut depth = 0usize;
-        let mut deps_depth = 0usize;
-        let mut dep_entry_depth = 0usize;
-        let mut current_dep_name: Option<String> = None;
+/// Line-oriented scan of a ZON manifest.
+fn scan_zon(content: &str) -> ZonScan {
+    let mut name: Option<String> = None;
+    let mut version: Option<String> = None;
+    let mut deps: Vec<ZonDependency> = Vec::new();
 
-        for line in content.lines() {
-            let trimmed = line.trim();
+    // Track parser state.
+    // We care about three depth levels:
+    //   top-level `.{ ... }` → depth 1
+    //   `.dependencies = .{ ... }` → depth 2
+    //   individual dep `.depname = .{ ... }` → depth 3
+    #[derive(PartialEq)]
+    enum State {
+        TopLevel,
+        InDeps,
+        InDepEntry,
+    }
 
-            // Skip comments
-            if trimmed.starts_with("//") || trimmed.is_empty() {
-                continue;
-            }
+    let mut state = State::TopLevel;
+    let mut depth = 0usize;
+    let mut deps_depth = 0usize;
+    let mut dep_entry_depth = 0usize;
+    let mut current_dep: Option<ZonDependency> = None;
 
-            // Count brace changes on this line.
-            let opens = trimmed.chars().filter(|&c| c == '{').count();
-            let closes = trimmed.chars().filter(|&c| c == '}').count();
+    for line in content.lines() {
+        let trimmed = line.trim();
 
-            // Extract .name and .version at top level (depth 1).
-            if state == State::TopLevel && depth <= 1 {
-                if let Some(v) = extract_field_string(trimmed, ".name")
-                    && name.is_none()
-                {
-                    name = Some(v);
-                }
-                if let Some(v) = extract_field_string(trimmed, ".version")
-                    && version.is_none()
-                {
-                    version = Some(v);
-                }
-            }
+        // Skip comments
+        if trimmed.starts_with("//") || trimmed.is_empty() {
+            continue;
+        }
 
-            // Detect entry into .dependencies = .{
-            if state == State::TopLevel
-                && trimmed.contains(".dependencies")
-                && trimmed.contains('=')
-                && opens > 0
+        // Count brace changes on this line.
+        let opens = trimmed.chars().filter(|&c| c == '{').count();
+        let closes = trimmed.chars().filter(|&c| c == '}').count();
+
+        // Extract .name and .version at top level (depth 1).
+        if state == State::TopLevel && depth <= 1 {
+            if let Some(v) = extract_field_string(trimmed, ".name")
+                && name.is_none()
             {
-                state = State::InDeps;
-                deps_depth = depth + opens - closes;
-                depth = depth + opens - closes;
-                continue;
+                name = Some(v);
             }
+            if let Some(v) = extract_field_string(trimmed, ".version")
+                && version.is_none()
+            {
+                version = Some(v);
+            }
+        }
 
-            // Inside dependencies block, detect individual dep entries: .name = .{
-            if state == State::InDeps {
-                // Check for leaving the deps block.
-                let new_depth = depth + opens - closes;
-                if new_depth < deps_depth {
-                    state = State::TopLevel;
-                    depth = new_depth;
-                    continue;
-                }
-
-                // Detect a dep entry: line like `.depname = .{`
-                if opens > 0
-                    && trimmed.starts_with('.')
-                    && trimmed.contains('=')
-                    && let Some(dep_name) = extract_zon_key(trimmed)
-                {
-                    state = State::InDepEntry;
-                    current_dep_name = Some(dep_name);
-                    dep_entry_depth = new_depth;
-                    depth = new_depth;
-                    continue;
-                }
+        // Detect entry into .dependencies = .{
+        if state == State::TopLevel
+            && trimmed.contains(".dependencies")
+            && trimmed.contains('=')
+            && opens > 0
+        {
+            state = State::InDeps;
+            deps_depth = depth + opens - closes;
+            depth = depth + opens - closes;
+            continue;
+        }
 
+        // Inside dependencies block, detect individual dep entries: .name = .{
+        if state == State::InDeps {
+            // Check for leaving the deps block.
+            let new_depth = depth + opens - closes;
+            if new_depth < deps_depth {
+                state = State::TopLevel;
                 depth = new_depth;
                 continue;
             }
 
-            // Inside a single dep entry.
-            if state == State::InDepEntry {
-                let new_depth = depth + opens - closes;
-                if new_depth < dep_entry_depth {
-                    // Leaving this dep entry; emit it.
-                    if let Some(dep_name) = current_dep_name.take() {
-                        deps.push(DeclaredDep {
-                            name: dep_name,
-                            version_req: None,
-                            kind: DepKind::Normal,
-                        });
-                    }
-                    // Are we back in deps or fully out?
-                    if new_depth < deps_depth {
-                        state = State::TopLevel;
-                    } else {
-                        state = State::InDeps;
-                    }
+            // Detect a dep entry: line like `.depname = .{`
+            if opens > 0
+                && trimmed.starts_with('.')
+                && trimmed.contains('=')
+                && let Some(dep_name) = extract_zon_key(trimmed)
+            {
+                let mut dep = ZonDependency {
+                    name: dep_name,
+                    ..Default::default()
+                };
+                // Single-line entry: `.foo = .{ .path = "../foo" },`
+                if closes >= opens {
+                    read_zon_dep_fields(trimmed, &mut dep);
+                    deps.push(dep);
                     depth = new_depth;
                     continue;
                 }
+                state = State::InDepEntry;
+                current_dep = Some(dep);
+                dep_entry_depth = new_depth;
                 depth = new_depth;
                 continue;
             }
 
-            depth = (depth + opens).saturating_sub(closes);
+            depth = new_depth;
+            continue;
         }
 
-        // Handle unclosed final dep entry (file ends without closing brace).
-        if state == State::InDepEntry
-            && let Some(dep_name) = current_dep_name.take()
-        {
-            deps.push(DeclaredDep {
-                name: dep_name,
-                version_req: None,
-                kind: DepKind::Normal,
-            });
+        // Inside a single dep entry.
+        if state == State::InDepEntry {
+            if let Some(dep) = current_dep.as_mut() {
+                read_zon_dep_fields(trimmed, dep);
+            }
+            let new_depth = depth + opens - closes;
+            if new_depth < dep_entry_depth {
+                // Leaving this dep entry; emit it.
+                if let Some(dep) = current_dep.take() {
+                    deps.push(dep);
+                }
+                // Are we back in deps or fully out?
+                if new_depth < deps_depth {
+                    state = State::TopLevel;
+                } else {
+                    state = State::InDeps;
+                }
+                depth = new_depth;
+                continue;
+            }
+            depth = new_depth;
+            continue;
         }
 
-        Ok(ParsedManifest {
-            ecosystem: "zig",
-            name,
-            version,
-            dependencies: deps,
-        })
+        depth = (depth + opens).saturating_sub(closes);
+    }
+
+    // Handle unclosed final dep entry (file ends without closing brace).
+    if state == State::InDepEntry
+        && let Some(dep) = current_dep.take()
+    {
+        deps.push(dep);
+    }
+
+    ZonScan {
+        name,
+        version,
+        dependencies: deps,
+    }
+}
+
+/// Record any `.url`, `.hash`, or `.path` fields present on a dep-entry line.
+fn read_zon_dep_fields(line: &str, dep: &mut ZonDependency) {
+    // Fields may share a line with the entry key (`.foo = .{ .path = "x" },`),
+    // so search for each field anywhere in the line.
+    for (field, slot) in [
+        (".url", &mut dep.url),
+        (".hash", &mut dep.hash),
+        (".path", &mut dep.path),
+    ] {
+        if slot.is_none()
+            && let Some(pos) = line.find(field)
+            && let Some(v) = extract_field_string(&line[pos..], field)
+        {
+            *slot = Some(v);
+        }
     }
 }
 
@@ -209,6 +278,37 @@ mod tests {
         assert!(m.dependencies.iter().all(|d| d.kind == DepKind::Normal));
     }
 
+    #[test]
+    fn test_zon_dependency_fields() {
+        let content = r#".{
+    .name = .app,
+    .dependencies = .{
+        .zap = .{
+            .url = "https://example.com/zap.tar.gz",
+            .hash = "1220abcd",
+        },
+        .local = .{ .path = "../local" },
+    },
+}
+"#;
+        let deps = parse_zon_dependencies(SAMPLE);
+        assert_eq!(
+            deps[0].hash.as_deref(),
+            Some("122059d35a68afb4f5e59b52fdc63be4c09ee07f72bf7c7abaab46c5ebe8c39e8f")
+        );
+        let deps = parse_zon_dependencies(content);
+        assert_eq!(deps.len(), 2, "{deps:?}");
+        assert_eq!(deps[0].name, "zap");
+        assert_eq!(
+            deps[0].url.as_deref(),
+            Some("https://example.com/zap.tar.gz")
+        );
+        assert_eq!(deps[0].hash.as_deref(), Some("1220abcd"));
+        assert_eq!(deps[1].name, "local");
+        assert_eq!(deps[1].path.as_deref(), Some("../local"));
+        assert!(deps[1].hash.is_none());
+    }
+
     #[test]
     fn test_no_deps() {
         let content = r#".{
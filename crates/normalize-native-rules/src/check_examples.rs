@@ -177,6 +177,7 @@ impl From<CheckExamplesReport> for DiagnosticsReport {
             sources_run: vec!["check-examples".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
@@ -215,6 +215,7 @@ impl From<CheckRefsReport> for DiagnosticsReport {
             sources_run: vec!["check-refs".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
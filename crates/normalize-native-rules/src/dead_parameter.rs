@@ -118,6 +118,7 @@ impl FileRule for DeadParameterRule {
             sources_run: vec!["dead-parameter".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
@@ -178,6 +178,7 @@ impl FileRule for HighComplexityRule {
             sources_run: vec!["high-complexity".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
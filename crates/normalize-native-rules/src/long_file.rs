@@ -175,6 +175,7 @@ impl FileRule for LongFileRule {
             sources_run: vec!["long-file".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
@@ -169,6 +169,7 @@ impl FileRule for LongFunctionRule {
             sources_run: vec!["long-function".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
@@ -125,6 +125,7 @@ pub fn build_stale_doc_report(
                     .into(),
             }],
             daemon_cached: false,
+            metrics: vec![],
         };
     }
 
@@ -161,6 +162,7 @@ pub fn build_stale_doc_report(
                 message: "co_change_edges table is empty or index could not be read — run `normalize structure rebuild`".into(),
             }],
             daemon_cached: false,
+            metrics: vec![],
         };
     };
 
@@ -174,6 +176,7 @@ pub fn build_stale_doc_report(
                 message: "co_change_edges table is empty — run `normalize structure rebuild` to populate it".into(),
             }],
             daemon_cached: false,
+            metrics: vec![],
         };
     }
 
@@ -205,6 +208,7 @@ pub fn build_stale_doc_report(
             sources_run: vec!["stale-doc".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         };
     }
 
@@ -322,5 +326,6 @@ pub fn build_stale_doc_report(
         sources_run: vec!["stale-doc".into()],
         tool_errors: vec![],
         daemon_cached: false,
+        metrics: vec![],
     }
 }
@@ -222,6 +222,7 @@ impl From<StaleDocsReport> for DiagnosticsReport {
             sources_run: vec!["stale-docs".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         }
     }
 }
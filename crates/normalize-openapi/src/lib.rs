@@ -1,28 +1,39 @@
-//! OpenAPI client code generation.
+//! OpenAPI client and server code generation.
 //!
-//! Trait-based design allows multiple implementations per language/framework.
+//! Trait-based design allows multiple implementations per language/framework:
+//! [`OpenApiClientGenerator`] for clients, [`OpenApiServerGenerator`] for
+//! server stubs.
 //!
 //! # Extensibility
 //!
 //! Users can register custom generators via [`register()`]:
 //!
 //! ```ignore
-//! use normalize_openapi::{OpenApiClientGenerator, register};
-//! use serde_json::Value;
+//! use normalize_openapi::{NormalizedSpec, OpenApiClientGenerator, register};
 //!
 //! struct MyGenerator;
 //!
 //! impl OpenApiClientGenerator for MyGenerator {
 //!     fn language(&self) -> &'static str { "mylang" }
 //!     fn variant(&self) -> &'static str { "myvariant" }
-//!     fn generate(&self, spec: &Value) -> String { /* ... */ }
+//!     fn generate(&self, spec: &NormalizedSpec) -> String { /* ... */ }
 //! }
 //!
 //! // Register before first use
 //! register(&MyGenerator);
 //! ```
 
-use serde_json::Value;
+mod load;
+mod server;
+mod spec;
+
+pub use load::load_spec;
+pub use server::{
+    OpenApiServerGenerator, get_server_generator, list_server_generators, register_server,
+};
+pub use spec::{
+    NormalizedSpec, Operation, Parameter, Property, RequestBody, Schema, SchemaKind, normalize,
+};
 use std::sync::{OnceLock, RwLock};
 
 /// A code generator for a specific language/framework.
@@ -33,8 +44,9 @@ pub trait OpenApiClientGenerator: Send + Sync {
     /// Framework/variant name (e.g., "fetch", "axios", "urllib")
     fn variant(&self) -> &'static str;
 
-    /// Generate client code from OpenAPI JSON.
-    fn generate(&self, spec: &Value) -> String;
+    /// Generate client code from a spec prepared by [`normalize()`] or
+    /// [`load_spec()`].
+    fn generate(&self, spec: &NormalizedSpec) -> String;
 }
 
 /// Global registry of generator plugins.
@@ -73,15 +85,19 @@ pub fn get_generator(lang: &str) -> Option<&'static dyn OpenApiClientGenerator>
         .read()
         .unwrap()
         .iter()
-        .find(|g| {
-            g.language() == lang_lower
-                || (lang_lower == "ts" && g.language() == "typescript")
-                || (lang_lower == "py" && g.language() == "python")
-                || (lang_lower == "rs" && g.language() == "rust")
-        })
+        .find(|g| language_matches(g.language(), &lang_lower))
         .copied()
 }
 
+/// Whether a generator for `language` serves a request for `requested`
+/// (lowercase), accepting the short aliases `ts`, `py` and `rs`.
+fn language_matches(language: &str, requested: &str) -> bool {
+    language == requested
+        || (requested == "ts" && language == "typescript")
+        || (requested == "py" && language == "python")
+        || (requested == "rs" && language == "rust")
+}
+
 /// List all available generators (language, variant) from the global registry.
 pub fn list_generators() -> Vec<(&'static str, &'static str)> {
     init_builtin();
@@ -111,7 +127,7 @@ impl OpenApiClientGenerator for GeneratorWrapper {
         self.0.variant()
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &NormalizedSpec) -> String {
         self.0.generate(spec)
     }
 }
@@ -140,117 +156,118 @@ impl OpenApiClientGenerator for TypeScriptFetch {
         "fetch"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &NormalizedSpec) -> String {
         let mut out = String::new();
         out.push_str("// Auto-generated from OpenAPI spec\n");
         out.push_str("// Uses fetch (built-in)\n\n");
 
         // Generate interfaces from schemas
-        if let Some(schemas) = spec
-            .pointer("/components/schemas")
-            .and_then(|s| s.as_object())
-        {
-            for (name, schema) in schemas {
-                out.push_str(&format!("export interface {} {{\n", name));
-                if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
-                    let required: Vec<&str> = schema
-                        .get("required")
-                        .and_then(|r| r.as_array())
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                        .unwrap_or_default();
-                    for (prop_name, prop) in props {
-                        let ts_type = json_schema_to_ts(prop);
-                        let opt = if required.contains(&prop_name.as_str()) {
-                            ""
-                        } else {
-                            "?"
-                        };
-                        out.push_str(&format!("  {}{}: {};\n", prop_name, opt, ts_type));
-                    }
-                }
-                out.push_str("}\n\n");
+        for (name, schema) in &spec.schemas {
+            out.push_str(&format!("export interface {} {{\n", name));
+            for prop in properties(schema) {
+                let ts_type = schema_to_ts(&prop.schema);
+                let opt = if prop.required { "" } else { "?" };
+                out.push_str(&format!("  {}{}: {};\n", prop.name, opt, ts_type));
             }
+            out.push_str("}\n\n");
         }
 
+        // Error type for non-2xx responses
+        out.push_str("export class ApiError extends Error {\n");
+        out.push_str("  constructor(public status: number, public body: string) {\n");
+        out.push_str("    super(`HTTP ${status}`);\n");
+        out.push_str("  }\n");
+        out.push_str("}\n\n");
+
         // Generate client class
         out.push_str("export class ApiClient {\n");
         out.push_str("  constructor(private baseUrl = 'http://localhost:8080') {}\n\n");
-        out.push_str("  private async request<T>(path: string, params?: Record<string, string | number | undefined>): Promise<T> {\n");
+        out.push_str("  private async request<T>(method: string, path: string, params?: Record<string, unknown>, body?: unknown): Promise<T> {\n");
         out.push_str("    const url = new URL(path, this.baseUrl);\n");
         out.push_str("    if (params) {\n");
         out.push_str("      for (const [k, v] of Object.entries(params)) {\n");
         out.push_str("        if (v !== undefined) url.searchParams.set(k, String(v));\n");
         out.push_str("      }\n");
         out.push_str("    }\n");
-        out.push_str("    const res = await fetch(url.toString());\n");
-        out.push_str("    if (!res.ok) throw new Error(`HTTP ${res.status}`);\n");
-        out.push_str("    return await res.json() as T;\n");
+        out.push_str("    const init: RequestInit = { method };\n");
+        out.push_str("    if (body !== undefined) {\n");
+        out.push_str("      init.headers = { 'Content-Type': 'application/json' };\n");
+        out.push_str("      init.body = JSON.stringify(body);\n");
+        out.push_str("    }\n");
+        out.push_str("    const res = await fetch(url.toString(), init);\n");
+        out.push_str("    if (!res.ok) throw new ApiError(res.status, await res.text());\n");
+        out.push_str("    const text = await res.text();\n");
+        out.push_str("    return (text ? JSON.parse(text) : undefined) as T;\n");
         out.push_str("  }\n\n");
 
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-
-                    // Response type from nested path
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_ts)
-                        .unwrap_or_else(|| "void".to_string());
-
-                    let mut args = Vec::new();
-                    for p in &path_params {
-                        args.push(format!("{}: string", p));
-                    }
-                    if !query_params.is_empty() {
-                        let opts: Vec<String> = query_params
-                            .iter()
-                            .map(|p| format!("{}?: string | number", p))
-                            .collect();
-                        args.push(format!("options?: {{ {} }}", opts.join("; ")));
-                    }
-
-                    let url_template = path.replace('{', "${");
-                    let call_params = if query_params.is_empty() {
-                        ""
-                    } else {
-                        ", options"
-                    };
+        // Generate methods from operations
+        for op in &spec.operations {
+            let resp_type = op
+                .response
+                .as_ref()
+                .map(schema_to_ts)
+                .unwrap_or_else(|| "void".to_string());
+
+            let mut args = Vec::new();
+            for p in &op.path_params {
+                let ts_type = p
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "string".to_string(), schema_to_ts);
+                args.push(format!("{}: {}", p.name, ts_type));
+            }
+            if let Some(body) = &op.body {
+                let opt = if body.required { "" } else { "?" };
+                args.push(format!("body{}: {}", opt, schema_to_ts(&body.schema)));
+            }
+            if !op.query_params.is_empty() {
+                let opts: Vec<String> = op
+                    .query_params
+                    .iter()
+                    .map(|p| {
+                        let ts_type = p
+                            .schema
+                            .as_ref()
+                            .map_or_else(|| "string | number".to_string(), schema_to_ts);
+                        let opt = if p.required { "" } else { "?" };
+                        format!("{}{}: {}", p.name, opt, ts_type)
+                    })
+                    .collect();
+                let opt = if op.query_params.iter().any(|p| p.required) {
+                    ""
+                } else {
+                    "?"
+                };
+                args.push(format!("options{}: {{ {} }}", opt, opts.join("; ")));
+            }
 
-                    out.push_str(&format!(
-                        "  async {}({}): Promise<{}> {{\n",
-                        op_id,
-                        args.join(", "),
-                        resp_type
-                    ));
-                    out.push_str(&format!(
-                        "    return this.request<{}>(`{}`{});\n",
-                        resp_type, url_template, call_params
-                    ));
-                    out.push_str("  }\n\n");
+            let url_template = op.path.replace('{', "${");
+            let mut call_args = vec![
+                format!("'{}'", op.method.to_uppercase()),
+                format!("`{}`", url_template),
+            ];
+            if !op.query_params.is_empty() {
+                call_args.push("options".to_string());
+            }
+            if op.body.is_some() {
+                if op.query_params.is_empty() {
+                    call_args.push("undefined".to_string());
                 }
+                call_args.push("body".to_string());
             }
+
+            out.push_str(&format!(
+                "  async {}({}): Promise<{}> {{\n",
+                op.operation_id,
+                args.join(", "),
+                resp_type
+            ));
+            out.push_str(&format!(
+                "    return this.request<{}>({});\n",
+                resp_type,
+                call_args.join(", ")
+            ));
+            out.push_str("  }\n\n");
         }
 
         out.push_str("}\n");
@@ -270,136 +287,155 @@ impl OpenApiClientGenerator for PythonUrllib {
         "urllib"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &NormalizedSpec) -> String {
         let mut out = String::new();
         out.push_str("# Auto-generated from OpenAPI spec\n");
         out.push_str("# Uses urllib (stdlib)\n\n");
-        out.push_str("from dataclasses import dataclass\n");
+        out.push_str("from dataclasses import asdict, dataclass, is_dataclass\n");
         out.push_str("from typing import Any, Optional\n");
+        out.push_str("from urllib.error import HTTPError\n");
         out.push_str("from urllib.parse import urlencode\n");
-        out.push_str("from urllib.request import urlopen\n");
+        out.push_str("from urllib.request import Request, urlopen\n");
         out.push_str("import json\n\n\n");
 
         // Generate dataclasses from schemas
-        if let Some(schemas) = spec
-            .pointer("/components/schemas")
-            .and_then(|s| s.as_object())
-        {
-            for (name, schema) in schemas {
-                out.push_str("@dataclass\n");
-                out.push_str(&format!("class {}:\n", name));
-                if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
-                    let required: Vec<&str> = schema
-                        .get("required")
-                        .and_then(|r| r.as_array())
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                        .unwrap_or_default();
-
-                    // Required fields first
-                    for (prop_name, prop) in props {
-                        if required.contains(&prop_name.as_str()) {
-                            let py_type = json_schema_to_py(prop);
-                            out.push_str(&format!("    {}: {}\n", prop_name, py_type));
-                        }
-                    }
-                    // Optional fields
-                    for (prop_name, prop) in props {
-                        if !required.contains(&prop_name.as_str()) {
-                            let py_type = json_schema_to_py(prop);
-                            out.push_str(&format!(
-                                "    {}: Optional[{}] = None\n",
-                                prop_name, py_type
-                            ));
-                        }
-                    }
-                    if props.is_empty() {
-                        out.push_str("    pass\n");
-                    }
+        for (name, schema) in &spec.schemas {
+            out.push_str("@dataclass\n");
+            out.push_str(&format!("class {}:\n", name));
+            let props = properties(schema);
+
+            // Required fields first
+            for prop in props.iter().filter(|p| p.required) {
+                let py_type = schema_to_py(&prop.schema);
+                out.push_str(&format!("    {}: {}\n", prop.name, py_type));
+            }
+            // Optional fields
+            for prop in props.iter().filter(|p| !p.required) {
+                let py_type = schema_to_py(&prop.schema);
+                if prop.schema.nullable {
+                    out.push_str(&format!("    {}: {} = None\n", prop.name, py_type));
                 } else {
-                    out.push_str("    pass\n");
+                    out.push_str(&format!(
+                        "    {}: Optional[{}] = None\n",
+                        prop.name, py_type
+                    ));
                 }
-                out.push_str("\n\n");
             }
+            if props.is_empty() {
+                out.push_str("    pass\n");
+            }
+            out.push_str("\n\n");
         }
 
+        // Error type for non-2xx responses
+        out.push_str("class ApiError(Exception):\n");
+        out.push_str("    def __init__(self, status: int, body: str):\n");
+        out.push_str("        super().__init__(f'HTTP {status}')\n");
+        out.push_str("        self.status = status\n");
+        out.push_str("        self.body = body\n\n\n");
+
         // Generate client class
         out.push_str("class ApiClient:\n");
         out.push_str("    def __init__(self, base_url: str = 'http://localhost:8080'):\n");
         out.push_str("        self.base_url = base_url.rstrip('/')\n\n");
-        out.push_str("    def _request(self, path: str, params: Optional[dict] = None) -> dict:\n");
+        out.push_str("    def _request(self, method: str, path: str, params: Optional[dict] = None, body: Any = None) -> Any:\n");
         out.push_str("        url = f'{self.base_url}{path}'\n");
         out.push_str("        if params:\n");
         out.push_str("            filtered = {k: v for k, v in params.items() if v is not None}\n");
         out.push_str("            if filtered:\n");
         out.push_str("                url = f'{url}?{urlencode(filtered)}'\n");
-        out.push_str("        with urlopen(url) as response:\n");
-        out.push_str("            return json.load(response)\n\n");
-
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_py)
-                        .unwrap_or_else(|| "dict".to_string());
-
-                    let mut args = vec!["self".to_string()];
-                    for p in &path_params {
-                        args.push(format!("{}: str", p));
-                    }
-                    if !query_params.is_empty() {
-                        args.push("*".to_string());
-                        for p in &query_params {
-                            args.push(format!("{}: Optional[str] = None", p));
-                        }
-                    }
-
-                    let params_dict = if query_params.is_empty() {
-                        String::new()
+        out.push_str("        data = None\n");
+        out.push_str("        headers = {}\n");
+        out.push_str("        if body is not None:\n");
+        out.push_str("            if is_dataclass(body) and not isinstance(body, type):\n");
+        out.push_str("                body = asdict(body)\n");
+        out.push_str("            data = json.dumps(body).encode()\n");
+        out.push_str("            headers['Content-Type'] = 'application/json'\n");
+        out.push_str("        request = Request(url, data=data, headers=headers, method=method)\n");
+        out.push_str("        try:\n");
+        out.push_str("            with urlopen(request) as response:\n");
+        out.push_str("                raw = response.read()\n");
+        out.push_str("        except HTTPError as e:\n");
+        out.push_str(
+            "            raise ApiError(e.code, e.read().decode(errors='replace')) from e\n",
+        );
+        out.push_str("        return json.loads(raw) if raw else None\n\n");
+
+        // Generate methods from operations
+        for op in &spec.operations {
+            let resp_type = match &op.response {
+                Some(schema) => schema_to_py(schema),
+                None if op.response_empty => "None".to_string(),
+                None => "dict".to_string(),
+            };
+
+            let mut args = vec!["self".to_string()];
+            for p in &op.path_params {
+                let py_type = p
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "str".to_string(), schema_to_py);
+                args.push(format!("{}: {}", p.name, py_type));
+            }
+            if let Some(body) = &op.body {
+                let py_type = schema_to_py(&body.schema);
+                if body.required {
+                    args.push(format!("body: {}", py_type));
+                } else {
+                    args.push(format!("body: Optional[{}] = None", py_type));
+                }
+            }
+            if !op.query_params.is_empty() {
+                args.push("*".to_string());
+                for p in &op.query_params {
+                    let py_type = p
+                        .schema
+                        .as_ref()
+                        .map_or_else(|| "str".to_string(), schema_to_py);
+                    if p.required {
+                        args.push(format!("{}: {}", p.name, py_type));
                     } else {
-                        let kv: Vec<_> = query_params
-                            .iter()
-                            .map(|p| format!("'{}': {}", p, p))
-                            .collect();
-                        format!(", {{{}}}", kv.join(", "))
-                    };
-
-                    out.push_str(&format!(
-                        "    def {}({}) -> {}:\n",
-                        op_id,
-                        args.join(", "),
-                        resp_type
-                    ));
-                    out.push_str(&format!(
-                        "        data = self._request(f'{}'{})\n",
-                        path, params_dict
-                    ));
-                    out.push_str(&format!("        return {}(**data)\n\n", resp_type));
+                        args.push(format!("{}: Optional[{}] = None", p.name, py_type));
+                    }
                 }
             }
+
+            let mut call_args = vec![
+                format!("'{}'", op.method.to_uppercase()),
+                format!("f'{}'", op.path),
+            ];
+            if !op.query_params.is_empty() {
+                let kv: Vec<_> = op
+                    .query_params
+                    .iter()
+                    .map(|p| format!("'{}': {}", p.name, p.name))
+                    .collect();
+                call_args.push(format!("{{{}}}", kv.join(", ")));
+            }
+            if op.body.is_some() {
+                call_args.push("body=body".to_string());
+            }
+            let call = format!("self._request({})", call_args.join(", "));
+
+            out.push_str(&format!(
+                "    def {}({}) -> {}:\n",
+                op.operation_id,
+                args.join(", "),
+                resp_type
+            ));
+            // Schema objects become dataclasses; anything else is returned as parsed
+            if resp_type == "None" {
+                out.push_str(&format!("        {}\n\n", call));
+            } else if let Some(Schema {
+                kind: SchemaKind::Ref(_),
+                nullable: false,
+            }) = op.response
+            {
+                out.push_str(&format!("        data = {}\n", call));
+                out.push_str(&format!("        return {}(**data)\n\n", resp_type));
+            } else {
+                out.push_str(&format!("        return {}\n\n", call));
+            }
         }
 
         out
@@ -418,43 +454,14 @@ impl OpenApiClientGenerator for RustUreq {
         "ureq"
     }
 
-    fn generate(&self, spec: &Value) -> String {
+    fn generate(&self, spec: &NormalizedSpec) -> String {
         let mut out = String::new();
         out.push_str("//! Auto-generated from OpenAPI spec\n");
         out.push_str("//! Uses ureq (blocking HTTP)\n\n");
         out.push_str("use serde::{Deserialize, Serialize};\n\n");
 
         // Generate structs from schemas
-        if let Some(schemas) = spec
-            .pointer("/components/schemas")
-            .and_then(|s| s.as_object())
-        {
-            for (name, schema) in schemas {
-                out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
-                out.push_str(&format!("pub struct {} {{\n", name));
-                if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
-                    let required: Vec<&str> = schema
-                        .get("required")
-                        .and_then(|r| r.as_array())
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                        .unwrap_or_default();
-                    for (prop_name, prop) in props {
-                        let rust_type = json_schema_to_rust(prop);
-                        let field_type = if required.contains(&prop_name.as_str()) {
-                            rust_type
-                        } else {
-                            format!("Option<{}>", rust_type)
-                        };
-                        out.push_str(&format!(
-                            "    pub {}: {},\n",
-                            to_snake_case(prop_name),
-                            field_type
-                        ));
-                    }
-                }
-                out.push_str("}\n\n");
-            }
-        }
+        out.push_str(&rust_structs(spec));
 
         // Generate client struct
         out.push_str("pub struct ApiClient {\n");
@@ -466,99 +473,72 @@ impl OpenApiClientGenerator for RustUreq {
         out.push_str("        Self { base_url: base_url.into() }\n");
         out.push_str("    }\n\n");
 
-        // Generate methods from paths
-        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
-            for (path, methods) in paths {
-                if let Some(op) = methods.get("get").and_then(|g| g.as_object()) {
-                    let op_id = op
-                        .get("operationId")
-                        .and_then(|id| id.as_str())
-                        .unwrap_or("unknown");
-                    let params = op
-                        .get("parameters")
-                        .and_then(|p| p.as_array())
-                        .map(|a| a.as_slice())
-                        .unwrap_or(&[]);
-
-                    let path_params: Vec<&str> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("path"))
-                        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
-                        .collect();
-                    let query_params: Vec<(&str, bool)> = params
-                        .iter()
-                        .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some("query"))
-                        .filter_map(|p| {
-                            let name = p.get("name").and_then(|n| n.as_str())?;
-                            let required =
-                                p.get("required").and_then(|r| r.as_bool()).unwrap_or(false);
-                            Some((name, required))
-                        })
-                        .collect();
-
-                    let op_value = Value::Object(op.clone());
-                    let resp_type = op_value
-                        .pointer("/responses/200/content/application~1json/schema")
-                        .map(json_schema_to_rust)
-                        .unwrap_or_else(|| "()".to_string());
-
-                    // Build function signature
-                    let mut args = Vec::new();
-                    args.push("&self".to_string());
-                    for p in &path_params {
-                        args.push(format!("{}: &str", to_snake_case(p)));
-                    }
-                    for (p, required) in &query_params {
-                        let param_type = if *required {
-                            "&str".to_string()
-                        } else {
-                            "Option<&str>".to_string()
-                        };
-                        args.push(format!("{}: {}", to_snake_case(p), param_type));
-                    }
+        // Generate methods from GET operations (other verbs aren't generated yet)
+        for op in spec.operations.iter().filter(|op| op.method == "get") {
+            let resp_type = op
+                .response
+                .as_ref()
+                .map(schema_to_rust)
+                .unwrap_or_else(|| "()".to_string());
+
+            // Build function signature
+            let mut args = Vec::new();
+            args.push("&self".to_string());
+            for p in &op.path_params {
+                args.push(format!("{}: &str", to_snake_case(&p.name)));
+            }
+            for p in &op.query_params {
+                let param_type = if p.required {
+                    "&str".to_string()
+                } else {
+                    "Option<&str>".to_string()
+                };
+                args.push(format!("{}: {}", to_snake_case(&p.name), param_type));
+            }
 
+            out.push_str(&format!(
+                "    pub fn {}({}) -> Result<{}, ureq::Error> {{\n",
+                to_snake_case(&op.operation_id),
+                args.join(", "),
+                resp_type
+            ));
+
+            // Build URL with path params
+            let url_expr = if op.path_params.is_empty() {
+                format!("format!(\"{{}}{}\"", op.path)
+            } else {
+                let rust_path = op.path_params.iter().fold(op.path.to_string(), |acc, p| {
+                    acc.replace(
+                        &format!("{{{}}}", p.name),
+                        &format!("{{{}}}", to_snake_case(&p.name)),
+                    )
+                });
+                format!("format!(\"{{}}{}\", ", rust_path)
+            };
+            out.push_str(&format!("        let url = {}self.base_url);\n", url_expr));
+
+            // Build request
+            out.push_str("        let mut req = ureq::get(&url);\n");
+            for p in &op.query_params {
+                let snake = to_snake_case(&p.name);
+                if p.required {
                     out.push_str(&format!(
-                        "    pub fn {}({}) -> Result<{}, ureq::Error> {{\n",
-                        to_snake_case(op_id),
-                        args.join(", "),
-                        resp_type
+                        "        req = req.query(\"{}\", {});\n",
+                        p.name, snake
+                    ));
+                } else {
+                    out.push_str(&format!(
+                        "        if let Some(v) = {} {{ req = req.query(\"{}\", v); }}\n",
+                        snake, p.name
                     ));
-
-                    // Build URL with path params
-                    let url_expr = if path_params.is_empty() {
-                        format!("format!(\"{{}}{}\"", path)
-                    } else {
-                        let rust_path = path_params.iter().fold(path.to_string(), |acc, p| {
-                            acc.replace(&format!("{{{}}}", p), &format!("{{{}}}", to_snake_case(p)))
-                        });
-                        format!("format!(\"{{}}{}\", ", rust_path)
-                    };
-                    out.push_str(&format!("        let url = {}self.base_url);\n", url_expr));
-
-                    // Build request
-                    out.push_str("        let mut req = ureq::get(&url);\n");
-                    for (p, required) in &query_params {
-                        let snake = to_snake_case(p);
-                        if *required {
-                            out.push_str(&format!(
-                                "        req = req.query(\"{}\", {});\n",
-                                p, snake
-                            ));
-                        } else {
-                            out.push_str(&format!(
-                                "        if let Some(v) = {} {{ req = req.query(\"{}\", v); }}\n",
-                                snake, p
-                            ));
-                        }
-                    }
-
-                    out.push_str("        let resp: ");
-                    out.push_str(&resp_type);
-                    out.push_str(" = req.call()?.into_json()?;\n");
-                    out.push_str("        Ok(resp)\n");
-                    out.push_str("    }\n\n");
                 }
             }
+
+            out.push_str("        let resp: ");
+            out.push_str(&resp_type);
+            out.push_str(" = req.call()?.into_json()?;\n");
+            out.push_str("        Ok(resp)\n");
+            out.push_str("    }\n\n");
         }
 
         out.push_str("}\n");
@@ -582,132 +562,100 @@ fn to_snake_case(s: &str) -> String {
     result
 }
 
-fn json_schema_to_rust(schema: &Value) -> String {
-    if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-        return ref_path
-            .split('/')
-            .next_back()
-            .unwrap_or("serde_json::Value")
-            .to_string();
-    }
-
-    let type_val = schema.get("type");
-
-    if let Some(arr) = type_val.and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_str_to_rust(non_null[0]);
-            return format!("Option<{}>", base);
+/// Serde structs for the spec's named schemas, shared by the Rust client and
+/// server generators
+fn rust_structs(spec: &NormalizedSpec) -> String {
+    let mut out = String::new();
+    for (name, schema) in &spec.schemas {
+        out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        out.push_str(&format!("pub struct {} {{\n", name));
+        for prop in properties(schema) {
+            let rust_type = schema_to_rust(&prop.schema);
+            // Nullable types are already `Option`s
+            let field_type = if prop.required || prop.schema.nullable {
+                rust_type
+            } else {
+                format!("Option<{}>", rust_type)
+            };
+            out.push_str(&rust_field(&prop.name, &field_type));
         }
+        out.push_str("}\n\n");
     }
+    out
+}
 
-    if let Some(type_str) = type_val.and_then(|t| t.as_str()) {
-        if type_str == "array" {
-            if let Some(items) = schema.get("items") {
-                return format!("Vec<{}>", json_schema_to_rust(items));
-            }
-            return "Vec<serde_json::Value>".to_string();
-        }
-        return type_str_to_rust(type_str);
-    }
-
-    "serde_json::Value".to_string()
+/// A `pub` struct field named in snake_case, renamed back to `name` for serde
+/// when they differ
+fn rust_field(name: &str, field_type: &str) -> String {
+    let snake = to_snake_case(name);
+    let rename = if snake == name {
+        String::new()
+    } else {
+        format!("    #[serde(rename = \"{}\")]\n", name)
+    };
+    format!("{}    pub {}: {},\n", rename, snake, field_type)
 }
 
-fn type_str_to_rust(t: &str) -> String {
-    match t {
-        "string" => "String".to_string(),
-        "integer" => "i64".to_string(),
-        "number" => "f64".to_string(),
-        "boolean" => "bool".to_string(),
-        "object" => "serde_json::Value".to_string(),
-        _ => "serde_json::Value".to_string(),
+fn schema_to_rust(schema: &Schema) -> String {
+    let base = match &schema.kind {
+        SchemaKind::Ref(name) => name.clone(),
+        SchemaKind::String => "String".to_string(),
+        SchemaKind::Integer => "i64".to_string(),
+        SchemaKind::Number => "f64".to_string(),
+        SchemaKind::Boolean => "bool".to_string(),
+        SchemaKind::Array(items) => format!("Vec<{}>", schema_to_rust(items)),
+        SchemaKind::Object(_) | SchemaKind::Any => return "serde_json::Value".to_string(),
+    };
+    if schema.nullable {
+        format!("Option<{}>", base)
+    } else {
+        base
     }
 }
 
 // --- Helpers ---
 
-fn json_schema_to_ts(schema: &Value) -> String {
-    if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-        return ref_path
-            .split('/')
-            .next_back()
-            .unwrap_or("unknown")
-            .to_string();
+/// Properties of an object schema (none for anything else)
+fn properties(schema: &Schema) -> &[Property] {
+    match &schema.kind {
+        SchemaKind::Object(props) => props,
+        _ => &[],
     }
-
-    let type_val = schema.get("type");
-
-    if let Some(arr) = type_val.and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_str_to_ts(non_null[0]);
-            return format!("{} | null", base);
-        }
-    }
-
-    if let Some(type_str) = type_val.and_then(|t| t.as_str()) {
-        if type_str == "array" {
-            if let Some(items) = schema.get("items") {
-                return format!("{}[]", json_schema_to_ts(items));
-            }
-            return "unknown[]".to_string();
-        }
-        return type_str_to_ts(type_str);
-    }
-
-    "unknown".to_string()
 }
 
-fn type_str_to_ts(t: &str) -> String {
-    match t {
-        "string" => "string".to_string(),
-        "integer" | "number" => "number".to_string(),
-        "boolean" => "boolean".to_string(),
-        "object" => "Record<string, unknown>".to_string(),
-        _ => "unknown".to_string(),
+fn schema_to_ts(schema: &Schema) -> String {
+    let base = match &schema.kind {
+        SchemaKind::Ref(name) => name.clone(),
+        SchemaKind::String => "string".to_string(),
+        SchemaKind::Integer | SchemaKind::Number => "number".to_string(),
+        SchemaKind::Boolean => "boolean".to_string(),
+        SchemaKind::Array(items) if items.nullable => format!("({})[]", schema_to_ts(items)),
+        SchemaKind::Array(items) => format!("{}[]", schema_to_ts(items)),
+        SchemaKind::Object(_) => "Record<string, unknown>".to_string(),
+        SchemaKind::Any => return "unknown".to_string(),
+    };
+    if schema.nullable {
+        format!("{} | null", base)
+    } else {
+        base
     }
 }
 
-fn json_schema_to_py(schema: &Value) -> String {
-    if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-        return ref_path.split('/').next_back().unwrap_or("Any").to_string();
-    }
-
-    let type_val = schema.get("type");
-
-    if let Some(arr) = type_val.and_then(|t| t.as_array()) {
-        let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-        let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-        if non_null.len() == 1 {
-            let base = type_str_to_py(non_null[0]);
-            return format!("Optional[{}]", base);
-        }
-    }
-
-    if let Some(type_str) = type_val.and_then(|t| t.as_str()) {
-        if type_str == "array" {
-            if let Some(items) = schema.get("items") {
-                return format!("list[{}]", json_schema_to_py(items));
-            }
-            return "list".to_string();
-        }
-        return type_str_to_py(type_str);
-    }
-
-    "Any".to_string()
-}
-
-fn type_str_to_py(t: &str) -> String {
-    match t {
-        "string" => "str".to_string(),
-        "integer" => "int".to_string(),
-        "number" => "float".to_string(),
-        "boolean" => "bool".to_string(),
-        "object" => "dict".to_string(),
-        _ => "Any".to_string(),
+fn schema_to_py(schema: &Schema) -> String {
+    let base = match &schema.kind {
+        SchemaKind::Ref(name) => name.clone(),
+        SchemaKind::String => "str".to_string(),
+        SchemaKind::Integer => "int".to_string(),
+        SchemaKind::Number => "float".to_string(),
+        SchemaKind::Boolean => "bool".to_string(),
+        SchemaKind::Array(items) => format!("list[{}]", schema_to_py(items)),
+        SchemaKind::Object(_) => "dict".to_string(),
+        SchemaKind::Any => return "Any".to_string(),
+    };
+    if schema.nullable {
+        format!("Optional[{}]", base)
+    } else {
+        base
     }
 }
 
@@ -734,6 +682,144 @@ mod tests {
         assert!(gens.iter().any(|(l, _)| *l == "rust"));
     }
 
+    fn users_spec() -> NormalizedSpec {
+        normalize(&serde_json::json!({
+            "components": {
+                "schemas": {
+                    "NewUser": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"]
+                    },
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "name": { "type": "string" }
+                        },
+                        "required": ["id", "name"]
+                    }
+                },
+                "parameters": {
+                    "UserId": {
+                        "name": "id",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "integer" }
+                    }
+                }
+            },
+            "paths": {
+                "/users": {
+                    "get": {
+                        "operationId": "listUsers",
+                        "parameters": [
+                            { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+                        ],
+                        "responses": { "200": { "content": { "application/json": {
+                            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/User" } }
+                        } } } }
+                    },
+                    "post": {
+                        "operationId": "createUser",
+                        "requestBody": {
+                            "required": true,
+                            "content": { "application/json": {
+                                "schema": { "$ref": "#/components/schemas/NewUser" }
+                            } }
+                        },
+                        "responses": { "201": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/User" }
+                        } } } }
+                    }
+                },
+                "/users/{id}": {
+                    "parameters": [{ "$ref": "#/components/parameters/UserId" }],
+                    "delete": {
+                        "operationId": "deleteUser",
+                        "responses": { "204": { "description": "Deleted" } }
+                    },
+                    "patch": {
+                        "requestBody": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/NewUser" }
+                        } } },
+                        "responses": { "200": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/User" }
+                        } } } }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_operations_cover_all_methods() {
+        let spec = users_spec();
+        let ops = &spec.operations;
+        let ids: Vec<(&str, &str)> = ops
+            .iter()
+            .map(|o| (o.method.as_str(), o.operation_id.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("get", "listUsers"),
+                ("post", "createUser"),
+                ("delete", "deleteUser"),
+                ("patch", "patchUsersId"),
+            ]
+        );
+
+        // Path-level parameters reach every operation under the path
+        let delete = &ops[2];
+        assert_eq!(delete.path_params.len(), 1);
+        assert_eq!(delete.path_params[0].name, "id");
+        assert!(delete.response.is_none());
+        assert!(delete.response_empty);
+
+        // 201 counts as the success response
+        assert!(ops[1].response.is_some());
+        assert_eq!(ops[1].body.as_ref().map(|b| b.required), Some(true));
+    }
+
+    #[test]
+    fn test_typescript_generates_all_methods() {
+        let code = TypeScriptFetch.generate(&users_spec());
+        assert!(code.contains("  async listUsers(options?: { limit?: number }): Promise<User[]> {\n    return this.request<User[]>('GET', `/users`, options);\n"), "{}", code);
+        assert!(code.contains("  async createUser(body: NewUser): Promise<User> {\n    return this.request<User>('POST', `/users`, undefined, body);\n"), "{}", code);
+        assert!(code.contains("  async deleteUser(id: number): Promise<void> {\n    return this.request<void>('DELETE', `/users/${id}`);\n"), "{}", code);
+        assert!(
+            code.contains("  async patchUsersId(id: number, body?: NewUser): Promise<User> {\n"),
+            "{}",
+            code
+        );
+        assert!(code.contains("export class ApiError extends Error {"));
+    }
+
+    #[test]
+    fn test_python_generates_all_methods() {
+        let code = PythonUrllib.generate(&users_spec());
+        assert!(code.contains("    def listUsers(self, *, limit: Optional[int] = None) -> list[User]:\n        return self._request('GET', f'/users', {'limit': limit})\n"), "{}", code);
+        assert!(code.contains("    def createUser(self, body: NewUser) -> User:\n        data = self._request('POST', f'/users', body=body)\n        return User(**data)\n"), "{}", code);
+        assert!(code.contains("    def deleteUser(self, id: int) -> None:\n        self._request('DELETE', f'/users/{id}')\n"), "{}", code);
+        assert!(
+            code.contains(
+                "    def patchUsersId(self, id: int, body: Optional[NewUser] = None) -> User:\n"
+            ),
+            "{}",
+            code
+        );
+        assert!(code.contains("class ApiError(Exception):"));
+    }
+
+    #[test]
+    fn test_rust_generates_get_only() {
+        let code = RustUreq.generate(&users_spec());
+        assert!(code.contains("    pub fn list_users(&self, limit: Option<&str>) -> Result<Vec<User>, ureq::Error> {"), "{}", code);
+        assert!(!code.contains("create_user"));
+    }
+
     #[test]
     fn test_to_snake_case() {
         assert_eq!(to_snake_case("getUserById"), "get_user_by_id");
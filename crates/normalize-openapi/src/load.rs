@@ -0,0 +1,169 @@
+//! Reading specs from disk or text.
+//!
+//! [`load_spec`] accepts a path or the spec itself, in JSON or YAML (with the
+//! `yaml` feature), and returns it [normalized](crate::normalize).
+
+use crate::spec::{NormalizedSpec, normalize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Load and normalize a spec from a file path or from the spec text itself.
+///
+/// `source` is read as text when it starts with `{` or spans several lines,
+/// and as a path otherwise. The format comes from the file extension
+/// (`.json`, `.yaml`, `.yml`), falling back to JSON for text starting with `{`
+/// and YAML for anything else. Errors name the file and, for syntax errors,
+/// the line and column.
+pub fn load_spec(source: &str) -> Result<NormalizedSpec, String> {
+    let trimmed = source.trim_start();
+    let (text, origin, yaml) = if trimmed.starts_with('{') || source.contains('\n') {
+        (
+            source.to_string(),
+            "spec".to_string(),
+            !trimmed.starts_with('{'),
+        )
+    } else {
+        let path = Path::new(source);
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", source, e))?;
+        let yaml = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => false,
+            Some("yaml" | "yml") => true,
+            _ => !text.trim_start().starts_with('{'),
+        };
+        (text, source.to_string(), yaml)
+    };
+
+    let value = if yaml {
+        parse_yaml(&text, &origin)?
+    } else {
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse {} as JSON: {}", origin, e))?
+    };
+
+    let is_spec = value.as_object().is_some_and(|o| {
+        o.contains_key("openapi") || o.contains_key("swagger") || o.contains_key("paths")
+    });
+    if !is_spec {
+        return Err(format!(
+            "{} is not an OpenAPI spec: expected a mapping with an `openapi`, `swagger` or `paths` key",
+            origin
+        ));
+    }
+    normalize(&value).map_err(|e| format!("{}: {}", origin, e))
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(text: &str, origin: &str) -> Result<Value, String> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(text)
+        .map_err(|e| format!("Failed to parse {} as YAML: {}", origin, e))?;
+    yaml_to_json(yaml).map_err(|e| format!("Failed to parse {} as YAML: {}", origin, e))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_text: &str, origin: &str) -> Result<Value, String> {
+    Err(format!(
+        "{} looks like YAML, but normalize-openapi was built without the `yaml` feature",
+        origin
+    ))
+}
+
+/// Convert a YAML document to JSON. Unlike deserializing straight into
+/// [`Value`], this accepts non-string keys, which YAML specs use for response
+/// codes (`200:`).
+#[cfg(feature = "yaml")]
+fn yaml_to_json(yaml: serde_yaml::Value) -> Result<Value, String> {
+    use serde_yaml::Value as Yaml;
+    Ok(match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                n.as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| format!("{} has no JSON equivalent", n))?
+            }
+        }
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => Value::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Mapping(map) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in map {
+                let key = match key {
+                    Yaml::String(s) => s,
+                    Yaml::Number(n) => n.to_string(),
+                    Yaml::Bool(b) => b.to_string(),
+                    other => return Err(format!("unsupported mapping key: {:?}", other)),
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_json_text() {
+        let spec = load_spec(r#"{"openapi": "3.0.0", "paths": {"/a": {"get": {}}}}"#).unwrap();
+        assert_eq!(spec.operations.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_load_yaml_file() {
+        let path = std::env::temp_dir().join(format!(
+            "normalize-openapi-load-{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "openapi: 3.0.0\npaths:\n  /pets:\n    get:\n      operationId: listPets\n      responses:\n        200:\n          content:\n            application/json:\n              schema:\n                type: array\n                items:\n                  type: string\n",
+        )
+        .unwrap();
+        let spec = load_spec(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let spec = spec.unwrap();
+        assert_eq!(spec.operations[0].operation_id, "listPets");
+        // The integer `200` key still counts as the success response
+        assert!(spec.operations[0].response.is_some());
+    }
+
+    #[test]
+    fn test_errors_are_descriptive() {
+        let err = load_spec("{\"openapi\": \"3.0.0\",\n \"paths\": }").unwrap_err();
+        assert!(err.contains("as JSON") && err.contains("line 2"), "{}", err);
+
+        let err = load_spec("missing-spec.json").unwrap_err();
+        assert!(
+            err.starts_with("Failed to read missing-spec.json"),
+            "{}",
+            err
+        );
+
+        let err = load_spec(r#"{"name": "not a spec"}"#).unwrap_err();
+        assert!(err.contains("is not an OpenAPI spec"), "{}", err);
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_yaml_syntax_error_has_location() {
+        let err = load_spec("openapi: 3.0.0\npaths:\n  - [unclosed\n").unwrap_err();
+        assert!(err.contains("as YAML") && err.contains("line"), "{}", err);
+    }
+}
@@ -0,0 +1,423 @@
+//! OpenAPI server stub generation.
+//!
+//! The reverse of the client generators: from a spec, emit the server side
+//! with routing and request extraction filled in, leaving one method per
+//! operation to implement. Custom generators register via
+//! [`register_server()`], as client generators do via
+//! [`register()`](crate::register).
+
+use crate::spec::{NormalizedSpec, Operation, Schema};
+use crate::{language_matches, rust_field, rust_structs, schema_to_rust, to_snake_case};
+use std::sync::{OnceLock, RwLock};
+
+/// A server stub generator for a specific language/framework.
+pub trait OpenApiServerGenerator: Send + Sync {
+    /// Language name (e.g., "rust")
+    fn language(&self) -> &'static str;
+
+    /// Framework name (e.g., "axum")
+    fn variant(&self) -> &'static str;
+
+    /// Generate server code from a spec prepared by
+    /// [`normalize()`](crate::normalize) or [`load_spec()`](crate::load_spec).
+    fn generate(&self, spec: &NormalizedSpec) -> String;
+}
+
+/// Global registry of server generator plugins.
+static SERVER_GENERATORS: RwLock<Vec<&'static dyn OpenApiServerGenerator>> =
+    RwLock::new(Vec::new());
+static SERVER_INITIALIZED: OnceLock<()> = OnceLock::new();
+
+/// Register a custom server generator plugin.
+pub fn register_server(generator: &'static dyn OpenApiServerGenerator) {
+    // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a global registry is unrecoverable
+    SERVER_GENERATORS.write().unwrap().push(generator);
+}
+
+/// Initialize built-in server generators (called automatically on first use).
+fn init_builtin() {
+    SERVER_INITIALIZED.get_or_init(|| {
+        // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a global registry is unrecoverable
+        let mut generators = SERVER_GENERATORS.write().unwrap();
+        static AXUM: RustAxum = RustAxum;
+        generators.push(&AXUM);
+    });
+}
+
+/// Get a server generator by language (returns first match).
+pub fn get_server_generator(lang: &str) -> Option<&'static dyn OpenApiServerGenerator> {
+    init_builtin();
+    let lang_lower = lang.to_lowercase();
+    // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a global registry is unrecoverable
+    SERVER_GENERATORS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|g| language_matches(g.language(), &lang_lower))
+        .copied()
+}
+
+/// List all available server generators (language, variant).
+pub fn list_server_generators() -> Vec<(&'static str, &'static str)> {
+    init_builtin();
+    // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison on a global registry is unrecoverable
+    SERVER_GENERATORS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|g| (g.language(), g.variant()))
+        .collect()
+}
+
+// --- Rust (axum) ---
+
+/// An axum router plus an `Api` trait with one method per operation. Handlers
+/// extract path, query and body values into typed arguments and delegate to
+/// the trait.
+struct RustAxum;
+
+impl OpenApiServerGenerator for RustAxum {
+    fn language(&self) -> &'static str {
+        "rust"
+    }
+    fn variant(&self) -> &'static str {
+        "axum"
+    }
+
+    fn generate(&self, spec: &NormalizedSpec) -> String {
+        let ops: Vec<AxumOp> = spec.operations.iter().map(AxumOp::new).collect();
+
+        let mut out = String::new();
+        out.push_str("//! Auto-generated from OpenAPI spec\n");
+        out.push_str("//! Uses axum\n\n");
+        // Each route starts with a free routing function (`get(..)`); further
+        // methods on the same path chain (`.post(..)`)
+        let mut paths: Vec<&str> = Vec::new();
+        let mut methods: Vec<&str> = Vec::new();
+        for op in &ops {
+            if !paths.contains(&op.op.path.as_str()) {
+                paths.push(&op.op.path);
+                if !methods.contains(&op.op.method.as_str()) {
+                    methods.push(&op.op.method);
+                }
+            }
+        }
+        // Import only what the handlers use, so the stub compiles warning-free
+        let mut extractors = Vec::new();
+        if ops.iter().any(|op| !op.path_params.is_empty()) {
+            extractors.push("Path");
+        }
+        if ops.iter().any(|op| !op.op.query_params.is_empty()) {
+            extractors.push("Query");
+        }
+        extractors.push("State");
+        out.push_str(&format!(
+            "use axum::extract::{{{}}};\n",
+            extractors.join(", ")
+        ));
+        out.push_str("use axum::http::StatusCode;\n");
+        match methods.as_slice() {
+            [] => {}
+            [method] => out.push_str(&format!("use axum::routing::{};\n", method)),
+            _ => out.push_str(&format!("use axum::routing::{{{}}};\n", methods.join(", "))),
+        }
+        let uses_json = ops
+            .iter()
+            .any(|op| op.op.body.is_some() || op.op.response.is_some());
+        if uses_json {
+            out.push_str("use axum::{Json, Router};\n");
+        } else {
+            out.push_str("use axum::Router;\n");
+        }
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+        out.push_str("use std::future::Future;\n");
+        out.push_str("use std::sync::Arc;\n\n");
+
+        // Generate structs from schemas
+        out.push_str(&rust_structs(spec));
+
+        // Query parameter structs
+        for op in &ops {
+            if op.op.query_params.is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "/// Query parameters of `{}`\n",
+                op.op.operation_id
+            ));
+            out.push_str("#[derive(Debug, Clone, Deserialize)]\n");
+            out.push_str(&format!("pub struct {} {{\n", op.query_type()));
+            for p in &op.op.query_params {
+                let rust_type = p
+                    .schema
+                    .as_ref()
+                    .map_or_else(|| "String".to_string(), schema_to_rust);
+                let nullable = p.schema.as_ref().is_some_and(|s| s.nullable);
+                let field_type = if p.required || nullable {
+                    rust_type
+                } else {
+                    format!("Option<{}>", rust_type)
+                };
+                out.push_str(&rust_field(&p.name, &field_type));
+            }
+            out.push_str("}\n\n");
+        }
+
+        // Trait with one method per operation
+        out.push_str("/// The API's operations; implement this and pass it to [`router`].\n");
+        out.push_str("pub trait Api: Send + Sync + 'static {\n");
+        for op in &ops {
+            let mut args = vec!["&self".to_string()];
+            args.extend(
+                op.args()
+                    .into_iter()
+                    .map(|(name, ty)| format!("{}: {}", name, ty)),
+            );
+            out.push_str(&format!(
+                "    fn {}({}) -> impl Future<Output = Result<{}, StatusCode>> + Send;\n",
+                op.name,
+                args.join(", "),
+                op.response_type()
+            ));
+        }
+        out.push_str("}\n\n");
+
+        // Handlers
+        for op in &ops {
+            let mut params = vec!["State(api): State<Arc<A>>".to_string()];
+            let args = op.args();
+            match op.path_params.as_slice() {
+                [] => {}
+                [p] => params.push(format!(
+                    "Path({}): Path<{}>",
+                    to_snake_case(&p.name),
+                    path_param_type(p.schema.as_ref())
+                )),
+                many => {
+                    let names: Vec<String> = many.iter().map(|p| to_snake_case(&p.name)).collect();
+                    let types: Vec<String> = many
+                        .iter()
+                        .map(|p| path_param_type(p.schema.as_ref()))
+                        .collect();
+                    params.push(format!(
+                        "Path(({})): Path<({})>",
+                        names.join(", "),
+                        types.join(", ")
+                    ));
+                }
+            }
+            if !op.op.query_params.is_empty() {
+                params.push(format!("Query(query): Query<{}>", op.query_type()));
+            }
+            if let Some(body) = &op.op.body {
+                let ty = schema_to_rust(&body.schema);
+                if body.required {
+                    params.push(format!("Json(body): Json<{}>", ty));
+                } else {
+                    params.push(format!("body: Option<Json<{}>>", ty));
+                }
+            }
+            let call_args: Vec<String> = args
+                .iter()
+                .map(|(name, _)| match &op.op.body {
+                    Some(body) if name == "body" && !body.required => {
+                        "body.map(|Json(body)| body)".to_string()
+                    }
+                    _ => name.clone(),
+                })
+                .collect();
+
+            out.push_str(&format!(
+                "async fn {}<A: Api>({}) -> Result<{}, StatusCode> {{\n",
+                op.name,
+                params.join(", "),
+                op.response_type()
+            ));
+            out.push_str(&format!(
+                "    api.{}({}).await\n",
+                op.name,
+                call_args.join(", ")
+            ));
+            out.push_str("}\n\n");
+        }
+
+        // Router, one route per path
+        out.push_str("/// Route every operation to `api`.\n");
+        out.push_str("pub fn router<A: Api>(api: A) -> Router {\n");
+        out.push_str("    Router::new()\n");
+        for path in paths {
+            let handlers: Vec<String> = ops
+                .iter()
+                .filter(|op| op.op.path == path)
+                .map(|op| format!("{}({}::<A>)", op.op.method, op.name))
+                .collect();
+            out.push_str(&format!(
+                "        .route(\"{}\", {})\n",
+                path,
+                handlers.join(".")
+            ));
+        }
+        out.push_str("        .with_state(Arc::new(api))\n");
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// An operation with the names the axum generator derives from it
+struct AxumOp<'a> {
+    op: &'a Operation,
+    /// snake_case handler and trait method name
+    name: String,
+    /// Path parameters in the order they appear in the path, which is the
+    /// order axum's tuple extractor fills them
+    path_params: Vec<&'a crate::Parameter>,
+}
+
+impl<'a> AxumOp<'a> {
+    fn new(op: &'a Operation) -> Self {
+        let mut path_params: Vec<&crate::Parameter> = op.path_params.iter().collect();
+        path_params.sort_by_key(|p| op.path.find(&format!("{{{}}}", p.name)));
+        AxumOp {
+            op,
+            name: to_snake_case(&op.operation_id),
+            path_params,
+        }
+    }
+
+    fn query_type(&self) -> String {
+        format!("{}Query", to_pascal_case(&self.op.operation_id))
+    }
+
+    /// The trait method's arguments after `&self`: path parameters, then the
+    /// query struct, then the body
+    fn args(&self) -> Vec<(String, String)> {
+        let mut args: Vec<(String, String)> = self
+            .path_params
+            .iter()
+            .map(|p| (to_snake_case(&p.name), path_param_type(p.schema.as_ref())))
+            .collect();
+        if !self.op.query_params.is_empty() {
+            args.push(("query".to_string(), self.query_type()));
+        }
+        if let Some(body) = &self.op.body {
+            let ty = schema_to_rust(&body.schema);
+            let ty = if body.required {
+                ty
+            } else {
+                format!("Option<{}>", ty)
+            };
+            args.push(("body".to_string(), ty));
+        }
+        args
+    }
+
+    /// `Json<T>` for operations that return a body, a bare status otherwise
+    fn response_type(&self) -> String {
+        match &self.op.response {
+            Some(schema) => format!("Json<{}>", schema_to_rust(schema)),
+            None => "StatusCode".to_string(),
+        }
+    }
+}
+
+fn path_param_type(schema: Option<&Schema>) -> String {
+    schema.map_or_else(|| "String".to_string(), schema_to_rust)
+}
+
+/// `listUsers` / `list_users` -> `ListUsers`
+fn to_pascal_case(s: &str) -> String {
+    let mut out = String::new();
+    for word in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize;
+
+    #[test]
+    fn test_get_server_generator() {
+        assert!(get_server_generator("rust").is_some());
+        assert!(get_server_generator("rs").is_some());
+        assert!(get_server_generator("python").is_none());
+        assert!(list_server_generators().contains(&("rust", "axum")));
+    }
+
+    #[test]
+    fn test_axum_stub() {
+        let spec = normalize(&serde_json::json!({
+            "openapi": "3.0.0",
+            "components": { "schemas": {
+                "Post": {
+                    "type": "object",
+                    "properties": { "authorId": { "type": "integer" } },
+                    "required": ["authorId"]
+                }
+            } },
+            "paths": {
+                "/users/{userId}/posts/{postId}": {
+                    "parameters": [
+                        { "name": "postId", "in": "path", "required": true, "schema": { "type": "integer" } },
+                        { "name": "userId", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "get": {
+                        "operationId": "getPost",
+                        "parameters": [
+                            { "name": "pageSize", "in": "query", "schema": { "type": "integer" } }
+                        ],
+                        "responses": { "200": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Post" }
+                        } } } }
+                    },
+                    "put": {
+                        "operationId": "putPost",
+                        "requestBody": { "required": true, "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Post" }
+                        } } },
+                        "responses": { "204": { "description": "Updated" } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let code = RustAxum.generate(&spec);
+
+        assert!(code.contains("use axum::routing::get;\n"), "{}", code);
+        assert!(
+            code.contains("    #[serde(rename = \"authorId\")]\n    pub author_id: i64,\n"),
+            "{}",
+            code
+        );
+        assert!(
+            code.contains(
+                "pub struct GetPostQuery {\n    #[serde(rename = \"pageSize\")]\n    pub page_size: Option<i64>,\n}"
+            ),
+            "{}",
+            code
+        );
+        assert!(code.contains("    fn get_post(&self, user_id: String, post_id: i64, query: GetPostQuery) -> impl Future<Output = Result<Json<Post>, StatusCode>> + Send;\n"), "{}", code);
+        assert!(code.contains("    fn put_post(&self, user_id: String, post_id: i64, body: Post) -> impl Future<Output = Result<StatusCode, StatusCode>> + Send;\n"), "{}", code);
+        // Path parameters are extracted in path order, not declaration order
+        assert!(code.contains("async fn get_post<A: Api>(State(api): State<Arc<A>>, Path((user_id, post_id)): Path<(String, i64)>, Query(query): Query<GetPostQuery>) -> Result<Json<Post>, StatusCode> {\n    api.get_post(user_id, post_id, query).await\n}"), "{}", code);
+        assert!(
+            code.contains(
+                "        .route(\"/users/{userId}/posts/{postId}\", get(get_post::<A>).put(put_post::<A>))\n"
+            ),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("listUsers"), "ListUsers");
+        assert_eq!(to_pascal_case("list_users"), "ListUsers");
+    }
+}
@@ -0,0 +1,669 @@
+//! Normalized OpenAPI spec.
+//!
+//! Specs come as Swagger 2.0, OpenAPI 3.0 or OpenAPI 3.1, which spell the same
+//! things differently: `definitions` vs `components/schemas`, `in: body`
+//! parameters vs `requestBody`, `nullable: true` vs `type: ["string", "null"]`.
+//! [`normalize`] reduces all three to one IR so generators only handle one
+//! shape. Named schemas stay as [`SchemaKind::Ref`]s. Every other `$ref`
+//! (parameters, request bodies, responses) is resolved, and `allOf` members
+//! are merged into a single object.
+
+use serde_json::{Map, Value};
+
+/// A spec reduced to what client generators need.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedSpec {
+    /// Named schemas (`components/schemas`, or `definitions` in Swagger 2.0)
+    pub schemas: Vec<(String, Schema)>,
+    /// Every operation, in path then method order
+    pub operations: Vec<Operation>,
+}
+
+/// A type, as far as code generation cares.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub kind: SchemaKind,
+    /// `null` is also a valid value
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaKind {
+    /// A named schema from [`NormalizedSpec::schemas`]
+    Ref(String),
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array(Box<Schema>),
+    /// An object; `properties` is empty for free-form objects
+    Object(Vec<Property>),
+    /// No usable type information (or several unrelated types)
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub schema: Schema,
+    pub required: bool,
+}
+
+/// One operation (path + HTTP method).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    pub path: String,
+    /// Lowercase HTTP method, e.g. "post"
+    pub method: String,
+    /// `operationId`, or a camelCase name derived from method and path
+    pub operation_id: String,
+    /// Path parameters, including those declared on the path item
+    pub path_params: Vec<Parameter>,
+    /// Query parameters, including those declared on the path item
+    pub query_params: Vec<Parameter>,
+    /// JSON request body
+    pub body: Option<RequestBody>,
+    /// Schema of the first 2xx (else `default`) response
+    pub response: Option<Schema>,
+    /// The success response is declared without content (e.g. 204)
+    pub response_empty: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub required: bool,
+    pub schema: Option<Schema>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestBody {
+    pub schema: Schema,
+    pub required: bool,
+}
+
+impl Schema {
+    fn new(kind: SchemaKind) -> Self {
+        Schema {
+            kind,
+            nullable: false,
+        }
+    }
+
+    fn any() -> Self {
+        Schema::new(SchemaKind::Any)
+    }
+}
+
+/// HTTP methods a path item can define operations for, in the order the
+/// OpenAPI spec lists them.
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// `$ref` prefixes that name a schema, so the reference is kept by name
+const SCHEMA_REF_PREFIXES: &[&str] = &["#/components/schemas/", "#/definitions/"];
+
+/// Deepest `allOf`/`$ref` chain followed before giving up (guards cycles)
+const MAX_DEPTH: usize = 32;
+
+/// Normalize a Swagger 2.0, OpenAPI 3.0 or OpenAPI 3.1 spec.
+///
+/// Specs without a version field are read as OpenAPI 3. Errors on other
+/// versions (Swagger 1.x, a future OpenAPI 4).
+pub fn normalize(spec: &Value) -> Result<NormalizedSpec, String> {
+    let swagger2 = match (version(spec, "swagger"), version(spec, "openapi")) {
+        (Some(v), _) if v == "2" || v.starts_with("2.") => true,
+        (Some(v), _) => return Err(format!("Unsupported Swagger version: {}", v)),
+        (None, Some(v)) if v.starts_with("3.") => false,
+        (None, Some(v)) => return Err(format!("Unsupported OpenAPI version: {}", v)),
+        (None, None) => false,
+    };
+    let n = Normalizer { spec, swagger2 };
+
+    let schema_pointer = if swagger2 {
+        "/definitions"
+    } else {
+        "/components/schemas"
+    };
+    let schemas = spec
+        .pointer(schema_pointer)
+        .and_then(|s| s.as_object())
+        .map(|s| {
+            s.iter()
+                .map(|(name, schema)| (name.clone(), n.named_schema(schema)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(NormalizedSpec {
+        schemas,
+        operations: n.operations(),
+    })
+}
+
+/// A version field, which unquoted in YAML (`swagger: 2.0`) is a number
+fn version(spec: &Value, key: &str) -> Option<String> {
+    match spec.get(key)? {
+        Value::String(v) => Some(v.clone()),
+        Value::Number(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+struct Normalizer<'a> {
+    spec: &'a Value,
+    swagger2: bool,
+}
+
+impl<'a> Normalizer<'a> {
+    /// Follow a local `$ref`; other values are returned as is
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        let mut value = value;
+        for _ in 0..MAX_DEPTH {
+            let Some(target) = value
+                .get("$ref")
+                .and_then(|r| r.as_str())
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| self.spec.pointer(pointer))
+            else {
+                break;
+            };
+            value = target;
+        }
+        value
+    }
+
+    /// A named schema's definition. Unlike [`Normalizer::schema`], a top-level
+    /// `$ref` or `allOf` is always expanded, so the generated type has the
+    /// fields rather than aliasing another name.
+    fn named_schema(&self, schema: &'a Value) -> Schema {
+        if schema.get("allOf").is_some() {
+            let mut merged = self.merged_object(schema, 0);
+            merged.nullable = self.nullable(schema);
+            return merged;
+        }
+        if schema.get("$ref").is_some() {
+            let target = self.resolve(schema);
+            if std::ptr::eq(target, schema) || target.get("$ref").is_some() {
+                return Schema::any();
+            }
+            return self.named_schema(target);
+        }
+        self.schema(schema)
+    }
+
+    fn schema(&self, schema: &'a Value) -> Schema {
+        self.schema_at(schema, 0)
+    }
+
+    fn schema_at(&self, schema: &'a Value, depth: usize) -> Schema {
+        if depth > MAX_DEPTH {
+            return Schema::any();
+        }
+
+        if let Some(r) = schema.get("$ref").and_then(|r| r.as_str()) {
+            if let Some(name) = SCHEMA_REF_PREFIXES.iter().find_map(|p| r.strip_prefix(p))
+                && !name.contains('/')
+            {
+                return Schema::new(SchemaKind::Ref(name.to_string()));
+            }
+            let target = self.resolve(schema);
+            if std::ptr::eq(target, schema) {
+                return Schema::any();
+            }
+            return self.schema_at(target, depth + 1);
+        }
+
+        if let Some(members) = schema.get("allOf").and_then(|a| a.as_array()) {
+            // `allOf: [{$ref}]` alone (often used to attach a description)
+            // is just that type
+            if members.len() == 1 && schema.get("properties").is_none() {
+                let mut single = self.schema_at(&members[0], depth + 1);
+                single.nullable |= self.nullable(schema);
+                return single;
+            }
+            let mut merged = self.merged_object(schema, depth);
+            merged.nullable = self.nullable(schema);
+            return merged;
+        }
+
+        // `anyOf`/`oneOf` of one type and `null` (the OpenAPI 3.1 spelling of
+        // a nullable reference)
+        if let Some(variants) = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(|v| v.as_array())
+        {
+            let non_null: Vec<&Value> = variants
+                .iter()
+                .filter(|v| v.get("type").and_then(|t| t.as_str()) != Some("null"))
+                .collect();
+            if non_null.len() == 1 {
+                let mut inner = self.schema_at(non_null[0], depth + 1);
+                inner.nullable |= non_null.len() < variants.len() || self.nullable(schema);
+                return inner;
+            }
+            return Schema::any();
+        }
+
+        let type_name = match schema.get("type") {
+            Some(Value::String(t)) => Some(t.as_str()),
+            Some(Value::Array(types)) => {
+                let non_null: Vec<&str> = types
+                    .iter()
+                    .filter_map(|t| t.as_str())
+                    .filter(|t| *t != "null")
+                    .collect();
+                match non_null.as_slice() {
+                    [t] => Some(*t),
+                    _ => return Schema::any(),
+                }
+            }
+            _ if schema.get("properties").is_some() => Some("object"),
+            _ => None,
+        };
+
+        let kind = match type_name {
+            Some("string") => SchemaKind::String,
+            Some("integer") => SchemaKind::Integer,
+            Some("number") => SchemaKind::Number,
+            Some("boolean") => SchemaKind::Boolean,
+            Some("array") => SchemaKind::Array(Box::new(
+                schema
+                    .get("items")
+                    .map_or_else(Schema::any, |items| self.schema_at(items, depth + 1)),
+            )),
+            Some("object") => SchemaKind::Object(self.properties(schema, depth)),
+            _ => SchemaKind::Any,
+        };
+        Schema {
+            kind,
+            nullable: self.nullable(schema),
+        }
+    }
+
+    /// `nullable: true` (3.0, or `x-nullable` in Swagger 2.0) or `null` in a
+    /// type array (3.1)
+    fn nullable(&self, schema: &Value) -> bool {
+        let flag = if self.swagger2 {
+            "x-nullable"
+        } else {
+            "nullable"
+        };
+        schema.get(flag).and_then(|n| n.as_bool()).unwrap_or(false)
+            || schema
+                .get("type")
+                .and_then(|t| t.as_array())
+                .is_some_and(|types| types.iter().any(|t| t == "null"))
+    }
+
+    fn properties(&self, schema: &'a Value, depth: usize) -> Vec<Property> {
+        let Some(props) = schema.get("properties").and_then(|p| p.as_object()) else {
+            return Vec::new();
+        };
+        let required = required_names(schema);
+        props
+            .iter()
+            .map(|(name, prop)| Property {
+                name: name.clone(),
+                schema: self.schema_at(prop, depth + 1),
+                required: required.contains(&name.as_str()),
+            })
+            .collect()
+    }
+
+    /// `schema`'s own properties plus those of every `allOf` member (following
+    /// `$ref`s), as one object. Later members override earlier same-named
+    /// properties; a property is required if any member requires it.
+    fn merged_object(&self, schema: &'a Value, depth: usize) -> Schema {
+        let mut props: Vec<Property> = Vec::new();
+        self.collect_properties(schema, depth, &mut props);
+        Schema::new(SchemaKind::Object(props))
+    }
+
+    fn collect_properties(&self, schema: &'a Value, depth: usize, props: &mut Vec<Property>) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let schema = self.resolve(schema);
+        if let Some(members) = schema.get("allOf").and_then(|a| a.as_array()) {
+            for member in members {
+                self.collect_properties(member, depth + 1, props);
+            }
+        }
+        let required = required_names(schema);
+        for prop in self.properties(schema, depth) {
+            match props.iter_mut().find(|p| p.name == prop.name) {
+                Some(existing) => {
+                    existing.required |= prop.required;
+                    existing.schema = prop.schema;
+                }
+                None => props.push(prop),
+            }
+        }
+        // `required` may name properties declared by another member
+        for name in required {
+            if let Some(p) = props.iter_mut().find(|p| p.name == name) {
+                p.required = true;
+            }
+        }
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        let mut ops = Vec::new();
+        let Some(paths) = self.spec.get("paths").and_then(|p| p.as_object()) else {
+            return ops;
+        };
+        for (path, item) in paths {
+            let item = self.resolve(item);
+            let shared = self.parameter_list(item.get("parameters"));
+            for &method in HTTP_METHODS {
+                let Some(op) = item.get(method).and_then(|o| o.as_object()) else {
+                    continue;
+                };
+
+                let own = self.parameter_list(op.get("parameters"));
+                let mut params: Vec<&Value> = shared
+                    .iter()
+                    .filter(|s| !own.iter().any(|o| same_param(s, o)))
+                    .copied()
+                    .collect();
+                params.extend(own);
+
+                let success = self.success_response(op);
+                ops.push(Operation {
+                    path: path.clone(),
+                    method: method.to_string(),
+                    operation_id: op
+                        .get("operationId")
+                        .and_then(|id| id.as_str())
+                        .map(str::to_string)
+                        .unwrap_or_else(|| derive_op_id(method, path)),
+                    path_params: self.params_in(&params, "path"),
+                    query_params: self.params_in(&params, "query"),
+                    body: self.request_body(op, &params),
+                    response: success
+                        .and_then(|r| self.content_schema(r))
+                        .map(|s| self.schema(s)),
+                    response_empty: success.is_some_and(|r| self.content_schema(r).is_none()),
+                });
+            }
+        }
+        ops
+    }
+
+    /// A path item's or operation's `parameters`, with `$ref`s followed
+    fn parameter_list(&self, params: Option<&'a Value>) -> Vec<&'a Value> {
+        params
+            .and_then(|p| p.as_array())
+            .map(|a| a.iter().map(|p| self.resolve(p)).collect())
+            .unwrap_or_default()
+    }
+
+    fn params_in(&self, params: &[&'a Value], location: &str) -> Vec<Parameter> {
+        params
+            .iter()
+            .filter(|p| p.get("in").and_then(|i| i.as_str()) == Some(location))
+            .filter_map(|p| {
+                // Swagger 2.0 puts `type`/`items` on the parameter itself
+                let schema = match p.get("schema") {
+                    Some(s) => Some(self.schema(s)),
+                    None if p.get("type").is_some() => Some(self.schema(p)),
+                    None => None,
+                };
+                Some(Parameter {
+                    name: p.get("name").and_then(|n| n.as_str())?.to_string(),
+                    // Path parameters are always required
+                    required: location == "path"
+                        || p.get("required").and_then(|r| r.as_bool()).unwrap_or(false),
+                    schema,
+                })
+            })
+            .collect()
+    }
+
+    /// `requestBody` (3.x) or the `in: body` parameter (Swagger 2.0)
+    fn request_body(
+        &self,
+        op: &'a Map<String, Value>,
+        params: &[&'a Value],
+    ) -> Option<RequestBody> {
+        let (schema, required) = if self.swagger2 {
+            let param = params
+                .iter()
+                .find(|p| p.get("in").and_then(|i| i.as_str()) == Some("body"))?;
+            (param.get("schema")?, param.get("required"))
+        } else {
+            let body = self.resolve(op.get("requestBody")?);
+            (
+                body.pointer("/content/application~1json/schema")?,
+                body.get("required"),
+            )
+        };
+        Some(RequestBody {
+            schema: self.schema(schema),
+            required: required.and_then(|r| r.as_bool()).unwrap_or(false),
+        })
+    }
+
+    /// The first 2xx response, else `default`
+    fn success_response(&self, op: &'a Map<String, Value>) -> Option<&'a Value> {
+        let responses = op.get("responses")?.as_object()?;
+        let mut codes: Vec<&String> = responses.keys().filter(|c| c.starts_with('2')).collect();
+        codes.sort();
+        let code = codes
+            .first()
+            .copied()
+            .or_else(|| responses.keys().find(|c| *c == "default"))?;
+        responses.get(code).map(|r| self.resolve(r))
+    }
+
+    /// A response's JSON schema: `schema` in Swagger 2.0, JSON content in 3.x
+    fn content_schema(&self, response: &'a Value) -> Option<&'a Value> {
+        if self.swagger2 {
+            response.get("schema")
+        } else {
+            response.pointer("/content/application~1json/schema")
+        }
+    }
+}
+
+fn required_names(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn same_param(a: &Value, b: &Value) -> bool {
+    a.get("name") == b.get("name") && a.get("in") == b.get("in")
+}
+
+/// Name an operation without an `operationId`: `post /users/{id}/posts`
+/// becomes `postUsersIdPosts`.
+fn derive_op_id(method: &str, path: &str) -> String {
+    let mut id = method.to_string();
+    for word in path.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            id.push(first.to_ascii_uppercase());
+            id.push_str(chars.as_str());
+        }
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn string() -> Schema {
+        Schema::new(SchemaKind::String)
+    }
+
+    #[test]
+    fn test_swagger2() {
+        let spec = normalize(&json!({
+            "swagger": "2.0",
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "owner": { "$ref": "#/definitions/Owner" }
+                    },
+                    "required": ["name"]
+                },
+                "Owner": { "type": "object", "properties": { "tag": { "type": "string", "x-nullable": true } } }
+            },
+            "parameters": {
+                "PetId": { "name": "petId", "in": "path", "required": true, "type": "integer" }
+            },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{ "$ref": "#/parameters/PetId" }],
+                    "put": {
+                        "operationId": "updatePet",
+                        "parameters": [
+                            { "name": "pet", "in": "body", "required": true, "schema": { "$ref": "#/definitions/Pet" } },
+                            { "name": "tags", "in": "query", "type": "array", "items": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "schema": { "$ref": "#/definitions/Pet" } } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let names: Vec<&str> = spec.schemas.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["Owner", "Pet"]);
+        let SchemaKind::Object(owner) = &spec.schemas[0].1.kind else {
+            panic!("Owner is an object");
+        };
+        assert!(owner[0].schema.nullable);
+
+        let op = &spec.operations[0];
+        assert_eq!(op.operation_id, "updatePet");
+        assert_eq!(op.path_params[0].name, "petId");
+        assert_eq!(
+            op.path_params[0].schema,
+            Some(Schema::new(SchemaKind::Integer))
+        );
+        assert_eq!(
+            op.query_params[0].schema,
+            Some(Schema::new(SchemaKind::Array(Box::new(string()))))
+        );
+        let pet = Schema::new(SchemaKind::Ref("Pet".to_string()));
+        assert_eq!(
+            op.body,
+            Some(RequestBody {
+                schema: pet.clone(),
+                required: true
+            })
+        );
+        assert_eq!(op.response, Some(pet));
+    }
+
+    #[test]
+    fn test_openapi31_nullable() {
+        let spec = normalize(&json!({
+            "openapi": "3.1.0",
+            "components": { "schemas": {
+                "Item": {
+                    "type": "object",
+                    "properties": {
+                        "label": { "type": ["string", "null"] },
+                        "parent": { "anyOf": [{ "$ref": "#/components/schemas/Item" }, { "type": "null" }] },
+                        "mixed": { "type": ["string", "integer"] }
+                    }
+                }
+            } }
+        }))
+        .unwrap();
+        let SchemaKind::Object(props) = &spec.schemas[0].1.kind else {
+            panic!("Item is an object");
+        };
+        let by_name = |n: &str| &props.iter().find(|p| p.name == n).unwrap().schema;
+        assert_eq!(
+            by_name("label"),
+            &Schema {
+                kind: SchemaKind::String,
+                nullable: true
+            }
+        );
+        assert_eq!(
+            by_name("parent"),
+            &Schema {
+                kind: SchemaKind::Ref("Item".to_string()),
+                nullable: true
+            }
+        );
+        assert_eq!(by_name("mixed"), &Schema::any());
+    }
+
+    #[test]
+    fn test_all_of_merges_properties() {
+        let spec = normalize(&json!({
+            "openapi": "3.0.3",
+            "components": { "schemas": {
+                "Base": {
+                    "type": "object",
+                    "properties": { "id": { "type": "integer" } },
+                    "required": ["id"]
+                },
+                "Named": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Base" },
+                        { "type": "object", "properties": { "name": { "type": "string" } } }
+                    ],
+                    "required": ["name"]
+                },
+                "Wrapper": {
+                    "type": "object",
+                    "properties": {
+                        "base": { "allOf": [{ "$ref": "#/components/schemas/Base" }], "nullable": true }
+                    }
+                }
+            } }
+        }))
+        .unwrap();
+        let (_, named) = spec.schemas.iter().find(|(n, _)| n == "Named").unwrap();
+        let SchemaKind::Object(props) = &named.kind else {
+            panic!("Named is an object");
+        };
+        let fields: Vec<(&str, bool)> = props
+            .iter()
+            .map(|p| (p.name.as_str(), p.required))
+            .collect();
+        assert_eq!(fields, vec![("id", true), ("name", true)]);
+
+        // A lone `allOf` member keeps its name
+        let (_, wrapper) = spec.schemas.iter().find(|(n, _)| n == "Wrapper").unwrap();
+        assert_eq!(
+            properties_of(wrapper)[0].schema,
+            Schema {
+                kind: SchemaKind::Ref("Base".to_string()),
+                nullable: true
+            }
+        );
+    }
+
+    fn properties_of(schema: &Schema) -> &[Property] {
+        match &schema.kind {
+            SchemaKind::Object(props) => props,
+            _ => &[],
+        }
+    }
+
+    #[test]
+    fn test_unsupported_versions() {
+        assert!(normalize(&json!({ "swagger": "1.2" })).is_err());
+        assert!(normalize(&json!({ "openapi": "4.0.0" })).is_err());
+        assert!(normalize(&json!({})).is_ok());
+        // Unquoted in YAML
+        assert!(normalize(&json!({ "swagger": 2.0 })).is_ok());
+    }
+}
@@ -145,6 +145,48 @@ pub struct ToolFailure {
     pub message: String,
 }
 
+/// A measurement reported by a rule, such as a tuple of a fact rule's
+/// declared output relation.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RuleMetric {
+    /// File the measurement applies to (empty for project-wide metrics).
+    pub file: String,
+    pub line: Option<usize>,
+    pub rule_id: String,
+    pub name: String,
+    pub value: f64,
+    /// Remaining columns of the output tuple, rendered as text.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<(String, String)>,
+}
+
+impl RuleMetric {
+    /// Format as `file:line: [rule_id] name = value (field=value, ...)`.
+    fn format_line(&self) -> String {
+        let mut line = String::new();
+        if !self.file.is_empty() {
+            line.push_str(&self.file);
+            if let Some(l) = self.line {
+                line.push_str(&format!(":{l}"));
+            }
+            line.push_str(": ");
+        }
+        line.push_str(&format!(
+            "[{}] {} = {}",
+            self.rule_id, self.name, self.value
+        ));
+        if !self.fields.is_empty() {
+            let fields: Vec<String> = self
+                .fields
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect();
+            line.push_str(&format!(" ({})", fields.join(", ")));
+        }
+        line
+    }
+}
+
 /// Report containing diagnostic issues from one or more checks.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct DiagnosticsReport {
@@ -160,6 +202,9 @@ pub struct DiagnosticsReport {
     /// should skip local re-evaluation of those engines.
     #[serde(skip_serializing_if = "std::ops::Not::not", default)]
     pub daemon_cached: bool,
+    /// Measurements reported by rules alongside their issues.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub metrics: Vec<RuleMetric>,
 }
 
 impl DiagnosticsReport {
@@ -171,6 +216,7 @@ impl DiagnosticsReport {
             sources_run: Vec::new(),
             tool_errors: Vec::new(),
             daemon_cached: false,
+            metrics: Vec::new(),
         }
     }
 
@@ -188,6 +234,7 @@ impl DiagnosticsReport {
             }
         }
         self.tool_errors.extend(other.tool_errors);
+        self.metrics.extend(other.metrics);
     }
 
     /// Sort issues by file, then line, then severity (most severe first).
@@ -227,31 +274,38 @@ impl DiagnosticsReport {
             .issues
             .iter()
             .map(|issue| {
+                // SARIF lines are 1-based; line 0 means the finding covers the
+                // whole file, which SARIF expresses by omitting the region.
                 let mut region = serde_json::Map::new();
-                if let Some(line) = issue.line {
+                if let Some(line) = issue.line.filter(|&l| l > 0) {
                     region.insert("startLine".into(), serde_json::json!(line));
-                }
-                if let Some(col) = issue.column {
-                    region.insert("startColumn".into(), serde_json::json!(col));
-                }
-                if let Some(end_line) = issue.end_line {
-                    region.insert("endLine".into(), serde_json::json!(end_line));
-                }
-                if let Some(end_col) = issue.end_column {
-                    region.insert("endColumn".into(), serde_json::json!(end_col));
+                    if let Some(col) = issue.column {
+                        region.insert("startColumn".into(), serde_json::json!(col));
+                    }
+                    if let Some(end_line) = issue.end_line.filter(|&l| l >= line) {
+                        region.insert("endLine".into(), serde_json::json!(end_line));
+                    }
+                    if let Some(end_col) = issue.end_column {
+                        region.insert("endColumn".into(), serde_json::json!(end_col));
+                    }
                 }
 
-                serde_json::json!({
+                let mut physical = serde_json::json!({
+                    "artifactLocation": { "uri": issue.file },
+                });
+                if !region.is_empty() {
+                    physical["region"] = serde_json::Value::Object(region);
+                }
+                let mut result = serde_json::json!({
                     "ruleId": issue.rule_id,
                     "level": severity_to_sarif_level(issue.severity),
                     "message": { "text": issue.message },
-                    "locations": [{
-                        "physicalLocation": {
-                            "artifactLocation": { "uri": issue.file },
-                            "region": region
-                        }
-                    }]
-                })
+                });
+                // Findings without a file (e.g. project-wide fact rules) carry no location.
+                if !issue.file.is_empty() {
+                    result["locations"] = serde_json::json!([{ "physicalLocation": physical }]);
+                }
+                result
             })
             .collect();
 
@@ -314,6 +368,7 @@ impl DiagnosticsReport {
                 self.files_checked,
                 self.sources_run.join(", ")
             ));
+            self.push_metrics(&mut out);
             return out;
         }
 
@@ -413,8 +468,24 @@ impl DiagnosticsReport {
             ));
         }
 
+        self.push_metrics(&mut out);
         out
     }
+
+    /// Append the report's metrics, one per line, after a blank line.
+    fn push_metrics(&self, out: &mut String) {
+        if self.metrics.is_empty() {
+            return;
+        }
+        out.push_str(&format!(
+            "\n{} metric{}:\n",
+            self.metrics.len(),
+            if self.metrics.len() == 1 { "" } else { "s" }
+        ));
+        for metric in &self.metrics {
+            out.push_str(&format!("  {}\n", metric.format_line()));
+        }
+    }
 }
 
 impl OutputFormatter for DiagnosticsReport {
@@ -453,6 +524,7 @@ impl OutputFormatter for DiagnosticsReport {
                 Color::Green.paint("✓"),
                 self.files_checked
             ));
+            self.push_metrics(&mut out);
             return out;
         }
         let errors = self.count_by_severity(Severity::Error);
@@ -571,6 +643,7 @@ impl OutputFormatter for DiagnosticsReport {
             }
         }
 
+        self.push_metrics(&mut out);
         out
     }
 }
@@ -597,12 +670,41 @@ mod tests {
             sources_run: vec!["check-refs".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         };
         let text = report.format_text();
         assert!(text.contains("No issues found"));
         assert!(text.contains("10 files checked"));
     }
 
+    #[test]
+    fn test_report_lists_metrics() {
+        let mut report = DiagnosticsReport::new();
+        report.metrics.push(RuleMetric {
+            file: "src/lib.rs".into(),
+            line: None,
+            rule_id: "file-size".into(),
+            name: "size".into(),
+            value: 120.0,
+            fields: vec![],
+        });
+        report.metrics.push(RuleMetric {
+            file: String::new(),
+            line: None,
+            rule_id: "coupling".into(),
+            name: "score".into(),
+            value: 0.5,
+            fields: vec![("module".into(), "core".into())],
+        });
+        let text = report.format_text();
+        assert!(text.contains("No issues found"));
+        assert!(text.contains("2 metrics:"));
+        assert!(text.contains("  src/lib.rs: [file-size] size = 120\n"));
+        assert!(text.contains("  [coupling] score = 0.5 (module=core)\n"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["metrics"][0]["value"], 120.0);
+    }
+
     #[test]
     fn test_issue_format_location() {
         let issue = Issue {
@@ -659,6 +761,7 @@ mod tests {
             sources_run: vec!["check-refs".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         };
         let b = DiagnosticsReport {
             issues: vec![Issue {
@@ -678,6 +781,7 @@ mod tests {
             sources_run: vec!["stale-docs".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         };
         a.merge(b);
         assert_eq!(a.issues.len(), 2);
@@ -727,6 +831,7 @@ mod tests {
             sources_run: vec!["s".into()],
             tool_errors: vec![],
             daemon_cached: false,
+            metrics: vec![],
         };
         report.sort();
         assert_eq!(report.issues[0].file, "a.rs");
@@ -750,6 +855,7 @@ mod tests {
                 },
             ],
             daemon_cached: false,
+            metrics: vec![],
         };
         let text = report.format_text();
         assert!(text.contains("2 tool errors:"));
@@ -768,6 +874,7 @@ mod tests {
                 message: "error a".into(),
             }],
             daemon_cached: false,
+            metrics: vec![],
         };
         let b = DiagnosticsReport {
             issues: vec![],
@@ -778,10 +885,54 @@ mod tests {
                 message: "error b".into(),
             }],
             daemon_cached: false,
+            metrics: vec![],
         };
         a.merge(b);
         assert_eq!(a.tool_errors.len(), 2);
         assert_eq!(a.tool_errors[0].tool, "tool-a");
         assert_eq!(a.tool_errors[1].tool, "tool-b");
     }
+
+    #[test]
+    fn test_format_sarif_regions() {
+        let issue = |file: &str, line, end_line| Issue {
+            file: file.into(),
+            line,
+            column: None,
+            end_line,
+            end_column: None,
+            rule_id: "god-class".into(),
+            message: "Parser".into(),
+            severity: Severity::Warning,
+            source: "fact-rules".into(),
+            related: vec![],
+            suggestion: None,
+        };
+        let report = DiagnosticsReport {
+            issues: vec![
+                issue("src/parser.rs", Some(10), Some(42)),
+                issue("src/big.rs", Some(0), None),
+                issue("", None, None),
+            ],
+            files_checked: 2,
+            sources_run: vec!["fact-rules".into()],
+            tool_errors: vec![],
+            daemon_cached: false,
+            metrics: vec![],
+        };
+        let sarif: serde_json::Value = serde_json::from_str(&report.format_sarif()).unwrap();
+        let results = &sarif["runs"][0]["results"];
+
+        let span = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(span["artifactLocation"]["uri"], "src/parser.rs");
+        assert_eq!(span["region"]["startLine"], 10);
+        assert_eq!(span["region"]["endLine"], 42);
+
+        // Line 0 is a whole-file finding: no region.
+        let whole_file = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(whole_file["artifactLocation"]["uri"], "src/big.rs");
+        assert!(whole_file.get("region").is_none());
+
+        assert!(results[2].get("locations").is_none());
+    }
 }
@@ -1,12 +1,25 @@
 //! Output formatting utilities.
 //!
-//! Provides text formatting via the `OutputFormatter` trait.
+//! Provides text formatting via the `OutputFormatter` trait, plus CSV/TSV/markdown
+//! table rendering (see [`table`]) and terminal-width/pager helpers (see [`terminal`]).
+//! Reports render into any `io::Write`; [`sink`] covers atomic `--output <file>` writes,
+//! and [`template`] renders a report's JSON through a user-provided template.
+//! Long-running operations report through [`progress::Progress`].
 //! JSON/jq/jsonl/schema output is handled by server-less at the CLI macro level.
 
 pub mod diagnostics;
+pub mod progress;
+pub mod sink;
+pub mod table;
+pub mod template;
+pub mod terminal;
+
+pub use table::{OutputFormat, Table};
+pub use template::TemplateError;
 
 use serde::{Deserialize, Serialize};
-use std::io::IsTerminal;
+use std::io::{self, IsTerminal};
+use std::path::Path;
 use std::str::FromStr;
 
 /// Color output mode.
@@ -46,6 +59,7 @@ impl FromStr for ColorMode {
 /// enabled = true       # auto-enable when TTY (default: auto)
 /// colors = "auto"      # "auto", "always", or "never"
 /// highlight = true     # syntax highlighting on signatures
+/// pager = true         # page long pretty reports through $PAGER (default: false)
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize, Default, schemars::JsonSchema)]
 #[serde(default)]
@@ -56,6 +70,8 @@ pub struct PrettyConfig {
     pub colors: Option<ColorMode>,
     /// Enable syntax highlighting. Default: true
     pub highlight: Option<bool>,
+    /// Page long pretty output through `$PAGER` (`less -R` if unset). Default: false
+    pub pager: Option<bool>,
 }
 
 impl PrettyConfig {
@@ -85,6 +101,11 @@ impl PrettyConfig {
     pub fn highlight(&self) -> bool {
         self.highlight.unwrap_or(true)
     }
+
+    /// Should long pretty output go through the pager?
+    pub fn pager(&self) -> bool {
+        self.pager.unwrap_or(false)
+    }
 }
 
 /// Trait for types that can format output in multiple formats.
@@ -100,6 +121,77 @@ pub trait OutputFormatter: Serialize + schemars::JsonSchema {
     fn format_pretty(&self) -> String {
         self.format_text()
     }
+
+    /// The report as a single table, for CSV/TSV/markdown output.
+    ///
+    /// Table-shaped reports (per-tool stats, per-repo rows, ...) override this to
+    /// pick their columns. The default `None` makes [`format_as`](Self::format_as)
+    /// flatten the JSON form instead (see [`Table::from_json`]).
+    fn format_table(&self) -> Option<Table> {
+        None
+    }
+
+    /// Format in the given output format.
+    ///
+    /// Template failures (missing file, syntax errors) come back as the error
+    /// message; use [`try_format_as`](Self::try_format_as) to handle them.
+    fn format_as(&self, format: &OutputFormat) -> String {
+        self.try_format_as(format).unwrap_or_else(|e| e.to_string())
+    }
+
+    /// Format in the given output format, failing if a template can't be rendered.
+    fn try_format_as(&self, format: &OutputFormat) -> Result<String, TemplateError> {
+        let json = || serde_json::to_value(self).unwrap_or_default();
+        Ok(match format {
+            OutputFormat::Text => self.format_text(),
+            OutputFormat::Pretty => self.format_pretty(),
+            OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Markdown => self
+                .format_table()
+                .unwrap_or_else(|| Table::from_json(&json()))
+                .render(format),
+            OutputFormat::Template { path } => template::render_file(path, &json())?,
+        })
+    }
+
+    /// Write the report in `format` to `out`, followed by a newline.
+    fn write(&self, format: &OutputFormat, out: &mut dyn io::Write) -> io::Result<()> {
+        let text = self.try_format_as(format).map_err(io::Error::other)?;
+        writeln!(out, "{}", text)
+    }
+
+    /// Write the report to stdout.
+    fn print(&self, format: &OutputFormat) -> io::Result<()> {
+        self.write(format, &mut io::stdout().lock())
+    }
+
+    /// Write the report to `path`, replacing it atomically (see [`sink::AtomicFile`]).
+    fn write_to_file(&self, format: &OutputFormat, path: &Path) -> io::Result<()> {
+        let mut file = sink::AtomicFile::create(path)?;
+        self.write(format, &mut file)?;
+        file.commit()
+    }
+
+    /// Format like [`try_format_as`](Self::try_format_as), paging `Pretty` output when
+    /// `pager` is set and the report is taller than the terminal (see [`terminal::page`]).
+    ///
+    /// Returns what is left for the caller to print: empty if the pager showed it.
+    fn format_paged(&self, format: &OutputFormat, pager: bool) -> Result<String, TemplateError> {
+        if *format == OutputFormat::Pretty {
+            return Ok(self.format_pretty_paged(pager));
+        }
+        self.try_format_as(format)
+    }
+
+    /// [`format_pretty`](Self::format_pretty), paged when `pager` is set (see
+    /// [`format_paged`](Self::format_paged)).
+    fn format_pretty_paged(&self, pager: bool) -> String {
+        let text = self.format_pretty();
+        if pager && terminal::page(&text) {
+            String::new()
+        } else {
+            text
+        }
+    }
 }
 
 /// Map a [`RiskTier`](normalize_rank::ranked::RiskTier) to its house-style
@@ -208,6 +300,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_as_falls_back_to_json_flattening() {
+        let output = TestOutput {
+            name: "reads".to_string(),
+            count: 3,
+        };
+        assert_eq!(output.format_as(&OutputFormat::Text), "reads: 3");
+        assert_eq!(output.format_as(&OutputFormat::Csv), "count,name\n3,reads");
+        assert_eq!(
+            output.format_as(&OutputFormat::Tsv),
+            "count\tname\n3\treads"
+        );
+    }
+
+    #[test]
+    fn test_write_to_sink_and_file() {
+        let output = TestOutput {
+            name: "edits".to_string(),
+            count: 2,
+        };
+        let mut buf = Vec::new();
+        output.write(&OutputFormat::Csv, &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "count,name\n2,edits\n");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.txt");
+        output.write_to_file(&OutputFormat::Text, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "edits: 2\n");
+
+        let template = dir.path().join("report.j2");
+        std::fs::write(&template, "{{ count }}x {{ name | upper }}").unwrap();
+        let format = OutputFormat::Template { path: template };
+        assert_eq!(output.format_as(&format), "2x EDITS");
+
+        let missing = OutputFormat::Template {
+            path: dir.path().join("missing.j2"),
+        };
+        let mut buf = Vec::new();
+        assert!(output.write(&missing, &mut buf).is_err());
+        assert!(buf.is_empty());
+    }
+
     #[test]
     fn test_pretty_config_use_colors() {
         // Always mode
@@ -0,0 +1,283 @@
+//! Progress reporting for long-running operations (indexing, rule runs,
+//! multi-session analysis).
+//!
+//! Producers report through the [`Progress`] trait and never decide how (or
+//! whether) progress is shown. [`reporter`] picks the display: an indicatif
+//! spinner/bar/percentage on a stderr terminal, or [`NoProgress`] when stderr is
+//! redirected or machine-readable output was requested ([`set_silent`]). Every
+//! implementation is `Send + Sync`, so a `&dyn Progress` can be shared with rayon
+//! workers; [`channel`] decouples workers from the display entirely.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Receiver of progress updates.
+pub trait Progress: Send + Sync {
+    /// Set the number of steps; `None` when unknown.
+    fn set_total(&self, total: Option<u64>);
+
+    /// Advance by `n` steps.
+    fn inc(&self, n: u64);
+
+    /// Replace the status message.
+    fn set_message(&self, message: &str);
+
+    /// The operation is done; clear any display.
+    fn finish(&self);
+}
+
+/// How a terminal reporter draws progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressKind {
+    /// Spinner with message and elapsed time, for unknown totals.
+    #[default]
+    Spinner,
+    /// Bar with `pos/len` counts.
+    Bar,
+    /// Percentage complete.
+    Percent,
+}
+
+static SILENT: AtomicBool = AtomicBool::new(false);
+
+/// Suppress all [`reporter`] output for the rest of the process, e.g. when the
+/// CLI was asked for `--json`/`--jsonl`/`--jq` output.
+pub fn set_silent(silent: bool) {
+    SILENT.store(silent, Ordering::Relaxed);
+}
+
+/// Whether [`set_silent`] is in effect.
+pub fn is_silent() -> bool {
+    SILENT.load(Ordering::Relaxed)
+}
+
+/// Reporter for an operation described by `message`: a terminal display of the
+/// given kind, or [`NoProgress`] when silenced or stderr is not a terminal.
+pub fn reporter(kind: ProgressKind, message: &str) -> Box<dyn Progress> {
+    if is_silent() || !std::io::stderr().is_terminal() {
+        return Box::new(NoProgress);
+    }
+    Box::new(TerminalProgress::new(kind, message))
+}
+
+/// Discards all updates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn set_total(&self, _total: Option<u64>) {}
+    fn inc(&self, _n: u64) {}
+    fn set_message(&self, _message: &str) {}
+    fn finish(&self) {}
+}
+
+/// Draws progress on stderr with indicatif. Colors follow `NO_COLOR`.
+pub struct TerminalProgress {
+    bar: indicatif::ProgressBar,
+}
+
+impl TerminalProgress {
+    pub fn new(kind: ProgressKind, message: &str) -> Self {
+        let bar =
+            indicatif::ProgressBar::with_draw_target(None, indicatif::ProgressDrawTarget::stderr());
+        let colors = std::env::var_os("NO_COLOR").is_none();
+        bar.set_style(style(kind, colors));
+        bar.set_message(message.to_string());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self { bar }
+    }
+}
+
+fn style(kind: ProgressKind, colors: bool) -> indicatif::ProgressStyle {
+    let spinner = if colors {
+        "{spinner:.cyan}"
+    } else {
+        "{spinner}"
+    };
+    let template = match kind {
+        ProgressKind::Spinner => format!("{spinner} {{msg}} [{{elapsed_precise}}]"),
+        ProgressKind::Bar => {
+            let width = crate::terminal::bar_width(30);
+            let bar = if colors {
+                format!("{{bar:{width}.cyan/dim}}")
+            } else {
+                format!("{{bar:{width}}}")
+            };
+            format!("{spinner} {{msg}} [{bar}] {{pos}}/{{len}} [{{elapsed_precise}}]")
+        }
+        ProgressKind::Percent => {
+            format!("{spinner} {{msg}} {{percent:>3}}% [{{elapsed_precise}}]")
+        }
+    };
+    indicatif::ProgressStyle::with_template(&template)
+        .unwrap_or_else(|_| indicatif::ProgressStyle::default_spinner())
+        .progress_chars("##-")
+}
+
+impl Progress for TerminalProgress {
+    fn set_total(&self, total: Option<u64>) {
+        match total {
+            Some(total) => self.bar.set_length(total),
+            None => self.bar.unset_length(),
+        }
+    }
+
+    fn inc(&self, n: u64) {
+        self.bar.inc(n);
+    }
+
+    fn set_message(&self, message: &str) {
+        self.bar.set_message(message.to_string());
+    }
+
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// One update sent through a [`channel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+    Total(Option<u64>),
+    Inc(u64),
+    Message(String),
+    Finish,
+}
+
+impl ProgressEvent {
+    /// Apply this update to `target`.
+    pub fn apply(&self, target: &dyn Progress) {
+        match self {
+            Self::Total(total) => target.set_total(*total),
+            Self::Inc(n) => target.inc(*n),
+            Self::Message(message) => target.set_message(message),
+            Self::Finish => target.finish(),
+        }
+    }
+}
+
+/// Create a progress channel: the sender implements [`Progress`] and can be
+/// cloned into workers; the receiver replays updates on another thread.
+pub fn channel() -> (ChannelProgress, ProgressEvents) {
+    let (tx, rx) = mpsc::channel();
+    (ChannelProgress { tx }, ProgressEvents { rx })
+}
+
+/// Sending half of a [`channel`]. Updates are dropped once the receiver is gone.
+#[derive(Debug, Clone)]
+pub struct ChannelProgress {
+    tx: mpsc::Sender<ProgressEvent>,
+}
+
+impl ChannelProgress {
+    fn send(&self, event: ProgressEvent) {
+        let _ = self.tx.send(event);
+    }
+}
+
+impl Progress for ChannelProgress {
+    fn set_total(&self, total: Option<u64>) {
+        self.send(ProgressEvent::Total(total));
+    }
+
+    fn inc(&self, n: u64) {
+        self.send(ProgressEvent::Inc(n));
+    }
+
+    fn set_message(&self, message: &str) {
+        self.send(ProgressEvent::Message(message.to_string()));
+    }
+
+    fn finish(&self) {
+        self.send(ProgressEvent::Finish);
+    }
+}
+
+/// Receiving half of a [`channel`].
+#[derive(Debug)]
+pub struct ProgressEvents {
+    rx: mpsc::Receiver<ProgressEvent>,
+}
+
+impl ProgressEvents {
+    /// Apply updates to `target` until every sender has been dropped.
+    pub fn forward_to(self, target: &dyn Progress) {
+        for event in self.rx {
+            event.apply(target);
+        }
+    }
+
+    /// Apply the updates received so far without blocking.
+    pub fn drain_to(&self, target: &dyn Progress) {
+        for event in self.rx.try_iter() {
+            event.apply(target);
+        }
+    }
+}
+
+impl IntoIterator for ProgressEvents {
+    type Item = ProgressEvent;
+    type IntoIter = mpsc::IntoIter<ProgressEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rx.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+    use std::sync::atomic::AtomicU64;
+
+    #[derive(Default)]
+    struct Counter {
+        total: AtomicU64,
+        done: AtomicU64,
+        finished: AtomicBool,
+    }
+
+    impl Progress for Counter {
+        fn set_total(&self, total: Option<u64>) {
+            self.total.store(total.unwrap_or(0), Ordering::Relaxed);
+        }
+        fn inc(&self, n: u64) {
+            self.done.fetch_add(n, Ordering::Relaxed);
+        }
+        fn set_message(&self, _message: &str) {}
+        fn finish(&self) {
+            self.finished.store(true, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_channel_collects_updates_from_rayon_workers() {
+        let (progress, events) = channel();
+        let counter = Counter::default();
+        std::thread::scope(|s| {
+            s.spawn(|| events.forward_to(&counter));
+            progress.set_total(Some(100));
+            (0..100u64)
+                .into_par_iter()
+                .for_each_with(progress, |p, _| p.inc(1));
+        });
+        assert_eq!(counter.total.load(Ordering::Relaxed), 100);
+        assert_eq!(counter.done.load(Ordering::Relaxed), 100);
+    }
+
+    #[test]
+    fn test_drain_to_does_not_block() {
+        let (progress, events) = channel();
+        progress.set_message("scanning");
+        progress.inc(2);
+        progress.finish();
+        let counter = Counter::default();
+        events.drain_to(&counter);
+        assert_eq!(counter.done.load(Ordering::Relaxed), 2);
+        assert!(counter.finished.load(Ordering::Relaxed));
+        drop(progress);
+        assert_eq!(events.into_iter().count(), 0);
+    }
+}
@@ -0,0 +1,203 @@
+//! Output destinations for rendered reports.
+//!
+//! [`OutputFormatter::write`](crate::OutputFormatter::write) renders into any
+//! `io::Write`; this module provides the file side of `--output <file>`, where a
+//! half-written report must never replace a good one, and records the
+//! `--json`/`--jq` format such a file should use.
+
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tempfile::NamedTempFile;
+
+/// A file that only appears at its destination once fully written.
+///
+/// Writes go to a uniquely named temporary file in the destination's directory
+/// (so the final rename stays on one filesystem). [`commit`](Self::commit)
+/// flushes it to disk and renames it over the destination; dropping without
+/// committing removes it and leaves any existing file untouched.
+pub struct AtomicFile {
+    path: PathBuf,
+    file: NamedTempFile,
+}
+
+impl AtomicFile {
+    /// Start writing a replacement for `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if path.file_name().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("not a file path: {}", path.display()),
+            ));
+        }
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let file = tempfile::Builder::new()
+            .prefix(".normalize-output")
+            .tempfile_in(dir)?;
+        Ok(Self { path, file })
+    }
+
+    /// Destination path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flush to disk and move the temporary file into place.
+    pub fn commit(self) -> io::Result<()> {
+        self.file.as_file().sync_all()?;
+        self.file.persist(&self.path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Write `contents` to `path` atomically (see [`AtomicFile`]).
+pub fn write_atomic(path: impl AsRef<Path>, contents: &[u8]) -> io::Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents)?;
+    file.commit()
+}
+
+/// Machine-readable output requested on the command line (`--json`,
+/// `--jsonl`, `--jq`).
+///
+/// server-less prints it straight to stdout without consulting the command, so
+/// commands that send their report to `--output` look it up here
+/// ([`machine_format`]) to write the same JSON to the file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MachineFormat {
+    /// `--jsonl`: one line per array element
+    pub lines: bool,
+    /// `--jq` filter, after saved-filter expansion
+    pub jq: Option<String>,
+}
+
+static MACHINE_FORMAT: OnceLock<Option<MachineFormat>> = OnceLock::new();
+
+impl MachineFormat {
+    /// The machine format requested by a command line, if any.
+    ///
+    /// Arguments after `--` are operands, not flags.
+    pub fn from_args(args: &[OsString]) -> Option<Self> {
+        let mut format: Option<Self> = None;
+        let mut args = args.iter().map(|a| a.to_str());
+        while let Some(arg) = args.next() {
+            match arg {
+                Some("--") => break,
+                Some("--json") => {
+                    format.get_or_insert_default();
+                }
+                Some("--jsonl") => format.get_or_insert_default().lines = true,
+                Some("--jq") => {
+                    format.get_or_insert_default().jq = args.next().flatten().map(String::from);
+                }
+                Some(arg) => {
+                    if let Some(filter) = arg.strip_prefix("--jq=") {
+                        format.get_or_insert_default().jq = Some(filter.to_string());
+                    }
+                }
+                None => {}
+            }
+        }
+        format
+    }
+
+    /// Render `value` the way server-less prints it for this format.
+    pub fn render(&self, value: &impl serde::Serialize) -> io::Result<String> {
+        let value = serde_json::to_value(value).map_err(io::Error::other)?;
+        server_less::cli_format_output(value, self.lines, !self.lines, self.jq.as_deref())
+            .map_err(|e| io::Error::other(e.to_string()))
+    }
+}
+
+/// Record the machine format the CLI was invoked with; the first call wins.
+pub fn set_machine_format(format: Option<MachineFormat>) {
+    let _ = MACHINE_FORMAT.set(format);
+}
+
+/// The machine format recorded by [`set_machine_format`], if any.
+pub fn machine_format() -> Option<&'static MachineFormat> {
+    MACHINE_FORMAT.get().and_then(Option::as_ref)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_replaces_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.csv");
+        std::fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_drop_without_commit_keeps_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.txt");
+        std::fs::write(&path, "old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"partial").unwrap();
+        drop(file);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_machine_format_from_args() {
+        let args = |a: &[&str]| -> Vec<OsString> { a.iter().map(OsString::from).collect() };
+        assert_eq!(MachineFormat::from_args(&args(&["normalize", "x"])), None);
+        assert_eq!(
+            MachineFormat::from_args(&args(&["normalize", "--json"])),
+            Some(MachineFormat::default())
+        );
+        assert_eq!(
+            MachineFormat::from_args(&args(&["normalize", "--jq", ".a", "--jsonl"])),
+            Some(MachineFormat {
+                lines: true,
+                jq: Some(".a".to_string()),
+            })
+        );
+        assert_eq!(
+            MachineFormat::from_args(&args(&["normalize", "--jq=.b"])).and_then(|f| f.jq),
+            Some(".b".to_string())
+        );
+        assert_eq!(
+            MachineFormat::from_args(&args(&["normalize", "--", "--json"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_machine_format_render() {
+        let value = serde_json::json!({"a": [1, 2]});
+        assert_eq!(
+            MachineFormat::default().render(&value).unwrap(),
+            r#"{"a":[1,2]}"#
+        );
+        let jq = MachineFormat {
+            lines: false,
+            jq: Some(".a[1]".to_string()),
+        };
+        assert_eq!(jq.render(&value).unwrap(), "2");
+    }
+}
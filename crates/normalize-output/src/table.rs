@@ -0,0 +1,379 @@
+//! Tabular output: CSV, TSV and markdown rendering for table-shaped reports.
+//!
+//! Reports opt in by returning a [`Table`] from
+//! [`OutputFormatter::format_table`](crate::OutputFormatter::format_table). Reports
+//! that don't are flattened from their JSON form by [`Table::from_json`], so every
+//! command can be piped into a spreadsheet, if not always with ideal columns.
+
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// How a report is rendered as text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Minimal text (`format_text`, the default).
+    #[default]
+    Text,
+    /// Human-friendly text with colors (`format_pretty`).
+    Pretty,
+    /// Comma-separated values with RFC 4180 quoting.
+    Csv,
+    /// Tab-separated values.
+    Tsv,
+    /// GitHub-flavored markdown table.
+    Markdown,
+    /// The report's JSON form rendered through a user template (see [`crate::template`]).
+    Template { path: PathBuf },
+}
+
+impl OutputFormat {
+    /// Whether this format renders a [`Table`] rather than the report's own text.
+    pub fn is_tabular(&self) -> bool {
+        matches!(self, Self::Csv | Self::Tsv | Self::Markdown)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "pretty" => Ok(Self::Pretty),
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => match s.strip_prefix("template:") {
+                Some(path) if !path.is_empty() => Ok(Self::Template { path: path.into() }),
+                _ => Err(format!(
+                    "unknown output format `{s}`; expected text, pretty, csv, tsv, markdown, or template:<path>"
+                )),
+            },
+        }
+    }
+}
+
+/// Narrowest a column is shrunk to when fitting [`Table::to_aligned`] output.
+pub const MIN_COLUMN_WIDTH: usize = 4;
+
+/// A header row plus data rows of already-formatted cells.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create an empty table with the given column headers.
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a data row. Rows shorter than the header are padded with empty cells.
+    pub fn push_row<S: Into<String>>(&mut self, row: impl IntoIterator<Item = S>) {
+        let mut row: Vec<String> = row.into_iter().map(Into::into).collect();
+        if row.len() < self.headers.len() {
+            row.resize(self.headers.len(), String::new());
+        }
+        self.rows.push(row);
+    }
+
+    /// Render in a tabular format. `Text` (and `Template`, which has no tabular
+    /// form) render as markdown; `Pretty` as aligned columns fitted to the terminal
+    /// (see [`to_aligned`](Self::to_aligned)).
+    pub fn render(&self, format: &OutputFormat) -> String {
+        match format {
+            OutputFormat::Csv => self.to_csv(),
+            OutputFormat::Tsv => self.to_tsv(),
+            OutputFormat::Pretty => self.to_aligned(crate::terminal::terminal_width()),
+            OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Template { .. } => {
+                self.to_markdown()
+            }
+        }
+    }
+
+    /// Render as CSV. Cells containing commas, quotes or line breaks are quoted,
+    /// with embedded quotes doubled (RFC 4180).
+    pub fn to_csv(&self) -> String {
+        self.join_lines(",", |cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+    }
+
+    /// Render as TSV. TSV has no quoting, so tabs and line breaks inside cells are
+    /// escaped as `\t`, `\n` and `\r` (backslashes as `\\`).
+    pub fn to_tsv(&self) -> String {
+        self.join_lines("\t", |cell| {
+            cell.replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        })
+    }
+
+    /// Render as a markdown table. Pipes are escaped and line breaks become spaces.
+    pub fn to_markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|").replace(['\n', '\r'], " ");
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+        let header: Vec<String> = self.headers.iter().map(|h| escape(h)).collect();
+        lines.push(format!("| {} |", header.join(" | ")));
+        lines.push(format!("|{}|", vec!["---"; self.headers.len()].join("|")));
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|c| escape(c)).collect();
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+        lines.join("\n")
+    }
+
+    /// Render as space-aligned columns at most `width` characters wide.
+    ///
+    /// When the natural layout is too wide, the widest column is narrowed first
+    /// (down to [`MIN_COLUMN_WIDTH`]) and its overflowing cells end in `…`.
+    pub fn to_aligned(&self, width: usize) -> String {
+        const GAP: usize = 2;
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+        let total =
+            |widths: &[usize]| widths.iter().sum::<usize>() + GAP * widths.len().saturating_sub(1);
+        while total(&widths) > width {
+            let Some(widest) = widths
+                .iter_mut()
+                .filter(|w| **w > MIN_COLUMN_WIDTH)
+                .max_by_key(|w| **w)
+            else {
+                break;
+            };
+            *widest -= 1;
+        }
+
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &w)| format!("{:<w$}", crate::terminal::truncate(cell, w)))
+                .collect();
+            padded.join(&" ".repeat(GAP)).trim_end().to_string()
+        };
+        let rule: Vec<String> = widths.iter().map(|&w| "─".repeat(w)).collect();
+        std::iter::once(line(&self.headers))
+            .chain(std::iter::once(rule.join(&" ".repeat(GAP))))
+            .chain(self.rows.iter().map(|row| line(row)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn join_lines(&self, separator: &str, escape: impl Fn(&str) -> String) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| escape(cell))
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Flatten a report's JSON form into a table.
+    ///
+    /// - An array of objects becomes one row per element.
+    /// - An object with array-of-objects fields uses the first such field (by key
+    ///   order), so `{"total": 3, "items": [...]}` tabulates `items`.
+    /// - Anything else becomes a single row.
+    ///
+    /// Nested objects flatten into dotted column names (`tokens.input`); nested arrays
+    /// are written as compact JSON. Columns appear in first-seen order.
+    pub fn from_json(value: &Value) -> Self {
+        let records: Vec<&Value> = match value {
+            Value::Array(items) => items.iter().collect(),
+            Value::Object(map) => match map.values().find(|v| is_array_of_objects(v)) {
+                Some(Value::Array(items)) => items.iter().collect(),
+                _ => vec![value],
+            },
+            _ => vec![value],
+        };
+
+        let flattened: Vec<Vec<(String, String)>> = records
+            .into_iter()
+            .map(|record| {
+                let mut cells = Vec::new();
+                flatten_into(record, "", &mut cells);
+                cells
+            })
+            .collect();
+
+        let mut headers: Vec<String> = Vec::new();
+        for cells in &flattened {
+            for (key, _) in cells {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+        }
+
+        let mut table = Table::new(headers);
+        for cells in flattened {
+            let row: Vec<String> = table
+                .headers
+                .iter()
+                .map(|header| {
+                    cells
+                        .iter()
+                        .find(|(key, _)| key == header)
+                        .map(|(_, cell)| cell.clone())
+                        .unwrap_or_default()
+                })
+                .collect();
+            table.rows.push(row);
+        }
+        table
+    }
+}
+
+fn is_array_of_objects(value: &Value) -> bool {
+    matches!(value, Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object))
+}
+
+/// Flatten `value` into `(column, cell)` pairs, prefixing nested keys with `prefix.`.
+fn flatten_into(value: &Value, prefix: &str, cells: &mut Vec<(String, String)>) {
+    let column = |prefix: &str| {
+        if prefix.is_empty() {
+            "value".to_string()
+        } else {
+            prefix.to_string()
+        }
+    };
+    match value {
+        Value::Object(map) => flatten_object(map, prefix, cells),
+        Value::Null => cells.push((column(prefix), String::new())),
+        Value::String(s) => cells.push((column(prefix), s.clone())),
+        Value::Array(_) => cells.push((column(prefix), value.to_string())),
+        Value::Bool(_) | Value::Number(_) => cells.push((column(prefix), value.to_string())),
+    }
+}
+
+fn flatten_object(map: &Map<String, Value>, prefix: &str, cells: &mut Vec<(String, String)>) {
+    for (key, value) in map {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        flatten_into(value, &key, cells);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Table {
+        let mut table = Table::new(["tool", "calls", "note"]);
+        table.push_row(["Read", "12", "plain"]);
+        table.push_row(["Bash", "3", "has, comma and \"quotes\""]);
+        table.push_row(["Edit", "1"]);
+        table
+    }
+
+    #[test]
+    fn test_to_csv_quotes_special_cells() {
+        assert_eq!(
+            sample().to_csv(),
+            "tool,calls,note\nRead,12,plain\nBash,3,\"has, comma and \"\"quotes\"\"\"\nEdit,1,"
+        );
+    }
+
+    #[test]
+    fn test_to_tsv_escapes_tabs_and_newlines() {
+        let mut table = Table::new(["a", "b"]);
+        table.push_row(["x\ty", "line\nbreak"]);
+        assert_eq!(table.to_tsv(), "a\tb\nx\\ty\tline\\nbreak");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut table = Table::new(["cmd", "count"]);
+        table.push_row(["a | b", "2"]);
+        assert_eq!(
+            table.to_markdown(),
+            "| cmd | count |\n|---|---|\n| a \\| b | 2 |"
+        );
+    }
+
+    #[test]
+    fn test_to_aligned_fits_width() {
+        let mut table = Table::new(["path", "lines"]);
+        table.push_row(["src/very/long/path/to/module.rs", "120"]);
+        table.push_row(["lib.rs", "8"]);
+        assert_eq!(
+            table.to_aligned(80),
+            "path                             lines\n\
+             ───────────────────────────────  ─────\n\
+             src/very/long/path/to/module.rs  120\n\
+             lib.rs                           8"
+        );
+        assert_eq!(
+            table.to_aligned(20),
+            "path           lines\n\
+             ─────────────  ─────\n\
+             src/very/lon…  120\n\
+             lib.rs         8"
+        );
+    }
+
+    #[test]
+    fn test_from_json_uses_record_array() {
+        let value = json!({
+            "total": 2,
+            "items": [
+                {"name": "a", "tokens": {"input": 1, "output": 2}},
+                {"name": "b", "tags": ["x", "y"], "cost": null},
+            ],
+        });
+        let table = Table::from_json(&value);
+        assert_eq!(
+            table.headers,
+            ["name", "tokens.input", "tokens.output", "cost", "tags"]
+        );
+        assert_eq!(table.rows[0], ["a", "1", "2", "", ""]);
+        assert_eq!(table.rows[1], ["b", "", "", "", "[\"x\",\"y\"]"]);
+    }
+
+    #[test]
+    fn test_from_json_single_row_fallback() {
+        let table = Table::from_json(&json!({"files": 3, "stats": {"lines": 10}}));
+        assert_eq!(table.headers, ["files", "stats.lines"]);
+        assert_eq!(table.rows, [["3", "10"]]);
+
+        let table = Table::from_json(&json!("just text"));
+        assert_eq!(table.headers, ["value"]);
+        assert_eq!(table.rows, [["just text"]]);
+    }
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("csv".parse::<OutputFormat>(), Ok(OutputFormat::Csv));
+        assert_eq!("md".parse::<OutputFormat>(), Ok(OutputFormat::Markdown));
+        assert_eq!(
+            "template:slack.j2".parse::<OutputFormat>(),
+            Ok(OutputFormat::Template {
+                path: "slack.j2".into()
+            })
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+        assert!("template:".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! User-provided Jinja-style templates over a report's JSON form.
+//!
+//! Any [`OutputFormatter`](crate::OutputFormatter) can be rendered through a
+//! template with [`OutputFormat::Template`](crate::OutputFormat::Template), so
+//! results can be shaped into Slack messages, PR comments or wiki markup without
+//! touching the Rust formatters. Templates use [minijinja] syntax.
+//!
+//! The template context is the report's serde JSON value: the fields of an
+//! object report are top-level variables, and the whole value is also bound to
+//! `report` (the only way to reach array-shaped reports). Templates in the same
+//! directory can be pulled in with `{% include %}` / `{% extends %}`.
+//!
+//! ```jinja
+//! *Session {{ session_id }}*: {{ tool_stats | length }} tools
+//! {% for name, tool in tool_stats | items %}
+//! - {{ name }}: {{ tool.calls }} calls
+//! {% endfor %}
+//! ```
+
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+
+/// A template could not be loaded or rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError(String);
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Render the template file at `path` against `value`.
+pub fn render_file(path: &Path, value: &Value) -> Result<String, TemplateError> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| TemplateError(format!("not a template file: {}", path.display())))?;
+    if !path.is_file() {
+        return Err(TemplateError(format!(
+            "template not found: {}",
+            path.display()
+        )));
+    }
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut env = environment();
+    env.set_loader(minijinja::path_loader(dir));
+    let template = env
+        .get_template(name)
+        .map_err(|e| template_error(path, e))?;
+    template
+        .render(context(value))
+        .map_err(|e| template_error(path, e))
+}
+
+/// Render template `source` against `value`.
+pub fn render_str(source: &str, value: &Value) -> Result<String, TemplateError> {
+    let env = environment();
+    env.render_str(source, context(value))
+        .map_err(|e| TemplateError(format!("template error: {e:#}")))
+}
+
+fn environment() -> minijinja::Environment<'static> {
+    let mut env = minijinja::Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env
+}
+
+/// Object fields at the top level, plus the whole value as `report`.
+fn context(value: &Value) -> Value {
+    let mut ctx = match value {
+        Value::Object(map) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    ctx.entry("report").or_insert_with(|| value.clone());
+    Value::Object(ctx)
+}
+
+fn template_error(path: &Path, e: minijinja::Error) -> TemplateError {
+    TemplateError(format!("template error in {}: {e:#}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_str_exposes_fields_and_report() {
+        let value = json!({"name": "stats", "rows": [{"tool": "Read"}, {"tool": "Edit"}]});
+        let out = render_str(
+            "{{ name }}:{% for r in rows %} {{ r.tool }}{% endfor %} ({{ report.rows | length }})",
+            &value,
+        )
+        .unwrap();
+        assert_eq!(out, "stats: Read Edit (2)");
+
+        let out = render_str("{{ report | length }} items", &json!([1, 2, 3])).unwrap();
+        assert_eq!(out, "3 items");
+    }
+
+    #[test]
+    fn test_render_file_supports_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("row.j2"), "<{{ item }}>").unwrap();
+        std::fs::write(
+            dir.path().join("main.j2"),
+            "{% for item in items %}{% include 'row.j2' %}{% endfor %}",
+        )
+        .unwrap();
+        let out = render_file(&dir.path().join("main.j2"), &json!({"items": ["a", "b"]})).unwrap();
+        assert_eq!(out, "<a><b>");
+    }
+
+    #[test]
+    fn test_render_errors_name_the_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.j2");
+        std::fs::write(&path, "{% for x in %}").unwrap();
+        let err = render_file(&path, &json!({})).unwrap_err();
+        assert!(err.to_string().contains("broken.j2"), "{err}");
+
+        let err = render_file(&dir.path().join("missing.j2"), &json!({})).unwrap_err();
+        assert!(err.to_string().starts_with("template not found"), "{err}");
+    }
+}
@@ -0,0 +1,127 @@
+//! Terminal-aware rendering: width detection for sizing bars and tables, and an
+//! opt-in pager for long pretty reports.
+//!
+//! Everything here degrades to fixed defaults when stdout is not a terminal, so
+//! piped and captured output (tests, snapshots, `| head`) stays stable.
+
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// Width assumed when stdout is not a terminal and `COLUMNS` is unset.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Height assumed when stdout is not a terminal and `LINES` is unset.
+pub const DEFAULT_HEIGHT: usize = 24;
+
+/// Terminal width in columns.
+///
+/// `COLUMNS` wins when set (so scripts and tests can pin it); otherwise the size
+/// of the terminal stdout is attached to; otherwise [`DEFAULT_WIDTH`].
+pub fn terminal_width() -> usize {
+    env_dimension("COLUMNS")
+        .or_else(|| terminal_size().map(|(_, cols)| cols))
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Terminal height in rows. Resolved like [`terminal_width`], from `LINES`.
+pub fn terminal_height() -> usize {
+    env_dimension("LINES")
+        .or_else(|| terminal_size().map(|(rows, _)| rows))
+        .unwrap_or(DEFAULT_HEIGHT)
+}
+
+fn env_dimension(var: &str) -> Option<usize> {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+}
+
+fn terminal_size() -> Option<(usize, usize)> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    console::Term::stdout()
+        .size_checked()
+        .map(|(rows, cols)| (rows as usize, cols as usize))
+}
+
+/// Width for a bar chart designed at `base` columns on an 80-column terminal,
+/// scaled to the current [`terminal_width`].
+pub fn bar_width(base: usize) -> usize {
+    scale_bar_width(base, terminal_width())
+}
+
+/// Scale `base` (sized for [`DEFAULT_WIDTH`]) to `columns`, clamped to half and
+/// double the base so bars stay readable on very narrow or very wide terminals.
+pub fn scale_bar_width(base: usize, columns: usize) -> usize {
+    let scaled = base * columns / DEFAULT_WIDTH;
+    scaled.clamp((base / 2).max(1), base * 2)
+}
+
+/// Send `text` through the user's pager if it is taller than the terminal.
+///
+/// Uses `$PAGER`, defaulting to `less -R` so ANSI colors survive. Returns `true`
+/// when the pager displayed the text; `false` (print it yourself) when stdout is
+/// not a terminal, the text fits on one screen, or the pager could not start.
+pub fn page(text: &str) -> bool {
+    if !std::io::stdout().is_terminal() || text.lines().count() < terminal_height() {
+        return false;
+    }
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+    let Ok(mut child) = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting the pager early closes the pipe; that's not an error.
+        let _ = stdin.write_all(text.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    }
+    child.wait().is_ok()
+}
+
+/// Shorten `text` to at most `width` characters, ending in `…` when cut.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut out: String = text.chars().take(width - 1).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_bar_width() {
+        assert_eq!(scale_bar_width(30, 80), 30);
+        assert_eq!(scale_bar_width(30, 120), 45);
+        assert_eq!(scale_bar_width(30, 400), 60);
+        assert_eq!(scale_bar_width(20, 20), 10);
+        assert_eq!(scale_bar_width(1, 10), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly", 7), "exactly");
+        assert_eq!(truncate("truncated", 5), "trun…");
+        assert_eq!(truncate("x", 0), "");
+    }
+}
@@ -14,23 +14,9 @@ pub struct IndexMeta {
     pub url: String,
 }
 
-/// Get base cache directory: ~/.cache/normalize
-fn cache_base() -> Option<PathBuf> {
-    let base = if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
-        PathBuf::from(cache)
-    } else if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home).join(".cache")
-    } else if let Ok(home) = std::env::var("USERPROFILE") {
-        PathBuf::from(home).join(".cache")
-    } else {
-        return None;
-    };
-    Some(base.join("normalize"))
-}
-
 /// Get index cache directory: ~/.cache/normalize/indices
 fn index_cache_dir() -> Option<PathBuf> {
-    Some(cache_base()?.join("indices"))
+    normalize_cache::cache_path("indices")
 }
 
 /// Generate a safe cache key from a URL.
@@ -56,13 +42,14 @@ fn index_paths(ecosystem: &str, name: &str) -> Option<(PathBuf, PathBuf)> {
 /// Read index metadata (for staleness check).
 pub fn read_index_meta(ecosystem: &str, name: &str) -> Option<IndexMeta> {
     let (_, meta_path) = index_paths(ecosystem, name)?;
-    let content = fs::read_to_string(&meta_path).ok()?;
-    serde_json::from_str(&content).ok()
+    normalize_cache::read_or_discard(&meta_path, |bytes| serde_json::from_slice(bytes).ok())
 }
 
 /// Read cached index data.
 pub fn read_index(ecosystem: &str, name: &str) -> Option<Vec<u8>> {
     let (data_path, _) = index_paths(ecosystem, name)?;
+    // Wait out a concurrent `write_index` so data and metadata match.
+    let _lock = normalize_cache::CacheLock::shared(&data_path).ok()?;
     fs::read(&data_path).ok()
 }
 
@@ -95,13 +82,12 @@ pub fn write_index(
         return;
     };
 
-    // Create directory if needed
-    if let Some(parent) = data_path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-
-    // Write data
-    if fs::write(&data_path, data).is_err() {
+    // Data and metadata are replaced together under the entry's lock, each
+    // atomically, so concurrent fetches never pair new data with old headers.
+    let Ok(_lock) = normalize_cache::CacheLock::exclusive(&data_path) else {
+        return;
+    };
+    if normalize_cache::write_atomic(&data_path, data).is_err() {
         return;
     }
 
@@ -119,7 +105,7 @@ pub fn write_index(
     };
 
     if let Ok(json) = serde_json::to_string_pretty(&meta) {
-        let _ = fs::write(&meta_path, json);
+        let _ = normalize_cache::write_atomic(&meta_path, json);
     }
 }
 
@@ -1102,6 +1102,7 @@ pub fn build_ratchet_report(
                 sources_run: vec!["ratchet".into()],
                 tool_errors: vec![],
                 daemon_cached: false,
+                metrics: vec![],
             };
         }
     };
@@ -1154,6 +1155,7 @@ pub fn build_ratchet_report(
         sources_run: vec!["ratchet".into()],
         tool_errors: vec![],
         daemon_cached: false,
+        metrics: vec![],
     }
 }
 
@@ -128,6 +128,7 @@ pub async fn plan_add_parameter(
         plan: RefactoringPlan {
             operation: "add_parameter".to_string(),
             edits,
+            deletions: vec![],
             warnings,
         },
         call_sites_updated,
@@ -467,6 +467,7 @@ pub async fn plan_extract_function(
             new_content,
             description: format!("extract function '{}'", function_name),
         }],
+        deletions: vec![],
         warnings: warnings.iter().map(|w| w.to_string_lossy()).collect(),
     };
 
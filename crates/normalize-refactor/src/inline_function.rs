@@ -120,6 +120,7 @@ pub fn plan_inline_function(
             new_content: final_content,
             description: format!("inline {function_name}"),
         }],
+        deletions: vec![],
         warnings: vec![],
     };
 
@@ -175,6 +175,7 @@ pub fn plan_inline_variable(
             new_content,
             description: format!("inline variable '{}'", var_name),
         }],
+        deletions: vec![],
         warnings,
     };
 
@@ -178,6 +178,7 @@ pub fn plan_introduce_variable(
             new_content,
             description: format!("introduce variable '{}'", name),
         }],
+        deletions: vec![],
         warnings: vec![],
     };
 
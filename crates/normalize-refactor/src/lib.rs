@@ -2,7 +2,7 @@
 //!
 //! Three layers:
 //! - **Actions** (`actions.rs`): Pure query and mutation primitives
-//! - **Recipes** (`rename.rs`, future: `move.rs`, `extract.rs`): Compositions of actions
+//! - **Recipes** (`rename.rs`, `move_item.rs`, `move_file.rs`, ...): Compositions of actions
 //! - **Executor** (`RefactoringExecutor`): Shared apply/dry-run/shadow logic
 
 pub mod actions;
@@ -11,12 +11,14 @@ pub mod extract_function;
 pub mod inline_function;
 pub mod inline_variable;
 pub mod introduce_variable;
+pub mod move_file;
 pub mod move_item;
 pub mod refactor_query;
 pub mod rename;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use normalize_edit::{EditPlan, TextEdit};
 use normalize_shadow::{EditInfo, Shadow};
 
 /// A planned edit to a single file (not yet applied).
@@ -31,6 +33,8 @@ pub struct PlannedEdit {
 pub struct RefactoringPlan {
     pub operation: String,
     pub edits: Vec<PlannedEdit>,
+    /// Files removed once the edits are written (e.g. the old path of a moved file).
+    pub deletions: Vec<PathBuf>,
     pub warnings: Vec<String>,
 }
 
@@ -72,7 +76,7 @@ pub struct ImportRef {
     pub confidence: &'static str,
 }
 
-/// Executes a `RefactoringPlan`: writes files, manages shadow snapshots.
+/// Executes a `RefactoringPlan`: writes files atomically, manages shadow snapshots.
 pub struct RefactoringExecutor {
     pub root: PathBuf,
     pub dry_run: bool,
@@ -80,18 +84,90 @@ pub struct RefactoringExecutor {
     pub message: Option<String>,
 }
 
+impl RefactoringPlan {
+    /// The plan as an [`EditPlan`], so it can be diffed or written atomically.
+    ///
+    /// Edits to the same file must chain (each starting from the previous
+    /// one's result), and every file must still have the content it was
+    /// planned against.
+    pub fn to_edit_plan(&self) -> Result<EditPlan, String> {
+        // (file, content it was planned against, final content, description)
+        let mut files: Vec<(&Path, &str, &str, &str)> = Vec::new();
+        for edit in &self.edits {
+            match files.iter_mut().find(|(file, ..)| *file == edit.file) {
+                Some((_, _, new_content, _)) if *new_content == edit.original => {
+                    *new_content = &edit.new_content;
+                }
+                Some((file, ..)) => {
+                    return Err(format!(
+                        "Conflicting edits in {}: {} doesn't build on the previous edit",
+                        file.display(),
+                        edit.description
+                    ));
+                }
+                None => files.push((
+                    &edit.file,
+                    &edit.original,
+                    &edit.new_content,
+                    &edit.description,
+                )),
+            }
+        }
+
+        let mut plan = EditPlan::new();
+        for (file, original, new_content, description) in files {
+            if plan.original(file)? != original {
+                return Err(format!(
+                    "{} changed on disk since the {} was planned",
+                    file.display(),
+                    self.operation
+                ));
+            }
+            plan.add(
+                file,
+                TextEdit::new(0, original.len(), new_content),
+                description,
+            )?;
+        }
+        for file in &self.deletions {
+            plan.remove_file(file)?;
+        }
+        Ok(plan)
+    }
+}
+
 impl RefactoringExecutor {
     /// Apply the plan. On dry-run, returns the list of files that *would* change.
-    /// On real run, writes files and records shadow history.
+    /// On real run, writes every file (or none, on error) through an
+    /// [`EditPlan`] and records shadow history.
     pub fn apply(&self, plan: &RefactoringPlan) -> Result<Vec<String>, String> {
-        if plan.edits.is_empty() {
+        if plan.edits.is_empty() && plan.deletions.is_empty() {
             return Ok(vec![]);
         }
 
-        let abs_paths: Vec<PathBuf> = plan.edits.iter().map(|e| e.file.clone()).collect();
+        let abs_paths: Vec<PathBuf> = plan
+            .edits
+            .iter()
+            .map(|e| e.file.clone())
+            .chain(plan.deletions.iter().cloned())
+            .collect();
+        let mut modified: Vec<String> = vec![];
+        for path in &abs_paths {
+            let rel_path = self.rel_path(path);
+            if !modified.contains(&rel_path) {
+                modified.push(rel_path);
+            }
+        }
+
+        let edit_plan = plan
+            .to_edit_plan()
+            .map_err(|e| format!("{} failed: {}", plan.operation, e))?;
+        if self.dry_run {
+            return Ok(modified);
+        }
 
         // Shadow: snapshot before
-        if !self.dry_run && self.shadow_enabled {
+        if self.shadow_enabled {
             let shadow = Shadow::new(&self.root);
             if let Err(e) =
                 shadow.before_edit(&abs_paths.iter().map(|p| p.as_path()).collect::<Vec<_>>())
@@ -100,34 +176,12 @@ impl RefactoringExecutor {
             }
         }
 
-        let mut modified: Vec<String> = vec![];
-
-        for edit in &plan.edits {
-            let rel_path = edit
-                .file
-                .strip_prefix(&self.root)
-                .unwrap_or(&edit.file)
-                .to_string_lossy()
-                .to_string();
-
-            if self.dry_run {
-                if !modified.contains(&rel_path) {
-                    modified.push(rel_path);
-                }
-            } else {
-                match std::fs::write(&edit.file, &edit.new_content) {
-                    Ok(_) => {
-                        if !modified.contains(&rel_path) {
-                            modified.push(rel_path);
-                        }
-                    }
-                    Err(e) => eprintln!("error writing {}: {}", rel_path, e),
-                }
-            }
-        }
+        edit_plan
+            .apply()
+            .map_err(|e| format!("{} failed: {} (no files were changed)", plan.operation, e))?;
 
         // Shadow: commit after
-        if !self.dry_run && self.shadow_enabled && !modified.is_empty() {
+        if self.shadow_enabled {
             let shadow = Shadow::new(&self.root);
             let info = EditInfo {
                 operation: plan.operation.clone(),
@@ -147,6 +201,13 @@ impl RefactoringExecutor {
 
         Ok(modified)
     }
+
+    fn rel_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +235,7 @@ mod tests {
                 new_content: "modified".to_string(),
                 description: "test edit".to_string(),
             }],
+            deletions: vec![],
             warnings: vec![],
         };
 
@@ -204,6 +266,7 @@ mod tests {
                 new_content: "modified".to_string(),
                 description: "test edit".to_string(),
             }],
+            deletions: vec![],
             warnings: vec![],
         };
 
@@ -241,6 +304,7 @@ mod tests {
                     description: "edit 2".to_string(),
                 },
             ],
+            deletions: vec![],
             warnings: vec![],
         };
 
@@ -249,6 +313,114 @@ mod tests {
         assert_eq!(std::fs::read_to_string(&file).unwrap(), "step2");
     }
 
+    #[test]
+    fn failed_write_skips_deletions_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old.rs");
+        std::fs::write(&old, "content").unwrap();
+        // A directory in the way makes the write fail.
+        let new = dir.path().join("new.rs");
+        std::fs::create_dir(&new).unwrap();
+
+        let executor = RefactoringExecutor {
+            root: dir.path().to_path_buf(),
+            dry_run: false,
+            shadow_enabled: false,
+            message: None,
+        };
+
+        let plan = RefactoringPlan {
+            operation: "move".to_string(),
+            edits: vec![PlannedEdit {
+                file: new,
+                original: String::new(),
+                new_content: "content".to_string(),
+                description: "move".to_string(),
+            }],
+            deletions: vec![old.clone()],
+            warnings: vec![],
+        };
+
+        let err = executor.apply(&plan).unwrap_err();
+        assert!(err.contains("new.rs"), "{err}");
+        assert!(old.exists());
+    }
+
+    #[test]
+    fn executor_writes_all_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.rs");
+        let b = dir.path().join("b.rs");
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "changed since planning").unwrap();
+
+        let executor = RefactoringExecutor {
+            root: dir.path().to_path_buf(),
+            dry_run: false,
+            shadow_enabled: false,
+            message: None,
+        };
+
+        let plan = RefactoringPlan {
+            operation: "rename".to_string(),
+            edits: vec![
+                PlannedEdit {
+                    file: a.clone(),
+                    original: "a".to_string(),
+                    new_content: "A".to_string(),
+                    description: "edit a".to_string(),
+                },
+                PlannedEdit {
+                    file: b.clone(),
+                    original: "b".to_string(),
+                    new_content: "B".to_string(),
+                    description: "edit b".to_string(),
+                },
+            ],
+            deletions: vec![],
+            warnings: vec![],
+        };
+
+        let err = executor.apply(&plan).unwrap_err();
+        assert!(
+            err.contains("b.rs") && err.contains("changed on disk"),
+            "{err}"
+        );
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "a");
+    }
+
+    #[test]
+    fn executor_moves_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old.rs");
+        let new = dir.path().join("sub/new.rs");
+        std::fs::write(&old, "content").unwrap();
+
+        let executor = RefactoringExecutor {
+            root: dir.path().to_path_buf(),
+            dry_run: false,
+            shadow_enabled: false,
+            message: None,
+        };
+
+        let plan = RefactoringPlan {
+            operation: "move-file".to_string(),
+            edits: vec![PlannedEdit {
+                file: new.clone(),
+                original: String::new(),
+                new_content: "content".to_string(),
+                description: "move".to_string(),
+            }],
+            deletions: vec![old.clone()],
+            warnings: vec![],
+        };
+
+        let result = executor.apply(&plan).unwrap();
+        assert_eq!(result, vec!["sub/new.rs", "old.rs"]);
+        assert!(!old.exists());
+        assert_eq!(std::fs::read_to_string(&new).unwrap(), "content");
+    }
+
     #[test]
     fn empty_plan_returns_empty() {
         let dir = tempfile::tempdir().unwrap();
@@ -262,6 +434,7 @@ mod tests {
         let plan = RefactoringPlan {
             operation: "test".to_string(),
             edits: vec![],
+            deletions: vec![],
             warnings: vec![],
         };
 
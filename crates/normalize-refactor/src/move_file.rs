@@ -0,0 +1,675 @@
+//! Move-file recipe: relocate a whole file and rewrite every import that names it.
+//!
+//! Steps:
+//! 1. Read the file and check that the destination is free
+//! 2. Rebase the file's own relative imports onto its new directory
+//! 3. Find importers through the index's resolved import edges
+//! 4. Rewrite each importer's module specifier per language convention:
+//!    relative specifiers (`./utils`, `.utils`) are recomputed from the importer's
+//!    directory; absolute ones (`crate::a::b`, `pkg.mod`, Go import paths, TS
+//!    `baseUrl` paths) are re-derived from the language's `ModuleResolver`
+//! 5. Write the file at its new path and delete the old one, all in one plan that
+//!    the executor applies atomically
+//!
+//! Imports that only reach the file through a re-export keep working and are left
+//! alone. Specifiers that can't be rewritten confidently are skipped with a warning.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use normalize_languages::{ResolverConfig, support_for_path};
+
+use crate::move_item::{pathdiff, replace_module_on_line};
+use crate::{PlannedEdit, RefactoringContext, RefactoringPlan};
+
+/// Languages whose relative imports follow JavaScript resolution.
+const JS_LANGS: &[&str] = &["JavaScript", "TypeScript", "TSX"];
+
+/// One file's imports of the moved file: (line, module) → imported names.
+type ImportStatements = BTreeMap<(usize, Option<String>), Vec<String>>;
+
+/// Outcome details for a planned file move.
+pub struct MoveFileOutcome {
+    pub plan: RefactoringPlan,
+    pub from_file: String,
+    pub to_file: String,
+    pub import_sites_updated: usize,
+    pub import_sites_skipped: usize,
+}
+
+/// Build a file-move plan without touching the filesystem.
+///
+/// `from_rel_path` and `to_rel_path` are relative to `ctx.root`. The plan writes
+/// the file at `to_rel_path`, deletes `from_rel_path`, and edits every importer.
+pub async fn plan_move_file(
+    ctx: &RefactoringContext,
+    from_rel_path: &str,
+    to_rel_path: &str,
+) -> Result<MoveFileOutcome, String> {
+    let from_abs = normalize_lexically(&ctx.root.join(from_rel_path));
+    let to_abs = normalize_lexically(&ctx.root.join(to_rel_path));
+
+    if from_abs == to_abs {
+        return Err(format!(
+            "source and destination are the same file: {}",
+            from_rel_path
+        ));
+    }
+    if to_abs.exists() {
+        return Err(format!("destination already exists: {}", to_rel_path));
+    }
+
+    let content = std::fs::read_to_string(&from_abs)
+        .map_err(|e| format!("Error reading {}: {}", from_rel_path, e))?;
+
+    let mut edits: Vec<PlannedEdit> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut import_sites_updated = 0usize;
+    let mut import_sites_skipped = 0usize;
+    let mut moved_content = content.clone();
+
+    if let Some(ref idx) = ctx.index {
+        // 2. The file's own relative imports now start from a different directory.
+        let own_imports = idx.imports_of_file(from_rel_path).await.unwrap_or_default();
+        for (module, line_no) in own_imports {
+            let Some(module) = module else { continue };
+            let Some(new_module) = rebase_relative(&from_abs, &to_abs, &module) else {
+                continue;
+            };
+            if new_module == module {
+                continue;
+            }
+            match replace_module_on_line(&moved_content, line_no, &module, &new_module) {
+                Some(updated) => {
+                    moved_content = updated;
+                    import_sites_updated += 1;
+                }
+                None => {
+                    warnings.push(format!(
+                        "{}:{}: could not locate module string '{}' on line; skipped",
+                        from_rel_path, line_no, module
+                    ));
+                    import_sites_skipped += 1;
+                }
+            }
+        }
+
+        // 3. Group importers by file, then by statement (one row per imported name).
+        let importers = idx
+            .find_file_importers(from_rel_path)
+            .await
+            .unwrap_or_default();
+        let mut by_file: BTreeMap<String, ImportStatements> = BTreeMap::new();
+        for (file, module, name, line) in importers {
+            if file == from_rel_path {
+                continue;
+            }
+            by_file
+                .entry(file)
+                .or_default()
+                .entry((line, module))
+                .or_default()
+                .push(name);
+        }
+
+        // 4. Rewrite each statement's module specifier.
+        let mut resolvers = Resolvers::new(&ctx.root);
+        for (rel_path, statements) in by_file {
+            let abs_path = normalize_lexically(&ctx.root.join(&rel_path));
+            let original = match std::fs::read_to_string(&abs_path) {
+                Ok(c) => c,
+                Err(_) => {
+                    warnings.push(format!("could not read importer file: {}", rel_path));
+                    import_sites_skipped += statements.len();
+                    continue;
+                }
+            };
+
+            let mut current = original.clone();
+            let mut file_changed = false;
+            for ((line_no, module), names) in statements {
+                let Some(module) = module else {
+                    warnings.push(format!(
+                        "{}:{}: import has no module path; skipped",
+                        rel_path, line_no
+                    ));
+                    import_sites_skipped += 1;
+                    continue;
+                };
+                let new_module = match rewrite_specifier(
+                    &mut resolvers,
+                    &abs_path,
+                    &module,
+                    &names,
+                    &from_abs,
+                    &to_abs,
+                ) {
+                    SpecifierRewrite::Indirect => continue,
+                    SpecifierRewrite::Unsupported => {
+                        warnings.push(format!(
+                            "{}:{}: cannot derive new module path for '{}'; skipped",
+                            rel_path, line_no, module
+                        ));
+                        import_sites_skipped += 1;
+                        continue;
+                    }
+                    SpecifierRewrite::To(new_module) => new_module,
+                };
+                if new_module == module {
+                    continue;
+                }
+                match replace_module_on_line(&current, line_no, &module, &new_module) {
+                    Some(updated) => {
+                        current = updated;
+                        file_changed = true;
+                        import_sites_updated += 1;
+                    }
+                    None => {
+                        warnings.push(format!(
+                            "{}:{}: could not locate module string '{}' on line; skipped",
+                            rel_path, line_no, module
+                        ));
+                        import_sites_skipped += 1;
+                    }
+                }
+            }
+
+            if file_changed {
+                edits.push(PlannedEdit {
+                    file: abs_path,
+                    original,
+                    new_content: current,
+                    description: format!("rewrite imports of {}", from_rel_path),
+                });
+            }
+        }
+    } else {
+        warnings
+            .push("Index not available; moved file only (import sites not rewritten)".to_string());
+    }
+
+    // Module declarations that live outside import statements.
+    match language_name(&from_abs) {
+        Some("Rust") => warnings.push(format!(
+            "`mod` declarations are not rewritten; update the one for {} by hand",
+            from_rel_path
+        )),
+        Some("Go") if from_abs.parent() != to_abs.parent() => warnings.push(format!(
+            "{} changed directory; its `package` clause may need updating",
+            to_rel_path
+        )),
+        _ => {}
+    }
+
+    // 5. The file itself: written at the destination, removed from the source.
+    edits.insert(
+        0,
+        PlannedEdit {
+            file: to_abs.clone(),
+            original: String::new(),
+            new_content: moved_content,
+            description: format!("move {} to {}", from_rel_path, to_rel_path),
+        },
+    );
+
+    Ok(MoveFileOutcome {
+        plan: RefactoringPlan {
+            operation: "move-file".to_string(),
+            edits,
+            deletions: vec![from_abs],
+            warnings,
+        },
+        from_file: from_rel_path.to_string(),
+        to_file: to_rel_path.to_string(),
+        import_sites_updated,
+        import_sites_skipped,
+    })
+}
+
+/// What to do with one importer's module specifier.
+#[derive(Debug, PartialEq, Eq)]
+enum SpecifierRewrite {
+    /// Replace the specifier with this string.
+    To(String),
+    /// The specifier reaches the file through another module (a re-export);
+    /// it keeps working after the move.
+    Indirect,
+    /// The specifier names the file, but in a form we can't rewrite confidently.
+    Unsupported,
+}
+
+/// Per-language `ModuleResolver` configs, read once per move.
+struct Resolvers<'a> {
+    root: &'a Path,
+    configs: HashMap<&'static str, ResolverConfig>,
+}
+
+impl<'a> Resolvers<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            configs: HashMap::new(),
+        }
+    }
+
+    /// Canonical (old, new) module path pairs for a file moving from `old` to `new`.
+    /// Empty when the language has no resolver or either path has no module identity.
+    fn canonical_pairs(&mut self, old: &Path, new: &Path) -> Vec<(String, String)> {
+        let Some(lang) = support_for_path(old) else {
+            return Vec::new();
+        };
+        let Some(resolver) = lang.module_resolver() else {
+            return Vec::new();
+        };
+        let cfg = self
+            .configs
+            .entry(lang.name())
+            .or_insert_with(|| resolver.workspace_config(self.root));
+        let old_id = resolver
+            .module_of_file(self.root, old, cfg)
+            .into_iter()
+            .next();
+        let new_id = resolver
+            .module_of_file(self.root, new, cfg)
+            .into_iter()
+            .next();
+        let (Some(old_id), Some(new_id)) = (old_id, new_id) else {
+            return Vec::new();
+        };
+        let mut pairs = Vec::new();
+        // Rust: within the crate the same path is spelled `crate::…`.
+        if lang.name() == "Rust"
+            && let (Some((old_crate, old_rest)), Some((new_crate, new_rest))) = (
+                old_id.canonical_path.split_once("::"),
+                new_id.canonical_path.split_once("::"),
+            )
+            && old_crate == new_crate
+        {
+            pairs.push((format!("crate::{old_rest}"), format!("crate::{new_rest}")));
+        }
+        pairs.push((old_id.canonical_path, new_id.canonical_path));
+        pairs
+    }
+}
+
+/// Decide how an import in `importer` of `module` (bringing in `names`) changes
+/// when the file it resolves to moves from `old` to `new`.
+fn rewrite_specifier(
+    resolvers: &mut Resolvers,
+    importer: &Path,
+    module: &str,
+    names: &[String],
+    old: &Path,
+    new: &Path,
+) -> SpecifierRewrite {
+    let Some(importer_dir) = importer.parent() else {
+        return SpecifierRewrite::Unsupported;
+    };
+    let importer_lang = language_name(importer);
+
+    if is_js_language(importer_lang) && is_js_relative(module) {
+        let target = normalize_lexically(&importer_dir.join(module));
+        let old_stem = old.with_extension("");
+        let new_target = if target == old {
+            new.to_path_buf()
+        } else if is_js_language(language_name(&target))
+            && let Some(ext) = target.extension().and_then(|e| e.to_str())
+            && target.with_extension("") == old_stem
+        {
+            // `./utils.js` naming `utils.ts` (ESM-style TypeScript imports).
+            new.with_extension(ext)
+        } else if target == old_stem {
+            new.with_extension("")
+        } else if old.file_stem().and_then(|s| s.to_str()) == Some("index")
+            && Some(target.as_path()) == old.parent()
+        {
+            // A directory import resolved through its `index` file.
+            if new.file_stem().and_then(|s| s.to_str()) == Some("index") {
+                match new.parent() {
+                    Some(dir) => dir.to_path_buf(),
+                    None => return SpecifierRewrite::Unsupported,
+                }
+            } else {
+                new.with_extension("")
+            }
+        } else {
+            return SpecifierRewrite::Indirect;
+        };
+        return match js_relative(importer_dir, &new_target) {
+            Some(s) => SpecifierRewrite::To(s),
+            None => SpecifierRewrite::Unsupported,
+        };
+    }
+
+    if importer_lang == Some("Python") && module.starts_with('.') {
+        let (Some(target), Some(old_module), Some(new_module)) = (
+            python_relative_target(importer, module),
+            python_module_path(old),
+            python_module_path(new),
+        ) else {
+            return SpecifierRewrite::Unsupported;
+        };
+        if target == old_module {
+            return match python_relative(importer_dir, &new_module) {
+                Some(s) => SpecifierRewrite::To(s),
+                None => SpecifierRewrite::Unsupported,
+            };
+        }
+        // `from . import utils`: the module is named by the imported name.
+        if old_module.parent() == Some(target.as_path())
+            && old_module
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|stem| names.iter().any(|n| n == stem))
+        {
+            return SpecifierRewrite::Unsupported;
+        }
+        return SpecifierRewrite::Indirect;
+    }
+
+    let pairs = resolvers.canonical_pairs(old, new);
+    if pairs.is_empty() {
+        return SpecifierRewrite::Unsupported;
+    }
+    for (old_path, new_path) in &pairs {
+        if module == old_path {
+            return SpecifierRewrite::To(new_path.clone());
+        }
+        if let Some(rest) = module.strip_prefix(old_path.as_str())
+            && ["::", ".", "/"].iter().any(|sep| rest.starts_with(sep))
+        {
+            return SpecifierRewrite::To(format!("{new_path}{rest}"));
+        }
+    }
+    // `from pkg import utils` / `use crate::{utils}`: the imported name is the module.
+    let names_module = pairs.iter().any(|(old_path, _)| {
+        names.iter().any(|name| {
+            ["::", ".", "/"]
+                .iter()
+                .any(|sep| *old_path == format!("{module}{sep}{name}"))
+        })
+    });
+    if names_module {
+        SpecifierRewrite::Unsupported
+    } else {
+        SpecifierRewrite::Indirect
+    }
+}
+
+/// The new form of one of the moved file's own imports, if it is relative.
+///
+/// Relative specifiers are resolved against the old directory and re-expressed
+/// from the new one; everything else is location-independent.
+fn rebase_relative(old: &Path, new: &Path, module: &str) -> Option<String> {
+    let lang = language_name(old);
+    let new_dir = new.parent()?;
+    if is_js_language(lang) && is_js_relative(module) {
+        let target = normalize_lexically(&old.parent()?.join(module));
+        return js_relative(new_dir, &target);
+    }
+    if lang == Some("Python") && module.starts_with('.') {
+        let target = python_relative_target(old, module)?;
+        return python_relative(new_dir, &target);
+    }
+    None
+}
+
+/// Name of the language `path` is written in, per the language registry.
+fn language_name(path: &Path) -> Option<&'static str> {
+    support_for_path(path).map(|lang| lang.name())
+}
+
+fn is_js_language(name: Option<&str>) -> bool {
+    name.is_some_and(|n| JS_LANGS.contains(&n))
+}
+
+fn is_js_relative(module: &str) -> bool {
+    module == "." || module == ".." || module.starts_with("./") || module.starts_with("../")
+}
+
+/// `./`-prefixed relative specifier from `from_dir` to `target`.
+fn js_relative(from_dir: &Path, target: &Path) -> Option<String> {
+    let rel = pathdiff(target, from_dir)?;
+    let parts: Vec<&str> = rel
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    let joined = parts.join("/");
+    if joined.starts_with('.') {
+        Some(joined)
+    } else {
+        Some(format!("./{joined}"))
+    }
+}
+
+/// Path (without extension) a relative Python import names, e.g. `..pkg.mod`
+/// from `a/b/main.py` → `a/pkg/mod`.
+fn python_relative_target(importer: &Path, module: &str) -> Option<PathBuf> {
+    let dots = module.chars().take_while(|&c| c == '.').count();
+    let mut base = importer.parent()?;
+    for _ in 1..dots {
+        base = base.parent()?;
+    }
+    let mut target = base.to_path_buf();
+    for part in module[dots..].split('.').filter(|p| !p.is_empty()) {
+        target.push(part);
+    }
+    Some(target)
+}
+
+/// Path a Python module is imported by: the package directory for `__init__.py`,
+/// otherwise the file without its extension.
+fn python_module_path(file: &Path) -> Option<PathBuf> {
+    if file.file_stem()?.to_str()? == "__init__" {
+        Some(file.parent()?.to_path_buf())
+    } else {
+        Some(file.with_extension(""))
+    }
+}
+
+/// Relative Python module spelling (`.mod`, `..pkg.mod`) of `target` from `from_dir`.
+fn python_relative(from_dir: &Path, target: &Path) -> Option<String> {
+    let rel = pathdiff(target, from_dir)?;
+    let mut ups = 0usize;
+    let mut parts = Vec::new();
+    for c in rel.components() {
+        match c {
+            Component::ParentDir => ups += 1,
+            Component::Normal(s) => parts.push(s.to_str()?),
+            _ => {}
+        }
+    }
+    Some(format!("{}{}", ".".repeat(ups + 1), parts.join(".")))
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(importer: &str, module: &str, names: &[&str], old: &str, new: &str) -> String {
+        let root = Path::new("/p");
+        let mut resolvers = Resolvers::new(root);
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        match rewrite_specifier(
+            &mut resolvers,
+            &root.join(importer),
+            module,
+            &names,
+            &root.join(old),
+            &root.join(new),
+        ) {
+            SpecifierRewrite::To(s) => s,
+            other => format!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn js_relative_specifiers_follow_the_file() {
+        assert_eq!(
+            rewrite(
+                "src/app.ts",
+                "./utils",
+                &["f"],
+                "src/utils.ts",
+                "src/lib/utils.ts"
+            ),
+            "./lib/utils"
+        );
+        assert_eq!(
+            rewrite(
+                "src/a/app.ts",
+                "../utils.js",
+                &["f"],
+                "src/utils.ts",
+                "src/b/u.ts"
+            ),
+            "../b/u.js"
+        );
+        assert_eq!(
+            rewrite(
+                "src/app.js",
+                "./lib",
+                &["f"],
+                "src/lib/index.js",
+                "src/util/index.js"
+            ),
+            "./util"
+        );
+        // Reaches the file through a barrel re-export.
+        assert_eq!(
+            rewrite(
+                "src/app.ts",
+                "./index",
+                &["f"],
+                "src/utils.ts",
+                "src/lib/utils.ts"
+            ),
+            "Indirect"
+        );
+    }
+
+    #[test]
+    fn python_relative_specifiers_follow_the_file() {
+        assert_eq!(
+            rewrite(
+                "pkg/main.py",
+                ".utils",
+                &["f"],
+                "pkg/utils.py",
+                "pkg/sub/helpers.py"
+            ),
+            ".sub.helpers"
+        );
+        assert_eq!(
+            rewrite(
+                "pkg/a/main.py",
+                "..utils",
+                &["f"],
+                "pkg/utils.py",
+                "pkg/a/utils.py"
+            ),
+            ".utils"
+        );
+        assert_eq!(
+            rewrite(
+                "pkg/main.py",
+                ".",
+                &["utils"],
+                "pkg/utils.py",
+                "pkg/sub/utils.py"
+            ),
+            "Unsupported"
+        );
+    }
+
+    #[test]
+    fn own_relative_imports_are_rebased() {
+        let old = Path::new("/p/src/app.ts");
+        let new = Path::new("/p/src/ui/app.ts");
+        assert_eq!(
+            rebase_relative(old, new, "./utils").as_deref(),
+            Some("../utils")
+        );
+        assert_eq!(rebase_relative(old, new, "react"), None);
+        assert_eq!(
+            rebase_relative(Path::new("/p/pkg/m.py"), Path::new("/p/pkg/a/m.py"), ".x").as_deref(),
+            Some("..x")
+        );
+    }
+
+    #[tokio::test]
+    async fn plan_move_file_rewrites_indexed_importers() {
+        if normalize_languages::parsers::parser_for("typescript").is_none() {
+            eprintln!("skipping: typescript grammar not available");
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/ui")).unwrap();
+        std::fs::write(
+            root.join("src/utils.ts"),
+            "import { base } from \"./base\";\nexport function add(a: number) { return a + base; }\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/base.ts"), "export const base = 1;\n").unwrap();
+        std::fs::write(
+            root.join("src/ui/view.ts"),
+            "import { add } from \"../utils\";\nadd(1);\n",
+        )
+        .unwrap();
+
+        let mut index = normalize_facts::FileIndex::open(&root.join("index.sqlite"), root)
+            .await
+            .unwrap();
+        index.refresh().await.unwrap();
+        index.refresh_call_graph().await.unwrap();
+
+        let ctx = RefactoringContext {
+            root: root.to_path_buf(),
+            editor: normalize_edit::Editor::new(),
+            index: Some(index),
+            loader: normalize_languages::GrammarLoader::new(),
+        };
+        let outcome = plan_move_file(&ctx, "src/utils.ts", "src/lib/math.ts")
+            .await
+            .expect("plan_move_file");
+
+        assert_eq!(outcome.plan.deletions, vec![root.join("src/utils.ts")]);
+        let content_of = |path: &str| {
+            outcome
+                .plan
+                .edits
+                .iter()
+                .find(|e| e.file == root.join(path))
+                .map(|e| e.new_content.clone())
+                .unwrap_or_else(|| panic!("no edit for {path}"))
+        };
+        assert!(content_of("src/lib/math.ts").starts_with("import { base } from \"../base\";"));
+        assert!(content_of("src/ui/view.ts").starts_with("import { add } from \"../lib/math\";"));
+        assert_eq!(outcome.import_sites_updated, 2);
+        assert_eq!(outcome.import_sites_skipped, 0);
+    }
+
+    #[test]
+    fn normalize_lexically_resolves_dots() {
+        assert_eq!(
+            normalize_lexically(Path::new("/a/b/./../c")),
+            PathBuf::from("/a/c")
+        );
+    }
+}
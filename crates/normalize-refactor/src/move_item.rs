@@ -3,8 +3,10 @@
 //! Steps:
 //! 1. Locate the symbol in the source file
 //! 2. Extract its definition text via the editor
-//! 3. Append the definition to the destination file
-//! 4. Delete the definition from the source file
+//! 3. Add the definition to the destination file (inside the same-named
+//!    container when there is one, otherwise at the end)
+//! 4. Delete the definition from the source file, fixing the imports of both
+//!    files (`Editor::move_symbol_at`)
 //! 5. Rewrite import statements in every file that imported it from the old module path
 //!    (best-effort: emits a warning and skips when the new path can't be derived)
 //! 6. Optionally leave a re-export in the source file (`--reexport`)
@@ -54,7 +56,7 @@ pub async fn plan_move(
 
     // 1. Locate the symbol in the source.
     let mut loc = actions::locate_symbol(ctx, &from_abs, &from_content, symbol_name)
-        .ok_or_else(|| format!("Symbol '{}' not found in {}", symbol_name, from_rel_path))?;
+        .map_err(|e| format!("{} in {}", e, from_rel_path))?;
 
     // Extend the start backward to include leading decorations (doc comments,
     // attributes, decorators) identified via tree-sitter `node.kind()`. Falls
@@ -63,40 +65,27 @@ pub async fn plan_move(
         actions::decoration_extended_start(&from_abs, &from_content, &loc);
     loc.start_byte = extended_start;
 
-    // 2. Extract definition text (whole-line span of the symbol).
-    let line_start = from_content[..loc.start_byte]
-        .rfind('\n')
-        .map(|i| i + 1)
-        .unwrap_or(0);
-    let mut def_end = loc.end_byte;
-    if def_end < from_content.len() && from_content.as_bytes()[def_end] == b'\n' {
-        def_end += 1;
-    }
-    let definition_text = from_content[line_start..def_end].to_string();
-
-    let mut edits: Vec<PlannedEdit> = Vec::new();
     let mut warnings: Vec<String> = Vec::new();
     if let Some(w) = decoration_warning {
         warnings.push(w);
     }
 
-    // 3. Append the definition to the destination file (creating it if absent).
-    let dest_original = std::fs::read_to_string(&to_abs).unwrap_or_default();
-    let dest_new = ctx.editor.append_to_file(&dest_original, &definition_text);
-    edits.push(PlannedEdit {
-        file: to_abs.clone(),
-        original: dest_original.clone(),
-        new_content: dest_new,
-        description: format!("append {}", symbol_name),
-    });
-
-    // 4. Remove the definition from the source file (and optionally leave a re-export).
-    let mut src_new = ctx.editor.delete_symbol(&from_content, &loc);
+    // 2-4. Move the definition (into the same-named container when the
+    // destination has one) and fix the imports of the two files involved.
+    let mut move_plan = ctx.editor.move_symbol_at(&from_abs, &loc, &to_abs)?;
+
+    // Optionally leave a re-export in the source file. Skip the stub when the
+    // move already added an identical import because the source still uses it.
     let mut reexport_added = false;
     if reexport {
         if let Some(stub) = build_reexport(&from_abs, &to_abs, symbol_name) {
-            // Append the re-export at the end of the source file.
-            src_new = ctx.editor.append_to_file(&src_new, &stub);
+            let already_imported = move_plan
+                .render()
+                .iter()
+                .any(|(path, content)| *path == from_abs && content.contains(stub.trim_end()));
+            if !already_imported {
+                move_plan.append_to_file(&from_abs, &stub)?;
+            }
             reexport_added = true;
         } else {
             warnings.push(format!(
@@ -105,12 +94,21 @@ pub async fn plan_move(
             ));
         }
     }
-    edits.push(PlannedEdit {
-        file: from_abs.clone(),
-        original: from_content.clone(),
-        new_content: src_new,
-        description: format!("remove {}", symbol_name),
-    });
+
+    let mut edits: Vec<PlannedEdit> = Vec::new();
+    for (file, new_content) in move_plan.render() {
+        let description = if file == to_abs {
+            format!("append {}", symbol_name)
+        } else {
+            format!("remove {}", symbol_name)
+        };
+        edits.push(PlannedEdit {
+            original: move_plan.original(&file)?.to_string(),
+            file,
+            new_content,
+            description,
+        });
+    }
 
     // 5. Rewrite import statements in every importer.
     let mut import_sites_updated = 0usize;
@@ -126,8 +124,8 @@ pub async fn plan_move(
         use std::collections::HashMap;
         let mut by_file: HashMap<String, Vec<(usize, Option<String>)>> = HashMap::new();
         for (file, _name, _alias, line, module) in importers {
-            // Skip: the source file itself (we already edited it).
-            if file == from_rel_path {
+            // Skip: the source and destination (their imports were fixed by the move).
+            if file == from_rel_path || file == to_rel_path {
                 continue;
             }
             by_file.entry(file).or_default().push((line, module));
@@ -198,6 +196,7 @@ pub async fn plan_move(
         plan: RefactoringPlan {
             operation: "move".to_string(),
             edits,
+            deletions: vec![],
             warnings,
         },
         symbol: symbol_name.to_string(),
@@ -212,7 +211,7 @@ pub async fn plan_move(
 
 /// Replace `old_module` with `new_module` on a specific 1-based line of `content`.
 /// Returns `None` if the substring is not present on that line.
-fn replace_module_on_line(
+pub(crate) fn replace_module_on_line(
     content: &str,
     line_no: usize,
     old_module: &str,
@@ -336,7 +335,7 @@ fn derive_js_relative(importer_path: &Path, dest_path: &Path) -> Option<String>
 }
 
 /// Minimal `pathdiff::diff_paths` reimplementation (no external dep).
-fn pathdiff(target: &Path, base: &Path) -> Option<PathBuf> {
+pub(crate) fn pathdiff(target: &Path, base: &Path) -> Option<PathBuf> {
     use std::path::Component;
     let target: Vec<Component> = target.components().collect();
     let base: Vec<Component> = base.components().collect();
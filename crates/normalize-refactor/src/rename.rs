@@ -30,7 +30,7 @@ pub async fn plan_rename(
 
     // 1. Locate definition
     let loc = actions::locate_symbol(ctx, &def_abs_path, &def_content, old_name)
-        .ok_or_else(|| format!("Symbol '{}' not found in {}", old_name, def_rel_path))?;
+        .map_err(|e| format!("{} in {}", e, def_rel_path))?;
 
     // 2. Find cross-file references
     let refs = actions::find_references(ctx, old_name, &def_rel_path).await;
@@ -157,6 +157,7 @@ pub async fn plan_rename(
     Ok(RefactoringPlan {
         operation: "rename".to_string(),
         edits,
+        deletions: vec![],
         warnings,
     })
 }
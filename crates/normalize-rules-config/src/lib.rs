@@ -77,6 +77,7 @@ pub struct SarifTool {
 /// rule and deserialized from the same TOML table via `#[serde(flatten)]`.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, Default, schemars::JsonSchema)]
 #[serde(default)]
+#[schemars(extend("additionalProperties" = true))]
 pub struct RuleOverride {
     /// Override the rule's severity (error, warning, info, hint).
     pub severity: Option<String>,
@@ -88,6 +89,11 @@ pub struct RuleOverride {
     /// Additional tags to add to this rule (appends to built-in tags).
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Severity by file glob (e.g. `"src/core/**" = "error"`). Overrides
+    /// `severity` for diagnostics in matching files; when several patterns
+    /// match, the longest wins. Fact rules only.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub paths: std::collections::BTreeMap<String, String>,
     /// Raw TOML table for rule-specific fields. Each rule deserializes its
     /// own typed config from this via [`RuleOverride::rule_config`].
     #[serde(flatten)]
@@ -120,10 +126,12 @@ impl normalize_core::Merge for RuleOverride {
     /// - `Option` fields: `other`'s value wins if `Some`; falls back to `self`.
     /// - Vec fields (`allow`, `tags`): if `other`'s field is non-empty it replaces
     ///   `self`'s field entirely; an empty `other` field inherits from `self`.
-    /// - `extra` HashMap: merged key-by-key, `other`'s keys override `self`'s.
+    /// - `paths` and `extra` maps: merged key-by-key, `other`'s keys override `self`'s.
     fn merge(self, other: Self) -> Self {
         let mut extra = self.extra;
         extra.extend(other.extra);
+        let mut paths = self.paths;
+        paths.extend(other.paths);
         Self {
             severity: other.severity.or(self.severity),
             enabled: other.enabled.or(self.enabled),
@@ -137,6 +145,7 @@ impl normalize_core::Merge for RuleOverride {
             } else {
                 other.tags
             },
+            paths,
             extra,
         }
     }
@@ -189,6 +198,10 @@ pub struct RulesConfig {
     /// External tools that emit SARIF 2.1.0 output (the `sarif` engine).
     #[serde(rename = "sarif-tools", default, skip_serializing_if = "Vec::is_empty")]
     pub sarif_tools: Vec<SarifTool>,
+    /// Worker threads for evaluating fact rules in parallel. Default: one per
+    /// CPU core.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
     /// Per-rule configuration overrides, keyed by rule ID.
     ///
     /// Serialized under the `rule` sub-table (`[rules.rule."<id>"]`). On
@@ -202,7 +215,7 @@ pub struct RulesConfig {
 /// Engine-wide bare keys reserved under `[rules]`. Anything else found at the
 /// top level of `[rules]` is interpreted as a legacy `[rules."<id>"]` per-rule
 /// override (and triggers a deprecation warning).
-const RULES_RESERVED_KEYS: &[&str] = &["global-allow", "sarif-tools", "rule"];
+const RULES_RESERVED_KEYS: &[&str] = &["global-allow", "sarif-tools", "threads", "rule"];
 
 impl<'de> serde::Deserialize<'de> for RulesConfig {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -216,6 +229,7 @@ impl<'de> serde::Deserialize<'de> for RulesConfig {
 
         let mut global_allow: Vec<String> = Vec::new();
         let mut sarif_tools: Vec<SarifTool> = Vec::new();
+        let mut threads: Option<usize> = None;
         let mut rules: HashMap<String, RuleOverride> = HashMap::new();
         let mut legacy_rule_ids: Vec<String> = Vec::new();
 
@@ -227,6 +241,9 @@ impl<'de> serde::Deserialize<'de> for RulesConfig {
                 "sarif-tools" => {
                     sarif_tools = value.try_into().map_err(serde::de::Error::custom)?;
                 }
+                "threads" => {
+                    threads = Some(value.try_into().map_err(serde::de::Error::custom)?);
+                }
                 "rule" => {
                     let nested: HashMap<String, RuleOverride> =
                         value.try_into().map_err(serde::de::Error::custom)?;
@@ -265,6 +282,7 @@ impl<'de> serde::Deserialize<'de> for RulesConfig {
         Ok(RulesConfig {
             global_allow,
             sarif_tools,
+            threads,
             rules,
         })
     }
@@ -277,6 +295,7 @@ impl normalize_core::Merge for RulesConfig {
     ///   it replaces `self`'s field; an empty `other` field inherits from `self`.
     ///   **This means you cannot reset a Vec to empty via merge** — an empty `other`
     ///   vec is treated as "no override" rather than "clear the list".
+    /// - `threads`: `other`'s value if set, otherwise `self`'s.
     /// - `rules` HashMap: merged using `extend`, so `other`'s keys override `self`'s
     ///   keys. Keys present only in `self` are preserved.
     fn merge(self, other: Self) -> Self {
@@ -295,6 +314,7 @@ impl normalize_core::Merge for RulesConfig {
         Self {
             global_allow,
             sarif_tools,
+            threads: other.threads.or(self.threads),
             rules: merged_rules,
         }
     }
@@ -688,6 +708,17 @@ allow = ["**/tests/**", "crates/*/tests/**", "**/normalize-scope/**"]
         assert_eq!(ngl.allow[2], "**/normalize-scope/**");
     }
 
+    #[test]
+    fn threads_is_an_engine_key() {
+        let config: RulesConfig = toml::from_str("threads = 4\n").unwrap();
+        assert_eq!(config.threads, Some(4));
+        assert!(config.rules.is_empty());
+
+        use normalize_core::Merge;
+        let merged = config.merge(RulesConfig::default());
+        assert_eq!(merged.threads, Some(4));
+    }
+
     #[test]
     fn legacy_layout_still_parses() {
         // Old layout: per-rule entries directly under [rules]. Should still load
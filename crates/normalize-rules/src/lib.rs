@@ -4,6 +4,7 @@
 //! - `runner` — unified run, list, show, tags, enable/disable, add/update/remove
 //! - `cmd_rules` — syntax rule runner (tree-sitter based)
 //! - `loader` — diagnostic formatting helpers for fact rules
+//! - `watch` — re-run fact rules as files change (feature-gated)
 //! - `service` — `RulesService` with `#[cli]` registration (feature-gated)
 //!
 //! The `RulesRunConfig` struct allows callers to pass rule config without depending on
@@ -17,14 +18,18 @@ pub mod runner;
 pub mod service;
 #[cfg(feature = "cli")]
 pub mod setup;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 pub use runner::{
     ListFilters, RuleEntry, RuleInfoReport, RuleKind, RuleOverride, RulesConfig, RulesListReport,
-    RulesRunConfig, RulesTagsReport, SarifTool, TagEntry, abi_diagnostic_to_issue, add_rule,
-    apply_native_rules_config, build_list_report, build_relations_from_index,
-    collect_fact_diagnostics, collect_fact_diagnostics_incremental, enable_disable,
-    finding_to_issue, list_tags, list_tags_structured, remove_rule, run_rules_report,
-    run_sarif_tools, show_rule, show_rule_structured, try_rules_via_daemon, update_rules,
+    RulesRunConfig, RulesTagsReport, SarifTool, TagEntry, abi_diagnostic_to_issue,
+    add_churn_relations, add_dependency_relations, add_rule, apply_native_rules_config,
+    build_list_report, build_relations_from_index, collect_fact_diagnostics,
+    collect_fact_diagnostics_incremental, collect_fact_diagnostics_with_relations, enable_disable,
+    finding_to_issue, list_tags, list_tags_structured, load_enabled_fact_rules, remove_rule,
+    rules_read_dependencies, run_rules_report, run_sarif_tools, show_rule, show_rule_structured,
+    try_rules_via_daemon, update_rules, write_fact_baseline,
 };
 
 pub use loader::format_diagnostic;
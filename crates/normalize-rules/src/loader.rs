@@ -37,7 +37,12 @@ pub fn format_diagnostic(diag: &Diagnostic, use_colors: bool) -> String {
 
     // Location
     if let Some(ref loc) = diag.location {
-        out.push_str(&format!("{}:{}: ", loc.file, loc.line));
+        match loc.end_line {
+            Some(end) if end > loc.line => {
+                out.push_str(&format!("{}:{}-{}: ", loc.file, loc.line, end))
+            }
+            _ => out.push_str(&format!("{}:{}: ", loc.file, loc.line)),
+        }
     }
 
     // Level and rule
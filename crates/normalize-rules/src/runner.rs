@@ -1365,9 +1365,11 @@ pub async fn collect_fact_diagnostics(
 /// engine cache is consulted: each rule's engine is primed on the first call and
 /// only the strata affected by the changed files are re-derived on subsequent calls.
 ///
-/// Pass `None` for `changed_files` to bypass the cache and do a fresh full evaluation.
+/// Pass `None` for `changed_files` to bypass the engine cache and do a full evaluation.
 /// This is the correct choice for one-shot CLI invocations where no previous engine
-/// state is available or relevant.
+/// state is available or relevant. Rules whose input relations are unchanged since a
+/// previous run are then served from `.normalize/findings-cache.sqlite` instead of
+/// being re-derived (see [`interpret::rule_cache_key`]).
 pub async fn collect_fact_diagnostics_incremental(
     root: &Path,
     config: &RulesConfig,
@@ -1375,19 +1377,12 @@ pub async fn collect_fact_diagnostics_incremental(
     filter_rule: Option<&str>,
     changed_files: Option<&[PathBuf]>,
 ) -> Vec<normalize_facts_rules_api::Diagnostic> {
-    let all_rules_unfiltered = interpret::load_all_rules(root, config);
-    let all_rules: Vec<_> = all_rules_unfiltered
-        .into_iter()
-        .filter(|r| r.enabled)
-        .filter(|r| filter_ids.is_none_or(|ids| ids.contains(&r.id)))
-        .filter(|r| filter_rule.is_none_or(|id| r.id == id))
-        .collect();
-
+    let all_rules = load_enabled_fact_rules(root, config, filter_ids, filter_rule);
     if all_rules.is_empty() {
         return Vec::new();
     }
 
-    let relations = match ensure_relations(root).await {
+    let relations = match ensure_relations(root, rules_read_dependencies(&all_rules)).await {
         Ok(r) => r,
         Err(e) => {
             tracing::warn!("failed to build relations for fact rules: {}", e);
@@ -1395,6 +1390,131 @@ pub async fn collect_fact_diagnostics_incremental(
         }
     };
 
+    let mut diagnostics =
+        evaluate_fact_rules(root, &all_rules, &relations, changed_files, config.threads);
+    apply_fact_baseline(root, &mut diagnostics);
+    diagnostics
+}
+
+/// Like [`collect_fact_diagnostics_incremental`], but evaluates against
+/// caller-provided relations instead of loading them from the index.
+///
+/// Long-running hosts (the daemon) keep one `Relations` per root and drop it
+/// when the index changes, so repeated evaluations skip the index load.
+pub fn collect_fact_diagnostics_with_relations(
+    root: &Path,
+    config: &RulesConfig,
+    filter_ids: Option<&HashSet<String>>,
+    filter_rule: Option<&str>,
+    changed_files: Option<&[PathBuf]>,
+    relations: &normalize_facts_rules_api::Relations,
+) -> Vec<normalize_facts_rules_api::Diagnostic> {
+    let all_rules = load_enabled_fact_rules(root, config, filter_ids, filter_rule);
+    if all_rules.is_empty() {
+        return Vec::new();
+    }
+    let mut diagnostics =
+        evaluate_fact_rules(root, &all_rules, relations, changed_files, config.threads);
+    apply_fact_baseline(root, &mut diagnostics);
+    diagnostics
+}
+
+/// Collect the metrics of enabled fact rules that declare `[output]` relations.
+///
+/// Only those rules are evaluated, through [`interpret::run_rule_findings`];
+/// their diagnostics are reported by [`collect_fact_diagnostics`].
+pub async fn collect_fact_metrics(
+    root: &Path,
+    config: &RulesConfig,
+    filter_ids: Option<&HashSet<String>>,
+    filter_rule: Option<&str>,
+) -> Vec<normalize_facts_rules_api::Metric> {
+    let metric_rules: Vec<interpret::FactsRule> =
+        load_enabled_fact_rules(root, config, filter_ids, filter_rule)
+            .into_iter()
+            .filter(|r| !r.outputs.is_empty())
+            .collect();
+    if metric_rules.is_empty() {
+        return Vec::new();
+    }
+
+    let relations = match ensure_relations(root, rules_read_dependencies(&metric_rules)).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!("failed to build relations for fact rule metrics: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut metrics = Vec::new();
+    for rule in &metric_rules {
+        match interpret::run_rule_findings(rule, &relations) {
+            Ok(findings) => metrics.extend(findings.into_iter().filter_map(|f| match f {
+                normalize_facts_rules_api::Finding::Metric(m) => Some(m),
+                normalize_facts_rules_api::Finding::Diagnostic(_) => None,
+            })),
+            Err(e) => tracing::warn!(rule_id = %rule.id, "fact rule metrics failed: {}", e),
+        }
+    }
+    metrics
+}
+
+/// Drop diagnostics accepted by the project's `.normalize/facts-baseline.json`.
+fn apply_fact_baseline(root: &Path, diagnostics: &mut Vec<normalize_facts_rules_api::Diagnostic>) {
+    match interpret::Baseline::load(root) {
+        Ok(Some(baseline)) => {
+            let suppressed = baseline.filter(diagnostics);
+            tracing::debug!(suppressed, "fact rule baseline");
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("ignoring fact rule baseline: {}", e),
+    }
+}
+
+/// Record every current fact rule diagnostic in `.normalize/facts-baseline.json`,
+/// replacing any existing baseline. Returns the number of entries written.
+pub async fn write_fact_baseline(root: &Path, config: &RulesConfig) -> Result<usize, String> {
+    let all_rules = load_enabled_fact_rules(root, config, None, None);
+    let diagnostics = if all_rules.is_empty() {
+        Vec::new()
+    } else {
+        let relations = ensure_relations(root, rules_read_dependencies(&all_rules)).await?;
+        evaluate_fact_rules(root, &all_rules, &relations, None, config.threads)
+    };
+    let baseline = interpret::Baseline::from_diagnostics(&diagnostics);
+    baseline.save(root)?;
+    Ok(baseline.entries.len())
+}
+
+pub fn load_enabled_fact_rules(
+    root: &Path,
+    config: &RulesConfig,
+    filter_ids: Option<&HashSet<String>>,
+    filter_rule: Option<&str>,
+) -> Vec<interpret::FactsRule> {
+    interpret::load_all_rules(root, config)
+        .into_iter()
+        .filter(|r| r.enabled)
+        .filter(|r| filter_ids.is_none_or(|ids| ids.contains(&r.id)))
+        .filter(|r| filter_rule.is_none_or(|id| r.id == id))
+        .collect()
+}
+
+/// `FindingsCache` engine name for persisted fact rule diagnostics.
+const FACT_CACHE_ENGINE: &str = "fact";
+
+/// `FindingsCache` path under which a fact rule's diagnostics are stored.
+fn fact_cache_path(rule_id: &str) -> String {
+    format!("fact-rule:{}", rule_id)
+}
+
+fn evaluate_fact_rules(
+    root: &Path,
+    all_rules: &[interpret::FactsRule],
+    relations: &normalize_facts_rules_api::Relations,
+    changed_files: Option<&[PathBuf]>,
+    threads: Option<usize>,
+) -> Vec<normalize_facts_rules_api::Diagnostic> {
     let mut all_diagnostics: Vec<normalize_facts_rules_api::Diagnostic> = Vec::new();
 
     if let Some(changed) = changed_files {
@@ -1408,7 +1528,7 @@ pub async fn collect_fact_diagnostics_incremental(
         // normalize-syntax-allow: rust/unwrap-in-impl - mutex poison = programmer error
         let mut cache = engine_cache().lock().unwrap();
 
-        for rule in &all_rules {
+        for rule in all_rules {
             let cache_key = engine_cache_key(root, &rule.id);
             // Remove from map so we can take ownership and pass &mut Option.
             let mut cached_engine: Option<interpret::CachedRuleEngine> =
@@ -1417,7 +1537,7 @@ pub async fn collect_fact_diagnostics_incremental(
             let mut diagnostics = match interpret::run_rule_with_cache(
                 &mut cached_engine,
                 rule,
-                &relations,
+                relations,
                 &changed_strs,
             ) {
                 Ok(d) => d,
@@ -1438,45 +1558,61 @@ pub async fn collect_fact_diagnostics_incremental(
 
             // Apply per-rule allow patterns and severity (mirrors run_rules_batch logic).
             if !rule.allow.is_empty() {
-                diagnostics.retain(|d| {
-                    let match_str = match d.location.as_ref() {
-                        Some(loc) => loc.file.as_str(),
-                        None => d.message.as_str(),
-                    };
-                    !rule.allow.iter().any(|p| p.matches(match_str))
-                });
-            }
-            use normalize_facts_rules_api::DiagnosticLevel;
-            use normalize_rules_config::Severity;
-            match rule.severity {
-                Severity::Error => {
-                    for d in &mut diagnostics {
-                        d.level = DiagnosticLevel::Error;
-                    }
-                }
-                Severity::Info | Severity::Hint => {
-                    for d in &mut diagnostics {
-                        if d.level == DiagnosticLevel::Warning {
-                            d.level = DiagnosticLevel::Hint;
-                        }
-                    }
-                }
-                Severity::Warning => {}
+                diagnostics.retain(|d| !rule.allows(d));
             }
+            rule.apply_severity(&mut diagnostics);
 
             all_diagnostics.extend(diagnostics);
         }
         // Cache mutex released here when `cache` drops.
     } else {
-        // Full-evaluation path (no incremental cache): original batch behavior.
-        let rule_refs: Vec<&interpret::FactsRule> = all_rules.iter().collect();
-        all_diagnostics = match interpret::run_rules_batch(&rule_refs, &relations) {
-            Ok(diagnostics) => diagnostics,
-            Err(e) => {
-                tracing::warn!("fact rules batch failed: {}", e);
-                Vec::new()
+        // Full-evaluation path: reuse each rule's persisted diagnostics when its
+        // cache key (rule + fingerprints of the relations it reads) is unchanged,
+        // and evaluate the rest in parallel on `threads` workers.
+        let fingerprints = interpret::relation_fingerprints(relations);
+        let cache = normalize_native_rules::FindingsCache::open(root);
+        let mut per_rule: Vec<Option<Vec<normalize_facts_rules_api::Diagnostic>>> =
+            Vec::with_capacity(all_rules.len());
+        let mut misses: Vec<(usize, String)> = Vec::new();
+        for (i, rule) in all_rules.iter().enumerate() {
+            let key = interpret::rule_cache_key(rule, &fingerprints);
+            let cached = cache
+                .get(&fact_cache_path(&rule.id), 0, &key, FACT_CACHE_ENGINE)
+                .and_then(|json| {
+                    serde_json::from_str::<Vec<normalize_facts_rules_api::Diagnostic>>(&json).ok()
+                });
+            if cached.is_none() {
+                misses.push((i, key));
             }
-        };
+            per_rule.push(cached);
+        }
+        tracing::debug!(
+            cached = all_rules.len() - misses.len(),
+            evaluated = misses.len(),
+            "fact rule cache"
+        );
+
+        let rule_refs: Vec<&interpret::FactsRule> =
+            misses.iter().map(|(i, _)| &all_rules[*i]).collect();
+        let results = interpret::with_rule_threads(threads, || {
+            interpret::run_rules_batch_per_rule(&rule_refs, relations)
+        });
+        cache.begin();
+        for ((i, key), result) in misses.iter().zip(results) {
+            let rule = &all_rules[*i];
+            match result {
+                Ok(diagnostics) => {
+                    if let Ok(json) = serde_json::to_string(&diagnostics) {
+                        cache.put(&fact_cache_path(&rule.id), 0, key, FACT_CACHE_ENGINE, &json);
+                    }
+                    per_rule[*i] = Some(diagnostics);
+                }
+                Err(e) => tracing::warn!(rule_id = %rule.id, "fact rule failed: {}", e),
+            }
+        }
+        cache.commit();
+        // Merge in rule order, so output doesn't depend on which rules were cached.
+        all_diagnostics = per_rule.into_iter().flatten().flatten().collect();
     }
 
     interpret::filter_inline_allowed(&mut all_diagnostics, root);
@@ -1789,22 +1925,65 @@ pub fn run_rules_report(
                 .filter_map(|s| glob::Pattern::new(s).ok())
                 .collect();
             for d in &diagnostics {
-                let file = match &d.location {
-                    Some(loc) => loc.file.as_str(),
-                    None => d.message.as_str(),
-                };
                 // Skip diagnostics for files that are gitignored (stale index entries).
-                if !allowed_files.contains(file) {
+                if let Some(loc) = &d.location
+                    && !allowed_files.contains(loc.file.as_str())
+                {
                     continue;
                 }
-                if global_allow.is_empty() || !global_allow.iter().any(|p| p.matches(file)) {
-                    report.issues.push(abi_diagnostic_to_issue(d));
+                if interpret::allow_matches(&global_allow, d) {
+                    continue;
                 }
+                report.issues.push(abi_diagnostic_to_issue(d));
             }
             report.sources_run.push("fact-rules".into());
         }
     }
 
+    // Fact rule metrics are evaluated locally even when the daemon served the
+    // diagnostics: it only caches issues.
+    if matches!(engine, RuleKind::All | RuleKind::Fact) {
+        let rt = tokio::runtime::Runtime::new().unwrap_or_else(|e| {
+            tracing::warn!("failed to create tokio runtime: {}", e);
+            panic!("failed to create tokio runtime: {}", e)
+        });
+        let metrics = std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024) // 64MB: Datalog transitive queries recurse deeply
+            .spawn({
+                let project_root = project_root.to_path_buf();
+                let rules = config.rules.clone();
+                let filter_ids = filter_ids.clone();
+                let filter_rule = filter_rule.map(|s| s.to_string());
+                move || {
+                    rt.block_on(collect_fact_metrics(
+                        &project_root,
+                        &rules,
+                        filter_ids.as_ref(),
+                        filter_rule.as_deref(),
+                    ))
+                }
+            })
+            .expect("failed to spawn fact engine thread")
+            .join()
+            .expect("fact engine thread panicked");
+        let global_allow: Vec<glob::Pattern> = config
+            .rules
+            .global_allow
+            .iter()
+            .filter_map(|s| glob::Pattern::new(s).ok())
+            .collect();
+        report.metrics.extend(
+            metrics
+                .iter()
+                .filter(|m| {
+                    m.location
+                        .as_ref()
+                        .is_none_or(|loc| !global_allow.iter().any(|p| p.matches(&loc.file)))
+                })
+                .map(abi_metric_to_rule_metric),
+        );
+    }
+
     // SARIF passthrough: run external tools and merge their SARIF output
     // (SARIF is not cached by the daemon — always run locally)
     if matches!(engine, RuleKind::All | RuleKind::Sarif) {
@@ -2031,8 +2210,11 @@ pub fn run_sarif_tools(
 }
 
 /// Build relations from the index, auto-building the index if it doesn't exist.
-async fn ensure_relations(root: &Path) -> Result<normalize_facts_rules_api::Relations, String> {
-    match build_relations_from_index(root).await {
+pub(crate) async fn ensure_relations(
+    root: &Path,
+    with_dependencies: bool,
+) -> Result<normalize_facts_rules_api::Relations, String> {
+    match build_relations_from_index(root, with_dependencies).await {
         Ok(r) => Ok(r),
         Err(_) => {
             tracing::info!("Facts index not found. Building...");
@@ -2060,13 +2242,13 @@ async fn ensure_relations(root: &Path) -> Result<normalize_facts_rules_api::Rela
                 stats.calls,
                 stats.imports
             );
-            build_relations_from_index(root).await
+            build_relations_from_index(root, with_dependencies).await
         }
     }
 }
 
 /// Get the normalize data directory for a project.
-fn get_normalize_dir(root: &Path) -> std::path::PathBuf {
+pub(crate) fn get_normalize_dir(root: &Path) -> std::path::PathBuf {
     if let Ok(index_dir) = std::env::var("NORMALIZE_INDEX_DIR") {
         let path = std::path::PathBuf::from(&index_dir);
         if path.is_absolute() {
@@ -2091,15 +2273,20 @@ fn get_normalize_dir(root: &Path) -> std::path::PathBuf {
 /// [`WalkConfig::with_daemon_baseline`] so the index walkers never descend into
 /// `.normalize/` even when no `[walk]` section is present. Delegates to the
 /// shared [`normalize_config_paths::ConfigSlices::walk`] loader.
-fn load_walk_config(root: &Path) -> normalize_rules_config::WalkConfig {
+pub(crate) fn load_walk_config(root: &Path) -> normalize_rules_config::WalkConfig {
     normalize_config_paths::ConfigSlices::load(root).walk()
 }
 
 /// Build Relations from the file index.
+///
+/// Loads every file's facts via [`Relations::from_index`](normalize_facts_rules_api::Relations::from_index),
+/// then adds churn facts. Dependency facts need a scan of every ecosystem's
+/// installed packages, so they are only added when `with_dependencies` is set.
 pub async fn build_relations_from_index(
     root: &Path,
+    with_dependencies: bool,
 ) -> Result<normalize_facts_rules_api::Relations, String> {
-    use normalize_facts_rules_api::Relations;
+    use normalize_facts_rules_api::{Relations, RelationsFilter};
 
     let normalize_dir = get_normalize_dir(root);
     let db_path = normalize_dir.join("index.sqlite");
@@ -2107,100 +2294,81 @@ pub async fn build_relations_from_index(
         .await
         .map_err(|e| format!("Failed to open index: {}", e))?;
 
-    let mut relations = Relations::new();
-
-    // Get symbols (file, name, kind, start_line, end_line, parent, visibility)
-    let symbols = idx
-        .all_symbols_with_details()
-        .await
-        .map_err(|e| format!("Failed to get symbols: {}", e))?;
-
-    for (file, name, kind, start_line, end_line, parent, visibility, is_impl) in &symbols {
-        relations.add_symbol(file, name, kind, *start_line as u32);
-        relations.add_symbol_range(file, name, *start_line as u32, *end_line as u32);
-        relations.add_visibility(file, name, visibility);
-        if let Some(parent_name) = parent {
-            relations.add_parent(file, name, parent_name);
-        }
-        if *is_impl {
-            relations.add_is_impl(file, name);
-        }
-    }
-
-    // Get symbol attributes
-    let attrs = idx
-        .all_symbol_attributes()
-        .await
-        .map_err(|e| format!("Failed to get symbol attributes: {}", e))?;
-
-    for (file, name, attribute) in &attrs {
-        relations.add_attribute(file, name, attribute);
+    let mut relations = Relations::from_index(&idx, &RelationsFilter::all()).await?;
+    add_churn_relations(&mut relations, &idx).await?;
+    if with_dependencies {
+        add_dependency_relations(&mut relations, root);
     }
 
-    // Get symbol implements
-    let implements = idx
-        .all_symbol_implements()
-        .await
-        .map_err(|e| format!("Failed to get symbol implements: {}", e))?;
-
-    for (file, name, interface) in &implements {
-        relations.add_implements(file, name, interface);
-    }
+    Ok(relations)
+}
 
-    // Get type methods
-    let type_methods = idx
-        .all_type_methods()
-        .await
-        .map_err(|e| format!("Failed to get type methods: {}", e))?;
+/// Whether any of `rules` reads the `depends_on` relation.
+pub fn rules_read_dependencies(rules: &[interpret::FactsRule]) -> bool {
+    rules.iter().any(|rule| {
+        interpret::compile_rules_source(&rule.source)
+            .relations_used
+            .iter()
+            .any(|name| name == "depends_on")
+    })
+}
 
-    for (file, type_name, method_name) in &type_methods {
-        relations.add_type_method(file, type_name, method_name);
-    }
+/// Window for the `churn(file, commits_90d)` relation.
+const CHURN_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
 
-    // Get imports (file, module, name, line)
-    let imports = idx
-        .all_imports()
+/// Add `churn(file, commits_90d)` facts from the index's git history tables.
+///
+/// The tables are filled by `normalize structure rebuild`; until then (or
+/// outside a git repository) no churn facts are added.
+pub async fn add_churn_relations(
+    relations: &mut normalize_facts_rules_api::Relations,
+    idx: &normalize_facts::FileIndex,
+) -> Result<(), String> {
+    let since = (chrono::Utc::now().timestamp() - CHURN_WINDOW_SECS).max(0) as u64;
+    let churn = idx
+        .file_churn_since(since)
         .await
-        .map_err(|e| format!("Failed to get imports: {}", e))?;
-
-    for (file, module, name, _line) in imports {
-        relations.add_import(&file, &module, &name);
+        .map_err(|e| format!("Failed to get file churn: {}", e))?;
+    for ch in &churn {
+        let commits = u32::try_from(ch.recent_commits).unwrap_or(u32::MAX);
+        relations.add_churn(&ch.file, commits);
     }
+    Ok(())
+}
 
-    // Get calls with qualifiers (caller_file, caller_symbol, callee_name, qualifier, line)
-    let calls = idx
-        .all_calls_with_qualifiers()
-        .await
-        .map_err(|e| format!("Failed to get calls: {}", e))?;
-
-    for (file, caller, callee, qualifier, line) in &calls {
-        relations.add_call(file, caller, callee, *line);
-        if let Some(qual) = qualifier {
-            relations.add_qualifier(file, caller, callee, qual);
+/// Add `depends_on(package, dependency, version)` facts for every ecosystem
+/// whose manifest is present at `root`.
+///
+/// The version is the locally-installed one when the dependency resolves on
+/// disk, otherwise the requirement declared in the manifest.
+pub fn add_dependency_relations(relations: &mut normalize_facts_rules_api::Relations, root: &Path) {
+    let mut seen_manifests = std::collections::HashSet::new();
+    for deps in normalize_local_deps::registry::all_local_deps() {
+        let Some(manifest) = deps
+            .project_manifest_filenames()
+            .iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+        else {
+            continue;
+        };
+        // JavaScript and TypeScript share package.json; scan it once.
+        if !seen_manifests.insert(manifest) {
+            continue;
+        }
+        let Some(graph) = normalize_local_deps::LocalDependencyGraph::build(deps, root) else {
+            continue;
+        };
+        for edge in &graph.edges {
+            let version = graph
+                .node(&edge.to)
+                .and_then(|n| n.version.clone())
+                .or_else(|| edge.version_req.clone())
+                .unwrap_or_default();
+            let manifest = edge.manifest.strip_prefix(root).unwrap_or(&edge.manifest);
+            relations.add_depends_on(&edge.from, &edge.to, &version, &manifest.to_string_lossy());
         }
     }
-
-    // Get CFG edges (Phase 4)
-    let cfg_edges = idx
-        .all_cfg_edges()
-        .await
-        .map_err(|e| format!("Failed to get CFG edges: {}", e))?;
-
-    for (file, func, func_line, from, to, kind, exception_type) in &cfg_edges {
-        relations.add_cfg_edge(file, func, *func_line, *from, *to, kind, exception_type);
-    }
-
-    // Get CFG effects (Phase 3)
-    let cfg_effects = idx
-        .all_cfg_effects()
-        .await
-        .map_err(|e| format!("Failed to get CFG effects: {}", e))?;
-
-    for (file, func, func_line, block, kind, line, label) in &cfg_effects {
-        relations.add_cfg_effect(file, func, *func_line, *block, kind, *line, label);
-    }
-
-    Ok(relations)
 }
 
 // =============================================================================
@@ -2506,18 +2674,41 @@ fn syntax_severity(s: normalize_syntax_rules::Severity) -> normalize_output::dia
 }
 
 /// Convert a facts-rules-api `Diagnostic` into a unified `Issue`.
+/// Convert a fact rule [`Metric`](normalize_facts_rules_api::Metric) to a report metric.
+pub fn abi_metric_to_rule_metric(
+    m: &normalize_facts_rules_api::Metric,
+) -> normalize_output::diagnostics::RuleMetric {
+    normalize_output::diagnostics::RuleMetric {
+        file: m
+            .location
+            .as_ref()
+            .map(|loc| loc.file.to_string())
+            .unwrap_or_default(),
+        line: m
+            .location
+            .as_ref()
+            .map(|loc| loc.line as usize)
+            .filter(|&l| l > 0),
+        rule_id: m.rule_id.clone(),
+        name: m.name.clone(),
+        value: m.value,
+        fields: m.fields.clone(),
+    }
+}
+
 pub fn abi_diagnostic_to_issue(
     d: &normalize_facts_rules_api::Diagnostic,
 ) -> normalize_output::diagnostics::Issue {
     use normalize_output::diagnostics::{Issue, RelatedLocation};
 
-    let (file, line, column) = match &d.location {
+    let (file, line, column, end_line) = match &d.location {
         Some(loc) => (
             loc.file.to_string(),
             Some(loc.line as usize),
             loc.column.map(|c| c as usize),
+            loc.end_line.map(|l| l as usize),
         ),
-        None => (String::new(), None, None),
+        None => (String::new(), None, None, None),
     };
 
     let related = d
@@ -2536,7 +2727,7 @@ pub fn abi_diagnostic_to_issue(
         file,
         line,
         column,
-        end_line: None,
+        end_line,
         end_column: None,
         rule_id: d.rule_id.to_string(),
         message: d.message.to_string(),
@@ -1731,6 +1731,172 @@ impl RulesService {
             Ok(report)
         }
     }
+
+    /// Run fixture tests for Datalog fact rules.
+    ///
+    /// Each `<rule>.dl` under `rules_dir` (defaults to `.normalize/rules/`) with a
+    /// colocated `<rule>.test.toml` or `<rule>.test.json` is evaluated against the
+    /// fixture's input facts, and the diagnostics are compared with its expectations:
+    /// ```toml
+    /// [[case]]
+    /// name = "two-file cycle"
+    /// [case.facts]
+    /// import = [["a.py", "b", "x"], ["b.py", "a", "y"]]
+    /// [[case.expect]]
+    /// file = "a.py"
+    /// ```
+    ///
+    /// Examples:
+    ///   normalize rules test-facts                       # test .normalize/rules/*.dl
+    ///   normalize rules test-facts --rules-dir my/rules  # custom dir
+    #[cli(display_with = "display_output")]
+    pub fn test_facts(
+        &self,
+        #[param(
+            short = 'd',
+            help = "Directory containing .dl rules and fixtures (default: .normalize/rules/)"
+        )]
+        rules_dir: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<RulesFixtureTestReport, String> {
+        let effective_root = root
+            .as_deref()
+            .map(std::path::PathBuf::from)
+            .map(Ok)
+            .unwrap_or_else(std::env::current_dir)
+            .map_err(|e| format!("Failed to get current directory: {e}"))?;
+
+        let dir = match rules_dir {
+            Some(ref d) => {
+                let p = std::path::Path::new(d);
+                if p.is_absolute() {
+                    p.to_path_buf()
+                } else {
+                    effective_root.join(p)
+                }
+            }
+            None => effective_root.join(".normalize").join("rules"),
+        };
+
+        let results = normalize_facts_rules_test::test_rules_dir(&dir)?;
+        if results.is_empty() {
+            return Err(format!(
+                "No fact-rule fixtures found under '{}'. \
+                 Add a <rule>.test.toml next to a <rule>.dl.",
+                dir.display()
+            ));
+        }
+
+        let cases: Vec<FixtureCaseResult> = results
+            .iter()
+            .flat_map(|r| {
+                r.cases.iter().map(|c| FixtureCaseResult {
+                    case: format!("{}: {}", r.rule_id, c.name),
+                    passed: c.passed(),
+                    diff: c.diff(),
+                })
+            })
+            .collect();
+        let passed = cases.iter().filter(|c| c.passed).count();
+        let failed = cases.len() - passed;
+
+        let report = RulesFixtureTestReport {
+            fixture_dir: dir.display().to_string(),
+            cases,
+            passed,
+            failed,
+            updated: false,
+        };
+
+        if failed > 0 {
+            let detail = self.display_output(&report);
+            Err(format!("{detail}\n{failed} fixture case(s) failed"))
+        } else {
+            Ok(report)
+        }
+    }
+
+    /// Watch the project and re-run fact rules as files change
+    ///
+    /// Runs every enabled fact rule once, then re-extracts changed files into the
+    /// index and re-runs only the rules that read a relation whose facts changed.
+    /// Streams JSON lines until Ctrl-C: an `update` event per evaluation (changed
+    /// files, changed relations, re-run rules), followed by `new` and `resolved`
+    /// events carrying the diagnostics that appeared or went away.
+    ///
+    /// Examples:
+    ///   normalize rules watch                        # all enabled fact rules
+    ///   normalize rules watch --rule circular-deps   # a single rule
+    pub async fn watch(
+        &self,
+        #[param(help = "Specific fact rule ID to watch")] rule: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<String, String> {
+        use std::io::Write;
+
+        let effective_root = root
+            .as_deref()
+            .map(std::path::PathBuf::from)
+            .map(Ok)
+            .unwrap_or_else(std::env::current_dir)
+            .map_err(|e| format!("Failed to get current directory: {e}"))?;
+        let config = load_rules_config(&effective_root);
+
+        eprintln!(
+            "Watching {} (press Ctrl-C to stop)",
+            effective_root.display()
+        );
+        let mut stdout = std::io::stdout();
+        crate::watch::watch_fact_rules(&effective_root, &config.rules, rule.as_deref(), |event| {
+            serde_json::to_string(event).is_ok_and(|line| {
+                writeln!(stdout, "{line}")
+                    .and_then(|_| stdout.flush())
+                    .is_ok()
+            })
+        })
+        .await?;
+        Ok("Watch ended".to_string())
+    }
+
+    /// Accept current fact rule violations as a baseline
+    ///
+    /// Writes every current fact rule diagnostic to `.normalize/facts-baseline.json`.
+    /// Later runs (`rules run`, the daemon, `rules watch`) hide diagnostics recorded
+    /// there and report only new ones. Entries match by rule id and a fingerprint of
+    /// file + message, so line shifts don't resurface them. Re-run to shrink the
+    /// baseline after fixing violations.
+    ///
+    /// Examples:
+    ///   normalize rules baseline             # record current fact rule findings
+    #[cli(display_with = "display_output")]
+    pub async fn baseline(
+        &self,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<RuleShowReport, String> {
+        let effective_root = root
+            .as_deref()
+            .map(std::path::PathBuf::from)
+            .map(Ok)
+            .unwrap_or_else(std::env::current_dir)
+            .map_err(|e| format!("Failed to get current directory: {e}"))?;
+        let config = load_rules_config(&effective_root);
+        let count = crate::runner::write_fact_baseline(&effective_root, &config.rules).await?;
+        Ok(RuleShowReport {
+            success: true,
+            message: Some(format!(
+                "Wrote {} baselined finding{} to {}",
+                count,
+                if count == 1 { "" } else { "s" },
+                normalize_facts_rules_interpret::BASELINE_PATH
+            )),
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,198 @@
+//! Watch mode for fact rules.
+//!
+//! [`watch_fact_rules`] evaluates the enabled fact rules once, then watches the
+//! project for file changes. Each batch of changes is re-extracted into the
+//! index (only the changed files), the relations are rebuilt, and only the
+//! rules reading a relation whose contents changed are re-run (see
+//! [`interpret::WatchSession`]). Callers receive a stream of
+//! [`FactsWatchEvent`]s: what was re-run, and which diagnostics appeared or
+//! went away.
+
+use crate::runner::{
+    build_relations_from_index, ensure_relations, get_normalize_dir, load_enabled_fact_rules,
+    load_walk_config, rules_read_dependencies,
+};
+use normalize_facts_rules_api::{Diagnostic, Relations};
+use normalize_facts_rules_interpret as interpret;
+use normalize_rules_config::RulesConfig;
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long to wait after a change for more changes before re-running rules.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// One line of `normalize rules watch` output.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum FactsWatchEvent {
+    /// Rules were (re-)evaluated. Emitted before that evaluation's `new` and
+    /// `resolved` events.
+    Update {
+        /// Files re-extracted into the index (empty for the initial run).
+        changed_files: Vec<String>,
+        /// Input relations whose contents changed.
+        changed_relations: Vec<String>,
+        /// Rules that were re-evaluated.
+        rerun: Vec<String>,
+    },
+    /// A diagnostic that wasn't reported before.
+    New { diagnostic: Diagnostic },
+    /// A previously reported diagnostic that no longer occurs.
+    Resolved { diagnostic: Diagnostic },
+    /// A rule failed to evaluate, or (without `rule`) refreshing the index
+    /// failed; previous diagnostics stand and the watch continues.
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rule: Option<String>,
+        message: String,
+    },
+}
+
+/// Run the enabled fact rules and keep re-running them as files change.
+///
+/// `emit` is called for every event; returning `false` stops the watch.
+/// Rule files are read once at startup; restart the watch to pick up edits to
+/// `.normalize/rules/`.
+pub async fn watch_fact_rules(
+    root: &Path,
+    config: &RulesConfig,
+    filter_rule: Option<&str>,
+    mut emit: impl FnMut(&FactsWatchEvent) -> bool,
+) -> Result<(), String> {
+    // Watcher events carry absolute paths.
+    let root = &std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let rules = load_enabled_fact_rules(root, config, None, filter_rule);
+    if rules.is_empty() {
+        return Err("No enabled fact rules to watch".to_string());
+    }
+    let with_dependencies = rules_read_dependencies(&rules);
+    let mut session = interpret::WatchSession::new(rules).with_inline_allow(root);
+    if let Some(baseline) = interpret::Baseline::load(root)? {
+        session = session.with_baseline(baseline);
+    }
+
+    let relations = ensure_relations(root, with_dependencies).await?;
+    if !emit_delta(&mut emit, Vec::new(), session.update(&relations)) {
+        return Ok(());
+    }
+
+    let normalize_dir = get_normalize_dir(root);
+    let mut idx = normalize_facts::FileIndex::open(&normalize_dir.join("index.sqlite"), root)
+        .await
+        .map_err(|e| format!("Failed to open index: {}", e))?;
+    idx.set_walk_config(load_walk_config(root));
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            for path in event.paths {
+                let _ = tx.send(path);
+            }
+        }
+    })
+    .map_err(|e| format!("Failed to start file watcher: {}", e))?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+
+    // The index lives under the project, so its own writes show up as events.
+    let is_relevant = |path: &Path| {
+        !path.starts_with(&normalize_dir)
+            && !path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .components()
+                .any(|c| c.as_os_str() == ".git")
+    };
+
+    while let Some(first) = rx.recv().await {
+        let mut relevant = is_relevant(&first);
+        tokio::time::sleep(DEBOUNCE).await;
+        while let Ok(path) = rx.try_recv() {
+            relevant |= is_relevant(&path);
+        }
+        if !relevant {
+            continue;
+        }
+
+        let (changed_files, relations) = match refresh(&mut idx, root, with_dependencies).await {
+            Ok(Some(refreshed)) => refreshed,
+            Ok(None) => continue,
+            Err(message) => {
+                if !emit(&FactsWatchEvent::Error {
+                    rule: None,
+                    message,
+                }) {
+                    break;
+                }
+                continue;
+            }
+        };
+        if !emit_delta(&mut emit, changed_files, session.update(&relations)) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Re-extract changed files and rebuild the relations. Returns the changed
+/// files relative to `root`, or `None` when nothing changed.
+async fn refresh(
+    idx: &mut normalize_facts::FileIndex,
+    root: &Path,
+    with_dependencies: bool,
+) -> Result<Option<(Vec<String>, Relations)>, String> {
+    let changed = idx
+        .incremental_refresh_force()
+        .await
+        .map_err(|e| format!("Failed to refresh index: {}", e))?;
+    if changed.is_empty() {
+        return Ok(None);
+    }
+    if let Err(e) = idx.incremental_call_graph_refresh().await {
+        tracing::warn!("call graph refresh failed: {}", e);
+    }
+    let relations = build_relations_from_index(root, with_dependencies).await?;
+    let changed_files = changed
+        .iter()
+        .map(|p| p.strip_prefix(root).unwrap_or(p).display().to_string())
+        .collect();
+    Ok(Some((changed_files, relations)))
+}
+
+/// Emit the events for one update; `false` once `emit` asks to stop.
+fn emit_delta(
+    emit: &mut impl FnMut(&FactsWatchEvent) -> bool,
+    changed_files: Vec<String>,
+    delta: interpret::WatchDelta,
+) -> bool {
+    let mut events = vec![FactsWatchEvent::Update {
+        changed_files,
+        changed_relations: delta.changed_relations,
+        rerun: delta.rerun,
+    }];
+    events.extend(
+        delta
+            .errors
+            .into_iter()
+            .map(|(rule, e)| FactsWatchEvent::Error {
+                rule: Some(rule),
+                message: e.to_string(),
+            }),
+    );
+    events.extend(
+        delta
+            .resolved
+            .into_iter()
+            .map(|diagnostic| FactsWatchEvent::Resolved { diagnostic }),
+    );
+    events.extend(
+        delta
+            .new
+            .into_iter()
+            .map(|diagnostic| FactsWatchEvent::New { diagnostic }),
+    );
+    events.iter().all(emit)
+}
@@ -456,6 +456,8 @@ fn collect_binding_identifiers(
 mod tests {
     use super::*;
 
+    /// Debugging aid for writing scope queries; call from a test to dump a parse tree.
+    #[allow(dead_code)]
     fn print_tree(lang: &str, src: &str, loader: &GrammarLoader) {
         let Some(grammar) = loader.get(lang).ok() else {
             eprintln!("no grammar for {lang}");
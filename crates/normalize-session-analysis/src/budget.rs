@@ -0,0 +1,501 @@
+//! Cost budgets: per-project USD limits checked against a ledger of session
+//! costs that persists across invocations.
+//!
+//! Session logs are pruned by their agents (Claude Code keeps about a month),
+//! so month-to-date spend cannot always be recomputed from the logs alone.
+//! Every costed session is recorded in a [`CostLedger`] keyed by session id;
+//! re-recording a session overwrites its entry, so repeated runs never
+//! double-count. [`BudgetReport::build`] rolls the ledger up per project and
+//! flags days and sessions over their limits.
+
+use crate::{TrendBucket, date_from_days, days_from_date};
+use normalize_output::OutputFormatter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Spending limits in USD. Unset limits are not checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct BudgetLimits {
+    pub daily_usd: Option<f64>,
+    pub weekly_usd: Option<f64>,
+    pub monthly_usd: Option<f64>,
+}
+
+impl BudgetLimits {
+    pub fn is_empty(&self) -> bool {
+        self.daily_usd.is_none() && self.weekly_usd.is_none() && self.monthly_usd.is_none()
+    }
+
+    /// `self` with unset limits filled in from `fallback`.
+    pub fn or(self, fallback: BudgetLimits) -> BudgetLimits {
+        BudgetLimits {
+            daily_usd: self.daily_usd.or(fallback.daily_usd),
+            weekly_usd: self.weekly_usd.or(fallback.weekly_usd),
+            monthly_usd: self.monthly_usd.or(fallback.monthly_usd),
+        }
+    }
+}
+
+/// Session cost budgets (`[sessions.budget]`).
+///
+/// ```toml
+/// [sessions.budget]
+/// daily_usd = 20.0            # applies to each project on its own
+/// monthly_usd = 300.0
+///
+/// [sessions.budget.projects.normalize]
+/// monthly_usd = 50.0          # overrides monthly; daily still 20.0
+/// ```
+///
+/// Projects are named as in `sessions stats --by-repo`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct BudgetConfig {
+    #[serde(flatten)]
+    pub default: BudgetLimits,
+    /// Per-project limits; unset fields fall back to the defaults above.
+    pub projects: BTreeMap<String, BudgetLimits>,
+}
+
+impl BudgetConfig {
+    /// Whether any limit is configured at all.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_empty() && self.projects.values().all(BudgetLimits::is_empty)
+    }
+
+    /// Effective limits for `project`.
+    pub fn limits_for(&self, project: &str) -> BudgetLimits {
+        match self.projects.get(project) {
+            Some(limits) => limits.or(self.default),
+            None => self.default,
+        }
+    }
+}
+
+/// One session's cost as recorded in the ledger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LedgerEntry {
+    pub project: String,
+    /// Day the session is billed to (`YYYY-MM-DD`, UTC).
+    pub date: String,
+    pub cost_usd: f64,
+}
+
+/// Accumulated session costs, keyed by session id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CostLedger {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, LedgerEntry>,
+}
+
+impl CostLedger {
+    /// `$XDG_DATA_HOME/normalize/session-costs.json`, else
+    /// `~/.local/share/normalize/session-costs.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = if let Ok(data) = std::env::var("XDG_DATA_HOME") {
+            PathBuf::from(data)
+        } else if let Ok(home) = std::env::var("HOME") {
+            PathBuf::from(home).join(".local/share")
+        } else if let Ok(home) = std::env::var("USERPROFILE") {
+            PathBuf::from(home).join(".local/share")
+        } else {
+            return None;
+        };
+        Some(base.join("normalize").join("session-costs.json"))
+    }
+
+    /// Load the ledger at `path`; a missing file is an empty ledger.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Error parsing {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Error reading {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Record (or replace) the cost of one session.
+    pub fn record(&mut self, session_id: &str, entry: LedgerEntry) {
+        self.sessions.insert(session_id.to_string(), entry);
+    }
+
+    /// Merge `entries` into the ledger at `path` and return the result.
+    ///
+    /// The read-modify-write runs under an exclusive lock and the file is
+    /// replaced atomically, so concurrent invocations never lose entries.
+    pub fn update(
+        path: &Path,
+        entries: impl IntoIterator<Item = (String, LedgerEntry)>,
+    ) -> Result<Self, String> {
+        let _lock = normalize_cache::CacheLock::exclusive(path)
+            .map_err(|e| format!("Failed to lock {}: {}", path.display(), e))?;
+        let mut ledger = Self::load(path)?;
+        for (id, entry) in entries {
+            ledger.record(&id, entry);
+        }
+        let json = serde_json::to_string_pretty(&ledger)
+            .map_err(|e| format!("Failed to serialize cost ledger: {}", e))?;
+        normalize_cache::write_atomic(path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(ledger)
+    }
+}
+
+/// Today's date (`YYYY-MM-DD`, UTC).
+pub fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    date_from_days((secs / 86_400) as i64)
+}
+
+/// Spend rollup and limit checks for one project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ProjectBudget {
+    pub project: String,
+    pub today_usd: f64,
+    pub week_usd: f64,
+    pub month_usd: f64,
+    /// Month-to-date spend extrapolated to the end of the month.
+    pub projected_month_usd: f64,
+    pub limits: BudgetLimits,
+    /// Limits currently exceeded: `daily`, `weekly`, `monthly`, or
+    /// `projected monthly` (on track to exceed the monthly limit).
+    pub exceeded: Vec<String>,
+}
+
+/// A day or a single session that cost more than its daily limit.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BudgetBreach {
+    pub project: String,
+    /// `day` or `session`.
+    pub kind: String,
+    /// The date for days, the session id for sessions.
+    pub key: String,
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+}
+
+/// Per-project cost rollups for the current day, week and month, checked
+/// against [`BudgetConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BudgetReport {
+    /// Reference date (`YYYY-MM-DD`) the periods are computed from.
+    pub today: String,
+    /// Projects with spend this month or a configured budget, highest
+    /// month-to-date spend first.
+    pub projects: Vec<ProjectBudget>,
+    /// Days and sessions this month over their project's daily limit.
+    pub breaches: Vec<BudgetBreach>,
+}
+
+impl BudgetReport {
+    /// Roll `ledger` up per project for the day, ISO week and calendar month
+    /// containing `today` (`YYYY-MM-DD`).
+    pub fn build(config: &BudgetConfig, ledger: &CostLedger, today: &str) -> Self {
+        let month = &today[..today.len().min(7)];
+        let week = TrendBucket::Week.key(today);
+        let (day_of_month, days_in_month) = month_progress(today);
+
+        let mut projects: BTreeMap<&str, ProjectBudget> = BTreeMap::new();
+        let mut daily: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+        let mut breaches = Vec::new();
+        for name in config.projects.keys() {
+            projects
+                .entry(name)
+                .or_insert_with(|| empty_project(name, config));
+        }
+        for (id, entry) in &ledger.sessions {
+            if !entry.date.starts_with(month) {
+                continue;
+            }
+            let project = projects
+                .entry(&entry.project)
+                .or_insert_with(|| empty_project(&entry.project, config));
+            project.month_usd += entry.cost_usd;
+            if TrendBucket::Week.key(&entry.date) == week {
+                project.week_usd += entry.cost_usd;
+            }
+            if entry.date == today {
+                project.today_usd += entry.cost_usd;
+            }
+            *daily.entry((&entry.project, &entry.date)).or_default() += entry.cost_usd;
+            if let Some(limit) = project.limits.daily_usd
+                && entry.cost_usd > limit
+            {
+                breaches.push(BudgetBreach {
+                    project: entry.project.clone(),
+                    kind: "session".to_string(),
+                    key: id.clone(),
+                    spent_usd: entry.cost_usd,
+                    limit_usd: limit,
+                });
+            }
+        }
+        for ((project, date), spent) in daily {
+            if let Some(limit) = config.limits_for(project).daily_usd
+                && spent > limit
+            {
+                breaches.push(BudgetBreach {
+                    project: project.to_string(),
+                    kind: "day".to_string(),
+                    key: date.to_string(),
+                    spent_usd: spent,
+                    limit_usd: limit,
+                });
+            }
+        }
+
+        let mut projects: Vec<ProjectBudget> = projects.into_values().collect();
+        for p in &mut projects {
+            p.projected_month_usd = p.month_usd / day_of_month as f64 * days_in_month as f64;
+            let checks = [
+                ("daily", p.today_usd, p.limits.daily_usd),
+                ("weekly", p.week_usd, p.limits.weekly_usd),
+                ("monthly", p.month_usd, p.limits.monthly_usd),
+            ];
+            for (name, spent, limit) in checks {
+                if limit.is_some_and(|l| spent > l) {
+                    p.exceeded.push(name.to_string());
+                }
+            }
+            if let Some(limit) = p.limits.monthly_usd
+                && p.month_usd <= limit
+                && p.projected_month_usd > limit
+            {
+                p.exceeded.push("projected monthly".to_string());
+            }
+        }
+        projects.sort_by(|a, b| {
+            b.month_usd
+                .total_cmp(&a.month_usd)
+                .then_with(|| a.project.cmp(&b.project))
+        });
+        breaches.sort_by(|a, b| (&a.project, &a.kind, &a.key).cmp(&(&b.project, &b.kind, &b.key)));
+
+        Self {
+            today: today.to_string(),
+            projects,
+            breaches,
+        }
+    }
+
+    /// Whether any project is over (or projected over) a limit.
+    pub fn over_budget(&self) -> bool {
+        self.projects.iter().any(|p| !p.exceeded.is_empty())
+    }
+
+    fn lines(&self, pretty: bool) -> Vec<String> {
+        let (bold, red, yellow, reset) = if pretty {
+            ("\x1b[1m", "\x1b[31m", "\x1b[33m", "\x1b[0m")
+        } else {
+            ("", "", "", "")
+        };
+        let mut lines = vec![
+            format!("{bold}Budget ({}):{reset}", self.today),
+            format!(
+                "  {:<24} {:>9} {:>9} {:>9} {:>10}  limits (day/week/month)",
+                "project", "today", "week", "month", "projected"
+            ),
+        ];
+        for p in &self.projects {
+            let limits = [
+                p.limits.daily_usd,
+                p.limits.weekly_usd,
+                p.limits.monthly_usd,
+            ]
+            .iter()
+            .map(|l| l.map_or_else(|| "-".to_string(), |l| format!("${:.2}", l)))
+            .collect::<Vec<_>>()
+            .join("/");
+            let row = format!(
+                "  {:<24} {:>9} {:>9} {:>9} {:>10}  {}",
+                p.project,
+                format!("${:.2}", p.today_usd),
+                format!("${:.2}", p.week_usd),
+                format!("${:.2}", p.month_usd),
+                format!("${:.2}", p.projected_month_usd),
+                limits,
+            );
+            if p.exceeded.is_empty() {
+                lines.push(row);
+            } else {
+                let color = if p.exceeded.iter().all(|e| e == "projected monthly") {
+                    yellow
+                } else {
+                    red
+                };
+                lines.push(format!(
+                    "{color}{row}  over: {}{reset}",
+                    p.exceeded.join(", ")
+                ));
+            }
+        }
+        if !self.breaches.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{bold}Over daily budget this month:{reset}"));
+            for b in &self.breaches {
+                lines.push(format!(
+                    "  {:<24} {:<7} {:<36} ${:.2} > ${:.2}",
+                    b.project, b.kind, b.key, b.spent_usd, b.limit_usd
+                ));
+            }
+        }
+        lines
+    }
+}
+
+impl OutputFormatter for BudgetReport {
+    fn format_text(&self) -> String {
+        self.lines(false).join("\n")
+    }
+
+    fn format_pretty(&self) -> String {
+        self.lines(true).join("\n")
+    }
+}
+
+fn empty_project(name: &str, config: &BudgetConfig) -> ProjectBudget {
+    ProjectBudget {
+        project: name.to_string(),
+        today_usd: 0.0,
+        week_usd: 0.0,
+        month_usd: 0.0,
+        projected_month_usd: 0.0,
+        limits: config.limits_for(name),
+        exceeded: Vec::new(),
+    }
+}
+
+/// `(day of month, days in month)` for a `YYYY-MM-DD` date; `(1, 30)` if it
+/// does not parse.
+fn month_progress(date: &str) -> (i64, i64) {
+    let parsed = (|| {
+        let today = days_from_date(date)?;
+        let (year, month) = (date.get(..4)?, date.get(5..7)?);
+        let first = days_from_date(&format!("{year}-{month}-01"))?;
+        let next = if month == "12" {
+            let year: i64 = year.parse().ok()?;
+            days_from_date(&format!("{:04}-01-01", year + 1))?
+        } else {
+            let month: i64 = month.parse().ok()?;
+            days_from_date(&format!("{year}-{:02}-01", month + 1))?
+        };
+        Some((today - first + 1, next - first))
+    })();
+    parsed.unwrap_or((1, 30))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(project: &str, date: &str, cost_usd: f64) -> LedgerEntry {
+        LedgerEntry {
+            project: project.to_string(),
+            date: date.to_string(),
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn project_limits_fall_back_to_defaults() {
+        let config: BudgetConfig = toml::from_str(
+            "daily_usd = 20.0\nmonthly_usd = 300.0\n\n[projects.app]\nmonthly_usd = 50.0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.limits_for("app"),
+            BudgetLimits {
+                daily_usd: Some(20.0),
+                weekly_usd: None,
+                monthly_usd: Some(50.0),
+            }
+        );
+        assert_eq!(config.limits_for("other").monthly_usd, Some(300.0));
+        assert!(!config.is_empty());
+        assert!(BudgetConfig::default().is_empty());
+    }
+
+    #[test]
+    fn month_progress_handles_lengths() {
+        assert_eq!(month_progress("2026-02-14"), (14, 28));
+        assert_eq!(month_progress("2028-02-29"), (29, 29));
+        assert_eq!(month_progress("2026-12-31"), (31, 31));
+    }
+
+    #[test]
+    fn report_rolls_up_and_flags_breaches() {
+        let config: BudgetConfig = toml::from_str(
+            "daily_usd = 10.0\n\n[projects.app]\nweekly_usd = 30.0\nmonthly_usd = 60.0\n",
+        )
+        .unwrap();
+        let mut ledger = CostLedger::default();
+        // 2026-10-15 is a Thursday; its week starts Monday 2026-10-12.
+        ledger.record("a", entry("app", "2026-10-15", 4.0));
+        ledger.record("b", entry("app", "2026-10-15", 8.0));
+        ledger.record("c", entry("app", "2026-10-12", 12.0));
+        ledger.record("d", entry("app", "2026-10-02", 6.0));
+        ledger.record("e", entry("app", "2026-09-30", 100.0));
+        ledger.record("f", entry("lib", "2026-10-15", 1.0));
+
+        let report = BudgetReport::build(&config, &ledger, "2026-10-15");
+        let app = &report.projects[0];
+        assert_eq!(app.project, "app");
+        assert_eq!(app.today_usd, 12.0);
+        assert_eq!(app.week_usd, 24.0);
+        assert_eq!(app.month_usd, 30.0);
+        // 30 spent over 15 of 31 days.
+        assert!((app.projected_month_usd - 62.0).abs() < 1e-9);
+        assert_eq!(app.exceeded, vec!["daily", "projected monthly"]);
+
+        let lib = &report.projects[1];
+        assert_eq!(lib.month_usd, 1.0);
+        assert!(lib.exceeded.is_empty());
+
+        let breaches: Vec<(&str, &str)> = report
+            .breaches
+            .iter()
+            .map(|b| (b.kind.as_str(), b.key.as_str()))
+            .collect();
+        assert_eq!(
+            breaches,
+            vec![
+                ("day", "2026-10-12"),
+                ("day", "2026-10-15"),
+                ("session", "c")
+            ]
+        );
+        assert!(report.over_budget());
+        assert!(
+            report
+                .format_text()
+                .contains("over: daily, projected monthly")
+        );
+    }
+
+    #[test]
+    fn ledger_updates_replace_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/session-costs.json");
+        assert!(CostLedger::load(&path).unwrap().sessions.is_empty());
+
+        CostLedger::update(&path, [("s1".to_string(), entry("app", "2026-10-01", 1.0))]).unwrap();
+        let ledger = CostLedger::update(
+            &path,
+            [
+                ("s1".to_string(), entry("app", "2026-10-01", 2.5)),
+                ("s2".to_string(), entry("lib", "2026-10-02", 1.0)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(ledger.sessions.len(), 2);
+        assert_eq!(CostLedger::load(&path).unwrap(), ledger);
+        assert_eq!(ledger.sessions["s1"].cost_usd, 2.5);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(CostLedger::load(&path).is_err());
+    }
+}
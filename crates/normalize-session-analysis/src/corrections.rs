@@ -0,0 +1,361 @@
+//! Correction detection: phrase packs and regex patterns that flag assistant
+//! apologies, acknowledged mistakes and self-corrections.
+//!
+//! The bundled table (`corrections.toml` in this crate) has English, German,
+//! French, Spanish, Portuguese, Japanese and Chinese packs for four
+//! categories. A project's `.normalize/corrections.toml` can narrow the
+//! languages, add phrases or regexes to a category, replace a category's
+//! bundled phrases outright, or define new categories:
+//!
+//! ```toml
+//! languages = ["en", "de"]          # packs to use (default: all)
+//!
+//! [[category]]
+//! id = "apology"                    # existing id: merged into the bundled one
+//! phrases.en = ["oops"]
+//! patterns = ['(?i)\bmea culpa\b']  # regexes, matched against the original text
+//!
+//! [[category]]
+//! id = "mistake"
+//! replace = true                    # drop the bundled phrases and patterns first
+//! phrases.en = ["i goofed"]
+//!
+//! [[category]]
+//! id = "scope_creep"                # new category, tried after the bundled ones
+//! label = "Scope creep"
+//! phrases.en = ["while i'm at it"]
+//! ```
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const BUNDLED_CORRECTIONS: &str = include_str!("../corrections.toml");
+
+/// Longest excerpt kept for a detected correction, in characters.
+const EXCERPT_CHARS: usize = 80;
+
+/// Category of a correction, identified by its config id (`apology`,
+/// `mistake`, `let_me_fix`, `actually`, or a project-defined id).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(transparent)]
+pub struct CorrectionKind(Cow<'static, str>);
+
+impl CorrectionKind {
+    pub const APOLOGY: CorrectionKind = CorrectionKind(Cow::Borrowed("apology"));
+    pub const MISTAKE: CorrectionKind = CorrectionKind(Cow::Borrowed("mistake"));
+    pub const LET_ME_FIX: CorrectionKind = CorrectionKind(Cow::Borrowed("let_me_fix"));
+    pub const ACTUALLY: CorrectionKind = CorrectionKind(Cow::Borrowed("actually"));
+
+    pub fn new(id: impl Into<String>) -> Self {
+        CorrectionKind(Cow::Owned(id.into()))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// Display label from the active [`CorrectionPatterns`], falling back to
+    /// the id for categories it does not define.
+    pub fn as_str(&self) -> &str {
+        CorrectionPatterns::active()
+            .label(self)
+            .unwrap_or(self.id())
+    }
+}
+
+/// One compiled detection category.
+#[derive(Debug, Clone)]
+pub struct CorrectionCategory {
+    pub kind: CorrectionKind,
+    pub label: String,
+    /// Lowercased phrases from the enabled language packs.
+    pub phrases: Vec<String>,
+    pub patterns: Vec<Regex>,
+}
+
+/// Phrase packs and regexes used by [`detect_correction`].
+#[derive(Debug, Clone, Default)]
+pub struct CorrectionPatterns {
+    categories: Vec<CorrectionCategory>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CorrectionsFile {
+    languages: Option<Vec<String>>,
+    #[serde(default)]
+    category: Vec<CategoryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryEntry {
+    id: String,
+    label: Option<String>,
+    #[serde(default)]
+    replace: bool,
+    /// Phrases per language pack.
+    #[serde(default)]
+    phrases: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    patterns: Vec<String>,
+}
+
+impl CorrectionsFile {
+    fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|e| format!("Failed to parse correction patterns: {}", e))
+    }
+
+    /// Fold a project file over this one: categories merge by id, new ones
+    /// are appended, and the project's `languages` wins.
+    fn merge(&mut self, project: CorrectionsFile) {
+        if project.languages.is_some() {
+            self.languages = project.languages;
+        }
+        for entry in project.category {
+            let Some(existing) = self.category.iter_mut().find(|c| c.id == entry.id) else {
+                self.category.push(entry);
+                continue;
+            };
+            if entry.replace {
+                existing.phrases.clear();
+                existing.patterns.clear();
+            }
+            if entry.label.is_some() {
+                existing.label = entry.label;
+            }
+            for (lang, phrases) in entry.phrases {
+                existing.phrases.entry(lang).or_default().extend(phrases);
+            }
+            existing.patterns.extend(entry.patterns);
+        }
+    }
+
+    fn compile(self) -> Result<CorrectionPatterns, String> {
+        let languages = self.languages;
+        let categories = self
+            .category
+            .into_iter()
+            .map(|entry| {
+                let phrases = entry
+                    .phrases
+                    .into_iter()
+                    .filter(|(lang, _)| languages.as_ref().is_none_or(|l| l.contains(lang)))
+                    .flat_map(|(_, phrases)| phrases)
+                    .map(|p| p.to_lowercase())
+                    .collect();
+                let patterns = entry
+                    .patterns
+                    .iter()
+                    .map(|p| {
+                        Regex::new(p).map_err(|e| {
+                            format!("Invalid pattern '{}' in category '{}': {}", p, entry.id, e)
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(CorrectionCategory {
+                    label: entry.label.unwrap_or_else(|| entry.id.clone()),
+                    kind: CorrectionKind::new(entry.id),
+                    phrases,
+                    patterns,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(CorrectionPatterns { categories })
+    }
+}
+
+static ACTIVE_CORRECTIONS: std::sync::OnceLock<CorrectionPatterns> = std::sync::OnceLock::new();
+
+impl CorrectionPatterns {
+    /// Parse a standalone corrections file (every language pack enabled
+    /// unless it sets `languages`).
+    pub fn parse(source: &str) -> Result<Self, String> {
+        CorrectionsFile::parse(source)?.compile()
+    }
+
+    /// The compiled-in defaults (`corrections.toml` in this crate).
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CORRECTIONS).expect("bundled corrections.toml is valid")
+    }
+
+    /// Bundled defaults merged with `<root>/.normalize/corrections.toml` if it
+    /// exists.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let mut file =
+            CorrectionsFile::parse(BUNDLED_CORRECTIONS).expect("bundled corrections.toml is valid");
+        let path = root.join(".normalize").join("corrections.toml");
+        if path.exists() {
+            let source = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+            let project = CorrectionsFile::parse(&source)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            file.merge(project);
+        }
+        file.compile()
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Make `patterns` the ones [`detect_correction`] uses.
+    ///
+    /// Only the first call per process takes effect; returns `false` if
+    /// patterns were already active.
+    pub fn install(patterns: CorrectionPatterns) -> bool {
+        ACTIVE_CORRECTIONS.set(patterns).is_ok()
+    }
+
+    /// The installed patterns, or the bundled defaults if none were installed.
+    pub fn active() -> &'static CorrectionPatterns {
+        ACTIVE_CORRECTIONS.get_or_init(Self::bundled)
+    }
+
+    pub fn categories(&self) -> &[CorrectionCategory] {
+        &self.categories
+    }
+
+    /// Display label of `kind`, if this table defines it.
+    pub fn label(&self, kind: &CorrectionKind) -> Option<&str> {
+        self.categories
+            .iter()
+            .find(|c| &c.kind == kind)
+            .map(|c| c.label.as_str())
+    }
+
+    /// First category whose phrases or patterns match `text`, with an
+    /// excerpt starting at the match.
+    pub fn detect(&self, text: &str) -> Option<(CorrectionKind, String)> {
+        let lower = text.to_lowercase();
+        for category in &self.categories {
+            // Lowercasing can change byte lengths, so map the match back to
+            // the original text by character index.
+            let phrase_hit = category.phrases.iter().find_map(|p| {
+                lower
+                    .find(p.as_str())
+                    .map(|pos| lower[..pos].chars().count())
+            });
+            let start = phrase_hit.or_else(|| {
+                category
+                    .patterns
+                    .iter()
+                    .find_map(|re| re.find(text))
+                    .map(|m| text[..m.start()].chars().count())
+            });
+            if let Some(start) = start {
+                let excerpt = text.chars().skip(start).take(EXCERPT_CHARS).collect();
+                return Some((category.kind.clone(), excerpt));
+            }
+        }
+        None
+    }
+}
+
+/// Detect correction patterns in assistant text using the active
+/// [`CorrectionPatterns`].
+/// Returns (category, excerpt) if a correction is found.
+pub fn detect_correction(text: &str) -> Option<(CorrectionKind, String)> {
+    CorrectionPatterns::active().detect(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_packs_cover_several_languages() {
+        let patterns = CorrectionPatterns::bundled();
+        let detect = |text| patterns.detect(text);
+        assert_eq!(
+            detect("Sorry — I apologize for that."),
+            Some((CorrectionKind::APOLOGY, "I apologize for that.".to_string()))
+        );
+        assert_eq!(
+            detect("Oh, das war falsch.").map(|(k, _)| k),
+            Some(CorrectionKind::MISTAKE)
+        );
+        assert_eq!(
+            detect("En fait, le test échoue.").map(|(k, _)| k),
+            Some(CorrectionKind::ACTUALLY)
+        );
+        assert_eq!(
+            detect("申し訳ありません。修正します。"),
+            Some((
+                CorrectionKind::APOLOGY,
+                "申し訳ありません。修正します。".to_string()
+            ))
+        );
+        assert_eq!(detect("All tests pass."), None);
+        assert_eq!(CorrectionKind::LET_ME_FIX.as_str(), "Let me fix");
+    }
+
+    #[test]
+    fn project_file_narrows_extends_and_adds_categories() {
+        let mut file = CorrectionsFile::parse(BUNDLED_CORRECTIONS).unwrap();
+        file.merge(
+            CorrectionsFile::parse(
+                r#"
+languages = ["en"]
+
+[[category]]
+id = "apology"
+phrases.en = ["oops"]
+patterns = ['(?i)\bmea culpa\b']
+
+[[category]]
+id = "mistake"
+replace = true
+phrases.en = ["i goofed"]
+
+[[category]]
+id = "scope_creep"
+label = "Scope creep"
+phrases.en = ["while i'm at it"]
+"#,
+            )
+            .unwrap(),
+        );
+        let patterns = file.compile().unwrap();
+        let kind = |text| patterns.detect(text).map(|(k, _)| k);
+
+        assert_eq!(kind("Oops, wrong file."), Some(CorrectionKind::APOLOGY));
+        assert_eq!(
+            kind("Mea culpa: wrong path."),
+            Some(CorrectionKind::APOLOGY)
+        );
+        assert_eq!(kind("Es tut mir leid."), None);
+        assert_eq!(kind("My mistake, retrying."), None);
+        assert_eq!(kind("I goofed."), Some(CorrectionKind::MISTAKE));
+        let scope = CorrectionKind::new("scope_creep");
+        assert_eq!(kind("While I'm at it, renaming."), Some(scope.clone()));
+        assert_eq!(patterns.label(&scope), Some("Scope creep"));
+        assert_eq!(patterns.categories().len(), 5);
+    }
+
+    #[test]
+    fn invalid_regex_is_reported() {
+        let err = CorrectionPatterns::parse(
+            r#"
+[[category]]
+id = "x"
+patterns = ["(unclosed"]
+"#,
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("Invalid pattern '(unclosed' in category 'x'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn kinds_keep_their_serialized_ids() {
+        assert_eq!(
+            serde_json::to_string(&CorrectionKind::LET_ME_FIX).unwrap(),
+            "\"let_me_fix\""
+        );
+        let kind: CorrectionKind = serde_json::from_str("\"scope_creep\"").unwrap();
+        assert_eq!(kind.id(), "scope_creep");
+    }
+}
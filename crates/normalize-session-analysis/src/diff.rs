@@ -0,0 +1,603 @@
+//! Before/after comparison of two session analyses.
+//!
+//! [`diff_analyses`] lines up the metrics that move when an agent prompt or
+//! configuration changes — tool success, token efficiency, cost, retry
+//! hotspots and context growth — so a tuning run can be judged against a
+//! baseline session (or an aggregate of several) at a glance.
+
+use crate::{SessionAnalysisReport, format_tokens};
+use normalize_output::OutputFormatter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// How a [`MetricChange`] value is measured, for formatting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricUnit {
+    Count,
+    Tokens,
+    /// A ratio in 0.0–1.0, shown as a percentage.
+    Ratio,
+    Usd,
+}
+
+impl MetricUnit {
+    fn format(self, value: f64) -> String {
+        match self {
+            MetricUnit::Count => format!("{:.0}", value),
+            MetricUnit::Tokens => format_tokens(value.max(0.0).round() as u64),
+            MetricUnit::Ratio => format!("{:.1}%", value * 100.0),
+            MetricUnit::Usd => format!("${:.2}", value),
+        }
+    }
+
+    fn format_delta(self, delta: f64) -> String {
+        let sign = if delta < 0.0 { "-" } else { "+" };
+        match self {
+            MetricUnit::Ratio => format!("{}{:.1}pp", sign, delta.abs() * 100.0),
+            _ => format!("{}{}", sign, self.format(delta.abs())),
+        }
+    }
+}
+
+/// One scalar metric in both sessions.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MetricChange {
+    /// Grouping for display: `tools`, `tokens`, `cost` or `context`.
+    pub section: String,
+    pub metric: String,
+    pub before: f64,
+    pub after: f64,
+    pub unit: MetricUnit,
+    /// Whether a decrease is an improvement (errors, cost) rather than an
+    /// increase (success rate, uniqueness).
+    pub lower_is_better: bool,
+}
+
+impl MetricChange {
+    fn new(
+        section: &str,
+        metric: &str,
+        before: f64,
+        after: f64,
+        unit: MetricUnit,
+        lower_is_better: bool,
+    ) -> Self {
+        Self {
+            section: section.to_string(),
+            metric: metric.to_string(),
+            before,
+            after,
+            unit,
+            lower_is_better,
+        }
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+
+    /// Relative change in percent; `None` when `before` is zero.
+    pub fn percent_change(&self) -> Option<f64> {
+        (self.before != 0.0).then(|| self.delta() / self.before.abs() * 100.0)
+    }
+
+    /// `Some(true)` if the change is an improvement, `None` if unchanged.
+    pub fn improved(&self) -> Option<bool> {
+        let delta = self.delta();
+        if delta.abs() < f64::EPSILON {
+            None
+        } else {
+            Some((delta < 0.0) == self.lower_is_better)
+        }
+    }
+
+    fn change_str(&self) -> String {
+        if self.improved().is_none() {
+            return "=".to_string();
+        }
+        let mut s = self.unit.format_delta(self.delta());
+        if self.unit != MetricUnit::Ratio
+            && let Some(pct) = self.percent_change()
+        {
+            s.push_str(&format!(" ({:+.0}%)", pct));
+        }
+        s
+    }
+}
+
+/// Calls and success rate of one tool in both sessions.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ToolChange {
+    pub tool: String,
+    pub before_calls: usize,
+    pub after_calls: usize,
+    /// Success rate (0.0–1.0); `None` if the tool was not called.
+    pub before_success: Option<f64>,
+    pub after_success: Option<f64>,
+}
+
+/// A retry hotspot present in either session.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RetryHotspotChange {
+    pub pattern: String,
+    pub before_failures: usize,
+    pub after_failures: usize,
+    pub before_attempts: usize,
+    pub after_attempts: usize,
+}
+
+impl RetryHotspotChange {
+    /// `new`, `resolved`, `worse`, `better` or `same`.
+    pub fn status(&self) -> &'static str {
+        match (self.before_attempts, self.after_attempts) {
+            (0, _) => "new",
+            (_, 0) => "resolved",
+            _ if self.after_failures > self.before_failures => "worse",
+            _ if self.after_failures < self.before_failures => "better",
+            _ => "same",
+        }
+    }
+}
+
+/// Differences between a baseline (`before`) and a candidate (`after`)
+/// session analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AnalysisDiff {
+    pub before: PathBuf,
+    pub after: PathBuf,
+    pub metrics: Vec<MetricChange>,
+    /// Tools called in either session, most calls first.
+    pub tools: Vec<ToolChange>,
+    /// Retry hotspots from either session, largest failure change first.
+    pub retry_hotspots: Vec<RetryHotspotChange>,
+}
+
+/// Compare two analyses, treating `a` as the baseline and `b` as the
+/// candidate.
+pub fn diff_analyses(a: &SessionAnalysisReport, b: &SessionAnalysisReport) -> AnalysisDiff {
+    use MetricUnit::*;
+
+    let mut metrics = vec![
+        MetricChange::new(
+            "tools",
+            "turns",
+            a.total_turns as f64,
+            b.total_turns as f64,
+            Count,
+            true,
+        ),
+        MetricChange::new(
+            "tools",
+            "tool calls",
+            a.total_tool_calls() as f64,
+            b.total_tool_calls() as f64,
+            Count,
+            true,
+        ),
+        MetricChange::new(
+            "tools",
+            "tool errors",
+            a.total_errors() as f64,
+            b.total_errors() as f64,
+            Count,
+            true,
+        ),
+        MetricChange::new(
+            "tokens",
+            "input tokens",
+            (a.token_stats.total_input + a.token_stats.cache_read) as f64,
+            (b.token_stats.total_input + b.token_stats.cache_read) as f64,
+            Tokens,
+            true,
+        ),
+        MetricChange::new(
+            "tokens",
+            "output tokens",
+            a.token_stats.total_output as f64,
+            b.token_stats.total_output as f64,
+            Tokens,
+            true,
+        ),
+    ];
+    // A session without tool calls has no meaningful success rate.
+    if a.total_tool_calls() > 0 && b.total_tool_calls() > 0 {
+        metrics.insert(
+            3,
+            MetricChange::new(
+                "tools",
+                "success rate",
+                a.overall_success_rate(),
+                b.overall_success_rate(),
+                Ratio,
+                false,
+            ),
+        );
+    }
+    if let (Some(da), Some(db)) = (&a.dedup_tokens, &b.dedup_tokens) {
+        metrics.push(MetricChange::new(
+            "tokens",
+            "uniqueness ratio",
+            da.uniqueness_ratio,
+            db.uniqueness_ratio,
+            Ratio,
+            false,
+        ));
+        let redundant = |d: &crate::DedupTokenStats| {
+            d.total_billed
+                .saturating_sub(d.unique_input + d.unique_output) as f64
+        };
+        metrics.push(MetricChange::new(
+            "tokens",
+            "redundant context",
+            redundant(da),
+            redundant(db),
+            Tokens,
+            true,
+        ));
+    }
+    if let (Some(ca), Some(cb)) = (a.actual_cost, b.actual_cost) {
+        metrics.push(MetricChange::new("cost", "total cost", ca, cb, Usd, true));
+        if a.total_turns > 0 && b.total_turns > 0 {
+            metrics.push(MetricChange::new(
+                "cost",
+                "cost per turn",
+                ca / a.total_turns as f64,
+                cb / b.total_turns as f64,
+                Usd,
+                true,
+            ));
+        }
+    }
+    metrics.extend([
+        MetricChange::new(
+            "context",
+            "average context",
+            a.token_stats.avg_context() as f64,
+            b.token_stats.avg_context() as f64,
+            Tokens,
+            true,
+        ),
+        MetricChange::new(
+            "context",
+            "peak context",
+            a.token_stats.max_context as f64,
+            b.token_stats.max_context as f64,
+            Tokens,
+            true,
+        ),
+        MetricChange::new(
+            "context",
+            "growth per turn",
+            context_growth_per_turn(&a.context_per_turn),
+            context_growth_per_turn(&b.context_per_turn),
+            Tokens,
+            true,
+        ),
+    ]);
+
+    let names: BTreeSet<&String> = a.tool_stats.keys().chain(b.tool_stats.keys()).collect();
+    let mut tools: Vec<ToolChange> = names
+        .into_iter()
+        .map(|name| {
+            let before = a.tool_stats.get(name).filter(|t| t.calls > 0);
+            let after = b.tool_stats.get(name).filter(|t| t.calls > 0);
+            ToolChange {
+                tool: name.clone(),
+                before_calls: before.map_or(0, |t| t.calls),
+                after_calls: after.map_or(0, |t| t.calls),
+                before_success: before.map(|t| t.success_rate()),
+                after_success: after.map(|t| t.success_rate()),
+            }
+        })
+        .filter(|t| t.before_calls > 0 || t.after_calls > 0)
+        .collect();
+    tools.sort_by(|x, y| {
+        y.before_calls
+            .max(y.after_calls)
+            .cmp(&x.before_calls.max(x.after_calls))
+            .then_with(|| x.tool.cmp(&y.tool))
+    });
+
+    let patterns: BTreeSet<&String> = a
+        .retry_hotspots
+        .iter()
+        .chain(&b.retry_hotspots)
+        .map(|h| &h.pattern)
+        .collect();
+    let mut retry_hotspots: Vec<RetryHotspotChange> = patterns
+        .into_iter()
+        .map(|pattern| {
+            let before = a.retry_hotspots.iter().find(|h| &h.pattern == pattern);
+            let after = b.retry_hotspots.iter().find(|h| &h.pattern == pattern);
+            RetryHotspotChange {
+                pattern: pattern.clone(),
+                before_failures: before.map_or(0, |h| h.failures),
+                after_failures: after.map_or(0, |h| h.failures),
+                before_attempts: before.map_or(0, |h| h.attempts),
+                after_attempts: after.map_or(0, |h| h.attempts),
+            }
+        })
+        .collect();
+    retry_hotspots.sort_by_key(|h| std::cmp::Reverse(h.after_failures.abs_diff(h.before_failures)));
+
+    AnalysisDiff {
+        before: a.session_path.clone(),
+        after: b.session_path.clone(),
+        metrics,
+        tools,
+        retry_hotspots,
+    }
+}
+
+/// Average growth in context size per turn, from the first to the last turn
+/// with token usage. Zero for fewer than two such turns.
+fn context_growth_per_turn(context_per_turn: &[u64]) -> f64 {
+    let used: Vec<(usize, u64)> = context_per_turn
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, c)| c > 0)
+        .collect();
+    match (used.first(), used.last()) {
+        (Some(&(i, first)), Some(&(j, last))) if j > i => {
+            (last as f64 - first as f64) / (j - i) as f64
+        }
+        _ => 0.0,
+    }
+}
+
+fn success_str(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.0}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl OutputFormatter for AnalysisDiff {
+    fn format_text(&self) -> String {
+        let mut lines = vec![
+            "# Session Diff".to_string(),
+            String::new(),
+            format!("- **Before**: {}", self.before.display()),
+            format!("- **After**: {}", self.after.display()),
+            String::new(),
+            "## Metrics".to_string(),
+            String::new(),
+            "| Section | Metric | Before | After | Change |".to_string(),
+            "|---------|--------|--------|-------|--------|".to_string(),
+        ];
+        for m in &self.metrics {
+            let verdict = match m.improved() {
+                Some(true) => " (better)",
+                Some(false) => " (worse)",
+                None => "",
+            };
+            lines.push(format!(
+                "| {} | {} | {} | {} | {}{} |",
+                m.section,
+                m.metric,
+                m.unit.format(m.before),
+                m.unit.format(m.after),
+                m.change_str(),
+                verdict
+            ));
+        }
+        lines.push(String::new());
+
+        if !self.tools.is_empty() {
+            lines.push("## Tool Success".to_string());
+            lines.push(String::new());
+            lines.push("| Tool | Calls | Success |".to_string());
+            lines.push("|------|-------|---------|".to_string());
+            for t in &self.tools {
+                lines.push(format!(
+                    "| {} | {} → {} | {} → {} |",
+                    t.tool,
+                    t.before_calls,
+                    t.after_calls,
+                    success_str(t.before_success),
+                    success_str(t.after_success)
+                ));
+            }
+            lines.push(String::new());
+        }
+
+        if !self.retry_hotspots.is_empty() {
+            lines.push("## Retry Hotspots".to_string());
+            lines.push(String::new());
+            lines.push("| Pattern | Failures | Attempts | Status |".to_string());
+            lines.push("|---------|----------|----------|--------|".to_string());
+            for h in &self.retry_hotspots {
+                lines.push(format!(
+                    "| {} | {} → {} | {} → {} | {} |",
+                    h.pattern,
+                    h.before_failures,
+                    h.after_failures,
+                    h.before_attempts,
+                    h.after_attempts,
+                    h.status()
+                ));
+            }
+            lines.push(String::new());
+        }
+
+        lines.join("\n")
+    }
+
+    fn format_pretty(&self) -> String {
+        let mut out = String::new();
+        out.push_str("\x1b[1;36m━━━ Session Diff ━━━\x1b[0m\n");
+        out.push_str(&format!(
+            "\x1b[1mBefore:\x1b[0m {}\n",
+            self.before.display()
+        ));
+        out.push_str(&format!("\x1b[1mAfter:\x1b[0m  {}\n", self.after.display()));
+
+        let width = self
+            .metrics
+            .iter()
+            .map(|m| m.metric.len())
+            .max()
+            .unwrap_or(10);
+        let mut section = "";
+        for m in &self.metrics {
+            if m.section != section {
+                section = &m.section;
+                out.push_str(&format!("\n\x1b[1;36m━━━ {} ━━━\x1b[0m\n", section));
+            }
+            let color = match m.improved() {
+                Some(true) => "\x1b[32m",
+                Some(false) => "\x1b[31m",
+                None => "\x1b[2m",
+            };
+            out.push_str(&format!(
+                "{:<width$}  {:>9} → {:<9} {}{}\x1b[0m\n",
+                m.metric,
+                m.unit.format(m.before),
+                m.unit.format(m.after),
+                color,
+                m.change_str(),
+                width = width
+            ));
+        }
+
+        if !self.tools.is_empty() {
+            out.push_str("\n\x1b[1;36m━━━ Tool Success ━━━\x1b[0m\n");
+            let width = self.tools.iter().map(|t| t.tool.len()).max().unwrap_or(10);
+            for t in &self.tools {
+                let color = match (t.before_success, t.after_success) {
+                    (Some(x), Some(y)) if y > x => "\x1b[32m",
+                    (Some(x), Some(y)) if y < x => "\x1b[31m",
+                    _ => "",
+                };
+                out.push_str(&format!(
+                    "{:<width$}  {:>5} → {:<5}  {}{:>4} → {}\x1b[0m\n",
+                    t.tool,
+                    t.before_calls,
+                    t.after_calls,
+                    color,
+                    success_str(t.before_success),
+                    success_str(t.after_success),
+                    width = width
+                ));
+            }
+        }
+
+        if !self.retry_hotspots.is_empty() {
+            out.push_str("\n\x1b[1;36m━━━ Retry Hotspots ━━━\x1b[0m\n");
+            for h in &self.retry_hotspots {
+                let color = match h.status() {
+                    "new" | "worse" => "\x1b[31m",
+                    "resolved" | "better" => "\x1b[32m",
+                    _ => "\x1b[2m",
+                };
+                out.push_str(&format!(
+                    "{}{:<8}\x1b[0m {} → {} failures  {}\n",
+                    color,
+                    h.status(),
+                    h.before_failures,
+                    h.after_failures,
+                    h.pattern
+                ));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RetryHotspot, ToolStats};
+
+    fn report(path: &str, calls: usize, errors: usize) -> SessionAnalysisReport {
+        let mut r = SessionAnalysisReport::new(PathBuf::from(path), "claude");
+        r.total_turns = 10;
+        r.tool_stats.insert(
+            "Bash".to_string(),
+            ToolStats {
+                calls,
+                errors,
+                ..ToolStats::new("Bash")
+            },
+        );
+        r
+    }
+
+    #[test]
+    fn success_rate_and_cost_changes_are_judged() {
+        let mut a = report("a.jsonl", 10, 4);
+        let mut b = report("b.jsonl", 10, 1);
+        a.actual_cost = Some(2.0);
+        b.actual_cost = Some(3.0);
+        let diff = diff_analyses(&a, &b);
+
+        let rate = diff
+            .metrics
+            .iter()
+            .find(|m| m.metric == "success rate")
+            .unwrap();
+        assert!((rate.before - 0.6).abs() < 1e-9);
+        assert!((rate.after - 0.9).abs() < 1e-9);
+        assert_eq!(rate.improved(), Some(true));
+
+        let cost = diff
+            .metrics
+            .iter()
+            .find(|m| m.metric == "total cost")
+            .unwrap();
+        assert_eq!(cost.improved(), Some(false));
+        assert_eq!(cost.percent_change(), Some(50.0));
+        assert_eq!(cost.change_str(), "+$1.00 (+50%)");
+
+        let turns = diff.metrics.iter().find(|m| m.metric == "turns").unwrap();
+        assert_eq!(turns.improved(), None);
+        assert_eq!(diff.tools[0].tool, "Bash");
+    }
+
+    #[test]
+    fn cost_is_omitted_unless_both_sessions_are_priced() {
+        let mut a = report("a.jsonl", 1, 0);
+        a.actual_cost = Some(1.0);
+        let diff = diff_analyses(&a, &report("b.jsonl", 1, 0));
+        assert!(diff.metrics.iter().all(|m| m.section != "cost"));
+    }
+
+    #[test]
+    fn retry_hotspots_are_matched_by_pattern() {
+        let hotspot = |pattern: &str, failures| RetryHotspot {
+            pattern: pattern.to_string(),
+            attempts: failures + 1,
+            failures,
+            ..Default::default()
+        };
+        let mut a = report("a.jsonl", 5, 0);
+        let mut b = report("b.jsonl", 5, 0);
+        a.retry_hotspots = vec![hotspot("cargo test", 4), hotspot("npm run", 1)];
+        b.retry_hotspots = vec![hotspot("cargo test", 1), hotspot("pytest", 2)];
+        let diff = diff_analyses(&a, &b);
+        let status: Vec<_> = diff
+            .retry_hotspots
+            .iter()
+            .map(|h| (h.pattern.as_str(), h.status()))
+            .collect();
+        assert_eq!(
+            status,
+            vec![
+                ("cargo test", "better"),
+                ("pytest", "new"),
+                ("npm run", "resolved")
+            ]
+        );
+    }
+
+    #[test]
+    fn context_growth_spans_turns_with_usage() {
+        assert_eq!(context_growth_per_turn(&[]), 0.0);
+        assert_eq!(context_growth_per_turn(&[0, 5_000]), 0.0);
+        assert_eq!(
+            context_growth_per_turn(&[0, 10_000, 0, 14_000, 16_000]),
+            2_000.0
+        );
+    }
+}
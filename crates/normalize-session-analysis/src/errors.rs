@@ -0,0 +1,373 @@
+//! Error categorization: ordered rules that bucket failed tool results into
+//! [`ErrorPattern`](crate::ErrorPattern) categories.
+//!
+//! The bundled rules (`errors.toml` in this crate) recognise Python
+//! tracebacks, Rust compile errors, test failures and shell exit codes before
+//! falling back to generic keywords. A project's `.normalize/errors.toml` adds
+//! rules that are tried before the bundled ones:
+//!
+//! ```toml
+//! bundled = false                      # drop the bundled rules (default: keep)
+//!
+//! [[rule]]
+//! category = "Flaky network"
+//! keywords = ["connection reset", "econnrefused"]
+//!
+//! [[rule]]
+//! category = "Type error"
+//! tools = ["Bash"]                     # only for these tools
+//! regex = 'error TS\d+:'
+//!
+//! [[rule]]
+//! category = "HTTP 5xx"
+//! json_path = "response.status"        # tool output parsed as JSON
+//! matches = '^5'
+//! ```
+
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+
+const BUNDLED_ERRORS: &str = include_str!("../errors.toml");
+
+/// Category for errors no rule matches.
+pub const OTHER_ERROR_CATEGORY: &str = "Other";
+
+/// How an [`ErrorCategorizer`] recognises an error.
+#[derive(Debug, Clone)]
+pub enum ErrorMatcher {
+    /// Any of these lowercased substrings.
+    Keywords(Vec<String>),
+    Regex(Regex),
+    /// A dotted path into output that parses as JSON. Matches when the value
+    /// is present and not null and, if given, its text matches the regex.
+    JsonPath {
+        path: Vec<String>,
+        matches: Option<Regex>,
+    },
+}
+
+/// One categorization rule.
+#[derive(Debug, Clone)]
+pub struct ErrorCategorizer {
+    pub category: String,
+    /// Lowercased tool names the rule applies to; empty means every tool.
+    pub tools: Vec<String>,
+    pub matcher: ErrorMatcher,
+}
+
+impl ErrorCategorizer {
+    /// Whether this rule matches an error from `tool` with output `text`.
+    /// `lower` and `json` are `text` lowercased and parsed, computed once by
+    /// the caller.
+    fn matches(
+        &self,
+        tool: Option<&str>,
+        text: &str,
+        lower: &str,
+        json: Option<&serde_json::Value>,
+    ) -> bool {
+        if !self.tools.is_empty()
+            && !tool.is_some_and(|t| self.tools.iter().any(|r| r.eq_ignore_ascii_case(t)))
+        {
+            return false;
+        }
+        match &self.matcher {
+            ErrorMatcher::Keywords(keywords) => keywords.iter().any(|k| lower.contains(k.as_str())),
+            ErrorMatcher::Regex(re) => re.is_match(text),
+            ErrorMatcher::JsonPath { path, matches } => {
+                let Some(value) = json.and_then(|v| lookup(v, path)) else {
+                    return false;
+                };
+                match (value, matches) {
+                    (serde_json::Value::Null, _) => false,
+                    (_, None) => true,
+                    (serde_json::Value::String(s), Some(re)) => re.is_match(s),
+                    (other, Some(re)) => re.is_match(&other.to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Follow `path` through objects (by key) and arrays (by index).
+fn lookup<'a>(value: &'a serde_json::Value, path: &[String]) -> Option<&'a serde_json::Value> {
+    path.iter().try_fold(value, |v, segment| match v {
+        serde_json::Value::Object(map) => map.get(segment),
+        serde_json::Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+/// Ordered error categorization rules used by [`categorize_error`].
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCategorizers {
+    rules: Vec<ErrorCategorizer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ErrorsFile {
+    /// Keep the bundled rules after this file's (project files only).
+    bundled: Option<bool>,
+    #[serde(default)]
+    rule: Vec<RuleEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleEntry {
+    category: String,
+    #[serde(default)]
+    tools: Vec<String>,
+    keywords: Option<Vec<String>>,
+    regex: Option<String>,
+    json_path: Option<String>,
+    matches: Option<String>,
+}
+
+fn compile_regex(pattern: &str, category: &str) -> Result<Regex, String> {
+    Regex::new(pattern)
+        .map_err(|e| format!("Invalid regex '{}' in rule '{}': {}", pattern, category, e))
+}
+
+impl RuleEntry {
+    fn compile(self) -> Result<ErrorCategorizer, String> {
+        if self.matches.is_some() && self.json_path.is_none() {
+            return Err(format!(
+                "Rule '{}': `matches` requires `json_path`",
+                self.category
+            ));
+        }
+        let matcher = match (self.keywords, self.regex, self.json_path) {
+            (Some(keywords), None, None) => {
+                ErrorMatcher::Keywords(keywords.iter().map(|k| k.to_lowercase()).collect())
+            }
+            (None, Some(regex), None) => {
+                ErrorMatcher::Regex(compile_regex(&regex, &self.category)?)
+            }
+            (None, None, Some(path)) => ErrorMatcher::JsonPath {
+                path: path
+                    .trim_start_matches('$')
+                    .split('.')
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                matches: self
+                    .matches
+                    .as_deref()
+                    .map(|m| compile_regex(m, &self.category))
+                    .transpose()?,
+            },
+            _ => {
+                return Err(format!(
+                    "Rule '{}' needs exactly one of `keywords`, `regex` or `json_path`",
+                    self.category
+                ));
+            }
+        };
+        Ok(ErrorCategorizer {
+            category: self.category,
+            tools: self.tools.iter().map(|t| t.to_lowercase()).collect(),
+            matcher,
+        })
+    }
+}
+
+impl ErrorsFile {
+    fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|e| format!("Failed to parse error categorizers: {}", e))
+    }
+
+    fn compile(self) -> Result<Vec<ErrorCategorizer>, String> {
+        self.rule.into_iter().map(RuleEntry::compile).collect()
+    }
+}
+
+static ACTIVE_ERROR_CATEGORIZERS: std::sync::OnceLock<ErrorCategorizers> =
+    std::sync::OnceLock::new();
+
+impl ErrorCategorizers {
+    /// Parse a standalone rules file.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        Ok(Self {
+            rules: ErrorsFile::parse(source)?.compile()?,
+        })
+    }
+
+    /// The compiled-in defaults (`errors.toml` in this crate).
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_ERRORS).expect("bundled errors.toml is valid")
+    }
+
+    /// Rules from `<root>/.normalize/errors.toml`, if it exists, followed by
+    /// the bundled defaults unless the file sets `bundled = false`.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let path = root.join(".normalize").join("errors.toml");
+        if !path.exists() {
+            return Ok(Self::bundled());
+        }
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        let file = ErrorsFile::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let keep_bundled = file.bundled.unwrap_or(true);
+        let mut rules = file
+            .compile()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        if keep_bundled {
+            rules.extend(Self::bundled().rules);
+        }
+        Ok(Self { rules })
+    }
+
+    /// Make `categorizers` the ones [`categorize_error`] uses.
+    ///
+    /// Only the first call per process takes effect; returns `false` if
+    /// categorizers were already active.
+    pub fn install(categorizers: ErrorCategorizers) -> bool {
+        ACTIVE_ERROR_CATEGORIZERS.set(categorizers).is_ok()
+    }
+
+    /// The installed categorizers, or the bundled defaults if none were
+    /// installed.
+    pub fn active() -> &'static ErrorCategorizers {
+        ACTIVE_ERROR_CATEGORIZERS.get_or_init(Self::bundled)
+    }
+
+    pub fn rules(&self) -> &[ErrorCategorizer] {
+        &self.rules
+    }
+
+    /// Category of the first rule matching an error from `tool` (if known)
+    /// with output `text`, or [`OTHER_ERROR_CATEGORY`].
+    pub fn categorize(&self, tool: Option<&str>, text: &str) -> &str {
+        let lower = text.to_lowercase();
+        let trimmed = text.trim_start();
+        let json = (trimmed.starts_with('{') || trimmed.starts_with('['))
+            .then(|| serde_json::from_str::<serde_json::Value>(trimmed).ok())
+            .flatten();
+        self.rules
+            .iter()
+            .find(|r| r.matches(tool, text, &lower, json.as_ref()))
+            .map_or(OTHER_ERROR_CATEGORY, |r| r.category.as_str())
+    }
+}
+
+/// Categorize an error from `tool` (if known) by its content, using the
+/// active [`ErrorCategorizers`].
+pub fn categorize_error(tool: Option<&str>, error_text: &str) -> &'static str {
+    ErrorCategorizers::active().categorize(tool, error_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_rules_separate_stacks() {
+        let rules = ErrorCategorizers::bundled();
+        let cat = |tool, text| rules.categorize(tool, text).to_string();
+        assert_eq!(
+            cat(
+                Some("Bash"),
+                "Exit code 1\nTraceback (most recent call last):\n  File \"x.py\"\nModuleNotFoundError: No module named 'foo'"
+            ),
+            "Import error"
+        );
+        assert_eq!(
+            cat(
+                Some("Bash"),
+                "Exit code 1\nTraceback (most recent call last):\nKeyError: 'x'"
+            ),
+            "Python exception"
+        );
+        assert_eq!(
+            cat(
+                Some("Bash"),
+                "Exit code 101\nerror[E0308]: mismatched types"
+            ),
+            "Compile error"
+        );
+        assert_eq!(
+            cat(Some("Bash"), "bash: rg: command not found"),
+            "Command not found"
+        );
+        assert_eq!(cat(Some("Bash"), "Exit code 2"), "Command failure");
+        assert_eq!(cat(Some("Read"), "File not found: x"), "File not found");
+        assert_eq!(cat(None, "something odd"), OTHER_ERROR_CATEGORY);
+    }
+
+    #[test]
+    fn json_path_rules_read_structured_output() {
+        let rules = ErrorCategorizers::bundled();
+        let out = r#"{"output": "boom", "metadata": {"exit_code": 1}}"#;
+        assert_eq!(rules.categorize(Some("shell"), out), "Command failure");
+        // Per-tool rules do not fire for other tools.
+        assert_eq!(rules.categorize(Some("Read"), out), OTHER_ERROR_CATEGORY);
+        let ok = r#"{"output": "", "metadata": {"exit_code": 0}}"#;
+        assert_eq!(rules.categorize(Some("shell"), ok), OTHER_ERROR_CATEGORY);
+
+        let custom = ErrorCategorizers::parse(
+            r#"
+[[rule]]
+category = "HTTP 5xx"
+json_path = "$.responses.1.status"
+matches = '^5'
+"#,
+        )
+        .unwrap();
+        let body = r#"{"responses": [{"status": 200}, {"status": 503}]}"#;
+        assert_eq!(custom.categorize(None, body), "HTTP 5xx");
+    }
+
+    #[test]
+    fn project_rules_come_first() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".normalize")).unwrap();
+        let path = dir.path().join(".normalize").join("errors.toml");
+        std::fs::write(
+            &path,
+            r#"
+[[rule]]
+category = "Flaky network"
+keywords = ["Connection reset"]
+"#,
+        )
+        .unwrap();
+        let rules = ErrorCategorizers::load(dir.path()).unwrap();
+        assert_eq!(
+            rules.categorize(None, "connection reset: timeout after 30s"),
+            "Flaky network"
+        );
+        assert_eq!(rules.categorize(None, "timeout"), "Timeout");
+
+        std::fs::write(
+            &path,
+            "bundled = false\n[[rule]]\ncategory = \"Net\"\nregex = 'ECONN'\n",
+        )
+        .unwrap();
+        let rules = ErrorCategorizers::load(dir.path()).unwrap();
+        assert_eq!(rules.rules().len(), 1);
+        assert_eq!(rules.categorize(None, "timeout"), OTHER_ERROR_CATEGORY);
+    }
+
+    #[test]
+    fn invalid_rules_are_reported() {
+        let err = ErrorCategorizers::parse(
+            "[[rule]]\ncategory = \"x\"\nkeywords = [\"a\"]\nregex = 'b'\n",
+        )
+        .unwrap_err();
+        assert!(err.contains("exactly one of"), "{err}");
+        let err = ErrorCategorizers::parse("[[rule]]\ncategory = \"x\"\nregex = '(unclosed'\n")
+            .unwrap_err();
+        assert!(
+            err.contains("Invalid regex '(unclosed' in rule 'x'"),
+            "{err}"
+        );
+        let err = ErrorCategorizers::parse(
+            "[[rule]]\ncategory = \"x\"\nkeywords = [\"a\"]\nmatches = 'b'\n",
+        )
+        .unwrap_err();
+        assert!(err.contains("`matches` requires `json_path`"), "{err}");
+    }
+}
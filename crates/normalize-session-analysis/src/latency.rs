@@ -0,0 +1,400 @@
+//! Wall-clock latency from message timestamps: how long each tool call took
+//! (tool_use → tool_result), how long the session sat idle between turns,
+//! and how long it ran overall.
+
+use normalize_chat_sessions::{ContentBlock, Turn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Distribution of a set of durations, in milliseconds.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+    pub total_ms: u64,
+    /// Raw samples, kept so aggregates can recompute percentiles.
+    #[serde(skip)]
+    #[schemars(skip)]
+    samples: Vec<u64>,
+}
+
+impl DurationStats {
+    fn from_samples(mut samples: Vec<u64>) -> Self {
+        samples.sort_unstable();
+        Self {
+            count: samples.len(),
+            p50_ms: percentile(&samples, 50),
+            p95_ms: percentile(&samples, 95),
+            max_ms: samples.last().copied().unwrap_or(0),
+            total_ms: samples.iter().sum(),
+            samples,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted `samples` (0 when empty).
+fn percentile(samples: &[u64], p: usize) -> u64 {
+    if samples.is_empty() {
+        return 0;
+    }
+    let rank = (p * samples.len()).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
+/// Durations of one tool's calls.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct ToolLatency {
+    pub tool: String,
+    #[serde(flatten)]
+    pub durations: DurationStats,
+}
+
+/// Timing derived from message timestamps.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct LatencyStats {
+    /// First to last timestamped message (summed across sessions in aggregates).
+    pub session_duration_ms: u64,
+    /// Per-tool call durations, most total time first.
+    pub tools: Vec<ToolLatency>,
+    /// Time between the last message of one turn and the first of the next.
+    pub idle_gaps: DurationStats,
+}
+
+impl LatencyStats {
+    /// Combine per-session stats, recomputing distributions from samples.
+    pub fn merge(stats: &[&LatencyStats]) -> Option<LatencyStats> {
+        if stats.is_empty() {
+            return None;
+        }
+        let mut tools: HashMap<String, Vec<u64>> = HashMap::new();
+        let mut gaps = Vec::new();
+        let mut duration = 0;
+        for s in stats {
+            duration += s.session_duration_ms;
+            for t in &s.tools {
+                tools
+                    .entry(t.tool.clone())
+                    .or_default()
+                    .extend(&t.durations.samples);
+            }
+            gaps.extend(&s.idle_gaps.samples);
+        }
+        Some(LatencyStats::build(duration, tools, gaps))
+    }
+
+    fn build(
+        session_duration_ms: u64,
+        tools: HashMap<String, Vec<u64>>,
+        gaps: Vec<u64>,
+    ) -> LatencyStats {
+        let mut tools: Vec<ToolLatency> = tools
+            .into_iter()
+            .map(|(tool, samples)| ToolLatency {
+                tool,
+                durations: DurationStats::from_samples(samples),
+            })
+            .collect();
+        tools.sort_by(|a, b| {
+            b.durations
+                .total_ms
+                .cmp(&a.durations.total_ms)
+                .then_with(|| a.tool.cmp(&b.tool))
+        });
+        LatencyStats {
+            session_duration_ms,
+            tools,
+            idle_gaps: DurationStats::from_samples(gaps),
+        }
+    }
+}
+
+/// Accumulates [`LatencyStats`] turn by turn.
+#[derive(Default)]
+pub(crate) struct LatencyTracker {
+    first: Option<i64>,
+    last: Option<i64>,
+    /// Last timestamp of the previous turn.
+    turn_end: Option<i64>,
+    /// Tool calls waiting for a result: id → (tool, start).
+    pending: HashMap<String, (String, i64)>,
+    tools: HashMap<String, Vec<u64>>,
+    gaps: Vec<u64>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn add_turn(&mut self, turn: &Turn) {
+        let mut turn_first = None;
+        for msg in &turn.messages {
+            let Some(ts) = msg.timestamp.as_deref().and_then(parse_millis) else {
+                continue;
+            };
+            self.first = Some(self.first.map_or(ts, |f| f.min(ts)));
+            self.last = Some(self.last.map_or(ts, |l| l.max(ts)));
+            if turn_first.is_none() {
+                turn_first = Some(ts);
+                if let Some(end) = self.turn_end {
+                    self.gaps.push((ts - end).max(0) as u64);
+                }
+            }
+            for block in &msg.content {
+                match block {
+                    ContentBlock::ToolUse { id, name, .. } => {
+                        self.pending.insert(id.clone(), (name.clone(), ts));
+                    }
+                    ContentBlock::ToolResult { tool_use_id, .. } => {
+                        if let Some((name, start)) = self.pending.remove(tool_use_id) {
+                            self.tools
+                                .entry(name)
+                                .or_default()
+                                .push((ts - start).max(0) as u64);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self.turn_end = Some(ts);
+        }
+    }
+
+    /// `None` when no message carried a parseable timestamp.
+    pub(crate) fn finish(self) -> Option<LatencyStats> {
+        let (first, last) = (self.first?, self.last?);
+        Some(LatencyStats::build(
+            (last - first) as u64,
+            self.tools,
+            self.gaps,
+        ))
+    }
+}
+
+/// Milliseconds since the Unix epoch for an RFC 3339 timestamp.
+fn parse_millis(timestamp: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+/// `850ms`, `12.3s`, `4m05s`, `1h02m`
+pub(crate) fn format_duration_ms(ms: u64) -> String {
+    let secs = ms / 1000;
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if secs < 60 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Markdown "Latency" section.
+pub(crate) fn push_latency(lines: &mut Vec<String>, latency: &LatencyStats) {
+    lines.push("## Latency".to_string());
+    lines.push(String::new());
+    lines.push(format!(
+        "- **Session duration**: {}",
+        format_duration_ms(latency.session_duration_ms)
+    ));
+    let gaps = &latency.idle_gaps;
+    if gaps.count > 0 {
+        lines.push(format!(
+            "- **Idle between turns**: {} total, p50 {}, p95 {}, max {}",
+            format_duration_ms(gaps.total_ms),
+            format_duration_ms(gaps.p50_ms),
+            format_duration_ms(gaps.p95_ms),
+            format_duration_ms(gaps.max_ms)
+        ));
+    }
+    lines.push(String::new());
+    if !latency.tools.is_empty() {
+        lines.push("| Tool | Calls | p50 | p95 | Max | Total |".to_string());
+        lines.push("|------|-------|-----|-----|-----|-------|".to_string());
+        for t in &latency.tools {
+            let d = &t.durations;
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} | {} |",
+                t.tool,
+                d.count,
+                format_duration_ms(d.p50_ms),
+                format_duration_ms(d.p95_ms),
+                format_duration_ms(d.max_ms),
+                format_duration_ms(d.total_ms)
+            ));
+        }
+        lines.push(String::new());
+    }
+}
+
+/// ANSI "Latency" section.
+pub(crate) fn write_latency(out: &mut String, latency: &LatencyStats) -> std::fmt::Result {
+    use std::fmt::Write;
+    writeln!(out, "\x1b[1;36m━━━ Latency ━━━\x1b[0m")?;
+    write!(
+        out,
+        "Session: \x1b[33m{}\x1b[0m",
+        format_duration_ms(latency.session_duration_ms)
+    )?;
+    let gaps = &latency.idle_gaps;
+    if gaps.count > 0 {
+        write!(
+            out,
+            " | idle {} (p50 {}, p95 {})",
+            format_duration_ms(gaps.total_ms),
+            format_duration_ms(gaps.p50_ms),
+            format_duration_ms(gaps.p95_ms)
+        )?;
+    }
+    writeln!(out)?;
+    let width = latency
+        .tools
+        .iter()
+        .map(|t| t.tool.len())
+        .max()
+        .unwrap_or(10);
+    for t in &latency.tools {
+        let d = &t.durations;
+        // Slow tails stand out: p95 more than five times the median.
+        let color = if d.p95_ms > d.p50_ms.saturating_mul(5) && d.p95_ms >= 10_000 {
+            "\x1b[31m"
+        } else {
+            "\x1b[33m"
+        };
+        writeln!(
+            out,
+            "{:>width$}  p50 {:>7}  p95 {}{:>7}\x1b[0m  max {:>7}  total {:>7}  ({} calls)",
+            t.tool,
+            format_duration_ms(d.p50_ms),
+            color,
+            format_duration_ms(d.p95_ms),
+            format_duration_ms(d.max_ms),
+            format_duration_ms(d.total_ms),
+            d.count,
+            width = width
+        )?;
+    }
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use normalize_chat_sessions::{Message, Role};
+
+    fn msg(role: Role, ts: Option<&str>, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role,
+            content,
+            timestamp: ts.map(str::to_string),
+        }
+    }
+
+    fn tool_use(id: &str, name: &str) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::Value::Null,
+        }
+    }
+
+    fn tool_result(id: &str) -> ContentBlock {
+        ContentBlock::ToolResult {
+            tool_use_id: id.to_string(),
+            content: String::new(),
+            is_error: false,
+        }
+    }
+
+    fn turn(messages: Vec<Message>) -> Turn {
+        Turn {
+            messages,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tool_durations_gaps_and_session_length() {
+        let mut tracker = LatencyTracker::default();
+        tracker.add_turn(&turn(vec![
+            msg(Role::User, Some("2026-01-01T10:00:00Z"), vec![]),
+            msg(
+                Role::Assistant,
+                Some("2026-01-01T10:00:05Z"),
+                vec![tool_use("a", "Bash"), tool_use("b", "Read")],
+            ),
+            msg(
+                Role::User,
+                Some("2026-01-01T10:00:35Z"),
+                vec![tool_result("a")],
+            ),
+            msg(
+                Role::User,
+                Some("2026-01-01T10:00:05.200Z"),
+                vec![tool_result("b")],
+            ),
+        ]));
+        // A message without a timestamp is skipped rather than guessed.
+        tracker.add_turn(&turn(vec![
+            msg(Role::User, None, vec![]),
+            msg(
+                Role::Assistant,
+                Some("2026-01-01T10:02:05.200Z"),
+                vec![tool_use("c", "Bash")],
+            ),
+            msg(
+                Role::User,
+                Some("2026-01-01T10:02:07.200Z"),
+                vec![tool_result("c")],
+            ),
+        ]));
+        let stats = tracker.finish().unwrap();
+
+        assert_eq!(stats.session_duration_ms, 127_200);
+        assert_eq!(stats.tools[0].tool, "Bash");
+        let bash = &stats.tools[0].durations;
+        assert_eq!((bash.count, bash.p50_ms, bash.max_ms), (2, 2_000, 30_000));
+        assert_eq!(bash.total_ms, 32_000);
+        assert_eq!(stats.tools[1].durations.p50_ms, 200);
+        assert_eq!(stats.idle_gaps.count, 1);
+        assert_eq!(stats.idle_gaps.p50_ms, 120_000);
+    }
+
+    #[test]
+    fn untimed_sessions_have_no_latency() {
+        let mut tracker = LatencyTracker::default();
+        tracker.add_turn(&turn(vec![msg(Role::User, None, vec![])]));
+        assert!(tracker.finish().is_none());
+    }
+
+    #[test]
+    fn merge_recomputes_percentiles() {
+        let a = LatencyStats::build(
+            1_000,
+            HashMap::from([("Bash".to_string(), vec![100, 200])]),
+            vec![],
+        );
+        let b = LatencyStats::build(
+            2_000,
+            HashMap::from([("Bash".to_string(), vec![300, 400, 10_000])]),
+            vec![50],
+        );
+        let merged = LatencyStats::merge(&[&a, &b]).unwrap();
+        assert_eq!(merged.session_duration_ms, 3_000);
+        let bash = &merged.tools[0].durations;
+        assert_eq!((bash.count, bash.p50_ms, bash.p95_ms), (5, 300, 10_000));
+        assert_eq!(merged.idle_gaps.count, 1);
+    }
+
+    #[test]
+    fn percentiles_and_durations_format() {
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[1, 2, 3, 4], 50), 2);
+        assert_eq!(percentile(&[1, 2, 3, 4], 95), 4);
+        assert_eq!(format_duration_ms(850), "850ms");
+        assert_eq!(format_duration_ms(12_340), "12.3s");
+        assert_eq!(format_duration_ms(245_000), "4m05s");
+        assert_eq!(format_duration_ms(3_720_000), "1h02m");
+    }
+}
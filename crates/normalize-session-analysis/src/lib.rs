@@ -4,11 +4,33 @@
 //! Analysis is intentionally in the CLI, not the parsing library,
 //! because what metrics matter is subjective and consumer-specific.
 
-use normalize_chat_sessions::{ContentBlock, Session};
+use normalize_chat_sessions::{ApiErrorKind, ContentBlock, Role, Session, Turn};
 use normalize_output::OutputFormatter;
+use normalize_tokens::Tokenizer;
 use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+mod budget;
+pub use budget::{
+    BudgetBreach, BudgetConfig, BudgetLimits, BudgetReport, CostLedger, LedgerEntry, ProjectBudget,
+    today,
+};
+mod diff;
+pub use diff::{
+    AnalysisDiff, MetricChange, MetricUnit, RetryHotspotChange, ToolChange, diff_analyses,
+};
+mod corrections;
+pub use corrections::{CorrectionCategory, CorrectionKind, CorrectionPatterns, detect_correction};
+mod otlp;
+pub use otlp::{OtlpTrace, session_to_otlp};
+mod errors;
+pub use errors::{
+    ErrorCategorizer, ErrorCategorizers, ErrorMatcher, OTHER_ERROR_CATEGORY, categorize_error,
+};
+mod latency;
+pub use latency::{DurationStats, LatencyStats, ToolLatency};
 
 /// Statistics for a single tool.
 #[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema, Deserialize)]
@@ -30,6 +52,48 @@ pub struct LargestToolResult {
     pub preview: String,
 }
 
+/// What kind of content a tool result carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResultClass {
+    /// Source or text file contents (e.g. `Read` output).
+    FileContents,
+    /// Unified diff or patch.
+    Diff,
+    /// Exception traceback, panic backtrace or similar.
+    StackTrace,
+    /// A JSON document.
+    Json,
+    /// Shell command output.
+    CommandOutput,
+    /// Anything else.
+    Other,
+}
+
+impl ToolResultClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolResultClass::FileContents => "file contents",
+            ToolResultClass::Diff => "diff",
+            ToolResultClass::StackTrace => "stack trace",
+            ToolResultClass::Json => "json",
+            ToolResultClass::CommandOutput => "command output",
+            ToolResultClass::Other => "other",
+        }
+    }
+}
+
+/// Tool result volume for one [`ToolResultClass`].
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct ToolResultClassStats {
+    pub class: ToolResultClass,
+    /// Number of tool results of this class.
+    pub results: usize,
+    pub chars: usize,
+    /// Estimated tokens (about four characters per token).
+    pub tokens: u64,
+}
+
 impl ToolStats {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
@@ -152,42 +216,11 @@ impl ModelPricing {
 
     /// Look up pricing from a model identifier string (e.g. `"claude-opus-4-6"`).
     ///
-    /// Matching is case-insensitive and uses substring search on the model
-    /// identifier. The family (`opus`, `sonnet`, `haiku`) is detected first,
-    /// then the version number within that family. Returns `None` for unknown
-    /// model identifiers.
+    /// Consults the active [`PricingTable`]: the project's
+    /// `.normalize/pricing.toml` when one was installed, then the bundled
+    /// defaults. Returns `None` for models no entry matches.
     pub fn from_model_str(model: &str) -> Option<&'static ModelPricing> {
-        let m = model.to_lowercase();
-        if m.contains("opus") {
-            if m.contains("4-5") || m.contains("4.5") || m.contains("4-6") || m.contains("4.6") {
-                Some(&Self::OPUS_4_5)
-            } else {
-                Some(&Self::OPUS_3)
-            }
-        } else if m.contains("sonnet") {
-            if m.contains("4-5") || m.contains("4.5") || m.contains("4-6") || m.contains("4.6") {
-                Some(&Self::SONNET_4_5)
-            } else if m.contains("3-7") || m.contains("3.7") {
-                Some(&Self::SONNET_3_7)
-            } else if m.contains("3-5") || m.contains("3.5") {
-                Some(&Self::SONNET_3_5)
-            } else if m.contains("-3") || m.ends_with("3") {
-                Some(&Self::SONNET_3)
-            } else {
-                // Default to latest sonnet pricing for unrecognized variants
-                Some(&Self::SONNET_4_5)
-            }
-        } else if m.contains("haiku") {
-            if m.contains("4") {
-                Some(&Self::HAIKU_4_5)
-            } else if m.contains("3-5") || m.contains("3.5") {
-                Some(&Self::HAIKU_3_5)
-            } else {
-                Some(&Self::HAIKU_3)
-            }
-        } else {
-            None
-        }
+        PricingTable::active().lookup(model)
     }
 
     /// Calculate cost for a single turn's token usage.
@@ -227,6 +260,149 @@ impl ModelPricing {
     }
 }
 
+/// Bundled pricing table, compiled in from `pricing.toml`.
+const BUNDLED_PRICING: &str = include_str!("../pricing.toml");
+
+/// Model pricing loaded from TOML, matched against model ids by glob patterns.
+///
+/// The file is a list of `[[model]]` entries:
+///
+/// ```toml
+/// [[model]]
+/// name = "GPT-4o"
+/// match = ["gpt-4o*"]          # globs against the lowercased model id
+/// input_per_mtok = 2.50
+/// output_per_mtok = 10.0
+/// cache_read_per_mtok = 1.25   # cache rates default to the input rate
+/// ```
+///
+/// Entries are tried in order and the first match wins.
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    rules: Vec<PricingRule>,
+}
+
+/// One `[[model]]` entry of a [`PricingTable`].
+#[derive(Debug, Clone)]
+pub struct PricingRule {
+    /// Lowercased glob patterns (`*` matches any run of characters).
+    pub patterns: Vec<String>,
+    pub pricing: ModelPricing,
+}
+
+#[derive(Deserialize)]
+struct PricingFile {
+    #[serde(default)]
+    model: Vec<PricingEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingEntry {
+    name: String,
+    #[serde(rename = "match")]
+    patterns: Vec<String>,
+    input_per_mtok: f64,
+    output_per_mtok: f64,
+    cache_write_per_mtok: Option<f64>,
+    cache_read_per_mtok: Option<f64>,
+}
+
+static ACTIVE_PRICING: std::sync::OnceLock<PricingTable> = std::sync::OnceLock::new();
+
+impl PricingTable {
+    /// Parse a pricing file.
+    ///
+    /// Model names are leaked to give [`ModelPricing`] its `'static` name;
+    /// tables are loaded once per process, so this is bounded.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let file: PricingFile =
+            toml::from_str(source).map_err(|e| format!("Failed to parse pricing table: {}", e))?;
+        let rules = file
+            .model
+            .into_iter()
+            .map(|e| PricingRule {
+                patterns: e.patterns.iter().map(|p| p.to_lowercase()).collect(),
+                pricing: ModelPricing {
+                    name: Box::leak(e.name.into_boxed_str()),
+                    input_per_mtok: e.input_per_mtok,
+                    output_per_mtok: e.output_per_mtok,
+                    cache_write_per_mtok: e.cache_write_per_mtok.unwrap_or(e.input_per_mtok),
+                    cache_read_per_mtok: e.cache_read_per_mtok.unwrap_or(e.input_per_mtok),
+                },
+            })
+            .collect();
+        Ok(Self { rules })
+    }
+
+    /// The compiled-in defaults (`pricing.toml` in this crate).
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_PRICING).expect("bundled pricing.toml is valid")
+    }
+
+    /// Bundled defaults, preceded by `<root>/.normalize/pricing.toml` if it exists.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let mut table = Self::bundled();
+        let path = root.join(".normalize").join("pricing.toml");
+        if path.exists() {
+            let source = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+            let mut project =
+                Self::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+            project.rules.append(&mut table.rules);
+            table = project;
+        }
+        Ok(table)
+    }
+
+    /// Make `table` the one [`ModelPricing::from_model_str`] consults.
+    ///
+    /// Only the first call per process takes effect; returns `false` if a
+    /// table was already active.
+    pub fn install(table: PricingTable) -> bool {
+        ACTIVE_PRICING.set(table).is_ok()
+    }
+
+    /// The installed table, or the bundled defaults if none was installed.
+    pub fn active() -> &'static PricingTable {
+        ACTIVE_PRICING.get_or_init(Self::bundled)
+    }
+
+    /// Pricing for the first entry whose patterns match `model`.
+    pub fn lookup(&self, model: &str) -> Option<&ModelPricing> {
+        let model = model.to_lowercase();
+        self.rules
+            .iter()
+            .find(|r| r.patterns.iter().any(|p| glob_match(p, &model)))
+            .map(|r| &r.pricing)
+    }
+
+    pub fn rules(&self) -> &[PricingRule] {
+        &self.rules
+    }
+}
+
+/// Whole-string match where `*` in `pattern` matches any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: the prefix must be the whole text.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 /// Cost breakdown for a session.
 #[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
 pub struct CostBreakdown {
@@ -308,27 +484,6 @@ impl ToolChain {
     }
 }
 
-/// Type of correction made by the assistant.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum CorrectionKind {
-    Apology,
-    Mistake,
-    LetMeFix,
-    Actually,
-}
-
-impl CorrectionKind {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            CorrectionKind::Apology => "Apology",
-            CorrectionKind::Mistake => "Mistake",
-            CorrectionKind::LetMeFix => "Let me fix",
-            CorrectionKind::Actually => "Actually",
-        }
-    }
-}
-
 /// An assistant correction or apology.
 #[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
 pub struct Correction {
@@ -337,6 +492,29 @@ pub struct Correction {
     pub category: CorrectionKind,
 }
 
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct CorrectionKindCount {
+    pub kind: CorrectionKind,
+    pub count: usize,
+}
+
+fn add_correction_kind(counts: &mut Vec<CorrectionKindCount>, kind: CorrectionKind, count: usize) {
+    if let Some(k) = counts.iter_mut().find(|k| k.kind == kind) {
+        k.count += count;
+    } else {
+        counts.push(CorrectionKindCount { kind, count });
+    }
+}
+
+/// `Apology 3, Mistake 1`
+fn correction_kinds_summary(counts: &[CorrectionKindCount]) -> String {
+    counts
+        .iter()
+        .map(|k| format!("{} {}", k.kind.as_str(), k.count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// File operation statistics.
 #[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema, Deserialize)]
 pub struct FileOperation {
@@ -405,6 +583,239 @@ pub struct DedupTokenStats {
     pub total_billed: u64,
     /// Ratio of unique tokens to total billed (0.0-1.0).
     pub uniqueness_ratio: f64,
+    /// Counted from message content with a tokenizer because the log carries
+    /// no usage data, assuming one API call per turn.
+    #[serde(default)]
+    pub estimated: bool,
+}
+
+/// Provider-level API errors (rate limits, overloads, timeouts) and their cost.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct ApiErrorStats {
+    pub total: usize,
+    /// Error count per kind, most frequent first.
+    pub by_kind: Vec<ApiErrorKindCount>,
+    /// Time spent waiting before retries, in milliseconds.
+    pub retry_delay_ms: u64,
+    /// Turns that hit at least one API error.
+    pub retried_turns: usize,
+    /// Tokens billed in those turns (input, cache read/create and output).
+    pub retried_turn_tokens: u64,
+    /// Cost of those turns, when the model's pricing is known.
+    pub retried_turn_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct ApiErrorKindCount {
+    pub kind: ApiErrorKind,
+    pub count: usize,
+}
+
+impl ApiErrorStats {
+    fn add_kind(&mut self, kind: ApiErrorKind, count: usize) {
+        self.total += count;
+        if let Some(k) = self.by_kind.iter_mut().find(|k| k.kind == kind) {
+            k.count += count;
+        } else {
+            self.by_kind.push(ApiErrorKindCount { kind, count });
+        }
+    }
+
+    /// `rate limit 3, overloaded 1`
+    fn kinds_summary(&self) -> String {
+        self.by_kind
+            .iter()
+            .map(|k| format!("{} {}", k.kind.as_str(), k.count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// How files matching a [`ViewRecommendation`] pattern should be viewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ViewPolicy {
+    /// Signatures only (`normalize view <file>`): the files were read but never edited.
+    SkeletonOnly,
+    /// Cap each view at roughly `tokens` (`normalize view <file> --budget N`):
+    /// the files were edited, so bodies matter, but single reads were very large.
+    Budget { tokens: u64 },
+}
+
+impl std::fmt::Display for ViewPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewPolicy::SkeletonOnly => write!(f, "skeleton-only"),
+            ViewPolicy::Budget { tokens } => write!(f, "budget {}", format_tokens(*tokens)),
+        }
+    }
+}
+
+/// A suggested view truncation policy for a group of token-hotspot files.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct ViewRecommendation {
+    /// File or glob the policy applies to (e.g. `src/generated/*.ts`).
+    pub pattern: String,
+    pub policy: ViewPolicy,
+    /// Files in the session matching the pattern.
+    pub files: usize,
+    pub reads: usize,
+    /// Tokens those reads returned.
+    pub tokens: u64,
+    /// Estimated tokens the policy would have saved.
+    pub saved_tokens: u64,
+    /// `saved_tokens` priced at the session's average cost per billed token.
+    pub saved_cost: Option<f64>,
+    /// Files whose skeleton size came from the facts index rather than the
+    /// default estimate.
+    #[serde(default)]
+    pub indexed_files: usize,
+}
+
+/// Skeleton size as a fraction of the full file when nothing better is known.
+/// Signatures plus doc comments are typically a fifth to a third of a source file.
+pub const DEFAULT_SKELETON_FRACTION: f64 = 0.25;
+
+/// Reads larger than this (tokens, on average) suggest a budget for edited files.
+const VIEW_BUDGET_THRESHOLD: u64 = 8_000;
+
+/// Budget suggested for edited files with oversized reads.
+const VIEW_BUDGET_TOKENS: u64 = 4_000;
+
+/// Recommendations saving less than this are not worth a policy.
+const MIN_VIEW_SAVINGS: u64 = 2_000;
+
+/// Suggest view truncation policies from the report's token hotspots.
+///
+/// Files are grouped by directory and extension (`dir/*.ext`); a group with one
+/// file, or one mixing edited and read-only files, is reported per path. Groups never edited in the session get
+/// [`ViewPolicy::SkeletonOnly`]; edited groups whose reads averaged more than
+/// 8K tokens get [`ViewPolicy::Budget`]. `skeleton_fraction` gives a file's
+/// skeleton size relative to its full contents (e.g. from the facts index);
+/// `None` falls back to [`DEFAULT_SKELETON_FRACTION`].
+pub fn recommend_view_policies(
+    report: &SessionAnalysisReport,
+    skeleton_fraction: &dyn Fn(&str) -> Option<f64>,
+) -> Vec<ViewRecommendation> {
+    #[derive(Default)]
+    struct Group {
+        files: Vec<String>,
+        reads: usize,
+        edits: usize,
+        tokens: u64,
+        skeleton_tokens: f64,
+        indexed_files: usize,
+    }
+
+    let ops = |path: &str| {
+        report
+            .file_operations
+            .get(path)
+            .map(|op| (op.reads, op.edits + op.writes))
+            .unwrap_or((1, 0))
+    };
+    // A glob only stands for files that would all get the same policy: a
+    // directory mixing edited and read-only files is reported file by file.
+    let mut edited_by_pattern: HashMap<String, (bool, bool)> = HashMap::new();
+    for path in report.file_tokens.keys() {
+        let entry = edited_by_pattern
+            .entry(view_group_pattern(path))
+            .or_default();
+        if ops(path).1 > 0 {
+            entry.0 = true;
+        } else {
+            entry.1 = true;
+        }
+    }
+
+    let mut groups: HashMap<String, Group> = HashMap::new();
+    for (path, &tokens) in &report.file_tokens {
+        let (reads, edits) = ops(path);
+        let fraction = skeleton_fraction(path);
+        let pattern = view_group_pattern(path);
+        let key = match edited_by_pattern.get(&pattern) {
+            Some((true, true)) => path.clone(),
+            _ => pattern,
+        };
+        let g = groups.entry(key).or_default();
+        g.files.push(path.clone());
+        g.reads += reads.max(1);
+        g.edits += edits;
+        g.tokens += tokens;
+        g.skeleton_tokens += tokens as f64
+            * fraction
+                .unwrap_or(DEFAULT_SKELETON_FRACTION)
+                .clamp(0.0, 1.0);
+        g.indexed_files += usize::from(fraction.is_some());
+    }
+
+    let billed = report.token_stats.total_input
+        + report.token_stats.cache_read
+        + report.token_stats.cache_create
+        + report.token_stats.total_output;
+    let cost_per_token = report
+        .actual_cost
+        .filter(|_| billed > 0)
+        .map(|c| c / billed as f64);
+
+    let mut recs: Vec<ViewRecommendation> = groups
+        .into_iter()
+        .filter_map(|(pattern, g)| {
+            let (policy, saved_tokens) = if g.edits == 0 {
+                let saved = g.tokens.saturating_sub(g.skeleton_tokens.round() as u64);
+                (ViewPolicy::SkeletonOnly, saved)
+            } else if g.tokens / g.reads as u64 > VIEW_BUDGET_THRESHOLD {
+                let capped = g.reads as u64 * VIEW_BUDGET_TOKENS;
+                (
+                    ViewPolicy::Budget {
+                        tokens: VIEW_BUDGET_TOKENS,
+                    },
+                    g.tokens.saturating_sub(capped),
+                )
+            } else {
+                return None;
+            };
+            if saved_tokens < MIN_VIEW_SAVINGS {
+                return None;
+            }
+            let pattern = if g.files.len() == 1 {
+                g.files[0].clone()
+            } else {
+                pattern
+            };
+            Some(ViewRecommendation {
+                pattern,
+                policy,
+                files: g.files.len(),
+                reads: g.reads,
+                tokens: g.tokens,
+                saved_tokens,
+                saved_cost: cost_per_token.map(|c| c * saved_tokens as f64),
+                indexed_files: g.indexed_files,
+            })
+        })
+        .collect();
+    recs.sort_by(|a, b| {
+        b.saved_tokens
+            .cmp(&a.saved_tokens)
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+    recs
+}
+
+/// `src/gen/a.ts` -> `src/gen/*.ts`; paths without an extension stay as-is.
+fn view_group_pattern(path: &str) -> String {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, path),
+    };
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => match dir {
+            Some(dir) => format!("{dir}/*.{ext}"),
+            None => format!("*.{ext}"),
+        },
+        _ => path.to_string(),
+    }
 }
 
 /// Complete analysis of a session.
@@ -425,6 +836,10 @@ pub struct SessionAnalysisReport {
     pub tool_chains: Vec<ToolChain>,
     /// Assistant corrections and apologies
     pub corrections: Vec<Correction>,
+    /// Correction counts by kind, largest first. Aggregates keep only these,
+    /// since turn numbers are not comparable across sessions.
+    #[serde(default)]
+    pub correction_counts: Vec<CorrectionKindCount>,
     /// Context size per turn (input + cache_read)
     pub context_per_turn: Vec<u64>,
     /// File operation frequency (Read/Edit/Write)
@@ -441,6 +856,17 @@ pub struct SessionAnalysisReport {
     pub dedup_tokens: Option<DedupTokenStats>,
     /// Top 10 individual tool results by character count.
     pub largest_tool_results: Vec<LargestToolResult>,
+    /// Tool result volume by content class, largest first.
+    pub tool_result_classes: Vec<ToolResultClassStats>,
+    /// Provider-level API errors (None if the session had none).
+    pub api_errors: Option<ApiErrorStats>,
+    /// Suggested view truncation policies for token-hotspot files, largest saving first.
+    #[serde(default)]
+    pub view_recommendations: Vec<ViewRecommendation>,
+    /// Tool durations, idle gaps and session length from message timestamps
+    /// (None if the log has no timestamps).
+    #[serde(default)]
+    pub latency: Option<LatencyStats>,
     /// Sort hint for tool rows in formatted output.
     /// Valid values: "name" (asc), "calls" (desc, default), "errors" (desc).
     /// Set by the CLI `--sort` flag; not serialized.
@@ -503,6 +929,16 @@ impl SessionAnalysisReport {
                 stat.output_chars += v.output_chars;
             }
 
+            for c in &a.tool_result_classes {
+                add_tool_result_class(
+                    &mut aggregate.tool_result_classes,
+                    c.class,
+                    c.results,
+                    c.chars,
+                    c.tokens,
+                );
+            }
+
             // Collect largest tool results for later re-ranking
             aggregate
                 .largest_tool_results
@@ -531,6 +967,9 @@ impl SessionAnalysisReport {
             }
 
             aggregate.total_turns += a.total_turns;
+            for k in &a.correction_counts {
+                add_correction_kind(&mut aggregate.correction_counts, k.kind.clone(), k.count);
+            }
             aggregate.parallel_opportunities += a.parallel_opportunities;
 
             // Collect tool chains for pattern analysis
@@ -587,6 +1026,20 @@ impl SessionAnalysisReport {
                 *aggregate.actual_cost.get_or_insert(0.0) += cost;
             }
 
+            // Aggregate API error stats
+            if let Some(errors) = &a.api_errors {
+                let agg = aggregate.api_errors.get_or_insert_with(Default::default);
+                for k in &errors.by_kind {
+                    agg.add_kind(k.kind, k.count);
+                }
+                agg.retry_delay_ms += errors.retry_delay_ms;
+                agg.retried_turns += errors.retried_turns;
+                agg.retried_turn_tokens += errors.retried_turn_tokens;
+                if let Some(cost) = errors.retried_turn_cost {
+                    *agg.retried_turn_cost.get_or_insert(0.0) += cost;
+                }
+            }
+
             // Aggregate dedup token stats
             if let Some(dedup) = &a.dedup_tokens {
                 let agg = aggregate
@@ -595,6 +1048,7 @@ impl SessionAnalysisReport {
                 agg.unique_input += dedup.unique_input;
                 agg.unique_output += dedup.unique_output;
                 agg.total_billed += dedup.total_billed;
+                agg.estimated |= dedup.estimated;
             }
         }
 
@@ -620,12 +1074,26 @@ impl SessionAnalysisReport {
                 (dedup.unique_input + dedup.unique_output) as f64 / dedup.total_billed as f64;
         }
 
+        sort_tool_result_classes(&mut aggregate.tool_result_classes);
+
+        if let Some(errors) = &mut aggregate.api_errors {
+            errors.by_kind.sort_by_key(|k| std::cmp::Reverse(k.count));
+        }
+        aggregate
+            .correction_counts
+            .sort_by_key(|k| std::cmp::Reverse(k.count));
+
         // Re-rank and trim largest tool results across all sessions
         aggregate
             .largest_tool_results
             .sort_by_key(|b| std::cmp::Reverse(b.chars));
         aggregate.largest_tool_results.truncate(10);
 
+        aggregate.view_recommendations = recommend_view_policies(&aggregate, &|_| None);
+
+        let latencies: Vec<_> = reports.iter().filter_map(|r| r.latency.as_ref()).collect();
+        aggregate.latency = LatencyStats::merge(&latencies);
+
         // Update format to show aggregate info
         aggregate.format = format!("aggregate ({} sessions)", reports.len());
 
@@ -687,6 +1155,10 @@ impl SessionAnalysisReport {
             lines.push(String::new());
         }
 
+        if let Some(latency) = &self.latency {
+            latency::push_latency(&mut lines, latency);
+        }
+
         // Largest tool results
         if !self.largest_tool_results.is_empty() {
             lines.push("## Largest Tool Results".to_string());
@@ -702,6 +1174,25 @@ impl SessionAnalysisReport {
             lines.push(String::new());
         }
 
+        // Tool result content classes
+        if !self.tool_result_classes.is_empty() {
+            let total: u64 = self.tool_result_classes.iter().map(|c| c.tokens).sum();
+            lines.push("## Tool Result Content".to_string());
+            lines.push(String::new());
+            lines.push("| Class | Results | Est. Tokens | Share |".to_string());
+            lines.push("|-------|---------|-------------|-------|".to_string());
+            for c in &self.tool_result_classes {
+                lines.push(format!(
+                    "| {} | {} | {} | {:.0}% |",
+                    c.class.as_str(),
+                    c.results,
+                    format_tokens(c.tokens),
+                    share(c.tokens, total)
+                ));
+            }
+            lines.push(String::new());
+        }
+
         // Token usage
         if self.token_stats.api_calls > 0 {
             let ts = &self.token_stats;
@@ -786,28 +1277,7 @@ impl SessionAnalysisReport {
 
             // Token efficiency
             if let Some(dedup) = &self.dedup_tokens {
-                lines.push("## Token Efficiency".to_string());
-                lines.push(String::new());
-                lines.push(format!(
-                    "- **Unique input**: {}",
-                    format_tokens(dedup.unique_input)
-                ));
-                lines.push(format!(
-                    "- **Unique output**: {}",
-                    format_tokens(dedup.unique_output)
-                ));
-                lines.push(format!(
-                    "- **Uniqueness ratio**: {:.1}%",
-                    dedup.uniqueness_ratio * 100.0
-                ));
-                let redundant = dedup
-                    .total_billed
-                    .saturating_sub(dedup.unique_input + dedup.unique_output);
-                lines.push(format!(
-                    "- **Redundant context**: {}",
-                    format_tokens(redundant)
-                ));
-                lines.push(String::new());
+                push_token_efficiency(&mut lines, dedup);
             }
 
             // Token growth
@@ -848,6 +1318,9 @@ impl SessionAnalysisReport {
                 }
                 lines.push(String::new());
             }
+        } else if let Some(dedup) = &self.dedup_tokens {
+            // No usage data: content-based estimate only
+            push_token_efficiency(&mut lines, dedup);
         }
 
         // Command breakdown
@@ -920,6 +1393,25 @@ impl SessionAnalysisReport {
             lines.push(String::new());
         }
 
+        // View recommendations
+        if !self.view_recommendations.is_empty() {
+            lines.push("## View Recommendations".to_string());
+            lines.push(String::new());
+            lines.push("| Files | Policy | Reads | Tokens | Est. savings |".to_string());
+            lines.push("|-------|--------|-------|--------|--------------|".to_string());
+            for rec in self.view_recommendations.iter().take(10) {
+                lines.push(format!(
+                    "| {} | {} | {} | {} | {} |",
+                    rec.pattern,
+                    rec.policy,
+                    rec.reads,
+                    format_tokens(rec.tokens),
+                    view_savings(rec)
+                ));
+            }
+            lines.push(String::new());
+        }
+
         // File operations heatmap
         if !self.file_operations.is_empty() {
             lines.push("## File Operations".to_string());
@@ -1024,9 +1516,13 @@ impl SessionAnalysisReport {
         }
 
         // Corrections
-        if !self.corrections.is_empty() {
+        if !self.correction_counts.is_empty() {
             lines.push("## Corrections & Apologies".to_string());
             lines.push(String::new());
+            lines.push(format!(
+                "- **By kind**: {}",
+                correction_kinds_summary(&self.correction_counts)
+            ));
             for correction in &self.corrections {
                 lines.push(format!(
                     "- **Turn {}** [{}]: {}",
@@ -1038,6 +1534,32 @@ impl SessionAnalysisReport {
             lines.push(String::new());
         }
 
+        // Provider API errors
+        if let Some(errors) = &self.api_errors {
+            lines.push("## API Errors".to_string());
+            lines.push(String::new());
+            lines.push(format!(
+                "- **Errors**: {} ({})",
+                errors.total,
+                errors.kinds_summary()
+            ));
+            lines.push(format!(
+                "- **Retry wait**: {:.1}s",
+                errors.retry_delay_ms as f64 / 1000.0
+            ));
+            let cost = errors
+                .retried_turn_cost
+                .map(|c| format!(", ${:.2}", c))
+                .unwrap_or_default();
+            lines.push(format!(
+                "- **Retried turns**: {} ({} tokens{})",
+                errors.retried_turns,
+                format_tokens(errors.retried_turn_tokens),
+                cost
+            ));
+            lines.push(String::new());
+        }
+
         // Error patterns
         if !self.error_patterns.is_empty() {
             lines.push("## Error Patterns".to_string());
@@ -1097,8 +1619,8 @@ impl SessionAnalysisReport {
             let max_calls = tools.first().map(|t| t.calls).unwrap_or(1);
             let max_name_len = tools.iter().map(|t| t.name.len()).max().unwrap_or(10);
 
+            let bar_width = normalize_output::terminal::bar_width(30);
             for tool in tools {
-                let bar_width = 30;
                 let filled = (tool.calls as f64 / max_calls as f64 * bar_width as f64) as usize;
                 let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
 
@@ -1125,6 +1647,10 @@ impl SessionAnalysisReport {
             writeln!(out)?;
         }
 
+        if let Some(latency) = &self.latency {
+            latency::write_latency(out, latency)?;
+        }
+
         // Largest tool results
         if !self.largest_tool_results.is_empty() {
             writeln!(out, "\x1b[1;36m━━━ Largest Tool Results ━━━\x1b[0m")?;
@@ -1141,6 +1667,23 @@ impl SessionAnalysisReport {
             writeln!(out)?;
         }
 
+        // Tool result content classes
+        if !self.tool_result_classes.is_empty() {
+            let total: u64 = self.tool_result_classes.iter().map(|c| c.tokens).sum();
+            writeln!(out, "\x1b[1;36m━━━ Tool Result Content ━━━\x1b[0m")?;
+            for c in &self.tool_result_classes {
+                writeln!(
+                    out,
+                    "{:>14}  \x1b[33m{:>4.0}%\x1b[0m  {:>7} tokens  {:>5} results",
+                    c.class.as_str(),
+                    share(c.tokens, total),
+                    format_tokens(c.tokens),
+                    c.results
+                )?;
+            }
+            writeln!(out)?;
+        }
+
         // Token usage
         if self.token_stats.api_calls > 0 {
             let ts = &self.token_stats;
@@ -1225,34 +1768,25 @@ impl SessionAnalysisReport {
 
             // Token efficiency
             if let Some(dedup) = &self.dedup_tokens {
-                writeln!(out)?;
-                writeln!(out, "\x1b[1;36m━━━ Token Efficiency ━━━\x1b[0m")?;
-                writeln!(
-                    out,
-                    "Unique input: {} | Unique output: {}",
-                    format_tokens(dedup.unique_input),
-                    format_tokens(dedup.unique_output)
-                )?;
-                writeln!(
-                    out,
-                    "Uniqueness: \x1b[33m{:.1}%\x1b[0m",
-                    dedup.uniqueness_ratio * 100.0
-                )?;
-                let redundant = dedup
-                    .total_billed
-                    .saturating_sub(dedup.unique_input + dedup.unique_output);
-                writeln!(out, "Redundant context: {}", format_tokens(redundant))?;
+                write_token_efficiency(out, dedup)?;
             }
             writeln!(out)?;
 
             // Token growth visualization
             if !self.context_per_turn.is_empty() && self.context_per_turn.iter().any(|&c| c > 0) {
                 writeln!(out, "\x1b[1;36m━━━ Context Growth ━━━\x1b[0m")?;
-                for line in token_growth_chart(&self.context_per_turn, 20) {
+                for line in token_growth_chart(
+                    &self.context_per_turn,
+                    normalize_output::terminal::bar_width(20),
+                ) {
                     writeln!(out, "{}", line)?;
                 }
                 writeln!(out)?;
             }
+        } else if let Some(dedup) = &self.dedup_tokens {
+            // No usage data: content-based estimate only
+            write_token_efficiency(out, dedup)?;
+            writeln!(out)?;
         }
 
         // Command breakdown with bar charts
@@ -1271,8 +1805,8 @@ impl SessionAnalysisReport {
                 .max()
                 .unwrap_or(8);
 
+            let bar_width = normalize_output::terminal::bar_width(20);
             for stat in &self.command_stats {
-                let bar_width = 20;
                 let filled =
                     (stat.total_calls as f64 / max_calls as f64 * bar_width as f64) as usize;
                 let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
@@ -1323,8 +1857,8 @@ impl SessionAnalysisReport {
             let mut ops: Vec<_> = self.file_operations.values().collect();
             ops.sort_by_key(|b| std::cmp::Reverse(b.total()));
 
+            let bar_width = normalize_output::terminal::bar_width(20);
             for op in ops.iter().take(15) {
-                let bar_width = 20;
                 let max_total = ops.first().map(|o| o.total()).unwrap_or(1);
                 let filled = (op.total() as f64 / max_total as f64 * bar_width as f64) as usize;
                 let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
@@ -1366,8 +1900,8 @@ impl SessionAnalysisReport {
 
             let max_tokens = paths.first().map(|(_, t)| **t).unwrap_or(1);
 
+            let bar_width = normalize_output::terminal::bar_width(20);
             for (path, tokens) in paths.iter().take(10) {
-                let bar_width = 20;
                 let filled = (**tokens as f64 / max_tokens as f64 * bar_width as f64) as usize;
                 let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
                 writeln!(out, "{} {:>8} {}", bar, format_tokens(**tokens), path)?;
@@ -1375,6 +1909,23 @@ impl SessionAnalysisReport {
             writeln!(out)?;
         }
 
+        // View recommendations
+        if !self.view_recommendations.is_empty() {
+            writeln!(out, "\x1b[1;36m━━━ View Recommendations ━━━\x1b[0m")?;
+            for rec in self.view_recommendations.iter().take(10) {
+                writeln!(
+                    out,
+                    "\x1b[33m{:<14}\x1b[0m {} \x1b[2m({} reads, {})\x1b[0m saves \x1b[32m{}\x1b[0m",
+                    rec.policy.to_string(),
+                    rec.pattern,
+                    rec.reads,
+                    format_tokens(rec.tokens),
+                    view_savings(rec)
+                )?;
+            }
+            writeln!(out)?;
+        }
+
         // Message types (compact)
         if !self.message_counts.is_empty() {
             writeln!(out, "\x1b[1;36m━━━ Message Types ━━━\x1b[0m")?;
@@ -1472,9 +2023,14 @@ impl SessionAnalysisReport {
         }
 
         // Corrections
-        if !self.corrections.is_empty() {
+        if !self.correction_counts.is_empty() {
             writeln!(out)?;
             writeln!(out, "\x1b[1;36m━━━ Corrections & Apologies ━━━\x1b[0m")?;
+            writeln!(
+                out,
+                "By kind: {}",
+                correction_kinds_summary(&self.correction_counts)
+            )?;
             for correction in &self.corrections {
                 writeln!(
                     out,
@@ -1486,6 +2042,33 @@ impl SessionAnalysisReport {
             }
         }
 
+        // Provider API errors
+        if let Some(errors) = &self.api_errors {
+            writeln!(out)?;
+            writeln!(out, "\x1b[1;36m━━━ API Errors ━━━\x1b[0m")?;
+            writeln!(
+                out,
+                "\x1b[31m{}\x1b[0m errors ({})",
+                errors.total,
+                errors.kinds_summary()
+            )?;
+            writeln!(
+                out,
+                "Retry wait: {:.1}s",
+                errors.retry_delay_ms as f64 / 1000.0
+            )?;
+            write!(
+                out,
+                "Retried turns: {} ({} tokens",
+                errors.retried_turns,
+                format_tokens(errors.retried_turn_tokens)
+            )?;
+            if let Some(cost) = errors.retried_turn_cost {
+                write!(out, ", \x1b[33m${:.2}\x1b[0m", cost)?;
+            }
+            writeln!(out, ")")?;
+        }
+
         Ok(())
     }
 }
@@ -1501,6 +2084,29 @@ impl OutputFormatter for SessionAnalysisReport {
         // Call the inherent method (colored bar charts)
         SessionAnalysisReport::format_pretty(self)
     }
+
+    /// Tool usage, one row per tool in `--sort` order.
+    fn format_table(&self) -> Option<normalize_output::Table> {
+        let mut table = normalize_output::Table::new([
+            "tool",
+            "calls",
+            "errors",
+            "success_rate",
+            "output_chars",
+        ]);
+        let mut tools: Vec<_> = self.tool_stats.values().collect();
+        sort_tool_stats_by_hint(&mut tools, self.tool_sort.as_deref());
+        for tool in tools {
+            table.push_row([
+                tool.name.clone(),
+                tool.calls.to_string(),
+                tool.errors.to_string(),
+                format!("{:.3}", tool.success_rate()),
+                tool.output_chars.to_string(),
+            ]);
+        }
+        Some(table)
+    }
 }
 
 impl std::fmt::Display for SessionAnalysisReport {
@@ -1509,26 +2115,327 @@ impl std::fmt::Display for SessionAnalysisReport {
     }
 }
 
-/// Format token count with K/M suffix.
-fn format_tokens(tokens: u64) -> String {
-    if tokens >= 1_000_000 {
-        format!("{:.1}M", tokens as f64 / 1_000_000.0)
-    } else if tokens >= 1_000 {
-        format!("{:.1}K", tokens as f64 / 1_000.0)
-    } else {
-        tokens.to_string()
+/// Period length for [`SessionTrendReport`] buckets.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, schemars::JsonSchema, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendBucket {
+    #[default]
+    Day,
+    /// ISO weeks, keyed by the Monday they start on.
+    Week,
+}
+
+impl TrendBucket {
+    /// Bucket key for a `YYYY-MM-DD` date: the date itself for days, the
+    /// Monday of its week for weeks. `None` if `date` does not parse.
+    pub fn key(self, date: &str) -> Option<String> {
+        let days = days_from_date(date)?;
+        let start = match self {
+            TrendBucket::Day => days,
+            // 1970-01-01 was a Thursday.
+            TrendBucket::Week => days - (days + 3).rem_euclid(7),
+        };
+        Some(date_from_days(start))
     }
 }
 
-/// Generate ASCII bar chart for token growth.
-fn token_growth_chart(context_per_turn: &[u64], width: usize) -> Vec<String> {
-    if context_per_turn.is_empty() {
-        return vec![];
+impl std::str::FromStr for TrendBucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(TrendBucket::Day),
+            "week" | "weekly" => Ok(TrendBucket::Week),
+            _ => Err(format!(
+                "unknown trend bucket '{}': expected 'day' or 'week'",
+                s
+            )),
+        }
     }
+}
 
-    let max_context = *context_per_turn.iter().max().unwrap_or(&1);
-    let threshold_80k = 80_000;
-    let threshold_100k = 100_000;
+impl std::fmt::Display for TrendBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrendBucket::Day => write!(f, "day"),
+            TrendBucket::Week => write!(f, "week"),
+        }
+    }
+}
+
+/// Aggregate metrics for the sessions in one trend period.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct TrendPoint {
+    /// Period start (`YYYY-MM-DD`).
+    pub period: String,
+    pub sessions: usize,
+    pub turns: usize,
+    pub tool_calls: usize,
+    pub tool_errors: usize,
+    /// Tool errors / tool calls (0.0–1.0).
+    pub error_rate: f64,
+    pub corrections: usize,
+    pub api_errors: usize,
+    /// Input tokens including cache reads.
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    /// Cost in USD (None if no model pricing was known).
+    pub cost_usd: Option<f64>,
+    /// Retry hotspot with the most failures in the period.
+    pub top_retry: Option<String>,
+}
+
+impl TrendPoint {
+    fn from_aggregate(period: String, sessions: usize, a: &SessionAnalysisReport) -> Self {
+        let tool_calls = a.total_tool_calls();
+        let tool_errors = a.total_errors();
+        Self {
+            period,
+            sessions,
+            turns: a.total_turns,
+            tool_calls,
+            tool_errors,
+            error_rate: if tool_calls > 0 {
+                tool_errors as f64 / tool_calls as f64
+            } else {
+                0.0
+            },
+            corrections: a.correction_counts.iter().map(|k| k.count).sum(),
+            api_errors: a.api_errors.as_ref().map_or(0, |e| e.total),
+            tokens_in: a.token_stats.total_input + a.token_stats.cache_read,
+            tokens_out: a.token_stats.total_output,
+            cost_usd: a.actual_cost,
+            top_retry: a.retry_hotspots.first().map(|h| h.pattern.clone()),
+        }
+    }
+}
+
+/// Per-period aggregates across many sessions, oldest period first.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema, Deserialize)]
+pub struct SessionTrendReport {
+    pub bucket: TrendBucket,
+    pub points: Vec<TrendPoint>,
+    /// Every session folded together.
+    pub total: TrendPoint,
+}
+
+impl SessionTrendReport {
+    /// Bucket `(YYYY-MM-DD, report)` pairs by period and aggregate each
+    /// bucket with [`SessionAnalysisReport::aggregate`]. Reports whose date
+    /// does not parse count toward the total only.
+    pub fn build(bucket: TrendBucket, dated: &[(String, SessionAnalysisReport)]) -> Self {
+        let mut periods: std::collections::BTreeMap<String, Vec<SessionAnalysisReport>> =
+            std::collections::BTreeMap::new();
+        for (date, report) in dated {
+            if let Some(key) = bucket.key(date) {
+                periods.entry(key).or_default().push(report.clone());
+            }
+        }
+        let points = periods
+            .into_iter()
+            .map(|(period, reports)| {
+                let agg = SessionAnalysisReport::aggregate(&reports);
+                TrendPoint::from_aggregate(period, reports.len(), &agg)
+            })
+            .collect();
+        let all: Vec<SessionAnalysisReport> = dated.iter().map(|(_, r)| r.clone()).collect();
+        let total = TrendPoint::from_aggregate(
+            "total".to_string(),
+            all.len(),
+            &SessionAnalysisReport::aggregate(&all),
+        );
+        Self {
+            bucket,
+            points,
+            total,
+        }
+    }
+
+    fn rows(&self) -> Vec<String> {
+        let mut rows = vec![format!(
+            "{:<10}  {:>8}  {:>6}  {:>6}  {:>6}  {:>5}  {:>4}  {:>8}  {:>8}  {:>8}  top retry",
+            "period", "sessions", "turns", "tools", "err%", "corr", "api", "in", "out", "cost"
+        )];
+        for p in self.points.iter().chain(std::iter::once(&self.total)) {
+            rows.push(format!(
+                "{:<10}  {:>8}  {:>6}  {:>6}  {:>5.1}%  {:>5}  {:>4}  {:>8}  {:>8}  {:>8}  {}",
+                p.period,
+                p.sessions,
+                p.turns,
+                p.tool_calls,
+                p.error_rate * 100.0,
+                p.corrections,
+                p.api_errors,
+                format_tokens(p.tokens_in),
+                format_tokens(p.tokens_out),
+                p.cost_usd
+                    .map(|c| format!("${:.2}", c))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                p.top_retry.as_deref().unwrap_or("-"),
+            ));
+        }
+        rows
+    }
+}
+
+impl OutputFormatter for SessionTrendReport {
+    fn format_text(&self) -> String {
+        let unit = match self.bucket {
+            TrendBucket::Day => "day",
+            TrendBucket::Week => "week (starting Monday)",
+        };
+        let mut lines = vec![
+            format!("# Session Trends by {}", unit),
+            String::new(),
+            "```".to_string(),
+        ];
+        lines.extend(self.rows());
+        lines.push("```".to_string());
+        lines.join("\n")
+    }
+
+    fn format_pretty(&self) -> String {
+        let mut rows = self.rows();
+        let total = rows.pop().unwrap_or_default();
+        let mut out = format!("\x1b[1m{}\x1b[0m\n", rows.remove(0));
+        for (row, p) in rows.iter().zip(&self.points) {
+            if p.error_rate > self.total.error_rate * 1.5 && p.tool_errors > 0 {
+                out.push_str(&format!("\x1b[31m{}\x1b[0m\n", row));
+            } else {
+                out.push_str(row);
+                out.push('\n');
+            }
+        }
+        out.push_str(&format!("\x1b[1m{}\x1b[0m", total));
+        out
+    }
+}
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date.
+fn days_from_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // Civil-from-days inverse, with years starting in March.
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// `YYYY-MM-DD` for a count of days since 1970-01-01.
+fn date_from_days(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Format token count with K/M suffix.
+/// Markdown "Token Efficiency" section.
+fn push_token_efficiency(lines: &mut Vec<String>, dedup: &DedupTokenStats) {
+    lines.push(if dedup.estimated {
+        "## Token Efficiency (estimated)".to_string()
+    } else {
+        "## Token Efficiency".to_string()
+    });
+    lines.push(String::new());
+    lines.push(format!(
+        "- **Unique input**: {}",
+        format_tokens(dedup.unique_input)
+    ));
+    lines.push(format!(
+        "- **Unique output**: {}",
+        format_tokens(dedup.unique_output)
+    ));
+    lines.push(format!(
+        "- **Uniqueness ratio**: {:.1}%",
+        dedup.uniqueness_ratio * 100.0
+    ));
+    let redundant = dedup
+        .total_billed
+        .saturating_sub(dedup.unique_input + dedup.unique_output);
+    lines.push(format!(
+        "- **Redundant context**: {}",
+        format_tokens(redundant)
+    ));
+    lines.push(String::new());
+}
+
+/// ANSI "Token Efficiency" section.
+fn write_token_efficiency(out: &mut String, dedup: &DedupTokenStats) -> std::fmt::Result {
+    use std::fmt::Write;
+    writeln!(out)?;
+    let estimated = if dedup.estimated { " (estimated)" } else { "" };
+    writeln!(out, "\x1b[1;36m━━━ Token Efficiency{estimated} ━━━\x1b[0m")?;
+    writeln!(
+        out,
+        "Unique input: {} | Unique output: {}",
+        format_tokens(dedup.unique_input),
+        format_tokens(dedup.unique_output)
+    )?;
+    writeln!(
+        out,
+        "Uniqueness: \x1b[33m{:.1}%\x1b[0m",
+        dedup.uniqueness_ratio * 100.0
+    )?;
+    let redundant = dedup
+        .total_billed
+        .saturating_sub(dedup.unique_input + dedup.unique_output);
+    writeln!(out, "Redundant context: {}", format_tokens(redundant))
+}
+
+/// `~12.3K tokens (~$0.40)`
+fn view_savings(rec: &ViewRecommendation) -> String {
+    let mut s = format!("~{} tokens", format_tokens(rec.saved_tokens));
+    if let Some(cost) = rec.saved_cost {
+        s.push_str(&format!(" (~${:.2})", cost));
+    }
+    s
+}
+
+fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{:.1}K", tokens as f64 / 1_000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// `part` as a percentage of `total` (0 when `total` is 0).
+fn share(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
+/// Generate ASCII bar chart for token growth.
+fn token_growth_chart(context_per_turn: &[u64], width: usize) -> Vec<String> {
+    if context_per_turn.is_empty() {
+        return vec![];
+    }
+
+    let max_context = *context_per_turn.iter().max().unwrap_or(&1);
+    let threshold_80k = 80_000;
+    let threshold_100k = 100_000;
 
     let mut lines = Vec::new();
 
@@ -1581,85 +2488,160 @@ fn token_growth_chart(context_per_turn: &[u64], width: usize) -> Vec<String> {
     lines
 }
 
-/// Categorize an error by its content.
-pub fn categorize_error(error_text: &str) -> &'static str {
-    let text = error_text.to_lowercase();
-    if text.contains("exit code") {
-        "Command failure"
-    } else if text.contains("not found") {
-        "File not found"
-    } else if text.contains("permission") {
-        "Permission error"
-    } else if text.contains("timeout") {
-        "Timeout"
-    } else if text.contains("syntax") {
-        "Syntax error"
-    } else if text.contains("import") {
-        "Import error"
-    } else {
-        "Other"
+/// Classify a tool result by its content, using the tool name as a tie-breaker.
+///
+/// Content signals win over the tool name: a `Bash` result holding a Python
+/// traceback is a stack trace, and one holding `git diff` output is a diff.
+/// Plain output of file-reading tools is file contents, and of shell tools is
+/// command output.
+pub fn classify_tool_result(tool_name: Option<&str>, content: &str) -> ToolResultClass {
+    let trimmed = content.trim();
+    if looks_like_stack_trace(trimmed) {
+        return ToolResultClass::StackTrace;
+    }
+    if looks_like_diff(trimmed) {
+        return ToolResultClass::Diff;
+    }
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return ToolResultClass::Json;
+    }
+    let tool = tool_name.unwrap_or("").to_lowercase();
+    if matches!(
+        tool.as_str(),
+        "read" | "read_file" | "readfile" | "view_file" | "open_file"
+    ) || looks_like_numbered_listing(trimmed)
+    {
+        return ToolResultClass::FileContents;
     }
+    if matches!(
+        tool.as_str(),
+        "bash" | "shell" | "exec_command" | "run_command" | "execute_command" | "run_shell_command"
+    ) {
+        return ToolResultClass::CommandOutput;
+    }
+    ToolResultClass::Other
 }
 
-/// Extract file path from tool input JSON.
-fn extract_file_path(tool_name: &str, input: &serde_json::Value) -> Option<String> {
-    match tool_name {
-        "Read" | "Write" | "Edit" => {
-            if let Some(path) = input.get("file_path").and_then(|v| v.as_str()) {
-                return Some(normalize_path(path));
-            }
-        }
-        _ => {}
+fn looks_like_stack_trace(text: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "Traceback (most recent call last)",
+        "stack backtrace:",
+        "Exception in thread \"",
+        "panic: runtime error",
+    ];
+    if MARKERS.iter().any(|m| text.contains(m)) {
+        return true;
     }
-    None
+    // Rust panics, JS/Java/C# frames ("    at foo (file:1:2)"), Go goroutine dumps.
+    let frames = text
+        .lines()
+        .filter(|l| {
+            let l = l.trim_start();
+            l.starts_with("at ") && (l.contains(':') || l.contains('('))
+        })
+        .count();
+    frames >= 2
+        || (text.contains("panicked at") && text.contains("note: run with `RUST_BACKTRACE"))
+        || text
+            .lines()
+            .any(|l| l.starts_with("goroutine ") && l.ends_with("]:"))
 }
 
-/// Detect correction patterns in assistant text.
-/// Returns (category, excerpt) if a correction is found.
-pub fn detect_correction(text: &str) -> Option<(CorrectionKind, String)> {
-    let lower = text.to_lowercase();
+fn looks_like_diff(text: &str) -> bool {
+    text.starts_with("diff --git")
+        || text.lines().any(|l| l.starts_with("diff --git "))
+        || (text.lines().any(|l| l.starts_with("--- "))
+            && text.lines().any(|l| l.starts_with("+++ "))
+            && text.lines().any(|l| l.starts_with("@@ ")))
+}
 
-    // Look for apology patterns
-    let apology_phrases = ["i apologize", "i'm sorry", "sorry about", "my apologies"];
-    for phrase in &apology_phrases {
-        if let Some(pos) = lower.find(phrase) {
-            let excerpt = text.chars().skip(pos).take(80).collect();
-            return Some((CorrectionKind::Apology, excerpt));
+/// Line-numbered file listings (`cat -n`, Claude Code's `Read` output `   12→...`).
+fn looks_like_numbered_listing(text: &str) -> bool {
+    let mut total = 0;
+    let mut numbered = 0;
+    for line in text.lines().take(20) {
+        total += 1;
+        let rest = line.trim_start();
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits > 0 && matches!(rest[digits..].chars().next(), Some('→' | '\t')) {
+            numbered += 1;
         }
     }
+    total >= 2 && numbered * 10 >= total * 8
+}
 
-    // Look for mistake acknowledgment
-    let mistake_phrases = [
-        "i made a mistake",
-        "i was wrong",
-        "that was incorrect",
-        "my mistake",
-    ];
-    for phrase in &mistake_phrases {
-        if let Some(pos) = lower.find(phrase) {
-            let excerpt = text.chars().skip(pos).take(80).collect();
-            return Some((CorrectionKind::Mistake, excerpt));
-        }
+/// Add `results` tool results totalling `chars`/`tokens` to the stats for `class`.
+fn add_tool_result_class(
+    classes: &mut Vec<ToolResultClassStats>,
+    class: ToolResultClass,
+    results: usize,
+    chars: usize,
+    tokens: u64,
+) {
+    if let Some(c) = classes.iter_mut().find(|c| c.class == class) {
+        c.results += results;
+        c.chars += chars;
+        c.tokens += tokens;
+    } else {
+        classes.push(ToolResultClassStats {
+            class,
+            results,
+            chars,
+            tokens,
+        });
     }
+}
+
+fn sort_tool_result_classes(classes: &mut [ToolResultClassStats]) {
+    classes.sort_by_key(|c| std::cmp::Reverse(c.tokens));
+}
 
-    // Look for "let me fix" patterns
-    let fix_phrases = ["let me fix", "i'll fix", "let me correct"];
-    for phrase in &fix_phrases {
-        if let Some(pos) = lower.find(phrase) {
-            let excerpt = text.chars().skip(pos).take(80).collect();
-            return Some((CorrectionKind::LetMeFix, excerpt));
+/// Count one turn's provider API errors, and its tokens/cost if it hit any.
+fn add_turn_api_errors(stats: &mut ApiErrorStats, turn: &Turn) {
+    if turn.api_errors.is_empty() {
+        return;
+    }
+    for error in &turn.api_errors {
+        stats.add_kind(error.kind, 1);
+        stats.retry_delay_ms += error.retry_delay_ms.unwrap_or(0);
+    }
+    stats.retried_turns += 1;
+    if let Some(usage) = &turn.token_usage {
+        stats.retried_turn_tokens += usage.input
+            + usage.output
+            + usage.cache_read.unwrap_or(0)
+            + usage.cache_create.unwrap_or(0);
+        if let Some(pricing) = usage
+            .model
+            .as_deref()
+            .and_then(ModelPricing::from_model_str)
+        {
+            *stats.retried_turn_cost.get_or_insert(0.0) += pricing.calculate_turn_cost(usage);
         }
     }
+}
 
-    // Look for "actually" corrections
-    let actually_phrases = ["actually,", "actually i", "actually that"];
-    for phrase in &actually_phrases {
-        if let Some(pos) = lower.find(phrase) {
-            let excerpt = text.chars().skip(pos).take(80).collect();
-            return Some((CorrectionKind::Actually, excerpt));
-        }
+/// Finished API error stats, or `None` when the session hit no errors.
+fn finish_api_error_stats(mut stats: ApiErrorStats) -> Option<ApiErrorStats> {
+    if stats.total == 0 {
+        return None;
     }
+    stats.by_kind.sort_by_key(|k| std::cmp::Reverse(k.count));
+    Some(stats)
+}
 
+/// Extract file path from tool input JSON.
+fn extract_file_path(tool_name: &str, input: &serde_json::Value) -> Option<String> {
+    match tool_name {
+        "Read" | "Write" | "Edit" => {
+            if let Some(path) = input.get("file_path").and_then(|v| v.as_str()) {
+                return Some(normalize_path(path));
+            }
+        }
+        _ => {}
+    }
     None
 }
 
@@ -2060,18 +3042,57 @@ pub fn extract_tool_patterns(chains: &[ToolChain]) -> Vec<ToolPattern> {
 }
 
 /// Analyze a parsed session and compute statistics.
+///
+/// Content-derived token counts use [`normalize_tokens::default_tokenizer`].
 pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
-    let mut analysis = SessionAnalysisReport::new(session.path.clone(), &session.format);
+    analyze_session_with(session, normalize_tokens::default_tokenizer())
+}
 
-    // Count message types by role. Role::User = human input, Role::Tool = tool results.
-    for turn in &session.turns {
-        for msg in &turn.messages {
-            *analysis
-                .message_counts
-                .entry(msg.role.to_string())
-                .or_insert(0) += 1;
-        }
-    }
+/// [`analyze_session`] with an explicit tokenizer for content-derived token
+/// counts (tool-result classes, and dedup stats for logs without usage data).
+pub fn analyze_session_with(session: &Session, tokenizer: &dyn Tokenizer) -> SessionAnalysisReport {
+    analyze_turns_with(
+        session.path.clone(),
+        &session.format,
+        &session.turns,
+        tokenizer,
+    )
+}
+
+/// Largest tool results kept for the report.
+const LARGEST_TOOL_RESULTS: usize = 10;
+
+/// Analyze a session one turn at a time.
+///
+/// Makes a single pass and keeps no turn after processing it, so memory stays
+/// flat however long the transcript is — pair it with
+/// `normalize_chat_sessions::SessionReader` to analyze a log without loading
+/// it. Accepts owned turns or references (`&session.turns`).
+pub fn analyze_turns<I>(session_path: PathBuf, format: &str, turns: I) -> SessionAnalysisReport
+where
+    I: IntoIterator,
+    I::Item: Borrow<Turn>,
+{
+    analyze_turns_with(
+        session_path,
+        format,
+        turns,
+        normalize_tokens::default_tokenizer(),
+    )
+}
+
+/// [`analyze_turns`] with an explicit tokenizer.
+pub fn analyze_turns_with<I>(
+    session_path: PathBuf,
+    format: &str,
+    turns: I,
+    tokenizer: &dyn Tokenizer,
+) -> SessionAnalysisReport
+where
+    I: IntoIterator,
+    I::Item: Borrow<Turn>,
+{
+    let mut analysis = SessionAnalysisReport::new(session_path, format);
 
     // Analyze tool usage, detect tool chains, and collect command data
     let mut current_chain: Option<Vec<(usize, String)>> = None;
@@ -2084,8 +3105,29 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
     let mut output_tokens_per_turn: Vec<u64> = Vec::new();
     // Candidates for largest tool results: (chars, turn_idx, tool_name, preview)
     let mut tool_result_candidates: Vec<(usize, usize, String, String)> = Vec::new();
+    // Token usage accumulators
+    let mut actual_cost_sum: f64 = 0.0;
+    let mut has_model_pricing = false;
+    let mut prev_context = 0u64;
+    let mut unique_input = 0u64;
+    let mut api_errors = ApiErrorStats::default();
+    // Content-based estimate, only needed while no turn has reported billed tokens.
+    let mut dedup_estimate = Some(DedupEstimate::default());
+    let mut latency = latency::LatencyTracker::default();
+
+    for (turn_idx, turn) in turns.into_iter().enumerate() {
+        let turn: &Turn = turn.borrow();
+        analysis.total_turns += 1;
+        latency.add_turn(turn);
+
+        // Count message types by role. Role::User = human input, Role::Tool = tool results.
+        for msg in &turn.messages {
+            *analysis
+                .message_counts
+                .entry(msg.role.to_string())
+                .or_insert(0) += 1;
+        }
 
-    for (turn_idx, turn) in session.turns.iter().enumerate() {
         let mut tool_uses_in_turn = 0;
         let mut tool_name_in_turn: Option<String> = None;
 
@@ -2095,6 +3137,8 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
         let mut tool_errors: HashMap<String, bool> = HashMap::new();
         // Map tool_use_id -> tool_name for result attribution
         let mut tool_id_to_name: HashMap<String, String> = HashMap::new();
+        // Map Read tool_use_id -> file path, for per-file token hotspots
+        let mut read_paths: HashMap<String, String> = HashMap::new();
 
         for msg in &turn.messages {
             // Detect corrections in assistant messages
@@ -2134,7 +3178,10 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
                                     ..Default::default()
                                 });
                             match name.as_str() {
-                                "Read" => op.reads += 1,
+                                "Read" => {
+                                    op.reads += 1;
+                                    read_paths.insert(id.clone(), file_path.clone());
+                                }
                                 "Edit" => op.edits += 1,
                                 "Write" => op.writes += 1,
                                 _ => {}
@@ -2165,6 +3212,22 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
 
                         // Accumulate output_chars and collect largest result candidates
                         let content_chars = content.chars().count();
+                        let content_tokens = tokenizer.count(content) as u64;
+                        let class = classify_tool_result(
+                            tool_id_to_name.get(tool_use_id).map(String::as_str),
+                            content,
+                        );
+                        add_tool_result_class(
+                            &mut analysis.tool_result_classes,
+                            class,
+                            1,
+                            content_chars,
+                            content_tokens,
+                        );
+                        if !*is_error && let Some(path) = read_paths.get(tool_use_id) {
+                            *analysis.file_tokens.entry(path.clone()).or_insert(0) +=
+                                content_tokens;
+                        }
                         if let Some(tool_name) = tool_id_to_name.get(tool_use_id) {
                             if let Some(stat) = analysis.tool_stats.get_mut(tool_name) {
                                 stat.output_chars += content_chars;
@@ -2181,23 +3244,32 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
                                 tool_name.clone(),
                                 preview,
                             ));
+                            if tool_result_candidates.len() >= 8 * LARGEST_TOOL_RESULTS {
+                                // Stable sort keeps the earliest of equal-sized results,
+                                // as the final sort over every candidate would.
+                                tool_result_candidates.sort_by_key(|b| std::cmp::Reverse(b.0));
+                                tool_result_candidates.truncate(LARGEST_TOOL_RESULTS);
+                            }
                         }
 
                         if *is_error {
                             // Attribute error to tool stat
                             // Find which tool this result belongs to by scanning the turn
+                            let mut error_tool = None;
                             for m in &turn.messages {
                                 for b in &m.content {
                                     if let ContentBlock::ToolUse { id, name, .. } = b
                                         && id == tool_use_id
-                                        && let Some(stat) = analysis.tool_stats.get_mut(name)
                                     {
-                                        stat.errors += 1;
+                                        error_tool = Some(name.as_str());
+                                        if let Some(stat) = analysis.tool_stats.get_mut(name) {
+                                            stat.errors += 1;
+                                        }
                                     }
                                 }
                             }
 
-                            let category = categorize_error(content);
+                            let category = categorize_error(error_tool, content);
                             let pattern = analysis
                                 .error_patterns
                                 .iter_mut()
@@ -2255,38 +3327,8 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
                 analysis.tool_chains.push(ToolChain { tools, turn_range });
             }
         }
-    }
-
-    // Handle final chain
-    if let Some(chain) = current_chain
-        && chain.len() >= 3
-    {
-        let tools: Vec<String> = chain.iter().map(|(_, name)| name.clone()).collect();
-        let turn_range = (chain[0].0, chain[chain.len() - 1].0);
-        analysis.tool_chains.push(ToolChain { tools, turn_range });
-    }
 
-    // Build largest_tool_results: top 10 individual results by char count
-    tool_result_candidates.sort_by_key(|b| std::cmp::Reverse(b.0));
-    analysis.largest_tool_results = tool_result_candidates
-        .into_iter()
-        .take(10)
-        .map(|(chars, turn, tool_name, preview)| LargestToolResult {
-            tool_name,
-            chars,
-            turn,
-            preview,
-        })
-        .collect();
-
-    // Analyze token usage and build output_tokens_per_turn
-    analysis.total_turns = session.turns.len();
-    let mut actual_cost_sum: f64 = 0.0;
-    let mut has_model_pricing = false;
-    let mut prev_context = 0u64;
-    let mut unique_input = 0u64;
-
-    for turn in &session.turns {
+        // Token usage and output_tokens_per_turn
         if let Some(usage) = &turn.token_usage {
             analysis.token_stats.api_calls += 1;
             analysis.token_stats.total_input += usage.input;
@@ -2318,12 +3360,51 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
             analysis.context_per_turn.push(0);
             output_tokens_per_turn.push(0);
         }
+
+        add_turn_api_errors(&mut api_errors, turn);
+
+        if analysis.token_stats.total_input
+            + analysis.token_stats.cache_read
+            + analysis.token_stats.total_output
+            > 0
+        {
+            dedup_estimate = None;
+        } else if let Some(estimate) = &mut dedup_estimate {
+            estimate.add_turn(turn, tokenizer);
+        }
     }
 
+    // Handle final chain
+    if let Some(chain) = current_chain
+        && chain.len() >= 3
+    {
+        let tools: Vec<String> = chain.iter().map(|(_, name)| name.clone()).collect();
+        let turn_range = (chain[0].0, chain[chain.len() - 1].0);
+        analysis.tool_chains.push(ToolChain { tools, turn_range });
+    }
+
+    sort_tool_result_classes(&mut analysis.tool_result_classes);
+
+    // Build largest_tool_results: top 10 individual results by char count
+    tool_result_candidates.sort_by_key(|b| std::cmp::Reverse(b.0));
+    analysis.largest_tool_results = tool_result_candidates
+        .into_iter()
+        .take(LARGEST_TOOL_RESULTS)
+        .map(|(chars, turn, tool_name, preview)| LargestToolResult {
+            tool_name,
+            chars,
+            turn,
+            preview,
+        })
+        .collect();
+
     if has_model_pricing {
         analysis.actual_cost = Some(actual_cost_sum);
     }
 
+    analysis.api_errors = finish_api_error_stats(api_errors);
+    analysis.latency = latency.finish();
+
     // Compute dedup token stats
     let total_billed = analysis.token_stats.total_input
         + analysis.token_stats.cache_read
@@ -2336,9 +3417,14 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
             unique_output,
             total_billed,
             uniqueness_ratio: unique_total as f64 / total_billed as f64,
+            estimated: false,
         });
+    } else {
+        analysis.dedup_tokens = dedup_estimate.and_then(DedupEstimate::finish);
     }
 
+    analysis.view_recommendations = recommend_view_policies(&analysis, &|_| None);
+
     // Build command stats and retry hotspots
     analysis.command_stats = build_command_stats(&command_invocations, &output_tokens_per_turn);
     analysis.retry_hotspots = detect_retry_hotspots(&retry_candidates, &output_tokens_per_turn);
@@ -2348,7 +3434,73 @@ pub fn analyze_session(session: &Session) -> SessionAnalysisReport {
         .error_patterns
         .sort_by_key(|b| std::cmp::Reverse(b.count));
 
+    for c in &analysis.corrections {
+        add_correction_kind(&mut analysis.correction_counts, c.category.clone(), 1);
+    }
     analysis
+        .correction_counts
+        .sort_by_key(|k| std::cmp::Reverse(k.count));
+
+    analysis
+}
+
+/// Dedup stats counted from message content, for logs without usage data.
+///
+/// Assumes one API call per turn whose input is every message so far: user,
+/// tool and system content is new input once, and each call re-reads the
+/// whole transcript up to it.
+#[derive(Default)]
+struct DedupEstimate {
+    context: u64,
+    unique_input: u64,
+    unique_output: u64,
+    total_billed: u64,
+}
+
+impl DedupEstimate {
+    fn add_turn(&mut self, turn: &Turn, tokenizer: &dyn Tokenizer) {
+        let mut input = 0u64;
+        let mut output = 0u64;
+        for msg in &turn.messages {
+            let tokens: u64 = msg
+                .content
+                .iter()
+                .map(|block| content_block_tokens(block, tokenizer))
+                .sum();
+            if msg.role == Role::Assistant {
+                output += tokens;
+            } else {
+                input += tokens;
+            }
+        }
+        self.unique_input += input;
+        self.unique_output += output;
+        self.context += input;
+        self.total_billed += self.context + output;
+        self.context += output;
+    }
+
+    fn finish(self) -> Option<DedupTokenStats> {
+        (self.total_billed > 0).then(|| DedupTokenStats {
+            unique_input: self.unique_input,
+            unique_output: self.unique_output,
+            total_billed: self.total_billed,
+            uniqueness_ratio: (self.unique_input + self.unique_output) as f64
+                / self.total_billed as f64,
+            estimated: true,
+        })
+    }
+}
+
+fn content_block_tokens(block: &ContentBlock, tokenizer: &dyn Tokenizer) -> u64 {
+    let tokens = match block {
+        ContentBlock::Text { text } | ContentBlock::Thinking { text } => tokenizer.count(text),
+        ContentBlock::ToolUse { name, input, .. } => {
+            tokenizer.count(name) + tokenizer.count(&input.to_string())
+        }
+        ContentBlock::ToolResult { content, .. } => tokenizer.count(content),
+    };
+    tokens as u64
 }
 
 #[cfg(test)]
@@ -2384,6 +3536,548 @@ mod tests {
         assert_eq!(agg.format, "aggregate (2 sessions)");
     }
 
+    #[test]
+    fn classify_tool_result_by_content() {
+        let class = |tool, text| classify_tool_result(Some(tool), text);
+        assert_eq!(
+            class(
+                "Bash",
+                "Traceback (most recent call last):\n  File \"a.py\", line 1\nKeyError: 'x'"
+            ),
+            ToolResultClass::StackTrace
+        );
+        assert_eq!(
+            class(
+                "Bash",
+                "Error: boom\n    at run (/app/index.js:3:9)\n    at main (/app/index.js:9:1)"
+            ),
+            ToolResultClass::StackTrace
+        );
+        assert_eq!(
+            class(
+                "Bash",
+                "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b"
+            ),
+            ToolResultClass::Diff
+        );
+        assert_eq!(
+            class("mcp__api", "{\"ok\": true, \"items\": [1, 2]}"),
+            ToolResultClass::Json
+        );
+        assert_eq!(
+            class("Read", "     1→fn main() {\n     2→}"),
+            ToolResultClass::FileContents
+        );
+        assert_eq!(
+            class("cat_tool", "  1\tline one\n  2\tline two\n  3\tline three"),
+            ToolResultClass::FileContents
+        );
+        assert_eq!(
+            class("Bash", "Compiling foo v0.1.0\nFinished"),
+            ToolResultClass::CommandOutput
+        );
+        assert_eq!(class("WebSearch", "3 results"), ToolResultClass::Other);
+    }
+
+    #[test]
+    fn aggregate_merges_tool_result_classes() {
+        let mut a = report_with("Bash", 1, 0, 1);
+        add_tool_result_class(
+            &mut a.tool_result_classes,
+            ToolResultClass::StackTrace,
+            1,
+            400,
+            100,
+        );
+        let mut b = report_with("Bash", 1, 0, 1);
+        add_tool_result_class(&mut b.tool_result_classes, ToolResultClass::Json, 2, 40, 10);
+        add_tool_result_class(
+            &mut b.tool_result_classes,
+            ToolResultClass::StackTrace,
+            1,
+            80,
+            20,
+        );
+        let agg = SessionAnalysisReport::aggregate(&[a, b]);
+        let classes: Vec<_> = agg
+            .tool_result_classes
+            .iter()
+            .map(|c| (c.class, c.results, c.tokens))
+            .collect();
+        assert_eq!(
+            classes,
+            [
+                (ToolResultClass::StackTrace, 2, 120),
+                (ToolResultClass::Json, 2, 10)
+            ]
+        );
+        assert!(
+            agg.format_text()
+                .contains("| stack trace | 2 | 120 | 92% |")
+        );
+    }
+
+    #[test]
+    fn api_errors_counted_with_retried_turn_cost() {
+        use normalize_chat_sessions::{ApiError, TokenUsage, Turn};
+
+        let error = |kind, retry_delay_ms| ApiError {
+            kind,
+            status: None,
+            message: String::new(),
+            retry_delay_ms,
+            timestamp: None,
+        };
+        let mut session = Session::new(PathBuf::from("s"), "claude");
+        session.turns.push(Turn {
+            token_usage: Some(TokenUsage {
+                input: 100,
+                output: 50,
+                cache_read: Some(1000),
+                model: Some("claude-sonnet-4-5".to_string()),
+                ..Default::default()
+            }),
+            api_errors: vec![
+                error(ApiErrorKind::Overloaded, Some(500)),
+                error(ApiErrorKind::Overloaded, Some(1000)),
+                error(ApiErrorKind::RateLimit, None),
+            ],
+            ..Default::default()
+        });
+        session.turns.push(Turn::default());
+
+        let report = analyze_session(&session);
+        let errors = report.api_errors.as_ref().expect("api errors recorded");
+        assert_eq!(errors.total, 3);
+        assert_eq!(errors.by_kind[0].kind, ApiErrorKind::Overloaded);
+        assert_eq!(errors.by_kind[0].count, 2);
+        assert_eq!(errors.retry_delay_ms, 1500);
+        assert_eq!(errors.retried_turns, 1);
+        assert_eq!(errors.retried_turn_tokens, 1150);
+        assert!(errors.retried_turn_cost.is_some_and(|c| c > 0.0));
+        assert!(
+            report
+                .format_text()
+                .contains("- **Errors**: 3 (overloaded 2, rate limit 1)")
+        );
+
+        let agg = SessionAnalysisReport::aggregate(&[report.clone(), report]);
+        let errors = agg.api_errors.unwrap();
+        assert_eq!((errors.total, errors.retried_turns), (6, 2));
+
+        assert!(
+            analyze_session(&Session::new(PathBuf::from("s"), "claude"))
+                .api_errors
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn dedup_estimated_from_content_without_usage() {
+        use normalize_chat_sessions::{Message, Turn};
+
+        struct Words;
+        impl Tokenizer for Words {
+            fn name(&self) -> &'static str {
+                "words"
+            }
+            fn count(&self, text: &str) -> usize {
+                text.split_whitespace().count()
+            }
+        }
+
+        let message = |role, block| Message {
+            role,
+            content: vec![block],
+            timestamp: None,
+        };
+        let text = |t: &str| ContentBlock::Text {
+            text: t.to_string(),
+        };
+        let mut session = Session::new(PathBuf::from("s"), "mapped");
+        session.turns.push(Turn {
+            messages: vec![
+                message(Role::User, text("a b")),
+                message(Role::Assistant, text("c")),
+            ],
+            ..Default::default()
+        });
+        session.turns.push(Turn {
+            messages: vec![
+                message(
+                    Role::Tool,
+                    ContentBlock::ToolResult {
+                        tool_use_id: "t1".to_string(),
+                        content: "d e f".to_string(),
+                        is_error: false,
+                    },
+                ),
+                message(Role::Assistant, text("g h")),
+            ],
+            ..Default::default()
+        });
+
+        let report = analyze_session_with(&session, &Words);
+        let dedup = report.dedup_tokens.as_ref().expect("estimated dedup stats");
+        assert!(dedup.estimated);
+        assert_eq!((dedup.unique_input, dedup.unique_output), (5, 3));
+        // Turn 1 reads 2 and writes 1; turn 2 re-reads those 3 plus 3 new, writes 2.
+        assert_eq!(dedup.total_billed, 11);
+        assert_eq!(report.tool_result_classes[0].tokens, 3);
+        assert!(
+            report
+                .format_text()
+                .contains("## Token Efficiency (estimated)")
+        );
+    }
+
+    #[test]
+    fn streamed_turns_keep_largest_tool_results() {
+        use normalize_chat_sessions::{Message, Turn};
+
+        struct Words;
+        impl Tokenizer for Words {
+            fn name(&self) -> &'static str {
+                "words"
+            }
+            fn count(&self, text: &str) -> usize {
+                text.split_whitespace().count()
+            }
+        }
+
+        // Many more results than the bounded candidate list holds; sizes repeat
+        // so ties must resolve to the earliest turn.
+        let turns = (0..200).map(|i| {
+            let id = format!("t{i}");
+            Turn {
+                messages: vec![
+                    Message {
+                        role: Role::Assistant,
+                        content: vec![ContentBlock::ToolUse {
+                            id: id.clone(),
+                            name: "Bash".to_string(),
+                            input: serde_json::json!({ "command": "ls" }),
+                        }],
+                        timestamp: None,
+                    },
+                    Message {
+                        role: Role::Tool,
+                        content: vec![ContentBlock::ToolResult {
+                            tool_use_id: id,
+                            content: "x".repeat(1 + i % 50),
+                            is_error: false,
+                        }],
+                        timestamp: None,
+                    },
+                ],
+                ..Default::default()
+            }
+        });
+        let session = Session {
+            turns: turns.clone().collect(),
+            ..Session::new(PathBuf::from("s"), "claude")
+        };
+
+        let streamed = analyze_turns_with(PathBuf::from("s"), "claude", turns, &Words);
+        assert_eq!(streamed.total_turns, 200);
+        let largest: Vec<_> = streamed
+            .largest_tool_results
+            .iter()
+            .map(|r| (r.chars, r.turn))
+            .collect();
+        assert_eq!(largest[..3], [(50, 49), (50, 99), (50, 149)]);
+        assert_eq!(largest.len(), 10);
+        assert_eq!(
+            serde_json::to_value(&streamed).unwrap(),
+            serde_json::to_value(analyze_session_with(&session, &Words)).unwrap()
+        );
+    }
+
+    #[test]
+    fn read_results_fill_file_token_hotspots() {
+        use normalize_chat_sessions::{Message, Turn};
+
+        struct Words;
+        impl Tokenizer for Words {
+            fn name(&self) -> &'static str {
+                "words"
+            }
+            fn count(&self, text: &str) -> usize {
+                text.split_whitespace().count()
+            }
+        }
+
+        let message = |role, content| Message {
+            role,
+            content,
+            timestamp: None,
+        };
+        let read = |id: &str, path: &str| ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: "Read".to_string(),
+            input: serde_json::json!({ "file_path": path }),
+        };
+        let result = |id: &str, content: &str, is_error| ContentBlock::ToolResult {
+            tool_use_id: id.to_string(),
+            content: content.to_string(),
+            is_error,
+        };
+        let mut session = Session::new(PathBuf::from("s"), "claude");
+        session.turns.push(Turn {
+            messages: vec![
+                message(
+                    Role::Assistant,
+                    vec![
+                        read("r1", "/home/u/p/src/a.rs"),
+                        read("r2", "/home/u/p/src/b.rs"),
+                    ],
+                ),
+                message(
+                    Role::Tool,
+                    vec![
+                        result("r1", "fn a() {}", false),
+                        result("r2", "No such file", true),
+                    ],
+                ),
+            ],
+            ..Default::default()
+        });
+
+        let report = analyze_session_with(&session, &Words);
+        assert_eq!(report.file_tokens.get("src/a.rs"), Some(&3));
+        // Failed reads return no file contents.
+        assert!(!report.file_tokens.contains_key("src/b.rs"));
+    }
+
+    #[test]
+    fn view_recommendations_group_and_price_hotspots() {
+        let mut r = SessionAnalysisReport::new(PathBuf::from("s"), "claude");
+        let mut add = |path: &str, tokens, reads, edits| {
+            r.file_tokens.insert(path.to_string(), tokens);
+            r.file_operations.insert(
+                path.to_string(),
+                FileOperation {
+                    path: path.to_string(),
+                    reads,
+                    edits,
+                    writes: 0,
+                },
+            );
+        };
+        add("gen/a.ts", 6_000, 2, 0);
+        add("gen/b.ts", 6_000, 1, 0);
+        add("src/big.rs", 30_000, 2, 1);
+        add("src/small.rs", 1_000, 1, 0);
+        r.token_stats.total_input = 100_000;
+        r.actual_cost = Some(1.0);
+
+        let recs = recommend_view_policies(&r, &|p| (p == "gen/a.ts").then_some(0.5));
+        assert_eq!(recs.len(), 2);
+        // Budget: two 15K reads capped at 4K each.
+        assert_eq!(recs[0].pattern, "src/big.rs");
+        assert_eq!(recs[0].policy, ViewPolicy::Budget { tokens: 4_000 });
+        assert_eq!(recs[0].saved_tokens, 22_000);
+        // Skeleton: 3K from the indexed file, 4.5K at the default fraction.
+        assert_eq!(recs[1].pattern, "gen/*.ts");
+        assert_eq!(recs[1].policy, ViewPolicy::SkeletonOnly);
+        assert_eq!((recs[1].files, recs[1].reads), (2, 3));
+        assert_eq!(recs[1].saved_tokens, 7_500);
+        assert_eq!(recs[1].indexed_files, 1);
+        assert!((recs[1].saved_cost.unwrap() - 0.075).abs() < 1e-9);
+
+        r.view_recommendations = recs;
+        let text = r.format_text();
+        assert!(text.contains("## View Recommendations"));
+        assert!(text.contains("| gen/*.ts | skeleton-only | 3 | 12.0K | ~7.5K tokens (~$0.08) |"));
+    }
+
+    #[test]
+    fn bundled_pricing_matches_known_models() {
+        let table = PricingTable::bundled();
+        let name = |m: &str| table.lookup(m).map(|p| p.name);
+        assert_eq!(name("claude-opus-4-6"), Some("Claude Opus 4.5/4.6"));
+        assert_eq!(
+            name("claude-opus-4-1-20250805"),
+            Some("Claude Opus 3/4/4.1")
+        );
+        assert_eq!(name("claude-3-opus-20240229"), Some("Claude Opus 3/4/4.1"));
+        assert_eq!(
+            name("claude-sonnet-4-5-20250929"),
+            Some("Claude Sonnet 4.5")
+        );
+        assert_eq!(
+            name("claude-3-7-sonnet-20250219"),
+            Some("Claude Sonnet 3.7")
+        );
+        assert_eq!(
+            name("claude-3-5-sonnet-20241022"),
+            Some("Claude Sonnet 3.5")
+        );
+        assert_eq!(name("claude-3-sonnet-20240229"), Some("Claude Sonnet 3"));
+        assert_eq!(name("claude-sonnet-4-20250514"), Some("Claude Sonnet 4.5"));
+        assert_eq!(name("claude-haiku-4-5-20251001"), Some("Claude Haiku 4.5"));
+        assert_eq!(name("claude-3-5-haiku-20241022"), Some("Claude Haiku 3.5"));
+        // The date contains a 4; that must not read as Haiku 4.x.
+        assert_eq!(name("claude-3-haiku-20240307"), Some("Claude Haiku 3"));
+        assert_eq!(name("gpt-4o-mini-2024-07-18"), Some("GPT-4o mini"));
+        assert_eq!(name("GPT-4o"), Some("GPT-4o"));
+        assert_eq!(name("gemini-2.5-pro"), Some("Gemini 2.5 Pro"));
+        assert_eq!(name("llama-3.1-70b"), None);
+
+        let opus = table.lookup("claude-opus-4-6").unwrap();
+        assert_eq!(
+            opus.cache_read_per_mtok,
+            ModelPricing::OPUS_4_5.cache_read_per_mtok
+        );
+        // Unlisted cache rates fall back to the input rate.
+        let gpt = table.lookup("gpt-4.1").unwrap();
+        assert_eq!(gpt.cache_write_per_mtok, gpt.input_per_mtok);
+    }
+
+    #[test]
+    fn project_pricing_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".normalize")).unwrap();
+        std::fs::write(
+            dir.path().join(".normalize/pricing.toml"),
+            r#"
+[[model]]
+name = "Local Llama"
+match = ["llama-*"]
+input_per_mtok = 0.0
+output_per_mtok = 0.0
+
+[[model]]
+name = "Discounted Sonnet"
+match = ["*sonnet*"]
+input_per_mtok = 1.0
+output_per_mtok = 5.0
+"#,
+        )
+        .unwrap();
+        let table = PricingTable::load(dir.path()).unwrap();
+        let name = |m: &str| table.lookup(m).map(|p| p.name);
+        assert_eq!(name("llama-3.1-70b"), Some("Local Llama"));
+        assert_eq!(name("claude-sonnet-4-5"), Some("Discounted Sonnet"));
+        assert_eq!(name("claude-opus-4-6"), Some("Claude Opus 4.5/4.6"));
+
+        std::fs::write(
+            dir.path().join(".normalize/pricing.toml"),
+            "[[model]]\nname = \"x\"\nmatch = [\"x\"]\ninput = 1.0\n",
+        )
+        .unwrap();
+        let err = PricingTable::load(dir.path()).unwrap_err();
+        assert!(err.contains("pricing.toml"), "{err}");
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("gpt-4o*", "gpt-4o-2024-08-06"));
+        assert!(glob_match("*opus*4-5*", "claude-opus-4-5-20251101"));
+        assert!(!glob_match("*opus*4-5*", "claude-opus-4-1-20250805"));
+        assert!(glob_match("*sonnet*-3", "claude-sonnet-3"));
+        assert!(!glob_match("*sonnet*-3", "claude-sonnet-3-x"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("exact", "exactly"));
+        assert!(glob_match("a*a", "aa"));
+        assert!(!glob_match("a*a", "a"));
+    }
+
+    #[test]
+    fn aggregate_sums_correction_kinds() {
+        let mut a = report_with("Read", 1, 0, 1);
+        a.correction_counts = vec![CorrectionKindCount {
+            kind: CorrectionKind::APOLOGY,
+            count: 1,
+        }];
+        let mut b = report_with("Read", 1, 0, 1);
+        b.correction_counts = vec![
+            CorrectionKindCount {
+                kind: CorrectionKind::MISTAKE,
+                count: 1,
+            },
+            CorrectionKindCount {
+                kind: CorrectionKind::APOLOGY,
+                count: 2,
+            },
+        ];
+        let agg = SessionAnalysisReport::aggregate(&[a, b]);
+        assert_eq!(
+            correction_kinds_summary(&agg.correction_counts),
+            "Apology 3, Mistake 1"
+        );
+        assert!(
+            agg.format_text()
+                .contains("**By kind**: Apology 3, Mistake 1")
+        );
+    }
+
+    #[test]
+    fn trend_bucket_keys() {
+        assert_eq!(
+            TrendBucket::Day.key("2026-10-17").as_deref(),
+            Some("2026-10-17")
+        );
+        // 2026-10-17 is a Saturday; its ISO week starts Monday 2026-10-12.
+        assert_eq!(
+            TrendBucket::Week.key("2026-10-17").as_deref(),
+            Some("2026-10-12")
+        );
+        assert_eq!(
+            TrendBucket::Week.key("2026-10-12").as_deref(),
+            Some("2026-10-12")
+        );
+        // Weeks span month and year boundaries.
+        assert_eq!(
+            TrendBucket::Week.key("2026-01-01").as_deref(),
+            Some("2025-12-29")
+        );
+        assert_eq!(
+            TrendBucket::Week.key("2024-03-01").as_deref(),
+            Some("2024-02-26")
+        );
+        assert_eq!(TrendBucket::Day.key("not-a-date"), None);
+        assert_eq!("weekly".parse::<TrendBucket>(), Ok(TrendBucket::Week));
+    }
+
+    #[test]
+    fn trend_report_buckets_sessions_by_week() {
+        let mut a = report_with("Bash", 4, 2, 100);
+        a.actual_cost = Some(0.5);
+        a.retry_hotspots.push(RetryHotspot {
+            pattern: "cargo test".to_string(),
+            attempts: 3,
+            failures: 2,
+            output_tokens: 10,
+            turn_indices: vec![1, 2],
+        });
+        let b = report_with("Bash", 6, 0, 50);
+        let c = report_with("Read", 2, 0, 10);
+        let dated = vec![
+            ("2026-10-13".to_string(), a),
+            ("2026-10-15".to_string(), b),
+            ("2026-10-20".to_string(), c),
+        ];
+        let trend = SessionTrendReport::build(TrendBucket::Week, &dated);
+        assert_eq!(trend.points.len(), 2);
+        let first = &trend.points[0];
+        assert_eq!(first.period, "2026-10-12");
+        assert_eq!(
+            (first.sessions, first.tool_calls, first.tool_errors),
+            (2, 10, 2)
+        );
+        assert!((first.error_rate - 0.2).abs() < 1e-9);
+        assert_eq!(first.tokens_in, 150);
+        assert_eq!(first.cost_usd, Some(0.5));
+        assert_eq!(first.top_retry.as_deref(), Some("cargo test"));
+        assert_eq!(trend.points[1].period, "2026-10-19");
+        assert_eq!(trend.points[1].cost_usd, None);
+        assert_eq!((trend.total.sessions, trend.total.tool_calls), (3, 12));
+        let text = trend.format_text();
+        assert!(text.starts_with("# Session Trends by week"), "{text}");
+        assert!(text.contains("cargo test"), "{text}");
+    }
+
     #[test]
     fn aggregate_merges_distinct_tools() {
         let a = report_with("Read", 1, 0, 1);
@@ -2393,4 +4087,17 @@ mod tests {
         assert_eq!(agg.tool_stats.get("Edit").unwrap().calls, 4);
         assert_eq!(agg.total_tool_calls(), 5);
     }
+
+    #[test]
+    fn tool_usage_renders_as_csv() {
+        let a = report_with("Read", 1, 0, 1);
+        let b = report_with("Edit", 4, 1, 2);
+        let agg = SessionAnalysisReport::aggregate(&[a, b]);
+        assert_eq!(
+            agg.format_as(&normalize_output::OutputFormat::Csv),
+            "tool,calls,errors,success_rate,output_chars\n\
+             Edit,4,1,0.750,100\n\
+             Read,1,0,1.000,100"
+        );
+    }
 }
@@ -0,0 +1,486 @@
+//! OpenTelemetry export: a session as OTLP trace spans.
+//!
+//! [`session_to_otlp`] builds an OTLP/JSON `ExportTraceServiceRequest` (the
+//! body a collector accepts at `POST /v1/traces`), so sessions can be browsed
+//! in Jaeger or Grafana Tempo next to the services the agent worked on:
+//!
+//! ```text
+//! session <id>                 whole session, totals as attributes
+//! ├── turn 0                   token usage, cost, API errors as events
+//! │   ├── tool Read            tool_use → tool_result, error status on failure
+//! │   └── tool Bash
+//! └── turn 1
+//! ```
+//!
+//! Trace and span ids are derived from the session id, so exporting the same
+//! session twice produces the same trace instead of a duplicate. Messages
+//! without timestamps are placed 1ms after the previous known time.
+
+use crate::ModelPricing;
+use normalize_chat_sessions::{ContentBlock, Session, Turn};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Longest tool input kept in the `tool.input` attribute.
+const MAX_INPUT_CHARS: usize = 1024;
+
+/// An OTLP/JSON trace request for one session.
+#[derive(Debug, Clone)]
+pub struct OtlpTrace {
+    /// Hex trace id shared by every span.
+    pub trace_id: String,
+    pub span_count: usize,
+    /// `ExportTraceServiceRequest` in OTLP/JSON encoding.
+    pub request: Value,
+}
+
+/// Convert a session into one trace: a root span for the session, a child
+/// span per turn, and a grandchild span per tool call.
+pub fn session_to_otlp(session: &Session) -> OtlpTrace {
+    let key = session
+        .metadata
+        .session_id
+        .clone()
+        .unwrap_or_else(|| session.path.display().to_string());
+    let trace_id = format!("{:016x}{:016x}", fnv1a(&key, 0), fnv1a(&key, 1));
+    let root_id = span_id(&key, "session");
+
+    let mut clock = Clock::new(session.metadata.timestamp.as_deref());
+    let mut spans = Vec::new();
+    // Tool spans are closed by their result, which may arrive in a later turn.
+    let mut open_tools: HashMap<String, Value> = HashMap::new();
+    let mut session_start = None;
+    let mut totals = Totals::default();
+
+    for (turn_idx, turn) in session.turns.iter().enumerate() {
+        let turn_id = span_id(&key, &format!("turn {}", turn_idx));
+        let mut turn_start = None;
+        for message in &turn.messages {
+            let now = clock.tick(message.timestamp.as_deref());
+            turn_start.get_or_insert(now);
+            for block in &message.content {
+                match block {
+                    ContentBlock::ToolUse { id, name, input } => {
+                        totals.tool_calls += 1;
+                        let input = input.to_string();
+                        let input: String = input.chars().take(MAX_INPUT_CHARS).collect();
+                        open_tools.insert(
+                            id.clone(),
+                            json!({
+                                "traceId": trace_id,
+                                "spanId": span_id(&key, &format!("tool {}", id)),
+                                "parentSpanId": turn_id,
+                                "name": format!("tool {}", name),
+                                "kind": 1,
+                                "startTimeUnixNano": now.to_string(),
+                                "endTimeUnixNano": now.to_string(),
+                                "attributes": [
+                                    string_attr("tool.name", name),
+                                    string_attr("tool.call.id", id),
+                                    string_attr("tool.input", &input),
+                                ],
+                                "status": { "code": 0 },
+                            }),
+                        );
+                    }
+                    ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        is_error,
+                    } => {
+                        let Some(mut span) = open_tools.remove(tool_use_id) else {
+                            continue;
+                        };
+                        span["endTimeUnixNano"] = json!(now.to_string());
+                        if let Some(attrs) = span["attributes"].as_array_mut() {
+                            attrs.push(int_attr("tool.result.chars", content.len() as u64));
+                            attrs.push(int_attr("tool.result.tokens", content.len() as u64 / 4));
+                        }
+                        if *is_error {
+                            totals.tool_errors += 1;
+                            let first_line = content.lines().next().unwrap_or_default();
+                            span["status"] = json!({ "code": 2, "message": first_line });
+                        } else {
+                            span["status"] = json!({ "code": 1 });
+                        }
+                        spans.push(span);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let start = turn_start.unwrap_or_else(|| clock.tick(None));
+        session_start.get_or_insert(start);
+        spans.push(turn_span(
+            session,
+            turn,
+            turn_idx,
+            &trace_id,
+            &turn_id,
+            &root_id,
+            start,
+            clock.now,
+            &mut totals,
+        ));
+    }
+
+    // Calls that never got a result end with the session.
+    let mut unfinished: Vec<Value> = open_tools.into_values().collect();
+    unfinished.sort_by(|a, b| a["spanId"].as_str().cmp(&b["spanId"].as_str()));
+    for mut span in unfinished {
+        span["endTimeUnixNano"] = json!(clock.now.to_string());
+        spans.push(span);
+    }
+
+    let start = session_start.unwrap_or(clock.now);
+    let mut attributes = vec![
+        string_attr("session.id", &key),
+        string_attr("session.format", &session.format),
+        int_attr("session.turns", session.turns.len() as u64),
+        int_attr("session.tool_calls", totals.tool_calls),
+        int_attr("session.tool_errors", totals.tool_errors),
+        int_attr("gen_ai.usage.input_tokens", totals.input),
+        int_attr("gen_ai.usage.output_tokens", totals.output),
+    ];
+    if let Some(model) = &session.metadata.model {
+        attributes.push(string_attr("gen_ai.request.model", model));
+    }
+    if let Some(provider) = &session.metadata.provider {
+        attributes.push(string_attr("gen_ai.system", provider));
+    }
+    if let Some(project) = &session.metadata.project {
+        attributes.push(string_attr("session.project", project));
+    }
+    if let Some(cost) = totals.cost {
+        attributes.push(double_attr("session.cost_usd", cost));
+    }
+    spans.insert(
+        0,
+        json!({
+            "traceId": trace_id,
+            "spanId": root_id,
+            "name": format!("session {}", key),
+            "kind": 1,
+            "startTimeUnixNano": start.to_string(),
+            "endTimeUnixNano": clock.now.to_string(),
+            "attributes": attributes,
+            "status": { "code": 0 },
+        }),
+    );
+
+    let span_count = spans.len();
+    let request = json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    string_attr("service.name", &session.format),
+                    string_attr("telemetry.sdk.name", "normalize"),
+                ],
+            },
+            "scopeSpans": [{
+                "scope": {
+                    "name": "normalize-session-analysis",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "spans": spans,
+            }],
+        }],
+    });
+    OtlpTrace {
+        trace_id,
+        span_count,
+        request,
+    }
+}
+
+#[derive(Default)]
+struct Totals {
+    tool_calls: u64,
+    tool_errors: u64,
+    input: u64,
+    output: u64,
+    cost: Option<f64>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn turn_span(
+    session: &Session,
+    turn: &Turn,
+    turn_idx: usize,
+    trace_id: &str,
+    turn_id: &str,
+    root_id: &str,
+    start: u64,
+    end: u64,
+    totals: &mut Totals,
+) -> Value {
+    let mut attributes = vec![int_attr("turn.index", turn_idx as u64)];
+    if let Some(usage) = &turn.token_usage {
+        totals.input += usage.input + usage.cache_read.unwrap_or(0);
+        totals.output += usage.output;
+        attributes.push(int_attr("gen_ai.usage.input_tokens", usage.input));
+        attributes.push(int_attr("gen_ai.usage.output_tokens", usage.output));
+        attributes.push(int_attr(
+            "gen_ai.usage.cache_read_tokens",
+            usage.cache_read.unwrap_or(0),
+        ));
+        attributes.push(int_attr(
+            "gen_ai.usage.cache_creation_tokens",
+            usage.cache_create.unwrap_or(0),
+        ));
+        let model = usage.model.as_ref().or(session.metadata.model.as_ref());
+        if let Some(model) = model {
+            attributes.push(string_attr("gen_ai.response.model", model));
+            if let Some(pricing) = ModelPricing::from_model_str(model) {
+                let cost = pricing.calculate_turn_cost(usage);
+                *totals.cost.get_or_insert(0.0) += cost;
+                attributes.push(double_attr("turn.cost_usd", cost));
+            }
+        }
+    }
+    let events: Vec<Value> = turn
+        .api_errors
+        .iter()
+        .map(|e| {
+            let mut attrs = vec![
+                string_attr("error.type", e.kind.as_str()),
+                string_attr("exception.message", &e.message),
+            ];
+            if let Some(status) = e.status {
+                attrs.push(int_attr("http.response.status_code", u64::from(status)));
+            }
+            if let Some(delay) = e.retry_delay_ms {
+                attrs.push(int_attr("retry.delay_ms", delay));
+            }
+            let time = e
+                .timestamp
+                .as_deref()
+                .and_then(parse_nanos)
+                .unwrap_or(start);
+            json!({
+                "timeUnixNano": time.to_string(),
+                "name": "api_error",
+                "attributes": attrs,
+            })
+        })
+        .collect();
+    json!({
+        "traceId": trace_id,
+        "spanId": turn_id,
+        "parentSpanId": root_id,
+        "name": format!("turn {}", turn_idx),
+        "kind": 1,
+        "startTimeUnixNano": start.to_string(),
+        "endTimeUnixNano": end.max(start).to_string(),
+        "attributes": attributes,
+        "events": events,
+        "status": { "code": 0 },
+    })
+}
+
+/// Monotonic message clock: real timestamps when present, else 1ms after the
+/// previous one.
+struct Clock {
+    now: u64,
+}
+
+impl Clock {
+    fn new(session_timestamp: Option<&str>) -> Self {
+        Self {
+            now: session_timestamp.and_then(parse_nanos).unwrap_or(0),
+        }
+    }
+
+    fn tick(&mut self, timestamp: Option<&str>) -> u64 {
+        self.now = match timestamp.and_then(parse_nanos) {
+            Some(t) => t.max(self.now),
+            None => self.now + 1_000_000,
+        };
+        self.now
+    }
+}
+
+/// Nanoseconds since the Unix epoch for an RFC 3339 timestamp.
+fn parse_nanos(timestamp: &str) -> Option<u64> {
+    let t = chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    u64::try_from(t.timestamp_nanos_opt()?).ok()
+}
+
+/// 64-bit FNV-1a of `seed` followed by `s`.
+fn fnv1a(s: &str, seed: u8) -> u64 {
+    std::iter::once(seed)
+        .chain(s.bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+fn span_id(key: &str, name: &str) -> String {
+    format!("{:016x}", fnv1a(&format!("{}/{}", key, name), 2))
+}
+
+fn string_attr(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP/JSON encodes 64-bit integers as strings.
+fn int_attr(key: &str, value: u64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+fn double_attr(key: &str, value: f64) -> Value {
+    json!({ "key": key, "value": { "doubleValue": value } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use normalize_chat_sessions::{Message, Role, TokenUsage};
+    use std::path::PathBuf;
+
+    fn message(role: Role, timestamp: Option<&str>, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role,
+            content,
+            timestamp: timestamp.map(str::to_string),
+        }
+    }
+
+    fn sample() -> Session {
+        let mut session = Session::new(PathBuf::from("/logs/abc.jsonl"), "claude");
+        session.metadata.session_id = Some("abc".to_string());
+        session.turns.push(Turn {
+            messages: vec![
+                message(
+                    Role::Assistant,
+                    Some("2026-01-01T10:00:00Z"),
+                    vec![
+                        ContentBlock::ToolUse {
+                            id: "t1".to_string(),
+                            name: "Bash".to_string(),
+                            input: json!({ "command": "cargo test" }),
+                        },
+                        ContentBlock::ToolUse {
+                            id: "t2".to_string(),
+                            name: "Read".to_string(),
+                            input: json!({ "file_path": "src/lib.rs" }),
+                        },
+                    ],
+                ),
+                message(
+                    Role::User,
+                    Some("2026-01-01T10:00:02Z"),
+                    vec![
+                        ContentBlock::ToolResult {
+                            tool_use_id: "t1".to_string(),
+                            content: "error: test failed\nmore".to_string(),
+                            is_error: true,
+                        },
+                        ContentBlock::ToolResult {
+                            tool_use_id: "t2".to_string(),
+                            content: "fn main() {}".to_string(),
+                            is_error: false,
+                        },
+                    ],
+                ),
+            ],
+            token_usage: Some(TokenUsage {
+                input: 100,
+                output: 20,
+                cache_read: Some(900),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        session.turns.push(Turn {
+            messages: vec![message(
+                Role::Assistant,
+                None,
+                vec![ContentBlock::Text {
+                    text: "done".to_string(),
+                }],
+            )],
+            ..Default::default()
+        });
+        session
+    }
+
+    fn spans(trace: &OtlpTrace) -> &Vec<Value> {
+        trace.request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap()
+    }
+
+    fn attr<'a>(span: &'a Value, key: &str) -> &'a Value {
+        &span["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["key"] == key)
+            .unwrap()["value"]
+    }
+
+    #[test]
+    fn tool_calls_are_children_of_their_turn() {
+        let trace = session_to_otlp(&sample());
+        let spans = spans(&trace);
+        assert_eq!(trace.span_count, 5);
+        let names: Vec<&str> = spans.iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert_eq!(
+            names,
+            vec!["session abc", "tool Bash", "tool Read", "turn 0", "turn 1"]
+        );
+        let (root, bash, read, turn0) = (&spans[0], &spans[1], &spans[2], &spans[3]);
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(turn0["parentSpanId"], root["spanId"]);
+        assert_eq!(bash["parentSpanId"], turn0["spanId"]);
+        assert!(
+            spans
+                .iter()
+                .all(|s| s["traceId"] == trace.trace_id.as_str())
+        );
+
+        assert_eq!(bash["status"]["code"], 2);
+        assert_eq!(bash["status"]["message"], "error: test failed");
+        assert_eq!(read["status"]["code"], 1);
+        assert_eq!(
+            bash["endTimeUnixNano"]
+                .as_str()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+                - bash["startTimeUnixNano"]
+                    .as_str()
+                    .unwrap()
+                    .parse::<u64>()
+                    .unwrap(),
+            2_000_000_000
+        );
+        assert_eq!(attr(turn0, "gen_ai.usage.input_tokens")["intValue"], "100");
+        assert_eq!(attr(root, "gen_ai.usage.input_tokens")["intValue"], "1000");
+        assert_eq!(attr(root, "session.tool_errors")["intValue"], "1");
+    }
+
+    #[test]
+    fn untimed_messages_follow_the_previous_timestamp() {
+        let trace = session_to_otlp(&sample());
+        let turn1 = &spans(&trace)[4];
+        let start: u64 = turn1["startTimeUnixNano"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            start,
+            parse_nanos("2026-01-01T10:00:02Z").unwrap() + 1_000_000
+        );
+    }
+
+    #[test]
+    fn ids_are_stable_across_exports() {
+        let a = session_to_otlp(&sample());
+        let b = session_to_otlp(&sample());
+        assert_eq!(a.trace_id.len(), 32);
+        assert_eq!(a.request, b.request);
+    }
+}
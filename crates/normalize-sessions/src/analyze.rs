@@ -1,33 +1,60 @@
 //! Session analysis functions.
 
 use crate::sessions::{
-    SessionAnalysisReport, analyze_session, parse_session, parse_session_with_format,
+    ClaudeCodeFormat, SessionAnalysisReport, SessionSource, analyze_session, analyze_turns,
+    detect_format, parse_session, parse_session_with_format, recommend_view_policies,
 };
+use normalize_chat_sessions::{ParseError, SessionReader};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 
-/// Analyze a session and output statistics.
-pub fn print_session_analysis(path: &Path, format: Option<&str>) -> i32 {
-    // Parse the session
-    let session = if let Some(fmt) = format {
-        parse_session_with_format(path, fmt)
-    } else {
-        parse_session(path)
+/// Parse and analyze one session file.
+///
+/// Claude Code logs are streamed turn by turn, so even very large transcripts
+/// are analyzed in constant memory; other formats are loaded whole.
+pub fn analyze_session_file(
+    path: &Path,
+    format: Option<&str>,
+) -> Result<SessionAnalysisReport, ParseError> {
+    let claude = ClaudeCodeFormat.name();
+    let streamable = match format {
+        Some(fmt) => fmt == claude,
+        None => detect_format(path).is_some_and(|f| f.name() == claude),
     };
+    if !streamable {
+        let session = match format {
+            Some(fmt) => parse_session_with_format(path, fmt)?,
+            None => parse_session(path)?,
+        };
+        return Ok(analyze_session(&session));
+    }
 
-    let session = match session {
-        Ok(s) => s,
+    let mut reader = SessionReader::open(path)?;
+    let mut error = None;
+    let turns = reader
+        .by_ref()
+        .map_while(|turn| turn.map_err(|e| error = Some(e)).ok());
+    let analysis = analyze_turns(path.to_path_buf(), claude, turns);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(analysis),
+    }
+}
+
+/// Analyze a session and output statistics.
+pub fn print_session_analysis(path: &Path, format: Option<&str>) -> i32 {
+    match analyze_session_file(path, format) {
+        Ok(analysis) => {
+            println!("{}", analysis.format_text());
+            0
+        }
         Err(e) => {
             eprintln!("Failed to parse session: {}", e);
-            return 1;
+            1
         }
-    };
-
-    // Analyze the parsed session
-    let analysis = analyze_session(&session);
-    println!("{}", analysis.format_text());
-    0
+    }
 }
 
 /// Analyze multiple sessions and aggregate statistics, printing results.
@@ -51,21 +78,22 @@ pub fn aggregate_sessions(
 ) -> Option<SessionAnalysisReport> {
     // Parse and analyze each session into a per-session report. The pure fold
     // over these reports lives with the model in `normalize-session-analysis`.
+    let progress = normalize_output::progress::reporter(
+        normalize_output::progress::ProgressKind::Bar,
+        "Analyzing sessions...",
+    );
+    progress.set_total(Some(paths.len() as u64));
     let mut reports = Vec::new();
     for path in paths {
-        let session = if let Some(fmt) = format {
-            parse_session_with_format(path, fmt)
-        } else {
-            parse_session(path)
-        };
-
-        match session {
-            Ok(s) => reports.push(analyze_session(&s)),
+        match analyze_session_file(path, format) {
+            Ok(report) => reports.push(report),
             Err(e) => {
                 eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
             }
         }
+        progress.inc(1);
     }
+    progress.finish();
 
     if reports.is_empty() {
         return None;
@@ -74,6 +102,80 @@ pub fn aggregate_sessions(
     Some(SessionAnalysisReport::aggregate(&reports))
 }
 
+/// Lines of skeleton output per indexed symbol (signature plus a doc summary).
+const SKELETON_LINES_PER_SYMBOL: f64 = 2.0;
+
+/// Skeleton size relative to the full file for every file in the facts index,
+/// estimated from its symbol and line counts. Empty when `root` has no index.
+pub fn indexed_skeleton_fractions(root: &Path) -> HashMap<String, f64> {
+    let db_path = normalize_facts::get_normalize_dir(root).join("index.sqlite");
+    if !db_path.exists() {
+        return HashMap::new();
+    }
+    let load = async {
+        let index = normalize_facts::FileIndex::open(&db_path, root).await?;
+        let mut rows = index
+            .connection()
+            .query(
+                "SELECT f.path, f.lines, COUNT(s.name) FROM files f \
+                 LEFT JOIN symbols s ON s.file = f.path \
+                 WHERE f.is_dir = 0 AND f.lines > 0 GROUP BY f.path",
+                (),
+            )
+            .await?;
+        let mut fractions = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let path: String = row.get(0)?;
+            let lines: i64 = row.get(1)?;
+            let symbols: i64 = row.get(2)?;
+            let fraction = (symbols as f64 * SKELETON_LINES_PER_SYMBOL / lines as f64).min(1.0);
+            fractions.insert(path, fraction);
+        }
+        Ok::<_, libsql::Error>(fractions)
+    };
+    // A scoped thread with its own runtime works whether or not the caller is
+    // already inside one.
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .ok()
+                .and_then(|rt| rt.block_on(load).ok())
+        })
+        .join()
+        .ok()
+        .flatten()
+    })
+    .unwrap_or_default()
+}
+
+/// Recompute the report's view recommendations with skeleton sizes from the
+/// facts index under `root`, when there is one.
+///
+/// Session paths are trimmed to start at a project marker (`src/`, `crates/`,
+/// ...), so a path that is not an index key is matched by unique suffix.
+pub fn refine_view_recommendations(report: &mut SessionAnalysisReport, root: &Path) {
+    if report.file_tokens.is_empty() {
+        return;
+    }
+    let fractions = indexed_skeleton_fractions(root);
+    if fractions.is_empty() {
+        return;
+    }
+    let lookup = |path: &str| {
+        fractions.get(path).copied().or_else(|| {
+            let suffix = format!("/{path}");
+            let mut matches = fractions.iter().filter(|(k, _)| k.ends_with(&suffix));
+            match (matches.next(), matches.next()) {
+                (Some((_, &f)), None) => Some(f),
+                _ => None,
+            }
+        })
+    };
+    report.view_recommendations = recommend_view_policies(report, &lookup);
+}
+
 /// Apply jq filter to each line of a JSONL file.
 pub fn print_session_jq(path: &Path, filter: &str) -> i32 {
     use jaq_core::load::{Arena, File as JaqFile, Loader};
@@ -165,3 +267,52 @@ pub fn print_session_jq(path: &Path, filter: &str) -> i32 {
 
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_refines_skeleton_estimates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let db_path = normalize_facts::get_normalize_dir(root).join("index.sqlite");
+        std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(async {
+                let index = normalize_facts::FileIndex::open(&db_path, root)
+                    .await
+                    .unwrap();
+                index
+                    .execute(
+                        "INSERT INTO files (path, is_dir, mtime, lines) VALUES \
+                         ('crates/x/src/gen.rs', 0, 0, 1000)",
+                    )
+                    .await
+                    .unwrap();
+                for i in 0..50 {
+                    index
+                        .execute(&format!(
+                            "INSERT INTO symbols (file, name, kind, start_line, end_line) \
+                             VALUES ('crates/x/src/gen.rs', 'f{i}', 'function', {i}, {i})"
+                        ))
+                        .await
+                        .unwrap();
+                }
+            });
+
+        let fractions = indexed_skeleton_fractions(root);
+        assert_eq!(fractions.get("crates/x/src/gen.rs"), Some(&0.1));
+
+        // The session path was trimmed at `src/`; it resolves by suffix.
+        let mut report = SessionAnalysisReport::default();
+        report.file_tokens.insert("src/gen.rs".to_string(), 20_000);
+        refine_view_recommendations(&mut report, root);
+        let rec = &report.view_recommendations[0];
+        assert_eq!(rec.indexed_files, 1);
+        assert_eq!(rec.saved_tokens, 18_000);
+    }
+}
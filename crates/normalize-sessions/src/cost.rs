@@ -2,14 +2,14 @@
 
 use crate::output::OutputFormatter;
 use crate::sessions::{
-    FormatRegistry, ModelPricing, SessionFile, SessionSource, TokenUsage, parse_session,
-    parse_session_with_format,
+    BudgetConfig, BudgetReport, CostLedger, FormatRegistry, LedgerEntry, ModelPricing, SessionFile,
+    SessionSource, TokenUsage, parse_session, parse_session_with_format,
 };
 use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 
-use super::stats::{list_all_project_sessions_by_mode, parse_date};
+use super::stats::{extract_day, extract_repo_name, list_all_project_sessions_by_mode, parse_date};
 use super::{SessionMode, list_sessions_by_mode, session_matches_grep};
 
 /// Cost for a single turn.
@@ -43,6 +43,9 @@ pub struct CostReport {
     pub cache_efficiency_pct: Option<f64>,
     /// Models seen in this session.
     pub models: Vec<String>,
+    /// Per-project budget rollup, when budgets are configured or requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetReport>,
 }
 
 impl CostReport {
@@ -165,6 +168,10 @@ impl OutputFormatter for CostReport {
         if let Some(e) = self.cache_efficiency_pct {
             writeln!(out, "  Cache efficiency:    {:.1}%", e).unwrap();
         }
+        if let Some(budget) = &self.budget {
+            writeln!(out).unwrap();
+            writeln!(out, "{}", budget.format_text()).unwrap();
+        }
         out
     }
 
@@ -245,6 +252,10 @@ impl OutputFormatter for CostReport {
         if let Some(e) = self.cache_efficiency_pct {
             writeln!(out, "  Cache efficiency:    \x1b[33m{:.1}%\x1b[0m", e).unwrap();
         }
+        if let Some(budget) = &self.budget {
+            writeln!(out).unwrap();
+            writeln!(out, "{}", budget.format_pretty()).unwrap();
+        }
         out
     }
 }
@@ -362,6 +373,9 @@ pub fn build_cost_report_for_session(
 }
 
 /// Build a cost report across multiple filtered sessions (aggregate totals only).
+///
+/// With `budget`, each costed session is also recorded in the [`CostLedger`]
+/// and the report carries a [`BudgetReport`] over everything recorded so far.
 #[allow(clippy::too_many_arguments)]
 pub fn build_cost_report(
     root: Option<&Path>,
@@ -375,6 +389,7 @@ pub fn build_cost_report(
     all_projects: bool,
     mode: &SessionMode,
     agent_type: Option<&str>,
+    budget: Option<&BudgetConfig>,
 ) -> Result<CostReport, String> {
     let registry = FormatRegistry::new();
     let source: &dyn SessionSource = match format_name {
@@ -442,14 +457,55 @@ pub fn build_cost_report(
     }
 
     let mut all_usages: Vec<(usize, TokenUsage)> = Vec::new();
+    let mut ledger_entries: Vec<(String, LedgerEntry)> = Vec::new();
     let mut offset = 0;
     for sf in &sessions {
         if let Some(usages) = collect_turn_usages(&sf.path, format_name) {
+            if budget.is_some()
+                && let Some(cost_usd) = session_cost(&usages)
+            {
+                let id = sf
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                ledger_entries.push((
+                    id,
+                    LedgerEntry {
+                        project: extract_repo_name(&sf.path),
+                        date: extract_day(&sf.mtime),
+                        cost_usd,
+                    },
+                ));
+            }
             let n = usages.len();
             all_usages.extend(usages.into_iter().map(|(i, u)| (i + offset, u)));
             offset += n;
         }
     }
 
-    Ok(build_report_from_turns(PathBuf::from("."), all_usages))
+    let mut report = build_report_from_turns(PathBuf::from("."), all_usages);
+    if let Some(config) = budget {
+        let path = CostLedger::default_path()
+            .ok_or_else(|| "Cannot locate the cost ledger: no home directory".to_string())?;
+        let ledger = CostLedger::update(&path, ledger_entries)?;
+        report.budget = Some(BudgetReport::build(
+            config,
+            &ledger,
+            &crate::sessions::today(),
+        ));
+    }
+    Ok(report)
+}
+
+/// Total priced cost of one session's turns; `None` if no turn had a known model.
+fn session_cost(usages: &[(usize, TokenUsage)]) -> Option<f64> {
+    let costs: Vec<f64> = usages
+        .iter()
+        .filter_map(|(_, u)| {
+            let pricing = ModelPricing::from_model_str(u.model.as_deref()?)?;
+            Some(pricing.calculate_turn_cost(u))
+        })
+        .collect();
+    (!costs.is_empty()).then(|| costs.iter().sum())
 }
@@ -0,0 +1,404 @@
+//! Export a session as a readable markdown document for review.
+//!
+//! The document keeps the whole conversation: user prompts and assistant text
+//! as prose, thinking and tool calls/results in collapsible `<details>` blocks,
+//! and a token/cost line under each turn heading so expensive turns stand out.
+
+use super::show::parse_session_for_show;
+use super::{resolve_session_paths, resolve_session_paths_literal};
+use crate::output::OutputFormatter;
+use normalize_chat_sessions::{ContentBlock, Role, Session, TokenUsage, Turn};
+use normalize_session_analysis::ModelPricing;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Options for [`session_to_markdown`].
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Keep at most this many lines of each tool result (`None` = all).
+    pub max_result_lines: Option<usize>,
+}
+
+/// Report returned by `sessions export`.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct SessionExportReport {
+    pub session_id: Option<String>,
+    pub turns: usize,
+    /// File the markdown was written to, if `--output` was given.
+    pub output: Option<String>,
+    pub markdown: String,
+}
+
+impl OutputFormatter for SessionExportReport {
+    fn format_text(&self) -> String {
+        match &self.output {
+            Some(path) => format!("Exported {} turns to {}", self.turns, path),
+            None => self.markdown.clone(),
+        }
+    }
+}
+
+/// Export a session (data only; writes `output` when given).
+pub fn build_export_report(
+    session_id: &str,
+    project: Option<&Path>,
+    format_name: Option<&str>,
+    exact: bool,
+    options: &ExportOptions,
+    output: Option<&Path>,
+) -> Result<SessionExportReport, String> {
+    let paths = if exact {
+        resolve_session_paths_literal(session_id, project, format_name)
+    } else {
+        resolve_session_paths(session_id, project, format_name)
+    };
+    let path = paths
+        .first()
+        .ok_or_else(|| format!("No sessions found matching: {}", session_id))?;
+    let session = parse_session_for_show(path, format_name)?;
+    let markdown = session_to_markdown(&session, options);
+    if let Some(output) = output {
+        normalize_output::sink::write_atomic(output, markdown.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+    }
+    Ok(SessionExportReport {
+        session_id: session.metadata.session_id.clone(),
+        turns: session.turns.len(),
+        output: output.map(|p| p.display().to_string()),
+        markdown,
+    })
+}
+
+/// Render a full session as markdown.
+pub fn session_to_markdown(session: &Session, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    let title = session
+        .metadata
+        .session_id
+        .as_deref()
+        .unwrap_or("(unknown)");
+    let _ = writeln!(out, "# Session {}", title);
+    let _ = writeln!(out);
+
+    let total = session.total_tokens();
+    let total_cost: Option<f64> = session
+        .turns
+        .iter()
+        .filter_map(|t| turn_cost(session, t))
+        .reduce(|a, b| a + b);
+    let mut rows: Vec<(&str, String)> = vec![("Format", session.format.clone())];
+    if let Some(model) = &session.metadata.model {
+        rows.push(("Model", model.clone()));
+    }
+    if let Some(ts) = &session.metadata.timestamp {
+        rows.push(("Started", ts.clone()));
+    }
+    if let Some(project) = &session.metadata.project {
+        rows.push(("Project", format!("`{}`", project)));
+    }
+    rows.push(("Turns", session.turns.len().to_string()));
+    if total.input > 0 || total.output > 0 {
+        rows.push(("Tokens", usage_summary(&total)));
+    }
+    if let Some(cost) = total_cost {
+        rows.push(("Cost", format!("${:.2}", cost)));
+    }
+    let _ = writeln!(out, "| | |");
+    let _ = writeln!(out, "|---|---|");
+    for (key, value) in rows {
+        let _ = writeln!(out, "| {} | {} |", key, value.replace('|', "\\|"));
+    }
+
+    // Tool results only carry the call id; look the tool name up by id.
+    let tool_names: HashMap<&str, &str> = session
+        .turns
+        .iter()
+        .flat_map(|t| &t.messages)
+        .flat_map(|m| &m.content)
+        .filter_map(|b| match b {
+            ContentBlock::ToolUse { id, name, .. } => Some((id.as_str(), name.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    for (turn_idx, turn) in session.turns.iter().enumerate() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "---");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Turn {}", turn_idx + 1);
+        let _ = writeln!(out);
+        if let Some(note) = turn_annotation(session, turn) {
+            let _ = writeln!(out, "*{}*", note);
+            let _ = writeln!(out);
+        }
+
+        let mut last_role = None;
+        for msg in &turn.messages {
+            // Tool results stay under the assistant heading that called them.
+            let speaker = match msg.role {
+                Role::User if msg.content.iter().all(is_tool_result) => Role::Assistant,
+                Role::Tool => Role::Assistant,
+                role => role,
+            };
+            if last_role != Some(speaker) {
+                let _ = writeln!(out, "### {}", role_heading(speaker));
+                let _ = writeln!(out);
+                last_role = Some(speaker);
+            }
+            for block in &msg.content {
+                write_block(&mut out, block, &tool_names, options);
+            }
+        }
+    }
+    out
+}
+
+fn is_tool_result(block: &ContentBlock) -> bool {
+    matches!(block, ContentBlock::ToolResult { .. })
+}
+
+fn role_heading(role: Role) -> &'static str {
+    match role {
+        Role::User => "User",
+        Role::Assistant => "Assistant",
+        Role::System => "System",
+        Role::Tool => "Tool",
+    }
+}
+
+fn write_block(
+    out: &mut String,
+    block: &ContentBlock,
+    tool_names: &HashMap<&str, &str>,
+    options: &ExportOptions,
+) {
+    match block {
+        ContentBlock::Text { text } => {
+            let text = text.trim();
+            if !text.is_empty() && text != "(no content)" {
+                let _ = writeln!(out, "{}", text);
+                let _ = writeln!(out);
+            }
+        }
+        ContentBlock::Thinking { text } => {
+            if !text.trim().is_empty() {
+                write_details(out, "Thinking", text.trim(), "");
+            }
+        }
+        ContentBlock::ToolUse { name, input, .. } => {
+            let summary = match tool_argument(input) {
+                Some(arg) => format!("Tool: {} <code>{}</code>", name, escape_html(&arg)),
+                None => format!("Tool: {}", name),
+            };
+            let body = serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string());
+            write_details(out, &summary, &body, "json");
+        }
+        ContentBlock::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => {
+            let name = tool_names
+                .get(tool_use_id.as_str())
+                .copied()
+                .unwrap_or("tool");
+            let lines = content.lines().count();
+            let label = if *is_error { "Error" } else { "Result" };
+            let summary = format!(
+                "{}: {} ({} line{})",
+                label,
+                name,
+                lines,
+                if lines == 1 { "" } else { "s" }
+            );
+            let body = match options.max_result_lines {
+                Some(max) if lines > max => {
+                    let kept: Vec<&str> = content.lines().take(max).collect();
+                    format!("{}\n… {} more lines", kept.join("\n"), lines - max)
+                }
+                _ => content.trim_end().to_string(),
+            };
+            write_details(out, &summary, &body, "");
+        }
+    }
+}
+
+/// A collapsed `<details>` block with `body` in a code fence.
+fn write_details(out: &mut String, summary: &str, body: &str, lang: &str) {
+    let fence = fence_for(body);
+    let _ = writeln!(out, "<details>");
+    let _ = writeln!(out, "<summary>{}</summary>", summary);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}{}", fence, lang);
+    let _ = writeln!(out, "{}", body);
+    let _ = writeln!(out, "{}", fence);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "</details>");
+    let _ = writeln!(out);
+}
+
+/// A backtick fence longer than any backtick run inside `body`.
+fn fence_for(body: &str) -> String {
+    let longest = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// The argument that best identifies a tool call, on one short line.
+fn tool_argument(input: &serde_json::Value) -> Option<String> {
+    const KEYS: &[&str] = &[
+        "command",
+        "file_path",
+        "path",
+        "pattern",
+        "query",
+        "url",
+        "description",
+    ];
+    let arg = KEYS
+        .iter()
+        .find_map(|k| input.get(*k).and_then(|v| v.as_str()))?;
+    let line = arg.lines().next().unwrap_or("");
+    let mut short: String = line.chars().take(80).collect();
+    if short.len() < arg.len() {
+        short.push('…');
+    }
+    Some(short)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Cost of one turn, when its model's pricing is known.
+fn turn_cost(session: &Session, turn: &Turn) -> Option<f64> {
+    let usage = turn.token_usage.as_ref()?;
+    let model = usage.model.as_ref().or(session.metadata.model.as_ref())?;
+    ModelPricing::from_model_str(model).map(|p| p.calculate_turn_cost(usage))
+}
+
+/// `12.3K in (10.0K cached) · 450 out · $0.0312 · 2 API errors`
+fn turn_annotation(session: &Session, turn: &Turn) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(usage) = &turn.token_usage {
+        parts.push(usage_summary(usage));
+    }
+    if let Some(cost) = turn_cost(session, turn) {
+        parts.push(format!("${:.4}", cost));
+    }
+    match turn.api_errors.len() {
+        0 => {}
+        1 => parts.push("1 API error".to_string()),
+        n => parts.push(format!("{} API errors", n)),
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+fn usage_summary(usage: &TokenUsage) -> String {
+    let cached = usage.cache_read.unwrap_or(0);
+    let input = usage.input + cached + usage.cache_create.unwrap_or(0);
+    let mut s = format!("{} in", format_tokens(input));
+    if cached > 0 {
+        let _ = write!(s, " ({} cached)", format_tokens(cached));
+    }
+    let _ = write!(s, " · {} out", format_tokens(usage.output));
+    s
+}
+
+fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{:.1}K", tokens as f64 / 1_000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use normalize_chat_sessions::Message;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn message(role: Role, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role,
+            content,
+            timestamp: None,
+        }
+    }
+
+    fn session() -> Session {
+        let mut session = Session::new(PathBuf::from("s.jsonl"), "claude");
+        session.metadata.session_id = Some("abc".to_string());
+        session.metadata.model = Some("claude-sonnet-4-5".to_string());
+        session.turns.push(Turn {
+            messages: vec![
+                message(
+                    Role::User,
+                    vec![ContentBlock::Text {
+                        text: "Run the tests".to_string(),
+                    }],
+                ),
+                message(
+                    Role::Assistant,
+                    vec![
+                        ContentBlock::Text {
+                            text: "Running them.".to_string(),
+                        },
+                        ContentBlock::ToolUse {
+                            id: "t1".to_string(),
+                            name: "Bash".to_string(),
+                            input: json!({"command": "cargo test <all>"}),
+                        },
+                    ],
+                ),
+                message(
+                    Role::User,
+                    vec![ContentBlock::ToolResult {
+                        tool_use_id: "t1".to_string(),
+                        content: "line 1\n```\nline 3\nline 4".to_string(),
+                        is_error: true,
+                    }],
+                ),
+            ],
+            token_usage: Some(TokenUsage {
+                input: 1000,
+                output: 200,
+                cache_read: Some(9000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        session
+    }
+
+    #[test]
+    fn renders_turns_tools_and_costs() {
+        let md = session_to_markdown(&session(), &ExportOptions::default());
+        assert!(md.starts_with("# Session abc\n"));
+        assert!(md.contains("| Model | claude-sonnet-4-5 |"));
+        assert!(md.contains("## Turn 1\n\n*10.0K in (9.0K cached) · 200 out · $0."));
+        assert!(md.contains("### User\n\nRun the tests\n"));
+        assert!(md.contains("<summary>Tool: Bash <code>cargo test &lt;all&gt;</code></summary>"));
+        assert!(md.contains("<summary>Error: Bash (4 lines)</summary>"));
+        // The result contains a ``` fence, so it is wrapped in a longer one.
+        assert!(md.contains("````\nline 1\n```\nline 3\nline 4\n````"));
+        // Tool results stay under the assistant heading.
+        assert_eq!(md.matches("### User").count(), 1);
+        assert_eq!(md.matches("### Assistant").count(), 1);
+    }
+
+    #[test]
+    fn truncates_long_results() {
+        let options = ExportOptions {
+            max_result_lines: Some(2),
+        };
+        let md = session_to_markdown(&session(), &options);
+        assert!(md.contains("line 1\n```\n… 2 more lines\n````"));
+    }
+}
@@ -7,6 +7,7 @@
 
 pub mod analyze;
 pub mod cost;
+pub mod export;
 pub mod heatmap;
 pub mod list;
 pub mod mark;
@@ -15,6 +16,7 @@ pub mod ngrams;
 pub mod parallelization;
 pub mod patterns;
 pub mod plans;
+pub mod rules;
 #[cfg(feature = "sessions-web")]
 mod serve;
 pub mod service;
@@ -50,6 +52,11 @@ pub(crate) fn resolve_pretty(root: &std::path::Path, pretty: bool, compact: bool
     !compact && (pretty || load_pretty_config(root).enabled())
 }
 
+/// Whether `[pretty] pager = true` is set in the global or project config.
+pub(crate) fn resolve_pager(root: &std::path::Path) -> bool {
+    load_pretty_config(root).pager()
+}
+
 fn load_pretty_config(root: &std::path::Path) -> normalize_output::PrettyConfig {
     #[derive(serde::Deserialize, Default)]
     struct Wrapper {
@@ -76,7 +83,43 @@ fn load_pretty_config(root: &std::path::Path) -> normalize_output::PrettyConfig
     cfg
 }
 
+/// Sessions section of the config (`[sessions]`).
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct SessionsConfig {
+    /// Cost budgets checked by `sessions cost` (`[sessions.budget]`).
+    pub budget: crate::sessions::BudgetConfig,
+}
+
+/// Load the `[sessions]` section (global config, then project config) without
+/// depending on the main crate's full config.
+pub(crate) fn load_sessions_config(root: &std::path::Path) -> SessionsConfig {
+    #[derive(serde::Deserialize, Default)]
+    struct Wrapper {
+        #[serde(default)]
+        sessions: SessionsConfig,
+    }
+    let mut cfg = SessionsConfig::default();
+    let global = std::env::var("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .ok()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+        .map(|c| c.join("normalize").join("config.toml"));
+    for path in [global, Some(root.join(".normalize").join("config.toml"))]
+        .into_iter()
+        .flatten()
+    {
+        if let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(w) = toml::from_str::<Wrapper>(&content)
+        {
+            cfg = w.sessions;
+        }
+    }
+    cfg
+}
+
 pub use cost::{CostReport, build_cost_report, build_cost_report_for_session};
+pub use export::{ExportOptions, SessionExportReport, build_export_report, session_to_markdown};
 pub use heatmap::{HeatmapReport, build_heatmap_report, build_heatmap_report_for_session};
 pub use list::{SessionListReport, build_session_list};
 pub use mark::{MarkReport, load_reviewed, mark_session, unmark_session};
@@ -87,12 +130,18 @@ pub use parallelization::{
 };
 pub use patterns::{PatternsReport, build_patterns_report};
 pub use plans::{PlanContent, PlansListReport, build_plan_content, build_plans_list};
+pub use rules::{
+    SessionRuleFinding, SessionRulesReport, add_session_facts, build_session_rules_report,
+};
 #[cfg(feature = "sessions-web")]
 pub use serve::serve_sessions;
 pub use show::{SessionShowReport, build_analyze_report, build_show_report};
-pub use stats::{RepoStatsReport, build_repo_stats, build_stats_data, show_stats_grouped};
+pub use stats::{
+    RepoStatsReport, build_repo_stats, build_stats_data, build_trend_stats, show_stats_grouped,
+};
 
 pub mod subagents;
+pub mod trace;
 
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -103,6 +152,7 @@ use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config, Matcher};
 
 pub use subagents::{SubagentSummaryItem, SubagentsReport};
+pub use trace::{SessionTraceReport, build_trace_report};
 
 /// Metadata about truncation applied by `--limit`.
 /// Included in reports so text/pretty output can show a notice and JSON output
@@ -81,7 +81,7 @@ impl OutputFormatter for NgramsReport {
         .unwrap();
         // Find max count for bar chart scaling
         let max_count = self.ngrams.first().map(|e| e.count).unwrap_or(1);
-        let bar_width = 30usize;
+        let bar_width = crate::output::terminal::bar_width(30);
         for entry in &self.ngrams {
             let bar_len = (entry.count * bar_width) / max_count.max(1);
             let bar: String = "#".repeat(bar_len);
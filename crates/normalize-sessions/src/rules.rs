@@ -0,0 +1,406 @@
+//! Datalog rules over agent sessions.
+//!
+//! Each selected session is turned into facts — `tool_call(session, turn, call,
+//! tool, is_error)`, `session_cost(session, dollars)`, `api_error(session, turn,
+//! kind)` — and the session rules (builtins, `~/.config/normalize/session-rules/`,
+//! `.normalize/session-rules/`) run over them with the same interpreter, rule
+//! format and `[rules]` overrides as code rules.
+
+use crate::output::OutputFormatter;
+use crate::sessions::{
+    ContentBlock, FormatRegistry, ModelPricing, Session, SessionFile, SessionSource, parse_session,
+    parse_session_with_format,
+};
+use normalize_chat_sessions::ApiErrorKind;
+use normalize_facts_rules_api::{DiagnosticLevel, Relations};
+use normalize_rules_config::RulesConfig;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::stats::{list_all_project_sessions_by_mode, parse_date};
+use super::{SessionMode, list_sessions_by_mode, session_matches_grep};
+
+/// One diagnostic produced by a session rule.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct SessionRuleFinding {
+    pub rule_id: String,
+    /// "error", "warning" or "hint"
+    pub severity: String,
+    /// Session the finding is about (file stem of the session log).
+    pub session: String,
+    /// 1-based turn, when the rule reports one.
+    pub turn: Option<u32>,
+    pub message: String,
+}
+
+/// Report for `normalize sessions rules`.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct SessionRulesReport {
+    pub sessions_checked: usize,
+    /// IDs of the rules that ran.
+    pub rules: Vec<String>,
+    pub findings: Vec<SessionRuleFinding>,
+}
+
+impl OutputFormatter for SessionRulesReport {
+    fn format_text(&self) -> String {
+        let mut out = String::new();
+        for f in &self.findings {
+            let turn = f.turn.map(|t| format!(" turn {t}")).unwrap_or_default();
+            writeln!(
+                out,
+                "{}: [{}] {}{}: {}",
+                f.severity, f.rule_id, f.session, turn, f.message
+            )
+            .unwrap();
+        }
+        if !self.findings.is_empty() {
+            writeln!(out).unwrap();
+        }
+        writeln!(
+            out,
+            "{} finding(s) in {} session(s), {} rule(s)",
+            self.findings.len(),
+            self.sessions_checked,
+            self.rules.len()
+        )
+        .unwrap();
+        out
+    }
+}
+
+/// Add the facts for one session to `relations`.
+///
+/// Turns are numbered from 1. Tool calls are numbered from 0 across the whole
+/// session, and a call counts as an error when its result is marked as one
+/// (results may arrive in a later turn than the call).
+pub fn add_session_facts(relations: &mut Relations, session_id: &str, session: &Session) {
+    let failed: HashSet<&str> = session
+        .turns
+        .iter()
+        .flat_map(|t| &t.messages)
+        .flat_map(|m| &m.content)
+        .filter_map(|b| match b {
+            ContentBlock::ToolResult {
+                tool_use_id,
+                is_error: true,
+                ..
+            } => Some(tool_use_id.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut call = 0u32;
+    let mut dollars = 0.0;
+    let mut priced = false;
+    for (i, turn) in session.turns.iter().enumerate() {
+        let turn_no = i as u32 + 1;
+        for block in turn.messages.iter().flat_map(|m| &m.content) {
+            if let ContentBlock::ToolUse { id, name, .. } = block {
+                relations.add_tool_call(
+                    session_id,
+                    turn_no,
+                    call,
+                    name,
+                    failed.contains(id.as_str()),
+                );
+                call += 1;
+            }
+        }
+        for err in &turn.api_errors {
+            relations.add_api_error(session_id, turn_no, api_error_kind(err.kind));
+        }
+        if let Some(usage) = &turn.token_usage
+            && let Some(pricing) = usage
+                .model
+                .as_ref()
+                .or(session.metadata.model.as_ref())
+                .and_then(|m| ModelPricing::from_model_str(m))
+        {
+            dollars += pricing.calculate_turn_cost(usage);
+            priced = true;
+        }
+    }
+    if priced {
+        relations.add_session_cost(session_id, dollars);
+    }
+}
+
+/// Snake-case name used for `api_error` facts (matches the JSON serialization).
+fn api_error_kind(kind: ApiErrorKind) -> &'static str {
+    match kind {
+        ApiErrorKind::RateLimit => "rate_limit",
+        ApiErrorKind::Overloaded => "overloaded",
+        ApiErrorKind::Timeout => "timeout",
+        ApiErrorKind::Server => "server",
+        ApiErrorKind::Other => "other",
+    }
+}
+
+/// Load the `[rules]` section (global config, then project config) without
+/// depending on the main crate's full config.
+fn load_rules_config(root: &Path) -> RulesConfig {
+    #[derive(serde::Deserialize, Default)]
+    struct Wrapper {
+        #[serde(default)]
+        rules: RulesConfig,
+    }
+    let mut cfg = RulesConfig::default();
+    let global = std::env::var("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .ok()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+        .map(|c| c.join("normalize").join("config.toml"));
+    for path in [global, Some(root.join(".normalize").join("config.toml"))]
+        .into_iter()
+        .flatten()
+    {
+        if let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(w) = toml::from_str::<Wrapper>(&content)
+        {
+            cfg = w.rules;
+        }
+    }
+    cfg
+}
+
+/// Run session rules over filtered sessions.
+#[allow(clippy::too_many_arguments)]
+pub fn build_session_rules_report(
+    root: Option<&Path>,
+    limit: usize,
+    format_name: Option<&str>,
+    grep: Option<&str>,
+    days: Option<u32>,
+    since: Option<&str>,
+    until: Option<&str>,
+    project_filter: Option<&Path>,
+    all_projects: bool,
+    mode: &SessionMode,
+    agent_type: Option<&str>,
+    rule_filter: Option<&str>,
+) -> Result<SessionRulesReport, String> {
+    let rules_root = root.unwrap_or(Path::new("."));
+    let rules_config = load_rules_config(rules_root);
+    let rules: Vec<_> =
+        normalize_facts_rules_interpret::load_session_rules(rules_root, &rules_config)
+            .into_iter()
+            .filter(|r| match rule_filter {
+                Some(id) => r.id == id,
+                None => r.enabled,
+            })
+            .collect();
+    if let Some(id) = rule_filter
+        && rules.is_empty()
+    {
+        return Err(format!("Unknown session rule: {}", id));
+    }
+
+    let registry = FormatRegistry::new();
+    let source: &dyn SessionSource = match format_name {
+        Some(name) => registry
+            .get(name)
+            .ok_or_else(|| format!("Unknown format: {}", name))?,
+        None => registry.get("claude").ok_or_else(|| {
+            "Claude format not available (compile with feature = format-claude)".to_string()
+        })?,
+    };
+
+    let grep_re = grep
+        .map(|p| regex::Regex::new(p).map_err(|_| format!("Invalid grep pattern: {}", p)))
+        .transpose()?;
+
+    let mut sessions: Vec<SessionFile> = if all_projects {
+        list_all_project_sessions_by_mode(source, mode)
+    } else {
+        let project = project_filter.or(root);
+        list_sessions_by_mode(source, project, mode)
+    };
+
+    let now = std::time::SystemTime::now();
+    let since_time = if let Some(d) = days {
+        Some(now - std::time::Duration::from_secs(d as u64 * 86400))
+    } else if let Some(s) = since {
+        Some(parse_date(s).ok_or_else(|| format!("Invalid date format: {} (use YYYY-MM-DD)", s))?)
+    } else {
+        None
+    };
+    let until_time = if let Some(u) = until {
+        Some(
+            parse_date(u).ok_or_else(|| format!("Invalid date format: {} (use YYYY-MM-DD)", u))?
+                + std::time::Duration::from_secs(86400),
+        )
+    } else {
+        None
+    };
+
+    if let Some(since) = since_time {
+        sessions.retain(|s| s.mtime >= since);
+    }
+    if let Some(until) = until_time {
+        sessions.retain(|s| s.mtime <= until);
+    }
+    if let Some(ref re) = grep_re {
+        sessions.retain(|s| session_matches_grep(&s.path, re));
+    }
+    if let Some(at) = agent_type {
+        let at_lower = at.to_lowercase();
+        sessions.retain(|s| {
+            s.subagent_type
+                .as_deref()
+                .is_some_and(|t| t.to_lowercase() == at_lower)
+        });
+    }
+
+    sessions.sort_by_key(|b| std::cmp::Reverse(b.mtime));
+    if limit > 0 {
+        sessions.truncate(limit);
+    }
+
+    if sessions.is_empty() {
+        return Err("No sessions found".to_string());
+    }
+
+    let mut relations = Relations::new();
+    let mut checked = 0;
+    for sf in &sessions {
+        let parsed = match format_name {
+            Some(fmt) => parse_session_with_format(&sf.path, fmt),
+            None => parse_session(&sf.path),
+        };
+        let Ok(session) = parsed else { continue };
+        let id = sf
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        add_session_facts(&mut relations, &id, &session);
+        checked += 1;
+    }
+
+    let diagnostics =
+        normalize_facts_rules_interpret::with_rule_threads(rules_config.threads, || {
+            normalize_facts_rules_interpret::run_rules_parallel(&rules, &relations)
+        })
+        .map_err(|e| format!("Failed to run session rules: {}", e))?;
+
+    let mut findings: Vec<SessionRuleFinding> = diagnostics
+        .into_iter()
+        .map(|d| {
+            let (session, turn) = match d.location {
+                Some(loc) => (loc.file, (loc.line > 0).then_some(loc.line)),
+                None => (String::new(), None),
+            };
+            SessionRuleFinding {
+                rule_id: d.rule_id,
+                severity: match d.level {
+                    DiagnosticLevel::Error => "error",
+                    DiagnosticLevel::Warning => "warning",
+                    DiagnosticLevel::Hint => "hint",
+                }
+                .to_string(),
+                session,
+                turn,
+                message: d.message,
+            }
+        })
+        .collect();
+    findings.sort_by(|a, b| {
+        (&a.session, a.turn, &a.rule_id, &a.message)
+            .cmp(&(&b.session, b.turn, &b.rule_id, &b.message))
+    });
+
+    let mut rule_ids: Vec<String> = rules.into_iter().map(|r| r.id).collect();
+    rule_ids.sort();
+
+    Ok(SessionRulesReport {
+        sessions_checked: checked,
+        rules: rule_ids,
+        findings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{Message, Role, TokenUsage, Turn};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn tool_turn(calls: &[(&str, &str, bool)]) -> Turn {
+        let uses = calls
+            .iter()
+            .map(|(id, name, _)| ContentBlock::ToolUse {
+                id: id.to_string(),
+                name: name.to_string(),
+                input: json!({}),
+            })
+            .collect();
+        let results = calls
+            .iter()
+            .map(|(id, _, is_error)| ContentBlock::ToolResult {
+                tool_use_id: id.to_string(),
+                content: "out".to_string(),
+                is_error: *is_error,
+            })
+            .collect();
+        Turn {
+            messages: vec![
+                Message {
+                    role: Role::Assistant,
+                    content: uses,
+                    timestamp: None,
+                },
+                Message {
+                    role: Role::User,
+                    content: results,
+                    timestamp: None,
+                },
+            ],
+            token_usage: Some(TokenUsage {
+                input: 1_000_000,
+                output: 0,
+                ..Default::default()
+            }),
+            api_errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn session_facts_and_builtin_rule() {
+        let mut session = Session::new(PathBuf::from("s1.jsonl"), "claude");
+        session.metadata.model = Some("claude-sonnet-4-5".to_string());
+        session.turns.push(tool_turn(&[
+            ("a", "Bash", true),
+            ("b", "Bash", true),
+            ("c", "Read", false),
+        ]));
+        session
+            .turns
+            .push(tool_turn(&[("d", "Bash", false), ("e", "Bash", false)]));
+        session.turns.push(tool_turn(&[("f", "Bash", false)]));
+
+        let mut relations = Relations::new();
+        add_session_facts(&mut relations, "s1", &session);
+        assert_eq!(relations.tool_calls.len(), 6);
+        assert_eq!(
+            relations.tool_calls.iter().filter(|c| c.is_error).count(),
+            2
+        );
+        assert_eq!(relations.tool_calls[3].turn, 2);
+        assert_eq!(relations.session_costs.len(), 1);
+        assert!(relations.session_costs[0].dollars > 0.0);
+
+        let rules = normalize_facts_rules_interpret::load_session_rules(
+            Path::new("/nonexistent"),
+            &RulesConfig::default(),
+        );
+        let refs: Vec<_> = rules.iter().collect();
+        let diags = normalize_facts_rules_interpret::run_rules_batch(&refs, &relations).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].rule_id, "tool-error-rate");
+        assert_eq!(diags[0].message, "Bash");
+    }
+}
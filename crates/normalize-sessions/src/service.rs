@@ -1,28 +1,56 @@
 //! Sessions management service for server-less CLI.
 
-use crate::output::OutputFormatter;
-use crate::sessions::SessionAnalysisReport;
+use crate::output::{OutputFormat, OutputFormatter};
+use crate::sessions::{AnalysisDiff, SessionAnalysisReport, TrendBucket};
 use crate::{
-    CostReport, HeatmapReport, MarkReport, MessagesReport, NgramRole, NgramsReport,
-    ParallelizationReport, PatternsReport, PlanContent, PlansListReport, SessionListReport,
-    SessionMode, SessionShowReport, SubagentsReport,
+    CostReport, ExportOptions, HeatmapReport, MarkReport, MessagesReport, NgramRole, NgramsReport,
+    ParallelizationReport, PatternsReport, PlanContent, PlansListReport, SessionExportReport,
+    SessionListReport, SessionMode, SessionRulesReport, SessionShowReport, SessionTraceReport,
+    SubagentsReport,
 };
 use server_less::cli;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 
 /// Sessions management sub-service.
 pub struct SessionsService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
+    output: RefCell<Option<PathBuf>>,
+    template: RefCell<Option<PathBuf>>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
+    csv: Cell<bool>,
+    tsv: Cell<bool>,
 }
 
 impl SessionsService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
+            output: RefCell::new(None),
+            template: RefCell::new(None),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
+            csv: Cell::new(false),
+            tsv: Cell::new(false),
+        }
+    }
+
+    /// Output format from `--template`, the `--csv`/`--tsv` globals and the resolved
+    /// pretty flag.
+    fn output_format(&self) -> OutputFormat {
+        if let Some(path) = self.template.borrow().clone() {
+            OutputFormat::Template { path }
+        } else if self.csv.get() {
+            OutputFormat::Csv
+        } else if self.tsv.get() {
+            OutputFormat::Tsv
+        } else if self.pretty.get() {
+            OutputFormat::Pretty
+        } else {
+            OutputFormat::Text
         }
     }
 
@@ -32,6 +60,28 @@ impl SessionsService {
             self.pretty_raw.get(),
             self.compact_raw.get(),
         ));
+        self.pager.set(super::resolve_pager(root));
+        // Cost figures use the project's `.normalize/pricing.toml` when present.
+        match crate::sessions::PricingTable::load(root) {
+            Ok(table) => {
+                crate::sessions::PricingTable::install(table);
+            }
+            Err(e) => eprintln!("warning: {}; using bundled pricing", e),
+        }
+        // Correction detection uses `.normalize/corrections.toml` the same way.
+        match crate::sessions::CorrectionPatterns::load(root) {
+            Ok(patterns) => {
+                crate::sessions::CorrectionPatterns::install(patterns);
+            }
+            Err(e) => eprintln!("warning: {}; using bundled correction patterns", e),
+        }
+        // Error categorization prepends rules from `.normalize/errors.toml`.
+        match crate::sessions::ErrorCategorizers::load(root) {
+            Ok(rules) => {
+                crate::sessions::ErrorCategorizers::install(rules);
+            }
+            Err(e) => eprintln!("warning: {}; using bundled error categorizers", e),
+        }
     }
 }
 
@@ -40,6 +90,8 @@ impl server_less::CliGlobals for SessionsService {
         match name {
             "pretty" => self.pretty_raw.set(value),
             "compact" => self.compact_raw.set(value),
+            "csv" => self.csv.set(value),
+            "tsv" => self.tsv.set(value),
             _ => {}
         }
     }
@@ -64,20 +116,41 @@ impl crate::output::OutputFormatter for PlansReport {
 
 impl SessionsService {
     fn display_analyze(&self, a: &SessionAnalysisReport) -> String {
-        if self.pretty.get() {
-            a.format_pretty()
-        } else {
-            a.format_text()
-        }
+        self.display_output(a)
     }
 
-    /// Generic display bridge: delegates to the report's own pretty flag.
+    /// Generic display bridge: renders in the format selected by the globals,
+    /// paging long pretty reports when `[pretty] pager = true`. With `--output`,
+    /// [`emit`](Self::emit) already wrote the report, so only a confirmation is
+    /// printed.
     fn display_output<T: OutputFormatter>(&self, value: &T) -> String {
-        if self.pretty.get() {
-            value.format_pretty()
-        } else {
-            value.format_text()
+        if let Some(path) = self.output.borrow().as_deref() {
+            return format!("Wrote {}", path.display());
+        }
+        // `emit` has rendered any template once already, so this can't fail
+        // for the commands that take one.
+        value
+            .format_paged(&self.output_format(), self.pager.get())
+            .unwrap_or_else(|e| e.to_string())
+    }
+
+    /// Finish a command that takes `--output`/`--template`: write the report
+    /// to the output file (as JSON under `--json`/`--jq`), or check that the
+    /// template renders, so failures fail the command instead of its display.
+    fn emit<T: OutputFormatter>(&self, report: T) -> Result<T, String> {
+        let format = self.output_format();
+        if let Some(path) = self.output.borrow().as_deref() {
+            let written = match crate::output::sink::machine_format() {
+                Some(machine) => machine.render(&report).and_then(|text| {
+                    crate::output::sink::write_atomic(path, format!("{}\n", text).as_bytes())
+                }),
+                None => report.write_to_file(&format, path),
+            };
+            written.map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+        } else if matches!(format, OutputFormat::Template { .. }) {
+            report.try_format_as(&format).map_err(|e| e.to_string())?;
         }
+        Ok(report)
     }
 }
 
@@ -87,6 +160,8 @@ impl SessionsService {
     global = [
         pretty = "Human-friendly output with colors and formatting",
         compact = "Compact output without colors (overrides TTY detection)",
+        csv = "Render table-shaped reports as CSV (others are flattened from JSON)",
+        tsv = "Render table-shaped reports as TSV (others are flattened from JSON)",
     ]
 )]
 impl SessionsService {
@@ -191,6 +266,91 @@ impl SessionsService {
         crate::build_show_report(&session, effective_project, format.as_deref(), full, exact)
     }
 
+    /// Export a session as a markdown document for review
+    ///
+    /// User and assistant text render as prose; thinking, tool calls and tool
+    /// results are collapsible blocks; each turn is annotated with its tokens and cost.
+    ///
+    /// Examples:
+    ///   normalize sessions export abc123                        # print markdown to stdout
+    ///   normalize sessions export abc123 -o review.md           # write to a file
+    ///   normalize sessions export abc123 --max-result-lines 40  # trim long tool output
+    #[cli(display_with = "display_output")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn export(
+        &self,
+        #[param(positional, help = "Session ID or path")] session: String,
+        #[param(
+            short = 'o',
+            help = "Write the markdown to this file instead of stdout"
+        )]
+        output: Option<String>,
+        #[param(help = "Keep at most N lines of each tool result")] max_result_lines: Option<usize>,
+        #[param(help = "Require exact/prefix match (disable fuzzy)")] exact: bool,
+        #[param(help = "Force specific format: claude, codex, gemini, normalize")] format: Option<
+            String,
+        >,
+        #[param(help = "Filter by specific project path")] project: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<SessionExportReport, String> {
+        let root_path = root.as_deref().map(std::path::Path::new);
+        let project_path = project.as_deref().map(std::path::Path::new);
+        let resolved_root = root_path.unwrap_or(std::path::Path::new("."));
+        self.resolve_format(resolved_root);
+        let effective_project = project_path.or(root_path);
+        crate::build_export_report(
+            &session,
+            effective_project,
+            format.as_deref(),
+            exact,
+            &ExportOptions { max_result_lines },
+            output.as_deref().map(std::path::Path::new),
+        )
+    }
+
+    /// Export a session as OpenTelemetry trace spans (OTLP/JSON) for Jaeger, Tempo, etc.
+    ///
+    /// Examples:
+    ///   normalize sessions trace abc123                            # print the OTLP/JSON request
+    ///   normalize sessions trace abc123 -o trace.json              # write it to a file
+    ///   normalize sessions trace abc123 --endpoint http://localhost:4318  # send to a collector
+    #[cli(display_with = "display_output")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn trace(
+        &self,
+        #[param(positional, help = "Session ID or path")] session: String,
+        #[param(short = 'o', help = "Write the OTLP/JSON request to this file")] output: Option<
+            String,
+        >,
+        #[param(
+            help = "OTLP/HTTP collector URL to send the spans to (e.g. http://localhost:4318)"
+        )]
+        endpoint: Option<String>,
+        #[param(help = "Require exact/prefix match (disable fuzzy)")] exact: bool,
+        #[param(help = "Force specific format: claude, codex, gemini, normalize")] format: Option<
+            String,
+        >,
+        #[param(help = "Filter by specific project path")] project: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<SessionTraceReport, String> {
+        let root_path = root.as_deref().map(std::path::Path::new);
+        let project_path = project.as_deref().map(std::path::Path::new);
+        self.resolve_format(root_path.unwrap_or(std::path::Path::new(".")));
+        let effective_project = project_path.or(root_path);
+        crate::build_trace_report(
+            &session,
+            effective_project,
+            format.as_deref(),
+            exact,
+            output.as_deref().map(std::path::Path::new),
+            endpoint.as_deref(),
+        )
+    }
+
     /// Run deep behavioral analysis on a session (tool stats, errors, token costs, corrections)
     ///
     /// Examples:
@@ -198,6 +358,8 @@ impl SessionsService {
     ///   normalize sessions analyze abc123 --pretty    # colored terminal output
     ///   normalize sessions analyze abc123 --json      # machine-readable analysis
     ///   normalize sessions analyze agent-abc --mode subagent  # analyze a subagent
+    ///   normalize sessions analyze abc123 --csv -o tools.csv  # tool usage as a spreadsheet
+    ///   normalize sessions analyze abc123 --template slack.j2  # render through a template
     #[cli(display_with = "display_analyze")]
     #[allow(clippy::too_many_arguments)]
     pub fn analyze(
@@ -216,6 +378,13 @@ impl SessionsService {
         >,
         #[param(help = "Filter by agent type (e.g. Explore, general-purpose, Plan)")]
         agent_type: Option<String>,
+        #[param(
+            short = 'o',
+            help = "Write the report to this file instead of stdout (replaced atomically)"
+        )]
+        output: Option<String>,
+        #[param(help = "Render the report's JSON through this Jinja template file")]
+        template: Option<String>,
     ) -> Result<SessionAnalysisReport, String> {
         let _mode = mode; // session resolution already searches subagents
         let _agent_type = agent_type; // session resolution already searches subagents
@@ -223,8 +392,43 @@ impl SessionsService {
         let project_path = project.as_deref().map(std::path::Path::new);
         let resolved_root = root_path.unwrap_or(std::path::Path::new("."));
         self.resolve_format(resolved_root);
+        self.output.replace(output.map(PathBuf::from));
+        self.template.replace(template.map(PathBuf::from));
+        let effective_project = project_path.or(root_path);
+        let report =
+            crate::build_analyze_report(&session, effective_project, format.as_deref(), exact)?;
+        self.emit(report)
+    }
+
+    /// Compare two sessions' analyses (tool success, token efficiency, cost, retries, context growth)
+    ///
+    /// Examples:
+    ///   normalize sessions diff abc123 def456         # baseline abc123 vs candidate def456
+    ///   normalize sessions diff abc123 def456 --pretty  # colored improvements/regressions
+    ///   normalize sessions diff abc123 def456 --json  # machine-readable diff
+    #[cli(display_with = "display_output")]
+    pub fn diff(
+        &self,
+        #[param(positional, help = "Baseline session ID or pattern")] before: String,
+        #[param(positional, help = "Candidate session ID or pattern")] after: String,
+        #[param(help = "Require exact/prefix match (disable fuzzy)")] exact: bool,
+        #[param(help = "Force specific format: claude, codex, gemini, normalize")] format: Option<
+            String,
+        >,
+        #[param(help = "Filter by specific project path")] project: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<AnalysisDiff, String> {
+        let root_path = root.as_deref().map(std::path::Path::new);
+        let project_path = project.as_deref().map(std::path::Path::new);
+        self.resolve_format(root_path.unwrap_or(std::path::Path::new(".")));
         let effective_project = project_path.or(root_path);
-        crate::build_analyze_report(&session, effective_project, format.as_deref(), exact)
+        let before =
+            crate::build_analyze_report(&before, effective_project, format.as_deref(), exact)?;
+        let after =
+            crate::build_analyze_report(&after, effective_project, format.as_deref(), exact)?;
+        Ok(crate::sessions::diff_analyses(&before, &after))
     }
 
     /// Show aggregate statistics across sessions
@@ -235,9 +439,11 @@ impl SessionsService {
     ///   normalize sessions stats --group-by project          # group results by project
     ///   normalize sessions stats --group-by project,day      # group by project and day
     ///   normalize sessions stats --by-repo                   # cross-repo comparison (all projects)
+    ///   normalize sessions stats --trend week --days 60      # weekly trend of errors, retries, cost
     ///   normalize sessions stats --mode subagent             # stats for subagent sessions only
     ///   normalize sessions stats --sort name                 # sort tool rows alphabetically
     ///   normalize sessions stats --sort errors               # sort tool rows by error count
+    ///   normalize sessions stats --by-repo --csv -o repos.csv  # per-repo table as CSV
     #[cli(display_with = "display_output")]
     #[allow(clippy::too_many_arguments)]
     pub fn stats(
@@ -272,12 +478,25 @@ impl SessionsService {
         sort: Option<String>,
         #[param(help = "Group sessions by repository and compare metrics across repos")]
         by_repo: bool,
+        #[param(
+            help = "Aggregate per period and show the trend: day or week (weeks start Monday)"
+        )]
+        trend: Option<TrendBucket>,
+        #[param(
+            short = 'o',
+            help = "Write the report to this file instead of stdout (replaced atomically)"
+        )]
+        output: Option<String>,
+        #[param(help = "Render the report's JSON through this Jinja template file")]
+        template: Option<String>,
     ) -> Result<SessionAnalysisReport, String> {
         let limit = limit.unwrap_or(0);
         let root_path = root.as_deref().map(std::path::Path::new);
         let project_path = project.as_deref().map(std::path::Path::new);
         let resolved_root = root_path.unwrap_or(std::path::Path::new("."));
         self.resolve_format(resolved_root);
+        self.output.replace(output.map(PathBuf::from));
+        self.template.replace(template.map(PathBuf::from));
         let mode = mode.unwrap_or_default();
 
         // --by-repo: delegate to the repo stats path which exits directly after printing.
@@ -295,6 +514,28 @@ impl SessionsService {
                 &mode,
                 agent_type.as_deref(),
             )?;
+            let report = self.emit(report)?;
+            println!("{}", self.display_output(&report));
+            std::process::exit(0);
+        }
+
+        // --trend: same as --by-repo, but bucketed by session date.
+        if let Some(bucket) = trend {
+            let report = crate::build_trend_stats(
+                root_path,
+                limit,
+                format.as_deref(),
+                grep.as_deref(),
+                days,
+                since.as_deref(),
+                until.as_deref(),
+                project_path,
+                all_projects,
+                &mode,
+                agent_type.as_deref(),
+                bucket,
+            )?;
+            let report = self.emit(report)?;
             println!("{}", self.display_output(&report));
             std::process::exit(0);
         }
@@ -303,6 +544,9 @@ impl SessionsService {
         // per-group output directly. This uses process::exit to avoid double-printing
         // from the service framework.
         if let Some(ref group_by_str) = group_by {
+            if self.output.borrow().is_some() {
+                return Err("--output is not supported with --group-by".to_string());
+            }
             let group_by_fields: Vec<String> = group_by_str
                 .split(',')
                 .map(|s| s.trim().to_string())
@@ -325,7 +569,7 @@ impl SessionsService {
             std::process::exit(exit_code);
         }
 
-        crate::build_stats_data(
+        let report = crate::build_stats_data(
             root_path,
             limit,
             format.as_deref(),
@@ -338,7 +582,8 @@ impl SessionsService {
             &mode,
             agent_type.as_deref(),
             sort.as_deref(),
-        )
+        )?;
+        self.emit(report)
     }
 
     /// Extract n-gram frequencies from session messages
@@ -803,6 +1048,7 @@ impl SessionsService {
     ///   normalize sessions cost abc123 --pretty     # colored output
     ///   normalize sessions cost --days 7            # aggregate costs across recent sessions
     ///   normalize sessions cost --all-projects      # costs across all projects
+    ///   normalize sessions cost --all-projects --budget  # per-project budget rollup
     #[cli(display_with = "display_output")]
     #[allow(clippy::too_many_arguments)]
     pub fn cost(
@@ -835,6 +1081,10 @@ impl SessionsService {
         >,
         #[param(help = "Filter by agent type (e.g. Explore, general-purpose, Plan)")]
         agent_type: Option<String>,
+        #[param(
+            help = "Record session costs and show the budget rollup (on by default when [sessions.budget] is configured)"
+        )]
+        budget: bool,
     ) -> Result<CostReport, String> {
         let limit = limit.unwrap_or(0);
         let root_path = root.as_deref().map(std::path::Path::new);
@@ -851,6 +1101,8 @@ impl SessionsService {
                 exact,
             )
         } else {
+            let budget_config = crate::load_sessions_config(resolved_root).budget;
+            let budget_config = (budget || !budget_config.is_empty()).then_some(&budget_config);
             crate::build_cost_report(
                 root_path,
                 limit,
@@ -863,10 +1115,71 @@ impl SessionsService {
                 all_projects,
                 &mode,
                 agent_type.as_deref(),
+                budget_config,
             )
         }
     }
 
+    /// Run Datalog rules over session facts (tool calls, costs, API errors)
+    ///
+    /// Rules come from builtins, ~/.config/normalize/session-rules/ and
+    /// .normalize/session-rules/, and see the relations
+    /// tool_call(session, turn, call, tool, is_error), session_cost(session, dollars)
+    /// and api_error(session, turn, kind).
+    ///
+    /// Examples:
+    ///   normalize sessions rules                          # all enabled session rules
+    ///   normalize sessions rules --days 7                 # sessions from the last week
+    ///   normalize sessions rules --rule tool-error-rate   # one rule (even if disabled)
+    ///   normalize sessions rules --all-projects --json    # findings as JSON
+    #[cli(display_with = "display_output")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn rules(
+        &self,
+        #[param(help = "Run only this rule ID")] rule: Option<String>,
+        #[param(help = "Filter sessions by grep pattern")] grep: Option<String>,
+        #[param(help = "Filter sessions from the last N days")] days: Option<u32>,
+        #[param(help = "Filter sessions since date (YYYY-MM-DD)")] since: Option<String>,
+        #[param(help = "Filter sessions until date (YYYY-MM-DD)")] until: Option<String>,
+        #[param(help = "Filter by specific project path")] project: Option<String>,
+        #[param(help = "Show sessions from all projects")] all_projects: bool,
+        #[param(help = "Force specific format: claude, codex, gemini, normalize")] format: Option<
+            String,
+        >,
+        #[param(
+            short = 'n',
+            help = "Maximum number of sessions (0 = all, default: all)"
+        )]
+        limit: Option<usize>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(help = "Session mode: interactive (default), subagent, or all")] mode: Option<
+            SessionMode,
+        >,
+        #[param(help = "Filter by agent type (e.g. Explore, general-purpose, Plan)")]
+        agent_type: Option<String>,
+    ) -> Result<SessionRulesReport, String> {
+        let root_path = root.as_deref().map(std::path::Path::new);
+        let project_path = project.as_deref().map(std::path::Path::new);
+        let resolved_root = root_path.unwrap_or(std::path::Path::new("."));
+        self.resolve_format(resolved_root);
+        crate::build_session_rules_report(
+            root_path,
+            limit.unwrap_or(0),
+            format.as_deref(),
+            grep.as_deref(),
+            days,
+            since.as_deref(),
+            until.as_deref(),
+            project_path,
+            all_projects,
+            &mode.unwrap_or_default(),
+            agent_type.as_deref(),
+            rule.as_deref(),
+        )
+    }
+
     /// List and view agent plans
     ///
     /// Examples:
@@ -768,7 +768,7 @@ pub fn print_session_show(
 }
 
 /// Parse a session file for the show command.
-fn parse_session_for_show(path: &Path, format: Option<&str>) -> Result<Session, String> {
+pub(crate) fn parse_session_for_show(path: &Path, format: Option<&str>) -> Result<Session, String> {
     use normalize_chat_sessions::{FormatRegistry, SessionSource, parse_session};
 
     if let Some(name) = format {
@@ -1019,8 +1019,6 @@ pub fn build_analyze_report(
     format_name: Option<&str>,
     exact: bool,
 ) -> Result<crate::sessions::SessionAnalysisReport, String> {
-    use crate::sessions::analyze_session;
-
     let paths = if exact {
         resolve_session_paths_literal(session_id, project, format_name)
     } else {
@@ -1031,11 +1029,12 @@ pub fn build_analyze_report(
         return Err(format!("No sessions found matching: {}", session_id));
     }
 
-    if paths.len() > 1 {
+    let mut report = if paths.len() > 1 {
         super::analyze::aggregate_sessions(&paths, format_name)
-            .ok_or_else(|| "No sessions could be analyzed".to_string())
+            .ok_or_else(|| "No sessions could be analyzed".to_string())?
     } else {
-        let session = parse_session_for_show(&paths[0], format_name)?;
-        Ok(analyze_session(&session))
-    }
+        super::analyze::analyze_session_file(&paths[0], format_name).map_err(|e| e.to_string())?
+    };
+    super::analyze::refine_view_recommendations(&mut report, project.unwrap_or(Path::new(".")));
+    Ok(report)
 }
@@ -1,12 +1,14 @@
 //! Aggregate statistics across sessions.
 
 use super::{
-    analyze::{aggregate_sessions, print_sessions_analysis},
+    analyze::{aggregate_sessions, analyze_session_file, print_sessions_analysis},
     session_matches_grep,
     sort::{DefaultDir, SortDir, SortSpec},
 };
 use crate::output::OutputFormatter;
-use crate::sessions::{FormatRegistry, SessionFile, SessionSource};
+use crate::sessions::{
+    FormatRegistry, SessionFile, SessionSource, SessionTrendReport, TrendBucket,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -83,6 +85,32 @@ impl OutputFormatter for RepoStatsReport {
         }
         lines.join("\n")
     }
+
+    fn format_table(&self) -> Option<crate::output::Table> {
+        let mut table = crate::output::Table::new([
+            "repo",
+            "sessions",
+            "turns",
+            "tokens_in",
+            "tokens_out",
+            "error_rate",
+            "parallelization_rate",
+            "cost_usd",
+        ]);
+        for e in &self.repos {
+            table.push_row([
+                e.repo.clone(),
+                e.session_count.to_string(),
+                e.turns.to_string(),
+                e.tokens_in.to_string(),
+                e.tokens_out.to_string(),
+                format!("{:.3}", e.error_rate),
+                format!("{:.3}", e.parallelization_rate),
+                e.cost_usd.map(|c| format!("{:.4}", c)).unwrap_or_default(),
+            ]);
+        }
+        Some(table)
+    }
 }
 
 /// Format token count with K/M suffix (u64 variant).
@@ -457,6 +485,11 @@ pub fn build_stats_data(
     let paths: Vec<_> = sessions.iter().map(|s| s.path.clone()).collect();
     let mut report = aggregate_sessions(&paths, format_name)
         .ok_or_else(|| "No sessions could be analyzed".to_string())?;
+    // Sessions from one project can use that project's index for skeleton sizes.
+    if !all_projects {
+        let project = project_filter.or(root).unwrap_or(Path::new("."));
+        super::analyze::refine_view_recommendations(&mut report, project);
+    }
 
     // Apply sort hint to tool rows in formatted output.
     if let Some(s) = sort {
@@ -606,7 +639,7 @@ fn resolve_claude_dir_name(dir_name: &str) -> Option<PathBuf> {
 /// Extract repository name from session path.
 /// For paths like ~/.claude/projects/-home-me-git-normalize/session.jsonl, returns "normalize".
 /// Handles projects with dashes in their name (e.g. `-home-me-git-my-cool-project` → `my-cool-project`).
-fn extract_repo_name(path: &Path) -> String {
+pub(crate) fn extract_repo_name(path: &Path) -> String {
     let path_str = path.to_string_lossy();
 
     if let Some(projects_idx) = path_str.find(".claude/projects/") {
@@ -636,7 +669,7 @@ fn extract_repo_name(path: &Path) -> String {
 }
 
 /// Extract day string (YYYY-MM-DD) from a SystemTime.
-fn extract_day(mtime: &SystemTime) -> String {
+pub(crate) fn extract_day(mtime: &SystemTime) -> String {
     let secs = mtime
         .duration_since(SystemTime::UNIX_EPOCH)
         .map(|d| d.as_secs())
@@ -906,6 +939,104 @@ pub fn build_repo_stats(
     })
 }
 
+/// Build a per-day or per-week trend report (`sessions stats --trend`).
+///
+/// Sessions are selected like `build_stats_data`, dated by modification time,
+/// analyzed one at a time, and aggregated per period.
+#[allow(clippy::too_many_arguments)]
+pub fn build_trend_stats(
+    root: Option<&Path>,
+    limit: usize,
+    format_name: Option<&str>,
+    grep: Option<&str>,
+    days: Option<u32>,
+    since: Option<&str>,
+    until: Option<&str>,
+    project_filter: Option<&Path>,
+    all_projects: bool,
+    mode: &super::SessionMode,
+    agent_type: Option<&str>,
+    bucket: TrendBucket,
+) -> Result<SessionTrendReport, String> {
+    let registry = FormatRegistry::new();
+    let source: &dyn SessionSource = match format_name {
+        Some(name) => registry
+            .get(name)
+            .ok_or_else(|| format!("Unknown format: {}", name))?,
+        None => registry.get("claude").ok_or_else(|| {
+            "Claude format not available (compile with feature = format-claude)".to_string()
+        })?,
+    };
+
+    let grep_re = grep
+        .map(|p| regex::Regex::new(p).map_err(|_| format!("Invalid grep pattern: {}", p)))
+        .transpose()?;
+
+    let mut sessions: Vec<SessionFile> = if all_projects {
+        list_all_project_sessions_by_mode(source, mode)
+    } else {
+        let project = project_filter.or(root);
+        super::list_sessions_by_mode(source, project, mode)
+    };
+
+    let now = SystemTime::now();
+    let since_time = if let Some(d) = days {
+        Some(now - Duration::from_secs(d as u64 * 86400))
+    } else if let Some(s) = since {
+        Some(parse_date(s).ok_or_else(|| format!("Invalid date format: {} (use YYYY-MM-DD)", s))?)
+    } else {
+        None
+    };
+    let until_time = if let Some(u) = until {
+        Some(
+            parse_date(u).ok_or_else(|| format!("Invalid date format: {} (use YYYY-MM-DD)", u))?
+                + Duration::from_secs(86400),
+        )
+    } else {
+        None
+    };
+
+    if let Some(since) = since_time {
+        sessions.retain(|s| s.mtime >= since);
+    }
+    if let Some(until) = until_time {
+        sessions.retain(|s| s.mtime <= until);
+    }
+    if let Some(ref re) = grep_re {
+        sessions.retain(|s| session_matches_grep(&s.path, re));
+    }
+    if let Some(at) = agent_type {
+        let at_lower = at.to_lowercase();
+        sessions.retain(|s| {
+            s.subagent_type
+                .as_deref()
+                .is_some_and(|t| t.to_lowercase() == at_lower)
+        });
+    }
+
+    sessions.sort_by_key(|b| std::cmp::Reverse(b.mtime));
+    if limit > 0 {
+        sessions.truncate(limit);
+    }
+
+    if sessions.is_empty() {
+        return Err("No sessions found".to_string());
+    }
+
+    let mut dated = Vec::with_capacity(sessions.len());
+    for s in &sessions {
+        match analyze_session_file(&s.path, format_name) {
+            Ok(report) => dated.push((extract_day(&s.mtime), report)),
+            Err(e) => eprintln!("Warning: Failed to parse {}: {}", s.path.display(), e),
+        }
+    }
+    if dated.is_empty() {
+        return Err("No sessions could be analyzed".to_string());
+    }
+
+    Ok(SessionTrendReport::build(bucket, &dated))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,125 @@
+//! Export a session as OpenTelemetry trace spans (OTLP/JSON).
+//!
+//! The request body can be printed, written to a file for later replay with
+//! `curl --data @file`, or POSTed straight to a collector's OTLP/HTTP
+//! endpoint (Jaeger, Grafana Tempo and the OpenTelemetry Collector all accept
+//! it on port 4318).
+
+use super::show::parse_session_for_show;
+use super::{resolve_session_paths, resolve_session_paths_literal};
+use crate::output::OutputFormatter;
+use normalize_session_analysis::session_to_otlp;
+use std::path::Path;
+
+/// Report returned by `sessions trace`.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct SessionTraceReport {
+    pub session_id: Option<String>,
+    pub trace_id: String,
+    pub spans: usize,
+    /// File the request was written to, if `--output` was given.
+    pub output: Option<String>,
+    /// URL the spans were sent to, if `--endpoint` was given.
+    pub endpoint: Option<String>,
+    /// The OTLP/JSON `ExportTraceServiceRequest`.
+    pub request: serde_json::Value,
+}
+
+impl OutputFormatter for SessionTraceReport {
+    fn format_text(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(path) = &self.output {
+            lines.push(format!(
+                "Wrote {} spans (trace {}) to {}",
+                self.spans, self.trace_id, path
+            ));
+        }
+        if let Some(url) = &self.endpoint {
+            lines.push(format!(
+                "Sent {} spans (trace {}) to {}",
+                self.spans, self.trace_id, url
+            ));
+        }
+        if lines.is_empty() {
+            return serde_json::to_string_pretty(&self.request).unwrap_or_default();
+        }
+        lines.join("\n")
+    }
+}
+
+/// The OTLP/HTTP traces URL for a collector base URL: `/v1/traces` is
+/// appended unless already present.
+fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// Convert a session to OTLP spans; writes `output` and/or POSTs to
+/// `endpoint` when given.
+pub fn build_trace_report(
+    session_id: &str,
+    project: Option<&Path>,
+    format_name: Option<&str>,
+    exact: bool,
+    output: Option<&Path>,
+    endpoint: Option<&str>,
+) -> Result<SessionTraceReport, String> {
+    let paths = if exact {
+        resolve_session_paths_literal(session_id, project, format_name)
+    } else {
+        resolve_session_paths(session_id, project, format_name)
+    };
+    let path = paths
+        .first()
+        .ok_or_else(|| format!("No sessions found matching: {}", session_id))?;
+    let session = parse_session_for_show(path, format_name)?;
+    let trace = session_to_otlp(&session);
+    let body = serde_json::to_string(&trace.request)
+        .map_err(|e| format!("Failed to serialize spans: {}", e))?;
+
+    if let Some(output) = output {
+        normalize_output::sink::write_atomic(output, body.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+    }
+    let endpoint = endpoint.map(traces_url);
+    if let Some(url) = &endpoint {
+        ureq::post(url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map_err(|e| format!("Failed to send spans to {}: {}", url, e))?;
+    }
+
+    Ok(SessionTraceReport {
+        session_id: session.metadata.session_id.clone(),
+        trace_id: trace.trace_id,
+        spans: trace.span_count,
+        output: output.map(|p| p.display().to_string()),
+        endpoint,
+        request: trace.request,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_url_appends_the_otlp_path_once() {
+        assert_eq!(
+            traces_url("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("https://tempo.example/otlp/v1/traces"),
+            "https://tempo.example/otlp/v1/traces"
+        );
+    }
+}
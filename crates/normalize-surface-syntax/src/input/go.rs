@@ -0,0 +1,959 @@
+//! Tree-sitter based Go reader.
+//!
+//! Covers the imperative subset: functions, `:=`/`var`/`const` bindings,
+//! assignments, arithmetic, calls, `if`, expression `switch` and the three
+//! `for` forms. Methods, type declarations, type switches, `go`/`defer` and
+//! channels are skipped.
+
+use crate::ir::*;
+use crate::traits::{ReadError, Reader};
+use tree_sitter::{Node, Parser, Tree};
+
+/// Static instance of the Go reader for registry.
+pub static GO_READER: GoReader = GoReader;
+
+/// Go reader using tree-sitter.
+pub struct GoReader;
+
+impl Reader for GoReader {
+    fn language(&self) -> &'static str {
+        "go"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["go"]
+    }
+
+    fn read(&self, source: &str) -> Result<Program, ReadError> {
+        read_go(source)
+    }
+}
+
+/// Parse Go source into surface-syntax IR.
+pub fn read_go(source: &str) -> Result<Program, ReadError> {
+    let language = normalize_languages::parsers::grammar_loader()
+        .get("go")
+        .map_err(|e| ReadError::Parse(format!("load go grammar: {e}")))?;
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language)
+        .map_err(|err| ReadError::Parse(err.to_string()))?;
+
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| ReadError::Parse("failed to parse".into()))?;
+
+    let ctx = ReadContext::new(source);
+    ctx.read_program(&tree)
+}
+
+struct ReadContext<'a> {
+    source: &'a str,
+}
+
+impl<'a> ReadContext<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source }
+    }
+
+    fn node_text(&self, node: Node) -> &str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn named_children<'t>(&self, node: Node<'t>) -> Vec<Node<'t>> {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor).collect()
+    }
+
+    fn read_program(&self, tree: &Tree) -> Result<Program, ReadError> {
+        let root = tree.root_node();
+
+        if root.has_error() {
+            return Err(ReadError::Parse("syntax error in source".into()));
+        }
+
+        let mut statements = Vec::new();
+        for child in self.named_children(root) {
+            statements.extend(self.read_stmt(child)?);
+        }
+
+        Ok(Program::new(statements))
+    }
+
+    /// Read one statement. Go declarations can bind several names at once
+    /// (`var a, b = 1, 2`, `import ( ... )`), so this yields zero or more IR
+    /// statements.
+    fn read_stmt(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let stmt = match node.kind() {
+            // Comments and the package clause carry no IR
+            "comment" | "package_clause" | "empty_statement" => return Ok(Vec::new()),
+
+            // Imports
+            "import_declaration" => return self.read_import_declaration(node),
+
+            // Bindings
+            "short_var_declaration" => return self.read_short_var_declaration(node),
+            "var_declaration" => return self.read_var_declaration(node, true),
+            "const_declaration" => return self.read_var_declaration(node, false),
+
+            // Assignment and ++/--
+            "assignment_statement" => self.read_assignment(node)?,
+            "inc_statement" => Stmt::expr(self.read_step(node, BinaryOp::Add)?),
+            "dec_statement" => Stmt::expr(self.read_step(node, BinaryOp::Sub)?),
+
+            // Control flow
+            "if_statement" => self.read_if_statement(node)?,
+            "for_statement" => self.read_for_statement(node)?,
+            "expression_switch_statement" => self.read_switch_statement(node)?,
+            "fallthrough_statement" => {
+                return Err(ReadError::Unsupported("fallthrough".into()));
+            }
+            "return_statement" => self.read_return_statement(node)?,
+            "break_statement" => Stmt::break_stmt(),
+            "continue_statement" => Stmt::continue_stmt(),
+            "block" => self.read_block(node)?,
+
+            // Function declaration
+            "function_declaration" => self.read_function_declaration(node)?,
+
+            // Expression statement (bare calls, etc.)
+            "expression_statement" => {
+                let expr = node.named_child(0).ok_or_else(|| {
+                    ReadError::Parse("expression_statement missing expression".into())
+                })?;
+                Stmt::expr(self.read_expr(expr)?)
+            }
+
+            // Methods, type declarations, type switches, defer, go, ... (skip for now)
+            _ => return Ok(Vec::new()),
+        };
+        Ok(vec![stmt])
+    }
+
+    /// `import "fmt"` / `import ( f "fmt"; "os" )` → one `Stmt::Import` per spec.
+    fn read_import_declaration(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let mut specs = Vec::new();
+        for child in self.named_children(node) {
+            match child.kind() {
+                "import_spec" => specs.push(child),
+                "import_spec_list" => specs.extend(
+                    self.named_children(child)
+                        .into_iter()
+                        .filter(|c| c.kind() == "import_spec"),
+                ),
+                _ => {}
+            }
+        }
+
+        let mut stmts = Vec::new();
+        for spec in specs {
+            let path = spec
+                .child_by_field_name("path")
+                .ok_or_else(|| ReadError::Parse("import_spec missing path".into()))?;
+            let source = self.string_content(path).to_string();
+            // The package name defaults to the last path segment.
+            let package = source.rsplit('/').next().unwrap_or(&source).to_string();
+            let name = match spec.child_by_field_name("name") {
+                Some(alias) if alias.kind() == "dot" => ImportName::namespace("."),
+                Some(alias) => ImportName::aliased(package, self.node_text(alias)),
+                None => ImportName::named(package),
+            };
+            stmts.push(Stmt::import(source, vec![name]));
+        }
+        Ok(stmts)
+    }
+
+    /// `a := 1`, `a, b := 1, 2` or `a, b := f()`.
+    fn read_short_var_declaration(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let left = node
+            .child_by_field_name("left")
+            .ok_or_else(|| ReadError::Parse("short_var_declaration missing left".into()))?;
+        let right = node
+            .child_by_field_name("right")
+            .ok_or_else(|| ReadError::Parse("short_var_declaration missing right".into()))?;
+
+        let names: Vec<&str> = self
+            .named_children(left)
+            .into_iter()
+            .map(|n| self.node_text(n))
+            .collect();
+        let values = self.named_children(right);
+        let span = Span::from_ts(node.start_position(), node.end_position());
+
+        self.bind(&names, &values, None, true, span)
+    }
+
+    /// `var x int = 1`, `const a, b = 1, 2`, or a parenthesized group of specs.
+    fn read_var_declaration(&self, node: Node, mutable: bool) -> Result<Vec<Stmt>, ReadError> {
+        let mut specs = Vec::new();
+        for child in self.named_children(node) {
+            match child.kind() {
+                "var_spec" | "const_spec" => specs.push(child),
+                "var_spec_list" => specs.extend(
+                    self.named_children(child)
+                        .into_iter()
+                        .filter(|c| c.kind() == "var_spec"),
+                ),
+                _ => {}
+            }
+        }
+
+        let mut stmts = Vec::new();
+        for spec in specs {
+            let mut cursor = spec.walk();
+            let names: Vec<&str> = spec
+                .children_by_field_name("name", &mut cursor)
+                .filter(|n| n.kind() == "identifier")
+                .map(|n| self.node_text(n))
+                .collect();
+            let values = spec
+                .child_by_field_name("value")
+                .map(|v| self.named_children(v))
+                .unwrap_or_default();
+            let type_annotation = spec
+                .child_by_field_name("type")
+                .map(|t| self.node_text(t).to_string());
+            let span = Span::from_ts(spec.start_position(), spec.end_position());
+            stmts.extend(self.bind(&names, &values, type_annotation, mutable, span)?);
+        }
+        Ok(stmts)
+    }
+
+    /// Bind `names` to `values` pairwise; several names bound to one call
+    /// (`a, b := f()`) become an array destructure.
+    fn bind(
+        &self,
+        names: &[&str],
+        values: &[Node],
+        type_annotation: Option<String>,
+        mutable: bool,
+        span: Span,
+    ) -> Result<Vec<Stmt>, ReadError> {
+        if names.len() > 1 && values.len() == 1 {
+            let pat = Pat::Array(names.iter().map(|n| Some(Pat::ident(*n))).collect(), None);
+            let value = self.read_expr(values[0])?;
+            return Ok(vec![Stmt::destructure(pat, value, mutable).with_span(span)]);
+        }
+        if !values.is_empty() && values.len() != names.len() {
+            return Err(ReadError::Parse(format!(
+                "{} names bound to {} values",
+                names.len(),
+                values.len()
+            )));
+        }
+
+        let mut stmts = Vec::new();
+        for (i, name) in names.iter().enumerate() {
+            let init = values.get(i).map(|v| self.read_expr(*v)).transpose()?;
+            stmts.push(Stmt::Let {
+                name: name.to_string(),
+                init,
+                mutable,
+                type_annotation: type_annotation.clone(),
+                span: Some(span),
+            });
+        }
+        Ok(stmts)
+    }
+
+    fn read_assignment(&self, node: Node) -> Result<Stmt, ReadError> {
+        let left = node
+            .child_by_field_name("left")
+            .ok_or_else(|| ReadError::Parse("assignment_statement missing left".into()))?;
+        let right = node
+            .child_by_field_name("right")
+            .ok_or_else(|| ReadError::Parse("assignment_statement missing right".into()))?;
+        let op_node = node
+            .child_by_field_name("operator")
+            .ok_or_else(|| ReadError::Parse("assignment_statement missing operator".into()))?;
+
+        let targets = self.named_children(left);
+        let values = self.named_children(right);
+        if targets.len() != 1 || values.len() != 1 {
+            return Err(ReadError::Unsupported(
+                "Go tuple assignment (a, b = x, y)".into(),
+            ));
+        }
+
+        let target = self.read_expr(targets[0])?;
+        let rhs = self.read_expr(values[0])?;
+        let op_text = self.node_text(op_node);
+        let value = match op_text {
+            "=" => rhs,
+            "+=" => Expr::binary(target.clone(), BinaryOp::Add, rhs),
+            "-=" => Expr::binary(target.clone(), BinaryOp::Sub, rhs),
+            "*=" => Expr::binary(target.clone(), BinaryOp::Mul, rhs),
+            "/=" => Expr::binary(target.clone(), BinaryOp::Div, rhs),
+            "%=" => Expr::binary(target.clone(), BinaryOp::Mod, rhs),
+            _ => {
+                return Err(ReadError::Unsupported(format!(
+                    "Go assignment operator '{}'",
+                    op_text
+                )));
+            }
+        };
+
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        Ok(Stmt::expr(Expr::assign(target, value).with_span(span)))
+    }
+
+    /// `x++` / `x--` → `x = x + 1` / `x = x - 1`.
+    fn read_step(&self, node: Node, op: BinaryOp) -> Result<Expr, ReadError> {
+        let operand = node
+            .named_child(0)
+            .ok_or_else(|| ReadError::Parse(format!("{} missing operand", node.kind())))?;
+        let target = self.read_expr(operand)?;
+        Ok(Expr::assign(
+            target.clone(),
+            Expr::binary(target, op, Expr::number(1)),
+        ))
+    }
+
+    fn read_if_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let condition = node
+            .child_by_field_name("condition")
+            .ok_or_else(|| ReadError::Parse("if missing condition".into()))?;
+        let consequence = node
+            .child_by_field_name("consequence")
+            .ok_or_else(|| ReadError::Parse("if missing consequence".into()))?;
+
+        let test = self.read_expr(condition)?;
+        let consequent = self.read_block(consequence)?;
+
+        // `else { ... }` is a block, `else if` a nested if_statement
+        let alternate = node
+            .child_by_field_name("alternative")
+            .map(|alt| match alt.kind() {
+                "if_statement" => self.read_if_statement(alt),
+                _ => self.read_block(alt),
+            })
+            .transpose()?;
+
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        let if_stmt = Stmt::if_stmt(test, consequent, alternate).with_span(span);
+
+        // `if v := f(); v > 0 { ... }` — the initializer is scoped to the if,
+        // so keep both in a block.
+        match node.child_by_field_name("initializer") {
+            Some(init) => {
+                let mut stmts = self.read_stmt(init)?;
+                stmts.push(if_stmt);
+                Ok(Stmt::block(stmts))
+            }
+            None => Ok(if_stmt),
+        }
+    }
+
+    /// `switch v { case a, b: ... default: ... }`. A tagless `switch { }`
+    /// switches on `true`, matching Go's semantics.
+    fn read_switch_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let discriminant = match node.child_by_field_name("value") {
+            Some(value) => self.read_expr(value)?,
+            None => Expr::bool(true),
+        };
+
+        let mut cases = Vec::new();
+        let mut default = None;
+        for arm in self.named_children(node) {
+            match arm.kind() {
+                "expression_case" => {
+                    let tests = match arm.child_by_field_name("value") {
+                        Some(list) => self
+                            .named_children(list)
+                            .into_iter()
+                            .map(|test| self.read_expr(test))
+                            .collect::<Result<_, _>>()?,
+                        None => Vec::new(),
+                    };
+                    cases.push(SwitchCase::new(tests, self.read_switch_arm(arm)?));
+                }
+                "default_case" => default = Some(self.read_switch_arm(arm)?),
+                _ => {}
+            }
+        }
+
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        let switch = Stmt::switch(discriminant, cases, default).with_span(span);
+
+        // Same scoping as `if`: keep the initializer next to the switch.
+        match node.child_by_field_name("initializer") {
+            Some(init) => {
+                let mut stmts = self.read_stmt(init)?;
+                stmts.push(switch);
+                Ok(Stmt::block(stmts))
+            }
+            None => Ok(switch),
+        }
+    }
+
+    /// Statements of a case arm. Go arms never fall through implicitly, so a
+    /// trailing `break` is redundant and dropped.
+    fn read_switch_arm(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let mut stmts = Vec::new();
+        for child in self.named_children(node) {
+            if child.kind() == "statement_list" {
+                for stmt in self.named_children(child) {
+                    stmts.extend(self.read_stmt(stmt)?);
+                }
+            }
+        }
+        if matches!(stmts.last(), Some(Stmt::Break)) {
+            stmts.pop();
+        }
+        Ok(stmts)
+    }
+
+    /// `for { }`, `for cond { }`, `for init; cond; post { }` and
+    /// `for k := range xs { }` / `for _, v := range xs { }`.
+    fn read_for_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let body = node
+            .child_by_field_name("body")
+            .ok_or_else(|| ReadError::Parse("for missing body".into()))?;
+        let body_stmt = self.read_block(body)?;
+        let span = Span::from_ts(node.start_position(), node.end_position());
+
+        let header = self
+            .named_children(node)
+            .into_iter()
+            .find(|c| c.id() != body.id() && c.kind() != "comment");
+
+        let stmt = match header {
+            None => Stmt::while_loop(Expr::bool(true), body_stmt),
+            Some(clause) if clause.kind() == "for_clause" => {
+                let init = clause
+                    .child_by_field_name("initializer")
+                    .map(|n| self.read_simple_stmt(n))
+                    .transpose()?;
+                let test = clause
+                    .child_by_field_name("condition")
+                    .map(|n| self.read_expr(n))
+                    .transpose()?;
+                let update = clause
+                    .child_by_field_name("update")
+                    .map(|n| match self.read_simple_stmt(n)? {
+                        Stmt::Expr(e) => Ok(e),
+                        _ => Err(ReadError::Unsupported(
+                            "Go for-loop update that is not an expression".into(),
+                        )),
+                    })
+                    .transpose()?;
+                Stmt::for_loop(init, test, update, body_stmt)
+            }
+            Some(clause) if clause.kind() == "range_clause" => {
+                let right = clause
+                    .child_by_field_name("right")
+                    .ok_or_else(|| ReadError::Parse("range_clause missing right".into()))?;
+                let vars: Vec<&str> = clause
+                    .child_by_field_name("left")
+                    .map(|l| {
+                        self.named_children(l)
+                            .into_iter()
+                            .map(|n| self.node_text(n))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let variable = match vars.as_slice() {
+                    [key] => *key,
+                    ["_", value] => *value,
+                    [] => "_",
+                    _ => {
+                        return Err(ReadError::Unsupported(
+                            "Go range loop binding both key and value".into(),
+                        ));
+                    }
+                };
+                Stmt::for_in(variable, self.read_expr(right)?, body_stmt)
+            }
+            Some(condition) => Stmt::while_loop(self.read_expr(condition)?, body_stmt),
+        };
+        Ok(stmt.with_span(span))
+    }
+
+    /// A simple statement in a `for` header or `if` initializer, which must
+    /// produce exactly one IR statement.
+    fn read_simple_stmt(&self, node: Node) -> Result<Stmt, ReadError> {
+        let mut stmts = self.read_stmt(node)?;
+        match stmts.len() {
+            1 => Ok(stmts.remove(0)),
+            _ => Err(ReadError::Unsupported(format!(
+                "Go simple statement '{}'",
+                self.node_text(node)
+            ))),
+        }
+    }
+
+    fn read_return_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let values = node
+            .named_child(0)
+            .map(|list| self.named_children(list))
+            .unwrap_or_default();
+        let expr = match values.as_slice() {
+            [] => None,
+            [value] => Some(self.read_expr(*value)?),
+            // Multiple results become an array (closest equivalent)
+            _ => Some(Expr::array(
+                values
+                    .iter()
+                    .map(|v| self.read_expr(*v))
+                    .collect::<Result<_, _>>()?,
+            )),
+        };
+        Ok(Stmt::return_stmt(expr))
+    }
+
+    fn read_function_declaration(&self, node: Node) -> Result<Stmt, ReadError> {
+        let name = node
+            .child_by_field_name("name")
+            .ok_or_else(|| ReadError::Parse("function missing name".into()))?;
+        let body = node
+            .child_by_field_name("body")
+            .ok_or_else(|| ReadError::Parse("function missing body".into()))?;
+
+        let mut func = self.read_function_parts(node, body)?;
+        func.name = self.node_text(name).to_string();
+        func.span = Some(Span::from_ts(node.start_position(), node.end_position()));
+        Ok(Stmt::function(func))
+    }
+
+    /// Parameters, result type and body shared by declarations and literals.
+    fn read_function_parts(&self, node: Node, body: Node) -> Result<Function, ReadError> {
+        let params = node
+            .child_by_field_name("parameters")
+            .map(|p| self.read_parameters(p))
+            .transpose()?
+            .unwrap_or_default();
+        let body = self.read_block_stmts(body)?;
+
+        let mut func = Function::anonymous(params, body);
+        func.return_type = node
+            .child_by_field_name("result")
+            .map(|n| self.node_text(n).to_string());
+        Ok(func)
+    }
+
+    /// `(a, b int, s ...string)` — names sharing a type each get it.
+    fn read_parameters(&self, node: Node) -> Result<Vec<Param>, ReadError> {
+        let mut params = Vec::new();
+        for decl in self.named_children(node) {
+            if !matches!(
+                decl.kind(),
+                "parameter_declaration" | "variadic_parameter_declaration"
+            ) {
+                continue;
+            }
+            let type_annotation = decl
+                .child_by_field_name("type")
+                .map(|t| self.node_text(t).to_string());
+            let mut cursor = decl.walk();
+            for name in decl.children_by_field_name("name", &mut cursor) {
+                let mut param = Param::new(self.node_text(name));
+                param.type_annotation = type_annotation.clone();
+                params.push(param);
+            }
+        }
+        Ok(params)
+    }
+
+    fn read_block(&self, node: Node) -> Result<Stmt, ReadError> {
+        Ok(Stmt::block(self.read_block_stmts(node)?))
+    }
+
+    fn read_block_stmts(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let mut stmts = Vec::new();
+        for child in self.named_children(node) {
+            // Blocks wrap their statements in a statement_list
+            if child.kind() == "statement_list" {
+                for stmt in self.named_children(child) {
+                    stmts.extend(self.read_stmt(stmt)?);
+                }
+            } else {
+                stmts.extend(self.read_stmt(child)?);
+            }
+        }
+        Ok(stmts)
+    }
+
+    /// Contents of a string literal without its quotes.
+    fn string_content(&self, node: Node) -> &str {
+        let text = self.node_text(node);
+        match node.kind() {
+            "raw_string_literal" => text.trim_matches('`'),
+            "rune_literal" => text.trim_matches('\''),
+            _ => text.trim_matches('"'),
+        }
+    }
+
+    fn read_expr(&self, node: Node) -> Result<Expr, ReadError> {
+        match node.kind() {
+            // Literals
+            "int_literal" => {
+                let text = self.node_text(node).replace('_', "");
+                let num = match text.get(..2) {
+                    Some("0x" | "0X") => i64::from_str_radix(&text[2..], 16).map(|n| n as f64),
+                    Some("0o" | "0O") => i64::from_str_radix(&text[2..], 8).map(|n| n as f64),
+                    Some("0b" | "0B") => i64::from_str_radix(&text[2..], 2).map(|n| n as f64),
+                    _ => text.parse::<i64>().map(|n| n as f64),
+                };
+                Ok(Expr::number(num.unwrap_or(0.0)))
+            }
+            "float_literal" => {
+                let text = self.node_text(node).replace('_', "");
+                Ok(Expr::number(text.parse::<f64>().unwrap_or(0.0)))
+            }
+            "interpreted_string_literal" | "raw_string_literal" | "rune_literal" => {
+                Ok(Expr::string(self.string_content(node)))
+            }
+            "true" => Ok(Expr::bool(true)),
+            "false" => Ok(Expr::bool(false)),
+            "nil" => Ok(Expr::null()),
+
+            // Identifiers
+            "identifier" | "field_identifier" | "package_identifier" => {
+                Ok(Expr::ident(self.node_text(node)))
+            }
+
+            // Operators
+            "binary_expression" => self.read_binary_expression(node),
+            "unary_expression" => self.read_unary_expression(node),
+
+            // Calls and access
+            "call_expression" => self.read_call(node),
+            "selector_expression" => {
+                let operand = node
+                    .child_by_field_name("operand")
+                    .ok_or_else(|| ReadError::Parse("selector missing operand".into()))?;
+                let field = node
+                    .child_by_field_name("field")
+                    .ok_or_else(|| ReadError::Parse("selector missing field".into()))?;
+                Ok(Expr::member(
+                    self.read_expr(operand)?,
+                    self.node_text(field),
+                ))
+            }
+            "index_expression" => {
+                let operand = node
+                    .child_by_field_name("operand")
+                    .ok_or_else(|| ReadError::Parse("index missing operand".into()))?;
+                let index = node
+                    .child_by_field_name("index")
+                    .ok_or_else(|| ReadError::Parse("index missing index".into()))?;
+                Ok(Expr::index(
+                    self.read_expr(operand)?,
+                    self.read_expr(index)?,
+                ))
+            }
+
+            // Slice, array, map and struct literals
+            "composite_literal" => {
+                let body = node
+                    .child_by_field_name("body")
+                    .ok_or_else(|| ReadError::Parse("composite_literal missing body".into()))?;
+                self.read_literal_value(body)
+            }
+            "literal_value" => self.read_literal_value(node),
+            "literal_element" => {
+                let inner = node
+                    .named_child(0)
+                    .ok_or_else(|| ReadError::Parse("literal_element missing value".into()))?;
+                self.read_expr(inner)
+            }
+
+            // Parenthesized
+            "parenthesized_expression" => {
+                let inner = node.named_child(0).ok_or_else(|| {
+                    ReadError::Parse("parenthesized_expression missing inner".into())
+                })?;
+                self.read_expr(inner)
+            }
+
+            // Function literal
+            "func_literal" => {
+                let body = node
+                    .child_by_field_name("body")
+                    .ok_or_else(|| ReadError::Parse("func_literal missing body".into()))?;
+                let func = self.read_function_parts(node, body)?;
+                Ok(Expr::Function(Box::new(func)))
+            }
+
+            _ => Err(ReadError::Parse(format!(
+                "unsupported expression: {}",
+                node.kind()
+            ))),
+        }
+    }
+
+    fn read_binary_expression(&self, node: Node) -> Result<Expr, ReadError> {
+        let left = node
+            .child_by_field_name("left")
+            .ok_or_else(|| ReadError::Parse("binary_expression missing left".into()))?;
+        let right = node
+            .child_by_field_name("right")
+            .ok_or_else(|| ReadError::Parse("binary_expression missing right".into()))?;
+        let op_node = node
+            .child_by_field_name("operator")
+            .ok_or_else(|| ReadError::Parse("binary_expression missing operator".into()))?;
+
+        let op = match self.node_text(op_node) {
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Sub,
+            "*" => BinaryOp::Mul,
+            "/" => BinaryOp::Div,
+            "%" => BinaryOp::Mod,
+            "==" => BinaryOp::Eq,
+            "!=" => BinaryOp::Ne,
+            "<" => BinaryOp::Lt,
+            "<=" => BinaryOp::Le,
+            ">" => BinaryOp::Gt,
+            ">=" => BinaryOp::Ge,
+            "&&" => BinaryOp::And,
+            "||" => BinaryOp::Or,
+            other => {
+                return Err(ReadError::Unsupported(format!(
+                    "Go binary operator '{}'",
+                    other
+                )));
+            }
+        };
+
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        Ok(Expr::binary(self.read_expr(left)?, op, self.read_expr(right)?).with_span(span))
+    }
+
+    fn read_unary_expression(&self, node: Node) -> Result<Expr, ReadError> {
+        let op_node = node
+            .child_by_field_name("operator")
+            .ok_or_else(|| ReadError::Parse("unary_expression missing operator".into()))?;
+        let operand = node
+            .child_by_field_name("operand")
+            .ok_or_else(|| ReadError::Parse("unary_expression missing operand".into()))?;
+
+        let op = match self.node_text(op_node) {
+            "-" => UnaryOp::Neg,
+            "!" => UnaryOp::Not,
+            "+" => return self.read_expr(operand), // Unary + is no-op
+            other => {
+                return Err(ReadError::Unsupported(format!(
+                    "Go unary operator '{}'",
+                    other
+                )));
+            }
+        };
+
+        Ok(Expr::unary(op, self.read_expr(operand)?))
+    }
+
+    fn read_call(&self, node: Node) -> Result<Expr, ReadError> {
+        let function = node
+            .child_by_field_name("function")
+            .ok_or_else(|| ReadError::Parse("call missing function".into()))?;
+
+        let args = node
+            .child_by_field_name("arguments")
+            .map(|a| {
+                self.named_children(a)
+                    .into_iter()
+                    .filter(|c| c.kind() != "comment")
+                    .map(|c| self.read_expr(c))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        Ok(Expr::call(self.read_expr(function)?, args).with_span(span))
+    }
+
+    /// `{1, 2}` → array; `{"a": 1}` / `{X: 1}` → object.
+    fn read_literal_value(&self, node: Node) -> Result<Expr, ReadError> {
+        let elements: Vec<Node> = self
+            .named_children(node)
+            .into_iter()
+            .filter(|c| c.kind() != "comment")
+            .collect();
+
+        if elements.iter().any(|e| e.kind() == "keyed_element") {
+            let mut pairs = Vec::new();
+            for element in elements {
+                let key = element
+                    .child_by_field_name("key")
+                    .ok_or_else(|| ReadError::Parse("keyed_element missing key".into()))?;
+                let value = element
+                    .child_by_field_name("value")
+                    .ok_or_else(|| ReadError::Parse("keyed_element missing value".into()))?;
+                let key_node = key.named_child(0).unwrap_or(key);
+                let key_str = match key_node.kind() {
+                    "interpreted_string_literal" | "raw_string_literal" => {
+                        self.string_content(key_node)
+                    }
+                    _ => self.node_text(key_node),
+                };
+                pairs.push((key_str.to_string(), self.read_expr(value)?));
+            }
+            return Ok(Expr::object(pairs));
+        }
+
+        let items = elements
+            .into_iter()
+            .map(|e| self.read_expr(e))
+            .collect::<Result<_, _>>()?;
+        Ok(Expr::array(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_var_declaration() -> Result<(), ReadError> {
+        let ir = read_go("package main\n\nfunc main() {\n\tx := 1 + 2\n}")?;
+        match &ir.body[0] {
+            Stmt::Function(f) => match &f.body[0] {
+                Stmt::Let { name, mutable, .. } => {
+                    assert_eq!(name, "x");
+                    assert!(*mutable);
+                }
+                other => panic!("expected Let, got {:?}", other),
+            },
+            other => panic!("expected Function, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_declaration() -> Result<(), ReadError> {
+        let ir = read_go("package main\n\nfunc add(a, b int) int {\n\treturn a + b\n}")?;
+        assert_eq!(ir.body.len(), 1);
+        match &ir.body[0] {
+            Stmt::Function(f) => {
+                assert_eq!(f.name, "add");
+                assert_eq!(f.params.len(), 2);
+                assert_eq!(f.params[0].name, "a");
+                assert_eq!(f.params[1].type_annotation.as_deref(), Some("int"));
+                assert_eq!(f.return_type.as_deref(), Some("int"));
+                assert!(matches!(
+                    &f.body[0],
+                    Stmt::Return(Some(Expr::Binary {
+                        op: BinaryOp::Add,
+                        ..
+                    }))
+                ));
+            }
+            other => panic!("expected Function, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_imports() -> Result<(), ReadError> {
+        let ir = read_go("package main\n\nimport (\n\t\"fmt\"\n\tp \"path/filepath\"\n)")?;
+        assert_eq!(ir.body.len(), 2);
+        match &ir.body[1] {
+            Stmt::Import { source, names, .. } => {
+                assert_eq!(source, "path/filepath");
+                assert_eq!(names[0].name, "filepath");
+                assert_eq!(names[0].alias.as_deref(), Some("p"));
+            }
+            other => panic!("expected Import, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_forms() -> Result<(), ReadError> {
+        let ir = read_go(
+            "package main\n\nfunc f(xs []int) {\n\tfor i := 0; i < 10; i++ {\n\t}\n\tfor x > 0 {\n\t\tx -= 1\n\t}\n\tfor _, v := range xs {\n\t\tfmt.Println(v)\n\t}\n}",
+        )?;
+        let Stmt::Function(f) = &ir.body[0] else {
+            panic!("expected Function");
+        };
+        assert!(matches!(
+            &f.body[0],
+            Stmt::For {
+                init: Some(_),
+                test: Some(_),
+                update: Some(Expr::Assign { .. }),
+                ..
+            }
+        ));
+        assert!(matches!(&f.body[1], Stmt::While { .. }));
+        assert!(matches!(&f.body[2], Stmt::ForIn { variable, .. } if variable == "v"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_if_else_if() -> Result<(), ReadError> {
+        let ir = read_go(
+            "package main\n\nfunc f(x int) {\n\tif x > 0 {\n\t\tx = 1\n\t} else if x < 0 {\n\t\tx = -1\n\t} else {\n\t\tx = 0\n\t}\n}",
+        )?;
+        let Stmt::Function(f) = &ir.body[0] else {
+            panic!("expected Function");
+        };
+        match &f.body[0] {
+            Stmt::If {
+                alternate: Some(alt),
+                ..
+            } => assert!(matches!(alt.as_ref(), Stmt::If { .. })),
+            other => panic!("expected If with else-if, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_value_binding() -> Result<(), ReadError> {
+        let ir = read_go("package main\n\nfunc f() {\n\ta, b := g()\n\tvar c, d = 1, 2\n}")?;
+        let Stmt::Function(f) = &ir.body[0] else {
+            panic!("expected Function");
+        };
+        assert!(matches!(&f.body[0], Stmt::Destructure { .. }));
+        assert_eq!(f.body.len(), 3);
+        Ok(())
+    }
+
+    #[cfg(feature = "write-lua")]
+    #[test]
+    fn test_go_to_lua() -> Result<(), ReadError> {
+        use crate::output::LuaWriter;
+        let ir = read_go(
+            "package main\n\nfunc double(n int) int {\n\tresult := n * 2\n\treturn result\n}",
+        )?;
+        let lua = LuaWriter::emit(&ir);
+        assert!(lua.contains("function double(n)"), "{lua}");
+        assert!(lua.contains("local result = (n * 2)"), "{lua}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_switch() -> Result<(), ReadError> {
+        let ir = read_go(
+            "package main\n\nfunc f() {\n\tswitch x {\n\tcase 1, 2:\n\t\ta()\n\tdefault:\n\t\tb()\n\t}\n}",
+        )?;
+        let Stmt::Function(f) = &ir.body[0] else {
+            panic!("expected Function");
+        };
+        let Stmt::Switch { cases, default, .. } = &f.body[0] else {
+            panic!("expected Switch, got {:?}", f.body[0]);
+        };
+        assert_eq!(cases[0].tests.len(), 2);
+        assert!(default.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tagless_switch_and_fallthrough() -> Result<(), ReadError> {
+        let ir = read_go("package main\n\nfunc f() {\n\tswitch {\n\tcase x > 1:\n\t\ta()\n\t}\n}")?;
+        let Stmt::Function(f) = &ir.body[0] else {
+            panic!("expected Function");
+        };
+        assert!(matches!(
+            &f.body[0],
+            Stmt::Switch { discriminant, .. } if *discriminant == Expr::bool(true)
+        ));
+
+        let err = read_go(
+            "package main\n\nfunc f() {\n\tswitch x {\n\tcase 1:\n\t\tfallthrough\n\tcase 2:\n\t}\n}",
+        );
+        assert!(matches!(err, Err(ReadError::Unsupported(_))));
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@
 use crate::ir::*;
 use crate::traits::{ReadError, Reader};
 
-use super::typescript::read_with_language;
+use super::typescript::{read_expr_with_language, read_with_language};
 
 /// Static instance of the JavaScript reader for registry.
 pub static JAVASCRIPT_READER: JavaScriptReader = JavaScriptReader;
@@ -30,6 +30,10 @@ impl Reader for JavaScriptReader {
     fn read(&self, source: &str) -> Result<Program, ReadError> {
         read_javascript(source)
     }
+
+    fn read_expr(&self, source: &str) -> Result<Expr, ReadError> {
+        read_javascript_expr(source)
+    }
 }
 
 /// Parse JavaScript source into surface-syntax IR.
@@ -40,6 +44,14 @@ pub fn read_javascript(source: &str) -> Result<Program, ReadError> {
     read_with_language(source, language)
 }
 
+/// Parse a single JavaScript expression into surface-syntax IR.
+pub fn read_javascript_expr(source: &str) -> Result<Expr, ReadError> {
+    let language = normalize_languages::parsers::grammar_loader()
+        .get("javascript")
+        .map_err(|e| ReadError::Parse(format!("load javascript grammar: {e}")))?;
+    read_expr_with_language(source, language)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,6 +22,10 @@ impl Reader for LuaReader {
     fn read(&self, source: &str) -> Result<Program, ReadError> {
         read_lua(source)
     }
+
+    fn read_expr(&self, source: &str) -> Result<Expr, ReadError> {
+        read_lua_expr(source)
+    }
 }
 
 /// Parse Lua source into surface-syntax IR.
@@ -42,6 +46,49 @@ pub fn read_lua(source: &str) -> Result<Program, ReadError> {
     ctx.read_program(&tree)
 }
 
+/// Parse a single Lua expression into surface-syntax IR.
+///
+/// A bare expression is not a Lua chunk, so the source is parsed as the
+/// value of a `return` statement.
+pub fn read_lua_expr(source: &str) -> Result<Expr, ReadError> {
+    let language = normalize_languages::parsers::grammar_loader()
+        .get("lua")
+        .map_err(|e| ReadError::Parse(format!("load lua grammar: {e}")))?;
+    let wrapped = format!("return {source}\n");
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language)
+        .map_err(|err| ReadError::Parse(err.to_string()))?;
+
+    let tree = parser
+        .parse(&wrapped, None)
+        .ok_or_else(|| ReadError::Parse("failed to parse".into()))?;
+    let root = tree.root_node();
+    if root.has_error() {
+        return Err(ReadError::Parse("syntax error in expression".into()));
+    }
+
+    fn named<'t>(node: Node<'t>) -> Vec<Node<'t>> {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .filter(|n| n.kind() != "comment")
+            .collect()
+    }
+    let values = match named(root).as_slice() {
+        [ret] if ret.kind() == "return_statement" => named(*ret)
+            .into_iter()
+            .find(|n| n.kind() == "expression_list")
+            .map(named),
+        _ => None,
+    };
+    let expr = match values.as_deref() {
+        Some([expr]) => *expr,
+        _ => return Err(ReadError::Parse("expected a single expression".into())),
+    };
+
+    ReadContext::new(&wrapped).read_expr(expr)
+}
+
 struct ReadContext<'a> {
     source: &'a str,
 }
@@ -1401,4 +1448,18 @@ end
         }
         Ok(())
     }
+
+    #[test]
+    fn test_read_expr() -> Result<(), ReadError> {
+        let expr = read_lua_expr("a ~= nil and b")?;
+        assert!(matches!(
+            expr,
+            Expr::Binary {
+                op: BinaryOp::And,
+                ..
+            }
+        ));
+        assert!(read_lua_expr("a, b").is_err());
+        Ok(())
+    }
 }
@@ -4,22 +4,28 @@
 pub mod typescript;
 
 #[cfg(feature = "read-typescript")]
-pub use typescript::{TYPESCRIPT_READER, TypeScriptReader, read_typescript};
+pub use typescript::{TYPESCRIPT_READER, TypeScriptReader, read_typescript, read_typescript_expr};
 
 #[cfg(feature = "read-javascript")]
 pub mod javascript;
 
 #[cfg(feature = "read-javascript")]
-pub use javascript::{JAVASCRIPT_READER, JavaScriptReader, read_javascript};
+pub use javascript::{JAVASCRIPT_READER, JavaScriptReader, read_javascript, read_javascript_expr};
 
 #[cfg(feature = "read-lua")]
 pub mod lua;
 
 #[cfg(feature = "read-lua")]
-pub use lua::{LUA_READER, LuaReader, read_lua};
+pub use lua::{LUA_READER, LuaReader, read_lua, read_lua_expr};
 
 #[cfg(feature = "read-python")]
 pub mod python;
 
 #[cfg(feature = "read-python")]
 pub use python::{PYTHON_READER, PythonReader, read_python};
+
+#[cfg(feature = "read-go")]
+pub mod go;
+
+#[cfg(feature = "read-go")]
+pub use go::{GO_READER, GoReader, read_go};
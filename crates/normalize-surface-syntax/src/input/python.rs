@@ -112,8 +112,9 @@ impl<'a> ReadContext<'a> {
             // Class definitions — parse into first-class IR nodes
             "class_definition" => self.read_class_definition(node).map(Some),
 
-            // Try/except (skip for now)
-            "try_statement" => Ok(None),
+            // Try/except
+            "try_statement" => self.read_try_statement(node).map(Some),
+            "match_statement" => self.read_match_statement(node).map(Some),
 
             // With (skip for now)
             "with_statement" => Ok(None),
@@ -309,6 +310,139 @@ impl<'a> ReadContext<'a> {
         Ok(Stmt::if_stmt(test, consequent, alternate))
     }
 
+    /// `try`/`except`/`finally`. The IR catch clause is untyped, so only a
+    /// single `except` is accepted and its exception type is dropped.
+    fn read_try_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let body = node
+            .child_by_field_name("body")
+            .ok_or_else(|| ReadError::Parse("try missing body".into()))?;
+        let body = self.read_block(body)?;
+
+        let mut catch_param = None;
+        let mut catch_body = None;
+        let mut finally_body = None;
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "except_clause" | "except_group_clause" => {
+                    if catch_body.is_some() {
+                        return Err(ReadError::Unsupported("multiple except clauses".into()));
+                    }
+                    let mut inner = child.walk();
+                    for part in child.named_children(&mut inner) {
+                        match part.kind() {
+                            "block" => catch_body = Some(self.read_block(part)?),
+                            "as_pattern" => {
+                                catch_param = part
+                                    .child_by_field_name("alias")
+                                    .map(|alias| self.node_text(alias).to_string());
+                            }
+                            _ => {}
+                        }
+                    }
+                    if catch_body.is_none() {
+                        catch_body = Some(Stmt::block(vec![]));
+                    }
+                }
+                "else_clause" => {
+                    return Err(ReadError::Unsupported("try/else".into()));
+                }
+                "finally_clause" => {
+                    let mut inner = child.walk();
+                    let block = child
+                        .named_children(&mut inner)
+                        .find(|c| c.kind() == "block");
+                    finally_body = Some(match block {
+                        Some(block) => self.read_block(block)?,
+                        None => Stmt::block(vec![]),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Stmt::try_catch(body, catch_param, catch_body, finally_body))
+    }
+
+    /// `match` over literal and dotted-constant patterns. Capture, class,
+    /// sequence and mapping patterns as well as guards are unsupported.
+    fn read_match_statement(&self, node: Node) -> Result<Stmt, ReadError> {
+        let subject = node
+            .child_by_field_name("subject")
+            .ok_or_else(|| ReadError::Parse("match missing subject".into()))?;
+        let discriminant = self.read_expr(subject)?;
+        let body = node
+            .child_by_field_name("body")
+            .ok_or_else(|| ReadError::Parse("match missing body".into()))?;
+
+        let mut cases = Vec::new();
+        let mut default = None;
+        let mut cursor = body.walk();
+        for clause in body.named_children(&mut cursor) {
+            if clause.kind() != "case_clause" {
+                continue;
+            }
+            if clause.child_by_field_name("guard").is_some() {
+                return Err(ReadError::Unsupported("match guard".into()));
+            }
+            let stmts = match clause.child_by_field_name("consequence") {
+                Some(block) => self.read_block_stmts(block)?,
+                None => Vec::new(),
+            };
+
+            let mut tests = Vec::new();
+            let mut wildcard = false;
+            let mut inner = clause.walk();
+            for pattern in clause.named_children(&mut inner) {
+                if pattern.kind() != "case_pattern" {
+                    continue;
+                }
+                match pattern.named_child(0) {
+                    // `case _:` parses as an empty pattern
+                    None => wildcard = true,
+                    Some(p) if p.kind() == "union_pattern" => {
+                        let mut alts = p.walk();
+                        for alt in p.named_children(&mut alts) {
+                            tests.push(self.read_case_pattern(alt)?);
+                        }
+                    }
+                    Some(p) => tests.push(self.read_case_pattern(p)?),
+                }
+            }
+
+            if wildcard {
+                default = Some(stmts);
+                // Later arms are unreachable after a wildcard.
+                break;
+            }
+            cases.push(SwitchCase::new(tests, stmts));
+        }
+
+        Ok(Stmt::switch(discriminant, cases, default))
+    }
+
+    fn read_case_pattern(&self, node: Node) -> Result<Expr, ReadError> {
+        match node.kind() {
+            "integer" | "float" | "string" | "concatenated_string" | "true" | "false" | "none" => {
+                self.read_expr(node)
+            }
+            // A bare name is a capture pattern; only dotted names are constants.
+            "dotted_name" if node.named_child_count() > 1 => {
+                let mut cursor = node.walk();
+                let mut parts = node.named_children(&mut cursor);
+                let first = parts
+                    .next()
+                    .ok_or_else(|| ReadError::Parse("empty dotted name".into()))?;
+                let mut expr = Expr::ident(self.node_text(first));
+                for part in parts {
+                    expr = Expr::member(expr, self.node_text(part));
+                }
+                Ok(expr)
+            }
+            other => Err(ReadError::Unsupported(format!("match pattern {}", other))),
+        }
+    }
+
     fn read_elif_clause(&self, node: Node) -> Result<Stmt, ReadError> {
         let condition = node
             .child_by_field_name("condition")
@@ -1307,4 +1441,53 @@ mod tests {
         assert_eq!(out.trim(), "x, y = arr");
         Ok(())
     }
+
+    #[test]
+    fn test_try_except_finally() -> Result<(), ReadError> {
+        let src = "try:\n    a()\nexcept ValueError as e:\n    b(e)\nfinally:\n    c()\n";
+        let program = read_python(src)?;
+        match &program.body[0] {
+            Stmt::TryCatch {
+                catch_param,
+                catch_body,
+                finally_body,
+                ..
+            } => {
+                assert_eq!(catch_param.as_deref(), Some("e"));
+                assert!(catch_body.is_some());
+                assert!(finally_body.is_some());
+            }
+            other => panic!("expected TryCatch, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiple_except_unsupported() {
+        let src = "try:\n    a()\nexcept A:\n    b()\nexcept B:\n    c()\n";
+        assert!(matches!(read_python(src), Err(ReadError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_match_literal_patterns() -> Result<(), ReadError> {
+        let src = "match x:\n    case 1 | 2:\n        a()\n    case Color.RED:\n        b()\n    case _:\n        c()\n";
+        let program = read_python(src)?;
+        let Stmt::Switch { cases, default, .. } = &program.body[0] else {
+            panic!("expected Switch, got {:?}", program.body[0]);
+        };
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].tests, vec![Expr::number(1), Expr::number(2)]);
+        assert_eq!(
+            cases[1].tests,
+            vec![Expr::member(Expr::ident("Color"), "RED")]
+        );
+        assert!(default.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_capture_unsupported() {
+        let src = "match x:\n    case y:\n        a()\n";
+        assert!(matches!(read_python(src), Err(ReadError::Unsupported(_))));
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::ir::{
     BinaryOp, ExportName, Expr, Function, ImportName, Method, Param, Pat, PatField, Program, Span,
-    Stmt, TemplatePart, UnaryOp,
+    Stmt, SwitchCase, TemplatePart, UnaryOp,
 };
 use crate::traits::{ReadError, Reader};
 use tree_sitter::{Node, Parser, Tree};
@@ -25,6 +25,10 @@ impl Reader for TypeScriptReader {
     fn read(&self, source: &str) -> Result<Program, ReadError> {
         read_typescript(source)
     }
+
+    fn read_expr(&self, source: &str) -> Result<Expr, ReadError> {
+        read_typescript_expr(source)
+    }
 }
 
 /// Parse TypeScript source into surface-syntax IR.
@@ -35,6 +39,51 @@ pub fn read_typescript(source: &str) -> Result<Program, ReadError> {
     read_with_language(source, language)
 }
 
+/// Parse a single TypeScript expression into surface-syntax IR.
+pub fn read_typescript_expr(source: &str) -> Result<Expr, ReadError> {
+    let language = normalize_languages::parsers::grammar_loader()
+        .get("typescript")
+        .map_err(|e| ReadError::Parse(format!("load typescript grammar: {e}")))?;
+    read_expr_with_language(source, language)
+}
+
+/// Parse a single expression using the given tree-sitter language.
+///
+/// The source is parsed inside parentheses so that `{ a: 1 }` is an object
+/// literal rather than a block; the newline before `)` keeps a trailing line
+/// comment from swallowing it.
+pub(crate) fn read_expr_with_language(
+    source: &str,
+    language: tree_sitter::Language,
+) -> Result<Expr, ReadError> {
+    let wrapped = format!("({source}\n)");
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language)
+        .map_err(|err| ReadError::Parse(err.to_string()))?;
+
+    let tree = parser
+        .parse(&wrapped, None)
+        .ok_or_else(|| ReadError::Parse("failed to parse".into()))?;
+    let root = tree.root_node();
+    if root.has_error() {
+        return Err(ReadError::Parse("syntax error in expression".into()));
+    }
+
+    let mut cursor = root.walk();
+    let statements: Vec<Node> = root
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() != "comment")
+        .collect();
+    let expr = match statements.as_slice() {
+        [stmt] if stmt.kind() == "expression_statement" => stmt.named_child(0),
+        _ => None,
+    }
+    .ok_or_else(|| ReadError::Parse("expected a single expression".into()))?;
+
+    ReadContext::new(&wrapped).read_expr(expr)
+}
+
 /// Parse source into surface-syntax IR using the given tree-sitter language.
 /// Used by language readers that share TypeScript's node-type grammar (e.g. JavaScript).
 pub(crate) fn read_with_language(
@@ -89,6 +138,17 @@ impl<'a> ReadContext<'a> {
     }
 
     fn read_stmt(&self, node: Node) -> Result<Option<Stmt>, ReadError> {
+        let stmt = self.read_stmt_kind(node)?;
+        Ok(stmt.map(|stmt| {
+            if stmt.span().is_some() {
+                stmt
+            } else {
+                stmt.with_span(Span::from_ts(node.start_position(), node.end_position()))
+            }
+        }))
+    }
+
+    fn read_stmt_kind(&self, node: Node) -> Result<Option<Stmt>, ReadError> {
         match node.kind() {
             // Empty statements (skip)
             "empty_statement" => Ok(None),
@@ -156,7 +216,17 @@ impl<'a> ReadContext<'a> {
         }
     }
 
+    /// Read an expression, recording its span unless a nested node (e.g. the
+    /// inside of parentheses) already supplied a tighter one.
     fn read_expr(&self, node: Node) -> Result<Expr, ReadError> {
+        let expr = self.read_expr_kind(node)?;
+        if expr.span().is_some() {
+            return Ok(expr);
+        }
+        Ok(expr.with_span(Span::from_ts(node.start_position(), node.end_position())))
+    }
+
+    fn read_expr_kind(&self, node: Node) -> Result<Expr, ReadError> {
         match node.kind() {
             // Literals
             "number" => self.read_number(node),
@@ -1129,87 +1199,66 @@ impl<'a> ReadContext<'a> {
             .child_by_field_name("body")
             .ok_or_else(|| ReadError::Parse("switch_statement missing body".into()))?;
 
-        let value_expr = self.read_expr(value)?;
+        let discriminant = self.read_expr(value)?;
 
-        // Collect cases and default
-        let mut cases: Vec<(Expr, Vec<Stmt>)> = Vec::new();
-        let mut default_body: Vec<Stmt> = Vec::new();
+        let mut cases: Vec<SwitchCase> = Vec::new();
+        let mut default: Option<Vec<Stmt>> = None;
+        // Tests of `case a:` labels with no body, which fall into the next arm
+        let mut pending_tests: Vec<Expr> = Vec::new();
 
         let mut cursor = body.walk();
         for child in body.children(&mut cursor) {
             match child.kind() {
                 "switch_case" => {
                     if let Some(case_value) = child.child_by_field_name("value") {
-                        let case_expr = self.read_expr(case_value)?;
-                        let mut body_stmts = Vec::new();
-
-                        let mut inner_cursor = child.walk();
-                        let mut past_colon = false;
-                        for inner_child in child.children(&mut inner_cursor) {
-                            if inner_child.kind() == ":" {
-                                past_colon = true;
-                                continue;
-                            }
-                            if past_colon
-                                && inner_child.is_named()
-                                && inner_child.kind() != "break_statement"
-                                && let Some(stmt) = self.read_stmt(inner_child)?
-                            {
-                                body_stmts.push(stmt);
-                            }
+                        pending_tests.push(self.read_expr(case_value)?);
+                        let body_stmts = self.read_switch_arm(child)?;
+                        if !body_stmts.is_empty() {
+                            cases.push(SwitchCase::new(
+                                std::mem::take(&mut pending_tests),
+                                body_stmts,
+                            ));
                         }
-
-                        cases.push((case_expr, body_stmts));
                     }
                 }
                 "switch_default" => {
-                    let mut inner_cursor = child.walk();
-                    let mut past_colon = false;
-                    for inner_child in child.children(&mut inner_cursor) {
-                        if inner_child.kind() == ":" {
-                            past_colon = true;
-                            continue;
-                        }
-                        if past_colon
-                            && inner_child.is_named()
-                            && inner_child.kind() != "break_statement"
-                            && let Some(stmt) = self.read_stmt(inner_child)?
-                        {
-                            default_body.push(stmt);
-                        }
-                    }
+                    // `case a: default: ...` — `a` only reaches the default arm
+                    pending_tests.clear();
+                    default = Some(self.read_switch_arm(child)?);
                 }
                 _ => {}
             }
         }
+        // Trailing `case a:` with no body does nothing
+        if !pending_tests.is_empty() {
+            cases.push(SwitchCase::new(pending_tests, Vec::new()));
+        }
 
-        // Build nested if-else from cases (reverse order to build from inside out)
-        let default_stmt = if default_body.len() == 1 {
-            default_body.remove(0)
-        } else if default_body.is_empty() {
-            Stmt::block(vec![])
-        } else {
-            Stmt::block(default_body)
-        };
-
-        let result = cases.into_iter().rev().fold(
-            default_stmt,
-            |else_branch, (case_val, mut body_stmts)| {
-                let body_stmt = if body_stmts.len() == 1 {
-                    body_stmts.remove(0)
-                } else if body_stmts.is_empty() {
-                    Stmt::block(vec![])
-                } else {
-                    Stmt::block(body_stmts)
-                };
-
-                let condition = Expr::binary(value_expr.clone(), BinaryOp::Eq, case_val);
-
-                Stmt::if_stmt(condition, body_stmt, Some(else_branch))
-            },
-        );
+        let span = Span::from_ts(node.start_position(), node.end_position());
+        Ok(Stmt::switch(discriminant, cases, default).with_span(span))
+    }
 
-        Ok(result)
+    /// Statements after a `case x:` / `default:` label, minus the trailing `break`.
+    fn read_switch_arm(&self, node: Node) -> Result<Vec<Stmt>, ReadError> {
+        let mut stmts = Vec::new();
+        let mut cursor = node.walk();
+        let mut past_colon = false;
+        for child in node.children(&mut cursor) {
+            if child.kind() == ":" {
+                past_colon = true;
+                continue;
+            }
+            if past_colon
+                && child.is_named()
+                && let Some(stmt) = self.read_stmt(child)?
+            {
+                stmts.push(stmt);
+            }
+        }
+        if matches!(stmts.last(), Some(Stmt::Break)) {
+            stmts.pop();
+        }
+        Ok(stmts)
     }
 
     fn read_try_statement(&self, node: Node) -> Result<Stmt, ReadError> {
@@ -1644,6 +1693,21 @@ impl<'a> ReadContext<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_spans_on_statements_and_expressions() -> Result<(), ReadError> {
+        let program = read_typescript("go();\nfunction f() {\n  return a + b;\n}")?;
+        let call = program.body[0].span().expect("expression statement span");
+        assert_eq!((call.start_line, call.end_col), (1, 4));
+        let func = program.body[1].span().expect("function span");
+        assert_eq!((func.start_line, func.end_line), (2, 4));
+        let Stmt::Function(f) = &program.body[1] else {
+            panic!("expected function, got {:?}", program.body[1]);
+        };
+        let ret = f.body[0].span().expect("return expression span");
+        assert_eq!((ret.start_line, ret.start_col), (3, 9));
+        Ok(())
+    }
+
     #[test]
     fn test_simple_let() -> Result<(), ReadError> {
         let program = read_typescript("let x = 42;")?;
@@ -2142,4 +2206,39 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_switch_stacked_cases() -> Result<(), ReadError> {
+        let src = "switch (x) { case 1: case 2: a(); break; case 3: return b; default: c(); }";
+        let program = read_typescript(src)?;
+        let Stmt::Switch { cases, default, .. } = &program.body[0] else {
+            panic!("expected Switch, got {:?}", program.body[0]);
+        };
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].tests.len(), 2);
+        // The trailing `break` is implied by the IR
+        assert_eq!(cases[0].body.len(), 1);
+        assert!(matches!(cases[1].body[0], Stmt::Return(_)));
+        assert_eq!(default.as_ref().map(Vec::len), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_expr() -> Result<(), ReadError> {
+        let expr = read_typescript_expr("{ a: 1 } // trailing")?;
+        assert_eq!(expr, Expr::object(vec![("a".into(), Expr::number(1))]));
+
+        let expr = read_typescript_expr("x > 1 && ok")?;
+        assert!(matches!(
+            expr,
+            Expr::Binary {
+                op: BinaryOp::And,
+                ..
+            }
+        ));
+
+        assert!(read_typescript_expr("a); (b").is_err());
+        assert!(read_typescript_expr("let x = 1").is_err());
+        Ok(())
+    }
 }
@@ -232,6 +232,32 @@ impl Expr {
         Expr::TemplateLiteral(parts)
     }
 
+    /// Whether evaluating this expression more than once is harmless: a
+    /// literal, an identifier, or a non-computed member chain over one.
+    pub fn is_pure_operand(&self) -> bool {
+        match self {
+            Expr::Literal(_) | Expr::Ident(_) => true,
+            Expr::Member {
+                object, computed, ..
+            } => !computed && object.is_pure_operand(),
+            _ => false,
+        }
+    }
+
+    /// Source location, if a reader recorded one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Expr::Binary { span, .. }
+            | Expr::Unary { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::Member { span, .. }
+            | Expr::Conditional { span, .. }
+            | Expr::Assign { span, .. } => *span,
+            Expr::Function(f) => f.span,
+            _ => None,
+        }
+    }
+
     /// Attach a source location span to this expression.
     pub fn with_span(self, span: Span) -> Self {
         match self {
@@ -280,6 +306,10 @@ impl Expr {
                 value,
                 span: Some(span),
             },
+            Expr::Function(mut f) => {
+                f.span = Some(span);
+                Expr::Function(f)
+            }
             other => other,
         }
     }
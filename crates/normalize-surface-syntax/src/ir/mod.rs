@@ -10,19 +10,23 @@ mod expr;
 mod pat;
 mod stmt;
 mod structure_eq;
+mod validate;
 
 pub use expr::*;
 pub use pat::*;
 pub use stmt::*;
 pub use structure_eq::StructureEq;
+pub use validate::{Construct, IrDiagnostic, IrDiagnosticKind, validate, validate_for};
 
 use serde::{Deserialize, Serialize};
 
 /// Source location span (1-based lines, 0-based columns).
 ///
 /// Used for error messages ("expected foo at line 5:12") and debugging
-/// round-trips. Writers ignore spans in their output — they are read-only
-/// metadata populated by input readers.
+/// round-trips. Spans are read-only metadata populated by input readers;
+/// writers never emit them into source, but
+/// [`Writer::write_with_source_map`](crate::Writer::write_with_source_map)
+/// reports which output line each spanned statement landed on.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Span {
     pub start_line: u32,
@@ -113,6 +117,9 @@ pub struct Function {
     pub return_type: Option<String>,
     /// Function body.
     pub body: Vec<Stmt>,
+    /// Source location (populated by readers; ignored by writers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
 impl Function {
@@ -122,6 +129,7 @@ impl Function {
             params,
             return_type: None,
             body,
+            span: None,
         }
     }
 
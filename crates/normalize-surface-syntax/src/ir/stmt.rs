@@ -109,6 +109,49 @@ impl Method {
     }
 }
 
+/// One arm of a [`Stmt::Switch`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwitchCase {
+    /// Values compared against the discriminant; the arm runs if any is equal.
+    /// Several tests come from stacked `case a: case b:` labels.
+    pub tests: Vec<Expr>,
+    /// Arm body, without the trailing `break`.
+    pub body: Vec<Stmt>,
+}
+
+impl SwitchCase {
+    pub fn new(tests: Vec<Expr>, body: Vec<Stmt>) -> Self {
+        Self { tests, body }
+    }
+}
+
+/// The if/else-if chain equivalent to switching on `subject`, for writers
+/// without a native switch. Each arm tests `subject == test` joined with `or`,
+/// so `subject` is evaluated once per test; bind a discriminant with side
+/// effects to a temporary first (see [`Expr::is_pure_operand`]).
+///
+/// `None` when there is nothing to run (no cases and no default).
+pub fn switch_to_if_chain(
+    subject: &Expr,
+    cases: &[SwitchCase],
+    default: Option<&[Stmt]>,
+) -> Option<Stmt> {
+    let default = default.map(|body| Stmt::block(body.to_vec()));
+    cases.iter().rev().fold(default, |else_branch, case| {
+        let test = case
+            .tests
+            .iter()
+            .map(|t| Expr::binary(subject.clone(), super::BinaryOp::Eq, t.clone()))
+            .reduce(|acc, t| Expr::binary(acc, super::BinaryOp::Or, t))
+            .unwrap_or_else(|| Expr::bool(false));
+        Some(Stmt::if_stmt(
+            test,
+            Stmt::block(case.body.clone()),
+            else_branch,
+        ))
+    })
+}
+
 /// A statement (doesn't produce a value directly).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Stmt {
@@ -180,6 +223,21 @@ pub enum Stmt {
     /// Continue statement.
     Continue,
 
+    /// Switch statement: `switch (discriminant) { case a: ... default: ... }`.
+    ///
+    /// Arms never fall through. Writers without a native equivalent lower it
+    /// to an if/else-if chain comparing the discriminant with `==`.
+    Switch {
+        discriminant: Expr,
+        cases: Vec<SwitchCase>,
+        /// The `default` arm, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<Vec<Stmt>>,
+        /// Source location (populated by readers; ignored by writers).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        span: Option<Span>,
+    },
+
     /// Try/catch/finally statement.
     TryCatch {
         body: Box<Stmt>,
@@ -367,6 +425,15 @@ impl Stmt {
         }
     }
 
+    pub fn switch(discriminant: Expr, cases: Vec<SwitchCase>, default: Option<Vec<Stmt>>) -> Self {
+        Stmt::Switch {
+            discriminant,
+            cases,
+            default,
+            span: None,
+        }
+    }
+
     pub fn function(f: crate::Function) -> Self {
         Stmt::Function(f)
     }
@@ -427,9 +494,41 @@ impl Stmt {
         }
     }
 
+    /// Source location, if a reader recorded one.
+    ///
+    /// Expression statements and `return expr` report their expression's span;
+    /// blocks, `break` and `continue` carry none.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Stmt::Let { span, .. }
+            | Stmt::If { span, .. }
+            | Stmt::While { span, .. }
+            | Stmt::For { span, .. }
+            | Stmt::ForIn { span, .. }
+            | Stmt::Switch { span, .. }
+            | Stmt::TryCatch { span, .. }
+            | Stmt::Import { span, .. }
+            | Stmt::Export { span, .. }
+            | Stmt::Class { span, .. }
+            | Stmt::Destructure { span, .. }
+            | Stmt::Comment { span, .. } => *span,
+            Stmt::Expr(e) | Stmt::Return(Some(e)) => e.span(),
+            Stmt::Function(f) => f.span,
+            Stmt::Return(None) | Stmt::Break | Stmt::Continue | Stmt::Block(_) => None,
+        }
+    }
+
     /// Attach a source location span to this statement.
+    ///
+    /// Expression statements pass the span to their expression; `return`,
+    /// `break`, `continue` and blocks are returned unchanged.
     pub fn with_span(self, span: Span) -> Self {
         match self {
+            Stmt::Expr(e) => Stmt::Expr(e.with_span(span)),
+            Stmt::Function(mut f) => {
+                f.span = Some(span);
+                Stmt::Function(f)
+            }
             Stmt::Let {
                 name,
                 init,
@@ -483,6 +582,17 @@ impl Stmt {
                 body,
                 span: Some(span),
             },
+            Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                ..
+            } => Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                span: Some(span),
+            },
             Stmt::TryCatch {
                 body,
                 catch_param,
@@ -188,6 +188,32 @@ impl StructureEq for Stmt {
                     }
             }
 
+            (
+                Stmt::Switch {
+                    discriminant: d1,
+                    cases: c1,
+                    default: df1,
+                    span: _,
+                },
+                Stmt::Switch {
+                    discriminant: d2,
+                    cases: c2,
+                    default: df2,
+                    span: _,
+                },
+            ) => {
+                d1.structure_eq(d2)
+                    && c1.len() == c2.len()
+                    && c1.iter().zip(c2).all(|(a, b)| {
+                        vec_structure_eq(&a.tests, &b.tests) && vec_structure_eq(&a.body, &b.body)
+                    })
+                    && match (df1, df2) {
+                        (None, None) => true,
+                        (Some(a), Some(b)) => vec_structure_eq(a, b),
+                        _ => false,
+                    }
+            }
+
             (Stmt::Function(a), Stmt::Function(b)) => a.structure_eq(b),
 
             // Comments: compare text and block flag; ignore span
@@ -453,6 +479,7 @@ fn option_structure_eq<T: StructureEq>(a: Option<&T>, b: Option<&T>) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ir::SwitchCase;
 
     #[test]
     fn test_mutable_is_ignored() {
@@ -525,4 +552,17 @@ mod tests {
 
         assert!(p1.structure_eq(&p2));
     }
+
+    #[test]
+    fn test_switch_compares_case_tests() {
+        let make = |test: f64| {
+            Stmt::switch(
+                Expr::ident("x"),
+                vec![SwitchCase::new(vec![Expr::number(test)], vec![])],
+                None,
+            )
+        };
+        assert!(make(1.0).structure_eq(&make(1.0)));
+        assert!(!make(1.0).structure_eq(&make(2.0)));
+    }
 }
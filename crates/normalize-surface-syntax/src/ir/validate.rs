@@ -0,0 +1,624 @@
+//! Validation pass over a [`Program`].
+//!
+//! Writers emit whatever they are given, so IR that a target language cannot
+//! express comes out as silently wrong source. [`validate`] reports structural
+//! problems in the IR itself; [`validate_for`] additionally reports the
+//! constructs a particular [`Writer`] cannot express (see [`Writer::supports`]).
+
+use super::{Expr, Function, Pat, Program, Span, Stmt, TemplatePart};
+use crate::traits::Writer;
+use std::collections::HashSet;
+use std::fmt;
+
+/// An IR construct that not every writer can express faithfully.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Construct {
+    /// `continue` inside a loop.
+    Continue,
+    /// `export` statement.
+    Export,
+    /// Object destructuring pattern: `const { a, b } = obj`.
+    ObjectPattern,
+    /// Anonymous function whose body is more than a single `return`.
+    MultiStatementLambda,
+}
+
+impl Construct {
+    fn describe(self) -> &'static str {
+        match self {
+            Construct::Continue => "`continue`",
+            Construct::Export => "`export`",
+            Construct::ObjectPattern => "object destructuring",
+            Construct::MultiStatementLambda => "multi-statement anonymous function",
+        }
+    }
+}
+
+/// What an [`IrDiagnostic`] is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IrDiagnosticKind {
+    /// A name is read but never bound in any enclosing scope.
+    UndefinedVariable(String),
+    /// Two function declarations with the same name in one statement list.
+    DuplicateFunction(String),
+    /// Statements following a `return`, `break` or `continue`.
+    Unreachable,
+    /// A construct the target writer cannot express.
+    Unsupported {
+        construct: Construct,
+        language: &'static str,
+    },
+}
+
+/// A problem found by [`validate`] or [`validate_for`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IrDiagnostic {
+    pub kind: IrDiagnosticKind,
+    /// Span of the innermost enclosing statement that carries one.
+    pub span: Option<Span>,
+}
+
+impl fmt::Display for IrDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(span) = self.span {
+            write!(f, "{}:{}: ", span.start_line, span.start_col)?;
+        }
+        match &self.kind {
+            IrDiagnosticKind::UndefinedVariable(name) => write!(f, "undefined variable `{name}`"),
+            IrDiagnosticKind::DuplicateFunction(name) => {
+                write!(f, "function `{name}` is declared more than once")
+            }
+            IrDiagnosticKind::Unreachable => write!(f, "unreachable statement"),
+            IrDiagnosticKind::Unsupported {
+                construct,
+                language,
+            } => write!(
+                f,
+                "{} cannot be expressed in {language}",
+                construct.describe()
+            ),
+        }
+    }
+}
+
+/// Names always in scope: host-provided values readers leave as identifiers.
+const IMPLICIT_NAMES: &[&str] = &["this", "super", "arguments", "undefined", "NaN", "Infinity"];
+
+/// Check a program for undefined variables, duplicate function declarations
+/// and unreachable statements.
+///
+/// Bindings are function-scoped: a name bound anywhere in a function body
+/// (or the top level) counts as defined throughout it, so only names that are
+/// never bound are reported. Names used solely as call targets or as the
+/// object of a member access (`print(x)`, `console.log`) are assumed to be
+/// host globals and not reported.
+pub fn validate(program: &Program) -> Vec<IrDiagnostic> {
+    let mut validator = Validator::default();
+    validator.function_scope(&[], &program.body);
+    validator.diagnostics
+}
+
+/// [`validate`], plus an `Unsupported` diagnostic for each construct that
+/// `writer` reports it cannot express.
+pub fn validate_for(program: &Program, writer: &dyn Writer) -> Vec<IrDiagnostic> {
+    let mut validator = Validator {
+        writer: Some(writer),
+        ..Validator::default()
+    };
+    validator.function_scope(&[], &program.body);
+    validator.diagnostics
+}
+
+#[derive(Default)]
+struct Validator<'w> {
+    writer: Option<&'w dyn Writer>,
+    /// One set of bound names per enclosing function, innermost last.
+    scopes: Vec<HashSet<String>>,
+    /// Span of the innermost enclosing statement that has one.
+    span: Option<Span>,
+    diagnostics: Vec<IrDiagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, kind: IrDiagnosticKind) {
+        self.diagnostics.push(IrDiagnostic {
+            kind,
+            span: self.span,
+        });
+    }
+
+    fn check_construct(&mut self, construct: Construct) {
+        if let Some(writer) = self.writer
+            && !writer.supports(construct)
+        {
+            self.report(IrDiagnosticKind::Unsupported {
+                construct,
+                language: writer.language(),
+            });
+        }
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        IMPLICIT_NAMES.contains(&name) || self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    fn function_scope(&mut self, params: &[String], body: &[Stmt]) {
+        let mut scope: HashSet<String> = params.iter().cloned().collect();
+        for stmt in body {
+            collect_bindings(stmt, &mut scope);
+        }
+        self.scopes.push(scope);
+        self.stmts(body);
+        self.scopes.pop();
+    }
+
+    fn function(&mut self, func: &Function) {
+        let params: Vec<String> = func.params.iter().map(|p| p.name.clone()).collect();
+        let outer = self.span;
+        self.span = func.span.or(outer);
+        self.function_scope(&params, &func.body);
+        self.span = outer;
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        let mut functions = HashSet::new();
+        let mut terminated = false;
+        let mut reported_unreachable = false;
+        for stmt in stmts {
+            if matches!(stmt, Stmt::Comment { .. }) {
+                continue;
+            }
+            // One diagnostic for the first dead statement is enough.
+            if terminated && !reported_unreachable {
+                let outer = self.span;
+                self.span = stmt.span().or(outer);
+                self.report(IrDiagnosticKind::Unreachable);
+                self.span = outer;
+                reported_unreachable = true;
+            }
+            if let Stmt::Function(func) = stmt
+                && !func.name.is_empty()
+                && !functions.insert(func.name.as_str())
+            {
+                let outer = self.span;
+                self.span = func.span.or(outer);
+                self.report(IrDiagnosticKind::DuplicateFunction(func.name.clone()));
+                self.span = outer;
+            }
+            self.stmt(stmt);
+            if matches!(stmt, Stmt::Return(_) | Stmt::Break | Stmt::Continue) {
+                terminated = true;
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        let outer = self.span;
+        self.span = stmt.span().or(outer);
+        match stmt {
+            Stmt::Expr(expr) => self.expr(expr),
+            Stmt::Let { init, .. } => {
+                if let Some(init) = init {
+                    self.expr(init);
+                }
+            }
+            Stmt::Block(stmts) => self.stmts(stmts),
+            Stmt::If {
+                test,
+                consequent,
+                alternate,
+                ..
+            } => {
+                self.expr(test);
+                self.stmt(consequent);
+                if let Some(alternate) = alternate {
+                    self.stmt(alternate);
+                }
+            }
+            Stmt::While { test, body, .. } => {
+                self.expr(test);
+                self.stmt(body);
+            }
+            Stmt::For {
+                init,
+                test,
+                update,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.stmt(init);
+                }
+                if let Some(test) = test {
+                    self.expr(test);
+                }
+                if let Some(update) = update {
+                    self.expr(update);
+                }
+                self.stmt(body);
+            }
+            Stmt::ForIn { iterable, body, .. } => {
+                self.expr(iterable);
+                self.stmt(body);
+            }
+            Stmt::Return(expr) => {
+                if let Some(expr) = expr {
+                    self.expr(expr);
+                }
+            }
+            Stmt::Break => {}
+            Stmt::Continue => self.check_construct(Construct::Continue),
+            Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                ..
+            } => {
+                self.expr(discriminant);
+                for case in cases {
+                    for test in &case.tests {
+                        self.expr(test);
+                    }
+                    self.stmts(&case.body);
+                }
+                if let Some(default) = default {
+                    self.stmts(default);
+                }
+            }
+            Stmt::TryCatch {
+                body,
+                catch_body,
+                finally_body,
+                ..
+            } => {
+                self.stmt(body);
+                if let Some(catch_body) = catch_body {
+                    self.stmt(catch_body);
+                }
+                if let Some(finally_body) = finally_body {
+                    self.stmt(finally_body);
+                }
+            }
+            Stmt::Function(func) => self.function(func),
+            Stmt::Import { .. } | Stmt::Comment { .. } => {}
+            Stmt::Export { .. } => self.check_construct(Construct::Export),
+            Stmt::Class { methods, .. } => {
+                for method in methods {
+                    let params: Vec<String> =
+                        method.params.iter().map(|p| p.name.clone()).collect();
+                    self.function_scope(&params, &method.body);
+                }
+            }
+            Stmt::Destructure { pat, value, .. } => {
+                self.pat(pat);
+                self.expr(value);
+            }
+        }
+        self.span = outer;
+    }
+
+    fn pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(_) => {}
+            Pat::Object(fields) => {
+                self.check_construct(Construct::ObjectPattern);
+                for field in fields {
+                    self.pat(&field.pat);
+                    if let Some(default) = &field.default {
+                        self.expr(default);
+                    }
+                }
+            }
+            Pat::Array(elements, _) => {
+                for element in elements.iter().flatten() {
+                    self.pat(element);
+                }
+            }
+            Pat::Rest(inner) => self.pat(inner),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(_) => {}
+            Expr::Ident(name) => {
+                if !self.is_defined(name) {
+                    self.report(IrDiagnosticKind::UndefinedVariable(name.clone()));
+                }
+            }
+            Expr::Binary { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::Unary { expr, .. } => self.expr(expr),
+            Expr::Call { callee, args, .. } => {
+                // A bare callee may be a host global (`print`, `require`).
+                if !matches!(callee.as_ref(), Expr::Ident(_)) {
+                    self.expr(callee);
+                }
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Expr::Member {
+                object,
+                property,
+                computed,
+                ..
+            } => {
+                // A bare object may be a host namespace (`console`, `math`).
+                if !matches!(object.as_ref(), Expr::Ident(_)) {
+                    self.expr(object);
+                }
+                if *computed {
+                    self.expr(property);
+                }
+            }
+            Expr::Array(items) => {
+                for item in items {
+                    self.expr(item);
+                }
+            }
+            Expr::Object(pairs) => {
+                for (_, value) in pairs {
+                    self.expr(value);
+                }
+            }
+            Expr::Function(func) => {
+                if !matches!(func.body.as_slice(), [Stmt::Return(Some(_))]) {
+                    self.check_construct(Construct::MultiStatementLambda);
+                }
+                self.function(func);
+            }
+            Expr::Conditional {
+                test,
+                consequent,
+                alternate,
+                ..
+            } => {
+                self.expr(test);
+                self.expr(consequent);
+                self.expr(alternate);
+            }
+            Expr::Assign { target, value, .. } => {
+                // Assigning to a bare name binds it (collected up front).
+                if !matches!(target.as_ref(), Expr::Ident(_)) {
+                    self.expr(target);
+                }
+                self.expr(value);
+            }
+            Expr::TemplateLiteral(parts) => {
+                for part in parts {
+                    if let TemplatePart::Expr(expr) = part {
+                        self.expr(expr);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Add every name `stmt` binds in the current function scope, without
+/// descending into nested functions.
+fn collect_bindings(stmt: &Stmt, scope: &mut HashSet<String>) {
+    match stmt {
+        Stmt::Expr(expr) => collect_assigned(expr, scope),
+        Stmt::Let { name, .. } => {
+            scope.insert(name.clone());
+        }
+        Stmt::Block(stmts) => {
+            for stmt in stmts {
+                collect_bindings(stmt, scope);
+            }
+        }
+        Stmt::If {
+            consequent,
+            alternate,
+            ..
+        } => {
+            collect_bindings(consequent, scope);
+            if let Some(alternate) = alternate {
+                collect_bindings(alternate, scope);
+            }
+        }
+        Stmt::While { body, .. } => collect_bindings(body, scope),
+        Stmt::For {
+            init, update, body, ..
+        } => {
+            if let Some(init) = init {
+                collect_bindings(init, scope);
+            }
+            if let Some(update) = update {
+                collect_assigned(update, scope);
+            }
+            collect_bindings(body, scope);
+        }
+        Stmt::ForIn { variable, body, .. } => {
+            scope.insert(variable.clone());
+            collect_bindings(body, scope);
+        }
+        Stmt::Switch { cases, default, .. } => {
+            for stmt in cases
+                .iter()
+                .flat_map(|c| &c.body)
+                .chain(default.iter().flatten())
+            {
+                collect_bindings(stmt, scope);
+            }
+        }
+        Stmt::TryCatch {
+            body,
+            catch_param,
+            catch_body,
+            finally_body,
+            ..
+        } => {
+            collect_bindings(body, scope);
+            if let Some(param) = catch_param {
+                scope.insert(param.clone());
+            }
+            for stmt in [catch_body, finally_body].into_iter().flatten() {
+                collect_bindings(stmt, scope);
+            }
+        }
+        Stmt::Function(func) => {
+            if !func.name.is_empty() {
+                scope.insert(func.name.clone());
+            }
+        }
+        Stmt::Class { name, .. } => {
+            scope.insert(name.clone());
+        }
+        Stmt::Import { names, .. } => {
+            for import in names {
+                scope.insert(import.alias.clone().unwrap_or_else(|| import.name.clone()));
+            }
+        }
+        Stmt::Destructure { pat, .. } => collect_pat(pat, scope),
+        Stmt::Return(_)
+        | Stmt::Break
+        | Stmt::Continue
+        | Stmt::Export { .. }
+        | Stmt::Comment { .. } => {}
+    }
+}
+
+/// Names bound by assigning to a bare identifier (Lua globals, Python
+/// rebinding), including assignments nested in other expressions.
+fn collect_assigned(expr: &Expr, scope: &mut HashSet<String>) {
+    match expr {
+        Expr::Assign { target, value, .. } => {
+            if let Expr::Ident(name) = target.as_ref() {
+                scope.insert(name.clone());
+            }
+            collect_assigned(value, scope);
+        }
+        Expr::Binary { left, right, .. } => {
+            collect_assigned(left, scope);
+            collect_assigned(right, scope);
+        }
+        Expr::Conditional {
+            consequent,
+            alternate,
+            ..
+        } => {
+            collect_assigned(consequent, scope);
+            collect_assigned(alternate, scope);
+        }
+        _ => {}
+    }
+}
+
+fn collect_pat(pat: &Pat, scope: &mut HashSet<String>) {
+    match pat {
+        Pat::Ident(name) => {
+            scope.insert(name.clone());
+        }
+        Pat::Object(fields) => {
+            for field in fields {
+                collect_pat(&field.pat, scope);
+            }
+        }
+        Pat::Array(elements, rest) => {
+            for element in elements.iter().flatten() {
+                collect_pat(element, scope);
+            }
+            if let Some(rest) = rest {
+                scope.insert(rest.clone());
+            }
+        }
+        Pat::Rest(inner) => collect_pat(inner, scope),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(program: &Program) -> Vec<IrDiagnosticKind> {
+        validate(program).into_iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn test_undefined_variable() {
+        let program = Program::new(vec![
+            Stmt::let_decl("x", Some(Expr::number(1))),
+            Stmt::expr(Expr::call(
+                Expr::ident("print"),
+                vec![Expr::ident("x"), Expr::ident("y")],
+            )),
+        ]);
+        assert_eq!(
+            kinds(&program),
+            vec![IrDiagnosticKind::UndefinedVariable("y".into())]
+        );
+    }
+
+    #[test]
+    fn test_function_scoped_bindings() {
+        // `total` is bound inside the if but read after it, as Python allows;
+        // params and the function's own name are in scope in its body.
+        let program = Program::new(vec![Stmt::function(Function::new(
+            "sum",
+            vec!["n".into()],
+            vec![
+                Stmt::if_stmt(
+                    Expr::ident("n"),
+                    Stmt::block(vec![Stmt::let_decl("total", Some(Expr::number(0)))]),
+                    None,
+                ),
+                Stmt::return_stmt(Some(Expr::call(
+                    Expr::ident("sum"),
+                    vec![Expr::ident("total")],
+                ))),
+            ],
+        ))]);
+        assert!(validate(&program).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_function() {
+        let f = || Stmt::function(Function::new("f", vec![], vec![]));
+        let program = Program::new(vec![f(), f()]);
+        assert_eq!(
+            kinds(&program),
+            vec![IrDiagnosticKind::DuplicateFunction("f".into())]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_after_return() {
+        let program = Program::new(vec![Stmt::function(Function::new(
+            "f",
+            vec![],
+            vec![
+                Stmt::return_stmt(None),
+                Stmt::comment_line("ignored"),
+                Stmt::expr(Expr::call(Expr::ident("g"), vec![])),
+                Stmt::expr(Expr::call(Expr::ident("h"), vec![])),
+            ],
+        ))]);
+        assert_eq!(kinds(&program), vec![IrDiagnosticKind::Unreachable]);
+    }
+
+    #[cfg(feature = "write-lua")]
+    #[test]
+    fn test_unsupported_for_writer() {
+        use crate::output::LUA_WRITER;
+        let program = Program::new(vec![Stmt::while_loop(
+            Expr::bool(true),
+            Stmt::block(vec![Stmt::continue_stmt()]),
+        )]);
+        assert!(validate(&program).is_empty());
+        assert_eq!(
+            validate_for(&program, &LUA_WRITER)
+                .into_iter()
+                .map(|d| d.kind)
+                .collect::<Vec<_>>(),
+            vec![IrDiagnosticKind::Unsupported {
+                construct: Construct::Continue,
+                language: "lua",
+            }]
+        );
+    }
+}
@@ -44,6 +44,7 @@
 
 pub mod ir;
 pub mod registry;
+pub mod source_map;
 pub mod traits;
 
 #[cfg(feature = "sexpr")]
@@ -54,10 +55,14 @@ pub mod output;
 
 // Re-exports: IR types
 pub use ir::{
-    BinaryOp, ExportName, Expr, Function, ImportName, Literal, Method, Param, Pat, PatField,
-    Program, Span, Stmt, StructureEq, TemplatePart, UnaryOp,
+    BinaryOp, Construct, ExportName, Expr, Function, ImportName, IrDiagnostic, IrDiagnosticKind,
+    Literal, Method, Param, Pat, PatField, Program, Span, Stmt, StructureEq, SwitchCase,
+    TemplatePart, UnaryOp, validate, validate_for,
 };
 
+// Re-exports: Source maps
+pub use source_map::{LineMapping, SourceMap};
+
 // Re-exports: Traits
 pub use traits::{ReadError, Reader, Writer};
 
@@ -78,6 +83,22 @@ pub use input::typescript::TypeScriptReader;
 pub use output::LuaWriter;
 #[cfg(feature = "write-lua")]
 pub use output::lua::LuaWriterImpl;
+#[cfg(feature = "write-typescript")]
+pub use output::{TypeScriptWriter, TypeScriptWriterImpl, TypeScriptWriterOptions};
 
 #[cfg(feature = "sexpr")]
-pub use sexpr::{SExprError, from_sexpr, to_sexpr};
+pub use sexpr::{SExpr, SExprError, from_sexpr, to_sexpr};
+
+/// Translate a single expression between languages, e.g. a condition stored
+/// in config: `translate_expr("typescript", "lua", "a !== null && b")`.
+///
+/// Uses the readers and writers in the [`registry`]; a language without one
+/// is reported as [`ReadError::Unsupported`].
+pub fn translate_expr(src_lang: &str, dst_lang: &str, text: &str) -> Result<String, ReadError> {
+    let reader = reader_for_language(src_lang)
+        .ok_or_else(|| ReadError::Unsupported(format!("no reader for {src_lang}")))?;
+    let writer = writer_for_language(dst_lang)
+        .ok_or_else(|| ReadError::Unsupported(format!("no writer for {dst_lang}")))?;
+    let expr = reader.read_expr(text)?;
+    Ok(writer.emit_expr(&expr))
+}
@@ -6,8 +6,9 @@
 //! `let`/`const`, etc.), so JavaScript output is just TypeScript output with a
 //! `.js` extension and "javascript" language label.
 
-use crate::ir::Program;
-use crate::output::typescript::TypeScriptWriter;
+use crate::ir::{Expr, Program};
+use crate::output::typescript::{TypeScriptWriter, TypeScriptWriterOptions};
+use crate::source_map::SourceMap;
 use crate::traits::Writer;
 
 /// Static instance of the JavaScript writer for registry.
@@ -28,4 +29,12 @@ impl Writer for JavaScriptWriterImpl {
     fn write(&self, program: &Program) -> String {
         TypeScriptWriter::emit(program)
     }
+
+    fn write_with_source_map(&self, program: &Program) -> (String, SourceMap) {
+        TypeScriptWriter::emit_with_source_map(program, TypeScriptWriterOptions::DEFAULT)
+    }
+
+    fn emit_expr(&self, expr: &Expr) -> String {
+        TypeScriptWriter::emit_expr(expr)
+    }
 }
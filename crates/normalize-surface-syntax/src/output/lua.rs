@@ -3,6 +3,7 @@
 //! Emits surface-syntax IR as Lua source code.
 
 use crate::ir::*;
+use crate::source_map::{SourceMap, SourceMapBuilder};
 use crate::traits::Writer;
 
 /// Static instance of the Lua writer for registry.
@@ -23,12 +24,30 @@ impl Writer for LuaWriterImpl {
     fn write(&self, program: &Program) -> String {
         LuaWriter::emit(program)
     }
+
+    fn write_with_source_map(&self, program: &Program) -> (String, SourceMap) {
+        LuaWriter::emit_with_source_map(program)
+    }
+
+    fn emit_expr(&self, expr: &Expr) -> String {
+        LuaWriter::emit_expr(expr)
+    }
+
+    fn supports(&self, construct: Construct) -> bool {
+        // `continue` and `export` become comments; object patterns unpack
+        // positionally instead of by key.
+        !matches!(
+            construct,
+            Construct::Continue | Construct::Export | Construct::ObjectPattern
+        )
+    }
 }
 
 /// Emits IR as Lua source code.
 pub struct LuaWriter {
     output: String,
     indent: usize,
+    source_map: SourceMapBuilder,
 }
 
 impl LuaWriter {
@@ -36,6 +55,7 @@ impl LuaWriter {
         Self {
             output: String::new(),
             indent: 0,
+            source_map: SourceMapBuilder::default(),
         }
     }
 
@@ -46,6 +66,21 @@ impl LuaWriter {
         writer.output
     }
 
+    /// Emit a single expression to Lua source.
+    pub fn emit_expr(expr: &Expr) -> String {
+        let mut writer = Self::new();
+        writer.write_expr(expr);
+        writer.output
+    }
+
+    /// Emit a program to Lua source, along with the output line each
+    /// spanned statement starts on.
+    pub fn emit_with_source_map(program: &Program) -> (String, SourceMap) {
+        let mut writer = Self::new();
+        writer.write_program(program);
+        (writer.output, writer.source_map.finish())
+    }
+
     fn write_program(&mut self, program: &Program) {
         for stmt in &program.body {
             self.write_stmt(stmt);
@@ -61,6 +96,12 @@ impl LuaWriter {
 
     fn write_stmt(&mut self, stmt: &Stmt) {
         self.write_indent();
+        self.write_stmt_no_indent(stmt);
+    }
+
+    /// Write a statement at the current position, without leading indent.
+    fn write_stmt_no_indent(&mut self, stmt: &Stmt) {
+        self.source_map.record(&self.output, stmt.span());
         match stmt {
             Stmt::Expr(expr) => {
                 self.write_expr(expr);
@@ -198,6 +239,30 @@ impl LuaWriter {
                     .push_str("-- continue (not supported in Lua 5.1)");
             }
 
+            Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                ..
+            } => {
+                // No switch in Lua: lower to an if chain. A discriminant with
+                // side effects is bound once, scoped by `do ... end`.
+                if discriminant.is_pure_operand() {
+                    if let Some(chain) = switch_to_if_chain(discriminant, cases, default.as_deref())
+                    {
+                        self.write_stmt_no_indent(&chain);
+                    }
+                } else {
+                    let mut stmts = vec![Stmt::let_decl("_switch", Some(discriminant.clone()))];
+                    stmts.extend(switch_to_if_chain(
+                        &Expr::ident("_switch"),
+                        cases,
+                        default.as_deref(),
+                    ));
+                    self.write_stmt_no_indent(&Stmt::block(stmts));
+                }
+            }
+
             Stmt::TryCatch {
                 body,
                 catch_param,
@@ -870,4 +935,56 @@ mod tests {
         let lua = LuaWriter::emit(&program);
         assert!(lua.contains("--[[block comment]]"), "got: {lua}");
     }
+
+    #[cfg(feature = "read-typescript")]
+    #[test]
+    fn test_source_map_from_typescript() {
+        use crate::input::read_typescript;
+        let src = "let total = 0;\nfor (const v of xs) {\n\n  total = total + v;\n}\n";
+        let program = read_typescript(src).expect("parse failed");
+        let (lua, map) = LuaWriter::emit_with_source_map(&program);
+        let line = lua
+            .lines()
+            .position(|l| l.contains("total = (total + v)"))
+            .expect("assignment emitted") as u32
+            + 1;
+        let span = map.span_for_line(line).expect("assignment mapped");
+        assert_eq!((span.start_line, span.start_col), (4, 2), "{lua}");
+    }
+
+    #[test]
+    fn test_switch_lowers_to_if_chain() {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::ident("x"),
+            vec![SwitchCase::new(
+                vec![Expr::number(1), Expr::number(2)],
+                vec![Stmt::expr(Expr::call(Expr::ident("a"), vec![]))],
+            )],
+            Some(vec![Stmt::expr(Expr::call(Expr::ident("b"), vec![]))]),
+        )]);
+        let lua = LuaWriter::emit(&program);
+        assert!(lua.contains("if ((x == 1) or (x == 2)) then"), "{lua}");
+        assert!(lua.contains("else"), "{lua}");
+    }
+
+    #[test]
+    fn test_switch_impure_discriminant_evaluated_once() {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::call(Expr::ident("f"), vec![]),
+            vec![SwitchCase::new(
+                vec![Expr::number(1)],
+                vec![Stmt::break_stmt()],
+            )],
+            None,
+        )]);
+        let lua = LuaWriter::emit(&program);
+        assert!(lua.contains("local _switch = f()"), "{lua}");
+        assert_eq!(lua.matches("f()").count(), 1, "{lua}");
+    }
+
+    #[test]
+    fn test_emit_expr() {
+        let expr = Expr::binary(Expr::ident("a"), BinaryOp::Ne, Expr::null());
+        assert_eq!(LuaWriter::emit_expr(&expr), "(a ~= nil)");
+    }
 }
@@ -10,7 +10,9 @@ pub use lua::{LUA_WRITER, LuaWriter, LuaWriterImpl};
 pub mod typescript;
 
 #[cfg(feature = "write-typescript")]
-pub use typescript::{TYPESCRIPT_WRITER, TypeScriptWriter, TypeScriptWriterImpl};
+pub use typescript::{
+    TYPESCRIPT_WRITER, TypeScriptWriter, TypeScriptWriterImpl, TypeScriptWriterOptions,
+};
 
 #[cfg(feature = "write-javascript")]
 pub mod javascript;
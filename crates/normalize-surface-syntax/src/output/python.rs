@@ -3,6 +3,7 @@
 //! Emits surface-syntax IR as Python source code.
 
 use crate::ir::*;
+use crate::source_map::{SourceMap, SourceMapBuilder};
 use crate::traits::Writer;
 use std::fmt::Write;
 
@@ -24,12 +25,30 @@ impl Writer for PythonWriterImpl {
     fn write(&self, program: &Program) -> String {
         PythonWriter::emit(program)
     }
+
+    fn write_with_source_map(&self, program: &Program) -> (String, SourceMap) {
+        PythonWriter::emit_with_source_map(program)
+    }
+
+    fn emit_expr(&self, expr: &Expr) -> String {
+        PythonWriter::emit_expr(expr)
+    }
+
+    fn supports(&self, construct: Construct) -> bool {
+        // `export` becomes a comment, multi-statement lambdas `None`, and
+        // object patterns unpack positionally instead of by key.
+        !matches!(
+            construct,
+            Construct::Export | Construct::MultiStatementLambda | Construct::ObjectPattern
+        )
+    }
 }
 
 /// Emits IR as Python source code.
 pub struct PythonWriter {
     output: String,
     indent: usize,
+    source_map: SourceMapBuilder,
 }
 
 impl PythonWriter {
@@ -37,6 +56,7 @@ impl PythonWriter {
         Self {
             output: String::new(),
             indent: 0,
+            source_map: SourceMapBuilder::default(),
         }
     }
 
@@ -47,6 +67,21 @@ impl PythonWriter {
         writer.output
     }
 
+    /// Emit a single expression to Python source.
+    pub fn emit_expr(expr: &Expr) -> String {
+        let mut writer = Self::new();
+        writer.write_expr(expr);
+        writer.output
+    }
+
+    /// Emit a program to Python source, along with the output line each
+    /// spanned statement starts on.
+    pub fn emit_with_source_map(program: &Program) -> (String, SourceMap) {
+        let mut writer = Self::new();
+        writer.write_program(program);
+        (writer.output, writer.source_map.finish())
+    }
+
     fn write_program(&mut self, program: &Program) {
         for stmt in &program.body {
             self.write_stmt(stmt);
@@ -62,6 +97,13 @@ impl PythonWriter {
 
     fn write_stmt(&mut self, stmt: &Stmt) {
         self.write_indent();
+        self.write_stmt_no_indent(stmt);
+    }
+
+    /// Write a statement at the current position, without leading indent
+    /// (after `el` for `elif`, or inside a lowered construct).
+    fn write_stmt_no_indent(&mut self, stmt: &Stmt) {
+        self.source_map.record(&self.output, stmt.span());
         match stmt {
             Stmt::Expr(expr) => {
                 self.write_expr(expr);
@@ -193,6 +235,39 @@ impl PythonWriter {
                 self.output.push_str("continue");
             }
 
+            Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                ..
+            } => {
+                // Lowered to an if/elif chain rather than `match`, whose bare
+                // names are capture patterns, not comparisons.
+                let subject = if discriminant.is_pure_operand() {
+                    discriminant.clone()
+                } else {
+                    self.output.push_str("_switch = ");
+                    self.write_expr(discriminant);
+                    self.output.push('\n');
+                    self.write_indent();
+                    Expr::ident("_switch")
+                };
+                match switch_to_if_chain(&subject, cases, default.as_deref()) {
+                    Some(Stmt::Block(stmts)) if !stmts.is_empty() => {
+                        // Only a default arm: its statements run unconditionally
+                        for (i, s) in stmts.iter().enumerate() {
+                            if i > 0 {
+                                self.output.push('\n');
+                                self.write_indent();
+                            }
+                            self.write_stmt_no_indent(s);
+                        }
+                    }
+                    Some(chain @ Stmt::If { .. }) => self.write_stmt_no_indent(&chain),
+                    _ => self.output.push_str("pass"),
+                }
+            }
+
             Stmt::TryCatch {
                 body,
                 catch_param,
@@ -384,39 +459,6 @@ impl PythonWriter {
         }
     }
 
-    fn write_stmt_no_indent(&mut self, stmt: &Stmt) {
-        // Write statement without leading indent (for elif)
-        match stmt {
-            Stmt::If {
-                test,
-                consequent,
-                alternate,
-                ..
-            } => {
-                self.output.push_str("if ");
-                self.write_expr(test);
-                self.output.push_str(":\n");
-                self.indent += 1;
-                self.write_block_body(consequent);
-                self.indent -= 1;
-
-                if let Some(alt) = alternate {
-                    self.write_indent();
-                    if let Stmt::If { .. } = alt.as_ref() {
-                        self.output.push_str("el");
-                        self.write_stmt_no_indent(alt);
-                    } else {
-                        self.output.push_str("else:\n");
-                        self.indent += 1;
-                        self.write_block_body(alt);
-                        self.indent -= 1;
-                    }
-                }
-            }
-            _ => self.write_stmt(stmt),
-        }
-    }
-
     fn write_block_body(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Block(stmts) if stmts.is_empty() => {
@@ -824,4 +866,26 @@ mod tests {
         let output = PythonWriter::emit(&program);
         assert!(output.contains("lambda a, b: (a + b)"));
     }
+
+    #[test]
+    fn test_switch_lowers_to_if_chain() {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::call(Expr::ident("f"), vec![]),
+            vec![
+                SwitchCase::new(
+                    vec![Expr::number(1)],
+                    vec![Stmt::expr(Expr::call(Expr::ident("a"), vec![]))],
+                ),
+                SwitchCase::new(
+                    vec![Expr::number(2)],
+                    vec![Stmt::expr(Expr::call(Expr::ident("b"), vec![]))],
+                ),
+            ],
+            None,
+        )]);
+        let output = PythonWriter::emit(&program);
+        assert!(output.starts_with("_switch = f()\n"), "{output}");
+        assert!(output.contains("if (_switch == 1):"), "{output}");
+        assert!(output.contains("elif (_switch == 2):"), "{output}");
+    }
 }
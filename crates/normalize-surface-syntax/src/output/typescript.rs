@@ -1,15 +1,57 @@
 //! TypeScript writer for surface-syntax IR.
 //!
-//! Emits surface-syntax IR as TypeScript source code.
+//! Emits surface-syntax IR as TypeScript source code. Formatting (semicolons,
+//! `const` vs `let`, indent width) is set by [`TypeScriptWriterOptions`].
 
 use crate::ir::*;
+use crate::source_map::{SourceMap, SourceMapBuilder};
 use crate::traits::Writer;
 
 /// Static instance of the TypeScript writer for registry.
-pub static TYPESCRIPT_WRITER: TypeScriptWriterImpl = TypeScriptWriterImpl;
+pub static TYPESCRIPT_WRITER: TypeScriptWriterImpl =
+    TypeScriptWriterImpl::with_options(TypeScriptWriterOptions::DEFAULT);
+
+/// Formatting choices for emitted TypeScript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeScriptWriterOptions {
+    /// Terminate statements with `;`. When off, an expression statement that
+    /// would otherwise continue the previous line under automatic semicolon
+    /// insertion (one starting with `(`, `[`, `` ` ``, `+`, `-` or `/`) gets a
+    /// leading `;`.
+    pub semicolons: bool,
+    /// Declare immutable bindings with `const`. When off, every binding
+    /// (including `for ... of` variables) uses `let`.
+    pub prefer_const: bool,
+    /// Spaces per indentation level.
+    pub indent_width: usize,
+}
+
+impl TypeScriptWriterOptions {
+    /// Semicolons, `const` for immutable bindings, two-space indent.
+    pub const DEFAULT: Self = Self {
+        semicolons: true,
+        prefer_const: true,
+        indent_width: 2,
+    };
+}
+
+impl Default for TypeScriptWriterOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
 
 /// TypeScript writer implementing the Writer trait.
-pub struct TypeScriptWriterImpl;
+pub struct TypeScriptWriterImpl {
+    options: TypeScriptWriterOptions,
+}
+
+impl TypeScriptWriterImpl {
+    /// A writer that emits with `options` (for registering a non-default style).
+    pub const fn with_options(options: TypeScriptWriterOptions) -> Self {
+        Self { options }
+    }
+}
 
 impl Writer for TypeScriptWriterImpl {
     fn language(&self) -> &'static str {
@@ -21,7 +63,15 @@ impl Writer for TypeScriptWriterImpl {
     }
 
     fn write(&self, program: &Program) -> String {
-        TypeScriptWriter::emit(program)
+        TypeScriptWriter::emit_with(program, self.options)
+    }
+
+    fn write_with_source_map(&self, program: &Program) -> (String, SourceMap) {
+        TypeScriptWriter::emit_with_source_map(program, self.options)
+    }
+
+    fn emit_expr(&self, expr: &Expr) -> String {
+        TypeScriptWriter::emit_expr_with(expr, self.options)
     }
 }
 
@@ -29,23 +79,60 @@ impl Writer for TypeScriptWriterImpl {
 pub struct TypeScriptWriter {
     output: String,
     indent: usize,
+    options: TypeScriptWriterOptions,
+    source_map: SourceMapBuilder,
 }
 
 impl TypeScriptWriter {
     pub fn new() -> Self {
+        Self::with_options(TypeScriptWriterOptions::DEFAULT)
+    }
+
+    pub fn with_options(options: TypeScriptWriterOptions) -> Self {
         Self {
             output: String::new(),
             indent: 0,
+            options,
+            source_map: SourceMapBuilder::default(),
         }
     }
 
     /// Emit a program to TypeScript source.
     pub fn emit(program: &Program) -> String {
-        let mut writer = Self::new();
+        Self::emit_with(program, TypeScriptWriterOptions::DEFAULT)
+    }
+
+    /// Emit a program to TypeScript source with the given formatting options.
+    pub fn emit_with(program: &Program, options: TypeScriptWriterOptions) -> String {
+        let mut writer = Self::with_options(options);
         writer.write_program(program);
         writer.output
     }
 
+    /// Emit a single expression to TypeScript source.
+    pub fn emit_expr(expr: &Expr) -> String {
+        Self::emit_expr_with(expr, TypeScriptWriterOptions::DEFAULT)
+    }
+
+    /// Emit a single expression to TypeScript source with the given
+    /// formatting options.
+    pub fn emit_expr_with(expr: &Expr, options: TypeScriptWriterOptions) -> String {
+        let mut writer = Self::with_options(options);
+        writer.write_expr(expr);
+        writer.output
+    }
+
+    /// Emit a program to TypeScript source, along with the output line each
+    /// spanned statement starts on.
+    pub fn emit_with_source_map(
+        program: &Program,
+        options: TypeScriptWriterOptions,
+    ) -> (String, SourceMap) {
+        let mut writer = Self::with_options(options);
+        writer.write_program(program);
+        (writer.output, writer.source_map.finish())
+    }
+
     fn write_program(&mut self, program: &Program) {
         for stmt in &program.body {
             self.write_stmt(stmt);
@@ -54,17 +141,39 @@ impl TypeScriptWriter {
     }
 
     fn write_indent(&mut self) {
-        for _ in 0..self.indent {
-            self.output.push_str("  ");
+        for _ in 0..self.indent * self.options.indent_width {
+            self.output.push(' ');
+        }
+    }
+
+    fn write_semicolon(&mut self) {
+        if self.options.semicolons {
+            self.output.push(';');
+        }
+    }
+
+    fn binding_keyword(&self, mutable: bool) -> &'static str {
+        if mutable || !self.options.prefer_const {
+            "let "
+        } else {
+            "const "
         }
     }
 
     fn write_stmt(&mut self, stmt: &Stmt) {
         self.write_indent();
+        self.source_map.record(&self.output, stmt.span());
         match stmt {
             Stmt::Expr(expr) => {
+                let start = self.output.len();
                 self.write_expr(expr);
-                self.output.push(';');
+                if !self.options.semicolons
+                    && self.output[start..].starts_with(['(', '[', '`', '+', '-', '/'])
+                {
+                    self.output.insert(start, ';');
+                    self.source_map.inserted(start, 1);
+                }
+                self.write_semicolon();
             }
 
             Stmt::Let {
@@ -74,8 +183,8 @@ impl TypeScriptWriter {
                 type_annotation,
                 ..
             } => {
-                self.output
-                    .push_str(if *mutable { "let " } else { "const " });
+                let keyword = self.binding_keyword(*mutable);
+                self.output.push_str(keyword);
                 self.output.push_str(name);
                 if let Some(t) = type_annotation {
                     self.output.push_str(": ");
@@ -85,7 +194,7 @@ impl TypeScriptWriter {
                     self.output.push_str(" = ");
                     self.write_expr(init);
                 }
-                self.output.push(';');
+                self.write_semicolon();
             }
 
             Stmt::Destructure {
@@ -94,12 +203,12 @@ impl TypeScriptWriter {
                 mutable,
                 ..
             } => {
-                self.output
-                    .push_str(if *mutable { "let " } else { "const " });
+                let keyword = self.binding_keyword(*mutable);
+                self.output.push_str(keyword);
                 self.write_pat(pat);
                 self.output.push_str(" = ");
                 self.write_expr(value);
-                self.output.push(';');
+                self.write_semicolon();
             }
 
             Stmt::Block(stmts) => {
@@ -171,7 +280,9 @@ impl TypeScriptWriter {
                 body,
                 ..
             } => {
-                self.output.push_str("for (const ");
+                self.output.push_str("for (");
+                let keyword = self.binding_keyword(false);
+                self.output.push_str(keyword);
                 self.output.push_str(variable);
                 self.output.push_str(" of ");
                 self.write_expr(iterable);
@@ -185,15 +296,46 @@ impl TypeScriptWriter {
                     self.output.push(' ');
                     self.write_expr(e);
                 }
-                self.output.push(';');
+                self.write_semicolon();
             }
 
             Stmt::Break => {
-                self.output.push_str("break;");
+                self.output.push_str("break");
+                self.write_semicolon();
             }
 
             Stmt::Continue => {
-                self.output.push_str("continue;");
+                self.output.push_str("continue");
+                self.write_semicolon();
+            }
+
+            Stmt::Switch {
+                discriminant,
+                cases,
+                default,
+                ..
+            } => {
+                self.output.push_str("switch (");
+                self.write_expr(discriminant);
+                self.output.push_str(") {\n");
+                self.indent += 1;
+                for case in cases {
+                    for test in &case.tests {
+                        self.write_indent();
+                        self.output.push_str("case ");
+                        self.write_expr(test);
+                        self.output.push_str(":\n");
+                    }
+                    self.write_switch_arm(&case.body, true);
+                }
+                if let Some(body) = default {
+                    self.write_indent();
+                    self.output.push_str("default:\n");
+                    self.write_switch_arm(body, false);
+                }
+                self.indent -= 1;
+                self.write_indent();
+                self.output.push('}');
             }
 
             Stmt::TryCatch {
@@ -274,7 +416,7 @@ impl TypeScriptWriter {
                     self.output.push_str(source);
                     self.output.push('\'');
                 }
-                self.output.push(';');
+                self.write_semicolon();
             }
 
             Stmt::Export { names, source, .. } => {
@@ -300,7 +442,7 @@ impl TypeScriptWriter {
                     self.output.push_str(src);
                     self.output.push('\'');
                 }
-                self.output.push(';');
+                self.write_semicolon();
             }
 
             Stmt::Class {
@@ -405,8 +547,8 @@ impl TypeScriptWriter {
                 type_annotation,
                 ..
             } => {
-                self.output
-                    .push_str(if *mutable { "let " } else { "const " });
+                let keyword = self.binding_keyword(*mutable);
+                self.output.push_str(keyword);
                 self.output.push_str(name);
                 if let Some(t) = type_annotation {
                     self.output.push_str(": ");
@@ -423,8 +565,8 @@ impl TypeScriptWriter {
                 mutable,
                 ..
             } => {
-                self.output
-                    .push_str(if *mutable { "let " } else { "const " });
+                let keyword = self.binding_keyword(*mutable);
+                self.output.push_str(keyword);
                 self.write_pat(pat);
                 self.output.push_str(" = ");
                 self.write_expr(value);
@@ -436,6 +578,28 @@ impl TypeScriptWriter {
         }
     }
 
+    /// Body of a `case`/`default` arm. IR arms never fall through, so a `break`
+    /// is added unless the arm already ends in a jump.
+    fn write_switch_arm(&mut self, body: &[Stmt], terminate: bool) {
+        self.indent += 1;
+        for s in body {
+            self.write_stmt(s);
+            self.output.push('\n');
+        }
+        if terminate
+            && !matches!(
+                body.last(),
+                Some(Stmt::Return(_) | Stmt::Break | Stmt::Continue)
+            )
+        {
+            self.write_indent();
+            self.output.push_str("break");
+            self.write_semicolon();
+            self.output.push('\n');
+        }
+        self.indent -= 1;
+    }
+
     fn write_block_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Block(stmts) => {
@@ -945,6 +1109,7 @@ mod tests {
     fn test_typed_function() {
         use crate::Param;
         let program = Program::new(vec![Stmt::function(crate::Function {
+            span: None,
             name: "greet".to_string(),
             params: vec![
                 Param::typed("name", "string"),
@@ -984,6 +1149,69 @@ mod tests {
         assert_eq!(ts.trim(), "const msg = `Hello ${name}!`;");
     }
 
+    #[test]
+    fn test_options_without_semicolons() {
+        let program = Program::new(vec![
+            Stmt::const_decl("x", Expr::number(1)),
+            Stmt::expr(Expr::call(Expr::ident("f"), vec![])),
+            Stmt::expr(Expr::binary(
+                Expr::ident("a"),
+                BinaryOp::Add,
+                Expr::ident("b"),
+            )),
+            Stmt::expr(Expr::array(vec![Expr::number(1)])),
+            Stmt::return_stmt(None),
+        ]);
+        let options = TypeScriptWriterOptions {
+            semicolons: false,
+            ..Default::default()
+        };
+        let ts = TypeScriptWriter::emit_with(&program, options);
+        // Lines starting with `(` or `[` are guarded against ASI continuation.
+        assert_eq!(ts, "const x = 1\nf()\n;(a + b)\n;[1]\nreturn\n");
+    }
+
+    #[test]
+    fn test_options_let_only() {
+        let program = Program::new(vec![
+            Stmt::const_decl("x", Expr::number(1)),
+            Stmt::for_in("item", Expr::ident("items"), Stmt::block(vec![])),
+        ]);
+        let options = TypeScriptWriterOptions {
+            prefer_const: false,
+            ..Default::default()
+        };
+        let ts = TypeScriptWriter::emit_with(&program, options);
+        assert!(ts.contains("let x = 1;"));
+        assert!(ts.contains("for (let item of items)"));
+    }
+
+    #[test]
+    fn test_options_indent_width() {
+        let program = Program::new(vec![Stmt::if_stmt(
+            Expr::ident("ok"),
+            Stmt::block(vec![Stmt::expr(Expr::call(Expr::ident("go"), vec![]))]),
+            None,
+        )]);
+        let options = TypeScriptWriterOptions {
+            indent_width: 4,
+            ..Default::default()
+        };
+        let writer = TypeScriptWriterImpl::with_options(options);
+        assert_eq!(writer.write(&program), "if (ok) {\n    go();\n}\n");
+    }
+
+    #[cfg(feature = "read-lua")]
+    #[test]
+    fn test_lua_to_typescript() {
+        use crate::input::read_lua;
+        let program =
+            read_lua("local total = 0\nfor _, v in ipairs(xs) do\n  total = total + v\nend")
+                .expect("parse failed");
+        let ts = TypeScriptWriter::emit(&program);
+        assert!(ts.contains("total = (total + v);"), "{ts}");
+    }
+
     #[test]
     fn test_template_literal_round_trip() {
         use crate::input::read_typescript;
@@ -992,4 +1220,54 @@ mod tests {
         let ts = TypeScriptWriter::emit(&program);
         assert_eq!(ts.trim(), src);
     }
+
+    #[test]
+    fn test_source_map_lines() {
+        use crate::input::read_typescript;
+        let src = "const a = 1;\n\nfunction f(x) {\n  return x + 1;\n}\nf(a);\n";
+        let program = read_typescript(src).expect("parse failed");
+        let (ts, map) = TYPESCRIPT_WRITER.write_with_source_map(&program);
+        assert_eq!(
+            ts,
+            "const a = 1;\nfunction f(x) {\n  return (x + 1);\n}\nf(a);\n"
+        );
+        let lines: Vec<_> = map
+            .mappings
+            .iter()
+            .map(|m| (m.output_line, m.span.start_line))
+            .collect();
+        assert_eq!(lines, vec![(1, 1), (2, 3), (3, 4), (5, 6)]);
+        // The closing brace falls back to the statement above it.
+        assert_eq!(map.span_for_line(4).map(|s| s.start_line), Some(4));
+    }
+
+    #[test]
+    fn test_switch_native() {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::ident("x"),
+            vec![
+                SwitchCase::new(
+                    vec![Expr::number(1), Expr::number(2)],
+                    vec![Stmt::expr(Expr::call(Expr::ident("a"), vec![]))],
+                ),
+                SwitchCase::new(
+                    vec![Expr::number(3)],
+                    vec![Stmt::return_stmt(Some(Expr::ident("y")))],
+                ),
+            ],
+            Some(vec![Stmt::expr(Expr::call(Expr::ident("b"), vec![]))]),
+        )]);
+        let ts = TypeScriptWriter::emit(&program);
+        assert!(ts.contains("switch (x) {"), "{ts}");
+        assert!(ts.contains("case 1:\n  case 2:"), "{ts}");
+        // One `break` for the first arm; the `return` arm needs none
+        assert_eq!(ts.matches("break;").count(), 1, "{ts}");
+        assert!(ts.contains("default:"), "{ts}");
+    }
+
+    #[test]
+    fn test_emit_expr() {
+        let expr = Expr::binary(Expr::ident("a"), BinaryOp::Ne, Expr::null());
+        assert_eq!(TypeScriptWriter::emit_expr(&expr), "(a !== null)");
+    }
 }
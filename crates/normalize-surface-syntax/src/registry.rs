@@ -41,6 +41,10 @@ fn init_readers() {
         {
             register_reader(&crate::input::python::PYTHON_READER);
         }
+        #[cfg(feature = "read-go")]
+        {
+            register_reader(&crate::input::go::GO_READER);
+        }
     });
 }
 
@@ -145,6 +149,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(all(feature = "read-typescript", feature = "write-lua"))]
+    fn test_translate_expr() -> Result<(), String> {
+        let lua = crate::translate_expr("typescript", "lua", "a !== null && !b")
+            .map_err(|e| e.to_string())?;
+        assert_eq!(lua, "((a ~= nil) and not b)");
+        assert!(crate::translate_expr("cobol", "lua", "1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "read-python", feature = "write-typescript"))]
+    fn test_default_read_expr() -> Result<(), String> {
+        let reader = reader_for_language("python").ok_or("python reader not found")?;
+        let expr = reader.read_expr("x + 1").map_err(|e| e.to_string())?;
+        let writer = writer_for_language("typescript").ok_or("typescript writer not found")?;
+        assert_eq!(writer.emit_expr(&expr), "(x + 1)");
+        assert!(reader.read_expr("x = 1\ny = 2").is_err());
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "read-lua")]
     fn test_lua_reader_lookup() -> Result<(), String> {
@@ -154,6 +179,14 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "read-go")]
+    fn test_go_reader_lookup() -> Result<(), String> {
+        let reader = reader_for_extension("go").ok_or("go extension not found")?;
+        assert_eq!(reader.language(), "go");
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "write-typescript")]
     fn test_typescript_writer_lookup() -> Result<(), String> {
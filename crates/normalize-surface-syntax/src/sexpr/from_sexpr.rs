@@ -128,6 +128,50 @@ fn parse_opcode(opcode: &str, args: &[Value]) -> Result<Stmt, SExprError> {
         "std.break" => Ok(Stmt::break_stmt()),
         "std.continue" => Ok(Stmt::continue_stmt()),
 
+        "std.switch" => {
+            if args.is_empty() {
+                return Err(SExprError::WrongArity {
+                    opcode: opcode.into(),
+                    expected: 1,
+                    got: 0,
+                });
+            }
+            let discriminant = value_to_expr(&args[0])?;
+            let mut cases = Vec::new();
+            let mut default = None;
+
+            for arg in &args[1..] {
+                let arm = arg.as_array().ok_or_else(|| {
+                    SExprError::InvalidArgument("std.switch arm must be an array".into())
+                })?;
+                match arm.first().and_then(|v| v.as_str()) {
+                    Some("case") if arm.len() == 3 => {
+                        let tests = arm[1]
+                            .as_array()
+                            .ok_or_else(|| {
+                                SExprError::InvalidArgument(
+                                    "std.switch case tests must be an array".into(),
+                                )
+                            })?
+                            .iter()
+                            .map(value_to_expr)
+                            .collect::<Result<_, _>>()?;
+                        cases.push(SwitchCase::new(tests, seq_to_stmts(&arm[2])?));
+                    }
+                    Some("default") if arm.len() == 2 => {
+                        default = Some(seq_to_stmts(&arm[1])?);
+                    }
+                    _ => {
+                        return Err(SExprError::InvalidArgument(format!(
+                            "std.switch arm must be [\"case\", tests, body] or [\"default\", body], got {arg}"
+                        )));
+                    }
+                }
+            }
+
+            Ok(Stmt::switch(discriminant, cases, default))
+        }
+
         "std.try" => {
             if args.is_empty() {
                 return Err(SExprError::WrongArity {
@@ -471,6 +515,14 @@ fn parse_params(value: &Value) -> Result<Vec<Param>, SExprError> {
         .collect()
 }
 
+/// Statements of a `std.seq` (or of any single statement).
+fn seq_to_stmts(value: &Value) -> Result<Vec<Stmt>, SExprError> {
+    match value_to_stmt(value)? {
+        Stmt::Block(stmts) => Ok(stmts),
+        stmt => Ok(vec![stmt]),
+    }
+}
+
 fn ensure_arity(opcode: &str, args: &[Value], expected: usize) -> Result<(), SExprError> {
     if args.len() != expected {
         Err(SExprError::WrongArity {
@@ -533,4 +585,19 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_switch_round_trip() -> Result<(), SExprError> {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::ident("x"),
+            vec![SwitchCase::new(
+                vec![Expr::number(1), Expr::string("a")],
+                vec![Stmt::expr(Expr::call(Expr::ident("f"), vec![]))],
+            )],
+            Some(vec![Stmt::return_stmt(None)]),
+        )]);
+        let back = from_sexpr(&crate::sexpr::to_sexpr(&program))?;
+        assert!(back.structure_eq(&program));
+        Ok(())
+    }
 }
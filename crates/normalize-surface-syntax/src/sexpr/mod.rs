@@ -8,11 +8,19 @@
 //! - `["std.if", cond, then, else]` → conditional
 //!
 //! This format is compact and used for storage (e.g., lotus verbs).
+//! [`SExpr`] also reads and writes the same tree as text — `(std.let x 1)` —
+//! for diffing and hand-editing (see the `text` module for the syntax).
+//!
+//! Source spans are not part of the format: [`to_sexpr`] drops them and
+//! [`from_sexpr`] yields nodes without spans. Keep the IR (or a
+//! [`SourceMap`](crate::SourceMap) from the writer) when positions matter.
 
 mod from_sexpr;
+mod text;
 mod to_sexpr;
 
 pub use from_sexpr::from_sexpr;
+pub use text::{SExpr, TEXT_WIDTH};
 pub use to_sexpr::to_sexpr;
 
 use thiserror::Error;
@@ -37,4 +45,11 @@ pub enum SExprError {
 
     #[error("invalid argument: {0}")]
     InvalidArgument(String),
+
+    #[error("{line}:{column}: {message}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
 }
@@ -0,0 +1,560 @@
+//! Textual S-expression syntax.
+//!
+//! The same tree as the JSON-array form, written Lisp-style so stored verbs
+//! can be diffed and edited by hand:
+//!
+//! ```text
+//! (std.seq
+//!   (std.let x 1)
+//!   (console.log "hello, world" (std.var x)))
+//! ```
+//!
+//! - `(a b c)` is an array; `()` the empty array.
+//! - `null`, `true` and `false` are keywords; numbers use JSON number syntax
+//!   (`1`, `-2.5`, `1e-3`), so integers and floats round-trip distinctly.
+//! - Any other bare token is a string (opcodes, names, `+`, `std.var`).
+//!   Strings that would not read back as a bare token — empty, containing
+//!   spaces, parentheses, quotes or `;`, or spelled like a keyword or number —
+//!   are written in double quotes with `\"`, `\\`, `\n`, `\r`, `\t` and
+//!   `\u{...}` escapes.
+//! - `;` starts a comment that runs to the end of the line.
+//!
+//! [`SExpr::to_text`] is canonical: a list that fits in [`TEXT_WIDTH`]
+//! columns stays on one line, otherwise its arguments go one per line,
+//! indented two spaces past the opening parenthesis.
+
+use super::{SExprError, from_sexpr, to_sexpr};
+use crate::ir::Program;
+use serde_json::{Number, Value};
+
+/// Column limit for [`SExpr::to_text`] before a list is broken across lines.
+pub const TEXT_WIDTH: usize = 80;
+
+/// An S-expression: a JSON value made of arrays, strings, numbers, booleans
+/// and null (no objects).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SExpr(Value);
+
+impl SExpr {
+    /// Wrap a JSON-array S-expression, rejecting JSON objects anywhere in it.
+    pub fn from_value(value: Value) -> Result<Self, SExprError> {
+        fn check(value: &Value) -> Result<(), SExprError> {
+            match value {
+                Value::Object(_) => Err(SExprError::InvalidArgument(
+                    "unexpected object in S-expression".into(),
+                )),
+                Value::Array(items) => items.iter().try_for_each(check),
+                _ => Ok(()),
+            }
+        }
+        check(&value)?;
+        Ok(Self(value))
+    }
+
+    /// Serialize a program (see [`to_sexpr`]).
+    pub fn from_program(program: &Program) -> Self {
+        Self(to_sexpr(program))
+    }
+
+    /// Convert back to a program (see [`from_sexpr`]).
+    pub fn to_program(&self) -> Result<Program, SExprError> {
+        from_sexpr(&self.0)
+    }
+
+    /// The JSON-array form.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+
+    /// Read the textual syntax. The input must hold exactly one expression,
+    /// optionally surrounded by whitespace and comments.
+    pub fn parse_text(text: &str) -> Result<Self, SExprError> {
+        let mut reader = Reader { text, pos: 0 };
+        reader.skip_trivia();
+        if reader.at_end() {
+            return Err(reader.error("expected an expression"));
+        }
+        let value = reader.read_value()?;
+        reader.skip_trivia();
+        if !reader.at_end() {
+            return Err(reader.error("unexpected input after expression"));
+        }
+        Ok(Self(value))
+    }
+
+    /// Write the canonical textual syntax (no trailing newline).
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        write_pretty(&self.0, 0, &mut out);
+        out
+    }
+}
+
+impl TryFrom<Value> for SExpr {
+    type Error = SExprError;
+
+    fn try_from(value: Value) -> Result<Self, SExprError> {
+        Self::from_value(value)
+    }
+}
+
+impl std::str::FromStr for SExpr {
+    type Err = SExprError;
+
+    fn from_str(text: &str) -> Result<Self, SExprError> {
+        Self::parse_text(text)
+    }
+}
+
+impl std::fmt::Display for SExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+// ============================================================================
+// Writer
+// ============================================================================
+
+fn write_pretty(value: &Value, indent: usize, out: &mut String) {
+    let flat = flat_text(value);
+    let items = match value {
+        Value::Array(items) if items.len() > 1 && indent + flat.chars().count() > TEXT_WIDTH => {
+            items
+        }
+        _ => {
+            out.push_str(&flat);
+            return;
+        }
+    };
+    out.push('(');
+    write_pretty(&items[0], indent + 1, out);
+    for item in &items[1..] {
+        out.push('\n');
+        out.extend(std::iter::repeat_n(' ', indent + 2));
+        write_pretty(item, indent + 2, out);
+    }
+    out.push(')');
+}
+
+fn flat_text(value: &Value) -> String {
+    let mut out = String::new();
+    write_flat(value, &mut out);
+    out
+}
+
+fn write_flat(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&n.to_string()),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('(');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                write_flat(item, out);
+            }
+            out.push(')');
+        }
+        // Rejected by `SExpr::from_value`; `to_sexpr` never produces objects.
+        Value::Object(_) => unreachable!("S-expressions contain no objects"),
+    }
+}
+
+fn write_string(s: &str, out: &mut String) {
+    if is_bare(s) {
+        out.push_str(s);
+        return;
+    }
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Whether `s` reads back as the same string when written without quotes.
+fn is_bare(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars().all(is_atom_char)
+        && !matches!(s, "null" | "true" | "false")
+        && !looks_numeric(s)
+}
+
+fn is_atom_char(c: char) -> bool {
+    !c.is_whitespace() && !c.is_control() && !matches!(c, '(' | ')' | '"' | ';')
+}
+
+/// Atoms starting like a number are read as numbers (and rejected if they are
+/// not valid JSON numbers) rather than as strings.
+fn looks_numeric(atom: &str) -> bool {
+    let rest = atom.strip_prefix(['-', '+']).unwrap_or(atom);
+    let rest = rest.strip_prefix('.').unwrap_or(rest);
+    rest.starts_with(|c: char| c.is_ascii_digit())
+}
+
+// ============================================================================
+// Reader
+// ============================================================================
+
+struct Reader<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> SExprError {
+        let before = &self.text[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        SExprError::Syntax {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Skip whitespace and `;` comments.
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == ';' {
+                while self.bump().is_some_and(|c| c != '\n') {}
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read_value(&mut self) -> Result<Value, SExprError> {
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some('(') => {
+                let open = self.pos;
+                self.bump();
+                let mut items = Vec::new();
+                loop {
+                    self.skip_trivia();
+                    match self.peek() {
+                        Some(')') => {
+                            self.bump();
+                            return Ok(Value::Array(items));
+                        }
+                        Some(_) => items.push(self.read_value()?),
+                        None => {
+                            self.pos = open;
+                            return Err(self.error("unclosed '('"));
+                        }
+                    }
+                }
+            }
+            Some(')') => Err(self.error("unexpected ')'")),
+            Some('"') => self.read_string().map(Value::String),
+            Some(_) => self.read_atom(),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, SExprError> {
+        let open = self.pos;
+        self.bump();
+        let mut s = String::new();
+        loop {
+            let escape_at = self.pos;
+            match self.bump() {
+                None => {
+                    self.pos = open;
+                    return Err(self.error("unterminated string"));
+                }
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('u') => {
+                        let c = self.read_unicode_escape().ok_or_else(|| {
+                            self.pos = escape_at;
+                            self.error("invalid \\u{...} escape")
+                        })?;
+                        s.push(c);
+                    }
+                    _ => {
+                        self.pos = escape_at;
+                        return Err(self.error("invalid escape"));
+                    }
+                },
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    /// The `{hex}` part of a `\u{hex}` escape.
+    fn read_unicode_escape(&mut self) -> Option<char> {
+        let rest = self.text[self.pos..].strip_prefix('{')?;
+        let len = rest.find('}')?;
+        let c = u32::from_str_radix(&rest[..len], 16)
+            .ok()
+            .and_then(char::from_u32)?;
+        self.pos += len + 2;
+        Some(c)
+    }
+
+    fn read_atom(&mut self) -> Result<Value, SExprError> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_atom_char) {
+            self.bump();
+        }
+        let atom = &self.text[start..self.pos];
+        if atom.is_empty() {
+            return Err(self.error("unexpected character"));
+        }
+        Ok(match atom {
+            "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ if looks_numeric(atom) => {
+                let number = parse_number(atom).ok_or_else(|| {
+                    self.pos = start;
+                    self.error(format!("invalid number '{atom}'"))
+                })?;
+                Value::Number(number)
+            }
+            _ => Value::String(atom.to_string()),
+        })
+    }
+}
+
+/// A JSON-syntax number. Floats are re-parsed with `f64::from_str`, which
+/// (unlike serde_json's default parser) round-trips every value it printed.
+fn parse_number(atom: &str) -> Option<Number> {
+    let number: Number = serde_json::from_str(atom).ok()?;
+    if number.is_f64() {
+        Number::from_f64(atom.parse().ok()?)
+    } else {
+        Some(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::*;
+    use serde_json::json;
+
+    fn sexpr(value: Value) -> SExpr {
+        SExpr::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn writes_bare_and_quoted_atoms() {
+        let s = sexpr(json!([
+            "std.let", "x", 1, 2.5, null, true, "-", "+x", "héllo"
+        ]));
+        assert_eq!(s.to_text(), "(std.let x 1 2.5 null true - +x héllo)");
+        let s = sexpr(json!([
+            "",
+            "two words",
+            "null",
+            "-1",
+            ".5",
+            "a\"b\\c\nd",
+            "\u{1}"
+        ]));
+        assert_eq!(
+            s.to_text(),
+            r#"("" "two words" "null" "-1" ".5" "a\"b\\c\nd" "\u{1}")"#
+        );
+    }
+
+    #[test]
+    fn reads_comments_and_whitespace() {
+        let s = SExpr::parse_text(
+            "; a stored verb\n(std.seq\n  (std.let x 1) ; bind\n  (console.log \"hi\"))\n",
+        )
+        .unwrap();
+        assert_eq!(
+            s.into_value(),
+            json!(["std.seq", ["std.let", "x", 1], ["console.log", "hi"]])
+        );
+    }
+
+    #[test]
+    fn breaks_long_lists_one_argument_per_line() {
+        let long = "a".repeat(40);
+        let s = sexpr(json!([
+            "std.seq",
+            ["std.let", "x", 1],
+            ["console.log", long, ["std.var", "x"]]
+        ]));
+        assert_eq!(
+            s.to_text(),
+            format!("(std.seq\n  (std.let x 1)\n  (console.log {long} (std.var x)))")
+        );
+        assert_eq!(SExpr::parse_text(&s.to_text()).unwrap(), s);
+    }
+
+    #[test]
+    fn syntax_errors_report_position() {
+        let err = |text: &str| SExpr::parse_text(text).unwrap_err().to_string();
+        assert_eq!(err("(std.let x"), "1:1: unclosed '('");
+        assert_eq!(err("(a)\n  b"), "2:3: unexpected input after expression");
+        assert_eq!(err("(a \"b"), "1:4: unterminated string");
+        assert_eq!(err("(a \"\\q\")"), "1:5: invalid escape");
+        assert_eq!(err("(a 1x)"), "1:4: invalid number '1x'");
+        assert_eq!(err(")"), "1:1: unexpected ')'");
+        assert_eq!(err("  ; nothing"), "1:12: expected an expression");
+        assert!(SExpr::from_value(json!(["a", {"b": 1}])).is_err());
+    }
+
+    /// xorshift64: deterministic pseudo-random values without a test dependency.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    fn random_string(rng: &mut Rng) -> String {
+        const PIECES: &[&str] = &[
+            "a", "std.let", "x", " ", "(", ")", "\"", "\\", ";", "\n", "\t", "\u{0}", "é", "-",
+            "+", ".", "1", "e", "null", "true", "😀", "\u{7f}",
+        ];
+        (0..rng.below(5))
+            .map(|_| PIECES[rng.below(PIECES.len() as u64) as usize])
+            .collect()
+    }
+
+    fn random_value(rng: &mut Rng, depth: u32) -> Value {
+        match rng.below(if depth == 0 { 5 } else { 7 }) {
+            0 => Value::Null,
+            1 => Value::Bool(rng.below(2) == 0),
+            2 => json!(rng.next() as i64 >> rng.below(64)),
+            3 => json!((rng.next() as i64 as f64) / (1u64 << rng.below(60)) as f64),
+            4 => Value::String(random_string(rng)),
+            _ => Value::Array(
+                (0..rng.below(6))
+                    .map(|_| random_value(rng, depth - 1))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn random_values_round_trip() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..2000 {
+            let value = sexpr(random_value(&mut rng, 5));
+            let text = value.to_text();
+            let read = SExpr::parse_text(&text)
+                .unwrap_or_else(|e| panic!("{e} reading back {text:?} from {value:?}"));
+            assert_eq!(read, value, "{text}");
+            assert_eq!(read.to_text(), text);
+        }
+    }
+
+    #[test]
+    fn ir_round_trips_through_text() {
+        let programs = [
+            Program::new(vec![Stmt::const_decl("x", Expr::number(42))]),
+            Program::new(vec![
+                Stmt::let_decl("greeting", Some(Expr::string("hello, \"world\"\n"))),
+                Stmt::expr(Expr::call(
+                    Expr::member(Expr::ident("console"), "log"),
+                    vec![Expr::ident("greeting"), Expr::number(-1.5)],
+                )),
+            ]),
+            Program::new(vec![Stmt::if_stmt(
+                Expr::binary(Expr::ident("n"), BinaryOp::Le, Expr::number(1)),
+                Stmt::return_stmt(Some(Expr::ident("n"))),
+                Some(Stmt::return_stmt(Some(Expr::binary(
+                    Expr::call(
+                        Expr::ident("fib"),
+                        vec![Expr::binary(
+                            Expr::ident("n"),
+                            BinaryOp::Sub,
+                            Expr::number(1),
+                        )],
+                    ),
+                    BinaryOp::Add,
+                    Expr::call(
+                        Expr::ident("fib"),
+                        vec![Expr::binary(
+                            Expr::ident("n"),
+                            BinaryOp::Sub,
+                            Expr::number(2),
+                        )],
+                    ),
+                )))),
+            )]),
+            Program::new(vec![Stmt::while_loop(
+                Expr::unary(UnaryOp::Not, Expr::ident("done")),
+                Stmt::block(vec![
+                    Stmt::expr(Expr::call(Expr::ident("step"), vec![])),
+                    Stmt::break_stmt(),
+                ]),
+            )]),
+            Program::new(vec![Stmt::function(Function::new(
+                "add",
+                vec![Param::new("a"), Param::new("b")],
+                vec![Stmt::return_stmt(Some(Expr::binary(
+                    Expr::ident("a"),
+                    BinaryOp::Add,
+                    Expr::ident("b"),
+                )))],
+            ))]),
+            Program::new(vec![Stmt::expr(Expr::array(vec![
+                Expr::null(),
+                Expr::bool(true),
+                Expr::string("null"),
+                Expr::string(""),
+            ]))]),
+        ];
+        for program in &programs {
+            let value = SExpr::from_program(program);
+            let text = value.to_text();
+            let read = SExpr::parse_text(&text).unwrap();
+            assert_eq!(read, value, "{text}");
+            let reread = SExpr::from_program(&read.to_program().unwrap());
+            assert_eq!(reread, value, "{text}");
+        }
+    }
+}
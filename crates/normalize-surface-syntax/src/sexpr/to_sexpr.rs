@@ -112,6 +112,24 @@ fn stmt_to_sexpr(stmt: &Stmt) -> Value {
         Stmt::Break => json!(["std.break"]),
         Stmt::Continue => json!(["std.continue"]),
 
+        // ["std.switch", disc, ["case", [tests...], seq]..., ["default", seq]]
+        Stmt::Switch {
+            discriminant,
+            cases,
+            default,
+            ..
+        } => {
+            let mut parts: Vec<Value> = vec![json!("std.switch"), expr_to_sexpr(discriminant)];
+            for case in cases {
+                let tests: Vec<Value> = case.tests.iter().map(expr_to_sexpr).collect();
+                parts.push(json!(["case", tests, stmts_to_seq(&case.body)]));
+            }
+            if let Some(body) = default {
+                parts.push(json!(["default", stmts_to_seq(body)]));
+            }
+            Value::Array(parts)
+        }
+
         Stmt::TryCatch {
             body,
             catch_param,
@@ -214,6 +232,14 @@ fn stmt_to_sexpr(stmt: &Stmt) -> Value {
 }
 
 /// Recursively collect let-binding s-expressions from a pattern.
+/// `["std.seq", ...]` even for zero or one statement, so arm bodies read back
+/// as statement lists.
+fn stmts_to_seq(stmts: &[Stmt]) -> Value {
+    let mut arr = vec![json!("std.seq")];
+    arr.extend(stmts.iter().map(stmt_to_sexpr));
+    Value::Array(arr)
+}
+
 fn collect_pat_bindings(pat: &Pat, rhs: &Value, out: &mut Vec<Value>) {
     match pat {
         Pat::Ident(name) => {
@@ -485,4 +511,26 @@ mod tests {
         let sexpr = to_sexpr(&program);
         assert_eq!(sexpr, json!(["console.log", "hello"]));
     }
+
+    #[test]
+    fn test_switch() {
+        let program = Program::new(vec![Stmt::switch(
+            Expr::ident("x"),
+            vec![SwitchCase::new(
+                vec![Expr::number(1)],
+                vec![Stmt::break_stmt()],
+            )],
+            Some(vec![]),
+        )]);
+        let sexpr = to_sexpr(&program);
+        assert_eq!(
+            sexpr,
+            json!([
+                "std.switch",
+                ["std.var", "x"],
+                ["case", [1.0], ["std.seq", ["std.break"]]],
+                ["default", ["std.seq"]]
+            ])
+        );
+    }
 }
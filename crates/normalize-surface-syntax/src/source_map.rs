@@ -0,0 +1,82 @@
+//! Output-line to input-span side-tables.
+//!
+//! Readers attach [`Span`]s to IR nodes; writers emit source without them.
+//! [`Writer::write_with_source_map`](crate::Writer::write_with_source_map)
+//! additionally returns a [`SourceMap`] recording, for every emitted statement
+//! that carries a span, the output line it starts on — so an error at line 12
+//! of generated Lua can be traced back to line 7 of the original TypeScript.
+
+use crate::ir::Span;
+use serde::{Deserialize, Serialize};
+
+/// One statement's position in the output, paired with its input span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineMapping {
+    /// Output line (1-based) the statement starts on.
+    pub output_line: u32,
+    /// Input span the statement was read from.
+    pub span: Span,
+}
+
+/// Side-table mapping output lines to input spans, in output order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMap {
+    pub mappings: Vec<LineMapping>,
+}
+
+impl SourceMap {
+    /// Input span for `output_line` (1-based).
+    ///
+    /// Returns the innermost statement starting on that line, or, for a line
+    /// no statement starts on (a closing brace, a wrapped argument list), the
+    /// nearest statement starting above it.
+    pub fn span_for_line(&self, output_line: u32) -> Option<Span> {
+        self.mappings
+            .iter()
+            .rev()
+            .find(|m| m.output_line <= output_line)
+            .map(|m| m.span)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+}
+
+/// Builds a [`SourceMap`] while a writer appends to its output buffer.
+///
+/// Line numbers are counted incrementally, so recording is linear in the
+/// output size.
+#[derive(Debug, Default)]
+pub(crate) struct SourceMapBuilder {
+    map: SourceMap,
+    scanned: usize,
+    line: u32,
+}
+
+impl SourceMapBuilder {
+    /// Record that a statement with `span` starts at the end of `output`.
+    pub(crate) fn record(&mut self, output: &str, span: Option<Span>) {
+        let Some(span) = span else { return };
+        self.line += output.as_bytes()[self.scanned..]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count() as u32;
+        self.scanned = output.len();
+        self.map.mappings.push(LineMapping {
+            output_line: self.line + 1,
+            span,
+        });
+    }
+
+    /// Account for `len` bytes the writer inserted at byte offset `at`.
+    pub(crate) fn inserted(&mut self, at: usize, len: usize) {
+        if at < self.scanned {
+            self.scanned += len;
+        }
+    }
+
+    pub(crate) fn finish(self) -> SourceMap {
+        self.map
+    }
+}
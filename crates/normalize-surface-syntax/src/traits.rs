@@ -1,6 +1,7 @@
 //! Traits for language readers and writers.
 
-use crate::ir::Program;
+use crate::ir::{Construct, Expr, Program, Stmt};
+use crate::source_map::SourceMap;
 
 /// Error that can occur when reading source code into IR.
 #[derive(Debug, thiserror::Error)]
@@ -25,6 +26,19 @@ pub trait Reader: Send + Sync {
 
     /// Parse source code into the IR.
     fn read(&self, source: &str) -> Result<Program, ReadError>;
+
+    /// Parse a single expression, such as a condition stored in config.
+    ///
+    /// The default reads `source` as a program and accepts it only if it is
+    /// one expression statement. Readers for languages where a bare
+    /// expression is not a valid program override this.
+    fn read_expr(&self, source: &str) -> Result<Expr, ReadError> {
+        let mut program = self.read(source)?;
+        match (program.body.pop(), program.body.is_empty()) {
+            (Some(Stmt::Expr(expr)), true) => Ok(expr),
+            _ => Err(ReadError::Parse("expected a single expression".into())),
+        }
+    }
 }
 
 /// A writer emits the IR as source code in a target language.
@@ -37,4 +51,32 @@ pub trait Writer: Send + Sync {
 
     /// Emit the IR as source code.
     fn write(&self, program: &Program) -> String;
+
+    /// Emit a single expression, without statement terminator or newline.
+    ///
+    /// The default writes it as a one-statement program and trims the
+    /// trailing `;` and whitespace.
+    fn emit_expr(&self, expr: &Expr) -> String {
+        let program = Program::new(vec![Stmt::expr(expr.clone())]);
+        let code = self.write(&program);
+        code.trim_end().trim_end_matches(';').to_string()
+    }
+
+    /// Emit the IR as source code, plus a side-table mapping output lines to
+    /// the input spans of the statements emitted there.
+    ///
+    /// The default returns an empty map, for writers that do not track lines.
+    fn write_with_source_map(&self, program: &Program) -> (String, SourceMap) {
+        (self.write(program), SourceMap::default())
+    }
+
+    /// Whether this writer can express `construct` faithfully. Used by
+    /// [`validate_for`](crate::ir::validate_for) to flag IR that would come out
+    /// as a placeholder or a lossy approximation.
+    ///
+    /// The default claims support for everything.
+    fn supports(&self, construct: Construct) -> bool {
+        let _ = construct;
+        true
+    }
 }
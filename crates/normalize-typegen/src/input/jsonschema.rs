@@ -1,29 +1,42 @@
 //! JSON Schema to IR parser.
+//!
+//! Handles draft 2020-12 composition: `allOf` members are merged into a single
+//! struct, `oneOf`/`anyOf` become unions (tagged unions when a `discriminator`
+//! is present), and `$ref`s resolve against both `$defs` and `definitions`.
 
 use crate::ir::{
     EnumDef, EnumKind, Field, Schema, StringVariant, StructDef, TaggedUnion, TaggedVariant, Type,
     TypeDef, TypeDefKind,
 };
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+/// Limit on `$ref` chains and `allOf` nesting, so cyclic schemas fail instead of hanging.
+const MAX_DEPTH: usize = 32;
 
 /// Parse a JSON Schema document into an IR Schema.
 pub fn parse_json_schema(input: &Value) -> Result<Schema, ParseError> {
     let mut schema = Schema::new();
-    let mut parser = Parser::new();
-
-    // Handle $defs / definitions
-    if let Some(defs) = input.get("$defs").or_else(|| input.get("definitions"))
-        && let Some(obj) = defs.as_object()
-    {
-        for (name, def) in obj {
-            if let Some(type_def) = parser.parse_definition(name, def)? {
-                schema.add(type_def);
+    let mut parser = Parser::new(input);
+
+    // Handle $defs (2019-09+) and definitions (draft-07 and earlier)
+    for key in ["$defs", "definitions"] {
+        if let Some(obj) = input.get(key).and_then(|d| d.as_object()) {
+            for (name, def) in obj {
+                if let Some(type_def) = parser.parse_definition(name, def)? {
+                    schema.add(type_def);
+                }
+                for hoisted in parser.hoisted.drain(..) {
+                    schema.add(hoisted);
+                }
             }
         }
     }
 
     // Handle root schema if it defines a type
-    if input.get("type").is_some() || input.get("properties").is_some() {
+    let defines_type = ["type", "properties", "allOf", "oneOf", "anyOf", "enum"]
+        .iter()
+        .any(|key| input.get(key).is_some());
+    if defines_type {
         let root_name = input
             .get("title")
             .and_then(|t| t.as_str())
@@ -31,6 +44,9 @@ pub fn parse_json_schema(input: &Value) -> Result<Schema, ParseError> {
         if let Some(type_def) = parser.parse_definition(root_name, input)? {
             schema.add(type_def);
         }
+        for hoisted in parser.hoisted.drain(..) {
+            schema.add(hoisted);
+        }
     }
 
     Ok(schema)
@@ -42,13 +58,19 @@ pub enum ParseError {
     Unsupported(String),
 }
 
-struct Parser {
-    // Could track state like seen refs, etc.
+struct Parser<'a> {
+    /// The document being parsed, used to resolve `$ref`s.
+    root: &'a Value,
+    /// Inline object members of untagged unions, lifted into named definitions.
+    hoisted: Vec<TypeDef>,
 }
 
-impl Parser {
-    fn new() -> Self {
-        Self {}
+impl<'a> Parser<'a> {
+    fn new(root: &'a Value) -> Self {
+        Self {
+            root,
+            hoisted: Vec::new(),
+        }
     }
 
     fn parse_definition(
@@ -66,11 +88,25 @@ impl Parser {
             return Ok(Some(self.parse_enum(name, enum_values, docs)?));
         }
 
-        // Check for oneOf with discriminator (tagged union)
-        if let Some(one_of) = schema.get("oneOf")
+        let members = schema.get("oneOf").or_else(|| schema.get("anyOf"));
+
+        // Check for oneOf/anyOf with discriminator (tagged union)
+        if let Some(members) = members
             && let Some(disc) = schema.get("discriminator")
         {
-            return Ok(Some(self.parse_tagged_union(name, one_of, disc, docs)?));
+            return Ok(Some(self.parse_tagged_union(name, members, disc, docs)?));
+        }
+
+        // Check for allOf (merged into one struct when the members contribute properties)
+        if schema.get("allOf").is_some() {
+            let merged = self.merge_all_of(schema)?;
+            if merged
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .is_some_and(|p| !p.is_empty())
+            {
+                return Ok(Some(self.parse_struct(name, &merged, docs)?));
+            }
         }
 
         // Check for object type
@@ -79,6 +115,21 @@ impl Parser {
             return Ok(Some(self.parse_struct(name, schema, docs)?));
         }
 
+        // Check for oneOf/anyOf without discriminator (untagged union)
+        if let Some(arr) = members.and_then(|m| m.as_array()) {
+            let mut types = Vec::new();
+            for (i, member) in arr.iter().enumerate() {
+                types.push(self.parse_union_member(name, i, member)?);
+            }
+            if let Some(ty) = union_of(types) {
+                return Ok(Some(TypeDef {
+                    name: name.to_string(),
+                    docs,
+                    kind: TypeDefKind::Alias(ty),
+                }));
+            }
+        }
+
         // Simple type alias
         if let Some(ty) = self.parse_type(schema)? {
             return Ok(Some(TypeDef {
@@ -91,12 +142,47 @@ impl Parser {
         Ok(None)
     }
 
+    /// Parse one member of an untagged union. Inline object schemas have no
+    /// name to refer to, so they are hoisted into `<Union>Variant<N>`.
+    fn parse_union_member(
+        &mut self,
+        union_name: &str,
+        index: usize,
+        member: &Value,
+    ) -> Result<Type, ParseError> {
+        let inline_object = member.get("$ref").is_none()
+            && (member.get("properties").is_some() || member.get("allOf").is_some());
+        if inline_object {
+            let name = format!("{}Variant{}", union_name, index + 1);
+            if let Some(def) = self.parse_definition(&name, member)? {
+                self.hoisted.push(def);
+                return Ok(Type::Ref(name));
+            }
+        }
+        Ok(self.parse_type(member)?.unwrap_or(Type::Any))
+    }
+
     fn parse_struct(
         &mut self,
         name: &str,
         schema: &Value,
         docs: Option<String>,
     ) -> Result<TypeDef, ParseError> {
+        Ok(TypeDef {
+            name: name.to_string(),
+            docs,
+            kind: TypeDefKind::Struct(StructDef {
+                fields: self.parse_fields(schema, None)?,
+            }),
+        })
+    }
+
+    /// Parse an object schema's properties into fields, leaving out `skip`.
+    fn parse_fields(
+        &mut self,
+        schema: &Value,
+        skip: Option<&str>,
+    ) -> Result<Vec<Field>, ParseError> {
         let mut fields = Vec::new();
 
         let required: Vec<&str> = schema
@@ -107,6 +193,9 @@ impl Parser {
 
         if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
             for (prop_name, prop_schema) in props {
+                if Some(prop_name.as_str()) == skip {
+                    continue;
+                }
                 let ty = self.parse_type(prop_schema)?.unwrap_or(Type::Any);
                 let field_docs = prop_schema
                     .get("description")
@@ -127,11 +216,7 @@ impl Parser {
             }
         }
 
-        Ok(TypeDef {
-            name: name.to_string(),
-            docs,
-            kind: TypeDefKind::Struct(StructDef { fields }),
-        })
+        Ok(fields)
     }
 
     fn parse_enum(
@@ -170,9 +255,16 @@ impl Parser {
                     .collect(),
             )
         } else {
-            return Err(ParseError::Unsupported(
-                "mixed-type enums not supported".into(),
-            ));
+            // Mixed values (or a nullable enum) become a union of literals
+            let literals = arr
+                .iter()
+                .map(|v| self.parse_const(v))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(TypeDef {
+                name: name.to_string(),
+                docs,
+                kind: TypeDefKind::Alias(union_of(literals).unwrap_or(Type::Any)),
+            });
         };
 
         Ok(TypeDef {
@@ -185,7 +277,7 @@ impl Parser {
     fn parse_tagged_union(
         &mut self,
         name: &str,
-        one_of: &Value,
+        members: &Value,
         discriminator: &Value,
         docs: Option<String>,
     ) -> Result<TypeDef, ParseError> {
@@ -196,56 +288,61 @@ impl Parser {
                 ParseError::Unsupported("discriminator must have propertyName".into())
             })?;
 
-        let variants_arr = one_of
+        // OpenAPI `mapping`: tag value → `$ref` of the variant schema
+        let mapping: Vec<(&str, &str)> = discriminator
+            .get("mapping")
+            .and_then(|m| m.as_object())
+            .map(|m| {
+                m.iter()
+                    .filter_map(|(tag, r)| Some((tag.as_str(), r.as_str()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let variants_arr = members
             .as_array()
             .ok_or_else(|| ParseError::Unsupported("oneOf must be an array".into()))?;
 
         let mut variants = Vec::new();
 
         for variant_schema in variants_arr {
-            // Get the discriminator value from const or enum
-            let tag = variant_schema
+            // Variants may be `$ref`s or `allOf` compositions; flatten them first
+            let merged = self.merge_all_of(variant_schema)?;
+            let ref_path = variant_schema.get("$ref").and_then(|r| r.as_str());
+
+            // Get the discriminator value from const or enum, then from the
+            // mapping or the name of the referenced schema
+            let tag = merged
                 .pointer(&format!("/properties/{}/const", disc_prop))
                 .and_then(|c| c.as_str())
                 .or_else(|| {
-                    variant_schema
+                    merged
                         .pointer(&format!("/properties/{}/enum/0", disc_prop))
                         .and_then(|e| e.as_str())
                 })
+                .or_else(|| {
+                    let ref_path = ref_path?;
+                    mapping
+                        .iter()
+                        .find(|(_, r)| *r == ref_path)
+                        .map(|(tag, _)| *tag)
+                        .or_else(|| Some(ref_name(ref_path)))
+                })
                 .ok_or_else(|| {
                     ParseError::Unsupported("variant must have discriminator const/enum".into())
-                })?;
+                })?
+                .to_string();
 
-            let variant_docs = variant_schema
+            let variant_docs = merged
                 .get("description")
                 .and_then(|d| d.as_str())
                 .map(String::from);
 
             // Parse fields (excluding discriminator)
-            let mut fields = Vec::new();
-            let required: Vec<&str> = variant_schema
-                .get("required")
-                .and_then(|r| r.as_array())
-                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                .unwrap_or_default();
-
-            if let Some(props) = variant_schema.get("properties").and_then(|p| p.as_object()) {
-                for (prop_name, prop_schema) in props {
-                    if prop_name == disc_prop {
-                        continue; // Skip discriminator field
-                    }
-                    let ty = self.parse_type(prop_schema)?.unwrap_or(Type::Any);
-                    let field = if required.contains(&prop_name.as_str()) {
-                        Field::required(prop_name.clone(), ty)
-                    } else {
-                        Field::optional(prop_name.clone(), ty)
-                    };
-                    fields.push(field);
-                }
-            }
+            let fields = self.parse_fields(&merged, Some(disc_prop))?;
 
             variants.push(TaggedVariant {
-                tag: tag.to_string(),
+                tag,
                 fields,
                 docs: variant_docs,
             });
@@ -263,11 +360,94 @@ impl Parser {
         })
     }
 
+    /// Follow `$ref`s until reaching a schema that is not itself a reference.
+    fn resolve<'v>(&self, mut schema: &'v Value) -> Result<&'v Value, ParseError>
+    where
+        'a: 'v,
+    {
+        for _ in 0..MAX_DEPTH {
+            let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) else {
+                return Ok(schema);
+            };
+            schema = self
+                .lookup(ref_path)
+                .ok_or_else(|| ParseError::Unsupported(format!("unresolved $ref {}", ref_path)))?;
+        }
+        Err(ParseError::Unsupported("$ref chain too deep".into()))
+    }
+
+    /// Find the schema a `$ref` points at: as a JSON pointer into the document,
+    /// or by its last segment in `$defs`/`definitions`.
+    fn lookup(&self, ref_path: &str) -> Option<&'a Value> {
+        if let Some(pointer) = ref_path.strip_prefix('#')
+            && let Some(target) = self.root.pointer(pointer)
+        {
+            return Some(target);
+        }
+        let name = ref_name(ref_path);
+        ["$defs", "definitions"]
+            .iter()
+            .find_map(|key| self.root.get(key)?.get(name))
+    }
+
+    /// Merge a schema with its `allOf` members (resolving `$ref`s) into a single
+    /// object schema holding every property and `required` entry.
+    fn merge_all_of(&self, schema: &Value) -> Result<Value, ParseError> {
+        let resolved = self.resolve(schema)?;
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        self.collect_all_of(resolved, &mut properties, &mut required, 0)?;
+
+        let mut merged = Map::new();
+        merged.insert("type".into(), Value::from("object"));
+        if let Some(description) = schema
+            .get("description")
+            .or_else(|| resolved.get("description"))
+        {
+            merged.insert("description".into(), description.clone());
+        }
+        merged.insert("properties".into(), Value::Object(properties));
+        merged.insert("required".into(), Value::Array(required));
+        Ok(Value::Object(merged))
+    }
+
+    fn collect_all_of(
+        &self,
+        schema: &Value,
+        properties: &mut Map<String, Value>,
+        required: &mut Vec<Value>,
+        depth: usize,
+    ) -> Result<(), ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(ParseError::Unsupported("allOf nesting too deep".into()));
+        }
+        let schema = self.resolve(schema)?;
+
+        // Members first, so a schema's own properties refine the ones it extends
+        if let Some(members) = schema.get("allOf").and_then(|a| a.as_array()) {
+            for member in members {
+                self.collect_all_of(member, properties, required, depth + 1)?;
+            }
+        }
+        if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (name, prop) in props {
+                properties.insert(name.clone(), prop.clone());
+            }
+        }
+        if let Some(names) = schema.get("required").and_then(|r| r.as_array()) {
+            for name in names {
+                if !required.contains(name) {
+                    required.push(name.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn parse_type(&mut self, schema: &Value) -> Result<Option<Type>, ParseError> {
         // Handle $ref
         if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-            let type_name = ref_path.rsplit('/').next().unwrap_or(ref_path);
-            return Ok(Some(Type::Ref(type_name.to_string())));
+            return Ok(Some(Type::Ref(ref_name(ref_path).to_string())));
         }
 
         // Handle const
@@ -275,20 +455,22 @@ impl Parser {
             return Ok(Some(self.parse_const(const_val)?));
         }
 
-        // Handle type array (union with null)
+        // Handle inline enum (union of literals)
+        if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+            let literals = values
+                .iter()
+                .map(|v| self.parse_const(v))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(union_of(literals));
+        }
+
+        // Handle type array (union, optional when it includes null)
         if let Some(arr) = schema.get("type").and_then(|t| t.as_array()) {
-            let types: Vec<&str> = arr.iter().filter_map(|v| v.as_str()).collect();
-            let has_null = types.contains(&"null");
-            let non_null: Vec<_> = types.iter().filter(|t| **t != "null").collect();
-
-            if non_null.len() == 1 {
-                let base = self.parse_type_string(non_null[0], schema)?;
-                return Ok(Some(if has_null {
-                    Type::Optional(Box::new(base))
-                } else {
-                    base
-                }));
+            let mut types = Vec::new();
+            for type_str in arr.iter().filter_map(|v| v.as_str()) {
+                types.push(self.parse_type_string(type_str, schema)?);
             }
+            return Ok(union_of(types));
         }
 
         // Handle type string
@@ -300,20 +482,14 @@ impl Parser {
         if let Some(any_of) = schema.get("anyOf").or_else(|| schema.get("oneOf"))
             && let Some(arr) = any_of.as_array()
         {
-            let types: Vec<Type> = arr
-                .iter()
-                .filter_map(|s| self.parse_type(s).ok().flatten())
-                .collect();
-            if types.len() == 1 {
-                // normalize-syntax-allow: rust/unwrap-in-impl - len() == 1 guarantees next() is Some
-                return Ok(Some(types.into_iter().next().unwrap()));
-            }
-            if !types.is_empty() {
-                return Ok(Some(Type::Union(types)));
+            let mut types = Vec::new();
+            for member in arr {
+                types.push(self.parse_type(member)?.unwrap_or(Type::Any));
             }
+            return Ok(union_of(types));
         }
 
-        // Handle allOf (intersection - flatten into first type for now)
+        // Handle allOf (an inline intersection has no name to merge into, so use the first member)
         if let Some(all_of) = schema.get("allOf")
             && let Some(arr) = all_of.as_array()
             && let Some(first) = arr.first()
@@ -385,6 +561,39 @@ impl Parser {
     }
 }
 
+/// The type name a `$ref` points at (its last path segment).
+fn ref_name(ref_path: &str) -> &str {
+    ref_path.rsplit('/').next().unwrap_or(ref_path)
+}
+
+/// Build a union from `types`: nested unions are flattened, `null` members turn
+/// the result into [`Type::Optional`], and a single remaining member stands alone.
+fn union_of(types: Vec<Type>) -> Option<Type> {
+    let mut nullable = false;
+    let mut members = Vec::new();
+    for ty in types {
+        match ty {
+            Type::Null => nullable = true,
+            Type::Optional(inner) => {
+                nullable = true;
+                members.push(*inner);
+            }
+            Type::Union(inner) => members.extend(inner),
+            other => members.push(other),
+        }
+    }
+    let ty = match members.len() {
+        0 => return nullable.then_some(Type::Null),
+        1 => members.remove(0),
+        _ => Type::Union(members),
+    };
+    Some(if nullable {
+        Type::Optional(Box::new(ty))
+    } else {
+        ty
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -470,4 +679,180 @@ mod tests {
             assert!(matches!(&status_field.ty, Type::Ref(name) if name == "Status"));
         }
     }
+
+    #[test]
+    fn parse_all_of_merges_properties() {
+        let input = json!({
+            "definitions": {
+                "Base": {
+                    "type": "object",
+                    "properties": { "id": { "type": "string" } },
+                    "required": ["id"]
+                },
+                "Dog": {
+                    "description": "A dog",
+                    "allOf": [
+                        { "$ref": "#/definitions/Base" },
+                        {
+                            "properties": { "barks": { "type": "boolean" } },
+                            "required": ["barks"]
+                        }
+                    ]
+                }
+            }
+        });
+
+        let schema = parse_json_schema(&input).unwrap();
+        let dog = schema.definitions.iter().find(|d| d.name == "Dog").unwrap();
+        assert_eq!(dog.docs.as_deref(), Some("A dog"));
+        let TypeDefKind::Struct(s) = &dog.kind else {
+            panic!("expected struct");
+        };
+        let mut names: Vec<_> = s.fields.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["barks", "id"]);
+        assert!(s.fields.iter().all(|f| f.required));
+    }
+
+    #[test]
+    fn parse_untagged_union_hoists_inline_objects() {
+        let input = json!({
+            "$defs": {
+                "Circle": {
+                    "type": "object",
+                    "properties": { "radius": { "type": "number" } }
+                },
+                "Shape": {
+                    "oneOf": [
+                        { "$ref": "#/$defs/Circle" },
+                        {
+                            "type": "object",
+                            "properties": { "side": { "type": "number" } }
+                        },
+                        { "type": "null" }
+                    ]
+                }
+            }
+        });
+
+        let schema = parse_json_schema(&input).unwrap();
+        let shape = schema
+            .definitions
+            .iter()
+            .find(|d| d.name == "Shape")
+            .unwrap();
+        let TypeDefKind::Alias(Type::Optional(inner)) = &shape.kind else {
+            panic!("expected nullable alias, got {:?}", shape.kind);
+        };
+        let Type::Union(members) = inner.as_ref() else {
+            panic!("expected union");
+        };
+        assert!(matches!(&members[0], Type::Ref(n) if n == "Circle"));
+        assert!(matches!(&members[1], Type::Ref(n) if n == "ShapeVariant2"));
+        assert!(
+            schema
+                .definitions
+                .iter()
+                .any(|d| d.name == "ShapeVariant2" && matches!(d.kind, TypeDefKind::Struct(_)))
+        );
+        assert!(schema.validate().is_empty());
+    }
+
+    #[test]
+    fn parse_discriminated_union_of_refs() {
+        let input = json!({
+            "$defs": {
+                "Cat": {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "lives": { "type": "integer" }
+                    },
+                    "required": ["kind"]
+                },
+                "Dog": {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "barks": { "type": "boolean" }
+                    }
+                },
+                "Pet": {
+                    "anyOf": [
+                        { "$ref": "#/$defs/Cat" },
+                        { "$ref": "#/$defs/Dog" }
+                    ],
+                    "discriminator": {
+                        "propertyName": "kind",
+                        "mapping": { "cat": "#/$defs/Cat" }
+                    }
+                }
+            }
+        });
+
+        let schema = parse_json_schema(&input).unwrap();
+        let pet = schema.definitions.iter().find(|d| d.name == "Pet").unwrap();
+        let TypeDefKind::Enum(EnumDef {
+            kind: EnumKind::Tagged(tagged),
+        }) = &pet.kind
+        else {
+            panic!("expected tagged union");
+        };
+        assert_eq!(tagged.discriminator, "kind");
+        // Tag from the mapping, then from the referenced schema's name
+        assert_eq!(tagged.variants[0].tag, "cat");
+        assert_eq!(tagged.variants[1].tag, "Dog");
+        assert_eq!(tagged.variants[0].fields.len(), 1);
+        assert_eq!(tagged.variants[0].fields[0].name, "lives");
+    }
+
+    #[test]
+    fn parse_inline_enum_and_const() {
+        let input = json!({
+            "type": "object",
+            "title": "Config",
+            "properties": {
+                "mode": { "type": "string", "enum": ["fast", "safe"] },
+                "level": { "enum": [1, "max", null] },
+                "version": { "const": 2 },
+                "id": { "type": ["string", "integer"] }
+            }
+        });
+
+        let schema = parse_json_schema(&input).unwrap();
+        let TypeDefKind::Struct(s) = &schema.definitions[0].kind else {
+            panic!("expected struct");
+        };
+        let field = |name: &str| &s.fields.iter().find(|f| f.name == name).unwrap().ty;
+        assert!(matches!(field("mode"), Type::Union(m) if m.len() == 2));
+        assert!(
+            matches!(field("level"), Type::Optional(inner) if matches!(inner.as_ref(), Type::Union(m) if m.len() == 2))
+        );
+        assert!(matches!(field("version"), Type::IntLiteral(2)));
+        assert!(matches!(field("id"), Type::Union(m) if m.len() == 2));
+    }
+
+    #[test]
+    fn parse_mixed_enum_definition() {
+        let input = json!({ "$defs": { "Size": { "enum": ["auto", 0] } } });
+
+        let schema = parse_json_schema(&input).unwrap();
+        assert!(matches!(
+            &schema.definitions[0].kind,
+            TypeDefKind::Alias(Type::Union(m)) if m.len() == 2
+        ));
+    }
+
+    #[test]
+    fn reject_cyclic_ref() {
+        let input = json!({
+            "$defs": {
+                "A": { "$ref": "#/$defs/B" },
+                "B": { "$ref": "#/$defs/A" },
+                "C": { "allOf": [{ "$ref": "#/$defs/A" }] }
+            }
+        });
+
+        assert!(parse_json_schema(&input).is_err());
+    }
 }
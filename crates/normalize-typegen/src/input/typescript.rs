@@ -44,97 +44,35 @@ impl<'a> ExtractContext<'a> {
     fn extract_schema(&self, tree: &Tree) -> Result<Schema, ParseError> {
         let root = tree.root_node();
         let mut schema = Schema::new();
-        let mut pending_comment: Option<String> = None;
 
         let mut cursor = root.walk();
         for child in root.children(&mut cursor) {
-            match child.kind() {
-                "comment" => {
-                    pending_comment = self.extract_doc_comment(child);
-                }
-                "interface_declaration" => {
-                    let mut def = self.extract_interface(child)?;
-                    if def.docs.is_none() {
-                        def.docs = pending_comment.take();
-                    }
-                    schema.add(def);
-                    pending_comment = None;
-                }
-                "type_alias_declaration" => {
-                    let mut def = self.extract_type_alias(child)?;
-                    if def.docs.is_none() {
-                        def.docs = pending_comment.take();
-                    }
-                    schema.add(def);
-                    pending_comment = None;
-                }
-                "enum_declaration" => {
-                    let mut def = self.extract_enum(child)?;
-                    if def.docs.is_none() {
-                        def.docs = pending_comment.take();
-                    }
-                    schema.add(def);
-                    pending_comment = None;
-                }
-                "export_statement" => {
-                    if let Some(decl) = child.child_by_field_name("declaration") {
-                        match decl.kind() {
-                            "interface_declaration" => {
-                                let mut def = self.extract_interface(decl)?;
-                                if def.docs.is_none() {
-                                    def.docs = pending_comment.take();
-                                }
-                                schema.add(def);
-                            }
-                            "type_alias_declaration" => {
-                                let mut def = self.extract_type_alias(decl)?;
-                                if def.docs.is_none() {
-                                    def.docs = pending_comment.take();
-                                }
-                                schema.add(def);
-                            }
-                            "enum_declaration" => {
-                                let mut def = self.extract_enum(decl)?;
-                                if def.docs.is_none() {
-                                    def.docs = pending_comment.take();
-                                }
-                                schema.add(def);
-                            }
-                            _ => {}
-                        }
-                    }
-                    pending_comment = None;
-                }
-                _ => {
-                    pending_comment = None;
-                }
-            }
+            // Exported declarations carry their JSDoc before the `export`.
+            let decl = match child.kind() {
+                "export_statement" => match child.child_by_field_name("declaration") {
+                    Some(decl) => decl,
+                    None => continue,
+                },
+                _ => child,
+            };
+            let mut def = match decl.kind() {
+                "interface_declaration" => self.extract_interface(decl)?,
+                "type_alias_declaration" => self.extract_type_alias(decl)?,
+                "enum_declaration" => self.extract_enum(decl)?,
+                _ => continue,
+            };
+            if def.docs.is_none() {
+                def.docs = self.doc_comment(child);
+            }
+            schema.add(def);
         }
 
         Ok(schema)
     }
 
-    fn extract_doc_comment(&self, node: Node) -> Option<String> {
-        let text = self.node_text(node);
-        if text.starts_with("/**") {
-            // JSDoc comment - strip delimiters and leading asterisks
-            let inner = text
-                .strip_prefix("/**")
-                .and_then(|s| s.strip_suffix("*/"))
-                .unwrap_or(text);
-            let lines: Vec<&str> = inner
-                .lines()
-                .map(|line| line.trim().trim_start_matches('*').trim())
-                .filter(|line| !line.is_empty())
-                .collect();
-            if lines.is_empty() {
-                None
-            } else {
-                Some(lines.join(" "))
-            }
-        } else {
-            None
-        }
+    /// JSDoc attached to `node`, via the shared ECMAScript doc-comment extractor.
+    fn doc_comment(&self, node: Node) -> Option<String> {
+        normalize_languages::ecmascript::extract_jsdoc(&node, self.source)
     }
 
     fn extract_interface(&self, node: Node) -> Result<TypeDef, ParseError> {
@@ -158,25 +96,15 @@ impl<'a> ExtractContext<'a> {
 
     fn extract_interface_body(&self, body: Node) -> Result<Vec<Field>, ParseError> {
         let mut fields = Vec::new();
-        let mut pending_comment: Option<String> = None;
         let mut cursor = body.walk();
 
         for child in body.children(&mut cursor) {
-            match child.kind() {
-                "comment" => {
-                    pending_comment = self.extract_doc_comment(child);
-                }
-                "property_signature" => {
-                    let mut field = self.extract_property_signature(child)?;
-                    if field.docs.is_none() {
-                        field.docs = pending_comment.take();
-                    }
-                    fields.push(field);
-                    pending_comment = None;
-                }
-                _ => {
-                    pending_comment = None;
+            if child.kind() == "property_signature" {
+                let mut field = self.extract_property_signature(child)?;
+                if field.docs.is_none() {
+                    field.docs = self.doc_comment(child);
                 }
+                fields.push(field);
             }
         }
 
@@ -832,6 +760,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_doc_comment_on_export() {
+        let schema = parse_typescript_types(
+            r#"
+            /**
+             * Order status.
+             */
+            export enum Status { Open = "open" }
+
+            // not a doc comment
+            export type Id = string;
+            "#,
+        )
+        .unwrap();
+        assert_eq!(schema.definitions[0].docs.as_deref(), Some("Order status."));
+        assert_eq!(schema.definitions[1].docs, None);
+    }
+
     #[test]
     fn test_type_ref() {
         let schema = parse_typescript_types(
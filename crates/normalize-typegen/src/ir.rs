@@ -5,6 +5,10 @@
 
 use serde::{Deserialize, Serialize};
 
+mod diff;
+
+pub use diff::{SchemaChange, SchemaDiff, Severity, diff};
+
 /// A complete schema containing multiple type definitions.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Schema {
@@ -154,7 +158,7 @@ pub struct TaggedVariant {
 }
 
 /// A type reference.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     // Primitives
     String,
@@ -318,6 +322,23 @@ impl Schema {
 
         errors
     }
+
+    /// Whether any type in the schema (including nested ones) satisfies `pred`.
+    ///
+    /// Backends use this to decide which imports or helpers a document needs.
+    pub fn contains_type(&self, pred: impl Fn(&Type) -> bool + Copy) -> bool {
+        self.definitions.iter().any(|def| match &def.kind {
+            TypeDefKind::Struct(s) => s.fields.iter().any(|f| f.ty.contains(pred)),
+            TypeDefKind::Enum(e) => match &e.kind {
+                EnumKind::Tagged(tagged) => tagged
+                    .variants
+                    .iter()
+                    .any(|v| v.fields.iter().any(|f| f.ty.contains(pred))),
+                _ => false,
+            },
+            TypeDefKind::Alias(ty) => ty.contains(pred),
+        })
+    }
 }
 
 fn is_valid_identifier(s: &str) -> bool {
@@ -454,6 +475,138 @@ fn dfs_cycle<'a>(
     state.insert(node, DfsState::Done);
 }
 
+/// Compact notation for messages: `string`, `i64`, `User[]`, `map<string, f64>`, `"a" | "b"`.
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::String => f.write_str("string"),
+            Type::Integer { bits, signed } => {
+                write!(f, "{}{bits}", if *signed { "i" } else { "u" })
+            }
+            Type::Float { bits } => write!(f, "f{bits}"),
+            Type::Boolean => f.write_str("boolean"),
+            Type::Null => f.write_str("null"),
+            Type::Array(inner) if matches!(**inner, Type::Union(_) | Type::Optional(_)) => {
+                write!(f, "({inner})[]")
+            }
+            Type::Array(inner) => write!(f, "{inner}[]"),
+            Type::Map { key, value } => write!(f, "map<{key}, {value}>"),
+            Type::Optional(inner) => write!(f, "{inner}?"),
+            Type::Ref(name) => f.write_str(name),
+            Type::Union(types) => {
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{ty}")?;
+                }
+                Ok(())
+            }
+            Type::StringLiteral(s) => write!(f, "{s:?}"),
+            Type::IntLiteral(i) => write!(f, "{i}"),
+            Type::BoolLiteral(b) => write!(f, "{b}"),
+            Type::Any => f.write_str("any"),
+        }
+    }
+}
+
+impl Type {
+    /// Whether this type or any type nested inside it satisfies `pred`.
+    pub fn contains(&self, pred: impl Fn(&Type) -> bool + Copy) -> bool {
+        pred(self)
+            || match self {
+                Type::Array(inner) | Type::Optional(inner) => inner.contains(pred),
+                Type::Map { key, value } => key.contains(pred) || value.contains(pred),
+                Type::Union(types) => types.iter().any(|t| t.contains(pred)),
+                _ => false,
+            }
+    }
+
+    /// Whether this is a string, integer or boolean literal.
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            Type::StringLiteral(_) | Type::IntLiteral(_) | Type::BoolLiteral(_)
+        )
+    }
+
+    /// The primitive shared by all members of a union of literals (`"a" | "b"` → `String`),
+    /// for backends without literal types. `None` if the members are of mixed kinds.
+    pub fn literal_base(types: &[Type]) -> Option<Type> {
+        let base = |ty: &Type| match ty {
+            Type::StringLiteral(_) => Some(Type::String),
+            Type::IntLiteral(_) => Some(Type::Integer {
+                bits: 64,
+                signed: true,
+            }),
+            Type::BoolLiteral(_) => Some(Type::Boolean),
+            _ => None,
+        };
+        let first = base(types.first()?)?;
+        types
+            .iter()
+            .all(|t| {
+                base(t)
+                    .is_some_and(|b| std::mem::discriminant(&b) == std::mem::discriminant(&first))
+            })
+            .then_some(first)
+    }
+
+    /// Union members with literals replaced by their primitive type, keeping the first member
+    /// of each shape, for backends whose unions cannot tell literal values apart.
+    pub fn widen_literals(types: &[Type]) -> Vec<Type> {
+        let mut widened: Vec<Type> = Vec::new();
+        for ty in types {
+            let ty = Type::literal_base(std::slice::from_ref(ty)).unwrap_or_else(|| ty.clone());
+            let name = ty.variant_name();
+            if !widened.iter().any(|t| t.variant_name() == name) {
+                widened.push(ty);
+            }
+        }
+        widened
+    }
+
+    /// A PascalCase name describing this type, for naming the variants of an untagged union
+    /// in backends that need one (`Ref("Circle")` → `Circle`, `Array(String)` → `StringList`).
+    pub fn variant_name(&self) -> String {
+        match self {
+            Type::String => "String".to_string(),
+            Type::Integer { .. } => "Integer".to_string(),
+            Type::Float { .. } => "Number".to_string(),
+            Type::Boolean => "Bool".to_string(),
+            Type::Null => "Null".to_string(),
+            Type::Array(inner) => format!("{}List", inner.variant_name()),
+            Type::Map { value, .. } => format!("{}Map", value.variant_name()),
+            Type::Optional(inner) => inner.variant_name(),
+            Type::Ref(name) => name.clone(),
+            Type::Union(_) => "Union".to_string(),
+            Type::StringLiteral(s) => {
+                let mut name = String::new();
+                let mut upper = true;
+                for c in s.chars() {
+                    if !c.is_alphanumeric() {
+                        upper = true;
+                    } else if upper {
+                        name.extend(c.to_uppercase());
+                        upper = false;
+                    } else {
+                        name.push(c);
+                    }
+                }
+                if name.starts_with(|c: char| c.is_alphabetic()) {
+                    name
+                } else {
+                    format!("V{}", name)
+                }
+            }
+            Type::IntLiteral(i) if *i < 0 => format!("Neg{}", i.unsigned_abs()),
+            Type::IntLiteral(i) => format!("V{}", i),
+            Type::BoolLiteral(b) => if *b { "True" } else { "False" }.to_string(),
+            Type::Any => "Value".to_string(),
+        }
+    }
+}
+
 impl TypeDef {
     pub fn structure(name: impl Into<String>, fields: Vec<Field>) -> Self {
         Self {
@@ -485,6 +638,15 @@ impl TypeDef {
         self.docs = Some(docs.into());
         self
     }
+
+    /// Names of the types this definition refers to, sorted and deduplicated.
+    pub fn references(&self) -> Vec<String> {
+        let mut refs = Vec::new();
+        collect_type_refs_for_def(self, &mut refs);
+        refs.sort();
+        refs.dedup();
+        refs
+    }
 }
 
 impl Field {
@@ -0,0 +1,437 @@
+//! Comparing two versions of a schema.
+//!
+//! [`diff`] lists what changed between two [`Schema`]s and classifies each change as
+//! [`Severity::Breaking`] — removed types, fields and enum values, type changes, newly
+//! required or non-nullable fields — or [`Severity::Additive`]. CI can fail on the former
+//! to keep generated API types backwards compatible.
+
+use super::{EnumKind, Field, Schema, Type, TypeDef, TypeDefKind};
+use serde::Serialize;
+
+/// Whether a change can break existing code or payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "cli", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Existing consumers or payloads may no longer work.
+    Breaking,
+    /// Existing consumers and payloads keep working.
+    Additive,
+}
+
+/// One difference between two schemas.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "cli", derive(schemars::JsonSchema))]
+pub struct SchemaChange {
+    pub severity: Severity,
+    /// What changed: `User`, `User.email`, `Status.active` or `Event.login.payload`.
+    pub path: String,
+    /// Human-readable description of the change.
+    pub message: String,
+}
+
+/// The result of [`diff`]: every change, in schema order.
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(feature = "cli", derive(schemars::JsonSchema))]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Whether any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.severity == Severity::Breaking)
+    }
+
+    /// Whether the schemas are equivalent (documentation is not compared).
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes with the given severity.
+    pub fn by_severity(&self, severity: Severity) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(move |c| c.severity == severity)
+    }
+
+    fn push(&mut self, severity: Severity, path: impl Into<String>, message: impl Into<String>) {
+        self.changes.push(SchemaChange {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Compare two schemas. Types are matched by name, fields by name, enum values by value
+/// and tagged-union variants by tag.
+pub fn diff(old: &Schema, new: &Schema) -> SchemaDiff {
+    let mut out = SchemaDiff::default();
+    for old_def in &old.definitions {
+        match new.definitions.iter().find(|d| d.name == old_def.name) {
+            Some(new_def) => diff_def(old_def, new_def, &mut out),
+            None => out.push(Severity::Breaking, &old_def.name, "type removed"),
+        }
+    }
+    for new_def in &new.definitions {
+        if !old.definitions.iter().any(|d| d.name == new_def.name) {
+            out.push(Severity::Additive, &new_def.name, "type added");
+        }
+    }
+    out
+}
+
+fn diff_def(old: &TypeDef, new: &TypeDef, out: &mut SchemaDiff) {
+    let name = &old.name;
+    match (&old.kind, &new.kind) {
+        (TypeDefKind::Struct(a), TypeDefKind::Struct(b)) => {
+            diff_fields(name, &a.fields, &b.fields, out);
+        }
+        (TypeDefKind::Alias(a), TypeDefKind::Alias(b)) => {
+            if a != b {
+                out.push(
+                    Severity::Breaking,
+                    name,
+                    format!("type changed from {a} to {b}"),
+                );
+            }
+        }
+        (TypeDefKind::Enum(a), TypeDefKind::Enum(b)) => match (&a.kind, &b.kind) {
+            (EnumKind::StringLiteral(a), EnumKind::StringLiteral(b)) => diff_values(
+                name,
+                &a.iter().map(|v| v.value.clone()).collect::<Vec<_>>(),
+                &b.iter().map(|v| v.value.clone()).collect::<Vec<_>>(),
+                out,
+            ),
+            (EnumKind::IntLiteral(a), EnumKind::IntLiteral(b)) => diff_values(
+                name,
+                &a.iter().map(|v| v.value.to_string()).collect::<Vec<_>>(),
+                &b.iter().map(|v| v.value.to_string()).collect::<Vec<_>>(),
+                out,
+            ),
+            (EnumKind::Tagged(a), EnumKind::Tagged(b)) => {
+                if a.discriminator != b.discriminator {
+                    out.push(
+                        Severity::Breaking,
+                        name,
+                        format!(
+                            "discriminator changed from {:?} to {:?}",
+                            a.discriminator, b.discriminator
+                        ),
+                    );
+                }
+                for old_variant in &a.variants {
+                    let path = format!("{name}.{}", old_variant.tag);
+                    match b.variants.iter().find(|v| v.tag == old_variant.tag) {
+                        Some(new_variant) => {
+                            diff_fields(&path, &old_variant.fields, &new_variant.fields, out)
+                        }
+                        None => out.push(Severity::Breaking, path, "variant removed"),
+                    }
+                }
+                for new_variant in &b.variants {
+                    if !a.variants.iter().any(|v| v.tag == new_variant.tag) {
+                        out.push(
+                            Severity::Additive,
+                            format!("{name}.{}", new_variant.tag),
+                            "variant added",
+                        );
+                    }
+                }
+            }
+            (a, b) => out.push(
+                Severity::Breaking,
+                name,
+                format!("changed from {} to {}", enum_kind(a), enum_kind(b)),
+            ),
+        },
+        (a, b) => out.push(
+            Severity::Breaking,
+            name,
+            format!("changed from {} to {}", def_kind(a), def_kind(b)),
+        ),
+    }
+}
+
+fn diff_fields(owner: &str, old: &[Field], new: &[Field], out: &mut SchemaDiff) {
+    for old_field in old {
+        let path = format!("{owner}.{}", old_field.name);
+        let Some(new_field) = new.iter().find(|f| f.name == old_field.name) else {
+            out.push(Severity::Breaking, path, "field removed");
+            continue;
+        };
+
+        let (old_ty, old_null) = unwrap_optional(old_field);
+        let (new_ty, new_null) = unwrap_optional(new_field);
+        if old_ty != new_ty {
+            out.push(
+                Severity::Breaking,
+                &path,
+                format!("type changed from {old_ty} to {new_ty}"),
+            );
+        }
+        match (old_field.required, new_field.required) {
+            (false, true) => out.push(Severity::Breaking, &path, "field is now required"),
+            (true, false) => out.push(Severity::Additive, &path, "field is now optional"),
+            _ => {}
+        }
+        match (old_null, new_null) {
+            (true, false) => out.push(Severity::Breaking, &path, "field no longer accepts null"),
+            (false, true) => out.push(Severity::Additive, &path, "field now accepts null"),
+            _ => {}
+        }
+    }
+    for new_field in new {
+        if !old.iter().any(|f| f.name == new_field.name) {
+            let path = format!("{owner}.{}", new_field.name);
+            if new_field.required {
+                out.push(Severity::Breaking, path, "required field added");
+            } else {
+                out.push(Severity::Additive, path, "optional field added");
+            }
+        }
+    }
+}
+
+fn diff_values(owner: &str, old: &[String], new: &[String], out: &mut SchemaDiff) {
+    for value in old.iter().filter(|v| !new.contains(v)) {
+        out.push(
+            Severity::Breaking,
+            format!("{owner}.{value}"),
+            "value removed",
+        );
+    }
+    for value in new.iter().filter(|v| !old.contains(v)) {
+        out.push(
+            Severity::Additive,
+            format!("{owner}.{value}"),
+            "value added",
+        );
+    }
+}
+
+/// A field's type without its `Optional` wrapper, and whether it accepts `null`.
+fn unwrap_optional(field: &Field) -> (&Type, bool) {
+    match &field.ty {
+        Type::Optional(inner) => (inner, true),
+        ty => (ty, field.nullable),
+    }
+}
+
+fn def_kind(kind: &TypeDefKind) -> &'static str {
+    match kind {
+        TypeDefKind::Struct(_) => "struct",
+        TypeDefKind::Enum(e) => enum_kind(&e.kind),
+        TypeDefKind::Alias(_) => "alias",
+    }
+}
+
+fn enum_kind(kind: &EnumKind) -> &'static str {
+    match kind {
+        EnumKind::StringLiteral(_) => "string enum",
+        EnumKind::IntLiteral(_) => "integer enum",
+        EnumKind::Tagged(_) => "tagged union",
+    }
+}
+
+#[cfg(feature = "cli")]
+impl normalize_output::OutputFormatter for SchemaDiff {
+    fn format_text(&self) -> String {
+        if self.is_empty() {
+            return "No schema changes".to_string();
+        }
+        let breaking = self.by_severity(Severity::Breaking).count();
+        let additive = self.changes.len() - breaking;
+        let mut out = format!("{breaking} breaking, {additive} additive change(s)\n");
+        for (severity, title) in [
+            (Severity::Breaking, "Breaking"),
+            (Severity::Additive, "Additive"),
+        ] {
+            let mut changes = self.by_severity(severity).peekable();
+            if changes.peek().is_none() {
+                continue;
+            }
+            out.push_str(&format!("\n{title}:\n"));
+            for change in changes {
+                out.push_str(&format!("  {}: {}\n", change.path, change.message));
+            }
+        }
+        out
+    }
+
+    fn format_pretty(&self) -> String {
+        use nu_ansi_term::Color;
+        let text = self.format_text();
+        let mut color = None;
+        let mut out = String::new();
+        for line in text.lines() {
+            match line {
+                "Breaking:" => color = Some(Color::Red),
+                "Additive:" => color = Some(Color::Green),
+                _ => {}
+            }
+            match color {
+                Some(c) if line.starts_with("  ") => out.push_str(&c.paint(line).to_string()),
+                _ => out.push_str(line),
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{EnumDef, TaggedUnion, TaggedVariant};
+
+    fn event(variants: Vec<(&str, Vec<Field>)>) -> TypeDef {
+        TypeDef {
+            name: "Event".into(),
+            docs: None,
+            kind: TypeDefKind::Enum(EnumDef {
+                kind: EnumKind::Tagged(TaggedUnion {
+                    discriminator: "type".into(),
+                    variants: variants
+                        .into_iter()
+                        .map(|(tag, fields)| TaggedVariant {
+                            tag: tag.into(),
+                            fields,
+                            docs: None,
+                        })
+                        .collect(),
+                }),
+            }),
+        }
+    }
+
+    fn summary(diff: &SchemaDiff) -> Vec<String> {
+        diff.changes
+            .iter()
+            .map(|c| format!("{:?} {}: {}", c.severity, c.path, c.message))
+            .collect()
+    }
+
+    #[test]
+    fn identical_schemas() {
+        let mut schema = Schema::new();
+        schema.add(TypeDef::structure(
+            "User",
+            vec![Field::required("id", Type::String)],
+        ));
+        let mut documented = schema.clone();
+        documented.definitions[0].docs = Some("A user".into());
+
+        let d = diff(&schema, &documented);
+        assert!(d.is_empty());
+        assert!(!d.is_breaking());
+    }
+
+    #[test]
+    fn field_changes() {
+        let mut old = Schema::new();
+        old.add(TypeDef::structure(
+            "User",
+            vec![
+                Field::required("id", Type::String),
+                Field::required(
+                    "age",
+                    Type::Integer {
+                        bits: 32,
+                        signed: true,
+                    },
+                ),
+                Field::optional("email", Type::String),
+                Field::required("name", Type::String),
+                Field::optional("nick", Type::Optional(Box::new(Type::String))),
+            ],
+        ));
+        let mut new = Schema::new();
+        new.add(TypeDef::structure(
+            "User",
+            vec![
+                Field::required("id", Type::String),
+                Field::required(
+                    "age",
+                    Type::Integer {
+                        bits: 64,
+                        signed: true,
+                    },
+                ),
+                Field::required("email", Type::String),
+                Field::optional("name", Type::String).nullable(),
+                Field::optional("nick", Type::String),
+                Field::optional("bio", Type::String),
+                Field::required("org", Type::Ref("Org".into())),
+            ],
+        ));
+
+        let d = diff(&old, &new);
+        assert_eq!(
+            summary(&d),
+            [
+                "Breaking User.age: type changed from i32 to i64",
+                "Breaking User.email: field is now required",
+                "Additive User.name: field is now optional",
+                "Additive User.name: field now accepts null",
+                "Breaking User.nick: field no longer accepts null",
+                "Additive User.bio: optional field added",
+                "Breaking User.org: required field added",
+            ]
+        );
+        assert!(d.is_breaking());
+        assert_eq!(d.by_severity(Severity::Additive).count(), 3);
+    }
+
+    #[test]
+    fn type_and_enum_changes() {
+        let mut old = Schema::new();
+        old.add(TypeDef::string_enum("Status", vec!["active", "banned"]));
+        old.add(TypeDef::structure("Legacy", vec![]));
+        old.add(TypeDef::structure("Id", vec![]));
+        old.add(event(vec![
+            ("login", vec![Field::required("user", Type::String)]),
+            ("logout", vec![]),
+        ]));
+        let mut new = Schema::new();
+        new.add(TypeDef::string_enum("Status", vec!["active", "pending"]));
+        new.add(TypeDef {
+            name: "Id".into(),
+            docs: None,
+            kind: TypeDefKind::Alias(Type::String),
+        });
+        new.add(event(vec![
+            (
+                "login",
+                vec![Field::required("user", Type::Ref("User".into()))],
+            ),
+            ("signup", vec![]),
+        ]));
+        new.add(TypeDef::structure("User", vec![]));
+
+        assert_eq!(
+            summary(&diff(&old, &new)),
+            [
+                "Breaking Status.banned: value removed",
+                "Additive Status.pending: value added",
+                "Breaking Legacy: type removed",
+                "Breaking Id: changed from struct to alias",
+                "Breaking Event.login.user: type changed from string to User",
+                "Breaking Event.logout: variant removed",
+                "Additive Event.signup: variant added",
+                "Additive User: type added",
+            ]
+        );
+    }
+
+    #[test]
+    fn additive_only_is_not_breaking() {
+        let old = Schema::new();
+        let mut new = Schema::new();
+        new.add(TypeDef::structure("User", vec![]));
+        let d = diff(&old, &new);
+        assert!(!d.is_empty());
+        assert!(!d.is_breaking());
+    }
+}
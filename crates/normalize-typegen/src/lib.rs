@@ -59,6 +59,46 @@
 //! }
 //! ```
 //!
+//! # Multi-file Output
+//!
+//! [`GeneratedModule::split`] renders one file per type, with cross-file imports
+//! and an index module; [`GeneratedModule::write`] writes the tree and a manifest.
+//!
+//! ```ignore
+//! let module = GeneratedModule::split(&schema, "typescript", |s| {
+//!     output::generate_typescript_types(s, &TypeScriptOptions { export: true, ..Default::default() })
+//! })?;
+//! module.write(Path::new("src/types"), "typescript")?;
+//! ```
+//!
+//! # Verifying Sample Payloads
+//!
+//! [`verify_samples`] checks a directory of example JSON payloads against the IR, so you
+//! can confirm that generated validators will accept real data before shipping them.
+//!
+//! ```ignore
+//! let report = verify_samples(&schema, Path::new("samples/"), Some("User"))?;
+//! for sample in report.failures() {
+//!     for m in &sample.mismatches {
+//!         eprintln!("{}: {}", sample.file.display(), m);
+//!     }
+//! }
+//! ```
+//!
+//! # Detecting Breaking Changes
+//!
+//! [`ir::diff`] compares two versions of a schema and marks each change as breaking or
+//! additive:
+//!
+//! ```ignore
+//! let changes = ir::diff(&old, &new);
+//! if changes.is_breaking() {
+//!     for c in changes.by_severity(ir::Severity::Breaking) {
+//!         eprintln!("{}: {}", c.path, c.message);
+//!     }
+//! }
+//! ```
+//!
 //! # Feature Flags
 //!
 //! Backend flags (use `backend-*` prefix):
@@ -68,6 +108,7 @@
 //! - `backend-python` - Python dataclasses/TypedDict
 //! - `backend-pydantic` - Pydantic model generation
 //! - `backend-go` - Go structs with json tags
+//! - `backend-csharp` - C# records with System.Text.Json attributes
 //! - `backend-rust` - Rust structs with serde
 //! - `backend-jsonschema` - JSON Schema (draft 2020-12) output
 //! - `backend-graphql` - GraphQL SDL output
@@ -77,8 +118,11 @@
 //! - `typescript` - backend-typescript + backend-zod + backend-valibot
 //! - `python` - backend-python + backend-pydantic
 //! - `go` - backend-go
+//! - `csharp` - backend-csharp
 //! - `rust-types` - backend-rust
 //!
+//! `cli` - `OutputFormatter` impls for report types such as [`ir::SchemaDiff`]
+//!
 //! Input parser flags:
 //! - `input-typescript` - TypeScript source parser (requires arborium-typescript)
 //! - `input-graphql` - GraphQL SDL parser (requires arborium-graphql)
@@ -86,9 +130,11 @@
 
 pub mod input;
 pub mod ir;
+pub mod module;
 pub mod output;
 pub mod registry;
 pub mod traits;
+pub mod verify;
 
 // Re-export commonly used items
 #[cfg(feature = "input-graphql")]
@@ -100,6 +146,12 @@ pub use input::{ParseError, parse_json_schema, parse_openapi, parse_proto};
 // Re-export traits
 pub use traits::{Backend, BackendCategory};
 
+// Re-export multi-file output
+pub use module::{GeneratedFile, GeneratedModule, Manifest};
+
+// Re-export sample verification
+pub use verify::{Mismatch, Validator, VerifyReport, verify_samples};
+
 // Re-export registry functions
 pub use registry::{
     backend_names, backends, backends_by_category, backends_for_language, get_backend,
@@ -125,6 +177,9 @@ pub use output::generate_pydantic;
 #[cfg(feature = "backend-go")]
 pub use output::generate_go_types;
 
+#[cfg(feature = "backend-csharp")]
+pub use output::generate_csharp_types;
+
 #[cfg(feature = "backend-rust")]
 pub use output::generate_rust_types;
 
@@ -156,6 +211,9 @@ pub use output::pydantic::PydanticBackend;
 #[cfg(feature = "backend-go")]
 pub use output::go::GoBackend;
 
+#[cfg(feature = "backend-csharp")]
+pub use output::csharp::CSharpBackend;
+
 #[cfg(feature = "backend-rust")]
 pub use output::rust::RustBackend;
 
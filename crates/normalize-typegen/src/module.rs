@@ -0,0 +1,439 @@
+//! Multi-file output: one file per type, with cross-file imports.
+//!
+//! Backends render a [`Schema`] to a single string. For large schemas that is
+//! unwieldy, so [`GeneratedModule::split`] renders each definition on its own,
+//! adds import statements for the types it references, and (where the language
+//! has one) an index file re-exporting everything. [`GeneratedModule::write`]
+//! materializes the tree together with a `manifest.json` describing it.
+//!
+//! Import conventions are chosen by backend name:
+//!
+//! | Backend | Import | Index |
+//! |---------|--------|-------|
+//! | `typescript` | `import type { User } from "./user";` | `index.ts` |
+//! | `zod`, `valibot` | `import { UserSchema } from "./user";` | `index.ts` |
+//! | `python`, `pydantic` | `from .user import User` | `__init__.py` |
+//! | `rust` | `use super::user::User;` | `mod.rs` |
+//! | `proto` | `import "user.proto";` | — |
+//! | `go`, `graphql` | — (one package / one schema) | — |
+//!
+//! TypeScript output must be generated with `export` enabled for the imports to resolve.
+
+use crate::ir::Schema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest written alongside the generated files.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// A tree of generated files, one per type definition.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedModule {
+    /// Generated files, in schema order.
+    pub files: Vec<GeneratedFile>,
+    /// Index file re-exporting every module (`index.ts`, `__init__.py`, `mod.rs`), if any.
+    pub index: Option<GeneratedFile>,
+}
+
+/// A single generated file.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedFile {
+    /// Path relative to the output directory.
+    pub path: String,
+    /// Type definitions contained in the file.
+    pub types: Vec<String>,
+    /// Modules (file stems) this file imports from.
+    pub imports: Vec<String>,
+    /// File contents.
+    #[serde(skip)]
+    pub contents: String,
+}
+
+/// Summary of a [`GeneratedModule`], written as `manifest.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Manifest<'a> {
+    /// Backend the files were generated with.
+    pub backend: &'a str,
+    /// Every generated file, index included.
+    pub files: Vec<&'a GeneratedFile>,
+}
+
+/// How a backend's files refer to each other.
+struct Conventions {
+    extension: &'static str,
+    /// Import statement for `names` defined in `module`.
+    import: Option<fn(&str, &[String]) -> String>,
+    /// Name a type is exported under (e.g. `UserSchema` for Zod).
+    exported_name: fn(&str) -> String,
+    /// Index file name and how to render it.
+    index: Option<(&'static str, IndexRenderer)>,
+}
+
+/// Renders an index file from `(module, exported type name)` pairs.
+type IndexRenderer = fn(&[(String, String)]) -> String;
+
+fn conventions(backend: &str) -> Result<Conventions, String> {
+    let same = |name: &str| name.to_string();
+    Ok(match backend {
+        "typescript" => Conventions {
+            extension: "ts",
+            import: Some(|module, names| {
+                format!(
+                    "import type {{ {} }} from \"./{}\";",
+                    names.join(", "),
+                    module
+                )
+            }),
+            exported_name: same,
+            index: Some(("index.ts", ts_index)),
+        },
+        "zod" | "valibot" => Conventions {
+            extension: "ts",
+            import: Some(|module, names| {
+                format!("import {{ {} }} from \"./{}\";", names.join(", "), module)
+            }),
+            exported_name: |name| format!("{}Schema", name),
+            index: Some(("index.ts", ts_index)),
+        },
+        "python" | "pydantic" => Conventions {
+            extension: "py",
+            import: Some(|module, names| format!("from .{} import {}", module, names.join(", "))),
+            exported_name: same,
+            index: Some(("__init__.py", |modules| {
+                let mut out = String::from("# Auto-generated by normalize-typegen\n\n");
+                for (module, name) in modules {
+                    out.push_str(&format!("from .{} import {}\n", module, name));
+                }
+                out
+            })),
+        },
+        "rust" => Conventions {
+            extension: "rs",
+            import: Some(|module, names| match names {
+                [name] => format!("use super::{}::{};", module, name),
+                _ => format!("use super::{}::{{{}}};", module, names.join(", ")),
+            }),
+            exported_name: same,
+            index: Some(("mod.rs", |modules| {
+                let mut out = String::from("//! Auto-generated by normalize-typegen\n\n");
+                for (module, _) in modules {
+                    out.push_str(&format!("pub mod {};\n", module));
+                }
+                out.push('\n');
+                for (module, _) in modules {
+                    out.push_str(&format!("pub use {}::*;\n", module));
+                }
+                out
+            })),
+        },
+        "proto" => Conventions {
+            extension: "proto",
+            import: Some(|module, _| format!("import \"{}.proto\";", module)),
+            exported_name: same,
+            index: None,
+        },
+        "go" => Conventions {
+            extension: "go",
+            import: None,
+            exported_name: same,
+            index: None,
+        },
+        "graphql" => Conventions {
+            extension: "graphql",
+            import: None,
+            exported_name: same,
+            index: None,
+        },
+        other => {
+            return Err(format!(
+                "the {} backend does not support one file per type",
+                other
+            ));
+        }
+    })
+}
+
+fn ts_index(modules: &[(String, String)]) -> String {
+    let mut out = String::from("// Auto-generated by normalize-typegen\n\n");
+    for (module, _) in modules {
+        out.push_str(&format!("export * from \"./{}\";\n", module));
+    }
+    out
+}
+
+impl GeneratedModule {
+    /// Render each definition of `schema` into its own file.
+    ///
+    /// `backend` names the backend (as in the registry) and selects the import
+    /// conventions; `generate` renders a schema with the caller's options.
+    pub fn split(
+        schema: &Schema,
+        backend: &str,
+        generate: impl Fn(&Schema) -> String,
+    ) -> Result<Self, String> {
+        let conventions = conventions(backend)?;
+        let module_of = |name: &str| {
+            schema
+                .definitions
+                .iter()
+                .find(|d| d.name == name)
+                .map(|d| module_name(&d.name))
+        };
+
+        let mut files = Vec::new();
+        for def in &schema.definitions {
+            let module = module_name(&def.name);
+            let mut single = Schema::new();
+            single.add(def.clone());
+            let mut contents = generate(&single);
+
+            // Group referenced types by the module defining them
+            let mut imports: Vec<(String, Vec<String>)> = Vec::new();
+            for name in def.references() {
+                let Some(target) = module_of(&name) else {
+                    continue; // Not defined in this schema
+                };
+                if target == module {
+                    continue;
+                }
+                let exported = (conventions.exported_name)(&name);
+                match imports.iter_mut().find(|(m, _)| *m == target) {
+                    Some((_, names)) => names.push(exported),
+                    None => imports.push((target, vec![exported])),
+                }
+            }
+
+            if let Some(import) = conventions.import
+                && !imports.is_empty()
+            {
+                let statements: Vec<String> = imports
+                    .iter()
+                    .map(|(module, names)| import(module, names))
+                    .collect();
+                contents = insert_imports(&contents, &statements);
+            }
+
+            files.push(GeneratedFile {
+                path: format!("{}.{}", module, conventions.extension),
+                types: vec![def.name.clone()],
+                imports: imports.into_iter().map(|(module, _)| module).collect(),
+                contents,
+            });
+        }
+
+        let index = conventions.index.map(|(path, render)| {
+            let modules: Vec<(String, String)> = schema
+                .definitions
+                .iter()
+                .map(|d| (module_name(&d.name), (conventions.exported_name)(&d.name)))
+                .collect();
+            GeneratedFile {
+                path: path.to_string(),
+                types: Vec::new(),
+                imports: modules.iter().map(|(module, _)| module.clone()).collect(),
+                contents: render(&modules),
+            }
+        });
+
+        Ok(Self { files, index })
+    }
+
+    /// Describe the generated files for `manifest.json`.
+    pub fn manifest<'a>(&'a self, backend: &'a str) -> Manifest<'a> {
+        Manifest {
+            backend,
+            files: self.files.iter().chain(&self.index).collect(),
+        }
+    }
+
+    /// Write every file, the index and `manifest.json` into `dir`, creating it if needed.
+    ///
+    /// Returns the paths written, manifest last.
+    pub fn write(&self, dir: &Path, backend: &str) -> Result<Vec<PathBuf>, String> {
+        if dir.is_file() {
+            return Err(format!("{} is a file, not a directory", dir.display()));
+        }
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create directory {}: {}", dir.display(), e))?;
+
+        let mut written = Vec::new();
+        for file in self.files.iter().chain(&self.index) {
+            let path = dir.join(&file.path);
+            std::fs::write(&path, &file.contents)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            written.push(path);
+        }
+
+        let path = dir.join(MANIFEST_FILE);
+        let manifest = serde_json::to_string_pretty(&self.manifest(backend))
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        std::fs::write(&path, manifest + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path);
+
+        Ok(written)
+    }
+}
+
+/// Convert a PascalCase or camelCase type name to a snake_case module name.
+///
+/// Examples: `UserType` → `user_type`, `HTTPSConfig` → `https_config`.
+pub fn module_name(type_name: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = type_name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && chars[i - 1].is_lowercase();
+            let next_lower = i + 1 < chars.len() && chars[i + 1].is_lowercase();
+            if i > 0 && (prev_lower || next_lower) {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c == '.' || c == '-' {
+            out.push('_');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Lines that may precede our imports: the language's own imports and headers.
+const PREAMBLE_PREFIXES: &[&str] = &["import ", "from ", "use ", "package ", "syntax "];
+
+/// Insert `imports` after the generated file's preamble: its leading comment
+/// block plus the import and package lines that follow it.
+fn insert_imports(code: &str, imports: &[String]) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut end = 0;
+    let mut in_header = true;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            in_header = false;
+        } else if (in_header && (trimmed.starts_with("//") || trimmed.starts_with('#')))
+            || PREAMBLE_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+        {
+            end = i + 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut out = String::new();
+    for line in &lines[..end] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+    for import in imports {
+        out.push_str(import);
+        out.push('\n');
+    }
+    out.push('\n');
+    let rest: Vec<&str> = lines[end..]
+        .iter()
+        .copied()
+        .skip_while(|l| l.trim().is_empty())
+        .collect();
+    for line in rest {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Field, Type, TypeDef};
+
+    fn schema() -> Schema {
+        let mut schema = Schema::new();
+        schema.add(TypeDef::string_enum("Status", vec!["active", "banned"]));
+        schema.add(TypeDef::structure(
+            "UserProfile",
+            vec![
+                Field::required("status", Type::Ref("Status".into())),
+                Field::optional(
+                    "friends",
+                    Type::Array(Box::new(Type::Ref("UserProfile".into()))),
+                ),
+                Field::optional("extra", Type::Ref("External".into())),
+            ],
+        ));
+        schema
+    }
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name("UserType"), "user_type");
+        assert_eq!(module_name("HTTPSConfig"), "https_config");
+        assert_eq!(module_name("status"), "status");
+    }
+
+    #[test]
+    fn test_split_adds_imports_and_index() {
+        let module = GeneratedModule::split(&schema(), "rust", |s| {
+            format!(
+                "//! Auto-generated by normalize-typegen\n\nuse serde::{{Deserialize, Serialize}};\n\n/// doc\npub struct {} {{}}\n",
+                s.definitions[0].name
+            )
+        })
+        .unwrap();
+
+        assert_eq!(module.files.len(), 2);
+        let user = &module.files[1];
+        assert_eq!(user.path, "user_profile.rs");
+        // Self- and unknown references are not imported
+        assert_eq!(user.imports, ["status"]);
+        assert!(
+            user.contents.contains(
+                "use serde::{Deserialize, Serialize};\n\nuse super::status::Status;\n\n/// doc\n"
+            ),
+            "{}",
+            user.contents
+        );
+
+        let index = module.index.as_ref().unwrap();
+        assert_eq!(index.path, "mod.rs");
+        assert!(index.contents.contains("pub mod user_profile;\n"));
+        assert!(index.contents.contains("pub use status::*;\n"));
+    }
+
+    #[test]
+    fn test_split_uses_exported_names() {
+        let module = GeneratedModule::split(&schema(), "zod", |_| {
+            "// Auto-generated by normalize-typegen\nimport { z } from \"zod\";\n\nexport const X = 1;\n".to_string()
+        })
+        .unwrap();
+        assert!(module.files[1].contents.contains(
+            "import { z } from \"zod\";\n\nimport { StatusSchema } from \"./status\";\n\nexport const X"
+        ));
+        assert!(GeneratedModule::split(&schema(), "jsonschema", |_| String::new()).is_err());
+    }
+
+    #[test]
+    fn test_write_materializes_tree() {
+        let dir =
+            std::env::temp_dir().join(format!("normalize-typegen-module-{}", std::process::id()));
+        let module = GeneratedModule::split(&schema(), "python", |s| {
+            format!("class {}: ...\n", s.definitions[0].name)
+        })
+        .unwrap();
+        let written = module.write(&dir, "python");
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE));
+        let init = std::fs::read_to_string(dir.join("__init__.py"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written.unwrap().len(), 4);
+        let manifest: serde_json::Value = serde_json::from_str(&manifest.unwrap()).unwrap();
+        assert_eq!(manifest["backend"], "python");
+        assert_eq!(manifest["files"][1]["path"], "user_profile.py");
+        assert_eq!(manifest["files"][1]["imports"][0], "status");
+        assert!(
+            init.unwrap()
+                .contains("from .user_profile import UserProfile\n")
+        );
+    }
+}
@@ -0,0 +1,548 @@
+//! C# type definitions output backend.
+//!
+//! Generates C# records for System.Text.Json: `JsonPropertyName` attributes, `required`
+//! members and nullable reference annotations. String enums use `JsonStringEnumMemberName`
+//! (.NET 9) and tagged unions use `JsonPolymorphic` (.NET 7). C# has no type aliases, so
+//! aliases become `using` directives over fully-qualified types, and untagged unions
+//! become `JsonElement`.
+
+use crate::ir::{DefaultValue, EnumKind, Field, Schema, Type, TypeDef, TypeDefKind};
+use crate::traits::{Backend, BackendCategory};
+use std::collections::HashMap;
+
+/// Options for C# code generation.
+#[derive(Debug, Clone, Default)]
+pub struct CSharpOptions {
+    /// Namespace (file-scoped).
+    pub namespace: String,
+    /// Whether to add `JsonPropertyName` / `JsonIgnore` attributes.
+    pub json_attributes: bool,
+}
+
+impl CSharpOptions {
+    pub fn with_namespace(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            json_attributes: true,
+        }
+    }
+}
+
+/// Generate C# type definitions from an IR schema.
+pub fn generate_csharp_types(schema: &Schema, options: &CSharpOptions) -> String {
+    let namespace = if options.namespace.is_empty() {
+        "Types"
+    } else {
+        &options.namespace
+    };
+    let ctx = Context {
+        namespace,
+        aliases: schema
+            .definitions
+            .iter()
+            .filter_map(|d| match &d.kind {
+                TypeDefKind::Alias(ty) => Some((d.name.as_str(), ty)),
+                _ => None,
+            })
+            .collect(),
+    };
+
+    let mut out = String::new();
+    out.push_str("// Auto-generated by normalize-typegen\n\n");
+    out.push_str("#nullable enable\n\n");
+    out.push_str("using System.Collections.Generic;\n");
+    out.push_str("using System.Text.Json;\n");
+    out.push_str("using System.Text.Json.Serialization;\n");
+
+    // Aliases must precede the namespace, and cannot refer to each other
+    let mut aliases = String::new();
+    for def in &schema.definitions {
+        if let TypeDefKind::Alias(ty) = &def.kind {
+            aliases.push_str("using ");
+            aliases.push_str(&def.name);
+            aliases.push_str(" = ");
+            aliases.push_str(&ctx.qualified(ty, 0));
+            aliases.push_str(";\n");
+        }
+    }
+    if !aliases.is_empty() {
+        out.push('\n');
+        out.push_str(&aliases);
+    }
+
+    out.push_str("\nnamespace ");
+    out.push_str(namespace);
+    out.push_str(";\n");
+
+    for def in &schema.definitions {
+        if !matches!(def.kind, TypeDefKind::Alias(_)) {
+            out.push('\n');
+            generate_typedef(&mut out, def, options);
+        }
+    }
+
+    out
+}
+
+fn generate_typedef(out: &mut String, def: &TypeDef, options: &CSharpOptions) {
+    push_docs(out, "", def.docs.as_deref());
+
+    match &def.kind {
+        TypeDefKind::Struct(s) => {
+            out.push_str("public sealed record ");
+            out.push_str(&def.name);
+            out.push('\n');
+            push_members(out, &def.name, &s.fields, options);
+        }
+        TypeDefKind::Enum(e) => match &e.kind {
+            EnumKind::StringLiteral(variants) => {
+                if options.json_attributes {
+                    out.push_str("[JsonConverter(typeof(JsonStringEnumConverter<");
+                    out.push_str(&def.name);
+                    out.push_str(">))]\n");
+                }
+                out.push_str("public enum ");
+                out.push_str(&def.name);
+                out.push_str("\n{\n");
+                for variant in variants {
+                    push_docs(out, "    ", variant.docs.as_deref());
+                    if options.json_attributes {
+                        out.push_str("    [JsonStringEnumMemberName(\"");
+                        out.push_str(&escape_string(&variant.value));
+                        out.push_str("\")]\n");
+                    }
+                    out.push_str("    ");
+                    out.push_str(&member_name(&variant.value));
+                    out.push_str(",\n");
+                }
+                out.push_str("}\n");
+            }
+            EnumKind::IntLiteral(variants) => {
+                out.push_str("public enum ");
+                out.push_str(&def.name);
+                out.push_str(" : long\n{\n");
+                for variant in variants {
+                    push_docs(out, "    ", variant.docs.as_deref());
+                    let name = match &variant.name {
+                        Some(name) => to_pascal_case(name),
+                        None => format!("Value{}", variant.value).replace('-', "Minus"),
+                    };
+                    out.push_str("    ");
+                    out.push_str(&name);
+                    out.push_str(" = ");
+                    out.push_str(&variant.value.to_string());
+                    out.push_str(",\n");
+                }
+                out.push_str("}\n");
+            }
+            EnumKind::Tagged(tagged) => {
+                // Abstract base record; System.Text.Json writes and reads the discriminator
+                if options.json_attributes {
+                    out.push_str("[JsonPolymorphic(TypeDiscriminatorPropertyName = \"");
+                    out.push_str(&escape_string(&tagged.discriminator));
+                    out.push_str("\")]\n");
+                    for variant in &tagged.variants {
+                        out.push_str("[JsonDerivedType(typeof(");
+                        out.push_str(&def.name);
+                        out.push_str(&to_pascal_case(&variant.tag));
+                        out.push_str("), \"");
+                        out.push_str(&escape_string(&variant.tag));
+                        out.push_str("\")]\n");
+                    }
+                }
+                out.push_str("public abstract record ");
+                out.push_str(&def.name);
+                out.push_str(";\n");
+
+                for variant in &tagged.variants {
+                    let variant_name = format!("{}{}", def.name, to_pascal_case(&variant.tag));
+                    out.push('\n');
+                    push_docs(out, "", variant.docs.as_deref());
+                    out.push_str("public sealed record ");
+                    out.push_str(&variant_name);
+                    out.push_str(" : ");
+                    out.push_str(&def.name);
+                    if variant.fields.is_empty() {
+                        out.push_str(";\n");
+                    } else {
+                        out.push('\n');
+                        push_members(out, &variant_name, &variant.fields, options);
+                    }
+                }
+            }
+        },
+        TypeDefKind::Alias(_) => {}
+    }
+}
+
+fn push_members(out: &mut String, owner: &str, fields: &[Field], options: &CSharpOptions) {
+    out.push_str("{\n");
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        generate_field(out, owner, field, options);
+    }
+    out.push_str("}\n");
+}
+
+fn generate_field(out: &mut String, owner: &str, field: &Field, options: &CSharpOptions) {
+    push_docs(out, "    ", field.docs.as_deref());
+
+    // An optional field with a primitive default keeps a non-nullable type
+    let initializer = if field.required || field.nullable {
+        None
+    } else {
+        field
+            .default
+            .as_ref()
+            .and_then(|d| default_literal(&field.ty, d))
+    };
+    let nullable = initializer.is_none() && (!field.required || field.nullable);
+
+    if options.json_attributes {
+        out.push_str("    [JsonPropertyName(\"");
+        out.push_str(&escape_string(&field.name));
+        out.push_str("\")]\n");
+        if !field.required && nullable {
+            out.push_str("    [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)]\n");
+        }
+    }
+
+    out.push_str("    public ");
+    if field.required {
+        out.push_str("required ");
+    }
+    let ty = match &field.ty {
+        Type::Optional(inner) => inner.as_ref(),
+        other => other,
+    };
+    out.push_str(&type_to_csharp(ty));
+    if nullable || matches!(field.ty, Type::Optional(_)) {
+        out.push('?');
+    }
+    out.push(' ');
+
+    let mut name = to_pascal_case(&field.name);
+    if name == owner {
+        // Members cannot share the enclosing type's name
+        name.push_str("Value");
+    }
+    out.push_str(&name);
+    out.push_str(" { get; init; }");
+    if let Some(value) = initializer {
+        out.push_str(" = ");
+        out.push_str(&value);
+        out.push(';');
+    }
+    out.push('\n');
+}
+
+fn type_to_csharp(ty: &Type) -> String {
+    match ty {
+        Type::String => "string".to_string(),
+        Type::Integer { bits, signed } => integer_type(*bits, *signed).to_string(),
+        Type::Float { bits } => if *bits <= 32 { "float" } else { "double" }.to_string(),
+        Type::Boolean => "bool".to_string(),
+        Type::Null => "object?".to_string(),
+        Type::Array(inner) => format!("List<{}>", type_to_csharp(inner)),
+        Type::Map { value, .. } => format!("Dictionary<string, {}>", type_to_csharp(value)),
+        Type::Optional(inner) => format!("{}?", type_to_csharp(inner)),
+        Type::Ref(name) => name.clone(),
+        Type::Union(types) => match Type::literal_base(types) {
+            Some(base) => type_to_csharp(&base),
+            None => "JsonElement".to_string(),
+        },
+        Type::StringLiteral(_) => "string".to_string(),
+        Type::IntLiteral(_) => "long".to_string(),
+        Type::BoolLiteral(_) => "bool".to_string(),
+        Type::Any => "JsonElement".to_string(),
+    }
+}
+
+fn integer_type(bits: u8, signed: bool) -> &'static str {
+    match (bits, signed) {
+        (8, true) => "sbyte",
+        (8, false) => "byte",
+        (16, true) => "short",
+        (16, false) => "ushort",
+        (32, true) => "int",
+        (32, false) => "uint",
+        (64, false) => "ulong",
+        _ => "long",
+    }
+}
+
+/// Names visible to `using` alias targets, which see neither other aliases nor
+/// the file's own `using` directives.
+struct Context<'a> {
+    namespace: &'a str,
+    aliases: HashMap<&'a str, &'a Type>,
+}
+
+impl Context<'_> {
+    fn qualified(&self, ty: &Type, depth: usize) -> String {
+        match ty {
+            Type::Array(inner) => format!(
+                "global::System.Collections.Generic.List<{}>",
+                self.qualified(inner, depth)
+            ),
+            Type::Map { value, .. } => format!(
+                "global::System.Collections.Generic.Dictionary<string, {}>",
+                self.qualified(value, depth)
+            ),
+            // Alias targets cannot be nullable reference types
+            Type::Optional(inner) => {
+                let inner = self.qualified(inner, depth);
+                if matches!(
+                    inner.as_str(),
+                    "sbyte"
+                        | "byte"
+                        | "short"
+                        | "ushort"
+                        | "int"
+                        | "uint"
+                        | "long"
+                        | "ulong"
+                        | "float"
+                        | "double"
+                        | "bool"
+                ) {
+                    format!("{inner}?")
+                } else {
+                    inner
+                }
+            }
+            Type::Ref(name) => match self.aliases.get(name.as_str()) {
+                Some(target) if depth < 32 => self.qualified(target, depth + 1),
+                Some(_) => "global::System.Text.Json.JsonElement".to_string(),
+                None => format!("global::{}.{}", self.namespace, name),
+            },
+            Type::Union(types) => match Type::literal_base(types) {
+                Some(base) => self.qualified(&base, depth),
+                None => "global::System.Text.Json.JsonElement".to_string(),
+            },
+            Type::Null => "object".to_string(),
+            Type::Any => "global::System.Text.Json.JsonElement".to_string(),
+            other => type_to_csharp(other),
+        }
+    }
+}
+
+/// Render a default value as a C# literal, for primitive field types only.
+fn default_literal(ty: &Type, default: &DefaultValue) -> Option<String> {
+    match (ty, default) {
+        (Type::String, DefaultValue::String(s)) => Some(format!("\"{}\"", escape_string(s))),
+        (Type::Integer { .. }, DefaultValue::Number(n)) if n.fract() == 0.0 => {
+            Some(format!("{}", *n as i64))
+        }
+        (Type::Float { bits }, DefaultValue::Number(n)) => Some(if *bits <= 32 {
+            format!("{n}f")
+        } else {
+            format!("{n:?}")
+        }),
+        (Type::Boolean, DefaultValue::Bool(b)) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn push_docs(out: &mut String, indent: &str, docs: Option<&str>) {
+    let Some(docs) = docs else { return };
+    out.push_str(indent);
+    out.push_str("/// <summary>\n");
+    for line in docs.lines() {
+        out.push_str(indent);
+        out.push_str("/// ");
+        out.push_str(
+            &line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+        );
+        out.push('\n');
+    }
+    out.push_str(indent);
+    out.push_str("/// </summary>\n");
+}
+
+fn escape_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Enum member name for a string value (`in-progress` → `InProgress`, `1x` → `_1x`).
+fn member_name(value: &str) -> String {
+    let name = to_pascal_case(value);
+    match name.chars().next() {
+        None => "Empty".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{name}"),
+        Some(_) => name,
+    }
+}
+
+fn to_pascal_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize_next = true;
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Static backend instance with default options.
+pub static CSHARP_BACKEND: CSharpBackend = CSharpBackend {
+    options: CSharpOptions {
+        namespace: String::new(), // Will use "Types" as default
+        json_attributes: true,
+    },
+};
+
+/// C# backend with configurable options.
+pub struct CSharpBackend {
+    /// Generation options.
+    pub options: CSharpOptions,
+}
+
+impl CSharpBackend {
+    /// Create a new C# backend with the given options.
+    pub fn new(options: CSharpOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Backend for CSharpBackend {
+    fn name(&self) -> &'static str {
+        "csharp"
+    }
+
+    fn language(&self) -> &'static str {
+        "csharp"
+    }
+
+    fn extension(&self) -> &'static str {
+        "cs"
+    }
+
+    fn category(&self) -> BackendCategory {
+        BackendCategory::Types
+    }
+
+    fn generate(&self, schema: &Schema) -> String {
+        generate_csharp_types(schema, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Field, TypeDef};
+
+    #[test]
+    fn generate_simple_record() {
+        let mut schema = Schema::default();
+        schema.add(TypeDef::structure(
+            "User",
+            vec![
+                Field::required("id", Type::String),
+                Field::optional("email", Type::String),
+                Field::required("deletedAt", Type::String).nullable(),
+                Field::optional(
+                    "retries",
+                    Type::Integer {
+                        bits: 32,
+                        signed: true,
+                    },
+                )
+                .with_default(DefaultValue::Number(3.0)),
+                Field::required("user", Type::Array(Box::new(Type::String))),
+            ],
+        ));
+
+        let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Models"));
+
+        assert!(output.contains("#nullable enable"));
+        assert!(output.contains("namespace Models;"));
+        assert!(output.contains("public sealed record User\n{"));
+        assert!(output.contains(
+            "    [JsonPropertyName(\"id\")]\n    public required string Id { get; init; }"
+        ));
+        assert!(output.contains("    public string? Email { get; init; }"));
+        assert!(output.contains("    public required string? DeletedAt { get; init; }"));
+        assert!(output.contains("    public int Retries { get; init; } = 3;"));
+        assert!(output.contains("    public required List<string> UserValue { get; init; }"));
+    }
+
+    #[test]
+    fn generate_string_enum() {
+        let mut schema = Schema::default();
+        schema.add(TypeDef::string_enum(
+            "Status",
+            vec!["pending", "in-progress", "2fa"],
+        ));
+
+        let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Models"));
+
+        assert!(output.contains("[JsonConverter(typeof(JsonStringEnumConverter<Status>))]"));
+        assert!(
+            output.contains("    [JsonStringEnumMemberName(\"in-progress\")]\n    InProgress,")
+        );
+        assert!(output.contains("    _2fa,"));
+    }
+
+    #[test]
+    fn aliases_are_fully_qualified() {
+        let mut schema = Schema::default();
+        schema.add(TypeDef {
+            name: "Id".into(),
+            docs: None,
+            kind: TypeDefKind::Alias(Type::String),
+        });
+        schema.add(TypeDef {
+            name: "Ids".into(),
+            docs: None,
+            kind: TypeDefKind::Alias(Type::Array(Box::new(Type::Ref("Id".into())))),
+        });
+        schema.add(TypeDef {
+            name: "Owners".into(),
+            docs: None,
+            kind: TypeDefKind::Alias(Type::Map {
+                key: Box::new(Type::String),
+                value: Box::new(Type::Ref("User".into())),
+            }),
+        });
+
+        let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Models"));
+
+        assert!(output.contains("using Id = string;\n"));
+        assert!(output.contains("using Ids = global::System.Collections.Generic.List<string>;\n"));
+        assert!(output.contains(
+            "using Owners = global::System.Collections.Generic.Dictionary<string, global::Models.User>;\n"
+        ));
+    }
+
+    #[test]
+    fn generate_without_attributes() {
+        let mut schema = Schema::default();
+        schema.add(TypeDef::structure(
+            "User",
+            vec![Field::required("id", Type::String)],
+        ));
+
+        let output = generate_csharp_types(
+            &schema,
+            &CSharpOptions {
+                namespace: "Models".into(),
+                json_attributes: false,
+            },
+        );
+
+        assert!(output.contains("public required string Id { get; init; }"));
+        assert!(!output.contains("[JsonPropertyName"));
+    }
+}
@@ -171,6 +171,37 @@ fn generate_typedef(out: &mut String, def: &TypeDef, options: &GoOptions) {
             }
         },
         TypeDefKind::Alias(ty) => {
+            // A union of named types becomes a sealed interface, like tagged unions
+            let members = match ty {
+                Type::Optional(inner) => inner.as_ref(),
+                other => other,
+            };
+            if let Type::Union(types) = members {
+                let refs: Vec<&str> = types
+                    .iter()
+                    .filter_map(|t| match t {
+                        Type::Ref(name) => Some(name.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                if refs.len() == types.len() {
+                    out.push_str("type ");
+                    out.push_str(&def.name);
+                    out.push_str(" interface {\n");
+                    out.push_str("\tis");
+                    out.push_str(&def.name);
+                    out.push_str("()\n");
+                    out.push_str("}\n");
+                    for name in refs {
+                        out.push_str("\nfunc (");
+                        out.push_str(name);
+                        out.push_str(") is");
+                        out.push_str(&def.name);
+                        out.push_str("() {}\n");
+                    }
+                    return;
+                }
+            }
             out.push_str("type ");
             out.push_str(&def.name);
             out.push_str(" = ");
@@ -249,7 +280,10 @@ fn type_to_go(ty: &Type, options: &GoOptions) -> String {
             }
         }
         Type::Ref(name) => name.clone(),
-        Type::Union(_) => "any".to_string(), // Go doesn't have union types
+        Type::Union(types) => match Type::literal_base(types) {
+            Some(base) => type_to_go(&base, options),
+            None => "any".to_string(), // Go doesn't have union types
+        },
         Type::StringLiteral(_) => "string".to_string(),
         Type::IntLiteral(_) => "int".to_string(),
         Type::BoolLiteral(_) => "bool".to_string(),
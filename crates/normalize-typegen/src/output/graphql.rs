@@ -8,7 +8,8 @@
 //! - Int-literal enums → `enum` with named variants (names upper-cased; unnamed use `V<n>`)
 //! - Tagged unions → `union Foo = Bar | Baz` (one type per variant; variant types are emitted
 //!   as anonymous inline types named `<UnionName><VariantTag>`)
-//! - Type aliases → a comment noting the alias; no native SDL equivalent
+//! - Untagged unions of named types → `union Foo = Bar | Baz`
+//! - Other type aliases → a comment noting the alias; no native SDL equivalent
 //!
 //! Nullability:
 //! - `required: true, nullable: false` → `FieldType!` (non-null)
@@ -165,6 +166,28 @@ fn generate_typedef(
         },
 
         TypeDefKind::Alias(ty) => {
+            // A union of object types maps directly onto SDL (nullability is decided by use).
+            let members = match ty {
+                Type::Optional(inner) => inner.as_ref(),
+                other => other,
+            };
+            if let Type::Union(types) = members {
+                let refs: Vec<&str> = types
+                    .iter()
+                    .filter_map(|t| match t {
+                        Type::Ref(name) => Some(name.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                if refs.len() == types.len() {
+                    out.push_str("union ");
+                    out.push_str(&def.name);
+                    out.push_str(" = ");
+                    out.push_str(&refs.join(" | "));
+                    out.push('\n');
+                    return;
+                }
+            }
             // GraphQL has no type aliases; emit a comment.
             out.push_str("# ");
             out.push_str(&def.name);
@@ -211,11 +234,11 @@ fn type_to_graphql(ty: &Type, non_null: bool) -> String {
         Type::Map { .. } => format!("String{bang}"), // No native map in GraphQL; fallback.
         Type::Optional(inner) => type_to_graphql(inner, false), // Strip non-null.
         Type::Ref(name) => format!("{name}{bang}"),
-        Type::Union(types) => {
+        Type::Union(types) => match Type::literal_base(types) {
+            Some(base) => type_to_graphql(&base, non_null),
             // GraphQL union syntax is at definition level; inline unions degrade to String.
-            let _ = types;
-            format!("String{bang}")
-        }
+            None => format!("String{bang}"),
+        },
         Type::StringLiteral(_) => format!("String{bang}"),
         Type::IntLiteral(_) => format!("Int{bang}"),
         Type::BoolLiteral(_) => format!("Boolean{bang}"),
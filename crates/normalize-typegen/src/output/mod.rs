@@ -48,6 +48,13 @@ pub mod go;
 #[cfg(feature = "backend-go")]
 pub use go::{GoBackend, GoOptions, generate_go_types};
 
+// C#
+#[cfg(feature = "backend-csharp")]
+pub mod csharp;
+
+#[cfg(feature = "backend-csharp")]
+pub use csharp::{CSharpBackend, CSharpOptions, generate_csharp_types};
+
 // Rust
 #[cfg(feature = "backend-rust")]
 pub mod rust;
@@ -8,6 +8,7 @@
 //! - Int-literal enums → `enum Status { … }` using the declared integer values
 //!   (a `0`-valued entry is inserted if none exists, per proto3 requirements)
 //! - Tagged unions → a `message` with `oneof` containing one message per variant
+//! - Untagged unions → a `message` with a `oneof value` over the member types
 //! - Type aliases → a comment; proto3 has no native type aliases
 //!
 //! Field numbers are assigned sequentially starting from 1 within each message.
@@ -168,6 +169,10 @@ fn generate_typedef(out: &mut String, def: &TypeDef, helpers: &mut Vec<String>)
         },
 
         TypeDefKind::Alias(ty) => {
+            if let Some(types) = union_types(ty) {
+                generate_union_message(out, &def.name, types);
+                return;
+            }
             // proto3 has no type aliases; emit as a comment.
             out.push_str("// type ");
             out.push_str(&def.name);
@@ -178,6 +183,44 @@ fn generate_typedef(out: &mut String, def: &TypeDef, helpers: &mut Vec<String>)
     }
 }
 
+/// Emit an untagged union as a message with a `oneof` over its members
+/// (left unset when the union is nullable).
+fn generate_union_message(out: &mut String, name: &str, types: &[Type]) {
+    out.push_str("message ");
+    out.push_str(name);
+    out.push_str(" {\n");
+    out.push_str("  oneof value {\n");
+    for (idx, member) in Type::widen_literals(types).iter().enumerate() {
+        let field_name = match member {
+            Type::Ref(name) => to_snake_case(name),
+            other => format!("{}_value", to_snake_case(&other.variant_name())),
+        };
+        // `oneof` fields cannot be repeated or maps.
+        let mut field_type = type_to_proto(member);
+        if field_type.starts_with("repeated ") || field_type.starts_with("map<") {
+            field_type = "google.protobuf.Any".to_string();
+        }
+        out.push_str("    ");
+        out.push_str(&field_type);
+        out.push(' ');
+        out.push_str(&field_name);
+        out.push_str(" = ");
+        out.push_str(&(idx + 1).to_string());
+        out.push_str(";\n");
+    }
+    out.push_str("  }\n");
+    out.push_str("}\n");
+}
+
+/// The members of a (possibly nullable) union type.
+fn union_types(ty: &Type) -> Option<&[Type]> {
+    match ty {
+        Type::Union(types) => Some(types),
+        Type::Optional(inner) => union_types(inner),
+        _ => None,
+    }
+}
+
 fn generate_field(out: &mut String, field: &Field, number: usize, indent: &str) {
     if let Some(docs) = &field.docs {
         out.push_str(indent);
@@ -242,7 +285,10 @@ fn type_to_proto(ty: &Type) -> String {
         }
         Type::Optional(inner) => type_to_proto(inner), // handled at field level
         Type::Ref(name) => name.clone(),
-        Type::Union(_) => "google.protobuf.Any".to_string(), // No native union in proto3
+        Type::Union(types) => match Type::literal_base(types) {
+            Some(base) => type_to_proto(&base),
+            None => "google.protobuf.Any".to_string(), // No native union in proto3
+        },
         Type::StringLiteral(_) => "string".to_string(),
         Type::IntLiteral(_) => "int64".to_string(),
         Type::BoolLiteral(_) => "bool".to_string(),
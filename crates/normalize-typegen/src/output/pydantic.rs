@@ -44,7 +44,7 @@ pub fn generate_pydantic(schema: &Schema, options: &PydanticOptions) -> String {
         .definitions
         .iter()
         .any(|d| matches!(d.kind, TypeDefKind::Enum(_)));
-    if has_enums {
+    if has_enums || schema.contains_type(Type::is_literal) {
         typing_imports.push("Literal");
     }
     let has_unions = schema.definitions.iter().any(|d| {
@@ -53,11 +53,11 @@ pub fn generate_pydantic(schema: &Schema, options: &PydanticOptions) -> String {
             TypeDefKind::Enum(e) if matches!(e.kind, EnumKind::Tagged(_))
         )
     });
-    if has_unions {
+    if has_unions || schema.contains_type(|t| matches!(t, Type::Union(_))) {
         typing_imports.push("Union");
-        if options.version == PydanticVersion::V2 {
-            typing_imports.push("Annotated");
-        }
+    }
+    if has_unions && options.version == PydanticVersion::V2 {
+        typing_imports.push("Annotated");
     }
 
     out.push_str("from typing import ");
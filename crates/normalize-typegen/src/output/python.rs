@@ -115,8 +115,14 @@ fn collect_typing_imports(imports: &mut Vec<&'static str>, def: &TypeDef) {
 
 fn collect_type_imports(imports: &mut Vec<&'static str>, ty: &Type) {
     match ty {
-        Type::Array(_) => imports.push("list"),
-        Type::Map { .. } => imports.push("dict"),
+        Type::Array(inner) => {
+            imports.push("list");
+            collect_type_imports(imports, inner);
+        }
+        Type::Map { value, .. } => {
+            imports.push("dict");
+            collect_type_imports(imports, value);
+        }
         Type::Optional(inner) => {
             imports.push("Optional");
             collect_type_imports(imports, inner);
@@ -127,6 +133,9 @@ fn collect_type_imports(imports: &mut Vec<&'static str>, ty: &Type) {
                 collect_type_imports(imports, t);
             }
         }
+        Type::StringLiteral(_) | Type::IntLiteral(_) | Type::BoolLiteral(_) => {
+            imports.push("Literal")
+        }
         Type::Any => imports.push("Any"),
         _ => {}
     }
@@ -229,7 +238,10 @@ fn generate_typedef(out: &mut String, def: &TypeDef, options: &PythonOptions) {
                     out.push_str(&variant.tag);
                     out.push_str("\"]\n");
 
-                    for field in &variant.fields {
+                    // Required fields first, then optional
+                    let (required, optional): (Vec<_>, Vec<_>) =
+                        variant.fields.iter().partition(|f| f.required);
+                    for field in required.iter().chain(optional.iter()) {
                         generate_dataclass_field(out, field);
                     }
                     out.push('\n');
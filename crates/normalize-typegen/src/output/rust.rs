@@ -222,6 +222,10 @@ fn generate_typedef(out: &mut String, def: &TypeDef, options: &RustOptions) {
             }
         },
         TypeDefKind::Alias(ty) => {
+            if let Some((members, nullable)) = union_members(ty) {
+                generate_union_enum(out, &def.name, members, nullable, &derives, vis, options);
+                return;
+            }
             out.push_str(vis);
             out.push_str("type ");
             out.push_str(&def.name);
@@ -232,6 +236,46 @@ fn generate_typedef(out: &mut String, def: &TypeDef, options: &RustOptions) {
     }
 }
 
+/// Emit an untagged enum for a union alias, one newtype variant per member.
+fn generate_union_enum(
+    out: &mut String,
+    name: &str,
+    members: &[Type],
+    nullable: bool,
+    derives: &[&str],
+    vis: &str,
+    options: &RustOptions,
+) {
+    if !derives.is_empty() {
+        out.push_str("#[derive(");
+        out.push_str(&derives.join(", "));
+        out.push_str(")]\n");
+    }
+    if options.serde {
+        out.push_str("#[serde(untagged)]\n");
+    }
+
+    out.push_str(vis);
+    out.push_str("enum ");
+    out.push_str(name);
+    out.push_str(" {\n");
+
+    // Literals are indistinguishable to an untagged enum, so they share their base type
+    for member in Type::widen_literals(members) {
+        out.push_str("    ");
+        out.push_str(&member.variant_name());
+        out.push('(');
+        out.push_str(&type_to_rust(&member));
+        out.push_str("),\n");
+    }
+    if nullable {
+        // A unit variant matches `null` in an untagged enum
+        out.push_str("    Null,\n");
+    }
+
+    out.push_str("}\n");
+}
+
 fn generate_field(out: &mut String, field: &Field, options: &RustOptions, in_enum_variant: bool) {
     let indent = if in_enum_variant { "        " } else { "    " };
 
@@ -307,6 +351,9 @@ fn type_to_rust(ty: &Type) -> String {
                     return format!("Option<{}>", type_to_rust(non_null[0]));
                 }
             }
+            if let Some(base) = Type::literal_base(types) {
+                return type_to_rust(&base);
+            }
             "serde_json::Value".to_string() // Fallback for arbitrary unions
         }
         Type::StringLiteral(_) => "String".to_string(), // Rust doesn't have literal types
@@ -316,6 +363,19 @@ fn type_to_rust(ty: &Type) -> String {
     }
 }
 
+/// The members of a union alias that needs its own enum, and whether it is nullable.
+/// `None` for unions that map onto a plain Rust type (`T | null`, literals of one kind).
+fn union_members(ty: &Type) -> Option<(&[Type], bool)> {
+    let (inner, nullable) = match ty {
+        Type::Optional(inner) => (inner.as_ref(), true),
+        other => (other, false),
+    };
+    match inner {
+        Type::Union(types) if Type::literal_base(types).is_none() => Some((types, nullable)),
+        _ => None,
+    }
+}
+
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     for (i, c) in s.chars().enumerate() {
@@ -52,6 +52,11 @@ fn init_builtin() {
             backends.push(&crate::output::go::GO_BACKEND);
         }
 
+        #[cfg(feature = "backend-csharp")]
+        {
+            backends.push(&crate::output::csharp::CSHARP_BACKEND);
+        }
+
         #[cfg(feature = "backend-rust")]
         {
             backends.push(&crate::output::rust::RUST_BACKEND);
@@ -0,0 +1,648 @@
+//! Round-trip validation of sample payloads against a [`Schema`].
+//!
+//! Generated validators (Zod, Valibot, Pydantic) are a straight translation of the IR,
+//! so checking real payloads against the IR tells you whether the generated code will
+//! accept them — without running Node or Python. [`Validator`] checks one value;
+//! [`verify_samples`] checks every `.json` file under a directory.
+//!
+//! The checks mirror what the generated validators enforce: types, required fields,
+//! nullability, literals, enum values, tagged-union discriminators and numeric / length
+//! constraints. Unknown fields are accepted (Zod strips them, Pydantic ignores them).
+//! `pattern` and `format` constraints are not checked.
+
+use crate::ir::{EnumKind, Field, FieldConstraints, Schema, Type, TypeDef, TypeDefKind};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of alias hops followed before giving up (guards `A = B`, `B = A`).
+const MAX_DEPTH: usize = 64;
+
+/// A single place where a payload disagrees with the schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mismatch {
+    /// Location in the payload, as a JSONPath (e.g. `$.items[0].name`).
+    pub path: String,
+    /// What was expected and what was found.
+    pub message: String,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The result of checking one sample file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleReport {
+    /// The sample file.
+    pub file: PathBuf,
+    /// The type the sample was checked against (`None` if the file is not valid JSON).
+    pub type_name: Option<String>,
+    /// Mismatches found; empty when the sample is accepted.
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl SampleReport {
+    /// Whether the sample is accepted by the schema.
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// The result of [`verify_samples`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    /// One entry per sample file, sorted by path.
+    pub samples: Vec<SampleReport>,
+}
+
+impl VerifyReport {
+    /// Whether every sample is accepted.
+    pub fn passed(&self) -> bool {
+        self.samples.iter().all(SampleReport::passed)
+    }
+
+    /// Samples with at least one mismatch.
+    pub fn failures(&self) -> impl Iterator<Item = &SampleReport> {
+        self.samples.iter().filter(|s| !s.passed())
+    }
+}
+
+/// Checks JSON values against the types of a [`Schema`].
+pub struct Validator<'a> {
+    schema: &'a Schema,
+}
+
+impl<'a> Validator<'a> {
+    pub fn new(schema: &'a Schema) -> Self {
+        Self { schema }
+    }
+
+    /// Check `value` against the type named `type_name`.
+    ///
+    /// Returns an error if the schema has no such type.
+    pub fn validate(&self, type_name: &str, value: &Value) -> Result<Vec<Mismatch>, String> {
+        let def = self
+            .definition(type_name)
+            .ok_or_else(|| format!("unknown type: {type_name}"))?;
+        let mut out = Vec::new();
+        self.check_def(def, value, "$", 0, &mut out);
+        Ok(out)
+    }
+
+    /// Find the type that `value` fits best: the first definition without mismatches,
+    /// or else the one with the fewest. Returns `None` for an empty schema.
+    pub fn best_match(&self, value: &Value) -> Option<(&'a str, Vec<Mismatch>)> {
+        let mut best: Option<(&str, Vec<Mismatch>)> = None;
+        for def in &self.schema.definitions {
+            let mut out = Vec::new();
+            self.check_def(def, value, "$", 0, &mut out);
+            if out.is_empty() {
+                return Some((&def.name, out));
+            }
+            if best.as_ref().is_none_or(|(_, b)| out.len() < b.len()) {
+                best = Some((&def.name, out));
+            }
+        }
+        best
+    }
+
+    fn definition(&self, name: &str) -> Option<&'a TypeDef> {
+        self.schema.definitions.iter().find(|d| d.name == name)
+    }
+
+    fn check_def(
+        &self,
+        def: &TypeDef,
+        value: &Value,
+        path: &str,
+        depth: usize,
+        out: &mut Vec<Mismatch>,
+    ) {
+        match &def.kind {
+            TypeDefKind::Struct(s) => {
+                if value.is_object() {
+                    self.check_fields(&s.fields, value, path, depth, out);
+                } else {
+                    mismatch(out, path, format!("expected {} object", def.name), value);
+                }
+            }
+            TypeDefKind::Enum(e) => match &e.kind {
+                EnumKind::StringLiteral(variants) => {
+                    if !variants.iter().any(|v| value.as_str() == Some(&v.value)) {
+                        let names: Vec<_> =
+                            variants.iter().map(|v| format!("{:?}", v.value)).collect();
+                        mismatch(
+                            out,
+                            path,
+                            format!("expected one of {}", names.join(", ")),
+                            value,
+                        );
+                    }
+                }
+                EnumKind::IntLiteral(variants) => {
+                    if !variants.iter().any(|v| value.as_i64() == Some(v.value)) {
+                        let names: Vec<_> = variants.iter().map(|v| v.value.to_string()).collect();
+                        mismatch(
+                            out,
+                            path,
+                            format!("expected one of {}", names.join(", ")),
+                            value,
+                        );
+                    }
+                }
+                EnumKind::Tagged(union) => {
+                    if !value.is_object() {
+                        mismatch(out, path, format!("expected {} object", def.name), value);
+                        return;
+                    }
+                    let tag_path = child(path, &union.discriminator);
+                    let Some(tag) = value.get(&union.discriminator) else {
+                        out.push(Mismatch {
+                            path: tag_path,
+                            message: "missing discriminator".into(),
+                        });
+                        return;
+                    };
+                    match union.variants.iter().find(|v| tag.as_str() == Some(&v.tag)) {
+                        Some(variant) => {
+                            self.check_fields(&variant.fields, value, path, depth, out)
+                        }
+                        None => {
+                            let tags: Vec<_> = union
+                                .variants
+                                .iter()
+                                .map(|v| format!("{:?}", v.tag))
+                                .collect();
+                            mismatch(
+                                out,
+                                &tag_path,
+                                format!("expected one of {}", tags.join(", ")),
+                                tag,
+                            );
+                        }
+                    }
+                }
+            },
+            TypeDefKind::Alias(ty) => self.check_type(ty, value, path, depth + 1, out),
+        }
+    }
+
+    fn check_fields(
+        &self,
+        fields: &[Field],
+        value: &Value,
+        path: &str,
+        depth: usize,
+        out: &mut Vec<Mismatch>,
+    ) {
+        for field in fields {
+            let field_path = child(path, &field.name);
+            match value.get(&field.name) {
+                None if field.required => out.push(Mismatch {
+                    path: field_path,
+                    message: "missing required field".into(),
+                }),
+                None => {}
+                Some(Value::Null) if field.nullable => {}
+                Some(v) => {
+                    self.check_type(&field.ty, v, &field_path, depth, out);
+                    if let Some(c) = &field.constraints {
+                        check_constraints(c, v, &field_path, out);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_type(
+        &self,
+        ty: &Type,
+        value: &Value,
+        path: &str,
+        depth: usize,
+        out: &mut Vec<Mismatch>,
+    ) {
+        let ok = match ty {
+            Type::String => value.is_string(),
+            Type::Integer { bits, signed } => is_integer(value, *bits, *signed),
+            Type::Float { .. } => value.is_number(),
+            Type::Boolean => value.is_boolean(),
+            Type::Null => value.is_null(),
+            Type::Any => true,
+            Type::StringLiteral(s) => value.as_str() == Some(s),
+            Type::IntLiteral(i) => value.as_i64() == Some(*i),
+            Type::BoolLiteral(b) => value.as_bool() == Some(*b),
+            Type::Optional(inner) => {
+                if !value.is_null() {
+                    self.check_type(inner, value, path, depth, out);
+                }
+                return;
+            }
+            Type::Array(inner) => {
+                let Some(items) = value.as_array() else {
+                    return mismatch(out, path, "expected array".into(), value);
+                };
+                for (i, item) in items.iter().enumerate() {
+                    self.check_type(inner, item, &format!("{path}[{i}]"), depth, out);
+                }
+                return;
+            }
+            Type::Map { key, value: inner } => {
+                let Some(entries) = value.as_object() else {
+                    return mismatch(out, path, "expected object".into(), value);
+                };
+                for (k, v) in entries {
+                    let entry_path = child(path, k);
+                    if matches!(**key, Type::Integer { .. }) && k.parse::<i64>().is_err() {
+                        out.push(Mismatch {
+                            path: entry_path.clone(),
+                            message: "expected integer key".into(),
+                        });
+                    }
+                    self.check_type(inner, v, &entry_path, depth, out);
+                }
+                return;
+            }
+            Type::Ref(name) => {
+                match self.definition(name) {
+                    Some(_) if depth > MAX_DEPTH => out.push(Mismatch {
+                        path: path.to_string(),
+                        message: format!("type {name} is too deeply nested"),
+                    }),
+                    Some(def) => self.check_def(def, value, path, depth, out),
+                    None => out.push(Mismatch {
+                        path: path.to_string(),
+                        message: format!("unknown type {name}"),
+                    }),
+                }
+                return;
+            }
+            Type::Union(members) => members.iter().any(|m| {
+                let mut scratch = Vec::new();
+                self.check_type(m, value, path, depth, &mut scratch);
+                scratch.is_empty()
+            }),
+        };
+        if !ok {
+            mismatch(out, path, format!("expected {}", describe(ty)), value);
+        }
+    }
+}
+
+/// Check every `.json` file under `dir` (recursively) against `schema`.
+///
+/// Each sample is checked against `type_name` when given. Otherwise the type is taken
+/// from the file name — `user.json` and `order_item.invalid.json` check against `User`
+/// and `OrderItem` — falling back to the best-matching definition.
+pub fn verify_samples(
+    schema: &Schema,
+    dir: &Path,
+    type_name: Option<&str>,
+) -> Result<VerifyReport, String> {
+    if let Some(name) = type_name
+        && !schema.definitions.iter().any(|d| d.name == name)
+    {
+        return Err(format!("unknown type: {name}"));
+    }
+
+    let mut files = Vec::new();
+    collect_json_files(dir, &mut files)?;
+    files.sort();
+
+    let validator = Validator::new(schema);
+    let mut report = VerifyReport::default();
+    for file in files {
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        let value: Value = match serde_json::from_str(&content) {
+            Ok(v) => v,
+            Err(e) => {
+                report.samples.push(SampleReport {
+                    file,
+                    type_name: None,
+                    mismatches: vec![Mismatch {
+                        path: "$".into(),
+                        message: format!("invalid JSON: {e}"),
+                    }],
+                });
+                continue;
+            }
+        };
+
+        let named = type_name.or_else(|| type_for_file(schema, &file));
+        let (name, mismatches) = match named {
+            Some(name) => (name, validator.validate(name, &value)?),
+            None => validator
+                .best_match(&value)
+                .ok_or_else(|| "schema has no type definitions".to_string())?,
+        };
+        report.samples.push(SampleReport {
+            file,
+            type_name: Some(name.to_string()),
+            mismatches,
+        });
+    }
+    Ok(report)
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .path();
+        if path.is_dir() {
+            collect_json_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The type named by the first dot-separated segment of the file name, if any.
+fn type_for_file<'a>(schema: &'a Schema, file: &Path) -> Option<&'a str> {
+    let stem = file.file_name()?.to_str()?.split('.').next()?;
+    let key = normalize_name(stem);
+    schema
+        .definitions
+        .iter()
+        .find(|d| normalize_name(&d.name) == key)
+        .map(|d| d.name.as_str())
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn check_constraints(c: &FieldConstraints, value: &Value, path: &str, out: &mut Vec<Mismatch>) {
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = c.min
+            && n < min
+        {
+            mismatch(out, path, format!("expected a value >= {min}"), value);
+        }
+        if let Some(max) = c.max
+            && n > max
+        {
+            mismatch(out, path, format!("expected a value <= {max}"), value);
+        }
+    }
+    let len = match value {
+        Value::String(s) => s.chars().count() as u64,
+        Value::Array(a) => a.len() as u64,
+        _ => return,
+    };
+    if let Some(min) = c.min_length
+        && len < min
+    {
+        mismatch(out, path, format!("expected length >= {min}"), value);
+    }
+    if let Some(max) = c.max_length
+        && len > max
+    {
+        mismatch(out, path, format!("expected length <= {max}"), value);
+    }
+}
+
+fn is_integer(value: &Value, bits: u8, signed: bool) -> bool {
+    // JSON has one number type; `1.0` is an integer to Zod and to Pydantic's lax mode.
+    let n = match value {
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 => f,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    if bits >= 64 {
+        return signed || n >= 0.0;
+    }
+    let (min, max) = if signed {
+        (
+            -(2f64.powi(bits as i32 - 1)),
+            2f64.powi(bits as i32 - 1) - 1.0,
+        )
+    } else {
+        (0.0, 2f64.powi(bits as i32) - 1.0)
+    };
+    (min..=max).contains(&n)
+}
+
+fn mismatch(out: &mut Vec<Mismatch>, path: &str, expected: String, found: &Value) {
+    out.push(Mismatch {
+        path: path.to_string(),
+        message: format!("{expected}, found {}", kind_of(found)),
+    });
+}
+
+fn child(path: &str, key: &str) -> String {
+    let is_ident = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_ident {
+        format!("{path}.{key}")
+    } else {
+        format!("{path}[{key:?}]")
+    }
+}
+
+fn kind_of(value: &Value) -> String {
+    match value {
+        Value::Null => "null".into(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) if s.chars().count() <= 32 => format!("{s:?}"),
+        Value::String(_) => "string".into(),
+        Value::Array(_) => "array".into(),
+        Value::Object(_) => "object".into(),
+    }
+}
+
+fn describe(ty: &Type) -> String {
+    match ty {
+        Type::String => "string".into(),
+        Type::Integer { bits, signed } => {
+            format!("{}{bits} integer", if *signed { "i" } else { "u" })
+        }
+        Type::Float { .. } => "number".into(),
+        Type::Boolean => "boolean".into(),
+        Type::Null => "null".into(),
+        Type::Array(_) => "array".into(),
+        Type::Map { .. } => "object".into(),
+        Type::Optional(inner) => format!("{} or null", describe(inner)),
+        Type::Ref(name) => name.clone(),
+        Type::Union(members) => members.iter().map(describe).collect::<Vec<_>>().join(" | "),
+        Type::StringLiteral(s) => format!("{s:?}"),
+        Type::IntLiteral(i) => i.to_string(),
+        Type::BoolLiteral(b) => b.to_string(),
+        Type::Any => "any value".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{EnumDef, TaggedUnion, TaggedVariant};
+    use serde_json::json;
+
+    fn schema() -> Schema {
+        let mut schema = Schema::new();
+        schema.add(TypeDef::structure(
+            "User",
+            vec![
+                Field::required(
+                    "id",
+                    Type::Integer {
+                        bits: 32,
+                        signed: false,
+                    },
+                ),
+                Field::required("name", Type::String).with_constraints(FieldConstraints {
+                    min_length: Some(1),
+                    ..Default::default()
+                }),
+                Field::optional("email", Type::String).nullable(),
+                Field::optional("tags", Type::Array(Box::new(Type::String))),
+                Field::optional("status", Type::Ref("Status".into())),
+            ],
+        ));
+        schema.add(TypeDef::string_enum("Status", vec!["active", "banned"]));
+        schema.add(TypeDef {
+            name: "Event".into(),
+            docs: None,
+            kind: TypeDefKind::Enum(EnumDef {
+                kind: EnumKind::Tagged(TaggedUnion {
+                    discriminator: "type".into(),
+                    variants: vec![TaggedVariant {
+                        tag: "login".into(),
+                        fields: vec![Field::required("user", Type::Ref("User".into()))],
+                        docs: None,
+                    }],
+                }),
+            }),
+        });
+        schema
+    }
+
+    #[test]
+    fn accepts_valid_payload() {
+        let schema = schema();
+        let v = Validator::new(&schema);
+        let user = json!({ "id": 1, "name": "ada", "email": null, "tags": ["a"], "extra": 1 });
+        assert_eq!(v.validate("User", &user).unwrap(), []);
+        let event = json!({ "type": "login", "user": { "id": 2.0, "name": "bob" } });
+        assert_eq!(v.validate("Event", &event).unwrap(), []);
+    }
+
+    #[test]
+    fn reports_paths_and_messages() {
+        let schema = schema();
+        let v = Validator::new(&schema);
+        let user = json!({ "id": -1, "name": "", "tags": ["a", 2], "status": "gone" });
+        let got: Vec<String> = v
+            .validate("User", &user)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            got,
+            [
+                "$.id: expected u32 integer, found -1",
+                "$.name: expected length >= 1, found \"\"",
+                "$.tags[1]: expected string, found 2",
+                "$.status: expected one of \"active\", \"banned\", found \"gone\"",
+            ]
+        );
+
+        let event = json!({ "type": "login", "user": { "name": "x" } });
+        assert_eq!(
+            v.validate("Event", &event).unwrap(),
+            [Mismatch {
+                path: "$.user.id".into(),
+                message: "missing required field".into()
+            }]
+        );
+        let event = json!({ "type": "logout" });
+        assert_eq!(
+            v.validate("Event", &event).unwrap()[0].message,
+            "expected one of \"login\", found \"logout\""
+        );
+    }
+
+    #[test]
+    fn unions_and_best_match() {
+        let mut schema = schema();
+        schema.add(TypeDef {
+            name: "Id".into(),
+            docs: None,
+            kind: TypeDefKind::Alias(Type::Union(vec![
+                Type::String,
+                Type::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+            ])),
+        });
+        let v = Validator::new(&schema);
+        assert_eq!(v.validate("Id", &json!(3)).unwrap(), []);
+        assert_eq!(
+            v.validate("Id", &json!(true)).unwrap()[0].message,
+            "expected string | i64 integer, found true"
+        );
+        assert_eq!(v.best_match(&json!("banned")).unwrap().0, "Status");
+        assert_eq!(v.best_match(&json!({ "id": 1 })).unwrap().0, "User");
+        assert!(v.validate("Nope", &json!(1)).is_err());
+    }
+
+    #[test]
+    fn samples_directory() {
+        let dir = std::env::temp_dir().join(format!("typegen-verify-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("events")).unwrap();
+        std::fs::write(dir.join("user.json"), r#"{"id": 1, "name": "ada"}"#).unwrap();
+        std::fs::write(dir.join("user.bad.json"), r#"{"id": "1", "name": "ada"}"#).unwrap();
+        std::fs::write(
+            dir.join("events/1.json"),
+            r#"{"type": "login", "user": {"id": 1, "name": "a"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let report = verify_samples(&schema(), &dir, None).unwrap();
+        let summary: Vec<_> = report
+            .samples
+            .iter()
+            .map(|s| {
+                (
+                    s.file.strip_prefix(&dir).unwrap().display().to_string(),
+                    s.type_name.clone(),
+                    s.passed(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("broken.json".into(), None, false),
+                ("events/1.json".into(), Some("Event".into()), true),
+                ("user.bad.json".into(), Some("User".into()), false),
+                ("user.json".into(), Some("User".into()), true),
+            ]
+        );
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 2);
+        assert!(verify_samples(&schema(), &dir, Some("Nope")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,9 +1,13 @@
 //! Integration tests for normalize-typegen.
 
 use normalize_typegen::{
+    GeneratedModule,
     input::{parse_json_schema, parse_openapi},
     output::{
+        csharp::{CSharpOptions, generate_csharp_types},
         go::{GoOptions, generate_go_types},
+        graphql::{GraphQlOptions, generate_graphql_sdl},
+        proto::generate_proto,
         pydantic::{PydanticOptions, generate_pydantic},
         python::{PythonOptions, PythonStyle, generate_python_types},
         rust::{RustOptions, generate_rust_types},
@@ -212,6 +216,17 @@ fn go_types_no_tags() {
     insta::assert_snapshot!(output);
 }
 
+// === C# ===
+
+#[test]
+fn csharp_types_user() {
+    let input = load_fixture("user");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Models"));
+
+    insta::assert_snapshot!(output);
+}
+
 // === Rust ===
 
 #[test]
@@ -313,3 +328,115 @@ fn tagged_union_rust() {
 
     insta::assert_snapshot!(output);
 }
+
+#[test]
+fn tagged_union_csharp() {
+    let input = load_fixture("events");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Events"));
+
+    insta::assert_snapshot!(output);
+}
+
+// === Composition (allOf / oneOf / anyOf) ===
+
+#[test]
+fn composition_typescript() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    assert!(schema.validate().is_empty());
+    let output = generate_typescript_types(
+        &schema,
+        &TypeScriptOptions {
+            export: true,
+            ..Default::default()
+        },
+    );
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_pydantic() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_pydantic(&schema, &PydanticOptions::default());
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_go() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_go_types(&schema, &GoOptions::with_package("models"));
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_rust() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_rust_types(&schema, &RustOptions::with_serde());
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_csharp() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_csharp_types(&schema, &CSharpOptions::with_namespace("Models"));
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_proto() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_proto(&schema, None);
+
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn composition_graphql() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let output = generate_graphql_sdl(&schema, &GraphQlOptions::default());
+
+    insta::assert_snapshot!(output);
+}
+
+// === Multi-file Output ===
+
+#[test]
+fn split_typescript_composition() {
+    let input = load_fixture("composition");
+    let schema = parse_json_schema(&input).unwrap();
+    let module = GeneratedModule::split(&schema, "typescript", |s| {
+        generate_typescript_types(
+            s,
+            &TypeScriptOptions {
+                export: true,
+                ..Default::default()
+            },
+        )
+    })
+    .unwrap();
+
+    assert_eq!(module.files.len(), schema.definitions.len());
+    let canvas = module.files.iter().find(|f| f.path == "canvas.ts").unwrap();
+    assert_eq!(canvas.imports, ["drawable", "shape", "size"]);
+    insta::assert_snapshot!(canvas.contents);
+
+    let index = module.index.as_ref().unwrap();
+    assert_eq!(index.path, "index.ts");
+    assert!(
+        index
+            .contents
+            .contains("export * from \"./size_variant3\";\n")
+    );
+}
@@ -201,7 +201,7 @@ fn category_color(cat: BudgetCategory) -> nu_ansi_term::Color {
 
 fn render_bar_chart(categories: &[CategoryEntry], total: usize) -> String {
     use nu_ansi_term::Color;
-    let bar_width = 25_usize;
+    let bar_width = crate::output::terminal::bar_width(25);
     let mut lines = Vec::new();
     lines.push(format!(
         "  {:<18} {:>8}  {:>6}  {}",
@@ -210,7 +210,10 @@ fn render_bar_chart(categories: &[CategoryEntry], total: usize) -> String {
         Color::DarkGray.paint("Pct"),
         Color::DarkGray.paint("Bar"),
     ));
-    lines.push(format!("  {}", Color::DarkGray.paint("-".repeat(60))));
+    lines.push(format!(
+        "  {}",
+        Color::DarkGray.paint("-".repeat(35 + bar_width))
+    ));
     for entry in categories {
         let filled = ((entry.pct / 100.0) * bar_width as f64).round() as usize;
         let bar_str = format!(
@@ -227,7 +230,10 @@ fn render_bar_chart(categories: &[CategoryEntry], total: usize) -> String {
             color.paint(bar_str),
         ));
     }
-    lines.push(format!("  {}", Color::DarkGray.paint("-".repeat(60))));
+    lines.push(format!(
+        "  {}",
+        Color::DarkGray.paint("-".repeat(35 + bar_width))
+    ));
     lines.push(format!(
         "  {:<18} {:>8}  {:>5.1}%",
         "total", total, 100.0_f64
@@ -20,6 +20,7 @@ pub mod module_health;
 pub mod provenance;
 pub mod query;
 pub mod report;
+pub mod score;
 pub mod security;
 pub mod size;
 pub mod skeleton_diff;
@@ -0,0 +1,459 @@
+//! Project health score: one weighted number for CI, with a per-category breakdown.
+//!
+//! Combines rule diagnostics, import cycles, churn hotspots, duplication, file
+//! sizes, complexity, and test ratio. Categories that cannot be computed (no
+//! facts index, no git history) are reported as `n/a` and left out of the
+//! weighted total rather than scored as zero.
+//!
+//! A baseline saved to `.normalize/health.json` turns the score into a trend:
+//! later runs report per-category deltas and `--check` fails on regression.
+
+use crate::health::{HealthReport, analyze_health, grade_for_score};
+use crate::output::OutputFormatter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Commits within the churn window that make a large file a hotspot.
+const HOT_COMMITS: usize = 10;
+/// Lines at which a frequently-changed file counts as a churn hotspot.
+const HOT_LINES: usize = 500;
+/// Churn window: 90 days.
+const CHURN_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+/// Score drops smaller than this are noise, not regressions.
+const REGRESSION_TOLERANCE: f64 = 0.005;
+
+/// One scored category.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ScoreCategory {
+    pub name: String,
+    /// Share of the total score (0–1), before renormalizing over computed categories.
+    pub weight: f64,
+    /// Category score (0–1); `None` when the inputs were unavailable.
+    pub score: Option<f64>,
+    /// What the score is based on.
+    pub detail: String,
+    /// Change vs the baseline score for this category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta: Option<f64>,
+}
+
+/// Report for `normalize overview score`.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ProjectScoreReport {
+    /// Weighted score (0–1) over the categories that could be computed.
+    pub score: f64,
+    pub grade: String,
+    pub categories: Vec<ScoreCategory>,
+    /// Baseline the deltas are measured against, if one is stored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<ScoreBaseline>,
+    /// Change in the total score vs the baseline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta: Option<f64>,
+    /// Whether this run was written as the new baseline.
+    pub baseline_saved: bool,
+}
+
+/// Stored score baseline (`.normalize/health.json`).
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ScoreBaseline {
+    /// File format version (currently `1`).
+    pub version: u32,
+    /// Date the baseline was recorded (YYYY-MM-DD).
+    pub recorded: String,
+    pub score: f64,
+    /// Per-category scores; categories that were `n/a` are absent.
+    pub categories: BTreeMap<String, f64>,
+}
+
+impl ScoreBaseline {
+    /// Path to the baseline file (`<root>/.normalize/health.json`).
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".normalize").join("health.json")
+    }
+
+    /// Load the baseline, returning `Ok(None)` when none has been saved.
+    pub fn load(root: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), String> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json + "\n")
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+impl ProjectScoreReport {
+    /// Whether the total score dropped below the baseline by more than noise.
+    pub fn regressed(&self) -> bool {
+        self.delta.is_some_and(|d| d < -REGRESSION_TOLERANCE)
+    }
+
+    fn to_baseline(&self) -> ScoreBaseline {
+        ScoreBaseline {
+            version: 1,
+            recorded: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+            score: self.score,
+            categories: self
+                .categories
+                .iter()
+                .filter_map(|c| c.score.map(|s| (c.name.clone(), s)))
+                .collect(),
+        }
+    }
+}
+
+impl OutputFormatter for ProjectScoreReport {
+    fn format_text(&self) -> String {
+        let mut out = format!(
+            "# Project Health Score  {} ({:.0}%)",
+            self.grade,
+            self.score * 100.0
+        );
+        if let (Some(delta), Some(baseline)) = (self.delta, &self.baseline) {
+            out.push_str(&format!(
+                "  {} vs baseline ({})",
+                format_pct_delta(delta),
+                baseline.recorded
+            ));
+        }
+        out.push('\n');
+        for c in &self.categories {
+            let score = c
+                .score
+                .map_or_else(|| "n/a".to_string(), |s| format!("{:.0}%", s * 100.0));
+            let mut line = format!(
+                "  {:<13} {:>4}  (w {:>2.0}%)  {}",
+                c.name,
+                score,
+                c.weight * 100.0,
+                c.detail
+            );
+            if let Some(delta) = c.delta
+                && delta.abs() >= REGRESSION_TOLERANCE
+            {
+                line.push_str(&format!("  [{}]", format_pct_delta(delta)));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        if self.baseline_saved {
+            out.push_str("\nSaved as baseline (.normalize/health.json)\n");
+        }
+        out.trim_end().to_string()
+    }
+}
+
+fn format_pct_delta(delta: f64) -> String {
+    let pct = (delta * 100.0).round();
+    let sign = if pct >= 0.0 { "+" } else { "" };
+    format!("{sign}{:.0}%", pct + 0.0)
+}
+
+/// Raw measurements behind the score.
+pub struct ScoreInputs {
+    pub health: HealthReport,
+    /// (errors, warnings) from syntax and fact rules.
+    pub diagnostics: Option<(usize, usize)>,
+    /// (files in cycles, number of cycles, files in the import graph).
+    pub cycles: Option<(usize, usize, usize)>,
+    /// Large files with at least `HOT_COMMITS` commits in the churn window.
+    pub hotspots: Option<Vec<String>>,
+}
+
+/// Map `value` onto a score using ascending `(upper_bound, score)` steps.
+fn step_score(value: f64, steps: &[(f64, f64)], worst: f64) -> f64 {
+    steps
+        .iter()
+        .find(|(bound, _)| value <= *bound)
+        .map_or(worst, |(_, score)| *score)
+}
+
+/// Score every category from the raw measurements.
+pub fn score_categories(inputs: &ScoreInputs) -> Vec<ScoreCategory> {
+    let health = &inputs.health;
+    let b = health.score_breakdown();
+    let category = |name: &str, weight: f64, score: Option<f64>, detail: String| ScoreCategory {
+        name: name.to_string(),
+        weight,
+        score,
+        detail,
+        delta: None,
+    };
+
+    let diagnostics = match inputs.diagnostics {
+        Some((errors, warnings)) => {
+            let kloc = (health.total_lines as f64 / 1000.0).max(1.0);
+            let density = (errors * 5 + warnings) as f64 / kloc;
+            let score = step_score(
+                density,
+                &[(0.5, 1.0), (1.0, 0.9), (2.0, 0.8), (5.0, 0.6), (10.0, 0.4)],
+                0.2,
+            );
+            let detail = format!(
+                "{} errors, {} warnings ({:.1} weighted per 1k lines)",
+                errors, warnings, density
+            );
+            category("diagnostics", 0.20, Some(score), detail)
+        }
+        None => category("diagnostics", 0.20, None, "rules not run".to_string()),
+    };
+
+    let architecture = match inputs.cycles {
+        Some((in_cycles, cycles, nodes)) => {
+            let ratio = in_cycles as f64 / nodes.max(1) as f64;
+            let score = step_score(
+                ratio,
+                &[
+                    (0.01, 1.0),
+                    (0.03, 0.9),
+                    (0.05, 0.8),
+                    (0.10, 0.6),
+                    (0.20, 0.4),
+                ],
+                0.2,
+            );
+            let detail = if cycles == 0 {
+                "no import cycles".to_string()
+            } else {
+                format!(
+                    "{} files in {} import cycles ({:.0}% of graph)",
+                    in_cycles,
+                    cycles,
+                    ratio * 100.0
+                )
+            };
+            category("architecture", 0.15, Some(score), detail)
+        }
+        None => category(
+            "architecture",
+            0.15,
+            None,
+            "no facts index (run `normalize structure rebuild`)".to_string(),
+        ),
+    };
+
+    let churn = match &inputs.hotspots {
+        Some(hot) => {
+            let score = 1.0 / (1.0 + hot.len() as f64 / 5.0);
+            let detail = match hot.len() {
+                0 => "no large files under heavy churn".to_string(),
+                n => format!(
+                    "{} file{} over {} lines changed {}+ times in 90 days",
+                    n,
+                    if n == 1 { "" } else { "s" },
+                    HOT_LINES,
+                    HOT_COMMITS
+                ),
+            };
+            category("churn", 0.10, Some(score), detail)
+        }
+        None => category(
+            "churn",
+            0.10,
+            None,
+            "no git history in index (run `normalize structure rebuild`)".to_string(),
+        ),
+    };
+
+    // Code metrics are meaningless without files; report them as n/a rather than 0%.
+    let has_files = health.total_files > 0;
+    let duplication = category(
+        "duplication",
+        0.15,
+        has_files.then_some((b.duplicates + b.uniqueness) / 2.0),
+        format!("{}, {}", b.duplicates_reason, b.uniqueness_reason),
+    );
+    let size = category(
+        "size",
+        0.15,
+        has_files.then_some(b.file_size),
+        b.file_size_reason.clone(),
+    );
+    let complexity = category(
+        "complexity",
+        0.15,
+        has_files.then_some((b.complexity + b.risk) / 2.0),
+        format!("{}, {}", b.complexity_reason, b.risk_reason),
+    );
+    let tests = category(
+        "tests",
+        0.10,
+        health
+            .test_ratio
+            .filter(|_| has_files)
+            .map(|_| b.test_coverage),
+        b.test_coverage_reason.clone(),
+    );
+
+    vec![
+        diagnostics,
+        architecture,
+        churn,
+        duplication,
+        size,
+        complexity,
+        tests,
+    ]
+}
+
+/// Weighted total over the categories that have a score.
+pub fn weighted_total(categories: &[ScoreCategory]) -> f64 {
+    let (sum, weights) = categories
+        .iter()
+        .filter_map(|c| c.score.map(|s| (s * c.weight, c.weight)))
+        .fold((0.0, 0.0), |(a, b), (s, w)| (a + s, b + w));
+    if weights > 0.0 { sum / weights } else { 0.0 }
+}
+
+/// Compute the project score, compare it with the stored baseline, and
+/// optionally save it as the new baseline.
+pub async fn analyze_project_score(root: &Path, save: bool) -> Result<ProjectScoreReport, String> {
+    // Build the index first: the health metrics read file stats from it.
+    let idx = crate::index::ensure_ready(root).await.ok();
+    let health = analyze_health(root);
+    let diagnostics = count_diagnostics(root).await;
+    let (cycles, hotspots) = match &idx {
+        Some(idx) => (import_cycles(idx).await, churn_hotspots(root, idx).await),
+        None => (None, None),
+    };
+    let inputs = ScoreInputs {
+        health,
+        diagnostics,
+        cycles,
+        hotspots,
+    };
+
+    let mut categories = score_categories(&inputs);
+    let score = weighted_total(&categories);
+    let baseline = ScoreBaseline::load(root)?;
+    if let Some(base) = &baseline {
+        for c in &mut categories {
+            if let (Some(now), Some(then)) = (c.score, base.categories.get(&c.name)) {
+                c.delta = Some(now - then);
+            }
+        }
+    }
+    let mut report = ProjectScoreReport {
+        score,
+        grade: grade_for_score(score).to_string(),
+        categories,
+        delta: baseline.as_ref().map(|b| score - b.score),
+        baseline,
+        baseline_saved: false,
+    };
+    if save {
+        report.to_baseline().save(root)?;
+        report.baseline_saved = true;
+    }
+    Ok(report)
+}
+
+/// Count error and warning diagnostics from the syntax and fact rule engines.
+async fn count_diagnostics(root: &Path) -> Option<(usize, usize)> {
+    use normalize_output::diagnostics::Severity;
+    use normalize_rules::{RuleKind, load_rules_config, run_rules_report};
+
+    let root = root.to_path_buf();
+    let report = tokio::task::spawn_blocking(move || {
+        let config = load_rules_config(&root);
+        let mut merged = normalize_output::diagnostics::DiagnosticsReport::new();
+        for kind in [RuleKind::Syntax, RuleKind::Fact] {
+            merged.merge(run_rules_report(
+                &root,
+                &root,
+                None,
+                None,
+                &kind,
+                &[],
+                &config,
+                None,
+                &normalize_rules_config::PathFilter::default(),
+            ));
+        }
+        merged
+    })
+    .await
+    .ok()?;
+    let count = |severity: Severity| {
+        report
+            .issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    };
+    Some((count(Severity::Error), count(Severity::Warning)))
+}
+
+/// Files in import cycles, number of cycles, and import-graph size.
+async fn import_cycles(idx: &crate::index::FileIndex) -> Option<(usize, usize, usize)> {
+    let graph = crate::index::build_import_graph(idx).await.ok()?;
+    let imports = graph.imports_by_file;
+    let nodes = normalize_graph::all_nodes(&imports).len();
+    let cycles: Vec<Vec<String>> = normalize_graph::tarjan_sccs(&imports)
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .collect();
+    let in_cycles = cycles.iter().map(Vec::len).sum();
+    Some((in_cycles, cycles.len(), nodes))
+}
+
+/// Large files that changed at least `HOT_COMMITS` times in the churn window.
+///
+/// Returns `None` when the index holds no commit history at all.
+async fn churn_hotspots(root: &Path, idx: &crate::index::FileIndex) -> Option<Vec<String>> {
+    let since = (chrono::Utc::now().timestamp() - CHURN_WINDOW_SECS).max(0) as u64;
+    let files = idx.file_churn_since(since).await.ok()?;
+    if files.is_empty() {
+        return None;
+    }
+    let hot = files
+        .into_iter()
+        .filter(|f| f.recent_commits >= HOT_COMMITS)
+        .filter(|f| {
+            std::fs::read_to_string(root.join(&f.file))
+                .is_ok_and(|content| content.lines().count() >= HOT_LINES)
+        })
+        .map(|f| f.file)
+        .collect();
+    Some(hot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unavailable_categories_are_left_out_of_the_total() {
+        let category = |name: &str, weight: f64, score: Option<f64>| ScoreCategory {
+            name: name.to_string(),
+            weight,
+            score,
+            detail: String::new(),
+            delta: None,
+        };
+        let categories = vec![
+            category("a", 0.5, Some(1.0)),
+            category("b", 0.25, Some(0.5)),
+            category("c", 0.25, None),
+        ];
+        // (0.5 * 1.0 + 0.25 * 0.5) / 0.75
+        assert!((weighted_total(&categories) - 0.8333).abs() < 1e-3);
+        assert_eq!(weighted_total(&[category("x", 1.0, None)]), 0.0);
+    }
+}
@@ -21,6 +21,36 @@ pub struct ArchStats {
     pub hubs: usize,
 }
 
+/// A symbol's share of its file's churn, from blaming the file at HEAD.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SymbolChurn {
+    pub name: String,
+    pub kind: String,
+    /// Distinct commits behind the symbol's current lines.
+    pub commits: usize,
+    pub authors: usize,
+    pub last_modified: String,
+}
+
+/// A file that changed often in the churn window.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct FileChurnEntry {
+    pub file: String,
+    pub commits_90d: usize,
+    pub commits: usize,
+    pub authors: usize,
+    pub last_modified: String,
+    /// Most-changed symbols (only filled for the top few files).
+    pub symbols: Vec<SymbolChurn>,
+}
+
+/// Files listed in the churn section, ranked by commits in the last 90 days.
+const CHURN_FILES: usize = 5;
+/// How many of those files get a per-symbol breakdown (one blame each).
+const CHURN_BLAMED_FILES: usize = 3;
+const CHURN_SYMBOLS: usize = 3;
+const CHURN_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+
 /// Single-page codebase overview.
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct SummaryReport {
@@ -30,6 +60,9 @@ pub struct SummaryReport {
     pub module_health: ModuleHealthReport,
     pub arch: ArchStats,
     pub concerns: Vec<Concern>,
+    /// Files (and their symbols) that changed most in the last 90 days.
+    /// Empty outside git or until `normalize structure rebuild` has walked history.
+    pub churn: Vec<FileChurnEntry>,
 }
 
 fn extract_arch_stats(report: &ArchitectureReport) -> ArchStats {
@@ -88,6 +121,7 @@ pub async fn analyze_summary(root: &Path, module_limit: usize) -> SummaryReport
 
     // Architecture requires the facts index — best-effort.
     let arch = try_architecture(root).await.unwrap_or_default();
+    let churn = try_churn(root).await.unwrap_or_default();
 
     let concerns = build_concerns(&health, &module_health);
 
@@ -103,6 +137,7 @@ pub async fn analyze_summary(root: &Path, module_limit: usize) -> SummaryReport
         module_health,
         arch,
         concerns,
+        churn,
     }
 }
 
@@ -112,6 +147,85 @@ async fn try_architecture(root: &Path) -> Option<ArchStats> {
     Some(extract_arch_stats(&report))
 }
 
+async fn try_churn(root: &Path) -> Option<Vec<FileChurnEntry>> {
+    let idx = crate::index::ensure_ready(root).await.ok()?;
+    let since = (chrono::Utc::now().timestamp() - CHURN_WINDOW_SECS).max(0) as u64;
+    let files = idx.file_churn_since(since).await.ok()?;
+    let entries = files
+        .into_iter()
+        .filter(|f| f.recent_commits > 0 && root.join(&f.file).is_file())
+        .take(CHURN_FILES)
+        .enumerate()
+        .map(|(i, f)| FileChurnEntry {
+            symbols: if i < CHURN_BLAMED_FILES {
+                symbol_churn(root, &f.file)
+            } else {
+                Vec::new()
+            },
+            file: f.file,
+            commits_90d: f.recent_commits,
+            commits: f.commits,
+            authors: f.authors,
+            last_modified: normalize_git::format_unix_date(f.last_changed as i64),
+        })
+        .collect();
+    Some(entries)
+}
+
+/// Rank the leaf symbols of `file` by the commits behind their current lines.
+fn symbol_churn(root: &Path, file: &str) -> Vec<SymbolChurn> {
+    let full_path = root.join(file);
+    let Ok(content) = std::fs::read_to_string(&full_path) else {
+        return Vec::new();
+    };
+    let extracted = crate::skeleton::SkeletonExtractor::new().extract(&full_path, &content);
+    let mut leaves = Vec::new();
+    let mut stack: Vec<&normalize_languages::Symbol> = extracted.symbols.iter().collect();
+    while let Some(sym) = stack.pop() {
+        if sym.children.is_empty() {
+            leaves.push(sym);
+        } else {
+            stack.extend(sym.children.iter());
+        }
+    }
+    let ranges: Vec<(usize, usize)> = leaves.iter().map(|s| (s.start_line, s.end_line)).collect();
+    let Some(churn) = normalize_git::git_blame_range_churn(root, file, &ranges) else {
+        return Vec::new();
+    };
+    let mut symbols: Vec<SymbolChurn> = leaves
+        .iter()
+        .zip(churn)
+        .filter(|(_, c)| c.commits > 0)
+        .map(|(sym, c)| SymbolChurn {
+            name: sym.name.clone(),
+            kind: sym.kind.as_str().to_string(),
+            commits: c.commits,
+            authors: c.authors,
+            last_modified: normalize_git::format_unix_date(c.last_modified as i64),
+        })
+        .collect();
+    symbols.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    symbols.truncate(CHURN_SYMBOLS);
+    symbols
+}
+
+fn churn_lines(churn: &[FileChurnEntry]) -> Vec<String> {
+    let mut out = Vec::new();
+    for f in churn {
+        out.push(format!(
+            "  {} — {} commits in 90d ({} total), {} authors, last {}",
+            f.file, f.commits_90d, f.commits, f.authors, f.last_modified
+        ));
+        for s in &f.symbols {
+            out.push(format!(
+                "    {} {} — {} commits, {} authors",
+                s.kind, s.name, s.commits, s.authors
+            ));
+        }
+    }
+    out
+}
+
 fn breakdown_rows(b: &crate::health::HealthScoreBreakdown) -> Vec<(&'static str, f64, &str)> {
     vec![
         ("complexity", b.complexity, &b.complexity_reason),
@@ -192,6 +306,13 @@ impl OutputFormatter for SummaryReport {
             out.push(format!("  {} hub modules", self.arch.hubs));
         }
 
+        // Churn
+        if !self.churn.is_empty() {
+            out.push(String::new());
+            out.push("## Churn (90 days)".to_string());
+            out.extend(churn_lines(&self.churn));
+        }
+
         out.join("\n")
     }
 
@@ -290,6 +411,13 @@ impl OutputFormatter for SummaryReport {
             out.push(format!("  {} hub modules", self.arch.hubs));
         }
 
+        // Churn
+        if !self.churn.is_empty() {
+            out.push(String::new());
+            out.push(Style::new().bold().paint("Churn (90 days)").to_string());
+            out.extend(churn_lines(&self.churn));
+        }
+
         out.join("\n")
     }
 }
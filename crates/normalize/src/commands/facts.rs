@@ -1,7 +1,7 @@
 //! Facts management commands (file index, symbols, calls, imports).
 
 use crate::index;
-use normalize_facts_rules_api::Relations;
+use normalize_facts_rules_api::{Relations, RelationsFilter};
 use std::path::Path;
 
 /// What to extract during indexing (files are always indexed).
@@ -50,98 +50,8 @@ pub async fn build_relations_from_index(root: &Path) -> Result<Relations, String
         .await
         .map_err(|e| format!("Failed to open index: {}", e))?;
 
-    let mut relations = Relations::new();
-
-    // Get symbols (file, name, kind, start_line, end_line, parent, visibility)
-    let symbols = idx
-        .all_symbols_with_details()
-        .await
-        .map_err(|e| format!("Failed to get symbols: {}", e))?;
-
-    for (file, name, kind, start_line, end_line, parent, visibility, is_impl) in &symbols {
-        relations.add_symbol(file, name, kind, *start_line as u32);
-        relations.add_symbol_range(file, name, *start_line as u32, *end_line as u32);
-        relations.add_visibility(file, name, visibility);
-        if let Some(parent_name) = parent {
-            relations.add_parent(file, name, parent_name);
-        }
-        if *is_impl {
-            relations.add_is_impl(file, name);
-        }
-    }
-
-    // Get symbol attributes
-    let attrs = idx
-        .all_symbol_attributes()
-        .await
-        .map_err(|e| format!("Failed to get symbol attributes: {}", e))?;
-
-    for (file, name, attribute) in &attrs {
-        relations.add_attribute(file, name, attribute);
-    }
-
-    // Get symbol implements
-    let implements = idx
-        .all_symbol_implements()
-        .await
-        .map_err(|e| format!("Failed to get symbol implements: {}", e))?;
-
-    for (file, name, interface) in &implements {
-        relations.add_implements(file, name, interface);
-    }
-
-    // Get type methods
-    let type_methods = idx
-        .all_type_methods()
-        .await
-        .map_err(|e| format!("Failed to get type methods: {}", e))?;
-
-    for (file, type_name, method_name) in &type_methods {
-        relations.add_type_method(file, type_name, method_name);
-    }
-
-    // Get imports (file, module, name, line)
-    let imports = idx
-        .all_imports()
-        .await
-        .map_err(|e| format!("Failed to get imports: {}", e))?;
-
-    for (file, module, name, _line) in imports {
-        relations.add_import(&file, &module, &name);
-    }
-
-    // Get calls with qualifiers (caller_file, caller_symbol, callee_name, qualifier, line)
-    let calls = idx
-        .all_calls_with_qualifiers()
-        .await
-        .map_err(|e| format!("Failed to get calls: {}", e))?;
-
-    for (file, caller, callee, qualifier, line) in &calls {
-        relations.add_call(file, caller, callee, *line);
-        if let Some(qual) = qualifier {
-            relations.add_qualifier(file, caller, callee, qual);
-        }
-    }
-
-    // Get CFG edges (Phase 4)
-    let cfg_edges = idx
-        .all_cfg_edges()
-        .await
-        .map_err(|e| format!("Failed to get CFG edges: {}", e))?;
-
-    for (file, func, func_line, from, to, kind, exception_type) in &cfg_edges {
-        relations.add_cfg_edge(file, func, *func_line, *from, *to, kind, exception_type);
-    }
-
-    // Get CFG effects (Phase 3)
-    let cfg_effects = idx
-        .all_cfg_effects()
-        .await
-        .map_err(|e| format!("Failed to get CFG effects: {}", e))?;
-
-    for (file, func, func_line, block, kind, line, label) in &cfg_effects {
-        relations.add_cfg_effect(file, func, *func_line, *block, kind, *line, label);
-    }
+    let mut relations = Relations::from_index(&idx, &RelationsFilter::all()).await?;
+    normalize_rules::add_churn_relations(&mut relations, &idx).await?;
 
     Ok(relations)
 }
@@ -115,48 +115,9 @@ pub fn run_types_service(
             valibot::{ValibotOptions, generate_valibot},
             zod::{ZodOptions, generate_zod},
         },
-        parse_json_schema, parse_openapi,
     };
 
-    let content = read_input(&input)?;
-
-    let detected_format = match format {
-        InputFormat::Auto => {
-            let lang = normalize_languages::support_for_path(&input).map(|s| s.name());
-            match lang {
-                Some("TypeScript") | Some("TSX") => InputFormat::Typescript,
-                _ => InputFormat::Auto,
-            }
-        }
-        f => f,
-    };
-
-    let schema: Schema = if matches!(detected_format, InputFormat::Typescript) {
-        normalize_typegen::parse_typescript_types(&content)
-            .map_err(|e| format!("Failed to parse TypeScript: {}", e))?
-    } else {
-        let json: serde_json::Value =
-            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        let json_format = match detected_format {
-            InputFormat::Auto => {
-                if json.get("openapi").is_some() {
-                    InputFormat::OpenApi
-                } else {
-                    InputFormat::JsonSchema
-                }
-            }
-            f => f,
-        };
-
-        match json_format {
-            InputFormat::OpenApi => {
-                parse_openapi(&json).map_err(|e| format!("Failed to parse OpenAPI: {}", e))?
-            }
-            _ => parse_json_schema(&json)
-                .map_err(|e| format!("Failed to parse JSON Schema: {}", e))?,
-        }
-    };
+    let schema = load_schema(&input, format)?;
 
     // Helper: generate code for a schema (one or many types)
     let generate_code = |s: &Schema| -> String {
@@ -224,8 +185,25 @@ pub fn run_types_service(
     };
 
     if split {
-        // --split: emit one file per top-level type
-        if !dry_run {
+        // --split: emit one file per top-level type, with cross-file imports
+        let module = normalize_typegen::GeneratedModule::split(
+            &schema,
+            &backend.to_string(),
+            generate_code,
+        )?;
+
+        if dry_run {
+            let mut combined_preview = String::new();
+            for file in module.files.iter().chain(&module.index) {
+                combined_preview.push_str(&format!("--- {} ---\n", file.path));
+                combined_preview.push_str(&file.contents);
+                combined_preview.push('\n');
+            }
+            Ok(crate::service::generate::GenerateReport {
+                output: combined_preview,
+                path: None,
+            })
+        } else {
             let dir = output.as_deref().ok_or_else(|| {
                 "--split requires --output to specify an output directory".to_string()
             })?;
@@ -235,42 +213,17 @@ pub fn run_types_service(
                     dir.display()
                 ));
             }
-            std::fs::create_dir_all(dir)
-                .map_err(|e| format!("Failed to create directory {}: {}", dir.display(), e))?;
-        }
-
-        let mut combined_preview = String::new();
-        let mut files_written = Vec::new();
-
-        for def in &schema.definitions {
-            let mut single = Schema::new();
-            single.add(def.clone());
-            let code = generate_code(&single);
-            let filename = format!("{}.{}", type_name_to_filename(&def.name), extension);
-
-            if dry_run {
-                combined_preview.push_str(&format!("--- {} ---\n", filename));
-                combined_preview.push_str(&code);
-                combined_preview.push('\n');
-            } else {
-                let dir = output.as_deref().unwrap();
-                let path = dir.join(&filename);
-                std::fs::write(&path, &code)
-                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            let written = module.write(dir, &backend.to_string())?;
+            for path in &written {
                 eprintln!("Generated {}", path.display());
-                files_written.push(path.display().to_string());
             }
-        }
-
-        if dry_run {
             Ok(crate::service::generate::GenerateReport {
-                output: combined_preview,
-                path: None,
-            })
-        } else {
-            Ok(crate::service::generate::GenerateReport {
-                output: files_written.join("\n"),
-                path: output.map(|p| p.display().to_string()),
+                output: written
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                path: Some(dir.display().to_string()),
             })
         }
     } else {
@@ -292,26 +245,99 @@ pub fn run_types_service(
     }
 }
 
-/// Convert a PascalCase or camelCase type name to snake_case filename.
-///
-/// Examples: `UserType` → `user_type`, `HTTPSConfig` → `https_config`.
+/// Parse a schema file (or stdin for `-`) into the typegen IR.
 #[cfg(feature = "cli")]
-fn type_name_to_filename(name: &str) -> String {
-    let mut out = String::new();
-    let chars: Vec<char> = name.chars().collect();
-    for (i, &c) in chars.iter().enumerate() {
-        if c.is_uppercase() {
-            let prev_lower = i > 0 && chars[i - 1].is_lowercase();
-            let next_lower = i + 1 < chars.len() && chars[i + 1].is_lowercase();
-            if i > 0 && (prev_lower || next_lower) {
-                out.push('_');
+fn load_schema(
+    input: &std::path::Path,
+    format: InputFormat,
+) -> Result<normalize_typegen::ir::Schema, String> {
+    use normalize_typegen::{parse_json_schema, parse_openapi};
+
+    let content = read_input(input)?;
+
+    let detected_format = match format {
+        InputFormat::Auto => {
+            let lang = normalize_languages::support_for_path(input).map(|s| s.name());
+            match lang {
+                Some("TypeScript") | Some("TSX") => InputFormat::Typescript,
+                _ => InputFormat::Auto,
             }
-            out.push(c.to_ascii_lowercase());
-        } else {
-            out.push(c);
         }
-    }
-    out
+        f => f,
+    };
+
+    let schema = if matches!(detected_format, InputFormat::Typescript) {
+        normalize_typegen::parse_typescript_types(&content)
+            .map_err(|e| format!("Failed to parse TypeScript: {}", e))?
+    } else {
+        let json: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        let json_format = match detected_format {
+            InputFormat::Auto => {
+                if json.get("openapi").is_some() {
+                    InputFormat::OpenApi
+                } else {
+                    InputFormat::JsonSchema
+                }
+            }
+            f => f,
+        };
+
+        match json_format {
+            InputFormat::OpenApi => {
+                parse_openapi(&json).map_err(|e| format!("Failed to parse OpenAPI: {}", e))?
+            }
+            _ => parse_json_schema(&json)
+                .map_err(|e| format!("Failed to parse JSON Schema: {}", e))?,
+        }
+    };
+    Ok(schema)
+}
+
+/// Service-callable version of `generate verify`.
+#[cfg(feature = "cli")]
+pub fn run_verify_service(
+    input: PathBuf,
+    format: InputFormat,
+    samples: PathBuf,
+    type_name: Option<String>,
+) -> Result<crate::service::generate::VerifyReport, String> {
+    use crate::service::generate::{VerifyMismatch, VerifyReport, VerifySample};
+
+    let schema = load_schema(&input, format)?;
+    let report = normalize_typegen::verify_samples(&schema, &samples, type_name.as_deref())?;
+    Ok(VerifyReport {
+        valid: report.passed(),
+        samples: report
+            .samples
+            .into_iter()
+            .map(|s| VerifySample {
+                file: s.file.display().to_string(),
+                type_name: s.type_name,
+                mismatches: s
+                    .mismatches
+                    .into_iter()
+                    .map(|m| VerifyMismatch {
+                        path: m.path,
+                        message: m.message,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Service-callable version of `generate diff`.
+#[cfg(feature = "cli")]
+pub fn run_diff_service(
+    old: PathBuf,
+    new: PathBuf,
+    format: InputFormat,
+) -> Result<normalize_typegen::ir::SchemaDiff, String> {
+    let old = load_schema(&old, format)?;
+    let new = load_schema(&new, format)?;
+    Ok(normalize_typegen::ir::diff(&old, &new))
 }
 
 #[cfg(feature = "cli")]
@@ -320,7 +346,7 @@ fn write_generate_result(
     output: Option<PathBuf>,
 ) -> Result<crate::service::generate::GenerateReport, String> {
     if let Some(ref path) = output {
-        std::fs::write(path, &code)
+        crate::output::sink::write_atomic(path, code.as_bytes())
             .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
         eprintln!("Generated {}", path.display());
         Ok(crate::service::generate::GenerateReport {
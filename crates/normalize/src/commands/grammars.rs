@@ -8,11 +8,15 @@ use std::io::IsTerminal;
 use std::path::Path;
 use std::path::PathBuf;
 
-/// A single grammar entry with its name and file path.
+/// A single grammar entry with its name and how it is provided.
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct GrammarEntry {
     pub name: String,
-    pub path: String,
+    /// Shared library path; absent for grammars linked into the binary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `"static"` (linked in) or `"dynamic"` (shared library).
+    pub mode: String,
 }
 
 /// Grammar list report
@@ -22,16 +26,30 @@ pub struct GrammarListReport {
 }
 
 impl GrammarListReport {
-    pub fn new(grammars: Vec<(String, PathBuf)>) -> Self {
-        Self {
-            grammars: grammars
+    /// Build from the shared libraries found on disk plus the grammars linked
+    /// into the binary. A static grammar shadows a shared library of the same
+    /// name, matching `GrammarLoader`'s lookup order.
+    pub fn new(grammars: Vec<(String, PathBuf)>, static_grammars: &[&str]) -> Self {
+        let mut entries: Vec<GrammarEntry> = static_grammars
+            .iter()
+            .map(|name| GrammarEntry {
+                name: name.to_string(),
+                path: None,
+                mode: "static".to_string(),
+            })
+            .collect();
+        entries.extend(
+            grammars
                 .into_iter()
+                .filter(|(name, _)| !static_grammars.contains(&name.as_str()))
                 .map(|(name, path)| GrammarEntry {
                     name,
-                    path: path.display().to_string(),
-                })
-                .collect(),
-        }
+                    path: Some(path.display().to_string()),
+                    mode: "dynamic".to_string(),
+                }),
+        );
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { grammars: entries }
     }
 }
 
@@ -48,7 +66,11 @@ impl OutputFormatter for GrammarListReport {
         } else {
             let mut lines = vec![format!("Installed grammars ({}):", self.grammars.len())];
             for entry in &self.grammars {
-                lines.push(entry.name.clone());
+                if entry.path.is_none() {
+                    lines.push(format!("{} (static)", entry.name));
+                } else {
+                    lines.push(entry.name.clone());
+                }
             }
             lines.join("\n")
         }
@@ -113,8 +135,7 @@ fn dir_has_grammars(dir: &Path) -> bool {
 /// the worst case is "we re-check next time."
 #[cfg(feature = "cli")]
 fn write_installed_stamp(dir: &Path, marker: &str) {
-    let _ = std::fs::create_dir_all(dir);
-    let _ = std::fs::write(dir.join(INSTALLED_STAMP), marker);
+    let _ = normalize_cache::write_atomic(&dir.join(INSTALLED_STAMP), marker);
 }
 
 /// Outcome of a first-run grammar check, reported back to the caller so it
@@ -1,25 +1,47 @@
 //! CLI command implementations - one module per top-level command.
 
 use crate::config::NormalizeConfig;
-use crate::filter::Filter;
-use aliases::detect_project_languages;
+use crate::filter::{Filter, FilterPredicate};
 use std::path::Path;
 
 /// Build a `Filter` from `--exclude` / `--only` patterns, printing any warnings.
 /// Returns `None` if both slices are empty (no filtering needed).
 pub fn build_filter(root: &Path, exclude: &[String], only: &[String]) -> Option<Filter> {
-    if exclude.is_empty() && only.is_empty() {
+    build_filter_with_predicates(root, exclude, only, &[])
+}
+
+/// Parse `--exclude-larger-than` / `--only-modified-since` values into filter predicates.
+pub fn parse_predicates(
+    exclude_larger_than: Option<&str>,
+    only_modified_since: Option<&str>,
+) -> Result<Vec<FilterPredicate>, String> {
+    let mut predicates = Vec::new();
+    if let Some(size) = exclude_larger_than {
+        predicates.push(FilterPredicate::larger_than(size)?);
+    }
+    if let Some(window) = only_modified_since {
+        predicates.push(FilterPredicate::modified_since(window)?);
+    }
+    Ok(predicates)
+}
+
+/// Like [`build_filter`], adding size/age predicates such as `--exclude-larger-than`.
+pub fn build_filter_with_predicates(
+    root: &Path,
+    exclude: &[String],
+    only: &[String],
+    predicates: &[FilterPredicate],
+) -> Option<Filter> {
+    if exclude.is_empty() && only.is_empty() && predicates.is_empty() {
         return None;
     }
     let config = NormalizeConfig::load(root);
-    let languages = detect_project_languages(root);
-    let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
-    match Filter::new(exclude, only, &config.aliases, &lang_refs) {
+    match Filter::for_project(exclude, only, &config.aliases, root) {
         Ok(f) => {
             for warning in f.warnings() {
                 eprintln!("warning: {}", warning);
             }
-            Some(f)
+            Some(f.with_predicates(root, predicates))
         }
         Err(e) => {
             eprintln!("error: {}", e);
@@ -28,7 +50,6 @@ pub fn build_filter(root: &Path, exclude: &[String], only: &[String]) -> Option<
     }
 }
 
-pub mod aliases;
 pub mod analyze;
 pub mod ci;
 pub mod context;
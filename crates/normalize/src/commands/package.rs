@@ -1,22 +1,35 @@
 //! Package registry queries.
 
 use normalize_ecosystems::{
-    Dependency, DependencyTree, PackageError, PackageInfo, Vulnerability, VulnerabilitySeverity,
-    all_ecosystems, detect_all_ecosystems,
+    Dependency, DependencyTree, Lockfile, LockfileAudit, LockfileDiff, LockfileFormat,
+    PackageError, PackageInfo, Vulnerability, VulnerabilitySeverity, all_ecosystems,
+    detect_all_ecosystems,
 };
 use std::path::Path;
 
 // ── Public data-fetching functions used by the service layer ─────────────────
 
-/// Get package info for a single package, returning (ecosystem_name, PackageInfo).
+/// Get package info for a single package, returning (ecosystem_name, PackageInfo, advisories).
+///
+/// With `osv`, advisories for the resolved version come from OSV.dev; a failed
+/// lookup (offline with no cache, or no advisory database) yields none.
 pub fn get_info(
     package: &str,
     ecosystem: Option<&str>,
     root: &Path,
-) -> Result<(String, PackageInfo), String> {
+    osv: bool,
+) -> Result<(String, PackageInfo, Vec<Vulnerability>), String> {
     let eco = resolve_single_ecosystem(ecosystem, root)?;
     match eco.query(package, root) {
-        Ok(info) => Ok((eco.name().to_string(), info)),
+        Ok(info) => {
+            let advisories = if osv {
+                eco.audit_package(&info.name, &info.version)
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            Ok((eco.name().to_string(), info, advisories))
+        }
         Err(e) => Err(format_package_error(&e, eco.name())),
     }
 }
@@ -50,6 +63,46 @@ pub fn get_tree(ecosystem: Option<&str>, root: &Path) -> Result<(String, Depende
     }
 }
 
+/// Build the merged dependency graph for the project.
+///
+/// With `registry`, packages with no known dependencies are completed from
+/// registry metadata.
+pub fn get_graph(
+    ecosystem: Option<&str>,
+    root: &Path,
+    registry: bool,
+) -> Result<normalize_ecosystems::DependencyGraph, String> {
+    let eco = resolve_single_ecosystem(ecosystem, root)?;
+    normalize_ecosystems::DependencyGraph::build(eco, root, registry)
+        .map_err(|e| format!("error: {}", e))
+}
+
+/// Trace why `package` is in the dependency tree, returning (ecosystem_name, WhyResult).
+pub fn get_why(
+    package: &str,
+    ecosystem: Option<&str>,
+    root: &Path,
+) -> Result<(String, normalize_ecosystems::WhyResult), String> {
+    let eco = resolve_single_ecosystem(ecosystem, root)?;
+    match eco.why(package, root) {
+        Ok(why) => Ok((eco.name().to_string(), why)),
+        Err(e) => Err(format!("error: {}", e)),
+    }
+}
+
+/// Diff two lockfiles of the same format.
+pub fn get_lockfile_diff(old: &Path, new: &Path) -> Result<LockfileDiff, String> {
+    let old = Lockfile::from_path(old).map_err(|e| format!("error: {}", e))?;
+    let new = Lockfile::from_path(new).map_err(|e| format!("error: {}", e))?;
+    if old.format != new.format {
+        return Err(format!(
+            "error: lockfile formats differ ({:?} vs {:?})",
+            old.format, new.format
+        ));
+    }
+    Ok(normalize_ecosystems::diff_lockfiles(&old, &new))
+}
+
 /// A single outdated package entry: (name, installed_version, latest_version, wanted_constraint).
 pub type OutdatedRow = (String, Option<String>, String, Option<String>);
 
@@ -67,12 +120,18 @@ pub fn show_outdated_data(ecosystem: Option<&str>, root: &Path) -> OutdatedResul
     let mut outdated = Vec::new();
     let mut errors = Vec::new();
 
-    for dep in &deps {
+    let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+    let results = eco.query_many(&names, root);
+
+    for (dep, result) in deps.iter().zip(results) {
         let installed = eco.installed_version(&dep.name, root);
-        match eco.query(&dep.name, root) {
+        match result {
             Ok(info) => {
                 let is_outdated = match &installed {
-                    Some(v) => v != &info.version,
+                    Some(v) => {
+                        eco.version_scheme().compare(&info.version, v)
+                            == std::cmp::Ordering::Greater
+                    }
                     None => true,
                 };
                 if is_outdated {
@@ -94,17 +153,122 @@ pub fn show_outdated_data(ecosystem: Option<&str>, root: &Path) -> OutdatedResul
 }
 
 /// Get audit results, returning (ecosystem_name, Vec<Vulnerability>).
+///
+/// With `osv`, every package in the merged dependency graph is checked against OSV.dev
+/// instead of running the ecosystem's audit tool.
 pub fn get_audit(
     ecosystem: Option<&str>,
     root: &Path,
+    osv: bool,
 ) -> Result<(String, Vec<Vulnerability>), String> {
     let eco = resolve_single_ecosystem(ecosystem, root)?;
-    match eco.audit(root) {
+    let result = if osv {
+        normalize_ecosystems::DependencyGraph::build(eco, root, false)
+            .and_then(|graph| normalize_ecosystems::audit_dependency_graph(eco, &graph))
+    } else {
+        eco.audit(root)
+    };
+    match result {
         Ok(result) => Ok((eco.name().to_string(), result.vulnerabilities)),
         Err(e) => Err(format!("error: {}", e)),
     }
 }
 
+/// Check every package in a lockfile against OSV.dev.
+///
+/// With no explicit `lockfile`, the first lockfile of the detected ecosystem
+/// found in `root` is used. Returns (lockfile_path, LockfileAudit).
+pub fn get_security(
+    lockfile: Option<&Path>,
+    ecosystem: Option<&str>,
+    root: &Path,
+) -> Result<(String, LockfileAudit), String> {
+    let path = match lockfile {
+        Some(path) => {
+            let path = root.join(path);
+            if let Some(ecosystem) = ecosystem {
+                let eco = resolve_single_ecosystem(Some(ecosystem), root)?;
+                let format = LockfileFormat::from_path(&path).map(|f| f.ecosystem());
+                if format.is_some_and(|f| f != eco.name()) {
+                    return Err(format!(
+                        "error: {} is not a {} lockfile",
+                        path.display(),
+                        eco.name()
+                    ));
+                }
+            }
+            path
+        }
+        None => {
+            let eco = resolve_single_ecosystem(ecosystem, root)?;
+            eco.lockfiles()
+                .iter()
+                .map(|l| root.join(l.filename))
+                .find(|p| p.is_file() && LockfileFormat::from_path(p).is_some())
+                .ok_or_else(|| {
+                    format!(
+                        "error: no supported {} lockfile found in {}",
+                        eco.name(),
+                        root.display()
+                    )
+                })?
+        }
+    };
+    let parsed = Lockfile::from_path(&path).map_err(|e| format!("error: {}", e))?;
+    let audit =
+        normalize_ecosystems::audit_lockfile(&parsed).map_err(|e| format!("error: {}", e))?;
+    Ok((path.display().to_string(), audit))
+}
+
+/// Export the registry cache for the project's dependencies to a bundle at `out`.
+pub fn get_bundle_export(
+    ecosystem: Option<&str>,
+    root: &Path,
+    out: &Path,
+) -> Result<normalize_ecosystems::BundleExport, String> {
+    let eco = resolve_single_ecosystem(ecosystem, root)?;
+    normalize_ecosystems::bundle::export(eco, root, out).map_err(|e| format!("error: {}", e))
+}
+
+/// Import a bundle written by `normalize package export`.
+pub fn get_bundle_import(archive: &Path) -> Result<normalize_ecosystems::BundleImport, String> {
+    normalize_ecosystems::bundle::import(archive).map_err(|e| format!("error: {}", e))
+}
+
+/// Check every dependency's license against the `[licenses]` policy,
+/// returning (LicenseAudit, whether a policy is configured).
+pub fn get_licenses(
+    ecosystem: Option<&str>,
+    root: &Path,
+) -> Result<(normalize_ecosystems::LicenseAudit, bool), String> {
+    let eco = resolve_single_ecosystem(ecosystem, root)?;
+    let policy = crate::config::NormalizeConfig::load(root).licenses;
+    let configured =
+        !(policy.allowed.is_empty() && policy.denied.is_empty() && policy.needs_review.is_empty());
+    let audit = normalize_ecosystems::LicenseAudit::run(eco, root, &policy)
+        .map_err(|e| format!("error: {}", e))?;
+    Ok((audit, configured))
+}
+
+/// Build an SBOM from the merged dependency graph, returning (ecosystem_name, Sbom).
+///
+/// With `registry`, licenses, descriptions and URLs are filled in from the
+/// ecosystem's registry (cached; failed lookups are left blank).
+pub fn get_sbom(
+    ecosystem: Option<&str>,
+    root: &Path,
+    registry: bool,
+) -> Result<(String, normalize_ecosystems::Sbom), String> {
+    let eco = resolve_single_ecosystem(ecosystem, root)?;
+    let graph = normalize_ecosystems::DependencyGraph::build(eco, root, false)
+        .map_err(|e| format!("error: {}", e))?;
+    let mut sbom = normalize_ecosystems::Sbom::from_graph(&graph, eco.purl_type());
+    if registry {
+        sbom.enrich(eco, root);
+    }
+    Ok((eco.name().to_string(), sbom))
+}
+
 // ── Formatting helpers used by report format_text() impls ────────────────────
 
 /// Format a package info response in human-readable form.
@@ -249,36 +413,6 @@ pub fn print_audit_human(vulnerabilities: &[Vulnerability], ecosystem: &str) ->
 
 // ── Path-finding helpers (used by service layer) ─────────────────────────────
 
-/// Find all paths from root packages to the target dependency.
-pub fn find_dependency_paths(tree: &DependencyTree, target: &str) -> Vec<Vec<(String, String)>> {
-    let mut all_paths = Vec::new();
-
-    for root in &tree.roots {
-        let mut current_path = vec![(root.name.clone(), root.version.clone())];
-        find_paths_recursive(root, target, &mut current_path, &mut all_paths);
-    }
-
-    all_paths
-}
-
-fn find_paths_recursive(
-    node: &normalize_ecosystems::TreeNode,
-    target: &str,
-    current_path: &mut Vec<(String, String)>,
-    all_paths: &mut Vec<Vec<(String, String)>>,
-) {
-    if node.name == target || node.name.ends_with(&format!("/{}", target)) {
-        all_paths.push(current_path.clone());
-        return;
-    }
-
-    for child in &node.dependencies {
-        current_path.push((child.name.clone(), child.version.clone()));
-        find_paths_recursive(child, target, current_path, all_paths);
-        current_path.pop();
-    }
-}
-
 // ── Internal helpers ──────────────────────────────────────────────────────────
 
 fn resolve_single_ecosystem(
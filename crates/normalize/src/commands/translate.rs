@@ -10,6 +10,8 @@ pub enum SourceLanguage {
     Lua,
     /// Python
     Python,
+    /// Go
+    Go,
 }
 
 /// Target language for translation.
@@ -30,6 +32,7 @@ impl SourceLanguage {
             SourceLanguage::Typescript => "typescript",
             SourceLanguage::Lua => "lua",
             SourceLanguage::Python => "python",
+            SourceLanguage::Go => "go",
         }
     }
 }
@@ -47,6 +50,7 @@ impl std::str::FromStr for SourceLanguage {
             "typescript" => Ok(Self::Typescript),
             "lua" => Ok(Self::Lua),
             "python" => Ok(Self::Python),
+            "go" => Ok(Self::Go),
             _ => Err(format!("unknown source language: {s}")),
         }
     }
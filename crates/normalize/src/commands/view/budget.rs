@@ -0,0 +1,245 @@
+//! Token-budgeted view output.
+//!
+//! `normalize view --budget N` shrinks a view until its text rendering fits in
+//! roughly N tokens. Detail is elided in a fixed order so the same input and
+//! budget always produce the same output:
+//!
+//! 1. private (non-public) symbols
+//! 2. bodies — full source falls back to the skeleton, and nested members of
+//!    classes/impls are collapsed
+//! 3. docstrings (including the module summary)
+//! 4. trailing top-level symbols, as a last resort
+//!
+//! Each step taken is reported in a single `budget:` warning on the report.
+
+use super::report::ViewReport;
+use crate::output::OutputFormatter;
+use crate::tree::{DocstringDisplay, ViewNode, ViewNodeKind};
+use std::path::Path;
+
+/// Estimate the token count of `text` with [`normalize_tokens::default_tokenizer`].
+///
+/// The heuristic tokenizer is deterministic, so budgets are reproducible for a
+/// given build; with feature `bpe` counts are exact `cl100k_base`.
+pub fn estimate_tokens(text: &str) -> usize {
+    normalize_tokens::count_tokens(text)
+}
+
+/// Shrink `report` until its text rendering fits in `budget` tokens.
+///
+/// `root` is needed to rebuild a full-source file view as a skeleton.
+pub fn fit_to_budget(report: &mut ViewReport, budget: usize, root: &Path) {
+    if fits(report, budget) {
+        return;
+    }
+
+    let mut elided: Vec<String> = Vec::new();
+    let note = report.warnings.len();
+    report.warnings.push(String::new());
+
+    // 1. Private symbols.
+    let mut private = drop_private(&mut report.node);
+    if private > 0 {
+        elided.push(format!("{} private symbols", private));
+    }
+    if update(report, budget, note, &elided) {
+        return;
+    }
+
+    // 2. Bodies.
+    if report.source.is_some() {
+        if matches!(report.node.kind, ViewNodeKind::File) {
+            if let Ok(skeleton) = super::file::build_view_file_service(
+                &report.target,
+                root,
+                2,
+                false,
+                false,
+                false,
+                false,
+                DocstringDisplay::Summary,
+                false,
+            ) {
+                report.node = skeleton.node;
+                report.summary = skeleton.summary;
+                report.source = None;
+                let dropped = drop_private(&mut report.node);
+                if dropped > 0 {
+                    private += dropped;
+                    elided.retain(|e| !e.ends_with("private symbols"));
+                    elided.push(format!("{} private symbols", private));
+                }
+                elided.push("function bodies".to_string());
+            }
+        } else {
+            report.source = None;
+            elided.push("body".to_string());
+        }
+    }
+    let nested = collapse_nested(&mut report.node);
+    if nested > 0 {
+        elided.push(format!("{} nested members", nested));
+    }
+    if update(report, budget, note, &elided) {
+        return;
+    }
+
+    // 3. Docstrings.
+    let mut docs = strip_docstrings(&mut report.node);
+    if report.summary.take().is_some() {
+        docs += 1;
+    }
+    if docs > 0 {
+        elided.push(format!("{} docstrings", docs));
+    }
+    if update(report, budget, note, &elided) {
+        return;
+    }
+
+    // 4. Trailing top-level symbols.
+    elided.push(String::new());
+    let mut dropped = 0;
+    while report.node.children.pop().is_some() {
+        dropped += 1;
+        if let Some(last) = elided.last_mut() {
+            *last = format!("{} trailing symbols", dropped);
+        }
+        if update(report, budget, note, &elided) {
+            return;
+        }
+    }
+}
+
+fn fits(report: &ViewReport, budget: usize) -> bool {
+    estimate_tokens(&report.format_text()) <= budget
+}
+
+/// Rewrite the budget warning at `note` and check whether the report now fits.
+fn update(report: &mut ViewReport, budget: usize, note: usize, elided: &[String]) -> bool {
+    let steps: Vec<&str> = elided
+        .iter()
+        .map(String::as_str)
+        .filter(|s| !s.is_empty())
+        .collect();
+    report.warnings[note] = if steps.is_empty() {
+        format!("budget: {} tokens, nothing left to elide", budget)
+    } else {
+        format!("budget: {} tokens, elided {}", budget, steps.join(", "))
+    };
+    fits(report, budget)
+}
+
+/// Remove private symbols at any depth, returning how many were removed.
+fn drop_private(node: &mut ViewNode) -> usize {
+    let before = node.children.len();
+    node.children.retain(|c| !c.private);
+    let mut removed = before - node.children.len();
+    for child in &mut node.children {
+        removed += drop_private(child);
+    }
+    removed
+}
+
+/// Clear the children of every top-level symbol, returning how many were removed.
+fn collapse_nested(node: &mut ViewNode) -> usize {
+    node.children
+        .iter_mut()
+        .map(|c| {
+            std::mem::take(&mut c.children)
+                .iter()
+                .map(count)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+fn strip_docstrings(node: &mut ViewNode) -> usize {
+    let own = usize::from(node.docstring.take().is_some());
+    own + node
+        .children
+        .iter_mut()
+        .map(strip_docstrings)
+        .sum::<usize>()
+}
+
+fn count(node: &ViewNode) -> usize {
+    1 + node.children.iter().map(count).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, private: bool, children: Vec<ViewNode>) -> ViewNode {
+        ViewNode {
+            name: name.to_string(),
+            kind: ViewNodeKind::Symbol("function".to_string()),
+            path: format!("lib.rs/{}", name),
+            children,
+            signature: Some(format!("fn {}(input: &str) -> Result<Output, Error>", name)),
+            docstring: Some(format!(
+                "Documentation for {} explaining what it does.",
+                name
+            )),
+            line_range: Some((1, 10)),
+            grammar: Some("rust".to_string()),
+            private,
+        }
+    }
+
+    fn report() -> ViewReport {
+        let node = ViewNode::file("lib.rs", "lib.rs").with_children(vec![
+            symbol("public_a", false, vec![symbol("method", false, vec![])]),
+            symbol("helper", true, vec![]),
+            symbol("public_b", false, vec![]),
+            symbol("public_c", false, vec![]),
+        ]);
+        ViewReport {
+            target: "lib.rs".to_string(),
+            node,
+            source: None,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            parent_signatures: Vec::new(),
+            line_range: None,
+            grammar: Some("rust".to_string()),
+            warnings: Vec::new(),
+            summary: None,
+            dir_context: None,
+        }
+    }
+
+    #[test]
+    fn test_fit_to_budget_steps() {
+        let root = Path::new(".");
+
+        let mut generous = report();
+        fit_to_budget(&mut generous, 10_000, root);
+        assert!(generous.warnings.is_empty());
+        assert_eq!(generous.node.children.len(), 4);
+
+        // Each tighter budget elides strictly more, in the documented order.
+        let full = estimate_tokens(&report().format_text());
+        let mut tight = report();
+        fit_to_budget(&mut tight, full - 1, root);
+        assert!(estimate_tokens(&tight.format_text()) < full);
+        assert!(tight.warnings[0].contains("1 private symbols"));
+        assert!(tight.node.children.iter().all(|c| !c.private));
+
+        let mut tiny = report();
+        fit_to_budget(&mut tiny, 1, root);
+        assert!(tiny.node.children.is_empty());
+        let warning = &tiny.warnings[0];
+        let order = [
+            "private symbols",
+            "nested members",
+            "docstrings",
+            "trailing symbols",
+        ];
+        let positions: Vec<usize> = order
+            .iter()
+            .map(|step| warning.find(step).unwrap_or(usize::MAX))
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", warning);
+    }
+}
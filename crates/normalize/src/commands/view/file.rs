@@ -16,6 +16,7 @@ pub fn build_view_file_service(
     show_deps: bool,
     types_only: bool,
     show_tests: bool,
+    full_signatures: bool,
     _docstring_mode: DocstringDisplay,
     context: bool,
 ) -> Result<ViewReport, String> {
@@ -68,7 +69,10 @@ pub fn build_view_file_service(
         .as_ref()
         .and_then(|lang| lang.extract_module_doc(&content));
 
-    let extractor = skeleton::SkeletonExtractor::new();
+    let extractor = skeleton::SkeletonExtractor::with_options(skeleton::SkeletonOptions {
+        full_signatures,
+        ..Default::default()
+    });
     let skeleton_result = extractor.extract(&full_path, &content);
 
     let skeleton_result = if types_only {
@@ -1,9 +1,9 @@
-//! Symbol history via gix blame (line-range commit walk).
+//! Symbol history via line-range tracing (`git log -L` over gix).
 //!
-//! Uses gix blame with a line range to find the commits that introduced lines
-//! in the target range. This is analogous to `git log -L` but implemented via
-//! the blame algorithm — each unique commit in the blame output for the range
-//! is returned in reverse-chronological order.
+//! Resolves the target symbol to its current line range, then walks history
+//! with [`normalize_git::git_log_line_range`]: each commit's diff is mapped
+//! through the range so that edits elsewhere in the file shift it rather than
+//! lose it. Unlike blame, commits whose lines were later rewritten still show up.
 
 use super::report::{ViewHistoryCommit, ViewHistoryReport};
 use super::symbol::find_symbol_ci;
@@ -91,11 +91,11 @@ pub fn build_view_history_report(
     build_line_history_service(root, &file_path, start_line, end_line, limit)
 }
 
-/// Build history for a line range (service layer) via gix blame.
+/// Build history for a line range (service layer) via `git log -L`-style tracing.
 ///
-/// Blames the file restricted to [start_line, end_line] (1-based inclusive).
-/// Each unique commit_id in the blame output is resolved to author/date/message
-/// and returned in reverse-chronological order (newest first), up to `limit`.
+/// Follows [start_line, end_line] (1-based inclusive) back through first-parent
+/// history, returning every commit whose diff touched the range — newest first,
+/// up to `limit`. The first entry answers "who last changed this".
 fn build_line_history_service(
     root: &Path,
     file_path: &str,
@@ -103,68 +103,21 @@ fn build_line_history_service(
     end_line: usize,
     limit: usize,
 ) -> Result<ViewHistoryReport, String> {
-    let repo = gix::discover(root).map_err(|e| format!("Not a git repository: {e}"))?;
-    let head_id = repo
-        .head_id()
-        .map_err(|e| format!("Failed to resolve HEAD: {e}"))?;
-
-    let path_bstr: &gix::bstr::BStr = file_path.as_bytes().into();
-    let ranges = gix::blame::BlameRanges::from_one_based_inclusive_range(
-        (start_line as u32)..=(end_line as u32),
-    )
-    .map_err(|e| format!("Invalid line range {start_line}..={end_line}: {e}"))?;
-
-    let outcome = repo
-        .blame_file(
-            path_bstr,
-            head_id.detach(),
-            gix::repository::blame_file::Options {
-                ranges,
-                ..Default::default()
-            },
-        )
-        .map_err(|e| format!("git blame failed for {file_path}: {e}"))?;
-
-    // Collect unique commit ids preserving order (newest first from blame output).
-    let mut seen = std::collections::HashSet::new();
-    let mut unique_commit_ids: Vec<gix::hash::ObjectId> = Vec::new();
-    for entry in &outcome.entries {
-        if seen.insert(entry.commit_id) {
-            unique_commit_ids.push(entry.commit_id);
-        }
-        if unique_commit_ids.len() >= limit {
-            break;
-        }
-    }
-
-    // Resolve each commit id to author/date/message.
-    let mut commits = Vec::new();
-    for commit_id in unique_commit_ids {
-        let Ok(obj) = repo.find_object(commit_id) else {
-            continue;
-        };
-        let commit_obj = obj.into_commit();
-        let hash = commit_id.to_hex().to_string();
-        use gix::bstr::ByteSlice;
-        let author = commit_obj
-            .author()
-            .ok()
-            .map(|a| a.name.to_str_lossy().into_owned())
-            .unwrap_or_default();
-        let timestamp = commit_obj.time().ok().map(|t| t.seconds).unwrap_or(0);
-        let date = crate::commands::analyze::git_utils::format_unix_date(timestamp);
-        let message = commit_obj
-            .message()
-            .ok()
-            .map(|m| m.summary().to_str_lossy().into_owned())
-            .unwrap_or_default();
-        commits.push(ViewHistoryCommit {
-            hash,
-            author,
-            date,
-            message,
-        });
-    }
+    let traced = normalize_git::git_log_line_range(root, file_path, start_line, end_line, limit)?;
+    let commits = traced
+        .into_iter()
+        .map(|c| ViewHistoryCommit {
+            date: crate::commands::analyze::git_utils::format_unix_date(c.timestamp),
+            hash: c.id,
+            author: c.author,
+            email: c.email,
+            message: c.summary,
+            path: c.path,
+            lines: format!("{}-{}", c.start, c.end),
+            added: c.added,
+            deleted: c.deleted,
+        })
+        .collect();
 
     Ok(ViewHistoryReport {
         file: file_path.to_string(),
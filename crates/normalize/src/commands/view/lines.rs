@@ -108,6 +108,7 @@ pub fn build_view_line_range_service(
         docstring: None,
         line_range: Some((actual_start, actual_end)),
         grammar: grammar.clone(),
+        private: false,
     };
 
     Ok(ViewReport {
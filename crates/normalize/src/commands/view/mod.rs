@@ -1,5 +1,6 @@
 //! View command - unified view of files, directories, and symbols.
 
+pub mod budget;
 pub mod chunked;
 pub mod file;
 pub mod history;
@@ -76,16 +77,18 @@ pub async fn build_view_service(
     _focus: Option<&str>,
     _resolve_imports: bool,
     full: bool,
+    full_signatures: bool,
     docstring_mode: DocstringDisplay,
     context: bool,
     show_parent: bool,
     exclude: &[String],
     only: &[String],
+    predicates: &[crate::filter::FilterPredicate],
     case_insensitive: bool,
     context_files: &[&str],
 ) -> Result<report::ViewReport, String> {
-    // Build filter if exclude/only patterns are specified
-    let filter = super::build_filter(root, exclude, only);
+    // Build filter if exclude/only patterns or predicates are specified
+    let filter = super::build_filter_with_predicates(root, exclude, only, predicates);
 
     // If kind filter is specified, route to list service and error if multiple
     if let Some(kind) = kind_filter {
@@ -113,6 +116,7 @@ pub async fn build_view_service(
     // Handle "." as current directory
     if target_str == "." {
         return tree::build_view_directory_service(
+            root,
             root,
             depth,
             raw,
@@ -245,6 +249,7 @@ pub async fn build_view_service(
     if unified.is_directory {
         tree::build_view_directory_service(
             &root.join(&unified.file_path),
+            root,
             depth,
             raw,
             filter.as_ref(),
@@ -259,6 +264,7 @@ pub async fn build_view_service(
             _show_deps,
             types_only,
             show_tests,
+            full_signatures,
             docstring_mode,
             context,
         )
@@ -270,6 +276,7 @@ pub async fn build_view_service(
             _show_deps,
             types_only,
             show_tests,
+            full_signatures,
             docstring_mode,
             context,
         )
@@ -320,15 +327,17 @@ pub async fn build_view_list_service(
     show_tests: bool,
     raw: bool,
     _show_deps: bool,
+    full_signatures: bool,
     docstring_mode: DocstringDisplay,
     context: bool,
     show_parent: bool,
     exclude: &[String],
     only: &[String],
+    predicates: &[crate::filter::FilterPredicate],
     case_insensitive: bool,
     context_files: &[&str],
 ) -> Result<report::ViewListReport, String> {
-    let filter = super::build_filter(root, exclude, only);
+    let filter = super::build_filter_with_predicates(root, exclude, only, predicates);
 
     // Kind filter → list matching symbols
     if let Some(kind) = kind_filter {
@@ -341,8 +350,14 @@ pub async fn build_view_list_service(
 
     // Directory → list children
     if target_str == "." {
-        let dir_report =
-            tree::build_view_directory_service(root, depth, raw, filter.as_ref(), context_files)?;
+        let dir_report = tree::build_view_directory_service(
+            root,
+            root,
+            depth,
+            raw,
+            filter.as_ref(),
+            context_files,
+        )?;
         // Return all direct children as separate ViewReports
         let children: Vec<report::ViewReport> = dir_report
             .node
@@ -420,6 +435,7 @@ pub async fn build_view_list_service(
             if m.is_directory {
                 if let Ok(r) = tree::build_view_directory_service(
                     &root.join(&m.file_path),
+                    root,
                     depth,
                     raw,
                     filter.as_ref(),
@@ -434,6 +450,7 @@ pub async fn build_view_list_service(
                 _show_deps,
                 types_only,
                 show_tests,
+                full_signatures,
                 docstring_mode,
                 context,
             ) {
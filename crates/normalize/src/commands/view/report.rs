@@ -67,8 +67,17 @@ pub struct ViewHistoryReport {
 pub struct ViewHistoryCommit {
     pub hash: String,
     pub author: String,
+    pub email: String,
     pub date: String,
     pub message: String,
+    /// File path at this commit (differs from the report's `file` across renames).
+    pub path: String,
+    /// The traced range after this commit, as `start-end`.
+    pub lines: String,
+    /// Lines of the range this commit added or rewritten.
+    pub added: usize,
+    /// Lines this commit removed from the range.
+    pub deleted: usize,
 }
 
 // --- Rendering helpers ---
@@ -98,12 +107,15 @@ fn pretty_opts() -> FormatOptions {
 fn render_dir(report: &ViewReport, opts: &FormatOptions) -> String {
     let counts = count_dir_file_nodes(&report.node);
     let lines = format_view_node(&report.node, opts);
-    let tree_text = format!(
+    let mut tree_text = format!(
         "{}\n\n{} directories, {} files",
         lines.join("\n"),
         counts.directories,
         counts.files
     );
+    for w in &report.warnings {
+        tree_text.push_str(&format!("\nwarning: {}", w));
+    }
     if let Some(summary) = &report.summary {
         format!("{}\n\n{}", summary.trim_end(), tree_text)
     } else {
@@ -398,12 +410,17 @@ impl OutputFormatter for ViewHistoryReport {
         } else {
             for c in &self.commits {
                 text.push_str(&format!(
-                    "  {} {} {} {}\n",
+                    "  {} {} {} {} (+{} -{})\n",
                     &c.hash[..8.min(c.hash.len())],
                     c.date,
                     c.author,
-                    c.message
+                    c.message,
+                    c.added,
+                    c.deleted
                 ));
+                if c.path != self.file {
+                    text.push_str(&format!("    (as {} L{})\n", c.path, c.lines));
+                }
             }
         }
         text
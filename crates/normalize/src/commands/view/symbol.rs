@@ -437,6 +437,7 @@ pub fn build_view_symbol_service(
                         _ => None,
                     },
                     grammar: grammar.clone(),
+                    private: false,
                 }
             },
             source: Some(source),
@@ -557,6 +558,7 @@ pub fn build_view_symbol_glob_service(
                 docstring: None,
                 line_range: Some((m.symbol.start_line, m.symbol.end_line)),
                 grammar: grammar.clone(),
+                private: false,
             };
             ViewReport {
                 target: sym_path,
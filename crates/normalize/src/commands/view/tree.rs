@@ -2,7 +2,7 @@
 
 use super::report::ViewReport;
 use super::search::has_language_support;
-use crate::filter::Filter;
+use crate::filter::{DirDecision, Filter};
 use crate::tree::{ViewNode, ViewNodeKind};
 use crate::{path_resolve, symbols, tree};
 use serde::{Deserialize, Serialize};
@@ -57,8 +57,11 @@ impl SymbolKindFilter {
 }
 
 /// Build a directory tree view for the service layer.
+///
+/// `dir` is the directory to show; `root` is the project root the filter is relative to.
 pub fn build_view_directory_service(
     dir: &Path,
+    root: &Path,
     depth: i32,
     raw: bool,
     filter: Option<&Filter>,
@@ -83,7 +86,15 @@ pub fn build_view_directory_service(
     );
 
     let view_node = if let Some(f) = filter {
-        filter_view_node(view_node, f)
+        // Node paths start with the directory's own name; the filter wants them
+        // relative to the project root.
+        let top = format!(
+            "{}/",
+            dir.file_name()
+                .map_or_else(|| ".".into(), |n| n.to_string_lossy())
+        );
+        let base = dir.strip_prefix(root).unwrap_or(Path::new(""));
+        filter_view_node(view_node, f, base, &top)
     } else {
         view_node
     };
@@ -109,15 +120,21 @@ pub fn build_view_directory_service(
 }
 
 /// Filter a ViewNode tree, removing nodes that don't pass the filter.
-fn filter_view_node(mut node: ViewNode, filter: &Filter) -> ViewNode {
+///
+/// Each node path has its leading `top` replaced by `base` before it is matched.
+fn filter_view_node(mut node: ViewNode, filter: &Filter, base: &Path, top: &str) -> ViewNode {
     node.children = node
         .children
         .into_iter()
         .filter_map(|child| {
-            let path = std::path::Path::new(&child.path);
+            let path = base.join(child.path.strip_prefix(top).unwrap_or(&child.path));
+            let path = path.as_path();
             match child.kind {
                 ViewNodeKind::Directory => {
-                    let filtered = filter_view_node(child, filter);
+                    if filter.matches_dir(path) == DirDecision::Skip {
+                        return None;
+                    }
+                    let filtered = filter_view_node(child, filter, base, top);
                     if filtered.children.is_empty() {
                         None
                     } else {
@@ -215,6 +232,7 @@ pub fn build_view_filtered_service(
                 docstring: None,
                 line_range: Some((*line, *line)),
                 grammar: None,
+                private: false,
             };
             ViewReport {
                 target: sym_path,
@@ -4,8 +4,13 @@
 //! 1. Global: ~/.config/normalize/config.toml
 //! 2. Per-project: .normalize/config.toml (overrides global)
 //!
+//! Files are migrated to the current format version on load (see
+//! [`crate::config_migrate`]); `normalize config migrate --write` persists it.
+//!
 //! Example config.toml:
 //! ```toml
+//! version = 3                   # config format version (see config_migrate)
+//!
 //! [daemon]
 //! enabled = true
 //! auto_start = true
@@ -60,6 +65,7 @@
 //! enabled = true              # auto-enable when TTY (default: auto)
 //! colors = "auto"             # "auto", "always", or "never"
 //! highlight = true            # syntax highlighting on signatures
+//! pager = false               # page long pretty reports through $PAGER
 //!
 //! [serve]
 //! fact_debounce_ms = 1500     # debounce for LSP fact diagnostics (ms)
@@ -67,6 +73,11 @@
 //! [walk]
 //! ignore_files = [".gitignore"]  # gitignore-format files to respect (default: [".gitignore"])
 //! exclude = [".git"]             # directory names to always skip (default: [".git"])
+//!
+//! [licenses]
+//! allowed = ["MIT", "Apache-2.0", "BSD-3-Clause"]  # anything else is a violation
+//! denied = ["AGPL-3.0-only"]
+//! needs_review = ["MPL-2.0"]
 //! ```
 
 use crate::commands::analyze::AnalyzeConfig;
@@ -103,6 +114,9 @@ pub struct RuleTagsConfig(pub std::collections::HashMap<String, Vec<String>>);
 #[derive(Debug, Clone, Deserialize, Serialize, Default, JsonSchema, server_less::Config)]
 #[serde(default)]
 pub struct NormalizeConfig {
+    /// Config format version. Older files are migrated on load; run
+    /// `normalize config migrate --write` to update the file itself.
+    pub version: Option<u32>,
     #[param(nested)]
     pub daemon: DaemonConfig,
     #[param(nested, serde)]
@@ -139,6 +153,12 @@ pub struct NormalizeConfig {
     /// Walk configuration for directory traversal (`[walk]` section).
     #[param(nested, serde)]
     pub walk: normalize_rules_config::WalkConfig,
+    /// Session analysis settings (`[sessions]` section), e.g. cost budgets.
+    #[param(nested, serde)]
+    pub sessions: normalize_sessions::SessionsConfig,
+    /// License policy for `normalize package licenses` (`[licenses]` section).
+    #[param(nested, serde)]
+    pub licenses: normalize_ecosystems::LicensePolicy,
 }
 
 impl NormalizeConfig {
@@ -184,34 +204,15 @@ impl NormalizeConfig {
             return Self::bootstrap();
         }
 
-        // Pre-parse check: if any config file contains [embeddings] (removed in
-        // 0.3.0), emit a migration warning. The section is unknown to the current
-        // schema and will be ignored by serde — we do not abort so that commands
-        // running inside spawned git worktrees (e.g. `trend complexity`) which
-        // check out historical commits with older configs still work correctly.
-        for path in [Some(&project_config), global_config.as_ref()]
-            .into_iter()
-            .flatten()
-            .filter(|p| p.exists())
-        {
-            if let Ok(raw) = std::fs::read_to_string(path)
-                && let Ok(value) = raw.parse::<toml::Value>()
-                && value.get("embeddings").is_some()
-            {
-                eprintln!(
-                    "warning: {} contains [embeddings] which was removed in 0.3.0.\n\
-                     Remove the [embeddings] section from {} to silence this warning.",
-                    path.display(),
-                    path.display()
-                );
-            }
-        }
-
+        // Files in an older format are migrated in memory and loaded from the
+        // migrated table; we warn rather than abort so that commands running
+        // inside spawned git worktrees (e.g. `trend complexity`) which check out
+        // historical commits with older configs still work correctly.
         let mut sources = vec![server_less::ConfigSource::Defaults];
         if let Some(global_path) = global_config {
-            sources.push(server_less::ConfigSource::File(global_path));
+            sources.push(Self::migrated_source(global_path));
         }
-        sources.push(server_less::ConfigSource::File(project_config.clone()));
+        sources.push(Self::migrated_source(project_config.clone()));
         let mut config = <Self as server_less::ConfigLoad>::load(&sources).unwrap_or_else(|e| {
             // Warn on parse errors so the user knows their config is being ignored.
             // Missing files are silently skipped by ConfigLoad; only real errors surface here.
@@ -234,6 +235,52 @@ impl NormalizeConfig {
         config
     }
 
+    /// Config source for `path`, migrated in memory when the file predates
+    /// [`CURRENT_CONFIG_VERSION`](crate::config_migrate::CURRENT_CONFIG_VERSION).
+    ///
+    /// Missing, unreadable and unparsable files fall through to a plain
+    /// `File` source so `ConfigLoad` reports them as before.
+    fn migrated_source(path: std::path::PathBuf) -> server_less::ConfigSource {
+        use crate::config_migrate::{CURRENT_CONFIG_VERSION, config_version, migrate_document};
+
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            return server_less::ConfigSource::File(path);
+        };
+        let Ok(mut doc) = raw.parse::<toml_edit::DocumentMut>() else {
+            return server_less::ConfigSource::File(path);
+        };
+        let version = config_version(&doc);
+        if version > CURRENT_CONFIG_VERSION {
+            eprintln!(
+                "warning: {} has config version {version}, newer than this normalize \
+                 understands ({CURRENT_CONFIG_VERSION}); unknown keys will be ignored.",
+                path.display()
+            );
+        }
+        // An old file none of whose keys changed reads the same either way;
+        // only warn when the migration actually rewrote something.
+        let outcome = migrate_document(&mut doc);
+        if outcome.changes.is_empty() {
+            return server_less::ConfigSource::File(path);
+        }
+        eprintln!(
+            "warning: {} uses config version {} (current: {}); migrated in memory: {}.\n\
+             Run `normalize config migrate --write` to update the file.",
+            path.display(),
+            outcome.from_version,
+            outcome.to_version,
+            outcome.changes.join("; "),
+        );
+        match toml::from_str::<toml::Value>(&doc.to_string()) {
+            // `TomlTable` is the pre-parsed source the Config derive feeds to
+            // nested sections; at the top level it loads a whole file's table.
+            Ok(value) => server_less::ConfigSource::TomlTable(
+                server_less::config::NestedTomlTable::from_value(value),
+            ),
+            Err(_) => server_less::ConfigSource::File(path),
+        }
+    }
+
     /// Get the global config path.
     pub fn global_config_path() -> Option<std::path::PathBuf> {
         let config_home = std::env::var("XDG_CONFIG_HOME")
@@ -624,4 +671,21 @@ enabled = true
             "daemon.enabled must come from the config"
         );
     }
+
+    #[test]
+    fn test_legacy_config_is_migrated_on_load() {
+        let dir = TempDir::new().unwrap();
+        let normalize_dir = dir.path().join(".normalize");
+        std::fs::create_dir_all(&normalize_dir).unwrap();
+        let path = normalize_dir.join("config.toml");
+        let legacy = "[rules.\"rust/unwrap-in-impl\"]\ndeny = true\n";
+        std::fs::write(&path, legacy).unwrap();
+
+        let config = NormalizeConfig::load(dir.path());
+        let rule = &config.rules.rules["rust/unwrap-in-impl"];
+        assert_eq!(rule.severity.as_deref(), Some("error"));
+        assert!(!rule.extra.contains_key("deny"));
+        // Loading never rewrites the file.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), legacy);
+    }
 }
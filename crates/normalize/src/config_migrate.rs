@@ -0,0 +1,247 @@
+//! Versioned migrations for `config.toml`.
+//!
+//! Every config file carries a top-level `version` key (absent means 0). When a
+//! file is older than [`CURRENT_CONFIG_VERSION`], [`migrate_document`] applies
+//! each registered [`Migration`] in turn — renamed keys, moved sections,
+//! removed sections — and stamps the new version.
+//!
+//! [`NormalizeConfig::load`](crate::config::NormalizeConfig::load) migrates in
+//! memory (with a warning) so old files keep working; `normalize config migrate
+//! --write` rewrites the file. Edits go through `toml_edit`, so comments and
+//! layout outside the migrated keys survive the rewrite.
+//!
+//! To change the config format: append a migration to [`MIGRATIONS`]. Its
+//! position in the list is the version it upgrades *from*.
+
+use toml_edit::{DocumentMut, Item, Table, value};
+
+/// One step in the config format history.
+pub struct Migration {
+    /// Short description shown by `normalize config migrate`.
+    pub description: &'static str,
+    /// Rewrite `doc` in place, returning one line per change made.
+    pub apply: fn(&mut DocumentMut) -> Vec<String>,
+}
+
+/// Registered migrations; `MIGRATIONS[n]` upgrades version `n` to `n + 1`.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "remove [embeddings] (dropped in 0.3.0)",
+        apply: remove_embeddings,
+    },
+    Migration {
+        description: "move [rules.\"<id>\"] overrides under [rules.rule.\"<id>\"]",
+        apply: nest_rule_overrides,
+    },
+    Migration {
+        description: "replace rule `deny = true` with `severity = \"error\"`",
+        apply: deny_to_severity,
+    },
+];
+
+/// Version written by this build (and stamped by `normalize init`).
+pub const CURRENT_CONFIG_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// What [`migrate_document`] did to one file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationOutcome {
+    /// `version` found in the file (0 when absent).
+    pub from_version: u32,
+    /// Version after migrating; equals `from_version` when nothing applied.
+    pub to_version: u32,
+    /// Human-readable changes, in the order they were made.
+    pub changes: Vec<String>,
+}
+
+impl MigrationOutcome {
+    /// True when the document was rewritten.
+    pub fn migrated(&self) -> bool {
+        self.to_version != self.from_version
+    }
+}
+
+/// Read the `version` key of a config document (0 when absent or not an integer).
+pub fn config_version(doc: &DocumentMut) -> u32 {
+    doc.get("version")
+        .and_then(Item::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Apply every migration newer than the document's `version`, then stamp
+/// [`CURRENT_CONFIG_VERSION`].
+///
+/// A document already at (or beyond) the current version is left untouched.
+pub fn migrate_document(doc: &mut DocumentMut) -> MigrationOutcome {
+    let from_version = config_version(doc);
+    let mut outcome = MigrationOutcome {
+        from_version,
+        to_version: from_version,
+        changes: Vec::new(),
+    };
+    if from_version >= CURRENT_CONFIG_VERSION {
+        return outcome;
+    }
+    for migration in &MIGRATIONS[from_version as usize..] {
+        outcome.changes.extend((migration.apply)(doc));
+    }
+    doc.insert("version", value(i64::from(CURRENT_CONFIG_VERSION)));
+    outcome.to_version = CURRENT_CONFIG_VERSION;
+    outcome
+}
+
+/// Parse `source`, migrate it, and render the result.
+pub fn migrate_str(source: &str) -> Result<(String, MigrationOutcome), String> {
+    let mut doc: DocumentMut = source
+        .parse()
+        .map_err(|e| format!("Failed to parse config: {e}"))?;
+    let outcome = migrate_document(&mut doc);
+    Ok((doc.to_string(), outcome))
+}
+
+// ── Migrations ──────────────────────────────────────────────────────────────
+
+/// Keys under `[rules]` that belong to the engine rather than naming a rule.
+const RULES_ENGINE_KEYS: &[&str] = &["global-allow", "sarif-tools", "rule"];
+
+fn remove_embeddings(doc: &mut DocumentMut) -> Vec<String> {
+    match doc.remove("embeddings") {
+        Some(_) => vec!["removed [embeddings]".to_string()],
+        None => Vec::new(),
+    }
+}
+
+fn nest_rule_overrides(doc: &mut DocumentMut) -> Vec<String> {
+    let Some(rules) = doc.get_mut("rules").and_then(Item::as_table_like_mut) else {
+        return Vec::new();
+    };
+    let legacy: Vec<String> = rules
+        .iter()
+        .map(|(k, _)| k.to_string())
+        .filter(|k| !RULES_ENGINE_KEYS.contains(&k.as_str()))
+        .collect();
+    if legacy.is_empty() {
+        return Vec::new();
+    }
+    if rules.get("rule").is_none() {
+        let mut nested = Table::new();
+        nested.set_implicit(true);
+        rules.insert("rule", Item::Table(nested));
+    }
+    let mut moved = Vec::new();
+    for id in legacy {
+        let Some(item) = rules.remove(&id) else {
+            continue;
+        };
+        let Some(nested) = rules.get_mut("rule").and_then(Item::as_table_like_mut) else {
+            break;
+        };
+        if nested.contains_key(&id) {
+            // Same precedence as `RulesConfig` deserialization: nested wins.
+            moved.push(format!(
+                "dropped [rules.\"{id}\"] (superseded by [rules.rule.\"{id}\"])"
+            ));
+        } else {
+            nested.insert(&id, item);
+            moved.push(format!("moved [rules.\"{id}\"] to [rules.rule.\"{id}\"]"));
+        }
+    }
+    moved
+}
+
+fn deny_to_severity(doc: &mut DocumentMut) -> Vec<String> {
+    let Some(overrides) = doc
+        .get_mut("rules")
+        .and_then(Item::as_table_like_mut)
+        .and_then(|rules| rules.get_mut("rule"))
+        .and_then(Item::as_table_like_mut)
+    else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    for (id, item) in overrides.iter_mut() {
+        let Some(rule) = item.as_table_like_mut() else {
+            continue;
+        };
+        let Some(deny) = rule.remove("deny") else {
+            continue;
+        };
+        if deny.as_bool() == Some(true) && !rule.contains_key("severity") {
+            rule.insert("severity", value("error"));
+            changes.push(format!(
+                "[rules.rule.\"{id}\"]: deny = true → severity = \"error\""
+            ));
+        } else {
+            changes.push(format!("[rules.rule.\"{id}\"]: removed deny"));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_legacy_file_to_current_version() {
+        let src = r#"# project config
+[rules]
+global-allow = ["fixtures/**"]
+
+[embeddings]
+model = "x"
+
+[rules."rust/unwrap-in-impl"]
+deny = true # fail CI
+
+[rules.rule."no-todo"]
+enabled = false
+"#;
+        let (out, outcome) = migrate_str(src).unwrap();
+        assert_eq!(outcome.from_version, 0);
+        assert_eq!(outcome.to_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(outcome.changes.len(), 3, "{:?}", outcome.changes);
+        let stamp = format!("version = {CURRENT_CONFIG_VERSION}\n# project config\n");
+        assert!(out.starts_with(&stamp), "{out}");
+        assert!(!out.contains("embeddings"), "{out}");
+        assert!(!out.contains("deny"), "{out}");
+
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        let rule = &parsed["rules"]["rule"]["rust/unwrap-in-impl"];
+        assert_eq!(rule["severity"].as_str(), Some("error"));
+        assert_eq!(
+            parsed["rules"]["rule"]["no-todo"]["enabled"].as_bool(),
+            Some(false)
+        );
+
+        // Migrating again is a no-op.
+        let (again, outcome) = migrate_str(&out).unwrap();
+        assert!(!outcome.migrated());
+        assert_eq!(again, out);
+    }
+
+    #[test]
+    fn nested_override_wins_over_legacy() {
+        let src = r#"[rules."a"]
+severity = "hint"
+
+[rules.rule."a"]
+severity = "error"
+"#;
+        let (out, outcome) = migrate_str(src).unwrap();
+        assert!(outcome.changes[0].starts_with("dropped"), "{outcome:?}");
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        assert_eq!(
+            parsed["rules"]["rule"]["a"]["severity"].as_str(),
+            Some("error")
+        );
+    }
+
+    #[test]
+    fn current_version_is_untouched() {
+        let src = format!("version = {CURRENT_CONFIG_VERSION}\n[rules.\"x\"]\n");
+        let (out, outcome) = migrate_str(&src).unwrap();
+        assert!(!outcome.migrated());
+        assert_eq!(out, src);
+    }
+}
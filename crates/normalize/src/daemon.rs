@@ -161,6 +161,18 @@ pub enum Request {
         /// Absolute or relative paths to the files that changed.
         paths: Vec<PathBuf>,
     },
+    /// Return the symbol skeleton of one file as a `ViewNode` tree.
+    /// Served from the root's skeleton cache, which the watcher invalidates
+    /// together with the index; entries are also checked against the file's
+    /// mtime and size so an edit the watcher has not reported yet is never
+    /// served stale.
+    #[serde(rename = "skeleton")]
+    Skeleton {
+        /// The watched root the file belongs to.
+        root: PathBuf,
+        /// Absolute path, or path relative to `root`.
+        path: PathBuf,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -233,6 +245,8 @@ mod unix_impl {
     /// 2. `.normalize/config.toml` raw bytes (if present).
     /// 3. Every regular file under `.normalize/rules/` (sorted by path),
     ///    file name + bytes.
+    /// 4. `.normalize/facts-baseline.json` raw bytes (if present) — it hides
+    ///    fact rule diagnostics.
     ///
     /// This intentionally excludes source files and the index — those have
     /// their own per-file mtime tracking inside the rules engine. Hashing them
@@ -263,6 +277,9 @@ mod unix_impl {
                 }
             }
         }
+        if let Ok(content) = std::fs::read(root.join(".normalize/facts-baseline.json")) {
+            hasher.update(&content);
+        }
         hasher.finalize().to_hex().to_string()
     }
 
@@ -716,6 +733,42 @@ mod unix_impl {
         /// In-memory context index for `.normalize/context/` files.
         /// Updated incrementally when context files change via the file watcher.
         context_index: Arc<Mutex<ContextIndex>>,
+        /// Facts relations and file skeletons derived from the index. Dropped
+        /// together by [`DaemonServer::invalidate_root`] whenever files change.
+        caches: Arc<Mutex<RootCaches>>,
+    }
+
+    /// State derived from a root's index that must never outlive the index
+    /// contents it was built from.
+    ///
+    /// Keeping these next to the index (rather than in per-subsystem caches)
+    /// gives one invalidation point: every refresh and every `files_changed`
+    /// push goes through [`DaemonServer::invalidate_root`], so clients always
+    /// see relations and skeletons consistent with the index they query.
+    #[cfg(feature = "daemon")]
+    #[derive(Default)]
+    struct RootCaches {
+        /// Bumped on every invalidation. Relations built concurrently with an
+        /// invalidation are returned to their caller but not stored, so a slow
+        /// build can never reinstate pre-change facts.
+        generation: u64,
+        /// Facts relations for fact rules, built lazily from the index.
+        relations: Option<Arc<normalize_facts_rules_api::Relations>>,
+        /// Whether `relations` includes `depends_on` facts, which are only
+        /// built when an enabled rule reads them.
+        relations_have_dependencies: bool,
+        /// Serialized skeletons keyed by absolute file path.
+        skeletons: HashMap<PathBuf, CachedSkeleton>,
+        skeleton_hits: u64,
+        skeleton_misses: u64,
+    }
+
+    #[cfg(feature = "daemon")]
+    struct CachedSkeleton {
+        /// File mtime and size when the skeleton was extracted.
+        mtime: Option<std::time::SystemTime>,
+        len: u64,
+        node: serde_json::Value,
     }
 
     /// Sliding window over which refresh density is measured for spin detection.
@@ -928,6 +981,7 @@ mod unix_impl {
                     cached_scm_hashes: initial_scm_hashes,
                     serve_filter_pending: false,
                     context_index: Arc::new(Mutex::new(ctx_index)),
+                    caches: Arc::new(Mutex::new(RootCaches::default())),
                 },
             );
 
@@ -993,11 +1047,25 @@ mod unix_impl {
                 .lock()
                 .unwrap_or_else(|e| e.into_inner())
                 .clone();
+            let (mut relations, mut skeletons, mut hits, mut misses) = (0, 0, 0, 0);
+            for watched in roots.values() {
+                let caches = watched.caches.lock().unwrap_or_else(|e| e.into_inner());
+                relations += usize::from(caches.relations.is_some());
+                skeletons += caches.skeletons.len();
+                hits += caches.skeleton_hits;
+                misses += caches.skeleton_misses;
+            }
             Response::ok(serde_json::json!({
                 "uptime_secs": self.start_time.elapsed().as_secs(),
                 "roots_watched": roots.len(),
                 "pid": std::process::id(),
                 "spin_warnings": spin_warnings,
+                "caches": {
+                    "relations": relations,
+                    "skeletons": skeletons,
+                    "skeleton_hits": hits,
+                    "skeleton_misses": misses,
+                },
             }))
         }
 
@@ -1039,7 +1107,148 @@ mod unix_impl {
                     dir_name,
                 } => self.query_context(root, match_keys, all, dir_name),
                 Request::FilesChanged { root, paths } => self.handle_files_changed(root, paths),
+                Request::Skeleton { root, path } => self.skeleton(&root, &path),
+            }
+        }
+
+        /// Derived-state caches for a watched root, or `None` if not watched.
+        fn root_caches(&self, root: &Path) -> Option<Arc<Mutex<RootCaches>>> {
+            let roots = self.roots.lock().unwrap_or_else(|e| e.into_inner());
+            roots.get(root).map(|w| w.caches.clone())
+        }
+
+        /// Drop everything derived from the index for `root`: the facts
+        /// relations (any change can affect any relation) and the skeletons of
+        /// `changed` files. Relative paths are resolved against `root`.
+        fn invalidate_root(&self, root: &Path, changed: &[PathBuf]) {
+            let Some(caches) = self.root_caches(root) else {
+                return;
+            };
+            let mut caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+            caches.generation += 1;
+            caches.relations = None;
+            for path in changed {
+                caches.skeletons.remove(&root.join(path));
+            }
+        }
+
+        /// Facts relations for `root`, built from the index on a cache miss.
+        ///
+        /// `depends_on` facts are included only when an enabled rule under
+        /// `rules` reads them; cached relations built without them are rebuilt
+        /// once such a rule appears.
+        ///
+        /// Returns `None` (after logging) if the root is not watched or the
+        /// relations cannot be built; fact rules then report nothing, matching
+        /// the one-shot CLI path.
+        fn relations_for(
+            &self,
+            root: &Path,
+            rules: &normalize_rules_config::RulesConfig,
+        ) -> Option<Arc<normalize_facts_rules_api::Relations>> {
+            let caches = self.root_caches(root)?;
+            let with_dependencies = normalize_rules::rules_read_dependencies(
+                &normalize_rules::load_enabled_fact_rules(root, rules, None, None),
+            );
+            let generation = {
+                let caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(relations) = &caches.relations
+                    && (caches.relations_have_dependencies || !with_dependencies)
+                {
+                    return Some(relations.clone());
+                }
+                caches.generation
+            };
+            // Build without holding the cache lock; skeleton requests keep
+            // being served meanwhile.
+            let relations = match tokio::task::block_in_place(|| {
+                self.runtime_handle
+                    .block_on(normalize_rules::build_relations_from_index(
+                        root,
+                        with_dependencies,
+                    ))
+            }) {
+                Ok(r) => Arc::new(r),
+                Err(e) => {
+                    tracing::warn!(root = ?root, "failed to build relations for fact rules: {}", e);
+                    return None;
+                }
+            };
+            let mut caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+            if caches.generation == generation {
+                caches.relations = Some(relations.clone());
+                caches.relations_have_dependencies = with_dependencies;
             }
+            Some(relations)
+        }
+
+        /// Serve the skeleton of one file, extracting it on a cache miss.
+        fn skeleton(&self, root: &Path, path: &Path) -> Response {
+            let Some(caches) = self.root_caches(root) else {
+                return Response::err("root not watched");
+            };
+            let full_path = root.join(path);
+            let meta = match std::fs::metadata(&full_path) {
+                Ok(m) => m,
+                Err(e) => {
+                    return Response::err(&format!(
+                        "Failed to read {}: {}",
+                        full_path.display(),
+                        e
+                    ));
+                }
+            };
+            let mtime = meta.modified().ok();
+            let len = meta.len();
+
+            {
+                let mut caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(node) = caches
+                    .skeletons
+                    .get(&full_path)
+                    .filter(|c| c.mtime == mtime && c.len == len)
+                    .map(|c| c.node.clone())
+                {
+                    caches.skeleton_hits += 1;
+                    return Response::ok(node);
+                }
+            }
+
+            let content = match std::fs::read_to_string(&full_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    return Response::err(&format!(
+                        "Failed to read {}: {}",
+                        full_path.display(),
+                        e
+                    ));
+                }
+            };
+            let grammar = normalize_languages::support_for_path(&full_path)
+                .map(|s| s.grammar_name().to_string());
+            let extractor = crate::skeleton::SkeletonExtractor::new();
+            let node = {
+                use crate::skeleton::ExtractResultExt;
+                let result = extractor.extract(&full_path, &content);
+                match serde_json::to_value(result.to_view_node(grammar.as_deref())) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        return Response::err(&format!("Failed to serialize skeleton: {}", e));
+                    }
+                }
+            };
+
+            let mut caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+            caches.skeleton_misses += 1;
+            caches.skeletons.insert(
+                full_path,
+                CachedSkeleton {
+                    mtime,
+                    len,
+                    node: node.clone(),
+                },
+            );
+            Response::ok(node)
         }
 
         fn handle_files_changed(&self, root: PathBuf, paths: Vec<PathBuf>) -> Response {
@@ -1058,6 +1267,10 @@ mod unix_impl {
                 );
             }
 
+            // Drop derived state now rather than after the queued refresh, so
+            // queries issued right after the push never see the old contents.
+            self.invalidate_root(&root, &paths);
+
             // Broadcast FileChanged events so subscribers (e.g. `normalize daemon watch`)
             // see the push-triggered changes the same as inotify-triggered ones.
             for path in &paths {
@@ -1538,18 +1751,21 @@ mod unix_impl {
             let fact_issues: Vec<normalize_output::diagnostics::Issue> = {
                 let root_owned = root.to_path_buf();
                 let rules = config.rules.clone();
-                let handle = self.runtime_handle.clone();
+                let relations = self.relations_for(root, &rules);
                 std::thread::Builder::new()
                     .stack_size(64 * 1024 * 1024)
                     .spawn(move || {
-                        let diagnostics =
-                            handle.block_on(normalize_rules::collect_fact_diagnostics_incremental(
-                                &root_owned,
-                                &rules,
-                                None,
-                                None,
-                                None,
-                            ));
+                        let Some(relations) = relations else {
+                            return Vec::new();
+                        };
+                        let diagnostics = normalize_rules::collect_fact_diagnostics_with_relations(
+                            &root_owned,
+                            &rules,
+                            None,
+                            None,
+                            None,
+                            &relations,
+                        );
                         diagnostics
                             .iter()
                             .map(normalize_rules::abi_diagnostic_to_issue)
@@ -2200,6 +2416,7 @@ mod unix_impl {
                         // this refresh — backoff only suppresses *future* sends
                         // (see the dispatch loop); we never silently drop indexing.
                         self.record_refresh_and_detect_spin(root, &changed, Instant::now());
+                        self.invalidate_root(root, &changed);
 
                         if let Err(e) = self
                             .runtime_handle
@@ -2267,15 +2484,19 @@ mod unix_impl {
 
                         // --- Fact rules (incremental via ENGINE_CACHE) ---
                         let fact_issues: Vec<normalize_output::diagnostics::Issue> = {
-                            let diagnostics = self.runtime_handle.block_on(
-                                normalize_rules::collect_fact_diagnostics_incremental(
-                                    root,
-                                    &config.rules,
-                                    None,
-                                    None,
-                                    Some(&affected_vec),
-                                ),
-                            );
+                            let diagnostics = match self.relations_for(root, &config.rules) {
+                                Some(relations) => {
+                                    normalize_rules::collect_fact_diagnostics_with_relations(
+                                        root,
+                                        &config.rules,
+                                        None,
+                                        None,
+                                        Some(&affected_vec),
+                                        &relations,
+                                    )
+                                }
+                                None => Vec::new(),
+                            };
                             diagnostics
                                 .iter()
                                 .map(normalize_rules::abi_diagnostic_to_issue)
@@ -2561,18 +2782,21 @@ mod unix_impl {
                 let root_owned = root.to_path_buf();
                 let rules_config = new_rules.clone();
                 let filter_ids = rules_to_rerun.clone();
-                let handle = self.runtime_handle.clone();
+                let relations = self.relations_for(root, &rules_config);
                 std::thread::Builder::new()
                     .stack_size(64 * 1024 * 1024)
                     .spawn(move || {
-                        let diagnostics =
-                            handle.block_on(normalize_rules::collect_fact_diagnostics_incremental(
-                                &root_owned,
-                                &rules_config,
-                                Some(&filter_ids),
-                                None,
-                                None,
-                            ));
+                        let Some(relations) = relations else {
+                            return Vec::new();
+                        };
+                        let diagnostics = normalize_rules::collect_fact_diagnostics_with_relations(
+                            &root_owned,
+                            &rules_config,
+                            Some(&filter_ids),
+                            None,
+                            None,
+                            &relations,
+                        );
                         diagnostics
                             .iter()
                             .map(normalize_rules::abi_diagnostic_to_issue)
@@ -3652,6 +3876,18 @@ mod unix_impl {
             self.send(&request)
         }
 
+        /// Fetch a file's skeleton (a serialized `ViewNode`) from the daemon.
+        ///
+        /// `path` may be absolute or relative to `root`. Falls back to `Err`
+        /// if the daemon is not available; a response with `ok == false` means
+        /// the root is not watched or the file cannot be read.
+        pub fn skeleton(&self, root: &Path, path: &Path) -> Result<Response, String> {
+            self.send(&Request::Skeleton {
+                root: root.to_path_buf(),
+                path: path.to_path_buf(),
+            })
+        }
+
         /// Subscribe to daemon events, calling `on_event` for each one.
         ///
         /// Blocks until the connection is closed or `on_event` returns `false`.
@@ -3847,7 +4083,7 @@ mod unix_impl {
         /// Construct a minimal `DaemonServer` whose only working state is the
         /// SQLite-backed `WatchedRoot.index`. Watchers/channels are wired but
         /// unused — these tests never trigger refreshes or file events.
-        async fn make_test_server(root: &Path) -> Arc<DaemonServer> {
+        pub(super) async fn make_test_server(root: &Path) -> Arc<DaemonServer> {
             let (refresh_tx, _refresh_rx) = std::sync::mpsc::channel::<PathBuf>();
             let (config_reload_tx, _config_reload_rx) = std::sync::mpsc::channel::<PathBuf>();
             let (notify_tx, _notify_rx) =
@@ -3880,6 +4116,7 @@ mod unix_impl {
                     cached_scm_hashes: HashMap::new(),
                     serve_filter_pending: false,
                     context_index: Arc::new(Mutex::new(ContextIndex::new())),
+                    caches: Arc::new(Mutex::new(RootCaches::default())),
                 },
             );
             drop(roots);
@@ -4294,6 +4531,7 @@ allow = ["a.rs"]
                     cached_scm_hashes: HashMap::new(),
                     serve_filter_pending: false,
                     context_index: Arc::new(Mutex::new(ContextIndex::new())),
+                    caches: Arc::new(Mutex::new(RootCaches::default())),
                 },
             );
             drop(roots);
@@ -4522,6 +4760,117 @@ allow = ["a.rs"]
             assert_eq!(log.file_name().and_then(|n| n.to_str()), Some("daemon.log"));
         }
     }
+
+    #[cfg(all(test, feature = "daemon"))]
+    mod cache_tests {
+        use super::per_file_tests::make_test_server;
+        use super::*;
+
+        fn symbol_names(resp: &Response) -> Vec<String> {
+            let data = resp.data.as_ref().expect("skeleton data");
+            data["children"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|c| c["name"].as_str().unwrap().to_string())
+                .collect()
+        }
+
+        fn skeleton_counts(server: &DaemonServer, root: &Path) -> (u64, u64) {
+            let caches = server.root_caches(root).unwrap();
+            let caches = caches.lock().unwrap();
+            (caches.skeleton_hits, caches.skeleton_misses)
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+        async fn skeleton_is_cached_until_file_changes() {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path();
+            std::fs::write(root.join("a.py"), "def one():\n    pass\n").unwrap();
+            let server = make_test_server(root).await;
+
+            let first = server.skeleton(root, Path::new("a.py"));
+            assert!(first.ok, "{:?}", first.error);
+            assert_eq!(symbol_names(&first), vec!["one"]);
+            let second = server.skeleton(root, &root.join("a.py"));
+            assert_eq!(symbol_names(&second), vec!["one"]);
+            assert_eq!(skeleton_counts(&server, root), (1, 1));
+
+            // A write the watcher has not reported yet is caught by the size check.
+            std::fs::write(
+                root.join("a.py"),
+                "def one():\n    pass\n\ndef two():\n    pass\n",
+            )
+            .unwrap();
+            let third = server.skeleton(root, Path::new("a.py"));
+            assert_eq!(symbol_names(&third), vec!["one", "two"]);
+            assert_eq!(skeleton_counts(&server, root), (1, 2));
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+        async fn skeleton_errors_for_unwatched_root_or_missing_file() {
+            let dir = tempfile::tempdir().unwrap();
+            let server = make_test_server(dir.path()).await;
+            let missing = server.skeleton(dir.path(), Path::new("nope.py"));
+            assert!(!missing.ok);
+            let other = tempfile::tempdir().unwrap();
+            let unwatched = server.skeleton(other.path(), Path::new("a.py"));
+            assert_eq!(unwatched.error.as_deref(), Some("root not watched"));
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+        async fn invalidate_root_drops_relations_and_changed_skeletons() {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path();
+            std::fs::write(root.join("a.py"), "def a():\n    pass\n").unwrap();
+            std::fs::write(root.join("b.py"), "def b():\n    pass\n").unwrap();
+            let server = make_test_server(root).await;
+            server.skeleton(root, Path::new("a.py"));
+            server.skeleton(root, Path::new("b.py"));
+            let caches = server.root_caches(root).unwrap();
+            caches.lock().unwrap().relations =
+                Some(Arc::new(normalize_facts_rules_api::Relations::new()));
+
+            server.invalidate_root(root, &[PathBuf::from("a.py")]);
+
+            let caches = caches.lock().unwrap();
+            assert_eq!(caches.generation, 1);
+            assert!(caches.relations.is_none());
+            assert!(!caches.skeletons.contains_key(&root.join("a.py")));
+            assert!(caches.skeletons.contains_key(&root.join("b.py")));
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+        async fn files_changed_push_invalidates_before_refresh() {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path();
+            std::fs::write(root.join("a.py"), "def a():\n    pass\n").unwrap();
+            let server = make_test_server(root).await;
+            server.skeleton(root, Path::new("a.py"));
+
+            let resp = server.handle_request(Request::FilesChanged {
+                root: root.to_path_buf(),
+                paths: vec![root.join("a.py")],
+            });
+            assert!(resp.ok);
+            let caches = server.root_caches(root).unwrap();
+            assert!(caches.lock().unwrap().skeletons.is_empty());
+        }
+
+        #[test]
+        fn skeleton_request_wire_format() {
+            let req: Request =
+                serde_json::from_str(r#"{"cmd":"skeleton","root":"/r","path":"src/a.rs"}"#)
+                    .unwrap();
+            match req {
+                Request::Skeleton { root, path } => {
+                    assert_eq!(root, PathBuf::from("/r"));
+                    assert_eq!(path, PathBuf::from("src/a.rs"));
+                }
+                other => panic!("unexpected request: {other:?}"),
+            }
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -4656,6 +5005,10 @@ impl DaemonClient {
         Err("normalize daemon is not supported on Windows".to_string())
     }
 
+    pub fn skeleton(&self, _root: &Path, _path: &Path) -> Result<Response, String> {
+        Err("normalize daemon is not supported on Windows".to_string())
+    }
+
     pub fn watch_events(
         &self,
         _root: Option<&Path>,
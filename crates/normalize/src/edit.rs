@@ -2,7 +2,7 @@ use crate::path_resolve;
 use std::path::Path;
 
 // Re-export core types from the extracted crate
-pub use normalize_edit::{ContainerBody, Editor, SymbolLocation, line_to_byte};
+pub use normalize_edit::{ContainerBody, Editor, IncludeTrivia, SymbolLocation, line_to_byte};
 
 /// Extension methods that depend on CLI-internal modules (path_resolve, parsers)
 pub trait EditorExt {
@@ -347,15 +347,13 @@ impl BatchEdit {
         let content = std::fs::read_to_string(&file_path)
             .map_err(|e| format!("Failed to read file: {}", e))?;
 
-        // Get symbol name from path
-        let symbol_name = unified
-            .symbol_path
-            .last()
-            .ok_or_else(|| format!("No symbol specified in target: {}", target))?;
+        // Get symbol name from path, qualified by any parents it names
+        if unified.symbol_path.is_empty() {
+            return Err(format!("No symbol specified in target: {}", target));
+        }
+        let symbol_name = unified.symbol_path.join("::");
 
-        let location = editor
-            .find_symbol(&file_path, &content, symbol_name, false)
-            .ok_or_else(|| format!("Symbol not found: {}", symbol_name))?;
+        let location = editor.find_unique_symbol(&file_path, &content, &symbol_name, false)?;
 
         Ok((file_path, location))
     }
@@ -749,18 +749,22 @@ impl HealthReport {
     }
 
     pub fn grade(&self) -> &'static str {
-        let score = self.calculate_health_score();
-        if score >= 0.9 {
-            "A"
-        } else if score >= 0.8 {
-            "B"
-        } else if score >= 0.7 {
-            "C"
-        } else if score >= 0.6 {
-            "D"
-        } else {
-            "F"
-        }
+        grade_for_score(self.calculate_health_score())
+    }
+}
+
+/// Letter grade (A–F) for a 0–1 health score.
+pub fn grade_for_score(score: f64) -> &'static str {
+    if score >= 0.9 {
+        "A"
+    } else if score >= 0.8 {
+        "B"
+    } else if score >= 0.7 {
+        "C"
+    } else if score >= 0.6 {
+        "D"
+    } else {
+        "F"
     }
 }
 
@@ -0,0 +1,260 @@
+//! Named `--jq` filters and the shared jq prelude.
+//!
+//! `--jq @hotspots` loads `hotspots.jq` from the project's `.normalize/jq/`
+//! directory, falling back to the global `$XDG_CONFIG_HOME/normalize/jq/`
+//! (`~/.config/normalize/jq/`). Every `--jq` filter, named or inline, is compiled
+//! after [`PRELUDE`], so filters can call its helpers (`top(n)`, `count_by(f)`,
+//! `sum_by(f)`, ...).
+//!
+//! server-less parses `--jq` itself, so resolution happens by rewriting argv
+//! before dispatch (see [`expand_jq_args`]).
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Helper defs prepended to every `--jq` filter.
+pub const PRELUDE: &str = include_str!("jq_prelude.jq");
+
+/// Directories of saved `<name>.jq` filters, searched in order.
+#[derive(Debug, Clone, Default)]
+pub struct JqLibrary {
+    dirs: Vec<PathBuf>,
+}
+
+impl JqLibrary {
+    /// Search the given directories, first match wins.
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        Self { dirs }
+    }
+
+    /// The project's `.normalize/jq/`, then the global config's `jq/`.
+    pub fn for_root(root: &Path) -> Self {
+        let global = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .map(|c| c.join("normalize").join("jq"));
+        Self::new(
+            [Some(root.join(".normalize").join("jq")), global]
+                .into_iter()
+                .flatten()
+                .collect(),
+        )
+    }
+
+    /// Path of the saved filter `name`, if any directory has one.
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        self.dirs
+            .iter()
+            .map(|dir| dir.join(format!("{name}.jq")))
+            .find(|path| path.is_file())
+    }
+
+    /// Names of all saved filters, sorted and de-duplicated.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                (path.extension()? == "jq")
+                    .then(|| path.file_stem()?.to_str().map(str::to_string))
+                    .flatten()
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Expand a `--jq` value into the program to compile: the prelude followed by
+    /// either the saved filter (`@name`) or the inline filter itself.
+    pub fn resolve(&self, filter: &str) -> Result<String, String> {
+        let body = match filter.strip_prefix('@') {
+            Some(name) => self.load(name)?,
+            None => filter.to_string(),
+        };
+        Ok(format!("{PRELUDE}\n{body}"))
+    }
+
+    fn load(&self, name: &str) -> Result<String, String> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(format!(
+                "invalid jq filter name @{name} (use letters, digits, '-' and '_')"
+            ));
+        }
+        let Some(path) = self.find(name) else {
+            let names = self.names();
+            return Err(if names.is_empty() {
+                format!("unknown jq filter @{name}; save filters as .normalize/jq/<name>.jq")
+            } else {
+                format!(
+                    "unknown jq filter @{name} (available: {})",
+                    names
+                        .iter()
+                        .map(|n| format!("@{n}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            });
+        };
+        std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+/// Rewrite every `--jq <filter>` / `--jq=<filter>` in `argv` through
+/// [`JqLibrary::resolve`]. Arguments after `--` are left alone.
+pub fn expand_jq_args(argv: Vec<OsString>, root: &Path) -> Result<Vec<OsString>, String> {
+    if !argv.iter().any(is_jq_flag) {
+        return Ok(argv);
+    }
+    let library = JqLibrary::for_root(root);
+    let mut out = Vec::with_capacity(argv.len());
+    let mut args = argv.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("--") => {
+                out.push(arg);
+                out.extend(args);
+                break;
+            }
+            Some("--jq") => {
+                out.push(arg);
+                if let Some(value) = args.next() {
+                    out.push(expand_value(&library, value)?);
+                }
+            }
+            Some(s) if s.starts_with("--jq=") => {
+                let program = library.resolve(&s["--jq=".len()..])?;
+                out.push(format!("--jq={program}").into());
+            }
+            _ => out.push(arg),
+        }
+    }
+    Ok(out)
+}
+
+fn is_jq_flag(arg: &OsString) -> bool {
+    arg.to_str()
+        .is_some_and(|s| s == "--jq" || s.starts_with("--jq="))
+}
+
+fn expand_value(library: &JqLibrary, value: OsString) -> Result<OsString, String> {
+    match value.to_str() {
+        Some(filter) => library.resolve(filter).map(OsString::from),
+        // Non-UTF-8 filters can't be jq anyway; let server-less report them.
+        None => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: serde_json::Value) -> Vec<serde_json::Value> {
+        use jaq_core::load::{Arena, File, Loader};
+        use jaq_core::{Compiler, Ctx, Vars, data::JustLut, unwrap_valr};
+        use jaq_json::Val;
+
+        let loader = Loader::new(
+            jaq_core::defs()
+                .chain(jaq_std::defs())
+                .chain(jaq_json::defs()),
+        );
+        let arena = Arena::default();
+        let modules = loader
+            .load(
+                &arena,
+                File {
+                    code: program,
+                    path: (),
+                },
+            )
+            .unwrap_or_else(|e| panic!("parse error: {e:?}"));
+        let filter = Compiler::default()
+            .with_funs(
+                jaq_core::funs::<JustLut<Val>>()
+                    .chain(jaq_std::funs::<JustLut<Val>>())
+                    .chain(jaq_json::funs::<JustLut<Val>>()),
+            )
+            .compile(modules)
+            .unwrap_or_else(|e| panic!("compile error: {e:?}"));
+        let val: Val = serde_json::from_value(input).unwrap();
+        let ctx = Ctx::<JustLut<Val>>::new(&filter.lut, Vars::new([]));
+        filter
+            .id
+            .run((ctx, val))
+            .map(unwrap_valr)
+            .map(|v| serde_json::from_str(&v.unwrap().to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn prelude_helpers_compile_and_run() {
+        let library = JqLibrary::default();
+        let input = serde_json::json!([
+            {"tool": "Read", "calls": 5},
+            {"tool": "Edit", "calls": 2},
+            {"tool": "Read", "calls": 1},
+        ]);
+        let program = library
+            .resolve("{n: sum_by(.calls), by: count_by(.tool), first: (pluck(\"tool\") | top(1))}")
+            .unwrap();
+        assert_eq!(
+            run(&program, input),
+            [serde_json::json!({
+                "n": 8,
+                "by": [{"key": "Read", "count": 2}, {"key": "Edit", "count": 1}],
+                "first": ["Read"],
+            })]
+        );
+    }
+
+    #[test]
+    fn named_filters_resolve_from_library_dirs() {
+        let project = tempfile::tempdir().unwrap();
+        let global = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("busy.jq"), "where(.calls > 2)").unwrap();
+        std::fs::write(global.path().join("busy.jq"), "empty").unwrap();
+        std::fs::write(global.path().join("names.jq"), "pluck(\"tool\")").unwrap();
+        let library = JqLibrary::new(vec![
+            project.path().to_path_buf(),
+            global.path().to_path_buf(),
+        ]);
+
+        let program = library.resolve("@busy").unwrap();
+        assert!(program.starts_with(PRELUDE));
+        assert!(program.ends_with("where(.calls > 2)"));
+        assert_eq!(library.names(), ["busy", "names"]);
+
+        let err = library.resolve("@missing").unwrap_err();
+        assert_eq!(err, "unknown jq filter @missing (available: @busy, @names)");
+        assert!(library.resolve("@../secrets").is_err());
+    }
+
+    #[test]
+    fn expand_jq_args_rewrites_both_flag_forms() {
+        let root = tempfile::tempdir().unwrap();
+        let jq_dir = root.path().join(".normalize").join("jq");
+        std::fs::create_dir_all(&jq_dir).unwrap();
+        std::fs::write(jq_dir.join("hot.jq"), ".hotspots").unwrap();
+
+        let argv: Vec<OsString> = ["normalize", "rank", "--jq", "@hot", "--", "--jq=.x"]
+            .map(OsString::from)
+            .into();
+        let out = expand_jq_args(argv, root.path()).unwrap();
+        assert_eq!(out[3], OsString::from(format!("{PRELUDE}\n.hotspots")));
+        assert_eq!(out[5], OsString::from("--jq=.x"));
+
+        let argv: Vec<OsString> = ["normalize", "view", "--jq=.a"].map(OsString::from).into();
+        let out = expand_jq_args(argv, root.path()).unwrap();
+        assert_eq!(out[2], OsString::from(format!("--jq={PRELUDE}\n.a")));
+    }
+}
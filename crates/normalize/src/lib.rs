@@ -22,6 +22,7 @@ pub mod analyze;
 pub mod ast_grep;
 pub mod commands;
 pub mod config;
+pub mod config_migrate;
 pub mod daemon;
 pub mod deps;
 pub mod diagnostic_convert;
@@ -32,6 +33,7 @@ pub mod health;
 pub mod index;
 #[cfg(feature = "jq-cli")]
 pub mod jq;
+pub mod jq_library;
 pub mod multi_repo;
 pub mod output;
 pub mod parsers;
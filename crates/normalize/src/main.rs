@@ -273,6 +273,7 @@ async fn main() -> std::process::ExitCode {
             .without_time()
             .with_target(false)
             .with_level(false)
+            .with_writer(std::io::stderr)
             .with_env_filter(env_filter())
             .init();
     }
@@ -371,6 +372,25 @@ async fn main() -> std::process::ExitCode {
     // recall it on a later invocation without requiring `--command`.
     record_last_command(&argv);
 
+    // Resolve `--jq @name` saved filters and prepend the jq prelude. Done after
+    // recording so `normalize alias save` keeps the short `@name` form.
+    let root = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let argv = match normalize::jq_library::expand_jq_args(argv, &root) {
+        Ok(argv) => argv,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return std::process::ExitCode::FAILURE;
+        }
+    };
+
+    // Machine-readable output is often captured with stderr; keep progress
+    // displays out of it. Commands writing to `--output` render it themselves.
+    let machine_format = normalize::output::sink::MachineFormat::from_args(&argv);
+    if machine_format.is_some() {
+        normalize::output::progress::set_silent(true);
+    }
+    normalize::output::sink::set_machine_format(machine_format);
+
     let service = normalize::service::NormalizeService::new();
     match service.cli_run_with_async(argv).await {
         Ok(()) => std::process::ExitCode::SUCCESS,
@@ -35,12 +35,17 @@ impl IndexPathSource {
     /// Open from a sync context: uses `block_in_place` inside a tokio runtime,
     /// or creates a temporary runtime when called outside tokio.
     fn open_sync(root: &Path) -> Option<Self> {
-        match tokio::runtime::Handle::try_current() {
-            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(Self::open(root))),
-            Err(_) => tokio::runtime::Runtime::new()
-                .ok()?
-                .block_on(Self::open(root)),
-        }
+        block_on(Self::open(root))?
+    }
+}
+
+/// Run a future to completion from a sync context: uses `block_in_place` inside
+/// a tokio runtime, or creates a temporary runtime when called outside tokio.
+/// Returns `None` only if a temporary runtime can't be created.
+fn block_on<F: std::future::Future>(future: F) -> Option<F::Output> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => Some(tokio::task::block_in_place(|| handle.block_on(future))),
+        Err(_) => Some(tokio::runtime::Runtime::new().ok()?.block_on(future)),
     }
 }
 
@@ -81,8 +86,10 @@ impl PathSource for IndexPathSource {
 }
 
 fn alias_lookup(root: &Path) -> impl Fn(&str) -> Option<Vec<String>> {
-    let config = NormalizeConfig::load(root);
-    move |name: &str| config.aliases.get(name)
+    let aliases = NormalizeConfig::load(root)
+        .aliases
+        .scoped(crate::filter::AliasConfig::TARGET_SCOPE);
+    move |name: &str| aliases.get(name)
 }
 
 /// Expand an alias query like `@todo` or `@config/section`.
@@ -102,6 +109,68 @@ pub fn resolve_unified(query: &str, root: &Path) -> Option<UnifiedPath> {
     )
 }
 
+/// Resolve an edit target to a single file + symbol.
+///
+/// Tries [`resolve_unified`] first. If the query isn't a path, it is looked up
+/// as a symbol name (`name` or `Parent/name`) in the persistent index, so
+/// `normalize edit ... parse_config` works without spelling out the file. A
+/// name defined in several places is an error listing the candidates.
+pub fn resolve_target(query: &str, root: &Path) -> Result<UnifiedPath, String> {
+    if let Some(unified) = resolve_unified(query, root) {
+        return Ok(unified);
+    }
+    let not_found = || format!("No matches for: {}", query);
+    let (parent, name) = match query.rsplit_once('/') {
+        Some((p, n)) if !n.is_empty() => (Some(p), n),
+        _ => (None, query),
+    };
+    let matches = block_on(async {
+        let index = crate::index::open_if_enabled(root).await?;
+        index.find_symbols(name, None, false, 50).await.ok()
+    })
+    .flatten()
+    .ok_or_else(not_found)?;
+    let matches: Vec<_> = matches
+        .into_iter()
+        .filter(|m| m.name == name)
+        .filter(|m| parent.is_none() || m.parent.as_deref() == parent)
+        .collect();
+    match matches.as_slice() {
+        [] => Err(not_found()),
+        [m] => {
+            let mut symbol_path: Vec<String> = m
+                .parent
+                .as_deref()
+                .map(|p| p.split('/').map(String::from).collect())
+                .unwrap_or_default();
+            symbol_path.push(m.name.clone());
+            Ok(UnifiedPath {
+                file_path: m.file.clone(),
+                symbol_path,
+                is_directory: false,
+            })
+        }
+        many => {
+            let mut msg = format!(
+                "Symbol '{}' is defined in {} places - qualify it with a path:",
+                query,
+                many.len()
+            );
+            for m in many {
+                let qualified = match &m.parent {
+                    Some(p) => format!("{}/{}/{}", m.file, p, m.name),
+                    None => format!("{}/{}", m.file, m.name),
+                };
+                msg.push_str(&format!(
+                    "\n  {} ({}, line {})",
+                    qualified, m.kind, m.start_line
+                ));
+            }
+            Err(msg)
+        }
+    }
+}
+
 /// Resolve a query to ALL matching unified paths.
 pub fn resolve_unified_all(query: &str, root: &Path) -> Vec<UnifiedPath> {
     normalize_path_resolve::resolve_unified_all(
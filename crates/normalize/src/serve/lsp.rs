@@ -1,11 +1,12 @@
 //! LSP (Language Server Protocol) server for normalize.
 //!
 //! Provides IDE integration with document symbols, workspace symbols, hover,
-//! and diagnostics from syntax/fact rule engines.
+//! rename, code actions (allow-comment quick-fixes and symbol edits), and
+//! diagnostics from syntax/fact rule engines.
 
 use crate::index::FileIndex;
 use crate::skeleton::SkeletonExtractor;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -20,6 +21,8 @@ struct NormalizeBackend {
     index: Mutex<Option<FileIndex>>,
     /// Persistent extractor — avoids recreating grammar caches per request.
     extractor: SkeletonExtractor,
+    /// Text of documents open in the client, which may differ from disk.
+    open_documents: Mutex<HashMap<Url, String>>,
     /// Files with syntax diagnostics in the last per-file run.
     syntax_diagnosed_files: Arc<Mutex<HashSet<Url>>>,
     /// Files with fact diagnostics in the last workspace-wide run.
@@ -41,6 +44,7 @@ impl NormalizeBackend {
             root: Mutex::new(None),
             index: Mutex::new(None),
             extractor: SkeletonExtractor::new(),
+            open_documents: Mutex::new(HashMap::new()),
             syntax_diagnosed_files: Arc::new(Mutex::new(HashSet::new())),
             fact_diagnosed_files: Arc::new(Mutex::new(HashSet::new())),
             fact_diagnostics_generation: Arc::new(std::sync::atomic::AtomicU64::new(0)),
@@ -52,6 +56,15 @@ impl NormalizeBackend {
         }
     }
 
+    /// Current text of a document: the client's buffer if it is open,
+    /// otherwise the file on disk.
+    async fn document_text(&self, uri: &Url, path: &std::path::Path) -> Option<String> {
+        if let Some(text) = self.open_documents.lock().await.get(uri) {
+            return Some(text.clone());
+        }
+        std::fs::read_to_string(path).ok()
+    }
+
     /// Initialize index for the workspace root.
     async fn init_index(&self, root: PathBuf) {
         if let Some(idx) = crate::index::open_if_enabled(&root).await {
@@ -307,7 +320,7 @@ impl LanguageServer for NormalizeBackend {
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::INCREMENTAL),
+                        change: Some(TextDocumentSyncKind::FULL),
                         save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
                             include_text: Some(false),
                         })),
@@ -323,6 +336,15 @@ impl LanguageServer for NormalizeBackend {
                     prepare_provider: Some(true),
                     work_done_progress_options: Default::default(),
                 })),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR,
+                        ]),
+                        ..Default::default()
+                    },
+                )),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -342,6 +364,30 @@ impl LanguageServer for NormalizeBackend {
         Ok(())
     }
 
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.open_documents
+            .lock()
+            .await
+            .insert(params.text_document.uri, params.text_document.text);
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change carries the whole document.
+        if let Some(change) = params.content_changes.into_iter().last() {
+            self.open_documents
+                .lock()
+                .await
+                .insert(params.text_document.uri, change.text);
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.open_documents
+            .lock()
+            .await
+            .remove(&params.text_document.uri);
+    }
+
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         let uri = &params.text_document.uri;
         // Fast: per-file syntax diagnostics (immediate)
@@ -506,22 +552,6 @@ impl LanguageServer for NormalizeBackend {
         // Find symbol at position (1-indexed line)
         let line = position.line as usize + 1;
 
-        fn find_symbol_at_line(
-            symbols: &[normalize_languages::Symbol],
-            line: usize,
-        ) -> Option<&normalize_languages::Symbol> {
-            for sym in symbols {
-                if line >= sym.start_line && line <= sym.end_line {
-                    // Check children first for more specific match
-                    if let Some(child) = find_symbol_at_line(&sym.children, line) {
-                        return Some(child);
-                    }
-                    return Some(sym);
-                }
-            }
-            None
-        }
-
         let symbol = find_symbol_at_line(&result.symbols, line);
 
         match symbol {
@@ -850,6 +880,156 @@ impl LanguageServer for NormalizeBackend {
             change_annotations: None,
         }))
     }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let file_path = match uri.to_file_path() {
+            Ok(p) => p,
+            Err(_) => return Ok(None),
+        };
+        let Some(content) = self.document_text(&uri, &file_path).await else {
+            return Ok(None);
+        };
+        let comment = normalize_languages::support_for_path(&file_path)
+            .map_or(Some("//"), |lang| lang.line_comment());
+
+        let workspace_edit = |edits: Vec<TextEdit>| WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), edits)])),
+            document_changes: None,
+            change_annotations: None,
+        };
+        let mut actions: CodeActionResponse = Vec::new();
+
+        // Quick-fixes: suppress a normalize diagnostic with an allow comment.
+        for diag in &params.context.diagnostics {
+            let (Some(NumberOrString::String(rule_id)), Some(comment)) = (&diag.code, comment)
+            else {
+                continue;
+            };
+            let edit = match diag.source.as_deref() {
+                Some("normalize/syntax-rules") => allow_comment_edit(
+                    &content,
+                    diag.range.start.line as usize,
+                    comment,
+                    &format!("normalize-syntax-allow: {}", rule_id),
+                ),
+                Some("normalize/fact-rules") => allow_comment_edit(
+                    &content,
+                    0,
+                    comment,
+                    &format!("normalize-facts-allow: {}", rule_id),
+                ),
+                _ => continue,
+            };
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: format!("Allow {} here", rule_id),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diag.clone()]),
+                edit: Some(workspace_edit(vec![edit])),
+                ..Default::default()
+            }));
+        }
+
+        // Edit operations on the symbol declared on the cursor's line.
+        let result = self.extractor.extract(&file_path, &content);
+        let line = params.range.start.line as usize + 1;
+        if let Some(sym) = find_symbol_at_line(&result.symbols, line)
+            && sym.start_line == line
+        {
+            let editor = crate::edit::Editor::new();
+            let loc = crate::edit::SymbolLocation {
+                name: sym.name.clone(),
+                kind: sym.kind.as_str().to_string(),
+                start_byte: crate::edit::line_to_byte(&content, sym.start_line),
+                end_byte: crate::edit::line_to_byte(&content, sym.end_line + 1),
+                start_line: sym.start_line,
+                end_line: sym.end_line,
+                indent: String::new(),
+            };
+            let new_content = editor.delete_symbol(&content, &loc);
+            if let Some(edit) = changed_lines_edit(&content, &new_content) {
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Delete {} `{}`", sym.kind.as_str(), sym.name),
+                    kind: Some(CodeActionKind::REFACTOR),
+                    edit: Some(workspace_edit(vec![edit])),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(if actions.is_empty() {
+            None
+        } else {
+            Some(actions)
+        })
+    }
+}
+
+/// Innermost symbol whose range contains `line` (1-indexed).
+fn find_symbol_at_line(
+    symbols: &[normalize_languages::Symbol],
+    line: usize,
+) -> Option<&normalize_languages::Symbol> {
+    for sym in symbols {
+        if line >= sym.start_line && line <= sym.end_line {
+            // Check children first for more specific match
+            if let Some(child) = find_symbol_at_line(&sym.children, line) {
+                return Some(child);
+            }
+            return Some(sym);
+        }
+    }
+    None
+}
+
+/// Insert `<comment> <text>` on its own line above `line` (0-indexed), matching
+/// that line's indentation.
+fn allow_comment_edit(content: &str, line: usize, comment: &str, text: &str) -> TextEdit {
+    let indent: String = content
+        .lines()
+        .nth(line)
+        .map(|l| l.chars().take_while(|c| c.is_whitespace()).collect())
+        .unwrap_or_default();
+    let at = Position {
+        line: line as u32,
+        character: 0,
+    };
+    TextEdit {
+        range: Range { start: at, end: at },
+        new_text: format!("{}{} {}\n", indent, comment, text),
+    }
+}
+
+/// A single edit turning `old` into `new`, replacing only the lines between
+/// their common prefix and suffix. `None` if the two are identical.
+fn changed_lines_edit(old: &str, new: &str) -> Option<TextEdit> {
+    if old == new {
+        return None;
+    }
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let line_start = |line: usize| Position {
+        line: line as u32,
+        character: 0,
+    };
+    Some(TextEdit {
+        range: Range {
+            start: line_start(prefix),
+            end: line_start(old_lines.len() - suffix),
+        },
+        new_text: new_lines[prefix..new_lines.len() - suffix].concat(),
+    })
 }
 
 /// Word at a position with its range.
@@ -1149,3 +1329,137 @@ pub async fn run_lsp_server(root: Option<&std::path::Path>) -> i32 {
     Server::new(stdin, stdout, socket).serve(service).await;
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allow_comment_edit_matches_indentation() {
+        let content = "def f():\n    x = 1\n";
+        let edit = allow_comment_edit(content, 1, "#", "normalize-syntax-allow: py/x");
+        assert_eq!(edit.range.start, Position::new(1, 0));
+        assert_eq!(edit.range.end, Position::new(1, 0));
+        assert_eq!(edit.new_text, "    # normalize-syntax-allow: py/x\n");
+    }
+
+    #[test]
+    fn changed_lines_edit_replaces_only_changed_lines() {
+        let old = "a\nb\nc\nd\n";
+        let new = "a\nd\n";
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let edit = changed_lines_edit(old, new).unwrap();
+        assert_eq!(edit.range.start, Position::new(1, 0));
+        assert_eq!(edit.range.end, Position::new(3, 0));
+        assert_eq!(edit.new_text, "");
+        assert!(changed_lines_edit(old, old).is_none());
+    }
+
+    fn diagnostic(source: &str, rule: &str, line: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(line, 0), Position::new(line, 1)),
+            code: Some(NumberOrString::String(rule.to_string())),
+            source: Some(source.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn code_action_uses_open_buffer_and_language_comment() {
+        let (service, _socket) = LspService::new(NormalizeBackend::new);
+        let backend = service.inner();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("mod.py");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(&path, "on_disk = 1\n").unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let uri = Url::from_file_path(&path).unwrap();
+
+        backend
+            .did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "python".to_string(),
+                    1,
+                    "x = 1\n    in_buffer = 2\n".to_string(),
+                ),
+            })
+            .await;
+
+        let diag = diagnostic("normalize/syntax-rules", "python/example", 1);
+        let response = backend
+            .code_action(CodeActionParams {
+                text_document: TextDocumentIdentifier::new(uri.clone()),
+                range: diag.range,
+                context: CodeActionContext {
+                    diagnostics: vec![diag.clone()],
+                    only: None,
+                    trigger_kind: None,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .await
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            .unwrap()
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            .unwrap();
+
+        let quick_fixes: Vec<_> = response
+            .iter()
+            .filter_map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) if a.kind == Some(CodeActionKind::QUICKFIX) => {
+                    Some(a)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(quick_fixes.len(), 1);
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let changes = quick_fixes[0]
+            .edit
+            .as_ref()
+            .unwrap()
+            .changes
+            .as_ref()
+            .unwrap();
+        let edits = &changes[&uri];
+        // Indentation comes from the buffer, not the file on disk.
+        assert_eq!(
+            edits[0].new_text,
+            "    # normalize-syntax-allow: python/example\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn code_action_skips_quick_fix_without_line_comments() {
+        let (service, _socket) = LspService::new(NormalizeBackend::new);
+        let backend = service.inner();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("data.json");
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        std::fs::write(&path, "{}\n").unwrap();
+        // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+        let uri = Url::from_file_path(&path).unwrap();
+
+        let diag = diagnostic("normalize/syntax-rules", "json/example", 0);
+        let response = backend
+            .code_action(CodeActionParams {
+                text_document: TextDocumentIdentifier::new(uri),
+                range: diag.range,
+                context: CodeActionContext {
+                    diagnostics: vec![diag],
+                    only: None,
+                    trigger_kind: None,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .await
+            // normalize-syntax-allow: rust/unwrap-in-impl - test code, panic is appropriate
+            .unwrap();
+        assert!(response.is_none());
+    }
+}
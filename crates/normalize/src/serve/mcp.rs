@@ -8,16 +8,20 @@
 //!    - Lower token overhead for tool definitions (~50 vs ~8K tokens)
 //!    - Better for LLMs that handle CLI-style inputs well
 //!
-//! 2. Multi-tool mode (--full): Each command as a separate tool
+//! 2. Multi-tool mode (--full): view, symbol search, structural edits, rule
+//!    diagnostics and session analysis as separate typed tools
 //!    - Better discoverability for IDEs
-//!    - Explicit parameter schemas
+//!    - Explicit parameter schemas, taken from each subcommand's `--input-schema`
+//!      so they always match the CLI's own schemars types
+//!    - Calls run as `normalize <subcommand> --params-json <arguments> --json`
 
 #[cfg(feature = "mcp")]
 mod implementation {
     use std::process::Command;
     use std::sync::Arc;
 
-    use rmcp::handler::server::router::tool::ToolRouter;
+    use rmcp::handler::server::router::tool::{ToolRoute, ToolRouter};
+    use rmcp::handler::server::tool::ToolCallContext;
     use rmcp::handler::server::wrapper::Parameters;
     use rmcp::model::*;
     use rmcp::transport::stdio;
@@ -32,6 +36,95 @@ mod implementation {
         pub command: String,
     }
 
+    /// A CLI subcommand exposed as its own tool in multi-tool mode.
+    struct TypedTool {
+        name: &'static str,
+        command: &'static [&'static str],
+        description: &'static str,
+        read_only: bool,
+    }
+
+    const TYPED_TOOLS: &[TypedTool] = &[
+        TypedTool {
+            name: "view",
+            command: &["view"],
+            description: "View a directory tree, file skeleton, or symbol source. `target` is a path, path/Symbol, or bare symbol name; `budget` caps output tokens.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "history",
+            command: &["view", "history"],
+            description: "Commits that changed a symbol or file, newest first (`git log -L`-style line-range tracing). `target` is a path or path/Symbol.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "symbols",
+            command: &["structure", "symbols"],
+            description: "Search symbol definitions across the workspace (fuzzy by default, `exact` for exact names).",
+            read_only: true,
+        },
+        TypedTool {
+            name: "edit_delete",
+            command: &["edit", "delete"],
+            description: "Delete a symbol.",
+            read_only: false,
+        },
+        TypedTool {
+            name: "edit_replace",
+            command: &["edit", "replace"],
+            description: "Replace a symbol with new content.",
+            read_only: false,
+        },
+        TypedTool {
+            name: "edit_insert",
+            command: &["edit", "insert"],
+            description: "Insert content before, after, or inside a symbol.",
+            read_only: false,
+        },
+        TypedTool {
+            name: "edit_rename",
+            command: &["edit", "rename"],
+            description: "Rename a symbol across its definition, call sites, and imports.",
+            read_only: false,
+        },
+        TypedTool {
+            name: "edit_move",
+            command: &["edit", "move"],
+            description: "Move a symbol's definition to another file and rewrite imports.",
+            read_only: false,
+        },
+        TypedTool {
+            name: "diagnostics",
+            command: &["rules", "run"],
+            description: "Run syntax, fact, and native rules and report diagnostics.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "sessions_list",
+            command: &["sessions", "list"],
+            description: "List agent sessions for the project.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "sessions_show",
+            command: &["sessions", "show"],
+            description: "Show a session summary or full conversation.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "sessions_analyze",
+            command: &["sessions", "analyze"],
+            description: "Analyze a session: tool stats, errors, token costs, corrections.",
+            read_only: true,
+        },
+        TypedTool {
+            name: "sessions_stats",
+            command: &["sessions", "stats"],
+            description: "Aggregate statistics across sessions.",
+            read_only: true,
+        },
+    ];
+
     /// MCP server that wraps normalize CLI.
     #[derive(Clone)]
     pub struct NormalizeServer {
@@ -42,10 +135,22 @@ mod implementation {
     #[tool_router]
     impl NormalizeServer {
         /// Create a new MCP server for the given root directory.
-        pub fn new(root: &str) -> Self {
+        ///
+        /// With `full`, every entry in [`TYPED_TOOLS`] is registered alongside
+        /// the single `normalize` tool.
+        pub fn new(root: &str, full: bool) -> Self {
+            let mut tool_router = Self::tool_router();
+            if full {
+                for tool in TYPED_TOOLS {
+                    match typed_route(tool, root) {
+                        Ok(route) => tool_router.add_route(route),
+                        Err(e) => eprintln!("mcp: skipping tool '{}': {}", tool.name, e),
+                    }
+                }
+            }
             Self {
                 root: Arc::new(root.to_string()),
-                tool_router: Self::tool_router(),
+                tool_router,
             }
         }
 
@@ -55,10 +160,15 @@ mod implementation {
             &self,
             Parameters(req): Parameters<NormalizeRequest>,
         ) -> Result<CallToolResult, McpError> {
+            let args: Vec<String> = req.command.split_whitespace().map(String::from).collect();
+            Ok(self.run(args).await)
+        }
+
+        /// Run a normalize command off the async runtime and wrap its output.
+        async fn run(&self, args: Vec<String>) -> CallToolResult {
             let root = self.root.clone();
-            let command = req.command;
             let result =
-                tokio::task::spawn_blocking(move || execute_normalize_command(&command, &root))
+                tokio::task::spawn_blocking(move || execute_normalize_command(&args, &root))
                     .await
                     .unwrap_or_else(|e| CommandResult {
                         output: format!("Task panicked: {}", e),
@@ -74,16 +184,54 @@ mod implementation {
                 ))
             };
 
-            Ok(CallToolResult::success(vec![content]))
+            CallToolResult::success(vec![content])
         }
     }
 
+    /// Build the route for a typed tool, using the subcommand's input schema.
+    fn typed_route(
+        tool: &'static TypedTool,
+        root: &str,
+    ) -> Result<ToolRoute<NormalizeServer>, String> {
+        let mut args: Vec<String> = tool.command.iter().map(|s| s.to_string()).collect();
+        args.push("--input-schema".to_string());
+        let result = execute_normalize_command(&args, root);
+        if result.exit_code != 0 {
+            return Err(result.output);
+        }
+        let schema: JsonObject = serde_json::from_str(&result.output)
+            .map_err(|e| format!("invalid input schema: {}", e))?;
+
+        let annotations = ToolAnnotations::new()
+            .read_only(tool.read_only)
+            .destructive(!tool.read_only);
+        let attr = Tool::new(tool.name, tool.description, schema).annotate(annotations);
+
+        Ok(ToolRoute::new_dyn(
+            attr,
+            move |ctx: ToolCallContext<'_, NormalizeServer>| {
+                let params = serde_json::Value::Object(ctx.arguments.unwrap_or_default());
+                let mut args: Vec<String> = tool.command.iter().map(|s| s.to_string()).collect();
+                args.extend([
+                    "--params-json".to_string(),
+                    params.to_string(),
+                    "--json".to_string(),
+                ]);
+                let server = ctx.service;
+                Box::pin(async move { Ok(server.run(args).await) })
+            },
+        ))
+    }
+
     #[tool_handler]
     impl rmcp::ServerHandler for NormalizeServer {
         fn get_info(&self) -> ServerInfo {
             ServerInfo {
                 instructions: Some(
-                    "Use the 'normalize' tool to query code intelligence for the codebase.".into(),
+                    "Use the 'normalize' tool to query code intelligence for the codebase. \
+                     When typed tools (view, symbols, edit_*, diagnostics, sessions_*) are \
+                     listed, prefer them: their arguments match the CLI parameters."
+                        .into(),
                 ),
                 capabilities: ServerCapabilities::builder().enable_tools().build(),
                 ..Default::default()
@@ -98,7 +246,7 @@ mod implementation {
     }
 
     /// Execute a normalize CLI command.
-    fn execute_normalize_command(command: &str, root: &str) -> CommandResult {
+    fn execute_normalize_command(args: &[String], root: &str) -> CommandResult {
         let current_exe = match std::env::current_exe() {
             Ok(exe) => exe,
             Err(e) => {
@@ -109,7 +257,6 @@ mod implementation {
             }
         };
 
-        let args: Vec<&str> = command.split_whitespace().collect();
         if args.is_empty() {
             return CommandResult {
                 output: "Empty command".to_string(),
@@ -118,7 +265,7 @@ mod implementation {
         }
 
         let output = match Command::new(&current_exe)
-            .args(&args)
+            .args(args)
             .current_dir(root)
             .output()
         {
@@ -154,8 +301,11 @@ mod implementation {
     }
 
     /// Run the MCP server.
-    pub async fn run_server(_root: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let server = NormalizeServer::new(_root);
+    pub async fn run_server(
+        root: &str,
+        full: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let server = NormalizeServer::new(root, full);
         let service = server.serve(stdio()).await?;
         service.waiting().await?;
         Ok(())
@@ -163,22 +313,14 @@ mod implementation {
 }
 
 /// Run the MCP server for `normalize serve mcp`.
-pub fn serve_mcp(root: Option<&std::path::Path>) -> i32 {
+pub async fn serve_mcp(root: Option<&std::path::Path>, full: bool) -> i32 {
     #[cfg(feature = "mcp")]
     {
         let root = root
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| ".".to_string());
 
-        let rt = match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt,
-            Err(e) => {
-                eprintln!("Failed to create runtime: {}", e);
-                return 1;
-            }
-        };
-
-        match rt.block_on(implementation::run_server(&root)) {
+        match implementation::run_server(&root, full).await {
             Ok(()) => 0,
             Err(e) => {
                 eprintln!("MCP server error: {}", e);
@@ -189,7 +331,7 @@ pub fn serve_mcp(root: Option<&std::path::Path>) -> i32 {
 
     #[cfg(not(feature = "mcp"))]
     {
-        let _ = root;
+        let _ = (root, full);
         eprintln!("MCP server requires the 'mcp' feature.");
         eprintln!("Rebuild with: cargo build --features mcp");
         1
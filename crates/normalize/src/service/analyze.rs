@@ -31,6 +31,7 @@ impl From<String> for AnalyzeError {
 /// Analyze sub-service (health, complexity, security, duplicates, docs).
 pub struct AnalyzeService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
 }
@@ -39,6 +40,7 @@ impl AnalyzeService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -61,12 +63,13 @@ impl AnalyzeService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     /// Generic display bridge: routes to `format_pretty()` or `format_text()` based on pretty mode.
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
@@ -76,12 +79,9 @@ impl AnalyzeService {
         root: &std::path::Path,
         exclude: &[String],
         only: &[String],
+        predicates: &[crate::filter::FilterPredicate],
     ) -> Option<crate::filter::Filter> {
-        if exclude.is_empty() && only.is_empty() {
-            None
-        } else {
-            crate::commands::build_filter(root, exclude, only)
-        }
+        crate::commands::build_filter_with_predicates(root, exclude, only, predicates)
     }
 
     /// Build a filter with merged excludes: config global + per-subcommand + CLI args.
@@ -91,10 +91,11 @@ impl AnalyzeService {
         subcommand: &str,
         cli_exclude: &[String],
         only: &[String],
+        predicates: &[crate::filter::FilterPredicate],
     ) -> Option<crate::filter::Filter> {
         let mut excludes = config.excludes_for(subcommand);
         excludes.extend(cli_exclude.iter().cloned());
-        Self::build_filter(root, &excludes, only)
+        Self::build_filter(root, &excludes, only, predicates)
     }
 }
 
@@ -174,11 +175,26 @@ impl AnalyzeService {
         >,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
     ) -> Result<DocCoverageReport, AnalyzeError> {
         let root_path = Self::root_path(root)?;
         let config = crate::config::NormalizeConfig::load(&root_path);
-        let filter =
-            Self::build_filter_with_config(&root_path, &config.analyze, "docs", &exclude, &only);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )
+        .map_err(AnalyzeError::Message)?;
+        let filter = Self::build_filter_with_config(
+            &root_path,
+            &config.analyze,
+            "docs",
+            &exclude,
+            &only,
+            &predicates,
+        );
         Ok(crate::commands::analyze::docs::analyze_docs(
             &root_path,
             limit.unwrap_or(10),
@@ -352,14 +352,15 @@ fn format_schema_annotated(
 pub struct ConfigError {
     /// Which file the error was found in.
     pub file: String,
-    /// Which validation phase caught the error: "toml", "schema", "deserialize", "rules".
+    /// Which validation phase caught the error: "toml", "schema", "unknown-key",
+    /// "deserialize", "rules".
     pub phase: String,
     /// Human-readable description of the error.
     pub message: String,
-    /// Line number (1-based) if available from the TOML parser.
+    /// Line number (1-based) if the error can be located in the source file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<usize>,
-    /// Column number (1-based) if available from the TOML parser.
+    /// Column number (1-based) if the error can be located in the source file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<usize>,
 }
@@ -368,7 +369,8 @@ pub struct ConfigError {
 ///
 /// Runs multiple validation phases on each config file (project and global):
 /// 1. TOML syntax (catches duplicate keys, malformed syntax)
-/// 2. JSON Schema compliance (catches unknown sections, type mismatches)
+/// 2. JSON Schema compliance (catches type mismatches) and unknown keys (catches typos,
+///    with a "did you mean" suggestion when a declared key is close)
 /// 3. Serde deserialization as `NormalizeConfig` (catches field type mismatches)
 /// 4. Rules config parsing (catches rules-specific deserialization errors)
 ///
@@ -411,6 +413,33 @@ impl OutputFormatter for ConfigValidateReport {
     }
 }
 
+/// Report from `normalize config effective`: the merged config that commands actually see.
+///
+/// `sources` lists the config files that contributed, lowest precedence first; when
+/// none exist, the bootstrap defaults are reported.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigEffectiveReport {
+    pub sources: Vec<String>,
+    /// The merged `NormalizeConfig`, with unset optional fields omitted.
+    pub config: serde_json::Value,
+}
+
+impl OutputFormatter for ConfigEffectiveReport {
+    fn format_text(&self) -> String {
+        let mut out = if self.sources.is_empty() {
+            "# Effective config (no config files; bootstrap defaults)".to_string()
+        } else {
+            format!(
+                "# Effective config (merged from: {})",
+                self.sources.join(", ")
+            )
+        };
+        out.push_str("\n\n");
+        out.push_str(&json_to_toml_string(&self.config));
+        out.trim_end().to_string()
+    }
+}
+
 /// Report from `normalize config set`: records the key, old value, and new value applied.
 ///
 /// `dry_run` is true when the change was previewed but not written. `schema_warnings`
@@ -456,6 +485,66 @@ impl OutputFormatter for ConfigSetReport {
     }
 }
 
+/// One config file as seen by `normalize config migrate`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigFileMigration {
+    pub file: String,
+    /// `version` found in the file (0 when absent).
+    pub from_version: u32,
+    /// Version the file is (or would be) at after migrating.
+    pub to_version: u32,
+    /// One line per rewritten key or section.
+    pub changes: Vec<String>,
+    /// True when the migrated file was written back.
+    pub written: bool,
+}
+
+/// Report from `normalize config migrate`: per-file format upgrades.
+///
+/// Without `--write` this is a preview; files are never touched.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigMigrateReport {
+    pub current_version: u32,
+    pub files: Vec<ConfigFileMigration>,
+    pub write: bool,
+}
+
+impl OutputFormatter for ConfigMigrateReport {
+    fn format_text(&self) -> String {
+        if self.files.is_empty() {
+            return "No config files found".to_string();
+        }
+        let mut out = String::new();
+        let mut pending = false;
+        for f in &self.files {
+            if f.from_version == f.to_version {
+                out.push_str(&format!(
+                    "{}: up to date (version {})\n",
+                    f.file, f.from_version
+                ));
+                continue;
+            }
+            let verb = if f.written {
+                "migrated"
+            } else {
+                "would migrate"
+            };
+            pending |= !f.written;
+            out.push_str(&format!(
+                "{}: {verb} version {} → {}\n",
+                f.file, f.from_version, f.to_version
+            ));
+            for change in &f.changes {
+                out.push_str(&format!("  - {change}\n"));
+            }
+        }
+        if pending {
+            out.push_str("\nRun with --write to update the files.");
+        }
+        out.trim_end().to_string()
+    }
+}
+
 // ── Private helpers ─────────────────────────────────────────────────────────
 
 fn default_config_file(root: &Path) -> String {
@@ -548,15 +637,233 @@ fn validate_against_schema(
     schema_json: &serde_json::Value,
     instance: &serde_json::Value,
 ) -> Vec<String> {
+    schema_errors_with_paths(schema_json, instance)
+        .into_iter()
+        .map(|(msg, _)| msg)
+        .collect()
+}
+
+/// Schema violations paired with the instance path (key segments) they occurred at.
+fn schema_errors_with_paths(
+    schema_json: &serde_json::Value,
+    instance: &serde_json::Value,
+) -> Vec<(String, Vec<String>)> {
     match jsonschema::validator_for(schema_json) {
-        Err(e) => vec![format!("Schema compile error: {e}")],
+        Err(e) => vec![(format!("Schema compile error: {e}"), Vec::new())],
         Ok(validator) => validator
             .iter_errors(instance)
-            .map(|e| e.to_string())
+            .map(|e| {
+                let path = e
+                    .instance_path
+                    .as_str()
+                    .split('/')
+                    .skip(1)
+                    .map(|s| s.replace("~1", "/").replace("~0", "~"))
+                    .collect();
+                (e.to_string(), path)
+            })
             .collect(),
     }
 }
 
+/// A key present in the config file that the schema does not declare.
+struct UnknownKey {
+    /// Path to the key, including the key itself.
+    path: Vec<String>,
+    /// Closest declared sibling key, if one is similar enough to be a likely typo.
+    suggestion: Option<String>,
+}
+
+/// Flatten a schema node into the object variants it may take, resolving `$ref`
+/// and descending through `anyOf`/`oneOf`/`allOf` (e.g. `Option<T>` or flattened structs).
+fn schema_variants<'a>(
+    root: &'a serde_json::Value,
+    schema: &'a serde_json::Value,
+    out: &mut Vec<&'a serde_json::Value>,
+) {
+    let schema = resolve_ref(root, schema);
+    let mut composite = false;
+    for key in ["anyOf", "oneOf", "allOf"] {
+        if let Some(subs) = schema.get(key).and_then(|v| v.as_array()) {
+            composite = true;
+            for sub in subs {
+                schema_variants(root, sub, out);
+            }
+        }
+    }
+    if !composite || schema.get("properties").is_some() {
+        out.push(schema);
+    }
+}
+
+/// Collect keys in `instance` that no schema variant declares.
+///
+/// A table is only checked when the schema lists its `properties` and does not
+/// allow arbitrary extra keys — open maps (rule overrides, aliases, tag groups)
+/// are checked per-value against their `additionalProperties` schema instead.
+fn find_unknown_keys(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    instance: &serde_json::Value,
+    path: &mut Vec<String>,
+    out: &mut Vec<UnknownKey>,
+) {
+    let mut variants = Vec::new();
+    schema_variants(root, schema, &mut variants);
+    match instance {
+        serde_json::Value::Object(map) => {
+            let known: Vec<&str> = variants
+                .iter()
+                .filter_map(|v| v.get("properties").and_then(|p| p.as_object()))
+                .flat_map(|p| p.keys().map(String::as_str))
+                .collect();
+            let extra = variants
+                .iter()
+                .find_map(|v| v.get("additionalProperties").filter(|a| a.is_object()));
+            let open = known.is_empty()
+                || extra.is_some()
+                || variants
+                    .iter()
+                    .any(|v| v.get("additionalProperties") == Some(&serde_json::Value::Bool(true)));
+            for (key, value) in map {
+                path.push(key.clone());
+                let declared = variants.iter().find_map(|v| {
+                    v.get("properties")
+                        .and_then(|p| p.get(key.as_str()))
+                        .map(|s| resolve_ref(root, s))
+                });
+                if let Some(sub) = declared.or(extra) {
+                    find_unknown_keys(root, sub, value, path, out);
+                } else if !open {
+                    out.push(UnknownKey {
+                        path: path.clone(),
+                        suggestion: closest_key(key, &known),
+                    });
+                }
+                path.pop();
+            }
+        }
+        serde_json::Value::Array(items) => {
+            let item_schema = variants.iter().find_map(|v| v.get("items"));
+            if let Some(item_schema) = item_schema {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    find_unknown_keys(root, item_schema, item, path, out);
+                    path.pop();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Pick the candidate closest to `key` by edit distance, if it is close enough
+/// to plausibly be what the user meant.
+fn closest_key(key: &str, candidates: &[&str]) -> Option<String> {
+    let max = (key.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(key, c), *c))
+        .filter(|(d, _)| *d <= max)
+        .min()
+        .map(|(_, c)| c.to_string())
+}
+
+/// Levenshtein distance, counting adjacent transpositions as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev2: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut cur = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        prev2 = std::mem::replace(&mut prev, cur);
+    }
+    prev[b.len()]
+}
+
+/// Format a key path the way it would be written in TOML (`view.depth`, `rules."a/b"`).
+fn format_key_path(path: &[String]) -> String {
+    path.iter()
+        .map(|k| {
+            if !k.is_empty()
+                && k.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                k.clone()
+            } else {
+                format!("\"{k}\"")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Locate a key path in raw TOML source, returning 1-based line and column.
+///
+/// Points at the deepest key (or array element) along `path` that exists in the
+/// document, so an error inside a value still lands on the line that sets it.
+fn locate_key_path(doc: &toml_edit::ImDocument<&str>, path: &[String]) -> TomlSpan {
+    enum Node<'a> {
+        Item(&'a toml_edit::Item),
+        Table(&'a toml_edit::Table),
+        Value(&'a toml_edit::Value),
+    }
+
+    let mut node = Node::Item(doc.as_item());
+    let mut offset = None;
+    for segment in path {
+        let table: Option<&dyn toml_edit::TableLike> = match node {
+            Node::Item(item) => item.as_table_like(),
+            Node::Table(table) => Some(table),
+            Node::Value(value) => value
+                .as_inline_table()
+                .map(|t| t as &dyn toml_edit::TableLike),
+        };
+        let next = if let Some(table) = table {
+            table
+                .get_key_value(segment)
+                .map(|(key, value)| (key.span().or_else(|| value.span()), Node::Item(value)))
+        } else if let Ok(i) = segment.parse::<usize>() {
+            match node {
+                Node::Item(toml_edit::Item::ArrayOfTables(tables)) => {
+                    tables.get(i).map(|t| (t.span(), Node::Table(t)))
+                }
+                Node::Item(toml_edit::Item::Value(toml_edit::Value::Array(values)))
+                | Node::Value(toml_edit::Value::Array(values)) => {
+                    values.get(i).map(|v| (v.span(), Node::Value(v)))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+        let Some((span, child)) = next else { break };
+        if let Some(span) = span {
+            offset = Some(span.start);
+        }
+        node = child;
+    }
+    let Some(pos) = offset else {
+        return TomlSpan {
+            line: None,
+            column: None,
+        };
+    };
+    let before = &doc.raw()[..pos.min(doc.raw().len())];
+    TomlSpan {
+        line: Some(before.matches('\n').count() + 1),
+        column: Some(before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1),
+    }
+}
+
 /// Convert a `serde_json::Value` to a pretty TOML string, falling back to JSON.
 fn json_to_toml_string(value: &serde_json::Value) -> String {
     // serde_json → toml::Value → toml string
@@ -566,6 +873,18 @@ fn json_to_toml_string(value: &serde_json::Value) -> String {
     }
 }
 
+/// Remove `null` entries (unset `Option` fields), which TOML cannot represent.
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
 /// Type-coerce a string value for TOML: bool → i64 → f64 → string.
 fn coerce_value(s: &str) -> toml_edit::Value {
     if s == "true" {
@@ -684,6 +1003,22 @@ impl ConfigService {
         }
     }
 
+    fn display_effective(&self, r: &ConfigEffectiveReport) -> String {
+        if self.pretty.get() {
+            r.format_pretty()
+        } else {
+            r.format_text()
+        }
+    }
+
+    fn display_migrate(&self, r: &ConfigMigrateReport) -> String {
+        if self.pretty.get() {
+            r.format_pretty()
+        } else {
+            r.format_text()
+        }
+    }
+
     fn display_set(&self, r: &ConfigSetReport) -> String {
         if self.pretty.get() {
             r.format_pretty()
@@ -875,18 +1210,49 @@ impl ConfigService {
                 }
             };
 
-            // Phase 2: JSON Schema validation (catches unknown sections, type mismatches)
+            // Phase 2: JSON Schema validation (catches type mismatches) and unknown keys
+            // (typos like `[view] depht = 3` that serde would otherwise silently ignore)
             if let Some(ref tv) = toml_value
                 && let Ok(json_val) = serde_json::to_value(tv)
             {
-                let schema_errors = validate_against_schema(&schema_json, &json_val);
-                for msg in schema_errors {
+                let doc = toml_edit::ImDocument::parse(raw.as_str()).ok();
+                let locate = |path: &[String]| match &doc {
+                    Some(doc) => locate_key_path(doc, path),
+                    None => TomlSpan {
+                        line: None,
+                        column: None,
+                    },
+                };
+                for (msg, path) in schema_errors_with_paths(&schema_json, &json_val) {
+                    let TomlSpan { line, column } = locate(&path);
                     all_errors.push(ConfigError {
                         file: config_path.clone(),
                         phase: "schema".to_string(),
                         message: msg,
-                        line: None,
-                        column: None,
+                        line,
+                        column,
+                    });
+                }
+                let mut unknown = Vec::new();
+                find_unknown_keys(
+                    &schema_json,
+                    &schema_json,
+                    &json_val,
+                    &mut Vec::new(),
+                    &mut unknown,
+                );
+                for UnknownKey { path, suggestion } in unknown {
+                    let TomlSpan { line, column } = locate(&path);
+                    let mut message = format!("unknown key `{}`", format_key_path(&path));
+                    if let Some(s) = suggestion {
+                        message.push_str(&format!(" (did you mean `{s}`?)"));
+                    }
+                    all_errors.push(ConfigError {
+                        file: config_path.clone(),
+                        phase: "unknown-key".to_string(),
+                        message,
+                        line,
+                        column,
                     });
                 }
             }
@@ -945,6 +1311,93 @@ impl ConfigService {
         }
     }
 
+    /// Print the effective config: global and project config merged, with defaults filled in.
+    /// This is exactly what commands see, so it shows whether a setting actually took effect.
+    ///
+    /// Examples:
+    ///   normalize config effective              # merged config as TOML
+    ///   normalize config effective --json       # merged config as JSON
+    #[cli(display_with = "display_effective")]
+    pub fn effective(
+        &self,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<ConfigEffectiveReport, String> {
+        let root_path = Self::resolve_root(root)?;
+        self.resolve_format(&root_path);
+        let sources = NormalizeConfig::global_config_path()
+            .into_iter()
+            .chain([root_path.join(".normalize").join("config.toml")])
+            .filter(|p| p.exists())
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        let mut config = serde_json::to_value(NormalizeConfig::load(&root_path))
+            .map_err(|e| format!("Config serialization error: {e}"))?;
+        strip_nulls(&mut config);
+        Ok(ConfigEffectiveReport { sources, config })
+    }
+
+    /// Upgrade config files to the current format version (renamed keys, moved sections)
+    ///
+    /// Checks the project config (.normalize/config.toml) and the global config
+    /// (~/.config/normalize/config.toml) unless --file overrides. Previews by default;
+    /// comments and layout are kept when writing.
+    ///
+    /// Examples:
+    ///   normalize config migrate                         # preview pending migrations
+    ///   normalize config migrate --write                 # rewrite outdated files
+    ///   normalize config migrate --file custom.toml      # migrate a specific file only
+    #[cli(display_with = "display_migrate")]
+    pub fn migrate(
+        &self,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(help = "Path to config file (default: project and global config)")] file: Option<
+            String,
+        >,
+        #[param(help = "Write migrated files (default: preview only)")] write: bool,
+    ) -> Result<ConfigMigrateReport, String> {
+        let root_path = Self::resolve_root(root)?;
+        self.resolve_format(&root_path);
+        let paths: Vec<String> = match file {
+            Some(f) => vec![f],
+            None => NormalizeConfig::global_config_path()
+                .map(|p| p.to_string_lossy().into_owned())
+                .into_iter()
+                .chain([default_config_file(&root_path)])
+                .filter(|p| Path::new(p).exists())
+                .collect(),
+        };
+
+        let mut files = Vec::new();
+        for path in paths {
+            let content =
+                std::fs::read_to_string(&path).map_err(|e| format!("Cannot read '{path}': {e}"))?;
+            let (migrated, outcome) = crate::config_migrate::migrate_str(&content)
+                .map_err(|e| format!("{e} ('{path}')"))?;
+            let written = write && outcome.migrated();
+            if written {
+                std::fs::write(&path, migrated)
+                    .map_err(|e| format!("Cannot write '{path}': {e}"))?;
+            }
+            files.push(ConfigFileMigration {
+                file: path,
+                from_version: outcome.from_version,
+                to_version: outcome.to_version,
+                changes: outcome.changes,
+                written,
+            });
+        }
+
+        Ok(ConfigMigrateReport {
+            current_version: crate::config_migrate::CURRENT_CONFIG_VERSION,
+            files,
+            write,
+        })
+    }
+
     /// Set a config value by dotted key path (TOML files only)
     ///
     /// Examples:
@@ -1054,3 +1507,35 @@ impl ConfigService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_located_with_suggestions() {
+        let raw = "[view]\ndepht = 3\n\n[analyz]\nthreshold = 5\n\n[rules.rule.\"long-file\"]\nthreshold = 500\n";
+        let schema = load_schema(None).unwrap_or_default();
+        let instance =
+            serde_json::to_value(toml::from_str::<toml::Value>(raw).ok()).unwrap_or_default();
+        let mut unknown = Vec::new();
+        find_unknown_keys(&schema, &schema, &instance, &mut Vec::new(), &mut unknown);
+        let doc = toml_edit::ImDocument::parse(raw).ok();
+        let mut found: Vec<(String, Option<String>, Option<usize>)> = unknown
+            .into_iter()
+            .map(|u| {
+                let line = doc.as_ref().and_then(|d| locate_key_path(d, &u.path).line);
+                (format_key_path(&u.path), u.suggestion, line)
+            })
+            .collect();
+        found.sort();
+        // Rule-specific keys (`threshold` under a rule override) are allowed.
+        assert_eq!(
+            found,
+            vec![
+                ("analyz".to_string(), Some("analyze".to_string()), Some(4)),
+                ("view.depht".to_string(), Some("depth".to_string()), Some(2)),
+            ]
+        );
+    }
+}
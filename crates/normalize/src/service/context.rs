@@ -151,6 +151,7 @@ fn resolve_context_blocks(
 /// Context sub-service: frontmatter-filtered context resolution and migration.
 pub struct ContextService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
 }
@@ -159,6 +160,7 @@ impl ContextService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -170,11 +172,12 @@ impl ContextService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
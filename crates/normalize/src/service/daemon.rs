@@ -30,6 +30,26 @@ pub struct DaemonStatus {
     /// the spin failure mode, since the auto-started daemon's logs go to a file.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub spin_warnings: Vec<SpinWarningReport>,
+    /// Sizes of the caches the daemon keeps alongside each root's index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caches: Option<DaemonCacheStats>,
+}
+
+/// Daemon cache occupancy, summed over all watched roots.
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct DaemonCacheStats {
+    /// Roots whose facts relations are currently built.
+    #[serde(default)]
+    pub relations: u64,
+    /// Cached file skeletons.
+    #[serde(default)]
+    pub skeletons: u64,
+    /// Skeleton requests served from cache.
+    #[serde(default)]
+    pub skeleton_hits: u64,
+    /// Skeleton requests that required extraction.
+    #[serde(default)]
+    pub skeleton_misses: u64,
 }
 
 /// A single spin-loop detection surfaced in `normalize daemon status`.
@@ -68,6 +88,13 @@ impl OutputFormatter for DaemonStatus {
         if let Some(roots) = self.roots_watched {
             let _ = writeln!(out, "  Roots watched: {}", roots);
         }
+        if let Some(c) = &self.caches {
+            let _ = writeln!(
+                out,
+                "  Caches: {} relations, {} skeletons ({} hits, {} misses)",
+                c.relations, c.skeletons, c.skeleton_hits, c.skeleton_misses
+            );
+        }
         if self.spin_warnings.is_empty() {
             // Trim trailing newline from the last writeln above.
             while out.ends_with('\n') {
@@ -208,6 +235,7 @@ impl DaemonService {
                 uptime_secs: None,
                 roots_watched: None,
                 spin_warnings: Vec::new(),
+                caches: None,
             });
         }
 
@@ -226,6 +254,10 @@ impl DaemonService {
                     uptime_secs: data.get("uptime_secs").and_then(|v| v.as_u64()),
                     roots_watched: data.get("roots_watched").and_then(|v| v.as_u64()),
                     spin_warnings,
+                    caches: data
+                        .get("caches")
+                        .cloned()
+                        .and_then(|v| serde_json::from_value(v).ok()),
                 })
             }
             Ok(resp) => Err(resp.error.unwrap_or_default()),
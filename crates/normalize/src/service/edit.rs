@@ -204,8 +204,8 @@ fn insert_at_destination(
         result = match at {
             Position::Before | Position::After => {
                 let dest_loc = editor
-                    .find_symbol(file_path, &result, destination, case_insensitive)
-                    .ok_or_else(|| format!("Destination not found: {}", destination))?;
+                    .find_unique_symbol(file_path, &result, destination, case_insensitive)
+                    .map_err(|e| format!("Destination: {}", e))?;
                 if matches!(at, Position::Before) {
                     editor.insert_before(&result, &dest_loc, source_content)
                 } else {
@@ -242,8 +242,8 @@ fn insert_single_at_destination(
     match at {
         Position::Before | Position::After => {
             let dest_loc = editor
-                .find_symbol(file_path, content, destination, case_insensitive)
-                .ok_or_else(|| format!("Destination not found: {}", destination))?;
+                .find_unique_symbol(file_path, content, destination, case_insensitive)
+                .map_err(|e| format!("Destination: {}", e))?;
             Ok(if matches!(at, Position::Before) {
                 editor.insert_before(content, &dest_loc, source_content)
             } else {
@@ -437,8 +437,7 @@ fn do_edit(
         );
     }
 
-    let unified = path_resolve::resolve_unified(target, &root)
-        .ok_or_else(|| format!("No matches for: {}", target))?;
+    let unified = path_resolve::resolve_target(target, &root)?;
 
     if unified.is_directory {
         return Err(format!("Cannot edit a directory: {}", target));
@@ -492,11 +491,18 @@ fn do_edit(
         );
     }
 
-    // normalize-syntax-allow: rust/unwrap-in-impl - symbol_path is non-empty (checked via is_empty() + early return above)
-    let symbol_name = unified.symbol_path.last().unwrap();
-    let loc = editor
-        .find_symbol(&file_path, &content, symbol_name, case_insensitive)
-        .ok_or_else(|| format!("Symbol not found: {}", symbol_name))?;
+    // Parents in the target path (`file/Foo/new`) qualify the name, so
+    // `find_symbol_with` picks the right `new` or reports the ambiguity.
+    let symbol_name = &unified.symbol_path.join("::");
+    // Decorators, attributes and doc comments travel with the symbol, except on
+    // replace: replacement code rarely repeats them, so they stay in place.
+    let trivia = if matches!(action, EditAction::Replace { .. }) {
+        edit::IncludeTrivia::Definition
+    } else {
+        edit::IncludeTrivia::Leading
+    };
+    let loc =
+        editor.find_symbol_with(&file_path, &content, symbol_name, case_insensitive, trivia)?;
 
     let (operation, new_content) = match action {
         EditAction::Delete => (Operation::Delete, editor.delete_symbol(&content, &loc)),
@@ -510,8 +516,8 @@ fn do_edit(
 
         EditAction::Swap { ref other } => {
             let other_loc = editor
-                .find_symbol(&file_path, &content, other, case_insensitive)
-                .ok_or_else(|| format!("Other symbol not found: {}", other))?;
+                .find_symbol_with(&file_path, &content, other, case_insensitive, trivia)
+                .map_err(|e| format!("Other symbol: {}", e))?;
             let (first_loc, second_loc) = if loc.start_byte < other_loc.start_byte {
                 (&loc, &other_loc)
             } else {
@@ -534,7 +540,7 @@ fn do_edit(
                 Position::After => editor.insert_after(&content, &loc, insert_content),
                 Position::Prepend | Position::Append => {
                     let body = editor
-                        .find_container_body(&file_path, &content, symbol_name)
+                        .find_container_body(&file_path, &content, &loc.name)
                         .ok_or_else(|| format!("Error: '{}' is not a container", symbol_name))?;
                     if matches!(at, Position::Prepend) {
                         editor.prepend_to_container(&content, &body, insert_content)
@@ -661,12 +667,22 @@ fn do_edit_each(
             }
         };
 
-        let loc = match editor.find_symbol(&file_path, &content, symbol, case_insensitive) {
-            Some(l) => l,
-            None => {
+        // Files without the symbol are skipped; an ambiguous name is an error
+        // rather than an edit to whichever match comes first.
+        let loc = match editor.find_unique_symbol(&file_path, &content, symbol, case_insensitive) {
+            Ok(l) => l,
+            Err(_)
+                if editor
+                    .find_symbols(&file_path, &content, symbol, case_insensitive)
+                    .is_empty() =>
+            {
                 _skipped += 1;
                 continue;
             }
+            Err(e) => {
+                errors.push(format!("{}: {}", m.path, e));
+                continue;
+            }
         };
 
         let new_content = match &action {
@@ -941,6 +957,10 @@ impl EditService {
         report.format_text()
     }
 
+    fn display_move_file(&self, report: &MoveFileReport) -> String {
+        report.format_text()
+    }
+
     fn display_introduce_variable(&self, report: &IntroduceVariableReport) -> String {
         report.format_text()
     }
@@ -1385,8 +1405,11 @@ impl EditService {
     /// Move a symbol's definition to another file and rewrite import sites.
     ///
     /// Locates the symbol via the facts index (or in-place extraction when no index
-    /// is available), appends its definition to the destination file, removes it from
-    /// the source, and rewrites import statements in every file that imported it.
+    /// is available), adds its definition to the destination file (inside the same-named
+    /// class or impl when the destination has one), removes it from the source, and
+    /// rewrites import statements in every file that imported it. The source imports
+    /// the symbol from its new home if it still uses it; the destination drops its
+    /// import of it.
     ///
     /// Per-language import rewriting is best-effort: where the new module path can be
     /// derived (Python, Go, JS/TS), import sites are updated. Where it can't (Rust
@@ -1419,6 +1442,39 @@ impl EditService {
         .await
     }
 
+    /// Move a file to a new path and rewrite every import that names it.
+    ///
+    /// Importers are found through the facts index's resolved import edges. Relative
+    /// specifiers (`./utils`, `from .utils import …`) are recomputed from each
+    /// importer's directory, keeping the original extension style; absolute ones
+    /// (`crate::a::b`, `pkg.mod`, Go import paths, TS `baseUrl` paths) are re-derived
+    /// from the language's module resolver. The moved file's own relative imports are
+    /// rebased onto its new directory. Imports that reach the file through a
+    /// re-export are left alone; anything that can't be rewritten confidently is
+    /// reported as skipped with a warning. Rust `mod` declarations are not rewritten.
+    ///
+    /// Examples:
+    ///   normalize edit move-file src/utils.ts src/lib/utils.ts
+    ///   normalize edit move-file pkg/helpers.py pkg/util/helpers.py --dry-run
+    #[cli(name = "move-file", display_with = "display_move_file")]
+    pub async fn move_file(
+        &self,
+        #[param(positional, help = "File to move")] from: String,
+        #[param(positional, help = "New path for the file")] to: String,
+        #[param(help = "Dry run - show what would change")] dry_run: bool,
+        #[param(short = 'm', help = "Message for shadow history")] message: Option<String>,
+        #[param(short = 'r', help = "Root directory")] root: Option<String>,
+    ) -> Result<MoveFileReport, String> {
+        do_move_file(
+            &from,
+            &to,
+            root.as_deref().map(Path::new),
+            dry_run,
+            message.as_deref(),
+        )
+        .await
+    }
+
     /// Extract an expression into a named variable binding.
     ///
     /// Parses the file with tree-sitter to locate the expression at the given range,
@@ -1677,8 +1733,7 @@ async fn do_rename(
     };
 
     // Resolve path before entering refactoring engine
-    let unified = path_resolve::resolve_unified(target, &root)
-        .ok_or_else(|| format!("No matches for: {}", target))?;
+    let unified = path_resolve::resolve_target(target, &root)?;
 
     if unified.symbol_path.is_empty() {
         return Err(format!(
@@ -2308,8 +2363,7 @@ async fn do_move(
     let shadow_enabled = config.shadow.enabled();
 
     // Resolve the target → file + symbol name.
-    let unified = path_resolve::resolve_unified(target, &root)
-        .ok_or_else(|| format!("No matches for: {}", target))?;
+    let unified = path_resolve::resolve_target(target, &root)?;
     if unified.symbol_path.is_empty() {
         return Err(format!(
             "Target must include a symbol name (e.g. path/SymbolName), got: {}",
@@ -2390,6 +2444,151 @@ async fn do_move(
     })
 }
 
+// ── move-file ────────────────────────────────────────────────────────────────
+
+/// Report returned by `normalize edit move-file`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MoveFileReport {
+    /// Original path of the file (relative).
+    pub from_file: String,
+    /// New path of the file (relative).
+    pub to_file: String,
+    /// Number of import sites successfully rewritten.
+    pub import_sites_updated: usize,
+    /// Number of import sites that could not be rewritten confidently.
+    pub import_sites_skipped: usize,
+    /// Whether this was a dry run (no files written).
+    pub dry_run: bool,
+    /// Files written or removed (or that would be, on dry-run).
+    pub files: Vec<String>,
+    /// Warnings emitted during planning (skipped import sites, `mod` declarations
+    /// to update by hand).
+    pub warnings: Vec<String>,
+}
+
+impl OutputFormatter for MoveFileReport {
+    fn format_text(&self) -> String {
+        use std::fmt::Write as _;
+        let mut out = String::new();
+        let verb = if self.dry_run { "Would move" } else { "Moved" };
+        let _ = writeln!(out, "{} {} to {}", verb, self.from_file, self.to_file);
+        let _ = writeln!(
+            out,
+            "  imports: {} updated, {} skipped",
+            self.import_sites_updated, self.import_sites_skipped
+        );
+        if !self.files.is_empty() {
+            let _ = writeln!(out, "  files: {}", self.files.join(", "));
+        }
+        for w in &self.warnings {
+            let _ = writeln!(out, "  warning: {}", w);
+        }
+        out
+    }
+
+    fn format_pretty(&self) -> String {
+        use std::fmt::Write as _;
+        let mut out = String::new();
+        let verb = if self.dry_run {
+            "\x1b[1;33mWould move\x1b[0m"
+        } else {
+            "\x1b[1;32mMoved\x1b[0m"
+        };
+        let _ = writeln!(
+            out,
+            "{} \x1b[2m{}\x1b[0m to \x1b[2m{}\x1b[0m",
+            verb, self.from_file, self.to_file
+        );
+        let _ = writeln!(
+            out,
+            "  imports: \x1b[1;32m{}\x1b[0m updated, \x1b[1;33m{}\x1b[0m skipped",
+            self.import_sites_updated, self.import_sites_skipped
+        );
+        if !self.files.is_empty() {
+            let _ = writeln!(out, "  files: {}", self.files.join(", "));
+        }
+        for w in &self.warnings {
+            let _ = writeln!(out, "  \x1b[0;33mwarning\x1b[0m: {}", w);
+        }
+        out
+    }
+}
+
+async fn do_move_file(
+    from: &str,
+    to: &str,
+    root: Option<&Path>,
+    dry_run: bool,
+    message: Option<&str>,
+) -> Result<MoveFileReport, String> {
+    let root = root
+        .map(|p| p.to_path_buf())
+        // normalize-syntax-allow: rust/unwrap-in-impl - current_dir() only fails if cwd was deleted
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    let config = NormalizeConfig::load(&root);
+    let shadow_enabled = config.shadow.enabled();
+
+    // Accept absolute paths too; the recipe works on root-relative ones.
+    let relative = |input: &str| {
+        let path = Path::new(input);
+        if path.is_absolute() {
+            path.strip_prefix(&root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| input.to_string())
+        } else {
+            input.to_string()
+        }
+    };
+    let from_rel_path = relative(from);
+    let to_rel_path = relative(to);
+
+    let index = match crate::index::ensure_ready(&root).await {
+        Ok(idx) => Some(idx),
+        Err(e) => {
+            eprintln!("warning: index not available ({}); moving file only", e);
+            None
+        }
+    };
+
+    let ctx = normalize_refactor::RefactoringContext {
+        root: root.clone(),
+        editor: edit::Editor::new(),
+        index,
+        loader: normalize_languages::GrammarLoader::new(),
+    };
+
+    let outcome =
+        normalize_refactor::move_file::plan_move_file(&ctx, &from_rel_path, &to_rel_path).await?;
+
+    for w in &outcome.plan.warnings {
+        eprintln!("warning: {}", w);
+    }
+
+    let executor = normalize_refactor::RefactoringExecutor {
+        root: root.clone(),
+        dry_run,
+        shadow_enabled,
+        message: message.map(String::from),
+    };
+
+    let modified = executor.apply(&outcome.plan)?;
+
+    if !dry_run {
+        notify_daemon_after_edit(&root, &modified);
+    }
+
+    Ok(MoveFileReport {
+        from_file: outcome.from_file,
+        to_file: outcome.to_file,
+        import_sites_updated: outcome.import_sites_updated,
+        import_sites_skipped: outcome.import_sites_skipped,
+        dry_run,
+        files: modified,
+        warnings: outcome.plan.warnings,
+    })
+}
+
 // ── inline-function ───────────────────────────────────────────────────────────
 
 /// Report returned by `normalize edit inline-function`.
@@ -32,16 +32,90 @@ impl OutputFormatter for GenerateReport {
     }
 }
 
+/// Report for `normalize generate verify`.
+///
+/// `valid` is true only when every sample is accepted by the schema.
+#[derive(serde::Serialize, schemars::JsonSchema)]
+pub struct VerifyReport {
+    pub valid: bool,
+    /// One entry per sample file, sorted by path.
+    pub samples: Vec<VerifySample>,
+}
+
+/// A sample payload and the mismatches found in it.
+#[derive(serde::Serialize, schemars::JsonSchema)]
+pub struct VerifySample {
+    pub file: String,
+    /// Type the sample was checked against (absent if the file is not valid JSON).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    pub mismatches: Vec<VerifyMismatch>,
+}
+
+/// A place where a sample payload disagrees with the schema.
+#[derive(serde::Serialize, schemars::JsonSchema)]
+pub struct VerifyMismatch {
+    /// JSONPath of the offending value (e.g. `$.items[0].name`).
+    pub path: String,
+    pub message: String,
+}
+
+impl OutputFormatter for VerifyReport {
+    fn format_text(&self) -> String {
+        let failed: Vec<_> = self
+            .samples
+            .iter()
+            .filter(|s| !s.mismatches.is_empty())
+            .collect();
+        if failed.is_empty() {
+            return format!("All {} sample(s) match the schema", self.samples.len());
+        }
+        let mut out = format!(
+            "{} of {} sample(s) do not match the schema\n",
+            failed.len(),
+            self.samples.len()
+        );
+        for sample in failed {
+            match &sample.type_name {
+                Some(name) => out.push_str(&format!("  {} ({}):\n", sample.file, name)),
+                None => out.push_str(&format!("  {}:\n", sample.file)),
+            }
+            for m in &sample.mismatches {
+                out.push_str(&format!("    {}: {}\n", m.path, m.message));
+            }
+        }
+        out
+    }
+}
+
 impl GenerateService {
     /// Generic display bridge that routes to `OutputFormatter::format_text()`.
     fn display_output<T: OutputFormatter>(&self, value: &T) -> String {
         value.format_text()
     }
+
+    /// Write generated code to `output`, or return it for stdout.
+    fn emit(&self, code: String, output: Option<String>) -> Result<GenerateReport, String> {
+        if let Some(ref path) = output {
+            crate::output::sink::write_atomic(path, code.as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            eprintln!("Generated {}", path);
+            Ok(GenerateReport {
+                output: code,
+                path: Some(path.clone()),
+            })
+        } else {
+            Ok(GenerateReport {
+                output: code,
+                path: None,
+            })
+        }
+    }
 }
 
 #[cli(
     name = "generate",
-    description = "Generate code from an API spec. Use to scaffold clients or types from OpenAPI definitions."
+    description = "Generate code from an API spec. Use to scaffold clients, server stubs or types from OpenAPI definitions."
 )]
 impl GenerateService {
     /// Generate API client from OpenAPI spec
@@ -49,16 +123,16 @@ impl GenerateService {
     /// Examples:
     ///   normalize generate client api.json -l typescript          # generate TypeScript client
     ///   normalize generate client api.json -l python -o client.py # generate Python client to file
+    ///   normalize generate client api.yaml -l rust                # YAML and Swagger 2.0 specs work too
     #[cli(display_with = "display_output")]
     pub fn client(
         &self,
-        #[param(positional, help = "OpenAPI spec JSON file")] spec: String,
+        #[param(positional, help = "OpenAPI spec file (JSON or YAML)")] spec: String,
         #[param(short = 'l', help = "Target language: typescript, python, rust")] lang: String,
         #[param(short = 'o', help = "Output file (stdout if not specified)")] output: Option<
             String,
         >,
     ) -> Result<GenerateReport, String> {
-        let spec_path = PathBuf::from(&spec);
         let generator = normalize_openapi::find_generator(&lang).ok_or_else(|| {
             let mut msg = format!("Unknown language: {}. Available:", lang);
             for (l, variant) in normalize_openapi::list_generators() {
@@ -67,26 +141,33 @@ impl GenerateService {
             msg
         })?;
 
-        let content = std::fs::read_to_string(&spec_path)
-            .map_err(|e| format!("Failed to read {}: {}", spec, e))?;
-        let spec_json: serde_json::Value =
-            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        let normalized = normalize_openapi::load_spec(&spec)?;
+        self.emit(generator.generate(&normalized), output)
+    }
 
-        let code = generator.generate(&spec_json);
+    /// Generate a server stub from OpenAPI spec
+    ///
+    /// Examples:
+    ///   normalize generate server api.yaml -l rust -o src/api.rs  # axum router + `Api` trait to implement
+    #[cli(display_with = "display_output")]
+    pub fn server(
+        &self,
+        #[param(positional, help = "OpenAPI spec file (JSON or YAML)")] spec: String,
+        #[param(short = 'l', help = "Target language: rust")] lang: String,
+        #[param(short = 'o', help = "Output file (stdout if not specified)")] output: Option<
+            String,
+        >,
+    ) -> Result<GenerateReport, String> {
+        let generator = normalize_openapi::get_server_generator(&lang).ok_or_else(|| {
+            let mut msg = format!("Unknown server language: {}. Available:", lang);
+            for (l, variant) in normalize_openapi::list_server_generators() {
+                msg.push_str(&format!("\n  {} ({})", l, variant));
+            }
+            msg
+        })?;
 
-        if let Some(ref path) = output {
-            std::fs::write(path, &code).map_err(|e| format!("Failed to write {}: {}", path, e))?;
-            eprintln!("Generated {}", path);
-            Ok(GenerateReport {
-                output: code,
-                path: Some(path.clone()),
-            })
-        } else {
-            Ok(GenerateReport {
-                output: code,
-                path: None,
-            })
-        }
+        let normalized = normalize_openapi::load_spec(&spec)?;
+        self.emit(generator.generate(&normalized), output)
     }
 
     /// Generate types/validators from schema
@@ -123,7 +204,7 @@ impl GenerateService {
         #[param(help = "Package name (for Go)")] package: Option<String>,
         #[param(help = "Preview output without writing any files")] dry_run: bool,
         #[param(
-            help = "Emit one file per top-level type into the output directory (requires --output)"
+            help = "Emit one file per top-level type, with imports, an index and manifest.json (requires --output)"
         )]
         split: bool,
     ) -> Result<GenerateReport, String> {
@@ -146,4 +227,72 @@ impl GenerateService {
             split,
         )
     }
+
+    /// Check sample JSON payloads against a schema
+    ///
+    /// Confirms that validators generated from the schema (Zod, Pydantic, ...) will accept
+    /// real data. Each `.json` file under the samples directory is checked against --type,
+    /// or the type its file name matches (`user.json` -> `User`), or the best-fitting type.
+    ///
+    /// Examples:
+    ///   normalize generate verify schema.json samples/            # check every sample
+    ///   normalize generate verify api.yaml samples/ -t Order      # check against one type
+    #[cli(display_with = "display_output")]
+    pub fn verify(
+        &self,
+        #[param(
+            positional,
+            help = "Input schema file (JSON Schema or OpenAPI), use - for stdin"
+        )]
+        input: String,
+        #[param(positional, help = "Directory of sample JSON payloads")] samples: String,
+        #[param(short = 't', help = "Type to check every sample against")] r#type: Option<String>,
+        #[param(
+            short = 'f',
+            help = "Input format (auto, json-schema, openapi, typescript)"
+        )]
+        format: Option<InputFormat>,
+    ) -> Result<VerifyReport, String> {
+        crate::commands::generate::run_verify_service(
+            PathBuf::from(input),
+            format.unwrap_or(InputFormat::Auto),
+            PathBuf::from(samples),
+            r#type,
+        )
+    }
+
+    /// Compare two versions of a schema and classify the changes
+    ///
+    /// Breaking: removed types, fields and enum values, type changes, and fields that became
+    /// required or non-nullable. Everything else is additive. Use --check in CI to fail
+    /// when the types generated from the schema would break their consumers.
+    ///
+    /// Examples:
+    ///   normalize generate diff old.json schema.json          # list changes
+    ///   normalize generate diff old.yaml api.yaml --check     # CI: fail on breaking changes
+    #[cli(display_with = "display_output")]
+    pub fn diff(
+        &self,
+        #[param(positional, help = "Previous schema file (JSON Schema or OpenAPI)")] old: String,
+        #[param(positional, help = "New schema file (JSON Schema or OpenAPI)")] new: String,
+        #[param(
+            short = 'f',
+            help = "Input format (auto, json-schema, openapi, typescript)"
+        )]
+        format: Option<InputFormat>,
+        #[param(help = "Fail if any change is breaking")] check: bool,
+    ) -> Result<normalize_typegen::ir::SchemaDiff, String> {
+        let diff = crate::commands::generate::run_diff_service(
+            PathBuf::from(old),
+            PathBuf::from(new),
+            format.unwrap_or(InputFormat::Auto),
+        )?;
+        if check && diff.is_breaking() {
+            return Err(format!(
+                "{}\nSchema has breaking changes",
+                self.display_output(&diff)
+            ));
+        }
+        Ok(diff)
+    }
 }
@@ -86,7 +86,10 @@ impl GrammarService {
     #[cli(display_with = "display_output")]
     pub fn list(&self) -> Result<GrammarListReport, String> {
         let grammars = crate::parsers::available_external_grammars_with_paths();
-        Ok(GrammarListReport::new(grammars))
+        Ok(GrammarListReport::new(
+            grammars,
+            &normalize_languages::static_grammar_names(),
+        ))
     }
 
     /// Install grammars from GitHub release
@@ -118,6 +121,17 @@ impl GrammarService {
             .map(|c| c.join("normalize/grammars"))
             .ok_or_else(|| "Could not determine config directory".to_string())?;
 
+        // One install at a time: a concurrent invocation waits here, then sees
+        // the finished install below instead of downloading again.
+        let _lock = if dry_run {
+            None
+        } else {
+            Some(
+                normalize_cache::CacheLock::exclusive(&install_dir)
+                    .map_err(|e| format!("Failed to lock grammar directory: {}", e))?,
+            )
+        };
+
         // Check if grammars already exist
         if install_dir.exists()
             && !force
@@ -221,8 +235,17 @@ impl GrammarService {
                     || name_str.ends_with(".dylib")
                     || name_str.ends_with(".dll")
                 {
+                    // Unpack beside the target and rename over it: other processes
+                    // may have the old library mapped, and must keep a valid file.
                     let dest_path = install_dir.join(name);
-                    entry.unpack(&dest_path).map_err(|e| e.to_string())?;
+                    let mut staged = dest_path.clone().into_os_string();
+                    staged.push(format!(".{}.tmp", std::process::id()));
+                    let staged = std::path::PathBuf::from(staged);
+                    entry.unpack(&staged).map_err(|e| e.to_string())?;
+                    std::fs::rename(&staged, &dest_path).map_err(|e| {
+                        let _ = std::fs::remove_file(&staged);
+                        format!("Failed to install {}: {}", dest_path.display(), e)
+                    })?;
                     count += 1;
                 }
             }
@@ -51,6 +51,8 @@ use std::path::PathBuf;
 pub struct NormalizeService {
     /// Whether pretty output is active (resolved per-command from globals + config).
     pretty: Cell<bool>,
+    /// Whether long pretty output goes through the pager (`[pretty] pager = true`).
+    pager: Cell<bool>,
     /// Raw `--pretty`/`--compact` flag values delivered by `CliGlobals`, resolved
     /// per-command against the target root (TTY/config) into `pretty`.
     pretty_raw: Cell<bool>,
@@ -150,6 +152,7 @@ impl NormalizeService {
             trend: trend::TrendService::new(&pretty),
             view: view::ViewService::new(&pretty),
             pretty,
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -178,12 +181,13 @@ impl NormalizeService {
             self.pretty_raw.get(),
             self.compact_raw.get(),
         ));
+        self.pager.set(NormalizeConfig::load(root).pretty.pager());
     }
 
     /// Generic display bridge that respects pretty/compact state.
     fn display_output<T: OutputFormatter>(&self, value: &T) -> String {
         if self.pretty.get() {
-            value.format_pretty()
+            value.format_pretty_paged(self.pager.get())
         } else {
             value.format_text()
         }
@@ -253,6 +257,10 @@ pub struct TranslateReport {
     pub input_path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_path: Option<String>,
+    /// IR validation diagnostics: undefined names, dead code and constructs
+    /// the target language cannot express.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl OutputFormatter for TranslateReport {
@@ -283,6 +291,9 @@ pub struct AliasListEntry {
     pub value: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Command the alias is scoped to (`[aliases.<scope>]`); absent for global aliases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 impl OutputFormatter for AliasListReport {
@@ -290,7 +301,11 @@ impl OutputFormatter for AliasListReport {
         use std::fmt::Write as _;
         let mut out = String::new();
         for alias in &self.aliases {
-            let _ = write!(out, "@{} [{}, {}]", alias.name, alias.status, alias.syntax);
+            let _ = write!(out, "@{} [{}, {}", alias.name, alias.status, alias.syntax);
+            if let Some(scope) = &alias.scope {
+                let _ = write!(out, ", scope={scope}");
+            }
+            out.push(']');
             if !alias.value.is_empty() {
                 if alias.value.len() == 1 {
                     let _ = write!(out, ": {}", alias.value[0]);
@@ -348,6 +363,7 @@ impl NormalizeService {
     ///   normalize grep "TODO" --only "*.rs"    # search Rust files for TODO
     ///   normalize grep "fn main" src/          # search in specific directory
     ///   normalize grep "class \w+" --only "*.py" --json   # JSON output
+    ///   normalize grep "TODO" --only-modified-since 2w --exclude-larger-than 1MB
     #[server(group = "core")]
     #[cli(display_with = "display_output")]
     #[allow(clippy::too_many_arguments)]
@@ -364,6 +380,10 @@ impl NormalizeService {
         #[param(short = 'i', help = "Case-insensitive search")] ignore_case: bool,
         #[param(help = "Exclude files matching patterns or aliases")] exclude: Vec<String>,
         #[param(help = "Only include files matching patterns or aliases")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only search files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
     ) -> Result<GrepReport, String> {
         // `path` positional takes precedence over `--root` flag.
         let root_path = path
@@ -379,7 +399,12 @@ impl NormalizeService {
         let limit = limit.unwrap_or_else(|| config.text_search.limit());
         let ignore_case = ignore_case || config.text_search.ignore_case();
 
-        let filter = commands::build_filter(&root_path, &exclude, &only);
+        let predicates = commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
+        let filter =
+            commands::build_filter_with_predicates(&root_path, &exclude, &only, &predicates);
 
         match text_search::grep(&pattern, &root_path, filter.as_ref(), limit, ignore_case) {
             Ok(result) => {
@@ -479,6 +504,8 @@ impl NormalizeService {
                 r#"# Normalize configuration
 # See: https://github.com/rhi-zone/normalize
 
+version = {}
+
 [daemon]
 # enabled = true
 # auto_start = true
@@ -492,7 +519,9 @@ impl NormalizeService {
 # security = 2.0
 # clones = 0.3
 {}{}"#,
-                walk.toml, aliases_section
+                crate::config_migrate::CURRENT_CONFIG_VERSION,
+                walk.toml,
+                aliases_section
             );
             if !dry_run {
                 fs::write(&config_path, default_config)
@@ -749,6 +778,7 @@ impl NormalizeService {
                     Some("ts") | Some("tsx") | Some("js") | Some("jsx") => "typescript",
                     Some("lua") => "lua",
                     Some("py") => "python",
+                    Some("go") => "go",
                     _ => {
                         return Err(
                             "Cannot detect language from extension. Use --from to specify source language."
@@ -770,10 +800,20 @@ impl NormalizeService {
             .read(&content)
             .map_err(|e| format!("Failed to parse {} as {}: {}", input, source_lang, e))?;
 
+        // Writers emit lossy placeholders for IR they cannot express; say so.
+        let warnings: Vec<String> = normalize_surface_syntax::validate_for(&ir, writer)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        for w in &warnings {
+            eprintln!("warning: {}", w);
+        }
+
         let code = writer.write(&ir);
 
         if let Some(ref path) = output {
-            std::fs::write(path, &code).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            crate::output::sink::write_atomic(path, code.as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", path, e))?;
             tracing::info!("translated {} -> {} ({})", input, path, target_lang);
             Ok(TranslateReport {
                 code,
@@ -781,6 +821,7 @@ impl NormalizeService {
                 target_language: target_lang.to_string(),
                 input_path: input,
                 output_path: Some(path.clone()),
+                warnings,
             })
         } else {
             Ok(TranslateReport {
@@ -789,6 +830,7 @@ impl NormalizeService {
                 target_language: target_lang.to_string(),
                 input_path: input,
                 output_path: None,
+                warnings,
             })
         }
     }
@@ -811,7 +853,7 @@ impl NormalizeService {
         &self.guide
     }
 
-    /// Generate code from an API spec. Use to scaffold clients or types from OpenAPI definitions.
+    /// Generate code from an API spec. Use to scaffold clients, server stubs or types from OpenAPI definitions.
     #[server(group = "utilities")]
     pub fn generate(&self) -> &generate::GenerateService {
         &self.generate
@@ -861,9 +903,8 @@ impl NormalizeService {
         self.resolve_format(&root_path);
 
         let config = NormalizeConfig::load(&root_path);
-        let languages = commands::aliases::detect_project_languages(&root_path);
-        let lang_refs: Vec<&str> = languages.iter().map(String::as_str).collect();
-        let resolved = crate::filter::list_aliases(&config.aliases, &lang_refs);
+        let languages = crate::filter::detect_languages(&root_path);
+        let resolved = crate::filter::list_aliases(&config.aliases, &languages);
 
         let syntax_filter = syntax.as_deref();
 
@@ -876,12 +917,13 @@ impl NormalizeService {
                 status: a.status.to_string(),
                 value: a.value.as_strings(),
                 description: a.description,
+                scope: a.scope,
             })
             .collect();
 
         Ok(AliasListReport {
             aliases,
-            detected_languages: languages,
+            detected_languages: languages.into_iter().map(String::from).collect(),
         })
     }
 
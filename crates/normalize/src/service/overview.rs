@@ -3,7 +3,8 @@
 //! Thin main-crate composition verb over the analyze dashboards. `overview`
 //! aggregates health/quality signals (formerly `analyze health`), `overview
 //! --full` runs all passes (formerly `analyze all`), `overview summary` renders
-//! the single-page codebase overview (formerly `analyze summary`), and
+//! the single-page codebase overview (formerly `analyze summary`), `overview
+//! score` rolls everything into one weighted, baselined number for CI, and
 //! `overview cross-repo-health` ranks repositories by composite tech-debt.
 //!
 //! These are cross-cutting compositions with no owning compute crate, so per
@@ -12,6 +13,7 @@
 
 use crate::commands::analyze::cross_repo_health::CrossRepoHealthReport;
 use crate::commands::analyze::report::AnalyzeReport;
+use crate::commands::analyze::score::ProjectScoreReport;
 use crate::commands::analyze::summary::SummaryReport;
 use crate::output::OutputFormatter;
 use server_less::cli;
@@ -35,6 +37,7 @@ impl From<String> for OverviewError {
 /// Overview sub-service (health dashboard, summary, cross-repo health).
 pub struct OverviewService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
 }
@@ -43,6 +46,7 @@ impl OverviewService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -65,12 +69,13 @@ impl OverviewService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     /// Generic display bridge: routes to `format_pretty()` or `format_text()` based on pretty mode.
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
@@ -82,15 +87,12 @@ impl OverviewService {
         subcommand: &str,
         cli_exclude: &[String],
         only: &[String],
+        predicates: &[crate::filter::FilterPredicate],
     ) -> Option<crate::filter::Filter> {
         let config = crate::config::NormalizeConfig::load(root);
         let mut excludes = config.analyze.excludes_for(subcommand);
         excludes.extend(cli_exclude.iter().cloned());
-        if excludes.is_empty() && only.is_empty() {
-            None
-        } else {
-            crate::commands::build_filter(root, &excludes, only)
-        }
+        crate::commands::build_filter_with_predicates(root, &excludes, only, predicates)
     }
 }
 
@@ -132,6 +134,10 @@ impl OverviewService {
         >,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(
             short = 'l',
             help = "Maximum number of large files to include in output (0 = no limit, default 10)"
@@ -149,7 +155,12 @@ impl OverviewService {
         }
         self.resolve_format(&root_path);
         let subcommand = if full { "all" } else { "health" };
-        let filter = Self::build_filter(&root_path, subcommand, &exclude, &only);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )
+        .map_err(OverviewError::Message)?;
+        let filter = Self::build_filter(&root_path, subcommand, &exclude, &only, &predicates);
         let mut report = crate::commands::analyze::report::analyze(
             target.as_deref(),
             &root_path,
@@ -197,6 +208,40 @@ impl OverviewService {
         Ok(crate::commands::analyze::summary::analyze_summary(&root_path, effective_limit).await)
     }
 
+    /// Weighted project health score with a per-category breakdown, tracked against a baseline.
+    ///
+    /// Categories: rule diagnostics, import cycles, churn hotspots, duplication, file sizes,
+    /// complexity, and test ratio. Categories whose inputs are missing (no facts index, no
+    /// git history) show as n/a and are left out of the total. With a baseline saved in
+    /// `.normalize/health.json`, each category shows its change; `--check` exits non-zero
+    /// when the total score dropped.
+    ///
+    /// Examples:
+    ///   normalize overview score                 # score with per-category breakdown
+    ///   normalize overview score --save          # record the current score as the baseline
+    ///   normalize overview score --check         # CI: fail if the score regressed
+    #[cli(display_with = "display_output")]
+    pub async fn score(
+        &self,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(help = "Save this run as the baseline (.normalize/health.json)")] save: bool,
+        #[param(help = "Fail if the score dropped below the baseline")] check: bool,
+    ) -> Result<ProjectScoreReport, OverviewError> {
+        let root_path = Self::root_path(root)?;
+        self.resolve_format(&root_path);
+        let report =
+            crate::commands::analyze::score::analyze_project_score(&root_path, save).await?;
+        if check && report.regressed() {
+            return Err(OverviewError::Message(format!(
+                "{}\n\nHealth score regressed vs baseline",
+                self.display_output(&report)
+            )));
+        }
+        Ok(report)
+    }
+
     /// Rank repositories by composite tech-debt score (churn × complexity × coupling).
     ///
     /// Discovers git repos under `repos_dir` and computes a health score for each by
@@ -1,10 +1,12 @@
 //! Package management service for server-less CLI.
 
-use crate::commands::package::{
-    find_dependency_paths, print_audit_human, print_human, print_tree, show_outdated_data,
-};
+use crate::commands::package::{print_audit_human, print_human, print_tree, show_outdated_data};
 use crate::output::OutputFormatter;
-use normalize_ecosystems::{Dependency, DependencyTree, PackageInfo, Vulnerability};
+use normalize_ecosystems::{
+    BundleExport, BundleImport, Dependency, DependencyGraph, DependencyTree, DuplicateVersions,
+    LicenseAudit, LicenseVerdict, LockfileAudit, LockfileDiff, PackageInfo, PathEntry, SbomFormat,
+    VersionChange, Vulnerability, VulnerabilitySeverity,
+};
 use server_less::cli;
 use std::path::Path;
 
@@ -32,12 +34,28 @@ pub struct PackageInfoReport {
     pub ecosystem: String,
     /// Package metadata returned by the registry.
     pub info: PackageInfo,
+    /// OSV.dev advisories affecting the resolved version.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub advisories: Vec<Vulnerability>,
 }
 
 impl OutputFormatter for PackageInfoReport {
     fn format_text(&self) -> String {
         let mut out = String::new();
         out.push_str(&print_human(&self.info, &self.ecosystem));
+        if !self.advisories.is_empty() {
+            out.push_str(&format!("\n\nadvisories ({}):", self.advisories.len()));
+            for vuln in &self.advisories {
+                out.push_str(&format!(
+                    "\n  [{}] {}",
+                    vuln.severity.as_str().to_uppercase(),
+                    vuln.title
+                ));
+                if let Some(fixed) = &vuln.fixed_in {
+                    out.push_str(&format!(" (fixed in {})", fixed));
+                }
+            }
+        }
         out
     }
 }
@@ -97,15 +115,6 @@ impl OutputFormatter for PackageTreeReport {
     }
 }
 
-/// One entry in the dependency path for `normalize package why`.
-#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
-pub struct DependencyPathEntry {
-    /// Package name.
-    pub name: String,
-    /// Package version (may be empty if not known).
-    pub version: String,
-}
-
 /// Report for `normalize package why`: all paths from roots to the queried package.
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
 pub struct PackageWhyReport {
@@ -113,8 +122,14 @@ pub struct PackageWhyReport {
     pub package: String,
     /// The ecosystem searched (e.g. "cargo", "npm").
     pub ecosystem: String,
+    /// Direct dependencies that pull the package in.
+    pub direct: Vec<String>,
     /// All dependency paths from root packages to the queried package.
-    pub paths: Vec<Vec<DependencyPathEntry>>,
+    pub paths: Vec<Vec<PathEntry>>,
+    /// Inverted tree rendering. Stored for text formatting; not serialized.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub inverted: String,
 }
 
 impl OutputFormatter for PackageWhyReport {
@@ -122,27 +137,13 @@ impl OutputFormatter for PackageWhyReport {
         if self.paths.is_empty() {
             return format!("Package '{}' not found in dependency tree", self.package);
         }
-
-        let mut out = format!(
-            "'{}' is required by {} path(s):\n",
+        format!(
+            "'{}' is required by {} path(s) via {}:\n\n{}",
             self.package,
-            self.paths.len()
-        );
-        for (i, path) in self.paths.iter().enumerate() {
-            if i > 0 {
-                out.push('\n');
-            }
-            out.push('\n');
-            for (j, entry) in path.iter().enumerate() {
-                let indent = "  ".repeat(j);
-                if entry.version.is_empty() {
-                    out.push_str(&format!("{}{}\n", indent, entry.name));
-                } else {
-                    out.push_str(&format!("{}{} v{}\n", indent, entry.name, entry.version));
-                }
-            }
-        }
-        out.trim_end().to_string()
+            self.paths.len(),
+            self.direct.join(", "),
+            self.inverted
+        )
     }
 }
 
@@ -212,6 +213,301 @@ impl OutputFormatter for PackageAuditReport {
     }
 }
 
+/// Report for `normalize package security`: OSV.dev advisories for every
+/// package pinned by a lockfile.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct SecurityReport {
+    /// Path of the lockfile that was checked.
+    pub lockfile: String,
+    /// Affected packages, lookup counts and failures.
+    #[serde(flatten)]
+    pub audit: LockfileAudit,
+}
+
+impl OutputFormatter for SecurityReport {
+    fn format_text(&self) -> String {
+        let count = |severity: VulnerabilitySeverity| {
+            self.audit
+                .vulnerabilities()
+                .filter(|v| v.severity == severity)
+                .count()
+        };
+        let mut out = format!(
+            "Checked {} packages in {} ({}): {} affected, {} advisories - {} critical, {} high, {} medium, {} low",
+            self.audit.checked,
+            self.lockfile,
+            self.audit.ecosystem,
+            self.audit.affected.len(),
+            self.audit.vulnerabilities().count(),
+            count(VulnerabilitySeverity::Critical),
+            count(VulnerabilitySeverity::High),
+            count(VulnerabilitySeverity::Medium),
+            count(VulnerabilitySeverity::Low),
+        );
+        for pkg in &self.audit.affected {
+            out.push_str(&format!("\n\n{} {}", pkg.package, pkg.version));
+            for vuln in &pkg.advisories {
+                out.push_str(&format!(
+                    "\n  [{}] {}",
+                    vuln.severity.as_str().to_uppercase(),
+                    vuln.title
+                ));
+                if let Some(cve) = &vuln.cve {
+                    out.push_str(&format!("\n    CVE: {}", cve));
+                }
+                if let Some(url) = &vuln.url {
+                    out.push_str(&format!("\n    URL: {}", url));
+                }
+                if let Some(fixed) = &vuln.fixed_in {
+                    out.push_str(&format!("\n    Fixed in: {}", fixed));
+                }
+            }
+        }
+        if !self.audit.errors.is_empty() {
+            out.push_str(&format!("\n\n{} lookups failed:", self.audit.errors.len()));
+            for err in &self.audit.errors {
+                out.push_str(&format!("\n  {}", err));
+            }
+        }
+        out
+    }
+}
+
+/// Report for `normalize package export`: a registry bundle written for offline use.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct PackageExportReport {
+    /// Path of the bundle that was written.
+    pub path: String,
+    /// Manifest, entry count and failed lookups.
+    #[serde(flatten)]
+    pub export: BundleExport,
+}
+
+impl OutputFormatter for PackageExportReport {
+    fn format_text(&self) -> String {
+        let mut out = format!(
+            "Exported {} packages ({} cache entries, {}) to {}",
+            self.export.manifest.packages.len(),
+            self.export.entries,
+            self.export.manifest.ecosystem,
+            self.path
+        );
+        if !self.export.failed.is_empty() {
+            out.push_str(&format!(
+                "\n\n{} packages could not be fetched:",
+                self.export.failed.len()
+            ));
+            for failed in &self.export.failed {
+                out.push_str(&format!("\n  {}", failed));
+            }
+        }
+        out
+    }
+}
+
+/// Report for `normalize package import`: a registry bundle installed into the cache.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct PackageImportReport {
+    /// Name, location, manifest and entry count of the imported bundle.
+    #[serde(flatten)]
+    pub import: BundleImport,
+}
+
+impl OutputFormatter for PackageImportReport {
+    fn format_text(&self) -> String {
+        format!(
+            "Imported bundle '{}' ({} packages, {} cache entries, {}) into {}",
+            self.import.name,
+            self.import.manifest.packages.len(),
+            self.import.entries,
+            self.import.manifest.ecosystem,
+            self.import.path
+        )
+    }
+}
+
+/// Report for `normalize package licenses`: dependencies whose license the
+/// `[licenses]` policy does not plainly allow.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct PackageLicensesReport {
+    /// Whether a `[licenses]` policy is configured. Without one, only
+    /// missing or unparseable licenses are reported.
+    pub policy_configured: bool,
+    /// Packages checked and findings, worst first.
+    #[serde(flatten)]
+    pub audit: LicenseAudit,
+}
+
+impl OutputFormatter for PackageLicensesReport {
+    fn format_text(&self) -> String {
+        let audit = &self.audit;
+        if audit.findings.is_empty() {
+            return format!(
+                "All {} packages have allowed licenses ({}).",
+                audit.checked, audit.ecosystem
+            );
+        }
+        let count = |verdict: LicenseVerdict| {
+            audit
+                .findings
+                .iter()
+                .filter(|f| f.verdict == verdict)
+                .count()
+        };
+        let mut out = format!(
+            "Checked {} packages ({}): {} denied, {} not allowed, {} unknown, {} needs review",
+            audit.checked,
+            audit.ecosystem,
+            count(LicenseVerdict::Denied),
+            count(LicenseVerdict::NotAllowed),
+            count(LicenseVerdict::Unknown),
+            count(LicenseVerdict::NeedsReview),
+        );
+        if !self.policy_configured {
+            out.push_str(
+                "\nnote: no [licenses] policy configured; only unknown licenses are reported",
+            );
+        }
+        for finding in &audit.findings {
+            out.push_str(&format!(
+                "\n\n[{}] {} {} - {}",
+                finding.verdict.as_str().to_uppercase(),
+                finding.package,
+                finding.version.as_deref().unwrap_or("(unknown version)"),
+                finding.license.as_deref().unwrap_or("license unknown"),
+            ));
+            if !finding.path.is_empty() {
+                out.push_str(&format!("\n  via {}", finding.path.join(" -> ")));
+            }
+        }
+        out
+    }
+}
+
+/// Report for `normalize package diff`: packages changed between two lockfiles.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct PackageDiffReport {
+    /// Path of the old lockfile.
+    pub old: String,
+    /// Path of the new lockfile.
+    pub new: String,
+    /// Added, removed, upgraded and downgraded packages.
+    #[serde(flatten)]
+    pub diff: LockfileDiff,
+}
+
+impl OutputFormatter for PackageDiffReport {
+    fn format_text(&self) -> String {
+        if self.diff.is_empty() {
+            return format!(
+                "No dependency changes between {} and {}",
+                self.old, self.new
+            );
+        }
+        let mut out = String::new();
+        let mut section = |title: &str, lines: Vec<String>| {
+            if lines.is_empty() {
+                return;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("{} ({}):\n", title, lines.len()));
+            for line in lines {
+                out.push_str(&format!("  {}\n", line));
+            }
+        };
+        let change = |c: &VersionChange| format!("{} {} -> {}", c.name, c.from, c.to);
+        section(
+            "Added",
+            self.diff
+                .added
+                .iter()
+                .map(|p| format!("{} {}", p.name, p.version))
+                .collect(),
+        );
+        section(
+            "Removed",
+            self.diff
+                .removed
+                .iter()
+                .map(|p| format!("{} {}", p.name, p.version))
+                .collect(),
+        );
+        section("Upgraded", self.diff.upgraded.iter().map(change).collect());
+        section(
+            "Downgraded",
+            self.diff.downgraded.iter().map(change).collect(),
+        );
+        out.trim_end().to_string()
+    }
+}
+
+/// Report for `normalize package sbom`: a CycloneDX or SPDX JSON document.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(transparent)]
+pub struct PackageSbomReport {
+    /// The SBOM document.
+    pub document: serde_json::Value,
+}
+
+impl OutputFormatter for PackageSbomReport {
+    fn format_text(&self) -> String {
+        serde_json::to_string_pretty(&self.document).unwrap_or_default()
+    }
+}
+
+/// Report for `normalize package graph`: dependency graph merged from the
+/// lockfile, locally-installed packages and (optionally) the registry.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct PackageGraphReport {
+    /// Project name, nodes and edges.
+    #[serde(flatten)]
+    pub graph: DependencyGraph,
+    /// Packages present at more than one version.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateVersions>,
+    /// Graphviz rendering used when `--dot` is given. Not serialized.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub dot: Option<String>,
+}
+
+impl OutputFormatter for PackageGraphReport {
+    fn format_text(&self) -> String {
+        if let Some(dot) = &self.dot {
+            return dot.trim_end().to_string();
+        }
+        let graph = &self.graph;
+        let installed = graph.nodes.iter().filter(|n| n.path.is_some()).count();
+        let direct = graph.nodes.iter().filter(|n| n.direct).count();
+        let mut out = format!(
+            "{} ({}): {} dependencies, {} direct, {} installed locally\n",
+            graph.root,
+            graph.ecosystem,
+            graph.nodes.len(),
+            direct,
+            installed
+        );
+        for node in &graph.nodes {
+            let version = node.version.as_deref().unwrap_or("unknown version");
+            let via = match node.depth {
+                0 => "unreachable".to_string(),
+                1 => "direct".to_string(),
+                d => format!("depth {}", d),
+            };
+            out.push_str(&format!("  {} {} ({})\n", node.name, version, via));
+        }
+        if !self.duplicates.is_empty() {
+            out.push_str("\nduplicate versions:\n");
+            for dup in &self.duplicates {
+                out.push_str(&format!("  {} {}\n", dup.name, dup.versions.join(", ")));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
 // ── Service impl ──────────────────────────────────────────────────────────────
 
 #[cli(
@@ -224,6 +520,7 @@ impl PackageService {
     /// Examples:
     ///   normalize package info express                    # look up a package by name
     ///   normalize package info serde@1.0 -e cargo         # query a specific version and ecosystem
+    ///   normalize package info lodash@4.17.20 --osv       # include OSV.dev advisories
     #[cli(display_with = "display_output")]
     pub fn info(
         &self,
@@ -234,13 +531,15 @@ impl PackageService {
         #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
             String,
         >,
+        #[param(help = "Also look up OSV.dev advisories for the resolved version")] osv: bool,
     ) -> Result<PackageInfoReport, String> {
         let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
-        let (eco, info) =
-            crate::commands::package::get_info(&package, ecosystem.as_deref(), root_path)?;
+        let (eco, info, advisories) =
+            crate::commands::package::get_info(&package, ecosystem.as_deref(), root_path, osv)?;
         Ok(PackageInfoReport {
             ecosystem: eco,
             info,
+            advisories,
         })
     }
 
@@ -312,23 +611,39 @@ impl PackageService {
         >,
     ) -> Result<PackageWhyReport, String> {
         let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
-        let (eco, tree) = crate::commands::package::get_tree(ecosystem.as_deref(), root_path)?;
-        let raw_paths = find_dependency_paths(&tree, &package);
-        let paths = raw_paths
-            .into_iter()
-            .map(|path| {
-                path.into_iter()
-                    .map(|(name, version)| DependencyPathEntry { name, version })
-                    .collect()
-            })
-            .collect();
+        let (eco, why) =
+            crate::commands::package::get_why(&package, ecosystem.as_deref(), root_path)?;
         Ok(PackageWhyReport {
             package,
             ecosystem: eco,
-            paths,
+            direct: why
+                .direct_dependents()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            inverted: why.to_inverted_tree(),
+            paths: why.paths,
         })
     }
 
+    /// Compare two lockfiles
+    ///
+    /// Supports Cargo.lock, package-lock.json, pnpm-lock.yaml, poetry.lock,
+    /// uv.lock and go.sum; the format is detected from the file name.
+    ///
+    /// Examples:
+    ///   git show main:Cargo.lock > /tmp/Cargo.lock
+    ///   normalize package diff /tmp/Cargo.lock Cargo.lock  # what changed since main
+    #[cli(display_with = "display_output")]
+    pub fn diff(
+        &self,
+        #[param(positional, help = "Old lockfile")] old: String,
+        #[param(positional, help = "New lockfile")] new: String,
+    ) -> Result<PackageDiffReport, String> {
+        let diff = crate::commands::package::get_lockfile_diff(Path::new(&old), Path::new(&new))?;
+        Ok(PackageDiffReport { old, new, diff })
+    }
+
     /// Show outdated packages (installed vs latest)
     ///
     /// Examples:
@@ -366,6 +681,7 @@ impl PackageService {
     /// Examples:
     ///   normalize package audit                          # audit all ecosystems for vulnerabilities
     ///   normalize package audit -e cargo                  # audit only Cargo dependencies
+    ///   normalize package audit --osv                    # query OSV.dev for the dependency graph
     #[cli(display_with = "display_output")]
     pub fn audit(
         &self,
@@ -374,12 +690,194 @@ impl PackageService {
         #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
             String,
         >,
+        #[param(
+            help = "Check the merged dependency graph against OSV.dev instead of the ecosystem's audit tool"
+        )]
+        osv: bool,
     ) -> Result<PackageAuditReport, String> {
         let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
-        let (eco, vulns) = crate::commands::package::get_audit(ecosystem.as_deref(), root_path)?;
+        let (eco, vulns) =
+            crate::commands::package::get_audit(ecosystem.as_deref(), root_path, osv)?;
         Ok(PackageAuditReport {
             ecosystem: eco,
             vulnerabilities: vulns,
         })
     }
+
+    /// Check every package in a lockfile against OSV.dev advisories
+    ///
+    /// Covers the full transitive set at the exact versions the lockfile pins.
+    /// Supports Cargo.lock, package-lock.json, pnpm-lock.yaml, poetry.lock,
+    /// uv.lock and go.sum. Results are cached for six hours.
+    ///
+    /// Examples:
+    ///   normalize package security                       # lockfile of the detected ecosystem
+    ///   normalize package security poetry.lock           # a specific lockfile
+    ///   normalize package security --json                # machine-readable report
+    #[cli(display_with = "display_output")]
+    pub fn security(
+        &self,
+        #[param(
+            positional,
+            help = "Lockfile to check, relative to the root (defaults to the detected ecosystem's)"
+        )]
+        lockfile: Option<String>,
+        #[param(short = 'e', help = "Force specific ecosystem (cargo, npm, python)")]
+        ecosystem: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<SecurityReport, String> {
+        let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
+        let (lockfile, audit) = crate::commands::package::get_security(
+            lockfile.as_deref().map(Path::new),
+            ecosystem.as_deref(),
+            root_path,
+        )?;
+        Ok(SecurityReport { lockfile, audit })
+    }
+
+    /// Export registry data for all dependencies to an offline bundle
+    ///
+    /// Queries every locked package (or declared dependency, without a
+    /// lockfile) and its advisories, then packs the cached responses into a
+    /// .tar.gz. Import it with `normalize package import` on a machine without
+    /// network access.
+    ///
+    /// Examples:
+    ///   normalize package export deps.tar.gz             # bundle the detected ecosystem
+    ///   normalize package export npm.tar.gz -e npm       # bundle npm dependencies
+    #[cli(display_with = "display_output")]
+    pub fn export(
+        &self,
+        #[param(positional, help = "Bundle file to write (.tar.gz)")] output: String,
+        #[param(short = 'e', help = "Force specific ecosystem (cargo, npm, python)")]
+        ecosystem: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<PackageExportReport, String> {
+        let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
+        let export = crate::commands::package::get_bundle_export(
+            ecosystem.as_deref(),
+            root_path,
+            Path::new(&output),
+        )?;
+        Ok(PackageExportReport {
+            path: output,
+            export,
+        })
+    }
+
+    /// Import an offline registry bundle into the local cache
+    ///
+    /// Bundled entries answer package and advisory lookups whenever the live
+    /// cache has no fresh entry, so `normalize package info` works without
+    /// network access. Re-importing a bundle with the same file name replaces it.
+    ///
+    /// Examples:
+    ///   normalize package import deps.tar.gz             # install a bundle from `package export`
+    #[cli(display_with = "display_output")]
+    pub fn import(
+        &self,
+        #[param(positional, help = "Bundle file written by `normalize package export`")]
+        bundle: String,
+    ) -> Result<PackageImportReport, String> {
+        let import = crate::commands::package::get_bundle_import(Path::new(&bundle))?;
+        Ok(PackageImportReport { import })
+    }
+
+    /// Check dependency licenses against the project's license policy
+    ///
+    /// Parses each dependency's SPDX license expression and checks it against
+    /// the `[licenses]` section of .normalize/config.toml (allowed, denied and
+    /// needs_review lists). An OR expression passes if any branch is allowed;
+    /// an AND expression needs every part allowed. Each finding shows the
+    /// dependency path that pulls the package in.
+    ///
+    /// Examples:
+    ///   normalize package licenses                       # check the detected ecosystem
+    ///   normalize package licenses -e npm --json         # npm findings as JSON
+    #[cli(display_with = "display_output")]
+    pub fn licenses(
+        &self,
+        #[param(short = 'e', help = "Force specific ecosystem (cargo, npm, python)")]
+        ecosystem: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+    ) -> Result<PackageLicensesReport, String> {
+        let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
+        let (audit, policy_configured) =
+            crate::commands::package::get_licenses(ecosystem.as_deref(), root_path)?;
+        Ok(PackageLicensesReport {
+            policy_configured,
+            audit,
+        })
+    }
+
+    /// Show the dependency graph merged from lockfile, local installs and registry
+    ///
+    /// Combines the lockfile's pinned packages and tree with the packages found
+    /// on disk (node_modules, site-packages, cargo registry, ...) and their
+    /// manifests. With --registry, packages with no known dependencies are
+    /// completed from registry metadata. Packages locked at several versions
+    /// are listed as duplicates.
+    ///
+    /// Examples:
+    ///   normalize package graph                          # graph for the detected ecosystem
+    ///   normalize package graph -e npm --json            # npm graph as JSON
+    ///   normalize package graph --registry               # fill gaps from the registry
+    ///   normalize package graph --dot | dot -Tsvg > deps.svg
+    #[cli(display_with = "display_output")]
+    pub fn graph(
+        &self,
+        #[param(short = 'e', help = "Force specific ecosystem (cargo, npm, python)")]
+        ecosystem: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(help = "Render as Graphviz DOT")] dot: bool,
+        #[param(help = "Complete missing dependencies from registry metadata")] registry: bool,
+    ) -> Result<PackageGraphReport, String> {
+        let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
+        let graph = crate::commands::package::get_graph(ecosystem.as_deref(), root_path, registry)?;
+        Ok(PackageGraphReport {
+            duplicates: graph.duplicates(),
+            dot: dot.then(|| graph.to_dot()),
+            graph,
+        })
+    }
+
+    /// Generate a software bill of materials (SBOM)
+    ///
+    /// Lists every resolved dependency with its package URL and, unless
+    /// --no-registry is given, license and repository from the registry.
+    ///
+    /// Examples:
+    ///   normalize package sbom > sbom.cdx.json            # CycloneDX 1.5
+    ///   normalize package sbom --format spdx > sbom.spdx.json
+    ///   normalize package sbom --no-registry             # offline, no license data
+    #[cli(display_with = "display_output")]
+    pub fn sbom(
+        &self,
+        #[param(short = 'e', help = "Force specific ecosystem (cargo, npm, python)")]
+        ecosystem: Option<String>,
+        #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
+            String,
+        >,
+        #[param(short = 'f', help = "Output format: cyclonedx (default) or spdx")] format: Option<
+            String,
+        >,
+        #[param(help = "Skip registry lookups for license and repository metadata")]
+        no_registry: bool,
+    ) -> Result<PackageSbomReport, String> {
+        let root_path = root.as_deref().map(Path::new).unwrap_or(Path::new("."));
+        let format: SbomFormat = format.as_deref().unwrap_or("cyclonedx").parse()?;
+        let (_, sbom) =
+            crate::commands::package::get_sbom(ecosystem.as_deref(), root_path, !no_registry)?;
+        Ok(PackageSbomReport {
+            document: sbom.render(format),
+        })
+    }
 }
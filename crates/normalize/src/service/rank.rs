@@ -24,6 +24,7 @@ use std::path::PathBuf;
 /// Rank sub-service: ranked-list commands (ordered by a metric).
 pub struct RankService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
 }
@@ -32,6 +33,7 @@ impl RankService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -50,11 +52,12 @@ impl RankService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
@@ -120,12 +123,9 @@ impl RankService {
         root: &std::path::Path,
         exclude: &[String],
         only: &[String],
+        predicates: &[crate::filter::FilterPredicate],
     ) -> Option<crate::filter::Filter> {
-        if exclude.is_empty() && only.is_empty() {
-            None
-        } else {
-            crate::commands::build_filter(root, exclude, only)
-        }
+        crate::commands::build_filter_with_predicates(root, exclude, only, predicates)
     }
 
     /// Build a filter with merged excludes: config global + per-subcommand + CLI args.
@@ -135,10 +135,11 @@ impl RankService {
         subcommand: &str,
         cli_exclude: &[String],
         only: &[String],
+        predicates: &[crate::filter::FilterPredicate],
     ) -> Option<crate::filter::Filter> {
         let mut excludes = config.excludes_for(subcommand);
         excludes.extend(cli_exclude.iter().cloned());
-        Self::build_filter(root, &excludes, only)
+        Self::build_filter(root, &excludes, only, predicates)
     }
 }
 
@@ -192,6 +193,10 @@ impl RankService {
         limit: Option<usize>,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(help = "Show delta vs this git ref (branch, tag, commit, HEAD~N)")] diff: Option<
             String,
         >,
@@ -199,12 +204,17 @@ impl RankService {
         let root_path = Self::root_path(root)?;
         self.resolve_format(&root_path);
         let config = crate::config::NormalizeConfig::load(&root_path);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
         let filter = Self::build_filter_with_config(
             &root_path,
             &config.analyze,
             "complexity",
             &exclude,
             &only,
+            &predicates,
         );
         let effective_threshold = threshold.or_else(|| config.analyze.threshold());
         let effective_limit = match limit.unwrap_or(10) {
@@ -434,6 +444,10 @@ impl RankService {
         limit: Option<usize>,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(help = "Show delta vs this git ref (branch, tag, commit, HEAD~N)")] diff: Option<
             String,
         >,
@@ -441,8 +455,18 @@ impl RankService {
         let root_path = Self::root_path(root)?;
         self.resolve_format(&root_path);
         let config = crate::config::NormalizeConfig::load(&root_path);
-        let filter =
-            Self::build_filter_with_config(&root_path, &config.analyze, "length", &exclude, &only);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
+        let filter = Self::build_filter_with_config(
+            &root_path,
+            &config.analyze,
+            "length",
+            &exclude,
+            &only,
+            &predicates,
+        );
         let effective_limit = match limit.unwrap_or(10) {
             0 => usize::MAX,
             n => n,
@@ -518,16 +542,25 @@ impl RankService {
         limit: Option<usize>,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
     ) -> Result<TestGapsReport, String> {
         let root_path = Self::root_path(root)?;
         self.resolve_format(&root_path);
         let config = crate::config::NormalizeConfig::load(&root_path);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
         let filter = Self::build_filter_with_config(
             &root_path,
             &config.analyze,
             "test-gaps",
             &exclude,
             &only,
+            &predicates,
         );
         let allowlist = crate::commands::analyze::load_allow_file(&root_path, "test-gaps-allow");
         let effective_limit = match limit.unwrap_or(20) {
@@ -686,6 +719,10 @@ impl RankService {
         #[param(help = "Number of top clusters to show (default: 10)")] clusters: Option<usize>,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(help = "Show delta vs this git ref (branch, tag, commit, HEAD~N)")] diff: Option<
             String,
         >,
@@ -697,12 +734,17 @@ impl RankService {
             n => n,
         };
         let config = crate::config::NormalizeConfig::load(&root_path);
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
         let filter = Self::build_filter_with_config(
             &root_path,
             &config.analyze,
             "uniqueness",
             &exclude,
             &only,
+            &predicates,
         );
         let sim = similarity.unwrap_or(0.80);
         let min = min_lines.unwrap_or(5);
@@ -208,8 +208,8 @@ pub(crate) async fn do_rename_report(
 
     // Find definition location
     let loc = editor
-        .find_symbol(&def_abs_path, &def_content, old_name, false)
-        .ok_or_else(|| format!("Symbol '{}' not found in {}", old_name, def_rel_path))?;
+        .find_unique_symbol(&def_abs_path, &def_content, old_name, false)
+        .map_err(|e| format!("{} in {}", e, def_rel_path))?;
 
     // Try to open index for cross-file awareness (graceful degradation)
     let (callers, importers) = async {
@@ -12,14 +12,19 @@ impl ServeService {
     ///
     /// Examples:
     ///   normalize serve mcp                  # start MCP server on stdio
-    pub fn mcp(
+    ///   normalize serve mcp --full           # also expose view/edit/rules/sessions as typed tools
+    pub async fn mcp(
         &self,
         #[param(short = 'r', help = "Root directory (defaults to current directory)")] root: Option<
             String,
         >,
+        #[param(
+            help = "Expose view, symbols, edit, diagnostics and sessions as separate typed tools"
+        )]
+        full: bool,
     ) -> Result<(), String> {
         let root_path = root.as_deref().map(PathBuf::from);
-        let exit = crate::serve::mcp::serve_mcp(root_path.as_deref());
+        let exit = crate::serve::mcp::serve_mcp(root_path.as_deref(), full).await;
         if exit != 0 {
             Err(format!("MCP server exited with code {}", exit))
         } else {
@@ -12,6 +12,7 @@ use std::path::PathBuf;
 /// Trend sub-service: time-series commands tracking health metrics over git history.
 pub struct TrendService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
 }
@@ -20,6 +21,7 @@ impl TrendService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
         }
@@ -38,12 +40,13 @@ impl TrendService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     /// Generic display bridge: routes to `format_pretty()` or `format_text()` based on pretty mode.
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
@@ -34,6 +34,7 @@ impl std::fmt::Display for TraceReport {
 /// View sub-service: directory/file/symbol navigation and graph navigation.
 pub struct ViewService {
     pretty: Cell<bool>,
+    pager: Cell<bool>,
     pretty_raw: Cell<bool>,
     compact_raw: Cell<bool>,
     /// Text prefix to prepend to the default view output (used for --dir-context).
@@ -46,6 +47,7 @@ impl ViewService {
     pub fn new(pretty: &Cell<bool>) -> Self {
         Self {
             pretty: Cell::new(pretty.get()),
+            pager: Cell::new(false),
             pretty_raw: Cell::new(false),
             compact_raw: Cell::new(false),
             view_prefix: Cell::new(String::new()),
@@ -66,11 +68,12 @@ impl ViewService {
         let is_pretty =
             !self.compact_raw.get() && (self.pretty_raw.get() || config.pretty.enabled());
         self.pretty.set(is_pretty);
+        self.pager.set(config.pretty.pager());
     }
 
     fn display_output<T: OutputFormatter>(&self, r: &T) -> String {
         if self.pretty.get() {
-            r.format_pretty()
+            r.format_pretty_paged(self.pager.get())
         } else {
             r.format_text()
         }
@@ -147,8 +150,10 @@ impl ViewService {
     ///   normalize view file.rs:42                # jump to line 42
     ///   normalize view src/ --depth 2            # deeper expansion
     ///   normalize view src/main.rs --full        # full source code
+    ///   normalize view src/main.rs --full-signatures  # complete multi-line signatures
     ///   normalize view src/main.rs --deps        # show imports/exports
     ///   normalize view src/main.rs --context     # skeleton + imports combined
+    ///   normalize view src/main.rs --budget 500  # fit into ~500 tokens
     ///   normalize view referenced-by MyFn        # show callers of MyFn
     ///   normalize view references MyFn           # show what MyFn calls
     ///   normalize view history src/main.rs/MyFn  # git history for a symbol
@@ -181,6 +186,8 @@ impl ViewService {
         #[param(help = "Focus view on module")] focus: Option<String>,
         #[param(help = "Inline signatures of specific imported symbols")] resolve_imports: bool,
         #[param(help = "Show full source code")] full: bool,
+        #[param(help = "Show multi-line signatures in full (parameter lists, return types)")]
+        full_signatures: bool,
         #[param(help = "Show full docstrings")] docs: bool,
         #[param(help = "Hide all docstrings")] no_docs: bool,
         #[param(help = "Hide parent/ancestor context")] no_parent: bool,
@@ -191,7 +198,15 @@ impl ViewService {
         dir_context: Option<i32>,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(short = 'i', help = "Case-insensitive symbol matching")] case_insensitive: bool,
+        #[param(
+            help = "Fit output into roughly N tokens, eliding private symbols, bodies, then docstrings"
+        )]
+        budget: Option<usize>,
     ) -> Result<ViewReport, String> {
         let root_path = root
             .map(PathBuf::from)
@@ -274,6 +289,10 @@ impl ViewService {
             }
         }
 
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
         let mut report = crate::commands::view::build_view_service(
             target.as_deref(),
             &root_path,
@@ -287,16 +306,22 @@ impl ViewService {
             focus.as_deref(),
             resolve_imports,
             full,
+            full_signatures,
             docstring_mode,
             context,
             !no_parent,
             &exclude,
             &only,
+            &predicates,
             case_insensitive,
             &[],
         )
         .await?;
 
+        if let Some(budget) = budget {
+            crate::commands::view::budget::fit_to_budget(&mut report, budget, &root_path);
+        }
+
         // Populate dir_context for JSON consumers (--json/--jq).
         if let Some(content) = self.dir_context_content.borrow_mut().take() {
             report.dir_context = Some(content);
@@ -416,12 +441,18 @@ impl ViewService {
         #[param(help = "Show only type definitions")] types_only: bool,
         #[param(help = "Include test functions and test modules")] tests: bool,
         #[param(help = "Disable smart display (no collapsing single-child dirs)")] raw: bool,
+        #[param(help = "Show multi-line signatures in full (parameter lists, return types)")]
+        full_signatures: bool,
         #[param(help = "Show full docstrings")] docs: bool,
         #[param(help = "Hide all docstrings")] no_docs: bool,
         #[param(help = "Hide parent/ancestor context")] no_parent: bool,
         #[param(help = "Context view: skeleton + imports combined")] context: bool,
         #[param(help = "Exclude paths matching pattern")] exclude: Vec<String>,
         #[param(help = "Include only paths matching pattern")] only: Vec<String>,
+        #[param(help = "Skip files larger than this size (e.g. 500KB, 1MB)")]
+        exclude_larger_than: Option<String>,
+        #[param(help = "Only include files modified within this window (e.g. 3d, 2w)")]
+        only_modified_since: Option<String>,
         #[param(short = 'i', help = "Case-insensitive symbol matching")] case_insensitive: bool,
     ) -> Result<ViewListReport, String> {
         let root_path = root
@@ -442,6 +473,10 @@ impl ViewService {
             crate::tree::DocstringDisplay::Summary
         };
 
+        let predicates = crate::commands::parse_predicates(
+            exclude_larger_than.as_deref(),
+            only_modified_since.as_deref(),
+        )?;
         crate::commands::view::build_view_list_service(
             target.as_deref(),
             &root_path,
@@ -451,11 +486,13 @@ impl ViewService {
             tests,
             raw,
             false, // show_deps
+            full_signatures,
             docstring_mode,
             context,
             !no_parent,
             &exclude,
             &only,
+            &predicates,
             case_insensitive,
             &[],
         )
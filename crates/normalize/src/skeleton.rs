@@ -5,7 +5,7 @@
 
 use crate::tree::{ViewNode, ViewNodeKind};
 use normalize_facts::{ExtractResult, Extractor};
-use normalize_languages::Symbol;
+use normalize_languages::{Symbol, Visibility};
 use std::path::Path;
 
 /// Extension trait for converting Symbol to ViewNode
@@ -37,6 +37,7 @@ impl SymbolExt for Symbol {
             docstring: self.docstring.clone(),
             line_range: Some((self.start_line, self.end_line)),
             grammar: grammar.map(String::from),
+            private: self.visibility != Visibility::Public,
         }
     }
 }
@@ -64,9 +65,23 @@ impl ExtractResultExt for ExtractResult {
     }
 }
 
+/// Options controlling how much detail a skeleton carries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SkeletonOptions {
+    /// Rebuild each signature from source so parameter lists and return types
+    /// that span several lines are complete (the extracted signature is
+    /// usually just the first line of the declaration).
+    pub full_signatures: bool,
+    /// Reduce docstrings to their first non-empty line.
+    pub doc_summary: bool,
+    /// Maximum symbol nesting depth to keep (1 = top-level only; `None` = all).
+    pub max_depth: Option<usize>,
+}
+
 /// Skeleton extractor using shared Extractor from extract.rs
 pub struct SkeletonExtractor {
     extractor: Extractor,
+    options: SkeletonOptions,
 }
 
 impl Default for SkeletonExtractor {
@@ -77,15 +92,20 @@ impl Default for SkeletonExtractor {
 
 impl SkeletonExtractor {
     pub fn new() -> Self {
+        Self::with_options(SkeletonOptions::default())
+    }
+
+    pub fn with_options(options: SkeletonOptions) -> Self {
         Self {
             extractor: Extractor::new(),
+            options,
         }
     }
 
     pub fn extract(&self, path: &Path, content: &str) -> ExtractResult {
         let result = self.extractor.extract(path, content);
         ExtractResult {
-            symbols: result.symbols,
+            symbols: self.apply_options(result.symbols, content),
             file_path: result.file_path,
         }
     }
@@ -101,7 +121,7 @@ impl SkeletonExtractor {
             .extractor
             .extract_with_resolver(path, content, resolver);
         ExtractResult {
-            symbols: result.symbols,
+            symbols: self.apply_options(result.symbols, content),
             file_path: result.file_path,
         }
     }
@@ -120,6 +140,126 @@ impl SkeletonExtractor {
             file_path: result.file_path,
         })
     }
+
+    fn apply_options(&self, mut symbols: Vec<Symbol>, content: &str) -> Vec<Symbol> {
+        let options = self.options;
+        if !options.full_signatures && !options.doc_summary && options.max_depth.is_none() {
+            return symbols;
+        }
+        let lines: Vec<&str> = content.lines().collect();
+        apply_options(&mut symbols, &options, &lines, 1);
+        symbols
+    }
+}
+
+fn apply_options(symbols: &mut [Symbol], options: &SkeletonOptions, lines: &[&str], depth: usize) {
+    for sym in symbols {
+        if options.full_signatures
+            && !sym.signature.is_empty()
+            && let Some(sig) = full_signature(sym, lines)
+        {
+            sym.signature = sig;
+        }
+        if options.doc_summary {
+            sym.docstring = sym.docstring.as_deref().and_then(|d| {
+                d.lines()
+                    .map(str::trim)
+                    .find(|l| !l.is_empty())
+                    .map(String::from)
+            });
+        }
+        if options.max_depth.is_some_and(|max| depth >= max) {
+            sym.children.clear();
+        } else {
+            apply_options(&mut sym.children, options, lines, depth + 1);
+        }
+    }
+}
+
+/// Lines that continue a declaration header after its parameter list closes.
+const CONTINUATIONS: &[&str] = &[
+    "->",
+    "where",
+    ":",
+    "=>",
+    "throws",
+    "extends",
+    "implements",
+    "|",
+    "+",
+    "&",
+];
+
+/// Rebuild a symbol's declaration header from source: from the line naming
+/// the symbol up to the body (`{`, `;`, or a trailing `:`), joined onto one
+/// line. Returns `None` if the header can't be located.
+fn full_signature(sym: &Symbol, lines: &[&str]) -> Option<String> {
+    let start = sym.start_line.checked_sub(1)?;
+    let end = sym.end_line.min(lines.len());
+    // Skip attributes, decorators and doc comments that may precede the
+    // declaration within the symbol's range.
+    let header = (start..end.min(start + 10)).find(|&i| {
+        let t = lines[i].trim_start();
+        !t.starts_with(['#', '@', '/', '*'])
+            && !t.starts_with("--")
+            && lines[i].contains(sym.name.as_str())
+    })?;
+
+    let mut parts: Vec<&str> = Vec::new();
+    let mut depth = 0i32;
+    for i in header..end.min(header + 30) {
+        let line = lines[i].trim();
+        let mut cut = None;
+        for (j, c) in line.char_indices() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                '{' | ';' if depth <= 0 => {
+                    cut = Some(j);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        if let Some(j) = cut {
+            parts.push(&line[..j]);
+            break;
+        }
+        parts.push(line);
+        if depth > 0 {
+            continue;
+        }
+        let ends_open = line.ends_with([',', '(', '<', '='])
+            || line.ends_with("->")
+            || CONTINUATIONS
+                .iter()
+                .any(|c| line == *c || line.ends_with(&format!(" {}", c)));
+        let next_continues = lines.get(i + 1).is_some_and(|next| {
+            let next = next.trim_start();
+            CONTINUATIONS.iter().any(|c| next.starts_with(c)) && !next.starts_with("::")
+        });
+        if line.ends_with(':') || !(ends_open || next_continues) {
+            break;
+        }
+    }
+
+    let joined = parts
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(", )", ")")
+        .replace(",)", ")")
+        .replace(" )", ")");
+    let joined = joined.trim_end().trim_end_matches(',');
+    let joined = joined
+        .strip_suffix(':')
+        .filter(|j| !j.ends_with(':'))
+        .unwrap_or(joined)
+        .trim_end();
+    (!joined.is_empty()).then(|| joined.to_string())
 }
 
 #[cfg(test)]
@@ -671,4 +811,51 @@ class Bar:
         assert_eq!(bar.children[0].name, "method");
         assert_eq!(bar.children[0].kind, SymbolKind::Method);
     }
+
+    #[test]
+    fn test_skeleton_options() {
+        if !grammars_available("rust", "fn x() {}") {
+            return;
+        }
+        let extractor = SkeletonExtractor::with_options(SkeletonOptions {
+            full_signatures: true,
+            doc_summary: true,
+            max_depth: Some(1),
+        });
+        let content = r#"
+/// Connect to a server.
+///
+/// Retries with backoff.
+pub fn connect(
+    host: &str,
+    port: u16,
+) -> Result<Connection, Error>
+where
+    Error: Send,
+{
+    todo!()
+}
+
+pub struct Pool {
+    size: usize,
+}
+
+impl Pool {
+    pub fn get(&self) -> Option<Connection> {
+        None
+    }
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("test.rs"), content);
+        let connect = result.symbols.iter().find(|s| s.name == "connect").unwrap();
+        assert_eq!(
+            connect.signature,
+            "pub fn connect(host: &str, port: u16) -> Result<Connection, Error> where Error: Send"
+        );
+        assert_eq!(connect.docstring.as_deref(), Some("Connect to a server."));
+
+        let pool = result.symbols.iter().find(|s| s.name == "Pool").unwrap();
+        assert_eq!(pool.signature, "pub struct Pool");
+        assert!(pool.children.is_empty(), "depth limit should drop methods");
+    }
 }
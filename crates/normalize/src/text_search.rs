@@ -1,6 +1,6 @@
 //! Fast text search using ripgrep's grep crate.
 
-use crate::filter::Filter;
+use crate::filter::{DirDecision, Filter};
 use crate::output::OutputFormatter;
 use crate::symbols::SymbolParser;
 use grep_matcher::Matcher;
@@ -86,7 +86,16 @@ pub fn grep(
                 Err(_) => return ignore::WalkState::Continue,
             };
 
-            // Skip directories
+            // Skip directories, pruning those the filter rules out entirely
+            if entry.file_type().is_some_and(|t| t.is_dir()) {
+                let rel_dir = entry.path().strip_prefix(root).unwrap_or(entry.path());
+                if let Some(f) = filter
+                    && f.matches_dir(rel_dir) == DirDecision::Skip
+                {
+                    return ignore::WalkState::Skip;
+                }
+                return ignore::WalkState::Continue;
+            }
             if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
                 return ignore::WalkState::Continue;
             }
@@ -75,6 +75,11 @@ pub struct ViewNode {
     /// Grammar name for syntax highlighting (e.g., "rust", "python")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grammar: Option<String>,
+    /// True for symbols that are not publicly visible (private, protected,
+    /// crate-internal). Used to elide them under a token budget.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub private: bool,
 }
 
 /// Type of node in the view tree.
@@ -100,6 +105,7 @@ impl ViewNode {
             docstring: None,
             line_range: None,
             grammar: None,
+            private: false,
         }
     }
 
@@ -1347,6 +1353,7 @@ fn tree_node_to_view_node(
         docstring: None,
         line_range: None,
         grammar: None,
+        private: false,
     }
 }
 
@@ -1396,6 +1403,7 @@ fn skeleton_to_view_node(sym: &Symbol, parent_path: &str, grammar: &str) -> View
         docstring: sym.docstring.clone(),
         line_range: Some((sym.start_line, sym.end_line)),
         grammar: Some(grammar.to_string()),
+        private: false,
     }
 }
 
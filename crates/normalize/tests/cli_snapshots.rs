@@ -164,6 +164,11 @@ fn test_help_structure_files() {
     insta::assert_snapshot!(snapshot_help(&["structure", "files"]));
 }
 
+#[test]
+fn test_help_structure_symbols() {
+    insta::assert_snapshot!(snapshot_help(&["structure", "symbols"]));
+}
+
 #[test]
 fn test_help_structure_packages() {
     insta::assert_snapshot!(snapshot_help(&["structure", "packages"]));
@@ -331,6 +336,41 @@ fn test_help_package_audit() {
     insta::assert_snapshot!(snapshot_help(&["package", "audit"]));
 }
 
+#[test]
+fn test_help_package_graph() {
+    insta::assert_snapshot!(snapshot_help(&["package", "graph"]));
+}
+
+#[test]
+fn test_help_package_diff() {
+    insta::assert_snapshot!(snapshot_help(&["package", "diff"]));
+}
+
+#[test]
+fn test_help_package_security() {
+    insta::assert_snapshot!(snapshot_help(&["package", "security"]));
+}
+
+#[test]
+fn test_help_package_export() {
+    insta::assert_snapshot!(snapshot_help(&["package", "export"]));
+}
+
+#[test]
+fn test_help_package_import() {
+    insta::assert_snapshot!(snapshot_help(&["package", "import"]));
+}
+
+#[test]
+fn test_help_package_licenses() {
+    insta::assert_snapshot!(snapshot_help(&["package", "licenses"]));
+}
+
+#[test]
+fn test_help_package_sbom() {
+    insta::assert_snapshot!(snapshot_help(&["package", "sbom"]));
+}
+
 // tools subcommands
 #[test]
 fn test_help_tools_lint() {
@@ -359,16 +399,31 @@ fn test_help_serve_lsp() {
 }
 
 // generate subcommands
+#[test]
+fn test_help_generate_diff() {
+    insta::assert_snapshot!(snapshot_help(&["generate", "diff"]));
+}
+
 #[test]
 fn test_help_generate_client() {
     insta::assert_snapshot!(snapshot_help(&["generate", "client"]));
 }
 
+#[test]
+fn test_help_generate_server() {
+    insta::assert_snapshot!(snapshot_help(&["generate", "server"]));
+}
+
 #[test]
 fn test_help_generate_types() {
     insta::assert_snapshot!(snapshot_help(&["generate", "types"]));
 }
 
+#[test]
+fn test_help_generate_verify() {
+    insta::assert_snapshot!(snapshot_help(&["generate", "verify"]));
+}
+
 #[test]
 fn test_help_generate_typegen() {
     insta::assert_snapshot!(snapshot_help(&["generate", "typegen"]));
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -10,6 +11,7 @@ fn main() {
         Some("build-grammars") => build_grammars(&args[2..]),
         Some("bump-version") => bump_version(&args[2..]),
         Some("lint-rank-verbs") => lint_rank_verbs(),
+        Some("bench-compare") => bench_compare(&args[2..]),
         Some("help") | None => print_help(),
         Some(cmd) => {
             eprintln!("Unknown command: {cmd}");
@@ -42,9 +44,230 @@ fn print_help() {
     eprintln!("  lint-rank-verbs");
     eprintln!("      Fail if a rank-metric report (its entry `impl RankEntry`) is surfaced by");
     eprintln!("      a main-crate verb other than `rank`/`trend`. Guards metric-command drift.");
+    eprintln!("  bench-compare [--save] [--baseline <file>] [--threshold <pct>]");
+    eprintln!("      Compare the latest `cargo bench` results in target/criterion against a");
+    eprintln!("      JSON baseline of median times; exit 1 if any benchmark regressed.");
+    eprintln!("      --save             Write the latest results as the new baseline instead");
+    eprintln!("      --baseline <file>  Baseline file (default: benches/baseline.json)");
+    eprintln!("      --threshold <pct>  Slowdown that counts as a regression (default: 10)");
     eprintln!("  help             Show this message");
 }
 
+// ─── bench-compare ───────────────────────────────────────────────────────────
+
+/// Save or check a performance baseline from criterion's output.
+///
+/// After `cargo bench`, criterion leaves `<group>/<bench>/new/benchmark.json`
+/// (the benchmark id) and `new/estimates.json` (the statistics) under
+/// `target/criterion`. The baseline is a flat JSON object mapping each
+/// benchmark id to its median time in nanoseconds, so it diffs cleanly when
+/// committed. Benchmarks missing on either side are listed but never fail the
+/// comparison: a skipped bench (e.g. grammar not installed) is not a regression.
+fn bench_compare(args: &[String]) {
+    let root = find_workspace_root();
+    let mut save = false;
+    let mut baseline_path = root.join("benches/baseline.json");
+    let mut threshold = 10.0_f64;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--save" => save = true,
+            "--baseline" => {
+                i += 1;
+                let Some(path) = args.get(i) else {
+                    eprintln!("--baseline requires a file");
+                    std::process::exit(1);
+                };
+                baseline_path = PathBuf::from(path);
+            }
+            "--threshold" => {
+                i += 1;
+                let Some(pct) = args.get(i).and_then(|s| s.parse::<f64>().ok()) else {
+                    eprintln!("--threshold requires a number (percent)");
+                    std::process::exit(1);
+                };
+                threshold = pct;
+            }
+            other => {
+                eprintln!("Unknown argument: {other}");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let criterion_dir = env::var("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| root.join("target"))
+        .join("criterion");
+    let current = collect_criterion_medians(&criterion_dir);
+    if current.is_empty() {
+        eprintln!(
+            "No criterion results under {} — run `cargo bench` first.",
+            criterion_dir.display()
+        );
+        std::process::exit(1);
+    }
+
+    if save {
+        let json = serde_json::to_string_pretty(&current).expect("serialize baseline");
+        if let Err(e) = fs::write(&baseline_path, json + "\n") {
+            eprintln!("Failed to write {}: {e}", baseline_path.display());
+            std::process::exit(1);
+        }
+        println!(
+            "Saved {} benchmarks to {}",
+            current.len(),
+            baseline_path.display()
+        );
+        return;
+    }
+
+    let baseline: BTreeMap<String, f64> = match fs::read_to_string(&baseline_path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("Error parsing {}: {e}", baseline_path.display());
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!(
+                "Error reading {}: {e}\nRun `cargo xtask bench-compare --save` to create it.",
+                baseline_path.display()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let comparison = compare_medians(&baseline, &current, threshold);
+    for row in &comparison.rows {
+        let marker = if row.regressed { "REGRESSED" } else { "" };
+        println!(
+            "{:<60} {:>12} -> {:>12} {:>+8.1}%  {marker}",
+            row.id,
+            format_nanos(row.baseline),
+            format_nanos(row.current),
+            row.change_pct
+        );
+    }
+    for id in &comparison.added {
+        println!("{id:<60} (new, not in baseline)");
+    }
+    for id in &comparison.missing {
+        println!("{id:<60} (in baseline, not run)");
+    }
+
+    let regressions = comparison.rows.iter().filter(|r| r.regressed).count();
+    if regressions > 0 {
+        eprintln!("\n{regressions} benchmark(s) regressed by more than {threshold}%");
+        std::process::exit(1);
+    }
+    println!(
+        "\nNo regressions over {threshold}% ({} compared)",
+        comparison.rows.len()
+    );
+}
+
+/// Map every benchmark under `criterion_dir` to its latest median (ns).
+fn collect_criterion_medians(criterion_dir: &Path) -> BTreeMap<String, f64> {
+    let mut medians = BTreeMap::new();
+    let mut stack = vec![criterion_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().and_then(|n| n.to_str()) == Some("new") {
+                if let Some((id, median)) = read_criterion_result(&path) {
+                    medians.insert(id, median);
+                }
+            } else {
+                stack.push(path);
+            }
+        }
+    }
+    medians
+}
+
+/// Read `(full_id, median ns)` from a criterion `new/` directory.
+fn read_criterion_result(new_dir: &Path) -> Option<(String, f64)> {
+    let benchmark: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(new_dir.join("benchmark.json")).ok()?).ok()?;
+    let estimates: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(new_dir.join("estimates.json")).ok()?).ok()?;
+    let id = benchmark.get("full_id")?.as_str()?.to_string();
+    let median = estimates.pointer("/median/point_estimate")?.as_f64()?;
+    Some((id, median))
+}
+
+struct BenchRow {
+    id: String,
+    baseline: f64,
+    current: f64,
+    change_pct: f64,
+    regressed: bool,
+}
+
+struct BenchComparison {
+    rows: Vec<BenchRow>,
+    added: Vec<String>,
+    missing: Vec<String>,
+}
+
+fn compare_medians(
+    baseline: &BTreeMap<String, f64>,
+    current: &BTreeMap<String, f64>,
+    threshold_pct: f64,
+) -> BenchComparison {
+    let mut rows = Vec::new();
+    let mut added = Vec::new();
+    for (id, &now) in current {
+        let Some(&before) = baseline.get(id) else {
+            added.push(id.clone());
+            continue;
+        };
+        let change_pct = if before > 0.0 {
+            (now - before) / before * 100.0
+        } else {
+            0.0
+        };
+        rows.push(BenchRow {
+            id: id.clone(),
+            baseline: before,
+            current: now,
+            change_pct,
+            regressed: change_pct > threshold_pct,
+        });
+    }
+    let missing = baseline
+        .keys()
+        .filter(|id| !current.contains_key(*id))
+        .cloned()
+        .collect();
+    BenchComparison {
+        rows,
+        added,
+        missing,
+    }
+}
+
+fn format_nanos(ns: f64) -> String {
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{ns:.0} ns")
+    }
+}
+
 // ─── lint-rank-verbs ─────────────────────────────────────────────────────────
 
 /// Ownership lint (inversion plan §metric-core, B11): metric commands are
@@ -90,10 +313,7 @@ fn lint_rank_verbs() {
             }
             for line in content.lines() {
                 if let Some(name) = line.trim().strip_prefix("pub struct ") {
-                    let name = name
-                        .split(|c: char| c == ' ' || c == '{' || c == '(' || c == '<')
-                        .next()
-                        .unwrap_or("");
+                    let name = name.split([' ', '{', '(', '<']).next().unwrap_or("");
                     if name.ends_with("Report") {
                         metric_reports.push(name.to_string());
                     }
@@ -255,12 +475,93 @@ fn build_grammars(args: &[String]) {
         queries_copied += copy_bundled_queries(qdir, &out_dir);
     }
 
+    // Pin every library now in the output directory so GrammarLoader can
+    // detect stale or tampered grammars before loading them.
+    let sources: Vec<(String, String, PathBuf)> = grammars
+        .iter()
+        .map(|(lang, dir)| {
+            let version = dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(&format!("arborium-{lang}-")))
+                .unwrap_or("unknown")
+                .to_string();
+            (lang.clone(), version, dir.join("grammar/src/parser.c"))
+        })
+        .chain(
+            local_grammars
+                .iter()
+                .map(|(lang, dir)| (lang.clone(), "local".to_string(), dir.join("src/parser.c"))),
+        )
+        .collect();
+    match write_grammar_manifest(&out_dir, &sources, lib_extension(target)) {
+        Ok(n) => println!("Pinned {n} grammars in {GRAMMAR_MANIFEST}"),
+        Err(e) => eprintln!("Failed to write {GRAMMAR_MANIFEST}: {e}"),
+    }
+
     println!("\nCompiled {compiled} grammars, skipped {skipped} (already built), {failed} failed");
     if queries_copied > 0 {
         println!("Copied {queries_copied} query files");
     }
 }
 
+/// Manifest of built grammars, read by normalize's `GrammarLoader`.
+const GRAMMAR_MANIFEST: &str = "grammars.toml";
+
+/// Write `grammars.toml` pinning each built library's source version, ABI and
+/// blake3 hash. Later sources (local grammars) override earlier ones, matching
+/// the build order. Returns the number of pinned grammars.
+fn write_grammar_manifest(
+    out_dir: &Path,
+    sources: &[(String, String, PathBuf)],
+    lib_ext: &str,
+) -> Result<usize, String> {
+    let mut pins: BTreeMap<&str, String> = BTreeMap::new();
+    for (lang, version, parser_c) in sources {
+        let lib = out_dir.join(format!("{lang}.{lib_ext}"));
+        let Ok(bytes) = fs::read(&lib) else {
+            continue;
+        };
+        let mut entry = format!("[{}]\nversion = \"{version}\"\n", toml_key(lang));
+        if let Some(abi) = grammar_abi(parser_c) {
+            entry.push_str(&format!("abi = {abi}\n"));
+        }
+        entry.push_str(&format!("blake3 = \"{}\"\n", blake3::hash(&bytes).to_hex()));
+        pins.insert(lang, entry);
+    }
+    let mut out = String::from("# Generated by `cargo xtask build-grammars`; do not edit.\n");
+    for entry in pins.values() {
+        out.push('\n');
+        out.push_str(entry);
+    }
+    fs::write(out_dir.join(GRAMMAR_MANIFEST), out).map_err(|e| e.to_string())?;
+    Ok(pins.len())
+}
+
+/// Quote table names that aren't bare TOML keys (e.g. `c-sharp` is fine,
+/// `foo.bar` is not).
+fn toml_key(name: &str) -> String {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        name.to_string()
+    } else {
+        format!("\"{name}\"")
+    }
+}
+
+/// The `LANGUAGE_VERSION` (tree-sitter ABI) a generated parser.c declares.
+fn grammar_abi(parser_c: &Path) -> Option<u32> {
+    let source = fs::read_to_string(parser_c).ok()?;
+    source.lines().find_map(|line| {
+        line.strip_prefix("#define LANGUAGE_VERSION ")?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
 struct BuildArgs {
     out_dir: PathBuf,
     force: bool,